@@ -5,7 +5,9 @@ use chrono::Utc;
 use lookup::PathPrefix;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
+use tracing::warn;
 use value::Kind;
+use vector_config::configurable_component;
 use vector_core::{
     config::{log_schema, DataType, LogNamespace},
     event::Event,
@@ -16,7 +18,35 @@ use super::Deserializer;
 
 /// Config used to build a `JsonDeserializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct JsonDeserializerConfig;
+pub struct JsonDeserializerConfig {
+    /// The engine used to parse JSON.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub engine: JsonEngine,
+}
+
+/// The engine used to parse JSON.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonEngine {
+    /// Parses JSON with [`serde_json`][serde_json].
+    ///
+    /// [serde_json]: https://docs.rs/serde_json
+    #[default]
+    Serde,
+
+    /// Parses JSON with [`simd-json`][simd_json], which is faster on large volumes of JSON by
+    /// making use of the SIMD instructions of the CPU.
+    ///
+    /// This engine is only available when Vector is built with the `codecs-simd-json` feature.
+    /// Otherwise, the `serde` engine is used instead.
+    ///
+    /// [simd_json]: https://docs.rs/simd-json
+    Simd,
+}
 
 impl JsonDeserializerConfig {
     /// Build the `JsonDeserializer` from this configuration.
@@ -63,13 +93,28 @@ impl JsonDeserializerConfig {
 
 /// Deserializer that builds `Event`s from a byte frame containing JSON.
 #[derive(Debug, Clone, Default)]
-pub struct JsonDeserializer;
+pub struct JsonDeserializer {
+    #[cfg_attr(not(feature = "simd-json"), allow(dead_code))]
+    engine: JsonEngine,
+}
 
 impl JsonDeserializer {
     /// Creates a new `JsonDeserializer`.
     pub fn new() -> Self {
         Default::default()
     }
+
+    fn parse_json(&self, bytes: &[u8]) -> Result<serde_json::Value, String> {
+        #[cfg(feature = "simd-json")]
+        if self.engine == JsonEngine::Simd {
+            // `simd-json` parses in place, so it needs a mutable copy of the frame.
+            let mut bytes = bytes.to_vec();
+            return simd_json::serde::from_slice(&mut bytes)
+                .map_err(|error| format!("Error parsing JSON: {:?}", error));
+        }
+
+        serde_json::from_slice(bytes).map_err(|error| format!("Error parsing JSON: {:?}", error))
+    }
 }
 
 impl Deserializer for JsonDeserializer {
@@ -84,8 +129,7 @@ impl Deserializer for JsonDeserializer {
             return Ok(smallvec![]);
         }
 
-        let json: serde_json::Value = serde_json::from_slice(&bytes)
-            .map_err(|error| format!("Error parsing JSON: {:?}", error))?;
+        let json = self.parse_json(&bytes)?;
 
         // If the root is an Array, split it into multiple events
         let mut events = match json {
@@ -119,8 +163,16 @@ impl Deserializer for JsonDeserializer {
 }
 
 impl From<&JsonDeserializerConfig> for JsonDeserializer {
-    fn from(_: &JsonDeserializerConfig) -> Self {
-        Self
+    fn from(config: &JsonDeserializerConfig) -> Self {
+        let engine = if config.engine == JsonEngine::Simd && cfg!(not(feature = "simd-json")) {
+            warn!(
+                message = "The `simd` JSON engine isn't available in this build, falling back to the `serde` engine."
+            );
+            JsonEngine::Serde
+        } else {
+            config.engine
+        };
+        Self { engine }
     }
 }
 
@@ -194,6 +246,39 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "simd-json")]
+    #[test]
+    fn deserialize_json_simd_engine() {
+        let input = Bytes::from(r#"[{ "foo": 123 }, { "bar": [true, null, "baz"] }]"#);
+        let deserializer = JsonDeserializerConfig {
+            engine: JsonEngine::Simd,
+        }
+        .build();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["foo"], 123.into());
+        assert_eq!(
+            events[1].as_log()["bar"],
+            vec![value::Value::from(true), value::Value::Null, "baz".into()].into()
+        );
+    }
+
+    #[cfg(not(feature = "simd-json"))]
+    #[test]
+    fn simd_engine_falls_back_to_serde() {
+        let config = JsonDeserializerConfig {
+            engine: JsonEngine::Simd,
+        };
+        let deserializer = JsonDeserializer::from(&config);
+        assert_eq!(deserializer.engine, JsonEngine::Serde);
+
+        let events = deserializer
+            .parse(Bytes::from(r#"{ "foo": 123 }"#), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log()["foo"], 123.into());
+    }
+
     #[test]
     fn deserialize_skip_empty() {
         let input = Bytes::from("");
@@ -1,6 +1,7 @@
 use std::{
     collections::BTreeSet,
-    fs, io,
+    fs,
+    io::{self, Write},
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
@@ -40,6 +41,22 @@ struct Checkpoint {
     modified: DateTime<Utc>,
 }
 
+/// A store of checkpoints outliving the host Vector runs on.
+///
+/// Checkpoints are always persisted to the data directory, and are also persisted to the store
+/// when there is one. When no checkpoints are found in the data directory, such as when Vector
+/// runs on an ephemeral host, they are loaded from the store instead, so that reading resumes
+/// where the previous instance of Vector left off.
+///
+/// Both methods are called from blocking threads.
+pub trait CheckpointStore: Send + Sync {
+    /// Loads the checkpoints last stored, if any.
+    fn load(&self) -> io::Result<Option<Vec<u8>>>;
+
+    /// Stores the checkpoints, replacing those previously stored.
+    fn store(&self, checkpoints: &[u8]) -> io::Result<()>;
+}
+
 pub struct Checkpointer {
     directory: PathBuf,
     tmp_file_path: PathBuf,
@@ -47,6 +64,7 @@ pub struct Checkpointer {
     glob_string: String,
     checkpoints: Arc<CheckpointsView>,
     last: Mutex<Option<State>>,
+    store: Option<Box<dyn CheckpointStore>>,
 }
 
 /// A thread-safe handle for reading and writing checkpoints in-memory across
@@ -212,9 +230,16 @@ impl Checkpointer {
             stable_file_path,
             checkpoints: Arc::new(CheckpointsView::default()),
             last: Mutex::new(None),
+            store: None,
         }
     }
 
+    /// Persists the checkpoints to the given store, in addition to the data directory.
+    pub fn with_store(mut self, store: Box<dyn CheckpointStore>) -> Checkpointer {
+        self.store = Some(store);
+        self
+    }
+
     pub fn view(&self) -> Arc<CheckpointsView> {
         Arc::clone(&self.checkpoints)
     }
@@ -312,9 +337,10 @@ impl Checkpointer {
             // disk. If vector dies anywhere during this section, the existing
             // stable file will still be in its current valid state and we'll be
             // able to recover.
-            let mut f = io::BufWriter::new(fs::File::create(&self.tmp_file_path)?);
-            serde_json::to_writer(&mut f, &current)?;
-            f.into_inner()?.sync_all()?;
+            let data = serde_json::to_vec(&current)?;
+            let mut f = fs::File::create(&self.tmp_file_path)?;
+            f.write_all(&data)?;
+            f.sync_all()?;
 
             // Once the temp file is fully flushed, rename the tmp file to replace
             // the previous stable file. This is an atomic operation on POSIX
@@ -323,6 +349,10 @@ impl Checkpointer {
             // one full valid file to recover from.
             fs::rename(&self.tmp_file_path, &self.stable_file_path)?;
 
+            if let Some(store) = &self.store {
+                store.store(&data)?;
+            }
+
             *last = Some(current);
         }
 
@@ -387,6 +417,29 @@ impl Checkpointer {
             }
         }
 
+        // Without checkpoints in the data directory, such as on a fresh host,
+        // the checkpoints of the previous instance can still be in the store.
+        if let Some(store) = &self.store {
+            let state = store.load().and_then(|data| {
+                data.map(|data| {
+                    serde_json::from_slice(&data)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                })
+                .transpose()
+            });
+            match state {
+                Ok(Some(state)) => {
+                    info!(message = "Loaded checkpoint data from checkpoint store.");
+                    self.checkpoints.set_state(state, ignore_before);
+                    return;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    warn!(message = "Unable to load checkpoint data from checkpoint store.", %error);
+                }
+            }
+        }
+
         // If we haven't returned yet, go ahead and look for the legacy files
         // and try to read them.
         info!("Attempting to read legacy checkpoint files.");
@@ -426,16 +479,35 @@ impl Checkpointer {
 
 #[cfg(test)]
 mod test {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
     use chrono::{Duration, Utc};
     use similar_asserts::assert_eq;
     use tempfile::tempdir;
 
     use super::{
         super::{FingerprintStrategy, Fingerprinter},
-        Checkpoint, Checkpointer, FileFingerprint, FilePosition, CHECKPOINT_FILE_NAME,
-        TMP_FILE_NAME,
+        Checkpoint, CheckpointStore, Checkpointer, FileFingerprint, FilePosition,
+        CHECKPOINT_FILE_NAME, TMP_FILE_NAME,
     };
 
+    #[derive(Clone, Default)]
+    struct MemoryStore(Arc<Mutex<Option<Vec<u8>>>>);
+
+    impl CheckpointStore for MemoryStore {
+        fn load(&self) -> io::Result<Option<Vec<u8>>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn store(&self, checkpoints: &[u8]) -> io::Result<()> {
+            *self.0.lock().unwrap() = Some(checkpoints.to_vec());
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_checkpointer_basics() {
         let fingerprints = vec![
@@ -533,6 +605,26 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checkpointer_restart_from_store() {
+        let fingerprint = FileFingerprint::DevInode(1, 2);
+        let position: FilePosition = 1234;
+        let store = MemoryStore::default();
+        {
+            let data_dir = tempdir().unwrap();
+            let mut chkptr = Checkpointer::new(data_dir.path()).with_store(Box::new(store.clone()));
+            chkptr.update_checkpoint(fingerprint, position);
+            chkptr.write_checkpoints().unwrap();
+        }
+        // A new data directory, as on a fresh host.
+        {
+            let data_dir = tempdir().unwrap();
+            let mut chkptr = Checkpointer::new(data_dir.path()).with_store(Box::new(store));
+            chkptr.read_checkpoints(None);
+            assert_eq!(chkptr.get_checkpoint(fingerprint), Some(position));
+        }
+    }
+
     #[test]
     fn test_checkpointer_fingerprint_upgrades_unknown() {
         let log_dir = tempdir().unwrap();
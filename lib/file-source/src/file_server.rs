@@ -22,7 +22,7 @@ use crate::{
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
     paths_provider::PathsProvider,
-    FileSourceInternalEvents, ReadFrom,
+    FileSourceInternalEvents, ReadBackend, ReadFrom,
 };
 
 /// `FileServer` is a Source which cooperatively schedules reads over files,
@@ -45,6 +45,7 @@ where
     pub ignore_before: Option<DateTime<Utc>>,
     pub max_line_bytes: usize,
     pub line_delimiter: Bytes,
+    pub read_backend: ReadBackend,
     pub data_dir: PathBuf,
     pub glob_minimum_cooldown: Duration,
     pub fingerprinter: Fingerprinter,
@@ -230,6 +231,7 @@ where
             }
 
             // Collect lines by polling files.
+            FileWatcher::read_ahead(fp_map.values().filter(|watcher| watcher.should_read()));
             let mut global_bytes_read: usize = 0;
             let mut maxed_out_reading_single_file = false;
             for (&file_id, watcher) in &mut fp_map {
@@ -400,6 +402,7 @@ where
             self.ignore_before,
             self.max_line_bytes,
             self.line_delimiter.clone(),
+            self.read_backend,
         ) {
             Ok(mut watcher) => {
                 if let ReadFrom::Checkpoint(file_position) = read_from {
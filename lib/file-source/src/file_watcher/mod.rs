@@ -10,11 +10,17 @@ use chrono::{DateTime, Utc};
 use flate2::bufread::MultiGzDecoder;
 use tracing::debug;
 
+use self::reader::{FileReader, ReadAhead};
 use crate::{
-    buffer::read_until_with_max_size, metadata_ext::PortableFileExt, FilePosition, ReadFrom,
+    buffer::read_until_with_max_size, metadata_ext::PortableFileExt, FilePosition, ReadBackend,
+    ReadFrom,
 };
+
+mod reader;
 #[cfg(test)]
 mod tests;
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+mod uring;
 
 /// The `RawLine` struct is a thin wrapper around the bytes that have been read
 /// in order to retain the context of where in the file they have been read from.
@@ -38,6 +44,7 @@ pub struct FileWatcher {
     pub path: PathBuf,
     findable: bool,
     reader: Box<dyn BufRead>,
+    read_ahead: Option<ReadAhead>,
     file_position: FilePosition,
     devno: u64,
     inode: u64,
@@ -46,6 +53,7 @@ pub struct FileWatcher {
     last_read_success: Instant,
     max_line_bytes: usize,
     line_delimiter: Bytes,
+    read_backend: ReadBackend,
     buf: BytesMut,
 }
 
@@ -61,11 +69,13 @@ impl FileWatcher {
         ignore_before: Option<DateTime<Utc>>,
         max_line_bytes: usize,
         line_delimiter: Bytes,
+        read_backend: ReadBackend,
     ) -> Result<FileWatcher, io::Error> {
         let f = fs::File::open(&path)?;
         let (devno, ino) = (f.portable_dev()?, f.portable_ino()?);
         let metadata = f.metadata()?;
-        let mut reader = io::BufReader::new(f);
+        let mut reader = io::BufReader::new(FileReader::new(f, read_backend));
+        let read_ahead = reader.get_ref().read_ahead();
 
         let too_old = if let (Some(ignore_before), Ok(modified_time)) = (
             ignore_before,
@@ -139,6 +149,7 @@ impl FileWatcher {
             path,
             findable: true,
             reader,
+            read_ahead,
             file_position,
             devno,
             inode: ino,
@@ -147,6 +158,7 @@ impl FileWatcher {
             last_read_success: ts,
             max_line_bytes,
             line_delimiter,
+            read_backend,
             buf: BytesMut::new(),
         })
     }
@@ -154,7 +166,9 @@ impl FileWatcher {
     pub fn update_path(&mut self, path: PathBuf) -> io::Result<()> {
         let file_handle = File::open(&path)?;
         if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
-            let mut reader = io::BufReader::new(fs::File::open(&path)?);
+            let mut reader =
+                io::BufReader::new(FileReader::new(fs::File::open(&path)?, self.read_backend));
+            let read_ahead = reader.get_ref().read_ahead();
             let gzipped = is_gzipped(&mut reader)?;
             let new_reader: Box<dyn BufRead> = if gzipped {
                 if self.file_position != 0 {
@@ -167,6 +181,7 @@ impl FileWatcher {
                 Box::new(reader)
             };
             self.reader = new_reader;
+            self.read_ahead = read_ahead;
             self.devno = file_handle.portable_dev()?;
             self.inode = file_handle.portable_ino()?;
         }
@@ -263,6 +278,16 @@ impl FileWatcher {
         self.last_read_success
     }
 
+    /// Reads ahead the files of the watchers, in a single batch when they are read through
+    /// `io_uring`.
+    pub(super) fn read_ahead<'a>(watchers: impl IntoIterator<Item = &'a FileWatcher>) {
+        reader::read_ahead(
+            watchers
+                .into_iter()
+                .filter_map(|watcher| watcher.read_ahead.as_ref()),
+        );
+    }
+
     #[inline]
     pub fn should_read(&self) -> bool {
         self.last_read_success.elapsed() < Duration::from_secs(10)
@@ -270,7 +295,7 @@ impl FileWatcher {
     }
 }
 
-fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
+fn is_gzipped(r: &mut io::BufReader<FileReader>) -> io::Result<bool> {
     let header_bytes = r.fill_buf()?;
     // WARN: The paired `BufReader::consume` is not called intentionally. If we
     // do we'll chop a decent part of the potential gzip stream off.
@@ -0,0 +1,151 @@
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+use super::uring::{self, UringFile};
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+pub(super) use super::uring::{read_ahead, ReadAhead};
+use crate::ReadBackend;
+
+/// The handle through which a file is read ahead, which only files read through `io_uring` have.
+#[cfg(not(all(target_os = "linux", feature = "io-uring")))]
+#[derive(Clone)]
+pub(super) enum ReadAhead {}
+
+/// Reads ahead the files, in a single batch.
+#[cfg(not(all(target_os = "linux", feature = "io-uring")))]
+pub(super) fn read_ahead<'a>(_files: impl IntoIterator<Item = &'a ReadAhead>) {}
+
+/// A file opened for reading, through the backend it was opened with.
+pub(super) enum FileReader {
+    Std(File),
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    Uring(UringFile),
+}
+
+impl FileReader {
+    /// Opens a reader of the file with the given backend, falling back to the standard system
+    /// calls if the backend isn't available.
+    #[cfg_attr(
+        not(all(target_os = "linux", feature = "io-uring")),
+        allow(unused_variables)
+    )]
+    pub(super) fn new(file: File, backend: ReadBackend) -> Self {
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        if backend == ReadBackend::IoUring && uring::available() {
+            return Self::Uring(UringFile::new(file));
+        }
+        Self::Std(file)
+    }
+
+    /// Returns the handle through which the file is read ahead, if it is.
+    pub(super) fn read_ahead(&self) -> Option<ReadAhead> {
+        match self {
+            Self::Std(_) => None,
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Self::Uring(file) => Some(file.read_ahead()),
+        }
+    }
+}
+
+impl Read for FileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Std(file) => file.read(buf),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Self::Uring(file) => file.read(buf),
+        }
+    }
+}
+
+impl Seek for FileReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::Std(file) => file.seek(pos),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Self::Uring(file) => file.seek(pos),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+
+    use super::*;
+
+    fn read_lines(backend: ReadBackend) {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for i in 0..1000 {
+            writeln!(file, "line {}", i).unwrap();
+        }
+
+        let mut reader = BufReader::new(FileReader::new(File::open(file.path()).unwrap(), backend));
+        let lines = (&mut reader)
+            .lines()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(lines.len(), 1000);
+        assert_eq!(lines[999], "line 999");
+
+        // Lines written after reaching the end of the file are read.
+        writeln!(file, "line 1000").unwrap();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "line 1000\n");
+
+        let position = reader.seek(SeekFrom::Current(-5)).unwrap();
+        assert_eq!(position, file.as_file().metadata().unwrap().len() - 5);
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "1000\n");
+    }
+
+    #[test]
+    fn reads_lines_with_std() {
+        read_lines(ReadBackend::Std);
+    }
+
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    #[test]
+    fn reads_lines_with_io_uring() {
+        read_lines(ReadBackend::IoUring);
+    }
+
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    #[test]
+    fn reads_ahead_with_io_uring() {
+        if !uring::available() {
+            return;
+        }
+
+        let mut files = Vec::new();
+        let mut readers = Vec::new();
+        for i in 0..100 {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            writeln!(file, "file {}", i).unwrap();
+            readers.push(FileReader::new(
+                File::open(file.path()).unwrap(),
+                ReadBackend::IoUring,
+            ));
+            files.push(file);
+        }
+        let handles = readers
+            .iter()
+            .map(|reader| reader.read_ahead().unwrap())
+            .collect::<Vec<_>>();
+        read_ahead(&handles);
+
+        // The files are read from the data read ahead, so emptying them doesn't change what is read.
+        for file in &files {
+            file.as_file().set_len(0).unwrap();
+        }
+        for (i, reader) in readers.into_iter().enumerate() {
+            let mut data = String::new();
+            BufReader::new(reader).read_to_string(&mut data).unwrap();
+            assert_eq!(data, format!("file {}\n", i));
+        }
+    }
+}
@@ -9,7 +9,7 @@ use quickcheck::{QuickCheck, TestResult};
 
 use crate::{
     file_watcher::{tests::*, FileWatcher},
-    ReadFrom,
+    ReadBackend, ReadFrom,
 };
 
 // Interpret all FWActions, including truncation
@@ -36,6 +36,7 @@ fn experiment(actions: Vec<FileWatcherAction>) {
         None,
         100_000,
         Bytes::from("\n"),
+        ReadBackend::Std,
     )
     .expect("must be able to create");
 
@@ -5,7 +5,7 @@ use quickcheck::{QuickCheck, TestResult};
 
 use crate::{
     file_watcher::{tests::*, FileWatcher},
-    ReadFrom,
+    ReadBackend, ReadFrom,
 };
 
 // Interpret all FWActions, excluding truncation
@@ -24,6 +24,7 @@ fn experiment_no_truncations(actions: Vec<FileWatcherAction>) {
         None,
         100_000,
         Bytes::from("\n"),
+        ReadBackend::Std,
     )
     .expect("must be able to create");
 
@@ -0,0 +1,249 @@
+//! Reads of files through `io_uring`.
+//!
+//! The file server reads ahead all the files it is about to read in a poll cycle at once: their
+//! reads are submitted together to the ring of the thread reading them, and waited for with a
+//! single system call. Each file is then read from the data read ahead into its buffer, and once
+//! that is exhausted, through the ring one read at a time.
+//!
+//! The ring is set up without a kernel thread polling its submission queue. Every file source would
+//! start one of its own, spinning while the ring is idle, whereas batching the reads already brings
+//! them down to one system call per poll cycle.
+use std::{
+    cell::{RefCell, RefMut},
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    os::unix::{fs::FileExt, io::AsRawFd},
+    rc::Rc,
+};
+
+use io_uring::{opcode, squeue, types, IoUring};
+use tracing::warn;
+
+const RING_ENTRIES: u32 = 64;
+const BUFFER_SIZE: usize = 16 * 1024;
+
+thread_local! {
+    static RING: RefCell<Option<IoUring>> = RefCell::new(new_ring());
+}
+
+/// Returns whether files can be read through `io_uring` from the current thread.
+pub(super) fn available() -> bool {
+    RING.with(|ring| ring.borrow().is_some())
+}
+
+fn new_ring() -> Option<IoUring> {
+    match IoUring::new(RING_ENTRIES) {
+        Ok(ring) => Some(ring),
+        Err(error) => {
+            warn!(
+                message = "io_uring is not available, reading files with standard system calls.",
+                %error,
+            );
+            None
+        }
+    }
+}
+
+/// The data read from a file, and the position of the reads of the file.
+struct Buffer {
+    file: File,
+    position: u64,
+    // The reads are made into a buffer owned by the file, rather than into the buffers of the
+    // callers, so that they can be made ahead of them.
+    data: Box<[u8]>,
+    /// The offset in the file of the data.
+    offset: u64,
+    len: usize,
+    /// Whether the data reached the end of the file when it was read. A read past it then returns
+    /// no bytes, rather than trying the file again.
+    eof: bool,
+    /// The error of the last read ahead, returned by the next read of the file.
+    error: Option<io::Error>,
+}
+
+impl Buffer {
+    /// The data at the position of the reads.
+    fn buffered(&self) -> &[u8] {
+        match self.position.checked_sub(self.offset) {
+            Some(skip) if skip < self.len as u64 => &self.data[skip as usize..self.len],
+            _ => &[],
+        }
+    }
+
+    /// Returns the entry reading the data at the position of the reads.
+    fn entry(&mut self, user_data: u64) -> squeue::Entry {
+        self.offset = self.position;
+        self.len = 0;
+        self.eof = false;
+        opcode::Read::new(
+            types::Fd(self.file.as_raw_fd()),
+            self.data.as_mut_ptr(),
+            self.data.len() as u32,
+        )
+        .offset(self.position as _)
+        .build()
+        .user_data(user_data)
+    }
+
+    fn complete(&mut self, result: i32) {
+        if result < 0 {
+            self.error = Some(io::Error::from_raw_os_error(-result));
+        } else {
+            self.len = result as usize;
+            self.eof = self.len < self.data.len();
+        }
+    }
+}
+
+/// Submits the reads of the buffers, as many at a time as the ring has entries, and waits for all
+/// of them to complete.
+///
+/// If this fails, reads may still be in flight into the buffers.
+fn submit(ring: &mut IoUring, buffers: &mut [RefMut<'_, Buffer>]) -> io::Result<()> {
+    for batch in buffers.chunks_mut(RING_ENTRIES as usize) {
+        for (index, buffer) in batch.iter_mut().enumerate() {
+            let entry = buffer.entry(index as u64);
+            // SAFETY: the buffers are borrowed until all the reads into them completed.
+            unsafe { ring.submission().push(&entry) }
+                .map_err(|_| io::Error::new(io::ErrorKind::Other, "submission queue is full"))?;
+        }
+
+        let mut pending = batch.len();
+        while pending > 0 {
+            match ring.submit_and_wait(pending) {
+                Ok(_) => {}
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+            for completion in ring.completion() {
+                batch[completion.user_data() as usize].complete(completion.result());
+                pending -= 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads into the buffers through the ring of the current thread. Returns false if it has none.
+fn read_buffers(buffers: &mut [RefMut<'_, Buffer>]) -> bool {
+    RING.with(|cell| {
+        let mut state = cell.borrow_mut();
+        let ring = match state.as_mut() {
+            Some(ring) => ring,
+            None => return false,
+        };
+        match submit(ring, buffers) {
+            Ok(()) => true,
+            Err(error) => {
+                warn!(
+                    message = "io_uring failed, reading files with standard system calls.",
+                    %error,
+                );
+                // The reads may still be in flight, so the ring and the buffers they read into are
+                // leaked rather than freed.
+                std::mem::forget(state.take());
+                for buffer in buffers.iter_mut() {
+                    let data = vec![0; BUFFER_SIZE].into_boxed_slice();
+                    std::mem::forget(std::mem::replace(&mut buffer.data, data));
+                    buffer.len = 0;
+                    buffer.eof = false;
+                }
+                false
+            }
+        }
+    })
+}
+
+/// The handle through which a file read through `io_uring` is read ahead.
+#[derive(Clone)]
+pub(super) struct ReadAhead(Rc<RefCell<Buffer>>);
+
+/// Reads ahead the files whose data read ahead was all read, in a single batch.
+pub(super) fn read_ahead<'a>(files: impl IntoIterator<Item = &'a ReadAhead>) {
+    let mut buffers = files
+        .into_iter()
+        .map(|file| file.0.borrow_mut())
+        .filter(|buffer| buffer.buffered().is_empty() && buffer.error.is_none())
+        .collect::<Vec<_>>();
+    if !buffers.is_empty() {
+        read_buffers(&mut buffers);
+    }
+}
+
+/// A file read through `io_uring`.
+///
+/// Reads are positioned, so the file keeps track of its own position.
+pub(super) struct UringFile(Rc<RefCell<Buffer>>);
+
+impl UringFile {
+    pub(super) fn new(file: File) -> Self {
+        Self(Rc::new(RefCell::new(Buffer {
+            file,
+            position: 0,
+            data: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            offset: 0,
+            len: 0,
+            eof: false,
+            error: None,
+        })))
+    }
+
+    pub(super) fn read_ahead(&self) -> ReadAhead {
+        ReadAhead(Rc::clone(&self.0))
+    }
+}
+
+impl Read for UringFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut buffer = self.0.borrow_mut();
+        if let Some(error) = buffer.error.take() {
+            return Err(error);
+        }
+        if buffer.buffered().is_empty() && !buffer.eof {
+            if !read_buffers(std::slice::from_mut(&mut buffer)) {
+                let position = buffer.position;
+                let read = buffer.file.read_at(buf, position)?;
+                buffer.position += read as u64;
+                return Ok(read);
+            }
+            if let Some(error) = buffer.error.take() {
+                return Err(error);
+            }
+        }
+
+        let data = buffer.buffered();
+        let read = data.len().min(buf.len());
+        buf[..read].copy_from_slice(&data[..read]);
+        buffer.position += read as u64;
+        if read == 0 {
+            // The end of the file was returned, so the next read tries the file again.
+            buffer.eof = false;
+        }
+        Ok(read)
+    }
+}
+
+impl Seek for UringFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let mut buffer = self.0.borrow_mut();
+        let (base, offset) = match pos {
+            SeekFrom::Start(position) => (position, 0),
+            SeekFrom::End(offset) => (buffer.file.metadata()?.len(), offset),
+            SeekFrom::Current(offset) => (buffer.position, offset),
+        };
+        let position = if offset < 0 {
+            base.checked_sub(offset.unsigned_abs())
+        } else {
+            base.checked_add(offset as u64)
+        }
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        buffer.position = position;
+        buffer.eof = false;
+        Ok(position)
+    }
+}
@@ -14,7 +14,7 @@ mod metadata_ext;
 pub mod paths_provider;
 
 pub use self::{
-    checkpointer::{Checkpointer, CheckpointsView, CHECKPOINT_FILE_NAME},
+    checkpointer::{CheckpointStore, Checkpointer, CheckpointsView, CHECKPOINT_FILE_NAME},
     file_server::{calculate_ignore_before, FileServer, Line, Shutdown as FileServerShutdown},
     fingerprinter::{FileFingerprint, FingerprintStrategy, Fingerprinter},
     internal_events::FileSourceInternalEvents,
@@ -51,3 +51,21 @@ impl From<ReadFromConfig> for ReadFrom {
         }
     }
 }
+
+/// Interface used to read the files.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadBackend {
+    /// Read the files with the standard system calls.
+    #[default]
+    Std,
+
+    /// Read the files through `io_uring`.
+    ///
+    /// The files are read ahead in a single batch every time they are polled, which reduces the
+    /// overhead of the system calls when tailing a large number of files. Only available on Linux,
+    /// when Vector is built with the `sources-file-io-uring` feature. Otherwise, or when the kernel
+    /// doesn't support `io_uring`, the files are read with the standard system calls.
+    IoUring,
+}
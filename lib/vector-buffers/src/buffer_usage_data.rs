@@ -11,8 +11,11 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
-    spawn_named,
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent,
+        BufferPriorityEventsDropped,
+    },
+    spawn_named, EventPriority,
 };
 
 /// Snapshot of category metrics.
@@ -140,6 +143,19 @@ impl BufferUsageHandle {
             self.state.dropped.increment(count, byte_size);
         }
     }
+
+    /// Increment the number of events (and their total size) of the given priority which were
+    /// intentionally dropped by this buffer component.
+    ///
+    /// The events must also be counted with `increment_dropped_event_count_and_byte_size`.
+    pub fn increment_dropped_by_priority_event_count_and_byte_size(
+        &self,
+        priority: EventPriority,
+        count: u64,
+        byte_size: u64,
+    ) {
+        self.state.dropped_by_priority[priority.index()].increment(count, byte_size);
+    }
 }
 
 #[derive(Debug, Default)]
@@ -149,6 +165,7 @@ struct BufferUsageData {
     sent: CategoryMetrics,
     dropped: CategoryMetrics,
     dropped_intentional: CategoryMetrics,
+    dropped_by_priority: [CategoryMetrics; EventPriority::ALL.len()],
     max_size: CategoryMetrics,
 }
 
@@ -300,6 +317,17 @@ impl BufferUsage {
                             byte_size: dropped_intentional.event_byte_size,
                         });
                     }
+
+                    for priority in EventPriority::ALL {
+                        let dropped = stage.dropped_by_priority[priority.index()].consume();
+                        if dropped.has_updates() {
+                            emit(BufferPriorityEventsDropped {
+                                idx: stage.idx,
+                                priority: priority.as_str(),
+                                count: dropped.event_count,
+                            });
+                        }
+                    }
                 }
             }
         };
@@ -38,7 +38,7 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 5] = ["type", "max_events", "max_size", "when_full", "flush_mode"];
 
 struct BufferTypeVisitor;
 
@@ -51,6 +51,7 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut flush_mode: Option<FlushMode> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,6 +78,12 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "flush_mode" => {
+                    if flush_mode.is_some() {
+                        return Err(de::Error::duplicate_field("flush_mode"));
+                    }
+                    flush_mode = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -92,6 +99,12 @@ impl BufferTypeVisitor {
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if flush_mode.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "flush_mode",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
@@ -101,12 +114,13 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "flush_mode"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    flush_mode: flush_mode.unwrap_or_default(),
                 })
             }
         }
@@ -175,6 +189,31 @@ impl DiskUsage {
     }
 }
 
+/// Synchronization of the data written to a disk buffer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlushMode {
+    /// Synchronize the data to disk every time it is written.
+    ///
+    /// This is the most durable mode, as data can't be lost once it has been written to the buffer,
+    /// but also the slowest, as every write waits for the disk.
+    EveryWrite,
+
+    /// Synchronize the data to disk every 500ms.
+    ///
+    /// Up to 500ms worth of data can be lost if the host crashes.
+    #[default]
+    Interval,
+
+    /// Synchronize the data to disk once 8MB of it has been written, so that a single
+    /// synchronization commits a whole batch of writes, or every 500ms if less is written.
+    ///
+    /// This is the fastest mode on slow disks, but up to 8MB, or 500ms worth, of data can be lost if
+    /// the host crashes.
+    Batch,
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -202,7 +241,7 @@ pub enum BufferType {
     /// This is less performant, but more durable. Data that has been synchronized to disk will not
     /// be lost if Vector is restarted forcefully or crashes.
     ///
-    /// Data is synchronized to disk every 500ms.
+    /// Data is synchronized to disk according to `flush_mode`, every 500ms by default.
     #[configurable(title = "Events are buffered on disk.")]
     #[serde(rename = "disk")]
     DiskV2 {
@@ -218,6 +257,10 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        flush_mode: FlushMode,
     },
 }
 
@@ -286,9 +329,13 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                flush_mode,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, max_size, flush_mode),
+                    when_full,
+                );
             }
         };
 
@@ -389,7 +436,7 @@ impl BufferConfig {
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
-    use crate::{BufferConfig, BufferType, WhenFull};
+    use crate::{BufferConfig, BufferType, FlushMode, WhenFull};
 
     fn check_single_stage(source: &str, expected: BufferType) {
         let config: BufferConfig = serde_yaml::from_str(source).unwrap();
@@ -519,6 +566,20 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                flush_mode: FlushMode::Interval,
+            },
+        );
+
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          flush_mode: batch
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                flush_mode: FlushMode::Batch,
             },
         );
     }
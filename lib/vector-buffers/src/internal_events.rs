@@ -89,6 +89,27 @@ impl InternalEvent for BufferEventsDropped {
     }
 }
 
+pub struct BufferPriorityEventsDropped {
+    pub idx: usize,
+    pub priority: &'static str,
+    pub count: u64,
+}
+
+impl InternalEvent for BufferPriorityEventsDropped {
+    fn emit(self) {
+        debug!(
+            message = "Events dropped by priority.",
+            count = %self.count,
+            priority = %self.priority,
+            stage = %self.idx,
+        );
+        counter!(
+            "buffer_discarded_events_by_priority_total", self.count,
+            "priority" => self.priority,
+        );
+    }
+}
+
 pub struct BufferReadError {
     pub error_code: &'static str,
     pub error: String,
@@ -16,7 +16,7 @@ extern crate tracing;
 mod buffer_usage_data;
 
 pub mod config;
-pub use config::{BufferConfig, BufferType};
+pub use config::{BufferConfig, BufferType, FlushMode};
 use encoding::Encodable;
 use vector_config::configurable_component;
 
@@ -84,6 +84,68 @@ impl Arbitrary for WhenFull {
     }
 }
 
+/// The priority of events, which decides which events are shed first under overload.
+///
+/// Buffers which drop events when full start shedding the events of lower priorities before they
+/// are full, to keep room for the events of higher priorities.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum EventPriority {
+    /// Shed once the buffer is half full.
+    Background,
+
+    /// Shed once the buffer is three quarters full.
+    Low,
+
+    /// Shed only once the buffer is full.
+    #[default]
+    Normal,
+}
+
+impl EventPriority {
+    /// All the priorities, from the lowest to the highest.
+    pub const ALL: [Self; 3] = [Self::Background, Self::Low, Self::Normal];
+
+    /// Parses the name of a priority, as set in the metadata of events.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|priority| priority.as_str() == name)
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Background => "background",
+            Self::Low => "low",
+            Self::Normal => "normal",
+        }
+    }
+
+    /// The percentage of the capacity of a buffer in use from which events are shed.
+    pub const fn shed_percent(self) -> usize {
+        match self {
+            Self::Background => 50,
+            Self::Low => 75,
+            Self::Normal => 100,
+        }
+    }
+
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// An item whose events have a priority.
+pub trait Prioritized {
+    /// Gets the priority of the item, which is shed as a whole.
+    ///
+    /// Items are of the normal priority unless stated otherwise.
+    fn priority(&self) -> EventPriority {
+        EventPriority::Normal
+    }
+}
+
 /// An item that can be buffered.
 ///
 /// This supertrait serves as the base trait for any item that can be pushed into a buffer.
@@ -92,6 +154,7 @@ pub trait Bufferable:
     + ByteSizeOf
     + Encodable
     + EventCount
+    + Prioritized
     + Debug
     + Send
     + Sync
@@ -107,6 +170,7 @@ impl<T> Bufferable for T where
         + ByteSizeOf
         + Encodable
         + EventCount
+        + Prioritized
         + Debug
         + Send
         + Sync
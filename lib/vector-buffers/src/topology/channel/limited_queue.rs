@@ -93,6 +93,11 @@ impl<T: Bufferable> LimitedSender<T> {
         self.inner.limiter.available_permits()
     }
 
+    /// Gets the number of items that this channel can hold.
+    pub fn limit(&self) -> usize {
+        self.inner.limit
+    }
+
     /// Sends an item into the channel.
     ///
     /// # Errors
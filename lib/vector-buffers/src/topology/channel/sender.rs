@@ -7,7 +7,7 @@ use super::limited_queue::LimitedSender;
 use crate::{
     buffer_usage_data::BufferUsageHandle,
     variants::disk_v2::{self, ProductionFilesystem},
-    Bufferable, WhenFull,
+    Bufferable, EventPriority, WhenFull,
 };
 
 /// Adapter for papering over various sender backends.
@@ -94,6 +94,25 @@ where
         }
     }
 
+    /// Gets the priority of the item if it must be shed to keep room for higher priorities.
+    ///
+    /// Only in-memory buffers, whose usage is known, shed items before they are full.
+    pub(crate) fn shed_priority(&self, item: &T) -> Option<EventPriority> {
+        let Self::InMemory(tx) = self else {
+            return None;
+        };
+        let limit = tx.limit();
+        let used = limit.saturating_sub(tx.available_capacity());
+        let reached = |priority: EventPriority| used * 100 >= limit * priority.shed_percent();
+
+        // Only inspect the events of the item once the lowest priority may be shed.
+        if !reached(EventPriority::Background) {
+            return None;
+        }
+        let priority = item.priority();
+        (priority < EventPriority::Normal && reached(priority)).then_some(priority)
+    }
+
     pub fn capacity(&self) -> Option<usize> {
         match self {
             Self::InMemory(tx) => Some(tx.available_capacity()),
@@ -116,7 +135,8 @@ where
 ///
 /// In "block" mode, callers are simply forced to wait until the channel has enough capacity to
 /// accept the event.  In "drop newest" mode, any event being sent when the channel is full will be
-/// dropped and proceed no further. In "overflow" mode, events will be sent to another buffer
+/// dropped and proceed no further, and events of lower priorities are dropped before the channel is
+/// full. In "overflow" mode, events will be sent to another buffer
 /// sender.  Callers can specify the overflow sender to use when constructing their buffers initially.
 ///
 /// TODO: We should eventually rework `BufferSender`/`BufferReceiver` so that they contain a vector
@@ -188,12 +208,14 @@ impl<T: Bufferable> BufferSender<T> {
             .map(|_| (item.event_count(), item.size_of()));
 
         let mut sent_to_base = true;
-        let mut was_dropped = false;
+        let mut dropped_priority = None;
         match self.when_full {
             WhenFull::Block => self.base.send(item).await?,
             WhenFull::DropNewest => {
-                if self.base.try_send(item).await?.is_some() {
-                    was_dropped = true;
+                if let Some(priority) = self.base.shed_priority(&item) {
+                    dropped_priority = Some(priority);
+                } else if let Some(item) = self.base.try_send(item).await? {
+                    dropped_priority = Some(item.priority());
                 }
             }
             WhenFull::Overflow => {
@@ -217,12 +239,17 @@ impl<T: Bufferable> BufferSender<T> {
                     );
                 }
 
-                if was_dropped {
+                if let Some(priority) = dropped_priority {
                     instrumentation.increment_dropped_event_count_and_byte_size(
                         item_count as u64,
                         item_size as u64,
                         true,
                     );
+                    instrumentation.increment_dropped_by_priority_event_count_and_byte_size(
+                        priority,
+                        item_count as u64,
+                        item_size as u64,
+                    );
                 }
             }
         }
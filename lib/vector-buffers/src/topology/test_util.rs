@@ -9,7 +9,7 @@ use crate::{
     buffer_usage_data::BufferUsageHandle,
     encoding::FixedEncodable,
     topology::channel::{BufferReceiver, BufferSender},
-    Bufferable, EventCount, WhenFull,
+    Bufferable, EventCount, Prioritized, WhenFull,
 };
 
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
@@ -71,6 +71,8 @@ impl EventCount for Sample {
     }
 }
 
+impl Prioritized for Sample {}
+
 #[derive(Debug)]
 pub struct BasicError(pub(crate) String);
 
@@ -11,6 +11,7 @@ use super::{
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
 };
+use crate::FlushMode;
 
 // We don't want data files to be bigger than 128MB, but we might end up overshooting slightly.
 pub const DEFAULT_MAX_DATA_FILE_SIZE: usize = 128 * 1024 * 1024;
@@ -30,6 +31,11 @@ pub const MINIMUM_MAX_RECORD_SIZE: usize = align16(RECORD_HEADER_LEN + 1);
 // have it configured.
 pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
 
+// When fsyncs are batched, 8MB is a large enough batch to make the cost of an fsync negligible
+// compared to the writes it covers, even on slow disks, while still bounding the amount of data
+// that can be lost.
+pub const DEFAULT_FLUSH_BATCH_SIZE: u64 = 8 * 1024 * 1024;
+
 // Using 256KB as it aligns nicely with the I/O size exposed by major cloud providers.  This may not
 // be the underlying block size used by the OS, but it still aligns well with what will happen on
 // the "backend" for cloud providers, which is simply a useful default for when we want to look at
@@ -153,6 +159,17 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Flush mode for data files.
+    ///
+    /// Controls when data written to data files is "hard" flushed to disk: after every write, once
+    /// the flush interval has elapsed, or once enough data has been written to form a batch or the
+    /// flush interval has elapsed, whichever comes first.
+    pub(crate) flush_mode: FlushMode,
+
+    /// Amount of data, in bytes, written between "hard" flushes when the flush mode is
+    /// [`FlushMode::Batch`].
+    pub(crate) flush_batch_size: u64,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +190,8 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) flush_mode: Option<FlushMode>,
+    pub(crate) flush_batch_size: Option<u64>,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +207,8 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            flush_mode: None,
+            flush_batch_size: None,
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +289,29 @@ where
         self
     }
 
+    /// Sets the flush mode for data files.
+    ///
+    /// Data files can be "hard" flushed after every write, which is the most durable but also the
+    /// slowest option, once the flush interval has elapsed, or once enough data has been written
+    /// since the last flush, such that a single flush covers a batch of writes, while still flushing
+    /// at least once per flush interval.
+    ///
+    /// Defaults to flushing once the flush interval has elapsed.
+    pub fn flush_mode(mut self, mode: FlushMode) -> Self {
+        self.flush_mode = Some(mode);
+        self
+    }
+
+    /// Sets the amount of data, in bytes, written between "hard" flushes when data files are
+    /// flushed in batches.
+    ///
+    /// Defaults to 8MB, which only tests change.
+    #[cfg(test)]
+    pub fn flush_batch_size(mut self, amount: u64) -> Self {
+        self.flush_batch_size = Some(amount);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +331,8 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            flush_mode: self.flush_mode,
+            flush_batch_size: self.flush_batch_size,
             filesystem,
         }
     }
@@ -301,6 +347,8 @@ where
         let max_record_size = self.max_record_size.unwrap_or(DEFAULT_MAX_RECORD_SIZE);
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
+        let flush_mode = self.flush_mode.unwrap_or_default();
+        let flush_batch_size = self.flush_batch_size.unwrap_or(DEFAULT_FLUSH_BATCH_SIZE);
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -365,6 +413,13 @@ where
             });
         }
 
+        if flush_batch_size == 0 {
+            return Err(BuildError::InvalidParameter {
+                param_name: "flush_batch_size",
+                reason: "cannot be zero".to_string(),
+            });
+        }
+
         // Users configure the `max_size` of their disk buffers, which translates to the `max_buffer_size` field here,
         // and represents the maximum desired size of a disk buffer in terms of on-disk usage. In order to meet this
         // request, we do a few things internally and also enforce a lower bound on `max_buffer_size` to ensure we can
@@ -412,6 +467,8 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            flush_mode,
+            flush_batch_size,
             filesystem,
         })
     }
@@ -421,7 +478,7 @@ where
 mod tests {
     use proptest::{prop_assert, proptest, test_runner::Config};
 
-    use crate::variants::disk_v2::common::MAX_ALIGNABLE_AMOUNT;
+    use crate::{variants::disk_v2::common::MAX_ALIGNABLE_AMOUNT, FlushMode};
 
     use super::{
         align16, BuildError, DiskBufferConfigBuilder, MINIMUM_MAX_RECORD_SIZE, SERIALIZER_ALIGNMENT,
@@ -557,6 +614,20 @@ mod tests {
             ),
             _ => panic!("expected invalid parameter error"),
         }
+
+        // Flush batch size cannot be zero.
+        let result = DiskBufferConfigBuilder::from_path("/tmp/dummy/path")
+            .flush_mode(FlushMode::Batch)
+            .flush_batch_size(0)
+            .build();
+
+        match result {
+            Err(BuildError::InvalidParameter { param_name, .. }) => assert_eq!(
+                param_name, "flush_batch_size",
+                "invalid parameter should have been `flush_batch_size`"
+            ),
+            _ => panic!("expected invalid parameter error"),
+        }
     }
 
     proptest! {
@@ -208,7 +208,7 @@ use crate::{
         builder::IntoBuffer,
         channel::{ReceiverAdapter, SenderAdapter},
     },
-    Bufferable,
+    Bufferable, FlushMode,
 };
 
 /// Error that occurred when creating/loading a disk buffer.
@@ -301,14 +301,16 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    flush_mode: FlushMode,
 }
 
 impl DiskV2Buffer {
-    pub fn new(id: String, data_dir: PathBuf, max_size: NonZeroU64) -> Self {
+    pub fn new(id: String, data_dir: PathBuf, max_size: NonZeroU64, flush_mode: FlushMode) -> Self {
         Self {
             id,
             data_dir,
             max_size,
+            flush_mode,
         }
     }
 }
@@ -331,6 +333,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.flush_mode,
         )
         .await?;
 
@@ -343,6 +346,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    flush_mode: FlushMode,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -358,6 +362,7 @@ where
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
     let config = DiskBufferConfigBuilder::from_path(buffer_path)
         .max_buffer_size(max_size.get())
+        .flush_mode(flush_mode)
         .build()?;
     Buffer::from_config(config, usage_handle)
         .await
@@ -5,12 +5,17 @@ use tokio_test::{assert_pending, assert_ready, task::spawn};
 use tracing::Instrument;
 use vector_common::finalization::Finalizable;
 
-use super::{create_default_buffer_v2, read_next, read_next_some};
+use super::{
+    create_buffer_v2_with_flush_mode, create_default_buffer_v2, read_next, read_next_some,
+};
 use crate::{
     assert_buffer_is_empty, assert_buffer_records,
     test::{acknowledge, install_tracing_helpers, with_temp_dir, MultiEventRecord, SizedRecord},
-    variants::disk_v2::{tests::create_default_buffer_v2_with_usage, writer::RecordWriter},
-    EventCount,
+    variants::disk_v2::{
+        common::DEFAULT_FLUSH_INTERVAL, tests::create_default_buffer_v2_with_usage,
+        writer::RecordWriter,
+    },
+    EventCount, FlushMode,
 };
 
 #[tokio::test]
@@ -66,6 +71,77 @@ async fn basic_read_write_loop() {
     .await;
 }
 
+#[tokio::test]
+async fn read_write_loop_with_every_flush_mode() {
+    for flush_mode in [FlushMode::EveryWrite, FlushMode::Interval, FlushMode::Batch] {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                // Use a small batch size so that batched flushes happen over the course of the test.
+                let (mut writer, mut reader, ledger) =
+                    create_buffer_v2_with_flush_mode(data_dir, flush_mode, 2048).await;
+                assert_buffer_is_empty!(ledger);
+
+                let expected_items = (512..768)
+                    .cycle()
+                    .take(10)
+                    .map(SizedRecord::new)
+                    .collect::<Vec<_>>();
+
+                for item in expected_items.clone() {
+                    writer
+                        .write_record(item)
+                        .await
+                        .expect("write should not fail");
+                    writer.flush().await.expect("writer flush should not fail");
+                }
+                writer.close();
+
+                let mut actual_items = Vec::new();
+                while let Some(mut record) = read_next(&mut reader).await {
+                    acknowledge(record.take_finalizers()).await;
+                    actual_items.push(record);
+                }
+
+                assert_buffer_is_empty!(ledger);
+                assert_eq!(actual_items, expected_items, "flush mode: {:?}", flush_mode);
+            }
+        })
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn batch_flush_mode_syncs_once_flush_interval_elapsed() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // The batch can't be filled, so only the flush interval elapsing can trigger a sync.
+            let (mut writer, _reader, _ledger) =
+                create_buffer_v2_with_flush_mode::<_, SizedRecord>(
+                    data_dir,
+                    FlushMode::Batch,
+                    u64::MAX,
+                )
+                .await;
+
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("writer flush should not fail");
+            assert_ne!(writer.unsynced_bytes(), 0);
+
+            tokio::time::sleep(DEFAULT_FLUSH_INTERVAL * 2).await;
+            writer.flush().await.expect("writer flush should not fail");
+            assert_eq!(writer.unsynced_bytes(), 0);
+        }
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn reader_exits_cleanly_when_writer_done_and_in_flight_acks() {
     let assertion_registry = install_tracing_helpers();
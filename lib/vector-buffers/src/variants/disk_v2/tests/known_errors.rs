@@ -20,7 +20,7 @@ use crate::{
     encoding::{AsMetadata, Encodable},
     test::{acknowledge, install_tracing_helpers, with_temp_dir, SizedRecord, UndecodableRecord},
     variants::disk_v2::{backed_archive::BackedArchive, record::Record, ReaderError},
-    EventCount,
+    EventCount, Prioritized,
 };
 
 #[tokio::test]
@@ -745,6 +745,8 @@ async fn reader_throws_error_when_record_is_undecodable_via_metadata() {
         }
     }
 
+    impl Prioritized for ControllableRecord {}
+
     with_temp_dir(|dir| {
         let data_dir = dir.to_path_buf();
 
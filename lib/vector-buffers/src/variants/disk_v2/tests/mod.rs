@@ -18,7 +18,7 @@ use super::{
 };
 use crate::{
     buffer_usage_data::BufferUsageHandle, encoding::FixedEncodable,
-    variants::disk_v2::common::align16, Bufferable,
+    variants::disk_v2::common::align16, Bufferable, FlushMode,
 };
 
 type FilesystemUnderTest = ProductionFilesystem;
@@ -345,6 +345,31 @@ where
         .expect("should not fail to create buffer")
 }
 
+pub(crate) async fn create_buffer_v2_with_flush_mode<P, R>(
+    data_dir: P,
+    flush_mode: FlushMode,
+    flush_batch_size: u64,
+) -> (
+    Writer<R, FilesystemUnderTest>,
+    Reader<R, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .flush_mode(flush_mode)
+        .flush_batch_size(flush_batch_size)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
 pub(crate) fn get_corrected_max_record_size<T>(payload: &T) -> usize
 where
     T: FixedEncodable,
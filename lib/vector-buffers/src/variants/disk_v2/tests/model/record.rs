@@ -9,7 +9,7 @@ use vector_common::finalization::{
 use crate::{
     encoding::FixedEncodable,
     variants::disk_v2::{record::RECORD_HEADER_LEN, tests::align16},
-    EventCount,
+    EventCount, Prioritized,
 };
 
 #[derive(Debug)]
@@ -102,6 +102,8 @@ impl EventCount for Record {
     }
 }
 
+impl Prioritized for Record {}
+
 impl ByteSizeOf for Record {
     fn allocated_bytes(&self) -> usize {
         0
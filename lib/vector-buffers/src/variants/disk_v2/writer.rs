@@ -36,7 +36,7 @@ use crate::{
         reader::decode_record_payload,
         record::{try_as_record_archive, RECORD_HEADER_LEN},
     },
-    Bufferable,
+    Bufferable, FlushMode,
 };
 
 /// Error that occurred during calls to [`Writer`].
@@ -721,6 +721,7 @@ where
     unflushed_events: u64,
     data_file_size: u64,
     unflushed_bytes: u64,
+    unsynced_bytes: u64,
     data_file_full: bool,
     skip_to_next: bool,
     ready_to_write: bool,
@@ -744,6 +745,7 @@ where
             data_file_size: 0,
             data_file_full: false,
             unflushed_bytes: 0,
+            unsynced_bytes: 0,
             skip_to_next: false,
             ready_to_write: false,
             next_record_id,
@@ -760,6 +762,7 @@ where
         self.data_file_size += record_size;
         self.unflushed_events += event_count as u64;
         self.unflushed_bytes += record_size;
+        self.unsynced_bytes += record_size;
     }
 
     fn flush_write_state(&mut self) {
@@ -1314,10 +1317,11 @@ where
             self.ledger.notify_writer_waiters();
         }
 
-        if self.ledger.should_flush() || force_full_flush {
+        if self.should_sync() || force_full_flush {
             if let Some(writer) = self.writer.as_mut() {
                 writer.sync_all().await?;
             }
+            self.unsynced_bytes = 0;
 
             self.ledger.flush()
         } else {
@@ -1325,13 +1329,35 @@ where
         }
     }
 
+    /// Determines whether or not the data written so far should be synchronized to disk, based on
+    /// the configured flush mode.
+    fn should_sync(&self) -> bool {
+        match self.config.flush_mode {
+            FlushMode::EveryWrite => self.unsynced_bytes > 0,
+            FlushMode::Interval => self.ledger.should_flush(),
+            // All the writes made since the last synchronization are committed by a single one,
+            // once there are enough of them, or once the flush interval has elapsed, so that data
+            // written at a low rate still reaches the disk in a timely manner.
+            FlushMode::Batch => {
+                self.ledger.should_flush() || self.unsynced_bytes >= self.config.flush_batch_size
+            }
+        }
+    }
+
+    /// Gets the amount of data, in bytes, written since the last synchronization to disk.
+    #[cfg(test)]
+    pub(crate) fn unsynced_bytes(&self) -> u64 {
+        self.unsynced_bytes
+    }
+
     /// Flushes the writer.
     ///
     /// This must be called for the reader to be able to make progress.
     ///
-    /// This does not ensure that the data is fully synchronized (i.e. `fsync`) to disk, however it
-    /// may sometimes perform a full synchronization if the time since the last full synchronization
-    /// occurred has exceeded a configured limit.
+    /// This does not ensure that the data is fully synchronized (i.e. `fsync`) to disk, unless the
+    /// flush mode is [`FlushMode::EveryWrite`]. Otherwise, it performs a full synchronization when
+    /// the time since the last one has exceeded the flush interval, or, in batch mode, also when
+    /// the amount of data written since the last one has reached the flush batch size.
     ///
     /// # Errors
     ///
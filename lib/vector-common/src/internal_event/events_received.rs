@@ -9,6 +9,7 @@ crate::registered_event!(
         events: Counter = register_counter!("component_received_events_total"),
         events_in: Counter = register_counter!("events_in_total"),
         event_bytes: Counter = register_counter!("component_received_event_bytes_total"),
+        event_size: Histogram = register_histogram!("component_received_event_size_bytes"),
     }
 
     fn emit(&self, data: CountByteSize) {
@@ -23,3 +24,11 @@ crate::registered_event!(
         self.event_bytes.increment(byte_size as u64);
     }
 );
+
+impl EventsReceivedHandle {
+    /// Records the size of one of the received events, in bytes.
+    pub fn record_event_size(&self, byte_size: usize) {
+        #[allow(clippy::cast_precision_loss)]
+        self.event_size.record(byte_size as f64);
+    }
+}
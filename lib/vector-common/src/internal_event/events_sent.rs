@@ -1,4 +1,4 @@
-use metrics::{register_counter, Counter};
+use metrics::{register_counter, register_histogram, Counter, Histogram};
 use tracing::trace;
 
 use super::{CountByteSize, Output, SharedString};
@@ -24,6 +24,11 @@ crate::registered_event!(
         } else {
             register_counter!("component_sent_event_bytes_total")
         },
+        event_size: Histogram = if let Some(output) = &self.output {
+            register_histogram!("component_sent_event_size_bytes", "output" => output.clone())
+        } else {
+            register_histogram!("component_sent_event_size_bytes")
+        },
         output: Option<SharedString> = self.output,
     }
 
@@ -45,6 +50,14 @@ crate::registered_event!(
     }
 );
 
+impl EventsSentHandle {
+    /// Records the size of one of the sent events, in bytes.
+    pub fn record_event_size(&self, byte_size: usize) {
+        #[allow(clippy::cast_precision_loss)]
+        self.event_size.record(byte_size as f64);
+    }
+}
+
 impl From<Output> for EventsSent {
     fn from(output: Output) -> Self {
         Self { output: output.0 }
@@ -3,6 +3,7 @@ mod bytes_sent;
 pub mod component_events_dropped;
 mod events_received;
 mod events_sent;
+mod partitions;
 mod prelude;
 pub mod service;
 
@@ -13,6 +14,7 @@ pub use bytes_sent::BytesSent;
 pub use component_events_dropped::{ComponentEventsDropped, INTENTIONAL, UNINTENTIONAL};
 pub use events_received::EventsReceived;
 pub use events_sent::{EventsSent, DEFAULT_OUTPUT};
+pub use partitions::{PartitionClosed, PartitionEvicted, PartitionOpened};
 pub use prelude::{error_stage, error_type};
 pub use service::{CallError, PollReadyError};
 
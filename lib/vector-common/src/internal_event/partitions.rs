@@ -0,0 +1,60 @@
+use metrics::{counter, gauge};
+use tracing::debug;
+
+use super::InternalEvent;
+
+#[derive(Debug)]
+pub struct PartitionOpened {
+    pub open_partitions: usize,
+}
+
+impl InternalEvent for PartitionOpened {
+    fn emit(self) {
+        counter!("partitions_opened_total", 1);
+        #[allow(clippy::cast_precision_loss)]
+        gauge!("open_partitions", self.open_partitions as f64);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("PartitionOpened")
+    }
+}
+
+#[derive(Debug)]
+pub struct PartitionClosed {
+    pub open_partitions: usize,
+}
+
+impl InternalEvent for PartitionClosed {
+    fn emit(self) {
+        #[allow(clippy::cast_precision_loss)]
+        gauge!("open_partitions", self.open_partitions as f64);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("PartitionClosed")
+    }
+}
+
+#[derive(Debug)]
+pub struct PartitionEvicted {
+    pub open_partitions: usize,
+    pub reason: &'static str,
+}
+
+impl InternalEvent for PartitionEvicted {
+    fn emit(self) {
+        debug!(
+            message = "Flushed the batch of the least recently used partition early.",
+            reason = self.reason,
+            internal_log_rate_limit = true,
+        );
+        counter!("partitions_evicted_total", 1, "reason" => self.reason);
+        #[allow(clippy::cast_precision_loss)]
+        gauge!("open_partitions", self.open_partitions as f64);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("PartitionEvicted")
+    }
+}
@@ -87,7 +87,19 @@ where
 
     /// Generate a schema object covering all the descriptions of this type.
     pub fn generate_schemas(gen: &RefCell<SchemaGenerator>) -> Result<SchemaObject, GenerateError> {
-        let mut descriptions: Vec<_> = inventory::iter::<Self>.into_iter().collect();
+        Self::generate_schemas_matching(gen, |_| true)
+    }
+
+    /// Generate a schema object covering the descriptions of this type whose component name
+    /// matches the given predicate.
+    pub fn generate_schemas_matching(
+        gen: &RefCell<SchemaGenerator>,
+        matches: impl Fn(&str) -> bool,
+    ) -> Result<SchemaObject, GenerateError> {
+        let mut descriptions: Vec<_> = inventory::iter::<Self>
+            .into_iter()
+            .filter(|description| matches(description.component_name))
+            .collect();
         descriptions.sort_unstable_by_key(|desc| desc.component_name);
         let subschemas: Vec<SchemaObject> = descriptions
             .into_iter()
@@ -3,7 +3,8 @@ use std::time::Duration;
 use criterion::{
     criterion_group, measurement::WallTime, BatchSize, BenchmarkGroup, Criterion, SamplingMode,
 };
-use lookup::event_path;
+use lookup::{event_path, path};
+use value::Value;
 use vector_core::event::LogEvent;
 
 fn rename_key_flat(c: &mut Criterion) {
@@ -44,6 +45,28 @@ fn rename_key_flat(c: &mut Criterion) {
     });
 }
 
+fn clone_for_fanout(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> =
+        c.benchmark_group("vector_core::event::log_event::LogEvent::clone_for_fanout");
+    group.sampling_mode(SamplingMode::Auto);
+
+    let mut log_event = LogEvent::default();
+    log_event.insert("message", "hello world");
+    log_event.insert("host", "localhost");
+    log_event
+        .metadata_mut()
+        .value_mut()
+        .insert(path!("vector", "ingest", "source_id"), Value::from("in"));
+
+    group.bench_function("clone to 4 downstream components", |b| {
+        b.iter_batched(
+            || log_event.clone(),
+            |log_event| (0..4).map(|_| log_event.clone()).collect::<Vec<LogEvent>>(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 criterion_group!(
     name = benches;
     config = Criterion::default()
@@ -59,5 +82,5 @@ criterion_group!(
         .nresamples(100_000)
         // total samples to collect within the set measurement time
         .sample_size(150);
-    targets = rename_key_flat
+    targets = rename_key_flat, clone_for_fanout
 );
@@ -1,4 +1,14 @@
 fn main() {
+    // The `links` metadata of `openssl-sys` tells which library OpenSSL is provided by.
+    if std::env::var_os("CARGO_FEATURE_FIPS").is_some()
+        && std::env::var_os("DEP_OPENSSL_BORINGSSL").is_none()
+    {
+        panic!(
+            "The `fips` feature requires linking against BoringSSL built in FIPS mode: set \
+             `OPENSSL_DIR` to its location, and `OPENSSL_NO_VENDOR=1`."
+        );
+    }
+
     println!("cargo:rerun-if-changed=proto/event.proto");
     prost_build::Config::new()
         .protoc_arg("--experimental_allow_proto3_optional")
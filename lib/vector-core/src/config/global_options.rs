@@ -1,5 +1,6 @@
-use std::{fs::DirBuilder, path::PathBuf, time::Duration};
+use std::{collections::BTreeMap, fs::DirBuilder, path::PathBuf, time::Duration};
 
+use lookup::lookup_v2::ConfigTargetPath;
 use snafu::{ResultExt, Snafu};
 use vector_common::TimeZone;
 use vector_config::configurable_component;
@@ -115,6 +116,252 @@ pub struct GlobalOptions {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub expire_metrics_secs: Option<f64>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub lineage: LineageConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub latency: LatencyConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub stall_detection: StallDetectionConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub memory_budget: MemoryBudgetConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub event_size: EventSizeConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub telemetry: TelemetryConfig,
+}
+
+/// Controls the lineage record stamped in the metadata of events.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LineageConfig {
+    /// Whether or not to stamp events with a lineage record.
+    ///
+    /// The record is stored in the event metadata under `vector.lineage`. It holds the ID of the
+    /// source that produced the event, the IDs of the transforms the event went through, the ID
+    /// of this Vector instance, and the hash of the running configuration. It can be read in VRL
+    /// with the `get_lineage` function.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The ID of this Vector instance in lineage records.
+    ///
+    /// By default, the hostname is used.
+    #[configurable(metadata(docs::examples = "vector-aggregator-0"))]
+    #[serde(default)]
+    pub instance_id: Option<String>,
+}
+
+/// Controls the tracking of the end-to-end latency of events.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LatencyConfig {
+    /// Whether or not to track the end-to-end latency of events.
+    ///
+    /// When enabled, sources record the time at which they received each event in its metadata,
+    /// under `vector.ingest`, and sinks report the time elapsed since then once the event has been
+    /// delivered, in the `end_to_end_latency_seconds` histogram tagged with the ID of the source.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Controls the detection of sources stalled by backpressure.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct StallDetectionConfig {
+    /// Whether or not to detect sources stalled by backpressure.
+    ///
+    /// When enabled, sources blocked sending events downstream for longer than the threshold are
+    /// reported, along with the chain of components the stall is attributed to.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// How long, in seconds, a source must be blocked sending events downstream to be reported.
+    ///
+    /// Defaults to 30 seconds.
+    #[configurable(metadata(docs::examples = 10))]
+    #[serde(default)]
+    pub threshold_secs: Option<u64>,
+}
+
+impl StallDetectionConfig {
+    const DEFAULT_THRESHOLD_SECS: u64 = 30;
+
+    /// Returns how long a source must be blocked to be reported, if stall detection is enabled.
+    pub fn threshold(&self) -> Option<Duration> {
+        self.enabled.then(|| {
+            Duration::from_secs(
+                self.threshold_secs
+                    .unwrap_or(Self::DEFAULT_THRESHOLD_SECS)
+                    .max(1),
+            )
+        })
+    }
+}
+
+/// Controls the memory budget of sinks.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryBudgetConfig {
+    /// The soft limit, in bytes, on the approximate size of the events held in flight by all sinks.
+    ///
+    /// Events are held in flight by a sink from the time they are read from its buffer until they
+    /// are delivered or dropped. Sinks can also be given their own limit with the
+    /// `memory_limit_bytes` option.
+    #[configurable(metadata(docs::examples = 268435456))]
+    #[serde(default)]
+    pub limit_bytes: Option<usize>,
+
+    /// What to do when a memory limit is exceeded.
+    ///
+    /// Defaults to `block`.
+    #[serde(default)]
+    pub when_exceeded: Option<MemoryShedPolicy>,
+}
+
+/// What a sink does with its input while one of its memory limits is exceeded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryShedPolicy {
+    /// The sink stops reading its input until enough events held in flight have been released.
+    ///
+    /// This applies backpressure upstream, as the buffer of the sink fills up.
+    #[default]
+    Block,
+
+    /// The events read by the sink are dropped until enough events held in flight have been
+    /// released.
+    DropNewest,
+}
+
+/// Controls the maximum size of events.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct EventSizeConfig {
+    /// The maximum size, in bytes, of the events sent by sources and transforms.
+    ///
+    /// The size of an event is the estimated size of its JSON encoding. Events are checked after
+    /// being decoded by sources, and again after each transform.
+    #[configurable(metadata(docs::examples = 1048576))]
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+
+    /// What to do with the events exceeding the maximum size.
+    ///
+    /// Defaults to `truncate`.
+    #[serde(default)]
+    pub when_exceeded: Option<EventSizePolicy>,
+
+    /// The fields truncated, in order, by the `truncate` policy.
+    ///
+    /// Defaults to the message field of the global log schema.
+    #[configurable(metadata(docs::examples = "message"))]
+    #[configurable(metadata(docs::examples = "payload.body"))]
+    #[serde(default)]
+    pub truncate_fields: Vec<ConfigTargetPath>,
+}
+
+/// What is done with the events exceeding the maximum size.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSizePolicy {
+    /// The string values of the `truncate_fields` of log and trace events are truncated until the
+    /// events fit.
+    ///
+    /// Metric events, and the events still exceeding the maximum size once all the fields have
+    /// been truncated, are dropped.
+    #[default]
+    Truncate,
+
+    /// The events are dropped.
+    Drop,
+
+    /// The events are sent to the `dead_letter` sources, with the ID of the component which sent
+    /// them in their metadata, under `vector.dead_letter`.
+    ///
+    /// The events are dropped when there is no `dead_letter` source, and when they exceed the
+    /// maximum size after going through the dead letter queue once already.
+    DeadLetter,
+}
+
+/// Controls the labels of internal telemetry derived from the events.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryConfig {
+    /// Labels taken from the events flowing through components.
+    ///
+    /// When set, components count the events they receive and send in the
+    /// `component_received_labeled_events_total` and `component_sent_labeled_events_total`
+    /// metrics, along with their size in bytes, labeled with the value of these fields in each
+    /// event. Metric events are labeled with the value of the tag of the same name as the field.
+    #[configurable(metadata(
+        docs::additional_props_description = "The field of the events to take the value of a label from."
+    ))]
+    #[configurable(metadata(docs::examples = "example_event_labels()"))]
+    #[serde(default)]
+    pub event_labels: BTreeMap<String, ConfigTargetPath>,
+
+    /// The maximum number of distinct values of each label, per component.
+    ///
+    /// Once reached, the events holding other values are counted with the value `other`, which
+    /// bounds the cardinality of the metrics. Defaults to 100.
+    #[configurable(metadata(docs::examples = 500))]
+    #[serde(default)]
+    pub max_label_values: Option<usize>,
+}
+
+fn example_event_labels() -> BTreeMap<String, String> {
+    BTreeMap::from([(
+        "namespace".to_owned(),
+        ".kubernetes.pod_namespace".to_owned(),
+    )])
+}
+
+impl TelemetryConfig {
+    const DEFAULT_MAX_LABEL_VALUES: usize = 100;
+
+    /// Returns the maximum number of distinct values of each label.
+    pub fn max_label_values(&self) -> usize {
+        self.max_label_values
+            .unwrap_or(Self::DEFAULT_MAX_LABEL_VALUES)
+    }
 }
 
 impl GlobalOptions {
@@ -193,6 +440,63 @@ impl GlobalOptions {
             errors.push("conflicting values for 'timezone' found".to_owned());
         }
 
+        if conflicts(&self.lineage.instance_id, &with.lineage.instance_id) {
+            errors.push("conflicting values for 'lineage.instance_id' found".to_owned());
+        }
+
+        if conflicts(
+            &self.stall_detection.threshold_secs,
+            &with.stall_detection.threshold_secs,
+        ) {
+            errors.push("conflicting values for 'stall_detection.threshold_secs' found".to_owned());
+        }
+
+        if conflicts(
+            &self.memory_budget.limit_bytes,
+            &with.memory_budget.limit_bytes,
+        ) {
+            errors.push("conflicting values for 'memory_budget.limit_bytes' found".to_owned());
+        }
+
+        if conflicts(
+            &self.memory_budget.when_exceeded,
+            &with.memory_budget.when_exceeded,
+        ) {
+            errors.push("conflicting values for 'memory_budget.when_exceeded' found".to_owned());
+        }
+
+        if conflicts(&self.event_size.max_bytes, &with.event_size.max_bytes) {
+            errors.push("conflicting values for 'event_size.max_bytes' found".to_owned());
+        }
+
+        if conflicts(
+            &self.event_size.when_exceeded,
+            &with.event_size.when_exceeded,
+        ) {
+            errors.push("conflicting values for 'event_size.when_exceeded' found".to_owned());
+        }
+
+        if !self.event_size.truncate_fields.is_empty()
+            && !with.event_size.truncate_fields.is_empty()
+            && self.event_size.truncate_fields != with.event_size.truncate_fields
+        {
+            errors.push("conflicting values for 'event_size.truncate_fields' found".to_owned());
+        }
+
+        if !self.telemetry.event_labels.is_empty()
+            && !with.telemetry.event_labels.is_empty()
+            && self.telemetry.event_labels != with.telemetry.event_labels
+        {
+            errors.push("conflicting values for 'telemetry.event_labels' found".to_owned());
+        }
+
+        if conflicts(
+            &self.telemetry.max_label_values,
+            &with.telemetry.max_label_values,
+        ) {
+            errors.push("conflicting values for 'telemetry.max_label_values' found".to_owned());
+        }
+
         if conflicts(
             &self.acknowledgements.enabled,
             &with.acknowledgements.enabled,
@@ -227,6 +531,57 @@ impl GlobalOptions {
                 proxy: self.proxy.merge(&with.proxy),
                 expire_metrics: self.expire_metrics.or(with.expire_metrics),
                 expire_metrics_secs: self.expire_metrics_secs.or(with.expire_metrics_secs),
+                lineage: LineageConfig {
+                    enabled: self.lineage.enabled || with.lineage.enabled,
+                    instance_id: self
+                        .lineage
+                        .instance_id
+                        .clone()
+                        .or(with.lineage.instance_id),
+                },
+                latency: LatencyConfig {
+                    enabled: self.latency.enabled || with.latency.enabled,
+                },
+                stall_detection: StallDetectionConfig {
+                    enabled: self.stall_detection.enabled || with.stall_detection.enabled,
+                    threshold_secs: self
+                        .stall_detection
+                        .threshold_secs
+                        .or(with.stall_detection.threshold_secs),
+                },
+                memory_budget: MemoryBudgetConfig {
+                    limit_bytes: self
+                        .memory_budget
+                        .limit_bytes
+                        .or(with.memory_budget.limit_bytes),
+                    when_exceeded: self
+                        .memory_budget
+                        .when_exceeded
+                        .or(with.memory_budget.when_exceeded),
+                },
+                event_size: EventSizeConfig {
+                    max_bytes: self.event_size.max_bytes.or(with.event_size.max_bytes),
+                    when_exceeded: self
+                        .event_size
+                        .when_exceeded
+                        .or(with.event_size.when_exceeded),
+                    truncate_fields: if self.event_size.truncate_fields.is_empty() {
+                        with.event_size.truncate_fields
+                    } else {
+                        self.event_size.truncate_fields.clone()
+                    },
+                },
+                telemetry: TelemetryConfig {
+                    event_labels: if self.telemetry.event_labels.is_empty() {
+                        with.telemetry.event_labels
+                    } else {
+                        self.telemetry.event_labels.clone()
+                    },
+                    max_label_values: self
+                        .telemetry
+                        .max_label_values
+                        .or(with.telemetry.max_label_values),
+                },
             })
         } else {
             Err(errors)
@@ -350,6 +705,94 @@ mod tests {
         assert_eq!(merge(Some(4.0), Some(5.0)), Ok(Some(4.0))); // Uses minimum
     }
 
+    #[test]
+    fn merges_lineage() {
+        let merge = |a, b| merge("lineage.instance_id", a, b, |result| result.lineage);
+
+        assert_eq!(merge(None, None), Ok(LineageConfig::default()));
+        assert_eq!(
+            merge(Some("test1"), None).map(|lineage| lineage.instance_id),
+            Ok(Some("test1".into()))
+        );
+        assert_eq!(
+            merge(Some("test2"), Some("test2")).map(|lineage| lineage.instance_id),
+            Ok(Some("test2".into()))
+        );
+        assert_eq!(
+            merge(Some("test3"), Some("test4")),
+            Err(vec![
+                "conflicting values for 'lineage.instance_id' found".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn merges_latency() {
+        let merge = |a, b| merge("latency.enabled", a, b, |result| result.latency.enabled);
+
+        assert_eq!(merge(None, None), Ok(false));
+        assert_eq!(merge(Some(true), None), Ok(true));
+        assert_eq!(merge(None, Some(true)), Ok(true));
+        assert_eq!(merge(Some(false), Some(true)), Ok(true));
+    }
+
+    #[test]
+    fn merges_stall_detection() {
+        let merge = |a, b| {
+            merge("stall_detection.threshold_secs", a, b, |result| {
+                result.stall_detection.threshold_secs
+            })
+        };
+
+        assert_eq!(merge(None, None), Ok(None));
+        assert_eq!(merge(Some(10), None), Ok(Some(10)));
+        assert_eq!(merge(None, Some(20)), Ok(Some(20)));
+        assert_eq!(
+            merge(Some(30), Some(40)),
+            Err(vec![
+                "conflicting values for 'stall_detection.threshold_secs' found".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn merges_memory_budget() {
+        let merge = |a, b| {
+            merge("memory_budget.limit_bytes", a, b, |result| {
+                result.memory_budget.limit_bytes
+            })
+        };
+
+        assert_eq!(merge(None, None), Ok(None));
+        assert_eq!(merge(Some(1024), None), Ok(Some(1024)));
+        assert_eq!(merge(None, Some(2048)), Ok(Some(2048)));
+        assert_eq!(
+            merge(Some(1024), Some(2048)),
+            Err(vec![
+                "conflicting values for 'memory_budget.limit_bytes' found".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn merges_event_size() {
+        let merge = |a, b| {
+            merge("event_size.max_bytes", a, b, |result| {
+                result.event_size.max_bytes
+            })
+        };
+
+        assert_eq!(merge(None, None), Ok(None));
+        assert_eq!(merge(Some(1024), None), Ok(Some(1024)));
+        assert_eq!(merge(Some(1024), Some(1024)), Ok(Some(1024)));
+        assert_eq!(
+            merge(Some(1024), Some(2048)),
+            Err(vec![
+                "conflicting values for 'event_size.max_bytes' found".into()
+            ])
+        );
+    }
+
     fn merge<P: Debug, T>(
         name: &str,
         dd1: Option<P>,
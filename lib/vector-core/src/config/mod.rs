@@ -10,7 +10,10 @@ pub mod output_id;
 pub mod proxy;
 
 use crate::event::LogEvent;
-pub use global_options::GlobalOptions;
+pub use global_options::{
+    EventSizeConfig, EventSizePolicy, GlobalOptions, LatencyConfig, LineageConfig,
+    MemoryBudgetConfig, MemoryShedPolicy, StallDetectionConfig, TelemetryConfig,
+};
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 use lookup::{lookup_v2::ValuePath, path, PathPrefix};
 pub use output_id::OutputId;
@@ -72,6 +72,17 @@ pub struct ProxyConfig {
     #[configurable(metadata(docs::examples = "http://foo.bar:3128"))]
     pub https: Option<String>,
 
+    /// Proxy endpoint to use when proxying raw TCP traffic, such as the one of socket sinks.
+    ///
+    /// Must be a valid URI string, with either the `socks5` scheme for a SOCKS5 proxy, or the
+    /// `http` scheme for an HTTP proxy tunneling the connections with `CONNECT` requests.
+    /// Credentials can be set in the URI.
+    #[configurable(validation(format = "uri"))]
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "socks5://foo.bar:1080"))]
+    #[configurable(metadata(docs::examples = "http://foo.bar:3128"))]
+    pub tcp: Option<String>,
+
     /// A list of hosts to avoid proxying.
     ///
     /// Multiple patterns are allowed:
@@ -101,6 +112,7 @@ impl Default for ProxyConfig {
             enabled: Self::default_enabled(),
             http: None,
             https: None,
+            tcp: None,
             no_proxy: NoProxy::default(),
         }
     }
@@ -116,6 +128,7 @@ impl ProxyConfig {
             enabled: true,
             http: from_env("HTTP_PROXY"),
             https: from_env("HTTPS_PROXY"),
+            tcp: None,
             no_proxy: from_env("NO_PROXY").map(NoProxy::from).unwrap_or_default(),
         }
     }
@@ -143,10 +156,22 @@ impl ProxyConfig {
             enabled: self.enabled && other.enabled,
             http: other.http.clone().or_else(|| self.http.clone()),
             https: other.https.clone().or_else(|| self.https.clone()),
+            tcp: other.tcp.clone().or_else(|| self.tcp.clone()),
             no_proxy,
         }
     }
 
+    /// Returns the endpoint of the proxy to connect to the host through over TCP, if any.
+    pub fn tcp_proxy(&self, host: &str, port: u16) -> Option<&str> {
+        let bypassed =
+            self.no_proxy.matches(host) || self.no_proxy.matches(&format!("{host}:{port}"));
+        if self.enabled && !bypassed {
+            self.tcp.as_deref()
+        } else {
+            None
+        }
+    }
+
     fn build_proxy(
         &self,
         proxy_scheme: &'static str,
@@ -260,6 +285,26 @@ mod tests {
         assert!(result.no_proxy.matches("localhost"));
     }
 
+    #[test]
+    fn tcp_proxy() {
+        let config = ProxyConfig {
+            tcp: Some("socks5://1.2.3.4:1080".into()),
+            no_proxy: NoProxy::from("localhost"),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.tcp_proxy("example.com", 9000),
+            Some("socks5://1.2.3.4:1080")
+        );
+        assert_eq!(config.tcp_proxy("localhost", 9000), None);
+
+        let config = config.merge(&ProxyConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        assert_eq!(config.tcp_proxy("example.com", 9000), None);
+    }
+
     #[test]
     fn with_environment_variables() {
         let global_proxy = ProxyConfig {
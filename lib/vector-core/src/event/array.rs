@@ -7,12 +7,12 @@ use std::{iter, slice, vec};
 use futures::{stream, Stream};
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen};
-use vector_buffers::EventCount;
+use vector_buffers::{EventCount, EventPriority, Prioritized};
 use vector_common::finalization::{AddBatchNotifier, BatchNotifier, EventFinalizers, Finalizable};
 
 use super::{
-    EstimatedJsonEncodedSizeOf, Event, EventDataEq, EventFinalizer, EventMutRef, EventRef,
-    LogEvent, Metric, TraceEvent,
+    estimated_json_encoded_size_of::estimated_json_encoded_array_size, EstimatedJsonEncodedSizeOf,
+    Event, EventDataEq, EventFinalizer, EventMutRef, EventRef, LogEvent, Metric, TraceEvent,
 };
 use crate::ByteSizeOf;
 
@@ -147,6 +147,16 @@ impl EventArray {
         }
     }
 
+    /// Returns the estimated JSON encoded size of this array, calling `record` with the estimated
+    /// size of each of its events.
+    pub fn estimated_json_encoded_size_with(&self, mut record: impl FnMut(usize)) -> usize {
+        estimated_json_encoded_array_size(self.iter_events().map(|event| {
+            let size = event.estimated_json_encoded_size_of();
+            record(size);
+            size
+        }))
+    }
+
     /// Iterate over mutable references to this array's events.
     pub fn iter_events_mut(&mut self) -> impl Iterator<Item = EventMutRef> {
         match self {
@@ -251,6 +261,17 @@ impl EventCount for EventArray {
     }
 }
 
+impl Prioritized for EventArray {
+    /// Gets the highest priority of the events, so that events are only shed along with events
+    /// of the same or lower priorities.
+    fn priority(&self) -> EventPriority {
+        self.iter_events()
+            .map(|event| event.metadata().priority())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
 impl EventContainer for EventArray {
     type IntoIter = EventArrayIntoIter;
 
@@ -185,15 +185,20 @@ where
     V: EstimatedJsonEncodedSizeOf,
 {
     fn estimated_json_encoded_size_of(&self) -> usize {
-        let size = self.iter().fold(BRACKETS_SIZE, |acc, v| {
-            acc + COMMA_SIZE + v.estimated_json_encoded_size_of()
-        });
+        estimated_json_encoded_array_size(self.iter().map(V::estimated_json_encoded_size_of))
+    }
+}
 
-        if size > BRACKETS_SIZE {
-            size - COMMA_SIZE
-        } else {
-            size
-        }
+/// Returns the estimated size of a JSON array, given the estimated sizes of its elements.
+pub(crate) fn estimated_json_encoded_array_size(sizes: impl IntoIterator<Item = usize>) -> usize {
+    let size = sizes
+        .into_iter()
+        .fold(BRACKETS_SIZE, |acc, size| acc + COMMA_SIZE + size);
+
+    if size > BRACKETS_SIZE {
+        size - COMMA_SIZE
+    } else {
+        size
     }
 }
 
@@ -424,6 +429,7 @@ mod tests {
     use std::collections::HashMap;
 
     use super::*;
+    use crate::event::EventArray;
     use quickcheck::{Arbitrary, Gen, TestResult};
     use quickcheck_macros::quickcheck;
     use serde::Serialize;
@@ -594,6 +600,18 @@ mod tests {
         got == want.len()
     }
 
+    #[quickcheck]
+    fn estimate_event_array_with_event_sizes(events: EventArray) -> bool {
+        let mut sizes = Vec::new();
+        let got = events.estimated_json_encoded_size_with(|size| sizes.push(size));
+        let want = events
+            .iter_events()
+            .map(|event| event.estimated_json_encoded_size_of())
+            .collect::<Vec<_>>();
+
+        got == events.estimated_json_encoded_size_of() && sizes == want
+    }
+
     #[quickcheck]
     fn estimate_map(v: HashMap<ValidString, bool>) -> TestResult {
         let got = v.estimated_json_encoded_size_of();
@@ -3,8 +3,10 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use lookup::path;
 use serde::{Deserialize, Serialize};
 use value::{Kind, Secrets, Value};
+use vector_buffers::EventPriority;
 use vector_common::EventDataEq;
 
 use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus};
@@ -19,8 +21,11 @@ const SPLUNK_HEC_TOKEN: &str = "splunk_hec_token";
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EventMetadata {
     /// Arbitrary data stored with an event
+    ///
+    /// It is shared between the copies of an event made when fanning it out, and only copied once
+    /// one of them is mutated.
     #[serde(default = "default_metadata_value", skip)]
-    value: Value,
+    value: Arc<Value>,
 
     /// Storage for secrets
     #[serde(default, skip)]
@@ -37,15 +42,15 @@ pub struct EventMetadata {
     schema_definition: Arc<schema::Definition>,
 }
 
-fn default_metadata_value() -> Value {
-    Value::Object(BTreeMap::new())
+fn default_metadata_value() -> Arc<Value> {
+    Arc::new(Value::Object(BTreeMap::new()))
 }
 
 impl EventMetadata {
     /// Creates `EventMetadata` with the given `Value`, and the rest of the fields with default values
     pub fn default_with_value(value: Value) -> Self {
         Self {
-            value,
+            value: Arc::new(value),
             ..Default::default()
         }
     }
@@ -57,7 +62,7 @@ impl EventMetadata {
 
     /// Returns a mutable reference to the metadata value
     pub fn value_mut(&mut self) -> &mut Value {
-        &mut self.value
+        Arc::make_mut(&mut self.value)
     }
 
     /// Returns a reference to the secrets
@@ -89,12 +94,31 @@ impl EventMetadata {
     pub fn set_splunk_hec_token(&mut self, secret: Arc<str>) {
         self.secrets.insert(SPLUNK_HEC_TOKEN, secret);
     }
+
+    /// Returns the priority of the event, held under `vector.priority` in the metadata value.
+    ///
+    /// Events without a valid priority are of the normal priority.
+    pub fn priority(&self) -> EventPriority {
+        match self.value.get(path!("vector", "priority")) {
+            Some(Value::Bytes(name)) => std::str::from_utf8(name)
+                .ok()
+                .and_then(EventPriority::from_name)
+                .unwrap_or_default(),
+            _ => EventPriority::default(),
+        }
+    }
+
+    /// Set the priority of the event to passed value
+    pub fn set_priority(&mut self, priority: EventPriority) {
+        self.value_mut()
+            .insert(path!("vector", "priority"), priority.as_str());
+    }
 }
 
 impl Default for EventMetadata {
     fn default() -> Self {
         Self {
-            value: Value::Object(BTreeMap::new()),
+            value: default_metadata_value(),
             secrets: Secrets::new(),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
@@ -249,4 +273,29 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn get_set_priority() {
+        let mut metadata = EventMetadata::default();
+        assert_eq!(metadata.priority(), EventPriority::Normal);
+
+        metadata.set_priority(EventPriority::Background);
+        assert_eq!(metadata.priority(), EventPriority::Background);
+
+        metadata
+            .value_mut()
+            .insert(path!("vector", "priority"), "urgent");
+        assert_eq!(metadata.priority(), EventPriority::Normal);
+    }
+
+    #[test]
+    fn clones_share_value_until_mutated() {
+        let mut metadata = EventMetadata::default_with_value(Value::from(1));
+        let clone = metadata.clone();
+        assert!(Arc::ptr_eq(&metadata.value, &clone.value));
+
+        *metadata.value_mut() = Value::from(2);
+        assert_eq!(metadata.value(), &Value::from(2));
+        assert_eq!(clone.value(), &Value::from(1));
+    }
 }
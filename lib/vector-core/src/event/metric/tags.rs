@@ -426,11 +426,14 @@ impl<'de> Deserialize<'de> for TagValueSet {
             String(String),
             // This is the new form of tag values
             Array(Vec<TagValue>),
+            // A single bare tag is serialized as `null`
+            Bare,
         }
 
         Variants::deserialize(de).map(|v| match v {
             Variants::String(s) => Self::from([s]),
             Variants::Array(a) => Self::from(a),
+            Variants::Bare => Self::from([TagValue::Bare]),
         })
     }
 }
@@ -659,6 +662,13 @@ mod tests {
             }
         }
 
+        #[test]
+        fn serde_round_trip(values: TagValueSet) {
+            let json = serde_json::to_string(&values).unwrap();
+            let parsed: TagValueSet = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, values);
+        }
+
         #[test]
         fn eq_implies_hash_matches_proptest(values1: TagValueSet, values2: TagValueSet) {
             fn hash<T: Hash>(values: &T) -> u64 {
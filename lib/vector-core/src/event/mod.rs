@@ -18,10 +18,10 @@ pub use metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind};
 pub use r#ref::{EventMutRef, EventRef};
 use serde::{Deserialize, Serialize};
 pub use trace::TraceEvent;
-use vector_buffers::EventCount;
+use vector_buffers::{EventCount, EventPriority, Prioritized};
 use vector_common::{finalization, EventDataEq};
 #[cfg(feature = "vrl")]
-pub use vrl_target::{TargetEvents, VrlTarget};
+pub use vrl_target::{ReadOnlyVrlTarget, TargetEvents, VrlTarget};
 
 pub mod array;
 pub mod discriminant;
@@ -80,6 +80,12 @@ impl EventCount for Event {
     }
 }
 
+impl Prioritized for Event {
+    fn priority(&self) -> EventPriority {
+        self.metadata().priority()
+    }
+}
+
 impl Finalizable for Event {
     fn take_finalizers(&mut self) -> EventFinalizers {
         match self {
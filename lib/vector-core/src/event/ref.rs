@@ -3,6 +3,7 @@
 use vector_common::EventDataEq;
 
 use super::{Event, EventMetadata, LogEvent, Metric, TraceEvent};
+use crate::EstimatedJsonEncodedSizeOf;
 
 /// A wrapper for references to inner event types, where reconstituting
 /// a full `Event` from a `LogEvent` or `Metric` might be inconvenient.
@@ -94,6 +95,16 @@ impl<'a> From<&'a TraceEvent> for EventRef<'a> {
     }
 }
 
+impl<'a> EstimatedJsonEncodedSizeOf for EventRef<'a> {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        match self {
+            Self::Log(log) => log.estimated_json_encoded_size_of(),
+            Self::Metric(metric) => metric.estimated_json_encoded_size_of(),
+            Self::Trace(trace) => trace.estimated_json_encoded_size_of(),
+        }
+    }
+}
+
 impl<'a> EventDataEq<Event> for EventRef<'a> {
     fn event_data_eq(&self, that: &Event) -> bool {
         match (self, that) {
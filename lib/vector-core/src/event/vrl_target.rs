@@ -394,6 +394,64 @@ impl SecretTarget for VrlTarget {
     }
 }
 
+/// An adapter to turn references to log and trace `Event`s into read-only `vrl_lib::Target`s.
+///
+/// Programs compiled as read-only, such as conditions, run against the event itself rather than
+/// against a copy of it, so that the events shared between components once fanned out are passed
+/// through filters and routes without being copied.
+#[derive(Debug)]
+pub struct ReadOnlyVrlTarget<'a>(&'a LogEvent);
+
+impl<'a> ReadOnlyVrlTarget<'a> {
+    /// Returns the target of the event, unless it is a metric, whose fields can only be read
+    /// through a [`VrlTarget`].
+    pub fn new(event: &'a Event) -> Option<Self> {
+        match event {
+            Event::Log(log) => Some(Self(log)),
+            Event::Trace(trace) => Some(Self(trace.as_ref())),
+            Event::Metric(_) => None,
+        }
+    }
+}
+
+const READ_ONLY_TARGET: &str = "the event is read-only";
+
+impl vrl_lib::Target for ReadOnlyVrlTarget<'_> {
+    fn target_insert(&mut self, _: &OwnedTargetPath, _: ::value::Value) -> Result<(), String> {
+        Err(READ_ONLY_TARGET.to_owned())
+    }
+
+    fn target_get(&self, target_path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        Ok(match target_path.prefix {
+            PathPrefix::Event => self.0.value().get(&target_path.path),
+            PathPrefix::Metadata => self.0.metadata().value().get(&target_path.path),
+        })
+    }
+
+    fn target_get_mut(&mut self, _: &OwnedTargetPath) -> Result<Option<&mut Value>, String> {
+        Err(READ_ONLY_TARGET.to_owned())
+    }
+
+    fn target_remove(
+        &mut self,
+        _: &OwnedTargetPath,
+        _: bool,
+    ) -> Result<Option<::value::Value>, String> {
+        Err(READ_ONLY_TARGET.to_owned())
+    }
+}
+
+impl SecretTarget for ReadOnlyVrlTarget<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.0.metadata().secrets().get_secret(key)
+    }
+
+    // Read-only programs can't change the secrets of the event.
+    fn insert_secret(&mut self, _: &str, _: &str) {}
+
+    fn remove_secret(&mut self, _: &str) {}
+}
+
 /// Retrieves a value from a the provided metric using the path.
 /// Currently the root path and the following paths are supported:
 /// - name
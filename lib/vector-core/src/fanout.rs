@@ -1,4 +1,10 @@
-use std::{collections::HashMap, fmt, task::Poll};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    task::Poll,
+    time::Instant,
+};
 
 use futures::{Stream, StreamExt};
 use futures_util::{pending, poll};
@@ -42,9 +48,40 @@ impl fmt::Debug for ControlMessage {
 // so that high-lever components don't need to do the raw channel sends, etc.
 pub type ControlChannel = mpsc::UnboundedSender<ControlMessage>;
 
+/// A send of a fanout which is blocked on some of its sinks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockedSend {
+    /// When the send started.
+    pub since: Instant,
+
+    /// The sinks the send is still waiting on.
+    pub waiting_on: Vec<ComponentKey>,
+}
+
+/// The status of the sends of a fanout, shared with the topology to diagnose backpressure.
+#[derive(Clone, Debug, Default)]
+pub struct SendStatus(Arc<Mutex<Option<BlockedSend>>>);
+
+impl SendStatus {
+    /// Returns the send which is currently blocked, if any.
+    ///
+    /// # Panics
+    ///
+    /// Function will panic if the status lock is poisoned.
+    #[must_use]
+    pub fn blocked(&self) -> Option<BlockedSend> {
+        self.0.lock().expect("send status lock poisoned").clone()
+    }
+
+    fn set(&self, blocked: Option<BlockedSend>) {
+        *self.0.lock().expect("send status lock poisoned") = blocked;
+    }
+}
+
 pub struct Fanout {
     senders: IndexMap<ComponentKey, Option<Sender>>,
     control_channel: mpsc::UnboundedReceiver<ControlMessage>,
+    status: SendStatus,
 }
 
 impl Fanout {
@@ -54,11 +91,17 @@ impl Fanout {
         let fanout = Self {
             senders: Default::default(),
             control_channel: control_rx,
+            status: SendStatus::default(),
         };
 
         (fanout, control_tx)
     }
 
+    /// Returns the status of the sends of this fanout.
+    pub fn send_status(&self) -> SendStatus {
+        self.status.clone()
+    }
+
     /// Add a new sink as an output.
     ///
     /// # Panics
@@ -218,7 +261,7 @@ impl Fanout {
 
         // Create our send group which arms all senders to send the given events, and handles
         // adding/removing/replacing senders while the send is in-flight.
-        let mut send_group = SendGroup::new(&mut self.senders, events);
+        let mut send_group = SendGroup::new(&mut self.senders, events, &self.status);
 
         loop {
             tokio::select! {
@@ -269,10 +312,18 @@ impl Fanout {
 struct SendGroup<'a> {
     senders: &'a mut IndexMap<ComponentKey, Option<Sender>>,
     sends: HashMap<ComponentKey, ReusableBoxFuture<'static, crate::Result<Sender>>>,
+    status: &'a SendStatus,
+    started: Instant,
+    // The number of sends the status was last updated with, if it was.
+    reported: Option<usize>,
 }
 
 impl<'a> SendGroup<'a> {
-    fn new(senders: &'a mut IndexMap<ComponentKey, Option<Sender>>, events: EventArray) -> Self {
+    fn new(
+        senders: &'a mut IndexMap<ComponentKey, Option<Sender>>,
+        events: EventArray,
+        status: &'a SendStatus,
+    ) -> Self {
         // If we don't have a valid `Sender` for all sinks, then something went wrong in our logic
         // to ensure we were starting with all valid/idle senders prior to initiating the send.
         debug_assert!(senders.values().all(Option::is_some));
@@ -288,7 +339,9 @@ impl<'a> SendGroup<'a> {
                 .take()
                 .expect("sender must be present to initialize SendGroup");
 
-            // First, arm each sender with the item to actually send.
+            // First, arm each sender with the item to actually send. Log and trace events share
+            // their data and metadata with their clones until either is mutated, so the components
+            // which don't mutate them, such as filters and routes, pass them on without copying.
             if i == last_sender_idx {
                 sender.input = events.take();
             } else {
@@ -304,7 +357,28 @@ impl<'a> SendGroup<'a> {
             sends.insert(key.clone(), ReusableBoxFuture::new(send));
         }
 
-        Self { senders, sends }
+        Self {
+            senders,
+            sends,
+            status,
+            started: Instant::now(),
+            reported: None,
+        }
+    }
+
+    /// Records the sinks the send is still waiting on, if they changed since the last update.
+    fn report_blocked(&mut self) {
+        if self.reported == Some(self.sends.len()) {
+            return;
+        }
+
+        let mut waiting_on = self.sends.keys().cloned().collect::<Vec<_>>();
+        waiting_on.sort();
+        self.status.set(Some(BlockedSend {
+            since: self.started,
+            waiting_on,
+        }));
+        self.reported = Some(self.sends.len());
     }
 
     fn try_detach_send(&mut self, id: &ComponentKey) -> bool {
@@ -412,6 +486,8 @@ impl<'a> SendGroup<'a> {
             }
 
             if !self.sends.is_empty() {
+                self.report_blocked();
+
                 // We manually yield ourselves because we've polled all of the sends at this point,
                 // so if any are left, then we're scheduled for a wake-up... this is a really poor
                 // approximation of what `FuturesUnordered` is doing.
@@ -423,6 +499,14 @@ impl<'a> SendGroup<'a> {
     }
 }
 
+impl<'a> Drop for SendGroup<'a> {
+    fn drop(&mut self) {
+        if self.reported.is_some() {
+            self.status.set(None);
+        }
+    }
+}
+
 struct Sender {
     inner: BufferSender<EventArray>,
     input: Option<EventArray>,
@@ -457,11 +541,11 @@ mod tests {
             builder::TopologyBuilder,
             channel::{BufferReceiver, BufferSender},
         },
-        WhenFull,
+        EventPriority, WhenFull,
     };
 
     use super::{ControlMessage, Fanout};
-    use crate::event::{Event, EventArray, LogEvent};
+    use crate::event::{Event, EventArray, LogEvent, TraceEvent};
     use crate::test_util::{collect_ready, collect_ready_events};
     use crate::{config::ComponentKey, event::EventContainer};
 
@@ -609,6 +693,40 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn fanout_shares_events_between_sinks() {
+        fn data(events: &EventArray) -> Vec<(*const Value, *const Value)> {
+            let logs = match events {
+                EventArray::Logs(logs) => logs.iter().collect::<Vec<_>>(),
+                EventArray::Traces(traces) => {
+                    traces.iter().map(AsRef::<LogEvent>::as_ref).collect()
+                }
+                EventArray::Metrics(_) => panic!("metrics are copied"),
+            };
+            logs.into_iter()
+                .map(|log| {
+                    let value: *const Value = log.value();
+                    let metadata: *const Value = log.metadata().value();
+                    (value, metadata)
+                })
+                .collect()
+        }
+
+        let traces = _make_events(2).map(TraceEvent::from).collect::<Vec<_>>();
+        for events in [make_event_array(2), EventArray::Traces(traces)] {
+            let (mut fanout, _, receivers) = fanout_from_senders(&[2, 2, 2]).await;
+            let expected = data(&events);
+
+            fanout.send(events).await.expect("should not fail");
+
+            for receiver in receivers {
+                let received = collect_ready(receiver.into_stream());
+                assert_eq!(received.len(), 1);
+                assert_eq!(data(&received[0]), expected);
+            }
+        }
+    }
+
     #[tokio::test]
     async fn fanout_notready() {
         let (mut fanout, _, mut receivers) = fanout_from_senders(&[2, 1, 2]).await;
@@ -638,6 +756,32 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn fanout_reports_blocked_send() {
+        let (mut fanout, _, mut receivers) = fanout_from_senders(&[2, 1, 2]).await;
+        let status = fanout.send_status();
+        let events = make_events(2);
+
+        fanout
+            .send(events[0].clone().into())
+            .await
+            .expect("should not fail");
+        assert_eq!(status.blocked(), None);
+
+        // The second send is blocked on the sender with no capacity left:
+        let mut second_send = spawn(fanout.send(events[1].clone().into()));
+        assert_pending!(second_send.poll());
+        let blocked = status.blocked().expect("send should be blocked");
+        assert_eq!(blocked.waiting_on, vec![ComponentKey::from("1")]);
+
+        for receiver in &mut receivers {
+            assert_eq!(Some(events[0].clone().into()), receiver.next().await);
+        }
+        assert_ready!(second_send.poll()).expect("should not fail");
+        drop(second_send);
+        assert_eq!(status.blocked(), None);
+    }
+
     #[tokio::test]
     async fn fanout_grow() {
         let (mut fanout, _, mut receivers) = fanout_from_senders(&[4, 4]).await;
@@ -888,6 +1032,46 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn drop_newest_buffers_shed_lower_priorities_first() {
+        let (sender, receiver) = TopologyBuilder::standalone_memory(
+            NonZeroUsize::new(4).expect("capacity must be nonzero"),
+            WhenFull::DropNewest,
+        )
+        .await;
+        let (mut fanout, _control) = Fanout::new();
+        fanout.add(ComponentKey::from("0"), sender);
+
+        let events = [
+            EventPriority::Normal,
+            EventPriority::Normal,
+            EventPriority::Background,
+            EventPriority::Low,
+            EventPriority::Low,
+            EventPriority::Normal,
+        ]
+        .into_iter()
+        .map(|priority| {
+            let mut log = LogEvent::from(priority.as_str());
+            log.metadata_mut().set_priority(priority);
+            EventArray::from(log)
+        })
+        .collect::<Vec<_>>();
+        for events in events.clone() {
+            fanout.send(events).await.expect("should not fail");
+        }
+
+        // The background event is shed once the buffer is half full, and the second low event once
+        // it is three quarters full.
+        assert_eq!(
+            collect_ready(receiver.into_stream()),
+            [&events[0], &events[1], &events[3], &events[5]]
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+    }
+
     fn _make_events(count: usize) -> impl Iterator<Item = LogEvent> {
         (0..count).map(|i| LogEvent::from(format!("line {i}")))
     }
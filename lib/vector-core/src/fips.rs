@@ -0,0 +1,158 @@
+//! FIPS 140 compliance mode.
+//!
+//! When enabled, OpenSSL is switched to its FIPS mode, which requires Vector to be built with the
+//! `fips` feature, linking it against BoringSSL built in FIPS mode. TLS connections are then
+//! restricted to the FIPS-approved protocol versions and cipher suites, and the VRL functions
+//! implementing cryptographic algorithms outside of the validated module are unavailable, so that
+//! components requesting anything else fail to build.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The TLS 1.2 cipher suites approved in FIPS mode, as OpenSSL names.
+pub const TLS12_CIPHER_SUITES: &[&str] = &[
+    "ECDHE-ECDSA-AES256-GCM-SHA384",
+    "ECDHE-RSA-AES256-GCM-SHA384",
+    "ECDHE-ECDSA-AES128-GCM-SHA256",
+    "ECDHE-RSA-AES128-GCM-SHA256",
+    "DHE-RSA-AES256-GCM-SHA384",
+    "DHE-RSA-AES128-GCM-SHA256",
+    "ECDHE-ECDSA-AES256-SHA384",
+    "ECDHE-RSA-AES256-SHA384",
+    "ECDHE-ECDSA-AES128-SHA256",
+    "ECDHE-RSA-AES128-SHA256",
+];
+
+/// The TLS 1.3 cipher suites approved in FIPS mode.
+pub const TLS13_CIPHER_SUITES: &[&str] = &[
+    "TLS_AES_256_GCM_SHA384",
+    "TLS_AES_128_GCM_SHA256",
+    "TLS_AES_128_CCM_SHA256",
+];
+
+/// The VRL functions unavailable in FIPS mode, as they don't use the validated module.
+const DISALLOWED_VRL_FUNCTIONS: &[&str] = &[
+    "decrypt",
+    "encrypt",
+    "hmac",
+    "md5",
+    "random_bytes",
+    "sha1",
+    "sha2",
+    "sha3",
+];
+
+/// Enables the FIPS mode, for the whole process.
+///
+/// # Errors
+///
+/// Fails when Vector was not built with the `fips` feature, or when OpenSSL can't be switched to
+/// its FIPS mode.
+pub fn enable() -> Result<(), String> {
+    enable_openssl()?;
+    ENABLED.store(true, Ordering::Relaxed);
+    info!(message = "FIPS mode enabled.");
+    Ok(())
+}
+
+#[cfg(feature = "fips")]
+fn enable_openssl() -> Result<(), String> {
+    // OpenSSL may already run in FIPS mode, as set by its configuration.
+    if !openssl::fips::enabled() {
+        openssl::fips::enable(true)
+            .map_err(|error| format!("Could not enable the FIPS mode of OpenSSL: {}", error))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "fips"))]
+fn enable_openssl() -> Result<(), String> {
+    Err("Vector was not built with the `fips` feature.".to_owned())
+}
+
+/// Returns whether the FIPS mode is enabled.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Returns whether the cipher suite is approved in FIPS mode.
+pub fn is_approved_cipher_suite(suite: &str) -> bool {
+    TLS12_CIPHER_SUITES.contains(&suite) || TLS13_CIPHER_SUITES.contains(&suite)
+}
+
+/// Removes the VRL functions unavailable in FIPS mode, when it is enabled.
+pub fn retain_vrl_functions(functions: &mut Vec<Box<dyn vrl_lib::Function>>) {
+    if is_enabled() {
+        retain_allowed_vrl_functions(functions);
+    }
+}
+
+fn retain_allowed_vrl_functions(functions: &mut Vec<Box<dyn vrl_lib::Function>>) {
+    functions.retain(|function| !DISALLOWED_VRL_FUNCTIONS.contains(&function.identifier()));
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl_lib::{
+        prelude::{ArgumentList, Compiled, Example, FunctionCompileContext},
+        state::TypeState,
+        Function,
+    };
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct NamedFunction(&'static str);
+
+    impl Function for NamedFunction {
+        fn identifier(&self) -> &'static str {
+            self.0
+        }
+
+        fn examples(&self) -> &'static [Example] {
+            &[]
+        }
+
+        fn compile(
+            &self,
+            _state: &TypeState,
+            _ctx: &mut FunctionCompileContext,
+            _arguments: ArgumentList,
+        ) -> Compiled {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn removes_disallowed_vrl_functions() {
+        let mut functions = [
+            "md5",
+            "upcase",
+            "sha1",
+            "sha2",
+            "parse_json",
+            "hmac",
+            "encrypt",
+        ]
+        .into_iter()
+        .map(|name| Box::new(NamedFunction(name)) as Box<dyn Function>)
+        .collect();
+        retain_allowed_vrl_functions(&mut functions);
+
+        let retained = functions
+            .iter()
+            .map(|function| function.identifier())
+            .collect::<Vec<_>>();
+        assert_eq!(retained, ["upcase", "parse_json"]);
+    }
+
+    #[test]
+    fn approves_cipher_suites() {
+        assert!(is_approved_cipher_suite("ECDHE-RSA-AES128-GCM-SHA256"));
+        assert!(is_approved_cipher_suite("TLS_AES_256_GCM_SHA384"));
+        assert!(!is_approved_cipher_suite("ECDHE-RSA-CHACHA20-POLY1305"));
+        assert!(!is_approved_cipher_suite("TLS_CHACHA20_POLY1305_SHA256"));
+        assert!(!is_approved_cipher_suite("RC4-SHA"));
+    }
+}
@@ -248,7 +248,11 @@ macro_rules! update_counter {
 mod tests {
     use super::*;
 
-    use crate::event::MetricKind;
+    use vector_common::internal_event::{
+        CountByteSize, EventsReceived, EventsSent, InternalEventHandle, RegisterInternalEvent,
+    };
+
+    use crate::event::{EventArray, EventContainer, LogEvent, MetricKind};
 
     const IDLE_TIMEOUT: f64 = 0.5;
 
@@ -357,4 +361,54 @@ mod tests {
             value => panic!("Invalid metric value {value:?}"),
         }
     }
+
+    #[test]
+    fn records_each_event_size() {
+        let controller = init_metrics();
+
+        let events = EventArray::from(vec![
+            LogEvent::from("a"),
+            LogEvent::from("a".repeat(5000).as_str()),
+        ]);
+        let mut sizes = Vec::new();
+        let events_received = EventsReceived.register();
+        let byte_size = events.estimated_json_encoded_size_with(|size| {
+            sizes.push(size);
+            events_received.record_event_size(size);
+        });
+        events_received.emit(CountByteSize(events.len(), byte_size));
+        let events_sent = EventsSent { output: None }.register();
+        for size in &sizes {
+            events_sent.record_event_size(*size);
+        }
+        assert_eq!(byte_size, sizes.iter().sum::<usize>());
+
+        let metrics = controller.capture_metrics();
+        for name in [
+            "component_received_event_size_bytes",
+            "component_sent_event_size_bytes",
+        ] {
+            let metric = metrics
+                .iter()
+                .find(|metric| metric.name() == name)
+                .expect("Test metric is not present");
+            match metric.value() {
+                MetricValue::AggregatedHistogram {
+                    buckets,
+                    count,
+                    sum,
+                } => {
+                    assert_eq!(*count, 2);
+                    #[allow(clippy::cast_precision_loss)]
+                    let expected = byte_size as f64;
+                    assert_eq!(*sum, expected);
+                    // Each size lands in its own bucket, rather than both in the bucket of their
+                    // average.
+                    let filled = buckets.iter().filter(|bucket| bucket.count > 0).count();
+                    assert_eq!(filled, 2);
+                }
+                value => panic!("Invalid metric value {value:?}"),
+            }
+        }
+    }
 }
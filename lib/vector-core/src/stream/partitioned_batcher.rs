@@ -1,6 +1,6 @@
 use std::{
     cmp,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     hash::{BuildHasherDefault, Hash},
     mem,
     num::NonZeroUsize,
@@ -14,7 +14,10 @@ use pin_project::pin_project;
 use tokio_util::time::{delay_queue::Key, DelayQueue};
 use twox_hash::XxHash64;
 
+use vector_common::internal_event::{PartitionClosed, PartitionEvicted, PartitionOpened};
+
 use crate::{
+    internal_event::emit,
     partition::Partitioner,
     stream::batcher::{
         config::BatchConfigParts,
@@ -86,6 +89,12 @@ where
         }
     }
 
+    fn remove(&mut self, item_key: &K) {
+        if let Some(expiration_key) = self.expiration_map.remove(item_key) {
+            self.expirations.remove(&expiration_key);
+        }
+    }
+
     fn poll_expired(&mut self, cx: &mut Context) -> Poll<Option<K>> {
         match ready!(self.expirations.poll_expired(cx)) {
             // No expirations yet.
@@ -208,6 +217,62 @@ where
     }
 }
 
+/// The order in which partitions were last used.
+///
+/// Used by `PartitionedBatcher` to pick the partition to flush once it holds as many partitions, or
+/// as many bytes, as it is allowed to.
+struct Recency<K> {
+    next_stamp: u64,
+    stamps: HashMap<K, u64, BuildHasherDefault<XxHash64>>,
+    order: BTreeMap<u64, K>,
+}
+
+impl<K> Recency<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn new() -> Self {
+        Self {
+            next_stamp: 0,
+            stamps: HashMap::default(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    /// Marks the partition as the most recently used one.
+    fn touch(&mut self, key: &K) {
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+
+        if let Some(previous) = self.stamps.get_mut(key) {
+            let key = self
+                .order
+                .remove(previous)
+                .expect("stamped partition should be ordered");
+            *previous = stamp;
+            self.order.insert(stamp, key);
+        } else {
+            self.stamps.insert(key.clone(), stamp);
+            self.order.insert(stamp, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(stamp) = self.stamps.remove(key) {
+            self.order.remove(&stamp);
+        }
+    }
+
+    fn least_recent(&self) -> Option<&K> {
+        self.order.values().next()
+    }
+
+    fn clear(&mut self) {
+        self.stamps.clear();
+        self.order.clear();
+    }
+}
+
 /// Controls the behavior of the batcher in terms of batch size and flush interval.
 ///
 /// This is a temporary solution for pushing in a fixed settings structure so we don't have to worry
@@ -215,11 +280,18 @@ where
 /// `BatchConfig`/`BatchSettings`/`BatchSize` out of `vector` and move them into `vector_core`, and
 /// make it more generalized. We can't do that yet, though, until we've converted all of the sinks
 /// with their various specialized batch buffers.
+///
+/// The size, item and timeout limits apply to each batch, which is to say to each partition of a
+/// `PartitionedBatcher`.
 #[derive(Copy, Clone, Debug)]
 pub struct BatcherSettings {
     pub timeout: Duration,
     pub size_limit: usize,
     pub item_limit: usize,
+    /// The maximum number of partitions a `PartitionedBatcher` holds a batch for at once.
+    pub max_open_partitions: usize,
+    /// The maximum size of the batches of all partitions of a `PartitionedBatcher`.
+    pub max_open_bytes: usize,
 }
 
 impl BatcherSettings {
@@ -232,9 +304,32 @@ impl BatcherSettings {
             timeout,
             size_limit: size_limit.get(),
             item_limit: item_limit.get(),
+            max_open_partitions: usize::MAX,
+            max_open_bytes: usize::MAX,
         }
     }
 
+    /// Limits the partitions a `PartitionedBatcher` holds a batch for at once.
+    ///
+    /// Once either limit is reached, the batch of the least recently used partition is flushed
+    /// early. Partitions are unlimited by default.
+    #[must_use]
+    pub const fn with_partition_limits(
+        mut self,
+        max_open_partitions: Option<NonZeroUsize>,
+        max_open_bytes: Option<NonZeroUsize>,
+    ) -> Self {
+        self.max_open_partitions = match max_open_partitions {
+            Some(limit) => limit.get(),
+            None => usize::MAX,
+        };
+        self.max_open_bytes = match max_open_bytes {
+            Some(limit) => limit.get(),
+            None => usize::MAX,
+        };
+        self
+    }
+
     /// A batcher config using the `ByteSizeOf` trait to determine batch sizes.
     /// The output is a  `Vec<T>`.
     pub fn into_byte_size_config<T: ByteSizeOf>(
@@ -296,9 +391,18 @@ where
     batch_allocation_limit: usize,
     /// The maximum number of items that are allowed per-batch
     batch_item_limit: usize,
+    /// The maximum number of live batches.
+    max_open_partitions: usize,
+    /// The maximum number of bytes held by all live batches.
+    max_open_bytes: usize,
+    /// The number of bytes held by all live batches.
+    open_bytes: usize,
     /// The store of live batches. Note that the key here is an option type,
     /// on account of the interface of `Prt`.
     batches: HashMap<Prt::Key, Batch<Prt::Item>, BuildHasherDefault<XxHash64>>,
+    /// The order in which the partitions of the live batches were last used.
+    /// Only tracked when the live batches are limited.
+    recency: Option<Recency<Prt::Key>>,
     /// The store of 'closed' batches. When this is not empty it will be
     /// preferentially flushed prior to consuming any new items from the
     /// underlying stream.
@@ -320,10 +424,16 @@ where
     Prt::Item: ByteSizeOf,
 {
     pub fn new(stream: St, partitioner: Prt, settings: BatcherSettings) -> Self {
+        let limited =
+            settings.max_open_partitions != usize::MAX || settings.max_open_bytes != usize::MAX;
         Self {
             batch_allocation_limit: settings.size_limit,
             batch_item_limit: settings.item_limit,
+            max_open_partitions: settings.max_open_partitions,
+            max_open_bytes: settings.max_open_bytes,
+            open_bytes: 0,
             batches: HashMap::default(),
+            recency: limited.then(Recency::new),
             closed_batches: Vec::default(),
             timer: ExpirationQueue::new(settings.timeout),
             partitioner,
@@ -350,13 +460,58 @@ where
             batch_allocation_limit: batch_allocation_limit
                 .map_or(usize::max_value(), NonZeroUsize::get),
             batch_item_limit: batch_item_limit.get(),
+            max_open_partitions: usize::MAX,
+            max_open_bytes: usize::MAX,
+            open_bytes: 0,
             batches: HashMap::default(),
+            recency: None,
             closed_batches: Vec::default(),
             timer,
             partitioner,
             stream: stream.fuse(),
         }
     }
+
+    /// Limits the partitions this batcher holds a batch for at once.
+    ///
+    /// See [`BatcherSettings::with_partition_limits`].
+    #[must_use]
+    pub fn with_partition_limits(
+        mut self,
+        max_open_partitions: Option<NonZeroUsize>,
+        max_open_bytes: Option<NonZeroUsize>,
+    ) -> Self {
+        self.max_open_partitions = max_open_partitions.map_or(usize::MAX, NonZeroUsize::get);
+        self.max_open_bytes = max_open_bytes.map_or(usize::MAX, NonZeroUsize::get);
+        let limited = self.max_open_partitions != usize::MAX || self.max_open_bytes != usize::MAX;
+        self.recency = limited.then(Recency::new);
+        self
+    }
+}
+
+/// Closes the batch of the least recently used partition, returning whether there was one.
+fn close_least_recent<K, I, KT>(
+    batches: &mut HashMap<K, Batch<I>, BuildHasherDefault<XxHash64>>,
+    recency: &mut Recency<K>,
+    timer: &mut KT,
+    open_bytes: &mut usize,
+    closed_batches: &mut Vec<(K, Vec<I>)>,
+) -> bool
+where
+    K: Eq + Hash + Clone,
+    KT: KeyedTimer<K>,
+{
+    let key = match recency.least_recent() {
+        Some(key) => key.clone(),
+        None => return false,
+    };
+    recency.remove(&key);
+    timer.remove(&key);
+    if let Some(batch) = batches.remove(&key) {
+        *open_bytes -= batch.allocated_bytes;
+        closed_batches.push((key, batch.into_inner()));
+    }
+    true
 }
 
 impl<St, Prt, KT> Stream for PartitionedBatcher<St, Prt, KT>
@@ -389,7 +544,14 @@ where
                             .batches
                             .remove(&item_key)
                             .expect("batch should exist if it is set to expire");
+                        *this.open_bytes -= batch.allocated_bytes;
+                        if let Some(recency) = this.recency.as_mut() {
+                            recency.remove(&item_key);
+                        }
                         this.closed_batches.push((item_key, batch.into_inner()));
+                        emit(PartitionClosed {
+                            open_partitions: this.batches.len(),
+                        });
 
                         continue;
                     }
@@ -402,11 +564,16 @@ where
                     // we finish.
                     if !this.batches.is_empty() {
                         this.timer.clear();
+                        if let Some(recency) = this.recency.as_mut() {
+                            recency.clear();
+                        }
+                        *this.open_bytes = 0;
                         this.closed_batches.extend(
                             this.batches
                                 .drain()
                                 .map(|(key, batch)| (key, batch.into_inner())),
                         );
+                        emit(PartitionClosed { open_partitions: 0 });
                         continue;
                     }
                     return Poll::Ready(None);
@@ -415,6 +582,10 @@ where
                     let item_key = this.partitioner.partition(&item);
                     let item_limit: usize = *this.batch_item_limit;
                     let alloc_limit: usize = *this.batch_allocation_limit;
+                    *this.open_bytes += item.size_of();
+                    if let Some(recency) = this.recency.as_mut() {
+                        recency.touch(&item_key);
+                    }
 
                     if let Some(batch) = this.batches.get_mut(&item_key) {
                         if batch.has_space(&item) {
@@ -424,6 +595,7 @@ where
                         } else {
                             let new_batch = Batch::new(item_limit, alloc_limit).with(item);
                             let batch = mem::replace(batch, new_batch);
+                            *this.open_bytes -= batch.allocated_bytes;
 
                             // The batch for this partition key was set to
                             // expire, but now it's overflowed and must be
@@ -433,6 +605,25 @@ where
                             this.closed_batches.push((item_key, batch.into_inner()));
                         }
                     } else {
+                        // Make room for the new partition by flushing the
+                        // least recently used ones.
+                        if let Some(recency) = this.recency.as_mut() {
+                            while this.batches.len() >= *this.max_open_partitions
+                                && close_least_recent(
+                                    this.batches,
+                                    recency,
+                                    this.timer,
+                                    this.open_bytes,
+                                    this.closed_batches,
+                                )
+                            {
+                                emit(PartitionEvicted {
+                                    open_partitions: this.batches.len(),
+                                    reason: "max_open_partitions",
+                                });
+                            }
+                        }
+
                         // We have no batch yet for this partition key, so
                         // create one and create the expiration entries as well.
                         // This allows the batch to expire before filling up,
@@ -440,6 +631,28 @@ where
                         let batch = Batch::new(item_limit, alloc_limit).with(item);
                         this.batches.insert(item_key.clone(), batch);
                         this.timer.insert(item_key);
+                        emit(PartitionOpened {
+                            open_partitions: this.batches.len(),
+                        });
+                    }
+
+                    // Flush the least recently used partitions until the
+                    // live batches fit in their byte limit again.
+                    if let Some(recency) = this.recency.as_mut() {
+                        while *this.open_bytes > *this.max_open_bytes
+                            && close_least_recent(
+                                this.batches,
+                                recency,
+                                this.timer,
+                                this.open_bytes,
+                                this.closed_batches,
+                            )
+                        {
+                            emit(PartitionEvicted {
+                                open_partitions: this.batches.len(),
+                                reason: "max_open_bytes",
+                            });
+                        }
                     }
                 }
             }
@@ -457,14 +670,14 @@ mod test {
         time::Duration,
     };
 
-    use futures::{stream, Stream};
+    use futures::{stream, Stream, StreamExt};
     use pin_project::pin_project;
     use proptest::prelude::*;
     use tokio::{pin, time::advance};
 
     use crate::{
         partition::Partitioner,
-        stream::partitioned_batcher::{ExpirationQueue, PartitionedBatcher},
+        stream::partitioned_batcher::{BatcherSettings, ExpirationQueue, PartitionedBatcher},
         time::KeyedTimer,
     };
 
@@ -498,6 +711,10 @@ mod test {
             self.valid_keys.insert(item_key);
         }
 
+        fn remove(&mut self, item_key: &u8) {
+            self.valid_keys.remove(item_key);
+        }
+
         fn poll_expired(&mut self, _cx: &mut Context) -> Poll<Option<u8>> {
             match self.responses.pop() {
                 Some(Poll::Pending) => unreachable!(),
@@ -735,6 +952,48 @@ mod test {
         }
     }
 
+    fn limited_settings(
+        max_open_partitions: Option<usize>,
+        max_open_bytes: Option<usize>,
+    ) -> BatcherSettings {
+        BatcherSettings::new(
+            Duration::from_secs(60),
+            NonZeroUsize::new(1000).unwrap(),
+            NonZeroUsize::new(100).unwrap(),
+        )
+        .with_partition_limits(
+            max_open_partitions.and_then(NonZeroUsize::new),
+            max_open_bytes.and_then(NonZeroUsize::new),
+        )
+    }
+
+    #[tokio::test]
+    async fn max_open_partitions_flushes_least_recently_used() {
+        let partitioner = TestPartitioner {
+            key_space: NonZeroU8::new(10).unwrap(),
+        };
+        // Partition 1 is used again after partition 2 was opened, so opening partition 3 flushes
+        // partition 2.
+        let stream = stream::iter(vec![1, 2, 11, 3]).chain(stream::pending());
+        let mut batcher =
+            PartitionedBatcher::new(stream, partitioner, limited_settings(Some(2), None));
+
+        assert_eq!(batcher.next().await, Some((2, vec![2])));
+    }
+
+    #[tokio::test]
+    async fn max_open_bytes_flushes_least_recently_used() {
+        let partitioner = TestPartitioner {
+            key_space: NonZeroU8::new(10).unwrap(),
+        };
+        // Each item is 8 bytes, so the third one exceeds the limit and flushes partition 1.
+        let stream = stream::iter(vec![1, 2, 3]).chain(stream::pending());
+        let mut batcher =
+            PartitionedBatcher::new(stream, partitioner, limited_settings(None, Some(16)));
+
+        assert_eq!(batcher.next().await, Some((1, vec![1])));
+    }
+
     #[tokio::test(start_paused = true)]
     #[allow(clippy::semicolon_if_nothing_returned)] // https://github.com/rust-lang/rust-clippy/issues/7438
     async fn expiration_queue_impl_keyed_timer() {
@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     future::Future,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -27,14 +27,13 @@ use crate::tcp::{self, TcpKeepaliveConfig};
 
 impl TlsSettings {
     pub fn acceptor(&self) -> crate::tls::Result<SslAcceptor> {
-        match self.identity {
-            None => Err(TlsError::MissingRequiredIdentity),
-            Some(_) => {
-                let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls())
-                    .context(CreateAcceptorSnafu)?;
-                self.apply_context(&mut acceptor)?;
-                Ok(acceptor.build())
-            }
+        if self.has_identity() {
+            let mut acceptor =
+                SslAcceptor::mozilla_intermediate(SslMethod::tls()).context(CreateAcceptorSnafu)?;
+            self.apply_context(&mut acceptor)?;
+            Ok(acceptor.build())
+        } else {
+            Err(TlsError::MissingRequiredIdentity)
         }
     }
 }
@@ -43,29 +42,60 @@ impl MaybeTlsSettings {
     pub async fn bind(&self, addr: &SocketAddr) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
 
-        let acceptor = match self {
-            Self::Tls(tls) => Some(tls.acceptor()?),
-            Self::Raw(()) => None,
+        let (acceptor, rotated) = match self {
+            Self::Tls(tls) => {
+                tls.spiffe_ready().await?;
+                let rotated = tls.is_rotated().then(|| (tls.clone(), tls.generation()));
+                (Some(tls.acceptor()?), rotated)
+            }
+            Self::Raw(()) => (None, None),
         };
 
-        Ok(MaybeTlsListener { listener, acceptor })
+        Ok(MaybeTlsListener {
+            listener,
+            acceptor,
+            rotated,
+        })
     }
 }
 
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    /// The settings with rotated certificates, and the generation of the certificates of the
+    /// acceptor.
+    rotated: Option<(TlsSettings, u64)>,
 }
 
 impl MaybeTlsListener {
     pub async fn accept(&mut self) -> crate::tls::Result<MaybeTlsIncomingStream<TcpStream>> {
-        self.listener
+        let (stream, peer_addr) = self
+            .listener
             .accept()
             .await
-            .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
-            })
-            .context(IncomingListenerSnafu)
+            .context(IncomingListenerSnafu)?;
+        self.rotate_acceptor();
+        Ok(MaybeTlsIncomingStream::new(
+            stream,
+            peer_addr,
+            self.acceptor.clone(),
+        ))
+    }
+
+    /// Rebuilds the acceptor when the certificates have been rotated.
+    fn rotate_acceptor(&mut self) {
+        let Some((tls, generation)) = &mut self.rotated else {
+            return;
+        };
+        let current = tls.generation();
+        if current == *generation {
+            return;
+        }
+        *generation = current;
+        match tls.acceptor() {
+            Ok(acceptor) => self.acceptor = Some(acceptor),
+            Err(error) => warn!(message = "Failed to use rotated TLS certificates.", %error),
+        }
     }
 
     async fn into_accept(
@@ -134,6 +164,7 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            rotated: None,
         }
     }
 }
@@ -238,11 +269,8 @@ impl MaybeTlsIncomingStream<TcpStream> {
             )
         })?;
 
-        if let Some(time_secs) = keepalive.time_secs {
-            let config =
-                socket2::TcpKeepalive::new().with_time(std::time::Duration::from_secs(time_secs));
-
-            tcp::set_keepalive(stream, &config)?;
+        if let Some(params) = keepalive.params() {
+            tcp::set_keepalive(stream, &params)?;
         }
 
         Ok(())
@@ -343,6 +371,7 @@ pub struct CertificateMetadata {
     pub organization_name: Option<String>,
     pub organizational_unit_name: Option<String>,
     pub common_name: Option<String>,
+    pub subject_alt_names: Vec<String>,
 }
 
 impl CertificateMetadata {
@@ -387,10 +416,37 @@ impl From<X509> for CertificateMetadata {
             organization_name: subject_metadata.get("organizationName").cloned(),
             organizational_unit_name: subject_metadata.get("organizationalUnitName").cloned(),
             common_name: subject_metadata.get("commonName").cloned(),
+            subject_alt_names: cert
+                .subject_alt_names()
+                .map(|names| {
+                    names
+                        .iter()
+                        .filter_map(|name| {
+                            name.dnsname()
+                                .or_else(|| name.email())
+                                .or_else(|| name.uri())
+                                .map(ToString::to_string)
+                                .or_else(|| name.ipaddress().and_then(format_ip_address))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
         }
     }
 }
 
+fn format_ip_address(octets: &[u8]) -> Option<String> {
+    match octets.len() {
+        4 => <[u8; 4]>::try_from(octets)
+            .ok()
+            .map(|octets| IpAddr::from(octets).to_string()),
+        16 => <[u8; 16]>::try_from(octets)
+            .ok()
+            .map(|octets| IpAddr::from(octets).to_string()),
+        _ => None,
+    }
+}
+
 #[derive(Clone)]
 pub struct MaybeTlsConnectInfo {
     pub remote_addr: SocketAddr,
@@ -1,6 +1,6 @@
 #![allow(clippy::missing_errors_doc)]
 
-use std::{fmt::Debug, net::SocketAddr, path::PathBuf, time::Duration};
+use std::{fmt::Debug, net::SocketAddr, path::PathBuf};
 
 use openssl::{
     error::ErrorStack,
@@ -16,15 +16,19 @@ use crate::tcp::{self, TcpKeepaliveConfig};
 mod incoming;
 mod maybe_tls;
 mod outgoing;
+mod reload;
 mod settings;
+mod spiffe;
+mod verify;
 
 pub use incoming::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener};
 pub use maybe_tls::MaybeTls;
 pub use settings::{
-    MaybeTlsSettings, TlsConfig, TlsEnableableConfig, TlsSettings, TlsSourceConfig,
+    MaybeTlsSettings, TlsConfig, TlsEnableableConfig, TlsSettings, TlsSourceConfig, TlsVersion,
     PEM_START_MARKER, TEST_PEM_CA_PATH, TEST_PEM_CLIENT_CRT_PATH, TEST_PEM_CLIENT_KEY_PATH,
     TEST_PEM_CRT_PATH, TEST_PEM_INTERMEDIATE_CA_PATH, TEST_PEM_KEY_PATH,
 };
+pub use spiffe::SpiffeConfig;
 
 pub type Result<T> = std::result::Result<T, TlsError>;
 
@@ -130,6 +134,51 @@ pub enum TlsError {
     NewCaStack { source: ErrorStack },
     #[snafu(display("Could not push intermediate certificate onto stack"))]
     CaStackPush { source: ErrorStack },
+    #[snafu(display(
+        "Could not load certificate revocation list {:?}: {}",
+        filename,
+        source
+    ))]
+    CrlLoad {
+        filename: PathBuf,
+        source: ErrorStack,
+    },
+    #[snafu(display(
+        "Invalid pinned public key {:?}, expected a base64 encoded SHA-256 hash",
+        pin
+    ))]
+    InvalidPinnedPublicKey { pin: String },
+    #[snafu(display("Error setting up OCSP stapling verification: {}", source))]
+    SetStatusCallback { source: ErrorStack },
+    #[snafu(display("Could not set minimum TLS version: {}", source))]
+    SetTlsVersion { source: ErrorStack },
+    #[snafu(display("Could not set cipher suites: {}", source))]
+    SetCipherSuites { source: ErrorStack },
+    #[snafu(display("The {} is not allowed in FIPS mode", what))]
+    FipsDisallowed { what: String },
+    #[snafu(display("The {} is not supported by FIPS builds of Vector", what))]
+    FipsUnsupported { what: String },
+    #[snafu(display(
+        "SPIFFE Workload API address must be configured or set in SPIFFE_ENDPOINT_SOCKET"
+    ))]
+    SpiffeMissingEndpointSocket,
+    #[snafu(display("SPIFFE workload identity can't be used with crt_file or key_file"))]
+    SpiffeWithIdentityFile,
+    #[snafu(display(
+        "Invalid SPIFFE Workload API address {:?}, expected unix:///path/to/socket",
+        address
+    ))]
+    SpiffeInvalidEndpointSocket { address: String },
+    #[snafu(display("SPIFFE workload identities require a Tokio runtime"))]
+    SpiffeNoRuntime,
+    #[snafu(display("No SVID received from the SPIFFE Workload API yet"))]
+    SpiffeSvidUnavailable,
+    #[snafu(display("Invalid DER certificates in SVID"))]
+    SpiffeInvalidCertificates,
+    #[snafu(display("Could not parse certificate in SVID: {}", source))]
+    SpiffeCertificateParse { source: ErrorStack },
+    #[snafu(display("Could not parse private key in SVID: {}", source))]
+    SpiffePrivateKeyParse { source: ErrorStack },
 }
 
 impl MaybeTlsStream<TcpStream> {
@@ -146,15 +195,22 @@ impl MaybeTlsStream<TcpStream> {
             Self::Tls(tls) => tls.get_ref(),
         };
 
-        if let Some(time_secs) = keepalive.time_secs {
-            let config = socket2::TcpKeepalive::new().with_time(Duration::from_secs(time_secs));
-
-            tcp::set_keepalive(stream, &config)?;
+        if let Some(params) = keepalive.params() {
+            tcp::set_keepalive(stream, &params)?;
         }
 
         Ok(())
     }
 
+    pub fn set_nodelay(&mut self, nodelay: bool) -> std::io::Result<()> {
+        let stream = match self {
+            Self::Raw(raw) => raw,
+            Self::Tls(tls) => tls.get_ref(),
+        };
+
+        stream.set_nodelay(nodelay)
+    }
+
     pub fn set_send_buffer_bytes(&mut self, bytes: usize) -> std::io::Result<()> {
         let stream = match self {
             Self::Raw(raw) => raw,
@@ -178,18 +234,19 @@ pub fn tls_connector_builder(settings: &MaybeTlsSettings) -> Result<SslConnector
     let mut builder = SslConnector::builder(SslMethod::tls()).context(TlsBuildConnectorSnafu)?;
     if let Some(settings) = settings.tls() {
         settings.apply_context(&mut builder)?;
+        settings.apply_connector_context(&mut builder)?;
     }
     Ok(builder)
 }
 
 fn tls_connector(settings: &MaybeTlsSettings) -> Result<ConnectConfiguration> {
-    let verify_hostname = settings
-        .tls()
-        .map_or(true, |settings| settings.verify_hostname);
-    let configure = tls_connector_builder(settings)?
+    let mut configure = tls_connector_builder(settings)?
         .build()
         .configure()
-        .context(TlsBuildConnectorSnafu)?
-        .verify_hostname(verify_hostname);
+        .context(TlsBuildConnectorSnafu)?;
+    match settings.tls() {
+        Some(settings) => settings.apply_connect_configuration(&mut configure),
+        None => configure.set_verify_hostname(true),
+    }
     Ok(configure)
 }
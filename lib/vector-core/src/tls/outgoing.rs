@@ -15,7 +15,19 @@ impl MaybeTlsSettings {
         addr: &SocketAddr,
     ) -> crate::tls::Result<MaybeTlsStream<TcpStream>> {
         let stream = TcpStream::connect(addr).await.context(ConnectSnafu)?;
+        self.connect_stream(host, stream).await
+    }
 
+    /// Negotiates TLS, if enabled, over a stream already connected to the host.
+    ///
+    /// # Errors
+    ///
+    /// Fails when TLS can't be negotiated.
+    pub async fn connect_stream(
+        &self,
+        host: &str,
+        stream: TcpStream,
+    ) -> crate::tls::Result<MaybeTlsStream<TcpStream>> {
         match self {
             MaybeTlsSettings::Raw(()) => Ok(MaybeTlsStream::Raw(stream)),
             MaybeTlsSettings::Tls(_) => {
@@ -0,0 +1,182 @@
+//! Hot reloading of the TLS certificate and key files.
+//!
+//! The modification times of the files are checked when TLS contexts are built, that is when
+//! listeners accept connections and when connectors open connections, and the files are reloaded
+//! when they changed. Established connections are left untouched.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Mutex, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+use openssl::x509::X509;
+
+use super::{
+    settings::{IdentityStore, TlsConfig},
+    PEM_START_MARKER,
+};
+
+/// The minimum time between two checks of the modification times of the files.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The certificate and key files of TLS settings, reloaded when they change.
+pub(super) struct TlsFiles {
+    options: TlsConfig,
+    watched: Mutex<WatchedFiles>,
+    reloaded: RwLock<Option<ReloadedFiles>>,
+}
+
+struct WatchedFiles {
+    checked: Instant,
+    modified: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+/// The files loaded since the settings were built.
+#[derive(Clone)]
+pub(super) struct ReloadedFiles {
+    pub(super) authorities: Vec<X509>,
+    pub(super) identity: Option<IdentityStore>,
+    /// Incremented each time the files are reloaded.
+    pub(super) generation: u64,
+}
+
+impl TlsFiles {
+    /// Watches the files of the given options, if any of them isn't inline.
+    pub(super) fn watch(options: &TlsConfig) -> Option<Self> {
+        // The revocation lists are loaded when building TLS contexts, so they only need to be watched
+        // for the contexts to be rebuilt.
+        let files = [
+            &options.ca_file,
+            &options.crt_file,
+            &options.key_file,
+            &options.crl_file,
+        ];
+        let modified = files
+            .into_iter()
+            .flatten()
+            .filter(|path| !is_inline(path))
+            .map(|path| (path.clone(), modified(path)))
+            .collect::<Vec<_>>();
+        (!modified.is_empty()).then(|| Self {
+            options: TlsConfig {
+                ca_file: options.ca_file.clone(),
+                crt_file: options.crt_file.clone(),
+                key_file: options.key_file.clone(),
+                key_pass: options.key_pass.clone(),
+                ..TlsConfig::default()
+            },
+            watched: Mutex::new(WatchedFiles {
+                checked: Instant::now(),
+                modified,
+            }),
+            reloaded: RwLock::new(None),
+        })
+    }
+
+    /// Returns the files reloaded since the settings were built, after reloading them again if
+    /// they changed.
+    pub(super) fn current(&self) -> Option<ReloadedFiles> {
+        self.reload_if_changed();
+        self.reloaded.read().expect("poisoned lock").clone()
+    }
+
+    fn reload_if_changed(&self) {
+        let mut watched = self.watched.lock().expect("poisoned lock");
+        if watched.checked.elapsed() < CHECK_INTERVAL {
+            return;
+        }
+        watched.checked = Instant::now();
+
+        let mut changed = false;
+        for (path, last_modified) in &mut watched.modified {
+            let current = modified(path);
+            if current != *last_modified {
+                *last_modified = current;
+                changed = true;
+            }
+        }
+        if !changed {
+            return;
+        }
+
+        // A certificate and its key may not be replaced at once, in which case they don't match
+        // until both files are replaced, and the files are reloaded again.
+        let loaded = self
+            .options
+            .load_authorities()
+            .and_then(|authorities| Ok((authorities, self.options.load_identity()?)));
+        match loaded {
+            Ok((authorities, identity)) => {
+                let mut reloaded = self.reloaded.write().expect("poisoned lock");
+                let generation = reloaded.as_ref().map_or(0, |files| files.generation) + 1;
+                *reloaded = Some(ReloadedFiles {
+                    authorities,
+                    identity,
+                    generation,
+                });
+                info!(message = "Reloaded TLS certificates.");
+            }
+            Err(error) => warn!(
+                message = "Failed to reload TLS certificates, keeping the previous ones.",
+                %error,
+            ),
+        }
+    }
+}
+
+fn is_inline(path: &Path) -> bool {
+    path.to_str()
+        .map_or(false, |path| path.contains(PEM_START_MARKER))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{TEST_PEM_CA_PATH, TEST_PEM_CRT_PATH, TEST_PEM_KEY_PATH};
+
+    #[test]
+    fn reloads_changed_files() {
+        let dir = std::env::temp_dir().join(format!("vector-tls-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ca_file = dir.join("ca.pem");
+        std::fs::copy(TEST_PEM_CA_PATH, &ca_file).unwrap();
+
+        let options = TlsConfig {
+            ca_file: Some(ca_file.clone()),
+            crt_file: Some(TEST_PEM_CRT_PATH.into()),
+            key_file: Some(TEST_PEM_KEY_PATH.into()),
+            ..TlsConfig::default()
+        };
+        let files = TlsFiles::watch(&options).unwrap();
+        assert!(files.current().is_none());
+
+        // Forget the last check and modification time, as if the file changed.
+        {
+            let mut watched = files.watched.lock().unwrap();
+            watched.checked -= CHECK_INTERVAL;
+            watched.modified[0].1 = None;
+        }
+        let reloaded = files.current().unwrap();
+        assert_eq!(reloaded.generation, 1);
+        assert_eq!(reloaded.authorities.len(), 1);
+        assert!(reloaded.identity.is_some());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn ignores_inline_files() {
+        let options = TlsConfig {
+            ca_file: Some(format!("{}CERTIFICATE-----", PEM_START_MARKER).into()),
+            ..TlsConfig::default()
+        };
+        assert!(TlsFiles::watch(&options).is_none());
+    }
+}
@@ -3,13 +3,14 @@ use std::{
     fs::File,
     io::Read,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
 use lookup::lookup_v2::OptionalValuePath;
 use openssl::{
     pkcs12::{ParsedPkcs12_2, Pkcs12},
     pkey::{PKey, Private},
-    ssl::{ConnectConfiguration, SslContextBuilder, SslVerifyMode},
+    ssl::{ConnectConfiguration, SslContext, SslContextBuilder, SslVerifyMode, StatusType},
     stack::Stack,
     x509::{store::X509StoreBuilder, X509},
 };
@@ -17,12 +18,18 @@ use snafu::ResultExt;
 use vector_config::configurable_component;
 
 use super::{
+    reload::TlsFiles,
+    spiffe::{SpiffeConfig, Svid, SvidSource},
+    verify::{
+        add_crl_file, parse_pinned_public_key, public_key_hash, verify_ocsp_staple, PublicKeyHash,
+    },
     AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
     EncodeAlpnProtocolsSnafu, FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu,
     NewStoreBuilderSnafu, ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result,
-    SetAlpnProtocolsSnafu, SetCertificateSnafu, SetPrivateKeySnafu, SetVerifyCertSnafu, TlsError,
-    TlsIdentitySnafu, X509ParseSnafu,
+    SetAlpnProtocolsSnafu, SetCertificateSnafu, SetCipherSuitesSnafu, SetPrivateKeySnafu,
+    SetStatusCallbackSnafu, TlsError, TlsIdentitySnafu, X509ParseSnafu,
 };
+use crate::fips;
 
 pub const PEM_START_MARKER: &str = "-----BEGIN ";
 
@@ -107,6 +114,45 @@ pub struct TlsConfig {
     /// Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
     pub verify_hostname: Option<bool>,
 
+    /// Pins the public keys of the certificates presented by the remote host.
+    ///
+    /// When set, the certificate chain presented by the remote host must hold a certificate, either the leaf
+    /// certificate or a CA certificate, whose public key is pinned. The public keys are pinned by the base64 encoded
+    /// SHA-256 hash of their DER encoded SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+    ///
+    /// This has no effect unless `verify_certificate` is enabled.
+    #[configurable(metadata(
+        docs::examples = "sha256//YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg="
+    ))]
+    pub pinned_public_keys: Option<Vec<String>>,
+
+    /// Requires the remote host to staple a valid OCSP response to its certificate.
+    ///
+    /// The stapled OCSP response must report the certificate presented by the remote host as good, which prevents
+    /// connecting to hosts whose certificate has been revoked. This is not supported by FIPS builds of Vector.
+    ///
+    /// Only relevant for outgoing connections.
+    pub verify_ocsp_stapling: Option<bool>,
+
+    /// The minimum version of the TLS protocol.
+    ///
+    /// Defaults to the minimum version supported by OpenSSL, or to `TLSv1.2` in FIPS mode.
+    ///
+    /// Relevant for both incoming and outgoing connections.
+    pub min_tls_version: Option<TlsVersion>,
+
+    /// Sets the list of allowed cipher suites, as OpenSSL names.
+    ///
+    /// The TLS 1.3 cipher suites, whose names start with `TLS_`, and the cipher suites of the previous versions are set
+    /// separately: when none of either kind is listed, the default ones are kept for the corresponding versions. In FIPS
+    /// mode, only the FIPS-approved cipher suites can be listed, and they are the default ones. FIPS builds of Vector don't
+    /// support listing TLS 1.3 cipher suites.
+    ///
+    /// Relevant for both incoming and outgoing connections.
+    #[configurable(metadata(docs::examples = "TLS_AES_256_GCM_SHA384"))]
+    #[configurable(metadata(docs::examples = "ECDHE-RSA-AES256-GCM-SHA384"))]
+    pub cipher_suites: Option<Vec<String>>,
+
     /// Sets the list of supported ALPN protocols.
     ///
     /// Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
@@ -121,6 +167,16 @@ pub struct TlsConfig {
     #[configurable(metadata(docs::examples = "/path/to/certificate_authority.crt"))]
     pub ca_file: Option<PathBuf>,
 
+    /// Absolute path to a certificate revocation list (CRL) file.
+    ///
+    /// The revocation of all the certificates of the chain presented by the peer is checked against the lists of the
+    /// file, which must be in the DER or PEM format. Connections with peers presenting a revoked certificate, or a
+    /// certificate issued by a CA without revocation list, are refused. This is not supported by FIPS builds of Vector.
+    ///
+    /// Relevant for both incoming and outgoing connections.
+    #[configurable(metadata(docs::examples = "/path/to/revocation_list.crl"))]
+    pub crl_file: Option<PathBuf>,
+
     /// Absolute path to a certificate file used to identify this server.
     ///
     /// The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
@@ -144,6 +200,47 @@ pub struct TlsConfig {
     #[configurable(metadata(docs::examples = "${KEY_PASS_ENV_VAR}"))]
     #[configurable(metadata(docs::examples = "PassWord1"))]
     pub key_pass: Option<String>,
+
+    /// Uses the SPIFFE workload identity fetched from the SPIFFE Workload API.
+    ///
+    /// The identity certificate and the trust bundle are rotated without restarting Vector, as the
+    /// SPIFFE agent issues new SVIDs. This can't be used with `crt_file` and `key_file`.
+    ///
+    /// As the peers are identified by their SPIFFE ID rather than by their hostname,
+    /// `verify_hostname` defaults to `false` when this is set.
+    pub spiffe: Option<SpiffeConfig>,
+}
+
+/// A version of the TLS protocol.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum TlsVersion {
+    /// TLS 1.0.
+    #[serde(rename = "TLSv1.0")]
+    Tls10,
+
+    /// TLS 1.1.
+    #[serde(rename = "TLSv1.1")]
+    Tls11,
+
+    /// TLS 1.2.
+    #[serde(rename = "TLSv1.2")]
+    Tls12,
+
+    /// TLS 1.3.
+    #[serde(rename = "TLSv1.3")]
+    Tls13,
+}
+
+impl TlsVersion {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tls10 => "TLSv1.0",
+            Self::Tls11 => "TLSv1.1",
+            Self::Tls12 => "TLSv1.2",
+            Self::Tls13 => "TLSv1.3",
+        }
+    }
 }
 
 impl TlsConfig {
@@ -165,6 +262,16 @@ pub struct TlsSettings {
     authorities: Vec<X509>,
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
     alpn_protocols: Option<Vec<u8>>,
+    crl_file: Option<PathBuf>,
+    verify_ocsp_stapling: bool,
+    pinned_public_keys: Vec<PublicKeyHash>,
+    min_tls_version: Option<TlsVersion>,
+    cipher_list: Option<String>,
+    ciphersuites: Option<String>,
+    spiffe: Option<Arc<SvidSource>>,
+    files: Option<Arc<TlsFiles>>,
+    initial_generation: u64,
+    rotated_context: Arc<Mutex<Option<(u64, SslContext)>>>,
 }
 
 #[derive(Clone)]
@@ -191,23 +298,106 @@ impl TlsSettings {
             if options.verify_hostname == Some(false) {
                 warn!("The `verify_hostname` option is DISABLED, this may lead to security vulnerabilities.");
             }
+            if options.verify_certificate == Some(false) && options.pinned_public_keys.is_some() {
+                warn!("The `pinned_public_keys` option has no effect when `verify_certificate` is disabled.");
+            }
         }
 
-        Ok(Self {
+        let spiffe = match &options.spiffe {
+            Some(_) if options.crt_file.is_some() || options.key_file.is_some() => {
+                return Err(TlsError::SpiffeWithIdentityFile)
+            }
+            Some(spiffe) => Some(Arc::new(SvidSource::new(spiffe)?)),
+            None => None,
+        };
+        let (min_tls_version, cipher_list, ciphersuites) =
+            options.protocol_settings(fips::is_enabled())?;
+
+        let mut settings = Self {
             verify_certificate: options.verify_certificate.unwrap_or(!for_server),
-            verify_hostname: options.verify_hostname.unwrap_or(!for_server),
+            verify_hostname: options
+                .verify_hostname
+                .unwrap_or(!for_server && spiffe.is_none()),
             authorities: options.load_authorities()?,
             identity: options.load_identity()?,
             alpn_protocols: options.parse_alpn_protocols()?,
-        })
+            crl_file: options.check_crl_file()?,
+            verify_ocsp_stapling: options.check_ocsp_stapling()?,
+            pinned_public_keys: options
+                .pinned_public_keys
+                .iter()
+                .flatten()
+                .map(|pin| parse_pinned_public_key(pin))
+                .collect::<Result<_>>()?,
+            min_tls_version,
+            cipher_list,
+            ciphersuites,
+            spiffe,
+            files: TlsFiles::watch(options).map(Arc::new),
+            initial_generation: 0,
+            rotated_context: Arc::default(),
+        };
+        settings.initial_generation = settings.generation();
+        Ok(settings)
+    }
+
+    /// Returns whether an identity certificate is set, either from files or from SPIFFE.
+    pub(super) const fn has_identity(&self) -> bool {
+        self.identity.is_some() || self.spiffe.is_some()
+    }
+
+    /// Returns whether the certificates can be rotated, from files or from SPIFFE.
+    pub(super) const fn is_rotated(&self) -> bool {
+        self.files.is_some() || self.spiffe.is_some()
+    }
+
+    /// Returns the generation of the certificates, changing each time they are rotated.
+    pub(super) fn generation(&self) -> u64 {
+        let files = self
+            .files
+            .as_ref()
+            .and_then(|files| files.current())
+            .map_or(0, |files| files.generation);
+        let svid = self
+            .spiffe
+            .as_ref()
+            .and_then(|spiffe| spiffe.current().ok())
+            .map_or(0, |svid| svid.generation);
+        files + svid
+    }
+
+    fn authorities(&self) -> Vec<X509> {
+        match self.files.as_ref().and_then(|files| files.current()) {
+            Some(files) => files.authorities,
+            None => self.authorities.clone(),
+        }
+    }
+
+    fn identity_store(&self) -> Option<IdentityStore> {
+        match self.files.as_ref().and_then(|files| files.current()) {
+            Some(files) => files.identity,
+            None => self.identity.clone(),
+        }
+    }
+
+    /// Waits for the SPIFFE identity, if any, to be available.
+    pub(super) async fn spiffe_ready(&self) -> Result<()> {
+        match &self.spiffe {
+            Some(spiffe) => spiffe.ready().await,
+            None => Ok(()),
+        }
     }
 
     fn identity(&self) -> Option<ParsedPkcs12_2> {
+        if let Some(spiffe) = &self.spiffe {
+            return spiffe.current().ok().and_then(|svid| svid.identity().ok());
+        }
+
         // This data was test-built previously, so we can just use it
         // here and expect the results will not fail. This can all be
         // reworked when `openssl::pkcs12::ParsedPkcs12` gains the Clone
         // impl.
-        self.identity.as_ref().map(|identity| {
+        self.identity_store().map(|identity| {
             Pkcs12::from_der(&identity.0)
                 .expect("Could not build PKCS#12 archive from parsed data")
                 .parse2(&identity.1)
@@ -241,20 +431,72 @@ impl TlsSettings {
     }
 
     pub fn authorities_pem(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
-        self.authorities.iter().map(|authority| {
-            authority
-                .to_pem()
-                .expect("Invalid stored authority certificate")
-        })
+        let bundle = self
+            .spiffe
+            .as_ref()
+            .and_then(|spiffe| spiffe.current().ok())
+            .map(|svid| svid.bundle.clone())
+            .unwrap_or_default();
+        self.authorities()
+            .into_iter()
+            .chain(bundle)
+            .map(|authority| {
+                authority
+                    .to_pem()
+                    .expect("Invalid stored authority certificate")
+            })
     }
 
     pub(super) fn apply_context(&self, context: &mut SslContextBuilder) -> Result<()> {
-        context.set_verify(if self.verify_certificate {
+        let verify_mode = if self.verify_certificate {
             SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT
         } else {
             SslVerifyMode::NONE
-        });
-        if let Some(identity) = self.identity() {
+        };
+        if self.verify_certificate && (self.spiffe.is_some() || !self.pinned_public_keys.is_empty())
+        {
+            let spiffe = self.spiffe.clone();
+            let pins = self.pinned_public_keys.clone();
+            context.set_verify_callback(verify_mode, move |verified, store| {
+                // The peer certificate is verified last, once the whole chain is built.
+                if !verified || store.error_depth() != 0 {
+                    return verified;
+                }
+                let Some(cert) = store.current_cert() else {
+                    return false;
+                };
+                let allowed = spiffe
+                    .as_ref()
+                    .map_or(true, |spiffe| spiffe.is_allowed(cert));
+                let pinned = pins.is_empty()
+                    || store.chain().map_or(false, |chain| {
+                        chain
+                            .iter()
+                            .filter_map(public_key_hash)
+                            .any(|hash| pins.contains(&hash))
+                    });
+                allowed && pinned
+            });
+        } else {
+            context.set_verify(verify_mode);
+        }
+
+        let svid = self
+            .spiffe
+            .as_ref()
+            .map(|spiffe| spiffe.current())
+            .transpose()?;
+        let identity = match &svid {
+            Some(svid) => Some(svid.identity()?),
+            None => self.identity(),
+        };
+        let authorities = self
+            .authorities()
+            .into_iter()
+            .chain(svid.iter().flat_map(|svid| svid.bundle.iter().cloned()))
+            .collect::<Vec<_>>();
+
+        if let Some(identity) = identity {
             if let Some(cert) = &identity.cert {
                 context.set_certificate(cert).context(SetCertificateSnafu)?;
             }
@@ -270,7 +512,7 @@ impl TlsSettings {
                 }
             }
         }
-        if self.authorities.is_empty() {
+        if authorities.is_empty() {
             debug!("Fetching system root certs.");
 
             #[cfg(windows)]
@@ -278,34 +520,112 @@ impl TlsSettings {
 
             #[cfg(target_os = "macos")]
             load_mac_certs(context).unwrap();
+
+            if let Some(crl_file) = &self.crl_file {
+                add_crl_file(context.cert_store_mut(), crl_file)?;
+            }
         } else {
             let mut store = X509StoreBuilder::new().context(NewStoreBuilderSnafu)?;
-            for authority in &self.authorities {
-                store
-                    .add_cert(authority.clone())
-                    .context(AddCertToStoreSnafu)?;
+            for authority in authorities {
+                store.add_cert(authority).context(AddCertToStoreSnafu)?;
             }
+            if let Some(crl_file) = &self.crl_file {
+                add_crl_file(&mut store, crl_file)?;
+            }
+            set_verify_store(context, store)?;
+        }
+
+        if let Some(version) = self.min_tls_version {
+            set_min_tls_version(context, version)?;
+        }
+        if let Some(cipher_list) = &self.cipher_list {
             context
-                .set_verify_cert_store(store.build())
-                .context(SetVerifyCertSnafu)?;
+                .set_cipher_list(cipher_list)
+                .context(SetCipherSuitesSnafu)?;
+        }
+        if let Some(ciphersuites) = &self.ciphersuites {
+            set_tls13_cipher_suites(context, ciphersuites)?;
         }
 
         if let Some(alpn) = &self.alpn_protocols {
-            context
-                .set_alpn_protos(alpn.as_slice())
-                .context(SetAlpnProtocolsSnafu)?;
+            set_alpn_protocols(context, alpn)?;
         }
 
         Ok(())
     }
 
+    /// Applies the settings only relevant for outgoing connections to the context of a connector.
+    pub(super) fn apply_connector_context(&self, context: &mut SslContextBuilder) -> Result<()> {
+        if self.verify_ocsp_stapling {
+            context
+                .set_status_callback(verify_ocsp_staple)
+                .context(SetStatusCallbackSnafu)?;
+        }
+        Ok(())
+    }
+
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
+        if self.verify_ocsp_stapling {
+            if let Err(error) = connection.set_status_type(StatusType::OCSP) {
+                warn!(message = "Failed to request OCSP stapling.", %error);
+            }
+        }
+
+        // Connectors are built once, so the certificates rotated since then are swapped in.
+        if let Some(context) = self.rotated_context() {
+            if let Err(error) = connection.set_ssl_context(&context) {
+                warn!(message = "Failed to use rotated TLS certificates.", %error);
+            }
+        }
+    }
+
+    /// Returns the client context using the certificates rotated since the settings were built.
+    fn rotated_context(&self) -> Option<SslContext> {
+        if !self.is_rotated() {
+            return None;
+        }
+        let generation = self.generation();
+        if generation == self.initial_generation {
+            return None;
+        }
+
+        let mut rotated = self.rotated_context.lock().expect("poisoned lock");
+        if let Some((built, context)) = &*rotated {
+            if *built == generation {
+                return Some(context.clone());
+            }
+        }
+        match super::tls_connector_builder(&MaybeTls::Tls(self.clone())) {
+            Ok(builder) => {
+                let context = builder.build().into_context();
+                *rotated = Some((generation, context.clone()));
+                Some(context)
+            }
+            Err(error) => {
+                warn!(message = "Failed to use rotated TLS certificates.", %error);
+                None
+            }
+        }
+    }
+}
+
+impl Svid {
+    fn identity(&self) -> Result<ParsedPkcs12_2> {
+        let mut chain = Stack::new().context(NewCaStackSnafu)?;
+        for cert in &self.chain {
+            chain.push(cert.clone()).context(CaStackPushSnafu)?;
+        }
+        Ok(ParsedPkcs12_2 {
+            pkey: Some(self.key.clone()),
+            cert: Some(self.cert.clone()),
+            ca: Some(chain),
+        })
     }
 }
 
 impl TlsConfig {
-    fn load_authorities(&self) -> Result<Vec<X509>> {
+    pub(super) fn load_authorities(&self) -> Result<Vec<X509>> {
         match &self.ca_file {
             None => Ok(vec![]),
             Some(filename) => {
@@ -324,7 +644,7 @@ impl TlsConfig {
         }
     }
 
-    fn load_identity(&self) -> Result<Option<IdentityStore>> {
+    pub(super) fn load_identity(&self) -> Result<Option<IdentityStore>> {
         match (&self.crt_file, &self.key_file) {
             (None, Some(_)) => Err(TlsError::MissingCrtKeyFile),
             (None, None) => Ok(None),
@@ -339,6 +659,80 @@ impl TlsConfig {
         }
     }
 
+    /// Checks that the certificate revocation list file, if any, can be loaded.
+    fn check_crl_file(&self) -> Result<Option<PathBuf>> {
+        if let Some(crl_file) = &self.crl_file {
+            let mut store = X509StoreBuilder::new().context(NewStoreBuilderSnafu)?;
+            add_crl_file(&mut store, crl_file)?;
+        }
+        Ok(self.crl_file.clone())
+    }
+
+    /// Checks that stapled OCSP responses can be verified if requested, which FIPS builds don't
+    /// support.
+    fn check_ocsp_stapling(&self) -> Result<bool> {
+        let verify_ocsp_stapling = self.verify_ocsp_stapling.unwrap_or(false);
+        if verify_ocsp_stapling && cfg!(feature = "fips") {
+            return Err(TlsError::FipsUnsupported {
+                what: "verification of stapled OCSP responses".into(),
+            });
+        }
+        Ok(verify_ocsp_stapling)
+    }
+
+    /// Returns the minimum TLS version, and the cipher suites of TLS 1.2 and of TLS 1.3, checking
+    /// that they are approved in FIPS mode.
+    fn protocol_settings(
+        &self,
+        fips_mode: bool,
+    ) -> Result<(Option<TlsVersion>, Option<String>, Option<String>)> {
+        let min_tls_version = match self.min_tls_version {
+            Some(version) if fips_mode && version < TlsVersion::Tls12 => {
+                return Err(TlsError::FipsDisallowed {
+                    what: format!("TLS version {}", version.as_str()),
+                })
+            }
+            Some(version) => Some(version),
+            None => fips_mode.then_some(TlsVersion::Tls12),
+        };
+
+        let (tls13, tls12): (Vec<&str>, Vec<&str>) = self
+            .cipher_suites
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .partition(|suite| suite.starts_with("TLS_"));
+        if fips_mode {
+            if let Some(suite) = tls12
+                .iter()
+                .chain(&tls13)
+                .find(|suite| !fips::is_approved_cipher_suite(suite))
+            {
+                return Err(TlsError::FipsDisallowed {
+                    what: format!("cipher suite {:?}", suite),
+                });
+            }
+        }
+        if cfg!(feature = "fips") && !tls13.is_empty() {
+            return Err(TlsError::FipsUnsupported {
+                what: "choice of TLS 1.3 cipher suites".into(),
+            });
+        }
+        let join = |suites: Vec<&str>, approved: &[&str]| {
+            if suites.is_empty() {
+                fips_mode.then(|| approved.join(":"))
+            } else {
+                Some(suites.join(":"))
+            }
+        };
+
+        Ok((
+            min_tls_version,
+            join(tls12, fips::TLS12_CIPHER_SUITES),
+            join(tls13, fips::TLS13_CIPHER_SUITES),
+        ))
+    }
+
     /// The input must be in ALPN "wire format".
     ///
     /// It consists of a sequence of supported protocol names prefixed by their byte length.
@@ -407,6 +801,99 @@ impl TlsConfig {
 ///
 /// Most of this code is borrowed from https://github.com/ctz/rustls-native-certs
 
+/// Sets the store of the certificates trusted to verify the peers.
+#[cfg(not(feature = "fips"))]
+fn set_verify_store(context: &mut SslContextBuilder, store: X509StoreBuilder) -> Result<()> {
+    context
+        .set_verify_cert_store(store.build())
+        .context(super::SetVerifyCertSnafu)
+}
+
+/// Sets the store of the certificates trusted to verify the peers, which is also used to build the
+/// chain of the local certificate, as the `openssl` crate only supports a separate verification
+/// store with OpenSSL.
+#[cfg(feature = "fips")]
+#[allow(clippy::unnecessary_wraps)]
+fn set_verify_store(context: &mut SslContextBuilder, store: X509StoreBuilder) -> Result<()> {
+    context.set_cert_store(store.build());
+    Ok(())
+}
+
+/// Sets the minimum TLS version.
+#[cfg(not(feature = "fips"))]
+fn set_min_tls_version(context: &mut SslContextBuilder, version: TlsVersion) -> Result<()> {
+    use openssl::ssl::SslVersion;
+
+    let version = match version {
+        TlsVersion::Tls10 => SslVersion::TLS1,
+        TlsVersion::Tls11 => SslVersion::TLS1_1,
+        TlsVersion::Tls12 => SslVersion::TLS1_2,
+        TlsVersion::Tls13 => SslVersion::TLS1_3,
+    };
+    context
+        .set_min_proto_version(Some(version))
+        .context(super::SetTlsVersionSnafu)
+}
+
+/// Sets the minimum TLS version by disabling the older ones, as the `openssl` crate only supports
+/// setting it with OpenSSL.
+#[cfg(feature = "fips")]
+#[allow(clippy::unnecessary_wraps)]
+fn set_min_tls_version(context: &mut SslContextBuilder, version: TlsVersion) -> Result<()> {
+    use openssl::ssl::SslOptions;
+
+    for (older, option) in [
+        (TlsVersion::Tls10, SslOptions::NO_TLSV1),
+        (TlsVersion::Tls11, SslOptions::NO_TLSV1_1),
+        (TlsVersion::Tls12, SslOptions::NO_TLSV1_2),
+    ] {
+        if older < version {
+            context.set_options(option);
+        }
+    }
+    Ok(())
+}
+
+/// Sets the TLS 1.3 cipher suites.
+#[cfg(not(feature = "fips"))]
+fn set_tls13_cipher_suites(context: &mut SslContextBuilder, ciphersuites: &str) -> Result<()> {
+    context
+        .set_ciphersuites(ciphersuites)
+        .context(SetCipherSuitesSnafu)
+}
+
+/// BoringSSL doesn't allow choosing the TLS 1.3 cipher suites, which is rejected when loading the
+/// settings, and only negotiates the approved ones in FIPS mode.
+#[cfg(feature = "fips")]
+#[allow(clippy::unnecessary_wraps)]
+fn set_tls13_cipher_suites(_context: &mut SslContextBuilder, _ciphersuites: &str) -> Result<()> {
+    Ok(())
+}
+
+/// Sets the protocols offered through ALPN, given in wire format.
+#[cfg(not(feature = "fips"))]
+fn set_alpn_protocols(context: &mut SslContextBuilder, protocols: &[u8]) -> Result<()> {
+    context
+        .set_alpn_protos(protocols)
+        .context(SetAlpnProtocolsSnafu)
+}
+
+/// Sets the protocols offered through ALPN, given in wire format, calling BoringSSL directly as
+/// the `openssl` crate only supports ALPN with OpenSSL.
+#[cfg(feature = "fips")]
+fn set_alpn_protocols(context: &mut SslContextBuilder, protocols: &[u8]) -> Result<()> {
+    // SAFETY: The context outlives the call, and BoringSSL copies the protocols.
+    let result = unsafe {
+        openssl_sys::SSL_CTX_set_alpn_protos(context.as_ptr(), protocols.as_ptr(), protocols.len())
+    };
+    // Unlike most BoringSSL functions, this one returns zero on success.
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(openssl::error::ErrorStack::get()).context(SetAlpnProtocolsSnafu)
+    }
+}
+
 /// Load the system default certs from `schannel` this should be in place
 /// of openssl-probe on linux.
 #[cfg(windows)]
@@ -424,11 +911,7 @@ fn load_windows_certs(builder: &mut SslContextBuilder) -> Result<()> {
         store.add_cert(cert).context(AddCertToStoreSnafu)?;
     }
 
-    builder
-        .set_verify_cert_store(store.build())
-        .context(SetVerifyCertSnafu)?;
-
-    Ok(())
+    set_verify_store(builder, store)
 }
 
 #[cfg(target_os = "macos")]
@@ -482,11 +965,7 @@ fn load_mac_certs(builder: &mut SslContextBuilder) -> Result<()> {
         }
     }
 
-    builder
-        .set_verify_cert_store(store.build())
-        .context(SetVerifyCertSnafu)?;
-
-    Ok(())
+    set_verify_store(builder, store)
 }
 
 impl fmt::Debug for TlsSettings {
@@ -494,6 +973,7 @@ impl fmt::Debug for TlsSettings {
         f.debug_struct("TlsSettings")
             .field("verify_certificate", &self.verify_certificate)
             .field("verify_hostname", &self.verify_hostname)
+            .field("spiffe", &self.spiffe.is_some())
             .finish()
     }
 }
@@ -523,10 +1003,11 @@ impl MaybeTlsSettings {
                 if config.enabled.unwrap_or(false) {
                     let tls =
                         TlsSettings::from_options_base(&Some(config.options.clone()), for_server)?;
-                    match (for_server, &tls.identity) {
-                        // Servers require an identity certificate
-                        (true, None) => Err(TlsError::MissingRequiredIdentity),
-                        _ => Ok(Self::Tls(tls)),
+                    // Servers require an identity certificate
+                    if for_server && !tls.has_identity() {
+                        Err(TlsError::MissingRequiredIdentity)
+                    } else {
+                        Ok(Self::Tls(tls))
                     }
                 } else {
                     Ok(Self::Raw(())) // Explicitly disabled, still no TLS settings
@@ -586,7 +1067,7 @@ fn der_or_pem<T>(data: Vec<u8>, der_fn: impl Fn(Vec<u8>) -> T, pem_fn: impl Fn(S
 /// Open the named file and read its entire contents into memory. If the
 /// file "name" contains a PEM start marker, it is assumed to contain
 /// inline data and is used directly instead of opening a file.
-fn open_read(filename: &Path, note: &'static str) -> Result<(Vec<u8>, PathBuf)> {
+pub(super) fn open_read(filename: &Path, note: &'static str) -> Result<(Vec<u8>, PathBuf)> {
     if let Some(filename) = filename.to_str() {
         if filename.contains(PEM_START_MARKER) {
             return Ok((Vec::from(filename), "inline text".into()));
@@ -624,6 +1105,86 @@ mod test {
         assert_eq!(settings.alpn_protocols, Some(vec![2, 104, 50]));
     }
 
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn protocol_settings() {
+        let options = TlsConfig {
+            min_tls_version: Some(TlsVersion::Tls11),
+            cipher_suites: Some(vec![
+                "TLS_CHACHA20_POLY1305_SHA256".into(),
+                "ECDHE-RSA-AES128-GCM-SHA256".into(),
+            ]),
+            ..Default::default()
+        };
+        let (min_tls_version, cipher_list, ciphersuites) =
+            options.protocol_settings(false).unwrap();
+        assert_eq!(min_tls_version, Some(TlsVersion::Tls11));
+        assert_eq!(cipher_list.as_deref(), Some("ECDHE-RSA-AES128-GCM-SHA256"));
+        assert_eq!(
+            ciphersuites.as_deref(),
+            Some("TLS_CHACHA20_POLY1305_SHA256")
+        );
+    }
+
+    #[test]
+    fn fips_protocol_settings() {
+        let (min_tls_version, cipher_list, ciphersuites) =
+            TlsConfig::default().protocol_settings(true).unwrap();
+        assert_eq!(min_tls_version, Some(TlsVersion::Tls12));
+        assert_eq!(cipher_list, Some(fips::TLS12_CIPHER_SUITES.join(":")));
+        assert_eq!(ciphersuites, Some(fips::TLS13_CIPHER_SUITES.join(":")));
+
+        let options = TlsConfig {
+            min_tls_version: Some(TlsVersion::Tls13),
+            cipher_suites: Some(vec!["ECDHE-ECDSA-AES256-GCM-SHA384".into()]),
+            ..Default::default()
+        };
+        let (min_tls_version, cipher_list, _) = options.protocol_settings(true).unwrap();
+        assert_eq!(min_tls_version, Some(TlsVersion::Tls13));
+        assert_eq!(
+            cipher_list.as_deref(),
+            Some("ECDHE-ECDSA-AES256-GCM-SHA384")
+        );
+    }
+
+    #[test]
+    fn fips_rejects_disallowed_protocols() {
+        for version in [TlsVersion::Tls10, TlsVersion::Tls11] {
+            let options = TlsConfig {
+                min_tls_version: Some(version),
+                ..Default::default()
+            };
+            assert!(options.protocol_settings(false).is_ok());
+            match options.protocol_settings(true) {
+                Err(TlsError::FipsDisallowed { what }) => {
+                    assert_eq!(what, format!("TLS version {}", version.as_str()));
+                }
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn fips_rejects_disallowed_cipher_suites() {
+        for suite in [
+            "ECDHE-RSA-CHACHA20-POLY1305",
+            "AES128-SHA",
+            "DES-CBC3-SHA",
+            "TLS_CHACHA20_POLY1305_SHA256",
+        ] {
+            let options = TlsConfig {
+                cipher_suites: Some(vec!["ECDHE-RSA-AES128-GCM-SHA256".into(), suite.into()]),
+                ..Default::default()
+            };
+            match options.protocol_settings(true) {
+                Err(TlsError::FipsDisallowed { what }) => {
+                    assert_eq!(what, format!("cipher suite {:?}", suite));
+                }
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+    }
+
     #[test]
     fn from_options_pkcs12() {
         let options = TlsConfig {
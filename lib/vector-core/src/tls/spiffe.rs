@@ -0,0 +1,351 @@
+//! SPIFFE workload identities.
+//!
+//! The X.509 SVIDs of the workload are fetched from the SPIFFE Workload API exposed by the agent
+//! on a Unix socket, and kept up to date for as long as TLS settings use them: the agent streams a
+//! new SVID each time the previous one is rotated.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use http::uri::PathAndQuery;
+use once_cell::sync::Lazy;
+use openssl::{
+    pkey::{PKey, Private},
+    x509::{X509Ref, X509},
+};
+use snafu::ResultExt;
+use tokio::{net::UnixStream, sync::watch, task::JoinHandle};
+use tonic::{
+    codec::ProstCodec,
+    metadata::MetadataValue,
+    transport::{Endpoint, Uri},
+};
+use vector_config::configurable_component;
+
+use super::{Result, SpiffeCertificateParseSnafu, SpiffePrivateKeyParseSnafu, TlsError};
+
+/// The environment variable holding the address of the SPIFFE Workload API, when not configured.
+const ENDPOINT_SOCKET_ENV: &str = "SPIFFE_ENDPOINT_SOCKET";
+
+/// The header required by the SPIFFE Workload API on all requests.
+const WORKLOAD_API_HEADER: &str = "workload.spiffe.io";
+
+const FETCH_X509_SVID_PATH: &str = "/SpiffeWorkloadAPI/FetchX509SVID";
+
+/// How long to wait before connecting again to the Workload API after a failure.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// How long listeners wait for the first SVID before failing to start.
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// SPIFFE workload identity configuration.
+///
+/// When set, the identity certificate, its private key and the trusted CA certificates are fetched
+/// from the SPIFFE Workload API, and rotated as the agent issues new SVIDs. They are used in
+/// addition to `ca_file`, and instead of `crt_file` and `key_file`.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SpiffeConfig {
+    /// The address of the SPIFFE Workload API.
+    ///
+    /// Must be a Unix socket address, in the form `unix:///path/to/agent.sock`. If not set, the
+    /// address is read from the `SPIFFE_ENDPOINT_SOCKET` environment variable.
+    #[configurable(metadata(docs::examples = "unix:///run/spire/sockets/agent.sock"))]
+    pub endpoint_socket: Option<String>,
+
+    /// The SPIFFE IDs allowed to connect to, or be connected from.
+    ///
+    /// If empty, all the peers presenting an SVID issued by the trust bundle of the workload are
+    /// allowed.
+    #[configurable(metadata(docs::examples = "spiffe://example.org/vector/aggregator"))]
+    #[serde(default)]
+    pub allowed_ids: Vec<String>,
+}
+
+impl SpiffeConfig {
+    fn socket_path(&self) -> Result<PathBuf> {
+        let address = match &self.endpoint_socket {
+            Some(address) => address.clone(),
+            None => std::env::var(ENDPOINT_SOCKET_ENV)
+                .map_err(|_| TlsError::SpiffeMissingEndpointSocket)?,
+        };
+        parse_socket_address(&address)
+    }
+}
+
+fn parse_socket_address(address: &str) -> Result<PathBuf> {
+    match address.strip_prefix("unix://") {
+        Some(path) if path.starts_with('/') => Ok(PathBuf::from(path)),
+        _ => Err(TlsError::SpiffeInvalidEndpointSocket {
+            address: address.to_string(),
+        }),
+    }
+}
+
+/// An X.509 SVID of the workload.
+pub(super) struct Svid {
+    pub(super) spiffe_id: String,
+    pub(super) cert: X509,
+    pub(super) chain: Vec<X509>,
+    pub(super) key: PKey<Private>,
+    pub(super) bundle: Vec<X509>,
+    /// Incremented each time a new SVID is received.
+    pub(super) generation: u64,
+}
+
+/// The SVIDs fetched from a Workload API, shared by all the TLS settings using the same socket.
+pub(super) struct SvidSource {
+    allowed_ids: Vec<String>,
+    fetcher: Arc<SvidFetcher>,
+}
+
+struct SvidFetcher {
+    svid: watch::Receiver<Option<Arc<Svid>>>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for SvidFetcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+static FETCHERS: Lazy<Mutex<HashMap<PathBuf, Weak<SvidFetcher>>>> = Lazy::new(Default::default);
+
+impl SvidSource {
+    /// Starts fetching the SVIDs from the configured Workload API, unless they are already fetched.
+    pub(super) fn new(config: &SpiffeConfig) -> Result<Self> {
+        let socket = config.socket_path()?;
+
+        let mut fetchers = FETCHERS.lock().expect("poisoned lock");
+        fetchers.retain(|_, fetcher| fetcher.strong_count() > 0);
+        let fetcher = match fetchers.get(&socket).and_then(Weak::upgrade) {
+            Some(fetcher) => fetcher,
+            None => {
+                let runtime =
+                    tokio::runtime::Handle::try_current().map_err(|_| TlsError::SpiffeNoRuntime)?;
+                let (sender, svid) = watch::channel(None);
+                let task = runtime.spawn(fetch_svids(socket.clone(), sender));
+                let fetcher = Arc::new(SvidFetcher { svid, task });
+                fetchers.insert(socket, Arc::downgrade(&fetcher));
+                fetcher
+            }
+        };
+
+        Ok(Self {
+            allowed_ids: config.allowed_ids.clone(),
+            fetcher,
+        })
+    }
+
+    /// Returns the current SVID of the workload.
+    pub(super) fn current(&self) -> Result<Arc<Svid>> {
+        self.fetcher
+            .svid
+            .borrow()
+            .clone()
+            .ok_or(TlsError::SpiffeSvidUnavailable)
+    }
+
+    /// Waits for the first SVID of the workload.
+    pub(super) async fn ready(&self) -> Result<()> {
+        let mut svid = self.fetcher.svid.clone();
+        tokio::time::timeout(READY_TIMEOUT, async {
+            while svid.borrow().is_none() {
+                if svid.changed().await.is_err() {
+                    break;
+                }
+            }
+        })
+        .await
+        .map_err(|_| TlsError::SpiffeSvidUnavailable)?;
+        self.current().map(drop)
+    }
+
+    /// Returns whether the certificate presented by a peer holds an allowed SPIFFE ID.
+    pub(super) fn is_allowed(&self, cert: &X509Ref) -> bool {
+        self.allowed_ids.is_empty()
+            || spiffe_id(cert).map_or(false, |id| self.allowed_ids.contains(&id))
+    }
+}
+
+/// Returns the SPIFFE ID of an SVID, held by its URI subject alternative name.
+fn spiffe_id(cert: &X509Ref) -> Option<String> {
+    cert.subject_alt_names()?
+        .iter()
+        .filter_map(|name| name.uri())
+        .find(|uri| uri.starts_with("spiffe://"))
+        .map(str::to_string)
+}
+
+async fn fetch_svids(socket: PathBuf, sender: watch::Sender<Option<Arc<Svid>>>) {
+    let mut generation = 0;
+    loop {
+        match stream_svids(&socket, &sender, &mut generation).await {
+            Ok(()) => warn!(
+                message = "SPIFFE Workload API closed the SVID stream.",
+                socket = %socket.display(),
+            ),
+            Err(error) => error!(
+                message = "Failed to fetch SVIDs from the SPIFFE Workload API.",
+                socket = %socket.display(),
+                %error,
+            ),
+        }
+        if sender.is_closed() {
+            break;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn stream_svids(
+    socket: &Path,
+    sender: &watch::Sender<Option<Arc<Svid>>>,
+    generation: &mut u64,
+) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let socket = socket.to_path_buf();
+    // The URI is ignored by the connector, but must be valid.
+    let channel = Endpoint::from_static("http://localhost")
+        .connect_with_connector(tower::service_fn(move |_: Uri| {
+            UnixStream::connect(socket.clone())
+        }))
+        .await?;
+    let mut client = tonic::client::Grpc::new(channel);
+    client.ready().await?;
+
+    let mut request = tonic::Request::new(X509SvidRequest {});
+    request
+        .metadata_mut()
+        .insert(WORKLOAD_API_HEADER, MetadataValue::from_static("true"));
+    let mut responses = client
+        .server_streaming(
+            request,
+            PathAndQuery::from_static(FETCH_X509_SVID_PATH),
+            ProstCodec::<X509SvidRequest, X509SvidResponse>::default(),
+        )
+        .await?
+        .into_inner();
+
+    while let Some(response) = responses.message().await? {
+        // The first SVID is the default identity of the workload.
+        let Some(svid) = response.svids.into_iter().next() else {
+            warn!(message = "SPIFFE Workload API returned no SVID.");
+            continue;
+        };
+        *generation += 1;
+        let svid = parse_svid(svid, *generation)?;
+        info!(
+            message = "Received SVID from the SPIFFE Workload API.",
+            spiffe_id = %svid.spiffe_id,
+        );
+        if sender.send(Some(Arc::new(svid))).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn parse_svid(svid: X509SvidMessage, generation: u64) -> Result<Svid> {
+    let mut certs = parse_certificates(&svid.x509_svid)?.into_iter();
+    let cert = certs.next().ok_or(TlsError::MissingCertificate)?;
+    Ok(Svid {
+        spiffe_id: svid.spiffe_id,
+        cert,
+        chain: certs.collect(),
+        key: PKey::private_key_from_pkcs8(&svid.x509_svid_key)
+            .context(SpiffePrivateKeyParseSnafu)?,
+        bundle: parse_certificates(&svid.bundle)?,
+        generation,
+    })
+}
+
+/// Parses the concatenated DER certificates used by the Workload API.
+fn parse_certificates(mut der: &[u8]) -> Result<Vec<X509>> {
+    let mut certs = Vec::new();
+    while !der.is_empty() {
+        let length = der_length(der).ok_or(TlsError::SpiffeInvalidCertificates)?;
+        let (cert, rest) = der.split_at(length);
+        certs.push(X509::from_der(cert).context(SpiffeCertificateParseSnafu)?);
+        der = rest;
+    }
+    Ok(certs)
+}
+
+/// Returns the length of the first DER value, header included.
+fn der_length(der: &[u8]) -> Option<usize> {
+    let first = *der.get(1)?;
+    let (header, length) = if first & 0x80 == 0 {
+        (2, usize::from(first))
+    } else {
+        let count = usize::from(first & 0x7f);
+        let bytes = der.get(2..2 + count).filter(|_| count <= 4)?;
+        let length = bytes
+            .iter()
+            .fold(0, |length, byte| (length << 8) | usize::from(*byte));
+        (2 + count, length)
+    };
+    let total = header + length;
+    (total <= der.len()).then_some(total)
+}
+
+// Messages of the SPIFFE Workload API, from `workload.proto`.
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct X509SvidRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct X509SvidResponse {
+    #[prost(message, repeated, tag = "1")]
+    svids: Vec<X509SvidMessage>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct X509SvidMessage {
+    #[prost(string, tag = "1")]
+    spiffe_id: String,
+    #[prost(bytes = "vec", tag = "2")]
+    x509_svid: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    x509_svid_key: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    bundle: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{TEST_PEM_CA_PATH, TEST_PEM_INTERMEDIATE_CA_PATH};
+
+    #[test]
+    fn parses_socket_address() {
+        assert_eq!(
+            parse_socket_address("unix:///run/spire/agent.sock").unwrap(),
+            PathBuf::from("/run/spire/agent.sock")
+        );
+        assert!(parse_socket_address("tcp://127.0.0.1:8081").is_err());
+        assert!(parse_socket_address("unix://agent.sock").is_err());
+    }
+
+    #[test]
+    fn parses_concatenated_certificates() {
+        let pem = std::fs::read(TEST_PEM_INTERMEDIATE_CA_PATH).unwrap();
+        let expected = X509::stack_from_pem(&pem).unwrap();
+        let ca = X509::from_pem(&std::fs::read(TEST_PEM_CA_PATH).unwrap()).unwrap();
+
+        let mut der = Vec::new();
+        for cert in expected.iter().chain(Some(&ca)) {
+            der.extend(cert.to_der().unwrap());
+        }
+        let certs = parse_certificates(&der).unwrap();
+
+        assert_eq!(certs.len(), expected.len() + 1);
+        assert_eq!(certs[0].to_der().unwrap(), expected[0].to_der().unwrap());
+        assert!(parse_certificates(&der[..der.len() - 1]).is_err());
+    }
+}
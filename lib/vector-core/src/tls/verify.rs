@@ -0,0 +1,321 @@
+//! Certificate revocation checking and public key pinning.
+//!
+//! Revocation can't be checked in FIPS builds, which are linked against BoringSSL: the `openssl`
+//! crate supports neither enabling the revocation checks of its stores nor parsing OCSP responses
+//! with BoringSSL.
+
+use std::path::Path;
+
+use openssl::{
+    error::ErrorStack,
+    sha::sha256,
+    ssl::SslRef,
+    x509::{store::X509StoreBuilderRef, X509Ref},
+};
+#[cfg(not(feature = "fips"))]
+use openssl::{
+    hash::MessageDigest,
+    ocsp::{OcspCertId, OcspCertStatus, OcspFlag, OcspResponse, OcspResponseStatus},
+    ssl::SslFiletype,
+    x509::{store::X509Lookup, verify::X509VerifyFlags},
+};
+#[cfg(not(feature = "fips"))]
+use snafu::ResultExt;
+
+#[cfg(not(feature = "fips"))]
+use super::{settings::open_read, CrlLoadSnafu, PEM_START_MARKER};
+use super::{Result, TlsError};
+
+/// The leeway allowed on the validity period of stapled OCSP responses, in seconds.
+#[cfg(not(feature = "fips"))]
+const OCSP_VALIDITY_LEEWAY_SECS: u32 = 300;
+
+/// The SHA-256 hash of the DER encoded SubjectPublicKeyInfo of a certificate.
+pub(super) type PublicKeyHash = [u8; 32];
+
+/// Adds the certificate revocation lists of the given file to the store, and enables checking
+/// the revocation of all the certificates of the chains verified with the store.
+#[cfg(not(feature = "fips"))]
+pub(super) fn add_crl_file(store: &mut X509StoreBuilderRef, filename: &Path) -> Result<()> {
+    let (data, _) = open_read(filename, "certificate revocation list")?;
+    let file_type = if data.starts_with(PEM_START_MARKER.as_bytes()) {
+        SslFiletype::PEM
+    } else {
+        SslFiletype::ASN1
+    };
+    store
+        .add_lookup(X509Lookup::file())
+        .and_then(|lookup| lookup.load_crl_file(filename, file_type))
+        .with_context(|_| CrlLoadSnafu { filename })?;
+    store
+        .set_flags(X509VerifyFlags::CRL_CHECK | X509VerifyFlags::CRL_CHECK_ALL)
+        .with_context(|_| CrlLoadSnafu { filename })
+}
+
+#[cfg(feature = "fips")]
+pub(super) fn add_crl_file(_store: &mut X509StoreBuilderRef, _filename: &Path) -> Result<()> {
+    Err(TlsError::FipsUnsupported {
+        what: "certificate revocation list".into(),
+    })
+}
+
+/// Parses a pinned public key, given as the base64 encoded SHA-256 hash of its
+/// SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+pub(super) fn parse_pinned_public_key(pin: &str) -> Result<PublicKeyHash> {
+    let encoded = pin.strip_prefix("sha256//").unwrap_or(pin);
+    openssl::base64::decode_block(encoded)
+        .ok()
+        .and_then(|hash| hash.try_into().ok())
+        .ok_or_else(|| TlsError::InvalidPinnedPublicKey {
+            pin: pin.to_string(),
+        })
+}
+
+pub(super) fn public_key_hash(cert: &X509Ref) -> Option<PublicKeyHash> {
+    let der = cert.public_key().ok()?.public_key_to_der().ok()?;
+    Some(sha256(&der))
+}
+
+/// Checks the OCSP response stapled by the server, which must report the peer certificate as
+/// good.
+#[cfg(not(feature = "fips"))]
+pub(super) fn verify_ocsp_staple(ssl: &mut SslRef) -> std::result::Result<bool, ErrorStack> {
+    let Some(response) = ssl.ocsp_status() else {
+        warn!(message = "Server did not staple an OCSP response.");
+        return Ok(false);
+    };
+    let response = OcspResponse::from_der(response)?;
+    if response.status() != OcspResponseStatus::SUCCESSFUL {
+        warn!(message = "Server stapled an unsuccessful OCSP response.");
+        return Ok(false);
+    }
+    let response = response.basic()?;
+
+    let Some(chain) = ssl.verified_chain() else {
+        return Ok(false);
+    };
+    let (Some(cert), Some(issuer)) = (chain.get(0), chain.get(1)) else {
+        return Ok(false);
+    };
+    // The verified chain is trusted to have signed the response.
+    let store = ssl.ssl_context().cert_store();
+    if let Err(error) = response.verify(chain, store, OcspFlag::TRUSTOTHER) {
+        warn!(message = "Invalid stapled OCSP response.", %error);
+        return Ok(false);
+    }
+
+    let id = OcspCertId::from_cert(MessageDigest::sha1(), cert, issuer)?;
+    let Some(status) = response.find_status(&id) else {
+        warn!(message = "Stapled OCSP response does not cover the server certificate.");
+        return Ok(false);
+    };
+    if status.status != OcspCertStatus::GOOD {
+        warn!(message = "Server certificate is not reported as good by OCSP.");
+        return Ok(false);
+    }
+    Ok(status
+        .check_validity(OCSP_VALIDITY_LEEWAY_SECS, None)
+        .is_ok())
+}
+
+#[cfg(feature = "fips")]
+pub(super) fn verify_ocsp_staple(_ssl: &mut SslRef) -> std::result::Result<bool, ErrorStack> {
+    // OCSP stapling verification is rejected when loading the settings.
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use openssl::{
+        ssl::{SslAcceptor, SslMethod},
+        x509::X509,
+    };
+
+    use super::*;
+    use crate::tls::{
+        tls_connector, MaybeTlsSettings, TlsConfig, TEST_PEM_CA_PATH, TEST_PEM_CRT_PATH,
+        TEST_PEM_KEY_PATH,
+    };
+
+    const REVOKED_CRT_PATH: &str = "tests/data/ca/intermediate_server/certs/revoked-chain.cert.pem";
+    const REVOKED_KEY_PATH: &str = "tests/data/ca/intermediate_server/private/revoked.key.pem";
+    #[cfg(not(feature = "fips"))]
+    const CRL_PATH: &str = "tests/data/ca/intermediate_server/crl/ca-chain.crl.pem";
+    #[cfg(not(feature = "fips"))]
+    const GOOD_OCSP_PATH: &str = "tests/data/ca/intermediate_server/ocsp/localhost.ocsp.der";
+    #[cfg(not(feature = "fips"))]
+    const REVOKED_OCSP_PATH: &str = "tests/data/ca/intermediate_server/ocsp/revoked.ocsp.der";
+
+    /// Handshakes with a server using the given certificate, stapling the given OCSP response,
+    /// and returns whether the client accepted the server.
+    fn handshake(crt_file: &str, key_file: &str, staple: Option<&str>, client: TlsConfig) -> bool {
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        acceptor.set_certificate_chain_file(crt_file).unwrap();
+        acceptor
+            .set_private_key_file(key_file, openssl::ssl::SslFiletype::PEM)
+            .unwrap();
+        if let Some(staple) = staple {
+            let response = std::fs::read(staple).unwrap();
+            acceptor
+                .set_status_callback(move |ssl| {
+                    ssl.set_ocsp_status(&response)?;
+                    Ok(true)
+                })
+                .unwrap();
+        }
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            // The handshake fails on the server too when the client rejects it.
+            drop(acceptor.accept(stream));
+        });
+
+        let settings = MaybeTlsSettings::tls_client(&Some(TlsConfig {
+            ca_file: Some(TEST_PEM_CA_PATH.into()),
+            ..client
+        }))
+        .unwrap();
+        let stream = TcpStream::connect(addr).unwrap();
+        let accepted = tls_connector(&settings)
+            .unwrap()
+            .connect("localhost", stream)
+            .is_ok();
+        server.join().unwrap();
+        accepted
+    }
+
+    fn pin(path: &str) -> String {
+        let cert = X509::from_pem(&std::fs::read(path).unwrap()).unwrap();
+        openssl::base64::encode_block(&public_key_hash(&cert).unwrap())
+    }
+
+    #[test]
+    fn parses_pinned_public_keys() {
+        let cert = X509::from_pem(&std::fs::read(TEST_PEM_CRT_PATH).unwrap()).unwrap();
+        let hash = public_key_hash(&cert).unwrap();
+        let encoded = openssl::base64::encode_block(&hash);
+
+        assert_eq!(parse_pinned_public_key(&encoded).unwrap(), hash);
+        assert_eq!(
+            parse_pinned_public_key(&format!("sha256//{}", encoded)).unwrap(),
+            hash
+        );
+        assert!(parse_pinned_public_key("c2hvcnQ=").is_err());
+        assert!(parse_pinned_public_key("not base64!").is_err());
+    }
+
+    #[test]
+    fn accepts_pinned_public_key() {
+        let client = TlsConfig {
+            pinned_public_keys: Some(vec![pin(TEST_PEM_CA_PATH)]),
+            ..TlsConfig::default()
+        };
+        assert!(handshake(
+            TEST_PEM_CRT_PATH,
+            TEST_PEM_KEY_PATH,
+            None,
+            client
+        ));
+    }
+
+    #[test]
+    fn rejects_pin_mismatch() {
+        // A trusted certificate that is not in the chain of the server.
+        let other = "tests/data/ca/intermediate_client/certs/intermediate_client.cert.pem";
+        let client = TlsConfig {
+            pinned_public_keys: Some(vec![pin(other)]),
+            ..TlsConfig::default()
+        };
+        assert!(!handshake(
+            TEST_PEM_CRT_PATH,
+            TEST_PEM_KEY_PATH,
+            None,
+            client
+        ));
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn accepts_unrevoked_certificate() {
+        let client = TlsConfig {
+            crl_file: Some(CRL_PATH.into()),
+            ..TlsConfig::default()
+        };
+        assert!(handshake(
+            TEST_PEM_CRT_PATH,
+            TEST_PEM_KEY_PATH,
+            None,
+            client
+        ));
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn rejects_revoked_certificate() {
+        let client = TlsConfig {
+            crl_file: Some(CRL_PATH.into()),
+            ..TlsConfig::default()
+        };
+        assert!(!handshake(REVOKED_CRT_PATH, REVOKED_KEY_PATH, None, client));
+        // The certificate is only rejected because of the revocation list.
+        assert!(handshake(
+            REVOKED_CRT_PATH,
+            REVOKED_KEY_PATH,
+            None,
+            TlsConfig::default()
+        ));
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn accepts_good_ocsp_staple() {
+        let client = TlsConfig {
+            verify_ocsp_stapling: Some(true),
+            ..TlsConfig::default()
+        };
+        assert!(handshake(
+            TEST_PEM_CRT_PATH,
+            TEST_PEM_KEY_PATH,
+            Some(GOOD_OCSP_PATH),
+            client
+        ));
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn rejects_bad_ocsp_staple() {
+        let client = || TlsConfig {
+            verify_ocsp_stapling: Some(true),
+            ..TlsConfig::default()
+        };
+        // The staple reports the certificate as revoked.
+        assert!(!handshake(
+            REVOKED_CRT_PATH,
+            REVOKED_KEY_PATH,
+            Some(REVOKED_OCSP_PATH),
+            client()
+        ));
+        // The staple covers another certificate.
+        assert!(!handshake(
+            TEST_PEM_CRT_PATH,
+            TEST_PEM_KEY_PATH,
+            Some(REVOKED_OCSP_PATH),
+            client()
+        ));
+        // No staple at all.
+        assert!(!handshake(
+            TEST_PEM_CRT_PATH,
+            TEST_PEM_KEY_PATH,
+            None,
+            client()
+        ));
+    }
+}
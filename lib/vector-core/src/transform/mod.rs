@@ -272,6 +272,19 @@ impl TransformOutputs {
         (me, controls)
     }
 
+    /// Returns the status of the sends of each output.
+    pub fn send_statuses(&self) -> HashMap<Option<String>, fanout::SendStatus> {
+        self.primary_output
+            .iter()
+            .map(|output| (None, output.fanout.send_status()))
+            .chain(
+                self.named_outputs
+                    .iter()
+                    .map(|(name, output)| (Some(name.clone()), output.fanout.send_status())),
+            )
+            .collect()
+    }
+
     pub fn new_buf_with_capacity(&self, capacity: usize) -> TransformOutputsBuf {
         TransformOutputsBuf::new_with_capacity(self.outputs_spec.clone(), capacity)
     }
@@ -288,10 +301,11 @@ impl TransformOutputs {
     ) -> Result<(), Box<dyn error::Error + Send + Sync>> {
         if let Some(primary) = self.primary_output.as_mut() {
             let count = buf.primary_buffer.as_ref().map_or(0, OutputBuffer::len);
-            let byte_size = buf.primary_buffer.as_ref().map_or(
-                0,
-                EstimatedJsonEncodedSizeOf::estimated_json_encoded_size_of,
-            );
+            let byte_size = buf.primary_buffer.as_ref().map_or(0, |buf| {
+                buf.estimated_json_encoded_size_with(|size| {
+                    primary.events_sent.record_event_size(size);
+                })
+            });
             buf.primary_buffer
                 .as_mut()
                 .expect("mismatched outputs")
@@ -302,8 +316,10 @@ impl TransformOutputs {
 
         for (key, buf) in &mut buf.named_buffers {
             let count = buf.len();
-            let byte_size = buf.estimated_json_encoded_size_of();
             let output = self.named_outputs.get_mut(key).expect("unknown output");
+            let byte_size = buf.estimated_json_encoded_size_with(|size| {
+                output.events_sent.record_event_size(size);
+            });
             buf.send(&mut output.fanout).await?;
             output.events_sent.emit(CountByteSize(count, byte_size));
         }
@@ -409,6 +425,28 @@ impl TransformOutputsBuf {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Iterates over the events of all outputs.
+    pub fn iter_events(&self) -> impl Iterator<Item = EventRef> {
+        self.primary_buffer
+            .iter()
+            .chain(self.named_buffers.values())
+            .flat_map(OutputBuffer::iter_events)
+    }
+
+    /// Updates the event arrays of all outputs, removing the arrays left empty.
+    pub fn update_arrays(&mut self, mut update: impl FnMut(&mut EventArray)) {
+        for buffer in self
+            .primary_buffer
+            .iter_mut()
+            .chain(self.named_buffers.values_mut())
+        {
+            buffer.0.retain_mut(|array| {
+                update(array);
+                !array.is_empty()
+            });
+        }
+    }
 }
 
 impl ByteSizeOf for TransformOutputsBuf {
@@ -499,6 +537,15 @@ impl OutputBuffer {
         self.0.iter().flat_map(EventArray::iter_events)
     }
 
+    /// Returns the estimated JSON encoded size of the buffer, passing the size of each of its
+    /// events to `record`.
+    fn estimated_json_encoded_size_with(&self, mut record: impl FnMut(usize)) -> usize {
+        self.0
+            .iter()
+            .map(|array| array.estimated_json_encoded_size_with(&mut record))
+            .sum()
+    }
+
     pub fn into_events(self) -> impl Iterator<Item = Event> {
         self.0.into_iter().flat_map(EventArray::into_events)
     }
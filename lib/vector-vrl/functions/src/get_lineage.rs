@@ -0,0 +1,60 @@
+use ::value::{kind::Collection, Value};
+use lookup::{owned_value_path, OwnedTargetPath};
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+fn get_lineage(ctx: &mut Context, path: &OwnedTargetPath) -> Resolved {
+    Ok(ctx
+        .target()
+        .target_get(path)?
+        .cloned()
+        .unwrap_or(Value::Null))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetLineage;
+
+impl Function for GetLineage {
+    fn identifier(&self) -> &'static str {
+        "get_lineage"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Get the lineage record of the event",
+            source: r#"get_lineage()"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        _arguments: ArgumentList,
+    ) -> Compiled {
+        Ok(GetLineageFn {
+            path: OwnedTargetPath::metadata(owned_value_path!("vector", "lineage")),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetLineageFn {
+    path: OwnedTargetPath,
+}
+
+impl FunctionExpression for GetLineageFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        get_lineage(ctx, &self.path)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).add_null().infallible()
+    }
+}
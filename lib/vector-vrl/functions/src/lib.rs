@@ -1,5 +1,6 @@
 #![deny(warnings)]
 
+pub mod get_lineage;
 pub mod get_secret;
 pub mod remove_secret;
 pub mod set_secret;
@@ -19,6 +20,7 @@ pub const LEGACY_METADATA_KEYS: [&str; 2] = ["datadog_api_key", "splunk_hec_toke
 pub fn all() -> Vec<Box<dyn vrl::Function>> {
     vec![
         Box::new(set_semantic_meaning::SetSemanticMeaning) as _,
+        Box::new(get_lineage::GetLineage) as _,
         Box::new(get_secret::GetSecret) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
@@ -7,6 +7,7 @@ mod output;
 mod processed_bytes;
 mod processed_events;
 mod received_events;
+mod saturation;
 mod sent_events;
 mod sink;
 pub mod source;
@@ -33,6 +34,7 @@ pub use processed_events::{
 pub use received_events::{
     ComponentReceivedEventsThroughput, ComponentReceivedEventsTotal, ReceivedEventsTotal,
 };
+pub use saturation::Saturation;
 pub use sent_events::{ComponentSentEventsThroughput, ComponentSentEventsTotal, SentEventsTotal};
 pub use sink::{IntoSinkMetrics, SinkMetrics};
 pub use source::{IntoSourceMetrics, SourceMetrics};
@@ -55,6 +57,11 @@ pub struct MetricsQuery;
 
 #[Object]
 impl MetricsQuery {
+    /// Saturation of the Vector instance, which can drive the autoscaling of aggregators
+    async fn saturation(&self) -> Saturation {
+        Saturation::current()
+    }
+
     #[cfg(feature = "sources-host_metrics")]
     /// Vector host metrics
     async fn host_metrics(&self) -> host::HostMetrics {
@@ -0,0 +1,58 @@
+use async_graphql::Object;
+
+use crate::{
+    event::{Metric, MetricValue},
+    metrics::Controller,
+};
+
+pub struct Saturation(Vec<Metric>);
+
+impl Saturation {
+    pub fn current() -> Self {
+        Self(
+            Controller::get()
+                .expect("Metrics system not initialized. Please report.")
+                .capture_metrics()
+                .into_iter()
+                .filter(|m| m.name().starts_with("saturation_"))
+                .collect(),
+        )
+    }
+
+    fn gauge(&self, name: &str, resource: Option<&str>) -> f64 {
+        self.0
+            .iter()
+            .find(|m| {
+                m.name() == name
+                    && resource.map_or(true, |resource| m.tag_matches("resource", resource))
+            })
+            .map_or(0.0, |m| match m.value() {
+                MetricValue::Gauge { value } => *value,
+                _ => 0.0,
+            })
+    }
+}
+
+#[Object]
+/// Saturation of the Vector instance, sampled every few seconds
+impl Saturation {
+    /// Highest of the saturation ratios, from 0 to 1
+    async fn score(&self) -> f64 {
+        self.gauge("saturation_score", None)
+    }
+
+    /// Ratio of the size of the fullest buffer to its maximum size
+    async fn buffer(&self) -> f64 {
+        self.gauge("saturation_ratio", Some("buffer"))
+    }
+
+    /// Highest ratio of the requests in flight of a sink to its adaptive concurrency limit
+    async fn concurrency(&self) -> f64 {
+        self.gauge("saturation_ratio", Some("concurrency"))
+    }
+
+    /// Ratio of the CPU time used by the process to the CPU time available to it
+    async fn cpu(&self) -> f64 {
+        self.gauge("saturation_ratio", Some("cpu"))
+    }
+}
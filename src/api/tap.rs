@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     num::NonZeroUsize,
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 use futures::{future::try_join_all, FutureExt};
@@ -20,6 +21,7 @@ use super::{
     ShutdownRx, ShutdownTx,
 };
 use crate::{
+    audit,
     config::ComponentKey,
     event::{EventArray, LogArray, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
@@ -30,6 +32,9 @@ type TapSender = tokio_mpsc::Sender<TapPayload>;
 
 const TAP_BUFFER_SIZE: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(100) };
 
+/// Identifies the tap sessions in the audit log.
+static TAP_SESSION_ID: AtomicU64 = AtomicU64::new(0);
+
 /// Clients can supply glob patterns to find matched topology components.
 trait GlobMatcher<T> {
     fn matches_glob(&self, rhs: T) -> bool;
@@ -159,6 +164,7 @@ impl TapTransformer {
 #[derive(Debug)]
 pub struct TapController {
     _shutdown: ShutdownTx,
+    session_id: u64,
 }
 
 impl TapController {
@@ -168,9 +174,27 @@ impl TapController {
     pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: TapPatterns) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
 
+        let session_id = TAP_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        audit::record(audit::AuditAction::TapStarted {
+            session_id,
+            outputs_patterns: patterns.for_outputs.iter().cloned().collect(),
+            inputs_patterns: patterns.for_inputs.iter().cloned().collect(),
+        });
+
         tokio::spawn(tap_handler(patterns, tap_tx, watch_rx, shutdown_rx));
 
-        Self { _shutdown }
+        Self {
+            _shutdown,
+            session_id,
+        }
+    }
+}
+
+impl Drop for TapController {
+    fn drop(&mut self) {
+        audit::record(audit::AuditAction::TapStopped {
+            session_id: self.session_id,
+        });
     }
 }
 
@@ -587,6 +611,8 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: Duration::from_secs(1),
                 rate: None,
+                latency: None,
+                error_rate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -637,6 +663,7 @@ mod tests {
                     tags: None,
                     metric: MetricTypeConfig::Gauge,
                 }],
+                all_metrics: false,
             },
         );
         config.add_sink(
@@ -645,6 +672,8 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: Duration::from_secs(1),
                 rate: None,
+                latency: None,
+                error_rate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -695,6 +724,8 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: Duration::from_secs(1),
                 rate: None,
+                latency: None,
+                error_rate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -748,6 +779,8 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: Duration::from_secs(1),
                 rate: None,
+                latency: None,
+                error_rate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -822,6 +855,8 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: Duration::from_secs(1),
                 rate: None,
+                latency: None,
+                error_rate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -882,6 +917,8 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: Duration::from_secs(1),
                 rate: None,
+                latency: None,
+                error_rate: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -958,6 +995,8 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: Duration::from_secs(1),
                 rate: None,
+                latency: None,
+                error_rate: None,
                 acknowledgements: Default::default(),
             },
         );
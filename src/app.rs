@@ -22,9 +22,10 @@ use crate::metrics;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
+    audit,
     cli::{handle_config_errors, LogFormat, Opts, RootOpts},
     config::{self, Config, ConfigPath},
-    heartbeat,
+    heartbeat, saturation,
     signal::{SignalHandler, SignalPair, SignalRx, SignalTo},
     topology::{
         self, ReloadOutcome, RunningTopology, SharedTopologyController, TopologyController,
@@ -93,10 +94,16 @@ impl ApplicationConfig {
         #[cfg(feature = "api")]
         let api = config.api;
 
+        let audit_diff = audit::AuditConfigDiff::from(&diff);
         let result = topology::start_validated(config, diff, pieces).await;
         let (topology, (graceful_crash_sender, graceful_crash_receiver)) =
             result.ok_or(exitcode::CONFIG)?;
 
+        audit::record(audit::AuditAction::ConfigLoaded {
+            config_paths: audit::config_paths(&config_paths),
+            diff: audit_diff,
+        });
+
         Ok(Self {
             config_paths,
             topology,
@@ -173,7 +180,26 @@ impl Application {
             opts.root.internal_log_rate_limit,
         );
 
-        let runtime = build_runtime(opts.root.threads, "vector-worker")?;
+        if opts.root.fips {
+            vector_core::fips::enable().map_err(|error| {
+                error!(message = "Unable to enable FIPS mode.", %error);
+                exitcode::CONFIG
+            })?;
+        }
+
+        if let Some(path) = &opts.root.audit_log_path {
+            audit::init(path).map_err(|error| {
+                error!(message = "Unable to open the audit log.", path = ?path, %error);
+                exitcode::CONFIG
+            })?;
+        }
+
+        let runtime = build_runtime(
+            opts.root.threads,
+            opts.root.max_blocking_threads,
+            &opts.root.cpu_affinity,
+            "vector-worker",
+        )?;
 
         // Signal handler for OS and provider messages.
         let mut signals = SignalPair::new(&runtime);
@@ -204,6 +230,14 @@ impl Application {
 
         emit!(VectorStarted);
         runtime.spawn(heartbeat::heartbeat());
+        runtime.spawn(saturation::saturation());
+
+        // The topology is running, and healthy if required.
+        #[cfg(target_os = "linux")]
+        {
+            crate::systemd::notify_ready();
+            runtime.spawn(crate::systemd::watchdog());
+        }
 
         let Self {
             require_healthy,
@@ -330,6 +364,8 @@ impl FinishedApplication {
         match signal {
             SignalTo::Shutdown => {
                 emit!(VectorStopped);
+                #[cfg(target_os = "linux")]
+                crate::systemd::notify_stopping();
                 tokio::select! {
                     _ = topology_controller.stop() => (), // Graceful shutdown finished
                     _ = signal_rx.recv() => {
@@ -342,6 +378,8 @@ impl FinishedApplication {
             SignalTo::Quit => {
                 // It is highly unlikely that this event will exit from topology.
                 emit!(VectorQuit);
+                #[cfg(target_os = "linux")]
+                crate::systemd::notify_stopping();
                 drop(topology_controller);
             }
             _ => unreachable!(),
@@ -384,9 +422,17 @@ fn get_log_levels(default: &str) -> String {
         })
 }
 
-pub fn build_runtime(threads: Option<usize>, thread_name: &str) -> Result<Runtime, ExitCode> {
+pub fn build_runtime(
+    threads: Option<usize>,
+    max_blocking_threads: Option<usize>,
+    cpu_affinity: &[usize],
+    thread_name: &str,
+) -> Result<Runtime, ExitCode> {
     let mut rt_builder = runtime::Builder::new_multi_thread();
-    rt_builder.enable_all().thread_name(thread_name);
+    rt_builder
+        .enable_all()
+        .thread_name(thread_name)
+        .on_thread_start(crate::runtime::pin_threads(cpu_affinity.to_vec()));
 
     if let Some(threads) = threads {
         if threads < 1 {
@@ -403,6 +449,17 @@ pub fn build_runtime(threads: Option<usize>, thread_name: &str) -> Result<Runtim
         }
     }
 
+    if let Some(max_blocking_threads) = max_blocking_threads {
+        if max_blocking_threads < 1 {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("The `max-blocking-threads` argument must be greater or equal to 1.");
+            }
+            return Err(exitcode::CONFIG);
+        }
+        rt_builder.max_blocking_threads(max_blocking_threads);
+    }
+
     Ok(rt_builder.build().expect("Unable to create async runtime"))
 }
 
@@ -0,0 +1,209 @@
+//! Audit log of configuration and control operations.
+//!
+//! Audit records are kept apart from the internal logs. They are appended as JSON lines to the file
+//! given with `--audit-log-path`, and broadcast to the running `audit_logs` sources, which can
+//! route them to any sink.
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::{
+    config::{ComponentKey, ConfigDiff, ConfigPath, Difference},
+    internal_events::VectorAuditLogWriteError,
+};
+
+/// The number of records kept for `audit_logs` sources lagging behind.
+const AUDIT_CHANNEL_CAPACITY: usize = 1000;
+
+static AUDIT_FILE: OnceCell<AuditFile> = OnceCell::new();
+
+static AUDIT_SENDER: Lazy<broadcast::Sender<AuditRecord>> =
+    Lazy::new(|| broadcast::channel(AUDIT_CHANNEL_CAPACITY).0);
+
+struct AuditFile {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+/// An audited operation.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditAction {
+    /// The configuration was loaded when starting.
+    ConfigLoaded {
+        config_paths: Vec<String>,
+        diff: AuditConfigDiff,
+    },
+
+    /// The configuration was reloaded, changing the running topology.
+    ConfigReloaded {
+        config_paths: Vec<String>,
+        diff: AuditConfigDiff,
+    },
+
+    /// A client of the API started tapping into events.
+    TapStarted {
+        session_id: u64,
+        outputs_patterns: BTreeSet<String>,
+        inputs_patterns: BTreeSet<String>,
+    },
+
+    /// A client of the API stopped tapping into events.
+    TapStopped { session_id: u64 },
+
+    /// Secrets were retrieved from a secrets backend. Only the names of the secrets are recorded.
+    SecretsRetrieved {
+        backend: String,
+        secret_keys: BTreeSet<String>,
+        success: bool,
+    },
+}
+
+/// The components added, changed, and removed by a configuration change.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AuditConfigDiff {
+    sources: AuditDifference,
+    transforms: AuditDifference,
+    sinks: AuditDifference,
+    enrichment_tables: AuditDifference,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+struct AuditDifference {
+    added: BTreeSet<String>,
+    changed: BTreeSet<String>,
+    removed: BTreeSet<String>,
+}
+
+impl From<&Difference> for AuditDifference {
+    fn from(difference: &Difference) -> Self {
+        Self {
+            added: component_ids(&difference.to_add),
+            changed: component_ids(&difference.to_change),
+            removed: component_ids(&difference.to_remove),
+        }
+    }
+}
+
+fn component_ids(keys: &HashSet<ComponentKey>) -> BTreeSet<String> {
+    keys.iter().map(ToString::to_string).collect()
+}
+
+impl From<&ConfigDiff> for AuditConfigDiff {
+    fn from(diff: &ConfigDiff) -> Self {
+        Self {
+            sources: (&diff.sources).into(),
+            transforms: (&diff.transforms).into(),
+            sinks: (&diff.sinks).into(),
+            enrichment_tables: (&diff.enrichment_tables).into(),
+        }
+    }
+}
+
+/// A record of the audit log.
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub action: AuditAction,
+}
+
+/// Opens the file the audit log is appended to.
+///
+/// Can only be called once, further calls are ignored.
+pub fn init(path: &Path) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    _ = AUDIT_FILE.set(AuditFile {
+        path: path.to_owned(),
+        file: Mutex::new(file),
+    });
+
+    Ok(())
+}
+
+/// Records an audited operation.
+pub fn record(action: AuditAction) {
+    let record = AuditRecord {
+        timestamp: Utc::now(),
+        action,
+    };
+
+    if let Some(audit_file) = AUDIT_FILE.get() {
+        let mut line = serde_json::to_vec(&record).expect("audit records are serializable");
+        line.push(b'\n');
+
+        let mut file = audit_file.file.lock().expect("audit log mutex poisoned");
+        if let Err(error) = file.write_all(&line) {
+            emit!(VectorAuditLogWriteError {
+                error,
+                path: &audit_file.path,
+            });
+        }
+    }
+
+    // There are no receivers unless an `audit_logs` source is running.
+    _ = AUDIT_SENDER.send(record);
+}
+
+/// Subscribes to the records of the audit log.
+pub fn subscribe() -> broadcast::Receiver<AuditRecord> {
+    AUDIT_SENDER.subscribe()
+}
+
+/// Lists the configuration paths for recording.
+pub fn config_paths(paths: &[ConfigPath]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| <&PathBuf>::from(path).to_string_lossy().into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn records_are_written_and_broadcast() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        init(&path).unwrap();
+
+        let mut receiver = subscribe();
+        record(AuditAction::SecretsRetrieved {
+            backend: "audit_test".to_owned(),
+            secret_keys: BTreeSet::from(["api_key".to_owned()]),
+            success: true,
+        });
+
+        // Other tests may record operations concurrently.
+        loop {
+            if let AuditAction::SecretsRetrieved { backend, .. } =
+                receiver.recv().await.unwrap().action
+            {
+                if backend == "audit_test" {
+                    break;
+                }
+            }
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let line: serde_json::Value = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|line| line["backend"] == "audit_test")
+            .unwrap();
+        assert_eq!(line["action"], "secrets_retrieved");
+        assert_eq!(line["secret_keys"], serde_json::json!(["api_key"]));
+        assert_eq!(line["success"], true);
+        assert!(line.get("timestamp").is_some());
+    }
+}
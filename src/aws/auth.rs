@@ -1,15 +1,21 @@
 use std::time::Duration;
 
 use aws_config::{
+    credential_process::CredentialProcessProvider,
     default_provider::credentials::DefaultCredentialsChain,
     imds,
+    meta::credentials::LazyCachingCredentialsProvider,
     profile::{
         profile_file::{ProfileFileKind, ProfileFiles},
         ProfileFileCredentialsProvider,
     },
     sts::AssumeRoleProviderBuilder,
 };
-use aws_types::{credentials::SharedCredentialsProvider, region::Region, Credentials};
+use aws_types::{
+    credentials::{ProvideCredentials, SharedCredentialsProvider},
+    region::Region,
+    Credentials,
+};
 use serde_with::serde_as;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
@@ -18,6 +24,8 @@ use vector_config::configurable_component;
 // default rather than relying on the SDK default to not change
 const DEFAULT_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_PROFILE_NAME: &str = "default";
+// matches the default buffer time of the SDK's credentials cache
+const DEFAULT_REFRESH_BUFFER: Duration = Duration::from_secs(10);
 
 /// IMDS Client Configuration for authenticating with AWS.
 #[serde_as]
@@ -44,6 +52,16 @@ pub struct ImdsAuthentication {
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     #[derivative(Default(value = "default_timeout()"))]
     read_timeout: Duration,
+
+    /// Lifetime of the IMDSv2 session tokens.
+    ///
+    /// The IMDSv2 hop limit is set on the instance itself, through its `HttpPutResponseHopLimit`
+    /// metadata option, and must be at least 2 when Vector runs in a container.
+    #[serde(default = "default_token_ttl")]
+    #[serde(rename = "token_ttl_seconds")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_token_ttl()"))]
+    token_ttl: Duration,
 }
 
 const fn default_max_attempts() -> u32 {
@@ -54,6 +72,10 @@ const fn default_timeout() -> Duration {
     Duration::from_secs(1)
 }
 
+const fn default_token_ttl() -> Duration {
+    Duration::from_secs(21600) // default in the SDK
+}
+
 /// Configuration of the authentication strategy for interacting with AWS services.
 #[configurable_component]
 #[derive(Clone, Debug, Derivative)]
@@ -102,6 +124,53 @@ pub enum AwsAuthentication {
         #[configurable(metadata(docs::examples = "develop"))]
         #[serde(default = "default_profile")]
         profile: String,
+
+        /// How long before they expire the credentials are refreshed, in seconds.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        #[configurable(metadata(docs::examples = 300))]
+        refresh_buffer_secs: Option<u64>,
+    },
+
+    /// Authenticate using an [AWS IAM Identity Center (SSO)][sso] profile.
+    ///
+    /// The profile must be defined in the AWS config file, and its token must have been cached by
+    /// running `aws sso login`.
+    ///
+    /// [sso]: https://docs.aws.amazon.com/cli/latest/userguide/sso-configure-profile-token.html
+    Sso {
+        /// The name of the SSO profile to use.
+        #[configurable(metadata(docs::examples = "develop"))]
+        sso_profile: String,
+
+        /// Path to the AWS config file.
+        ///
+        /// If not set, the default config file is used.
+        #[configurable(metadata(docs::examples = "/my/aws/config"))]
+        config_file: Option<String>,
+
+        /// How long before they expire the credentials are refreshed, in seconds.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        #[configurable(metadata(docs::examples = 300))]
+        refresh_buffer_secs: Option<u64>,
+    },
+
+    /// Authenticate using the credentials printed by an external command.
+    Process {
+        /// The command to run to fetch credentials.
+        ///
+        /// The command must print the credentials in the [`credential_process`][credential_process]
+        /// format, and is run again when they are about to expire.
+        ///
+        /// [credential_process]: https://docs.aws.amazon.com/cli/latest/userguide/cli-configure-sourcing-external.html
+        #[configurable(metadata(
+            docs::examples = "/usr/local/bin/fetch-aws-credentials --role vector"
+        ))]
+        credential_process: String,
+
+        /// How long before they expire the credentials are refreshed, in seconds.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        #[configurable(metadata(docs::examples = 300))]
+        refresh_buffer_secs: Option<u64>,
     },
 
     /// Assume the given role ARN.
@@ -112,6 +181,14 @@ pub enum AwsAuthentication {
         #[configurable(metadata(docs::examples = "arn:aws:iam::123456789098:role/my_role"))]
         assume_role: String,
 
+        /// The ARNs of further IAM roles to assume, in order, after `assume_role`.
+        ///
+        /// Each role is assumed with the credentials of the previous one, which allows reaching
+        /// roles only trusted by another role.
+        #[configurable(metadata(docs::examples = "arn:aws:iam::123456789098:role/other_role"))]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        chained_roles: Vec<String>,
+
         /// Timeout for assuming the role, in seconds.
         ///
         /// Relevant when the default credentials chain or `assume_role` is used.
@@ -131,6 +208,11 @@ pub enum AwsAuthentication {
         /// [aws_region]: https://docs.aws.amazon.com/general/latest/gr/rande.html#regional-endpoints
         #[configurable(metadata(docs::examples = "us-west-2"))]
         region: Option<String>,
+
+        /// How long before they expire the credentials are refreshed, in seconds.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        #[configurable(metadata(docs::examples = 300))]
+        refresh_buffer_secs: Option<u64>,
     },
 
     /// Default authentication strategy which tries a variety of substrategies in sequential order.
@@ -146,6 +228,11 @@ pub enum AwsAuthentication {
         /// Configuration for authenticating with AWS through IMDS.
         #[serde(default)]
         imds: ImdsAuthentication,
+
+        /// How long before they expire the credentials are refreshed, in seconds.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        #[configurable(metadata(docs::examples = 300))]
+        refresh_buffer_secs: Option<u64>,
     },
 }
 
@@ -182,6 +269,7 @@ impl AwsAuthentication {
             AwsAuthentication::File {
                 credentials_file,
                 profile,
+                refresh_buffer_secs,
             } => {
                 // The SDK uses the default profile out of the box, but doesn't provide an optional
                 // type in the builder. We can just hardcode it so that everything works.
@@ -192,30 +280,73 @@ impl AwsAuthentication {
                     .profile_files(profile_files)
                     .profile_name(profile)
                     .build();
-                Ok(SharedCredentialsProvider::new(profile_provider))
+                Ok(cached_provider(profile_provider, *refresh_buffer_secs))
             }
+            AwsAuthentication::Sso {
+                sso_profile,
+                config_file,
+                refresh_buffer_secs,
+            } => {
+                let mut builder =
+                    ProfileFileCredentialsProvider::builder().profile_name(sso_profile);
+                if let Some(config_file) = config_file {
+                    builder = builder.profile_files(
+                        ProfileFiles::builder()
+                            .with_file(ProfileFileKind::Config, config_file)
+                            .build(),
+                    );
+                }
+                Ok(cached_provider(builder.build(), *refresh_buffer_secs))
+            }
+            AwsAuthentication::Process {
+                credential_process,
+                refresh_buffer_secs,
+            } => Ok(cached_provider(
+                CredentialProcessProvider::new(credential_process.clone()),
+                *refresh_buffer_secs,
+            )),
             AwsAuthentication::Role {
                 assume_role,
+                chained_roles,
                 load_timeout_secs,
                 imds,
                 region,
+                refresh_buffer_secs,
             } => {
                 let auth_region = region.clone().map(Region::new).unwrap_or(service_region);
-                let provider = AssumeRoleProviderBuilder::new(assume_role)
-                    .region(auth_region.clone())
-                    .build(
-                        default_credentials_provider(auth_region, *load_timeout_secs, *imds)
-                            .await?,
-                    );
+                let provider = default_credentials_provider(
+                    auth_region.clone(),
+                    *load_timeout_secs,
+                    *imds,
+                    None,
+                )
+                .await?;
+                let provider = std::iter::once(assume_role).chain(chained_roles).fold(
+                    provider,
+                    |provider, role| {
+                        SharedCredentialsProvider::new(
+                            AssumeRoleProviderBuilder::new(role)
+                                .region(auth_region.clone())
+                                .build(provider),
+                        )
+                    },
+                );
 
-                Ok(SharedCredentialsProvider::new(provider))
+                Ok(cached_provider(provider, *refresh_buffer_secs))
             }
             AwsAuthentication::Default {
                 load_timeout_secs,
                 imds,
-            } => Ok(SharedCredentialsProvider::new(
-                default_credentials_provider(service_region, *load_timeout_secs, *imds).await?,
-            )),
+                refresh_buffer_secs,
+            } => {
+                default_credentials_provider(
+                    service_region,
+                    *load_timeout_secs,
+                    *imds,
+                    *refresh_buffer_secs,
+                )
+                .await
+            }
         }
     }
 
@@ -230,15 +361,36 @@ impl AwsAuthentication {
     }
 }
 
+/// Caches the credentials of the provider, refreshing them `refresh_buffer_secs` before they expire.
+fn cached_provider(
+    provider: impl ProvideCredentials + 'static,
+    refresh_buffer_secs: Option<u64>,
+) -> SharedCredentialsProvider {
+    SharedCredentialsProvider::new(
+        LazyCachingCredentialsProvider::builder()
+            .load(provider)
+            .buffer_time(refresh_buffer(refresh_buffer_secs))
+            .build(),
+    )
+}
+
+fn refresh_buffer(refresh_buffer_secs: Option<u64>) -> Duration {
+    refresh_buffer_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_REFRESH_BUFFER)
+}
+
 async fn default_credentials_provider(
     region: Region,
     load_timeout_secs: Option<u64>,
     imds: ImdsAuthentication,
+    refresh_buffer_secs: Option<u64>,
 ) -> crate::Result<SharedCredentialsProvider> {
     let client = imds::Client::builder()
         .max_attempts(imds.max_attempts)
         .connect_timeout(imds.connect_timeout)
         .read_timeout(imds.read_timeout)
+        .token_ttl(imds.token_ttl)
         .build()
         .await?;
 
@@ -249,7 +401,8 @@ async fn default_credentials_provider(
             load_timeout_secs
                 .map(Duration::from_secs)
                 .unwrap_or(DEFAULT_LOAD_TIMEOUT),
-        );
+        )
+        .buffer_time(refresh_buffer(refresh_buffer_secs));
 
     Ok(SharedCredentialsProvider::new(chain.build().await))
 }
@@ -294,6 +447,7 @@ mod tests {
             AwsAuthentication::Default {
                 load_timeout_secs: Some(10),
                 imds: ImdsAuthentication { .. },
+                ..
             }
         ));
     }
@@ -317,7 +471,9 @@ mod tests {
                     max_attempts: 5,
                     connect_timeout: CONNECT_TIMEOUT,
                     read_timeout: READ_TIMEOUT,
+                    ..
                 },
+                ..
             }
         ));
     }
@@ -365,6 +521,7 @@ mod tests {
                 load_timeout_secs,
                 imds,
                 region,
+                ..
             } => {
                 assert_eq!(&assume_role, "root");
                 assert_eq!(load_timeout_secs, None);
@@ -374,6 +531,7 @@ mod tests {
                         max_attempts: 5,
                         connect_timeout: CONNECT_TIMEOUT,
                         read_timeout: READ_TIMEOUT,
+                        ..
                     }
                 ));
                 assert_eq!(region, None);
@@ -400,6 +558,7 @@ mod tests {
                 load_timeout_secs,
                 imds,
                 region,
+                ..
             } => {
                 assert_eq!(&assume_role, "auth.root");
                 assert_eq!(load_timeout_secs, Some(10));
@@ -466,6 +625,7 @@ mod tests {
             AwsAuthentication::File {
                 credentials_file,
                 profile,
+                ..
             } => {
                 assert_eq!(&credentials_file, "/path/to/file");
                 assert_eq!(&profile, "foo");
@@ -484,6 +644,7 @@ mod tests {
             AwsAuthentication::File {
                 credentials_file,
                 profile,
+                ..
             } => {
                 assert_eq!(&credentials_file, "/path/to/file");
                 assert_eq!(profile, "default".to_string());
@@ -491,4 +652,88 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn parsing_chained_roles() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.assume_role = "root"
+            auth.chained_roles = ["first", "second"]
+            auth.refresh_buffer_secs = 300
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::Role {
+                assume_role,
+                chained_roles,
+                refresh_buffer_secs,
+                ..
+            } => {
+                assert_eq!(&assume_role, "root");
+                assert_eq!(chained_roles, vec!["first", "second"]);
+                assert_eq!(refresh_buffer_secs, Some(300));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_sso() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.sso_profile = "develop"
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::Sso {
+                sso_profile,
+                config_file,
+                refresh_buffer_secs,
+            } => {
+                assert_eq!(&sso_profile, "develop");
+                assert_eq!(config_file, None);
+                assert_eq!(refresh_buffer_secs, None);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_process() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.credential_process = "/bin/credentials --json"
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::Process {
+                credential_process, ..
+            } => assert_eq!(&credential_process, "/bin/credentials --json"),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_imds_token_ttl() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.imds.token_ttl_seconds = 600
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::Default { imds, .. } => {
+                assert_eq!(imds.token_ttl, Duration::from_secs(600));
+                assert_eq!(imds.max_attempts, 4);
+            }
+            _ => panic!(),
+        }
+    }
 }
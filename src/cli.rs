@@ -124,6 +124,14 @@ pub struct RootOpts {
     #[arg(short, long, env = "VECTOR_THREADS")]
     pub threads: Option<usize>,
 
+    /// Maximum number of threads to use for blocking operations (default is 512)
+    #[arg(long, env = "VECTOR_MAX_BLOCKING_THREADS")]
+    pub max_blocking_threads: Option<usize>,
+
+    /// IDs of the CPUs to pin the processing threads to, separated by commas (Linux only)
+    #[arg(long, env = "VECTOR_CPU_AFFINITY", value_delimiter(','))]
+    pub cpu_affinity: Vec<usize>,
+
     /// Enable more detailed internal logging. Repeat to increase level. Overridden by `--quiet`.
     #[arg(short, long, action = ArgAction::Count)]
     pub verbose: u8,
@@ -159,6 +167,33 @@ pub struct RootOpts {
     )]
     pub internal_log_rate_limit: u64,
 
+    /// Append an audit log of configuration and control operations to this file.
+    ///
+    /// Each line of the file is a JSON record of a configuration load or reload, an API tap
+    /// session, or a retrieval of secrets.
+    #[arg(long, env = "VECTOR_AUDIT_LOG_PATH")]
+    pub audit_log_path: Option<PathBuf>,
+
+    /// Run in FIPS mode.
+    ///
+    /// OpenSSL is switched to its FIPS mode, which requires Vector to be built with the `fips`
+    /// feature against BoringSSL built in FIPS mode, and components requesting cryptographic
+    /// algorithms which aren't FIPS-approved fail to build: TLS versions before 1.2, cipher suites
+    /// which aren't FIPS-approved, HTTP/3, and the VRL cryptographic functions.
+    #[arg(long, env = "VECTOR_FIPS")]
+    pub fips: bool,
+
+    /// The name of the Windows service Vector is running as.
+    #[cfg(windows)]
+    #[arg(long, env = "VECTOR_SERVICE_NAME")]
+    pub service_name: Option<String>,
+
+    /// How long to wait for Vector to shut down gracefully when the Windows service is stopped,
+    /// in seconds. Vector is stopped forcefully once the timeout has elapsed.
+    #[cfg(windows)]
+    #[arg(long, env = "VECTOR_SERVICE_STOP_TIMEOUT", default_value = "60")]
+    pub service_stop_timeout: u64,
+
     /// Set runtime allocation tracing
     #[cfg(feature = "allocation-tracing")]
     #[arg(long, env = "ALLOCATION_TRACING", default_value = "false")]
@@ -221,7 +256,7 @@ pub enum SubCommand {
     /// For guidance on how to write unit tests check out <https://vector.dev/guides/level-up/unit-testing/>.
     Test(unit_test::Opts),
 
-    /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
+    /// Output the topology as visual representation in the DOT language, which can be rendered by GraphViz, or as a Mermaid or D2 diagram
     Graph(graph::Opts),
 
     /// Display topology and metrics in the console, for a local or remote Vector instance
@@ -251,7 +286,7 @@ impl SubCommand {
             Self::Config(c) => config::cmd(c),
             Self::Generate(g) => generate::cmd(g),
             Self::GenerateSchema => generate_schema::cmd(),
-            Self::Graph(g) => graph::cmd(g),
+            Self::Graph(g) => graph::cmd(g).await,
             Self::List(l) => list::cmd(l),
             #[cfg(windows)]
             Self::Service(s) => service::cmd(s),
@@ -265,6 +300,7 @@ impl SubCommand {
             Self::Vrl(s) => {
                 let mut functions = vrl_stdlib::all();
                 functions.extend(vector_vrl_functions::all());
+                vector_core::fips::retain_vrl_functions(&mut functions);
                 vrl_cli::cmd::cmd(s, functions)
             }
         }
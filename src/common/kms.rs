@@ -0,0 +1,17 @@
+use crate::aws::ClientBuilder;
+
+pub(crate) struct KmsClientBuilder;
+
+impl ClientBuilder for KmsClientBuilder {
+    type Config = aws_sdk_kms::config::Config;
+    type Client = aws_sdk_kms::client::Client;
+    type DefaultMiddleware = aws_sdk_kms::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_kms::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_kms::client::Client::with_config(client, config.into())
+    }
+}
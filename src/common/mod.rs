@@ -16,5 +16,32 @@ pub(crate) mod datadog;
 ))]
 pub(crate) mod sqs;
 
-#[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
+#[cfg(feature = "field-encryption-aws_kms")]
+pub(crate) mod kms;
+
+#[cfg(any(
+    feature = "sources-aws_s3",
+    feature = "sinks-aws_s3",
+    feature = "sources-utils-checkpoint-stores"
+))]
 pub(crate) mod s3;
+
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+pub(crate) mod dynamodb;
+
+#[cfg(any(
+    feature = "sources-aws_kinesis_streams",
+    feature = "sinks-aws_kinesis_streams"
+))]
+pub(crate) mod kinesis;
+
+#[cfg(any(
+    feature = "sources-websocket",
+    feature = "sources-websocket_server",
+    feature = "sinks-websocket",
+    feature = "sinks-websocket_server"
+))]
+pub(crate) mod websocket;
+
+#[cfg(any(feature = "sources-sftp", feature = "sinks-sftp"))]
+pub(crate) mod sftp;
@@ -0,0 +1,316 @@
+//! Connection settings and file operations shared by the `sftp` source and sink.
+use std::{
+    io::{self, Read, Seek, SeekFrom, Write},
+    net::TcpStream,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ssh2::{CheckResult, KnownHostFileKind, Session, Sftp};
+use vector_config::configurable_component;
+
+const DEFAULT_PORT: u16 = 22;
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the host key of the SFTP server is checked.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KnownHostsStrategy {
+    /// Only connect to servers whose host key is in the `known_hosts` file.
+    #[default]
+    Strict,
+
+    /// Add the host keys of unknown servers to the `known_hosts` file, but refuse to connect to
+    /// servers whose host key changed.
+    Add,
+
+    /// Connect to any server, without checking its host key.
+    ///
+    /// This is vulnerable to man-in-the-middle attacks, and should only be used for testing.
+    Accept,
+}
+
+/// SFTP connection configuration.
+///
+/// Only key-based authentication is supported: the key is read from `key`, or else the keys of
+/// the SSH agent of the user running Vector are tried. Host keys are checked against the
+/// `~/.ssh/known_hosts` file of that user.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SftpConnectionConfig {
+    /// The SFTP server to connect to.
+    ///
+    /// The port defaults to 22.
+    #[configurable(metadata(docs::examples = "sftp.example.com"))]
+    #[configurable(metadata(docs::examples = "10.0.0.5:2222"))]
+    pub endpoint: String,
+
+    /// The user to log in as.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub user: String,
+
+    /// The path of the private key to authenticate with.
+    #[configurable(metadata(docs::examples = "/home/vector/.ssh/id_ed25519"))]
+    pub key: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub known_hosts_strategy: KnownHostsStrategy,
+}
+
+impl SftpConnectionConfig {
+    /// Builds the files under `root`, relative to which all paths are resolved.
+    ///
+    /// The connection is only established by the first operation on the files.
+    pub fn build_filesystem(&self, root: &str) -> Arc<dyn Filesystem> {
+        Arc::new(SftpFilesystem {
+            connection: self.clone(),
+            root: PathBuf::from(root),
+            sftp: Mutex::new(None),
+        })
+    }
+
+    /// The host and port of the server.
+    fn host_port(&self) -> (&str, u16) {
+        let (host, port) = match self.endpoint.rsplit_once(':') {
+            // IPv6 addresses must be enclosed in brackets to be followed by a port.
+            Some((host, port)) if !host.contains(':') || host.starts_with('[') => {
+                match port.parse() {
+                    Ok(port) => (host, port),
+                    Err(_) => (self.endpoint.as_str(), DEFAULT_PORT),
+                }
+            }
+            _ => (self.endpoint.as_str(), DEFAULT_PORT),
+        };
+        (host.trim_start_matches('[').trim_end_matches(']'), port)
+    }
+
+    fn connect(&self) -> io::Result<Sftp> {
+        let (host, port) = self.host_port();
+        let stream = TcpStream::connect((host, port))?;
+        let mut session = Session::new()?;
+        session.set_timeout(TIMEOUT.as_millis() as u32);
+        session.set_tcp_stream(stream);
+        session.handshake()?;
+        self.check_host_key(&session, host, port)?;
+
+        match &self.key {
+            Some(key) => session.userauth_pubkey_file(&self.user, None, Path::new(key), None)?,
+            None => session.userauth_agent(&self.user)?,
+        }
+        Ok(session.sftp()?)
+    }
+
+    fn check_host_key(&self, session: &Session, host: &str, port: u16) -> io::Result<()> {
+        if self.known_hosts_strategy == KnownHostsStrategy::Accept {
+            return Ok(());
+        }
+
+        let path = dirs_next::home_dir()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No home directory found."))?
+            .join(".ssh/known_hosts");
+        let mut known_hosts = session.known_hosts()?;
+        if path.exists() {
+            known_hosts.read_file(&path, KnownHostFileKind::OpenSSH)?;
+        }
+        let (key, key_type) = session.host_key().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "The server sent no host key.")
+        })?;
+
+        match known_hosts.check_port(host, port, key) {
+            CheckResult::Match => Ok(()),
+            CheckResult::NotFound if self.known_hosts_strategy == KnownHostsStrategy::Add => {
+                let host = if port == DEFAULT_PORT {
+                    host.to_owned()
+                } else {
+                    format!("[{}]:{}", host, port)
+                };
+                known_hosts.add(&host, key, "", key_type.into())?;
+                Ok(known_hosts.write_file(&path, KnownHostFileKind::OpenSSH)?)
+            }
+            CheckResult::NotFound => Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("The host key of {} is not in {:?}.", host, path),
+            )),
+            CheckResult::Mismatch => Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "The host key of {} does not match the one in {:?}.",
+                    host, path
+                ),
+            )),
+            CheckResult::Failure => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("Failed to check the host key of {}.", host),
+            )),
+        }
+    }
+}
+
+/// An entry of a listed directory.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DirEntry {
+    /// A file, with its path and size.
+    File(String, u64),
+
+    /// A directory, whose path ends with a `/`.
+    Directory(String),
+}
+
+/// The operations of the `sftp` components on the files of the server.
+///
+/// All paths are relative to the root of the files. The operations block, so they must be run
+/// with [`spawn_blocking`].
+pub trait Filesystem: Send + Sync + 'static {
+    /// Lists the entries of the directory, which is the root if empty.
+    fn list(&self, directory: &str) -> io::Result<Vec<DirEntry>>;
+
+    /// Reads the given range of the file.
+    fn read_range(&self, path: &str, range: Range<u64>) -> io::Result<Vec<u8>>;
+
+    /// Writes the file, creating its parent directories.
+    fn write(&self, path: &str, data: &[u8]) -> io::Result<()>;
+
+    /// Moves the file, creating the parent directories of its destination.
+    fn rename(&self, from: &str, to: &str) -> io::Result<()>;
+
+    /// Deletes the file.
+    fn delete(&self, path: &str) -> io::Result<()>;
+
+    /// Checks that the root can be accessed.
+    fn check(&self) -> io::Result<()>;
+}
+
+/// Runs a blocking operation on the files.
+pub async fn spawn_blocking<T, F>(files: &Arc<dyn Filesystem>, operation: F) -> io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&dyn Filesystem) -> io::Result<T> + Send + 'static,
+{
+    let files = Arc::clone(files);
+    tokio::task::spawn_blocking(move || operation(files.as_ref()))
+        .await
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
+}
+
+/// The files of an SFTP server.
+///
+/// The connection is shared by all operations, and established again by the next operation once
+/// one fails.
+struct SftpFilesystem {
+    connection: SftpConnectionConfig,
+    root: PathBuf,
+    sftp: Mutex<Option<Sftp>>,
+}
+
+impl SftpFilesystem {
+    fn with_sftp<T>(&self, operation: impl FnOnce(&Sftp) -> io::Result<T>) -> io::Result<T> {
+        let mut sftp = self.sftp.lock().expect("Poisoned lock.");
+        let result = match sftp.as_ref() {
+            Some(sftp) => operation(sftp),
+            None => operation(sftp.insert(self.connection.connect()?)),
+        };
+        if result.is_err() {
+            *sftp = None;
+        }
+        result
+    }
+
+    fn create_parent_dirs(&self, sftp: &Sftp, path: &Path) -> io::Result<()> {
+        let mut missing = Vec::new();
+        for parent in path.ancestors().skip(1) {
+            if parent == self.root || sftp.stat(parent).is_ok() {
+                break;
+            }
+            missing.push(parent);
+        }
+        for directory in missing.into_iter().rev() {
+            sftp.mkdir(directory, 0o755)?;
+        }
+        Ok(())
+    }
+}
+
+impl Filesystem for SftpFilesystem {
+    fn list(&self, directory: &str) -> io::Result<Vec<DirEntry>> {
+        self.with_sftp(|sftp| {
+            let entries = sftp.readdir(&self.root.join(directory))?;
+            Ok(entries
+                .into_iter()
+                .filter_map(|(path, stat)| {
+                    let name = path.file_name()?.to_string_lossy();
+                    let path = format!("{}{}", directory, name);
+                    Some(if stat.is_dir() {
+                        DirEntry::Directory(path + "/")
+                    } else {
+                        DirEntry::File(path, stat.size.unwrap_or_default())
+                    })
+                })
+                .collect())
+        })
+    }
+
+    fn read_range(&self, path: &str, range: Range<u64>) -> io::Result<Vec<u8>> {
+        self.with_sftp(|sftp| {
+            let mut file = sftp.open(&self.root.join(path))?;
+            file.seek(SeekFrom::Start(range.start))?;
+            let mut data = Vec::with_capacity((range.end - range.start) as usize);
+            file.take(range.end - range.start).read_to_end(&mut data)?;
+            Ok(data)
+        })
+    }
+
+    fn write(&self, path: &str, data: &[u8]) -> io::Result<()> {
+        self.with_sftp(|sftp| {
+            let path = self.root.join(path);
+            self.create_parent_dirs(sftp, &path)?;
+            sftp.create(&path)?.write_all(data)
+        })
+    }
+
+    fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        self.with_sftp(|sftp| {
+            let to = self.root.join(to);
+            self.create_parent_dirs(sftp, &to)?;
+            Ok(sftp.rename(&self.root.join(from), &to, None)?)
+        })
+    }
+
+    fn delete(&self, path: &str) -> io::Result<()> {
+        self.with_sftp(|sftp| Ok(sftp.unlink(&self.root.join(path))?))
+    }
+
+    fn check(&self) -> io::Result<()> {
+        self.with_sftp(|sftp| Ok(sftp.stat(&self.root).map(drop)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(endpoint: &str) -> SftpConnectionConfig {
+        SftpConnectionConfig {
+            endpoint: endpoint.to_owned(),
+            user: "vector".to_owned(),
+            key: None,
+            known_hosts_strategy: KnownHostsStrategy::default(),
+        }
+    }
+
+    #[test]
+    fn parses_endpoint() {
+        assert_eq!(
+            config("sftp.example.com").host_port(),
+            ("sftp.example.com", 22)
+        );
+        assert_eq!(config("10.0.0.5:2222").host_port(), ("10.0.0.5", 2222));
+        assert_eq!(config("[::1]:2222").host_port(), ("::1", 2222));
+        assert_eq!(config("::1").host_port(), ("::1", 22));
+        assert_eq!(config("2001:db8::1").host_port(), ("2001:db8::1", 22));
+    }
+}
@@ -8,7 +8,7 @@ use crate::event::TargetEvents;
 use crate::{
     conditions::{Condition, Conditional, ConditionalConfig},
     emit,
-    event::{Event, VrlTarget},
+    event::{Event, ReadOnlyVrlTarget, VrlTarget},
     internal_events::VrlConditionExecutionError,
 };
 
@@ -38,11 +38,12 @@ impl ConditionalConfig for VrlConfig {
         //     },
         // };
 
-        let functions = vrl_stdlib::all()
+        let mut functions = vrl_stdlib::all()
             .into_iter()
             .chain(enrichment::vrl_functions().into_iter())
             .chain(vector_vrl_functions::all())
             .collect::<Vec<_>>();
+        vector_core::fips::retain_vrl_functions(&mut functions);
 
         let state = vrl::state::TypeState::default();
 
@@ -82,10 +83,17 @@ pub struct Vrl {
 
 impl Vrl {
     fn run(&self, event: Event) -> (Event, vrl::RuntimeResult) {
-        let mut target = VrlTarget::new(event, self.program.info(), false);
         // TODO: use timezone from remap config
         let timezone = TimeZone::default();
 
+        // Conditions are read-only, so logs and traces are read in place. Moving them into a
+        // `VrlTarget` would copy the events shared with other components.
+        if let Some(mut target) = ReadOnlyVrlTarget::new(&event) {
+            let result = Runtime::default().resolve(&mut target, &self.program, &timezone);
+            return (event, result);
+        }
+
+        let mut target = VrlTarget::new(event, self.program.info(), false);
         let result = Runtime::default().resolve(&mut target, &self.program, &timezone);
         let original_event = match target.into_events() {
             TargetEvents::One(event) => event,
@@ -251,4 +259,23 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn check_does_not_copy_shared_events() {
+        let config = VrlConfig {
+            source: r#".message == "foo""#.to_owned(),
+            runtime: Default::default(),
+        };
+        let condition = config.build(&Default::default()).unwrap();
+
+        let event = Event::from(log_event!["message" => "foo"]);
+        let shared = event.clone();
+        let (passed, event) = condition.check(event);
+
+        assert!(passed);
+        assert!(std::ptr::eq(
+            event.as_log().value(),
+            shared.as_log().value()
+        ));
+    }
 }
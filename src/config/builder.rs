@@ -1,9 +1,6 @@
-#[cfg(feature = "enterprise")]
-use std::collections::BTreeMap;
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 use indexmap::IndexMap;
-#[cfg(feature = "enterprise")]
 use serde_json::Value;
 use vector_config::configurable_component;
 use vector_core::config::GlobalOptions;
@@ -19,7 +16,7 @@ use super::api;
 use super::enterprise;
 use super::{
     compiler, schema, BoxedSource, BoxedTransform, ComponentKey, Config, EnrichmentTableOuter,
-    HealthcheckOptions, SinkOuter, SourceOuter, TestDefinition, TransformOuter,
+    HealthcheckOptions, PipelineTemplate, SinkOuter, SourceOuter, TestDefinition, TransformOuter,
 };
 
 /// A complete Vector configuration.
@@ -65,6 +62,13 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub transforms: IndexMap<ComponentKey, TransformOuter<String>>,
 
+    /// All configured pipeline templates.
+    ///
+    /// They are expanded into the sources, transforms and sinks of their instances when the
+    /// configuration is built.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub pipelines: IndexMap<ComponentKey, PipelineTemplate>,
+
     /// All configured unit tests.
     #[serde(default)]
     pub tests: Vec<TestDefinition<String>>,
@@ -80,7 +84,6 @@ pub struct ConfigBuilder {
     pub secret: IndexMap<ComponentKey, SecretBackends>,
 }
 
-#[cfg(feature = "enterprise")]
 #[derive(::serde::Serialize)]
 struct ConfigBuilderHash<'a> {
     version: String,
@@ -93,12 +96,12 @@ struct ConfigBuilderHash<'a> {
     sources: BTreeMap<&'a ComponentKey, &'a SourceOuter>,
     sinks: BTreeMap<&'a ComponentKey, &'a SinkOuter<String>>,
     transforms: BTreeMap<&'a ComponentKey, &'a TransformOuter<String>>,
+    // Pipelines are left out, as they are expanded into components before the hash is calculated.
     tests: &'a Vec<TestDefinition<String>>,
     provider: &'a Option<Providers>,
     secret: BTreeMap<&'a ComponentKey, &'a SecretBackends>,
 }
 
-#[cfg(feature = "enterprise")]
 impl ConfigBuilderHash<'_> {
     /// Sort inner JSON values to maintain a consistent ordering. This prevents
     /// non-deterministically serializable structures like HashMap from
@@ -125,7 +128,6 @@ impl ConfigBuilderHash<'_> {
 ///
 /// Rather than rely on the opaque underlying serde structures, we are explicit
 /// about sorting, sacrificing a bit of potential convenience for correctness.
-#[cfg(feature = "enterprise")]
 fn to_sorted_json_string<T>(value: T) -> String
 where
     T: ::serde::Serialize,
@@ -136,7 +138,6 @@ where
     serde_json::to_string(&value).expect("Should serialize Value to JSON string. Please report.")
 }
 
-#[cfg(feature = "enterprise")]
 fn sort_json_value(value: &mut Value) {
     match value {
         Value::Array(arr) => {
@@ -158,7 +159,6 @@ fn sort_json_value(value: &mut Value) {
     }
 }
 
-#[cfg(feature = "enterprise")]
 impl<'a> From<&'a ConfigBuilder> for ConfigBuilderHash<'a> {
     fn from(value: &'a ConfigBuilder) -> Self {
         ConfigBuilderHash {
@@ -222,6 +222,7 @@ impl From<Config> for ConfigBuilder {
             sources,
             sinks,
             transforms,
+            pipelines: IndexMap::new(),
             provider: None,
             tests,
             secret,
@@ -351,6 +352,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform id found: {}", k));
             }
         });
+        with.pipelines.keys().for_each(|k| {
+            if self.pipelines.contains_key(k) {
+                errors.push(format!("duplicate pipeline name found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -369,13 +375,13 @@ impl ConfigBuilder {
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
+        self.pipelines.extend(with.pipelines);
         self.tests.extend(with.tests);
         self.secret.extend(with.secret);
 
         Ok(())
     }
 
-    #[cfg(feature = "enterprise")]
     /// SHA256 hexadecimal representation of a config builder. This is generated by serializing
     /// an order-stable JSON of the config builder and feeding its bytes into a SHA256 hasher.
     pub fn sha256_hash(&self) -> String {
@@ -1,7 +1,8 @@
 use indexmap::IndexSet;
 
 use super::{
-    builder::ConfigBuilder, graph::Graph, id::Inputs, schema, validation, Config, OutputId,
+    builder::ConfigBuilder, enablement, graph::Graph, id::Inputs, pipeline, schema, validation,
+    Config, OutputId,
 };
 
 pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<String>> {
@@ -15,11 +16,24 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             .transforms
             .keys()
             .chain(builder.sources.keys())
-            .chain(builder.sinks.keys()),
+            .chain(builder.sinks.keys())
+            .chain(builder.pipelines.values().flat_map(|p| p.instances.keys())),
     ) {
         errors.extend(name_errors);
     }
 
+    if let Err(pipeline_errors) = pipeline::expand(&mut builder) {
+        errors.extend(pipeline_errors);
+        return Err(errors);
+    }
+
+    // Disabled components are removed, including those of pipeline instances, as if they weren't
+    // configured at all.
+    if let Err(enablement_errors) = enablement::remove_disabled(&mut builder) {
+        errors.extend(enablement_errors);
+        return Err(errors);
+    }
+
     expand_globs(&mut builder);
 
     if let Err(type_errors) = validation::check_shape(&builder) {
@@ -34,11 +48,9 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(output_errors);
     }
 
-    #[cfg(feature = "enterprise")]
-    let hash = Some(builder.sha256_hash());
-
-    #[cfg(not(feature = "enterprise"))]
-    let hash = None;
+    // The hash is only calculated when needed, as it requires serializing the whole configuration.
+    let hash = (cfg!(feature = "enterprise") || builder.global.lineage.enabled)
+        .then(|| builder.sha256_hash());
 
     let ConfigBuilder {
         global,
@@ -52,6 +64,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         sources,
         sinks,
         transforms,
+        pipelines: _,
         tests,
         provider: _,
         secret,
@@ -0,0 +1,411 @@
+use std::{collections::HashSet, iter::Peekable, str::CharIndices};
+
+use vector_config::configurable_component;
+
+use super::{builder::ConfigBuilder, ComponentKey, Inputs};
+
+/// Whether the component is enabled.
+///
+/// Disabled components are removed from the configuration when it is loaded, along with the
+/// references to their outputs in the `inputs` of other components. This allows a single
+/// configuration to be shared between environments which only differ by some of their components.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ComponentEnabled {
+    /// Whether the component is enabled.
+    Bool(bool),
+
+    /// An expression evaluated when the configuration is loaded.
+    ///
+    /// Operands are single- or double-quoted strings, bare words, `true` and `false`, and the
+    /// functions `env('NAME')`, returning the value of an environment variable or an empty string,
+    /// and `hostname()`. They can be compared with `==` and `!=`, and booleans can be combined with
+    /// `!`, `&&`, `||` and parentheses.
+    ///
+    /// As environment variables are interpolated in the configuration before it is parsed, they
+    /// can also be used directly, such as `"'${ENV}' == 'prod'"`.
+    #[configurable(metadata(docs::examples = "'${ENV}' == 'prod'"))]
+    #[configurable(metadata(docs::examples = "env('REGION') != 'eu' && hostname() != 'canary'"))]
+    Expression(String),
+}
+
+impl Default for ComponentEnabled {
+    fn default() -> Self {
+        Self::Bool(true)
+    }
+}
+
+impl ComponentEnabled {
+    pub const fn is_default(&self) -> bool {
+        matches!(self, Self::Bool(true))
+    }
+
+    /// Evaluates whether the component is enabled.
+    pub fn evaluate(&self) -> Result<bool, String> {
+        match self {
+            Self::Bool(enabled) => Ok(*enabled),
+            Self::Expression(expression) => {
+                let mut parser = Parser::new(expression);
+                let value = parser.expression()?;
+                match parser.token()? {
+                    None => value.into_bool(),
+                    Some(token) => Err(format!("unexpected {}", token)),
+                }
+            }
+        }
+    }
+}
+
+/// Removes the disabled components from the configuration, and the references to their outputs
+/// from the inputs of the other components.
+pub(super) fn remove_disabled(builder: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    let mut disabled = HashSet::new();
+    let mut is_enabled = |kind: &str, key: &ComponentKey, enabled: &ComponentEnabled| {
+        match enabled.evaluate() {
+            Ok(true) => return true,
+            Ok(false) => debug!(message = "Component disabled.", component_id = %key),
+            Err(error) => errors.push(format!(
+                "{} \"{}\": invalid `enabled` expression: {}",
+                kind, key, error
+            )),
+        }
+        disabled.insert(key.id().to_string());
+        false
+    };
+
+    builder
+        .sources
+        .retain(|key, source| is_enabled("Source", key, &source.enabled));
+    builder
+        .transforms
+        .retain(|key, transform| is_enabled("Transform", key, &transform.enabled));
+    builder
+        .sinks
+        .retain(|key, sink| is_enabled("Sink", key, &sink.enabled));
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    if disabled.is_empty() {
+        return Ok(());
+    }
+
+    let retain_enabled = |inputs: &mut Inputs<String>| {
+        *inputs = std::mem::take(inputs)
+            .into_iter()
+            .filter(|input| {
+                let component = input.split_once('.').map_or(input.as_str(), |(id, _)| id);
+                !disabled.contains(component)
+            })
+            .collect();
+    };
+    for transform in builder.transforms.values_mut() {
+        retain_enabled(&mut transform.inputs);
+    }
+    for sink in builder.sinks.values_mut() {
+        retain_enabled(&mut sink.inputs);
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    String(String),
+}
+
+impl Value {
+    fn into_bool(self) -> Result<bool, String> {
+        match self {
+            Self::Bool(value) => Ok(value),
+            Self::String(value) => Err(format!("expected a boolean, found \"{}\"", value)),
+        }
+    }
+
+    fn into_string(self) -> String {
+        match self {
+            Self::Bool(value) => value.to_string(),
+            Self::String(value) => value,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Equal,
+    NotEqual,
+    Not,
+    And,
+    Or,
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "`{}`", word),
+            Self::Quoted(string) => write!(f, "string \"{}\"", string),
+            Self::Equal => write!(f, "`==`"),
+            Self::NotEqual => write!(f, "`!=`"),
+            Self::Not => write!(f, "`!`"),
+            Self::And => write!(f, "`&&`"),
+            Self::Or => write!(f, "`||`"),
+            Self::OpenParen => write!(f, "`(`"),
+            Self::CloseParen => write!(f, "`)`"),
+            Self::Comma => write!(f, "`,`"),
+        }
+    }
+}
+
+/// Recursive descent parser evaluating the expression as it parses it.
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    source: &'a str,
+    peeked: Option<Option<Token>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.char_indices().peekable(),
+            source,
+            peeked: None,
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&Token>, String> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.lex()?);
+        }
+        Ok(self.peeked.as_ref().and_then(Option::as_ref))
+    }
+
+    fn token(&mut self) -> Result<Option<Token>, String> {
+        match self.peeked.take() {
+            Some(token) => Ok(token),
+            None => self.lex(),
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.token()? {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {}, found {}", expected, token)),
+            None => Err(format!(
+                "expected {}, found the end of the expression",
+                expected
+            )),
+        }
+    }
+
+    fn lex(&mut self) -> Result<Option<Token>, String> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        let Some((start, c)) = self.chars.next() else {
+            return Ok(None);
+        };
+        let token = match c {
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            ',' => Token::Comma,
+            '=' => self.pair(c, Token::Equal)?,
+            '&' => self.pair(c, Token::And)?,
+            '|' => self.pair(c, Token::Or)?,
+            '!' => match self.chars.next_if(|(_, c)| *c == '=') {
+                Some(_) => Token::NotEqual,
+                None => Token::Not,
+            },
+            '\'' | '"' => {
+                let mut string = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some((_, end)) if end == c => break,
+                        Some((_, c)) => string.push(c),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                Token::Quoted(string)
+            }
+            c if is_word_char(c) => {
+                let mut end = start + c.len_utf8();
+                while let Some((index, c)) = self.chars.next_if(|(_, c)| is_word_char(*c)) {
+                    end = index + c.len_utf8();
+                }
+                Token::Word(self.source[start..end].to_string())
+            }
+            c => return Err(format!("unexpected character `{}`", c)),
+        };
+        Ok(Some(token))
+    }
+
+    /// Lexes the second character of a two characters operator.
+    fn pair(&mut self, first: char, token: Token) -> Result<Token, String> {
+        match self.chars.next_if(|(_, c)| *c == first) {
+            Some(_) => Ok(token),
+            None => Err(format!("expected `{}{}`", first, first)),
+        }
+    }
+
+    /// expression := conjunction ( "||" conjunction )*
+    fn expression(&mut self) -> Result<Value, String> {
+        let value = self.conjunction()?;
+        if self.peek()? != Some(&Token::Or) {
+            return Ok(value);
+        }
+        let mut value = value.into_bool()?;
+        while self.peek()? == Some(&Token::Or) {
+            self.token()?;
+            // Both sides are evaluated so that errors are reported regardless of the environment.
+            let right = self.conjunction()?.into_bool()?;
+            value = value || right;
+        }
+        Ok(Value::Bool(value))
+    }
+
+    /// conjunction := unary ( "&&" unary )*
+    fn conjunction(&mut self) -> Result<Value, String> {
+        let mut value = self.unary()?;
+        while self.peek()? == Some(&Token::And) {
+            self.token()?;
+            let left = value.into_bool()?;
+            let right = self.unary()?.into_bool()?;
+            value = Value::Bool(left && right);
+        }
+        Ok(value)
+    }
+
+    /// unary := "!" unary | comparison
+    fn unary(&mut self) -> Result<Value, String> {
+        if self.peek()? == Some(&Token::Not) {
+            self.token()?;
+            return Ok(Value::Bool(!self.unary()?.into_bool()?));
+        }
+        self.comparison()
+    }
+
+    /// comparison := operand ( ( "==" | "!=" ) operand )?
+    fn comparison(&mut self) -> Result<Value, String> {
+        let left = self.operand()?;
+        let equal = match self.peek()? {
+            Some(Token::Equal) => true,
+            Some(Token::NotEqual) => false,
+            _ => return Ok(left),
+        };
+        self.token()?;
+        let right = self.operand()?;
+        Ok(Value::Bool(
+            (left.into_string() == right.into_string()) == equal,
+        ))
+    }
+
+    /// operand := "(" expression ")" | string | word | word "(" arguments ")"
+    fn operand(&mut self) -> Result<Value, String> {
+        match self.token()? {
+            Some(Token::OpenParen) => {
+                let value = self.expression()?;
+                self.expect(Token::CloseParen)?;
+                Ok(value)
+            }
+            Some(Token::Quoted(string)) => Ok(Value::String(string)),
+            Some(Token::Word(word)) if self.peek()? == Some(&Token::OpenParen) => {
+                self.token()?;
+                self.call(&word)
+            }
+            Some(Token::Word(word)) => Ok(match word.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => Value::String(word),
+            }),
+            Some(token) => Err(format!("expected a value, found {}", token)),
+            None => Err("expected a value, found the end of the expression".to_string()),
+        }
+    }
+
+    fn call(&mut self, function: &str) -> Result<Value, String> {
+        let mut arguments = Vec::new();
+        if self.peek()? == Some(&Token::CloseParen) {
+            self.token()?;
+        } else {
+            loop {
+                arguments.push(self.expression()?);
+                match self.token()? {
+                    Some(Token::Comma) => continue,
+                    Some(Token::CloseParen) => break,
+                    Some(token) => return Err(format!("expected `,` or `)`, found {}", token)),
+                    None => return Err("expected `)`, found the end of the expression".to_string()),
+                }
+            }
+        }
+
+        match (function, arguments.as_slice()) {
+            ("env", [name]) => Ok(Value::String(
+                std::env::var(name.clone().into_string()).unwrap_or_default(),
+            )),
+            ("hostname", []) => crate::get_hostname()
+                .map(Value::String)
+                .map_err(|error| format!("could not get the hostname: {}", error)),
+            ("env" | "hostname", _) => Err(format!(
+                "wrong number of arguments for function `{}`",
+                function
+            )),
+            _ => Err(format!("unknown function `{}`", function)),
+        }
+    }
+}
+
+const fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str) -> Result<bool, String> {
+        ComponentEnabled::Expression(expression.to_string()).evaluate()
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        std::env::set_var("VECTOR_ENABLEMENT_TEST_ENV", "prod");
+
+        assert_eq!(evaluate("true"), Ok(true));
+        assert_eq!(evaluate("prod == 'prod'"), Ok(true));
+        assert_eq!(evaluate("'staging' == \"prod\""), Ok(false));
+        assert_eq!(evaluate("staging != prod"), Ok(true));
+        assert_eq!(
+            evaluate("env('VECTOR_ENABLEMENT_TEST_ENV') == 'prod'"),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate("env('VECTOR_ENABLEMENT_TEST_UNSET') == ''"),
+            Ok(true)
+        );
+        assert_eq!(evaluate("!(a == b) && (false || c == c)"), Ok(true));
+        assert_eq!(evaluate("a == b || !true"), Ok(false));
+        assert_eq!(
+            evaluate(&format!(
+                "hostname() == '{}'",
+                crate::get_hostname().unwrap()
+            )),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(evaluate("").is_err());
+        assert!(evaluate("prod").is_err());
+        assert!(evaluate(" == 'prod'").is_err());
+        assert!(evaluate("a = b").is_err());
+        assert!(evaluate("(a == b").is_err());
+        assert!(evaluate("'unterminated").is_err());
+        assert!(evaluate("unknown()").is_err());
+        assert!(evaluate("a == b c").is_err());
+    }
+}
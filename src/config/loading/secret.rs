@@ -11,6 +11,7 @@ use toml::value::Table;
 use vector_common::config::ComponentKey;
 
 use crate::{
+    audit,
     config::{
         loading::{deserialize_table, prepare_input, process::Process, ComponentHint, Loader},
         SecretBackend,
@@ -62,7 +63,13 @@ impl SecretBackendLoader {
                 },
                 Some(backend) => {
                     debug!(message = "Retrieving secret from a backend.", backend = ?backend_name);
-                    match backend.retrieve(keys.clone(), signal_rx) {
+                    let result = backend.retrieve(keys.clone(), signal_rx);
+                    audit::record(audit::AuditAction::SecretsRetrieved {
+                        backend: backend_name.clone(),
+                        secret_keys: keys.iter().cloned().collect(),
+                        success: result.is_ok(),
+                    });
+                    match result {
                         Err(e) => {
                             vec![Err(format!("Error while retrieving secret from backend \"{}\": {}.", backend_name, e))]
                         },
@@ -5,6 +5,7 @@ use std::{
     hash::Hash,
     net::SocketAddr,
     path::PathBuf,
+    time::Duration,
 };
 
 use indexmap::IndexMap;
@@ -22,6 +23,7 @@ mod builder;
 mod cmd;
 mod compiler;
 mod diff;
+mod enablement;
 mod enrichment_table;
 #[cfg(feature = "enterprise")]
 pub mod enterprise;
@@ -29,7 +31,9 @@ pub mod format;
 mod graph;
 mod id;
 mod loading;
+mod pipeline;
 pub mod provider;
+mod runtime;
 pub mod schema;
 mod secret;
 mod sink;
@@ -42,7 +46,8 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
-pub use diff::ConfigDiff;
+pub use diff::{ConfigDiff, Difference};
+pub use enablement::ComponentEnabled;
 pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter};
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, Inputs};
@@ -50,17 +55,30 @@ pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
     load_from_str, load_source_from_paths, merge_path_lists, process_paths, CONFIG_PATHS,
 };
+pub use pipeline::{PipelineInstance, PipelineParameter, PipelineTemplate};
 pub use provider::ProviderConfig;
+pub use runtime::ComponentRuntimeConfig;
 pub use secret::SecretBackend;
-pub use sink::{SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
+#[cfg(feature = "field-encryption-aws_kms")]
+pub use sink::AwsKmsKeyConfig;
+pub use sink::{
+    FieldEncryptionConfig, FileKeyConfig, KeyEncryptionKeyConfig, QuarantinePolicy,
+    SchemaValidationConfig, SchemaValidationFailure, ShadowConfig, SinkConfig, SinkContext,
+    SinkHealthcheckOptions, SinkOuter, SinkSchemaOptions,
+};
 pub use source::{BoxedSource, SourceConfig, SourceContext, SourceOuter};
-pub use transform::{BoxedTransform, TransformConfig, TransformContext, TransformOuter};
+pub use transform::{
+    BoxedTransform, OrderedMergeConfig, TransformConfig, TransformContext, TransformOuter,
+};
 pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
 pub use validation::warnings;
 pub use vector_core::config::{
     init_log_schema, log_schema, proxy::ProxyConfig, LogSchema, OutputId,
 };
 
+/// How long transforms and sinks removed by a reload are given to drain their input by default.
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub enum ConfigPath {
     File(PathBuf, FormatHint),
@@ -629,6 +647,163 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn disabled_components() {
+        let config = load_from_str(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sources.canary]
+            type = "test_basic"
+            enabled = "staging == 'prod'"
+
+            [transforms.sample]
+            type = "test_basic"
+            inputs = ["canary"]
+            suffix = "foo"
+            increase = 1.25
+            enabled = false
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in", "canary", "sample"]
+            enabled = "!(staging == 'prod')"
+            "#,
+            Format::Toml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config
+                .sources()
+                .map(|(key, _)| key.id())
+                .collect::<Vec<_>>(),
+            vec!["in"]
+        );
+        assert_eq!(config.transforms().count(), 0);
+        assert_eq!(
+            config.sink(&ComponentKey::from("out")).unwrap().inputs,
+            vec![config::OutputId::from(&ComponentKey::from("in"))]
+        );
+
+        let errors = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+            enabled = "staging == "
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "Source \"in\": invalid `enabled` expression: expected a value, found the end of the expression"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pipeline_templates() {
+        let config = load_from_str(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [pipelines.suffix]
+            output = "second"
+            params.first = {}
+            params.second = { default = "bar" }
+
+            [pipelines.suffix.transforms.first]
+            type = "test_basic"
+            inputs = ["%{inputs}"]
+            suffix = "%{first}"
+            increase = 1.25
+
+            [pipelines.suffix.transforms.second]
+            type = "test_basic"
+            inputs = ["first"]
+            suffix = "%{second}"
+            increase = 1.25
+
+            [pipelines.suffix.instances.a]
+            inputs = ["in"]
+            params.first = "foo"
+
+            [pipelines.suffix.instances.b]
+            inputs = ["a"]
+            params = { first = "baz", second = "qux" }
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["a", "b"]
+            "#,
+            Format::Toml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config
+                .transforms()
+                .map(|(key, transform)| (
+                    key.id(),
+                    transform
+                        .inputs
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a.first", vec!["in".to_string()]),
+                ("a.second", vec!["a.first".to_string()]),
+                ("b.first", vec!["a.second".to_string()]),
+                ("b.second", vec!["b.first".to_string()]),
+            ]
+        );
+        assert_eq!(
+            config.sink(&ComponentKey::from("out")).unwrap().inputs,
+            vec![
+                config::OutputId::from(&ComponentKey::from("a.second")),
+                config::OutputId::from(&ComponentKey::from("b.second")),
+            ]
+        );
+
+        let errors = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [pipelines.suffix.transforms.first]
+            type = "test_basic"
+            inputs = ["%{inputs}"]
+            suffix = "%{unknown}"
+            increase = 1.25
+
+            [pipelines.suffix.instances.a]
+            inputs = ["in"]
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["a.first"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["Pipeline \"suffix\": Unknown parameter \"unknown\" in transform \"first\"."]
+        );
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn conflicting_stdin_and_fd_resources() {
@@ -745,6 +920,46 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn warns_of_missing_dead_letter_source() {
+        let config = r#"
+            [event_size]
+            max_bytes = 1024
+            when_exceeded = "dead_letter"
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#;
+        let warnings = load(config, Format::Toml).await.unwrap();
+        assert_eq!(
+            warnings,
+            vec![
+                "Events exceeding \"event_size.max_bytes\" are dropped, as no \"dead_letter\" source is defined"
+            ]
+        );
+
+        #[cfg(feature = "sources-dead_letter")]
+        {
+            let config = format!(
+                r#"{}
+                [sources.dead_letters]
+                type = "dead_letter"
+
+                [sinks.dead_letters_out]
+                type = "test_basic"
+                inputs = ["dead_letters"]
+                "#,
+                config
+            );
+            let warnings = load(&config, Format::Toml).await.unwrap();
+            assert!(warnings.is_empty());
+        }
+    }
+
     #[tokio::test]
     async fn cycle() {
         let errors = load(
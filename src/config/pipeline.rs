@@ -0,0 +1,377 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use toml::Value;
+use vector_config::configurable_component;
+
+use super::{builder::ConfigBuilder, validation, ComponentKey, Inputs};
+
+/// The name of the parameter holding the inputs of a pipeline instance.
+const INPUTS_PARAMETER: &str = "inputs";
+
+/// A pipeline template.
+///
+/// A pipeline template is a parametrized group of sources, transforms and sinks, which is
+/// instantiated once for each of its `instances`. The components of an instance are named
+/// `<instance>.<component>`, and the instance itself can be used as an input of other components,
+/// standing for the `output` component of the template.
+///
+/// Within the components of the template, `%{name}` is replaced by the value of the parameter
+/// `name` of the instance. In the `inputs` of a component, `%{inputs}` is replaced by the inputs of
+/// the instance, and the names of the other components of the template refer to the components of
+/// the same instance.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineTemplate {
+    /// The parameters of the template.
+    #[serde(default)]
+    pub params: IndexMap<String, PipelineParameter>,
+
+    /// The component of the template standing for an instance when the instance is used as an
+    /// input of another component.
+    #[configurable(metadata(docs::examples = "parse"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// The sources of the template.
+    #[serde(default)]
+    pub sources: IndexMap<String, Value>,
+
+    /// The transforms of the template.
+    #[serde(default)]
+    pub transforms: IndexMap<String, Value>,
+
+    /// The sinks of the template.
+    #[serde(default)]
+    pub sinks: IndexMap<String, Value>,
+
+    /// The instances of the template.
+    #[serde(default)]
+    pub instances: IndexMap<ComponentKey, PipelineInstance>,
+}
+
+/// A parameter of a pipeline template.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineParameter {
+    /// The value of the parameter for the instances which don't set it.
+    ///
+    /// If not set, the instances must set the parameter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// An instance of a pipeline template.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineInstance {
+    /// The inputs of the instance, replacing `%{inputs}` in the `inputs` of the components of the
+    /// template.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+
+    /// The values of the parameters of the template for this instance.
+    #[serde(default)]
+    pub params: IndexMap<String, String>,
+}
+
+/// Adds the components of the instances of the pipeline templates to the configuration.
+pub(super) fn expand(builder: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let pipelines = std::mem::take(&mut builder.pipelines);
+    let mut errors = Vec::new();
+    let mut outputs = IndexMap::new();
+
+    for (name, template) in &pipelines {
+        if let Err(template_errors) = template.validate() {
+            errors.extend(
+                template_errors
+                    .into_iter()
+                    .map(|error| format!("Pipeline \"{}\": {}", name, error)),
+            );
+            continue;
+        }
+
+        for (id, instance) in &template.instances {
+            if let Err(instance_errors) = template.instantiate(id, instance, builder) {
+                errors.extend(
+                    instance_errors.into_iter().map(|error| {
+                        format!("Pipeline \"{}\", instance \"{}\": {}", name, id, error)
+                    }),
+                );
+            }
+            if let Some(output) = &template.output {
+                outputs.insert(id.id().to_string(), format!("{}.{}", id, output));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Instances used as inputs stand for the output component of their template.
+    let resolve = |inputs: &mut Inputs<String>| {
+        *inputs = std::mem::take(inputs)
+            .into_iter()
+            .map(|input| outputs.get(&input).cloned().unwrap_or(input))
+            .collect();
+    };
+    for transform in builder.transforms.values_mut() {
+        resolve(&mut transform.inputs);
+    }
+    for sink in builder.sinks.values_mut() {
+        resolve(&mut sink.inputs);
+    }
+
+    Ok(())
+}
+
+impl PipelineTemplate {
+    fn components(&self) -> impl Iterator<Item = (&'static str, &String, &Value)> {
+        let sources = self
+            .sources
+            .iter()
+            .map(|(name, value)| ("source", name, value));
+        let transforms = self
+            .transforms
+            .iter()
+            .map(|(name, value)| ("transform", name, value));
+        let sinks = self.sinks.iter().map(|(name, value)| ("sink", name, value));
+        sources.chain(transforms).chain(sinks)
+    }
+
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        let names = self
+            .components()
+            .map(|(_, name, _)| ComponentKey::from(name.as_str()))
+            .collect::<Vec<_>>();
+        if let Err(name_errors) = validation::check_names(names.iter()) {
+            errors.extend(name_errors);
+        }
+
+        if self.params.contains_key(INPUTS_PARAMETER) {
+            errors.push(format!(
+                "The parameter name \"{}\" is reserved.",
+                INPUTS_PARAMETER
+            ));
+        }
+
+        let mut used = HashSet::new();
+        for (kind, name, value) in self.components() {
+            let mut unknown = Vec::new();
+            visit_strings(value, &mut |string| {
+                for parameter in parameters(string) {
+                    if parameter != INPUTS_PARAMETER && !self.params.contains_key(parameter) {
+                        unknown.push(parameter.to_string());
+                    }
+                    used.insert(parameter.to_string());
+                }
+            });
+            errors.extend(unknown.into_iter().map(|parameter| {
+                format!(
+                    "Unknown parameter \"{}\" in {} \"{}\".",
+                    parameter, kind, name
+                )
+            }));
+        }
+        for parameter in self.params.keys() {
+            if !used.contains(parameter) {
+                warn!(message = "Unused pipeline parameter.", %parameter);
+            }
+        }
+
+        if let Some(output) = &self.output {
+            if !self.transforms.contains_key(output) && !self.sources.contains_key(output) {
+                errors.push(format!(
+                    "The output \"{}\" is not a source or a transform of the template.",
+                    output
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn instantiate(
+        &self,
+        id: &ComponentKey,
+        instance: &PipelineInstance,
+        builder: &mut ConfigBuilder,
+    ) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        // Outside of the `inputs` of the components, the inputs of the instance are listed as is.
+        let inputs = instance.inputs.join(", ");
+
+        for parameter in instance.params.keys() {
+            if !self.params.contains_key(parameter) {
+                errors.push(format!("Unknown parameter \"{}\".", parameter));
+            }
+        }
+        let mut values = IndexMap::new();
+        for (name, parameter) in &self.params {
+            match instance.params.get(name).or(parameter.default.as_ref()) {
+                Some(value) => {
+                    values.insert(name.as_str(), value.as_str());
+                }
+                None => errors.push(format!("Missing parameter \"{}\".", name)),
+            }
+        }
+        if builder.sources.contains_key(id)
+            || builder.transforms.contains_key(id)
+            || builder.sinks.contains_key(id)
+        {
+            errors.push("The instance has the same name as a component.".to_string());
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        values.insert(INPUTS_PARAMETER, &inputs);
+
+        for (kind, name, value) in self.components() {
+            let key = ComponentKey::from(format!("{}.{}", id, name));
+            let mut value = value.clone();
+            if let Value::Table(table) = &mut value {
+                if let Some(Value::Array(inputs)) = table.get_mut("inputs") {
+                    *inputs = self.instance_inputs(id, instance, inputs);
+                }
+            }
+            substitute(&mut value, &values);
+
+            let result = match kind {
+                "source" => {
+                    deserialize(value).map(|source| builder.sources.insert(key, source).is_some())
+                }
+                "transform" => deserialize(value)
+                    .map(|transform| builder.transforms.insert(key, transform).is_some()),
+                _ => deserialize(value).map(|sink| builder.sinks.insert(key, sink).is_some()),
+            };
+            match result {
+                Ok(false) => {}
+                Ok(true) => errors.push(format!("Duplicate {} \"{}.{}\".", kind, id, name)),
+                Err(error) => errors.push(format!("Invalid {} \"{}\": {}", kind, name, error)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Resolves the inputs of a component of the template for the given instance.
+    fn instance_inputs(
+        &self,
+        id: &ComponentKey,
+        instance: &PipelineInstance,
+        inputs: &[Value],
+    ) -> Vec<Value> {
+        let inputs_parameter = format!("%{{{}}}", INPUTS_PARAMETER);
+        let mut resolved = Vec::new();
+        for input in inputs {
+            match input {
+                Value::String(input) if *input == inputs_parameter => {
+                    resolved.extend(instance.inputs.iter().cloned().map(Value::String));
+                }
+                Value::String(input) => {
+                    let component = input.split_once('.').map_or(input.as_str(), |(id, _)| id);
+                    if self.transforms.contains_key(component)
+                        || self.sources.contains_key(component)
+                    {
+                        resolved.push(Value::String(format!("{}.{}", id, input)));
+                    } else {
+                        resolved.push(Value::String(input.clone()));
+                    }
+                }
+                input => resolved.push(input.clone()),
+            }
+        }
+        resolved
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(value: Value) -> Result<T, String> {
+    value.try_into().map_err(|error| error.to_string())
+}
+
+/// Calls the given function with all the strings of the value, recursively.
+fn visit_strings(value: &Value, visit: &mut impl FnMut(&str)) {
+    match value {
+        Value::String(string) => visit(string),
+        Value::Array(values) => values.iter().for_each(|value| visit_strings(value, visit)),
+        Value::Table(table) => table.values().for_each(|value| visit_strings(value, visit)),
+        _ => {}
+    }
+}
+
+/// Replaces the parameters in all the strings of the value, recursively.
+fn substitute(value: &mut Value, values: &IndexMap<&str, &str>) {
+    match value {
+        Value::String(string) => {
+            for (name, value) in values {
+                *string = string.replace(&format!("%{{{}}}", name), value);
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(|value| substitute(value, values)),
+        Value::Table(table) => table
+            .iter_mut()
+            .for_each(|(_, value)| substitute(value, values)),
+        _ => {}
+    }
+}
+
+/// Returns the names of the parameters used in the string.
+fn parameters(string: &str) -> impl Iterator<Item = &str> {
+    string.split("%{").skip(1).filter_map(|part| {
+        part.split_once('}')
+            .map(|(name, _)| name)
+            .filter(|name| !name.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_parameters() {
+        assert_eq!(
+            parameters("%{a} and %{b}, not %{} nor % {c}").collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn substitutes_parameters() {
+        let mut value: Value = toml::from_str(
+            r#"
+            field = ".%{field}"
+            nested = { list = ["%{field}-%{other}", 1] }
+            "#,
+        )
+        .unwrap();
+        let values = IndexMap::from([("field", "message"), ("other", "x")]);
+        substitute(&mut value, &values);
+
+        assert_eq!(
+            value,
+            toml::from_str::<Value>(
+                r#"
+                field = ".message"
+                nested = { list = ["message-x", 1] }
+                "#
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use std::num::NonZeroUsize;
+
+use vector_config::configurable_component;
+
+/// Dedicated runtime of a component.
+///
+/// When set, the component runs on its own pool of worker threads instead of the worker threads
+/// shared by all components, so that a component using a lot of CPU can't starve the others, and
+/// the other way around.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentRuntimeConfig {
+    /// The number of worker threads of the component.
+    pub threads: NonZeroUsize,
+
+    /// The IDs of the CPUs to pin the threads of the component to.
+    ///
+    /// By default, the threads can run on any CPU. Only supported on Linux.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpu_affinity: Vec<usize>,
+
+    /// The maximum number of threads the component can use for blocking operations.
+    ///
+    /// Defaults to 512.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_blocking_threads: Option<NonZeroUsize>,
+}
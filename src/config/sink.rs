@@ -1,6 +1,10 @@
+use std::{path::PathBuf, time::Duration};
+
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
+use lookup::lookup_v2::ConfigTargetPath;
 use serde::Serialize;
+use serde_with::serde_as;
 use vector_buffers::{BufferConfig, BufferType};
 use vector_config::{configurable_component, Configurable, NamedComponent};
 use vector_core::{
@@ -8,10 +12,14 @@ use vector_core::{
     sink::VectorSink,
 };
 
-use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{util::UriSerde, Healthcheck, Sinks};
+use super::{
+    id::Inputs, schema, ComponentEnabled, ComponentKey, ComponentRuntimeConfig, ProxyConfig,
+    Resource, DEFAULT_DRAIN_TIMEOUT,
+};
+use crate::sinks::{util::UriSerde, Healthcheck, HealthcheckFactory, Sinks};
 
 /// Fully resolved sink component.
+#[serde_as]
 #[configurable_component]
 #[configurable(metadata(docs::component_base_type = "sink"))]
 #[derive(Clone, Debug)]
@@ -22,6 +30,10 @@ where
     #[configurable(derived)]
     pub inputs: Inputs<T>,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "ComponentEnabled::is_default")]
+    pub enabled: ComponentEnabled,
+
     /// The full URI to make HTTP healthcheck requests to.
     ///
     /// This must be a valid URI, which requires at least the scheme and host. All other
@@ -40,6 +52,28 @@ where
     )]
     pub buffer: BufferConfig,
 
+    /// How long to wait, in seconds, for the sink to drain its buffer when it is removed by a
+    /// configuration reload.
+    ///
+    /// The events left in its buffer once this deadline has passed are abandoned. Defaults to 30
+    /// seconds.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drain_timeout_secs: Option<u64>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypt_fields: Option<FieldEncryptionConfig>,
+
+    /// The soft limit, in bytes, on the approximate size of the events held in flight by the sink.
+    ///
+    /// Events are held in flight from the time they are read from the buffer of the sink until they
+    /// are delivered or dropped. Once exceeded, the sink applies the `memory_budget.when_exceeded`
+    /// policy.
+    #[configurable(metadata(docs::advanced, docs::examples = 67108864))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit_bytes: Option<usize>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -47,7 +81,23 @@ where
     )]
     proxy: ProxyConfig,
 
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<ComponentRuntimeConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub schema: SinkSchemaOptions,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<ShadowConfig>,
+
     #[serde(flatten)]
+    #[serde_as(as = "crate::plugins::SinkComponents")]
     #[configurable(metadata(docs::hidden))]
     pub inner: Sinks,
 }
@@ -63,7 +113,14 @@ where
     {
         SinkOuter {
             inputs: Inputs::from_iter(inputs),
+            enabled: ComponentEnabled::default(),
             buffer: Default::default(),
+            drain_timeout_secs: None,
+            encrypt_fields: None,
+            memory_limit_bytes: None,
+            runtime: None,
+            schema: SinkSchemaOptions::default(),
+            shadow: None,
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
             inner: inner.into(),
@@ -73,6 +130,9 @@ where
 
     pub fn resources(&self, id: &ComponentKey) -> Vec<Resource> {
         let mut resources = self.inner.resources();
+        if let Some(shadow) = &self.shadow {
+            resources.extend(shadow.sink.resources());
+        }
         for stage in self.buffer.stages() {
             match stage {
                 BufferType::Memory { .. } => {}
@@ -82,6 +142,12 @@ where
         resources
     }
 
+    /// How long to wait for the sink to drain its buffer when it is removed.
+    pub fn drain_timeout(&self) -> Duration {
+        self.drain_timeout_secs
+            .map_or(DEFAULT_DRAIN_TIMEOUT, Duration::from_secs)
+    }
+
     pub fn healthcheck(&self) -> SinkHealthcheckOptions {
         if self.healthcheck_uri.is_some() && self.healthcheck.uri.is_some() {
             warn!("Both `healthcheck.uri` and `healthcheck_uri` options are specified. Using value of `healthcheck.uri`.")
@@ -119,8 +185,15 @@ where
     {
         SinkOuter {
             inputs: Inputs::from_iter(inputs),
+            enabled: self.enabled,
             inner: self.inner,
             buffer: self.buffer,
+            drain_timeout_secs: self.drain_timeout_secs,
+            encrypt_fields: self.encrypt_fields,
+            memory_limit_bytes: self.memory_limit_bytes,
+            runtime: self.runtime,
+            schema: self.schema,
+            shadow: self.shadow,
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
@@ -142,6 +215,42 @@ pub struct SinkHealthcheckOptions {
     /// components -- port, path, etc -- are allowed as well.
     #[configurable(validation(format = "uri"))]
     pub uri: Option<UriSerde>,
+
+    /// How often, in seconds, to check the health of the sink again while it is running.
+    ///
+    /// By default, the health of the sink is only checked when Vector starts up. Periodic
+    /// healthchecks are only run when healthchecks are enabled, and only supported by the `http`,
+    /// `datadog_events`, `datadog_logs`, `datadog_metrics`, `datadog_traces`, `splunk_hec_logs`,
+    /// and `splunk_hec_metrics` sinks.
+    #[configurable(metadata(docs::examples = 30))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u64>,
+
+    /// The number of consecutive failed periodic healthchecks after which the sink is quarantined.
+    ///
+    /// The sink is released from quarantine by the next healthcheck which passes.
+    #[configurable(validation(range(min = 1)))]
+    #[serde(skip_serializing_if = "is_default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "vector_core::serde::skip_serializing_if_default")]
+    pub quarantine: QuarantinePolicy,
+}
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+const fn is_default_failure_threshold(threshold: &u32) -> bool {
+    *threshold == DEFAULT_FAILURE_THRESHOLD
+}
+
+impl SinkHealthcheckOptions {
+    /// How often to check the health of the sink while it is running, if at all.
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+    }
 }
 
 impl Default for SinkHealthcheckOptions {
@@ -149,25 +258,234 @@ impl Default for SinkHealthcheckOptions {
         Self {
             enabled: true,
             uri: None,
+            interval_secs: None,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            quarantine: QuarantinePolicy::default(),
         }
     }
 }
 
 impl From<bool> for SinkHealthcheckOptions {
     fn from(enabled: bool) -> Self {
-        Self { enabled, uri: None }
+        Self {
+            enabled,
+            ..Default::default()
+        }
     }
 }
 
 impl From<UriSerde> for SinkHealthcheckOptions {
     fn from(uri: UriSerde) -> Self {
         Self {
-            enabled: true,
             uri: Some(uri),
+            ..Default::default()
         }
     }
 }
 
+/// What a quarantined sink does with the events sent to it.
+///
+/// A sink is quarantined after `failure_threshold` consecutive periodic healthchecks have failed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuarantinePolicy {
+    /// Stop reading events, leaving them in the buffer of the sink.
+    ///
+    /// Once the buffer is full, the buffer applies its `when_full` behavior.
+    #[default]
+    Buffer,
+
+    /// Drop the events.
+    ///
+    /// They are considered as delivered by the sources which wait for their acknowledgement.
+    Shed,
+}
+
+/// Schema options of a sink.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SinkSchemaOptions {
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<SchemaValidationConfig>,
+}
+
+/// Validation of the events sent to the sink against a JSON Schema.
+///
+/// Events are validated against the schema before they reach the sink, as their JSON
+/// representation: the fields of log and trace events, and the `native_json` representation of
+/// metric events. Exactly one of `json_schema` and `json_schema_path` must be set.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaValidationConfig {
+    /// The JSON Schema the events must conform to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_schema: Option<toml::Value>,
+
+    /// The path of a file holding the JSON Schema the events must conform to.
+    #[configurable(metadata(docs::examples = "/etc/vector/schemas/orders.json"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_schema_path: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub on_failure: SchemaValidationFailure,
+}
+
+/// What to do with the events which don't conform to the schema.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaValidationFailure {
+    /// Drop the events.
+    ///
+    /// They are considered as delivered by the sources which wait for their acknowledgement.
+    #[default]
+    Drop,
+
+    /// Drop the events, and reject them.
+    ///
+    /// The sources which wait for their acknowledgement report a failure to their clients, when
+    /// they are able to.
+    Reject,
+}
+
+/// Mirroring of a sample of the events sent to the sink to a secondary sink.
+///
+/// The secondary sink is configured inline, with the `type` and the options of any sink. It
+/// receives a copy of the sampled events as the sink receives them. Copies are sent on a
+/// best-effort basis: they are excluded from the acknowledgement of the events, and they are
+/// dropped while the secondary sink can not keep up rather than slowing the sink down. This allows
+/// validating a new backend against production traffic before switching over to it.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ShadowConfig {
+    /// The percentage of the requests of the sink to mirror.
+    ///
+    /// Each batch of events read by the sink is mirrored with this probability.
+    #[configurable(validation(range(min = 0.0, max = 100.0)))]
+    #[configurable(metadata(docs::examples = 10.0))]
+    #[serde(default = "default_shadow_percentage")]
+    pub percentage: f64,
+
+    #[serde(flatten)]
+    #[serde_as(as = "crate::plugins::SinkComponents")]
+    #[configurable(metadata(docs::hidden))]
+    pub sink: Sinks,
+}
+
+const fn default_shadow_percentage() -> f64 {
+    100.0
+}
+
+/// Encryption of fields of the events sent to the sink.
+///
+/// The fields are encrypted just before the events reach the sink, so that the storage backend
+/// only receives their ciphertext, while the rest of the pipeline processes their plaintext. They
+/// are encrypted with AES-256-GCM using a data key, which is generated when the sink is built and
+/// rotated regularly. The data key is wrapped by a key encryption key.
+///
+/// Each event with encrypted fields gets an envelope in its metadata, at `%field_encryption`,
+/// holding the algorithm, the wrapped data key, the ID of the key encryption key, and the paths of
+/// the encrypted fields, which are needed to decrypt the fields. The envelope is sent along with the
+/// events by the sinks encoding their metadata, such as the `vector` sink.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FieldEncryptionConfig {
+    /// The fields to encrypt.
+    ///
+    /// The values of the fields are encoded as JSON, encrypted, and replaced by the base64 encoding
+    /// of the nonce, the ciphertext and the authentication tag. The path of each field is used as
+    /// additional authenticated data. Missing fields are skipped, and events with none of the
+    /// fields are left untouched.
+    #[configurable(metadata(docs::examples = "user.email"))]
+    #[configurable(metadata(docs::examples = "card_number"))]
+    pub fields: Vec<ConfigTargetPath>,
+
+    /// The number of fields encrypted with a data key, after which a new data key is generated.
+    ///
+    /// As the nonces are random, encrypting more than 2^32 fields with the same data key would put
+    /// their confidentiality at risk.
+    #[serde(default = "default_data_key_max_seals")]
+    pub data_key_max_seals: u64,
+
+    /// The age of a data key, after which a new data key is generated.
+    #[serde(default = "default_data_key_rotation_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub data_key_rotation_secs: u64,
+
+    #[configurable(derived)]
+    pub key: KeyEncryptionKeyConfig,
+}
+
+const fn default_data_key_max_seals() -> u64 {
+    1 << 32
+}
+
+const fn default_data_key_rotation_secs() -> u64 {
+    86400
+}
+
+/// The key encryption key wrapping the data key.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The kind of key encryption key."))]
+pub enum KeyEncryptionKeyConfig {
+    /// A key of AWS KMS.
+    ///
+    /// The data key is generated and wrapped by AWS KMS.
+    #[cfg(feature = "field-encryption-aws_kms")]
+    AwsKms(AwsKmsKeyConfig),
+
+    /// A local AES-256 key.
+    ///
+    /// The data key is wrapped with AES-256-GCM.
+    File(FileKeyConfig),
+}
+
+/// A key of AWS KMS.
+#[cfg(feature = "field-encryption-aws_kms")]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct AwsKmsKeyConfig {
+    /// The ID, ARN or alias of the KMS key wrapping the data key.
+    #[configurable(metadata(docs::examples = "alias/vector"))]
+    pub key_id: String,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub region: crate::aws::RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: crate::aws::AwsAuthentication,
+
+    #[configurable(derived)]
+    pub tls: Option<crate::tls::TlsConfig>,
+}
+
+/// A local AES-256 key.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FileKeyConfig {
+    /// The path of the file holding the key, as 32 raw bytes or as their base64 encoding.
+    #[configurable(metadata(docs::examples = "/etc/vector/keys/fields.key"))]
+    pub path: PathBuf,
+
+    /// The ID of the key, stored in the envelope of the events.
+    ///
+    /// Defaults to the path of the file.
+    #[configurable(metadata(docs::examples = "fields-2024-01"))]
+    #[serde(default)]
+    pub key_id: Option<String>,
+}
+
 /// Generalized interface for describing and building sink components.
 #[async_trait]
 #[enum_dispatch]
@@ -183,6 +501,23 @@ pub trait SinkConfig: NamedComponent + core::fmt::Debug + Send + Sync {
     /// returned.
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)>;
 
+    /// Builds the healthchecks run periodically while the sink is running.
+    ///
+    /// The returned factory is built once, and called for each healthcheck, which must not build
+    /// the sink again. By default, `None` is returned, as the sink doesn't support periodic
+    /// healthchecks.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs while building the healthchecks, an error variant explaining the issue is
+    /// returned.
+    async fn build_healthcheck_factory(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<Option<HealthcheckFactory>> {
+        Ok(None)
+    }
+
     /// Gets the input configuration for this sink.
     fn input(&self) -> Input;
 
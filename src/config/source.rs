@@ -9,6 +9,7 @@ use vector_config::{
 use vector_config_common::attributes::CustomAttribute;
 use vector_config_common::schema::{SchemaGenerator, SchemaObject};
 use vector_core::{
+    buffers::EventPriority,
     config::{
         AcknowledgementsConfig, GlobalOptions, LogNamespace, SourceAcknowledgementsConfig,
         SourceOutput,
@@ -16,8 +17,13 @@ use vector_core::{
     source::Source,
 };
 
-use super::{schema, ComponentKey, ProxyConfig, Resource};
-use crate::{shutdown::ShutdownSignal, SourceSender};
+use super::{schema, ComponentEnabled, ComponentKey, ProxyConfig, Resource};
+#[cfg(feature = "coordination")]
+use crate::coordination::CoordinationConfig;
+use crate::{
+    coordination::HaMode, shutdown::ShutdownSignal, source_log_schema::SourceLogSchema,
+    tenancy::TenancyConfig, SourceSender,
+};
 
 pub type BoxedSource = Box<dyn SourceConfig>;
 
@@ -50,6 +56,10 @@ impl<T: SourceConfig + 'static> From<T> for BoxedSource {
 #[configurable(metadata(docs::component_base_type = "source"))]
 #[derive(Clone, Debug)]
 pub struct SourceOuter {
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "ComponentEnabled::is_default")]
+    pub enabled: ComponentEnabled,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -57,6 +67,36 @@ pub struct SourceOuter {
     )]
     pub proxy: ProxyConfig,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenancy: Option<TenancyConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_schema: Option<SourceLogSchema>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub ha_mode: HaMode,
+
+    /// The priority of the events produced by the source.
+    ///
+    /// Buffers which drop events when full shed the events of lower priorities first. The
+    /// priority is held under `vector.priority` in the metadata of the events, and can be changed
+    /// in VRL, for example with `%vector.priority = "low"`. By default, events are of the `normal`
+    /// priority.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<EventPriority>,
+
+    #[cfg(feature = "coordination")]
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordination: Option<CoordinationConfig>,
+
     #[serde(default, skip)]
     pub sink_acknowledgements: bool,
 
@@ -68,7 +108,14 @@ pub struct SourceOuter {
 impl SourceOuter {
     pub(crate) fn new<I: Into<BoxedSource>>(inner: I) -> Self {
         Self {
+            enabled: ComponentEnabled::default(),
             proxy: Default::default(),
+            tenancy: None,
+            log_schema: None,
+            ha_mode: HaMode::default(),
+            priority: None,
+            #[cfg(feature = "coordination")]
+            coordination: None,
             sink_acknowledgements: false,
             inner: inner.into(),
         }
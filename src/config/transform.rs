@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use dyn_clone::DynClone;
@@ -18,7 +19,9 @@ use vector_core::{
 
 use super::schema::Options as SchemaOptions;
 use super::OutputId;
-use super::{id::Inputs, ComponentKey};
+use super::{
+    id::Inputs, ComponentEnabled, ComponentKey, ComponentRuntimeConfig, DEFAULT_DRAIN_TIMEOUT,
+};
 
 pub type BoxedTransform = Box<dyn TransformConfig>;
 
@@ -57,11 +60,64 @@ where
     #[configurable(derived)]
     pub inputs: Inputs<T>,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "ComponentEnabled::is_default")]
+    pub enabled: ComponentEnabled,
+
+    /// How long to wait, in seconds, for the transform to drain its input into its downstream
+    /// components when it is removed by a configuration reload.
+    ///
+    /// The events left in its input once this deadline has passed are abandoned. Defaults to 30
+    /// seconds.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drain_timeout_secs: Option<u64>,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ordered_merge: Option<OrderedMergeConfig>,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<ComponentRuntimeConfig>,
+
     #[configurable(metadata(docs::hidden))]
     #[serde(flatten)]
     pub inner: BoxedTransform,
 }
 
+/// Ordered merge of the inputs of a transform.
+///
+/// When set, the events received by the transform are merged by timestamp, so that the events of
+/// all of its inputs are processed in chronological order. Events are held for at most the
+/// lateness window, and events without a timestamp are passed through as they are received.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OrderedMergeConfig {
+    /// How late an event can be received, in milliseconds, and still be merged in order.
+    ///
+    /// Events are held until an event with a timestamp later by this much has been received, and
+    /// at most for this long.
+    #[serde(default = "default_max_lateness_ms")]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub max_lateness_ms: u64,
+
+    /// The maximum number of events held for ordering.
+    ///
+    /// Once reached, the earliest events are released without waiting for the lateness window.
+    #[serde(default = "default_max_events")]
+    pub max_events: usize,
+}
+
+const fn default_max_lateness_ms() -> u64 {
+    1000
+}
+
+const fn default_max_events() -> usize {
+    10_000
+}
+
 impl<T> TransformOuter<T>
 where
     T: Configurable + Serialize,
@@ -73,7 +129,20 @@ where
     {
         let inputs = Inputs::from_iter(inputs);
         let inner = inner.into();
-        TransformOuter { inputs, inner }
+        TransformOuter {
+            inputs,
+            enabled: ComponentEnabled::default(),
+            drain_timeout_secs: None,
+            ordered_merge: None,
+            runtime: None,
+            inner,
+        }
+    }
+
+    /// How long to wait for the transform to drain its input when it is removed.
+    pub fn drain_timeout(&self) -> Duration {
+        self.drain_timeout_secs
+            .map_or(DEFAULT_DRAIN_TIMEOUT, Duration::from_secs)
     }
 
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> TransformOuter<U>
@@ -91,6 +160,10 @@ where
     {
         TransformOuter {
             inputs: Inputs::from_iter(inputs),
+            enabled: self.enabled,
+            drain_timeout_secs: self.drain_timeout_secs,
+            ordered_merge: self.ordered_merge,
+            runtime: self.runtime,
             inner: self.inner,
         }
     }
@@ -3,7 +3,8 @@ use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
 use std::{collections::HashMap, path::PathBuf};
-use vector_core::internal_event::DEFAULT_OUTPUT;
+use vector_config::NamedComponent;
+use vector_core::{config::EventSizePolicy, internal_event::DEFAULT_OUTPUT};
 
 use super::{builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource};
 
@@ -377,6 +378,20 @@ pub fn warnings(config: &Config) -> Vec<String> {
         }
     }
 
+    let event_size = &config.global.event_size;
+    if event_size.max_bytes.is_some()
+        && event_size.when_exceeded == Some(EventSizePolicy::DeadLetter)
+        && !config
+            .sources
+            .values()
+            .any(|source| source.inner.get_component_name() == "dead_letter")
+    {
+        warnings.push(
+            "Events exceeding \"event_size.max_bytes\" are dropped, as no \"dead_letter\" source is defined"
+                .to_owned(),
+        );
+    }
+
     warnings
 }
 
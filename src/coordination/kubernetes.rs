@@ -0,0 +1,157 @@
+use std::{path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use k8s_openapi::{
+    api::coordination::v1::{Lease as LeaseObject, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta},
+};
+use kube::{
+    api::{Api, PostParams},
+    config::{self, KubeConfigOptions},
+    Client, Config as ClientConfig,
+};
+use vector_config::configurable_component;
+
+use super::Lease;
+
+/// Kubernetes Lease coordination configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesLeaseConfig {
+    /// The namespace of the Lease object.
+    ///
+    /// By default, the namespace of the kubeconfig, or the namespace Vector runs in, is used.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub namespace: Option<String>,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    pub kube_config_file: Option<PathBuf>,
+}
+
+impl KubernetesLeaseConfig {
+    pub(super) async fn build(&self, lease_name: &str) -> crate::Result<KubernetesLease> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+        let api = match &self.namespace {
+            Some(namespace) => Api::namespaced(client, namespace),
+            None => Api::default_namespaced(client),
+        };
+
+        Ok(KubernetesLease {
+            api,
+            name: lease_name.to_owned(),
+        })
+    }
+}
+
+pub(super) struct KubernetesLease {
+    api: Api<LeaseObject>,
+    name: String,
+}
+
+/// Whether the request failed because the Lease object was created or updated concurrently.
+fn is_conflict(error: &kube::Error) -> bool {
+    matches!(error, kube::Error::Api(response) if response.code == 409)
+}
+
+#[async_trait]
+impl Lease for KubernetesLease {
+    async fn try_acquire(&mut self, identity: &str, duration: Duration) -> crate::Result<bool> {
+        let now = MicroTime(Utc::now());
+        let duration_secs = i32::try_from(duration.as_secs()).unwrap_or(i32::MAX);
+
+        let Some(mut lease) = self.api.get_opt(&self.name).await? else {
+            let lease = LeaseObject {
+                metadata: ObjectMeta {
+                    name: Some(self.name.clone()),
+                    ..Default::default()
+                },
+                spec: Some(LeaseSpec {
+                    holder_identity: Some(identity.to_owned()),
+                    lease_duration_seconds: Some(duration_secs),
+                    acquire_time: Some(now.clone()),
+                    renew_time: Some(now),
+                    lease_transitions: Some(0),
+                }),
+            };
+            return match self.api.create(&PostParams::default(), &lease).await {
+                Ok(_) => Ok(true),
+                Err(error) if is_conflict(&error) => Ok(false),
+                Err(error) => Err(error.into()),
+            };
+        };
+
+        let spec = lease.spec.get_or_insert_with(Default::default);
+        let held = spec.holder_identity.as_deref() == Some(identity);
+        if !held {
+            let expired = match (spec.holder_identity.as_deref(), &spec.renew_time) {
+                (None | Some(""), _) | (_, None) => true,
+                (Some(_), Some(renew_time)) => {
+                    let held_for = i64::from(spec.lease_duration_seconds.unwrap_or(0));
+                    renew_time.0 + chrono::Duration::seconds(held_for) < now.0
+                }
+            };
+            if !expired {
+                return Ok(false);
+            }
+            spec.holder_identity = Some(identity.to_owned());
+            spec.acquire_time = Some(now.clone());
+            spec.lease_transitions = Some(spec.lease_transitions.unwrap_or(0) + 1);
+        }
+        spec.lease_duration_seconds = Some(duration_secs);
+        spec.renew_time = Some(now);
+
+        // The update is rejected if the Lease object changed since it was read, in which case
+        // another instance renewed or acquired it in the meantime.
+        match self
+            .api
+            .replace(&self.name, &PostParams::default(), &lease)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(error) if is_conflict(&error) => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn release(&mut self, identity: &str) -> crate::Result<()> {
+        let Some(mut lease) = self.api.get_opt(&self.name).await? else {
+            return Ok(());
+        };
+        let Some(spec) = lease.spec.as_mut() else {
+            return Ok(());
+        };
+        if spec.holder_identity.as_deref() != Some(identity) {
+            return Ok(());
+        }
+        spec.holder_identity = None;
+        spec.acquire_time = None;
+        spec.renew_time = None;
+
+        match self
+            .api
+            .replace(&self.name, &PostParams::default(), &lease)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(error) if is_conflict(&error) => Ok(()),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
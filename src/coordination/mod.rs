@@ -0,0 +1,370 @@
+//! Coordination of the instances of Vector running a source.
+//!
+//! Sources configured with `ha_mode = "active_passive"` only run on the instance of their group
+//! holding a lease, so that pull-based sources can be deployed on several instances without
+//! receiving duplicate events. The other instances wait for the lease to become available, and
+//! take over when the instance holding it stops renewing it.
+#[cfg(feature = "coordination")]
+mod kubernetes;
+#[cfg(feature = "coordination")]
+mod redis;
+
+#[cfg(feature = "coordination")]
+use std::{num::NonZeroU64, time::Duration};
+
+#[cfg(feature = "coordination")]
+use async_trait::async_trait;
+#[cfg(feature = "coordination")]
+use tokio::time::{sleep, Instant};
+use vector_config::configurable_component;
+
+#[cfg(feature = "coordination")]
+use crate::{
+    internal_events::{LeaderElectionError, LeadershipAcquired, LeadershipLost},
+    shutdown::ShutdownSignal,
+    sources::Source,
+};
+
+/// How a source runs when several instances of Vector share its configuration.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HaMode {
+    /// The source runs on every instance.
+    #[default]
+    ActiveActive,
+
+    /// The source only runs on the instance holding the lease configured in `coordination`.
+    ///
+    /// If the lease is lost, the source is stopped with an error, which stops Vector.
+    ActivePassive,
+}
+
+/// Configuration of the lease held by the instance of Vector running a source.
+#[cfg(feature = "coordination")]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CoordinationConfig {
+    /// The name of the lease.
+    ///
+    /// The instances sharing a lease of the same name form a group, in which the source only
+    /// runs on one instance at a time.
+    #[configurable(metadata(docs::examples = "vector-http-client"))]
+    pub lease_name: String,
+
+    /// The identity of this instance in the group.
+    ///
+    /// By default, the hostname followed by a random suffix is used.
+    #[configurable(metadata(docs::examples = "vector-0"))]
+    pub identity: Option<String>,
+
+    /// How long the lease is held for without being renewed, in seconds.
+    ///
+    /// When the instance holding the lease stops, another instance takes over after this delay.
+    #[serde(default = "default_lease_duration_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub lease_duration_secs: NonZeroU64,
+
+    /// How often the lease is renewed, or its acquisition retried, in seconds.
+    ///
+    /// This must be less than `lease_duration_secs`.
+    #[serde(default = "default_renew_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub renew_interval_secs: NonZeroU64,
+
+    #[configurable(derived)]
+    pub backend: CoordinationBackend,
+}
+
+#[cfg(feature = "coordination")]
+fn default_lease_duration_secs() -> NonZeroU64 {
+    NonZeroU64::new(15).expect("static non-zero number")
+}
+
+#[cfg(feature = "coordination")]
+fn default_renew_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(5).expect("static non-zero number")
+}
+
+/// The service the lease is stored in.
+#[cfg(feature = "coordination")]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of coordination backend."))]
+pub enum CoordinationBackend {
+    /// Store the lease in a Kubernetes [Lease][lease] object.
+    ///
+    /// [lease]: https://kubernetes.io/docs/concepts/architecture/leases/
+    KubernetesLease(kubernetes::KubernetesLeaseConfig),
+
+    /// Store the lease in a Redis key which expires when the lease isn't renewed.
+    Redis(redis::RedisLeaseConfig),
+}
+
+/// A lease held by at most one instance of a group at a time.
+#[cfg(feature = "coordination")]
+#[async_trait]
+trait Lease: Send + Sync {
+    /// Acquires the lease if it's available, or renews it if it's already held by `identity`.
+    ///
+    /// Returns whether the lease is held by `identity`.
+    async fn try_acquire(&mut self, identity: &str, duration: Duration) -> crate::Result<bool>;
+
+    /// Releases the lease if it's held by `identity`, so that another instance can take over
+    /// without waiting for it to expire.
+    async fn release(&mut self, identity: &str) -> crate::Result<()>;
+}
+
+/// Elects the instance of a group running a source.
+#[cfg(feature = "coordination")]
+pub struct LeaderElector {
+    lease: Box<dyn Lease>,
+    lease_name: String,
+    identity: String,
+    lease_duration: Duration,
+    renew_interval: Duration,
+}
+
+#[cfg(feature = "coordination")]
+impl LeaderElector {
+    /// Builds the elector of the instance running a source in the given mode.
+    ///
+    /// Returns `None` if the source runs on every instance.
+    pub async fn build(
+        mode: HaMode,
+        config: Option<&CoordinationConfig>,
+    ) -> crate::Result<Option<Self>> {
+        let config = match (mode, config) {
+            (HaMode::ActiveActive, _) => return Ok(None),
+            (HaMode::ActivePassive, Some(config)) => config,
+            (HaMode::ActivePassive, None) => {
+                return Err(
+                    "`coordination` must be configured when `ha_mode` is `active_passive`".into(),
+                )
+            }
+        };
+        if config.renew_interval_secs >= config.lease_duration_secs {
+            return Err("`renew_interval_secs` must be less than `lease_duration_secs`".into());
+        }
+
+        let lease: Box<dyn Lease> = match &config.backend {
+            CoordinationBackend::KubernetesLease(backend) => {
+                Box::new(backend.build(&config.lease_name).await?)
+            }
+            CoordinationBackend::Redis(backend) => {
+                Box::new(backend.build(&config.lease_name).await?)
+            }
+        };
+        let identity = match &config.identity {
+            Some(identity) => identity.clone(),
+            None => format!(
+                "{}-{}",
+                crate::get_hostname()?,
+                uuid::Uuid::new_v4().simple()
+            ),
+        };
+
+        Ok(Some(Self {
+            lease,
+            lease_name: config.lease_name.clone(),
+            identity,
+            lease_duration: Duration::from_secs(config.lease_duration_secs.get()),
+            renew_interval: Duration::from_secs(config.renew_interval_secs.get()),
+        }))
+    }
+
+    /// Runs the source once this instance acquires the lease, for as long as it holds it.
+    ///
+    /// The source is dropped without being run if shut down before the lease is acquired.
+    pub fn run(mut self, mut shutdown: ShutdownSignal, source: Source) -> Source {
+        Box::pin(async move {
+            tokio::select! {
+                biased;
+
+                _ = &mut shutdown => return Ok(()),
+                _ = self.acquire() => {}
+            }
+            drop(shutdown);
+
+            let result = tokio::select! {
+                result = source => result,
+                _ = self.hold() => return Err(()),
+            };
+
+            if let Err(error) = self.lease.release(&self.identity).await {
+                emit!(LeaderElectionError {
+                    lease_name: &self.lease_name,
+                    error,
+                });
+            }
+            result
+        })
+    }
+
+    /// Waits until the lease is acquired.
+    async fn acquire(&mut self) {
+        loop {
+            match self
+                .lease
+                .try_acquire(&self.identity, self.lease_duration)
+                .await
+            {
+                Ok(true) => {
+                    emit!(LeadershipAcquired {
+                        lease_name: &self.lease_name,
+                        identity: &self.identity,
+                    });
+                    return;
+                }
+                Ok(false) => {}
+                Err(error) => emit!(LeaderElectionError {
+                    lease_name: &self.lease_name,
+                    error,
+                }),
+            }
+            sleep(self.renew_interval).await;
+        }
+    }
+
+    /// Renews the lease until it's lost.
+    ///
+    /// The lease is considered lost once it's held by another instance, or when it could expire
+    /// before the next renewal because the previous ones failed.
+    async fn hold(&mut self) {
+        let mut renewed_at = Instant::now();
+        loop {
+            sleep(self.renew_interval).await;
+            match self
+                .lease
+                .try_acquire(&self.identity, self.lease_duration)
+                .await
+            {
+                Ok(true) => renewed_at = Instant::now(),
+                Ok(false) => break,
+                Err(error) => {
+                    emit!(LeaderElectionError {
+                        lease_name: &self.lease_name,
+                        error,
+                    });
+                    if renewed_at.elapsed() + self.renew_interval >= self.lease_duration {
+                        break;
+                    }
+                }
+            }
+        }
+        emit!(LeadershipLost {
+            lease_name: &self.lease_name,
+            identity: &self.identity,
+        });
+    }
+}
+
+#[cfg(all(test, feature = "coordination"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures::FutureExt;
+    use tokio::sync::{mpsc, oneshot};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct MemoryLease(Arc<Mutex<Option<(String, Instant)>>>);
+
+    #[async_trait]
+    impl Lease for MemoryLease {
+        async fn try_acquire(&mut self, identity: &str, duration: Duration) -> crate::Result<bool> {
+            let mut holder = self.0.lock().unwrap();
+            match holder.as_ref() {
+                Some((held_by, expires_at))
+                    if held_by != identity && *expires_at > Instant::now() =>
+                {
+                    Ok(false)
+                }
+                _ => {
+                    *holder = Some((identity.to_owned(), Instant::now() + duration));
+                    Ok(true)
+                }
+            }
+        }
+
+        async fn release(&mut self, identity: &str) -> crate::Result<()> {
+            let mut holder = self.0.lock().unwrap();
+            if matches!(holder.as_ref(), Some((held_by, _)) if held_by == identity) {
+                *holder = None;
+            }
+            Ok(())
+        }
+    }
+
+    fn elector(lease: &MemoryLease, identity: &str) -> LeaderElector {
+        LeaderElector {
+            lease: Box::new(lease.clone()),
+            lease_name: "test".to_owned(),
+            identity: identity.to_owned(),
+            lease_duration: Duration::from_secs(15),
+            renew_interval: Duration::from_secs(5),
+        }
+    }
+
+    fn source(
+        identity: &'static str,
+        started: mpsc::UnboundedSender<&'static str>,
+        stop: oneshot::Receiver<()>,
+    ) -> Source {
+        async move {
+            started.send(identity).unwrap();
+            let _ = stop.await;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn runs_source_on_one_instance_at_a_time() {
+        let lease = MemoryLease::default();
+        let (started_tx, mut started_rx) = mpsc::unbounded_channel();
+
+        let (stop_first, stop) = oneshot::channel();
+        let first = tokio::spawn(elector(&lease, "first").run(
+            ShutdownSignal::noop(),
+            source("first", started_tx.clone(), stop),
+        ));
+        assert_eq!(started_rx.recv().await, Some("first"));
+
+        let (stop_second, stop) = oneshot::channel();
+        let second = tokio::spawn(
+            elector(&lease, "second")
+                .run(ShutdownSignal::noop(), source("second", started_tx, stop)),
+        );
+        sleep(Duration::from_secs(60)).await;
+        assert!(started_rx.try_recv().is_err());
+
+        // The lease is released when the source stops, so the other instance takes over.
+        stop_first.send(()).unwrap();
+        assert_eq!(first.await.unwrap(), Ok(()));
+        assert_eq!(started_rx.recv().await, Some("second"));
+
+        stop_second.send(()).unwrap();
+        assert_eq!(second.await.unwrap(), Ok(()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_source_when_lease_is_lost() {
+        let lease = MemoryLease::default();
+        let (started_tx, mut started_rx) = mpsc::unbounded_channel();
+        let (_stop, stop) = oneshot::channel();
+        let run = tokio::spawn(
+            elector(&lease, "first").run(ShutdownSignal::noop(), source("first", started_tx, stop)),
+        );
+        assert_eq!(started_rx.recv().await, Some("first"));
+
+        *lease.0.lock().unwrap() = Some((
+            "other".to_owned(),
+            Instant::now() + Duration::from_secs(3600),
+        ));
+        assert_eq!(run.await.unwrap(), Err(()));
+    }
+}
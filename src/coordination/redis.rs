@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::{aio::ConnectionManager, Script};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::Lease;
+
+// Sets the key to the identity if it's missing, or extends its expiration if it's already set to
+// the identity.
+const ACQUIRE_SCRIPT: &str = r#"
+local holder = redis.call("GET", KEYS[1])
+if holder == false then
+    redis.call("SET", KEYS[1], ARGV[1], "PX", ARGV[2])
+    return 1
+elseif holder == ARGV[1] then
+    redis.call("PEXPIRE", KEYS[1], ARGV[2])
+    return 1
+end
+return 0
+"#;
+
+// Deletes the key if it's set to the identity.
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// Redis coordination configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisLeaseConfig {
+    /// The Redis URL to connect to.
+    ///
+    /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub endpoint: SensitiveString,
+}
+
+impl RedisLeaseConfig {
+    pub(super) async fn build(&self, lease_name: &str) -> crate::Result<RedisLease> {
+        let client = redis::Client::open(self.endpoint.inner())?;
+        let connection = client.get_tokio_connection_manager().await?;
+        Ok(RedisLease {
+            connection,
+            key: lease_name.to_owned(),
+        })
+    }
+}
+
+pub(super) struct RedisLease {
+    connection: ConnectionManager,
+    key: String,
+}
+
+#[async_trait]
+impl Lease for RedisLease {
+    async fn try_acquire(&mut self, identity: &str, duration: Duration) -> crate::Result<bool> {
+        let acquired: i64 = Script::new(ACQUIRE_SCRIPT)
+            .key(&self.key)
+            .arg(identity)
+            .arg(duration.as_millis() as u64)
+            .invoke_async(&mut self.connection)
+            .await?;
+        Ok(acquired == 1)
+    }
+
+    async fn release(&mut self, identity: &str) -> crate::Result<()> {
+        let _: i64 = Script::new(RELEASE_SCRIPT)
+            .key(&self.key)
+            .arg(identity)
+            .invoke_async(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+}
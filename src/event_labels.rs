@@ -0,0 +1,253 @@
+//! Component telemetry labeled with values taken from the events.
+//!
+//! When `telemetry.event_labels` is set, components count the events they receive and send with
+//! labels taken from the fields of the events, such as their Kubernetes namespace. The number of
+//! distinct values of each label is bounded per component, the events holding other values being
+//! counted with the value `other`.
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use lookup::OwnedTargetPath;
+use metrics::Label;
+use value::Value;
+use vector_core::EstimatedJsonEncodedSizeOf;
+
+use crate::{
+    config::Config,
+    event::EventRef,
+    internal_events::{LabeledEventsReceived, LabeledEventsSent},
+};
+
+/// The value of the labels missing from an event.
+const MISSING_VALUE: &str = "none";
+
+/// The value of the labels whose number of distinct values reached the maximum.
+const OVERFLOW_VALUE: &str = "other";
+
+#[derive(Debug)]
+struct Inner {
+    labels: Vec<EventLabel>,
+    max_values: usize,
+    /// The values of each label reported so far, in the order of `labels`.
+    values: Mutex<Vec<HashSet<String>>>,
+}
+
+#[derive(Debug)]
+struct EventLabel {
+    name: String,
+    path: OwnedTargetPath,
+    /// The metric tag holding the value of the label, for metric events.
+    tag: String,
+}
+
+/// Counts the events flowing through a component by the values of the configured labels.
+#[derive(Clone, Debug)]
+pub struct EventLabels {
+    inner: Arc<Inner>,
+}
+
+impl EventLabels {
+    /// Creates the counter of a component, if event labels are configured.
+    pub fn new(config: &Config) -> Option<Self> {
+        let telemetry = &config.global.telemetry;
+        if telemetry.event_labels.is_empty() {
+            return None;
+        }
+
+        let labels: Vec<_> = telemetry
+            .event_labels
+            .iter()
+            .map(|(name, path)| {
+                let tag = String::from(path.clone());
+                EventLabel {
+                    name: name.clone(),
+                    path: path.0.clone(),
+                    tag: tag.strip_prefix('.').map(ToOwned::to_owned).unwrap_or(tag),
+                }
+            })
+            .collect();
+        Some(Self {
+            inner: Arc::new(Inner {
+                values: Mutex::new(vec![HashSet::new(); labels.len()]),
+                labels,
+                max_values: telemetry.max_label_values(),
+            }),
+        })
+    }
+
+    /// Counts the events received by the component.
+    pub fn received<'a>(&self, events: impl Iterator<Item = EventRef<'a>>) {
+        for (labels, (count, byte_size)) in self.count(events) {
+            emit!(LabeledEventsReceived {
+                labels,
+                count,
+                byte_size,
+            });
+        }
+    }
+
+    /// Counts the events sent by the component.
+    pub fn sent<'a>(&self, events: impl Iterator<Item = EventRef<'a>>) {
+        for (labels, (count, byte_size)) in self.count(events) {
+            emit!(LabeledEventsSent {
+                labels,
+                count,
+                byte_size,
+            });
+        }
+    }
+
+    /// Groups the events by the values of their labels, returning their count and size.
+    fn count<'a>(
+        &self,
+        events: impl Iterator<Item = EventRef<'a>>,
+    ) -> Vec<(Vec<Label>, (usize, usize))> {
+        let mut groups: HashMap<Vec<String>, (usize, usize)> = HashMap::new();
+        for event in events {
+            let values = self
+                .inner
+                .labels
+                .iter()
+                .map(|label| label.value_of(event))
+                .collect();
+            let group = groups.entry(values).or_default();
+            group.0 += 1;
+            group.1 += event.estimated_json_encoded_size_of();
+        }
+
+        // Values over the maximum are replaced, which can merge groups.
+        let mut known = self.inner.values.lock().expect("poisoned lock");
+        let mut bounded_groups: HashMap<Vec<String>, (usize, usize)> = HashMap::new();
+        for (values, (count, byte_size)) in groups {
+            let values = known
+                .iter_mut()
+                .zip(values)
+                .map(|(known, value)| bounded(known, self.inner.max_values, value))
+                .collect();
+            let group = bounded_groups.entry(values).or_default();
+            group.0 += count;
+            group.1 += byte_size;
+        }
+        drop(known);
+
+        bounded_groups
+            .into_iter()
+            .map(|(values, counts)| {
+                let labels = self
+                    .inner
+                    .labels
+                    .iter()
+                    .zip(values)
+                    .map(|(label, value)| Label::new(label.name.clone(), value))
+                    .collect();
+                (labels, counts)
+            })
+            .collect()
+    }
+}
+
+impl EventLabel {
+    fn value_of(&self, event: EventRef<'_>) -> String {
+        let value = match event {
+            EventRef::Log(log) => log.get(&self.path).map(Value::to_string_lossy),
+            EventRef::Trace(trace) => trace.get(&self.path).map(Value::to_string_lossy),
+            EventRef::Metric(metric) => metric.tag_value(&self.tag).map(Into::into),
+        };
+        value.map_or_else(|| MISSING_VALUE.to_owned(), |value| value.into_owned())
+    }
+}
+
+/// Returns the value if it's already known or there's room for it, recording it as known.
+fn bounded(known: &mut HashSet<String>, max_values: usize, value: String) -> String {
+    if known.contains(&value) {
+        value
+    } else if known.len() < max_values {
+        known.insert(value.clone());
+        value
+    } else {
+        OVERFLOW_VALUE.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use lookup::lookup_v2::ConfigTargetPath;
+    use vector_core::config::TelemetryConfig;
+
+    use super::*;
+    use crate::event::{Event, LogEvent, Metric, MetricKind, MetricValue};
+
+    fn labels(max_label_values: usize) -> EventLabels {
+        let mut config = Config::default();
+        config.global.telemetry = TelemetryConfig {
+            event_labels: BTreeMap::from([(
+                "namespace".to_owned(),
+                ConfigTargetPath::try_from("namespace".to_owned()).unwrap(),
+            )]),
+            max_label_values: Some(max_label_values),
+        };
+        EventLabels::new(&config).unwrap()
+    }
+
+    fn log(namespace: &str) -> Event {
+        let mut log = LogEvent::from("message");
+        log.insert("namespace", namespace);
+        log.into()
+    }
+
+    fn counts(labels: &EventLabels, events: &[Event]) -> BTreeMap<String, usize> {
+        labels
+            .count(events.iter().map(EventRef::from))
+            .into_iter()
+            .map(|(labels, (count, _))| (labels[0].value().to_owned(), count))
+            .collect()
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        assert!(EventLabels::new(&Config::default()).is_none());
+    }
+
+    #[test]
+    fn groups_events_by_label_values() {
+        let metric = Event::from(
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(vector_core::metric_tags!("namespace" => "a"))),
+        );
+        let events = [
+            log("a"),
+            log("b"),
+            log("a"),
+            metric,
+            LogEvent::from("x").into(),
+        ];
+
+        assert_eq!(
+            counts(&labels(10), &events),
+            BTreeMap::from([
+                ("a".to_owned(), 3),
+                ("b".to_owned(), 1),
+                ("none".to_owned(), 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn bounds_label_values() {
+        let labels = labels(2);
+        counts(&labels, &[log("a"), log("b")]);
+
+        assert_eq!(
+            counts(&labels, &[log("a"), log("c"), log("d")]),
+            BTreeMap::from([("a".to_owned(), 1), ("other".to_owned(), 2)])
+        );
+    }
+}
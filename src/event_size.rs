@@ -0,0 +1,273 @@
+//! Maximum size of events.
+//!
+//! When `event_size.max_bytes` is set, the events sent by sources and transforms are checked
+//! against the limit before being sent downstream, and the events exceeding it are truncated,
+//! dropped or sent to the dead letter queue, according to `event_size.when_exceeded`. The size of
+//! an event is the estimated size of its JSON encoding. The dead letter queue is read by the
+//! `dead_letter` sources.
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use lookup::{lookup_v2::OwnedTargetPath, path};
+use once_cell::sync::Lazy;
+use tokio::sync::broadcast;
+use value::Value;
+use vector_core::config::{log_schema, EventSizePolicy};
+
+use crate::{
+    config::{ComponentKey, Config},
+    event::{EstimatedJsonEncodedSizeOf, Event, EventArray, LogEvent},
+    internal_events::EventSizeExceeded,
+};
+
+/// The number of events held by the dead letter queue for each of its readers.
+const DEAD_LETTER_CAPACITY: usize = 1024;
+
+static DEAD_LETTERS: Lazy<broadcast::Sender<Event>> =
+    Lazy::new(|| broadcast::channel(DEAD_LETTER_CAPACITY).0);
+
+/// Subscribes to the events sent to the dead letter queue.
+pub fn subscribe_dead_letters() -> broadcast::Receiver<Event> {
+    DEAD_LETTERS.subscribe()
+}
+
+/// Enforces the maximum size of the events sent by a component.
+#[derive(Clone, Debug)]
+pub struct EventSizeGuard {
+    max_bytes: usize,
+    policy: EventSizePolicy,
+    fields: Vec<OwnedTargetPath>,
+    component_id: Value,
+}
+
+#[derive(Default)]
+struct Outcomes {
+    truncated: usize,
+    dropped: usize,
+    dead_lettered: usize,
+}
+
+impl EventSizeGuard {
+    /// Creates the guard of the given component, if a maximum event size is configured.
+    pub fn new(config: &Config, component: &ComponentKey) -> Option<Self> {
+        let event_size = &config.global.event_size;
+        let fields = if event_size.truncate_fields.is_empty() {
+            vec![log_schema().owned_message_path()]
+        } else {
+            event_size
+                .truncate_fields
+                .iter()
+                .map(|field| field.0.clone())
+                .collect()
+        };
+        Some(Self {
+            max_bytes: event_size.max_bytes?,
+            policy: event_size.when_exceeded.unwrap_or_default(),
+            fields,
+            component_id: Value::from(component.id()),
+        })
+    }
+
+    /// Applies the policy to the events exceeding the maximum size, removing the ones which are
+    /// dropped or sent to the dead letter queue from the array.
+    pub fn enforce(&self, array: &mut EventArray) {
+        if array
+            .iter_events()
+            .all(|event| event.estimated_json_encoded_size_of() <= self.max_bytes)
+        {
+            return;
+        }
+
+        let mut outcomes = Outcomes::default();
+        let mut admit = |event| self.admit(event, &mut outcomes);
+        *array = match std::mem::replace(array, EventArray::Logs(Vec::new())) {
+            EventArray::Logs(logs) => EventArray::Logs(
+                logs.into_iter()
+                    .filter_map(|log| admit(Event::Log(log)).map(Event::into_log))
+                    .collect(),
+            ),
+            EventArray::Metrics(metrics) => EventArray::Metrics(
+                metrics
+                    .into_iter()
+                    .filter_map(|metric| admit(Event::Metric(metric)).map(Event::into_metric))
+                    .collect(),
+            ),
+            EventArray::Traces(traces) => EventArray::Traces(
+                traces
+                    .into_iter()
+                    .filter_map(|trace| admit(Event::Trace(trace)).map(Event::into_trace))
+                    .collect(),
+            ),
+        };
+
+        for (outcome, count) in [
+            ("truncated", outcomes.truncated),
+            ("dropped", outcomes.dropped),
+            ("dead_lettered", outcomes.dead_lettered),
+        ] {
+            if count > 0 {
+                emit!(EventSizeExceeded { outcome, count });
+            }
+        }
+    }
+
+    fn admit(&self, mut event: Event, outcomes: &mut Outcomes) -> Option<Event> {
+        let size = event.estimated_json_encoded_size_of();
+        if size <= self.max_bytes {
+            return Some(event);
+        }
+
+        match self.policy {
+            EventSizePolicy::Truncate => {
+                if self.truncate(&mut event) {
+                    outcomes.truncated += 1;
+                    return Some(event);
+                }
+            }
+            EventSizePolicy::Drop => {}
+            EventSizePolicy::DeadLetter => {
+                // Events coming back from the dead letter queue are dropped, to not loop through
+                // it forever.
+                let metadata = event.metadata_mut().value_mut();
+                if metadata.get(path!("vector", "dead_letter")).is_none() {
+                    metadata.insert(
+                        path!("vector", "dead_letter"),
+                        Value::Object(BTreeMap::from([
+                            ("component_id".to_owned(), self.component_id.clone()),
+                            ("size_bytes".to_owned(), Value::from(size as i64)),
+                        ])),
+                    );
+                    if DEAD_LETTERS.send(event).is_ok() {
+                        outcomes.dead_lettered += 1;
+                        return None;
+                    }
+                }
+            }
+        }
+
+        outcomes.dropped += 1;
+        None
+    }
+
+    /// Truncates the fields of the event until it fits, returning whether it does.
+    fn truncate(&self, event: &mut Event) -> bool {
+        let log: &mut LogEvent = match event {
+            Event::Log(log) => log,
+            Event::Trace(trace) => trace.as_mut(),
+            Event::Metric(_) => return false,
+        };
+
+        for field in &self.fields {
+            loop {
+                let size = log.estimated_json_encoded_size_of();
+                if size <= self.max_bytes {
+                    return true;
+                }
+                let Some(Value::Bytes(bytes)) = log.get_mut(field) else {
+                    break;
+                };
+                if bytes.is_empty() {
+                    break;
+                }
+                // Escaped characters are encoded with more bytes than they hold, so the field may
+                // need to be truncated more than once.
+                let len = char_boundary(bytes, bytes.len().saturating_sub(size - self.max_bytes));
+                bytes.truncate(len);
+            }
+        }
+        log.estimated_json_encoded_size_of() <= self.max_bytes
+    }
+}
+
+/// Returns the largest length up to `len` which doesn't split an UTF-8 character.
+fn char_boundary(bytes: &Bytes, mut len: usize) -> usize {
+    while len > 0 && len < bytes.len() && bytes[len] & 0xC0 == 0x80 {
+        len -= 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::config::EventSizeConfig;
+
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+
+    fn guard(max_bytes: usize, policy: EventSizePolicy) -> EventSizeGuard {
+        let mut config = Config::default();
+        config.global.event_size = EventSizeConfig {
+            max_bytes: Some(max_bytes),
+            when_exceeded: Some(policy),
+            truncate_fields: Vec::new(),
+        };
+        EventSizeGuard::new(&config, &ComponentKey::from("in")).unwrap()
+    }
+
+    #[test]
+    fn truncates_messages() {
+        let guard = guard(64, EventSizePolicy::Truncate);
+        let small = LogEvent::from("small");
+        let mut array = EventArray::Logs(vec![small.clone(), LogEvent::from("é".repeat(100))]);
+        guard.enforce(&mut array);
+
+        let EventArray::Logs(logs) = array else {
+            panic!("expected logs");
+        };
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0], small);
+        assert!(logs[1].estimated_json_encoded_size_of() <= 64);
+        let message = logs[1].get("message").unwrap().as_bytes().unwrap();
+        assert!(!message.is_empty());
+        assert!(std::str::from_utf8(message).is_ok());
+    }
+
+    #[test]
+    fn drops_oversized_events() {
+        let truncate = guard(64, EventSizePolicy::Truncate);
+        let mut log = LogEvent::from("small");
+        log.insert("other", "x".repeat(100));
+        let mut array = EventArray::from(log);
+        truncate.enforce(&mut array);
+        assert!(array.is_empty());
+
+        let drop = guard(16, EventSizePolicy::Drop);
+        let mut array = EventArray::from(Metric::new(
+            "a_rather_long_metric_name",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        drop.enforce(&mut array);
+        assert!(array.is_empty());
+    }
+
+    #[test]
+    fn sends_dead_letters_once() {
+        // Other tests may send dead letters too, which are told apart by their message.
+        let message = "sends_dead_letters_once ".repeat(4);
+        let is_sent = |event: &Event| event.as_log()["message"] == message.as_str().into();
+
+        let guard = guard(64, EventSizePolicy::DeadLetter);
+        let mut dead_letters = subscribe_dead_letters();
+        let mut array = EventArray::from(LogEvent::from(message.as_str()));
+        guard.enforce(&mut array);
+        assert!(array.is_empty());
+
+        let event = std::iter::from_fn(|| dead_letters.try_recv().ok())
+            .find(is_sent)
+            .unwrap();
+        assert_eq!(
+            event
+                .metadata()
+                .value()
+                .get(path!("vector", "dead_letter", "component_id")),
+            Some(&Value::from("in"))
+        );
+
+        // Oversized events coming back from the dead letter queue are dropped.
+        let mut array = EventArray::from(event);
+        guard.enforce(&mut array);
+        assert!(array.is_empty());
+        assert!(!std::iter::from_fn(|| dead_letters.try_recv().ok()).any(|event| is_sent(&event)));
+    }
+}
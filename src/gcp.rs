@@ -1,19 +1,24 @@
 #![allow(missing_docs)]
 use std::{
+    collections::HashMap,
     sync::{Arc, RwLock},
     time::Duration,
 };
 
 use base64::prelude::{Engine as _, BASE64_URL_SAFE};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 pub use goauth::scopes::Scope;
 use goauth::{
     auth::{JwtClaims, Token, TokenErr},
     credentials::Credentials,
     GoErr,
 };
-use http::{uri::PathAndQuery, Uri};
-use hyper::header::AUTHORIZATION;
+use http::{uri::PathAndQuery, StatusCode, Uri};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
 use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::json;
 use smpl_jwt::Jwt;
 use snafu::{ResultExt, Snafu};
 use tokio::{sync::watch, time::Instant};
@@ -25,6 +30,16 @@ use crate::{config::ProxyConfig, http::HttpClient, http::HttpError};
 const SERVICE_ACCOUNT_TOKEN_URL: &str =
     "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
 
+const IAM_CREDENTIALS_URL: &str =
+    "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts";
+
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+// The longest lifetime of impersonated tokens allowed without an organization policy.
+const IMPERSONATED_TOKEN_LIFETIME: &str = "3600s";
+
 pub const PUBSUB_URL: &str = "https://pubsub.googleapis.com";
 
 pub static PUBSUB_ADDRESS: Lazy<String> = Lazy::new(|| {
@@ -56,6 +71,24 @@ pub enum GcpError {
     TokenJsonFromStr { source: serde_json::Error },
     #[snafu(display("Failed to build HTTP client: {}", source))]
     BuildHttpClient { source: HttpError },
+    #[snafu(display("Failed to read GCP credentials file: {}", source))]
+    ReadCredentials { source: std::io::Error },
+    #[snafu(display("Invalid GCP external account credentials: {}", source))]
+    InvalidExternalAccount { source: serde_json::Error },
+    #[snafu(display(
+        "Unsupported credential source in GCP external account credentials, only `file` and `url` are supported"
+    ))]
+    UnsupportedCredentialSource,
+    #[snafu(display("Failed to read subject token file: {}", source))]
+    ReadSubjectToken { source: std::io::Error },
+    #[snafu(display("Failed to get subject token: {}", source))]
+    GetSubjectToken { source: HttpError },
+    #[snafu(display("Subject token response has no string field {:?}", field))]
+    MissingSubjectToken { field: String },
+    #[snafu(display("Failed to send GCP token request: {}", source))]
+    SendTokenRequest { source: HttpError },
+    #[snafu(display("GCP token request failed with status {}: {}", status, body))]
+    TokenRequestFailed { status: StatusCode, body: String },
 }
 
 /// Configuration of the authentication strategy for interacting with GCP services.
@@ -89,9 +122,30 @@ pub struct GcpAuthConfig {
     /// running on. If this is not on a GCE instance, then you must define it with an API key or service account
     /// credentials JSON file.
     ///
+    /// The file can also hold [external account][gcp_external_account] credentials, as used by workload identity
+    /// federation, whose subject token is read from a file or fetched from a URL.
+    ///
     /// [gcp_service_account_credentials]: https://cloud.google.com/docs/authentication/production#manually
+    /// [gcp_external_account]: https://cloud.google.com/iam/docs/workload-identity-federation
     pub credentials_path: Option<String>,
 
+    /// The email of a [service account][gcp_impersonation] to impersonate.
+    ///
+    /// Short-lived tokens of this service account are requested with the credentials found as described for
+    /// `credentials_path`, which must be granted the `roles/iam.serviceAccountTokenCreator` role on it.
+    ///
+    /// [gcp_impersonation]: https://cloud.google.com/iam/docs/service-account-impersonation
+    #[configurable(metadata(docs::examples = "vector@my-project.iam.gserviceaccount.com"))]
+    pub impersonate_service_account: Option<String>,
+
+    /// The emails of the service accounts to delegate the impersonation through, in order.
+    ///
+    /// The credentials must be granted the `roles/iam.serviceAccountTokenCreator` role on the first delegate, each
+    /// delegate on the next one, and the last delegate on `impersonate_service_account`.
+    #[configurable(metadata(docs::examples = "delegate@my-project.iam.gserviceaccount.com"))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub impersonation_delegates: Vec<String>,
+
     /// Skip all authentication handling. For use with integration tests only.
     #[serde(default, skip_serializing)]
     #[configurable(metadata(docs::hidden))]
@@ -105,15 +159,82 @@ impl GcpAuthConfig {
         } else {
             let gap = std::env::var("GOOGLE_APPLICATION_CREDENTIALS").ok();
             let creds_path = self.credentials_path.as_ref().or(gap.as_ref());
+            let impersonation =
+                self.impersonate_service_account
+                    .as_ref()
+                    .map(|target| Impersonation {
+                        url: format!("{IAM_CREDENTIALS_URL}/{target}:generateAccessToken"),
+                        delegates: self.impersonation_delegates.clone(),
+                    });
             match (&creds_path, &self.api_key) {
-                (Some(path), _) => GcpAuthenticator::from_file(path, scope).await?,
+                (Some(path), _) => GcpAuthenticator::from_file(path, scope, impersonation).await?,
                 (None, Some(api_key)) => GcpAuthenticator::from_api_key(api_key.inner())?,
-                (None, None) => GcpAuthenticator::new_implicit().await?,
+                (None, None) => GcpAuthenticator::new_implicit(scope, impersonation).await?,
             }
         })
     }
 }
 
+/// The source of the tokens of credentials.
+#[derive(Debug)]
+enum TokenSource {
+    ServiceAccount(Credentials),
+    ExternalAccount(ExternalAccount),
+    Implicit,
+}
+
+/// A service account impersonated through the IAM Credentials API.
+#[derive(Debug)]
+struct Impersonation {
+    url: String,
+    delegates: Vec<String>,
+}
+
+/// [External account][external_account] credentials, as used by workload identity federation.
+///
+/// [external_account]: https://google.aip.dev/auth/4117
+#[derive(Debug, Deserialize)]
+struct ExternalAccount {
+    audience: String,
+    subject_token_type: String,
+    token_url: String,
+    service_account_impersonation_url: Option<String>,
+    credential_source: CredentialSource,
+}
+
+#[derive(Debug, Deserialize)]
+struct CredentialSource {
+    file: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    format: CredentialSourceFormat,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CredentialSourceFormat {
+    #[default]
+    Text,
+    Json {
+        subject_token_field_name: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct CredentialsType {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImpersonatedToken {
+    access_token: String,
+    expire_time: DateTime<Utc>,
+}
+
 #[derive(Clone, Debug)]
 pub enum GcpAuthenticator {
     Credentials(Arc<InnerCreds>),
@@ -123,22 +244,53 @@ pub enum GcpAuthenticator {
 
 #[derive(Debug)]
 pub struct InnerCreds {
-    creds: Option<(Credentials, Scope)>,
+    source: TokenSource,
+    scope: Scope,
+    impersonation: Option<Impersonation>,
     token: RwLock<Token>,
 }
 
 impl GcpAuthenticator {
-    async fn from_file(path: &str, scope: Scope) -> crate::Result<Self> {
-        let creds = Credentials::from_file(path).context(InvalidCredentialsSnafu)?;
-        let token = RwLock::new(fetch_token(&creds, &scope).await?);
-        let creds = Some((creds, scope));
-        Ok(Self::Credentials(Arc::new(InnerCreds { creds, token })))
+    async fn from_file(
+        path: &str,
+        scope: Scope,
+        impersonation: Option<Impersonation>,
+    ) -> crate::Result<Self> {
+        let text = std::fs::read_to_string(path).context(ReadCredentialsSnafu)?;
+        let kind = serde_json::from_str::<CredentialsType>(&text)
+            .ok()
+            .and_then(|creds| creds.kind);
+        let source = if kind.as_deref() == Some("external_account") {
+            TokenSource::ExternalAccount(
+                serde_json::from_str(&text).context(InvalidExternalAccountSnafu)?,
+            )
+        } else {
+            TokenSource::ServiceAccount(
+                Credentials::from_file(path).context(InvalidCredentialsSnafu)?,
+            )
+        };
+        Self::from_source(source, scope, impersonation).await
+    }
+
+    async fn new_implicit(
+        scope: Scope,
+        impersonation: Option<Impersonation>,
+    ) -> crate::Result<Self> {
+        Self::from_source(TokenSource::Implicit, scope, impersonation).await
     }
 
-    async fn new_implicit() -> crate::Result<Self> {
-        let token = RwLock::new(get_token_implicit().await?);
-        let creds = None;
-        Ok(Self::Credentials(Arc::new(InnerCreds { creds, token })))
+    async fn from_source(
+        source: TokenSource,
+        scope: Scope,
+        impersonation: Option<Impersonation>,
+    ) -> crate::Result<Self> {
+        let token = RwLock::new(fetch_source_token(&source, &scope, &impersonation).await?);
+        Ok(Self::Credentials(Arc::new(InnerCreds {
+            source,
+            scope,
+            impersonation,
+            token,
+        })))
     }
 
     fn from_api_key(api_key: &str) -> crate::Result<Self> {
@@ -223,10 +375,7 @@ impl GcpAuthenticator {
 
 impl InnerCreds {
     async fn regenerate_token(&self) -> crate::Result<()> {
-        let token = match &self.creds {
-            Some((creds, scope)) => fetch_token(creds, scope).await?,
-            None => get_token_implicit().await?,
-        };
+        let token = fetch_source_token(&self.source, &self.scope, &self.impersonation).await?;
         *self.token.write().unwrap() = token;
         Ok(())
     }
@@ -237,6 +386,32 @@ impl InnerCreds {
     }
 }
 
+async fn fetch_source_token(
+    source: &TokenSource,
+    scope: &Scope,
+    impersonation: &Option<Impersonation>,
+) -> crate::Result<Token> {
+    // Impersonating a service account requires a token allowed to use the IAM Credentials API, whose own scope is
+    // the one of the impersonated token.
+    let source_scope = match impersonation {
+        Some(_) => &Scope::CloudPlatform,
+        None => scope,
+    };
+    let token = match source {
+        TokenSource::ServiceAccount(creds) => fetch_token(creds, source_scope).await?,
+        TokenSource::ExternalAccount(account) => {
+            fetch_external_account_token(account, source_scope).await?
+        }
+        TokenSource::Implicit => get_token_implicit().await?,
+    };
+    match impersonation {
+        Some(impersonation) => {
+            Ok(impersonate(&impersonation.url, &token, &impersonation.delegates, scope).await?)
+        }
+        None => Ok(token),
+    }
+}
+
 async fn fetch_token(creds: &Credentials, scope: &Scope) -> crate::Result<Token> {
     let claims = JwtClaims::new(creds.iss(), scope, creds.token_uri(), None, None);
     let rsa_key = creds.rsa_key().context(InvalidRsaKeySnafu)?;
@@ -254,6 +429,149 @@ async fn fetch_token(creds: &Credentials, scope: &Scope) -> crate::Result<Token>
         .map_err(Into::into)
 }
 
+/// Exchanges the subject token of external account credentials for a GCP token.
+async fn fetch_external_account_token(
+    account: &ExternalAccount,
+    scope: &Scope,
+) -> Result<Token, GcpError> {
+    debug!(
+        message = "Fetching GCP external account authentication token.",
+        audience = %account.audience,
+        token_url = %account.token_url,
+    );
+    let subject_token = account.credential_source.subject_token().await?;
+
+    // The federated token is only allowed to impersonate the service account of the credentials if there is one.
+    let sts_scope = match account.service_account_impersonation_url {
+        Some(_) => &Scope::CloudPlatform,
+        None => scope,
+    };
+    let body = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("grant_type", TOKEN_EXCHANGE_GRANT_TYPE)
+        .append_pair("audience", &account.audience)
+        .append_pair("scope", &sts_scope.url())
+        .append_pair("requested_token_type", ACCESS_TOKEN_TYPE)
+        .append_pair("subject_token_type", &account.subject_token_type)
+        .append_pair("subject_token", &subject_token)
+        .finish();
+    let request = http::Request::post(&account.token_url)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(hyper::Body::from(body))
+        .unwrap();
+    let token = parse_token(&send_token_request(request).await?)?;
+
+    match &account.service_account_impersonation_url {
+        Some(url) => impersonate(url, &token, &[], scope).await,
+        None => Ok(token),
+    }
+}
+
+impl CredentialSource {
+    async fn subject_token(&self) -> Result<String, GcpError> {
+        let bytes = match (&self.file, &self.url) {
+            (Some(file), _) => tokio::fs::read(file)
+                .await
+                .context(ReadSubjectTokenSnafu)?
+                .into(),
+            (None, Some(url)) => {
+                let mut request = http::Request::get(url);
+                for (name, value) in &self.headers {
+                    request = request.header(name, value);
+                }
+                let request = request.body(hyper::Body::empty()).unwrap();
+                let proxy = ProxyConfig::from_env();
+                let response = HttpClient::new(None, &proxy)
+                    .context(BuildHttpClientSnafu)?
+                    .send(request)
+                    .await
+                    .context(GetSubjectTokenSnafu)?;
+                hyper::body::to_bytes(response.into_body())
+                    .await
+                    .context(GetTokenBytesSnafu)?
+            }
+            (None, None) => return Err(GcpError::UnsupportedCredentialSource),
+        };
+        self.format.subject_token(&bytes)
+    }
+}
+
+impl CredentialSourceFormat {
+    fn subject_token(&self, bytes: &[u8]) -> Result<String, GcpError> {
+        match self {
+            Self::Text => Ok(String::from_utf8_lossy(bytes).trim().to_string()),
+            Self::Json {
+                subject_token_field_name,
+            } => serde_json::from_slice::<serde_json::Value>(bytes)
+                .context(TokenJsonFromStrSnafu)?
+                .get(subject_token_field_name)
+                .and_then(serde_json::Value::as_str)
+                .map(Into::into)
+                .ok_or_else(|| GcpError::MissingSubjectToken {
+                    field: subject_token_field_name.clone(),
+                }),
+        }
+    }
+}
+
+/// Requests a token of an impersonated service account through the IAM Credentials API.
+async fn impersonate(
+    url: &str,
+    token: &Token,
+    delegates: &[String],
+    scope: &Scope,
+) -> Result<Token, GcpError> {
+    debug!(message = "Fetching impersonated GCP authentication token.", %url);
+    let delegates = delegates
+        .iter()
+        .map(|delegate| format!("projects/-/serviceAccounts/{delegate}"))
+        .collect::<Vec<_>>();
+    let body = json!({
+        "delegates": delegates,
+        "scope": [scope.url()],
+        "lifetime": IMPERSONATED_TOKEN_LIFETIME,
+    });
+    let request = http::Request::post(url)
+        .header(
+            AUTHORIZATION,
+            format!("{} {}", token.token_type(), token.access_token()),
+        )
+        .header(CONTENT_TYPE, "application/json")
+        .body(hyper::Body::from(body.to_string()))
+        .unwrap();
+    let bytes = send_token_request(request).await?;
+
+    let token =
+        serde_json::from_slice::<ImpersonatedToken>(&bytes).context(TokenJsonFromStrSnafu)?;
+    let expires_in = (token.expire_time - Utc::now()).num_seconds().max(0);
+    serde_json::from_value(json!({
+        "access_token": token.access_token,
+        "token_type": "Bearer",
+        "expires_in": expires_in,
+    }))
+    .context(TokenJsonFromStrSnafu)
+}
+
+async fn send_token_request(request: http::Request<hyper::Body>) -> Result<Bytes, GcpError> {
+    let proxy = ProxyConfig::from_env();
+    let response = HttpClient::new(None, &proxy)
+        .context(BuildHttpClientSnafu)?
+        .send(request)
+        .await
+        .context(SendTokenRequestSnafu)?;
+
+    let status = response.status();
+    let bytes = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(GetTokenBytesSnafu)?;
+    if !status.is_success() {
+        return Err(GcpError::TokenRequestFailed {
+            status,
+            body: String::from_utf8_lossy(&bytes).into(),
+        });
+    }
+    Ok(bytes)
+}
+
 async fn get_token_implicit() -> Result<Token, GcpError> {
     debug!("Fetching implicit GCP authentication token.");
     let req = http::Request::get(SERVICE_ACCOUNT_TOKEN_URL)
@@ -273,10 +591,14 @@ async fn get_token_implicit() -> Result<Token, GcpError> {
         .await
         .context(GetTokenBytesSnafu)?;
 
+    parse_token(&bytes)
+}
+
+fn parse_token(bytes: &[u8]) -> Result<Token, GcpError> {
     // Token::from_str is irresponsible and may panic!
-    match serde_json::from_slice::<Token>(&bytes) {
+    match serde_json::from_slice::<Token>(bytes) {
         Ok(token) => Ok(token),
-        Err(error) => Err(match serde_json::from_slice::<TokenErr>(&bytes) {
+        Err(error) => Err(match serde_json::from_slice::<TokenErr>(bytes) {
             Ok(error) => GcpError::TokenFromJson { source: error },
             Err(_) => GcpError::TokenJsonFromStr { source: error },
         }),
@@ -343,6 +665,83 @@ mod tests {
         assert_downcast_matches!(error, GcpError, GcpError::InvalidApiKey { .. });
     }
 
+    #[tokio::test]
+    async fn reads_external_account_subject_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let token_path = dir.path().join("token.json");
+        std::fs::write(&token_path, r#"{"id_token": "subject"}"#).unwrap();
+
+        let account: ExternalAccount = serde_json::from_value(json!({
+            "type": "external_account",
+            "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/provider",
+            "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+            "token_url": "https://sts.googleapis.com/v1/token",
+            "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/vector@project.iam.gserviceaccount.com:generateAccessToken",
+            "credential_source": {
+                "file": token_path,
+                "format": {
+                    "type": "json",
+                    "subject_token_field_name": "id_token",
+                },
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            account.credential_source.subject_token().await.unwrap(),
+            "subject"
+        );
+    }
+
+    #[test]
+    fn parses_subject_token_formats() {
+        assert_eq!(
+            CredentialSourceFormat::Text
+                .subject_token(b"subject\n")
+                .unwrap(),
+            "subject"
+        );
+
+        let format = CredentialSourceFormat::Json {
+            subject_token_field_name: "access_token".into(),
+        };
+        assert_eq!(
+            format
+                .subject_token(br#"{"access_token": "subject"}"#)
+                .unwrap(),
+            "subject"
+        );
+        assert!(matches!(
+            format.subject_token(br#"{"id_token": "subject"}"#),
+            Err(GcpError::MissingSubjectToken { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn fails_unsupported_credential_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials.json");
+        std::fs::write(
+            &path,
+            json!({
+                "type": "external_account",
+                "audience": "audience",
+                "subject_token_type": "urn:ietf:params:aws:token-type:aws4_request",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": {
+                    "environment_id": "aws1",
+                },
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let error = build_auth(&format!("credentials_path = {:?}", path))
+            .await
+            .expect_err("build failed to error");
+        assert_downcast_matches!(error, GcpError, GcpError::UnsupportedCredentialSource);
+    }
+
     fn apply_uri(auth: &GcpAuthenticator, uri: &str) -> String {
         let mut uri: Uri = uri.parse().unwrap();
         auth.apply_uri(&mut uri);
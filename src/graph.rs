@@ -2,8 +2,12 @@ use std::fmt::Write as _;
 use std::path::PathBuf;
 
 use clap::Parser;
+#[cfg(feature = "api-client")]
+use url::Url;
+#[cfg(feature = "api-client")]
+use vector_core::internal_event::DEFAULT_OUTPUT;
 
-use crate::config;
+use crate::config::{self, Config};
 
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
@@ -45,6 +49,36 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Format of the generated graph.
+    #[arg(long, default_value = "dot")]
+    format: Format,
+
+    /// Annotate the graph with live metrics from the GraphQL API of a running Vector instance at
+    /// this URL, such as `http://localhost:8686/graphql`. Edges are labeled with the rate of
+    /// events sent through them, and components with their rate of errors.
+    #[cfg(feature = "api-client")]
+    #[arg(long)]
+    metrics_url: Option<Url>,
+
+    /// Interval over which the live metrics are measured, in milliseconds.
+    #[cfg(feature = "api-client")]
+    #[arg(
+        long,
+        default_value = "1000",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    metrics_interval: u32,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// The DOT language, which can be rendered by GraphViz.
+    Dot,
+    /// A Mermaid flowchart.
+    Mermaid,
+    /// The D2 language.
+    D2,
 }
 
 impl Opts {
@@ -65,7 +99,7 @@ impl Opts {
     }
 }
 
-pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
     let paths = opts.paths_with_formats();
     let paths = match config::process_paths(&paths) {
         Some(paths) => paths,
@@ -83,54 +117,473 @@ pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
         }
     };
 
-    let mut dot = String::from("digraph {\n");
+    #[allow(unused_mut)]
+    let mut graph = Graph::new(&config);
+
+    #[cfg(feature = "api-client")]
+    if let Some(url) = &opts.metrics_url {
+        match metrics::fetch(url, opts.metrics_interval).await {
+            Some(Ok(metrics)) => graph.annotate(&metrics),
+            Some(Err(error)) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("Could not fetch metrics from {}: {}", url, error);
+                }
+                return exitcode::UNAVAILABLE;
+            }
+            // The reason was already reported by the API client.
+            None => return exitcode::UNAVAILABLE,
+        }
+    }
+
+    let output = match opts.format {
+        Format::Dot => graph.to_dot(),
+        Format::Mermaid => graph.to_mermaid(),
+        Format::D2 => graph.to_d2(),
+    };
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}", output);
+    }
+
+    exitcode::OK
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NodeKind {
+    Source,
+    Transform,
+    Sink,
+}
+
+#[derive(Debug)]
+struct Node {
+    id: String,
+    kind: NodeKind,
+    errors_per_sec: Option<f64>,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: String,
+    port: Option<String>,
+    to: String,
+    events_per_sec: Option<f64>,
+}
+
+/// The topology of a configuration, independently of the format it's output in.
+#[derive(Debug)]
+struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    fn new(config: &Config) -> Self {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut add_node = |id: String, kind| {
+            nodes.push(Node {
+                id,
+                kind,
+                errors_per_sec: None,
+            })
+        };
+
+        for (id, _source) in config.sources() {
+            add_node(id.to_string(), NodeKind::Source);
+        }
+
+        for (id, transform) in config.transforms() {
+            add_node(id.to_string(), NodeKind::Transform);
+            edges.extend(transform.inputs.iter().map(|input| Edge {
+                from: input.component.to_string(),
+                port: input.port.clone(),
+                to: id.to_string(),
+                events_per_sec: None,
+            }));
+        }
+
+        for (id, sink) in config.sinks() {
+            add_node(id.to_string(), NodeKind::Sink);
+            edges.extend(sink.inputs.iter().map(|input| Edge {
+                from: input.component.to_string(),
+                port: input.port.clone(),
+                to: id.to_string(),
+                events_per_sec: None,
+            }));
+        }
 
-    for (id, _source) in config.sources() {
-        writeln!(dot, "  \"{}\" [shape=trapezium]", id).expect("write to String never fails");
+        Self { nodes, edges }
     }
 
-    for (id, transform) in config.transforms() {
-        writeln!(dot, "  \"{}\" [shape=diamond]", id).expect("write to String never fails");
+    /// Labels the edges with the rate of events sent through them, and the components with
+    /// their rate of errors if they're erroring.
+    #[cfg(feature = "api-client")]
+    fn annotate(&mut self, metrics: &metrics::Metrics) {
+        for node in &mut self.nodes {
+            node.errors_per_sec = metrics
+                .errors_per_sec
+                .get(&node.id)
+                .copied()
+                .filter(|rate| *rate > 0.0);
+        }
 
-        for input in transform.inputs.iter() {
-            if let Some(port) = &input.port {
+        for edge in &mut self.edges {
+            edge.events_per_sec =
+                metrics
+                    .sent_events
+                    .get(&edge.from)
+                    .map(|sent| match &edge.port {
+                        Some(port) => sent.outputs.get(port).copied().unwrap_or_default(),
+                        None => sent
+                            .outputs
+                            .get(DEFAULT_OUTPUT)
+                            .copied()
+                            .unwrap_or(sent.total),
+                    });
+        }
+    }
+
+    fn node_label(node: &Node, newline: &str) -> String {
+        match node.errors_per_sec {
+            Some(rate) => format!("{}{}{:.1} errors/s", node.id, newline, rate),
+            None => node.id.clone(),
+        }
+    }
+
+    fn edge_label(edge: &Edge, newline: &str) -> Option<String> {
+        let rate = edge
+            .events_per_sec
+            .map(|rate| format!("{:.1} events/s", rate));
+        match (&edge.port, rate) {
+            (Some(port), Some(rate)) => Some(format!("{}{}{}", port, newline, rate)),
+            (Some(port), None) => Some(port.clone()),
+            (None, rate) => rate,
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Source => "trapezium",
+                NodeKind::Transform => "diamond",
+                NodeKind::Sink => "invtrapezium",
+            };
+            if node.errors_per_sec.is_some() {
                 writeln!(
                     dot,
-                    "  \"{}\" -> \"{}\" [label=\"{}\"]",
-                    input.component, id, port
+                    "  \"{}\" [shape={}, label=\"{}\"]",
+                    node.id,
+                    shape,
+                    Self::node_label(node, "\\n")
                 )
-                .expect("write to String never fails");
             } else {
-                writeln!(dot, "  \"{}\" -> \"{}\"", input, id)
-                    .expect("write to String never fails");
+                writeln!(dot, "  \"{}\" [shape={}]", node.id, shape)
             }
+            .expect("write to String never fails");
         }
-    }
-
-    for (id, sink) in config.sinks() {
-        writeln!(dot, "  \"{}\" [shape=invtrapezium]", id).expect("write to String never fails");
 
-        for input in &sink.inputs {
-            if let Some(port) = &input.port {
+        for edge in &self.edges {
+            if let Some(label) = Self::edge_label(edge, "\\n") {
                 writeln!(
                     dot,
                     "  \"{}\" -> \"{}\" [label=\"{}\"]",
-                    input.component, id, port
+                    edge.from, edge.to, label
                 )
+            } else {
+                writeln!(dot, "  \"{}\" -> \"{}\"", edge.from, edge.to)
+            }
+            .expect("write to String never fails");
+        }
+
+        dot += "}";
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        // Component IDs may contain characters which aren't valid in Mermaid node IDs, so nodes
+        // are identified by their position and labeled with their component ID.
+        let index_of = |id: &str| {
+            self.nodes
+                .iter()
+                .position(|node| node.id == id)
+                .expect("inputs are validated when loading the config")
+        };
+        let escape = |label: String| label.replace('"', "#quot;");
+
+        let mut mermaid = String::from("flowchart LR\n");
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = escape(Self::node_label(node, "<br>"));
+            let (open, close) = match node.kind {
+                NodeKind::Source => ("[/", "\\]"),
+                NodeKind::Transform => ("{", "}"),
+                NodeKind::Sink => ("[\\", "/]"),
+            };
+            writeln!(mermaid, "  n{}{}\"{}\"{}", index, open, label, close)
                 .expect("write to String never fails");
+        }
+
+        for edge in &self.edges {
+            let (from, to) = (index_of(&edge.from), index_of(&edge.to));
+            if let Some(label) = Self::edge_label(edge, "<br>") {
+                writeln!(mermaid, "  n{} -->|\"{}\"| n{}", from, escape(label), to)
             } else {
-                writeln!(dot, "  \"{}\" -> \"{}\"", input, id)
-                    .expect("write to String never fails");
+                writeln!(mermaid, "  n{} --> n{}", from, to)
             }
+            .expect("write to String never fails");
         }
+
+        mermaid.truncate(mermaid.trim_end().len());
+        mermaid
     }
 
-    dot += "}";
+    fn to_d2(&self) -> String {
+        let escape = |label: &str| {
+            label
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        };
 
-    #[allow(clippy::print_stdout)]
-    {
-        println!("{}", dot);
+        let mut d2 = String::from("direction: right\n");
+
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Source => "oval",
+                NodeKind::Transform => "diamond",
+                NodeKind::Sink => "cylinder",
+            };
+            writeln!(
+                d2,
+                "\"{}\": \"{}\" {{shape: {}}}",
+                escape(&node.id),
+                escape(&Self::node_label(node, "\n")),
+                shape
+            )
+            .expect("write to String never fails");
+        }
+
+        for edge in &self.edges {
+            let (from, to) = (escape(&edge.from), escape(&edge.to));
+            if let Some(label) = Self::edge_label(edge, "\n") {
+                writeln!(d2, "\"{}\" -> \"{}\": \"{}\"", from, to, escape(&label))
+            } else {
+                writeln!(d2, "\"{}\" -> \"{}\"", from, to)
+            }
+            .expect("write to String never fails");
+        }
+
+        d2.truncate(d2.trim_end().len());
+        d2
     }
+}
 
-    exitcode::OK
+#[cfg(feature = "api-client")]
+mod metrics {
+    use std::{collections::HashMap, time::Duration};
+
+    use futures::StreamExt;
+    use url::Url;
+    use vector_api_client::{
+        connect_subscription_client,
+        gql::{ComponentErrorsTotalsSubscription, MetricsSubscriptionExt},
+        BoxedSubscription, Client, SubscriptionClient,
+    };
+
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Rates of events sent by a component, per second.
+    #[derive(Debug, Default)]
+    pub(super) struct SentEvents {
+        pub(super) total: f64,
+        pub(super) outputs: HashMap<String, f64>,
+    }
+
+    #[derive(Debug, Default)]
+    pub(super) struct Metrics {
+        pub(super) sent_events: HashMap<String, SentEvents>,
+        pub(super) errors_per_sec: HashMap<String, f64>,
+    }
+
+    /// Measures the rates of events sent and errors of the components of a running instance
+    /// over the given interval, in milliseconds.
+    ///
+    /// Returns `None` if the API isn't reachable, which the API client reports itself.
+    pub(super) async fn fetch(url: &Url, interval: u32) -> Option<Result<Metrics, String>> {
+        Client::new_with_healthcheck(url.clone()).await?;
+        Some(measure(url, interval).await)
+    }
+
+    async fn measure(url: &Url, interval: u32) -> Result<Metrics, String> {
+        // Change the HTTP schema to WebSockets
+        let mut ws_url = url.clone();
+        ws_url
+            .set_scheme(match url.scheme() {
+                "https" => "wss",
+                _ => "ws",
+            })
+            .map_err(|_| "Couldn't build WebSocket URL".to_string())?;
+
+        // Measuring the rate of errors takes two samples, so give up after a few intervals, leaving
+        // time to connect.
+        let timeout = Duration::from_millis(u64::from(interval) * 4) + CONNECT_TIMEOUT;
+        let measured = tokio::time::timeout(timeout, async {
+            // Each subscription uses its own client, as the `top` command does, as multiplexing
+            // subscriptions over a single client isn't reliable.
+            let (sent_events, errors_per_sec) = tokio::try_join!(
+                sent_events(connect(&ws_url).await?, interval),
+                errors_per_sec(connect(&ws_url).await?, interval),
+            )?;
+            Ok::<_, String>(Metrics {
+                sent_events,
+                errors_per_sec,
+            })
+        });
+        measured
+            .await
+            .map_err(|_| "Timed out waiting for metrics".to_string())?
+    }
+
+    async fn connect(ws_url: &Url) -> Result<SubscriptionClient, String> {
+        connect_subscription_client(ws_url.clone())
+            .await
+            .map_err(|error| error.to_string())
+    }
+
+    async fn sent_events(
+        client: SubscriptionClient,
+        interval: u32,
+    ) -> Result<HashMap<String, SentEvents>, String> {
+        // Throughputs are the number of events sent during the interval.
+        let per_sec = 1000.0 / f64::from(interval);
+        let mut stream = client.component_sent_events_throughputs_subscription(interval.into());
+        let data = stream
+            .next()
+            .await
+            .flatten()
+            .and_then(|response| response.data)
+            .ok_or_else(|| "The subscription to sent events ended".to_string())?;
+
+        Ok(data
+            .component_sent_events_throughputs
+            .into_iter()
+            .map(|component| {
+                let sent = SentEvents {
+                    total: component.throughput as f64 * per_sec,
+                    outputs: component
+                        .outputs()
+                        .into_iter()
+                        .map(|(output, throughput)| (output, throughput as f64 * per_sec))
+                        .collect(),
+                };
+                (component.component_id, sent)
+            })
+            .collect())
+    }
+
+    async fn errors_per_sec(
+        client: SubscriptionClient,
+        interval: u32,
+    ) -> Result<HashMap<String, f64>, String> {
+        let mut stream = client.component_errors_totals_subscription(interval.into());
+        let first = errors_totals(&mut stream).await?;
+        let second = errors_totals(&mut stream).await?;
+
+        let per_sec = 1000.0 / f64::from(interval);
+        Ok(second
+            .into_iter()
+            .filter_map(|(component, total)| {
+                let previous = first.get(&component)?;
+                Some((component, (total - previous).max(0.0) * per_sec))
+            })
+            .collect())
+    }
+
+    async fn errors_totals(
+        stream: &mut BoxedSubscription<ComponentErrorsTotalsSubscription>,
+    ) -> Result<HashMap<String, f64>, String> {
+        stream
+            .next()
+            .await
+            .flatten()
+            .and_then(|response| response.data)
+            .map(|data| {
+                data.component_errors_totals
+                    .into_iter()
+                    .map(|component| (component.component_id, component.metric.errors_total))
+                    .collect()
+            })
+            .ok_or_else(|| "The subscription to errors ended".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        let node = |id: &str, kind| Node {
+            id: id.to_string(),
+            kind,
+            errors_per_sec: None,
+        };
+        let edge = |from: &str, port: Option<&str>, to: &str| Edge {
+            from: from.to_string(),
+            port: port.map(ToString::to_string),
+            to: to.to_string(),
+            events_per_sec: None,
+        };
+
+        Graph {
+            nodes: vec![
+                node("in", NodeKind::Source),
+                node("route", NodeKind::Transform),
+                node("out", NodeKind::Sink),
+            ],
+            edges: vec![
+                edge("in", None, "route"),
+                edge("route", Some("errors"), "out"),
+                edge("in", None, "out"),
+            ],
+        }
+    }
+
+    #[test]
+    fn mermaid() {
+        assert_eq!(
+            graph().to_mermaid(),
+            indoc::indoc! {r#"
+                flowchart LR
+                  n0[/"in"\]
+                  n1{"route"}
+                  n2[\"out"/]
+                  n0 --> n1
+                  n1 -->|"errors"| n2
+                  n0 --> n2"#}
+        );
+    }
+
+    #[test]
+    fn d2_with_metrics() {
+        let mut graph = graph();
+        graph.nodes[1].errors_per_sec = Some(0.5);
+        graph.edges[1].events_per_sec = Some(12.0);
+
+        assert_eq!(
+            graph.to_d2(),
+            indoc::indoc! {r#"
+                direction: right
+                "in": "in" {shape: oval}
+                "route": "route\n0.5 errors/s" {shape: diamond}
+                "out": "out" {shape: cylinder}
+                "in" -> "route"
+                "route" -> "out": "errors\n12.0 events/s"
+                "in" -> "out""#}
+        );
+    }
 }
@@ -4,7 +4,7 @@ use std::{
     task::{Context, Poll},
 };
 
-use futures::future::BoxFuture;
+use futures::{future::BoxFuture, TryFutureExt};
 use headers::{Authorization, HeaderMapExt};
 use http::{header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Uri};
 use hyper::{
@@ -26,6 +26,9 @@ use crate::{
     tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
 };
 
+#[cfg(feature = "http3")]
+mod http3;
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)))]
 pub enum HttpError {
@@ -39,13 +42,20 @@ pub enum HttpError {
     CallRequest { source: hyper::Error },
     #[snafu(display("Failed to build HTTP request: {}", source))]
     BuildRequest { source: http::Error },
+    #[snafu(display("Failed to build HTTP/3 client: {}", source))]
+    BuildHttp3Client { source: crate::Error },
+    #[snafu(display("Failed to make HTTP/3 request: {}", source))]
+    CallHttp3Request { source: crate::Error },
 }
 
 impl HttpError {
     pub const fn is_retriable(&self) -> bool {
         match self {
-            HttpError::BuildRequest { .. } | HttpError::MakeProxyConnector { .. } => false,
+            HttpError::BuildRequest { .. }
+            | HttpError::MakeProxyConnector { .. }
+            | HttpError::BuildHttp3Client { .. } => false,
             HttpError::CallRequest { .. }
+            | HttpError::CallHttp3Request { .. }
             | HttpError::BuildTlsConnector { .. }
             | HttpError::MakeHttpsConnector { .. } => true,
         }
@@ -55,10 +65,18 @@ impl HttpError {
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
 
 pub struct HttpClient<B = Body> {
-    client: Client<ProxyConnector<HttpsConnector<HttpConnector>>, B>,
+    client: ClientInner<B>,
     user_agent: HeaderValue,
 }
 
+enum ClientInner<B> {
+    Tcp(Client<ProxyConnector<HttpsConnector<HttpConnector>>, B>),
+    #[cfg(unix)]
+    Unix(Client<unix::UnixConnector, B>),
+    #[cfg(feature = "http3")]
+    Http3(http3::Http3Client),
+}
+
 impl<B> HttpClient<B>
 where
     B: fmt::Debug + HttpBody + Send + 'static,
@@ -80,11 +98,37 @@ where
         let proxy = build_proxy_connector(tls_settings.into(), proxy_config)?;
         let client = client_builder.build(proxy);
 
-        let version = crate::get_version();
-        let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
-            .expect("Invalid header value for version!");
+        Ok(HttpClient {
+            client: ClientInner::Tcp(client),
+            user_agent: default_user_agent(),
+        })
+    }
+
+    /// Creates a client sending all the requests to the Unix domain socket at `path`, whatever
+    /// the host of their URI.
+    #[cfg(unix)]
+    pub fn new_unix(path: impl Into<std::path::PathBuf>) -> HttpClient<B> {
+        let client = Client::builder().build(unix::UnixConnector::new(path.into()));
+
+        HttpClient {
+            client: ClientInner::Unix(client),
+            user_agent: default_user_agent(),
+        }
+    }
+
+    /// Creates a client sending the requests over HTTP/3 (QUIC) to the host and port of their URI.
+    #[cfg(feature = "http3")]
+    pub fn new_http3(
+        tls: Option<&crate::tls::TlsConfig>,
+        config: &Http3Config,
+    ) -> Result<HttpClient<B>, HttpError> {
+        let client = http3::Http3Client::new(tls, config)
+            .map_err(|source| HttpError::BuildHttp3Client { source })?;
 
-        Ok(HttpClient { client, user_agent })
+        Ok(HttpClient {
+            client: ClientInner::Http3(client),
+            user_agent: default_user_agent(),
+        })
     }
 
     pub fn send(
@@ -98,7 +142,27 @@ where
 
         emit!(http_client::AboutToSendHttpRequest { request: &request });
 
-        let response = self.client.request(request);
+        let response: BoxFuture<'static, Result<http::Response<Body>, HttpError>> =
+            match &self.client {
+                ClientInner::Tcp(client) => Box::pin(
+                    client
+                        .request(request)
+                        .map_err(|source| HttpError::CallRequest { source }),
+                ),
+                #[cfg(unix)]
+                ClientInner::Unix(client) => Box::pin(
+                    client
+                        .request(request)
+                        .map_err(|source| HttpError::CallRequest { source }),
+                ),
+                #[cfg(feature = "http3")]
+                ClientInner::Http3(client) => Box::pin(
+                    client
+                        .clone()
+                        .send(request)
+                        .map_err(|source| HttpError::CallHttp3Request { source }),
+                ),
+            };
 
         let fut = async move {
             // Capture the time right before we issue the request.
@@ -113,16 +177,14 @@ where
             let roundtrip = before.elapsed();
 
             // Handle the errors and extract the response.
-            let response = response_result
-                .map_err(|error| {
-                    // Emit the error into the internal events system.
-                    emit!(http_client::GotHttpWarning {
-                        error: &error,
-                        roundtrip
-                    });
-                    error
-                })
-                .context(CallRequestSnafu)?;
+            let response = response_result.map_err(|error| {
+                // Emit the underlying error into the internal events system.
+                emit!(http_client::GotHttpWarning {
+                    error: std::error::Error::source(&error).unwrap_or(&error),
+                    roundtrip
+                });
+                error
+            })?;
 
             // Emit the response into the internal events system.
             emit!(http_client::GotHttpResponse {
@@ -176,6 +238,12 @@ pub fn build_tls_connector(
     Ok(https)
 }
 
+fn default_user_agent() -> HeaderValue {
+    let version = crate::get_version();
+    HeaderValue::from_str(&format!("Vector/{}", version))
+        .expect("Invalid header value for version!")
+}
+
 fn default_request_headers<B>(request: &mut Request<B>, user_agent: &HeaderValue) {
     if !request.headers().contains_key("User-Agent") {
         request
@@ -220,6 +288,30 @@ impl<B> Clone for HttpClient<B> {
     }
 }
 
+impl<B> Clone for ClientInner<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Tcp(client) => Self::Tcp(client.clone()),
+            #[cfg(unix)]
+            Self::Unix(client) => Self::Unix(client.clone()),
+            #[cfg(feature = "http3")]
+            Self::Http3(client) => Self::Http3(client.clone()),
+        }
+    }
+}
+
+impl<B> fmt::Debug for ClientInner<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(client) => client.fmt(f),
+            #[cfg(unix)]
+            Self::Unix(client) => client.fmt(f),
+            #[cfg(feature = "http3")]
+            Self::Http3(_) => f.write_str("Http3Client"),
+        }
+    }
+}
+
 impl<B> fmt::Debug for HttpClient<B> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("HttpClient")
@@ -264,6 +356,31 @@ pub enum Auth {
     },
 }
 
+/// HTTP/3 configuration.
+///
+/// HTTP/3 runs over QUIC, which can substantially improve the throughput of lossy or high-latency
+/// links. Requests are sent to the UDP port of the host of the URI, whose scheme must be `https`.
+/// Proxies are not used for HTTP/3 requests. HTTP/3 is only available in builds of Vector with the
+/// `http3` feature, and can't be used in FIPS mode.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct Http3Config {
+    /// Whether to send requests over HTTP/3 instead of HTTP/1.1 or HTTP/2.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Whether to send the first requests of a resumed connection as 0-RTT early data.
+    ///
+    /// This saves a round trip when reconnecting to a server, but early data can be replayed by
+    /// an attacker on the network, in which case the server may receive a request twice, as when
+    /// a request is retried.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub zero_rtt: bool,
+}
+
 pub trait MaybeAuth: Sized {
     fn choose_one(&self, other: &Self) -> crate::Result<Self>;
 }
@@ -318,6 +435,97 @@ pub fn get_http_scheme_from_uri(uri: &Uri) -> &'static str {
     })
 }
 
+#[cfg(unix)]
+mod unix {
+    use std::{
+        io,
+        path::PathBuf,
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll},
+    };
+
+    use futures::future::BoxFuture;
+    use http::Uri;
+    use hyper::client::connect::{Connected, Connection};
+    use pin_project::pin_project;
+    use tokio::{
+        io::{AsyncRead, AsyncWrite, ReadBuf},
+        net::UnixStream,
+    };
+    use tower::Service;
+
+    /// Connects to a Unix domain socket, whatever the URI of the request.
+    #[derive(Clone, Debug)]
+    pub struct UnixConnector {
+        path: Arc<PathBuf>,
+    }
+
+    impl UnixConnector {
+        pub fn new(path: PathBuf) -> Self {
+            Self {
+                path: Arc::new(path),
+            }
+        }
+    }
+
+    impl Service<Uri> for UnixConnector {
+        type Response = UnixConnection;
+        type Error = io::Error;
+        type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _uri: Uri) -> Self::Future {
+            let path = Arc::clone(&self.path);
+            Box::pin(async move {
+                UnixStream::connect(path.as_path())
+                    .await
+                    .map(UnixConnection)
+            })
+        }
+    }
+
+    #[pin_project]
+    pub struct UnixConnection(#[pin] UnixStream);
+
+    impl Connection for UnixConnection {
+        fn connected(&self) -> Connected {
+            Connected::new()
+        }
+    }
+
+    impl AsyncRead for UnixConnection {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            self.project().0.poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncWrite for UnixConnection {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.project().0.poll_write(cx, buf)
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            self.project().0.poll_flush(cx)
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            self.project().0.poll_shutdown(cx)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,4 +559,40 @@ mod tests {
             Some(&HeaderValue::from_static("foo"))
         );
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_client_sends_requests_to_socket() {
+        use hyper::{
+            server::accept::from_stream,
+            service::{make_service_fn, service_fn},
+            Response, Server,
+        };
+        use tokio::net::UnixListener;
+        use tokio_stream::wrappers::UnixListenerStream;
+
+        let path = crate::test_util::temp_dir().join("http.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, hyper::Error>(service_fn(|request: Request<Body>| async move {
+                let body = format!(
+                    "{} {}",
+                    request.headers()["Host"].to_str().unwrap(),
+                    request.uri().path()
+                );
+                Ok::<_, hyper::Error>(Response::new(Body::from(body)))
+            }))
+        });
+        tokio::spawn(
+            Server::builder(from_stream(UnixListenerStream::new(listener))).serve(make_service),
+        );
+
+        let client = HttpClient::new_unix(&path);
+        let request = Request::get("http://localhost/ping")
+            .body(Body::empty())
+            .unwrap();
+        let response = client.send(request).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(body, "localhost /ping");
+    }
 }
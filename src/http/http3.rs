@@ -0,0 +1,224 @@
+//! The HTTP/3 transport of `HttpClient`, sending requests over QUIC connections.
+use std::{collections::HashMap, fs::File, io::BufReader, net::SocketAddr, path::Path, sync::Arc};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::future;
+use http::{Request, Response, Uri};
+use hyper::body::{Body, HttpBody};
+use quinn::{ClientConfig, Endpoint};
+use rustls::{Certificate, PrivateKey, RootCertStore};
+use rustls_pemfile::Item;
+use tokio::sync::Mutex;
+
+use super::Http3Config;
+use crate::tls::TlsConfig;
+
+type SendRequest = h3::client::SendRequest<h3_quinn::OpenStreams, Bytes>;
+
+/// Sends requests over HTTP/3, keeping one QUIC connection open per authority.
+#[derive(Clone)]
+pub struct Http3Client {
+    endpoint: Endpoint,
+    zero_rtt: bool,
+    connections: Arc<Mutex<HashMap<String, SendRequest>>>,
+}
+
+impl Http3Client {
+    pub fn new(tls: Option<&TlsConfig>, config: &Http3Config) -> crate::Result<Self> {
+        let mut crypto = build_tls_config(tls, vector_core::fips::is_enabled())?;
+        crypto.alpn_protocols = vec![b"h3".to_vec()];
+        // The session tickets are kept by the default in-memory resumption store, which lets the
+        // first requests of a reconnection be sent as early data.
+        crypto.enable_early_data = config.zero_rtt;
+
+        let mut endpoint = Endpoint::client(SocketAddr::from(([0u16; 8], 0)))
+            .or_else(|_| Endpoint::client(SocketAddr::from(([0u8; 4], 0))))?;
+        endpoint.set_default_client_config(ClientConfig::new(Arc::new(crypto)));
+
+        Ok(Self {
+            endpoint,
+            zero_rtt: config.zero_rtt,
+            connections: Default::default(),
+        })
+    }
+
+    pub async fn send<B>(self, request: Request<B>) -> crate::Result<Response<Body>>
+    where
+        B: HttpBody,
+        B::Error: Into<crate::Error>,
+    {
+        let (parts, body) = request.into_parts();
+        let body = hyper::body::to_bytes(body).await.map_err(Into::into)?;
+        let request = Request::from_parts(parts, ());
+
+        let authority = request
+            .uri()
+            .authority()
+            .map(ToString::to_string)
+            .ok_or("The URI of the request has no host.")?;
+        let mut send_request = self.connection(request.uri(), &authority).await?;
+
+        let result = round_trip(&mut send_request, request, body).await;
+        if result.is_err() {
+            // The connection may be broken, so the next request opens a new one.
+            self.connections.lock().await.remove(&authority);
+        }
+        result.map_err(Into::into)
+    }
+
+    async fn connection(&self, uri: &Uri, authority: &str) -> crate::Result<SendRequest> {
+        // The lock is held while connecting so that concurrent requests share the new connection.
+        let mut connections = self.connections.lock().await;
+        if let Some(send_request) = connections.get(authority) {
+            return Ok(send_request.clone());
+        }
+
+        let send_request = self.connect(uri).await?;
+        connections.insert(authority.to_owned(), send_request.clone());
+        Ok(send_request)
+    }
+
+    async fn connect(&self, uri: &Uri) -> crate::Result<SendRequest> {
+        let host = uri.host().ok_or("The URI of the request has no host.")?;
+        let server_name = host.trim_start_matches('[').trim_end_matches(']');
+        let port = uri.port_u16().unwrap_or(443);
+        let addr = tokio::net::lookup_host((server_name, port))
+            .await?
+            .next()
+            .ok_or_else(|| format!("Unable to resolve {}.", host))?;
+
+        let connecting = self.endpoint.connect(addr, server_name)?;
+        let connection = if self.zero_rtt {
+            // Without a session ticket for this server, the handshake has to complete first.
+            match connecting.into_0rtt() {
+                Ok((connection, _accepted)) => connection,
+                Err(connecting) => connecting.await?,
+            }
+        } else {
+            connecting.await?
+        };
+
+        let (mut driver, send_request) =
+            h3::client::new(h3_quinn::Connection::new(connection)).await?;
+        tokio::spawn(async move {
+            if let Err(error) = future::poll_fn(|cx| driver.poll_close(cx)).await {
+                debug!(message = "HTTP/3 connection closed.", %error);
+            }
+        });
+        Ok(send_request)
+    }
+}
+
+async fn round_trip(
+    send_request: &mut SendRequest,
+    request: Request<()>,
+    body: Bytes,
+) -> Result<Response<Body>, h3::Error> {
+    let mut stream = send_request.send_request(request).await?;
+    if !body.is_empty() {
+        stream.send_data(body).await?;
+    }
+    stream.finish().await?;
+
+    let response = stream.recv_response().await?;
+    let mut body = BytesMut::new();
+    while let Some(chunk) = stream.recv_data().await? {
+        body.put(chunk);
+    }
+    Ok(response.map(|()| Body::from(body.freeze())))
+}
+
+fn build_tls_config(
+    tls: Option<&TlsConfig>,
+    fips_mode: bool,
+) -> crate::Result<rustls::ClientConfig> {
+    // QUIC is provided by rustls, whose cryptography comes from ring rather than the validated
+    // module.
+    if fips_mode {
+        return Err("HTTP/3 is not allowed in FIPS mode.".into());
+    }
+    let tls = tls.cloned().unwrap_or_default();
+    if tls.verify_certificate == Some(false) || tls.verify_hostname == Some(false) {
+        return Err("Certificate and hostname verification can't be disabled with HTTP/3.".into());
+    }
+    if tls.alpn_protocols.is_some() {
+        return Err("ALPN protocols can't be set with HTTP/3.".into());
+    }
+    if tls.key_pass.is_some() {
+        return Err("Encrypted private keys are not supported with HTTP/3.".into());
+    }
+    if tls.spiffe.is_some() {
+        return Err("SPIFFE workload identities are not supported with HTTP/3.".into());
+    }
+
+    let mut roots = RootCertStore::empty();
+    let native_certs = rustls_native_certs::load_native_certs()?
+        .into_iter()
+        .map(|cert| cert.0)
+        .collect::<Vec<_>>();
+    roots.add_parsable_certificates(&native_certs);
+    if let Some(ca_file) = &tls.ca_file {
+        for cert in load_certs(ca_file)? {
+            roots.add(&cert)?;
+        }
+    }
+
+    let builder = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots);
+    match (&tls.crt_file, &tls.key_file) {
+        (Some(crt_file), Some(key_file)) => {
+            Ok(builder.with_single_cert(load_certs(crt_file)?, load_key(key_file)?)?)
+        }
+        (None, None) => Ok(builder.with_no_client_auth()),
+        _ => Err("Both `crt_file` and `key_file` must be set to authenticate with HTTP/3.".into()),
+    }
+}
+
+fn load_certs(path: &Path) -> crate::Result<Vec<Certificate>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let certs = rustls_pemfile::certs(&mut reader)?;
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+fn load_key(path: &Path) -> crate::Result<PrivateKey> {
+    let mut reader = BufReader::new(File::open(path)?);
+    loop {
+        match rustls_pemfile::read_one(&mut reader)? {
+            Some(Item::RSAKey(key) | Item::PKCS8Key(key) | Item::ECKey(key)) => {
+                return Ok(PrivateKey(key))
+            }
+            Some(_) => continue,
+            None => return Err(format!("No private key found in {}.", path.display()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unverified_tls() {
+        let tls = TlsConfig {
+            verify_certificate: Some(false),
+            ..Default::default()
+        };
+        assert!(build_tls_config(Some(&tls), false).is_err());
+    }
+
+    #[test]
+    fn rejects_fips_mode() {
+        let error = build_tls_config(None, true).unwrap_err();
+        assert!(error.to_string().contains("FIPS mode"));
+    }
+
+    #[test]
+    fn requires_both_crt_and_key_files() {
+        let tls = TlsConfig {
+            crt_file: Some("tests/data/ca/intermediate_server/certs/localhost.cert.pem".into()),
+            ..Default::default()
+        };
+        assert!(build_tls_config(Some(&tls), false).is_err());
+    }
+}
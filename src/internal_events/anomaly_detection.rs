@@ -0,0 +1,27 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AnomalyDetected<'a> {
+    pub name: &'a str,
+    pub value: f64,
+    pub expected: f64,
+    pub direction: &'static str,
+}
+
+impl<'a> InternalEvent for AnomalyDetected<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Anomalous metric value detected.",
+            metric_name = %self.name,
+            value = %self.value,
+            expected = %self.expected,
+            direction = %self.direction,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "anomalies_detected_total", 1,
+            "direction" => self.direction,
+        );
+    }
+}
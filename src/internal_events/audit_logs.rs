@@ -0,0 +1,38 @@
+use metrics::counter;
+use vector_common::internal_event::{ComponentEventsDropped, UNINTENTIONAL};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct AuditLogsBytesReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for AuditLogsBytesReceived {
+    fn emit(self) {
+        trace!(
+            message = "Bytes received.",
+            byte_size = %self.byte_size,
+            protocol = "internal",
+        );
+        counter!(
+            "component_received_bytes_total", self.byte_size as u64,
+            "protocol" => "internal",
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditLogsEventsDropped {
+    pub count: u64,
+}
+
+impl InternalEvent for AuditLogsEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count as usize,
+            reason: "The source lagged behind the audit log.",
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsRequestError<'a, E> {
+    pub operation: &'static str,
+    pub shard_id: Option<&'a str>,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AwsKinesisStreamsRequestError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Request to AWS Kinesis Data Streams failed.",
+            operation = %self.operation,
+            shard_id = %self.shard_id.unwrap_or_default(),
+            error = %self.error,
+            error_code = "failed_kinesis_request",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_kinesis_request",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsCheckpointWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for AwsKinesisStreamsCheckpointWriteError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing checkpoints.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "writing_checkpoints",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "writing_checkpoints",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -13,7 +13,7 @@ mod s3 {
     };
 
     use super::*;
-    use crate::sources::aws_s3::sqs::ProcessingError;
+    use crate::sources::aws_s3::{poll::DiscoveryError, sqs::ProcessingError};
 
     #[derive(Debug)]
     pub struct SqsMessageProcessingError<'a> {
@@ -122,6 +122,84 @@ mod s3 {
             counter!("sqs_message_delete_batch_failed_total", 1);
         }
     }
+
+    #[derive(Debug)]
+    pub struct AwsS3ObjectDiscoveryError<'a> {
+        pub error: &'a DiscoveryError,
+    }
+
+    impl<'a> InternalEvent for AwsS3ObjectDiscoveryError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed to discover S3 objects.",
+                error = %self.error,
+                error_code = "failed_discovering_s3_objects",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_discovering_s3_objects",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct AwsS3ObjectProcessingError<'a> {
+        pub bucket: &'a str,
+        pub key: &'a str,
+        pub error: &'a ProcessingError,
+    }
+
+    impl<'a> InternalEvent for AwsS3ObjectProcessingError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed to process S3 object.",
+                bucket = %self.bucket,
+                key = %self.key,
+                error = %self.error,
+                error_code = "failed_processing_s3_object",
+                error_type = error_type::PARSER_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_processing_s3_object",
+                "error_type" => error_type::PARSER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct AwsS3CheckpointWriteError<'a> {
+        pub error: std::io::Error,
+        pub path: &'a std::path::Path,
+    }
+
+    impl<'a> InternalEvent for AwsS3CheckpointWriteError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed writing checkpoints.",
+                path = ?self.path,
+                error = %self.error,
+                error_code = "writing_checkpoints",
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "writing_checkpoints",
+                "error_type" => error_type::WRITER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
 }
 
 #[derive(Debug)]
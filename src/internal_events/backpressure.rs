@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::config::{ComponentKey, OutputId};
+
+#[derive(Debug)]
+pub struct SourceStalled<'a> {
+    pub output: &'a OutputId,
+    pub blocked_for: Duration,
+    pub blame_chain: &'a [ComponentKey],
+}
+
+impl InternalEvent for SourceStalled<'_> {
+    fn emit(self) {
+        let blamed = self
+            .blame_chain
+            .last()
+            .unwrap_or(&self.output.component)
+            .to_string();
+        let blame_chain = self
+            .blame_chain
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        warn!(
+            message = "Source is stalled by backpressure.",
+            component_id = %self.output.component,
+            output = ?self.output.port,
+            blocked_secs = self.blocked_for.as_secs_f64(),
+            blamed_component_id = %blamed,
+            blame_chain = %blame_chain,
+        );
+        counter!(
+            "source_stalls_total", 1,
+            "component_id" => self.output.component.to_string(),
+            "blamed_component_id" => blamed,
+        );
+    }
+}
@@ -109,6 +109,26 @@ impl InternalEvent for StreamClosedError {
     }
 }
 
+#[derive(Debug)]
+pub struct ComponentEventsAbandoned {
+    pub count: usize,
+}
+
+impl InternalEvent for ComponentEventsAbandoned {
+    fn emit(self) {
+        error!(
+            message = "Events left in the input of the component were abandoned.",
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("component_abandoned_events_total", self.count as u64);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason: "Component was torn down before draining its input.",
+        });
+    }
+}
+
 #[derive(Debug)]
 pub struct RequestCompleted {
     pub start: Instant,
@@ -0,0 +1,69 @@
+use metrics::{counter, gauge};
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct LeadershipAcquired<'a> {
+    pub lease_name: &'a str,
+    pub identity: &'a str,
+}
+
+impl InternalEvent for LeadershipAcquired<'_> {
+    fn emit(self) {
+        info!(
+            message = "Acquired leadership, starting source.",
+            lease_name = %self.lease_name,
+            identity = %self.identity,
+        );
+        counter!("leadership_acquisitions_total", 1);
+        gauge!("leader", 1.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct LeadershipLost<'a> {
+    pub lease_name: &'a str,
+    pub identity: &'a str,
+}
+
+impl InternalEvent for LeadershipLost<'_> {
+    fn emit(self) {
+        error!(
+            message = "Lost leadership, stopping source.",
+            lease_name = %self.lease_name,
+            identity = %self.identity,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+        gauge!("leader", 0.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct LeaderElectionError<'a> {
+    pub lease_name: &'a str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for LeaderElectionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to acquire or renew the lease.",
+            lease_name = %self.lease_name,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
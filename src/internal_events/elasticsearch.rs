@@ -0,0 +1,30 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ElasticsearchInvalidVersionError<'a> {
+    pub version: &'a str,
+}
+
+impl InternalEvent for ElasticsearchInvalidVersionError<'_> {
+    fn emit(self) {
+        let reason = "Document version is not a non-negative integer.";
+        error!(
+            message = reason,
+            version = %self.version,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,58 @@
+use metrics::{counter, Label};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct LabeledEventsReceived {
+    pub labels: Vec<Label>,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for LabeledEventsReceived {
+    fn emit(self) {
+        trace!(
+            message = "Labeled events received.",
+            labels = ?self.labels,
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!(
+            "component_received_labeled_events_total",
+            self.count as u64,
+            self.labels.clone()
+        );
+        counter!(
+            "component_received_labeled_event_bytes_total",
+            self.byte_size as u64,
+            self.labels
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LabeledEventsSent {
+    pub labels: Vec<Label>,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for LabeledEventsSent {
+    fn emit(self) {
+        trace!(
+            message = "Labeled events sent.",
+            labels = ?self.labels,
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!(
+            "component_sent_labeled_events_total",
+            self.count as u64,
+            self.labels.clone()
+        );
+        counter!(
+            "component_sent_labeled_event_bytes_total",
+            self.byte_size as u64,
+            self.labels
+        );
+    }
+}
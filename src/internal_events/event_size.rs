@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct EventSizeExceeded {
+    pub outcome: &'static str,
+    pub count: usize,
+}
+
+impl InternalEvent for EventSizeExceeded {
+    fn emit(self) {
+        debug!(
+            message = "Events exceeded the maximum event size.",
+            outcome = %self.outcome,
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "event_size_exceeded_total", self.count as u64,
+            "outcome" => self.outcome,
+        );
+        if self.outcome == "dropped" {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: self.count,
+                reason: "Event exceeds the maximum event size.",
+            });
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, ComponentEventsDropped, UNINTENTIONAL};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct ExtProcRequestError<'a> {
+    pub error: &'a str,
+    pub error_type: &'static str,
+    pub count: usize,
+    pub dropped: bool,
+}
+
+impl InternalEvent for ExtProcRequestError<'_> {
+    fn emit(self) {
+        let reason = "External processor request failed.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = self.error_type,
+            stage = error_stage::PROCESSING,
+            dropped = self.dropped,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => self.error_type,
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.dropped {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: self.count,
+                reason,
+            });
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct FieldEncryptionFailed {
+    pub count: usize,
+    pub error: String,
+}
+
+impl InternalEvent for FieldEncryptionFailed {
+    fn emit(self) {
+        let reason = "Failed to encrypt the fields of events.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
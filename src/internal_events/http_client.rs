@@ -4,7 +4,7 @@ use http::{
     header::{self, HeaderMap, HeaderValue},
     Request, Response,
 };
-use hyper::{body::HttpBody, Error};
+use hyper::body::HttpBody;
 use metrics::{counter, histogram};
 use vector_core::internal_event::InternalEvent;
 
@@ -67,7 +67,7 @@ impl<'a, T: HttpBody> InternalEvent for GotHttpResponse<'a, T> {
 
 #[derive(Debug)]
 pub struct GotHttpWarning<'a> {
-    pub error: &'a Error,
+    pub error: &'a (dyn std::error::Error + 'a),
     pub roundtrip: Duration,
 }
 
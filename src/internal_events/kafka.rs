@@ -1,5 +1,9 @@
-use metrics::{counter, gauge};
-use vector_core::{internal_event::InternalEvent, update_counter};
+use crate::emit;
+use metrics::{absolute_counter, counter, gauge};
+use vector_core::{
+    internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL, UNINTENTIONAL},
+    update_counter,
+};
 
 use vector_common::internal_event::{error_stage, error_type};
 
@@ -115,6 +119,7 @@ impl InternalEvent for KafkaReadError {
 pub struct KafkaStatisticsReceived<'a> {
     pub statistics: &'a rdkafka::Statistics,
     pub expose_lag_metrics: bool,
+    pub expose_partition_metrics: bool,
 }
 
 impl InternalEvent for KafkaStatisticsReceived<'_> {
@@ -151,6 +156,52 @@ impl InternalEvent for KafkaStatisticsReceived<'_> {
             self.statistics.rxmsg_bytes as u64
         );
 
+        for broker in self.statistics.brokers.values() {
+            // The internal broker only holds messages that wait for a partition leader.
+            if broker.source == "internal" {
+                continue;
+            }
+            let name = broker.name.clone();
+            gauge!("kafka_broker_queue_messages", broker.outbuf_msg_cnt as f64, "broker" => name.clone());
+            gauge!("kafka_broker_inflight_requests", broker.waitresp_cnt as f64, "broker" => name.clone());
+            for (metric, window) in [
+                ("kafka_broker_queue_latency_seconds", &broker.int_latency),
+                (
+                    "kafka_broker_request_queue_latency_seconds",
+                    &broker.outbuf_latency,
+                ),
+                ("kafka_broker_rtt_seconds", &broker.rtt),
+            ] {
+                if let Some(window) = window {
+                    // Latencies are reported in microseconds.
+                    gauge!(metric, window.avg as f64 / 1_000_000.0, "broker" => name.clone());
+                }
+            }
+            absolute_counter!("kafka_broker_requests_total", broker.tx, "broker" => name.clone());
+            absolute_counter!("kafka_broker_request_errors_total", broker.txerrs, "broker" => name.clone());
+            absolute_counter!("kafka_broker_request_retries_total", broker.txretries, "broker" => name.clone());
+            absolute_counter!("kafka_broker_request_timeouts_total", broker.req_timeouts, "broker" => name.clone());
+            absolute_counter!("kafka_broker_responses_total", broker.rx, "broker" => name.clone());
+            absolute_counter!("kafka_broker_response_errors_total", broker.rxerrs, "broker" => name.clone());
+            if let Some(connects) = broker.connects {
+                absolute_counter!("kafka_broker_connects_total", connects as u64, "broker" => name.clone());
+            }
+            if let Some(disconnects) = broker.disconnects {
+                absolute_counter!("kafka_broker_disconnects_total", disconnects as u64, "broker" => name);
+            }
+        }
+
+        if let Some(cgrp) = &self.statistics.cgrp {
+            absolute_counter!(
+                "kafka_consumer_group_rebalances_total",
+                cgrp.rebalance_cnt as u64
+            );
+            gauge!(
+                "kafka_consumer_group_assigned_partitions",
+                cgrp.assignment_size as f64
+            );
+        }
+
         if self.expose_lag_metrics {
             for (topic_id, topic) in &self.statistics.topics {
                 for (partition_id, partition) in &topic.partitions {
@@ -158,6 +209,19 @@ impl InternalEvent for KafkaStatisticsReceived<'_> {
                 }
             }
         }
+
+        if self.expose_partition_metrics {
+            for (topic_id, topic) in &self.statistics.topics {
+                // Partition -1 holds the messages that are not yet assigned to a partition.
+                for (partition_id, partition) in topic.partitions.iter().filter(|(id, _)| **id >= 0)
+                {
+                    gauge!("kafka_partition_queue_messages", partition.msgq_cnt as f64, "topic_id" => topic_id.clone(), "partition_id" => partition_id.to_string());
+                    gauge!("kafka_partition_transmit_queue_messages", partition.xmit_msgq_cnt as f64, "topic_id" => topic_id.clone(), "partition_id" => partition_id.to_string());
+                    gauge!("kafka_partition_inflight_messages", partition.msgs_inflight as f64, "topic_id" => topic_id.clone(), "partition_id" => partition_id.to_string());
+                    absolute_counter!("kafka_partition_produced_messages_total", partition.txmsgs, "topic_id" => topic_id.clone(), "partition_id" => partition_id.to_string());
+                }
+            }
+        }
     }
 }
 
@@ -185,3 +249,243 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         counter!("kafka_header_extraction_failures_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaMessageFiltered<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+}
+
+impl<'a> InternalEvent for KafkaMessageFiltered<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Message skipped by header filters.",
+            topic = self.topic,
+            partition = %self.partition,
+        );
+        counter!(
+            "kafka_filtered_messages_total", 1,
+            "topic" => self.topic.to_string(),
+            "partition" => self.partition.to_string(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaInvalidPartitionError<'a> {
+    pub value: &'a str,
+}
+
+impl<'a> InternalEvent for KafkaInvalidPartitionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Rendered partition is not a valid partition number. Dropping event.",
+            value = self.value,
+            error_code = "invalid_partition",
+            error_type = error_type::TEMPLATE_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_partition",
+            "error_type" => error_type::TEMPLATE_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Rendered partition is not a valid partition number.",
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaUnknownTopicEventsDropped<'a> {
+    pub topic: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for KafkaUnknownTopicEventsDropped<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Topic does not exist.",
+            topic = self.topic,
+            internal_log_rate_limit = true,
+        );
+        counter!("kafka_unknown_topic_events_total", self.count as u64);
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Topic does not exist.",
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rdkafka::statistics::{Broker, ConsumerGroup, Partition, Topic, Window};
+    use vector_core::event::{Metric, MetricValue};
+
+    use super::*;
+    use crate::metrics::{self, Controller};
+
+    fn statistics() -> rdkafka::Statistics {
+        let broker = Broker {
+            name: "localhost:9092/1".to_owned(),
+            source: "configured".to_owned(),
+            outbuf_msg_cnt: 3,
+            waitresp_cnt: 2,
+            tx: 20,
+            txerrs: 1,
+            txretries: 2,
+            req_timeouts: 1,
+            rx: 19,
+            rxerrs: 1,
+            connects: Some(2),
+            disconnects: Some(1),
+            int_latency: Some(Window {
+                avg: 1_500,
+                ..Default::default()
+            }),
+            rtt: Some(Window {
+                avg: 250_000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let internal = Broker {
+            name: "GroupCoordinator".to_owned(),
+            source: "internal".to_owned(),
+            outbuf_msg_cnt: 7,
+            tx: 7,
+            ..Default::default()
+        };
+        let partition = Partition {
+            msgq_cnt: 4,
+            xmit_msgq_cnt: 5,
+            msgs_inflight: 6,
+            txmsgs: 30,
+            consumer_lag: 8,
+            ..Default::default()
+        };
+        let unassigned = Partition {
+            msgq_cnt: 9,
+            consumer_lag: -1,
+            ..Default::default()
+        };
+        let topic = Topic {
+            topic: "logs".to_owned(),
+            partitions: HashMap::from([(0, partition), (-1, unassigned)]),
+            ..Default::default()
+        };
+
+        rdkafka::Statistics {
+            msg_cnt: 10,
+            msg_size: 1024,
+            tx: 20,
+            tx_bytes: 2048,
+            rx: 19,
+            rx_bytes: 512,
+            txmsgs: 30,
+            txmsg_bytes: 3072,
+            brokers: HashMap::from([
+                ("localhost:9092/1".to_owned(), broker),
+                ("GroupCoordinator".to_owned(), internal),
+            ]),
+            topics: HashMap::from([("logs".to_owned(), topic)]),
+            cgrp: Some(ConsumerGroup {
+                rebalance_cnt: 3,
+                assignment_size: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn value(metrics: &[Metric], name: &str, tags: &[(&str, &str)]) -> f64 {
+        let metric = metrics
+            .iter()
+            .find(|metric| {
+                metric.name() == name
+                    && tags
+                        .iter()
+                        .all(|(key, value)| metric.tag_value(key).as_deref() == Some(*value))
+            })
+            .unwrap_or_else(|| panic!("Missing metric {} {:?}", name, tags));
+        match metric.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+            value => panic!("Unexpected value {:?} of metric {}", value, name),
+        }
+    }
+
+    #[test]
+    fn emits_statistics_metrics() {
+        metrics::init_test();
+        emit!(KafkaStatisticsReceived {
+            statistics: &statistics(),
+            expose_lag_metrics: true,
+            expose_partition_metrics: true,
+        });
+        let metrics = Controller::get()
+            .expect("There must be a controller")
+            .capture_metrics();
+
+        for (name, expected) in [
+            ("kafka_queue_messages", 10.0),
+            ("kafka_queue_messages_bytes", 1024.0),
+            ("kafka_requests_total", 20.0),
+            ("kafka_requests_bytes_total", 2048.0),
+            ("kafka_responses_total", 19.0),
+            ("kafka_responses_bytes_total", 512.0),
+            ("kafka_produced_messages_total", 30.0),
+            ("kafka_produced_messages_bytes_total", 3072.0),
+            ("kafka_consumer_group_rebalances_total", 3.0),
+            ("kafka_consumer_group_assigned_partitions", 1.0),
+        ] {
+            assert_eq!(value(&metrics, name, &[]), expected, "{}", name);
+        }
+
+        let broker = [("broker", "localhost:9092/1")];
+        for (name, expected) in [
+            ("kafka_broker_queue_messages", 3.0),
+            ("kafka_broker_inflight_requests", 2.0),
+            ("kafka_broker_queue_latency_seconds", 0.0015),
+            ("kafka_broker_rtt_seconds", 0.25),
+            ("kafka_broker_requests_total", 20.0),
+            ("kafka_broker_request_errors_total", 1.0),
+            ("kafka_broker_request_retries_total", 2.0),
+            ("kafka_broker_request_timeouts_total", 1.0),
+            ("kafka_broker_responses_total", 19.0),
+            ("kafka_broker_response_errors_total", 1.0),
+            ("kafka_broker_connects_total", 2.0),
+            ("kafka_broker_disconnects_total", 1.0),
+        ] {
+            assert_eq!(value(&metrics, name, &broker), expected, "{}", name);
+        }
+        // The latencies are only reported once they were measured.
+        assert!(!metrics
+            .iter()
+            .any(|metric| metric.name() == "kafka_broker_request_queue_latency_seconds"));
+        assert!(!metrics
+            .iter()
+            .any(|metric| metric.tag_value("broker").as_deref() == Some("GroupCoordinator")));
+
+        let partition = [("topic_id", "logs"), ("partition_id", "0")];
+        for (name, expected) in [
+            ("kafka_consumer_lag", 8.0),
+            ("kafka_partition_queue_messages", 4.0),
+            ("kafka_partition_transmit_queue_messages", 5.0),
+            ("kafka_partition_inflight_messages", 6.0),
+            ("kafka_partition_produced_messages_total", 30.0),
+        ] {
+            assert_eq!(value(&metrics, name, &partition), expected, "{}", name);
+        }
+        let unassigned = [("topic_id", "logs"), ("partition_id", "-1")];
+        assert_eq!(value(&metrics, "kafka_consumer_lag", &unassigned), -1.0);
+        assert!(!metrics.iter().any(|metric| {
+            metric.name().starts_with("kafka_partition_")
+                && metric.tag_value("partition_id").as_deref() == Some("-1")
+        }));
+    }
+}
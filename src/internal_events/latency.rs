@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+use metrics::histogram;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct EndToEndLatency<'a> {
+    pub source_id: &'a str,
+    pub latency: Duration,
+}
+
+impl<'a> InternalEvent for EndToEndLatency<'a> {
+    fn emit(self) {
+        histogram!(
+            "end_to_end_latency_seconds",
+            self.latency,
+            "source_id" => self.source_id.to_owned(),
+        );
+    }
+}
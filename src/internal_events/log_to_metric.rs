@@ -73,3 +73,29 @@ impl<'a> InternalEvent for LogToMetricParseFloatError<'a> {
         emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
     }
 }
+
+pub struct LogToMetricDeserializeError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for LogToMetricDeserializeError {
+    fn emit(self) {
+        let reason = "Failed to convert log event into a metric.";
+        error!(
+            message = reason,
+            error = ?self.error,
+            error_code = "failed_deserializing_metric",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_deserializing_metric",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason })
+    }
+}
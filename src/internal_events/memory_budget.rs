@@ -0,0 +1,42 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct ComponentMemoryUsed {
+    pub bytes: usize,
+}
+
+impl InternalEvent for ComponentMemoryUsed {
+    fn emit(self) {
+        gauge!("component_memory_used_bytes", self.bytes as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryLimitExceeded {
+    pub limit: &'static str,
+    pub policy: &'static str,
+    pub count: usize,
+}
+
+impl InternalEvent for MemoryLimitExceeded {
+    fn emit(self) {
+        debug!(
+            message = "Memory limit exceeded.",
+            limit = %self.limit,
+            policy = %self.policy,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "memory_limit_exceeded_total", 1,
+            "limit" => self.limit,
+            "policy" => self.policy,
+        );
+        if self.count > 0 {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: self.count,
+                reason: "Memory limit exceeded.",
+            });
+        }
+    }
+}
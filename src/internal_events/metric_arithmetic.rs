@@ -0,0 +1,44 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct MetricArithmeticUnsupportedValue<'a> {
+    pub name: &'a str,
+}
+
+impl<'a> InternalEvent for MetricArithmeticUnsupportedValue<'a> {
+    fn emit(self) {
+        error!(
+            message = "Operand metric must be a counter or a gauge.",
+            metric_name = %self.name,
+            error_code = "unsupported_value",
+            error_type = error_type::INVALID_METRIC,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "unsupported_value",
+            "error_type" => error_type::INVALID_METRIC,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MetricArithmeticDivisionByZero<'a> {
+    pub name: &'a str,
+}
+
+impl<'a> InternalEvent for MetricArithmeticDivisionByZero<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Division by zero, skipping derived metric.",
+            metric_name = %self.name,
+            internal_log_rate_limit = true,
+        );
+        counter!("metric_arithmetic_division_by_zero_total", 1);
+    }
+}
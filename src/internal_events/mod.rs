@@ -5,10 +5,14 @@ mod adaptive_concurrency;
 mod aggregate;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
 mod amqp;
+#[cfg(feature = "transforms-anomaly_detection")]
+mod anomaly_detection;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+#[cfg(feature = "sources-audit_logs")]
+mod audit_logs;
 #[cfg(feature = "aws-core")]
 mod aws;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
@@ -24,12 +28,19 @@ mod aws_ecs_metrics;
 mod aws_kinesis;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+mod aws_kinesis_streams;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
+mod backpressure;
 mod batch;
+#[cfg(feature = "sinks-blackhole")]
+mod blackhole;
 mod codecs;
 mod common;
 mod conditions;
+#[cfg(feature = "coordination")]
+mod coordination;
 #[cfg(feature = "sinks-datadog_metrics")]
 mod datadog_metrics;
 #[cfg(feature = "sinks-datadog_traces")]
@@ -42,11 +53,18 @@ mod demo_logs;
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(feature = "sinks-elasticsearch")]
+mod elasticsearch;
 mod encoding_transcode;
+mod event_labels;
+mod event_size;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
 mod exec;
+#[cfg(feature = "transforms-ext_proc")]
+mod ext_proc;
+mod field_encryption;
 #[cfg(any(feature = "sources-file-descriptor", feature = "sources-stdin"))]
 mod file_descriptor;
 #[cfg(feature = "transforms-filter")]
@@ -76,12 +94,16 @@ mod journald;
 mod kafka;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
+mod latency;
 mod log_to_metric;
 mod logplex;
 #[cfg(feature = "sinks-loki")]
 mod loki;
 #[cfg(feature = "transforms-lua")]
 mod lua;
+mod memory_budget;
+#[cfg(feature = "transforms-metric_arithmetic")]
+mod metric_arithmetic;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
@@ -91,6 +113,7 @@ mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+mod ordered_merge;
 mod parser;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
@@ -99,14 +122,22 @@ mod process;
 mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 mod pulsar;
+#[cfg(feature = "transforms-python")]
+mod python;
 #[cfg(feature = "sources-redis")]
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
 mod sample;
+mod saturation;
+mod schema_validation;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sources-sftp")]
+mod sftp;
+mod sink_quarantine;
+mod sink_shadow;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -116,12 +147,20 @@ mod statsd_sink;
 mod tag_cardinality_limit;
 mod tcp;
 mod template;
+mod tenancy;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
 mod udp;
 mod unix;
-#[cfg(feature = "sinks-websocket")]
+#[cfg(any(
+    feature = "sources-websocket",
+    feature = "sources-websocket_server",
+    feature = "sinks-websocket",
+    feature = "sinks-websocket_server"
+))]
 mod websocket;
+#[cfg(feature = "transforms-window")]
+mod window;
 
 #[cfg(any(
     feature = "sources-file",
@@ -138,10 +177,14 @@ pub(crate) use mongodb_metrics::*;
 pub(crate) use self::aggregate::*;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
 pub(crate) use self::amqp::*;
+#[cfg(feature = "transforms-anomaly_detection")]
+pub(crate) use self::anomaly_detection::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub(crate) use self::apache_metrics::*;
 #[cfg(feature = "api")]
 pub(crate) use self::api::*;
+#[cfg(feature = "sources-audit_logs")]
+pub(crate) use self::audit_logs::*;
 #[cfg(feature = "aws-core")]
 pub(crate) use self::aws::*;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
@@ -157,9 +200,15 @@ pub(crate) use self::aws_ecs_metrics::*;
 pub(crate) use self::aws_kinesis::*;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub(crate) use self::aws_kinesis_firehose::*;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub(crate) use self::aws_kinesis_streams::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sinks-blackhole")]
+pub(crate) use self::blackhole::*;
 pub(crate) use self::codecs::*;
+#[cfg(feature = "coordination")]
+pub(crate) use self::coordination::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
 #[cfg(feature = "sinks-datadog_traces")]
@@ -172,10 +221,14 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(feature = "sinks-elasticsearch")]
+pub(crate) use self::elasticsearch::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
 pub(crate) use self::exec::*;
+#[cfg(feature = "transforms-ext_proc")]
+pub(crate) use self::ext_proc::*;
 #[cfg(any(
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
@@ -222,6 +275,8 @@ pub(crate) use self::logplex::*;
 pub(crate) use self::loki::*;
 #[cfg(feature = "transforms-lua")]
 pub(crate) use self::lua::*;
+#[cfg(feature = "transforms-metric_arithmetic")]
+pub(crate) use self::metric_arithmetic::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
 #[cfg(feature = "sinks-nats")]
@@ -235,6 +290,8 @@ pub(crate) use self::postgresql_metrics::*;
 pub(crate) use self::prometheus::*;
 #[cfg(feature = "sinks-pulsar")]
 pub(crate) use self::pulsar::*;
+#[cfg(feature = "transforms-python")]
+pub(crate) use self::python::*;
 #[cfg(feature = "sources-redis")]
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
@@ -245,6 +302,8 @@ pub(crate) use self::remap::*;
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sources-sftp")]
+pub(crate) use self::sftp::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
 #[cfg(feature = "sinks-statsd")]
@@ -261,18 +320,29 @@ pub(crate) use self::throttle::*;
         feature = "sources-metrics",
         feature = "sources-statsd",
         feature = "sources-syslog",
-        feature = "sources-socket"
+        feature = "sources-socket",
+        feature = "sources-utils-http-prelude"
     ),
     unix
 ))]
 pub(crate) use self::unix::*;
-#[cfg(feature = "sinks-websocket")]
+#[cfg(any(
+    feature = "sources-websocket",
+    feature = "sources-websocket_server",
+    feature = "sinks-websocket",
+    feature = "sinks-websocket_server"
+))]
 pub(crate) use self::websocket::*;
+#[cfg(feature = "transforms-window")]
+pub(crate) use self::window::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, backpressure::*, batch::*, common::*, conditions::*,
+    encoding_transcode::*, event_labels::*, event_size::*, field_encryption::*, heartbeat::*,
+    latency::*, memory_budget::*, open::*, ordered_merge::*, process::*, saturation::*,
+    schema_validation::*, sink_quarantine::*, sink_shadow::*, socket::*, tcp::*, template::*,
+    tenancy::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
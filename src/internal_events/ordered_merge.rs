@@ -0,0 +1,18 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct OrderedMergeLateEvents {
+    pub count: usize,
+}
+
+impl InternalEvent for OrderedMergeLateEvents {
+    fn emit(self) {
+        debug!(
+            message = "Events received too late to be merged in order.",
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("ordered_merge_late_events_total", self.count as u64);
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use metrics::counter;
 use metrics::gauge;
 use vector_core::internal_event::InternalEvent;
@@ -47,6 +49,26 @@ impl InternalEvent for VectorReloaded<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct VectorReloadDrainTimeout<'a> {
+    pub component: &'a config::ComponentKey,
+    pub timeout: Duration,
+}
+
+impl InternalEvent for VectorReloadDrainTimeout<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Removed component failed to drain its input in time; aborting it.",
+            component_id = %self.component,
+            timeout_secs = self.timeout.as_secs_f64(),
+        );
+        counter!(
+            "reload_drain_timeouts_total", 1,
+            "component_id" => self.component.to_string(),
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct VectorStopped;
 
@@ -141,3 +163,57 @@ impl InternalEvent for VectorRecoveryError {
         counter!("recover_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct VectorAuditLogWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a std::path::Path,
+}
+
+impl InternalEvent for VectorAuditLogWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write to the audit log.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "audit_log_write",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "audit_log_write",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct VectorSystemdNotifyError<'a> {
+    pub error: std::io::Error,
+    pub state: &'a str,
+}
+
+#[cfg(target_os = "linux")]
+impl InternalEvent for VectorSystemdNotifyError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to notify systemd.",
+            state = %self.state,
+            error = %self.error,
+            error_code = "systemd_notify",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "systemd_notify",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct Saturation {
+    pub saturation: crate::saturation::Saturation,
+}
+
+impl InternalEvent for Saturation {
+    fn emit(self) {
+        let score = self.saturation.score();
+        trace!(
+            message = "Saturation sampled.",
+            score,
+            buffer = self.saturation.buffer,
+            concurrency = self.saturation.concurrency,
+            cpu = self.saturation.cpu,
+        );
+        gauge!("saturation_score", score);
+        gauge!("saturation_ratio", self.saturation.buffer, "resource" => "buffer");
+        gauge!("saturation_ratio", self.saturation.concurrency, "resource" => "concurrency");
+        gauge!("saturation_ratio", self.saturation.cpu, "resource" => "cpu");
+    }
+}
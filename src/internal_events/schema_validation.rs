@@ -0,0 +1,38 @@
+use metrics::counter;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SchemaValidationFailed {
+    pub count: usize,
+    pub error: String,
+    pub policy: &'static str,
+}
+
+impl InternalEvent for SchemaValidationFailed {
+    fn emit(self) {
+        let reason = "Events failed schema validation.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::SENDING,
+            policy = %self.policy,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        counter!(
+            "schema_validation_failed_events_total", self.count as u64,
+            "policy" => self.policy,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SftpListError<'a> {
+    pub error: std::io::Error,
+    pub directory: &'a str,
+}
+
+impl InternalEvent for SftpListError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to list directory.",
+            directory = %self.directory,
+            error = %self.error,
+            error_code = "failed_listing_directory",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_listing_directory",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SftpReadError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a str,
+}
+
+impl InternalEvent for SftpReadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read file.",
+            path = %self.path,
+            error = %self.error,
+            error_code = "failed_reading_file",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_reading_file",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SftpAfterReadError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a str,
+    pub action: &'static str,
+}
+
+impl InternalEvent for SftpAfterReadError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to clean up read file, retrying on next poll.",
+            path = %self.path,
+            action = %self.action,
+            error = %self.error,
+            error_code = "failed_cleaning_up_file",
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_cleaning_up_file",
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SftpCheckpointWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a str,
+}
+
+impl InternalEvent for SftpCheckpointWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing checkpoints.",
+            path = %self.path,
+            error = %self.error,
+            error_code = "writing_checkpoints",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "writing_checkpoints",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
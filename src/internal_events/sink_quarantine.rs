@@ -0,0 +1,68 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+use crate::config::QuarantinePolicy;
+
+#[derive(Debug)]
+pub struct SinkHealthcheckFailed<'a> {
+    pub error: &'a crate::Error,
+    pub consecutive_failures: u32,
+}
+
+impl<'a> InternalEvent for SinkHealthcheckFailed<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Healthcheck failed.",
+            error = %self.error,
+            consecutive_failures = %self.consecutive_failures,
+            internal_log_rate_limit = true,
+        );
+        counter!("healthcheck_failures_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkQuarantined {
+    pub policy: QuarantinePolicy,
+    pub consecutive_failures: u32,
+}
+
+impl InternalEvent for SinkQuarantined {
+    fn emit(self) {
+        let policy = match self.policy {
+            QuarantinePolicy::Buffer => "buffer",
+            QuarantinePolicy::Shed => "shed",
+        };
+        error!(
+            message = "Sink quarantined after consecutive healthcheck failures.",
+            consecutive_failures = %self.consecutive_failures,
+            policy,
+        );
+        counter!("quarantines_total", 1, "policy" => policy);
+        gauge!("quarantined", 1.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkQuarantineReleased;
+
+impl InternalEvent for SinkQuarantineReleased {
+    fn emit(self) {
+        info!(message = "Sink released from quarantine after a passing healthcheck.");
+        gauge!("quarantined", 0.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkQuarantineEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for SinkQuarantineEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Sink is quarantined.",
+        });
+    }
+}
@@ -0,0 +1,18 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SinkShadowEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for SinkShadowEventsDropped {
+    fn emit(self) {
+        debug!(
+            message = "Shadow sink is not keeping up, dropping the copy of events.",
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("shadow_events_dropped_total", self.count as u64);
+    }
+}
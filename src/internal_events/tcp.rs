@@ -2,7 +2,7 @@ use std::net::SocketAddr;
 
 use metrics::counter;
 use vector_common::internal_event::{error_stage, error_type};
-use vector_core::internal_event::InternalEvent;
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
 
 use crate::{emit, internal_events::SocketOutgoingConnectionError, tls::TlsError};
 
@@ -149,3 +149,59 @@ impl InternalEvent for TcpBytesReceived {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct TcpSinkAcknowledgementError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for TcpSinkAcknowledgementError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to get an acknowledgement for a batch, resending.",
+            error = %self.error,
+            error_code = "acknowledgement_failed",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "acknowledgement_failed",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+            "mode" => "tcp",
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TcpSinkBatchRejected {
+    pub count: usize,
+}
+
+impl InternalEvent for TcpSinkBatchRejected {
+    fn emit(self) {
+        let reason = "The peer rejected the batch.";
+        error!(
+            message = reason,
+            count = %self.count,
+            error_code = "batch_rejected",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "batch_rejected",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+            "mode" => "tcp",
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason
+        });
+    }
+}
@@ -0,0 +1,83 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type, ComponentEventsDropped, INTENTIONAL};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct TenantEventsReceived<'a> {
+    pub tenant: &'a str,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for TenantEventsReceived<'_> {
+    fn emit(self) {
+        trace!(
+            message = "Tenant events received.",
+            tenant = %self.tenant,
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!(
+            "tenant_received_events_total", self.count as u64,
+            "tenant" => self.tenant.to_owned(),
+        );
+        counter!(
+            "tenant_received_event_bytes_total", self.byte_size as u64,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantQuotaExceeded<'a> {
+    pub tenant: &'a str,
+    pub action: &'static str,
+}
+
+impl InternalEvent for TenantQuotaExceeded<'_> {
+    fn emit(self) {
+        debug!(
+            message = "Tenant quota exceeded.",
+            tenant = %self.tenant,
+            action = %self.action,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "tenant_over_quota_events_total", 1,
+            "tenant" => self.tenant.to_owned(),
+            "action" => self.action,
+        );
+        if self.action == "drop" {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: 1,
+                reason: "Tenant quota exceeded.",
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantIdVrlExecutionError<'a> {
+    pub error: &'a str,
+    pub source: &'a str,
+}
+
+impl InternalEvent for TenantIdVrlExecutionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Tenant ID expression execution failed; using the default tenant.",
+            error = %self.error,
+            source = %self.source,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
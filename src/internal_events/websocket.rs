@@ -89,3 +89,106 @@ impl InternalEvent for WsConnectionError {
         Some("WsConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct WsReceiveError {
+    pub error: tokio_tungstenite::tungstenite::Error,
+}
+
+impl InternalEvent for WsReceiveError {
+    fn emit(self) {
+        error!(
+            message = "WebSocket receive error.",
+            error = %self.error,
+            error_code = "ws_receive_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_receive_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsReceiveError")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsResumeTokenError<'a> {
+    pub error: &'a str,
+    pub source: &'a str,
+}
+
+impl InternalEvent for WsResumeTokenError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Resume token expression execution failed; keeping the previous token.",
+            error = %self.error,
+            source = %self.source,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsResumeTokenError")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsHandshakeError {
+    pub error: tokio_tungstenite::tungstenite::Error,
+}
+
+impl InternalEvent for WsHandshakeError {
+    fn emit(self) {
+        error!(
+            message = "WebSocket handshake with client failed.",
+            error = %self.error,
+            error_code = "ws_handshake_failed",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_handshake_failed",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsHandshakeError")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsClientLagged {
+    pub count: u64,
+}
+
+impl InternalEvent for WsClientLagged {
+    fn emit(self) {
+        warn!(
+            message = "Client is not keeping up; skipped the oldest messages.",
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsClientLagged")
+    }
+}
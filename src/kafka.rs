@@ -155,6 +155,7 @@ fn pathbuf_to_string(path: &Path) -> crate::Result<&str> {
 #[derive(Default)]
 pub(crate) struct KafkaStatisticsContext {
     pub(crate) expose_lag_metrics: bool,
+    pub(crate) expose_partition_metrics: bool,
 }
 
 impl ClientContext for KafkaStatisticsContext {
@@ -162,6 +163,7 @@ impl ClientContext for KafkaStatisticsContext {
         emit!(KafkaStatisticsReceived {
             statistics: &statistics,
             expose_lag_metrics: self.expose_lag_metrics,
+            expose_partition_metrics: self.expose_partition_metrics,
         });
     }
 }
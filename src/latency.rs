@@ -0,0 +1,170 @@
+//! End-to-end latency of events.
+//!
+//! When `latency.enabled` is set, sources record the time at which they received each event in its
+//! metadata, under `vector.ingest`, and sinks report the time elapsed since then once the event has
+//! been delivered, broken down by source.
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use lookup::path;
+use tracing::Instrument;
+use value::Value;
+
+use crate::{
+    config::{ComponentKey, Config},
+    event::{BatchNotifier, BatchStatus, EventArray, EventFinalizer, EventMetadata},
+    internal_events::EndToEndLatency,
+};
+
+/// Records the ingest time of the events produced by a source.
+#[derive(Clone, Debug)]
+pub struct SourceLatency {
+    source_id: Value,
+}
+
+impl SourceLatency {
+    /// Creates the ingest recorder of the given source, if latency tracking is enabled.
+    pub fn new(config: &Config, source: &ComponentKey) -> Option<Self> {
+        config.global.latency.enabled.then(|| Self {
+            source_id: Value::from(source.id()),
+        })
+    }
+
+    /// Stamps the events with the current time, replacing any ingest record they already hold.
+    pub fn stamp(&self, events: &mut EventArray) {
+        let timestamp = Value::from(Utc::now());
+        for mut event in events.iter_events_mut() {
+            let record = BTreeMap::from([
+                ("source_id".to_owned(), self.source_id.clone()),
+                ("timestamp".to_owned(), timestamp.clone()),
+            ]);
+            event
+                .metadata_mut()
+                .value_mut()
+                .insert(path!("vector", "ingest"), Value::Object(record));
+        }
+    }
+}
+
+/// Reports the end-to-end latency of the events delivered by a sink.
+#[derive(Clone, Debug)]
+pub struct SinkLatency;
+
+impl SinkLatency {
+    /// Creates the latency reporter of a sink, if latency tracking is enabled.
+    pub fn new(config: &Config) -> Option<Self> {
+        config.global.latency.enabled.then_some(Self)
+    }
+
+    /// Tracks the delivery of the events holding an ingest record.
+    ///
+    /// Once all of them have been delivered, the latency of each of them is reported from the span
+    /// of the sink. Nothing is reported if any of them couldn't be delivered.
+    pub fn track(&self, events: &mut EventArray) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut ingested = Vec::new();
+        for mut event in events.iter_events_mut() {
+            if let Some(ingest) = ingest_record(event.metadata()) {
+                ingested.push(ingest);
+                event
+                    .metadata_mut()
+                    .add_finalizer(EventFinalizer::new(batch.clone()));
+            }
+        }
+        drop(batch);
+
+        if ingested.is_empty() {
+            return;
+        }
+
+        tokio::spawn(
+            async move {
+                if receiver.await != BatchStatus::Delivered {
+                    return;
+                }
+
+                let now = Utc::now();
+                for (source_id, ingested_at) in ingested {
+                    // Clocks can go backwards, in which case the latency is unknown.
+                    if let Ok(latency) = now.signed_duration_since(ingested_at).to_std() {
+                        emit!(EndToEndLatency {
+                            source_id: &source_id,
+                            latency,
+                        });
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+fn ingest_record(metadata: &EventMetadata) -> Option<(String, DateTime<Utc>)> {
+    let source_id = match metadata.value().get(path!("vector", "ingest", "source_id")) {
+        Some(Value::Bytes(source_id)) => String::from_utf8_lossy(source_id).into_owned(),
+        _ => return None,
+    };
+    match metadata.value().get(path!("vector", "ingest", "timestamp")) {
+        Some(Value::Timestamp(timestamp)) => Some((source_id, *timestamp)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::config::LatencyConfig;
+
+    use super::*;
+    use crate::event::{Finalizable, LogEvent};
+
+    fn config(enabled: bool) -> Config {
+        let mut config = Config::default();
+        config.global.latency = LatencyConfig { enabled };
+        config
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let config = Config::default();
+        assert!(SourceLatency::new(&config, &ComponentKey::from("in")).is_none());
+        assert!(SinkLatency::new(&config).is_none());
+    }
+
+    #[test]
+    fn stamps_ingest_record() {
+        let config = config(true);
+        let source = SourceLatency::new(&config, &ComponentKey::from("in")).unwrap();
+
+        let mut events = EventArray::from(LogEvent::from("hello"));
+        source.stamp(&mut events);
+
+        let event = events.iter_events().next().unwrap();
+        let (source_id, timestamp) = ingest_record(event.metadata()).unwrap();
+        assert_eq!(source_id, "in");
+        assert!(timestamp <= Utc::now());
+    }
+
+    #[tokio::test]
+    async fn tracks_stamped_events() {
+        let config = config(true);
+        let source = SourceLatency::new(&config, &ComponentKey::from("in")).unwrap();
+        let sink = SinkLatency::new(&config).unwrap();
+
+        let mut events = EventArray::from(LogEvent::from("hello"));
+        source.stamp(&mut events);
+        sink.track(&mut events);
+
+        assert_eq!(events.take_finalizers().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn skips_events_without_ingest_record() {
+        let config = config(true);
+        let sink = SinkLatency::new(&config).unwrap();
+
+        let mut events = EventArray::from(LogEvent::from("hello"));
+        sink.track(&mut events);
+
+        assert!(events.take_finalizers().is_empty());
+    }
+}
@@ -62,13 +62,17 @@ pub mod amqp;
 pub mod api;
 pub mod app;
 pub mod async_read;
+pub mod audit;
 #[cfg(feature = "aws-config")]
 pub mod aws;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
+pub mod coordination;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
+pub mod event_labels;
+pub mod event_size;
 #[cfg(feature = "gcp")]
 pub mod gcp;
 pub(crate) mod graph;
@@ -79,13 +83,18 @@ pub mod http;
 pub mod kafka;
 #[allow(unreachable_pub)]
 pub mod kubernetes;
+pub mod latency;
 pub mod line_agg;
+pub mod lineage;
 pub mod list;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) mod nats;
+pub mod plugins;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
+pub mod runtime;
+pub mod saturation;
 pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
@@ -94,14 +103,18 @@ pub mod signal;
 pub(crate) mod sink;
 #[allow(unreachable_pub)]
 pub mod sinks;
+pub mod source_log_schema;
 pub mod source_sender;
 #[allow(unreachable_pub)]
 pub mod sources;
 pub mod stats;
+#[cfg(target_os = "linux")]
+pub mod systemd;
 #[cfg(feature = "api-client")]
 #[allow(unreachable_pub)]
 mod tap;
 pub mod template;
+pub mod tenancy;
 pub mod test_util;
 #[cfg(feature = "api-client")]
 #[allow(unreachable_pub)]
@@ -0,0 +1,164 @@
+//! Lineage records of events.
+//!
+//! When `lineage.enabled` is set, sources stamp each event with a lineage record in its metadata,
+//! under `vector.lineage`, and transforms append their ID to the record of the events they
+//! receive. The record can be read in VRL with the `get_lineage` function.
+use std::collections::BTreeMap;
+
+use lookup::path;
+use value::Value;
+
+use crate::{
+    config::{ComponentKey, Config},
+    event::EventArray,
+};
+
+/// The lineage record stamped on the events produced by a source.
+#[derive(Clone, Debug)]
+pub struct SourceLineage {
+    record: Value,
+}
+
+impl SourceLineage {
+    /// Creates the lineage record of the given source, if lineage is enabled.
+    pub fn new(config: &Config, source: &ComponentKey) -> Option<Self> {
+        let lineage = &config.global.lineage;
+        if !lineage.enabled {
+            return None;
+        }
+
+        let instance_id = lineage.instance_id.clone().unwrap_or_else(|| {
+            crate::get_hostname().unwrap_or_else(|error| {
+                warn!(message = "Failed to get hostname for lineage records.", %error);
+                String::from("unknown")
+            })
+        });
+
+        let record = BTreeMap::from([
+            ("source_id".to_owned(), Value::from(source.id())),
+            ("transforms".to_owned(), Value::Array(Vec::new())),
+            ("instance_id".to_owned(), Value::from(instance_id)),
+            (
+                "config_hash".to_owned(),
+                config.hash.clone().map_or(Value::Null, Value::from),
+            ),
+        ]);
+
+        Some(Self {
+            record: Value::Object(record),
+        })
+    }
+
+    /// Stamps the events with the lineage record, replacing any record they already hold.
+    pub fn stamp(&self, events: &mut EventArray) {
+        for mut event in events.iter_events_mut() {
+            event
+                .metadata_mut()
+                .value_mut()
+                .insert(path!("vector", "lineage"), self.record.clone());
+        }
+    }
+}
+
+/// The ID appended by a transform to the lineage record of the events it receives.
+#[derive(Clone, Debug)]
+pub struct TransformLineage {
+    id: Value,
+}
+
+impl TransformLineage {
+    /// Creates the lineage entry of the given transform, if lineage is enabled.
+    pub fn new(config: &Config, transform: &ComponentKey) -> Option<Self> {
+        config.global.lineage.enabled.then(|| Self {
+            id: Value::from(transform.id()),
+        })
+    }
+
+    /// Appends the transform to the lineage record of the events.
+    ///
+    /// Events without a lineage record, such as the ones created by a transform, are left as is.
+    pub fn stamp(&self, events: &mut EventArray) {
+        for mut event in events.iter_events_mut() {
+            if let Some(Value::Array(transforms)) =
+                event
+                    .metadata_mut()
+                    .value_mut()
+                    .get_mut(path!("vector", "lineage", "transforms"))
+            {
+                transforms.push(self.id.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::config::LineageConfig;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn config(lineage: LineageConfig) -> Config {
+        let mut config = Config::default();
+        config.global.lineage = lineage;
+        config.hash = Some("0123456789abcdef".to_owned());
+        config
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let config = config(LineageConfig::default());
+        assert!(SourceLineage::new(&config, &ComponentKey::from("in")).is_none());
+        assert!(TransformLineage::new(&config, &ComponentKey::from("parse")).is_none());
+    }
+
+    #[test]
+    fn stamps_source_and_transforms() {
+        let config = config(LineageConfig {
+            enabled: true,
+            instance_id: Some("vector-0".to_owned()),
+        });
+        let source = SourceLineage::new(&config, &ComponentKey::from("in")).unwrap();
+        let parse = TransformLineage::new(&config, &ComponentKey::from("parse")).unwrap();
+        let filter = TransformLineage::new(&config, &ComponentKey::from("filter")).unwrap();
+
+        let mut events = EventArray::from(LogEvent::from("hello"));
+        source.stamp(&mut events);
+        parse.stamp(&mut events);
+        filter.stamp(&mut events);
+
+        let event = events.iter_events().next().unwrap();
+        let lineage = event.metadata().value();
+        assert_eq!(
+            lineage.get(path!("vector", "lineage", "source_id")),
+            Some(&Value::from("in"))
+        );
+        assert_eq!(
+            lineage.get(path!("vector", "lineage", "transforms")),
+            Some(&Value::Array(vec!["parse".into(), "filter".into()]))
+        );
+        assert_eq!(
+            lineage.get(path!("vector", "lineage", "instance_id")),
+            Some(&Value::from("vector-0"))
+        );
+        assert_eq!(
+            lineage.get(path!("vector", "lineage", "config_hash")),
+            Some(&Value::from("0123456789abcdef"))
+        );
+    }
+
+    #[test]
+    fn transforms_skip_events_without_lineage() {
+        let config = config(LineageConfig {
+            enabled: true,
+            instance_id: None,
+        });
+        let parse = TransformLineage::new(&config, &ComponentKey::from("parse")).unwrap();
+
+        let mut events = EventArray::from(LogEvent::from("hello"));
+        parse.stamp(&mut events);
+
+        let event = events.iter_events().next().unwrap();
+        assert!(event.metadata().value().get(path!("vector")).is_none());
+    }
+}
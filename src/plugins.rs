@@ -0,0 +1,325 @@
+//! Registration of components defined outside of this crate.
+//!
+//! Crates linked into a Vector binary, such as the crate of a fork or an out-of-tree component,
+//! can add sources, transforms, and sinks without modifying this crate. Their configuration type
+//! is declared as any other component's, with `#[configurable_component(source("..."))]` and its
+//! `transform`/`sink` counterparts, which makes them appear in `vector list`, `vector generate`,
+//! and in the configuration schema. Their configuration trait is then implemented with
+//! [`register_source!`], [`register_transform!`], or [`register_sink!`]:
+//!
+//! ```ignore
+//! #[configurable_component(sink("my_sink"))]
+//! #[derive(Clone, Debug)]
+//! pub struct MySinkConfig {
+//!     // ...
+//! }
+//!
+//! vector::register_sink!("my_sink", impl SinkConfig for MySinkConfig {
+//!     async fn build(&self, cx: SinkContext) -> vector::Result<(VectorSink, Healthcheck)> {
+//!         // ...
+//!     }
+//!
+//!     // ...
+//! });
+//! ```
+//!
+//! The crate must depend on `inventory`, `typetag`, and `vector-config`, which the generated code
+//! refers to, in the same versions as this crate.
+use std::{cell::RefCell, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use serde_with::{DeserializeAs, SerializeAs};
+use vector_config::{
+    component::SinkDescription,
+    schema::{SchemaGenerator, SchemaObject},
+    Configurable, GenerateError, Metadata, NamedComponent,
+};
+use vector_core::config::{AcknowledgementsConfig, Input};
+
+use crate::{
+    config::{Resource, SinkConfig, SinkContext},
+    sinks::{Healthcheck, HealthcheckFactory, Sinks, VectorSink},
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use async_trait;
+    pub use inventory;
+    pub use typetag;
+}
+
+/// Implements `SourceConfig` for a source defined outside of this crate.
+///
+/// The name must be the one given to `#[configurable_component(source("..."))]`.
+#[macro_export]
+macro_rules! register_source {
+    ($name:literal, impl SourceConfig for $ty:ty { $($body:tt)* }) => {
+        #[$crate::plugins::__private::async_trait::async_trait]
+        #[$crate::plugins::__private::typetag::serde(name = $name)]
+        impl $crate::config::SourceConfig for $ty {
+            $($body)*
+        }
+    };
+}
+
+/// Implements `TransformConfig` for a transform defined outside of this crate.
+///
+/// The name must be the one given to `#[configurable_component(transform("..."))]`.
+#[macro_export]
+macro_rules! register_transform {
+    ($name:literal, impl TransformConfig for $ty:ty { $($body:tt)* }) => {
+        #[$crate::plugins::__private::async_trait::async_trait]
+        #[$crate::plugins::__private::typetag::serde(name = $name)]
+        impl $crate::config::TransformConfig for $ty {
+            $($body)*
+        }
+    };
+}
+
+/// Implements `SinkConfig` for a sink defined outside of this crate, and registers it.
+///
+/// The name must be the one given to `#[configurable_component(sink("..."))]`.
+#[macro_export]
+macro_rules! register_sink {
+    ($name:literal, impl SinkConfig for $ty:ty { $($body:tt)* }) => {
+        #[$crate::plugins::__private::async_trait::async_trait]
+        impl $crate::config::SinkConfig for $ty {
+            $($body)*
+        }
+
+        $crate::plugins::__private::inventory::submit! {
+            $crate::plugins::SinkRegistration::new::<$ty>($name)
+        }
+    };
+}
+
+type DeserializeSink = fn(Value) -> Result<Arc<dyn SinkConfig>, serde_json::Error>;
+
+/// A sink defined outside of this crate.
+///
+/// Built-in sinks are variants of [`Sinks`], which the sinks defined outside of this crate can't
+/// add to, so they're looked up in these registrations when deserializing the configuration.
+pub struct SinkRegistration {
+    name: &'static str,
+    deserialize: DeserializeSink,
+}
+
+impl SinkRegistration {
+    pub const fn new<C>(name: &'static str) -> Self
+    where
+        C: SinkConfig + de::DeserializeOwned + 'static,
+    {
+        Self {
+            name,
+            deserialize: deserialize_sink::<C>,
+        }
+    }
+
+    fn find(name: &str) -> Option<&'static Self> {
+        inventory::iter::<Self>
+            .into_iter()
+            .find(|registration| registration.name == name)
+    }
+}
+
+inventory::collect!(SinkRegistration);
+
+fn deserialize_sink<C>(mut config: Value) -> Result<Arc<dyn SinkConfig>, serde_json::Error>
+where
+    C: SinkConfig + de::DeserializeOwned + 'static,
+{
+    // The type is the tag of the sink, which isn't a field of its configuration.
+    if let Value::Object(fields) = &mut config {
+        fields.remove("type");
+    }
+    serde_json::from_value::<C>(config).map(|config| Arc::new(config) as Arc<dyn SinkConfig>)
+}
+
+/// The configuration of a sink defined outside of this crate.
+#[derive(Clone)]
+pub struct PluginSink {
+    name: &'static str,
+    /// The configuration as it was deserialized, which is what gets serialized back.
+    config: Value,
+    inner: Arc<dyn SinkConfig>,
+}
+
+impl fmt::Debug for PluginSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl NamedComponent for PluginSink {
+    fn get_component_name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[async_trait]
+impl SinkConfig for PluginSink {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.inner.build(cx).await
+    }
+
+    async fn build_healthcheck_factory(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<HealthcheckFactory>> {
+        self.inner.build_healthcheck_factory(cx).await
+    }
+
+    fn input(&self) -> Input {
+        self.inner.input()
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.inner.resources()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        self.inner.acknowledgements()
+    }
+}
+
+/// (De)serializes [`Sinks`], including the sinks defined outside of this crate.
+pub struct SinkComponents;
+
+impl SerializeAs<Sinks> for SinkComponents {
+    fn serialize_as<S: Serializer>(sink: &Sinks, serializer: S) -> Result<S::Ok, S::Error> {
+        match sink {
+            Sinks::Plugin(plugin) => plugin.config.serialize(serializer),
+            sink => sink.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> DeserializeAs<'de, Sinks> for SinkComponents {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Sinks, D::Error> {
+        let config = Value::deserialize(deserializer)?;
+        let registration = config
+            .get("type")
+            .and_then(Value::as_str)
+            .and_then(SinkRegistration::find);
+        match registration {
+            Some(registration) => (registration.deserialize)(config.clone())
+                .map(|inner| {
+                    Sinks::Plugin(PluginSink {
+                        name: registration.name,
+                        config,
+                        inner,
+                    })
+                })
+                .map_err(de::Error::custom),
+            None => Sinks::deserialize(config).map_err(de::Error::custom),
+        }
+    }
+}
+
+impl Configurable for SinkComponents {
+    // Not referenceable, so that the schema of the sinks isn't reused without the plugins.
+    fn metadata() -> Metadata {
+        Sinks::metadata()
+    }
+
+    fn generate_schema(gen: &RefCell<SchemaGenerator>) -> Result<SchemaObject, GenerateError> {
+        let mut schema = Sinks::generate_schema(gen)?;
+        if inventory::iter::<SinkRegistration>
+            .into_iter()
+            .next()
+            .is_none()
+        {
+            return Ok(schema);
+        }
+
+        let plugins = SinkDescription::generate_schemas_matching(gen, |name| {
+            SinkRegistration::find(name).is_some()
+        })?;
+        let plugins = plugins.subschemas.and_then(|subschemas| subschemas.one_of);
+        if let (Some(sinks), Some(plugins)) = (
+            schema
+                .subschemas
+                .as_mut()
+                .and_then(|subschemas| subschemas.one_of.as_mut()),
+            plugins,
+        ) {
+            sinks.extend(plugins);
+        }
+        Ok(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_config::configurable_component;
+
+    use super::*;
+    use crate::{config::SinkOuter, test_util::mock::sinks::BasicSinkConfig};
+
+    /// Configuration for the `test_plugin` sink.
+    #[configurable_component(sink("test_plugin"))]
+    #[derive(Clone, Debug, Default)]
+    struct TestPluginSinkConfig {
+        /// A field.
+        field: String,
+    }
+
+    impl_generate_config_from_default!(TestPluginSinkConfig);
+
+    crate::register_sink!("test_plugin", impl SinkConfig for TestPluginSinkConfig {
+        async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+            BasicSinkConfig::default().build(cx).await
+        }
+
+        fn input(&self) -> Input {
+            Input::all()
+        }
+
+        fn acknowledgements(&self) -> &AcknowledgementsConfig {
+            &AcknowledgementsConfig::DEFAULT
+        }
+    });
+
+    #[test]
+    fn deserializes_registered_sinks() {
+        let sink: SinkOuter<String> = toml::from_str(
+            r#"
+            type = "test_plugin"
+            inputs = ["in"]
+            field = "value"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(sink.inner.get_component_name(), "test_plugin");
+
+        let serialized = serde_json::to_value(&sink).unwrap();
+        assert_eq!(serialized["type"], "test_plugin");
+        assert_eq!(serialized["field"], "value");
+    }
+
+    #[test]
+    fn deserializes_builtin_sinks() {
+        let sink: SinkOuter<String> = toml::from_str(
+            r#"
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(sink.inner.get_component_name(), "test_basic");
+    }
+
+    #[test]
+    fn rejects_invalid_registered_sinks() {
+        let error = toml::from_str::<SinkOuter<String>>(
+            r#"
+            type = "test_plugin"
+            inputs = ["in"]
+            "#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("missing field `field`"));
+    }
+}
@@ -1,5 +1,11 @@
-#[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
+#[cfg(any(
+    feature = "sources-vector",
+    feature = "sinks-vector",
+    feature = "transforms-ext_proc"
+))]
 use crate::event::proto as event;
 
+#[cfg(feature = "transforms-ext_proc")]
+pub mod ext_proc;
 #[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
 pub mod vector;
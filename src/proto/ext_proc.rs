@@ -0,0 +1,9 @@
+#![allow(clippy::clone_on_ref_ptr)]
+#![allow(warnings, clippy::pedantic, clippy::nursery)]
+
+tonic::include_proto!("ext_proc");
+
+pub use external_processor_client::ExternalProcessorClient as Client;
+pub use external_processor_server::{
+    ExternalProcessor as Service, ExternalProcessorServer as Server,
+};
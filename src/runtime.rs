@@ -0,0 +1,150 @@
+//! Isolation of the worker threads running the topology.
+//!
+//! Worker threads can be pinned to a set of CPUs, and components can be given their own runtime, so
+//! that a component using a lot of CPU can't starve the others running on the shared worker
+//! threads.
+use std::{future::Future, io, thread};
+
+use tokio::{
+    runtime::{self, Handle},
+    sync::oneshot,
+    task::{JoinError, JoinHandle},
+};
+
+use crate::config::{ComponentKey, ComponentRuntimeConfig};
+
+/// Pins the current thread to the given CPUs.
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpus: &[usize]) -> io::Result<()> {
+    use nix::{
+        sched::{sched_setaffinity, CpuSet},
+        unistd::Pid,
+    };
+
+    let mut set = CpuSet::new();
+    for &cpu in cpus {
+        set.set(cpu)?;
+    }
+    sched_setaffinity(Pid::from_raw(0), &set)?;
+    Ok(())
+}
+
+/// Pins the current thread to the given CPUs.
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_cpus: &[usize]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "CPU affinity is only supported on Linux",
+    ))
+}
+
+/// Returns the callback pinning the threads of a runtime to the given CPUs, if any.
+pub fn pin_threads(cpus: Vec<usize>) -> impl Fn() + Send + Sync + 'static {
+    move || {
+        if cpus.is_empty() {
+            return;
+        }
+        if let Err(error) = pin_current_thread(&cpus) {
+            warn!(message = "Failed to pin thread to CPUs.", ?cpus, %error);
+        }
+    }
+}
+
+/// The runtime dedicated to a component.
+///
+/// The runtime runs until the task spawned on it is done, or until it is dropped if no task is
+/// spawned on it.
+pub(crate) struct DedicatedRuntime {
+    handle: Handle,
+    // Dropping it shuts down the runtime.
+    _shutdown: oneshot::Sender<()>,
+}
+
+impl DedicatedRuntime {
+    pub(crate) fn new(key: &ComponentKey, config: &ComponentRuntimeConfig) -> io::Result<Self> {
+        let name = format!("vector-{}", key);
+        let mut builder = runtime::Builder::new_multi_thread();
+        builder
+            .enable_all()
+            .thread_name(&name)
+            .worker_threads(config.threads.get())
+            .on_thread_start(pin_threads(config.cpu_affinity.clone()));
+        if let Some(max_blocking_threads) = config.max_blocking_threads {
+            builder.max_blocking_threads(max_blocking_threads.get());
+        }
+        let runtime = builder.build()?;
+
+        // The runtime is owned by a thread of its own, as it can't be dropped from an asynchronous
+        // context.
+        let handle = runtime.handle().clone();
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        thread::Builder::new().name(name).spawn(move || {
+            let _ = runtime.block_on(stopped);
+        })?;
+
+        Ok(Self {
+            handle,
+            _shutdown: shutdown,
+        })
+    }
+
+    /// Spawns the task of the component on the runtime.
+    ///
+    /// The returned handle is driven by the current runtime, and resolves once the task is done.
+    pub(crate) fn spawn<T, E>(
+        self,
+        task: impl Future<Output = Result<T, E>> + Send + 'static,
+    ) -> JoinHandle<Result<T, E>>
+    where
+        T: Send + 'static,
+        E: From<JoinError> + Send + 'static,
+    {
+        let task = self.handle.spawn(task);
+        tokio::spawn(async move {
+            let result = task.await;
+            drop(self);
+            result.unwrap_or_else(|error| Err(error.into()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_task_on_dedicated_threads() {
+        let config = ComponentRuntimeConfig {
+            threads: NonZeroUsize::new(1).unwrap(),
+            cpu_affinity: Vec::new(),
+            max_blocking_threads: None,
+        };
+        let runtime = DedicatedRuntime::new(&ComponentKey::from("heavy"), &config).unwrap();
+
+        let thread_name = runtime
+            .spawn(async { Ok::<_, JoinError>(thread::current().name().map(ToOwned::to_owned)) })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(thread_name.as_deref(), Some("vector-heavy"));
+    }
+
+    #[tokio::test]
+    async fn reports_panicked_task() {
+        async fn panics() -> Result<(), JoinError> {
+            panic!("boom");
+        }
+
+        let config = ComponentRuntimeConfig {
+            threads: NonZeroUsize::new(1).unwrap(),
+            cpu_affinity: Vec::new(),
+            max_blocking_threads: None,
+        };
+        let runtime = DedicatedRuntime::new(&ComponentKey::from("heavy"), &config).unwrap();
+
+        let result = runtime.spawn(panics()).await.unwrap();
+        assert!(result.unwrap_err().is_panic());
+    }
+}
@@ -0,0 +1,278 @@
+//! Computes how saturated this instance of Vector is.
+//!
+//! The saturation score is meant to drive the autoscaling of aggregators, through the
+//! `saturation_score` internal metric or the `saturation` field of the GraphQL API. It's the highest
+//! of the following ratios, each ranging from 0 to 1:
+//!
+//! - `buffer`: how full the fullest buffer is.
+//! - `concurrency`: how much of its adaptive concurrency limit the busiest sink uses.
+//! - `cpu`: how much of the available CPU time the process uses.
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use tokio::time::interval;
+
+use crate::{
+    event::{Metric, MetricValue},
+    internal_events::Saturation as SaturationEvent,
+    metrics::Controller,
+};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The saturation of this instance.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Saturation {
+    pub buffer: f64,
+    pub concurrency: f64,
+    pub cpu: f64,
+}
+
+impl Saturation {
+    /// The saturation score, the highest of the saturation ratios.
+    pub fn score(&self) -> f64 {
+        self.buffer.max(self.concurrency).max(self.cpu)
+    }
+}
+
+/// Emits the saturation of this instance every few seconds.
+pub async fn saturation() {
+    let controller = match Controller::get() {
+        Ok(controller) => controller,
+        Err(_) => return,
+    };
+    let mut sampler = Sampler::default();
+    let mut interval = interval(SAMPLE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let saturation = sampler.sample(&controller.capture_metrics(), cpu_time());
+        emit!(SaturationEvent { saturation });
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct HistogramTotals {
+    count: u64,
+    sum: f64,
+}
+
+impl HistogramTotals {
+    fn from_metric(metric: &Metric) -> Option<Self> {
+        match metric.value() {
+            MetricValue::AggregatedHistogram { count, sum, .. } => Some(Self {
+                count: *count,
+                sum: *sum,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The mean of the values recorded since the previous totals.
+    fn mean_since(&self, previous: Option<&Self>) -> Option<f64> {
+        let previous = previous.copied().unwrap_or_default();
+        // The histogram was reset, if the component was reloaded.
+        let previous = if self.count < previous.count {
+            Self::default()
+        } else {
+            previous
+        };
+        let count = self.count - previous.count;
+        (count > 0).then(|| (self.sum - previous.sum) / count as f64)
+    }
+}
+
+/// Computes the saturation from successive samples of the internal metrics.
+#[derive(Default)]
+struct Sampler {
+    in_flight: HashMap<String, HistogramTotals>,
+    limit: HashMap<String, HistogramTotals>,
+    cpu: Option<(Instant, Duration)>,
+}
+
+impl Sampler {
+    fn sample(&mut self, metrics: &[Metric], cpu_time: Option<Duration>) -> Saturation {
+        Saturation {
+            buffer: buffer_fill(metrics),
+            concurrency: self.concurrency_utilization(metrics),
+            cpu: self.cpu_utilization(Instant::now(), cpu_time),
+        }
+    }
+
+    /// The highest ratio of the mean number of requests in flight to the mean adaptive
+    /// concurrency limit of a sink since the previous sample.
+    fn concurrency_utilization(&mut self, metrics: &[Metric]) -> f64 {
+        let mut in_flight = HashMap::new();
+        let mut limit = HashMap::new();
+        for metric in metrics {
+            let totals = match metric.name() {
+                "adaptive_concurrency_in_flight" => &mut in_flight,
+                "adaptive_concurrency_limit" => &mut limit,
+                _ => continue,
+            };
+            if let (Some(component), Some(value)) = (
+                metric.tag_value("component_id"),
+                HistogramTotals::from_metric(metric),
+            ) {
+                totals.insert(component, value);
+            }
+        }
+
+        let utilization = in_flight
+            .iter()
+            .filter_map(|(component, in_flight_totals)| {
+                let in_flight_mean = in_flight_totals.mean_since(self.in_flight.get(component))?;
+                let limit_mean = limit
+                    .get(component)?
+                    .mean_since(self.limit.get(component))?;
+                (limit_mean > 0.0).then(|| in_flight_mean / limit_mean)
+            })
+            .fold(0.0, f64::max);
+
+        self.in_flight = in_flight;
+        self.limit = limit;
+        utilization.clamp(0.0, 1.0)
+    }
+
+    /// The ratio of the CPU time used by the process to the CPU time available to it since the
+    /// previous sample.
+    fn cpu_utilization(&mut self, now: Instant, cpu_time: Option<Duration>) -> f64 {
+        let Some(cpu_time) = cpu_time else {
+            return 0.0;
+        };
+        let utilization = match self.cpu {
+            Some((sampled_at, previous)) => {
+                let available =
+                    now.duration_since(sampled_at).as_secs_f64() * crate::num_threads() as f64;
+                if available > 0.0 {
+                    cpu_time.saturating_sub(previous).as_secs_f64() / available
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        };
+        self.cpu = Some((now, cpu_time));
+        utilization.clamp(0.0, 1.0)
+    }
+}
+
+/// The highest ratio of the size of a buffer to its maximum size.
+fn buffer_fill(metrics: &[Metric]) -> f64 {
+    let mut buffers: HashMap<(Option<String>, Option<String>), [f64; 4]> = HashMap::new();
+    for metric in metrics {
+        let index = match metric.name() {
+            "buffer_byte_size" => 0,
+            "buffer_max_byte_size" => 1,
+            "buffer_events" => 2,
+            "buffer_max_event_size" => 3,
+            _ => continue,
+        };
+        if let MetricValue::Gauge { value } = metric.value() {
+            let buffer = (metric.tag_value("component_id"), metric.tag_value("stage"));
+            buffers.entry(buffer).or_default()[index] = *value;
+        }
+    }
+
+    buffers
+        .values()
+        .filter_map(|[bytes, max_bytes, events, max_events]| {
+            if *max_bytes > 0.0 {
+                Some(bytes / max_bytes)
+            } else if *max_events > 0.0 {
+                Some(events / max_events)
+            } else {
+                None
+            }
+        })
+        .fold(0.0, f64::max)
+        .clamp(0.0, 1.0)
+}
+
+/// The CPU time used by the process so far.
+#[cfg(unix)]
+fn cpu_time() -> Option<Duration> {
+    use nix::sys::{
+        resource::{getrusage, UsageWho},
+        time::TimeValLike,
+    };
+
+    let usage = getrusage(UsageWho::RUSAGE_SELF).ok()?;
+    let micros = usage.user_time().num_microseconds() + usage.system_time().num_microseconds();
+    Some(Duration::from_micros(u64::try_from(micros).ok()?))
+}
+
+#[cfg(not(unix))]
+const fn cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::metric_tags;
+
+    use super::*;
+    use crate::event::MetricKind;
+
+    fn gauge(name: &str, component: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value }).with_tags(Some(
+            metric_tags!("component_id" => component, "stage" => "0"),
+        ))
+    }
+
+    fn histogram(name: &str, component: &str, count: u64, sum: f64) -> Metric {
+        Metric::new(
+            name,
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: Vec::new(),
+                count,
+                sum,
+            },
+        )
+        .with_tags(Some(metric_tags!("component_id" => component)))
+    }
+
+    #[test]
+    fn buffer_fill_is_the_fullest_buffer() {
+        let metrics = [
+            gauge("buffer_byte_size", "disk", 250.0),
+            gauge("buffer_max_byte_size", "disk", 1000.0),
+            gauge("buffer_events", "memory", 300.0),
+            gauge("buffer_max_event_size", "memory", 500.0),
+            // Buffers without a maximum size are never full.
+            gauge("buffer_events", "unbounded", 1000.0),
+        ];
+        assert_eq!(buffer_fill(&metrics), 0.6);
+    }
+
+    #[test]
+    fn concurrency_utilization_is_sampled_since_previous_sample() {
+        let mut sampler = Sampler::default();
+        let metrics = [
+            histogram("adaptive_concurrency_in_flight", "http", 10, 10.0),
+            histogram("adaptive_concurrency_limit", "http", 10, 20.0),
+        ];
+        assert_eq!(sampler.concurrency_utilization(&metrics), 0.5);
+
+        let metrics = [
+            histogram("adaptive_concurrency_in_flight", "http", 20, 50.0),
+            histogram("adaptive_concurrency_limit", "http", 20, 60.0),
+        ];
+        assert_eq!(sampler.concurrency_utilization(&metrics), 1.0);
+
+        // Nothing was recorded since the previous sample.
+        assert_eq!(sampler.concurrency_utilization(&metrics), 0.0);
+    }
+
+    #[test]
+    fn score_is_the_highest_ratio() {
+        let saturation = Saturation {
+            buffer: 0.2,
+            concurrency: 0.7,
+            cpu: 0.4,
+        };
+        assert_eq!(saturation.score(), 0.7);
+    }
+}
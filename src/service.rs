@@ -54,6 +54,27 @@ struct InstallOpts {
         value_delimiter(',')
     )]
     config_dirs: Vec<PathBuf>,
+
+    /// How the service is started.
+    #[arg(default_value = "manual", long)]
+    start_type: StartType,
+
+    /// The actions to take on the first, second, and subsequent failures of the service.
+    #[arg(long, value_delimiter(','))]
+    recovery_actions: Vec<RecoveryAction>,
+
+    /// How long to wait before taking a recovery action, in seconds.
+    #[arg(default_value = "60", long)]
+    recovery_delay: u32,
+
+    /// How long the service must run without failing before the count of failures is reset, in
+    /// seconds.
+    #[arg(default_value = "86400", long)]
+    recovery_reset_period: u32,
+
+    /// How long to wait for Vector to shut down gracefully when the service is stopped, in seconds.
+    #[arg(default_value = "60", long)]
+    stop_timeout: u32,
 }
 
 impl InstallOpts {
@@ -64,7 +85,13 @@ impl InstallOpts {
 
         let current_exe = ::std::env::current_exe().unwrap();
         let config_paths = self.config_paths_with_formats();
-        let arguments = create_service_arguments(&config_paths).unwrap();
+        let mut arguments = create_service_arguments(&config_paths).unwrap();
+        arguments.extend([
+            OsString::from("--service-name"),
+            OsString::from(service_name),
+            OsString::from("--service-stop-timeout"),
+            OsString::from(self.stop_timeout.to_string()),
+        ]);
 
         ServiceInfo {
             name: OsString::from(service_name),
@@ -72,6 +99,10 @@ impl InstallOpts {
             description: OsString::from(description),
             executable_path: current_exe,
             launch_arguments: arguments,
+            start_type: self.start_type,
+            recovery_actions: self.recovery_actions.clone(),
+            recovery_delay: Duration::from_secs(self.recovery_delay as u64),
+            recovery_reset_period: Duration::from_secs(self.recovery_reset_period as u64),
         }
     }
 
@@ -92,6 +123,26 @@ impl InstallOpts {
     }
 }
 
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum StartType {
+    /// Started on demand.
+    Manual,
+    /// Started when the system boots.
+    Auto,
+    /// Started shortly after the system boots, once the other automatic services are started.
+    DelayedAuto,
+    /// Can't be started.
+    Disabled,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum RecoveryAction {
+    /// Take no action.
+    None,
+    /// Restart the service.
+    Restart,
+}
+
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
 struct RestartOpts {
@@ -154,6 +205,11 @@ struct ServiceInfo {
 
     executable_path: std::path::PathBuf,
     launch_arguments: Vec<OsString>,
+
+    start_type: StartType,
+    recovery_actions: Vec<RecoveryAction>,
+    recovery_delay: Duration,
+    recovery_reset_period: Duration,
 }
 
 impl Default for ServiceInfo {
@@ -166,6 +222,10 @@ impl Default for ServiceInfo {
             description: OsString::from(crate::built_info::PKG_DESCRIPTION),
             executable_path: current_exe,
             launch_arguments: vec![],
+            start_type: StartType::Manual,
+            recovery_actions: vec![],
+            recovery_delay: Duration::default(),
+            recovery_reset_period: Duration::default(),
         }
     }
 }
@@ -209,12 +269,32 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
 fn control_service(service: &ServiceInfo, action: ControlAction) -> exitcode::ExitCode {
     use crate::vector_windows;
 
+    let start_type = match service.start_type {
+        StartType::Manual => vector_windows::service_control::StartType::Manual,
+        StartType::Auto => vector_windows::service_control::StartType::Automatic,
+        StartType::DelayedAuto => vector_windows::service_control::StartType::DelayedAutomatic,
+        StartType::Disabled => vector_windows::service_control::StartType::Disabled,
+    };
+
+    let recovery_actions = service
+        .recovery_actions
+        .iter()
+        .map(|action| match action {
+            RecoveryAction::None => vector_windows::service_control::RecoveryAction::None,
+            RecoveryAction::Restart => vector_windows::service_control::RecoveryAction::Restart,
+        })
+        .collect();
+
     let service_definition = vector_windows::service_control::ServiceDefinition {
         name: service.name.clone(),
         display_name: service.display_name.clone(),
         description: service.description.clone(),
         executable_path: service.executable_path.clone(),
         launch_arguments: service.launch_arguments.clone(),
+        start_type,
+        recovery_actions,
+        recovery_delay: service.recovery_delay,
+        recovery_reset_period: service.recovery_reset_period,
     };
 
     let res = match action {
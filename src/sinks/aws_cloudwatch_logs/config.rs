@@ -1,7 +1,10 @@
+use std::{collections::HashMap, num::NonZeroUsize};
+
 use aws_sdk_cloudwatchlogs::Client as CloudwatchLogsClient;
 use aws_smithy_types::retry::RetryConfig;
 use codecs::JsonSerializerConfig;
 use futures::FutureExt;
+use snafu::Snafu;
 use tower::ServiceBuilder;
 use value::Kind;
 use vector_config::configurable_component;
@@ -93,6 +96,40 @@ pub struct CloudwatchLogsSinkConfig {
     #[serde(default = "crate::serde::default_true")]
     pub create_missing_stream: bool,
 
+    /// The number of days to retain the events of the log groups created by the sink.
+    ///
+    /// Must be one of the [values supported by CloudWatch Logs][retention]. By default, the events
+    /// are retained indefinitely.
+    ///
+    /// [retention]: https://docs.aws.amazon.com/AmazonCloudWatchLogs/latest/APIReference/API_PutRetentionPolicy.html
+    #[configurable(metadata(docs::type_unit = "days"))]
+    #[configurable(metadata(docs::examples = 30))]
+    pub retention_days: Option<u32>,
+
+    /// The ARN of the [KMS key][kms_key] encrypting the events of the log groups created.
+    ///
+    /// [kms_key]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/encrypt-log-data-kms.html
+    #[configurable(metadata(
+        docs::examples = "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab"
+    ))]
+    pub kms_key: Option<String>,
+
+    /// The [tags][tags] applied to the log groups created by the sink.
+    ///
+    /// [tags]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html#log-group-tagging
+    #[configurable(metadata(docs::additional_props_description = "A tag value."))]
+    #[configurable(metadata(docs::examples = "example_tags()"))]
+    pub tags: Option<HashMap<String, String>>,
+
+    /// The number of log streams the events of each stream are spread over.
+    ///
+    /// Events can only be written to a log stream by one request at a time, so spreading them over
+    /// several streams allows sending more of them concurrently. When set, the batches are sent in
+    /// turn to streams named after `stream_name`, suffixed with `-` and the index of the stream,
+    /// starting from zero.
+    #[configurable(metadata(docs::examples = 4))]
+    pub stream_shards: Option<NonZeroUsize>,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
@@ -131,7 +168,35 @@ pub struct CloudwatchLogsSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The retention periods supported by CloudWatch Logs, in days.
+const RETENTION_DAYS: &[u32] = &[
+    1, 3, 5, 7, 14, 30, 60, 90, 120, 150, 180, 365, 400, 545, 731, 1096, 1827, 2192, 2557, 2922,
+    3288, 3653,
+];
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "`retention_days` must be one of the values supported by CloudWatch Logs, not {}",
+        days
+    ))]
+    InvalidRetentionDays { days: u32 },
+}
+
+fn example_tags() -> HashMap<String, String> {
+    HashMap::from([("team".to_owned(), "observability".to_owned())])
+}
+
 impl CloudwatchLogsSinkConfig {
+    fn validate_retention_days(&self) -> crate::Result<()> {
+        match self.retention_days {
+            Some(days) if !RETENTION_DAYS.contains(&days) => {
+                Err(BuildError::InvalidRetentionDays { days }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub async fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<CloudwatchLogsClient> {
         create_client::<CloudwatchLogsClientBuilder>(
             &self.auth,
@@ -163,6 +228,7 @@ impl CloudwatchLogsSinkConfig {
 #[async_trait::async_trait]
 impl SinkConfig for CloudwatchLogsSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.validate_retention_days()?;
         let batcher_settings = self.batch.into_batcher_settings()?;
         let request_settings = self
             .request
@@ -223,6 +289,10 @@ fn default_config(encoding: EncodingConfig) -> CloudwatchLogsSinkConfig {
         region: Default::default(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
@@ -244,10 +314,24 @@ impl SinkBatchSettings for CloudwatchLogsDefaultBatchSettings {
 
 #[cfg(test)]
 mod tests {
-    use crate::sinks::aws_cloudwatch_logs::config::CloudwatchLogsSinkConfig;
+    use codecs::JsonSerializerConfig;
+
+    use crate::sinks::aws_cloudwatch_logs::config::{default_config, CloudwatchLogsSinkConfig};
 
     #[test]
     fn test_generate_config() {
         crate::test_util::test_generate_config::<CloudwatchLogsSinkConfig>();
     }
+
+    #[test]
+    fn validates_retention_days() {
+        let mut config = default_config(JsonSerializerConfig::default().into());
+        assert!(config.validate_retention_days().is_ok());
+
+        config.retention_days = Some(30);
+        assert!(config.validate_retention_days().is_ok());
+
+        config.retention_days = Some(31);
+        assert!(config.validate_retention_days().is_err());
+    }
 }
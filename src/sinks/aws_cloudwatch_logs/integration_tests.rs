@@ -1,4 +1,5 @@
 use std::convert::TryFrom;
+use std::num::NonZeroUsize;
 use std::str::FromStr;
 
 use aws_sdk_cloudwatchlogs::Client as CloudwatchLogsClient;
@@ -44,6 +45,10 @@ async fn cloudwatch_insert_log_event() {
         encoding: TextSerializerConfig::default().into(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
@@ -94,6 +99,10 @@ async fn cloudwatch_insert_log_events_sorted() {
         encoding: TextSerializerConfig::default().into(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
@@ -169,6 +178,10 @@ async fn cloudwatch_insert_out_of_range_timestamp() {
         encoding: TextSerializerConfig::default().into(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
@@ -248,6 +261,10 @@ async fn cloudwatch_dynamic_group_and_stream_creation() {
         encoding: TextSerializerConfig::default().into(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
@@ -303,6 +320,10 @@ async fn cloudwatch_insert_log_event_batched() {
         encoding: TextSerializerConfig::default().into(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch,
         request: Default::default(),
@@ -339,6 +360,77 @@ async fn cloudwatch_insert_log_event_batched() {
     assert_eq!(output_lines, input_lines);
 }
 
+#[tokio::test]
+async fn cloudwatch_create_group_with_settings_and_shards() {
+    trace_init();
+
+    let stream_name = gen_name();
+    let group_name = gen_name();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(2);
+
+    let config = CloudwatchLogsSinkConfig {
+        stream_name: Template::try_from(stream_name.as_str()).unwrap(),
+        group_name: Template::try_from(group_name.as_str()).unwrap(),
+        region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        encoding: TextSerializerConfig::default().into(),
+        create_missing_group: true,
+        create_missing_stream: true,
+        retention_days: Some(7),
+        kms_key: None,
+        tags: Some([("team".to_owned(), "vector".to_owned())].into()),
+        stream_shards: NonZeroUsize::new(2),
+        compression: Default::default(),
+        batch,
+        request: Default::default(),
+        tls: Default::default(),
+        assume_role: None,
+        auth: Default::default(),
+        acknowledgements: Default::default(),
+    };
+
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let timestamp = chrono::Utc::now();
+
+    let (input_lines, events) = random_lines_with_stream(100, 10, None);
+    run_and_assert_sink_compliance(sink, events, &AWS_SINK_TAGS).await;
+
+    let client = create_client_test().await;
+    let group = client
+        .describe_log_groups()
+        .log_group_name_prefix(group_name.as_str())
+        .send()
+        .await
+        .unwrap()
+        .log_groups
+        .unwrap()
+        .remove(0);
+    assert_eq!(group.retention_in_days, Some(7));
+
+    // The batches are spread over both streams.
+    let mut output_lines = Vec::new();
+    for shard in 0..2 {
+        let response = client
+            .get_log_events()
+            .log_stream_name(format!("{}-{}", stream_name, shard))
+            .log_group_name(group_name.as_str())
+            .start_time(timestamp.timestamp_millis())
+            .send()
+            .await
+            .unwrap();
+        let events = response.events.unwrap();
+        assert!(!events.is_empty());
+        output_lines.extend(events.into_iter().map(|e| e.message.unwrap()));
+    }
+
+    output_lines.sort();
+    let mut input_lines = input_lines;
+    input_lines.sort();
+    assert_eq!(output_lines, input_lines);
+}
+
 #[tokio::test]
 async fn cloudwatch_insert_log_event_partitioned() {
     trace_init();
@@ -353,6 +445,10 @@ async fn cloudwatch_insert_log_event_partitioned() {
         encoding: TextSerializerConfig::default().into(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
@@ -445,6 +541,10 @@ async fn cloudwatch_healthcheck() {
         encoding: TextSerializerConfig::default().into(),
         create_missing_group: true,
         create_missing_stream: true,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
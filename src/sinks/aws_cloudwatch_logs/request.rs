@@ -1,12 +1,15 @@
 use std::{
+    collections::HashMap,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{ready, Context, Poll},
 };
 
 use aws_sdk_cloudwatchlogs::error::{
     CreateLogGroupError, CreateLogGroupErrorKind, CreateLogStreamError, CreateLogStreamErrorKind,
-    DescribeLogStreamsError, DescribeLogStreamsErrorKind, PutLogEventsError,
+    DescribeLogStreamsError, DescribeLogStreamsErrorKind, PutLogEventsError, PutLogEventsErrorKind,
+    PutRetentionPolicyError,
 };
 use aws_sdk_cloudwatchlogs::operation::PutLogEvents;
 
@@ -21,12 +24,27 @@ use tokio::sync::oneshot;
 
 use crate::sinks::aws_cloudwatch_logs::service::{CloudwatchError, SmithyClient};
 
+/// The number of times the events are sent again with the sequence token expected by CloudWatch
+/// Logs, when another writer to the stream sent events in the meantime.
+const SEQUENCE_TOKEN_RETRIES: usize = 3;
+
+/// The settings of the log groups created by the sink.
+#[derive(Debug, Default)]
+pub struct LogGroupSettings {
+    pub retention_days: Option<i32>,
+    pub kms_key: Option<String>,
+    pub tags: Option<HashMap<String, String>>,
+}
+
 pub struct CloudwatchFuture {
     client: Client,
     state: State,
     create_missing_group: bool,
     create_missing_stream: bool,
     events: Vec<Vec<InputLogEvent>>,
+    /// The events being put, to send them again on sequence token conflicts.
+    in_flight: Vec<InputLogEvent>,
+    sequence_token_retries: usize,
     token_tx: Option<oneshot::Sender<Option<String>>>,
 }
 
@@ -39,6 +57,7 @@ struct Client {
     smithy_client: SmithyClient,
     stream_name: String,
     group_name: String,
+    group_settings: Arc<LogGroupSettings>,
     headers: IndexMap<String, String>,
 }
 
@@ -46,6 +65,7 @@ type ClientResult<T, E> = BoxFuture<'static, Result<T, SdkError<E>>>;
 
 enum State {
     CreateGroup(ClientResult<(), CreateLogGroupError>),
+    PutRetentionPolicy(ClientResult<(), PutRetentionPolicyError>),
     CreateStream(ClientResult<(), CreateLogStreamError>),
     DescribeStream(ClientResult<DescribeLogStreamsOutput, DescribeLogStreamsError>),
     Put(ClientResult<PutLogEventsOutput, PutLogEventsError>),
@@ -60,6 +80,7 @@ impl CloudwatchFuture {
         headers: IndexMap<String, String>,
         stream_name: String,
         group_name: String,
+        group_settings: Arc<LogGroupSettings>,
         create_missing_group: bool,
         create_missing_stream: bool,
         mut events: Vec<Vec<InputLogEvent>>,
@@ -71,11 +92,14 @@ impl CloudwatchFuture {
             smithy_client,
             stream_name,
             group_name,
+            group_settings,
             headers,
         };
 
+        let mut in_flight = Vec::new();
         let state = if let Some(token) = token {
-            State::Put(client.put_logs(Some(token), events.pop().expect("No Events to send")))
+            in_flight = events.pop().expect("No Events to send");
+            State::Put(client.put_logs(Some(token), in_flight.clone()))
         } else {
             State::DescribeStream(client.describe_stream())
         };
@@ -83,12 +107,32 @@ impl CloudwatchFuture {
         Self {
             client,
             events,
+            in_flight,
+            sequence_token_retries: 0,
             state,
             token_tx: Some(token_tx),
             create_missing_group,
             create_missing_stream,
         }
     }
+
+    fn put_next(&mut self, token: Option<String>, events: Vec<InputLogEvent>) {
+        self.state = State::Put(self.client.put_logs(token, events.clone()));
+        self.in_flight = events;
+    }
+}
+
+/// Returns the sequence token expected by CloudWatch Logs, when the events were put with another.
+fn expected_sequence_token(error: &SdkError<PutLogEventsError>) -> Option<String> {
+    match error {
+        SdkError::ServiceError { err, raw: _ } => match &err.kind {
+            PutLogEventsErrorKind::InvalidSequenceTokenException(error) => {
+                error.expected_sequence_token().map(ToOwned::to_owned)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 impl Future for CloudwatchFuture {
@@ -134,7 +178,7 @@ impl Future for CloudwatchFuture {
                         let token = stream.upload_sequence_token;
 
                         info!(message = "Putting logs.", token = ?token);
-                        self.state = State::Put(self.client.put_logs(token, events));
+                        self.put_next(token, events);
                     } else if self.create_missing_stream {
                         info!("Provided stream does not exist; creating a new one.");
                         self.state = State::CreateStream(self.client.create_log_stream());
@@ -162,12 +206,28 @@ impl Future for CloudwatchFuture {
 
                     info!(message = "Group created.", name = %self.client.group_name);
 
+                    if self.client.group_settings.retention_days.is_some() {
+                        self.state = State::PutRetentionPolicy(self.client.put_retention_policy());
+                        continue;
+                    }
+
                     // self does not abide by `create_missing_stream` since a group
                     // never has any streams and thus we need to create one if a group
                     // is created no matter what.
                     self.state = State::CreateStream(self.client.create_log_stream());
                 }
 
+                State::PutRetentionPolicy(fut) => {
+                    if let Err(err) = ready!(fut.poll_unpin(cx)) {
+                        return Poll::Ready(Err(CloudwatchError::PutRetentionPolicy(err)));
+                    }
+
+                    info!(message = "Group retention policy set.", name = %self.client.group_name);
+
+                    // A group never has any streams, see `CreateGroup`.
+                    self.state = State::CreateStream(self.client.create_log_stream());
+                }
+
                 State::CreateStream(fut) => {
                     match ready!(fut.poll_unpin(cx)) {
                         Ok(_) => {}
@@ -193,12 +253,28 @@ impl Future for CloudwatchFuture {
                 State::Put(fut) => {
                     let next_token = match ready!(fut.poll_unpin(cx)) {
                         Ok(resp) => resp.next_sequence_token,
-                        Err(err) => return Poll::Ready(Err(CloudwatchError::Put(err))),
+                        Err(err) => {
+                            // Another writer put events to the stream in the meantime.
+                            let conflict = expected_sequence_token(&err)
+                                .filter(|_| self.sequence_token_retries < SEQUENCE_TOKEN_RETRIES);
+                            if let Some(token) = conflict {
+                                self.sequence_token_retries += 1;
+                                warn!(
+                                    message = "Sequence token conflict; putting logs again.",
+                                    stream = %self.client.stream_name,
+                                    internal_log_rate_limit = true,
+                                );
+                                let events = std::mem::take(&mut self.in_flight);
+                                self.put_next(Some(token), events);
+                                continue;
+                            }
+                            return Poll::Ready(Err(CloudwatchError::Put(err)));
+                        }
                     };
 
                     if let Some(events) = self.events.pop() {
                         debug!(message = "Putting logs.", next_token = ?next_token);
-                        self.state = State::Put(self.client.put_logs(next_token, events));
+                        self.put_next(next_token, events);
                     } else {
                         info!(message = "Putting logs was successful.", next_token = ?next_token);
 
@@ -283,10 +359,29 @@ impl Client {
     pub fn create_log_group(&self) -> ClientResult<(), CreateLogGroupError> {
         let client = self.client.clone();
         let group_name = self.group_name.clone();
+        let kms_key = self.group_settings.kms_key.clone();
+        let tags = self.group_settings.tags.clone();
         Box::pin(async move {
             client
                 .create_log_group()
                 .log_group_name(group_name)
+                .set_kms_key_id(kms_key)
+                .set_tags(tags)
+                .send()
+                .await?;
+            Ok(())
+        })
+    }
+
+    pub fn put_retention_policy(&self) -> ClientResult<(), PutRetentionPolicyError> {
+        let client = self.client.clone();
+        let group_name = self.group_name.clone();
+        let retention_days = self.group_settings.retention_days;
+        Box::pin(async move {
+            client
+                .put_retention_policy()
+                .log_group_name(group_name)
+                .set_retention_in_days(retention_days)
                 .send()
                 .await?;
             Ok(())
@@ -37,7 +37,10 @@ impl<T: Send + Sync + 'static> RetryLogic for CloudwatchRetryLogic<T> {
         match error {
             CloudwatchError::Put(err) => {
                 if let SdkError::ServiceError { err, raw: _ } = err {
-                    if let PutLogEventsErrorKind::ServiceUnavailableException(_) = err.kind {
+                    // The sequence token is fetched again when retrying.
+                    if let PutLogEventsErrorKind::ServiceUnavailableException(_)
+                    | PutLogEventsErrorKind::InvalidSequenceTokenException(_) = err.kind
+                    {
                         return true;
                     }
                 }
@@ -1,11 +1,13 @@
 use std::{
     collections::HashMap,
     fmt,
+    sync::Arc,
     task::{ready, Context, Poll},
 };
 
 use aws_sdk_cloudwatchlogs::error::{
     CreateLogGroupError, CreateLogStreamError, DescribeLogStreamsError, PutLogEventsError,
+    PutRetentionPolicyError,
 };
 use aws_sdk_cloudwatchlogs::model::InputLogEvent;
 use aws_sdk_cloudwatchlogs::types::SdkError;
@@ -30,8 +32,11 @@ use crate::{
     event::EventStatus,
     sinks::{
         aws_cloudwatch_logs::{
-            config::CloudwatchLogsSinkConfig, request, retry::CloudwatchRetryLogic,
-            sink::BatchCloudwatchRequest, CloudwatchKey,
+            config::CloudwatchLogsSinkConfig,
+            request::{self, LogGroupSettings},
+            retry::CloudwatchRetryLogic,
+            sink::BatchCloudwatchRequest,
+            CloudwatchKey,
         },
         util::{
             retries::FixedRetryPolicy, EncodedLength, TowerRequestConfig, TowerRequestSettings,
@@ -64,6 +69,7 @@ pub enum CloudwatchError {
     Describe(SdkError<DescribeLogStreamsError>),
     CreateStream(SdkError<CreateLogStreamError>),
     CreateGroup(SdkError<CreateLogGroupError>),
+    PutRetentionPolicy(SdkError<PutRetentionPolicyError>),
     NoStreamsFound,
 }
 
@@ -78,6 +84,9 @@ impl fmt::Display for CloudwatchError {
             CloudwatchError::CreateGroup(error) => {
                 write!(f, "CloudwatchError::CreateGroup: {}", error)
             }
+            CloudwatchError::PutRetentionPolicy(error) => {
+                write!(f, "CloudwatchError::PutRetentionPolicy: {}", error)
+            }
             CloudwatchError::NoStreamsFound => write!(f, "CloudwatchError: No Streams Found"),
         }
     }
@@ -137,15 +146,39 @@ impl CloudwatchLogsPartitionSvc {
             .request
             .tower
             .unwrap_with(&TowerRequestConfig::default());
+        let group_settings = Arc::new(LogGroupSettings {
+            // The supported retention periods all fit.
+            retention_days: config.retention_days.map(|days| days as i32),
+            kms_key: config.kms_key.clone(),
+            tags: config.tags.clone(),
+        });
 
         Self {
             config,
             clients: HashMap::new(),
+            shards: HashMap::new(),
             request_settings,
+            group_settings,
             client,
             smithy_client,
         }
     }
+
+    /// Returns the key of the stream shard the next batch for the key is sent to.
+    fn shard_key(&mut self, key: CloudwatchKey) -> CloudwatchKey {
+        match self.config.stream_shards {
+            Some(stream_shards) => {
+                let next = self.shards.entry(key.clone()).or_default();
+                let shard = *next;
+                *next = (shard + 1) % stream_shards.get();
+                CloudwatchKey {
+                    group: key.group,
+                    stream: format!("{}-{}", key.stream, shard),
+                }
+            }
+            None => key,
+        }
+    }
 }
 
 impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
@@ -161,7 +194,7 @@ impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
         let events_count = req.get_metadata().event_count();
         let events_byte_size = req.get_metadata().events_byte_size();
 
-        let key = req.key;
+        let key = self.shard_key(req.key);
         let events = req
             .events
             .into_iter()
@@ -194,6 +227,7 @@ impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
                     &key,
                     self.client.clone(),
                     std::sync::Arc::clone(&self.smithy_client),
+                    Arc::clone(&self.group_settings),
                 ));
 
             self.clients.insert(key, svc.clone());
@@ -216,6 +250,7 @@ impl CloudwatchLogsSvc {
         key: &CloudwatchKey,
         client: CloudwatchLogsClient,
         smithy_client: SmithyClient,
+        group_settings: Arc<LogGroupSettings>,
     ) -> Self {
         let group_name = key.group.clone();
         let stream_name = key.stream.clone();
@@ -229,6 +264,7 @@ impl CloudwatchLogsSvc {
             smithy_client,
             stream_name,
             group_name,
+            group_settings,
             create_missing_group,
             create_missing_stream,
             token: None,
@@ -310,6 +346,7 @@ impl Service<Vec<InputLogEvent>> for CloudwatchLogsSvc {
                 self.headers.clone(),
                 self.stream_name.clone(),
                 self.group_name.clone(),
+                Arc::clone(&self.group_settings),
                 self.create_missing_group,
                 self.create_missing_stream,
                 event_batches,
@@ -328,6 +365,7 @@ pub struct CloudwatchLogsSvc {
     headers: IndexMap<String, String>,
     stream_name: String,
     group_name: String,
+    group_settings: Arc<LogGroupSettings>,
     create_missing_group: bool,
     create_missing_stream: bool,
     token: Option<String>,
@@ -344,7 +382,10 @@ impl EncodedLength for InputLogEvent {
 pub struct CloudwatchLogsPartitionSvc {
     config: CloudwatchLogsSinkConfig,
     clients: HashMap<CloudwatchKey, Svc>,
+    /// The index of the stream shard the next batch is sent to, by key.
+    shards: HashMap<CloudwatchKey, usize>,
     request_settings: TowerRequestSettings,
+    group_settings: Arc<LogGroupSettings>,
     client: CloudwatchLogsClient,
     smithy_client: SmithyClient,
 }
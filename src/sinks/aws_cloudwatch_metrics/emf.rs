@@ -0,0 +1,174 @@
+use std::iter;
+
+use chrono::Utc;
+use futures::{future, stream::BoxStream, StreamExt};
+use serde_json::json;
+use vector_config::configurable_component;
+use vector_core::{
+    config::log_schema,
+    event::{EventArray, LogEvent},
+    sink::{StreamSink, VectorSink},
+};
+
+use super::{AwsCloudwatchMetricNormalize, CloudWatchMetricsSinkConfig};
+use crate::{
+    codecs::EncodingConfig,
+    config::{SinkConfig, SinkContext},
+    event::{
+        metric::{Metric, MetricValue},
+        Event,
+    },
+    sinks::{
+        aws_cloudwatch_logs::CloudwatchLogsSinkConfig,
+        util::{buffer::metrics::MetricNormalizer, http::RequestConfig},
+        Healthcheck,
+    },
+    template::Template,
+};
+
+/// CloudWatch accepts at most 30 dimensions per metric.
+const MAX_DIMENSIONS: usize = 30;
+
+/// CloudWatch accepts at most 100 values per metric in a single EMF document.
+const MAX_VALUES: usize = 100;
+
+/// Configuration for emitting metrics in the [Embedded Metric Format][emf] via CloudWatch Logs.
+///
+/// Metrics written this way are extracted by CloudWatch from the log events, which avoids the
+/// request rate and dimension limits of the `PutMetricData` API.
+///
+/// [emf]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/CloudWatch_Embedded_Metric_Format_Specification.html
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EmfConfig {
+    /// The group name of the CloudWatch Logs stream to write the metrics to.
+    ///
+    /// Metric tags are available as fields when rendering the template.
+    #[configurable(metadata(docs::examples = "vector-metrics"))]
+    pub group_name: Template,
+
+    /// The stream name of the CloudWatch Logs stream to write the metrics to.
+    ///
+    /// There can only be one writer to a log stream at a time. If multiple instances are writing to
+    /// the same log group, the stream name must include an identifier that is guaranteed to be
+    /// unique per instance.
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    pub stream_name: Template,
+
+    /// Dynamically create the log group if it does not already exist.
+    #[serde(default = "crate::serde::default_true")]
+    pub create_missing_group: bool,
+
+    /// Dynamically create the log stream if it does not already exist.
+    #[serde(default = "crate::serde::default_true")]
+    pub create_missing_stream: bool,
+}
+
+/// Builds a sink that writes metrics as EMF documents through the `aws_cloudwatch_logs` sink.
+pub(super) async fn build(
+    config: &CloudWatchMetricsSinkConfig,
+    emf: &EmfConfig,
+    cx: SinkContext,
+) -> crate::Result<(VectorSink, Healthcheck)> {
+    let logs_config = CloudwatchLogsSinkConfig {
+        group_name: emf.group_name.clone(),
+        stream_name: emf.stream_name.clone(),
+        region: config.region.clone(),
+        create_missing_group: emf.create_missing_group,
+        create_missing_stream: emf.create_missing_stream,
+        retention_days: None,
+        kms_key: None,
+        tags: None,
+        stream_shards: None,
+        encoding: EncodingConfig::from(codecs::JsonSerializerConfig::default()),
+        compression: config.compression,
+        batch: Default::default(),
+        request: RequestConfig {
+            tower: config.request,
+            headers: Default::default(),
+        },
+        tls: config.tls.clone(),
+        assume_role: config.assume_role.clone(),
+        auth: config.auth.clone(),
+        acknowledgements: config.acknowledgements,
+    };
+    let (inner, healthcheck) = logs_config.build(cx).await?;
+    let sink = EmfSink {
+        inner,
+        default_namespace: config.default_namespace.clone(),
+    };
+
+    Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+}
+
+struct EmfSink {
+    inner: VectorSink,
+    default_namespace: String,
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for EmfSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            inner,
+            default_namespace,
+        } = *self;
+        let mut normalizer = MetricNormalizer::<AwsCloudwatchMetricNormalize>::default();
+
+        let input = input.filter_map(move |event| {
+            let log = normalizer
+                .normalize(event.into_metric())
+                .and_then(|metric| encode_emf(metric, &default_namespace));
+            future::ready(log.map(EventArray::from))
+        });
+
+        inner.run(input).await
+    }
+}
+
+/// Encodes a normalized metric as an EMF document.
+///
+/// Returns `None` for metric types that CloudWatch can not represent.
+pub(super) fn encode_emf(metric: Metric, default_namespace: &str) -> Option<LogEvent> {
+    let value = match metric.value() {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => json!(value),
+        MetricValue::Set { values } => json!(values.len()),
+        MetricValue::Distribution { samples, .. } => json!(samples
+            .iter()
+            .flat_map(|sample| iter::repeat(sample.value).take(sample.rate as usize))
+            .take(MAX_VALUES)
+            .collect::<Vec<_>>()),
+        _ => return None,
+    };
+    let timestamp = metric.timestamp().unwrap_or_else(Utc::now);
+
+    let mut document = serde_json::Map::new();
+    let mut dimensions = Vec::new();
+    if let Some(tags) = metric.tags() {
+        for (key, value) in tags.iter_single().take(MAX_DIMENSIONS) {
+            dimensions.push(key.to_owned());
+            document.insert(key.to_owned(), json!(value));
+        }
+    }
+    document.insert(metric.name().to_owned(), value);
+    document.insert(
+        "_aws".to_owned(),
+        json!({
+            "Timestamp": timestamp.timestamp_millis(),
+            "CloudWatchMetrics": [{
+                "Namespace": metric.namespace().unwrap_or(default_namespace),
+                "Dimensions": [dimensions],
+                "Metrics": [{ "Name": metric.name() }],
+            }],
+        }),
+    );
+
+    let (_, _, metadata) = metric.into_parts();
+    let fields = document.into_iter().map(|(k, v)| (k, v.into())).collect();
+    let mut log = LogEvent::from_map(fields, metadata);
+    if let Some(timestamp_key) = log_schema().timestamp_key() {
+        log.insert((lookup::PathPrefix::Event, timestamp_key), timestamp);
+    }
+    Some(log)
+}
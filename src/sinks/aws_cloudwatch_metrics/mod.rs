@@ -1,3 +1,4 @@
+mod emf;
 #[cfg(all(test, feature = "aws-cloudwatch-metrics-integration-tests"))]
 mod integration_tests;
 #[cfg(test)]
@@ -36,6 +37,8 @@ use crate::{
     tls::TlsConfig,
 };
 
+pub use self::emf::EmfConfig;
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct CloudWatchMetricsDefaultBatchSettings;
 
@@ -81,6 +84,15 @@ pub struct CloudWatchMetricsSinkConfig {
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
+    /// Emit metrics in the Embedded Metric Format (EMF) via CloudWatch Logs.
+    ///
+    /// When set, metrics are written as log events to the configured log group instead of being
+    /// sent with the `PutMetricData` API. This lifts the API's request rate and dimension limits,
+    /// which makes it better suited to high-cardinality metrics. The `batch` setting does not
+    /// apply in this mode.
+    #[configurable(derived)]
+    pub emf: Option<EmfConfig>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -125,6 +137,10 @@ impl SinkConfig for CloudWatchMetricsSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if let Some(emf) = &self.emf {
+            return emf::build(self, emf, cx).await;
+        }
+
         let client = self.create_client(&cx.proxy).await?;
         let healthcheck = self.clone().healthcheck(client.clone()).boxed();
         let sink = CloudWatchMetricsSvc::new(self.clone(), client)?;
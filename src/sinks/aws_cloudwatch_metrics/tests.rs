@@ -147,3 +147,50 @@ async fn encode_events_set() {
             .build()]
     );
 }
+
+#[test]
+fn encode_emf_counter() {
+    let timestamp = Utc
+        .with_ymd_and_hms(2018, 11, 14, 8, 9, 10)
+        .single()
+        .expect("invalid timestamp");
+    let metric = Metric::new(
+        "exception_total",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    )
+    .with_namespace(Some("service"))
+    .with_tags(Some(metric_tags!("region" => "local", "host" => "a")))
+    .with_timestamp(Some(timestamp));
+
+    let log = emf::encode_emf(metric, "vector").unwrap();
+
+    assert_eq!(log["exception_total"], 1.0.into());
+    assert_eq!(log["region"], "local".into());
+    assert_eq!(log["host"], "a".into());
+    assert_eq!(log["_aws.Timestamp"], timestamp.timestamp_millis().into());
+    assert_eq!(log["_aws.CloudWatchMetrics[0].Namespace"], "service".into());
+    assert_eq!(
+        log["_aws.CloudWatchMetrics[0].Dimensions[0]"],
+        vec!["host", "region"].into()
+    );
+    assert_eq!(
+        log["_aws.CloudWatchMetrics[0].Metrics[0].Name"],
+        "exception_total".into()
+    );
+}
+
+#[test]
+fn encode_emf_skips_unsupported_values() {
+    let metric = Metric::new(
+        "requests",
+        MetricKind::Absolute,
+        MetricValue::AggregatedSummary {
+            quantiles: vector_core::quantiles![0.5 => 1.0],
+            count: 1,
+            sum: 1.0,
+        },
+    );
+
+    assert!(emf::encode_emf(metric, "vector").is_none());
+}
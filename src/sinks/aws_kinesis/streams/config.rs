@@ -7,7 +7,8 @@ use snafu::Snafu;
 use vector_config::{component::GenerateConfig, configurable_component};
 
 use crate::{
-    aws::{create_client, is_retriable_error, ClientBuilder},
+    aws::{create_client, is_retriable_error},
+    common::kinesis::KinesisClientBuilder,
     config::{AcknowledgementsConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
         util::{retries::RetryLogic, BatchConfig, SinkBatchSettings},
@@ -37,22 +38,6 @@ enum HealthcheckError {
     NoMatchingStreamName { stream_name: String },
 }
 
-pub struct KinesisClientBuilder;
-
-impl ClientBuilder for KinesisClientBuilder {
-    type Config = aws_sdk_kinesis::config::Config;
-    type Client = KinesisClient;
-    type DefaultMiddleware = aws_sdk_kinesis::middleware::DefaultMiddleware;
-
-    fn default_middleware() -> Self::DefaultMiddleware {
-        aws_sdk_kinesis::middleware::DefaultMiddleware::new()
-    }
-
-    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
-        KinesisClient::with_config(client, config.into())
-    }
-}
-
 pub const MAX_PAYLOAD_SIZE: usize = 5_000_000;
 pub const MAX_PAYLOAD_EVENTS: usize = 500;
 
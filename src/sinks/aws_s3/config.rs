@@ -26,8 +26,8 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
-            TowerRequestConfig,
+            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, PartitionLimitsConfig,
+            ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -117,6 +117,10 @@ pub struct S3SinkConfig {
     #[serde(default)]
     pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub partitions: PartitionLimitsConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -158,6 +162,7 @@ impl GenerateConfig for S3SinkConfig {
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
+            partitions: PartitionLimitsConfig::default(),
             request: TowerRequestConfig::default(),
             tls: Some(TlsConfig::default()),
             auth: AwsAuthentication::default(),
@@ -197,7 +202,7 @@ impl S3SinkConfig {
             .service(service);
 
         // Configure our partitioning/batching.
-        let batch_settings = self.batch.into_batcher_settings()?;
+        let batch_settings = self.partitions.apply(self.batch.into_batcher_settings()?);
         let key_prefix = self.key_prefix.clone().try_into()?;
         let ssekms_key_id = self
             .options
@@ -394,6 +394,7 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
         compression: Compression::None,
         batch,
+        partitions: Default::default(),
         request: TowerRequestConfig::default(),
         tls: Default::default(),
         auth: Default::default(),
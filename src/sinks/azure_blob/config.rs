@@ -12,11 +12,14 @@ use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
-            self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
+            self,
+            config::{AzureAuthentication, AzureBlobRetryLogic, BlobType},
+            service::AzureBlobService,
+            sink::AzureBlobSink,
         },
         util::{
             partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression, ServiceBuilderExt, TowerRequestConfig,
+            Compression, Concurrency, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -41,7 +44,8 @@ pub struct AzureBlobSinkConfig {
 
     /// The Azure Blob Storage Account name.
     ///
-    /// Attempts to load credentials for the account in the following ways, in order:
+    /// The credentials for the account are chosen with `auth`. By default, they are loaded in the
+    /// following ways, in order:
     ///
     /// - read from environment variables ([more information][env_cred_docs])
     /// - looks for a [Managed Identity][managed_ident_docs]
@@ -55,6 +59,10 @@ pub struct AzureBlobSinkConfig {
     #[configurable(metadata(docs::examples = "mylogstorage"))]
     pub storage_account: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AzureAuthentication,
+
     /// The Azure Blob Storage Endpoint URL.
     ///
     /// This is used to override the default blob storage endpoint URL in cases where you are using
@@ -100,6 +108,9 @@ pub struct AzureBlobSinkConfig {
     ///
     /// When set to an empty string, no timestamp is appended to the blob prefix.
     ///
+    /// When `blob_type` is `append`, this defaults to `%Y-%m-%d-%H`, so that batches are appended to
+    /// a new blob every hour.
+    ///
     /// [chrono_strftime_specifiers]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
     #[configurable(metadata(docs::syntax_override = "strftime"))]
     pub blob_time_format: Option<String>,
@@ -113,8 +124,14 @@ pub struct AzureBlobSinkConfig {
     ///
     /// This ensures there are no name collisions, and can be useful in high-volume workloads where
     /// blob keys must be unique.
+    ///
+    /// This can't be enabled when `blob_type` is `append`.
     pub blob_append_uuid: Option<bool>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub blob_type: BlobType,
+
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
@@ -148,11 +165,13 @@ impl GenerateConfig for AzureBlobSinkConfig {
         toml::Value::try_from(Self {
             connection_string: Some(String::from("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;").into()),
             storage_account: Some(String::from("some-account-name")),
+            auth: Default::default(),
             container_name: String::from("logs"),
             endpoint: None,
             blob_prefix: default_blob_prefix(),
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
+            blob_type: BlobType::Block,
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::default()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
@@ -173,6 +192,7 @@ impl SinkConfig for AzureBlobSinkConfig {
             self.storage_account.as_ref().map(|v| v.to_string()),
             self.container_name.clone(),
             self.endpoint.clone(),
+            &self.auth,
         )?;
 
         let healthcheck = azure_common::config::build_healthcheck(
@@ -194,16 +214,38 @@ impl SinkConfig for AzureBlobSinkConfig {
 
 const DEFAULT_KEY_PREFIX: &str = "blob/%F/";
 const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
+const DEFAULT_APPEND_FILENAME_TIME_FORMAT: &str = "%Y-%m-%d-%H";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
 
 impl AzureBlobSinkConfig {
     pub fn build_processor(&self, client: Arc<ContainerClient>) -> crate::Result<VectorSink> {
+        let (default_time_format, default_append_uuid, default_concurrency) = match self.blob_type {
+            BlobType::Block => (
+                DEFAULT_FILENAME_TIME_FORMAT,
+                DEFAULT_FILENAME_APPEND_UUID,
+                Concurrency::None,
+            ),
+            // Batches are appended one at a time, so they end up in the blob in order.
+            BlobType::Append => {
+                if self.blob_append_uuid == Some(true) {
+                    return Err(
+                        "`blob_append_uuid` can't be enabled when `blob_type` is `append`".into(),
+                    );
+                }
+                (
+                    DEFAULT_APPEND_FILENAME_TIME_FORMAT,
+                    false,
+                    Concurrency::Fixed(1),
+                )
+            }
+        };
+
         let request_limits = self
             .request
-            .unwrap_with(&TowerRequestConfig::default().rate_limit_num(250));
+            .unwrap_with(&TowerRequestConfig::new(default_concurrency).rate_limit_num(250));
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(AzureBlobService::new(client, self.blob_type));
 
         // Configure our partitioning/batching.
         let batcher_settings = self.batch.into_batcher_settings()?;
@@ -212,10 +254,8 @@ impl AzureBlobSinkConfig {
             .blob_time_format
             .as_ref()
             .cloned()
-            .unwrap_or_else(|| DEFAULT_FILENAME_TIME_FORMAT.into());
-        let blob_append_uuid = self
-            .blob_append_uuid
-            .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
+            .unwrap_or_else(|| default_time_format.into());
+        let blob_append_uuid = self.blob_append_uuid.unwrap_or(default_append_uuid);
 
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
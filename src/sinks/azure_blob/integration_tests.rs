@@ -19,7 +19,7 @@ use super::config::AzureBlobSinkConfig;
 use crate::{
     event::{Event, EventArray, LogEvent},
     sinks::{
-        azure_common,
+        azure_common::{self, config::BlobType},
         util::{Compression, TowerRequestConfig},
         VectorSink,
     },
@@ -37,6 +37,7 @@ async fn azure_blob_healthcheck_passed() {
         None,
         config.container_name.clone(),
         None,
+        &Default::default(),
     )
     .expect("Failed to create client");
 
@@ -57,6 +58,7 @@ async fn azure_blob_healthcheck_unknown_container() {
         config.storage_account.map(Into::into),
         config.container_name.clone(),
         config.endpoint.clone(),
+        &Default::default(),
     )
     .expect("Failed to create client");
 
@@ -215,17 +217,41 @@ async fn azure_blob_rotate_files_after_the_buffer_size_is_reached() {
     }
 }
 
+#[tokio::test]
+async fn azure_blob_append_lines_into_blob() {
+    let blob_prefix = format!("lines-append/into/blob/{}", random_string(10));
+    let config = AzureBlobSinkConfig::new_emulator().await;
+    let config = AzureBlobSinkConfig {
+        blob_prefix: blob_prefix.clone().try_into().unwrap(),
+        blob_time_format: Some(String::new()),
+        blob_type: BlobType::Append,
+        ..config
+    };
+    let (mut lines, input) = random_lines_with_stream(100, 10, None);
+    config.run_assert(input).await;
+    let (more_lines, input) = random_lines_with_stream(100, 10, None);
+    config.run_assert(input).await;
+
+    let blobs = config.list_blobs(blob_prefix).await;
+    assert_eq!(blobs.len(), 1);
+    let (_, blob_lines) = config.get_blob(blobs[0].clone()).await;
+    lines.extend(more_lines);
+    assert_eq!(lines, blob_lines);
+}
+
 impl AzureBlobSinkConfig {
     pub async fn new_emulator() -> AzureBlobSinkConfig {
         let address = std::env::var("AZURE_ADDRESS").unwrap_or_else(|_| "localhost".into());
         let config = AzureBlobSinkConfig {
                 connection_string: Some(format!("UseDevelopmentStorage=true;DefaultEndpointsProtocol=http;AccountName=devstoreaccount1;AccountKey=Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==;BlobEndpoint=http://{}:10000/devstoreaccount1;QueueEndpoint=http://{}:10001/devstoreaccount1;TableEndpoint=http://{}:10002/devstoreaccount1;", address, address, address).into()),
                 storage_account: None,
+                auth: Default::default(),
                 container_name: "logs".to_string(),
                 endpoint: None,
                 blob_prefix: Default::default(),
                 blob_time_format: None,
                 blob_append_uuid: None,
+                blob_type: BlobType::Block,
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
                 compression: Compression::None,
                 batch: Default::default(),
@@ -244,6 +270,7 @@ impl AzureBlobSinkConfig {
             self.storage_account.clone().map(Into::into),
             self.container_name.clone(),
             self.endpoint.clone(),
+            &Default::default(),
         )
         .expect("Failed to create client");
 
@@ -263,6 +290,7 @@ impl AzureBlobSinkConfig {
             self.storage_account.clone().map(Into::into),
             self.container_name.clone(),
             self.endpoint.clone(),
+            &Default::default(),
         )
         .unwrap();
         let response = client
@@ -292,6 +320,7 @@ impl AzureBlobSinkConfig {
             self.storage_account.clone().map(Into::into),
             self.container_name.clone(),
             self.endpoint.clone(),
+            &Default::default(),
         )
         .unwrap();
         let response = client
@@ -329,6 +358,7 @@ impl AzureBlobSinkConfig {
             self.storage_account.clone().map(Into::into),
             self.container_name.clone(),
             self.endpoint.clone(),
+            &Default::default(),
         )
         .unwrap();
         let request = client
@@ -10,6 +10,7 @@ use super::config::AzureBlobSinkConfig;
 use super::request_builder::AzureBlobRequestOptions;
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
+use crate::sinks::azure_common::{self, config::AzureAuthentication};
 use crate::sinks::util::{request_builder::RequestBuilder, Compression};
 use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
 
@@ -17,11 +18,13 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
     AzureBlobSinkConfig {
         connection_string: Default::default(),
         storage_account: Default::default(),
+        auth: Default::default(),
         container_name: Default::default(),
         endpoint: Default::default(),
         blob_prefix: Default::default(),
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
+        blob_type: Default::default(),
         encoding,
         compression: Compression::gzip_default(),
         batch: Default::default(),
@@ -216,3 +219,34 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[test]
+fn azure_blob_auth_requires_storage_account() {
+    let config: AzureBlobSinkConfig = toml::from_str(
+        r#"
+            connection_string = "DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=c29tZS1hY2NvdW50LWtleQ==;"
+            container_name = "logs"
+            auth.type = "managed_identity"
+            encoding.codec = "json"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.auth,
+        AzureAuthentication::ManagedIdentity { client_id: None }
+    );
+
+    let error = azure_common::config::build_client(
+        config.connection_string.map(|v| v.inner().to_string()),
+        config.storage_account,
+        config.container_name,
+        config.endpoint,
+        &config.auth,
+    )
+    .err()
+    .expect("client should fail to build");
+    assert_eq!(
+        error.to_string(),
+        "`auth` can only be used with `storage_account`"
+    );
+}
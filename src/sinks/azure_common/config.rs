@@ -1,21 +1,90 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
-use azure_core::{error::HttpError, RetryOptions};
-use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
+use azure_core::{error::HttpError, RetryOptions, TransportOptions};
+use azure_identity::{
+    AutoRefreshingTokenCredential, DefaultAzureCredential, ImdsManagedIdentityCredential,
+};
 use azure_storage::{prelude::*, CloudLocation, ConnectionString};
-use azure_storage_blobs::{blob::operations::PutBlockBlobResponse, prelude::*};
+use azure_storage_blobs::prelude::*;
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
 use snafu::Snafu;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_config::configurable_component;
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
+use super::sas_token::SasTokenFileClient;
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     sinks::{util::retries::RetryLogic, Healthcheck},
 };
 
+/// The credentials used to authenticate with a storage account.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The kind of credentials."))]
+pub enum AzureAuthentication {
+    /// Credentials are loaded in the following ways, in order:
+    ///
+    /// - read from environment variables ([more information][env_cred_docs])
+    /// - looks for a [Managed Identity][managed_ident_docs]
+    /// - uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+    ///
+    /// [env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    /// [az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+    #[default]
+    Default,
+
+    /// Authenticate with an Azure AD [managed identity][managed_ident_docs].
+    ///
+    /// Access tokens are requested again before they expire.
+    ///
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    ManagedIdentity {
+        /// The client ID of a user-assigned managed identity.
+        ///
+        /// If not set, the system-assigned managed identity is used.
+        #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+        client_id: Option<String>,
+    },
+
+    /// Authenticate with a [SAS token][sas_docs] read from a file.
+    ///
+    /// The file is read again whenever it changes, so that the token can be rotated without
+    /// restarting Vector, such as when it is mounted from a Kubernetes secret.
+    ///
+    /// [sas_docs]: https://learn.microsoft.com/en-us/azure/storage/common/storage-sas-overview
+    SasTokenFile {
+        /// The path of the file holding the SAS token.
+        #[configurable(metadata(docs::examples = "/var/run/secrets/azure/sas-token"))]
+        path: PathBuf,
+    },
+}
+
+/// The type of blob to write batches to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobType {
+    /// Each batch is written to a new [block blob][block_blobs].
+    ///
+    /// [block_blobs]: https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs#about-block-blobs
+    #[default]
+    Block,
+
+    /// Batches are appended to an [append blob][append_blobs], which is created if it doesn't
+    /// exist yet.
+    ///
+    /// Batches whose blob keys are the same are written to the same blob, so fewer and larger blobs
+    /// are created, and they can be read while they are being written to.
+    ///
+    /// [append_blobs]: https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs#about-append-blobs
+    Append,
+}
+
 #[derive(Debug, Clone)]
 pub struct AzureBlobRequest {
     pub blob_data: Bytes,
@@ -60,7 +129,6 @@ impl RetryLogic for AzureBlobRetryLogic {
 
 #[derive(Debug)]
 pub struct AzureBlobResponse {
-    pub inner: PutBlockBlobResponse,
     pub count: usize,
     pub events_byte_size: usize,
     pub byte_size: usize,
@@ -122,9 +190,13 @@ pub fn build_client(
     storage_account: Option<String>,
     container_name: String,
     endpoint: Option<String>,
+    auth: &AzureAuthentication,
 ) -> crate::Result<Arc<ContainerClient>> {
     let client;
     match (connection_string, storage_account) {
+        (Some(_), None) if *auth != AzureAuthentication::Default => {
+            return Err("`auth` can only be used with `storage_account`".into())
+        }
         (Some(connection_string_p), None) => {
             let connection_string = ConnectionString::new(&connection_string_p)?;
 
@@ -152,11 +224,33 @@ pub fn build_client(
             .container_client(container_name);
         }
         (None, Some(storage_account_p)) => {
-            let creds = std::sync::Arc::new(DefaultAzureCredential::default());
-            let auto_creds = std::sync::Arc::new(AutoRefreshingTokenCredential::new(creds));
-            let storage_credentials = StorageCredentials::TokenCredential(auto_creds);
+            let mut transport = None;
+            let storage_credentials = match auth {
+                AzureAuthentication::Default => {
+                    let creds = std::sync::Arc::new(DefaultAzureCredential::default());
+                    let auto_creds = std::sync::Arc::new(AutoRefreshingTokenCredential::new(creds));
+                    StorageCredentials::TokenCredential(auto_creds)
+                }
+                AzureAuthentication::ManagedIdentity { client_id } => {
+                    let mut creds = ImdsManagedIdentityCredential::default();
+                    if let Some(client_id) = client_id {
+                        creds = creds.with_client_id(client_id.clone());
+                    }
+                    let auto_creds = std::sync::Arc::new(AutoRefreshingTokenCredential::new(
+                        std::sync::Arc::new(creds),
+                    ));
+                    StorageCredentials::TokenCredential(auto_creds)
+                }
+                AzureAuthentication::SasTokenFile { path } => {
+                    // The token is added to the requests by the transport, so that it can change.
+                    transport = Some(TransportOptions::new(std::sync::Arc::new(
+                        SasTokenFileClient::new(path.clone())?,
+                    )));
+                    StorageCredentials::Anonymous
+                }
+            };
 
-            client = match endpoint {
+            let builder = match endpoint {
                 // If a blob_endpoint is provided in the configuration, use it with a Custom
                 // CloudLocation, to allow overriding the blob storage API endpoint
                 Some(endpoint) => ClientBuilder::with_location(CloudLocation::Custom {
@@ -168,7 +262,11 @@ pub fn build_client(
                 // the storage_account as input.
                 None => ClientBuilder::new(storage_account_p, storage_credentials),
             }
-            .retry(RetryOptions::none())
+            .retry(RetryOptions::none());
+            client = match transport {
+                Some(transport) => builder.transport(transport),
+                None => builder,
+            }
             .container_client(container_name);
         }
         (None, None) => {
@@ -1,3 +1,4 @@
 pub(crate) mod config;
+pub(crate) mod sas_token;
 pub(crate) mod service;
 pub(crate) mod sink;
@@ -0,0 +1,120 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use azure_core::{
+    error::{Error, ErrorKind},
+    HttpClient, Request, Response,
+};
+
+/// An HTTP client adding a SAS token, read from a file, to the query of each request.
+///
+/// The file is read again whenever its modification time changes, so that the token can be rotated
+/// by whatever writes the file, such as a mounted Kubernetes secret.
+#[derive(Debug)]
+pub struct SasTokenFileClient {
+    inner: Arc<dyn HttpClient>,
+    path: PathBuf,
+    token: Mutex<Option<CachedToken>>,
+}
+
+#[derive(Debug)]
+struct CachedToken {
+    modified: SystemTime,
+    pairs: Vec<(String, String)>,
+}
+
+impl SasTokenFileClient {
+    pub fn new(path: PathBuf) -> crate::Result<Self> {
+        // Fail early rather than on the first request if the file can't be read.
+        std::fs::read_to_string(&path)
+            .map_err(|error| format!("Could not read SAS token file {:?}: {}", path, error))?;
+        Ok(Self {
+            inner: azure_core::new_http_client(),
+            path,
+            token: Mutex::new(None),
+        })
+    }
+
+    async fn token(&self) -> azure_core::Result<Vec<(String, String)>> {
+        let modified = tokio::fs::metadata(&self.path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .map_err(|error| Error::new(ErrorKind::Io, error))?;
+        if let Some(cached) = &*self.token.lock().unwrap() {
+            if cached.modified == modified {
+                return Ok(cached.pairs.clone());
+            }
+        }
+
+        let text = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|error| Error::new(ErrorKind::Io, error))?;
+        let pairs = parse_sas_token(&text);
+        debug!(message = "Loaded SAS token.", path = ?self.path);
+        *self.token.lock().unwrap() = Some(CachedToken {
+            modified,
+            pairs: pairs.clone(),
+        });
+        Ok(pairs)
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpClient for SasTokenFileClient {
+    async fn execute_request(&self, request: &Request) -> azure_core::Result<Response> {
+        let token = self.token().await?;
+        let mut request = request.clone();
+        request.url_mut().query_pairs_mut().extend_pairs(token);
+        self.inner.execute_request(&request).await
+    }
+}
+
+/// Parses a SAS token, with or without its leading `?`, into its query parameters.
+fn parse_sas_token(text: &str) -> Vec<(String, String)> {
+    let text = text.trim();
+    let text = text.strip_prefix('?').unwrap_or(text);
+    url::form_urlencoded::parse(text.as_bytes())
+        .into_owned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sas_tokens() {
+        let expected = vec![
+            ("sv".to_string(), "2021-06-08".to_string()),
+            ("se".to_string(), "2023-01-01T00:00:00Z".to_string()),
+            ("sig".to_string(), "abc/def+=".to_string()),
+        ];
+        let token = "sv=2021-06-08&se=2023-01-01T00%3A00%3A00Z&sig=abc%2Fdef%2B%3D";
+        assert_eq!(parse_sas_token(token), expected);
+        assert_eq!(parse_sas_token(&format!("?{}\n", token)), expected);
+    }
+
+    #[tokio::test]
+    async fn rereads_changed_token_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sas");
+        std::fs::write(&path, "sig=first").unwrap();
+
+        let client = SasTokenFileClient::new(path.clone()).unwrap();
+        assert_eq!(
+            client.token().await.unwrap(),
+            vec![("sig".to_string(), "first".to_string())]
+        );
+
+        std::fs::write(&path, "sig=second").unwrap();
+        // The modification time may not change within the resolution of the file system.
+        client.token.lock().unwrap().as_mut().unwrap().modified = SystemTime::UNIX_EPOCH;
+        assert_eq!(
+            client.token().await.unwrap(),
+            vec![("sig".to_string(), "second".to_string())]
+        );
+    }
+}
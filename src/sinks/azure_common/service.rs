@@ -4,21 +4,27 @@ use std::{
     task::{Context, Poll},
 };
 
+use azure_core::request_options::IfMatchCondition;
 use azure_storage_blobs::prelude::*;
 use futures::future::BoxFuture;
+use http::StatusCode;
 use tower::Service;
 use tracing::Instrument;
 
-use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse};
+use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse, BlobType};
+
+/// The maximum size of a block appended to an append blob.
+const MAX_APPEND_BLOCK_SIZE: usize = 4 * 1024 * 1024;
 
 #[derive(Clone)]
 pub(crate) struct AzureBlobService {
     client: Arc<ContainerClient>,
+    blob_type: BlobType,
 }
 
 impl AzureBlobService {
-    pub fn new(client: Arc<ContainerClient>) -> AzureBlobService {
-        AzureBlobService { client }
+    pub fn new(client: Arc<ContainerClient>, blob_type: BlobType) -> AzureBlobService {
+        AzureBlobService { client, blob_type }
     }
 }
 
@@ -41,22 +47,14 @@ impl Service<AzureBlobRequest> for AzureBlobService {
                 .client
                 .blob_client(request.metadata.partition_key.as_str());
             let byte_size = request.blob_data.len();
-            let blob = client
-                .put_block_blob(request.blob_data)
-                .content_type(request.content_type);
-            let blob = match request.content_encoding {
-                Some(encoding) => blob.content_encoding(encoding),
-                None => blob,
-            };
-
-            let result = blob
-                .into_future()
-                .instrument(info_span!("request").or_current())
-                .await
-                .map_err(|err| err.into());
-
-            result.map(|inner| AzureBlobResponse {
-                inner,
+
+            let result = match this.blob_type {
+                BlobType::Block => put_block_blob(&client, &request).await,
+                BlobType::Append => append_blob(&client, &request).await,
+            }
+            .map_err(|err| err.into());
+
+            result.map(|()| AzureBlobResponse {
                 count: request.metadata.count,
                 events_byte_size: request.metadata.byte_size,
                 byte_size,
@@ -64,3 +62,76 @@ impl Service<AzureBlobRequest> for AzureBlobService {
         })
     }
 }
+
+async fn put_block_blob(client: &BlobClient, request: &AzureBlobRequest) -> azure_core::Result<()> {
+    let blob = client
+        .put_block_blob(request.blob_data.clone())
+        .content_type(request.content_type);
+    let blob = match request.content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+
+    blob.into_future()
+        .instrument(info_span!("request").or_current())
+        .await
+        .map(|_| ())
+}
+
+async fn append_blob(client: &BlobClient, request: &AzureBlobRequest) -> azure_core::Result<()> {
+    let mut created = false;
+    let mut offset = 0;
+    while offset < request.blob_data.len() {
+        let end = request.blob_data.len().min(offset + MAX_APPEND_BLOCK_SIZE);
+        let result = client
+            .append_block(request.blob_data.slice(offset..end))
+            .into_future()
+            .instrument(info_span!("request").or_current())
+            .await;
+
+        match result {
+            Ok(_) => offset = end,
+            // The blob is only created when the first block is appended to it.
+            Err(error) if !created && has_status(&error, StatusCode::NOT_FOUND) => {
+                create_append_blob(client, request).await?;
+                created = true;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_append_blob(
+    client: &BlobClient,
+    request: &AzureBlobRequest,
+) -> azure_core::Result<()> {
+    // Creating an append blob replaces any existing blob, so this must only succeed if there is
+    // none.
+    let blob = client
+        .put_append_blob()
+        .content_type(request.content_type)
+        .if_match(IfMatchCondition::NotMatch("*".into()));
+    let blob = match request.content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+
+    match blob
+        .into_future()
+        .instrument(info_span!("request").or_current())
+        .await
+    {
+        Ok(_) => Ok(()),
+        // Another writer created the blob in the meantime.
+        Err(error) if has_status(&error, StatusCode::CONFLICT) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+fn has_status(error: &azure_core::Error, status: StatusCode) -> bool {
+    error.as_http_error().map_or(false, |error| {
+        Into::<u16>::into(error.status()) == status.as_u16()
+    })
+}
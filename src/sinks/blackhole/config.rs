@@ -1,6 +1,8 @@
 use std::time::Duration;
 
 use futures::{future, FutureExt};
+use rand::Rng;
+use rand_distr::{Distribution, Exp, Normal};
 use serde_with::serde_as;
 use vector_config::configurable_component;
 
@@ -35,6 +37,18 @@ pub struct BlackholeConfig {
     #[configurable(metadata(docs::examples = 1000))]
     pub rate: Option<usize>,
 
+    #[configurable(derived)]
+    pub latency: Option<LatencyConfig>,
+
+    /// The fraction of events, between `0` and `1`, that the sink fails to deliver.
+    ///
+    /// Failed events are reported as errored to the sources that wait for acknowledgements, which
+    /// can be used to test how the pipeline handles delivery failures.
+    ///
+    /// By default, all events are delivered.
+    #[configurable(metadata(docs::examples = 0.01))]
+    pub error_rate: Option<f64>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -44,9 +58,105 @@ pub struct BlackholeConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Artificial latency added before consuming each batch of events.
+///
+/// This slows down the sink without limiting its throughput to a fixed rate, which can be used to
+/// simulate a slow endpoint.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "distribution", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The distribution of the latency."))]
+pub enum LatencyConfig {
+    /// The same latency for every batch.
+    Constant {
+        /// The latency, in milliseconds.
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        #[configurable(metadata(docs::examples = 100))]
+        ms: u64,
+    },
+
+    /// A latency picked uniformly between two bounds.
+    Uniform {
+        /// The lowest latency, in milliseconds.
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        #[configurable(metadata(docs::examples = 10))]
+        min_ms: u64,
+
+        /// The highest latency, in milliseconds.
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        #[configurable(metadata(docs::examples = 500))]
+        max_ms: u64,
+    },
+
+    /// A latency following a normal distribution.
+    ///
+    /// Negative samples are treated as no latency.
+    Normal {
+        /// The mean latency, in milliseconds.
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        #[configurable(metadata(docs::examples = 100))]
+        mean_ms: u64,
+
+        /// The standard deviation of the latency, in milliseconds.
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        #[configurable(metadata(docs::examples = 20))]
+        stddev_ms: u64,
+    },
+
+    /// A latency following an exponential distribution.
+    ///
+    /// Most batches are delayed briefly, while a few are delayed much longer, like the tail
+    /// latency of a real service.
+    Exponential {
+        /// The mean latency, in milliseconds.
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        #[configurable(metadata(docs::examples = 100))]
+        mean_ms: u64,
+    },
+}
+
+impl LatencyConfig {
+    fn validate(&self) -> crate::Result<()> {
+        match *self {
+            Self::Uniform { min_ms, max_ms } if min_ms > max_ms => {
+                Err("`latency.min_ms` must not be greater than `latency.max_ms`".into())
+            }
+            Self::Exponential { mean_ms: 0 } => {
+                Err("`latency.mean_ms` must be greater than zero".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Picks the latency of the next batch.
+    pub(super) fn sample(&self) -> Duration {
+        let mut rng = rand::thread_rng();
+        let ms = match *self {
+            Self::Constant { ms } => ms as f64,
+            Self::Uniform { min_ms, max_ms } => rng.gen_range(min_ms..=max_ms) as f64,
+            Self::Normal { mean_ms, stddev_ms } => Normal::new(mean_ms as f64, stddev_ms as f64)
+                .expect("standard deviation is finite")
+                .sample(&mut rng),
+            Self::Exponential { mean_ms } => Exp::new(1.0 / mean_ms as f64)
+                .expect("rate is positive")
+                .sample(&mut rng),
+        };
+        Duration::from_secs_f64(ms.max(0.0) / 1000.0)
+    }
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for BlackholeConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if let Some(latency) = &self.latency {
+            latency.validate()?;
+        }
+        if let Some(error_rate) = self.error_rate {
+            if !(0.0..=1.0).contains(&error_rate) {
+                return Err("`error_rate` must be between 0 and 1".into());
+            }
+        }
+
         let sink = BlackholeSink::new(self.clone());
         let healthcheck = future::ok(()).boxed();
 
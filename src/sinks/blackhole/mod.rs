@@ -9,8 +9,12 @@ mod tests {
     use std::time::Duration;
 
     use crate::{
+        event::{BatchNotifier, BatchStatus},
         sinks::{
-            blackhole::{config::BlackholeConfig, sink::BlackholeSink},
+            blackhole::{
+                config::{BlackholeConfig, LatencyConfig},
+                sink::BlackholeSink,
+            },
             VectorSink,
         },
         test_util::{
@@ -23,6 +27,8 @@ mod tests {
         let config = BlackholeConfig {
             print_interval_secs: Duration::from_secs(10),
             rate: None,
+            latency: None,
+            error_rate: None,
             acknowledgements: Default::default(),
         };
         let sink = BlackholeSink::new(config);
@@ -31,4 +37,45 @@ mod tests {
         let (_input_lines, events) = random_events_with_stream(100, 10, None);
         run_and_assert_nonsending_sink_compliance(sink, events, &[]).await;
     }
+
+    #[tokio::test]
+    async fn injects_errors() {
+        let config = BlackholeConfig {
+            print_interval_secs: Duration::from_secs(10),
+            rate: None,
+            latency: Some(LatencyConfig::Constant { ms: 1 }),
+            error_rate: Some(1.0),
+            acknowledgements: Default::default(),
+        };
+        let sink = BlackholeSink::new(config);
+        let sink = VectorSink::Stream(Box::new(sink));
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let (_input_lines, events) = random_events_with_stream(100, 10, Some(batch));
+        sink.run(events).await.unwrap();
+
+        assert_eq!(receiver.await, BatchStatus::Errored);
+    }
+
+    #[test]
+    fn samples_latency() {
+        let uniform = LatencyConfig::Uniform {
+            min_ms: 10,
+            max_ms: 20,
+        };
+        let normal = LatencyConfig::Normal {
+            mean_ms: 0,
+            stddev_ms: 100,
+        };
+        for _ in 0..100 {
+            let latency = uniform.sample();
+            assert!(latency >= Duration::from_millis(10) && latency <= Duration::from_millis(20));
+            // Negative samples are clamped.
+            let _ = normal.sample();
+        }
+        assert_eq!(
+            LatencyConfig::Constant { ms: 5 }.sample(),
+            Duration::from_millis(5)
+        );
+    }
 }
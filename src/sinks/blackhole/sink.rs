@@ -8,10 +8,11 @@ use std::{
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
+use rand::Rng;
 use tokio::{
     select,
     sync::watch,
-    time::{interval, sleep_until},
+    time::{interval, sleep, sleep_until},
 };
 use vector_common::internal_event::{
     ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
@@ -19,7 +20,8 @@ use vector_common::internal_event::{
 use vector_core::EstimatedJsonEncodedSizeOf;
 
 use crate::{
-    event::{EventArray, EventContainer},
+    event::{Event, EventArray, EventContainer, EventStatus, Finalizable},
+    internal_events::BlackholeInjectedError,
     sinks::{blackhole::config::BlackholeConfig, util::StreamSink},
 };
 
@@ -79,6 +81,10 @@ impl StreamSink<EventArray> for BlackholeSink {
         }
 
         while let Some(events) = input.next().await {
+            if let Some(latency) = self.config.latency {
+                sleep(latency.sample()).await;
+            }
+
             if let Some(rate) = self.config.rate {
                 let factor: f32 = 1.0 / rate as f32;
                 let secs: f32 = factor * (events.len() as f32);
@@ -87,14 +93,24 @@ impl StreamSink<EventArray> for BlackholeSink {
                 self.last = Some(until);
             }
 
-            let message_len = events.estimated_json_encoded_size_of();
+            let (count, message_len) = match self.config.error_rate {
+                Some(error_rate) => {
+                    let delivered = fail_events(events, error_rate);
+                    let message_len = delivered
+                        .iter()
+                        .map(EstimatedJsonEncodedSizeOf::estimated_json_encoded_size_of)
+                        .sum();
+                    (delivered.len(), message_len)
+                }
+                None => (events.len(), events.estimated_json_encoded_size_of()),
+            };
 
-            let _ = self.total_events.fetch_add(events.len(), Ordering::AcqRel);
+            let _ = self.total_events.fetch_add(count, Ordering::AcqRel);
             let _ = self
                 .total_raw_bytes
                 .fetch_add(message_len, Ordering::AcqRel);
 
-            events_sent.emit(CountByteSize(events.len(), message_len));
+            events_sent.emit(CountByteSize(count, message_len));
             bytes_sent.emit(ByteSize(message_len));
         }
 
@@ -104,3 +120,21 @@ impl StreamSink<EventArray> for BlackholeSink {
         Ok(())
     }
 }
+
+/// Marks a random `error_rate` fraction of the events as errored, returning the other ones.
+fn fail_events(events: EventArray, error_rate: f64) -> Vec<Event> {
+    let mut rng = rand::thread_rng();
+    let (failed, delivered): (Vec<_>, Vec<_>) =
+        events.into_events().partition(|_| rng.gen_bool(error_rate));
+
+    if !failed.is_empty() {
+        emit!(BlackholeInjectedError {
+            count: failed.len()
+        });
+        for mut event in failed {
+            event.take_finalizers().update_status(EventStatus::Errored);
+        }
+    }
+
+    delivered
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bytes::{BufMut, Bytes, BytesMut};
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
@@ -177,6 +179,10 @@ impl RetryLogic for ClickhouseRetryLogic {
         self.inner.is_retriable_error(error)
     }
 
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        self.inner.retry_after(response)
+    }
+
     fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
         match response.status() {
             StatusCode::INTERNAL_SERVER_ERROR => {
@@ -9,7 +9,13 @@ use vector_config::configurable_component;
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    sinks::{console::sink::WriterSink, Healthcheck, VectorSink},
+    sinks::{
+        console::{
+            pretty::{PrettyConfig, PrettyFormatter},
+            sink::WriterSink,
+        },
+        Healthcheck, VectorSink,
+    },
 };
 
 /// The [standard stream][standard_streams] to write to.
@@ -44,6 +50,10 @@ pub struct ConsoleSinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pretty: Option<PrettyConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -57,11 +67,26 @@ const fn default_target() -> Target {
     Target::Stdout
 }
 
+impl Target {
+    /// Whether the stream is attached to a terminal.
+    fn is_terminal(&self) -> bool {
+        match self {
+            #[cfg(unix)]
+            Target::Stdout => atty::is(atty::Stream::Stdout),
+            #[cfg(unix)]
+            Target::Stderr => atty::is(atty::Stream::Stderr),
+            #[cfg(windows)]
+            _ => false, // ANSI colors are not supported by cmd.exe
+        }
+    }
+}
+
 impl GenerateConfig for ConsoleSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             target: Target::Stdout,
             encoding: (None::<FramingConfig>, JsonSerializerConfig::default()).into(),
+            pretty: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -74,17 +99,23 @@ impl SinkConfig for ConsoleSinkConfig {
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::StreamBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
+        let pretty = self.pretty.as_ref().map(|pretty| {
+            let color = pretty.colors.use_color(self.target.is_terminal());
+            PrettyFormatter::new(pretty, color)
+        });
 
         let sink: VectorSink = match self.target {
             Target::Stdout => VectorSink::from_event_streamsink(WriterSink {
                 output: io::stdout(),
                 transformer,
                 encoder,
+                pretty,
             }),
             Target::Stderr => VectorSink::from_event_streamsink(WriterSink {
                 output: io::stderr(),
                 transformer,
                 encoder,
+                pretty,
             }),
         };
 
@@ -92,7 +123,11 @@ impl SinkConfig for ConsoleSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type())
+        if self.pretty.is_some() {
+            Input::all()
+        } else {
+            Input::new(self.encoding.config().1.input_type())
+        }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
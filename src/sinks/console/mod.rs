@@ -1,4 +1,5 @@
 mod config;
+mod pretty;
 mod sink;
 
 pub use config::{ConsoleSinkConfig, Target};
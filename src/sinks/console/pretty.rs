@@ -0,0 +1,316 @@
+use std::borrow::Cow;
+
+use vector_config::configurable_component;
+
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+
+/// Configuration for writing events in a human-readable layout.
+///
+/// When set, events are written with this layout instead of being serialized with
+/// `encoding.codec`. The `encoding` options that select fields still apply.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PrettyConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub layout: PrettyLayout,
+
+    /// The fields to write, in order.
+    ///
+    /// By default, all fields are written, starting with the timestamp, the severity and the
+    /// message.
+    #[configurable(metadata(docs::examples = "timestamp"))]
+    #[configurable(metadata(docs::examples = "level"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    pub fields: Option<Vec<String>>,
+
+    /// The field holding the severity of events.
+    ///
+    /// The severity is colored according to its value: red for errors, yellow for warnings, green
+    /// for informational messages, and blue for debug messages.
+    #[serde(default = "default_severity_field")]
+    #[configurable(metadata(docs::examples = "severity"))]
+    pub severity_field: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub colors: ColorMode,
+}
+
+fn default_severity_field() -> String {
+    "level".to_owned()
+}
+
+/// The layout of the written events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrettyLayout {
+    /// Each event is written on a line of `key=value` pairs.
+    #[default]
+    KeyValue,
+
+    /// Events are written as the rows of a table, with a header naming the fields.
+    ///
+    /// The columns are sized after the first event. Unless `fields` is set, the columns are the
+    /// fields of the first event.
+    Table,
+}
+
+/// When to color the output with ANSI escape codes.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMode {
+    /// Color the output when writing to a terminal.
+    #[default]
+    Auto,
+
+    /// Always color the output.
+    Always,
+
+    /// Never color the output.
+    Never,
+}
+
+impl ColorMode {
+    pub(super) fn use_color(self, terminal: bool) -> bool {
+        match self {
+            Self::Auto => terminal,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// The placeholder written in place of missing values in tables.
+const MISSING: &str = "-";
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+
+/// Formats events according to a [`PrettyConfig`].
+pub struct PrettyFormatter {
+    layout: PrettyLayout,
+    fields: Option<Vec<String>>,
+    severity_field: String,
+    color: bool,
+    /// The name and width of the table columns, once the header is written.
+    columns: Option<Vec<(String, usize)>>,
+}
+
+impl PrettyFormatter {
+    pub(super) fn new(config: &PrettyConfig, color: bool) -> Self {
+        Self {
+            layout: config.layout,
+            fields: config.fields.clone(),
+            severity_field: config.severity_field.clone(),
+            color,
+            columns: None,
+        }
+    }
+
+    /// Formats an event as one or more lines, each ending with a newline.
+    pub(super) fn format(&mut self, event: &Event) -> String {
+        let mut output = String::new();
+        match event {
+            Event::Log(log) => self.format_log(log, &mut output),
+            Event::Metric(metric) => output.push_str(&metric.to_string()),
+            Event::Trace(trace) => self.format_log(trace.as_ref(), &mut output),
+        }
+        output.push('\n');
+        output
+    }
+
+    fn format_log(&mut self, log: &LogEvent, output: &mut String) {
+        match self.layout {
+            PrettyLayout::KeyValue => {
+                let pairs = self
+                    .fields(log)
+                    .iter()
+                    .filter_map(|(key, value)| value.as_ref().map(|value| (key, value)))
+                    .map(|(key, value)| {
+                        let value = quote(value);
+                        let value = self.paint(key, &value);
+                        if self.color {
+                            format!("{}{}={}{}", DIM, key, RESET, value)
+                        } else {
+                            format!("{}={}", key, value)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                output.push_str(&pairs.join(" "));
+            }
+            PrettyLayout::Table => {
+                if self.columns.is_none() {
+                    let columns = self
+                        .fields(log)
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let width = value.map_or(MISSING.len(), |value| value.chars().count());
+                            let width = width.max(key.len());
+                            (key, width)
+                        })
+                        .collect::<Vec<_>>();
+                    let header = columns
+                        .iter()
+                        .map(|(key, width)| format!("{:width$}", key.to_uppercase()))
+                        .collect::<Vec<_>>();
+                    output.push_str(header.join("  ").trim_end());
+                    output.push('\n');
+                    self.columns = Some(columns);
+                }
+
+                let columns = self.columns.as_ref().expect("columns were just set");
+                let cells = columns
+                    .iter()
+                    .map(|(key, width)| {
+                        let value = log
+                            .get(key.as_str())
+                            .map_or(Cow::Borrowed(MISSING), Value::to_string_lossy);
+                        let padding = width.saturating_sub(value.chars().count());
+                        format!("{}{}", self.paint(key, &value), " ".repeat(padding))
+                    })
+                    .collect::<Vec<_>>();
+                output.push_str(cells.join("  ").trim_end());
+            }
+        }
+    }
+
+    /// Lists the fields to write with their values, which are missing if the event lacks them.
+    fn fields(&self, log: &LogEvent) -> Vec<(String, Option<String>)> {
+        let value = |value: &Value| value.to_string_lossy().into_owned();
+
+        if let Some(fields) = &self.fields {
+            return fields
+                .iter()
+                .map(|field| (field.clone(), log.get(field.as_str()).map(value)))
+                .collect();
+        }
+
+        let timestamp_key = log_schema().timestamp_key().map(ToString::to_string);
+        let leading = [
+            timestamp_key.as_deref(),
+            Some(self.severity_field.as_str()),
+            Some(log_schema().message_key()),
+        ];
+        let rank = |key: &str| {
+            leading
+                .iter()
+                .position(|leading| *leading == Some(key))
+                .unwrap_or(leading.len())
+        };
+
+        let mut fields = log
+            .all_fields()
+            .into_iter()
+            .flatten()
+            .map(|(key, field)| (key, Some(value(field))))
+            .collect::<Vec<_>>();
+        // The sort is stable, so the other fields stay in alphabetical order.
+        fields.sort_by_key(|(key, _)| rank(key));
+        fields
+    }
+
+    /// Colors the value of the severity field.
+    fn paint<'a>(&self, key: &str, value: &'a str) -> Cow<'a, str> {
+        if !self.color || key != self.severity_field {
+            return Cow::Borrowed(value);
+        }
+        match severity_color(value) {
+            Some(color) => Cow::Owned(format!("{}{}{}", color, value, RESET)),
+            None => Cow::Borrowed(value),
+        }
+    }
+}
+
+/// Picks the ANSI color of a severity.
+fn severity_color(severity: &str) -> Option<&'static str> {
+    let color = match severity.to_ascii_lowercase().as_str() {
+        "emerg" | "emergency" | "alert" | "crit" | "critical" | "fatal" | "panic" | "err"
+        | "error" => "\x1b[31m",
+        "warn" | "warning" => "\x1b[33m",
+        "notice" | "info" | "informational" => "\x1b[32m",
+        "debug" => "\x1b[34m",
+        "trace" => "\x1b[35m",
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Quotes values that would be ambiguous in `key=value` pairs.
+fn quote(value: &str) -> Cow<'_, str> {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '=' || c == '"') {
+        Cow::Owned(format!("{:?}", value))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatter(layout: PrettyLayout, fields: Option<Vec<&str>>, color: bool) -> PrettyFormatter {
+        let config = PrettyConfig {
+            layout,
+            fields: fields.map(|fields| fields.into_iter().map(Into::into).collect()),
+            severity_field: default_severity_field(),
+            colors: ColorMode::Auto,
+        };
+        PrettyFormatter::new(&config, color)
+    }
+
+    fn event(fields: &[(&str, &str)]) -> Event {
+        let mut log = LogEvent::default();
+        for (key, value) in fields {
+            log.insert(*key, *value);
+        }
+        log.into()
+    }
+
+    #[test]
+    fn key_value_layout() {
+        let mut formatter = formatter(PrettyLayout::KeyValue, None, false);
+        let event = event(&[("host", "a"), ("message", "hello world"), ("level", "info")]);
+
+        assert_eq!(
+            formatter.format(&event),
+            "level=info message=\"hello world\" host=a\n"
+        );
+    }
+
+    #[test]
+    fn key_value_layout_selects_fields() {
+        let mut formatter = formatter(PrettyLayout::KeyValue, Some(vec!["host", "user"]), false);
+        let event = event(&[("host", "a"), ("message", "hello")]);
+
+        assert_eq!(formatter.format(&event), "host=a\n");
+    }
+
+    #[test]
+    fn colors_severity() {
+        let mut formatter = formatter(PrettyLayout::KeyValue, Some(vec!["level"]), true);
+        let event = event(&[("level", "ERROR")]);
+
+        assert_eq!(
+            formatter.format(&event),
+            "\x1b[2mlevel\x1b[0m=\x1b[31mERROR\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn table_layout() {
+        let mut formatter = formatter(PrettyLayout::Table, None, false);
+
+        let first = event(&[("level", "info"), ("message", "started")]);
+        assert_eq!(formatter.format(&first), "LEVEL  MESSAGE\ninfo   started\n");
+
+        let second = event(&[("message", "stopped"), ("host", "a")]);
+        assert_eq!(formatter.format(&second), "-      stopped\n");
+    }
+}
@@ -11,6 +11,7 @@ use vector_core::{
     EstimatedJsonEncodedSizeOf,
 };
 
+use super::pretty::PrettyFormatter;
 use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, EventStatus, Finalizable},
@@ -21,6 +22,7 @@ pub struct WriterSink<T> {
     pub output: T,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
+    pub pretty: Option<PrettyFormatter>,
 }
 
 #[async_trait]
@@ -37,10 +39,14 @@ where
 
             let finalizers = event.take_finalizers();
             let mut bytes = BytesMut::new();
-            self.encoder.encode(event, &mut bytes).map_err(|_| {
-                // Error is handled by `Encoder`.
-                finalizers.update_status(EventStatus::Errored);
-            })?;
+            if let Some(pretty) = self.pretty.as_mut() {
+                bytes.extend_from_slice(pretty.format(&event).as_bytes());
+            } else {
+                self.encoder.encode(event, &mut bytes).map_err(|_| {
+                    // Error is handled by `Encoder`.
+                    finalizers.update_status(EventStatus::Errored);
+                })?;
+            }
 
             match self.output.write_all(&bytes).await {
                 Err(error) => {
@@ -89,6 +95,7 @@ mod test {
             output: Vec::new(),
             transformer: Default::default(),
             encoder,
+            pretty: None,
         };
 
         run_and_assert_sink_compliance(
@@ -0,0 +1,14 @@
+//! The `database` sink.
+//!
+//! Inserts batches of events as rows of a PostgreSQL or MySQL table, mapping the fields of the
+//! events to its columns. Each batch is inserted with a single multi-row statement, optionally
+//! upserting the rows on a set of key columns, over a pool of connections.
+
+mod config;
+mod pool;
+mod request_builder;
+mod schema;
+mod service;
+mod sink;
+
+pub use self::config::DatabaseSinkConfig;
@@ -0,0 +1,264 @@
+use std::{ops::Deref, sync::Mutex};
+
+use mysql_async::{prelude::Queryable, Conn as MySqlConn, Params, Pool as MySqlPool, TxOpts};
+use postgres_openssl::MakeTlsConnector;
+use serde_json::{Map, Value as JsonValue};
+use snafu::ResultExt;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio_postgres::{Client, Config, Error as PgError, NoTls};
+
+use super::{
+    schema::TableSchema,
+    service::{MySqlSnafu, PostgresSnafu, QueryError, RowsSnafu},
+};
+
+/// The maximum number of parameters of a MySQL statement.
+const MYSQL_MAX_PARAMS: usize = u16::MAX as usize;
+
+/// The connections to the database server, whatever its dialect.
+pub enum Database {
+    Postgres(ConnectionPool),
+    MySql(MySqlPool),
+}
+
+impl Database {
+    /// Takes a connection out of the pool of the server.
+    pub async fn get(&self) -> Result<Connection<'_>, QueryError> {
+        match self {
+            Self::Postgres(pool) => pool
+                .get()
+                .await
+                .map(Connection::Postgres)
+                .context(PostgresSnafu),
+            Self::MySql(pool) => pool
+                .get_conn()
+                .await
+                .map(Connection::MySql)
+                .context(MySqlSnafu),
+        }
+    }
+}
+
+/// A connection to the database server, returned to its pool when dropped.
+pub enum Connection<'a> {
+    Postgres(PooledClient<'a>),
+    MySql(MySqlConn),
+}
+
+impl<'a> Connection<'a> {
+    /// Executes a statement without parameters, such as the creation of the table.
+    pub async fn execute(&mut self, statement: &str) -> Result<(), QueryError> {
+        match self {
+            Self::Postgres(client) => client.batch_execute(statement).await.context(PostgresSnafu),
+            Self::MySql(conn) => conn.query_drop(statement).await.context(MySqlSnafu),
+        }
+    }
+
+    /// Queries the columns of the table filled from the events when no mapping is configured.
+    pub async fn query_columns(&mut self, schema: &TableSchema) -> Result<Vec<String>, QueryError> {
+        let query = schema.columns_query();
+        match self {
+            Self::Postgres(client) => {
+                let rows = client
+                    .query(query, &[&schema.schema_name(), &schema.table_name()])
+                    .await
+                    .context(PostgresSnafu)?;
+                rows.iter()
+                    .map(|row| row.try_get(0))
+                    .collect::<Result<_, _>>()
+                    .context(PostgresSnafu)
+            }
+            Self::MySql(conn) => conn
+                .exec(query, (schema.schema_name(), schema.table_name()))
+                .await
+                .context(MySqlSnafu),
+        }
+    }
+
+    /// Inserts the rows, passed as a JSON array of objects keyed by column, into the `columns` of
+    /// the table.
+    pub async fn insert(
+        &mut self,
+        schema: &TableSchema,
+        columns: &[String],
+        rows: &str,
+    ) -> Result<(), QueryError> {
+        match self {
+            Self::Postgres(client) => client
+                .execute(schema.insert_statement(columns).as_str(), &[&rows])
+                .await
+                .map(|_| ())
+                .context(PostgresSnafu),
+            Self::MySql(conn) => {
+                let rows =
+                    serde_json::from_str::<Vec<Map<String, JsonValue>>>(rows).context(RowsSnafu)?;
+                // The rows are split between as many statements as their parameters require,
+                // inserted all at once.
+                let chunk_size = (MYSQL_MAX_PARAMS / columns.len()).max(1);
+                let mut transaction = conn
+                    .start_transaction(TxOpts::default())
+                    .await
+                    .context(MySqlSnafu)?;
+                for chunk in rows.chunks(chunk_size) {
+                    let params = chunk
+                        .iter()
+                        .flat_map(|row| columns.iter().map(|column| mysql_value(row.get(column))))
+                        .collect();
+                    transaction
+                        .exec_drop(
+                            schema.insert_values_statement(columns, chunk.len()),
+                            Params::Positional(params),
+                        )
+                        .await
+                        .context(MySqlSnafu)?;
+                }
+                transaction.commit().await.context(MySqlSnafu)
+            }
+        }
+    }
+}
+
+/// Converts the value of a column to a MySQL parameter, which the server converts to the type of
+/// the column.
+fn mysql_value(value: Option<&JsonValue>) -> mysql_async::Value {
+    match value {
+        None | Some(JsonValue::Null) => mysql_async::Value::NULL,
+        Some(JsonValue::Bool(value)) => mysql_async::Value::Int(i64::from(*value)),
+        Some(JsonValue::Number(number)) => match (number.as_i64(), number.as_u64()) {
+            (Some(value), _) => mysql_async::Value::Int(value),
+            (None, Some(value)) => mysql_async::Value::UInt(value),
+            (None, None) => mysql_async::Value::Double(number.as_f64().unwrap_or_default()),
+        },
+        Some(JsonValue::String(value)) => mysql_async::Value::Bytes(value.clone().into_bytes()),
+        Some(value) => mysql_async::Value::Bytes(value.to_string().into_bytes()),
+    }
+}
+
+/// A pool of connections to a PostgreSQL server.
+///
+/// Connections are opened lazily, up to the size of the pool, and the ones found closed when taken
+/// out of the pool are replaced by new ones.
+pub struct ConnectionPool {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+    idle: Mutex<Vec<Client>>,
+    permits: Semaphore,
+}
+
+impl ConnectionPool {
+    pub fn new(config: Config, tls: Option<MakeTlsConnector>, size: usize) -> Self {
+        Self {
+            config,
+            tls,
+            idle: Mutex::new(Vec::with_capacity(size)),
+            permits: Semaphore::new(size),
+        }
+    }
+
+    /// Takes a connection out of the pool, waiting for one to be returned if they are all in use.
+    pub async fn get(&self) -> Result<PooledClient<'_>, PgError> {
+        let permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("the semaphore of the pool is never closed");
+
+        let idle = {
+            let mut idle = self.idle.lock().expect("poisoned lock");
+            idle.retain(|client| !client.is_closed());
+            idle.pop()
+        };
+        let client = match idle {
+            Some(client) => client,
+            None => self.connect().await?,
+        };
+
+        Ok(PooledClient {
+            client: Some(client),
+            pool: self,
+            _permit: permit,
+        })
+    }
+
+    async fn connect(&self) -> Result<Client, PgError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(async move {
+                    if let Err(error) = connection.await {
+                        debug!(message = "Database connection closed.", %error);
+                    }
+                });
+                client
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(async move {
+                    if let Err(error) = connection.await {
+                        debug!(message = "Database connection closed.", %error);
+                    }
+                });
+                client
+            }
+        };
+        Ok(client)
+    }
+}
+
+/// A connection taken out of a [`ConnectionPool`], returned to it when dropped.
+pub struct PooledClient<'a> {
+    client: Option<Client>,
+    pool: &'a ConnectionPool,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl<'a> Deref for PooledClient<'a> {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client
+            .as_ref()
+            .expect("the client is only taken on drop")
+    }
+}
+
+impl<'a> Drop for PooledClient<'a> {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if !client.is_closed() {
+                self.pool.idle.lock().expect("poisoned lock").push(client);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn converts_mysql_values() {
+        assert_eq!(mysql_value(None), mysql_async::Value::NULL);
+        assert_eq!(mysql_value(Some(&json!(null))), mysql_async::Value::NULL);
+        assert_eq!(mysql_value(Some(&json!(true))), mysql_async::Value::Int(1));
+        assert_eq!(mysql_value(Some(&json!(-3))), mysql_async::Value::Int(-3));
+        assert_eq!(
+            mysql_value(Some(&json!(u64::MAX))),
+            mysql_async::Value::UInt(u64::MAX)
+        );
+        assert_eq!(
+            mysql_value(Some(&json!(1.5))),
+            mysql_async::Value::Double(1.5)
+        );
+        assert_eq!(
+            mysql_value(Some(&json!("text"))),
+            mysql_async::Value::Bytes(b"text".to_vec())
+        );
+        assert_eq!(
+            mysql_value(Some(&json!({ "team": "core" }))),
+            mysql_async::Value::Bytes(br#"{"team":"core"}"#.to_vec())
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use serde_json::{Map, Value as JsonValue};
+use vector_common::finalization::{EventFinalizers, Finalizable};
+use vector_core::event::{Event, Value};
+
+use super::{
+    schema::{ColumnType, Dialect},
+    service::DatabaseRequest,
+};
+use crate::sinks::util::{metadata::RequestMetadataBuilder, request_builder::EncodeResult};
+
+/// Renders batches of events into rows.
+#[derive(Clone)]
+pub struct DatabaseRequestBuilder {
+    dialect: Dialect,
+    /// The columns of the table and the fields filling them, or `None` to fill the columns with
+    /// the fields of the same name.
+    mapping: Option<Vec<(String, String)>>,
+    upsert_keys: Vec<String>,
+    infer_types: bool,
+}
+
+impl DatabaseRequestBuilder {
+    pub const fn new(
+        dialect: Dialect,
+        mapping: Option<Vec<(String, String)>>,
+        upsert_keys: Vec<String>,
+        infer_types: bool,
+    ) -> Self {
+        Self {
+            dialect,
+            mapping,
+            upsert_keys,
+            infer_types,
+        }
+    }
+
+    pub const fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    fn row(&self, event: &Event) -> Map<String, JsonValue> {
+        let log = event.as_log();
+        match &self.mapping {
+            Some(mapping) => mapping
+                .iter()
+                .map(|(column, field)| {
+                    let value = log
+                        .get(field.as_str())
+                        .map_or(JsonValue::Null, |value| self.column_value(value));
+                    (column.clone(), value)
+                })
+                .collect(),
+            // The fields not matching a column are ignored.
+            None => log
+                .as_map()
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .map(|(name, value)| (name.clone(), self.column_value(value)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Renders the value of a column, in a format the server converts to the type of the column.
+    fn column_value(&self, value: &Value) -> JsonValue {
+        match (self.dialect, value) {
+            // MySQL doesn't parse the time zone of RFC 3339 timestamps, so they are rendered in
+            // UTC, the time zone of the connections.
+            (Dialect::MySql, Value::Timestamp(timestamp)) => {
+                JsonValue::String(timestamp.format("%Y-%m-%d %H:%M:%S%.6f").to_string())
+            }
+            _ => serde_json::to_value(value).unwrap_or(JsonValue::Null),
+        }
+    }
+
+    /// Infers the types of the mapped columns from the first event holding a value for each.
+    fn column_types(&self, events: &[Event]) -> Vec<ColumnType> {
+        let Some(mapping) = &self.mapping else {
+            return Vec::new();
+        };
+        mapping
+            .iter()
+            .map(|(_, field)| {
+                events
+                    .iter()
+                    .filter_map(|event| event.as_log().get(field.as_str()))
+                    .find(|value| !value.is_null())
+                    .map_or(ColumnType::Text, ColumnType::of)
+            })
+            .collect()
+    }
+
+    /// Keeps the last row of each upsert key, as a statement cannot update the same row twice.
+    fn deduplicate(&self, rows: Vec<Map<String, JsonValue>>) -> Vec<Map<String, JsonValue>> {
+        if self.upsert_keys.is_empty() {
+            return rows;
+        }
+
+        let mut positions = HashMap::with_capacity(rows.len());
+        let mut deduplicated = Vec::with_capacity(rows.len());
+        for row in rows {
+            let key = self
+                .upsert_keys
+                .iter()
+                .map(|key| row.get(key).cloned().unwrap_or(JsonValue::Null))
+                .collect::<Vec<_>>();
+            match positions.entry(JsonValue::Array(key).to_string()) {
+                Entry::Occupied(position) => deduplicated[*position.get()] = row,
+                Entry::Vacant(position) => {
+                    position.insert(deduplicated.len());
+                    deduplicated.push(row);
+                }
+            }
+        }
+        deduplicated
+    }
+
+    pub fn build_request(&self, mut events: Vec<Event>) -> DatabaseRequest {
+        let rows = events.iter().map(|event| self.row(event)).collect();
+        let rows = JsonValue::from(self.deduplicate(rows)).to_string();
+        let column_types = if self.infer_types {
+            self.column_types(&events)
+        } else {
+            Vec::new()
+        };
+
+        let finalizers: EventFinalizers = events.take_finalizers();
+        let metadata = RequestMetadataBuilder::from_events(&events)
+            .build(&EncodeResult::uncompressed(rows.as_bytes()));
+
+        DatabaseRequest {
+            rows,
+            column_types,
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn event(id: i64, message: &str) -> Event {
+        let mut log = LogEvent::default();
+        log.insert("id", id);
+        log.insert("message", message);
+        log.insert("labels.team", "core");
+        log.into()
+    }
+
+    #[test]
+    fn maps_fields_to_columns() {
+        let builder = DatabaseRequestBuilder::new(
+            Dialect::Postgres,
+            Some(vec![
+                ("id".to_string(), "id".to_string()),
+                ("body".to_string(), "message".to_string()),
+                ("team".to_string(), "labels.team".to_string()),
+                ("missing".to_string(), "missing".to_string()),
+            ]),
+            vec![],
+            true,
+        );
+        let request = builder.build_request(vec![event(1, "first"), event(2, "second")]);
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(&request.rows).unwrap(),
+            serde_json::json!([
+                { "id": 1, "body": "first", "team": "core", "missing": null },
+                { "id": 2, "body": "second", "team": "core", "missing": null },
+            ])
+        );
+        assert_eq!(
+            request.column_types,
+            [
+                ColumnType::BigInt,
+                ColumnType::Text,
+                ColumnType::Text,
+                ColumnType::Text
+            ]
+        );
+    }
+
+    #[test]
+    fn passes_events_as_rows_without_mapping() {
+        let builder = DatabaseRequestBuilder::new(Dialect::Postgres, None, vec![], false);
+        let request = builder.build_request(vec![event(1, "first")]);
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(&request.rows).unwrap(),
+            serde_json::json!([{ "id": 1, "message": "first", "labels": { "team": "core" } }])
+        );
+        assert!(request.column_types.is_empty());
+    }
+
+    #[test]
+    fn keeps_last_row_of_upsert_keys() {
+        let builder =
+            DatabaseRequestBuilder::new(Dialect::Postgres, None, vec!["id".to_string()], false);
+        let request = builder.build_request(vec![
+            event(1, "first"),
+            event(2, "second"),
+            event(1, "third"),
+        ]);
+        let rows = serde_json::from_str::<JsonValue>(&request.rows).unwrap();
+        assert_eq!(rows[0]["message"], "third");
+        assert_eq!(rows[1]["message"], "second");
+        assert_eq!(rows.as_array().unwrap().len(), 2);
+        assert_eq!(request.metadata.event_count(), 3);
+    }
+
+    #[test]
+    fn renders_mysql_timestamps_in_utc() {
+        let timestamp = chrono::DateTime::parse_from_rfc3339("2023-04-05T06:07:08.123456+02:00")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let mut log = LogEvent::default();
+        log.insert("timestamp", timestamp);
+
+        let builder = DatabaseRequestBuilder::new(Dialect::MySql, None, vec![], false);
+        let request = builder.build_request(vec![log.into()]);
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(&request.rows).unwrap(),
+            serde_json::json!([{ "timestamp": "2023-04-05 04:07:08.123456" }])
+        );
+    }
+}
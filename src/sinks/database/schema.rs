@@ -0,0 +1,351 @@
+use vector_core::event::Value;
+
+/// The SQL dialect of the database server.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+}
+
+impl Dialect {
+    /// The dialect of the server of `endpoint`, from the scheme of its URI.
+    ///
+    /// Endpoints without a scheme are PostgreSQL connection strings of `key=value` pairs.
+    pub fn from_endpoint(endpoint: &str) -> Option<Self> {
+        match endpoint.split_once("://") {
+            None | Some(("postgres" | "postgresql", _)) => Some(Self::Postgres),
+            Some(("mysql", _)) => Some(Self::MySql),
+            Some(_) => None,
+        }
+    }
+
+    /// The name of the protocol, for the telemetry of the sink.
+    pub const fn protocol(self) -> &'static str {
+        match self {
+            Self::Postgres => "postgres",
+            Self::MySql => "mysql",
+        }
+    }
+
+    /// Quotes an identifier, so that it is used as is whatever its case or characters.
+    fn quote_identifier(self, name: &str) -> String {
+        match self {
+            Self::Postgres => format!("\"{}\"", name.replace('"', "\"\"")),
+            Self::MySql => format!("`{}`", name.replace('`', "``")),
+        }
+    }
+
+    fn quote_identifiers(self, names: &[String]) -> String {
+        names
+            .iter()
+            .map(|name| self.quote_identifier(name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The name of a column type, for a column of the primary key if `key` is set.
+    const fn type_name(self, column_type: ColumnType, key: bool) -> &'static str {
+        match (self, column_type) {
+            (_, ColumnType::BigInt) => "BIGINT",
+            (_, ColumnType::Boolean) => "BOOLEAN",
+            (Self::Postgres, ColumnType::Double) => "DOUBLE PRECISION",
+            (Self::Postgres, ColumnType::Timestamp) => "TIMESTAMPTZ",
+            (Self::Postgres, ColumnType::Json) => "JSONB",
+            (Self::Postgres, ColumnType::Text) => "TEXT",
+            (Self::MySql, ColumnType::Double) => "DOUBLE",
+            (Self::MySql, ColumnType::Timestamp) => "DATETIME(6)",
+            // MySQL can't index the whole of a text or JSON column.
+            (Self::MySql, ColumnType::Json | ColumnType::Text) if key => "VARCHAR(255)",
+            (Self::MySql, ColumnType::Json) => "JSON",
+            (Self::MySql, ColumnType::Text) => "TEXT",
+        }
+    }
+}
+
+/// The type of the column created for a value, with `auto_create`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColumnType {
+    BigInt,
+    Double,
+    Boolean,
+    Timestamp,
+    Json,
+    Text,
+}
+
+impl ColumnType {
+    pub const fn of(value: &Value) -> Self {
+        match value {
+            Value::Integer(_) => Self::BigInt,
+            Value::Float(_) => Self::Double,
+            Value::Boolean(_) => Self::Boolean,
+            Value::Timestamp(_) => Self::Timestamp,
+            Value::Object(_) | Value::Array(_) => Self::Json,
+            Value::Bytes(_) | Value::Regex(_) | Value::Null => Self::Text,
+        }
+    }
+}
+
+/// The table the events are inserted into, and the statements inserting them.
+pub struct TableSchema {
+    dialect: Dialect,
+    schema_name: Option<String>,
+    table_name: String,
+    upsert_keys: Vec<String>,
+}
+
+impl TableSchema {
+    /// Creates the schema of `table`, optionally qualified with the name of its schema, or of its
+    /// database with MySQL.
+    pub fn new(dialect: Dialect, table: &str, upsert_keys: Vec<String>) -> Self {
+        let (schema_name, table_name) = match table.split_once('.') {
+            Some((schema_name, table_name)) => (Some(schema_name.to_string()), table_name),
+            None => (None, table),
+        };
+        Self {
+            dialect,
+            schema_name,
+            table_name: table_name.to_string(),
+            upsert_keys,
+        }
+    }
+
+    pub fn schema_name(&self) -> Option<&str> {
+        self.schema_name.as_deref()
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    fn qualified_name(&self) -> String {
+        match &self.schema_name {
+            Some(schema_name) => format!(
+                "{}.{}",
+                self.dialect.quote_identifier(schema_name),
+                self.dialect.quote_identifier(&self.table_name)
+            ),
+            None => self.dialect.quote_identifier(&self.table_name),
+        }
+    }
+
+    /// The statement inserting the rows passed as a JSON array of objects, keyed by column, into a
+    /// PostgreSQL table.
+    ///
+    /// The rows are converted to the types of the columns by the server itself, and upserted on
+    /// the `upsert_keys` if there are any.
+    pub fn insert_statement(&self, columns: &[String]) -> String {
+        let table = self.qualified_name();
+        let column_list = self.dialect.quote_identifiers(columns);
+        let mut statement = format!(
+            "INSERT INTO {table} ({column_list}) SELECT {column_list} \
+             FROM json_populate_recordset(NULL::{table}, $1::text::json)"
+        );
+
+        if !self.upsert_keys.is_empty() {
+            let updates = self
+                .updated_columns(columns)
+                .map(|column| format!("{column} = EXCLUDED.{column}"))
+                .collect::<Vec<_>>();
+            statement.push_str(&format!(
+                " ON CONFLICT ({}) DO ",
+                self.dialect.quote_identifiers(&self.upsert_keys)
+            ));
+            if updates.is_empty() {
+                statement.push_str("NOTHING");
+            } else {
+                statement.push_str(&format!("UPDATE SET {}", updates.join(", ")));
+            }
+        }
+
+        statement
+    }
+
+    /// The statement inserting `rows` rows into a MySQL table, whose values are passed as
+    /// positional parameters, row by row in the order of the `columns`.
+    ///
+    /// The rows are upserted on the unique keys of the table if there are `upsert_keys`.
+    pub fn insert_values_statement(&self, columns: &[String], rows: usize) -> String {
+        let placeholders = format!("({})", vec!["?"; columns.len()].join(", "));
+        let mut statement = format!(
+            "INSERT INTO {} ({}) VALUES {}",
+            self.qualified_name(),
+            self.dialect.quote_identifiers(columns),
+            vec![placeholders.as_str(); rows].join(", ")
+        );
+
+        if !self.upsert_keys.is_empty() {
+            let mut updates = self
+                .updated_columns(columns)
+                .map(|column| format!("{column} = VALUES({column})"))
+                .collect::<Vec<_>>();
+            if updates.is_empty() {
+                // Updating a key to itself leaves the existing row as is.
+                let key = self.dialect.quote_identifier(&self.upsert_keys[0]);
+                updates.push(format!("{key} = {key}"));
+            }
+            statement.push_str(&format!(" ON DUPLICATE KEY UPDATE {}", updates.join(", ")));
+        }
+
+        statement
+    }
+
+    /// The quoted columns which are not upsert keys, updated on conflicts.
+    fn updated_columns<'a>(&'a self, columns: &'a [String]) -> impl Iterator<Item = String> + 'a {
+        columns
+            .iter()
+            .filter(|column| !self.upsert_keys.contains(column))
+            .map(|column| self.dialect.quote_identifier(column))
+    }
+
+    /// The statement creating the table if it does not exist, with `auto_create`.
+    pub fn create_statement(&self, columns: &[String], types: &[ColumnType]) -> String {
+        let mut definitions = columns
+            .iter()
+            .zip(types)
+            .map(|(column, column_type)| {
+                let key = self.upsert_keys.contains(column);
+                format!(
+                    "{} {}",
+                    self.dialect.quote_identifier(column),
+                    self.dialect.type_name(*column_type, key)
+                )
+            })
+            .collect::<Vec<_>>();
+        if !self.upsert_keys.is_empty() {
+            definitions.push(format!(
+                "PRIMARY KEY ({})",
+                self.dialect.quote_identifiers(&self.upsert_keys)
+            ));
+        }
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} ({})",
+            self.qualified_name(),
+            definitions.join(", ")
+        )
+    }
+
+    /// The query of the columns of the table filled from the events when no mapping is
+    /// configured, taking the name of the schema, if any, and of the table as parameters.
+    ///
+    /// Columns with a default value, such as identity columns, are left to their default.
+    pub const fn columns_query(&self) -> &'static str {
+        match self.dialect {
+            Dialect::Postgres => {
+                "SELECT column_name::text FROM information_schema.columns \
+                 WHERE table_schema::text = COALESCE($1::text, current_schema()::text) \
+                 AND table_name::text = $2::text \
+                 AND column_default IS NULL AND is_identity = 'NO' AND is_generated = 'NEVER' \
+                 ORDER BY ordinal_position"
+            }
+            Dialect::MySql => {
+                "SELECT column_name FROM information_schema.columns \
+                 WHERE table_schema = COALESCE(?, DATABASE()) AND table_name = ? \
+                 AND column_default IS NULL AND extra NOT LIKE '%auto_increment%' \
+                 AND extra NOT LIKE '%GENERATED%' \
+                 ORDER BY ordinal_position"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<String> {
+        vec!["id".to_string(), "message".to_string(), "level".to_string()]
+    }
+
+    #[test]
+    fn detects_dialect() {
+        assert_eq!(
+            Dialect::from_endpoint("postgresql://localhost/db"),
+            Some(Dialect::Postgres)
+        );
+        assert_eq!(
+            Dialect::from_endpoint("host=localhost dbname=db"),
+            Some(Dialect::Postgres)
+        );
+        assert_eq!(
+            Dialect::from_endpoint("mysql://localhost/db"),
+            Some(Dialect::MySql)
+        );
+        assert_eq!(Dialect::from_endpoint("sqlite://db.sqlite"), None);
+    }
+
+    #[test]
+    fn builds_insert_statement() {
+        let schema = TableSchema::new(Dialect::Postgres, "logs", vec![]);
+        assert_eq!(
+            schema.insert_statement(&columns()),
+            "INSERT INTO \"logs\" (\"id\", \"message\", \"level\") \
+             SELECT \"id\", \"message\", \"level\" \
+             FROM json_populate_recordset(NULL::\"logs\", $1::text::json)"
+        );
+    }
+
+    #[test]
+    fn builds_upsert_statement() {
+        let schema = TableSchema::new(Dialect::Postgres, "app.Logs", vec!["id".to_string()]);
+        assert_eq!(
+            schema.insert_statement(&columns()),
+            "INSERT INTO \"app\".\"Logs\" (\"id\", \"message\", \"level\") \
+             SELECT \"id\", \"message\", \"level\" \
+             FROM json_populate_recordset(NULL::\"app\".\"Logs\", $1::text::json) \
+             ON CONFLICT (\"id\") DO UPDATE SET \"message\" = EXCLUDED.\"message\", \
+             \"level\" = EXCLUDED.\"level\""
+        );
+
+        let schema = TableSchema::new(Dialect::Postgres, "logs", columns());
+        assert!(schema
+            .insert_statement(&columns())
+            .ends_with("ON CONFLICT (\"id\", \"message\", \"level\") DO NOTHING"));
+    }
+
+    #[test]
+    fn builds_mysql_insert_statements() {
+        let schema = TableSchema::new(Dialect::MySql, "app.logs", vec![]);
+        assert_eq!(
+            schema.insert_values_statement(&columns(), 2),
+            "INSERT INTO `app`.`logs` (`id`, `message`, `level`) \
+             VALUES (?, ?, ?), (?, ?, ?)"
+        );
+
+        let schema = TableSchema::new(Dialect::MySql, "logs", vec!["id".to_string()]);
+        assert_eq!(
+            schema.insert_values_statement(&columns(), 1),
+            "INSERT INTO `logs` (`id`, `message`, `level`) VALUES (?, ?, ?) \
+             ON DUPLICATE KEY UPDATE `message` = VALUES(`message`), `level` = VALUES(`level`)"
+        );
+
+        let schema = TableSchema::new(Dialect::MySql, "logs", columns());
+        assert!(schema
+            .insert_values_statement(&columns(), 1)
+            .ends_with("ON DUPLICATE KEY UPDATE `id` = `id`"));
+    }
+
+    #[test]
+    fn builds_create_statement() {
+        let types = [ColumnType::BigInt, ColumnType::Text, ColumnType::Text];
+        let schema = TableSchema::new(Dialect::Postgres, "logs", vec!["id".to_string()]);
+        assert_eq!(
+            schema.create_statement(&columns(), &types),
+            "CREATE TABLE IF NOT EXISTS \"logs\" \
+             (\"id\" BIGINT, \"message\" TEXT, \"level\" TEXT, PRIMARY KEY (\"id\"))"
+        );
+        assert_eq!(schema.qualified_name(), "\"logs\"");
+        assert_eq!(
+            Dialect::Postgres.quote_identifier("we\"ird"),
+            "\"we\"\"ird\""
+        );
+
+        let schema = TableSchema::new(Dialect::MySql, "logs", vec!["message".to_string()]);
+        assert_eq!(
+            schema.create_statement(&columns(), &types),
+            "CREATE TABLE IF NOT EXISTS `logs` \
+             (`id` BIGINT, `message` VARCHAR(255), `level` TEXT, PRIMARY KEY (`message`))"
+        );
+        assert_eq!(Dialect::MySql.quote_identifier("we`ird"), "`we``ird`");
+    }
+}
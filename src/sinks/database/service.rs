@@ -0,0 +1,254 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use mysql_async::Error as MySqlError;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::OnceCell;
+use tokio_postgres::Error as PgError;
+use tower::Service;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_core::stream::DriverResponse;
+
+use super::{
+    pool::Database,
+    schema::{ColumnType, TableSchema},
+};
+use crate::sinks::util::retries::RetryLogic;
+
+#[derive(Debug, Snafu)]
+pub enum DatabaseError {
+    #[snafu(display("Failed to connect to the database: {}", source))]
+    Connect { source: QueryError },
+    #[snafu(display("Failed to query the columns of the table: {}", source))]
+    Columns { source: QueryError },
+    #[snafu(display("The table does not exist or has no columns to insert the events into"))]
+    NoColumns,
+    #[snafu(display("Failed to create the table: {}", source))]
+    CreateTable { source: QueryError },
+    #[snafu(display("Failed to insert the events: {}", source))]
+    Insert { source: QueryError },
+}
+
+/// An error returned by the database server, or its client.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub enum QueryError {
+    #[snafu(display("{}", source))]
+    Postgres { source: PgError },
+    #[snafu(display("{}", source))]
+    MySql { source: MySqlError },
+    #[snafu(display("Invalid rows: {}", source))]
+    Rows { source: serde_json::Error },
+}
+
+impl QueryError {
+    /// Whether the error is transient, such as a lost connection, a deadlock or an overloaded
+    /// server, rather than caused by the statement or the rows themselves.
+    fn is_transient(&self) -> bool {
+        match self {
+            // Errors without an SQLSTATE are raised by the connection itself.
+            Self::Postgres { source } => source
+                .code()
+                .map_or(true, |code| is_transient_state(code.code())),
+            Self::MySql { source } => match source {
+                MySqlError::Io(_) => true,
+                MySqlError::Driver(error) => matches!(
+                    error,
+                    mysql_async::DriverError::ConnectionClosed
+                        | mysql_async::DriverError::PoolDisconnected
+                ),
+                // The lock wait timeouts and too many connections errors have a generic SQLSTATE.
+                MySqlError::Server(error) => {
+                    is_transient_state(&error.state) || matches!(error.code, 1040 | 1205)
+                }
+                MySqlError::Other(_) | MySqlError::Url(_) => false,
+            },
+            Self::Rows { .. } => false,
+        }
+    }
+}
+
+/// Whether an SQLSTATE is of the connection exception, transaction rollback, insufficient resources
+/// or operator intervention classes.
+fn is_transient_state(state: &str) -> bool {
+    matches!(state.get(..2), Some("08" | "40" | "53" | "57"))
+}
+
+#[derive(Clone)]
+pub struct DatabaseRetryLogic;
+
+impl RetryLogic for DatabaseRetryLogic {
+    type Error = DatabaseError;
+    type Response = DatabaseResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            DatabaseError::Connect { .. } => true,
+            DatabaseError::NoColumns => false,
+            DatabaseError::Columns { source }
+            | DatabaseError::CreateTable { source }
+            | DatabaseError::Insert { source } => source.is_transient(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DatabaseRequest {
+    /// The rows, as a JSON array of objects keyed by column.
+    pub rows: String,
+    /// The types of the mapped columns, to create the table with `auto_create`.
+    pub column_types: Vec<ColumnType>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for DatabaseRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for DatabaseRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub struct DatabaseResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for DatabaseResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct DatabaseService {
+    database: Arc<Database>,
+    schema: Arc<TableSchema>,
+    /// The columns of the table, mapped explicitly or discovered from the first request.
+    columns: Arc<OnceCell<Vec<String>>>,
+    /// Set once the table is created, with `auto_create`.
+    created: Option<Arc<OnceCell<()>>>,
+}
+
+impl DatabaseService {
+    pub(super) fn new(
+        database: Arc<Database>,
+        schema: TableSchema,
+        columns: Option<Vec<String>>,
+        auto_create: bool,
+    ) -> Self {
+        Self {
+            database,
+            schema: Arc::new(schema),
+            columns: Arc::new(OnceCell::new_with(columns)),
+            created: auto_create.then(|| Arc::new(OnceCell::new())),
+        }
+    }
+}
+
+impl Service<DatabaseRequest> for DatabaseService {
+    type Response = DatabaseResponse;
+    type Error = DatabaseError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: DatabaseRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let metadata = request.get_metadata();
+            let mut connection = service.database.get().await.context(ConnectSnafu)?;
+
+            if let Some(created) = &service.created {
+                // With `auto_create`, the columns are always mapped explicitly.
+                let columns = service.columns.get().map_or(&[][..], Vec::as_slice);
+                let statement = service
+                    .schema
+                    .create_statement(columns, &request.column_types);
+                let connection = &mut connection;
+                created
+                    .get_or_try_init(|| async move { connection.execute(&statement).await })
+                    .await
+                    .context(CreateTableSnafu)?;
+            }
+
+            let columns = {
+                let connection = &mut connection;
+                let schema = &service.schema;
+                service
+                    .columns
+                    .get_or_try_init(|| async move { connection.query_columns(schema).await })
+                    .await
+                    .context(ColumnsSnafu)?
+            };
+            if columns.is_empty() {
+                return Err(DatabaseError::NoColumns);
+            }
+
+            connection
+                .insert(&service.schema, columns, &request.rows)
+                .await
+                .context(InsertSnafu)?;
+
+            Ok(DatabaseResponse { metadata })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_transient_errors() {
+        let logic = DatabaseRetryLogic;
+        assert!(!logic.is_retriable_error(&DatabaseError::NoColumns));
+        assert!(logic.is_retriable_error(&DatabaseError::Insert {
+            source: QueryError::MySql {
+                source: MySqlError::Server(mysql_async::ServerError {
+                    code: 1213,
+                    message: "Deadlock found when trying to get lock".to_string(),
+                    state: "40001".to_string(),
+                }),
+            },
+        }));
+        assert!(!logic.is_retriable_error(&DatabaseError::Insert {
+            source: QueryError::MySql {
+                source: MySqlError::Server(mysql_async::ServerError {
+                    code: 1062,
+                    message: "Duplicate entry".to_string(),
+                    state: "23000".to_string(),
+                }),
+            },
+        }));
+        assert!(!is_transient_state("23505"));
+        assert!(!is_transient_state("42P01"));
+        assert!(is_transient_state("40001"));
+        assert!(is_transient_state("08006"));
+    }
+}
@@ -0,0 +1,47 @@
+use futures_util::{stream::BoxStream, StreamExt};
+use vector_core::{event::Event, sink::StreamSink, stream::BatcherSettings};
+
+use super::{
+    request_builder::DatabaseRequestBuilder,
+    service::{DatabaseRetryLogic, DatabaseService},
+};
+use crate::sinks::util::{service::Svc, SinkBuilderExt};
+
+pub struct DatabaseSink {
+    batch_settings: BatcherSettings,
+    request_builder: DatabaseRequestBuilder,
+    service: Svc<DatabaseService, DatabaseRetryLogic>,
+}
+
+impl DatabaseSink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        request_builder: DatabaseRequestBuilder,
+        service: Svc<DatabaseService, DatabaseRetryLogic>,
+    ) -> Self {
+        Self {
+            batch_settings,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request_builder = self.request_builder;
+        let protocol = request_builder.dialect().protocol();
+        input
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(|events| request_builder.build_request(events))
+            .into_driver(self.service)
+            .protocol(protocol)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for DatabaseSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
             get_api_base_endpoint, DatadogCommonConfig,
         },
         util::{http::HttpStatusRetryLogic, ServiceBuilderExt, TowerRequestConfig},
-        Healthcheck, VectorSink,
+        Healthcheck, HealthcheckFactory, VectorSink,
     },
     tls::MaybeTlsSettings,
 };
@@ -100,6 +100,16 @@ impl SinkConfig for DatadogEventsConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn build_healthcheck_factory(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<HealthcheckFactory>> {
+        let client = self.build_client(cx.proxy())?;
+        self.dd_common
+            .build_healthcheck_factory(client, self.region.as_ref())
+            .map(Some)
+    }
+
     fn input(&self) -> Input {
         let requirement = schema::Requirement::empty()
             .required_meaning("message", Kind::bytes())
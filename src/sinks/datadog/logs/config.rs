@@ -19,7 +19,7 @@ use crate::{
             http::RequestConfig, service::ServiceBuilderExt, BatchConfig, Compression,
             SinkBatchSettings,
         },
-        Healthcheck, VectorSink,
+        Healthcheck, HealthcheckFactory, VectorSink,
     },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
@@ -173,6 +173,16 @@ impl SinkConfig for DatadogLogsConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn build_healthcheck_factory(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<HealthcheckFactory>> {
+        let client = self.create_client(&cx.proxy)?;
+        self.dd_common
+            .build_healthcheck_factory(client, self.region.as_ref())
+            .map(Some)
+    }
+
     fn input(&self) -> Input {
         let requirement = schema::Requirement::empty()
             .required_meaning("message", Kind::bytes())
@@ -16,7 +16,7 @@ use crate::{
     sinks::{
         datadog::DatadogCommonConfig,
         util::{batch::BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
-        Healthcheck, UriParseSnafu, VectorSink,
+        Healthcheck, HealthcheckFactory, UriParseSnafu, VectorSink,
     },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
@@ -129,6 +129,16 @@ impl SinkConfig for DatadogMetricsConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn build_healthcheck_factory(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<HealthcheckFactory>> {
+        let client = self.build_client(&cx.proxy)?;
+        self.dd_common
+            .build_healthcheck_factory(client, self.region.as_ref())
+            .map(Some)
+    }
+
     fn input(&self) -> Input {
         Input::metric()
     }
@@ -1,11 +1,12 @@
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use bytes::{Buf, Bytes};
 use futures::future::BoxFuture;
 use http::{
     header::{HeaderValue, CONTENT_ENCODING, CONTENT_TYPE},
-    Request, StatusCode, Uri,
+    HeaderMap, Request, StatusCode, Uri,
 };
 use hyper::Body;
 use snafu::ResultExt;
@@ -20,7 +21,10 @@ use vector_core::{
 use crate::{
     http::{BuildRequestSnafu, CallRequestSnafu, HttpClient},
     sinks::datadog::DatadogApiError,
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        http::retry_after,
+        retries::{RetryAction, RetryLogic},
+    },
 };
 
 /// Retry logic specific to the Datadog metrics endpoints.
@@ -51,6 +55,10 @@ impl RetryLogic for DatadogMetricsRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(&response.headers)
+    }
 }
 
 /// Generalized request for sending metrics to the Datadog metrics endpoints.
@@ -121,6 +129,7 @@ impl MetaDescriptive for DatadogMetricsRequest {
 #[derive(Debug)]
 pub struct DatadogMetricsResponse {
     status_code: StatusCode,
+    headers: HeaderMap,
     body: Bytes,
     batch_size: usize,
     byte_size: usize,
@@ -202,6 +211,7 @@ impl Service<DatadogMetricsRequest> for DatadogMetricsService {
 
             Ok(DatadogMetricsResponse {
                 status_code: parts.status,
+                headers: parts.headers,
                 body,
                 batch_size,
                 byte_size,
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use futures_util::FutureExt;
 use http::{Request, StatusCode, Uri};
 use hyper::body::Body;
@@ -12,7 +14,7 @@ use crate::{
     sinks::HealthcheckError,
 };
 
-use super::Healthcheck;
+use super::{Healthcheck, HealthcheckFactory};
 
 #[cfg(feature = "sinks-datadog_events")]
 pub mod events;
@@ -97,6 +99,17 @@ impl DatadogCommonConfig {
         client: HttpClient,
         region: Option<&Region>,
     ) -> crate::Result<Healthcheck> {
+        self.build_healthcheck_factory(client, region)
+            .map(|healthcheck| healthcheck())
+    }
+
+    /// Returns a factory of the healthchecks of `build_healthcheck`, to check the health of the
+    /// sink periodically with the same client.
+    fn build_healthcheck_factory(
+        &self,
+        client: HttpClient,
+        region: Option<&Region>,
+    ) -> crate::Result<HealthcheckFactory> {
         let validate_endpoint = get_api_validate_endpoint(
             self.endpoint.as_ref(),
             get_base_domain_region(self.site.as_str(), region),
@@ -104,7 +117,10 @@ impl DatadogCommonConfig {
 
         let api_key: String = self.default_api_key.clone().into();
 
-        Ok(build_healthcheck_future(client, validate_endpoint, api_key).boxed())
+        Ok(Arc::new(move || {
+            build_healthcheck_future(client.clone(), validate_endpoint.clone(), api_key.clone())
+                .boxed()
+        }))
     }
 }
 
@@ -27,7 +27,7 @@ use crate::{
             service::ServiceBuilderExt, BatchConfig, Compression, SinkBatchSettings,
             TowerRequestConfig,
         },
-        Healthcheck, UriParseSnafu, VectorSink,
+        Healthcheck, HealthcheckFactory, UriParseSnafu, VectorSink,
     },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
@@ -220,6 +220,16 @@ impl SinkConfig for DatadogTracesConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn build_healthcheck_factory(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<HealthcheckFactory>> {
+        let client = self.build_client(&cx.proxy)?;
+        self.dd_common
+            .build_healthcheck_factory(client, None)
+            .map(Some)
+    }
+
     fn input(&self) -> Input {
         Input::trace()
     }
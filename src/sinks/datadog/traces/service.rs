@@ -1,11 +1,12 @@
 use std::{
     collections::BTreeMap,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use bytes::{Buf, Bytes};
 use futures::future::BoxFuture;
-use http::{Request, StatusCode, Uri};
+use http::{HeaderMap, Request, StatusCode, Uri};
 use hyper::Body;
 use snafu::ResultExt;
 use tower::Service;
@@ -18,7 +19,10 @@ use vector_core::{
 
 use crate::{
     http::{BuildRequestSnafu, CallRequestSnafu, HttpClient, HttpError},
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        http::retry_after,
+        retries::{RetryAction, RetryLogic},
+    },
 };
 
 #[derive(Debug, Default, Clone)]
@@ -52,6 +56,10 @@ impl RetryLogic for TraceApiRetry {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(&response.headers)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -89,6 +97,7 @@ impl MetaDescriptive for TraceApiRequest {
 #[derive(Debug)]
 pub struct TraceApiResponse {
     status_code: StatusCode,
+    headers: HeaderMap,
     body: Bytes,
     batch_size: usize,
     byte_size: usize,
@@ -160,6 +169,7 @@ impl Service<TraceApiRequest> for TraceApiService {
 
             Ok(TraceApiResponse {
                 status_code: parts.status,
+                headers: parts.headers,
                 body,
                 batch_size,
                 byte_size,
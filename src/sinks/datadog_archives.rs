@@ -45,7 +45,7 @@ use crate::{
     sinks::{
         azure_common::{
             self,
-            config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic},
+            config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic, BlobType},
             service::AzureBlobService,
             sink::AzureBlobSink,
         },
@@ -312,6 +312,7 @@ impl DatadogArchivesSinkConfig {
                     None,
                     self.bucket.clone(),
                     None,
+                    &Default::default(),
                 )?;
                 let svc = self
                     .build_azure_sink(Arc::<ContainerClient>::clone(&client))
@@ -459,7 +460,7 @@ impl DatadogArchivesSinkConfig {
         let request_limits = self.request.unwrap_with(&Default::default());
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(AzureBlobService::new(client, BlobType::Block));
 
         let batcher_settings = BatchConfig::<DatadogArchivesDefaultBatchSettings>::default()
             .into_batcher_settings()
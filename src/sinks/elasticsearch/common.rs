@@ -99,8 +99,9 @@ impl ElasticsearchCommon {
             format!("{}s", tower_request.timeout.as_secs()),
         );
 
-        if let Some(pipeline) = &config.pipeline {
-            query_params.insert("pipeline".into(), pipeline.into());
+        // Templated pipelines are set per document in the bulk action instead.
+        if let Some(pipeline) = config.pipeline.as_ref().filter(|p| !p.is_dynamic()) {
+            query_params.insert("pipeline".into(), pipeline.get_ref().into());
         }
 
         let bulk_url = {
@@ -20,7 +20,7 @@ use crate::{
             service::{ElasticsearchService, HttpRequestBuilder},
             sink::ElasticsearchSink,
             ElasticsearchApiVersion, ElasticsearchAuth, ElasticsearchCommon,
-            ElasticsearchCommonMode, ElasticsearchMode,
+            ElasticsearchCommonMode, ElasticsearchMode, VersionType,
         },
         util::{
             http::RequestConfig, service::HealthConfig, BatchConfig, Compression,
@@ -111,10 +111,41 @@ pub struct ElasticsearchConfig {
     pub id_key: Option<String>,
 
     /// The name of the pipeline to apply.
+    ///
+    /// If the name is templated, it is rendered for each event, so that documents can be sent
+    /// through different pipelines.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
     #[configurable(metadata(docs::examples = "pipeline-name"))]
-    pub pipeline: Option<String>,
+    #[configurable(metadata(docs::examples = "{{ pipeline }}"))]
+    pub pipeline: Option<Template>,
+
+    /// The [routing][routing] value of each document.
+    ///
+    /// Documents with the same routing value are stored on the same shard, which is required for
+    /// parent/child relations.
+    ///
+    /// [routing]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-routing-field.html
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "{{ user_id }}"))]
+    pub routing: Option<Template>,
+
+    /// The version of each document, used for optimistic concurrency control.
+    ///
+    /// The rendered value must be a non-negative integer. Documents are only written when their
+    /// version is newer than the stored one, which makes re-ingesting the same events idempotent.
+    /// Events whose version can't be rendered or parsed are dropped.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "{{ version }}"))]
+    pub version: Option<Template>,
+
+    /// How the `version` of each document is compared to the stored version.
+    #[serde(default)]
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub version_type: VersionType,
 
     #[serde(default)]
     #[configurable(derived)]
@@ -207,6 +238,9 @@ impl Default for ElasticsearchConfig {
             request_retry_partial: false,
             id_key: None,
             pipeline: None,
+            routing: None,
+            version: None,
+            version_type: Default::default(),
             mode: Default::default(),
             compression: Default::default(),
             encoding: Default::default(),
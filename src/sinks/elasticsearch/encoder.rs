@@ -8,7 +8,7 @@ use crate::{
     codecs::Transformer,
     event::{EventFinalizers, Finalizable, LogEvent},
     sinks::{
-        elasticsearch::BulkAction,
+        elasticsearch::{BulkAction, VersionType},
         util::encoding::{as_tracked_write, Encoder},
     },
 };
@@ -19,6 +19,20 @@ pub struct ProcessedEvent {
     pub bulk_action: BulkAction,
     pub log: LogEvent,
     pub id: Option<String>,
+    pub document: DocumentMetadata,
+}
+
+/// Per-document parameters of a bulk action.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DocumentMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_type: Option<VersionType>,
 }
 
 impl Finalizable for ProcessedEvent {
@@ -29,7 +43,11 @@ impl Finalizable for ProcessedEvent {
 
 impl ByteSizeOf for ProcessedEvent {
     fn allocated_bytes(&self) -> usize {
-        self.index.allocated_bytes() + self.log.allocated_bytes() + self.id.allocated_bytes()
+        self.index.allocated_bytes()
+            + self.log.allocated_bytes()
+            + self.id.allocated_bytes()
+            + self.document.routing.allocated_bytes()
+            + self.document.pipeline.allocated_bytes()
     }
 }
 
@@ -73,6 +91,7 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
                 &self.doc_type,
                 self.suppress_type_name,
                 &event.id,
+                &event.document,
             )?;
             written_bytes +=
                 as_tracked_write::<_, _, io::Error>(writer, &log, |mut writer, log| {
@@ -86,6 +105,18 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
     }
 }
 
+#[derive(Serialize)]
+struct BulkActionMetadata<'a> {
+    #[serde(rename = "_index")]
+    index: &'a str,
+    #[serde(rename = "_type", skip_serializing_if = "Option::is_none")]
+    doc_type: Option<&'a str>,
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    #[serde(flatten)]
+    document: &'a DocumentMetadata,
+}
+
 fn write_bulk_action(
     writer: &mut dyn Write,
     bulk_action: &str,
@@ -93,37 +124,20 @@ fn write_bulk_action(
     doc_type: &str,
     suppress_type: bool,
     id: &Option<String>,
+    document: &DocumentMetadata,
 ) -> std::io::Result<usize> {
-    as_tracked_write(
-        writer,
-        (bulk_action, index, doc_type, id, suppress_type),
-        |writer, (bulk_action, index, doc_type, id, suppress_type)| match (id, suppress_type) {
-            (Some(id), true) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, id
-                )
-            }
-            (Some(id), false) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, doc_type, id
-                )
-            }
-            (None, true) => {
-                write!(writer, r#"{{"{}":{{"_index":"{}"}}}}"#, bulk_action, index)
-            }
-            (None, false) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}"}}}}"#,
-                    bulk_action, index, doc_type
-                )
-            }
-        },
-    )
+    let metadata = BulkActionMetadata {
+        index,
+        doc_type: (!suppress_type).then_some(doc_type),
+        id: id.as_deref(),
+        document,
+    };
+    as_tracked_write::<_, _, io::Error>(writer, metadata, |mut writer, metadata| {
+        write!(writer, r#"{{"{}":"#, bulk_action)?;
+        serde_json::to_writer(&mut writer, &metadata)?;
+        writer.write_all(b"}")?;
+        Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -141,6 +155,7 @@ mod tests {
             "TYPE",
             true,
             &Some("ID".to_string()),
+            &Default::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -162,7 +177,15 @@ mod tests {
     fn suppress_type_without_id() {
         let mut writer = Vec::new();
 
-        let _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", true, &None);
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &None,
+            &Default::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -189,6 +212,7 @@ mod tests {
             "TYPE",
             false,
             &Some("ID".to_string()),
+            &Default::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -211,7 +235,15 @@ mod tests {
     fn type_without_id() {
         let mut writer = Vec::new();
 
-        let _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", false, &None);
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            false,
+            &None,
+            &Default::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -227,4 +259,37 @@ mod tests {
         assert!(nested.contains_key("_type"));
         assert_eq!(nested.get("_type").unwrap().as_str(), Some("TYPE"));
     }
+
+    #[test]
+    fn document_metadata() {
+        let mut writer = Vec::new();
+
+        let document = DocumentMetadata {
+            routing: Some("ROUTING".to_string()),
+            pipeline: Some("PIPELINE".to_string()),
+            version: Some(3),
+            version_type: Some(VersionType::ExternalGte),
+        };
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &None,
+            &document,
+        );
+
+        let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
+        let nested = value.get("ACTION").unwrap().as_object().unwrap();
+
+        assert_eq!(nested.get("_index").unwrap().as_str(), Some("INDEX"));
+        assert_eq!(nested.get("routing").unwrap().as_str(), Some("ROUTING"));
+        assert_eq!(nested.get("pipeline").unwrap().as_str(), Some("PIPELINE"));
+        assert_eq!(nested.get("version").unwrap().as_u64(), Some(3));
+        assert_eq!(
+            nested.get("version_type").unwrap().as_str(),
+            Some("external_gte")
+        );
+    }
 }
@@ -104,6 +104,19 @@ impl BulkAction {
     }
 }
 
+/// Version types for optimistic concurrency control.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum VersionType {
+    /// Only write the document if its version is greater than the stored one.
+    #[default]
+    External,
+
+    /// Only write the document if its version is greater than or equal to the stored one.
+    ExternalGte,
+}
+
 impl TryFrom<&str> for BulkAction {
     type Error = String;
 
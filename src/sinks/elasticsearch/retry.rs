@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use http::StatusCode;
 use serde::Deserialize;
 
@@ -5,7 +7,10 @@ use crate::{
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
-        util::retries::{RetryAction, RetryLogic},
+        util::{
+            http::retry_after,
+            retries::{RetryAction, RetryLogic},
+        },
     },
 };
 
@@ -153,6 +158,10 @@ impl RetryLogic for ElasticsearchRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after(&self, response: &ElasticsearchResponse) -> Option<Duration> {
+        retry_after(response.http_response.headers())
+    }
 }
 
 #[cfg(test)]
@@ -206,6 +215,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn honors_retry_after() {
+        let response = Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .header("Retry-After", "7")
+            .body(Bytes::new())
+            .unwrap();
+        let logic = ElasticsearchRetryLogic {
+            retry_partial: false,
+        };
+        assert_eq!(
+            logic.retry_after(&ElasticsearchResponse {
+                http_response: response,
+                event_status: EventStatus::Errored,
+                batch_size: 1,
+                events_byte_size: 1,
+            }),
+            Some(Duration::from_secs(7))
+        );
+    }
+
     #[test]
     fn get_index_error_reason() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
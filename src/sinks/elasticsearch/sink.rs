@@ -8,14 +8,19 @@ use vector_core::stream::{BatcherSettings, DriverResponse};
 use crate::{
     codecs::Transformer,
     event::{Event, LogEvent, Value},
-    internal_events::SinkRequestBuildError,
+    internal_events::{
+        ElasticsearchInvalidVersionError, SinkRequestBuildError, TemplateRenderingError,
+    },
     sinks::{
         elasticsearch::{
-            encoder::ProcessedEvent, request_builder::ElasticsearchRequestBuilder,
-            service::ElasticsearchRequest, BulkAction, ElasticsearchCommonMode,
+            encoder::{DocumentMetadata, ProcessedEvent},
+            request_builder::ElasticsearchRequestBuilder,
+            service::ElasticsearchRequest,
+            BulkAction, ElasticsearchCommonMode, VersionType,
         },
         util::{SinkBuilderExt, StreamSink},
     },
+    template::Template,
     transforms::metric_to_log::MetricToLog,
 };
 
@@ -35,6 +40,66 @@ pub struct ElasticsearchSink<S> {
     pub metric_to_log: MetricToLog,
     pub mode: ElasticsearchCommonMode,
     pub id_key_field: Option<String>,
+    pub document: DocumentTemplates,
+}
+
+/// Templates for the per-document parameters of the bulk actions.
+#[derive(Clone, Debug, Default)]
+pub struct DocumentTemplates {
+    pub routing: Option<Template>,
+    pub pipeline: Option<Template>,
+    pub version: Option<Template>,
+    pub version_type: VersionType,
+}
+
+impl DocumentTemplates {
+    pub fn new(config: &ElasticsearchConfig) -> Self {
+        Self {
+            routing: config.routing.clone(),
+            // Static pipelines are applied to the whole request through the query string.
+            pipeline: config.pipeline.clone().filter(Template::is_dynamic),
+            version: config.version.clone(),
+            version_type: config.version_type,
+        }
+    }
+
+    /// Renders the parameters of a document, or returns `None` if the event should be dropped.
+    fn render(&self, log: &LogEvent) -> Option<DocumentMetadata> {
+        let render = |template: &Option<Template>, field| {
+            template
+                .as_ref()
+                .map(|template| {
+                    template.render_string(log).map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some(field),
+                            drop_event: true,
+                        });
+                    })
+                })
+                .transpose()
+        };
+
+        let routing = render(&self.routing, "routing").ok()?;
+        let pipeline = render(&self.pipeline, "pipeline").ok()?;
+        let version = match render(&self.version, "version").ok()? {
+            Some(version) => match version.parse::<u64>() {
+                Ok(version) => Some(version),
+                Err(_) => {
+                    emit!(ElasticsearchInvalidVersionError { version: &version });
+                    return None;
+                }
+            },
+            None => None,
+        };
+
+        Some(DocumentMetadata {
+            routing,
+            pipeline,
+            version,
+            version_type: version.map(|_| self.version_type),
+        })
+    }
 }
 
 impl<S> ElasticsearchSink<S> {
@@ -53,6 +118,7 @@ impl<S> ElasticsearchSink<S> {
             metric_to_log: common.metric_to_log.clone(),
             mode: common.mode.clone(),
             id_key_field: config.id_key.clone(),
+            document: DocumentTemplates::new(config),
         })
     }
 }
@@ -69,6 +135,7 @@ where
 
         let mode = self.mode;
         let id_key_field = self.id_key_field;
+        let document = self.document;
         let transformer = self.transformer.clone();
 
         input
@@ -86,7 +153,13 @@ where
             })
             .filter_map(|x| async move { x })
             .filter_map(move |log| {
-                future::ready(process_log(log, &mode, &id_key_field, &transformer))
+                future::ready(process_log(
+                    log,
+                    &mode,
+                    &id_key_field,
+                    &transformer,
+                    &document,
+                ))
             })
             .batched(self.batch_settings.into_byte_size_config())
             .request_builder(request_builder_concurrency_limit, self.request_builder)
@@ -112,9 +185,11 @@ pub(super) fn process_log(
     mode: &ElasticsearchCommonMode,
     id_key_field: &Option<String>,
     transformer: &Transformer,
+    document: &DocumentTemplates,
 ) -> Option<ProcessedEvent> {
     let index = mode.index(&log)?;
     let bulk_action = mode.bulk_action(&log)?;
+    let document = document.render(&log)?;
 
     if let Some(cfg) = mode.as_data_stream_config() {
         cfg.sync_fields(&mut log);
@@ -138,6 +213,7 @@ pub(super) fn process_log(
         bulk_action,
         log,
         id,
+        document,
     })
 }
 
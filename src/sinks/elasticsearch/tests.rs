@@ -5,8 +5,9 @@ use crate::{
     event::{LogEvent, Metric, MetricKind, MetricValue, Value},
     sinks::{
         elasticsearch::{
-            sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion,
-            ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode,
+            sink::{process_log, DocumentTemplates},
+            BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion, ElasticsearchCommon,
+            ElasticsearchConfig, ElasticsearchMode,
         },
         util::encoding::Encoder,
     },
@@ -53,7 +54,7 @@ async fn sets_create_action_when_configured() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &config.encoding, &Default::default()).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -107,7 +108,7 @@ async fn encode_datastream_mode() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &config.encoding, &Default::default()).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -158,7 +159,7 @@ async fn encode_datastream_mode_no_routing() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &config.encoding, &Default::default()).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -194,7 +195,7 @@ async fn handle_metrics() {
     es.request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &config.encoding, &Default::default()).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -303,7 +304,7 @@ async fn encode_datastream_mode_no_sync() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &config.encoding, &Default::default()).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -343,7 +344,7 @@ async fn allows_using_except_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &config.encoding, &Default::default()).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -378,7 +379,7 @@ async fn allows_using_only_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &config.encoding, &Default::default()).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -389,3 +390,49 @@ async fn allows_using_only_fields() {
     assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
     assert_eq!(encoded.len(), encoded_size);
 }
+
+#[tokio::test]
+async fn encodes_document_metadata() {
+    let config = ElasticsearchConfig {
+        bulk: BulkConfig {
+            index: parse_template("vector"),
+            ..Default::default()
+        },
+        routing: Some(parse_template("{{ user }}")),
+        pipeline: Some(parse_template("{{ pipeline }}")),
+        version: Some(parse_template("{{ version }}")),
+        endpoints: vec![String::from("https://example.com")],
+        api_version: ElasticsearchApiVersion::V6,
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+    let document = DocumentTemplates::new(&config);
+    assert!(!es.query_params.contains_key("pipeline"));
+
+    let mut log = LogEvent::from("hello there");
+    log.insert("user", "alice");
+    log.insert("pipeline", "geoip");
+    log.insert("version", 5);
+
+    let mut encoded = vec![];
+    let encoded_size = es
+        .request_builder
+        .encoder
+        .encode_input(
+            vec![process_log(log, &es.mode, &None, &config.encoding, &document).unwrap()],
+            &mut encoded,
+        )
+        .unwrap();
+
+    let expected = r#"{"index":{"_index":"vector","_type":"_doc","routing":"alice","pipeline":"geoip","version":5,"version_type":"external"}}
+{"message":"hello there","pipeline":"geoip","user":"alice","version":5}
+"#;
+    assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
+    assert_eq!(encoded.len(), encoded_size);
+
+    let mut log = LogEvent::from("hello there");
+    log.insert("user", "alice");
+    log.insert("pipeline", "geoip");
+    log.insert("version", "five");
+    assert!(process_log(log, &es.mode, &None, &config.encoding, &document).is_none());
+}
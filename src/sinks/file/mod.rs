@@ -35,8 +35,10 @@ use crate::{
     template::Template,
 };
 mod bytes_path;
+mod rotation;
 
 use bytes_path::BytesPath;
+use rotation::{Rotation, RotationConfig};
 
 /// Configuration for the `file` sink.
 #[serde_as]
@@ -73,6 +75,10 @@ pub struct FileSinkConfig {
     )]
     pub compression: Compression,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<RotationConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -89,6 +95,7 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Default::default(),
+            rotation: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -197,6 +204,7 @@ pub struct FileSink {
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
+    rotation: Option<Rotation>,
     events_sent: Registered<EventsSent>,
 }
 
@@ -205,6 +213,9 @@ impl FileSink {
         let transformer = config.encoding.transformer();
         let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
+        if let Some(rotation) = &config.rotation {
+            rotation.validate(config.compression)?;
+        }
 
         Ok(Self {
             path: config.path.clone(),
@@ -213,6 +224,7 @@ impl FileSink {
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            rotation: config.rotation.clone().map(Rotation::new),
             events_sent: register!(EventsSent::from(Output(None))),
         })
     }
@@ -283,6 +295,10 @@ impl FileSink {
                         Some((mut expired_file, path)) => {
                             // We got an expired file. All we really want is to
                             // flush and close it.
+                            let path = path.into_inner();
+                            if let Some(rotation) = self.rotation.as_mut() {
+                                rotation.closed(&path);
+                            }
                             if let Err(error) = expired_file.close().await {
                                 emit!(FileIoError {
                                     error,
@@ -318,6 +334,14 @@ impl FileSink {
             }
         };
 
+        if self
+            .rotation
+            .as_ref()
+            .map_or(false, |rotation| rotation.is_due(&path))
+        {
+            self.rotate(&path).await;
+        }
+
         let next_deadline = self.deadline_at();
         trace!(message = "Computed next deadline.", next_deadline = ?next_deadline, path = ?path);
 
@@ -344,6 +368,9 @@ impl FileSink {
                 }
             };
 
+            if let Some(rotation) = self.rotation.as_mut() {
+                rotation.opened(&path, &file).await;
+            }
             let outfile = OutFile::new(file, self.compression);
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
@@ -358,6 +385,9 @@ impl FileSink {
         let finalizers = event.take_finalizers();
         match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
             Ok(byte_size) => {
+                if let Some(rotation) = self.rotation.as_mut() {
+                    rotation.written(&path, byte_size);
+                }
                 finalizers.update_status(EventStatus::Delivered);
                 self.events_sent.emit(CountByteSize(1, event_size));
                 emit!(FileBytesSent {
@@ -377,6 +407,38 @@ impl FileSink {
             }
         }
     }
+
+    /// Closes the file at `path` and moves it aside, so that the next event is written to a new
+    /// file.
+    async fn rotate(&mut self, path: &Bytes) {
+        if let Some((mut file, _)) = self.files.remove(path) {
+            if let Err(error) = file.close().await {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_closing_file",
+                    message: "Failed to close file.",
+                    path,
+                    dropped_events: 0,
+                });
+            }
+            emit!(FileOpen {
+                count: self.files.len()
+            });
+        }
+
+        let rotation = self.rotation.as_mut().expect("rotation must be configured");
+        if let Err(error) = rotation.rotate(path).await {
+            emit!(FileIoError {
+                error,
+                code: "failed_rotating_file",
+                message: "Failed to rotate file.",
+                path,
+                dropped_events: 0,
+            });
+        } else {
+            debug!(message = "Rotated file.", ?path);
+        }
+    }
 }
 
 async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
@@ -451,6 +513,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -473,6 +536,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Gzip,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -495,6 +559,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Zstd,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -522,6 +587,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -599,6 +665,7 @@ mod tests {
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -642,6 +709,45 @@ mod tests {
         sink_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn rotates_by_size() {
+        let directory = temp_dir();
+        let path = directory.join("app.log");
+
+        let config = FileSinkConfig {
+            path: path.to_string_lossy().as_ref().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            compression: Compression::None,
+            rotation: Some(RotationConfig {
+                max_bytes: std::num::NonZeroU64::new(100),
+                max_age_secs: None,
+                suffix: ".rotated".to_owned(),
+                compression: Compression::None,
+                max_files: None,
+                retention_secs: None,
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let (input, _) = random_lines_with_stream(10, 64, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let mut output = Vec::new();
+        // Each file is rotated once it holds two lines.
+        for name in [
+            "app.log.rotated",
+            "app.log.rotated.1",
+            "app.log.rotated.2",
+            "app.log.rotated.3",
+            "app.log",
+        ] {
+            output.extend(lines_from_file(directory.join(name)));
+        }
+        assert_eq!(input, output);
+    }
+
     async fn run_assert_log_sink(config: FileSinkConfig, events: Vec<String>) {
         run_assert_sink(
             config,
@@ -0,0 +1,408 @@
+//! Rotation of the files written by the `file` sink.
+
+use std::{
+    collections::HashMap,
+    num::{NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
+use bytes::Bytes;
+use chrono::{
+    format::{Item, StrftimeItems},
+    Utc,
+};
+use tokio::{
+    fs::{self, File},
+    io::{self, AsyncWrite, AsyncWriteExt},
+};
+use vector_config::configurable_component;
+
+use super::{bytes_path::BytesPath, Compression};
+use crate::internal_events::FileIoError;
+
+/// Configuration for rotating the files written by the `file` sink.
+///
+/// When a file is rotated, it is renamed by appending `suffix` to its name, and events are written
+/// to a new file at the original path. Files in the same directory whose name starts with the name
+/// of the original file are considered to be its rotated files when applying the retention policy.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// The size a file can reach before it is rotated, in bytes.
+    ///
+    /// Files are rotated before writing the next event, so they can exceed this size by at most
+    /// one event.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 104857600))]
+    pub max_bytes: Option<NonZeroU64>,
+
+    /// The age a file can reach before it is rotated, in seconds.
+    ///
+    /// Files are rotated before writing the next event, so idle files are not rotated until they
+    /// are written to again.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 86400))]
+    pub max_age_secs: Option<NonZeroU64>,
+
+    /// The suffix appended to the name of rotated files.
+    ///
+    /// [`strftime` specifiers][strftime] are replaced with the time of rotation, in UTC. If a
+    /// rotated file with the same name already exists, a counter is appended.
+    ///
+    /// [strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+    #[serde(default = "default_suffix")]
+    #[configurable(metadata(docs::examples = ".%Y-%m-%d"))]
+    pub suffix: String,
+
+    /// The compression applied to rotated files.
+    ///
+    /// The extension of the compression format is appended to the name of compressed files. This
+    /// can not be combined with the `compression` option of the sink.
+    #[serde(default)]
+    pub compression: Compression,
+
+    /// The maximum number of rotated files to keep for each file.
+    ///
+    /// When exceeded, the oldest rotated files are deleted.
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_files: Option<NonZeroUsize>,
+
+    /// The maximum age of rotated files, in seconds.
+    ///
+    /// Rotated files that were last modified longer ago are deleted.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 604800))]
+    pub retention_secs: Option<NonZeroU64>,
+}
+
+fn default_suffix() -> String {
+    ".%Y%m%dT%H%M%S".to_owned()
+}
+
+impl RotationConfig {
+    pub(super) fn validate(&self, compression: Compression) -> crate::Result<()> {
+        if self.max_bytes.is_none() && self.max_age_secs.is_none() {
+            return Err("`rotation` requires `max_bytes` or `max_age_secs` to be set".into());
+        }
+        if self.suffix.is_empty() {
+            return Err("`rotation.suffix` must not be empty".into());
+        }
+        if StrftimeItems::new(&self.suffix).any(|item| matches!(item, Item::Error)) {
+            return Err(format!(
+                "Invalid strftime format in `rotation.suffix`: {}",
+                self.suffix
+            )
+            .into());
+        }
+        if compression != Compression::None && self.compression != Compression::None {
+            return Err(
+                "`rotation.compression` can not be combined with the `compression` option".into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The amount of data written to an open file, and since when it has been written to.
+struct FileState {
+    bytes: u64,
+    created_at: SystemTime,
+}
+
+/// Tracks the open files of the sink and rotates them according to a [`RotationConfig`].
+pub(super) struct Rotation {
+    config: RotationConfig,
+    files: HashMap<Bytes, FileState>,
+}
+
+impl Rotation {
+    pub(super) fn new(config: RotationConfig) -> Self {
+        Self {
+            config,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Starts tracking a newly opened file, accounting for the data it already contains.
+    pub(super) async fn opened(&mut self, path: &Bytes, file: &File) {
+        let now = SystemTime::now();
+        let (bytes, created_at) = match file.metadata().await {
+            Ok(metadata) => (metadata.len(), metadata.created().unwrap_or(now)),
+            Err(_) => (0, now),
+        };
+        self.files
+            .insert(path.clone(), FileState { bytes, created_at });
+    }
+
+    pub(super) fn written(&mut self, path: &Bytes, bytes: usize) {
+        if let Some(state) = self.files.get_mut(path) {
+            state.bytes += bytes as u64;
+        }
+    }
+
+    /// Stops tracking a file that was closed.
+    pub(super) fn closed(&mut self, path: &Bytes) {
+        self.files.remove(path);
+    }
+
+    /// Whether the file must be rotated before writing more data to it.
+    pub(super) fn is_due(&self, path: &Bytes) -> bool {
+        let Some(state) = self.files.get(path) else {
+            return false;
+        };
+        let too_big = self
+            .config
+            .max_bytes
+            .map_or(false, |max| state.bytes >= max.get());
+        let too_old = self.config.max_age_secs.map_or(false, |max| {
+            state
+                .created_at
+                .elapsed()
+                .map_or(false, |age| age >= Duration::from_secs(max.get()))
+        });
+        too_big || too_old
+    }
+
+    /// Renames a closed file, then compresses it and prunes the old rotated files in the
+    /// background.
+    pub(super) async fn rotate(&mut self, path: &Bytes) -> io::Result<()> {
+        self.closed(path);
+
+        let path = BytesPath::new(path.clone()).as_ref().to_path_buf();
+        let rotated = rotated_path(&path, &self.config.suffix).await;
+        fs::rename(&path, &rotated).await?;
+
+        let config = self.config.clone();
+        tokio::spawn(async move {
+            if let Err(error) = compress(&rotated, config.compression).await {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_compressing_file",
+                    message: "Failed to compress rotated file.",
+                    path: &rotated,
+                    dropped_events: 0,
+                });
+            }
+            prune(&path, &config).await;
+        });
+
+        Ok(())
+    }
+}
+
+/// Appends the rendered suffix to `path`, and a counter if the result is already taken.
+async fn rotated_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(Utc::now().format(suffix).to_string());
+
+    let mut candidate = PathBuf::from(&name);
+    let mut counter = 1;
+    while fs::metadata(&candidate).await.is_ok() {
+        let mut numbered = name.clone();
+        numbered.push(format!(".{}", counter));
+        candidate = PathBuf::from(numbered);
+        counter += 1;
+    }
+    candidate
+}
+
+/// Replaces `path` with a compressed copy, with the extension of the compression format added.
+async fn compress(path: &Path, compression: Compression) -> io::Result<()> {
+    let extension = match compression {
+        Compression::Gzip => ".gz",
+        Compression::Zstd => ".zst",
+        Compression::None => return Ok(()),
+    };
+    let mut compressed = path.as_os_str().to_owned();
+    compressed.push(extension);
+
+    let mut input = File::open(path).await?;
+    let output = File::create(&compressed).await?;
+    match compression {
+        Compression::Gzip => copy_into(&mut input, GzipEncoder::new(output)).await?,
+        Compression::Zstd => copy_into(&mut input, ZstdEncoder::new(output)).await?,
+        Compression::None => unreachable!(),
+    }
+    fs::remove_file(path).await
+}
+
+async fn copy_into<W: AsyncWrite + Unpin>(input: &mut File, mut output: W) -> io::Result<()> {
+    io::copy(input, &mut output).await?;
+    output.shutdown().await
+}
+
+/// Deletes the rotated files of `path` that exceed the retention policy.
+async fn prune(path: &Path, config: &RotationConfig) {
+    if config.max_files.is_none() && config.retention_secs.is_none() {
+        return;
+    }
+    let (Some(directory), Some(name)) = (path.parent(), path.file_name()) else {
+        return;
+    };
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+
+    let mut rotated = match rotated_files(directory, &name.to_string_lossy()).await {
+        Ok(rotated) => rotated,
+        Err(error) => {
+            emit!(FileIoError {
+                error,
+                code: "failed_listing_files",
+                message: "Failed to list rotated files.",
+                path: &directory,
+                dropped_events: 0,
+            });
+            return;
+        }
+    };
+    // Newest first.
+    rotated.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let retention = config
+        .retention_secs
+        .map(|secs| Duration::from_secs(secs.get()));
+    for (index, (path, modified)) in rotated.iter().enumerate() {
+        let too_many = config.max_files.map_or(false, |max| index >= max.get());
+        let too_old = retention.map_or(false, |retention| {
+            modified
+                .elapsed()
+                .map_or(false, |elapsed| elapsed > retention)
+        });
+        if too_many || too_old {
+            if let Err(error) = fs::remove_file(path).await {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_deleting_file",
+                    message: "Failed to delete rotated file.",
+                    path,
+                    dropped_events: 0,
+                });
+            } else {
+                debug!(message = "Deleted rotated file.", ?path);
+            }
+        }
+    }
+}
+
+/// Lists the files in `directory` whose name extends `name`, with their modification time.
+async fn rotated_files(directory: &Path, name: &str) -> io::Result<Vec<(PathBuf, SystemTime)>> {
+    let mut files = Vec::new();
+    let mut entries = fs::read_dir(directory).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        if file_name.len() <= name.len() || !file_name.starts_with(name) {
+            continue;
+        }
+        let metadata = entry.metadata().await?;
+        if metadata.is_file() {
+            files.push((entry.path(), metadata.modified()?));
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{lines_from_gzip_file, temp_dir};
+
+    fn config() -> RotationConfig {
+        RotationConfig {
+            max_bytes: NonZeroU64::new(10),
+            max_age_secs: None,
+            suffix: default_suffix(),
+            compression: Compression::None,
+            max_files: None,
+            retention_secs: None,
+        }
+    }
+
+    #[test]
+    fn validates_config() {
+        assert!(config().validate(Compression::None).is_ok());
+        assert!(RotationConfig {
+            max_bytes: None,
+            ..config()
+        }
+        .validate(Compression::None)
+        .is_err());
+        assert!(RotationConfig {
+            suffix: ".%Q".to_owned(),
+            ..config()
+        }
+        .validate(Compression::None)
+        .is_err());
+        assert!(RotationConfig {
+            compression: Compression::Gzip,
+            ..config()
+        }
+        .validate(Compression::Zstd)
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn rotated_path_avoids_collisions() {
+        let directory = temp_dir();
+        fs::create_dir_all(&directory).await.unwrap();
+        let path = directory.join("app.log");
+
+        let first = rotated_path(&path, ".rotated").await;
+        assert_eq!(first, directory.join("app.log.rotated"));
+        fs::write(&first, "").await.unwrap();
+
+        let second = rotated_path(&path, ".rotated").await;
+        assert_eq!(second, directory.join("app.log.rotated.1"));
+    }
+
+    #[tokio::test]
+    async fn compresses_rotated_file() {
+        let directory = temp_dir();
+        fs::create_dir_all(&directory).await.unwrap();
+        let path = directory.join("app.log.1");
+        fs::write(&path, "foo\nbar\n").await.unwrap();
+
+        compress(&path, Compression::Gzip).await.unwrap();
+
+        assert!(fs::metadata(&path).await.is_err());
+        let lines = lines_from_gzip_file(directory.join("app.log.1.gz"));
+        assert_eq!(lines, vec!["foo", "bar"]);
+    }
+
+    #[tokio::test]
+    async fn prunes_oldest_rotated_files() {
+        let directory = temp_dir();
+        fs::create_dir_all(&directory).await.unwrap();
+        let path = directory.join("app.log");
+        fs::write(&path, "").await.unwrap();
+        fs::write(directory.join("other.log.1"), "").await.unwrap();
+        for index in 1..=3 {
+            fs::write(directory.join(format!("app.log.{}", index)), "")
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let config = RotationConfig {
+            max_files: NonZeroUsize::new(2),
+            ..config()
+        };
+        prune(&path, &config).await;
+
+        let mut remaining = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec!["app.log", "app.log.2", "app.log.3", "other.log.1"]
+        );
+    }
+}
@@ -7,6 +7,7 @@ use indoc::indoc;
 use serde_json::{json, Value};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
+use uuid::Uuid;
 use vector_config::configurable_component;
 
 use crate::{
@@ -15,14 +16,17 @@ use crate::{
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope, PUBSUB_URL},
     http::HttpClient,
+    internal_events::TemplateRenderingError,
     sinks::{
         gcs_common::config::healthcheck_response,
         util::{
             http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
-            BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
+            BatchConfig, BoxedRawValue, Concurrency, JsonArrayBuffer, SinkBatchSettings,
+            TowerRequestConfig,
         },
         Healthcheck, UriParseSnafu, VectorSink,
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
@@ -71,6 +75,31 @@ pub struct PubsubConfig {
     #[serde(default, flatten)]
     pub auth: GcpAuthConfig,
 
+    /// The [ordering key][ordering] to publish each event with.
+    ///
+    /// Subscriptions with message ordering enabled receive the messages sharing an ordering key in
+    /// the order they were published. When this is set, requests are sent one at a time by default
+    /// to preserve that order. If the key can't be rendered for an event, the event is dropped.
+    ///
+    /// Ordering is only guaranteed when publishing to a [regional endpoint][regional_endpoints].
+    ///
+    /// [ordering]: https://cloud.google.com/pubsub/docs/ordering
+    /// [regional_endpoints]: https://cloud.google.com/pubsub/docs/reference/service_apis_overview#pubsub_endpoints
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    #[configurable(metadata(docs::examples = "{{ .user_id }}"))]
+    pub ordering_key: Option<Template>,
+
+    /// The name of an attribute to set to a unique ID for each event.
+    ///
+    /// The ID is generated once per event and is kept when a request is retried, so
+    /// subscribers can use it to discard the duplicates caused by retries. This complements
+    /// [exactly-once delivery][exactly_once], which only covers redeliveries of messages that
+    /// were published once.
+    ///
+    /// [exactly_once]: https://cloud.google.com/pubsub/docs/exactly-once-delivery
+    #[configurable(metadata(docs::examples = "vector_id"))]
+    pub deduplication_attribute: Option<String>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<PubsubDefaultBatchSettings>,
@@ -119,7 +148,14 @@ impl SinkConfig for PubsubConfig {
             .validate()?
             .limit_max_bytes(MAX_BATCH_PAYLOAD_SIZE)?
             .into_batch_settings()?;
-        let request_settings = self.request.unwrap_with(&Default::default());
+        let request_settings = if self.ordering_key.is_some() {
+            self.request.unwrap_with(&TowerRequestConfig {
+                concurrency: Concurrency::Fixed(1),
+                ..Default::default()
+            })
+        } else {
+            self.request.unwrap_with(&Default::default())
+        };
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls_settings, cx.proxy())?;
 
@@ -152,6 +188,8 @@ struct PubsubSink {
     uri_base: String,
     transformer: Transformer,
     encoder: Encoder<()>,
+    ordering_key: Option<Template>,
+    deduplication_attribute: Option<String>,
 }
 
 impl PubsubSink {
@@ -173,6 +211,8 @@ impl PubsubSink {
             uri_base,
             transformer,
             encoder,
+            ordering_key: config.ordering_key.clone(),
+            deduplication_attribute: config.deduplication_attribute.clone(),
         })
     }
 
@@ -187,17 +227,43 @@ impl PubsubSink {
 struct PubSubSinkEventEncoder {
     transformer: Transformer,
     encoder: Encoder<()>,
+    ordering_key: Option<Template>,
+    deduplication_attribute: Option<String>,
 }
 
 impl HttpEventEncoder<Value> for PubSubSinkEventEncoder {
     fn encode_event(&mut self, mut event: Event) -> Option<Value> {
+        // The ordering key is rendered before the transformer runs, so it can use fields that are
+        // excluded from the encoded message.
+        let ordering_key = match &self.ordering_key {
+            Some(template) => match template.render_string(&event) {
+                Ok(key) => Some(key),
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("ordering_key"),
+                        drop_event: true,
+                    });
+                    return None;
+                }
+            },
+            None => None,
+        };
+
         self.transformer.transform(&mut event);
         let mut bytes = BytesMut::new();
         // Errors are handled by `Encoder`.
         self.encoder.encode(event, &mut bytes).ok()?;
         // Each event needs to be base64 encoded, and put into a JSON object
         // as the `data` item.
-        Some(json!({ "data": BASE64_STANDARD.encode(&bytes) }))
+        let mut message = json!({ "data": BASE64_STANDARD.encode(&bytes) });
+        if let Some(ordering_key) = ordering_key {
+            message["orderingKey"] = Value::String(ordering_key);
+        }
+        if let Some(attribute) = &self.deduplication_attribute {
+            message["attributes"] = json!({ attribute: Uuid::new_v4().to_string() });
+        }
+        Some(message)
     }
 }
 
@@ -211,6 +277,8 @@ impl HttpSink for PubsubSink {
         PubSubSinkEventEncoder {
             transformer: self.transformer.clone(),
             encoder: self.encoder.clone(),
+            ordering_key: self.ordering_key.clone(),
+            deduplication_attribute: self.deduplication_attribute.clone(),
         }
     }
 
@@ -241,6 +309,7 @@ mod tests {
     use indoc::indoc;
 
     use super::*;
+    use crate::event::LogEvent;
 
     #[test]
     fn generate_config() {
@@ -259,6 +328,36 @@ mod tests {
             panic!("config.build failed to error");
         }
     }
+
+    #[test]
+    fn encodes_ordering_key_and_deduplication_attribute() {
+        let mut encoder = PubSubSinkEventEncoder {
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(codecs::TextSerializerConfig::default().build().into()),
+            ordering_key: Some(Template::try_from("{{ user }}").unwrap()),
+            deduplication_attribute: Some("vector_id".into()),
+        };
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("user", "alice");
+        let message = encoder.encode_event(event.clone()).unwrap();
+        assert_eq!(message["data"], BASE64_STANDARD.encode("hello"));
+        assert_eq!(message["orderingKey"], "alice");
+        let id = message["attributes"]["vector_id"].as_str().unwrap();
+        assert!(Uuid::parse_str(id).is_ok());
+
+        // Retried requests reuse the encoded messages, but each event gets its own ID.
+        let other = encoder.encode_event(event).unwrap();
+        assert_ne!(
+            other["attributes"]["vector_id"],
+            message["attributes"]["vector_id"]
+        );
+
+        // Events without an ordering key are dropped.
+        assert!(encoder
+            .encode_event(Event::Log(LogEvent::from("hello")))
+            .is_none());
+    }
 }
 
 #[cfg(all(test, feature = "gcp-integration-tests"))]
@@ -288,6 +387,8 @@ mod integration_tests {
                 skip_authentication: true,
                 ..Default::default()
             },
+            ordering_key: None,
+            deduplication_attribute: None,
             batch: Default::default(),
             request: Default::default(),
             encoding: JsonSerializerConfig::default().into(),
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use futures::FutureExt;
 use http::{StatusCode, Uri};
 use hyper::Body;
@@ -9,7 +11,10 @@ use crate::{
     http::HttpClient,
     sinks::{
         gcs_common::service::GcsResponse,
-        util::retries::{RetryAction, RetryLogic},
+        util::{
+            http::retry_after,
+            retries::{RetryAction, RetryLogic},
+        },
         Healthcheck, HealthcheckError,
     },
 };
@@ -161,4 +166,8 @@ impl RetryLogic for GcsRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(response.inner.headers())
+    }
 }
@@ -1,4 +1,4 @@
-use std::io::Write;
+use std::{io::Write, path::PathBuf, sync::Arc};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use codecs::encoding::{CharacterDelimitedEncoder, Framer, Serializer};
@@ -18,7 +18,7 @@ use crate::{
     components::validation::*,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
-    http::{Auth, HttpClient, MaybeAuth},
+    http::{Auth, Http3Config, HttpClient, MaybeAuth},
     register_validatable_component,
     sinks::util::{
         self,
@@ -40,6 +40,14 @@ pub struct HttpSinkConfig {
     #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/endpoint"))]
     pub uri: UriSerde,
 
+    /// The path of a Unix domain socket to send the requests to.
+    ///
+    /// When set, connections are made to this socket instead of the host and port of `uri`, whose
+    /// path and query are still used for the requests. TLS and proxies are not supported over
+    /// Unix domain sockets.
+    #[configurable(metadata(docs::examples = "/var/run/collector.sock"))]
+    pub unix_socket: Option<PathBuf>,
+
     /// The HTTP method to use when making the request.
     #[serde(default)]
     pub method: HttpMethod,
@@ -90,6 +98,10 @@ pub struct HttpSinkConfig {
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub http3: Http3Config,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -162,9 +174,46 @@ impl GenerateConfig for HttpSinkConfig {
 
 impl HttpSinkConfig {
     fn build_http_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
+        if let Some(path) = &self.unix_socket {
+            if self.tls.is_some() {
+                return Err("TLS is not supported on Unix domain sockets.".into());
+            }
+            if self.http3.enabled {
+                return Err("HTTP/3 is not supported on Unix domain sockets.".into());
+            }
+            #[cfg(unix)]
+            return Ok(HttpClient::new_unix(path));
+            #[cfg(not(unix))]
+            return Err(format!(
+                "Unix domain sockets are not supported on this platform: {}.",
+                path.display()
+            )
+            .into());
+        }
+
+        if self.http3.enabled {
+            if self.uri.uri.scheme_str() != Some("https") {
+                return Err("HTTP/3 requires the scheme of `uri` to be `https`.".into());
+            }
+            #[cfg(feature = "http3")]
+            return Ok(HttpClient::new_http3(self.tls.as_ref(), &self.http3)?);
+            #[cfg(not(feature = "http3"))]
+            return Err("Vector was built without HTTP/3 support.".into());
+        }
+
         let tls = TlsSettings::from_options(&self.tls)?;
         Ok(HttpClient::new(tls, cx.proxy())?)
     }
+
+    /// Builds the healthchecks of the sink, which always pass unless `healthcheck.uri` is set.
+    fn healthchecks(&self, cx: &SinkContext, client: HttpClient) -> super::HealthcheckFactory {
+        let uri = cx.healthcheck.uri.clone();
+        let auth = self.auth.clone();
+        Arc::new(move || match &uri {
+            Some(uri) => healthcheck(uri.clone(), auth.clone(), client.clone()).boxed(),
+            None => future::ok(()).boxed(),
+        })
+    }
 }
 
 struct HttpSink {
@@ -208,13 +257,7 @@ impl SinkConfig for HttpSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let client = self.build_http_client(&cx)?;
-
-        let healthcheck = match cx.healthcheck.uri {
-            Some(healthcheck_uri) => {
-                healthcheck(healthcheck_uri, self.auth.clone(), client.clone()).boxed()
-            }
-            None => future::ok(()).boxed(),
-        };
+        let healthcheck = self.healthchecks(&cx, client.clone())();
 
         let mut request = self.request.clone();
         request.add_old_option(self.headers.clone());
@@ -257,6 +300,14 @@ impl SinkConfig for HttpSinkConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn build_healthcheck_factory(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<super::HealthcheckFactory>> {
+        let client = self.build_http_client(&cx)?;
+        Ok(Some(self.healthchecks(&cx, client)))
+    }
+
     fn input(&self) -> Input {
         Input::new(self.encoding.config().1.input_type())
     }
@@ -274,6 +325,7 @@ impl ValidatableComponent for HttpSinkConfig {
         let config = Self {
             uri: UriSerde::from_str("http://127.0.0.1:9000/endpoint")
                 .expect("should never fail to parse"),
+            unix_socket: None,
             method: HttpMethod::Post,
             encoding: EncodingConfigWithFraming::new(
                 None,
@@ -286,6 +338,7 @@ impl ValidatableComponent for HttpSinkConfig {
             batch: BatchConfig::default(),
             request: RequestConfig::default(),
             tls: None,
+            http3: Http3Config::default(),
             acknowledgements: AcknowledgementsConfig::default(),
             payload_prefix: String::new(),
             payload_suffix: String::new(),
@@ -634,6 +687,22 @@ mod tests {
         let _ = config.build(cx).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn http_http3_requires_https() {
+        let config = r#"
+        uri = "http://localhost/"
+        encoding.codec = "text"
+        http3.enabled = true
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+        assert!(config.http3.zero_rtt);
+
+        let cx = SinkContext::new_test();
+
+        let error = config.build(cx).await.err().unwrap();
+        assert!(error.to_string().contains("https"));
+    }
+
     #[tokio::test]
     async fn http_happy_path_post() {
         run_sink(
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use bytes::BytesMut;
+use indexmap::IndexMap;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{Event, LogEvent, MetricTags, Value},
+    internal_events::{InfluxdbEncodingError, TemplateRenderingError},
+    sinks::{
+        influxdb::{encode_timestamp, influx_line_protocol, to_field, Field, ProtocolVersion},
+        util::tcp::TcpSinkConfig,
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+};
+
+/// Configuration for the `influxdb_ilp` sink.
+#[configurable_component(sink("influxdb_ilp"))]
+#[derive(Clone, Debug)]
+pub struct InfluxDbIlpConfig {
+    #[serde(flatten)]
+    pub socket: TcpSinkConfig,
+
+    /// The name of the measurement that is written to.
+    ///
+    /// With QuestDB, the measurement is the name of the table.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "{{ service }}"))]
+    pub measurement: Template,
+
+    /// The tags written with each line, and the fields of the events they are taken from.
+    ///
+    /// Tags whose field is missing from an event are not written. With QuestDB, tags are written
+    /// to `SYMBOL` columns.
+    #[configurable(metadata(
+        docs::additional_props_description = "The path of the field holding the value of the tag."
+    ))]
+    #[configurable(metadata(docs::examples = "example_tags()"))]
+    #[serde(default)]
+    pub tags: IndexMap<String, String>,
+
+    /// The fields written with each line, and the fields of the events they are taken from.
+    ///
+    /// If not set, all the fields of the events are written, except for the fields of the tags
+    /// and the timestamp. Nested fields are written with their full path as the name, such as
+    /// `parent.child`.
+    #[configurable(metadata(
+        docs::additional_props_description = "The path of the field holding the value of the line protocol field."
+    ))]
+    #[configurable(metadata(docs::examples = "example_fields()"))]
+    #[serde(default)]
+    pub fields: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn example_tags() -> IndexMap<String, String> {
+    IndexMap::from([
+        ("host".to_string(), "host".to_string()),
+        (
+            "service".to_string(),
+            "kubernetes.container_name".to_string(),
+        ),
+    ])
+}
+
+fn example_fields() -> IndexMap<String, String> {
+    IndexMap::from([
+        ("message".to_string(), "message".to_string()),
+        ("status".to_string(), "http.status".to_string()),
+    ])
+}
+
+impl GenerateConfig for InfluxDbIlpConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "localhost:9009"
+            measurement = "logs"
+            tags.host = "host"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for InfluxDbIlpConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.socket
+            .build(cx.proxy(), self.encoding.clone(), self.encoder())
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl InfluxDbIlpConfig {
+    fn encoder(&self) -> IlpEncoder {
+        IlpEncoder {
+            measurement: self.measurement.clone(),
+            tags: self.tags.clone(),
+            fields: self.fields.clone(),
+        }
+    }
+}
+
+/// Encodes each event as a line of the InfluxDB line protocol.
+#[derive(Clone, Debug)]
+struct IlpEncoder {
+    measurement: Template,
+    tags: IndexMap<String, String>,
+    fields: IndexMap<String, String>,
+}
+
+impl IlpEncoder {
+    fn encode_tags(&self, log: &LogEvent) -> MetricTags {
+        let mut tags = MetricTags::default();
+        for (tag, path) in &self.tags {
+            match log.get(path.as_str()) {
+                None | Some(Value::Null) => {}
+                Some(value) => tags.replace(tag.clone(), value.to_string_lossy().into_owned()),
+            }
+        }
+        tags
+    }
+
+    fn encode_fields(&self, log: &LogEvent) -> HashMap<String, Field> {
+        if !self.fields.is_empty() {
+            return self
+                .fields
+                .iter()
+                .filter_map(|(field, path)| match log.get(path.as_str()) {
+                    None | Some(Value::Null) => None,
+                    Some(value) => Some((field.clone(), to_field(value))),
+                })
+                .collect();
+        }
+
+        let timestamp_path = log.timestamp_path();
+        log.convert_to_fields()
+            .filter(|(key, value)| {
+                !value.is_null()
+                    && timestamp_path.as_ref() != Some(key)
+                    && !self.tags.values().any(|path| path == key)
+            })
+            .map(|(key, value)| (key, to_field(value)))
+            .collect()
+    }
+}
+
+impl tokio_util::codec::Encoder<Event> for IlpEncoder {
+    type Error = codecs::encoding::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let measurement = self.measurement.render_string(&event).map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("measurement"),
+                drop_event: true,
+            });
+            Self::Error::SerializingError("Failed to render the measurement.".into())
+        })?;
+
+        let log = event.into_log();
+        // The line protocol over TCP expects timestamps in nanoseconds.
+        let timestamp = encode_timestamp(match log.get_timestamp() {
+            Some(Value::Timestamp(timestamp)) => Some(*timestamp),
+            _ => None,
+        });
+
+        influx_line_protocol(
+            ProtocolVersion::V1,
+            &measurement,
+            Some(self.encode_tags(&log)),
+            Some(self.encode_fields(&log)),
+            timestamp,
+            buffer,
+        )
+        .map_err(|error_message| {
+            emit!(InfluxdbEncodingError {
+                error_message,
+                count: 1
+            });
+            Self::Error::SerializingError(error_message.into())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_util::codec::Encoder as _;
+
+    use super::*;
+    use crate::sinks::influxdb::test_util::{assert_fields, split_line_protocol, ts};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<InfluxDbIlpConfig>();
+    }
+
+    fn encode(config: &str, log: LogEvent) -> Option<String> {
+        let config = toml::from_str::<InfluxDbIlpConfig>(config).unwrap();
+        let mut buffer = BytesMut::new();
+        config
+            .encoder()
+            .encode(log.into(), &mut buffer)
+            .ok()
+            .map(|()| String::from_utf8(buffer.to_vec()).unwrap())
+    }
+
+    fn log() -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("host", "web-1");
+        log.insert("service", "api");
+        log.insert("http.status", 200);
+        log.insert("http.duration", 0.25);
+        log.insert("timestamp", ts());
+        log
+    }
+
+    #[test]
+    fn encodes_mapped_tags_and_fields() {
+        let line = encode(
+            r#"
+            address = "localhost:9009"
+            measurement = "{{ service }}_logs"
+            tags.hostname = "host"
+            tags.missing = "missing"
+            fields.status = "http.status"
+            fields.body = "message"
+            "#,
+            log(),
+        )
+        .unwrap();
+
+        let (measurement, tags, fields, timestamp) = split_line_protocol(&line);
+        assert_eq!(measurement, "api_logs");
+        assert_eq!(tags, "hostname=web-1");
+        assert_fields(fields, vec!["status=200i", "body=\"hello\""]);
+        assert_eq!(timestamp, "1542182950000000011\n");
+    }
+
+    #[test]
+    fn encodes_all_fields_without_mapping() {
+        let line = encode(
+            r#"
+            address = "localhost:9009"
+            measurement = "logs"
+            tags.host = "host"
+            "#,
+            log(),
+        )
+        .unwrap();
+
+        let (measurement, tags, fields, timestamp) = split_line_protocol(&line);
+        assert_eq!(measurement, "logs");
+        assert_eq!(tags, "host=web-1");
+        assert_fields(
+            fields,
+            vec![
+                "message=\"hello\"",
+                "service=\"api\"",
+                "http.status=200i",
+                "http.duration=0.25",
+            ],
+        );
+        assert_eq!(timestamp, "1542182950000000011\n");
+    }
+
+    #[test]
+    fn drops_events_without_fields() {
+        let config = r#"
+            address = "localhost:9009"
+            measurement = "logs"
+            tags.host = "host"
+            fields.status = "missing"
+        "#;
+        assert!(encode(config, log()).is_none());
+
+        let config = r#"
+            address = "localhost:9009"
+            measurement = "{{ missing }}"
+        "#;
+        assert!(encode(config, log()).is_none());
+    }
+}
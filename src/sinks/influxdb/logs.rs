@@ -19,8 +19,8 @@ use crate::{
     internal_events::InfluxdbEncodingError,
     sinks::{
         influxdb::{
-            encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
-            InfluxDb1Settings, InfluxDb2Settings, ProtocolVersion,
+            encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, to_field,
+            Field, InfluxDb1Settings, InfluxDb2Settings, ProtocolVersion,
         },
         util::{
             http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
@@ -390,15 +390,6 @@ impl InfluxDbLogsConfig {
     }
 }
 
-fn to_field(value: &Value) -> Field {
-    match value {
-        Value::Integer(num) => Field::Int(*num),
-        Value::Float(num) => Field::Float(num.into_inner()),
-        Value::Boolean(b) => Field::Bool(*b),
-        _ => Field::String(value.to_string_lossy().into_owned()),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use chrono::{offset::TimeZone, Utc};
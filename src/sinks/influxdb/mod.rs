@@ -1,3 +1,4 @@
+pub mod ilp;
 pub mod logs;
 pub mod metrics;
 
@@ -11,7 +12,7 @@ use snafu::{ResultExt, Snafu};
 use tower::Service;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
-use vector_core::event::MetricTags;
+use vector_core::event::{MetricTags, Value};
 
 use crate::http::HttpClient;
 
@@ -340,6 +341,15 @@ fn encode_string(key: &str, output: &mut BytesMut) {
     }
 }
 
+pub(in crate::sinks) fn to_field(value: &Value) -> Field {
+    match value {
+        Value::Integer(num) => Field::Int(*num),
+        Value::Float(num) => Field::Float(num.into_inner()),
+        Value::Boolean(b) => Field::Bool(*b),
+        _ => Field::String(value.to_string_lossy().into_owned()),
+    }
+}
+
 pub(in crate::sinks) fn encode_timestamp(timestamp: Option<DateTime<Utc>>) -> i64 {
     if let Some(ts) = timestamp {
         ts.timestamp_nanos()
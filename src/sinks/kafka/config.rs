@@ -23,6 +23,60 @@ use crate::{
 
 pub(crate) const QUEUED_MIN_MESSAGES: u64 = 100000;
 
+/// The behavior for events whose topic does not exist in the cluster.
+///
+/// An unknown topic is only reported by the producer once `librdkafka_options.topic.metadata.propagation.max.ms`
+/// has elapsed, which defaults to 30 seconds.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The kind of unknown topic behavior."))]
+pub enum UnknownTopicBehavior {
+    /// The events are rejected as failing to be delivered.
+    #[default]
+    Reject,
+
+    /// The events are dropped and reported through the `kafka_unknown_topic_events_total` metric.
+    Drop,
+
+    /// The events are written to a fallback topic instead.
+    Fallback {
+        /// The topic to write the events to.
+        #[configurable(metadata(docs::examples = "unrouted"))]
+        topic: String,
+    },
+
+    /// The topic is created, after which the events are written to it.
+    Create {
+        /// The number of partitions of the created topic.
+        #[serde(default = "default_num_partitions")]
+        num_partitions: i32,
+
+        /// The replication factor of the created topic.
+        #[serde(default = "default_replication_factor")]
+        replication_factor: i32,
+    },
+}
+
+const fn default_num_partitions() -> i32 {
+    1
+}
+
+const fn default_replication_factor() -> i32 {
+    1
+}
+
+/// Metrics configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    /// Expose producer queue metrics for all topics and partitions.
+    ///
+    /// Metric names start with `kafka_partition_`. As one series is exposed per partition, this
+    /// can produce a large number of metrics on topics with many partitions.
+    pub partition_metrics: bool,
+}
+
 /// Configuration for the `kafka` sink.
 #[serde_as]
 #[configurable_component(sink("kafka"))]
@@ -46,6 +100,20 @@ pub struct KafkaSinkConfig {
     ))]
     pub topic: Template,
 
+    /// The Kafka partition to write events to.
+    ///
+    /// The rendered value must be a partition number. If unspecified, the partition is chosen by
+    /// the producer's partitioner, based on the key of the event.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "0", docs::examples = "{{ partition }}"))]
+    pub partition: Option<Template>,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub unknown_topic: UnknownTopicBehavior,
+
     /// The log field name or tag key to use for the topic key.
     ///
     /// If the field does not exist in the log or in the tags, a blank value is used. If
@@ -110,6 +178,11 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<String>,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub metrics: Metrics,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -249,6 +322,8 @@ impl GenerateConfig for KafkaSinkConfig {
         toml::Value::try_from(Self {
             bootstrap_servers: "10.14.22.123:9092,10.14.23.332:9092".to_owned(),
             topic: Template::try_from("topic-1234".to_owned()).unwrap(),
+            partition: None,
+            unknown_topic: Default::default(),
             key_field: Some("user_id".to_owned()),
             encoding: JsonSerializerConfig::default().into(),
             batch: Default::default(),
@@ -258,6 +333,7 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            metrics: Default::default(),
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -7,7 +7,9 @@ use tokio_util::codec::Encoder as _;
 use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, Finalizable, Value},
-    internal_events::{KafkaHeaderExtractionError, TemplateRenderingError},
+    internal_events::{
+        KafkaHeaderExtractionError, KafkaInvalidPartitionError, TemplateRenderingError,
+    },
     sinks::{
         kafka::service::{KafkaRequest, KafkaRequestMetadata},
         util::metadata::RequestMetadataBuilder,
@@ -19,6 +21,7 @@ pub struct KafkaRequestBuilder {
     pub key_field: Option<String>,
     pub headers_key: Option<String>,
     pub topic_template: Template,
+    pub partition_template: Option<Template>,
     pub transformer: Transformer,
     pub encoder: Encoder<()>,
 }
@@ -36,6 +39,10 @@ impl KafkaRequestBuilder {
                 });
             })
             .ok()?;
+        let partition = match &self.partition_template {
+            Some(template) => Some(render_partition(template, &event)?),
+            None => None,
+        };
 
         let metadata_builder = RequestMetadataBuilder::from_events(&event);
 
@@ -45,6 +52,7 @@ impl KafkaRequestBuilder {
             timestamp_millis: get_timestamp_millis(&event),
             headers: get_headers(&event, &self.headers_key),
             topic,
+            partition,
         };
         self.transformer.transform(&mut event);
         let mut body = BytesMut::new();
@@ -62,6 +70,25 @@ impl KafkaRequestBuilder {
     }
 }
 
+fn render_partition(template: &Template, event: &Event) -> Option<i32> {
+    let partition = template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                field: Some("partition"),
+                drop_event: true,
+                error,
+            });
+        })
+        .ok()?;
+
+    partition
+        .trim()
+        .parse()
+        .map_err(|_| emit!(KafkaInvalidPartitionError { value: &partition }))
+        .ok()
+}
+
 fn get_key(event: &Event, key_field: &Option<String>) -> Option<Bytes> {
     key_field.as_ref().and_then(|key_field| match event {
         Event::Log(log) => log
@@ -143,4 +170,18 @@ mod tests {
         assert_eq!(headers.get(1).key, "b-key");
         assert_eq!(headers.get(1).value.unwrap(), "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_render_partition() {
+        let template = Template::try_from("{{ partition }}").unwrap();
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        assert_eq!(render_partition(&template, &event), None);
+
+        event.as_mut_log().insert("partition", "not a number");
+        assert_eq!(render_partition(&template, &event), None);
+
+        event.as_mut_log().insert("partition", 3);
+        assert_eq!(render_partition(&template, &event), Some(3));
+    }
 }
@@ -1,11 +1,18 @@
-use std::task::{Context, Poll};
+use std::{
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use rdkafka::{
+    admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
+    client::DefaultClientContext,
     error::KafkaError,
     message::OwnedHeaders,
     producer::{FutureProducer, FutureRecord},
+    types::RDKafkaErrorCode,
     util::Timeout,
 };
 use tower::Service;
@@ -19,7 +26,9 @@ use vector_core::{
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::KafkaUnknownTopicEventsDropped,
     kafka::KafkaStatisticsContext,
+    sinks::kafka::config::UnknownTopicBehavior,
 };
 
 pub struct KafkaRequest {
@@ -34,10 +43,11 @@ pub struct KafkaRequestMetadata {
     pub timestamp_millis: Option<i64>,
     pub headers: Option<OwnedHeaders>,
     pub topic: String,
+    pub partition: Option<i32>,
 }
 
 pub struct KafkaResponse {
-    event_byte_size: usize,
+    events_sent: CountByteSize,
 }
 
 impl DriverResponse for KafkaResponse {
@@ -46,7 +56,7 @@ impl DriverResponse for KafkaResponse {
     }
 
     fn events_sent(&self) -> CountByteSize {
-        CountByteSize(1, self.event_byte_size)
+        self.events_sent
     }
 }
 
@@ -65,16 +75,77 @@ impl MetaDescriptive for KafkaRequest {
 #[derive(Clone)]
 pub struct KafkaService {
     kafka_producer: FutureProducer<KafkaStatisticsContext>,
+    admin_client: Option<Arc<AdminClient<DefaultClientContext>>>,
+    unknown_topic: UnknownTopicBehavior,
     bytes_sent: Registered<BytesSent>,
 }
 
 impl KafkaService {
-    pub(crate) fn new(kafka_producer: FutureProducer<KafkaStatisticsContext>) -> KafkaService {
+    pub(crate) fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        admin_client: Option<AdminClient<DefaultClientContext>>,
+        unknown_topic: UnknownTopicBehavior,
+    ) -> KafkaService {
         KafkaService {
             kafka_producer,
+            admin_client: admin_client.map(Arc::new),
+            unknown_topic,
             bytes_sent: register!(BytesSent::from(Protocol("kafka".into()))),
         }
     }
+
+    async fn send(&self, topic: &str, request: &KafkaRequest) -> Result<(), KafkaError> {
+        let mut record = FutureRecord::to(topic).payload(request.body.as_ref());
+        if let Some(key) = &request.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(partition) = request.metadata.partition {
+            record = record.partition(partition);
+        }
+        if let Some(timestamp) = request.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = &request.metadata.headers {
+            record = record.headers(headers.clone());
+        }
+
+        // rdkafka will internally retry forever if the queue is full
+        self.kafka_producer
+            .send(record, Timeout::Never)
+            .await
+            .map(|(_partition, _offset)| ())
+            .map_err(|(kafka_err, _original_record)| kafka_err)
+    }
+
+    async fn create_topic(
+        &self,
+        topic: &str,
+        num_partitions: i32,
+        replication_factor: i32,
+    ) -> Result<(), KafkaError> {
+        let admin_client = self
+            .admin_client
+            .as_ref()
+            .expect("admin client must exist when creating unknown topics");
+        let new_topic = NewTopic::new(
+            topic,
+            num_partitions,
+            TopicReplication::Fixed(replication_factor),
+        );
+
+        for result in admin_client
+            .create_topics(&[new_topic], &AdminOptions::new())
+            .await?
+        {
+            match result {
+                // Another request may have created the topic concurrently.
+                Ok(_) | Err((_, RDKafkaErrorCode::TopicAlreadyExists)) => {}
+                Err((_, code)) => return Err(KafkaError::AdminOp(code)),
+            }
+        }
+        debug!(message = "Created unknown topic.", %topic);
+        Ok(())
+    }
 }
 
 impl Service<KafkaRequest> for KafkaService {
@@ -91,29 +162,224 @@ impl Service<KafkaRequest> for KafkaService {
 
         Box::pin(async move {
             let event_byte_size = request.get_metadata().events_byte_size();
+            let topic = &request.metadata.topic;
+
+            let sent = send_to_topic(
+                topic,
+                &this.unknown_topic,
+                |topic| this.send(topic, &request),
+                |topic, num_partitions, replication_factor| {
+                    this.create_topic(topic, num_partitions, replication_factor)
+                },
+            )
+            .await?;
+
+            if !sent {
+                return Ok(KafkaResponse {
+                    events_sent: CountByteSize(0, 0),
+                });
+            }
+
+            this.bytes_sent.emit(ByteSize(
+                request.body.len() + request.metadata.key.map(|x| x.len()).unwrap_or(0),
+            ));
+            Ok(KafkaResponse {
+                events_sent: CountByteSize(1, event_byte_size),
+            })
+        })
+    }
+}
 
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
+/// Sends a request to its topic with `send`, handling the topic being unknown as configured.
+///
+/// Returns whether the request was sent, which it isn't once dropped.
+async fn send_to_topic<'a, S, SF, C, CF>(
+    topic: &'a str,
+    unknown_topic: &'a UnknownTopicBehavior,
+    send: S,
+    create: C,
+) -> Result<bool, KafkaError>
+where
+    S: Fn(&'a str) -> SF,
+    SF: Future<Output = Result<(), KafkaError>>,
+    C: FnOnce(&'a str, i32, i32) -> CF,
+    CF: Future<Output = Result<(), KafkaError>>,
+{
+    match send(topic).await {
+        Err(error) if is_unknown_topic(&error) => match unknown_topic {
+            UnknownTopicBehavior::Reject => Err(error),
+            UnknownTopicBehavior::Drop => {
+                emit!(KafkaUnknownTopicEventsDropped { topic, count: 1 });
+                Ok(false)
             }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
+            UnknownTopicBehavior::Fallback { topic: fallback } => {
+                send(fallback).await.map(|()| true)
             }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
+            UnknownTopicBehavior::Create {
+                num_partitions,
+                replication_factor,
+            } => {
+                create(topic, *num_partitions, *replication_factor).await?;
+                send(topic).await.map(|()| true)
             }
+        },
+        result => result.map(|()| true),
+    }
+}
+
+fn is_unknown_topic(error: &KafkaError) -> bool {
+    matches!(
+        error,
+        KafkaError::MessageProduction(
+            RDKafkaErrorCode::UnknownTopic | RDKafkaErrorCode::UnknownTopicOrPartition
+        )
+    )
+}
 
-            // rdkafka will internally retry forever if the queue is full
-            match this.kafka_producer.send(record, Timeout::Never).await {
-                Ok((_partition, _offset)) => {
-                    this.bytes_sent.emit(ByteSize(
-                        request.body.len() + request.metadata.key.map(|x| x.len()).unwrap_or(0),
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashSet, future::ready};
+
+    use super::*;
+
+    /// A cluster in which only the known topics exist, recording the topics sent to.
+    #[derive(Default)]
+    struct Cluster {
+        known: RefCell<HashSet<String>>,
+        sent: RefCell<Vec<String>>,
+        created: RefCell<Vec<(String, i32, i32)>>,
+    }
+
+    impl Cluster {
+        fn with_topic(topic: &str) -> Self {
+            let cluster = Self::default();
+            cluster.known.borrow_mut().insert(topic.to_owned());
+            cluster
+        }
+
+        async fn send_to_topic(
+            &self,
+            topic: &str,
+            unknown_topic: &UnknownTopicBehavior,
+        ) -> Result<bool, KafkaError> {
+            send_to_topic(
+                topic,
+                unknown_topic,
+                |topic| {
+                    ready(if self.known.borrow().contains(topic) {
+                        self.sent.borrow_mut().push(topic.to_owned());
+                        Ok(())
+                    } else {
+                        Err(KafkaError::MessageProduction(
+                            RDKafkaErrorCode::UnknownTopicOrPartition,
+                        ))
+                    })
+                },
+                |topic, num_partitions, replication_factor| {
+                    self.known.borrow_mut().insert(topic.to_owned());
+                    self.created.borrow_mut().push((
+                        topic.to_owned(),
+                        num_partitions,
+                        replication_factor,
                     ));
-                    Ok(KafkaResponse { event_byte_size })
-                }
-                Err((kafka_err, _original_record)) => Err(kafka_err),
-            }
-        })
+                    ready(Ok(()))
+                },
+            )
+            .await
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_to_known_topics() {
+        let cluster = Cluster::with_topic("logs");
+        let sent = cluster
+            .send_to_topic("logs", &UnknownTopicBehavior::Drop)
+            .await;
+        assert!(sent.unwrap());
+        assert_eq!(*cluster.sent.borrow(), ["logs"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_topics() {
+        let cluster = Cluster::default();
+        let sent = cluster
+            .send_to_topic("logs", &UnknownTopicBehavior::Reject)
+            .await;
+        assert!(matches!(
+            sent,
+            Err(KafkaError::MessageProduction(
+                RDKafkaErrorCode::UnknownTopicOrPartition
+            ))
+        ));
+        assert!(cluster.sent.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn drops_unknown_topics() {
+        let cluster = Cluster::default();
+        let sent = cluster
+            .send_to_topic("logs", &UnknownTopicBehavior::Drop)
+            .await;
+        assert!(!sent.unwrap());
+        assert!(cluster.sent.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sends_unknown_topics_to_fallback() {
+        let cluster = Cluster::with_topic("unrouted");
+        let fallback = UnknownTopicBehavior::Fallback {
+            topic: "unrouted".to_owned(),
+        };
+        let sent = cluster.send_to_topic("logs", &fallback).await;
+        assert!(sent.unwrap());
+        assert_eq!(*cluster.sent.borrow(), ["unrouted"]);
+
+        // A fallback topic that is unknown too fails the request.
+        let cluster = Cluster::default();
+        let sent = cluster.send_to_topic("logs", &fallback).await;
+        assert!(sent.is_err());
+    }
+
+    #[tokio::test]
+    async fn creates_unknown_topics() {
+        let cluster = Cluster::default();
+        let create = UnknownTopicBehavior::Create {
+            num_partitions: 3,
+            replication_factor: 2,
+        };
+        let sent = cluster.send_to_topic("logs", &create).await;
+        assert!(sent.unwrap());
+        assert_eq!(*cluster.created.borrow(), [("logs".to_owned(), 3, 2)]);
+        assert_eq!(*cluster.sent.borrow(), ["logs"]);
+
+        // Known topics are not created again.
+        let sent = cluster.send_to_topic("logs", &create).await;
+        assert!(sent.unwrap());
+        assert_eq!(cluster.created.borrow().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_not_handled() {
+        let cluster = Cluster::default();
+        let create = UnknownTopicBehavior::Create {
+            num_partitions: 1,
+            replication_factor: 1,
+        };
+        let sent = send_to_topic(
+            "logs",
+            &create,
+            |_| {
+                ready(Err(KafkaError::MessageProduction(
+                    RDKafkaErrorCode::MessageSizeTooLarge,
+                )))
+            },
+            |_, _, _| {
+                cluster.created.borrow_mut().push(("logs".to_owned(), 1, 1));
+                ready(Ok(()))
+            },
+        )
+        .await;
+        assert!(sent.is_err());
+        assert!(cluster.created.borrow().is_empty());
     }
 }
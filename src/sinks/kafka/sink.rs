@@ -10,7 +10,7 @@ use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
 
-use super::config::{KafkaRole, KafkaSinkConfig};
+use super::config::{KafkaRole, KafkaSinkConfig, UnknownTopicBehavior};
 use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, LogEvent},
@@ -39,15 +39,20 @@ pub struct KafkaSink {
     encoder: Encoder<()>,
     service: KafkaService,
     topic: Template,
+    partition: Option<Template>,
     key_field: Option<String>,
     headers_key: Option<String>,
 }
 
 pub(crate) fn create_producer(
     client_config: ClientConfig,
+    expose_partition_metrics: bool,
 ) -> crate::Result<FutureProducer<KafkaStatisticsContext>> {
     let producer = client_config
-        .create_with_context(KafkaStatisticsContext::default())
+        .create_with_context(KafkaStatisticsContext {
+            expose_partition_metrics,
+            ..Default::default()
+        })
         .context(KafkaCreateFailedSnafu)?;
     Ok(producer)
 }
@@ -55,7 +60,13 @@ pub(crate) fn create_producer(
 impl KafkaSink {
     pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
-        let producer = create_producer(producer_config)?;
+        let producer = create_producer(producer_config.clone(), config.metrics.partition_metrics)?;
+        let admin = match config.unknown_topic {
+            UnknownTopicBehavior::Create { .. } => {
+                Some(producer_config.create().context(KafkaCreateFailedSnafu)?)
+            }
+            _ => None,
+        };
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
@@ -64,8 +75,9 @@ impl KafkaSink {
             headers_key: config.headers_key,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service: KafkaService::new(producer, admin, config.unknown_topic),
             topic: config.topic,
+            partition: config.partition,
             key_field: config.key_field,
         })
     }
@@ -77,10 +89,15 @@ impl KafkaSink {
             key_field: self.key_field,
             headers_key: self.headers_key,
             topic_template: self.topic,
+            partition_template: self.partition,
             transformer: self.transformer,
             encoder: self.encoder,
         };
 
+        // Events aren't batched by partition here, unlike in the other templated sinks: each event
+        // is handed over to librdkafka, whose queue batches them by topic partition and is bounded
+        // by `queue.buffering.max.messages` and `queue.buffering.max.kbytes` whatever the number
+        // of rendered topics.
         input
             .filter_map(|event|
                 // request_builder is fallible but the places it can fail are emitting
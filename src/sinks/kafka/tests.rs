@@ -25,7 +25,7 @@ mod integration_test {
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
-                config::{KafkaRole, KafkaSinkConfig},
+                config::{KafkaRole, KafkaSinkConfig, UnknownTopicBehavior},
                 sink::KafkaSink,
                 *,
             },
@@ -57,6 +57,8 @@ mod integration_test {
         let config = KafkaSinkConfig {
             bootstrap_servers: kafka_address(9091),
             topic: Template::try_from(topic.clone()).unwrap(),
+            partition: None,
+            unknown_topic: Default::default(),
             key_field: None,
             encoding: TextSerializerConfig::default().into(),
             batch: BatchConfig::default(),
@@ -66,6 +68,7 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            metrics: Default::default(),
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -109,6 +112,8 @@ mod integration_test {
         let config = KafkaSinkConfig {
             bootstrap_servers: kafka_address(9091),
             topic: Template::try_from(format!("{}-%Y%m%d", topic)).unwrap(),
+            partition: None,
+            unknown_topic: Default::default(),
             compression: KafkaCompression::None,
             encoding: TextSerializerConfig::default().into(),
             key_field: None,
@@ -121,6 +126,7 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            metrics: Default::default(),
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -231,6 +237,67 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_creates_unknown_topic() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let server = kafka_address(9091);
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            partition: None,
+            unknown_topic: UnknownTopicBehavior::Create {
+                num_partitions: 3,
+                replication_factor: 1,
+            },
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            // The broker would otherwise create the topic itself, with a single partition, and
+            // the topic would only be reported as unknown after 30 seconds.
+            librdkafka_options: HashMap::from([
+                ("allow.auto.create.topics".to_owned(), "false".to_owned()),
+                (
+                    "topic.metadata.propagation.max.ms".to_owned(),
+                    "1000".to_owned(),
+                ),
+            ]),
+            headers_key: None,
+            metrics: Default::default(),
+            acknowledgements: Default::default(),
+        };
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_input, events) = random_lines_with_stream(100, 10, Some(batch));
+        let sink = VectorSink::from_event_streamsink(KafkaSink::new(config).unwrap());
+        sink.run(events).await.expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        let metadata = consumer
+            .fetch_metadata(Some(&topic), Duration::from_secs(3))
+            .unwrap();
+        let partitions = metadata.topics()[0].partitions();
+        assert_eq!(partitions.len(), 3);
+
+        let messages = (0..3)
+            .map(|partition| {
+                let (low, high) = consumer
+                    .fetch_watermarks(&topic, partition, Duration::from_secs(3))
+                    .unwrap();
+                high - low
+            })
+            .sum::<i64>();
+        assert_eq!(messages, 10);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -243,6 +310,8 @@ mod integration_test {
         let config = KafkaSinkConfig {
             bootstrap_servers: server.clone(),
             topic: Template::try_from(format!("{}-%Y%m%d", topic)).unwrap(),
+            partition: None,
+            unknown_topic: Default::default(),
             key_field: None,
             encoding: TextSerializerConfig::default().into(),
             batch: BatchConfig::default(),
@@ -252,6 +321,7 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            metrics: Default::default(),
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -11,7 +11,10 @@ use crate::{
     http::{Auth, HttpClient, MaybeAuth},
     schema,
     sinks::{
-        util::{BatchConfig, Compression, SinkBatchSettings, TowerRequestConfig, UriSerde},
+        util::{
+            BatchConfig, Compression, PartitionLimitsConfig, SinkBatchSettings, TowerRequestConfig,
+            UriSerde,
+        },
         VectorSink,
     },
     template::Template,
@@ -82,6 +85,9 @@ pub struct LokiConfig {
     ///
     /// When running Loki locally, a tenant ID is not required.
     ///
+    /// If templated, the events of each tenant are batched and sent separately. When Loki rate
+    /// limits a tenant, its requests are backed off without delaying the other tenants.
+    ///
     /// [tenant_id]: https://grafana.com/docs/loki/latest/operations/multi-tenancy/
     #[configurable(metadata(
         docs::examples = "some_tenant_id",
@@ -134,6 +140,10 @@ pub struct LokiConfig {
     #[serde(default)]
     pub batch: BatchConfig<LokiDefaultBatchSettings>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub partitions: PartitionLimitsConfig,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
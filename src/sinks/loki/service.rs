@@ -1,9 +1,17 @@
-use std::task::{Context, Poll};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use http::StatusCode;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use snafu::Snafu;
+use tokio::time::sleep;
 use tower::Service;
 use tracing::Instrument;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
@@ -16,7 +24,8 @@ use vector_core::{
 use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression};
 use crate::{
     http::{Auth, HttpClient},
-    sinks::util::{retries::RetryLogic, UriSerde},
+    internal_events::LokiOutOfOrderEventDropped,
+    sinks::util::{http::retry_after, retries::RetryLogic, UriSerde},
 };
 
 #[derive(Clone)]
@@ -90,10 +99,77 @@ impl MetaDescriptive for LokiRequest {
     }
 }
 
+/// The first delay applied to the requests of a rate limited tenant.
+const MIN_TENANT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The longest delay applied to the requests of a rate limited tenant.
+const MAX_TENANT_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct BackoffState {
+    delay: Duration,
+    until: Instant,
+}
+
+/// Delays the requests of the tenants Loki is rate limiting, without holding back the requests of
+/// the other tenants.
+#[derive(Clone, Debug, Default)]
+struct TenantBackoff(Arc<Mutex<HashMap<Option<String>, BackoffState>>>);
+
+impl TenantBackoff {
+    /// Returns how long to wait before sending a request for the tenant.
+    fn remaining(&self, tenant_id: &Option<String>) -> Option<Duration> {
+        let states = self.0.lock().expect("poisoned lock");
+        states
+            .get(tenant_id)
+            .and_then(|state| state.until.checked_duration_since(Instant::now()))
+    }
+
+    /// Backs off the tenant further, waiting at least as long as Loki asked to.
+    fn rate_limited(&self, tenant_id: &Option<String>, retry_after: Option<Duration>) {
+        let mut states = self.0.lock().expect("poisoned lock");
+        let delay = states
+            .get(tenant_id)
+            .map_or(MIN_TENANT_BACKOFF, |state| state.delay * 2)
+            .min(MAX_TENANT_BACKOFF)
+            .max(retry_after.unwrap_or_default());
+        states.insert(
+            tenant_id.clone(),
+            BackoffState {
+                delay,
+                until: Instant::now() + delay,
+            },
+        );
+    }
+
+    fn succeeded(&self, tenant_id: &Option<String>) {
+        self.0.lock().expect("poisoned lock").remove(tenant_id);
+    }
+}
+
+static OUT_OF_ORDER_IGNORED: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"total ignored: (\d+) out of \d+").unwrap());
+
+/// Returns how many entries Loki ignored for being out of order, when the body of a response
+/// rejecting a push reports it.
+///
+/// Loki still stores the other entries of the push in that case.
+fn out_of_order_ignored(body: &str, event_count: usize) -> Option<usize> {
+    if !body.contains("out of order") && !body.contains("too far behind") {
+        return None;
+    }
+    let ignored = OUT_OF_ORDER_IGNORED
+        .captures(body)
+        .and_then(|captures| captures[1].parse().ok())
+        .unwrap_or(event_count);
+    Some(ignored)
+}
+
 #[derive(Debug, Clone)]
 pub struct LokiService {
     endpoint: UriSerde,
     client: HttpClient,
+    backoff: TenantBackoff,
 }
 
 impl LokiService {
@@ -105,7 +181,11 @@ impl LokiService {
     ) -> crate::Result<Self> {
         let endpoint = endpoint.append_path(&path)?.with_auth(auth);
 
-        Ok(Self { client, endpoint })
+        Ok(Self {
+            client,
+            endpoint,
+            backoff: TenantBackoff::default(),
+        })
     }
 }
 
@@ -129,7 +209,8 @@ impl Service<LokiRequest> for LokiService {
 
         let metadata = request.get_metadata();
 
-        if let Some(tenant_id) = request.tenant_id {
+        let tenant_id = request.tenant_id;
+        if let Some(tenant_id) = &tenant_id {
             req = req.header("X-Scope-OrgID", tenant_id);
         }
 
@@ -145,14 +226,35 @@ impl Service<LokiRequest> for LokiService {
         }
 
         let mut client = self.client.clone();
+        let backoff = self.backoff.clone();
 
         Box::pin(async move {
+            if let Some(delay) = backoff.remaining(&tenant_id) {
+                sleep(delay).await;
+            }
+
             match client.call(req).in_current_span().await {
                 Ok(response) => {
                     let status = response.status();
 
                     if status.is_success() {
+                        backoff.succeeded(&tenant_id);
                         Ok(LokiResponse { metadata })
+                    } else if status == StatusCode::TOO_MANY_REQUESTS {
+                        backoff.rate_limited(&tenant_id, retry_after(response.headers()));
+                        Err(LokiError::ServerError { code: status })
+                    } else if status == StatusCode::BAD_REQUEST {
+                        let body = hyper::body::to_bytes(response.into_body())
+                            .await
+                            .unwrap_or_default();
+                        let body = String::from_utf8_lossy(&body);
+                        match out_of_order_ignored(&body, metadata.event_count()) {
+                            Some(count) => {
+                                emit!(LokiOutOfOrderEventDropped { count });
+                                Ok(LokiResponse { metadata })
+                            }
+                            None => Err(LokiError::ServerError { code: status }),
+                        }
                     } else {
                         Err(LokiError::ServerError { code: status })
                     }
@@ -162,3 +264,38 @@ impl Service<LokiRequest> for LokiService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tenant_backoff() {
+        let backoff = TenantBackoff::default();
+        let tenant = Some("tenant1".to_owned());
+        let other = Some("tenant2".to_owned());
+
+        backoff.rate_limited(&tenant, None);
+        assert!(backoff.remaining(&tenant).unwrap() <= MIN_TENANT_BACKOFF);
+        assert!(backoff.remaining(&other).is_none());
+
+        backoff.rate_limited(&tenant, None);
+        let remaining = backoff.remaining(&tenant).unwrap();
+        assert!(remaining > MIN_TENANT_BACKOFF && remaining <= MIN_TENANT_BACKOFF * 2);
+
+        backoff.rate_limited(&tenant, Some(Duration::from_secs(30)));
+        assert!(backoff.remaining(&tenant).unwrap() > Duration::from_secs(20));
+
+        backoff.succeeded(&tenant);
+        assert!(backoff.remaining(&tenant).is_none());
+    }
+
+    #[test]
+    fn parses_out_of_order_rejections() {
+        let body = "entry with timestamp 2021-11-10 10:00:00 +0000 UTC ignored, reason: \
+                    'entry out of order' for stream: {app=\"foo\"},\ntotal ignored: 2 out of 5";
+        assert_eq!(out_of_order_ignored(body, 5), Some(2));
+        assert_eq!(out_of_order_ignored("entry too far behind", 5), Some(5));
+        assert_eq!(out_of_order_ignored("invalid labels", 5), None);
+    }
+}
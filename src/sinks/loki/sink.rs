@@ -443,7 +443,9 @@ impl LokiSink {
                 remove_label_fields: config.remove_label_fields,
                 remove_timestamp: config.remove_timestamp,
             },
-            batch_settings: config.batch.into_batcher_settings()?,
+            batch_settings: config
+                .partitions
+                .apply(config.batch.into_batcher_settings()?),
             out_of_order_action: config.out_of_order_action,
             service,
             protocol,
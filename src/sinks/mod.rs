@@ -1,4 +1,6 @@
 #![allow(missing_docs)]
+use std::sync::Arc;
+
 use enum_dispatch::enum_dispatch;
 use futures::future::BoxFuture;
 use snafu::Snafu;
@@ -36,6 +38,8 @@ pub mod blackhole;
 pub mod clickhouse;
 #[cfg(feature = "sinks-console")]
 pub mod console;
+#[cfg(feature = "sinks-database")]
+pub mod database;
 #[cfg(feature = "sinks-databend")]
 pub mod databend;
 #[cfg(any(
@@ -73,7 +77,13 @@ pub mod mezmo;
 pub mod nats;
 #[cfg(feature = "sinks-new_relic")]
 pub mod new_relic;
-#[cfg(feature = "sinks-webhdfs")]
+#[cfg(any(
+    feature = "sinks-microsoft_teams",
+    feature = "sinks-pagerduty",
+    feature = "sinks-slack"
+))]
+pub mod notifications;
+#[cfg(any(feature = "sinks-sftp", feature = "sinks-webhdfs"))]
 pub mod opendal_common;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
@@ -90,6 +100,10 @@ pub mod redis;
 pub mod s3_common;
 #[cfg(feature = "sinks-sematext")]
 pub mod sematext;
+#[cfg(feature = "sinks-sftp")]
+pub mod sftp;
+#[cfg(feature = "sinks-smtp")]
+pub mod smtp;
 #[cfg(feature = "sinks-socket")]
 pub mod socket;
 #[cfg(feature = "sinks-splunk_hec")]
@@ -100,8 +114,12 @@ pub mod statsd;
 pub mod vector;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod webhdfs;
+#[cfg(feature = "sinks-webhook_batch")]
+pub mod webhook_batch;
 #[cfg(feature = "sinks-websocket")]
 pub mod websocket;
+#[cfg(feature = "sinks-websocket_server")]
+pub mod websocket_server;
 
 use vector_config::{configurable_component, NamedComponent};
 pub use vector_core::{config::Input, sink::VectorSink};
@@ -113,6 +131,9 @@ use crate::config::{
 
 pub type Healthcheck = BoxFuture<'static, crate::Result<()>>;
 
+/// Builds a new healthcheck of a running sink each time it is called.
+pub type HealthcheckFactory = Arc<dyn Fn() -> Healthcheck + Send + Sync>;
+
 /// Common build errors
 #[derive(Debug, Snafu)]
 pub enum BuildError {
@@ -210,6 +231,11 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Console"))]
     Console(console::ConsoleSinkConfig),
 
+    /// Insert log events as rows of a PostgreSQL or MySQL table.
+    #[cfg(feature = "sinks-database")]
+    #[configurable(metadata(docs::label = "Database"))]
+    Database(database::DatabaseSinkConfig),
+
     /// Deliver log data to a Databend database.
     #[cfg(feature = "sinks-databend")]
     Databend(databend::DatabendConfig),
@@ -299,6 +325,11 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Humio Metrics"))]
     HumioMetrics(humio::metrics::HumioMetricsConfig),
 
+    /// Write log events as InfluxDB line protocol over TCP, to InfluxDB or QuestDB.
+    #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
+    #[configurable(metadata(docs::label = "InfluxDB Line Protocol"))]
+    InfluxdbIlp(influxdb::ilp::InfluxDbIlpConfig),
+
     /// Deliver log event data to InfluxDB.
     #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
     #[configurable(metadata(docs::label = "InfluxDB Logs"))]
@@ -329,6 +360,11 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Loki"))]
     Loki(loki::LokiConfig),
 
+    /// Post events as adaptive cards to Microsoft Teams.
+    #[cfg(feature = "sinks-microsoft_teams")]
+    #[configurable(metadata(docs::label = "Microsoft Teams"))]
+    MicrosoftTeams(notifications::microsoft_teams::MicrosoftTeamsConfig),
+
     /// Publish observability data to subjects on the NATS messaging system.
     #[cfg(feature = "sinks-nats")]
     #[configurable(metadata(docs::label = "NATS"))]
@@ -339,11 +375,22 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "New Relic"))]
     NewRelic(new_relic::NewRelicConfig),
 
+    /// Send events as alerts to PagerDuty.
+    #[cfg(feature = "sinks-pagerduty")]
+    #[configurable(metadata(docs::label = "PagerDuty"))]
+    Pagerduty(notifications::pagerduty::PagerdutyConfig),
+
     /// Deliver log events to Papertrail from SolarWinds.
     #[cfg(feature = "sinks-papertrail")]
     #[configurable(metadata(docs::label = "Papertrail"))]
     Papertrail(papertrail::PapertrailConfig),
 
+    /// A sink defined outside of this crate.
+    ///
+    /// These are deserialized from the sinks registered with `register_sink!`, rather than by tag.
+    #[serde(skip)]
+    Plugin(crate::plugins::PluginSink),
+
     /// Expose metric events on a Prometheus compatible endpoint.
     #[cfg(feature = "sinks-prometheus")]
     #[configurable(metadata(docs::label = "Prometheus Exporter"))]
@@ -374,6 +421,21 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Sematext Metrics"))]
     SematextMetrics(sematext::metrics::SematextMetricsConfig),
 
+    /// Write batches of events as files to an SFTP server.
+    #[cfg(feature = "sinks-sftp")]
+    #[configurable(metadata(docs::label = "SFTP"))]
+    Sftp(sftp::SftpSinkConfig),
+
+    /// Post events as messages to Slack.
+    #[cfg(feature = "sinks-slack")]
+    #[configurable(metadata(docs::label = "Slack"))]
+    Slack(notifications::slack::SlackConfig),
+
+    /// Send events as emails through an SMTP server.
+    #[cfg(feature = "sinks-smtp")]
+    #[configurable(metadata(docs::label = "SMTP"))]
+    Smtp(smtp::SmtpSinkConfig),
+
     /// Deliver logs to a remote socket endpoint.
     #[cfg(feature = "sinks-socket")]
     #[configurable(metadata(docs::label = "Socket"))]
@@ -430,10 +492,20 @@ pub enum Sinks {
     #[configurable(metadata(docs::label = "Vector"))]
     Vector(vector::VectorConfig),
 
+    /// Deliver batches of observability events to HTTP APIs with templated requests.
+    #[cfg(feature = "sinks-webhook_batch")]
+    #[configurable(metadata(docs::label = "Webhook Batch"))]
+    WebhookBatch(webhook_batch::WebhookBatchConfig),
+
     /// Deliver observability event data to a websocket listener.
     #[cfg(feature = "sinks-websocket")]
     #[configurable(metadata(docs::label = "Websocket"))]
     Websocket(websocket::WebSocketSinkConfig),
+
+    /// Broadcast observability events to the clients connected to a WebSocket server.
+    #[cfg(feature = "sinks-websocket_server")]
+    #[configurable(metadata(docs::label = "WebSocket Server"))]
+    WebsocketServer(websocket_server::WebSocketServerSinkConfig),
 }
 
 impl NamedComponent for Sinks {
@@ -467,6 +539,8 @@ impl NamedComponent for Sinks {
             Self::Clickhouse(config) => config.get_component_name(),
             #[cfg(feature = "sinks-console")]
             Self::Console(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-database")]
+            Self::Database(config) => config.get_component_name(),
             #[cfg(feature = "sinks-databend")]
             Self::Databend(config) => config.get_component_name(),
             #[cfg(feature = "sinks-datadog_archives")]
@@ -504,6 +578,8 @@ impl NamedComponent for Sinks {
             #[cfg(feature = "sinks-humio")]
             Self::HumioMetrics(config) => config.get_component_name(),
             #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
+            Self::InfluxdbIlp(config) => config.get_component_name(),
+            #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
             Self::InfluxdbLogs(config) => config.get_component_name(),
             #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
             Self::InfluxdbMetrics(config) => config.get_component_name(),
@@ -515,12 +591,17 @@ impl NamedComponent for Sinks {
             Self::Logdna(config) => config.get_component_name(),
             #[cfg(feature = "sinks-loki")]
             Self::Loki(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-microsoft_teams")]
+            Self::MicrosoftTeams(config) => config.get_component_name(),
             #[cfg(feature = "sinks-nats")]
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sinks-new_relic")]
             Self::NewRelic(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-pagerduty")]
+            Self::Pagerduty(config) => config.get_component_name(),
             #[cfg(feature = "sinks-papertrail")]
             Self::Papertrail(config) => config.get_component_name(),
+            Self::Plugin(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
             Self::PrometheusExporter(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
@@ -533,6 +614,12 @@ impl NamedComponent for Sinks {
             Self::SematextLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-sematext")]
             Self::SematextMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-sftp")]
+            Self::Sftp(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-slack")]
+            Self::Slack(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-smtp")]
+            Self::Smtp(config) => config.get_component_name(),
             #[cfg(feature = "sinks-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sinks-splunk_hec")]
@@ -557,8 +644,12 @@ impl NamedComponent for Sinks {
             Self::UnitTestStream(config) => config.get_component_name(),
             #[cfg(feature = "sinks-vector")]
             Self::Vector(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-webhook_batch")]
+            Self::WebhookBatch(config) => config.get_component_name(),
             #[cfg(feature = "sinks-websocket")]
             Self::Websocket(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-websocket_server")]
+            Self::WebsocketServer(config) => config.get_component_name(),
         }
     }
 }
@@ -0,0 +1,238 @@
+//! The `microsoft_teams` sink.
+//!
+//! Posts each event as an [adaptive card][adaptive_card] to a Microsoft Teams incoming webhook.
+//!
+//! [adaptive_card]: https://adaptivecards.io
+
+use futures::{future, FutureExt};
+use http::Uri;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use snafu::ResultExt;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::event::Event;
+
+use super::{
+    sink::{build_sink, default_text, render, Notifier, RateLimit},
+    Severity, SeverityConfig,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{util::TowerRequestConfig, Healthcheck, UriParseSnafu, VectorSink},
+    template::Template,
+    tls::TlsConfig,
+};
+
+/// Microsoft Teams incoming webhooks accept about four messages per second.
+const RATE_LIMIT: RateLimit = RateLimit {
+    num: 4,
+    duration_secs: 1,
+};
+
+/// Configuration for the `microsoft_teams` sink.
+#[configurable_component(sink("microsoft_teams"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MicrosoftTeamsConfig {
+    /// The URL of the Microsoft Teams [incoming webhook][incoming_webhook] to post the cards to.
+    ///
+    /// [incoming_webhook]: https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/add-incoming-webhook
+    #[configurable(metadata(docs::examples = "${TEAMS_WEBHOOK_URL}"))]
+    pub webhook_url: SensitiveString,
+
+    /// The template of the title of the cards.
+    ///
+    /// The title is colored after the severity of the event.
+    #[configurable(metadata(docs::examples = "{{ host }} is down"))]
+    pub title: Option<Template>,
+
+    /// The template of the text of the cards, in the [Markdown subset][markdown] of adaptive
+    /// cards.
+    ///
+    /// [markdown]: https://learn.microsoft.com/en-us/adaptive-cards/authoring-cards/text-features
+    #[configurable(metadata(docs::examples = "**{{ service }}**: {{ message }}"))]
+    #[serde(default = "default_text")]
+    pub text: Template,
+
+    /// Templates of the facts listed under the text of the cards, after the severity.
+    #[configurable(metadata(docs::additional_props_description = "The template of a fact."))]
+    #[configurable(metadata(docs::examples = "example_facts()"))]
+    #[serde(default)]
+    pub facts: IndexMap<String, Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub severity: SeverityConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn example_facts() -> IndexMap<String, String> {
+    IndexMap::from([
+        ("Host".to_string(), "{{ host }}".to_string()),
+        ("Service".to_string(), "{{ service }}".to_string()),
+    ])
+}
+
+impl GenerateConfig for MicrosoftTeamsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"webhook_url = "https://example.webhook.office.com/webhookb2/XXXX""#)
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for MicrosoftTeamsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let uri = self
+            .webhook_url
+            .inner()
+            .parse::<Uri>()
+            .context(UriParseSnafu)?;
+        let notifier = TeamsNotifier {
+            title: self.title.clone(),
+            text: self.text.clone(),
+            facts: self.facts.clone(),
+            severity: self.severity.clone(),
+        };
+        let sink = build_sink(notifier, uri, &self.request, RATE_LIMIT, &self.tls, &cx)?;
+
+        // Checking a webhook would post a card to the channel.
+        Ok((sink, future::ok(()).boxed()))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+struct TeamsNotifier {
+    title: Option<Template>,
+    text: Template,
+    facts: IndexMap<String, Template>,
+    severity: SeverityConfig,
+}
+
+const fn color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::Error => "Attention",
+        Severity::Warning => "Warning",
+        Severity::Info => "Accent",
+    }
+}
+
+impl Notifier for TeamsNotifier {
+    fn payload(&self, event: &Event) -> Option<Value> {
+        let severity = self.severity.severity(event);
+
+        let mut body = Vec::with_capacity(3);
+        if let Some(title) = &self.title {
+            body.push(json!({
+                "type": "TextBlock",
+                "text": render(title, event, "title")?,
+                "size": "Medium",
+                "weight": "Bolder",
+                "color": color(severity),
+                "wrap": true,
+            }));
+        }
+        body.push(json!({
+            "type": "TextBlock",
+            "text": render(&self.text, event, "text")?,
+            "wrap": true,
+        }));
+
+        let mut facts = vec![json!({ "title": "Severity", "value": severity.as_str() })];
+        for (title, value) in &self.facts {
+            facts.push(json!({ "title": title, "value": render(value, event, "facts")? }));
+        }
+        body.push(json!({ "type": "FactSet", "facts": facts }));
+
+        Some(json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": body,
+                },
+            }],
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MicrosoftTeamsConfig>();
+    }
+
+    #[test]
+    fn renders_adaptive_card() {
+        let config = toml::from_str::<MicrosoftTeamsConfig>(
+            r#"
+            webhook_url = "https://example.webhook.office.com/webhookb2/XXXX"
+            title = "{{ host }} is down"
+            facts.Service = "{{ service }}"
+            severity.default = "critical"
+        "#,
+        )
+        .unwrap();
+        let notifier = TeamsNotifier {
+            title: config.title,
+            text: config.text,
+            facts: config.facts,
+            severity: config.severity,
+        };
+
+        let mut log = LogEvent::from("connection refused");
+        log.insert("host", "web-1");
+        log.insert("service", "api");
+        let payload = notifier.payload(&Event::from(log)).unwrap();
+        assert_eq!(
+            payload["attachments"][0]["content"]["body"],
+            json!([
+                {
+                    "type": "TextBlock",
+                    "text": "web-1 is down",
+                    "size": "Medium",
+                    "weight": "Bolder",
+                    "color": "Attention",
+                    "wrap": true,
+                },
+                { "type": "TextBlock", "text": "connection refused", "wrap": true },
+                {
+                    "type": "FactSet",
+                    "facts": [
+                        { "title": "Severity", "value": "critical" },
+                        { "title": "Service", "value": "api" },
+                    ],
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! Notification sinks.
+//!
+//! Sends each event as a notification to a chat or incident management service: Slack, Microsoft
+//! Teams or PagerDuty. The payloads are rendered from templates, their severity is mapped from a
+//! field of the events, and the requests are rate limited to the limits of each service by default.
+
+#[cfg(feature = "sinks-microsoft_teams")]
+pub mod microsoft_teams;
+#[cfg(feature = "sinks-pagerduty")]
+pub mod pagerduty;
+#[cfg(feature = "sinks-slack")]
+pub mod slack;
+
+mod service;
+mod severity;
+mod sink;
+
+pub use self::severity::{Severity, SeverityConfig};
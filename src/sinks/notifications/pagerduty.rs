@@ -0,0 +1,362 @@
+//! The `pagerduty` sink.
+//!
+//! Sends each event as an alert to the PagerDuty [Events API v2][events_api].
+//!
+//! [events_api]: https://developer.pagerduty.com/docs/events-api-v2/overview/
+
+use chrono::SecondsFormat;
+use futures::{future, FutureExt};
+use http::Uri;
+use serde_json::{json, Map, Value};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::event::{Event, Value as EventValue};
+
+use super::{
+    sink::{build_sink, default_text, render, Notifier, RateLimit},
+    SeverityConfig,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{util::TowerRequestConfig, Healthcheck, UriParseSnafu, VectorSink},
+    template::Template,
+    tls::TlsConfig,
+};
+
+/// The Events API accepts 120 events per minute for each routing key.
+const RATE_LIMIT: RateLimit = RateLimit {
+    num: 120,
+    duration_secs: 60,
+};
+
+/// The maximum length of the summary of an alert.
+const MAX_SUMMARY_LENGTH: usize = 1024;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("A `dedup_key` is required to {} alerts", action))]
+    MissingDedupKey { action: &'static str },
+}
+
+/// Configuration for the `pagerduty` sink.
+#[configurable_component(sink("pagerduty"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PagerdutyConfig {
+    /// The integration key of the service to send the alerts to.
+    #[configurable(metadata(docs::examples = "${PAGERDUTY_ROUTING_KEY}"))]
+    pub routing_key: SensitiveString,
+
+    /// The endpoint of the Events API.
+    #[configurable(metadata(docs::examples = "https://events.eu.pagerduty.com/v2/enqueue"))]
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub event_action: EventAction,
+
+    /// The template of the summary of the alerts.
+    ///
+    /// Summaries longer than 1024 characters are truncated.
+    #[configurable(metadata(docs::examples = "{{ host }}: {{ message }}"))]
+    #[serde(default = "default_text")]
+    pub summary: Template,
+
+    /// The template of the source of the alerts, such as the host they are about.
+    #[configurable(metadata(docs::examples = "{{ kubernetes.pod_name }}"))]
+    #[serde(default = "default_source")]
+    pub source: Template,
+
+    /// The template of the deduplication key of the alerts.
+    ///
+    /// Alerts with the same key are grouped into the same incident. Required to acknowledge or
+    /// resolve alerts.
+    #[configurable(metadata(docs::examples = "{{ host }}-{{ check }}"))]
+    pub dedup_key: Option<Template>,
+
+    /// The template of the component of the source the alerts are about.
+    #[configurable(metadata(docs::examples = "{{ service }}"))]
+    pub component: Option<Template>,
+
+    /// The template of the logical group of the components the alerts are about.
+    #[configurable(metadata(docs::examples = "{{ cluster }}"))]
+    pub group: Option<Template>,
+
+    /// The template of the class of the alerts.
+    #[configurable(metadata(docs::examples = "{{ check }}"))]
+    pub class: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub severity: SeverityConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The action of the alerts.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventAction {
+    /// Triggers an alert, opening an incident or adding it to the incident of its `dedup_key`.
+    #[default]
+    Trigger,
+
+    /// Acknowledges the incident of the `dedup_key` of the alert.
+    Acknowledge,
+
+    /// Resolves the incident of the `dedup_key` of the alert.
+    Resolve,
+}
+
+impl EventAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Trigger => "trigger",
+            Self::Acknowledge => "acknowledge",
+            Self::Resolve => "resolve",
+        }
+    }
+}
+
+fn default_endpoint() -> String {
+    "https://events.pagerduty.com/v2/enqueue".to_string()
+}
+
+fn default_source() -> Template {
+    Template::try_from("{{ host }}").expect("the default source template is valid")
+}
+
+impl GenerateConfig for PagerdutyConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"routing_key = "${PAGERDUTY_ROUTING_KEY}""#).unwrap()
+    }
+}
+
+impl PagerdutyConfig {
+    fn build_notifier(&self) -> crate::Result<PagerdutyNotifier> {
+        if self.event_action != EventAction::Trigger && self.dedup_key.is_none() {
+            return Err(BuildError::MissingDedupKey {
+                action: self.event_action.as_str(),
+            }
+            .into());
+        }
+
+        Ok(PagerdutyNotifier {
+            routing_key: self.routing_key.inner().to_string(),
+            event_action: self.event_action,
+            summary: self.summary.clone(),
+            source: self.source.clone(),
+            dedup_key: self.dedup_key.clone(),
+            component: self.component.clone(),
+            group: self.group.clone(),
+            class: self.class.clone(),
+            severity: self.severity.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for PagerdutyConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let uri = self.endpoint.parse::<Uri>().context(UriParseSnafu)?;
+        let notifier = self.build_notifier()?;
+        let sink = build_sink(notifier, uri, &self.request, RATE_LIMIT, &self.tls, &cx)?;
+
+        // The Events API has no endpoint to check a routing key without sending an alert.
+        Ok((sink, future::ok(()).boxed()))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+struct PagerdutyNotifier {
+    routing_key: String,
+    event_action: EventAction,
+    summary: Template,
+    source: Template,
+    dedup_key: Option<Template>,
+    component: Option<Template>,
+    group: Option<Template>,
+    class: Option<Template>,
+    severity: SeverityConfig,
+}
+
+impl PagerdutyNotifier {
+    /// Renders the optional fields of the payload, dropping the event if one cannot be rendered.
+    fn render_optional_fields(
+        &self,
+        event: &Event,
+        payload: &mut Map<String, Value>,
+    ) -> Option<()> {
+        for (field, template) in [
+            ("component", &self.component),
+            ("group", &self.group),
+            ("class", &self.class),
+        ] {
+            if let Some(template) = template {
+                payload.insert(field.to_string(), render(template, event, field)?.into());
+            }
+        }
+        Some(())
+    }
+}
+
+impl Notifier for PagerdutyNotifier {
+    fn payload(&self, event: &Event) -> Option<Value> {
+        let mut alert = json!({
+            "routing_key": self.routing_key,
+            "event_action": self.event_action.as_str(),
+        });
+        if let Some(dedup_key) = &self.dedup_key {
+            alert["dedup_key"] = render(dedup_key, event, "dedup_key")?.into();
+        }
+        if self.event_action != EventAction::Trigger {
+            return Some(alert);
+        }
+
+        let mut summary = render(&self.summary, event, "summary")?;
+        if let Some((index, _)) = summary.char_indices().nth(MAX_SUMMARY_LENGTH) {
+            summary.truncate(index);
+        }
+
+        let log = event.as_log();
+        let mut payload = Map::new();
+        payload.insert("summary".to_string(), summary.into());
+        payload.insert(
+            "source".to_string(),
+            render(&self.source, event, "source")?.into(),
+        );
+        payload.insert(
+            "severity".to_string(),
+            self.severity.severity(event).as_str().into(),
+        );
+        if let Some(EventValue::Timestamp(timestamp)) = log.get_timestamp() {
+            payload.insert(
+                "timestamp".to_string(),
+                timestamp
+                    .to_rfc3339_opts(SecondsFormat::Millis, true)
+                    .into(),
+            );
+        }
+        self.render_optional_fields(event, &mut payload)?;
+        payload.insert(
+            "custom_details".to_string(),
+            serde_json::to_value(log).ok()?,
+        );
+
+        alert["payload"] = Value::Object(payload);
+        Some(alert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PagerdutyConfig>();
+    }
+
+    fn config(extra: &str) -> PagerdutyConfig {
+        toml::from_str(&format!(
+            r#"
+            routing_key = "R0UT1NGK3Y"
+            dedup_key = "{{{{ host }}}}"
+            {}
+        "#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::default();
+        log.insert("message", "disk full");
+        log.insert("host", "db-1");
+        log.insert("level", "critical");
+        log.into()
+    }
+
+    #[test]
+    fn renders_trigger() {
+        let notifier = config(
+            r#"
+            component = "{{ host }}"
+            severity.field = "level"
+        "#,
+        )
+        .build_notifier()
+        .unwrap();
+
+        assert_eq!(
+            notifier.payload(&event()).unwrap(),
+            json!({
+                "routing_key": "R0UT1NGK3Y",
+                "event_action": "trigger",
+                "dedup_key": "db-1",
+                "payload": {
+                    "summary": "disk full",
+                    "source": "db-1",
+                    "severity": "critical",
+                    "component": "db-1",
+                    "custom_details": {
+                        "message": "disk full",
+                        "host": "db-1",
+                        "level": "critical",
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn renders_resolve() {
+        let notifier = config(r#"event_action = "resolve""#)
+            .build_notifier()
+            .unwrap();
+        assert_eq!(
+            notifier.payload(&event()).unwrap(),
+            json!({
+                "routing_key": "R0UT1NGK3Y",
+                "event_action": "resolve",
+                "dedup_key": "db-1",
+            })
+        );
+    }
+
+    #[test]
+    fn requires_dedup_key_to_resolve() {
+        let config = toml::from_str::<PagerdutyConfig>(
+            r#"
+            routing_key = "R0UT1NGK3Y"
+            event_action = "acknowledge"
+        "#,
+        )
+        .unwrap();
+        assert!(config.build_notifier().is_err());
+    }
+}
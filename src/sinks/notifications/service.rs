@@ -0,0 +1,135 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{StatusCode, Uri};
+use snafu::Snafu;
+use tower::Service;
+use tracing::Instrument;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    stream::DriverResponse,
+};
+
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::util::retries::RetryLogic,
+};
+
+#[derive(Clone)]
+pub struct NotificationRetryLogic;
+
+impl RetryLogic for NotificationRetryLogic {
+    type Error = NotificationError;
+    type Response = NotificationResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            NotificationError::ServerError { code } => match *code {
+                StatusCode::TOO_MANY_REQUESTS => true,
+                StatusCode::NOT_IMPLEMENTED => false,
+                _ if code.is_server_error() => true,
+                _ => false,
+            },
+            NotificationError::HttpError { .. } => true,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum NotificationError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: HttpError },
+}
+
+#[derive(Clone)]
+pub struct NotificationRequest {
+    pub payload: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for NotificationRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for NotificationRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub struct NotificationResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for NotificationResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+/// Posts the JSON payloads of the notifications to the webhook or API of a service.
+#[derive(Clone)]
+pub struct NotificationService {
+    client: HttpClient,
+    uri: Uri,
+}
+
+impl NotificationService {
+    pub(super) const fn new(client: HttpClient, uri: Uri) -> Self {
+        Self { client, uri }
+    }
+}
+
+impl Service<NotificationRequest> for NotificationService {
+    type Response = NotificationResponse;
+    type Error = NotificationError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: NotificationRequest) -> Self::Future {
+        let metadata = request.get_metadata();
+        let req = http::Request::post(&self.uri)
+            .header("Content-Type", "application/json")
+            .body(hyper::Body::from(request.payload))
+            .unwrap();
+
+        let mut client = self.client.clone();
+
+        Box::pin(async move {
+            match client.call(req).in_current_span().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        Ok(NotificationResponse { metadata })
+                    } else {
+                        Err(NotificationError::ServerError { code: status })
+                    }
+                }
+                Err(error) => Err(NotificationError::HttpError { error }),
+            }
+        })
+    }
+}
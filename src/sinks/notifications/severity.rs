@@ -0,0 +1,116 @@
+use indexmap::IndexMap;
+use vector_config::configurable_component;
+use vector_core::event::Event;
+
+/// The severity of a notification.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Critical.
+    Critical,
+
+    /// Error.
+    Error,
+
+    /// Warning.
+    Warning,
+
+    /// Informational.
+    #[default]
+    Info,
+}
+
+impl Severity {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "critical" => Some(Self::Critical),
+            "error" => Some(Self::Error),
+            "warning" => Some(Self::Warning),
+            "info" => Some(Self::Info),
+            _ => None,
+        }
+    }
+}
+
+/// How the severity of a notification is mapped from its event.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SeverityConfig {
+    /// The field of the event holding its severity.
+    ///
+    /// Its value is looked up in `mapping`, and otherwise used as is if it is one of `critical`,
+    /// `error`, `warning` or `info`, ignoring case. If the field is missing or its value cannot be
+    /// mapped, the `default` severity is used.
+    #[configurable(metadata(docs::examples = "level"))]
+    pub field: Option<String>,
+
+    /// Maps the values of the severity field to severities.
+    #[configurable(metadata(
+        docs::additional_props_description = "The severity of a value of the severity field."
+    ))]
+    #[serde(default)]
+    pub mapping: IndexMap<String, Severity>,
+
+    /// The severity of the events whose severity cannot be mapped.
+    #[serde(default)]
+    pub default: Severity,
+}
+
+impl SeverityConfig {
+    pub fn severity(&self, event: &Event) -> Severity {
+        self.field
+            .as_ref()
+            .and_then(|field| event.as_log().get(field.as_str()))
+            .and_then(|value| {
+                let value = value.to_string_lossy();
+                self.mapping
+                    .get(value.as_ref())
+                    .copied()
+                    .or_else(|| Severity::parse(&value))
+            })
+            .unwrap_or(self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn maps_severity_from_field() {
+        let config = toml::from_str::<SeverityConfig>(
+            r#"
+            field = "level"
+            mapping.fatal = "critical"
+            mapping.warn = "warning"
+            default = "error"
+        "#,
+        )
+        .unwrap();
+
+        let severity = |level: Option<&str>| {
+            let mut log = LogEvent::from("message");
+            if let Some(level) = level {
+                log.insert("level", level);
+            }
+            config.severity(&Event::from(log))
+        };
+        assert_eq!(severity(Some("fatal")), Severity::Critical);
+        assert_eq!(severity(Some("warn")), Severity::Warning);
+        assert_eq!(severity(Some("INFO")), Severity::Info);
+        assert_eq!(severity(Some("debug")), Severity::Error);
+        assert_eq!(severity(None), Severity::Error);
+    }
+}
@@ -0,0 +1,133 @@
+use bytes::Bytes;
+use futures_util::{stream::BoxStream, StreamExt};
+use http::Uri;
+use serde_json::Value;
+use tower::ServiceBuilder;
+use vector_core::{event::Event, sink::StreamSink};
+
+use super::service::{NotificationRequest, NotificationRetryLogic, NotificationService};
+use crate::{
+    config::SinkContext,
+    event::Finalizable,
+    http::HttpClient,
+    internal_events::TemplateRenderingError,
+    sinks::{
+        util::{
+            metadata::RequestMetadataBuilder, request_builder::EncodeResult, service::Svc,
+            ServiceBuilderExt, SinkBuilderExt, TowerRequestConfig, TowerRequestSettings,
+        },
+        VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Renders events into the JSON payloads of the notifications of a service.
+pub trait Notifier: Send + Sync + 'static {
+    /// Renders the payload of the notification of an event, or `None` if the event is dropped.
+    fn payload(&self, event: &Event) -> Option<Value>;
+}
+
+/// Renders a template of a notification, emitting an error and dropping the event on failure.
+pub fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+/// The rate limit of a service, applied unless `request.rate_limit_num` is configured.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+    pub num: u64,
+    pub duration_secs: u64,
+}
+
+fn request_settings(request: &TowerRequestConfig, rate_limit: RateLimit) -> TowerRequestSettings {
+    let defaults = TowerRequestConfig::default();
+    if request.rate_limit_num == defaults.rate_limit_num {
+        request
+            .rate_limit_num(rate_limit.num)
+            .rate_limit_duration_secs(rate_limit.duration_secs)
+            .unwrap_with(&defaults)
+    } else {
+        request.unwrap_with(&defaults)
+    }
+}
+
+/// Builds the sink sending the notifications rendered by `notifier` to `uri`.
+pub fn build_sink<N: Notifier>(
+    notifier: N,
+    uri: Uri,
+    request: &TowerRequestConfig,
+    rate_limit: RateLimit,
+    tls: &Option<TlsConfig>,
+    cx: &SinkContext,
+) -> crate::Result<VectorSink> {
+    let tls = TlsSettings::from_options(tls)?;
+    let client = HttpClient::new(tls, cx.proxy())?;
+
+    let service = ServiceBuilder::new()
+        .settings(
+            request_settings(request, rate_limit),
+            NotificationRetryLogic,
+        )
+        .service(NotificationService::new(client, uri));
+
+    Ok(VectorSink::from_event_streamsink(NotificationSink {
+        notifier,
+        service,
+    }))
+}
+
+struct NotificationSink<N> {
+    notifier: N,
+    service: Svc<NotificationService, NotificationRetryLogic>,
+}
+
+fn build_request<N: Notifier>(notifier: &N, mut event: Event) -> Option<NotificationRequest> {
+    let payload = Bytes::from(notifier.payload(&event)?.to_string());
+
+    let finalizers = event.take_finalizers();
+    let metadata = RequestMetadataBuilder::from_events(&event)
+        .build(&EncodeResult::uncompressed(payload.clone()));
+
+    Some(NotificationRequest {
+        payload,
+        finalizers,
+        metadata,
+    })
+}
+
+impl<N: Notifier> NotificationSink<N> {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let notifier = self.notifier;
+        input
+            .filter_map(|event| {
+                let request = build_request(&notifier, event);
+                async move { request }
+            })
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Notifier> StreamSink<Event> for NotificationSink<N> {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// The default template of the text of the notifications.
+pub fn default_text() -> Template {
+    Template::try_from("{{ message }}").expect("the default text template is valid")
+}
@@ -0,0 +1,217 @@
+//! The `slack` sink.
+//!
+//! Posts each event as a message built from [blocks][blocks] to a Slack incoming webhook.
+//!
+//! [blocks]: https://api.slack.com/block-kit
+
+use futures::{future, FutureExt};
+use http::Uri;
+use serde_json::{json, Value};
+use snafu::ResultExt;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::event::Event;
+
+use super::{
+    sink::{build_sink, default_text, render, Notifier, RateLimit},
+    Severity, SeverityConfig,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{util::TowerRequestConfig, Healthcheck, UriParseSnafu, VectorSink},
+    template::Template,
+    tls::TlsConfig,
+};
+
+/// Slack incoming webhooks accept about one message per second.
+const RATE_LIMIT: RateLimit = RateLimit {
+    num: 1,
+    duration_secs: 1,
+};
+
+/// Configuration for the `slack` sink.
+#[configurable_component(sink("slack"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SlackConfig {
+    /// The URL of the Slack [incoming webhook][incoming_webhook] to post the messages to.
+    ///
+    /// [incoming_webhook]: https://api.slack.com/messaging/webhooks
+    #[configurable(metadata(docs::examples = "${SLACK_WEBHOOK_URL}"))]
+    pub webhook_url: SensitiveString,
+
+    /// The template of the header of the messages.
+    #[configurable(metadata(docs::examples = "{{ host }} is down"))]
+    pub title: Option<Template>,
+
+    /// The template of the text of the messages, in Slack's [`mrkdwn`][mrkdwn] format.
+    ///
+    /// [mrkdwn]: https://api.slack.com/reference/surfaces/formatting
+    #[configurable(metadata(docs::examples = "*{{ service }}*: {{ message }}"))]
+    #[serde(default = "default_text")]
+    pub text: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub severity: SeverityConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for SlackConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"webhook_url = "https://hooks.slack.com/services/T000/B000/XXXX""#)
+            .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SlackConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let uri = self
+            .webhook_url
+            .inner()
+            .parse::<Uri>()
+            .context(UriParseSnafu)?;
+        let notifier = SlackNotifier {
+            title: self.title.clone(),
+            text: self.text.clone(),
+            severity: self.severity.clone(),
+        };
+        let sink = build_sink(notifier, uri, &self.request, RATE_LIMIT, &self.tls, &cx)?;
+
+        // Checking a webhook would post a message to the channel.
+        Ok((sink, future::ok(()).boxed()))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+struct SlackNotifier {
+    title: Option<Template>,
+    text: Template,
+    severity: SeverityConfig,
+}
+
+const fn emoji(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => ":rotating_light:",
+        Severity::Error => ":red_circle:",
+        Severity::Warning => ":warning:",
+        Severity::Info => ":information_source:",
+    }
+}
+
+impl Notifier for SlackNotifier {
+    fn payload(&self, event: &Event) -> Option<Value> {
+        let title = match &self.title {
+            Some(title) => Some(render(title, event, "title")?),
+            None => None,
+        };
+        let text = render(&self.text, event, "text")?;
+        let severity = self.severity.severity(event);
+
+        let mut blocks = Vec::with_capacity(3);
+        if let Some(title) = &title {
+            blocks.push(json!({
+                "type": "header",
+                "text": { "type": "plain_text", "text": title },
+            }));
+        }
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": text },
+        }));
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": format!("{} *Severity:* {}", emoji(severity), severity.as_str()),
+            }],
+        }));
+
+        // The text is the fallback shown in notifications, where blocks are not rendered.
+        Some(json!({
+            "text": title.unwrap_or_else(|| text.clone()),
+            "blocks": blocks,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SlackConfig>();
+    }
+
+    #[test]
+    fn renders_blocks() {
+        let config = toml::from_str::<SlackConfig>(
+            r#"
+            webhook_url = "https://hooks.slack.com/services/T000/B000/XXXX"
+            title = "{{ host }} is down"
+            severity.field = "level"
+        "#,
+        )
+        .unwrap();
+        let notifier = SlackNotifier {
+            title: config.title,
+            text: config.text,
+            severity: config.severity,
+        };
+
+        let mut log = LogEvent::from("connection refused");
+        log.insert("host", "web-1");
+        log.insert("level", "error");
+        assert_eq!(
+            notifier.payload(&Event::from(log)).unwrap(),
+            json!({
+                "text": "web-1 is down",
+                "blocks": [
+                    {
+                        "type": "header",
+                        "text": { "type": "plain_text", "text": "web-1 is down" },
+                    },
+                    {
+                        "type": "section",
+                        "text": { "type": "mrkdwn", "text": "connection refused" },
+                    },
+                    {
+                        "type": "context",
+                        "elements": [{
+                            "type": "mrkdwn",
+                            "text": ":red_circle: *Severity:* error",
+                        }],
+                    },
+                ],
+            })
+        );
+
+        assert!(notifier
+            .payload(&Event::from(LogEvent::from("connection refused")))
+            .is_none());
+    }
+}
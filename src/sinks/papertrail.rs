@@ -68,7 +68,7 @@ impl GenerateConfig for PapertrailConfig {
 impl SinkConfig for PapertrailConfig {
     async fn build(
         &self,
-        _cx: SinkContext,
+        cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let host = self
             .endpoint
@@ -99,6 +99,7 @@ impl SinkConfig for PapertrailConfig {
         let encoder = Encoder::<()>::new(serializer);
 
         sink_config.build(
+            cx.proxy(),
             Transformer::default(),
             PapertrailEncoder {
                 pid,
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{AcknowledgementsConfig, DataType, Input},
+    sink::VectorSink,
+};
+
+use crate::{
+    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    common::sftp::{spawn_blocking, Filesystem, KnownHostsStrategy, SftpConnectionConfig},
+    config::{GenerateConfig, SinkConfig, SinkContext},
+    sinks::{
+        opendal_common::*,
+        util::{
+            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
+            Compression,
+        },
+        Healthcheck,
+    },
+};
+
+use super::service::SftpService;
+
+/// Configuration for the `sftp` sink.
+#[configurable_component(sink("sftp", "Write batches of events as files to an SFTP server."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SftpSinkConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub connection: SftpConnectionConfig,
+
+    /// The directory the files are written to.
+    ///
+    /// Must be a valid directory. Relative paths are resolved from the home directory of the user.
+    ///
+    /// The final file path is in the format of `{root}/{prefix}{suffix}`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/upload/vector"))]
+    pub root: String,
+
+    /// A prefix to apply to all file names.
+    ///
+    /// Prefixes are useful for partitioning files, such as by creating a file name that stores
+    /// files under a particular directory. If using a prefix for this purpose, it must end in `/`
+    /// to act as a directory path. A trailing `/` is **not** automatically added.
+    ///
+    /// The final file path is in the format of `{root}/{prefix}{suffix}`.
+    #[serde(default)]
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "date=%F/"))]
+    pub prefix: String,
+
+    #[serde(flatten)]
+    pub encoding: EncodingConfigWithFraming,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::gzip_default")]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for SftpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection: SftpConnectionConfig {
+                endpoint: "127.0.0.1:22".to_string(),
+                user: "vector".to_string(),
+                key: None,
+                known_hosts_strategy: KnownHostsStrategy::default(),
+            },
+            root: "/upload".to_string(),
+            prefix: "%F/".to_string(),
+
+            encoding: (
+                Some(NewlineDelimitedEncoderConfig::new()),
+                JsonSerializerConfig::default(),
+            )
+                .into(),
+            compression: Compression::gzip_default(),
+            batch: BatchConfig::default(),
+
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SftpSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let files = self.connection.build_filesystem(&self.root);
+
+        let check_files = Arc::clone(&files);
+        let healthcheck =
+            Box::pin(async move { Ok(spawn_blocking(&check_files, |files| files.check()).await?) });
+
+        let sink = self.build_processor(files)?;
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl SftpSinkConfig {
+    pub fn build_processor(&self, files: Arc<dyn Filesystem>) -> crate::Result<VectorSink> {
+        let batcher_settings = self.batch.into_batcher_settings()?;
+
+        let transformer = self.encoding.transformer();
+        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+        let encoder = Encoder::<Framer>::new(framer, serializer);
+
+        let request_builder = OpenDalRequestBuilder {
+            encoder: (transformer, encoder),
+            compression: self.compression,
+        };
+
+        let svc = ServiceBuilder::new().service(SftpService::new(files));
+
+        let sink = OpenDalSink::new(
+            svc,
+            request_builder,
+            self.key_partitioner()?,
+            batcher_settings,
+        );
+
+        Ok(VectorSink::from_event_streamsink(sink))
+    }
+
+    pub fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
+        let prefix = self.prefix.clone().try_into()?;
+        Ok(KeyPartitioner::new(prefix))
+    }
+}
@@ -0,0 +1,14 @@
+//! `sftp` sink.
+//!
+//! Writes batches of events as files to an SFTP server, for the integrations which still exchange
+//! logs through SFTP drops.
+//!
+//! The batches are built by an [`crate::sinks::opendal_common::OpenDalSink`], and written as files
+//! by the [`service::SftpService`].
+
+mod config;
+mod service;
+pub use self::config::SftpSinkConfig;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,57 @@
+use std::{io, sync::Arc, task::Poll};
+
+use tower::Service;
+use tracing::Instrument;
+
+use crate::{
+    common::sftp::{spawn_blocking, Filesystem},
+    sinks::{
+        opendal_common::{OpenDalRequest, OpenDalResponse},
+        BoxFuture,
+    },
+};
+
+/// Writes the requests as files to the SFTP server.
+#[derive(Clone)]
+pub struct SftpService {
+    files: Arc<dyn Filesystem>,
+}
+
+impl SftpService {
+    pub fn new(files: Arc<dyn Filesystem>) -> Self {
+        Self { files }
+    }
+}
+
+impl Service<OpenDalRequest> for SftpService {
+    type Response = OpenDalResponse;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: OpenDalRequest) -> Self::Future {
+        let files = Arc::clone(&self.files);
+        let response = OpenDalResponse {
+            count: request.metadata.count,
+            events_byte_size: request.metadata.byte_size,
+            byte_size: request.payload.len(),
+        };
+        let OpenDalRequest {
+            payload, metadata, ..
+        } = request;
+
+        Box::pin(async move {
+            spawn_blocking(&files, move |files| {
+                files.write(&metadata.partition_key, &payload)
+            })
+            .in_current_span()
+            .await
+            .map(|()| response)
+        })
+    }
+}
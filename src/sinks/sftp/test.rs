@@ -0,0 +1,83 @@
+use bytes::Bytes;
+use codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+use vector_core::partition::Partitioner;
+
+use super::config::SftpSinkConfig;
+use crate::{
+    codecs::{Encoder, SinkType},
+    event::LogEvent,
+    sinks::{
+        opendal_common::{OpenDalRequest, OpenDalRequestBuilder},
+        util::{
+            request_builder::{EncodeResult, RequestBuilder},
+            Compression,
+        },
+    },
+};
+
+fn default_config() -> SftpSinkConfig {
+    toml::from_str(
+        r#"
+        endpoint = "127.0.0.1:22"
+        user = "vector"
+        root = "/upload"
+        prefix = "{{ service }}/"
+        encoding.codec = "json"
+        framing.method = "newline_delimited"
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn sftp_generate_config() {
+    crate::test_util::test_generate_config::<SftpSinkConfig>();
+}
+
+fn build_request(compression: Compression) -> OpenDalRequest {
+    let sink_config = SftpSinkConfig {
+        compression,
+        encoding: (
+            Some(NewlineDelimitedEncoderConfig::new()),
+            JsonSerializerConfig::default(),
+        )
+            .into(),
+        ..default_config()
+    };
+
+    let mut log = LogEvent::default();
+    log.insert("service", "api");
+    let log = log.into();
+    let key = sink_config
+        .key_partitioner()
+        .unwrap()
+        .partition(&log)
+        .expect("key wasn't provided");
+
+    let transformer = sink_config.encoding.transformer();
+    let (framer, serializer) = sink_config
+        .encoding
+        .build(SinkType::MessageBased)
+        .expect("encoding must build with success");
+    let request_builder = OpenDalRequestBuilder {
+        encoder: (transformer, Encoder::<Framer>::new(framer, serializer)),
+        compression: sink_config.compression,
+    };
+    let (metadata, metadata_request_builder, _events) =
+        request_builder.split_input((key, vec![log]));
+    let payload = EncodeResult::uncompressed(Bytes::new());
+    let request_metadata = metadata_request_builder.build(&payload);
+
+    request_builder.build_request(metadata, request_metadata, payload)
+}
+
+#[test]
+fn sftp_build_request() {
+    let req = build_request(Compression::None);
+    assert!(req.metadata.partition_key.starts_with("api/"));
+    assert!(req.metadata.partition_key.ends_with(".log"));
+
+    let req = build_request(Compression::gzip_default());
+    assert!(req.metadata.partition_key.starts_with("api/"));
+    assert!(req.metadata.partition_key.ends_with(".log.gz"));
+}
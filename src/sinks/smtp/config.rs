@@ -0,0 +1,286 @@
+use std::sync::Arc;
+
+use futures::FutureExt;
+use lettre::{
+    address::AddressError,
+    message::Mailbox,
+    transport::smtp::{authentication::Credentials, Error as SmtpTransportError},
+    AsyncSmtpTransport, Tokio1Executor,
+};
+use snafu::{ResultExt, Snafu};
+use tower::ServiceBuilder;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use super::{
+    request_builder::SmtpRequestBuilder,
+    service::{SmtpRetryLogic, SmtpService},
+    sink::SmtpSink,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid email address {:?}: {}", address, source))]
+    InvalidAddress {
+        address: String,
+        source: AddressError,
+    },
+    #[snafu(display("At least one recipient must be configured in `to`"))]
+    NoRecipients,
+    #[snafu(display("Failed to build the SMTP transport: {}", source))]
+    Transport { source: SmtpTransportError },
+}
+
+/// Configuration for the `smtp` sink.
+#[configurable_component(sink("smtp"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpSinkConfig {
+    /// The hostname of the SMTP server.
+    #[configurable(metadata(docs::examples = "smtp.example.com"))]
+    pub host: String,
+
+    /// The port of the SMTP server.
+    ///
+    /// By default, port 465 is used with the `tls` mode, 587 with the `starttls` mode and 25
+    /// otherwise.
+    #[configurable(metadata(docs::examples = 587))]
+    pub port: Option<u16>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: SmtpTlsMode,
+
+    #[configurable(derived)]
+    pub auth: Option<SmtpAuth>,
+
+    /// The sender of the emails.
+    #[configurable(metadata(docs::examples = "Vector <alerts@example.com>"))]
+    pub from: String,
+
+    /// The recipients of the emails.
+    #[configurable(metadata(docs::examples = "oncall@example.com"))]
+    pub to: Vec<String>,
+
+    /// The template of the subject of the emails.
+    ///
+    /// The subject of a digest is rendered from its first event, followed by the number of other
+    /// events it holds.
+    #[configurable(metadata(docs::examples = "[{{ level }}] {{ host }}: {{ message }}"))]
+    pub subject: Template,
+
+    /// The template of the body rendered for each event.
+    ///
+    /// The bodies of the events of a digest are separated by a line.
+    #[configurable(metadata(docs::examples = "{{ timestamp }} {{ host }}\n{{ message }}"))]
+    #[serde(default = "default_body")]
+    pub body: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<SmtpDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// How the connection to the SMTP server is secured.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTlsMode {
+    /// The connection is not encrypted.
+    None,
+
+    /// The connection is upgraded to TLS with the `STARTTLS` command.
+    #[default]
+    Starttls,
+
+    /// The connection is encrypted with TLS from the start.
+    Tls,
+}
+
+/// The credentials used to authenticate to the SMTP server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpAuth {
+    /// The username to authenticate with.
+    #[configurable(metadata(docs::examples = "${SMTP_USERNAME}"))]
+    pub username: String,
+
+    /// The password to authenticate with.
+    #[configurable(metadata(docs::examples = "${SMTP_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmtpDefaultBatchSettings;
+
+impl SinkBatchSettings for SmtpDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(100);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 60.0;
+}
+
+fn default_body() -> Template {
+    Template::try_from("{{ message }}").expect("the default body template is valid")
+}
+
+impl GenerateConfig for SmtpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"host = "smtp.example.com"
+            from = "Vector <alerts@example.com>"
+            to = ["oncall@example.com"]
+            subject = "[{{ level }}] {{ host }}: {{ message }}""#,
+        )
+        .unwrap()
+    }
+}
+
+impl SmtpSinkConfig {
+    fn build_transport(&self) -> crate::Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let mut builder = match self.tls {
+            SmtpTlsMode::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+            }
+            SmtpTlsMode::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.host)
+                    .context(TransportSnafu)?
+            }
+            SmtpTlsMode::Tls => {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(&self.host).context(TransportSnafu)?
+            }
+        };
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+        if let Some(auth) = &self.auth {
+            builder = builder.credentials(Credentials::new(
+                auth.username.clone(),
+                auth.password.inner().to_owned(),
+            ));
+        }
+        Ok(builder.build())
+    }
+
+    fn build_service(&self) -> crate::Result<SmtpService> {
+        let parse = |address: &String| {
+            address
+                .parse::<Mailbox>()
+                .context(InvalidAddressSnafu { address })
+        };
+
+        let from = parse(&self.from)?;
+        let to = self.to.iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err(BuildError::NoRecipients.into());
+        }
+
+        Ok(SmtpService::new(
+            Arc::new(self.build_transport()?),
+            from,
+            to,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SmtpSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let service = self.build_service()?;
+        let healthcheck = healthcheck(Arc::clone(service.transport())).boxed();
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let service = ServiceBuilder::new()
+            .settings(request_settings, SmtpRetryLogic)
+            .service(service);
+
+        let request_builder = SmtpRequestBuilder::new(self.subject.clone(), self.body.clone());
+        let sink = SmtpSink::new(batch_settings, request_builder, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+async fn healthcheck(transport: Arc<AsyncSmtpTransport<Tokio1Executor>>) -> crate::Result<()> {
+    if transport.test_connection().await? {
+        Ok(())
+    } else {
+        Err("The SMTP server did not accept the connection.".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SmtpSinkConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config = toml::from_str::<SmtpSinkConfig>(
+            r#"
+            host = "smtp.example.com"
+            tls = "tls"
+            auth.username = "vector"
+            auth.password = "secret"
+            from = "Vector <alerts@example.com>"
+            to = ["oncall@example.com", "Team <team@example.com>"]
+            subject = "{{ host }} is down"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.tls, SmtpTlsMode::Tls);
+        assert_eq!(config.to.len(), 2);
+        assert!(config.build_service().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        let mut config = toml::from_str::<SmtpSinkConfig>(
+            r#"
+            host = "smtp.example.com"
+            from = "not an address"
+            to = ["oncall@example.com"]
+            subject = "{{ host }} is down"
+        "#,
+        )
+        .unwrap();
+        assert!(config.build_service().is_err());
+
+        config.from = "alerts@example.com".to_string();
+        config.to.clear();
+        assert!(config.build_service().is_err());
+    }
+}
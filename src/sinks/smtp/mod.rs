@@ -0,0 +1,12 @@
+//! The `smtp` sink.
+//!
+//! Renders events into emails with templates and sends them through an SMTP server. The events of a
+//! batch are sent together, as a single digest email, so that bursts of alerts do not flood the
+//! recipients.
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::SmtpSinkConfig;
@@ -0,0 +1,141 @@
+use vector_common::finalization::{EventFinalizers, Finalizable};
+use vector_core::event::Event;
+
+use super::service::SmtpRequest;
+use crate::{
+    internal_events::TemplateRenderingError,
+    sinks::util::{metadata::RequestMetadataBuilder, request_builder::EncodeResult},
+    template::Template,
+};
+
+/// The line separating the bodies of the events of a digest.
+const DIGEST_SEPARATOR: &str = "\n\n----------------------------------------\n\n";
+
+/// Renders batches of events into digest emails.
+#[derive(Clone)]
+pub struct SmtpRequestBuilder {
+    subject: Template,
+    body: Template,
+}
+
+impl SmtpRequestBuilder {
+    pub const fn new(subject: Template, body: Template) -> Self {
+        Self { subject, body }
+    }
+
+    /// Renders the email of a batch of events, dropping the events whose templates cannot be
+    /// rendered. Returns `None` if none of them could be.
+    pub fn build_request(&self, events: Vec<Event>) -> Option<SmtpRequest> {
+        let mut subject = None;
+        let mut bodies = Vec::with_capacity(events.len());
+        let mut rendered = Vec::with_capacity(events.len());
+        for event in events {
+            let event_subject = match subject {
+                Some(_) => None,
+                None => match self.subject.render_string(&event) {
+                    Ok(event_subject) => Some(event_subject),
+                    Err(error) => {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("subject"),
+                            drop_event: true,
+                        });
+                        continue;
+                    }
+                },
+            };
+            match self.body.render_string(&event) {
+                Ok(body) => bodies.push(body),
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("body"),
+                        drop_event: true,
+                    });
+                    continue;
+                }
+            }
+            subject = subject.or(event_subject);
+            rendered.push(event);
+        }
+
+        let subject = match rendered.len() {
+            0 => return None,
+            1 => subject?,
+            count => format!("{} (+{} more)", subject?, count - 1),
+        };
+        let body = bodies.join(DIGEST_SEPARATOR);
+
+        let finalizers: EventFinalizers = rendered.take_finalizers();
+        let metadata = RequestMetadataBuilder::from_events(&rendered)
+            .build(&EncodeResult::uncompressed(body.as_bytes()));
+
+        Some(SmtpRequest {
+            subject,
+            body,
+            finalizers,
+            metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn builder() -> SmtpRequestBuilder {
+        SmtpRequestBuilder::new(
+            Template::try_from("{{ host }} is down").unwrap(),
+            Template::try_from("{{ message }}").unwrap(),
+        )
+    }
+
+    fn event(host: &str, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("host", host);
+        log.into()
+    }
+
+    #[test]
+    fn renders_single_event() {
+        let request = builder()
+            .build_request(vec![event("web-1", "connection refused")])
+            .unwrap();
+        assert_eq!(request.subject, "web-1 is down");
+        assert_eq!(request.body, "connection refused");
+    }
+
+    #[test]
+    fn renders_digest() {
+        let request = builder()
+            .build_request(vec![
+                event("web-1", "connection refused"),
+                event("web-2", "timed out"),
+                event("web-3", "no route to host"),
+            ])
+            .unwrap();
+        assert_eq!(request.subject, "web-1 is down (+2 more)");
+        assert_eq!(
+            request.body,
+            ["connection refused", "timed out", "no route to host"].join(DIGEST_SEPARATOR)
+        );
+        assert_eq!(request.metadata.event_count(), 3);
+    }
+
+    #[test]
+    fn drops_events_failing_to_render() {
+        let request = builder()
+            .build_request(vec![
+                Event::from(LogEvent::from("no host")),
+                event("web-2", "timed out"),
+            ])
+            .unwrap();
+        assert_eq!(request.subject, "web-2 is down");
+        assert_eq!(request.body, "timed out");
+
+        assert!(builder()
+            .build_request(vec![Event::from(LogEvent::from("no host"))])
+            .is_none());
+    }
+}
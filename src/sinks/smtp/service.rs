@@ -0,0 +1,147 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::Error as SmtpTransportError,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use snafu::{ResultExt, Snafu};
+use tower::Service;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_core::stream::DriverResponse;
+
+use crate::sinks::util::retries::RetryLogic;
+
+#[derive(Debug, Snafu)]
+pub enum SmtpError {
+    #[snafu(display("Failed to build the email: {}", source))]
+    Message { source: lettre::error::Error },
+    #[snafu(display("Failed to send the email: {}", source))]
+    Send { source: SmtpTransportError },
+}
+
+#[derive(Clone)]
+pub struct SmtpRetryLogic;
+
+impl RetryLogic for SmtpRetryLogic {
+    type Error = SmtpError;
+    type Response = SmtpResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            SmtpError::Message { .. } => false,
+            // Permanent errors are the 5xx replies of the server, rejecting the email itself.
+            SmtpError::Send { source } => !source.is_permanent(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SmtpRequest {
+    pub subject: String,
+    pub body: String,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for SmtpRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for SmtpRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub struct SmtpResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for SmtpResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct SmtpService {
+    transport: Arc<AsyncSmtpTransport<Tokio1Executor>>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl SmtpService {
+    pub(super) const fn new(
+        transport: Arc<AsyncSmtpTransport<Tokio1Executor>>,
+        from: Mailbox,
+        to: Vec<Mailbox>,
+    ) -> Self {
+        Self {
+            transport,
+            from,
+            to,
+        }
+    }
+
+    pub(super) const fn transport(&self) -> &Arc<AsyncSmtpTransport<Tokio1Executor>> {
+        &self.transport
+    }
+
+    fn build_message(&self, request: &SmtpRequest) -> Result<Message, SmtpError> {
+        let builder = self
+            .to
+            .iter()
+            .cloned()
+            .fold(Message::builder().from(self.from.clone()), |builder, to| {
+                builder.to(to)
+            });
+        builder
+            .subject(request.subject.as_str())
+            .header(ContentType::TEXT_PLAIN)
+            .body(request.body.clone())
+            .context(MessageSnafu)
+    }
+}
+
+impl Service<SmtpRequest> for SmtpService {
+    type Response = SmtpResponse;
+    type Error = SmtpError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SmtpRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let metadata = request.get_metadata();
+            let message = service.build_message(&request)?;
+            service.transport.send(message).await.context(SendSnafu)?;
+            Ok(SmtpResponse { metadata })
+        })
+    }
+}
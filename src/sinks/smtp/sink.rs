@@ -0,0 +1,49 @@
+use futures_util::{stream::BoxStream, StreamExt};
+use vector_core::{event::Event, sink::StreamSink, stream::BatcherSettings};
+
+use super::{
+    request_builder::SmtpRequestBuilder,
+    service::{SmtpRetryLogic, SmtpService},
+};
+use crate::sinks::util::{service::Svc, SinkBuilderExt};
+
+pub struct SmtpSink {
+    batch_settings: BatcherSettings,
+    request_builder: SmtpRequestBuilder,
+    service: Svc<SmtpService, SmtpRetryLogic>,
+}
+
+impl SmtpSink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        request_builder: SmtpRequestBuilder,
+        service: Svc<SmtpService, SmtpRetryLogic>,
+    ) -> Self {
+        Self {
+            batch_settings,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request_builder = self.request_builder;
+        input
+            .batched(self.batch_settings.into_byte_size_config())
+            .filter_map(|events| {
+                let request = request_builder.build_request(events);
+                async move { request }
+            })
+            .into_driver(self.service)
+            .protocol("smtp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for SmtpSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -5,11 +5,14 @@ use codecs::{
 use vector_config::configurable_component;
 
 #[cfg(unix)]
-use crate::sinks::util::unix::UnixSinkConfig;
+use crate::sinks::util::unix::{UnixMode as UnixSocketMode, UnixSinkConfig};
 use crate::{
     codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
-    sinks::util::{tcp::TcpSinkConfig, udp::UdpSinkConfig},
+    sinks::util::{
+        tcp::{TcpAckConfig, TcpSinkConfig},
+        udp::UdpSinkConfig,
+    },
 };
 
 /// Configuration for the `socket` sink.
@@ -54,6 +57,9 @@ pub struct TcpMode {
 
     #[serde(flatten)]
     encoding: EncodingConfigWithFraming,
+
+    #[configurable(derived)]
+    ack_protocol: Option<TcpAckConfig>,
 }
 
 /// UDP configuration.
@@ -106,6 +112,7 @@ impl SocketSinkConfig {
             Mode::Tcp(TcpMode {
                 config: TcpSinkConfig::from_address(address),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                ack_protocol: None,
             }),
             acknowledgements,
         )
@@ -116,14 +123,23 @@ impl SocketSinkConfig {
 impl SinkConfig for SocketSinkConfig {
     async fn build(
         &self,
-        _cx: SinkContext,
+        cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         match &self.mode {
-            Mode::Tcp(TcpMode { config, encoding }) => {
+            Mode::Tcp(TcpMode {
+                config,
+                encoding,
+                ack_protocol,
+            }) => {
                 let transformer = encoding.transformer();
                 let (framer, serializer) = encoding.build(SinkType::StreamBased)?;
                 let encoder = Encoder::<Framer>::new(framer, serializer);
-                config.build(transformer, encoder)
+                match ack_protocol {
+                    Some(ack) => {
+                        config.build_acknowledged(cx.proxy(), transformer, encoder, ack.clone())
+                    }
+                    None => config.build(cx.proxy(), transformer, encoder),
+                }
             }
             Mode::Udp(UdpMode { config, encoding }) => {
                 let transformer = encoding.transformer();
@@ -135,6 +151,11 @@ impl SinkConfig for SocketSinkConfig {
             Mode::Unix(UnixMode { config, encoding }) => {
                 let transformer = encoding.transformer();
                 let (framer, serializer) = encoding.build(SinkType::StreamBased)?;
+                // Each frame is sent as a datagram, so events aren't delimited unless configured.
+                let framer = match (config.unix_mode, encoding.config().0) {
+                    (UnixSocketMode::Datagram, None) => codecs::BytesEncoder::new().into(),
+                    _ => framer,
+                };
                 let encoder = Encoder::<Framer>::new(framer, serializer);
                 config.build(transformer, encoder)
             }
@@ -168,6 +189,7 @@ mod test {
     use futures_util::stream;
     use serde_json::Value;
     use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
         net::TcpListener,
         time::{sleep, timeout, Duration},
     };
@@ -177,7 +199,7 @@ mod test {
     use super::*;
     use crate::{
         config::SinkContext,
-        event::{Event, LogEvent},
+        event::{BatchNotifier, BatchStatus, Event, LogEvent},
         test_util::{
             components::{assert_sink_compliance, run_and_assert_sink_compliance, SINK_TAGS},
             next_addr, next_addr_v6, random_lines_with_stream, trace_init, CountReceiver,
@@ -246,6 +268,7 @@ mod test {
             mode: Mode::Tcp(TcpMode {
                 config: TcpSinkConfig::from_address(addr.to_string()),
                 encoding: (None::<FramingConfig>, JsonSerializerConfig::default()).into(),
+                ack_protocol: None,
             }),
             acknowledgements: Default::default(),
         };
@@ -275,6 +298,65 @@ mod test {
         }
     }
 
+    async fn run_acknowledged_tcp(token: &'static str) -> (Vec<String>, Vec<String>, BatchStatus) {
+        let addr = next_addr();
+        let config = SocketSinkConfig {
+            mode: Mode::Tcp(TcpMode {
+                config: TcpSinkConfig::from_address(addr.to_string()),
+                encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                ack_protocol: Some(TcpAckConfig::default()),
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        // Respond to each length-prefixed batch with the token.
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            while let Ok(length) = socket.read_u32().await {
+                let mut batch = vec![0; length as usize];
+                socket.read_exact(&mut batch).await.unwrap();
+                received.extend(
+                    String::from_utf8(batch)
+                        .unwrap()
+                        .lines()
+                        .map(ToOwned::to_owned),
+                );
+                socket.write_u32(token.len() as u32).await.unwrap();
+                socket.write_all(token.as_bytes()).await.unwrap();
+            }
+            received
+        });
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (lines, events) = random_lines_with_stream(10, 100, Some(batch));
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+        sink.run(events).await.unwrap();
+
+        let status = receiver.try_recv().unwrap();
+        (lines, server.await.unwrap(), status)
+    }
+
+    #[tokio::test]
+    async fn tcp_stream_acknowledged() {
+        trace_init();
+
+        let (lines, received, status) = run_acknowledged_tcp("ack").await;
+        assert_eq!(lines, received);
+        assert_eq!(status, BatchStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn tcp_stream_rejected() {
+        trace_init();
+
+        let (lines, received, status) = run_acknowledged_tcp("nack").await;
+        assert_eq!(lines, received);
+        assert_eq!(status, BatchStatus::Rejected);
+    }
+
     // This is a test that checks that we properly receive all events in the
     // case of a proper server side write side shutdown.
     //
@@ -329,6 +411,7 @@ mod test {
                     None,
                 ),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                ack_protocol: None,
             }),
             acknowledgements: Default::default(),
         };
@@ -448,6 +531,7 @@ mod test {
             mode: Mode::Tcp(TcpMode {
                 config: TcpSinkConfig::from_address(addr.to_string()),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                ack_protocol: None,
             }),
             acknowledgements: Default::default(),
         };
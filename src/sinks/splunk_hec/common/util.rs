@@ -1,7 +1,7 @@
 use std::{borrow::Cow, sync::Arc};
 
 use bytes::Bytes;
-use futures_util::future::BoxFuture;
+use futures_util::{future::BoxFuture, FutureExt};
 use http::{Request, StatusCode, Uri};
 use hyper::Body;
 use lookup::lookup_v2::OptionalValuePath;
@@ -102,6 +102,16 @@ pub async fn build_healthcheck(
     }
 }
 
+/// Builds the healthchecks of the HEC endpoint, to check its health periodically with the same
+/// client.
+pub fn build_healthcheck_factory(
+    endpoint: String,
+    token: String,
+    client: HttpClient,
+) -> sinks::HealthcheckFactory {
+    Arc::new(move || build_healthcheck(endpoint.clone(), token.clone(), client.clone()).boxed())
+}
+
 pub fn build_uri(
     host: &str,
     path: &str,
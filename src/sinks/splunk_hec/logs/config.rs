@@ -17,14 +17,15 @@ use crate::{
     sinks::{
         splunk_hec::common::{
             acknowledgements::HecClientAcknowledgementsConfig,
-            build_healthcheck, build_http_batch_service, create_client, host_key,
+            build_healthcheck, build_healthcheck_factory, build_http_batch_service, create_client,
+            host_key,
             service::{HecService, HttpRequestBuilder},
             EndpointTarget, SplunkHecDefaultBatchSettings,
         },
         util::{
             http::HttpRetryLogic, BatchConfig, Compression, ServiceBuilderExt, TowerRequestConfig,
         },
-        Healthcheck,
+        Healthcheck, HealthcheckFactory,
     },
     template::Template,
     tls::TlsConfig,
@@ -201,6 +202,18 @@ impl SinkConfig for HecLogsSinkConfig {
         Ok((sink, healthcheck))
     }
 
+    async fn build_healthcheck_factory(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<Option<HealthcheckFactory>> {
+        let client = create_client(&self.tls, cx.proxy())?;
+        Ok(Some(build_healthcheck_factory(
+            self.endpoint.clone(),
+            self.default_token.inner().to_owned(),
+            client,
+        )))
+    }
+
     fn input(&self) -> Input {
         Input::new(self.encoding.config().input_type() & DataType::Log)
     }
@@ -14,7 +14,7 @@ use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
 #[cfg(unix)]
-use crate::sinks::util::unix::UnixSinkConfig;
+use crate::sinks::util::unix::{UnixService, UnixSinkConfig};
 use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::{
@@ -27,14 +27,14 @@ use crate::{
         encode_namespace,
         tcp::TcpSinkConfig,
         udp::{UdpService, UdpSinkConfig},
-        BatchConfig, BatchSink, Buffer, Compression, EncodedEvent,
+        BatchConfig, BatchSink, Buffer, Compression, EncodedEvent, SinkBatchSettings,
     },
 };
 
-use super::util::SinkBatchSettings;
-
-pub struct StatsdSvc {
-    inner: UdpService,
+pub enum StatsdSvc {
+    Udp(UdpService),
+    #[cfg(unix)]
+    Unix(UnixService),
 }
 
 /// Configuration for the `statsd` sink.
@@ -75,7 +75,7 @@ pub enum Mode {
 
     /// Send over a Unix domain socket (UDS).
     #[cfg(unix)]
-    Unix(UnixSinkConfig),
+    Unix(StatsdUnixConfig),
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -99,6 +99,19 @@ pub struct StatsdUdpConfig {
     pub batch: BatchConfig<StatsdDefaultBatchSettings>,
 }
 
+/// Unix domain socket configuration.
+#[cfg(unix)]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct StatsdUnixConfig {
+    #[serde(flatten)]
+    pub unix: UnixSinkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<StatsdDefaultBatchSettings>,
+}
+
 fn default_address() -> SocketAddr {
     SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8125)
 }
@@ -121,43 +134,23 @@ impl GenerateConfig for StatsdSinkConfig {
 impl SinkConfig for StatsdSinkConfig {
     async fn build(
         &self,
-        _cx: SinkContext,
+        cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let default_namespace = self.default_namespace.clone();
-        let mut encoder = StatsdEncoder { default_namespace };
+        let encoder = StatsdEncoder { default_namespace };
         match &self.mode {
-            Mode::Tcp(config) => config.build(Default::default(), encoder),
+            Mode::Tcp(config) => config.build(cx.proxy(), Default::default(), encoder),
             Mode::Udp(config) => {
-                // 1432 bytes is a recommended packet size to fit into MTU
-                // https://github.com/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets
-                // However we need to leave some space for +1 extra trailing event in the buffer.
-                // Also one might keep an eye on server side limitations, like
-                // mentioned here https://github.com/DataDog/dd-agent/issues/2638
-                let batch = config.batch.into_batch_settings()?;
                 let (service, healthcheck) = config.udp.build_service()?;
-                let service = StatsdSvc { inner: service };
-                let sink = BatchSink::new(
-                    ServiceBuilder::new().service(service),
-                    Buffer::new(batch.size, Compression::None),
-                    batch.timeout,
-                )
-                .sink_map_err(|error| error!(message = "Fatal statsd sink error.", %error))
-                .with_flat_map(move |event: Event| {
-                    stream::iter({
-                        let byte_size = event.size_of();
-                        let mut bytes = BytesMut::new();
-
-                        // Errors are handled by `Encoder`.
-                        encoder
-                            .encode(event, &mut bytes)
-                            .map(|_| Ok(EncodedEvent::new(bytes, byte_size)))
-                    })
-                });
-
-                Ok((super::VectorSink::from_event_sink(sink), healthcheck))
+                let sink = batch_sink(StatsdSvc::Udp(service), &config.batch, encoder)?;
+                Ok((sink, healthcheck))
             }
             #[cfg(unix)]
-            Mode::Unix(config) => config.build(Default::default(), encoder),
+            Mode::Unix(config) => {
+                let (service, healthcheck) = config.unix.build_service();
+                let sink = batch_sink(StatsdSvc::Unix(service), &config.batch, encoder)?;
+                Ok((sink, healthcheck))
+            }
         }
     }
 
@@ -170,6 +163,40 @@ impl SinkConfig for StatsdSinkConfig {
     }
 }
 
+/// Builds a sink batching the encoded metrics into the frames sent by the service.
+fn batch_sink(
+    service: StatsdSvc,
+    batch: &BatchConfig<StatsdDefaultBatchSettings>,
+    mut encoder: StatsdEncoder,
+) -> crate::Result<super::VectorSink> {
+    // 1432 bytes is a recommended packet size to fit into MTU
+    // https://github.com/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets
+    // However we need to leave some space for +1 extra trailing event in the buffer.
+    // Unix datagram sockets are sized the same way, as each frame is sent as one datagram.
+    // Also one might keep an eye on server side limitations, like
+    // mentioned here https://github.com/DataDog/dd-agent/issues/2638
+    let batch = batch.into_batch_settings()?;
+    let sink = BatchSink::new(
+        ServiceBuilder::new().service(service),
+        Buffer::new(batch.size, Compression::None),
+        batch.timeout,
+    )
+    .sink_map_err(|error| error!(message = "Fatal statsd sink error.", %error))
+    .with_flat_map(move |event: Event| {
+        stream::iter({
+            let byte_size = event.size_of();
+            let mut bytes = BytesMut::new();
+
+            // Errors are handled by `Encoder`.
+            encoder
+                .encode(event, &mut bytes)
+                .map(|_| Ok(EncodedEvent::new(bytes, byte_size)))
+        })
+    });
+
+    Ok(super::VectorSink::from_event_sink(sink))
+}
+
 // Note that if multi-valued tags are present, this encoding may change the order from the input
 // event, since the tags with multiple values may not have been grouped together.
 // This is not an issue, but noting as it may be an observed behavior.
@@ -306,12 +333,20 @@ impl Service<BytesMut> for StatsdSvc {
 
     // Emission of Error internal event is handled upstream by the caller
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.inner.poll_ready(cx).map_err(Into::into)
+        match self {
+            Self::Udp(service) => service.poll_ready(cx).map_err(Into::into),
+            #[cfg(unix)]
+            Self::Unix(service) => service.poll_ready(cx).map_err(Into::into),
+        }
     }
 
     // Emission of Error internal event is handled upstream by the caller
     fn call(&mut self, frame: BytesMut) -> Self::Future {
-        self.inner.call(frame).err_into().boxed()
+        match self {
+            Self::Udp(service) => service.call(frame).err_into().boxed(),
+            #[cfg(unix)]
+            Self::Unix(service) => service.call(frame).err_into().boxed(),
+        }
     }
 }
 
@@ -562,6 +597,66 @@ mod test {
         vector_common::assert_event_data_eq!(metric1, metric2);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_to_unix_datagram() {
+        use crate::sinks::util::unix::UnixMode;
+
+        trace_init();
+
+        let out_path = tempfile::tempdir()
+            .unwrap()
+            .into_path()
+            .join("statsd_unix_datagram");
+        let receiver = tokio::net::UnixDatagram::bind(&out_path).unwrap();
+        let mut batch = BatchConfig::default();
+        batch.max_bytes = Some(48);
+
+        let config = StatsdSinkConfig {
+            default_namespace: None,
+            mode: Mode::Unix(StatsdUnixConfig {
+                batch,
+                unix: UnixSinkConfig {
+                    path: out_path,
+                    unix_mode: UnixMode::Datagram,
+                },
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let events = (0..4).map(|index| {
+            Event::Metric(Metric::new(
+                format!("counter{}", index),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            ))
+        });
+
+        let context = SinkContext::new_test();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let (sink, _healthcheck) = config.build(context).await.unwrap();
+            sink.run(stream::iter(events).map(Into::into))
+                .await
+                .expect("Running sink failed")
+        })
+        .await;
+
+        // The batch limit splits the metrics across datagrams holding whole lines.
+        let mut received = String::new();
+        let mut buf = [0; 1024];
+        while received.lines().count() < 4 {
+            let size = receiver.recv(&mut buf).await.unwrap();
+            assert!(size <= 48);
+            let datagram = std::str::from_utf8(&buf[..size]).unwrap();
+            assert!(datagram.ends_with('\n'));
+            received.push_str(datagram);
+        }
+        assert_eq!(
+            received,
+            "counter0:1|c\ncounter1:1|c\ncounter2:1|c\ncounter3:1|c\n"
+        );
+    }
+
     #[tokio::test]
     async fn test_send_to_statsd() {
         trace_init();
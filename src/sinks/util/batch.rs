@@ -250,6 +250,40 @@ impl<D: SinkBatchSettings + Clone> BatchConfig<D, Merged> {
     }
 }
 
+/// Limits on the partitions batched at once.
+///
+/// Events are batched separately for each partition, such as for each rendered key prefix, and the
+/// `batch` limits apply to each partition. These limits bound the partitions batched at once, so
+/// that templates rendering many distinct values can't grow the memory used by the sink without
+/// bound.
+#[configurable_component]
+#[configurable(metadata(docs::advanced))]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PartitionLimitsConfig {
+    /// The maximum number of partitions batched at once.
+    ///
+    /// Once reached, the batch of the least recently used partition is flushed to make room for a
+    /// new partition.
+    #[configurable(metadata(docs::type_unit = "partitions"))]
+    pub max_open: Option<NonZeroUsize>,
+
+    /// The maximum size of the batches of all partitions.
+    ///
+    /// Once exceeded, the batches of the least recently used partitions are flushed until the
+    /// remaining batches fit. Like `batch.max_bytes`, this is based on the uncompressed size of the
+    /// batched events.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: Option<NonZeroUsize>,
+}
+
+impl PartitionLimitsConfig {
+    /// Applies these limits to the settings of a partitioned batcher.
+    pub const fn apply(self, settings: BatcherSettings) -> BatcherSettings {
+        settings.with_partition_limits(self.max_open, self.max_bytes)
+    }
+}
+
 // Going from a merged to unmerged configuration is fine, because we know it already had to have
 // been validated/limited.
 impl<D1, D2> From<BatchConfig<D1, Merged>> for BatchConfig<D2, Unmerged>
@@ -10,9 +10,10 @@ use std::{
 };
 
 use bytes::{Buf, Bytes};
+use chrono::{DateTime, TimeZone, Utc};
 use futures::{future::BoxFuture, Sink};
 use headers::HeaderName;
-use http::{header, HeaderValue, StatusCode};
+use http::{header, HeaderMap, HeaderValue, StatusCode};
 use hyper::{body, Body};
 use indexmap::IndexMap;
 use pin_project::pin_project;
@@ -464,8 +465,50 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+        retry_after(response.headers())
+    }
 }
 
+/// Reads how long the server asked to wait before retrying a request from its response headers.
+///
+/// The standard `Retry-After` header is used if set, in seconds or as an HTTP date. Otherwise, the
+/// `x-ratelimit-reset` header set by many rate limited APIs is used, in seconds or as a Unix
+/// timestamp.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let until = |timestamp: DateTime<Utc>| (timestamp - Utc::now()).to_std().unwrap_or_default();
+
+    if let Some(value) = headers
+        .get(header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+    {
+        let value = value.trim();
+        return match value.parse::<u64>() {
+            Ok(secs) => Some(Duration::from_secs(secs)),
+            Err(_) => DateTime::parse_from_rfc2822(value)
+                .ok()
+                .map(|date| until(date.with_timezone(&Utc))),
+        };
+    }
+
+    let reset = headers
+        .get("x-ratelimit-reset")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|reset| reset.is_finite() && *reset >= 0.0)?;
+    if reset >= RATELIMIT_RESET_TIMESTAMP_THRESHOLD {
+        let secs = reset.trunc() as i64;
+        let nanos = (reset.fract() * 1e9) as u32;
+        Utc.timestamp_opt(secs, nanos).single().map(until)
+    } else {
+        Some(Duration::from_secs_f64(reset))
+    }
+}
+
+/// `x-ratelimit-reset` values from this one on are Unix timestamps rather than durations.
+const RATELIMIT_RESET_TIMESTAMP_THRESHOLD: f64 = 1_000_000_000.0;
+
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
 /// to a status code
 #[derive(Debug)]
@@ -625,6 +668,42 @@ mod test {
             .is_not_retryable());
     }
 
+    #[test]
+    fn util_http_retry_after() {
+        let headers = |name: &str, value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+            headers
+        };
+
+        assert_eq!(
+            retry_after(&headers("retry-after", "120")),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            retry_after(&headers("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            retry_after(&headers("x-ratelimit-reset", "1.5")),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            retry_after(&headers("x-ratelimit-reset", "1445412480")),
+            Some(Duration::ZERO)
+        );
+
+        let in_a_minute = Utc::now().timestamp() + 60;
+        let delay = retry_after(&headers("x-ratelimit-reset", &in_a_minute.to_string())).unwrap();
+        assert!(delay > Duration::from_secs(55) && delay <= Duration::from_secs(60));
+
+        assert_eq!(retry_after(&headers("retry-after", "soon")), None);
+        assert_eq!(retry_after(&HeaderMap::new()), None);
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
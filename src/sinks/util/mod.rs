@@ -27,8 +27,9 @@ use std::borrow::Cow;
 
 pub use batch::{
     Batch, BatchConfig, BatchSettings, BatchSize, BulkSizeBasedDefaultBatchSettings, Merged,
-    NoDefaultsBatchSettings, PushResult, RealtimeEventBasedDefaultBatchSettings,
-    RealtimeSizeBasedDefaultBatchSettings, SinkBatchSettings, Unmerged,
+    NoDefaultsBatchSettings, PartitionLimitsConfig, PushResult,
+    RealtimeEventBasedDefaultBatchSettings, RealtimeSizeBasedDefaultBatchSettings,
+    SinkBatchSettings, Unmerged,
 };
 pub use buffer::{
     json::{BoxedRawValue, JsonArrayBuffer},
@@ -3,13 +3,19 @@ use std::{
     cmp,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
 use futures::FutureExt;
+use rand::{thread_rng, Rng};
 use tokio::time::{sleep, Sleep};
-use tower::{retry::Policy, timeout::error::Elapsed};
+use tower::{
+    retry::{budget::Budget, Policy},
+    timeout::error::Elapsed,
+};
+use vector_config::configurable_component;
 
 use crate::Error;
 
@@ -32,6 +38,44 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// How long the service asked to wait before retrying the request of this response, if it did.
+    ///
+    /// When set, it is used as the delay of the retry instead of the backoff.
+    fn retry_after(&self, _response: &Self::Response) -> Option<Duration> {
+        None
+    }
+}
+
+/// The jitter mode to use for retry backoff.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JitterMode {
+    /// No jitter.
+    ///
+    /// Retries wait for the full backoff.
+    #[default]
+    None,
+
+    /// Full jitter.
+    ///
+    /// Retries wait for a random delay between zero and the backoff, which spreads the retries of
+    /// many clients recovering from the same failure instead of having them retry in lockstep.
+    Full,
+}
+
+impl JitterMode {
+    /// Applies the jitter to the backoff.
+    pub fn apply(self, backoff: Duration) -> Duration {
+        match self {
+            Self::None => backoff,
+            Self::Full => {
+                let max_millis = u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX);
+                Duration::from_millis(thread_rng().gen_range(0..=max_millis))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +84,9 @@ pub struct FixedRetryPolicy<L> {
     previous_duration: Duration,
     current_duration: Duration,
     max_duration: Duration,
+    jitter_mode: JitterMode,
+    budget: Option<Arc<Budget>>,
+    retried: bool,
     logic: L,
 }
 
@@ -60,10 +107,28 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
             previous_duration: Duration::from_secs(0),
             current_duration: initial_backoff,
             max_duration,
+            jitter_mode: JitterMode::None,
+            budget: None,
+            retried: false,
             logic,
         }
     }
 
+    /// Sets the jitter applied to the backoff.
+    pub const fn with_jitter_mode(mut self, jitter_mode: JitterMode) -> Self {
+        self.jitter_mode = jitter_mode;
+        self
+    }
+
+    /// Sets the budget limiting the retries of the requests sent with this policy.
+    ///
+    /// Each request deposits into the budget, and each retry withdraws from it. Requests aren't
+    /// retried once the budget is exhausted.
+    pub fn with_budget(mut self, budget: Arc<Budget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
     fn advance(&self) -> FixedRetryPolicy<L> {
         let next_duration: Duration = self.previous_duration + self.current_duration;
 
@@ -72,6 +137,9 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
             previous_duration: self.current_duration,
             current_duration: cmp::min(next_duration, self.max_duration),
             max_duration: self.max_duration,
+            jitter_mode: self.jitter_mode,
+            budget: self.budget.clone(),
+            retried: true,
             logic: self.logic.clone(),
         }
     }
@@ -80,12 +148,33 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
         self.current_duration
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    fn jittered_backoff(&self) -> Duration {
+        self.jitter_mode.apply(self.backoff())
+    }
+
+    fn build_retry(&self, retry_after: Option<Duration>) -> Option<RetryPolicyFuture<L>> {
+        if let Some(budget) = &self.budget {
+            if budget.withdraw().is_err() {
+                error!(
+                    message = "Retry budget exhausted; dropping the request.",
+                    internal_log_rate_limit = true
+                );
+                return None;
+            }
+        }
+
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        // The delay asked for by the service is honored, up to the maximum backoff.
+        let delay = retry_after.map_or_else(
+            || self.jittered_backoff(),
+            |retry_after| cmp::min(retry_after, self.max_duration),
+        );
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
-        RetryPolicyFuture { delay, policy }
+        debug!(message = "Retrying request.", delay_ms = %delay.as_millis());
+        Some(RetryPolicyFuture {
+            delay: Box::pin(sleep(delay)),
+            policy,
+        })
     }
 }
 
@@ -99,6 +188,12 @@ where
     // NOTE: in the error cases- `Error` and `EventsDropped` internal events are emitted by the
     // driver, so only need to log here.
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
+        if !self.retried {
+            if let Some(budget) = &self.budget {
+                budget.deposit();
+            }
+        }
+
         match result {
             Ok(response) => match self.logic.should_retry_response(response) {
                 RetryAction::Retry(reason) => {
@@ -112,7 +207,7 @@ where
                     }
 
                     warn!(message = "Retrying after response.", reason = %reason, internal_log_rate_limit = true);
-                    Some(self.build_retry())
+                    self.build_retry(self.logic.retry_after(response))
                 }
 
                 RetryAction::DontRetry(reason) => {
@@ -131,7 +226,7 @@ where
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
                     if self.logic.is_retriable_error(expected) {
                         warn!(message = "Retrying after error.", error = %expected, internal_log_rate_limit = true);
-                        Some(self.build_retry())
+                        self.build_retry(None)
                     } else {
                         error!(
                             message = "Non-retriable error; dropping the request.",
@@ -145,7 +240,7 @@ where
                         message = "Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.",
                         internal_log_rate_limit = true
                     );
-                    Some(self.build_retry())
+                    self.build_retry(None)
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -203,6 +298,7 @@ pub struct ExponentialBackoff {
     base: u64,
     factor: u64,
     max_delay: Option<Duration>,
+    jitter_mode: JitterMode,
 }
 
 impl ExponentialBackoff {
@@ -217,6 +313,19 @@ impl ExponentialBackoff {
             base,
             factor: 1u64,
             max_delay: None,
+            jitter_mode: JitterMode::None,
+        }
+    }
+
+    /// Constructs a new exponential back-off strategy, given the initial delay and the multiplier
+    /// applied to the delay after each attempt.
+    pub fn from_initial_delay(initial_delay: Duration, multiplier: u64) -> ExponentialBackoff {
+        ExponentialBackoff {
+            current: u64::try_from(initial_delay.as_millis()).unwrap_or(u64::MAX),
+            base: multiplier,
+            factor: 1u64,
+            max_delay: None,
+            jitter_mode: JitterMode::None,
         }
     }
 
@@ -236,6 +345,14 @@ impl ExponentialBackoff {
         self
     }
 
+    /// Apply jitter to each retry delay, after the maximum delay.
+    ///
+    /// Default jitter mode is `JitterMode::None`.
+    pub const fn jitter_mode(mut self, jitter_mode: JitterMode) -> ExponentialBackoff {
+        self.jitter_mode = jitter_mode;
+        self
+    }
+
     /// Resents the exponential back-off strategy to its initial state.
     pub fn reset(&mut self) {
         self.current = self.base;
@@ -256,7 +373,7 @@ impl Iterator for ExponentialBackoff {
         // check if we reached max delay
         if let Some(ref max_delay) = self.max_delay {
             if duration > *max_delay {
-                return Some(*max_delay);
+                return Some(self.jitter_mode.apply(*max_delay));
             }
         }
 
@@ -266,7 +383,7 @@ impl Iterator for ExponentialBackoff {
             self.current = std::u64::MAX;
         }
 
-        Some(duration)
+        Some(self.jitter_mode.apply(duration))
     }
 }
 
@@ -361,6 +478,76 @@ mod tests {
         assert_eq!(fut.await.unwrap(), "world");
     }
 
+    #[tokio::test]
+    async fn response_retry_after() {
+        trace_init();
+
+        time::pause();
+
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        );
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_response("slow down");
+        assert_pending!(fut.poll());
+
+        // The delay asked for by the response is waited for instead of the backoff.
+        time::advance(Duration::from_secs(2)).await;
+        assert_pending!(fut.poll());
+        assert_pending!(handle.poll_request());
+
+        time::advance(Duration::from_secs(4)).await;
+        assert_pending!(fut.poll());
+
+        assert_request_eq!(handle, "hello").send_response("world");
+        assert_eq!(fut.await.unwrap(), "world");
+    }
+
+    #[tokio::test]
+    async fn exhausted_budget_no_retry() {
+        trace_init();
+
+        let budget = Arc::new(Budget::new(Duration::from_secs(10), 0, 0.0));
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_budget(budget);
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_error(Error(true));
+        assert_ready_err!(fut.poll());
+    }
+
+    #[test]
+    fn full_jitter_stays_within_backoff() {
+        let policy = FixedRetryPolicy::new(
+            10,
+            Duration::from_secs(3),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_jitter_mode(JitterMode::Full);
+
+        for _ in 0..100 {
+            assert!(policy.jittered_backoff() <= Duration::from_secs(3));
+        }
+    }
+
     #[test]
     fn backoff_grows_to_max() {
         let mut policy = FixedRetryPolicy::new(
@@ -403,6 +590,17 @@ mod tests {
         fn is_retriable_error(&self, error: &Self::Error) -> bool {
             error.0
         }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            match *response {
+                "slow down" => RetryAction::Retry("slow down".into()),
+                _ => RetryAction::Successful,
+            }
+        }
+
+        fn retry_after(&self, response: &Self::Response) -> Option<Duration> {
+            (*response == "slow down").then(|| Duration::from_secs(5))
+        }
     }
 
     #[derive(Debug)]
@@ -8,7 +8,7 @@ use tower::{
     discover::Change,
     layer::{util::Stack, Layer},
     limit::RateLimit,
-    retry::Retry,
+    retry::{budget::Budget, Retry},
     timeout::Timeout,
     Service, ServiceBuilder,
 };
@@ -25,7 +25,7 @@ use crate::{
         adaptive_concurrency::{
             AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
         },
-        retries::{FixedRetryPolicy, RetryLogic},
+        retries::{FixedRetryPolicy, JitterMode, RetryLogic},
         service::map::MapLayer,
         sink::Response,
         Batch, BatchSink, Partition, PartitionBatchSink,
@@ -36,6 +36,9 @@ mod concurrency;
 mod health;
 mod map;
 
+/// The window over which requests are counted towards the retry budget.
+const RETRY_BUDGET_TTL: Duration = Duration::from_secs(10);
+
 pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
@@ -132,6 +135,20 @@ pub struct TowerRequestConfig {
     #[serde(default = "default_retry_initial_backoff_secs")]
     pub retry_initial_backoff_secs: Option<u64>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub retry_jitter_mode: JitterMode,
+
+    /// The maximum ratio of retries to requests.
+    ///
+    /// For example, `0.1` allows one retry for every ten requests sent over the last ten seconds.
+    /// At least one retry per second is always allowed. Requests aren't retried once the budget is
+    /// exhausted.
+    ///
+    /// By default, retries are only limited by `retry_attempts`.
+    #[serde(default)]
+    pub retry_budget_ratio: Option<f64>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
@@ -177,6 +194,8 @@ impl Default for TowerRequestConfig {
             retry_attempts: default_retry_attempts(),
             retry_max_duration_secs: default_retry_max_duration_secs(),
             retry_initial_backoff_secs: default_retry_initial_backoff_secs(),
+            retry_jitter_mode: JitterMode::default(),
+            retry_budget_ratio: None,
             adaptive_concurrency: AdaptiveConcurrencySettings::default(),
         }
     }
@@ -258,6 +277,8 @@ impl TowerRequestConfig {
                     .or(default_retry_initial_backoff_secs())
                     .unwrap(),
             ),
+            retry_jitter_mode: self.retry_jitter_mode,
+            retry_budget_ratio: self.retry_budget_ratio.or(defaults.retry_budget_ratio),
             adaptive_concurrency: self.adaptive_concurrency,
         }
     }
@@ -272,17 +293,33 @@ pub struct TowerRequestSettings {
     pub retry_attempts: usize,
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
+    pub retry_jitter_mode: JitterMode,
+    pub retry_budget_ratio: Option<f64>,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
 }
 
 impl TowerRequestSettings {
-    pub const fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
-        FixedRetryPolicy::new(
+    pub fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
+        let policy = FixedRetryPolicy::new(
             self.retry_attempts,
             self.retry_initial_backoff_secs,
             self.retry_max_duration_secs,
             logic,
         )
+        .with_jitter_mode(self.retry_jitter_mode);
+
+        match self.retry_budget_ratio {
+            Some(ratio) => {
+                // The budget of `tower` panics outside of this range.
+                let ratio = if ratio.is_nan() {
+                    0.0
+                } else {
+                    ratio.clamp(0.0, 1000.0)
+                };
+                policy.with_budget(Arc::new(Budget::new(RETRY_BUDGET_TTL, 1, ratio as f32)))
+            }
+            None => policy,
+        }
     }
 
     /// Note: This has been deprecated, please do not use when creating new Sinks.
@@ -1,14 +1,19 @@
 use std::{
+    future::Future,
     io::{Error as IoError, ErrorKind},
     marker::Unpin,
     pin::Pin,
     task::{ready, Context, Poll},
+    time::Duration,
 };
 
 use bytes::Bytes;
 use futures::Sink;
 use pin_project::{pin_project, pinned_drop};
-use tokio::io::AsyncWrite;
+use tokio::{
+    io::AsyncWrite,
+    time::{sleep, Sleep},
+};
 use tokio_util::codec::{BytesCodec, FramedWrite};
 use vector_common::finalization::{EventFinalizers, EventStatus};
 
@@ -17,6 +22,9 @@ use crate::internal_events::{SocketBytesSent, SocketEventsSent, SocketMode};
 
 const MAX_PENDING_ITEMS: usize = 1_000;
 
+/// The message of the error returned when a write is stalled for longer than the send timeout.
+pub(crate) const SEND_TIMED_OUT: &str = "send timed out";
+
 pub enum ShutdownCheck {
     Error(IoError),
     Close(&'static str),
@@ -30,6 +38,7 @@ pub enum ShutdownCheck {
 /// - Flush all data on each `poll_ready` if total number of events in queue more than some limit.
 /// - Count event size on each `start_send`.
 /// - Ack all sent events on successful `poll_flush` and `poll_close` or on `Drop`.
+/// - Fail writes which are stalled for longer than the send timeout, if any.
 #[pin_project(PinnedDrop)]
 pub struct BytesSink<T>
 where
@@ -39,6 +48,8 @@ where
     inner: FramedWrite<T, BytesCodec>,
     shutdown_check: Box<dyn Fn(&mut T) -> ShutdownCheck + Send>,
     state: State,
+    send_timeout: Option<Duration>,
+    stalled: Option<Pin<Box<Sleep>>>,
 }
 
 impl<T> BytesSink<T>
@@ -60,8 +71,43 @@ where
                 socket_mode,
                 finalizers: Vec::new(),
             },
+            send_timeout: None,
+            stalled: None,
         }
     }
+
+    /// Sets how long a write may be stalled before it fails with a `TimedOut` error.
+    pub(crate) fn with_send_timeout(mut self, send_timeout: Option<Duration>) -> Self {
+        self.send_timeout = send_timeout;
+        self
+    }
+
+    /// Fails a pending write once it has been stalled for longer than the send timeout.
+    ///
+    /// The timer starts when a write becomes pending, and is reset once a write completes.
+    fn poll_send_timeout(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        poll: Poll<Result<(), IoError>>,
+    ) -> Poll<Result<(), IoError>> {
+        let this = self.project();
+        if poll.is_ready() {
+            *this.stalled = None;
+            return poll;
+        }
+
+        if let Some(send_timeout) = *this.send_timeout {
+            let stalled = this
+                .stalled
+                .get_or_insert_with(|| Box::pin(sleep(send_timeout)));
+            if stalled.as_mut().poll(cx).is_ready() {
+                *this.stalled = None;
+                this.state.ack(EventStatus::Errored);
+                return Poll::Ready(Err(IoError::new(ErrorKind::TimedOut, SEND_TIMED_OUT)));
+            }
+        }
+        Poll::Pending
+    }
 }
 
 struct State {
@@ -121,8 +167,9 @@ where
             }
         }
 
-        let inner = self.project().inner;
-        <FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_ready(inner, cx)
+        let inner = self.as_mut().project().inner;
+        let poll = <FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_ready(inner, cx);
+        self.poll_send_timeout(cx, poll)
     }
 
     fn start_send(self: Pin<&mut Self>, item: EncodedEvent<Bytes>) -> Result<(), Self::Error> {
@@ -154,9 +201,8 @@ where
         }
 
         let inner = self.as_mut().project().inner;
-        let result = ready!(<FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_flush(
-            inner, cx
-        ));
+        let poll = <FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_flush(inner, cx);
+        let result = ready!(self.as_mut().poll_send_timeout(cx, poll));
         self.as_mut().get_mut().state.ack(match result {
             Ok(_) => EventStatus::Delivered,
             Err(_) => EventStatus::Errored,
@@ -166,10 +212,52 @@ where
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         let inner = self.as_mut().project().inner;
-        let result = ready!(<FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_close(
-            inner, cx
-        ));
+        let poll = <FramedWrite<T, BytesCodec> as Sink<Bytes>>::poll_close(inner, cx);
+        let result = ready!(self.as_mut().poll_send_timeout(cx, poll));
         self.as_mut().get_mut().state.ack(EventStatus::Dropped);
         Poll::Ready(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::SinkExt;
+    use vector_common::finalization::{BatchNotifier, BatchStatus, EventFinalizer};
+
+    use super::*;
+
+    /// A writer whose writes never complete.
+    struct Stalled;
+
+    impl AsyncWrite for Stalled {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &[u8],
+        ) -> Poll<Result<usize, IoError>> {
+            Poll::Pending
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), IoError>> {
+            Poll::Pending
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), IoError>> {
+            Poll::Pending
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_stalled_writes() {
+        let mut sink = BytesSink::new(Stalled, |_| ShutdownCheck::Alive, SocketMode::Tcp)
+            .with_send_timeout(Some(Duration::from_millis(50)));
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut event = EncodedEvent::new(Bytes::from("event"), 5);
+        event.finalizers = EventFinalizers::new(EventFinalizer::new(batch));
+
+        let error = sink.send(event).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TimedOut);
+        assert_eq!(receiver.await, BatchStatus::Errored);
+    }
+}
@@ -1,42 +1,54 @@
 use std::{
-    io::ErrorKind,
-    net::SocketAddr,
+    io::{self, ErrorKind},
+    net::{IpAddr, SocketAddr},
+    num::{NonZeroU64, NonZeroUsize},
     pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
     time::Duration,
 };
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-use futures::{stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
-use futures_util::{future::ready, stream};
+use futures::{stream::BoxStream, task::noop_waker_ref, FutureExt, SinkExt, Stream, StreamExt};
+use futures_util::{
+    future::{self, join_all, ready},
+    stream,
+};
 use snafu::{ResultExt, Snafu};
 use tokio::{
-    io::{AsyncRead, ReadBuf},
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf},
     net::TcpStream,
-    time::sleep,
+    sync::mpsc,
+    time::{sleep, timeout, Instant},
 };
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::codec::Encoder;
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::Transformer,
+    config::ProxyConfig,
     dns,
-    event::Event,
+    event::{Event, EventFinalizers, EventStatus},
     internal_events::{
-        ConnectionOpen, OpenGauge, SocketMode, SocketSendError, TcpSocketConnectionEstablished,
+        ConnectionOpen, OpenGauge, SocketBytesSent, SocketEventsSent, SocketMode, SocketSendError,
+        TcpSinkAcknowledgementError, TcpSinkBatchRejected, TcpSocketConnectionEstablished,
         TcpSocketConnectionShutdown, TcpSocketOutgoingConnectionError,
     },
     sinks::{
         util::{
-            retries::ExponentialBackoff,
-            socket_bytes_sink::{BytesSink, ShutdownCheck},
+            retries::{ExponentialBackoff, JitterMode},
+            socket_bytes_sink::{BytesSink, ShutdownCheck, SEND_TIMED_OUT},
             EncodedEvent, SinkBuildError, StreamSink,
         },
         Healthcheck, VectorSink,
     },
-    tcp::TcpKeepaliveConfig,
+    tcp::{self, TcpConnectOptions, TcpKeepaliveConfig},
     tls::{MaybeTlsSettings, MaybeTlsStream, TlsEnableableConfig, TlsError},
 };
 
@@ -44,12 +56,16 @@ use crate::{
 enum TcpError {
     #[snafu(display("Connect error: {}", source))]
     ConnectError { source: TlsError },
+    #[snafu(display("Unable to connect through proxy: {}", source))]
+    ProxyError { source: io::Error },
     #[snafu(display("Unable to resolve DNS: {}", source))]
     DnsError { source: dns::DnsError },
     #[snafu(display("No addresses returned."))]
     NoAddresses,
     #[snafu(display("Send error: {}", source))]
     SendError { source: tokio::io::Error },
+    #[snafu(display("Send timed out after {:?}.", timeout))]
+    SendTimeout { timeout: Duration },
 }
 
 /// A TCP sink.
@@ -77,6 +93,188 @@ pub struct TcpSinkConfig {
     #[configurable(metadata(docs::type_unit = "bytes"))]
     #[configurable(metadata(docs::examples = 65536))]
     send_buffer_bytes: Option<usize>,
+
+    /// Whether or not to disable Nagle's algorithm on the connection.
+    ///
+    /// If set, the `TCP_NODELAY` option is set on the socket, so that small writes are sent
+    /// right away instead of being buffered until more data is written.
+    #[serde(default)]
+    nodelay: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    reconnect: TcpReconnectConfig,
+
+    /// The number of connections to send events over concurrently.
+    ///
+    /// Each event is sent over the connection with the fewest events waiting to be sent, so that a
+    /// slow connection does not hold back the others. The order of events is only preserved with a
+    /// single connection. This does not apply when application-level acknowledgements are enabled.
+    ///
+    /// Defaults to a single connection.
+    #[configurable(metadata(docs::examples = 4))]
+    connections: Option<NonZeroUsize>,
+
+    /// The local IP address to send from.
+    ///
+    /// On hosts with several addresses, this controls the source address of the connections. Only
+    /// addresses of the same family are connected to.
+    #[configurable(metadata(docs::examples = "192.168.0.10"))]
+    bind_address: Option<IpAddr>,
+
+    /// The network interface to send through.
+    ///
+    /// If set, the `SO_BINDTODEVICE` option is set on the socket. Only supported on Linux.
+    #[configurable(metadata(docs::examples = "eth1"))]
+    bind_interface: Option<String>,
+
+    /// The amount of time a connection can stay idle before it is closed, in seconds.
+    ///
+    /// Firewalls and load balancers may silently drop connections that are idle for a while, which
+    /// causes the first events sent afterwards to be lost. When set, idle connections are closed,
+    /// and a new connection is established once there are events to send again.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 300))]
+    idle_timeout_secs: Option<NonZeroU64>,
+
+    /// How often to resolve the address again while connected, in seconds.
+    ///
+    /// When the resolved IP addresses change, for example after a DNS-based failover, the
+    /// connections are closed and established again to the new addresses. This does not apply when
+    /// connecting through a proxy.
+    ///
+    /// Regardless of this setting, new connections are made to each of the resolved addresses in
+    /// turn.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 60))]
+    dns_refresh_secs: Option<NonZeroU64>,
+
+    /// How long a write can be stalled before the connection is closed, in seconds.
+    ///
+    /// A peer which stops reading without closing the connection would otherwise block the sink
+    /// indefinitely. When set, the events being written are considered as failed, and a new
+    /// connection is established to send the next events.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 30))]
+    send_timeout_secs: Option<NonZeroU64>,
+}
+
+/// Reconnection settings.
+///
+/// When the connection can't be established, attempts are retried after an exponentially
+/// increasing delay.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TcpReconnectConfig {
+    /// The delay before the first reconnection attempt, in milliseconds.
+    #[serde(default = "default_reconnect_initial_backoff_ms")]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    initial_backoff_ms: u64,
+
+    /// The multiplier applied to the delay after each failed reconnection attempt.
+    #[serde(default = "default_reconnect_backoff_factor")]
+    backoff_factor: u64,
+
+    /// The maximum delay between reconnection attempts, in seconds.
+    #[serde(default = "default_reconnect_max_backoff_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    max_backoff_secs: u64,
+
+    /// The jitter applied to the delay between reconnection attempts.
+    ///
+    /// Setting it spreads the reconnections of many sinks recovering from the same failure.
+    #[serde(default = "default_reconnect_jitter_mode")]
+    jitter_mode: JitterMode,
+}
+
+impl Default for TcpReconnectConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff_ms: default_reconnect_initial_backoff_ms(),
+            backoff_factor: default_reconnect_backoff_factor(),
+            max_backoff_secs: default_reconnect_max_backoff_secs(),
+            jitter_mode: default_reconnect_jitter_mode(),
+        }
+    }
+}
+
+impl TcpReconnectConfig {
+    fn backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoff::from_initial_delay(
+            Duration::from_millis(self.initial_backoff_ms),
+            self.backoff_factor,
+        )
+        .max_delay(Duration::from_secs(self.max_backoff_secs))
+        .jitter_mode(self.jitter_mode)
+    }
+}
+
+const fn default_reconnect_initial_backoff_ms() -> u64 {
+    500
+}
+
+const fn default_reconnect_backoff_factor() -> u64 {
+    2
+}
+
+const fn default_reconnect_max_backoff_secs() -> u64 {
+    60
+}
+
+const fn default_reconnect_jitter_mode() -> JitterMode {
+    JitterMode::None
+}
+
+/// Application-level acknowledgement settings.
+///
+/// When set, events are sent in batches, each prefixed with its length as a big-endian unsigned
+/// 32-bit integer. The peer must respond to each batch with an acknowledgement token, prefixed with
+/// its length in the same way, before the next batch is sent. Events are only finalized once their
+/// batch is acknowledged, and batches that aren't acknowledged in time are sent again over a new
+/// connection.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TcpAckConfig {
+    /// The token the peer responds with to acknowledge a batch.
+    ///
+    /// Any other response rejects the events of the batch.
+    #[serde(default = "default_ack_token")]
+    #[configurable(metadata(docs::examples = "ok"))]
+    token: String,
+
+    /// The maximum number of events sent in each batch.
+    #[serde(default = "default_ack_max_events")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    max_events: NonZeroUsize,
+
+    /// How long to wait for the acknowledgement of a batch before sending it again, in seconds.
+    #[serde(default = "default_ack_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    timeout_secs: u64,
+}
+
+impl Default for TcpAckConfig {
+    fn default() -> Self {
+        Self {
+            token: default_ack_token(),
+            max_events: default_ack_max_events(),
+            timeout_secs: default_ack_timeout_secs(),
+        }
+    }
+}
+
+fn default_ack_token() -> String {
+    "ack".to_owned()
+}
+
+fn default_ack_max_events() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("static non-zero number")
+}
+
+const fn default_ack_timeout_secs() -> u64 {
+    30
 }
 
 impl TcpSinkConfig {
@@ -91,6 +289,14 @@ impl TcpSinkConfig {
             keepalive,
             tls,
             send_buffer_bytes,
+            nodelay: false,
+            reconnect: TcpReconnectConfig::default(),
+            connections: None,
+            bind_address: None,
+            bind_interface: None,
+            idle_timeout_secs: None,
+            dns_refresh_secs: None,
+            send_timeout_secs: None,
         }
     }
 
@@ -100,20 +306,81 @@ impl TcpSinkConfig {
             keepalive: None,
             tls: None,
             send_buffer_bytes: None,
+            nodelay: false,
+            reconnect: TcpReconnectConfig::default(),
+            connections: None,
+            bind_address: None,
+            bind_interface: None,
+            idle_timeout_secs: None,
+            dns_refresh_secs: None,
+            send_timeout_secs: None,
         }
     }
 
+    fn connector(&self, proxy: &ProxyConfig) -> crate::Result<TcpConnector> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        Ok(TcpConnector {
+            host,
+            port,
+            keepalive: self.keepalive,
+            tls,
+            send_buffer_bytes: self.send_buffer_bytes,
+            nodelay: self.nodelay,
+            reconnect: self.reconnect,
+            proxy: proxy.clone(),
+            connect_options: TcpConnectOptions {
+                local_address: self.bind_address,
+                interface: self.bind_interface.clone(),
+            },
+            idle_timeout: self
+                .idle_timeout_secs
+                .map(|secs| Duration::from_secs(secs.get())),
+            dns_refresh: self
+                .dns_refresh_secs
+                .map(|secs| Duration::from_secs(secs.get())),
+            send_timeout: self
+                .send_timeout_secs
+                .map(|secs| Duration::from_secs(secs.get())),
+            next_address: Arc::default(),
+            #[cfg(test)]
+            resolved: None,
+        })
+    }
+
     pub fn build(
         &self,
+        proxy: &ProxyConfig,
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
-        let uri = self.address.parse::<http::Uri>()?;
-        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
-        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
-        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
-        let sink = TcpSink::new(connector.clone(), transformer, encoder);
+        let connector = self.connector(proxy)?;
+        let connections = self.connections.map_or(1, NonZeroUsize::get);
+        let sink = TcpSink::new(connector.clone(), transformer, encoder, connections);
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(async move { connector.healthcheck().await }),
+        ))
+    }
+
+    /// Builds a sink that waits for the peer to acknowledge each batch of events.
+    pub fn build_acknowledged(
+        &self,
+        proxy: &ProxyConfig,
+        transformer: Transformer,
+        encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+        ack: TcpAckConfig,
+    ) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = self.connector(proxy)?;
+        let sink = TcpAckSink {
+            connector: connector.clone(),
+            transformer,
+            encoder,
+            ack,
+        };
 
         Ok((
             VectorSink::from_event_streamsink(sink),
@@ -129,69 +396,147 @@ struct TcpConnector {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: MaybeTlsSettings,
     send_buffer_bytes: Option<usize>,
+    nodelay: bool,
+    reconnect: TcpReconnectConfig,
+    proxy: ProxyConfig,
+    connect_options: TcpConnectOptions,
+    idle_timeout: Option<Duration>,
+    dns_refresh: Option<Duration>,
+    send_timeout: Option<Duration>,
+    /// The index of the resolved address to connect to next, shared by all connections.
+    next_address: Arc<AtomicUsize>,
+    /// The addresses the host is resolved to instead of looking it up.
+    #[cfg(test)]
+    resolved: Option<Arc<std::sync::Mutex<Vec<IpAddr>>>>,
 }
 
 impl TcpConnector {
-    const fn new(
-        host: String,
-        port: u16,
-        keepalive: Option<TcpKeepaliveConfig>,
-        tls: MaybeTlsSettings,
-        send_buffer_bytes: Option<usize>,
-    ) -> Self {
+    #[cfg(test)]
+    fn from_host_port(host: String, port: u16) -> Self {
         Self {
             host,
             port,
-            keepalive,
-            tls,
-            send_buffer_bytes,
+            keepalive: None,
+            tls: None.into(),
+            send_buffer_bytes: None,
+            nodelay: false,
+            reconnect: TcpReconnectConfig::default(),
+            proxy: ProxyConfig::default(),
+            connect_options: TcpConnectOptions::default(),
+            idle_timeout: None,
+            dns_refresh: None,
+            send_timeout: None,
+            next_address: Arc::default(),
+            resolved: None,
         }
     }
 
-    #[cfg(test)]
-    fn from_host_port(host: String, port: u16) -> Self {
-        Self::new(host, port, None, None.into(), None)
+    fn fresh_backoff(&self) -> ExponentialBackoff {
+        self.reconnect.backoff()
     }
 
-    const fn fresh_backoff() -> ExponentialBackoff {
-        // TODO: make configurable
-        ExponentialBackoff::from_millis(2)
-            .factor(250)
-            .max_delay(Duration::from_secs(60))
+    async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, TcpError> {
+        let maybe_tls = match self.proxy.tcp_proxy(&self.host, self.port) {
+            // The host name is resolved by the proxy.
+            Some(proxy) => {
+                let stream =
+                    tcp::connect_through_proxy(proxy, &self.host, self.port, &self.connect_options)
+                        .await
+                        .context(ProxySnafu)?;
+                self.tls.connect_stream(&self.host, stream).await
+            }
+            None => {
+                let addresses = self.resolve().await?;
+                let index = self.next_address.fetch_add(1, Ordering::Relaxed);
+                let ip = addresses[index % addresses.len()];
+
+                let addr = SocketAddr::new(ip, self.port);
+                match tcp::connect(addr, &self.connect_options).await {
+                    Ok(stream) => self.tls.connect_stream(&self.host, stream).await,
+                    Err(source) => Err(TlsError::Connect { source }),
+                }
+            }
+        };
+
+        maybe_tls.context(ConnectSnafu).map(|mut maybe_tls| {
+            if let Some(keepalive) = self.keepalive {
+                if let Err(error) = maybe_tls.set_keepalive(keepalive) {
+                    warn!(message = "Failed configuring TCP keepalive.", %error);
+                }
+            }
+
+            if let Some(send_buffer_bytes) = self.send_buffer_bytes {
+                if let Err(error) = maybe_tls.set_send_buffer_bytes(send_buffer_bytes) {
+                    warn!(message = "Failed configuring send buffer size on TCP socket.", %error);
+                }
+            }
+
+            if self.nodelay {
+                if let Err(error) = maybe_tls.set_nodelay(true) {
+                    warn!(message = "Failed configuring TCP_NODELAY on TCP socket.", %error);
+                }
+            }
+
+            maybe_tls
+        })
     }
 
-    async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, TcpError> {
-        let ip = dns::Resolver
+    /// Resolves the host to the addresses of the same family as the bind address, in a stable
+    /// order.
+    async fn resolve(&self) -> Result<Vec<IpAddr>, TcpError> {
+        let local_address = self.connect_options.local_address;
+        #[cfg(test)]
+        if let Some(resolved) = &self.resolved {
+            return Ok(resolved.lock().expect("poisoned lock").clone());
+        }
+        let mut addresses = dns::Resolver
             .lookup_ip(self.host.clone())
             .await
             .context(DnsSnafu)?
-            .next()
-            .ok_or(TcpError::NoAddresses)?;
+            .filter(|ip| local_address.map_or(true, |local| local.is_ipv4() == ip.is_ipv4()))
+            .collect::<Vec<_>>();
+        if addresses.is_empty() {
+            return Err(TcpError::NoAddresses);
+        }
+        addresses.sort_unstable();
+        addresses.dedup();
+        Ok(addresses)
+    }
 
-        let addr = SocketAddr::new(ip, self.port);
-        self.tls
-            .connect(&self.host, &addr)
-            .await
-            .context(ConnectSnafu)
-            .map(|mut maybe_tls| {
-                if let Some(keepalive) = self.keepalive {
-                    if let Err(error) = maybe_tls.set_keepalive(keepalive) {
-                        warn!(message = "Failed configuring TCP keepalive.", %error);
-                    }
-                }
+    /// Completes once the resolved addresses of the host change, checking every `dns_refresh`.
+    ///
+    /// Never completes if `dns_refresh` is not set or the host is resolved by a proxy.
+    async fn addresses_changed(&self) {
+        let Some(interval) = self.dns_refresh else {
+            return future::pending().await;
+        };
+        if self.proxy.tcp_proxy(&self.host, self.port).is_some() {
+            return future::pending().await;
+        }
 
-                if let Some(send_buffer_bytes) = self.send_buffer_bytes {
-                    if let Err(error) = maybe_tls.set_send_buffer_bytes(send_buffer_bytes) {
-                        warn!(message = "Failed configuring send buffer size on TCP socket.", %error);
+        let mut known = self.resolve().await.ok();
+        loop {
+            sleep(interval).await;
+            match self.resolve().await {
+                Ok(addresses) if known.as_ref() != Some(&addresses) => {
+                    if known.is_some() {
+                        debug!(
+                            message = "Resolved addresses changed, reconnecting.",
+                            host = %self.host,
+                        );
+                        return;
                     }
+                    known = Some(addresses);
                 }
-
-                maybe_tls
-            })
+                Ok(_) => {}
+                // Keep the connection while the host can't be resolved.
+                Err(error) => debug!(message = "Failed resolving host.", host = %self.host, %error),
+            }
+        }
     }
 
     async fn connect_backoff(&self) -> MaybeTlsStream<TcpStream> {
-        let mut backoff = Self::fresh_backoff();
+        let mut backoff = self.fresh_backoff();
         loop {
             match self.connect().await {
                 Ok(socket) => {
@@ -220,23 +565,34 @@ where
     connector: TcpConnector,
     transformer: Transformer,
     encoder: E,
+    connections: usize,
 }
 
+/// The number of encoded events queued for each connection of a pool.
+const POOL_QUEUE_SIZE: usize = 128;
+
 impl<E> TcpSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
 {
-    const fn new(connector: TcpConnector, transformer: Transformer, encoder: E) -> Self {
+    const fn new(
+        connector: TcpConnector,
+        transformer: Transformer,
+        encoder: E,
+        connections: usize,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            connections,
         }
     }
 
     async fn connect(&self) -> BytesSink<MaybeTlsStream<TcpStream>> {
         let stream = self.connector.connect_backoff().await;
         BytesSink::new(stream, Self::shutdown_check, SocketMode::Tcp)
+            .with_send_timeout(self.connector.send_timeout)
     }
 
     fn shutdown_check(stream: &mut MaybeTlsStream<TcpStream>) -> ShutdownCheck {
@@ -262,6 +618,86 @@ where
             _ => ShutdownCheck::Alive,
         }
     }
+
+    /// Sends events over a single connection, reconnecting whenever it fails.
+    async fn send_events(&self, mut input: impl Stream<Item = EncodedEvent<Bytes>> + Send + Unpin) {
+        while let Some(item) = input.next().await {
+            let mut sink = self.connect().await;
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            let rest = match self.connector.idle_timeout {
+                Some(idle_timeout) => until_idle(&mut input, idle_timeout).left_stream(),
+                None => (&mut input).right_stream(),
+            };
+            let mut mapped_input = stream::once(ready(item))
+                .chain(rest)
+                .take_until(Box::pin(self.connector.addresses_changed()))
+                .map(Ok);
+
+            let result = match sink.send_all(&mut mapped_input).await {
+                Ok(()) => sink.close().await,
+                Err(error) => Err(error),
+            };
+
+            // TODO we can consider retrying once in the Error case. This sink is a "best effort"
+            // delivery due to the nature of the underlying protocol.
+            // For now, if an error occurs we cannot assume that the events succeeded in delivery
+            // so we will emit `Error` / `EventsDropped` internal events regardless of if the server
+            // responded with Ok(0).
+            if let Err(error) = result {
+                if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
+                    emit!(TcpSocketConnectionShutdown {});
+                }
+                match self.connector.send_timeout {
+                    Some(timeout)
+                        if error.kind() == ErrorKind::TimedOut
+                            && error.to_string() == SEND_TIMED_OUT =>
+                    {
+                        emit!(SocketSendError {
+                            mode: SocketMode::Tcp,
+                            error: TcpError::SendTimeout { timeout }
+                        });
+                    }
+                    _ => emit!(SocketSendError {
+                        mode: SocketMode::Tcp,
+                        error
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Spreads events over a pool of connections, each fed by its own queue.
+    async fn send_events_pooled(
+        &self,
+        mut input: impl Stream<Item = EncodedEvent<Bytes>> + Send + Unpin,
+    ) {
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..self.connections)
+            .map(|_| mpsc::channel(POOL_QUEUE_SIZE))
+            .unzip();
+
+        let dispatch = async move {
+            let mut next = 0;
+            while let Some(item) = input.next().await {
+                // Pick the connection with the fewest queued events, starting the search after the
+                // previously used one so that idle connections are used in turn.
+                let index = (0..senders.len())
+                    .map(|offset| (next + offset) % senders.len())
+                    .min_by_key(|&index| POOL_QUEUE_SIZE - senders[index].capacity())
+                    .expect("pool is not empty");
+                next = (index + 1) % senders.len();
+
+                if senders[index].send(item).await.is_err() {
+                    break;
+                }
+            }
+        };
+        let connections = receivers
+            .into_iter()
+            .map(|receiver| self.send_events(ReceiverStream::new(receiver)));
+
+        futures::join!(dispatch, join_all(connections));
+    }
 }
 
 #[async_trait]
@@ -273,7 +709,7 @@ where
         // We need [Peekable](https://docs.rs/futures/0.3.6/futures/stream/struct.Peekable.html) for initiating
         // connection only when we have something to send.
         let mut encoder = self.encoder.clone();
-        let mut input = input.map(|mut event| {
+        let input = input.map(|mut event| {
             let byte_size = event.size_of();
             let finalizers = event.metadata_mut().take_finalizers();
             self.transformer.transform(&mut event);
@@ -292,31 +728,183 @@ where
             }
         });
 
-        while let Some(item) = input.next().await {
-            let mut sink = self.connect().await;
-            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+        if self.connections > 1 {
+            self.send_events_pooled(input).await;
+        } else {
+            self.send_events(input).await;
+        }
 
-            let mut mapped_input = stream::once(ready(item)).chain(&mut input).map(Ok);
+        Ok(())
+    }
+}
 
-            let result = match sink.send_all(&mut mapped_input).await {
-                Ok(()) => sink.close().await,
-                Err(error) => Err(error),
+/// Yields the items of `input` until none arrives within `idle_timeout`.
+fn until_idle<S: Stream + Unpin>(
+    input: S,
+    idle_timeout: Duration,
+) -> impl Stream<Item = S::Item> + Unpin {
+    // The timer makes the stream `!Unpin`.
+    Box::pin(tokio_stream::StreamExt::timeout(input, idle_timeout))
+        .take_while(|item| {
+            if item.is_err() {
+                debug!(message = "Closing idle connection.");
+            }
+            ready(item.is_ok())
+        })
+        .filter_map(|item| ready(item.ok()))
+}
+
+/// The maximum length of an acknowledgement token read from the peer.
+const MAX_ACK_TOKEN_LENGTH: usize = 1024;
+
+struct TcpAckSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    connector: TcpConnector,
+    transformer: Transformer,
+    encoder: E,
+    ack: TcpAckConfig,
+}
+
+impl<E> TcpAckSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+{
+    /// Sends a batch and waits for its acknowledgement, returning whether the peer accepted it.
+    async fn send_batch(
+        &self,
+        stream: &mut MaybeTlsStream<TcpStream>,
+        batch: &[u8],
+    ) -> io::Result<bool> {
+        let write = async {
+            stream.write_all(batch).await?;
+            stream.flush().await
+        };
+        match self.connector.send_timeout {
+            Some(send_timeout) => timeout(send_timeout, write).await.map_err(|_| {
+                let error = TcpError::SendTimeout {
+                    timeout: send_timeout,
+                };
+                io::Error::new(ErrorKind::TimedOut, error)
+            })??,
+            None => write.await?,
+        }
+
+        let length = stream.read_u32().await? as usize;
+        if length > MAX_ACK_TOKEN_LENGTH {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("acknowledgement of {} bytes is too long", length),
+            ));
+        }
+        let mut token = vec![0; length];
+        stream.read_exact(&mut token).await?;
+
+        Ok(token == self.ack.token.as_bytes())
+    }
+}
+
+#[async_trait]
+impl<E> StreamSink<Event> for TcpAckSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut encoder = self.encoder.clone();
+        let mut batches = input.ready_chunks(self.ack.max_events.get());
+        let mut connection = None;
+        let mut last_sent = Instant::now();
+        let mut addresses_changed = Box::pin(self.connector.addresses_changed());
+
+        while let Some(events) = batches.next().await {
+            let mut finalizers = EventFinalizers::default();
+            let mut count = 0;
+            let mut byte_size = 0;
+            // Leave room for the length prefix, which is filled in once the batch is encoded.
+            let mut batch = BytesMut::from(&[0u8; 4][..]);
+            for mut event in events {
+                let event_byte_size = event.size_of();
+                finalizers.merge(event.metadata_mut().take_finalizers());
+                self.transformer.transform(&mut event);
+
+                // Errors are handled by `Encoder`.
+                if encoder.encode(event, &mut batch).is_ok() {
+                    count += 1;
+                    byte_size += event_byte_size;
+                }
+            }
+
+            if count == 0 {
+                continue;
+            }
+
+            let length = match u32::try_from(batch.len() - 4) {
+                Ok(length) => length,
+                Err(_) => {
+                    emit!(SocketSendError {
+                        mode: SocketMode::Tcp,
+                        error: "batch is too large to be sent"
+                    });
+                    finalizers.update_status(EventStatus::Rejected);
+                    continue;
+                }
             };
+            batch[..4].copy_from_slice(&length.to_be_bytes());
 
-            // TODO we can consider retrying once in the Error case. This sink is a "best effort"
-            // delivery due to the nature of the underlying protocol.
-            // For now, if an error occurs we cannot assume that the events succeeded in delivery
-            // so we will emit `Error` / `EventsDropped` internal events regardless of if the server
-            // responded with Ok(0).
-            if let Err(error) = result {
-                if error.kind() == ErrorKind::Other && error.to_string() == "ShutdownCheck::Close" {
-                    emit!(TcpSocketConnectionShutdown {});
+            if let Some(idle_timeout) = self.connector.idle_timeout {
+                if connection.is_some() && last_sent.elapsed() >= idle_timeout {
+                    debug!(message = "Closing idle connection.");
+                    connection = None;
+                }
+            }
+            if connection.is_some() && (&mut addresses_changed).now_or_never().is_some() {
+                connection = None;
+            }
+
+            let mut backoff = self.connector.fresh_backoff();
+            loop {
+                if connection.is_none() {
+                    connection = Some(self.connector.connect_backoff().await);
+                    addresses_changed = Box::pin(self.connector.addresses_changed());
                 }
-                emit!(SocketSendError {
-                    mode: SocketMode::Tcp,
-                    error
-                });
+                let stream = connection
+                    .as_mut()
+                    .expect("connection was just established");
+
+                let ack_timeout = Duration::from_secs(self.ack.timeout_secs);
+                let error = match timeout(ack_timeout, self.send_batch(stream, &batch)).await {
+                    Ok(Ok(true)) => {
+                        finalizers.update_status(EventStatus::Delivered);
+                        emit!(SocketEventsSent {
+                            mode: SocketMode::Tcp,
+                            count: count as u64,
+                            byte_size,
+                        });
+                        emit!(SocketBytesSent {
+                            mode: SocketMode::Tcp,
+                            byte_size: batch.len(),
+                        });
+                        break;
+                    }
+                    Ok(Ok(false)) => {
+                        finalizers.update_status(EventStatus::Rejected);
+                        emit!(TcpSinkBatchRejected { count });
+                        break;
+                    }
+                    Ok(Err(error)) => error,
+                    Err(_) => io::Error::new(
+                        ErrorKind::TimedOut,
+                        "timed out waiting for the acknowledgement",
+                    ),
+                };
+
+                // The peer may have received part of the batch, so the connection can't be reused.
+                emit!(TcpSinkAcknowledgementError { error });
+                connection = None;
+                sleep(backoff.next().unwrap()).await;
             }
+            last_sent = Instant::now();
         }
 
         Ok(())
@@ -325,10 +913,15 @@ where
 
 #[cfg(test)]
 mod test {
+    use codecs::{encoding::Framer, NewlineDelimitedEncoder, TextSerializerConfig};
     use tokio::net::TcpListener;
+    use tokio_util::codec::{FramedRead, LinesCodec};
 
     use super::*;
-    use crate::test_util::{next_addr, trace_init};
+    use crate::{
+        event::LogEvent,
+        test_util::{next_addr, random_lines_with_stream, trace_init},
+    };
 
     #[tokio::test]
     async fn healthcheck() {
@@ -343,4 +936,165 @@ mod test {
         let bad = TcpConnector::from_host_port(addr.ip().to_string(), addr.port());
         assert!(bad.healthcheck().await.is_err());
     }
+
+    #[test]
+    fn reconnect_backoff() {
+        let delays = TcpReconnectConfig::default()
+            .backoff()
+            .take(9)
+            .map(|delay| delay.as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            delays,
+            [500, 1000, 2000, 4000, 8000, 16000, 32000, 60000, 60000]
+        );
+
+        let config: TcpReconnectConfig = toml::from_str(
+            r#"
+            initial_backoff_ms = 100
+            backoff_factor = 3
+            max_backoff_secs = 1
+            jitter_mode = "full"
+            "#,
+        )
+        .unwrap();
+        for (delay, max) in config.backoff().zip([100, 300, 900, 1000]) {
+            assert!(delay <= Duration::from_millis(max));
+        }
+    }
+
+    #[tokio::test]
+    async fn pooled_connections() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(&addr).await.unwrap();
+        let receiver = tokio::spawn(async move {
+            let mut connections = Vec::new();
+            for _ in 0..3 {
+                let (socket, _) = listener.accept().await.unwrap();
+                connections.push(tokio::spawn(
+                    FramedRead::new(socket, LinesCodec::new())
+                        .map(Result::unwrap)
+                        .collect::<Vec<String>>(),
+                ));
+            }
+
+            let mut lines = Vec::new();
+            for connection in connections {
+                let received = connection.await.unwrap();
+                assert!(!received.is_empty());
+                lines.extend(received);
+            }
+            lines
+        });
+
+        let connector = TcpConnector::from_host_port(addr.ip().to_string(), addr.port());
+        let encoder = crate::codecs::Encoder::<Framer>::new(
+            NewlineDelimitedEncoder::new().into(),
+            TextSerializerConfig::default().build().into(),
+        );
+        let sink = TcpSink::new(connector, Transformer::default(), encoder, 3);
+
+        let (mut lines, events) = random_lines_with_stream(10, 100, None);
+        VectorSink::from_event_streamsink(sink)
+            .run(events)
+            .await
+            .unwrap();
+
+        let mut output = receiver.await.unwrap();
+        lines.sort();
+        output.sort();
+        assert_eq!(lines, output);
+    }
+
+    // Only Linux routes the whole 127.0.0.0/8 block to the loopback interface.
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn rotates_resolved_addresses() {
+        trace_init();
+
+        let port = next_addr().port();
+        let ips = (1..=3)
+            .map(|last| IpAddr::from([127, 0, 0, last]))
+            .collect::<Vec<_>>();
+        let _listeners = ips
+            .iter()
+            .map(|ip| std::net::TcpListener::bind((*ip, port)).unwrap())
+            .collect::<Vec<_>>();
+
+        let resolved = Arc::new(std::sync::Mutex::new(ips[..2].to_vec()));
+        let mut connector = TcpConnector::from_host_port("vector.test".into(), port);
+        connector.resolved = Some(Arc::clone(&resolved));
+        connector.dns_refresh = Some(Duration::from_millis(10));
+
+        async fn connected_ips(connector: &TcpConnector, count: usize) -> Vec<IpAddr> {
+            let mut connected = Vec::new();
+            for _ in 0..count {
+                let stream = connector.connect().await.unwrap();
+                connected.push(stream.peer_addr().unwrap().ip());
+            }
+            connected
+        }
+
+        // Each connection is made to the next address.
+        assert_eq!(connected_ips(&connector, 3).await, [ips[0], ips[1], ips[0]]);
+
+        // Stable addresses never trigger a reconnection.
+        let changed = timeout(Duration::from_millis(100), connector.addresses_changed()).await;
+        assert!(changed.is_err());
+
+        // Changed addresses do, and the new connections are made to them.
+        let changed = tokio::spawn({
+            let connector = connector.clone();
+            async move { connector.addresses_changed().await }
+        });
+        sleep(Duration::from_millis(50)).await;
+        *resolved.lock().unwrap() = ips[1..].to_vec();
+        timeout(Duration::from_secs(1), changed)
+            .await
+            .expect("Changed addresses should be noticed")
+            .unwrap();
+        assert_eq!(connected_ips(&connector, 2).await, [ips[2], ips[1]]);
+    }
+
+    #[tokio::test]
+    async fn closes_idle_connections() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(&addr).await.unwrap();
+        let receiver = tokio::spawn(async move {
+            let mut connections = Vec::new();
+            for _ in 0..2 {
+                let (socket, _) = listener.accept().await.unwrap();
+                let lines = FramedRead::new(socket, LinesCodec::new())
+                    .map(Result::unwrap)
+                    .collect::<Vec<String>>()
+                    .await;
+                connections.push(lines);
+            }
+            connections
+        });
+
+        let mut connector = TcpConnector::from_host_port(addr.ip().to_string(), addr.port());
+        connector.idle_timeout = Some(Duration::from_millis(100));
+        let encoder = crate::codecs::Encoder::<Framer>::new(
+            NewlineDelimitedEncoder::new().into(),
+            TextSerializerConfig::default().build().into(),
+        );
+        let sink = TcpSink::new(connector, Transformer::default(), encoder, 1);
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let sink = tokio::spawn(VectorSink::from_event_streamsink(sink).run(rx));
+
+        tx.unbounded_send(LogEvent::from("first").into()).unwrap();
+        sleep(Duration::from_millis(500)).await;
+        tx.unbounded_send(LogEvent::from("second").into()).unwrap();
+        drop(tx);
+        sink.await.unwrap().unwrap();
+
+        let connections = receiver.await.unwrap();
+        assert_eq!(connections, vec![vec!["first"], vec!["second"]]);
+    }
 }
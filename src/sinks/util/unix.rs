@@ -1,19 +1,33 @@
-use std::{path::PathBuf, pin::Pin, time::Duration};
+use std::{
+    path::PathBuf,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-use futures::{stream::BoxStream, SinkExt, StreamExt};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, SinkExt, StreamExt};
 use snafu::{ResultExt, Snafu};
-use tokio::{net::UnixStream, time::sleep};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{UnixDatagram, UnixStream},
+    sync::oneshot,
+    time::sleep,
+};
 use tokio_util::codec::Encoder;
+use tower::Service;
+use vector_common::internal_event::{
+    ByteSize, BytesSent, InternalEventHandle, Protocol, Registered,
+};
 use vector_config::configurable_component;
-use vector_core::ByteSizeOf;
+use vector_core::{ByteSizeOf, EstimatedJsonEncodedSizeOf};
 
 use crate::{
     codecs::Transformer,
-    event::{Event, Finalizable},
+    event::{Event, EventStatus, Finalizable},
     internal_events::{
-        ConnectionOpen, OpenGauge, SocketMode, UnixSocketConnectionEstablished,
+        ConnectionOpen, OpenGauge, SocketEventsSent, SocketMode, UnixSocketConnectionEstablished,
         UnixSocketOutgoingConnectionError, UnixSocketSendError,
     },
     sink::VecSinkExt,
@@ -34,6 +48,23 @@ pub enum UnixError {
         source: tokio::io::Error,
         path: PathBuf,
     },
+    #[snafu(display("Send error: {}", source))]
+    SendError { source: tokio::io::Error },
+    #[snafu(display("Failed to get socket back: {}", source))]
+    ServiceChannelRecvError { source: oneshot::error::RecvError },
+}
+
+/// The type of Unix socket.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnixMode {
+    /// A stream socket (`SOCK_STREAM`), to which a connection is established.
+    #[default]
+    Stream,
+
+    /// A datagram socket (`SOCK_DGRAM`), to which each frame is sent as a datagram.
+    Datagram,
 }
 
 /// A Unix Domain Socket sink.
@@ -45,11 +76,30 @@ pub struct UnixSinkConfig {
     /// This should be an absolute path.
     #[configurable(metadata(docs::examples = "/path/to/socket"))]
     pub path: PathBuf,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub unix_mode: UnixMode,
 }
 
 impl UnixSinkConfig {
     pub const fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            unix_mode: UnixMode::Stream,
+        }
+    }
+
+    fn build_connector(&self) -> UnixConnector {
+        UnixConnector::new(self.path.clone(), self.unix_mode)
+    }
+
+    pub fn build_service(&self) -> (UnixService, Healthcheck) {
+        let connector = self.build_connector();
+        (
+            UnixService::new(connector.clone()),
+            Box::pin(async move { connector.healthcheck().await }),
+        )
     }
 
     pub fn build(
@@ -57,7 +107,7 @@ impl UnixSinkConfig {
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
-        let connector = UnixConnector::new(self.path.clone());
+        let connector = self.build_connector();
         let sink = UnixSink::new(connector.clone(), transformer, encoder);
         Ok((
             VectorSink::from_event_streamsink(sink),
@@ -69,11 +119,12 @@ impl UnixSinkConfig {
 #[derive(Debug, Clone)]
 struct UnixConnector {
     pub path: PathBuf,
+    mode: UnixMode,
 }
 
 impl UnixConnector {
-    const fn new(path: PathBuf) -> Self {
-        Self { path }
+    const fn new(path: PathBuf, mode: UnixMode) -> Self {
+        Self { path, mode }
     }
 
     const fn fresh_backoff() -> ExponentialBackoff {
@@ -83,21 +134,28 @@ impl UnixConnector {
             .max_delay(Duration::from_secs(60))
     }
 
-    async fn connect(&self) -> Result<UnixStream, UnixError> {
-        UnixStream::connect(&self.path)
-            .await
-            .context(ConnectionSnafu {
-                path: self.path.clone(),
-            })
+    async fn connect(&self) -> Result<UnixSocket, UnixError> {
+        let socket = match self.mode {
+            UnixMode::Stream => UnixStream::connect(&self.path)
+                .await
+                .map(UnixSocket::Stream),
+            UnixMode::Datagram => UnixDatagram::unbound().and_then(|socket| {
+                socket.connect(&self.path)?;
+                Ok(UnixSocket::Datagram(socket))
+            }),
+        };
+        socket.context(ConnectionSnafu {
+            path: self.path.clone(),
+        })
     }
 
-    async fn connect_backoff(&self) -> UnixStream {
+    async fn connect_backoff(&self) -> UnixSocket {
         let mut backoff = Self::fresh_backoff();
         loop {
             match self.connect().await {
-                Ok(stream) => {
+                Ok(socket) => {
                     emit!(UnixSocketConnectionEstablished { path: &self.path });
-                    return stream;
+                    return socket;
                 }
                 Err(error) => {
                     emit!(UnixSocketOutgoingConnectionError { error });
@@ -112,6 +170,103 @@ impl UnixConnector {
     }
 }
 
+enum UnixSocket {
+    Stream(UnixStream),
+    Datagram(UnixDatagram),
+}
+
+impl UnixSocket {
+    /// Sends the bytes, as a single datagram for datagram sockets.
+    async fn send(&mut self, buf: &[u8]) -> tokio::io::Result<()> {
+        match self {
+            Self::Stream(stream) => stream.write_all(buf).await,
+            Self::Datagram(socket) => socket.send(buf).await.map(|_| ()),
+        }
+    }
+}
+
+enum UnixServiceState {
+    Disconnected,
+    Connecting(BoxFuture<'static, UnixSocket>),
+    Connected(UnixSocket),
+    /// Holds the socket once sent, or `None` when it must be reconnected.
+    Sending(oneshot::Receiver<Option<UnixSocket>>),
+}
+
+pub struct UnixService {
+    connector: UnixConnector,
+    state: UnixServiceState,
+    bytes_sent: Registered<BytesSent>,
+}
+
+impl UnixService {
+    fn new(connector: UnixConnector) -> Self {
+        Self {
+            connector,
+            state: UnixServiceState::Disconnected,
+            bytes_sent: register!(BytesSent::from(Protocol::UNIX)),
+        }
+    }
+}
+
+impl Service<BytesMut> for UnixService {
+    type Response = ();
+    type Error = UnixError;
+    type Future = BoxFuture<'static, Result<(), Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        loop {
+            self.state = match &mut self.state {
+                UnixServiceState::Disconnected => {
+                    let connector = self.connector.clone();
+                    UnixServiceState::Connecting(Box::pin(async move {
+                        connector.connect_backoff().await
+                    }))
+                }
+                UnixServiceState::Connecting(fut) => {
+                    let socket = ready!(fut.poll_unpin(cx));
+                    UnixServiceState::Connected(socket)
+                }
+                UnixServiceState::Connected(_) => break,
+                UnixServiceState::Sending(fut) => {
+                    match ready!(fut.poll_unpin(cx)).context(ServiceChannelRecvSnafu) {
+                        Ok(Some(socket)) => UnixServiceState::Connected(socket),
+                        Ok(None) => UnixServiceState::Disconnected,
+                        Err(error) => return Poll::Ready(Err(error)),
+                    }
+                }
+            };
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, msg: BytesMut) -> Self::Future {
+        let (sender, receiver) = oneshot::channel();
+        let byte_size = msg.len();
+        let bytes_sent = self.bytes_sent.clone();
+
+        let mut socket =
+            match std::mem::replace(&mut self.state, UnixServiceState::Sending(receiver)) {
+                UnixServiceState::Connected(socket) => socket,
+                _ => panic!("UnixService::poll_ready should be called first"),
+            };
+
+        Box::pin(async move {
+            let result = socket.send(&msg).await.context(SendSnafu);
+            // The socket is reconnected after a failed send, as the peer may have gone away.
+            let _ = sender.send(result.is_ok().then_some(socket));
+
+            if result.is_ok() {
+                bytes_sent.emit(ByteSize(byte_size));
+            }
+
+            result
+        })
+    }
+}
+
 struct UnixSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
@@ -134,18 +289,14 @@ where
     }
 
     async fn connect(&mut self) -> BytesSink<UnixStream> {
-        let stream = self.connector.connect_backoff().await;
+        let UnixSocket::Stream(stream) = self.connector.connect_backoff().await else {
+            unreachable!("stream sockets are connected in stream mode");
+        };
         BytesSink::new(stream, |_| ShutdownCheck::Alive, SocketMode::Unix)
     }
-}
 
-#[async_trait]
-impl<E> StreamSink<Event> for UnixSink<E>
-where
-    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
-{
     // Same as TcpSink, more details there.
-    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+    async fn run_stream(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut encoder = self.encoder.clone();
         let transformer = self.transformer.clone();
         let mut input = input
@@ -190,11 +341,72 @@ where
 
         Ok(())
     }
+
+    // Same as UdpSink, each frame is sent as a datagram.
+    async fn run_datagram(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let bytes_sent = register!(BytesSent::from(Protocol::UNIX));
+        let mut encoder = self.encoder.clone();
+        let mut input = input.peekable();
+
+        while Pin::new(&mut input).peek().await.is_some() {
+            let mut socket = self.connector.connect_backoff().await;
+            while let Some(mut event) = input.next().await {
+                let byte_size = event.estimated_json_encoded_size_of();
+
+                self.transformer.transform(&mut event);
+
+                let finalizers = event.take_finalizers();
+                let mut bytes = BytesMut::new();
+
+                // Errors are handled by `Encoder`.
+                if encoder.encode(event, &mut bytes).is_err() {
+                    continue;
+                }
+
+                match socket.send(&bytes).await {
+                    Ok(()) => {
+                        emit!(SocketEventsSent {
+                            mode: SocketMode::Unix,
+                            count: 1,
+                            byte_size,
+                        });
+
+                        bytes_sent.emit(ByteSize(bytes.len()));
+                        finalizers.update_status(EventStatus::Delivered);
+                    }
+                    Err(error) => {
+                        emit!(UnixSocketSendError {
+                            error: &error,
+                            path: &self.connector.path
+                        });
+                        finalizers.update_status(EventStatus::Errored);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<E> StreamSink<Event> for UnixSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        match self.connector.mode {
+            UnixMode::Stream => self.run_stream(input).await,
+            UnixMode::Datagram => self.run_datagram(input).await,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use codecs::{encoding::Framer, NewlineDelimitedEncoder, TextSerializerConfig};
+    use futures::future::poll_fn;
     use tokio::net::UnixListener;
 
     use super::*;
@@ -269,4 +481,56 @@ mod tests {
         // Receive the data sent by the Sink to the receiver
         assert_eq!(input_lines, receiver.await);
     }
+
+    fn datagram_config(path: PathBuf) -> UnixSinkConfig {
+        UnixSinkConfig {
+            path,
+            unix_mode: UnixMode::Datagram,
+        }
+    }
+
+    #[tokio::test]
+    async fn basic_unix_datagram_sink() {
+        let num_lines = 100;
+        let out_path = temp_uds_path("unix_datagram_test");
+        let receiver = UnixDatagram::bind(&out_path).unwrap();
+
+        let (sink, healthcheck) = datagram_config(out_path)
+            .build(
+                Default::default(),
+                Encoder::<()>::new(TextSerializerConfig::default().build().into()),
+            )
+            .unwrap();
+        healthcheck.await.unwrap();
+
+        let (input_lines, events) = random_lines_with_stream(100, num_lines, None);
+
+        assert_sink_compliance(&SINK_TAGS, async move { sink.run(events).await })
+            .await
+            .expect("Running sink failed");
+
+        let mut buf = [0; 256];
+        for line in input_lines {
+            let size = receiver.recv(&mut buf).await.unwrap();
+            assert_eq!(&buf[..size], line.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn unix_service_sends_datagrams() {
+        let out_path = temp_uds_path("unix_service_test");
+        let receiver = UnixDatagram::bind(&out_path).unwrap();
+
+        let (mut service, _healthcheck) = datagram_config(out_path).build_service();
+        for message in ["first", "second"] {
+            poll_fn(|cx| service.poll_ready(cx)).await.unwrap();
+            service.call(BytesMut::from(message)).await.unwrap();
+        }
+
+        let mut buf = [0; 16];
+        for message in ["first", "second"] {
+            let size = receiver.recv(&mut buf).await.unwrap();
+            assert_eq!(&buf[..size], message.as_bytes());
+        }
+    }
 }
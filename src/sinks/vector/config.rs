@@ -1,26 +1,40 @@
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, FutureExt, TryFutureExt};
 use http::Uri;
-use hyper::client::HttpConnector;
+use hyper::client::{
+    connect::dns::{GaiResolver, Name},
+    HttpConnector,
+};
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
+use serde::{Deserialize, Deserializer};
 use tonic::body::BoxBody;
-use tower::ServiceBuilder;
+use tower::{Service, ServiceBuilder};
 use vector_config::configurable_component;
 
 use super::{
     service::{VectorResponse, VectorService},
     sink::VectorSink,
-    VectorSinkError,
+    AddressRequiredSnafu, AddressesExclusiveSnafu, UnresolvedSnafu, VectorSinkError,
 };
 use crate::{
     config::{
         AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext,
         SinkHealthcheckOptions,
     },
+    dns,
     proto::vector as proto,
     sinks::{
         util::{
-            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
-            ServiceBuilderExt, TowerRequestConfig,
+            retries::RetryLogic,
+            service::{HealthConfig, HealthLogic},
+            BatchConfig, RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt,
+            TowerRequestConfig,
         },
         Healthcheck, VectorSink as VectorSinkType,
     },
@@ -46,16 +60,41 @@ pub struct VectorConfig {
     #[configurable(validation(format = "uri"))]
     #[configurable(metadata(docs::examples = "92.12.333.224:6000"))]
     #[configurable(metadata(docs::examples = "https://somehost:6000"))]
-    address: String,
+    address: Option<String>,
 
-    /// Whether or not to compress requests.
+    /// A list of downstream Vector addresses to balance requests across.
     ///
-    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    /// Each batch is sent to the least loaded of the healthy addresses. Addresses that keep failing
+    /// are taken out of rotation until they recover.
     ///
-    /// [gzip_docs]: https://www.gzip.org/
+    /// This option can't be used together with `address`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "aggregator-0:6000"))]
+    #[configurable(metadata(docs::examples = "https://aggregator-1:6000"))]
+    addresses: Vec<String>,
+
+    /// Whether to resolve each address to all of its DNS records.
+    ///
+    /// When enabled, requests are balanced across every IP address the hostname resolves to, as if
+    /// each of them was listed in `addresses`. Hostnames are only resolved when the sink starts.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    resolve_addresses: bool,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, rename = "distribution")]
+    endpoint_health: Option<HealthConfig>,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, deserialize_with = "compression_or_bool")]
+    compression: VectorCompression,
+
+    #[configurable(derived)]
     #[configurable(metadata(docs::advanced))]
     #[serde(default)]
-    compression: bool,
+    http2: Http2Config,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -78,6 +117,78 @@ pub struct VectorConfig {
     pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
+/// The compression applied to requests.
+///
+/// For backwards compatibility, `true` is also accepted as `gzip`, and `false` as `none`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorCompression {
+    /// Requests are not compressed.
+    #[default]
+    None,
+
+    /// Requests are compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    Gzip,
+
+    /// Requests are compressed with [`zstd`][zstd_docs].
+    ///
+    /// This usually compresses better than `gzip` while using less CPU. The receiving `vector`
+    /// source must run a version of Vector that supports it.
+    ///
+    /// [zstd_docs]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+fn compression_or_bool<'de, D>(deserializer: D) -> Result<VectorCompression, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CompressionOrBool {
+        Bool(bool),
+        Compression(VectorCompression),
+    }
+
+    Ok(match CompressionOrBool::deserialize(deserializer)? {
+        CompressionOrBool::Bool(true) => VectorCompression::Gzip,
+        CompressionOrBool::Bool(false) => VectorCompression::None,
+        CompressionOrBool::Compression(compression) => compression,
+    })
+}
+
+/// HTTP/2 settings of the connection to the downstream Vector.
+///
+/// Requests are multiplexed over a single connection, each on its own stream. Larger flow control
+/// windows allow more data in flight, which increases throughput on links with a high latency.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Http2Config {
+    /// The initial flow control window size of each stream.
+    ///
+    /// If not set, the HTTP/2 default of 65535 bytes is used.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 1048576))]
+    pub initial_stream_window_size: Option<u32>,
+
+    /// The initial flow control window size of the connection.
+    ///
+    /// If not set, the HTTP/2 default of 65535 bytes is used.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 4194304))]
+    pub initial_connection_window_size: Option<u32>,
+
+    /// Whether to adapt the flow control windows to the measured bandwidth-delay product.
+    ///
+    /// When enabled, the window size settings are ignored.
+    #[serde(default)]
+    pub adaptive_window: bool,
+}
+
 impl VectorConfig {
     /// Creates a `VectorConfig` with the given address.
     pub fn from_address(addr: Uri) -> Self {
@@ -95,8 +206,12 @@ impl GenerateConfig for VectorConfig {
 fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         version: None,
-        address: address.to_owned(),
-        compression: false,
+        address: Some(address.to_owned()),
+        addresses: Vec::new(),
+        resolve_addresses: false,
+        endpoint_health: None,
+        compression: VectorCompression::None,
+        http2: Http2Config::default(),
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -108,35 +223,70 @@ fn default_config(address: &str) -> VectorConfig {
 impl SinkConfig for VectorConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSinkType, Healthcheck)> {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let uri = with_default_scheme(&self.address, tls.is_tls())?;
-
-        let client = new_client(&tls, cx.proxy())?;
-
-        let healthcheck_uri = cx
-            .healthcheck
-            .uri
-            .clone()
-            .map(|uri| uri.uri)
-            .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
-        let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+        let endpoints = self.endpoints(tls.is_tls()).await?;
+
+        let mut services = Vec::new();
+        let mut healthcheck_services = Vec::new();
+        for (uri, ip) in endpoints {
+            let client = new_client(&tls, cx.proxy(), &self.http2, EndpointResolver { ip })?;
+            let endpoint = match ip {
+                Some(ip) => format!("{} ({})", uri, ip),
+                None => uri.to_string(),
+            };
+            let healthcheck_uri = cx
+                .healthcheck
+                .uri
+                .clone()
+                .map(|uri| uri.uri)
+                .unwrap_or_else(|| uri.clone());
+
+            healthcheck_services.push(VectorService::new(
+                client.clone(),
+                healthcheck_uri,
+                VectorCompression::None,
+            ));
+            services.push((endpoint, VectorService::new(client, uri, self.compression)));
+        }
+
+        // The sink is healthy as long as one of the downstream Vectors is.
+        let options = cx.healthcheck;
+        let healthcheck = futures::future::select_ok(
+            healthcheck_services
+                .into_iter()
+                .map(|service| healthcheck(service, options.clone()).boxed()),
+        )
+        .map_ok(|((), _)| ())
+        .boxed();
+
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
-        let service = ServiceBuilder::new()
-            .settings(request_settings, VectorGrpcRetryLogic)
-            .service(service);
+        let sink = if services.len() == 1 {
+            let (_, service) = services.into_iter().next().expect("one service");
+            let service = ServiceBuilder::new()
+                .settings(request_settings, VectorGrpcRetryLogic)
+                .service(service);
+
+            VectorSinkType::from_event_streamsink(VectorSink {
+                batch_settings,
+                service,
+            })
+        } else {
+            let health_config = self.endpoint_health.clone().unwrap_or_default();
+            let service = request_settings.distributed_service(
+                VectorGrpcRetryLogic,
+                services,
+                health_config,
+                VectorHealthLogic,
+            );
 
-        let sink = VectorSink {
-            batch_settings,
-            service,
+            VectorSinkType::from_event_streamsink(VectorSink {
+                batch_settings,
+                service,
+            })
         };
 
-        Ok((
-            VectorSinkType::from_event_streamsink(sink),
-            Box::pin(healthcheck),
-        ))
+        Ok((sink, healthcheck))
     }
 
     fn input(&self) -> Input {
@@ -148,6 +298,37 @@ impl SinkConfig for VectorConfig {
     }
 }
 
+impl VectorConfig {
+    /// Returns the URI of each downstream Vector, along with the IP address to connect to if it
+    /// was resolved up front.
+    async fn endpoints(&self, tls: bool) -> crate::Result<Vec<(Uri, Option<IpAddr>)>> {
+        let addresses = match (&self.address, self.addresses.is_empty()) {
+            (Some(address), true) => vec![address.clone()],
+            (None, false) => self.addresses.clone(),
+            (Some(_), false) => return AddressesExclusiveSnafu.fail().map_err(Into::into),
+            (None, true) => return AddressRequiredSnafu.fail().map_err(Into::into),
+        };
+
+        let mut endpoints = Vec::new();
+        for address in addresses {
+            let uri = with_default_scheme(&address, tls)?;
+            if self.resolve_addresses {
+                let host = uri.host().ok_or(VectorSinkError::NoHost)?.to_owned();
+                let ips = dns::Resolver.lookup_ip(host.clone()).await?;
+                let count = endpoints.len();
+                endpoints.extend(ips.map(|ip| (uri.clone(), Some(ip))));
+                if endpoints.len() == count {
+                    return UnresolvedSnafu { host }.fail().map_err(Into::into);
+                }
+            } else {
+                endpoints.push((uri, None));
+            }
+        }
+
+        Ok(endpoints)
+    }
+}
+
 /// Check to see if the remote service accepts new events.
 async fn healthcheck(
     mut service: VectorService,
@@ -207,8 +388,12 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
 fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
-) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
-    let mut http = HttpConnector::new();
+    http2: &Http2Config,
+    resolver: EndpointResolver,
+) -> crate::Result<
+    hyper::Client<ProxyConnector<HttpsConnector<HttpConnector<EndpointResolver>>>, BoxBody>,
+> {
+    let mut http = HttpConnector::new_with_resolver(resolver);
     http.enforce_http(false);
 
     let tls = tls_connector_builder(tls_settings)?;
@@ -226,7 +411,65 @@ fn new_client(
     let mut proxy = ProxyConnector::new(https).unwrap();
     proxy_config.configure(&mut proxy)?;
 
-    Ok(hyper::Client::builder().http2_only(true).build(proxy))
+    Ok(hyper::Client::builder()
+        .http2_only(true)
+        .http2_initial_stream_window_size(http2.initial_stream_window_size)
+        .http2_initial_connection_window_size(http2.initial_connection_window_size)
+        .http2_adaptive_window(http2.adaptive_window)
+        .build(proxy))
+}
+
+/// Resolves the hostname of a downstream Vector.
+///
+/// Addresses resolved up front always resolve to the same IP address, so that each of them gets
+/// its own connection while still being addressed by hostname, which TLS verification relies on.
+#[derive(Clone, Debug, Default)]
+pub struct EndpointResolver {
+    ip: Option<IpAddr>,
+}
+
+impl Service<Name> for EndpointResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        match self.ip {
+            // The port is replaced by the one of the address when connecting.
+            Some(ip) => futures::future::ok(vec![SocketAddr::new(ip, 0)].into_iter()).boxed(),
+            None => GaiResolver::new()
+                .call(name)
+                .map_ok(|addrs| addrs.collect::<Vec<_>>().into_iter())
+                .boxed(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct VectorHealthLogic;
+
+impl HealthLogic for VectorHealthLogic {
+    type Error = crate::Error;
+    type Response = VectorResponse;
+
+    fn is_healthy(&self, response: &Result<Self::Response, Self::Error>) -> Option<bool> {
+        match response {
+            Ok(_) => Some(true),
+            Err(error) => match error.downcast_ref::<VectorSinkError>() {
+                Some(VectorSinkError::Request { source }) => match source.code() {
+                    tonic::Code::Unavailable | tonic::Code::DeadlineExceeded => Some(false),
+                    _ => None,
+                },
+                _ => error
+                    .downcast_ref::<tower::timeout::error::Elapsed>()
+                    .map(|_| false),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
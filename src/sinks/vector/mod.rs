@@ -28,6 +28,15 @@ pub enum VectorSinkError {
 
     #[snafu(display("URL has no host."))]
     NoHost,
+
+    #[snafu(display("One of `address` or `addresses` must be set."))]
+    AddressRequired,
+
+    #[snafu(display("`address` and `addresses` can't be used together."))]
+    AddressesExclusive,
+
+    #[snafu(display("No IP address was resolved for {}.", host))]
+    Unresolved { host: String },
 }
 
 #[cfg(test)]
@@ -143,6 +152,68 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
     }
 
+    #[tokio::test]
+    async fn deliver_message_to_multiple_addresses() {
+        let num_lines = 100;
+
+        let in_addrs = [next_addr(), next_addr()];
+
+        let config = format!(
+            r#"addresses = ["http://{}/", "http://{}/"]
+            batch.max_events = 10"#,
+            in_addrs[0], in_addrs[1]
+        );
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let cx = SinkContext::new_test();
+
+        let (sink, _) = config.build(cx).await.unwrap();
+        let mut receivers = Vec::new();
+        let mut triggers = Vec::new();
+        for in_addr in in_addrs {
+            let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+                hyper::Response::builder()
+                    .header("grpc-status", "0") // OK
+                    .header("content-type", "application/grpc")
+                    .body(hyper::Body::from(encode_body(proto::PushEventsResponse {})))
+                    .unwrap()
+            });
+            tokio::spawn(server);
+            receivers.push(rx);
+            triggers.push(trigger);
+        }
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (mut input_lines, events) = random_lines_with_stream(8, num_lines, Some(batch));
+
+        sink.run(events).await.expect("Running sink failed");
+        drop(triggers);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let mut output_lines = Vec::new();
+        for rx in receivers {
+            output_lines.extend(get_received(rx, |_| {}).await);
+        }
+
+        input_lines.sort();
+        output_lines.sort();
+        assert_eq!(input_lines, output_lines);
+    }
+
+    #[tokio::test]
+    async fn address_and_addresses_are_exclusive() {
+        let config: VectorConfig = toml::from_str(
+            r#"address = "http://127.0.0.1:6000/"
+            addresses = ["http://127.0.0.1:6001/"]"#,
+        )
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+
+        let config: VectorConfig = toml::from_str("").unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
     #[test]
     fn test_with_default_scheme() {
         assert_eq!(
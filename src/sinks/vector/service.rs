@@ -1,7 +1,12 @@
-use std::task::{Context, Poll};
+use std::{
+    io,
+    task::{Context, Poll},
+};
 
+use bytes::{Buf, BufMut, BytesMut};
 use futures::{future::BoxFuture, TryFutureExt};
-use http::Uri;
+use http::{HeaderValue, Uri};
+use http_body::Body as _;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
@@ -11,7 +16,10 @@ use tower::Service;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
-use super::VectorSinkError;
+use super::{
+    config::{EndpointResolver, VectorCompression},
+    VectorSinkError,
+};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
@@ -63,17 +71,22 @@ impl MetaDescriptive for VectorRequest {
 
 impl VectorService {
     pub fn new(
-        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        hyper_client: hyper::Client<
+            ProxyConnector<HttpsConnector<HttpConnector<EndpointResolver>>>,
+            BoxBody,
+        >,
         uri: Uri,
-        compression: bool,
+        compression: VectorCompression,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
         let mut proto_client = proto_vector::Client::new(HyperSvc {
             uri,
             client: hyper_client,
+            compression,
         });
 
-        if compression {
+        // `tonic` doesn't support zstd, so `HyperSvc` compresses those messages itself.
+        if compression == VectorCompression::Gzip {
             proto_client = proto_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
         }
         Self {
@@ -132,12 +145,13 @@ impl Service<VectorRequest> for VectorService {
 #[derive(Clone, Debug)]
 pub struct HyperSvc {
     uri: Uri,
-    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector<EndpointResolver>>>, BoxBody>,
+    compression: VectorCompression,
 }
 
 impl Service<hyper::Request<BoxBody>> for HyperSvc {
     type Response = hyper::Response<hyper::Body>;
-    type Error = hyper::Error;
+    type Error = Error;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     // Emission of an internal event in case of errors is handled upstream by the caller.
@@ -156,6 +170,62 @@ impl Service<hyper::Request<BoxBody>> for HyperSvc {
 
         *req.uri_mut() = uri;
 
-        Box::pin(self.client.request(req))
+        let client = self.client.clone();
+        let compression = self.compression;
+        Box::pin(async move {
+            let req = match compression {
+                VectorCompression::Zstd => compress_zstd(req).await?,
+                VectorCompression::None | VectorCompression::Gzip => req,
+            };
+            client.request(req).await.map_err(Into::into)
+        })
     }
 }
+
+// Every gRPC message has a five byte header:
+// - a compressed flag (u8, 0/1 for decompressed/compressed)
+// - a length prefix, indicating the number of remaining bytes to read (u32)
+const GRPC_MESSAGE_HEADER_LEN: usize = 5;
+
+/// Compresses each gRPC message of the request with zstd.
+async fn compress_zstd(req: hyper::Request<BoxBody>) -> Result<hyper::Request<BoxBody>, Error> {
+    let (mut parts, body) = req.into_parts();
+    let mut body = hyper::body::to_bytes(body).await?;
+
+    let mut compressed = BytesMut::with_capacity(body.len());
+    while body.has_remaining() {
+        if body.len() < GRPC_MESSAGE_HEADER_LEN {
+            return Err(
+                io::Error::new(io::ErrorKind::InvalidData, "truncated gRPC message").into(),
+            );
+        }
+        let is_compressed = body.get_u8() == 1;
+        let message_len = body.get_u32() as usize;
+        if body.len() < message_len {
+            return Err(
+                io::Error::new(io::ErrorKind::InvalidData, "truncated gRPC message").into(),
+            );
+        }
+        let message = body.split_to(message_len);
+
+        if is_compressed {
+            compressed.put_u8(1);
+            compressed.put_u32(message_len as u32);
+            compressed.put(message);
+        } else {
+            let message = zstd::bulk::compress(&message, 0)?;
+            compressed.put_u8(1);
+            compressed.put_u32(u32::try_from(message.len())?);
+            compressed.put_slice(&message);
+        }
+    }
+
+    parts
+        .headers
+        .insert("grpc-encoding", HeaderValue::from_static("zstd"));
+    let body = http_body::Full::new(compressed.freeze())
+        .map_err(|never| match never {})
+        .boxed_unsync();
+
+    Ok(hyper::Request::from_parts(parts, body))
+}
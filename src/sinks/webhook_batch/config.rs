@@ -0,0 +1,294 @@
+use futures::future;
+use futures::FutureExt;
+use http::{header::HeaderName, Method};
+use indexmap::IndexMap;
+use snafu::{ResultExt, Snafu};
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    encoder::WebhookEncoder,
+    request_builder::WebhookRequestBuilder,
+    service::{WebhookRetryLogic, WebhookService},
+    sink::{WebhookPartitioner, WebhookSink},
+};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::{Auth, HttpClient},
+    sinks::{
+        util::{
+            BatchConfig, Compression, RealtimeSizeBasedDefaultBatchSettings, ServiceBuilderExt,
+            TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid header name {:?}: {}", name, source))]
+    InvalidHeaderName {
+        name: String,
+        source: http::header::InvalidHeaderName,
+    },
+    #[snafu(display("An `envelope` cannot be used with the `ndjson` body mode"))]
+    EnvelopeWithNdjson,
+}
+
+/// Configuration for the `webhook_batch` sink.
+#[configurable_component(sink("webhook_batch"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookBatchConfig {
+    /// The URI to send the batches of events to.
+    ///
+    /// Events are partitioned by the rendered URI, headers and envelope, so that each request is
+    /// sent with the values rendered from all of its events.
+    #[configurable(metadata(
+        docs::examples = "https://ingest.example.com/v1/{{ tenant }}/events"
+    ))]
+    pub uri: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub method: WebhookMethod,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    /// Templates of the HTTP headers added to each request.
+    #[configurable(metadata(
+        docs::additional_props_description = "The template of the value of an HTTP header."
+    ))]
+    #[serde(default)]
+    pub headers: IndexMap<String, Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub body_mode: BodyMode,
+
+    #[configurable(derived)]
+    pub envelope: Option<EnvelopeConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The HTTP method of the requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookMethod {
+    /// POST.
+    #[default]
+    Post,
+
+    /// PUT.
+    Put,
+
+    /// PATCH.
+    Patch,
+}
+
+impl From<WebhookMethod> for Method {
+    fn from(method: WebhookMethod) -> Self {
+        match method {
+            WebhookMethod::Post => Self::POST,
+            WebhookMethod::Put => Self::PUT,
+            WebhookMethod::Patch => Self::PATCH,
+        }
+    }
+}
+
+/// How the events of a batch are laid out in the body of a request.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyMode {
+    /// The events are sent as a JSON array, wrapped in the `envelope` if one is configured.
+    #[default]
+    JsonArray,
+
+    /// The events are sent as newline-delimited JSON objects.
+    Ndjson,
+}
+
+/// The JSON object wrapping the events of a batch.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EnvelopeConfig {
+    /// The template of the JSON object wrapping the events.
+    #[configurable(metadata(
+        docs::examples = "{\"service\": \"{{ service }}\", \"source\": \"vector\"}"
+    ))]
+    pub template: Template,
+
+    /// The field of the envelope holding the array of events.
+    #[configurable(metadata(docs::examples = "records"))]
+    #[serde(default = "default_events_field")]
+    pub events_field: String,
+}
+
+fn default_events_field() -> String {
+    "events".to_string()
+}
+
+impl GenerateConfig for WebhookBatchConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"uri = "https://ingest.example.com/v1/{{ tenant }}/events""#).unwrap()
+    }
+}
+
+impl WebhookBatchConfig {
+    fn build_partitioner(&self) -> crate::Result<WebhookPartitioner> {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                HeaderName::from_bytes(name.as_bytes())
+                    .context(InvalidHeaderNameSnafu { name })
+                    .map(|name| (name, value.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(WebhookPartitioner::new(
+            self.uri.clone(),
+            headers,
+            self.envelope
+                .as_ref()
+                .map(|envelope| envelope.template.clone()),
+        ))
+    }
+
+    fn build_encoder(&self) -> crate::Result<WebhookEncoder> {
+        if self.envelope.is_some() && self.body_mode == BodyMode::Ndjson {
+            return Err(BuildError::EnvelopeWithNdjson.into());
+        }
+
+        Ok(WebhookEncoder::new(
+            self.encoding.clone(),
+            self.body_mode,
+            self.envelope
+                .as_ref()
+                .map_or_else(default_events_field, |envelope| {
+                    envelope.events_field.clone()
+                }),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for WebhookBatchConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let partitioner = self.build_partitioner()?;
+        let encoder = self.build_encoder()?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let service = WebhookService::new(client, self.method.into(), self.auth.clone());
+        let service = ServiceBuilder::new()
+            .settings(request_settings, WebhookRetryLogic)
+            .service(service);
+
+        let request_builder = WebhookRequestBuilder::new(self.compression, encoder, self.body_mode);
+        let sink = WebhookSink::new(batch_settings, partitioner, request_builder, service);
+
+        // The URI being a template, there is no single endpoint to check the health of.
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            future::ok(()).boxed(),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebhookBatchConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config = toml::from_str::<WebhookBatchConfig>(
+            r#"
+            uri = "https://ingest.example.com/v1/{{ tenant }}/events"
+            method = "put"
+            headers.X-Tenant = "{{ tenant }}"
+            envelope.template = '{"tenant": "{{ tenant }}"}'
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.method, WebhookMethod::Put);
+        assert_eq!(config.body_mode, BodyMode::JsonArray);
+        assert_eq!(config.envelope.as_ref().unwrap().events_field, "events");
+        assert!(config.build_partitioner().is_ok());
+        assert!(config.build_encoder().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let config = toml::from_str::<WebhookBatchConfig>(
+            r#"
+            uri = "https://ingest.example.com/events"
+            body_mode = "ndjson"
+            envelope.template = '{"source": "vector"}'
+        "#,
+        )
+        .unwrap();
+        assert!(config.build_encoder().is_err());
+
+        let config = toml::from_str::<WebhookBatchConfig>(
+            r#"
+            uri = "https://ingest.example.com/events"
+            headers."X Tenant" = "{{ tenant }}"
+        "#,
+        )
+        .unwrap();
+        assert!(config.build_partitioner().is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use std::io;
+
+use codecs::{JsonSerializer, MetricTagValues};
+use serde_json::Value;
+
+use super::config::BodyMode;
+use crate::{codecs::Transformer, event::Event, sinks::util::encoding::Encoder};
+
+/// The events of a request, along with the envelope rendered for their partition.
+pub struct WebhookEvents {
+    pub envelope: Option<String>,
+    pub events: Vec<Event>,
+}
+
+#[derive(Clone)]
+pub struct WebhookEncoder {
+    transformer: Transformer,
+    serializer: JsonSerializer,
+    body_mode: BodyMode,
+    events_field: String,
+}
+
+impl WebhookEncoder {
+    pub const fn new(transformer: Transformer, body_mode: BodyMode, events_field: String) -> Self {
+        Self {
+            transformer,
+            serializer: JsonSerializer::new(MetricTagValues::Full),
+            body_mode,
+            events_field,
+        }
+    }
+}
+
+impl Encoder<WebhookEvents> for WebhookEncoder {
+    fn encode_input(&self, input: WebhookEvents, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let values = input
+            .events
+            .into_iter()
+            .map(|mut event| {
+                self.transformer.transform(&mut event);
+                self.serializer
+                    .to_json_value(event)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let body = match (self.body_mode, input.envelope) {
+            (BodyMode::Ndjson, _) => {
+                let mut body = Vec::new();
+                for value in values {
+                    serde_json::to_writer(&mut body, &value)?;
+                    body.push(b'\n');
+                }
+                body
+            }
+            (BodyMode::JsonArray, None) => serde_json::to_vec(&values)?,
+            (BodyMode::JsonArray, Some(envelope)) => {
+                let Value::Object(mut envelope) = serde_json::from_str::<Value>(&envelope)? else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "The rendered envelope is not a JSON object.",
+                    ));
+                };
+                envelope.insert(self.events_field.clone(), Value::Array(values));
+                serde_json::to_vec(&envelope)?
+            }
+        };
+
+        writer.write_all(&body)?;
+        Ok(body.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn encode(encoder: &WebhookEncoder, envelope: Option<&str>) -> io::Result<String> {
+        let events = ["first", "second"]
+            .into_iter()
+            .map(|message| {
+                let mut log = LogEvent::default();
+                log.insert("message", message);
+                Event::from(log)
+            })
+            .collect();
+        let mut body = Vec::new();
+        encoder.encode_input(
+            WebhookEvents {
+                envelope: envelope.map(Into::into),
+                events,
+            },
+            &mut body,
+        )?;
+        Ok(String::from_utf8(body).unwrap())
+    }
+
+    #[test]
+    fn encodes_json_array() {
+        let encoder = WebhookEncoder::new(
+            Transformer::default(),
+            BodyMode::JsonArray,
+            "events".to_string(),
+        );
+        assert_eq!(
+            encode(&encoder, None).unwrap(),
+            r#"[{"message":"first"},{"message":"second"}]"#
+        );
+    }
+
+    #[test]
+    fn encodes_ndjson() {
+        let encoder = WebhookEncoder::new(
+            Transformer::default(),
+            BodyMode::Ndjson,
+            "events".to_string(),
+        );
+        assert_eq!(
+            encode(&encoder, None).unwrap(),
+            "{\"message\":\"first\"}\n{\"message\":\"second\"}\n"
+        );
+    }
+
+    #[test]
+    fn wraps_batch_in_envelope() {
+        let encoder = WebhookEncoder::new(
+            Transformer::default(),
+            BodyMode::JsonArray,
+            "records".to_string(),
+        );
+        let body = encode(&encoder, Some(r#"{"tenant": "acme"}"#)).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap(),
+            serde_json::json!({
+                "tenant": "acme",
+                "records": [{"message": "first"}, {"message": "second"}]
+            })
+        );
+
+        assert!(encode(&encoder, Some("[]")).is_err());
+        assert!(encode(&encoder, Some("{")).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+//! The `webhook_batch` sink.
+//!
+//! Sends batches of events to HTTP APIs whose URI, headers and body envelope depend on the events
+//! themselves. Each of them is a template: events are partitioned by their rendered values, and
+//! every request is built from the values shared by all of its events.
+
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::WebhookBatchConfig;
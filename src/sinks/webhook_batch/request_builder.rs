@@ -0,0 +1,112 @@
+use std::io;
+
+use bytes::Bytes;
+use http::header::HeaderName;
+use vector_common::{
+    finalization::{EventFinalizers, Finalizable},
+    request_metadata::RequestMetadata,
+};
+use vector_core::event::Event;
+
+use super::{
+    config::BodyMode,
+    encoder::{WebhookEncoder, WebhookEvents},
+    service::WebhookRequest,
+};
+use crate::sinks::util::{
+    metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression, RequestBuilder,
+};
+
+/// The values rendered from the templates of the sink, shared by all the events of a request.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct WebhookPartitionKey {
+    pub uri: String,
+    pub headers: Vec<(HeaderName, String)>,
+    pub envelope: Option<String>,
+}
+
+pub struct WebhookRequestMetadata {
+    uri: String,
+    headers: Vec<(HeaderName, String)>,
+    finalizers: EventFinalizers,
+}
+
+#[derive(Clone)]
+pub struct WebhookRequestBuilder {
+    compression: Compression,
+    encoder: WebhookEncoder,
+    body_mode: BodyMode,
+}
+
+impl WebhookRequestBuilder {
+    pub const fn new(
+        compression: Compression,
+        encoder: WebhookEncoder,
+        body_mode: BodyMode,
+    ) -> Self {
+        Self {
+            compression,
+            encoder,
+            body_mode,
+        }
+    }
+}
+
+impl RequestBuilder<(WebhookPartitionKey, Vec<Event>)> for WebhookRequestBuilder {
+    type Metadata = WebhookRequestMetadata;
+    type Events = WebhookEvents;
+    type Encoder = WebhookEncoder;
+    type Payload = Bytes;
+    type Request = WebhookRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (WebhookPartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+
+        let metadata = WebhookRequestMetadata {
+            uri: key.uri,
+            headers: key.headers,
+            finalizers,
+        };
+        let events = WebhookEvents {
+            envelope: key.envelope,
+            events,
+        };
+        (metadata, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        webhook_metadata: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let content_type = match self.body_mode {
+            BodyMode::JsonArray => "application/json",
+            BodyMode::Ndjson => "application/x-ndjson",
+        };
+
+        WebhookRequest {
+            uri: webhook_metadata.uri,
+            headers: webhook_metadata.headers,
+            content_type,
+            content_encoding: self.compression.content_encoding(),
+            body: payload.into_payload(),
+            finalizers: webhook_metadata.finalizers,
+            metadata,
+        }
+    }
+}
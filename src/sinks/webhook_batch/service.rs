@@ -0,0 +1,171 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{header::HeaderName, Method, StatusCode};
+use snafu::{ResultExt, Snafu};
+use tower::Service;
+use tracing::Instrument;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    stream::DriverResponse,
+};
+
+use crate::{
+    http::{Auth, HttpClient, HttpError},
+    sinks::util::retries::RetryLogic,
+};
+
+#[derive(Clone)]
+pub struct WebhookRetryLogic;
+
+impl RetryLogic for WebhookRetryLogic {
+    type Error = WebhookError;
+    type Response = WebhookResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            WebhookError::ServerError { code } => match *code {
+                StatusCode::TOO_MANY_REQUESTS => true,
+                StatusCode::NOT_IMPLEMENTED => false,
+                _ if code.is_server_error() => true,
+                _ => false,
+            },
+            WebhookError::HttpError { .. } => true,
+            // The rendered URI or headers are invalid, which retrying will not fix.
+            WebhookError::BuildRequest { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum WebhookError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: HttpError },
+    #[snafu(display("Failed to build the HTTP request: {}", source))]
+    BuildRequest { source: http::Error },
+}
+
+#[derive(Clone)]
+pub struct WebhookRequest {
+    pub uri: String,
+    pub headers: Vec<(HeaderName, String)>,
+    pub content_type: &'static str,
+    pub content_encoding: Option<&'static str>,
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for WebhookRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for WebhookRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub struct WebhookResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for WebhookResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookService {
+    client: HttpClient,
+    method: Method,
+    auth: Option<Auth>,
+}
+
+impl WebhookService {
+    pub(super) const fn new(client: HttpClient, method: Method, auth: Option<Auth>) -> Self {
+        Self {
+            client,
+            method,
+            auth,
+        }
+    }
+
+    fn build_request(
+        &self,
+        request: WebhookRequest,
+    ) -> Result<http::Request<hyper::Body>, WebhookError> {
+        let mut builder = http::Request::builder()
+            .method(self.method.clone())
+            .uri(request.uri)
+            .header("Content-Type", request.content_type);
+
+        if let Some(content_encoding) = request.content_encoding {
+            builder = builder.header("Content-Encoding", content_encoding);
+        }
+
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+
+        let mut req = builder
+            .body(hyper::Body::from(request.body))
+            .context(BuildRequestSnafu)?;
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut req);
+        }
+
+        Ok(req)
+    }
+}
+
+impl Service<WebhookRequest> for WebhookService {
+    type Response = WebhookResponse;
+    type Error = WebhookError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: WebhookRequest) -> Self::Future {
+        let metadata = request.get_metadata();
+        let req = self.build_request(request);
+        let mut client = self.client.clone();
+
+        Box::pin(async move {
+            match client.call(req?).in_current_span().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        Ok(WebhookResponse { metadata })
+                    } else {
+                        Err(WebhookError::ServerError { code: status })
+                    }
+                }
+                Err(error) => Err(WebhookError::HttpError { error }),
+            }
+        })
+    }
+}
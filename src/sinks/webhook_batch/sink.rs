@@ -0,0 +1,160 @@
+use std::num::NonZeroUsize;
+
+use futures_util::{stream::BoxStream, StreamExt};
+use http::header::HeaderName;
+use vector_core::{
+    event::Event, partition::Partitioner, sink::StreamSink, stream::BatcherSettings,
+};
+
+use super::{
+    request_builder::{WebhookPartitionKey, WebhookRequestBuilder},
+    service::{WebhookRetryLogic, WebhookService},
+};
+use crate::{
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
+    sinks::util::{service::Svc, SinkBuilderExt},
+    template::Template,
+};
+
+/// Partitions events by the values rendered from the URI, headers and envelope templates.
+pub struct WebhookPartitioner {
+    uri: Template,
+    headers: Vec<(HeaderName, Template)>,
+    envelope: Option<Template>,
+}
+
+impl WebhookPartitioner {
+    pub(super) const fn new(
+        uri: Template,
+        headers: Vec<(HeaderName, Template)>,
+        envelope: Option<Template>,
+    ) -> Self {
+        Self {
+            uri,
+            headers,
+            envelope,
+        }
+    }
+}
+
+fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+impl Partitioner for WebhookPartitioner {
+    type Item = Event;
+    type Key = Option<WebhookPartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let uri = render(&self.uri, item, "uri")?;
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| Some((name.clone(), render(value, item, "headers")?)))
+            .collect::<Option<Vec<_>>>()?;
+        let envelope = match &self.envelope {
+            Some(envelope) => Some(render(envelope, item, "envelope")?),
+            None => None,
+        };
+
+        Some(WebhookPartitionKey {
+            uri,
+            headers,
+            envelope,
+        })
+    }
+}
+
+pub struct WebhookSink {
+    batch_settings: BatcherSettings,
+    partitioner: WebhookPartitioner,
+    request_builder: WebhookRequestBuilder,
+    service: Svc<WebhookService, WebhookRetryLogic>,
+}
+
+impl WebhookSink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        partitioner: WebhookPartitioner,
+        request_builder: WebhookRequestBuilder,
+        service: Svc<WebhookService, WebhookRetryLogic>,
+    ) -> Self {
+        Self {
+            batch_settings,
+            partitioner,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+
+        input
+            .batched_partitioned(self.partitioner, self.batch_settings)
+            .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("http")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for WebhookSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn partitions_by_rendered_templates() {
+        let partitioner = WebhookPartitioner::new(
+            Template::try_from("https://example.com/{{ tenant }}").unwrap(),
+            vec![(
+                HeaderName::from_static("x-service"),
+                Template::try_from("{{ service }}").unwrap(),
+            )],
+            Some(Template::try_from(r#"{"tenant": "{{ tenant }}"}"#).unwrap()),
+        );
+
+        let mut log = LogEvent::from("message");
+        log.insert("tenant", "acme");
+        log.insert("service", "api");
+        assert_eq!(
+            partitioner.partition(&Event::from(log.clone())),
+            Some(WebhookPartitionKey {
+                uri: "https://example.com/acme".to_string(),
+                headers: vec![(HeaderName::from_static("x-service"), "api".to_string())],
+                envelope: Some(r#"{"tenant": "acme"}"#.to_string()),
+            })
+        );
+
+        log.remove("service");
+        assert_eq!(partitioner.partition(&Event::from(log)), None);
+    }
+}
@@ -0,0 +1,96 @@
+use std::{net::SocketAddr, num::NonZeroUsize};
+
+use futures::FutureExt;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::EncodingConfig,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, Resource, SinkConfig, SinkContext},
+    http::Auth,
+    sinks::{websocket_server::sink::WebSocketServerSink, Healthcheck, VectorSink},
+    tls::TlsEnableableConfig,
+};
+
+/// Configuration for the `websocket_server` sink.
+#[configurable_component(sink("websocket_server"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketServerSinkConfig {
+    /// The socket address to listen for WebSocket connections on.
+    #[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+    pub address: SocketAddr,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    /// The maximum number of messages buffered for each client.
+    ///
+    /// Clients that fall further behind skip the oldest messages, rather than slowing down the
+    /// sink and the other clients.
+    #[serde(default = "default_client_buffer_size")]
+    #[configurable(metadata(docs::advanced))]
+    pub client_buffer_size: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_client_buffer_size() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("static non-zero value")
+}
+
+impl GenerateConfig for WebSocketServerSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "0.0.0.0:8080"
+            encoding.codec = "json"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for WebSocketServerSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = WebSocketServerSink::new(self)?;
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            futures::future::ok(()).boxed(),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketServerSinkConfig>();
+    }
+}
@@ -0,0 +1,373 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, SinkExt, StreamExt};
+use indexmap::IndexMap;
+use stream_cancel::Tripwire;
+use tokio::{
+    net::TcpStream,
+    sync::broadcast::{self, error::RecvError},
+};
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tokio_util::codec::Encoder as _;
+use tracing::Instrument;
+use vector_core::{
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    common::websocket::{accept, is_closed},
+    emit,
+    event::{Event, EventStatus, Finalizable, LogEvent},
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, OpenGauge, SocketBindError, SocketMode, SocketReceiveError,
+        TcpSocketConnectionEstablished, WsClientLagged, WsConnectionError, WsHandshakeError,
+    },
+    sinks::util::StreamSink,
+    sinks::websocket_server::config::WebSocketServerSinkConfig,
+    tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+};
+
+/// An encoded event, broadcast to the connected clients.
+struct Broadcast {
+    /// The event, to match it against the filters of the clients.
+    log: LogEvent,
+    message: Message,
+    byte_size: usize,
+}
+
+/// The fields a client subscribes to, read from the query parameters of its request.
+///
+/// An event is sent to the client if, for each of the fields, its value is one of the values
+/// given for that field. Clients without query parameters receive all the events.
+#[derive(Debug, Default)]
+struct ClientFilter {
+    fields: IndexMap<String, Vec<String>>,
+}
+
+impl ClientFilter {
+    fn new(query: Vec<(String, String)>) -> Self {
+        let mut fields = IndexMap::<String, Vec<String>>::new();
+        for (field, value) in query {
+            fields.entry(field).or_default().push(value);
+        }
+        Self { fields }
+    }
+
+    fn matches(&self, log: &LogEvent) -> bool {
+        self.fields.iter().all(|(field, values)| {
+            log.get(field.as_str()).map_or(false, |value| {
+                let value = value.to_string_lossy();
+                values.iter().any(|expected| expected.as_str() == value)
+            })
+        })
+    }
+}
+
+pub struct WebSocketServerSink {
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    auth: Option<Auth>,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    client_buffer_size: usize,
+}
+
+impl WebSocketServerSink {
+    pub fn new(config: &WebSocketServerSinkConfig) -> crate::Result<Self> {
+        let tls = MaybeTlsSettings::from_config(&config.tls, true)?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build()?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(Self {
+            address: config.address,
+            tls,
+            auth: config.auth.clone(),
+            transformer,
+            encoder,
+            client_buffer_size: config.client_buffer_size.get(),
+        })
+    }
+
+    fn encode(&mut self, mut event: Event) -> Option<Broadcast> {
+        self.transformer.transform(&mut event);
+        let byte_size = event.estimated_json_encoded_size_of();
+        let log = event.as_log().clone();
+
+        let mut bytes = BytesMut::new();
+        // Errors are handled by `Encoder`.
+        self.encoder.encode(event, &mut bytes).ok()?;
+        let message = match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => Message::Text(text),
+            Err(error) => Message::Binary(error.into_bytes()),
+        };
+
+        Some(Broadcast {
+            log,
+            message,
+            byte_size,
+        })
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for WebSocketServerSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let listener = self.tls.bind(&self.address).await.map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Tcp,
+                error,
+            })
+        })?;
+        info!(message = "Listening.", addr = %self.address);
+
+        let (sender, _) = broadcast::channel(self.client_buffer_size);
+        let (trigger, tripwire) = Tripwire::new();
+        tokio::spawn(accept_clients(
+            listener,
+            self.auth.clone(),
+            sender.clone(),
+            tripwire,
+        ));
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            // Events are not kept for the clients connecting later on.
+            if sender.receiver_count() == 0 {
+                finalizers.update_status(EventStatus::Delivered);
+                continue;
+            }
+
+            match self.encode(event) {
+                Some(broadcast) => {
+                    finalizers.update_status(EventStatus::Delivered);
+                    // The clients may have disconnected in the meantime.
+                    let _ = sender.send(Arc::new(broadcast));
+                }
+                None => finalizers.update_status(EventStatus::Errored),
+            }
+        }
+
+        // Stops accepting new clients. The connected clients are closed once they are sent the
+        // events already broadcast.
+        drop(trigger);
+        Ok(())
+    }
+}
+
+async fn accept_clients(
+    listener: MaybeTlsListener,
+    auth: Option<Auth>,
+    sender: broadcast::Sender<Arc<Broadcast>>,
+    tripwire: Tripwire,
+) {
+    let connection_gauge = OpenGauge::new();
+    let mut connections = listener.accept_stream().take_until(tripwire);
+    while let Some(connection) = connections.next().await {
+        let stream = match connection {
+            Ok(stream) => stream,
+            Err(error) => {
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Tcp,
+                    error: &error
+                });
+                continue;
+            }
+        };
+
+        let peer_addr = stream.peer_addr();
+        let span = info_span!("connection", %peer_addr);
+        // Subscribing before the handshake, so that the client does not miss the events sent in
+        // the meantime.
+        let receiver = sender.subscribe();
+        tokio::spawn(
+            serve_client(stream, auth.clone(), receiver, connection_gauge.clone()).instrument(span),
+        );
+    }
+}
+
+async fn serve_client(
+    stream: MaybeTlsIncomingStream<TcpStream>,
+    auth: Option<Auth>,
+    mut receiver: broadcast::Receiver<Arc<Broadcast>>,
+    connection_gauge: OpenGauge,
+) {
+    let peer_addr = stream.peer_addr();
+    let (ws_stream, query) = match accept(stream, auth.as_ref()).await {
+        Ok(accepted) => accepted,
+        Err(error) => {
+            emit!(WsHandshakeError { error });
+            return;
+        }
+    };
+    emit!(TcpSocketConnectionEstablished {
+        peer_addr: Some(peer_addr)
+    });
+    let _open_token = connection_gauge.open(|count| emit!(ConnectionOpen { count }));
+
+    let filter = ClientFilter::new(query);
+    let (mut ws_sink, mut ws_stream) = ws_stream.split();
+
+    let bytes_sent = register!(BytesSent::from(Protocol("websocket".into())));
+    let events_sent = register!(EventsSent::from(Output(None)));
+
+    loop {
+        tokio::select! {
+            broadcast = receiver.recv() => match broadcast {
+                Ok(broadcast) => {
+                    if !filter.matches(&broadcast.log) {
+                        continue;
+                    }
+
+                    let message = broadcast.message.clone();
+                    let message_len = message.len();
+                    if let Err(error) = ws_sink.send(message).await {
+                        if !is_closed(&error) {
+                            emit!(WsConnectionError { error });
+                        }
+                        break;
+                    }
+                    events_sent.emit(CountByteSize(1, broadcast.byte_size));
+                    bytes_sent.emit(ByteSize(message_len));
+                }
+                Err(RecvError::Lagged(count)) => emit!(WsClientLagged { count }),
+                Err(RecvError::Closed) => {
+                    let _ = ws_sink.close().await;
+                    break;
+                }
+            },
+
+            // Messages from the client are ignored, but reading them answers its pings and
+            // notices when it closes the connection.
+            message = ws_stream.next() => match message {
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(error)) => {
+                    if !is_closed(&error) {
+                        emit!(WsConnectionError { error });
+                    }
+                    break;
+                }
+            },
+        }
+    }
+
+    debug!(message = "Client disconnected.");
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{channel::mpsc, Stream};
+    use tokio::time::{timeout, Duration};
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{client::IntoClientRequest, Error as WsError},
+    };
+    use vector_core::event::EventArray;
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        test_util::{next_addr, trace_init, wait_for_tcp},
+    };
+
+    async fn next_message(
+        ws_stream: &mut (impl Stream<Item = Result<Message, WsError>> + Unpin),
+    ) -> Message {
+        timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap()
+    }
+
+    fn log(service: &str, message: &str) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("service", service);
+        log.insert("message", message);
+        log
+    }
+
+    #[test]
+    fn filters_events_by_query_parameters() {
+        let filter = ClientFilter::new(vec![]);
+        assert!(filter.matches(&log("api", "hello")));
+
+        let filter = ClientFilter::new(vec![
+            ("service".to_owned(), "api".to_owned()),
+            ("service".to_owned(), "web".to_owned()),
+        ]);
+        assert!(filter.matches(&log("api", "hello")));
+        assert!(filter.matches(&log("web", "hello")));
+        assert!(!filter.matches(&log("db", "hello")));
+
+        let filter = ClientFilter::new(vec![
+            ("service".to_owned(), "api".to_owned()),
+            ("level".to_owned(), "error".to_owned()),
+        ]);
+        assert!(!filter.matches(&log("api", "hello")));
+        let mut error = log("api", "hello");
+        error.insert("level", "error");
+        assert!(filter.matches(&error));
+    }
+
+    #[tokio::test]
+    async fn broadcasts_filtered_events_to_clients() {
+        trace_init();
+
+        let addr = next_addr();
+        let config = toml::from_str::<WebSocketServerSinkConfig>(&format!(
+            r#"
+            address = "{}"
+            encoding.codec = "text"
+            auth.strategy = "bearer"
+            auth.token = "secret"
+            "#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (tx, rx) = mpsc::unbounded::<EventArray>();
+        tokio::spawn(sink.run(rx));
+        wait_for_tcp(addr).await;
+
+        let request = |path: &str, token: &str| {
+            let mut request = format!("ws://{}{}", addr, path)
+                .into_client_request()
+                .unwrap();
+            request.headers_mut().insert(
+                "Authorization",
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            request
+        };
+
+        let unauthorized = connect_async(request("/", "invalid")).await;
+        assert!(matches!(unauthorized, Err(WsError::Http(response)) if response.status() == 401));
+
+        let (mut all, _) = connect_async(request("/", "secret")).await.unwrap();
+        let (mut api, _) = connect_async(request("/?service=api", "secret"))
+            .await
+            .unwrap();
+
+        for (service, message) in [("web", "first"), ("api", "second")] {
+            tx.unbounded_send(Event::from(log(service, message)).into())
+                .unwrap();
+        }
+        drop(tx);
+
+        assert_eq!(next_message(&mut all).await, Message::text("first"));
+        assert_eq!(next_message(&mut all).await, Message::text("second"));
+        assert_eq!(next_message(&mut api).await, Message::text("second"));
+        assert!(next_message(&mut api).await.is_close());
+    }
+}
@@ -0,0 +1,122 @@
+//! Per-source log schema overrides.
+//!
+//! Sources configured with `log_schema` receive events whose message, timestamp or host are held by
+//! other fields than the ones of the global log schema, such as events sent by other log shippers.
+//! The topology moves these fields to the ones of the global log schema before sending the events
+//! downstream.
+use lookup::lookup_v2::{ConfigValuePath, OwnedTargetPath};
+use vector_config::configurable_component;
+use vector_core::config::{log_schema, LogNamespace};
+
+use crate::event::{EventArray, LogEvent};
+
+/// The fields of the global log schema held by other fields in the events received by the source.
+///
+/// These fields are moved to the fields of the global log schema, replacing them if they exist,
+/// before the events are sent downstream. Only the events using the `legacy` log namespace are
+/// remapped.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SourceLogSchema {
+    /// The field holding the message of the events.
+    #[configurable(metadata(docs::examples = "msg"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<ConfigValuePath>,
+
+    /// The field holding the timestamp of the events.
+    #[configurable(metadata(docs::examples = "@timestamp"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_key: Option<ConfigValuePath>,
+
+    /// The field holding the host which sent the events.
+    #[configurable(metadata(docs::examples = "hostname"))]
+    #[configurable(metadata(docs::examples = "agent.hostname"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_key: Option<ConfigValuePath>,
+}
+
+/// Moves the fields of the log events of a source to the fields of the global log schema.
+#[derive(Clone, Debug)]
+pub struct LogSchemaRemap {
+    renames: Vec<(OwnedTargetPath, OwnedTargetPath)>,
+}
+
+impl LogSchemaRemap {
+    /// Returns the remapping of the given overrides, if anything is to be remapped.
+    pub fn new(overrides: &SourceLogSchema) -> Option<Self> {
+        let schema = log_schema();
+        let targets = [
+            (&overrides.message_key, Some(schema.owned_message_path())),
+            (
+                &overrides.timestamp_key,
+                schema.timestamp_key().cloned().map(OwnedTargetPath::event),
+            ),
+            (
+                &overrides.host_key,
+                lookup::lookup_v2::parse_target_path(schema.host_key()).ok(),
+            ),
+        ];
+        let renames = targets
+            .into_iter()
+            .filter_map(|(from, to)| Some((OwnedTargetPath::event(from.clone()?.0), to?)))
+            .filter(|(from, to)| from != to)
+            .collect::<Vec<_>>();
+        (!renames.is_empty()).then_some(Self { renames })
+    }
+
+    pub fn remap(&self, array: &mut EventArray) {
+        if let EventArray::Logs(logs) = array {
+            for log in logs {
+                self.remap_log(log);
+            }
+        }
+    }
+
+    fn remap_log(&self, log: &mut LogEvent) {
+        if log.namespace() != LogNamespace::Legacy {
+            return;
+        }
+        for (from, to) in &self.renames {
+            log.rename_key(from, to);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaps_into_global_log_schema() {
+        let overrides: SourceLogSchema = toml::from_str(
+            r#"
+            message_key = "msg"
+            host_key = "agent.hostname"
+            "#,
+        )
+        .unwrap();
+        let remap = LogSchemaRemap::new(&overrides).unwrap();
+
+        let mut log = LogEvent::default();
+        log.insert("msg", "hello");
+        log.insert("agent.hostname", "web-1");
+        log.insert("other", "kept");
+        let mut array = EventArray::from(log);
+        remap.remap(&mut array);
+
+        let EventArray::Logs(logs) = array else {
+            panic!("expected logs");
+        };
+        assert_eq!(logs[0]["message"], "hello".into());
+        assert_eq!(logs[0]["host"], "web-1".into());
+        assert_eq!(logs[0]["other"], "kept".into());
+        assert!(logs[0].get("msg").is_none());
+    }
+
+    #[test]
+    fn skips_identical_keys() {
+        let overrides: SourceLogSchema = toml::from_str(r#"message_key = "message""#).unwrap();
+        assert!(LogSchemaRemap::new(&overrides).is_none());
+    }
+}
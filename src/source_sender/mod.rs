@@ -262,7 +262,9 @@ impl Inner {
         events
             .iter_events()
             .for_each(|event| self.emit_lag_time(event, reference));
-        let byte_size = events.estimated_json_encoded_size_of();
+        let events_sent = &self.events_sent;
+        let byte_size =
+            events.estimated_json_encoded_size_with(|size| events_sent.record_event_size(size));
         let count = events.len();
         self.inner.send(events).await.map_err(|_| ClosedError)?;
         self.events_sent.emit(CountByteSize(count, byte_size));
@@ -296,7 +298,10 @@ impl Inner {
             events
                 .iter_events()
                 .for_each(|event| self.emit_lag_time(event, reference));
-            let cbs = CountByteSize(events.len(), events.estimated_json_encoded_size_of());
+            let events_sent = &self.events_sent;
+            let byte_size =
+                events.estimated_json_encoded_size_with(|size| events_sent.record_event_size(size));
+            let cbs = CountByteSize(events.len(), byte_size);
             match self.inner.send(events).await {
                 Ok(()) => {
                     self.events_sent.emit(cbs);
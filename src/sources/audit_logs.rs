@@ -0,0 +1,146 @@
+use futures::StreamExt;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use value::Kind;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, schema::Definition};
+
+use crate::{
+    audit::{self, AuditRecord},
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{EstimatedJsonEncodedSizeOf, Event, LogEvent},
+    internal_events::{
+        AuditLogsBytesReceived, AuditLogsEventsDropped, EventsReceived, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `audit_logs` source.
+#[configurable_component(source(
+    "audit_logs",
+    "Expose the audit log of configuration and control operations of the running Vector instance."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AuditLogsConfig {
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl_generate_config_from_default!(AuditLogsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "audit_logs")]
+impl SourceConfig for AuditLogsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let records = BroadcastStream::new(audit::subscribe());
+
+        Ok(Box::pin(run(records, cx.out, cx.shutdown, log_namespace)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition =
+            Definition::new_with_default_metadata(Kind::any_object(), [log_namespace])
+                .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    records: BroadcastStream<AuditRecord>,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+    let mut records = records.take_until(shutdown);
+
+    while let Some(record) = records.next().await {
+        let record = match record {
+            Ok(record) => record,
+            Err(BroadcastStreamRecvError::Lagged(count)) => {
+                emit!(AuditLogsEventsDropped { count });
+                continue;
+            }
+        };
+
+        let mut log = serde_json::to_value(&record.action)
+            .map_err(Into::into)
+            .and_then(LogEvent::try_from)
+            .expect("audit actions are serialized as objects");
+        log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            AuditLogsConfig::NAME,
+            record.timestamp,
+        );
+
+        let byte_size = log.estimated_json_encoded_size_of();
+        emit!(AuditLogsBytesReceived { byte_size });
+        events_received.emit(CountByteSize(1, byte_size));
+
+        if let Err(error) = out.send_event(Event::from(log)).await {
+            emit!(StreamClosedError { error, count: 1 });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::config::log_schema;
+
+    use super::*;
+    use crate::{
+        audit::AuditAction,
+        test_util::{
+            collect_ready,
+            components::{assert_source_compliance, SOURCE_TAGS},
+        },
+    };
+
+    #[test]
+    fn generates_config() {
+        crate::test_util::test_generate_config::<AuditLogsConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_audit_records() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let source = AuditLogsConfig::default()
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+
+            audit::record(AuditAction::TapStopped { session_id: 42 });
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+            // Other tests may record operations concurrently.
+            let events = collect_ready(rx).await;
+            let log = events
+                .iter()
+                .map(Event::as_log)
+                .find(|log| {
+                    log.get("action") == Some(&"tap_stopped".into())
+                        && log.get("session_id") == Some(&42.into())
+                })
+                .expect("the audit record was received");
+            assert_eq!(log["source_type"], "audit_logs".into());
+            assert!(log.get(log_schema().timestamp_key()).is_some());
+        })
+        .await;
+    }
+}
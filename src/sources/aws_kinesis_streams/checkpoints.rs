@@ -0,0 +1,246 @@
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::PathBuf,
+};
+
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+use file_source::CheckpointStore;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+
+use crate::internal_events::AwsKinesisStreamsCheckpointWriteError;
+
+const CHECKPOINT_FILE_NAME: &str = "checkpoints.json";
+const TMP_CHECKPOINT_FILE_NAME: &str = "checkpoints.new.json";
+
+#[derive(Debug, Snafu)]
+pub(super) enum CheckpointsLoadError {
+    #[snafu(display("Unable to read checkpoints from {:?}: {}", path, source))]
+    Read { source: io::Error, path: PathBuf },
+    #[snafu(display("Unable to parse checkpoints from {:?}: {}", path, source))]
+    Parse {
+        source: serde_json::Error,
+        path: PathBuf,
+    },
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[snafu(display("Unable to load checkpoints from the checkpoint store: {}", source))]
+    LoadStored { source: io::Error },
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[snafu(display("Unable to parse checkpoints from the checkpoint store: {}", source))]
+    ParseStored { source: serde_json::Error },
+}
+
+/// The position reached in a shard.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum ShardCheckpoint {
+    /// The sequence number of the last record that was processed.
+    Sequence(String),
+
+    /// The shard was closed by resharding and all of its records were processed.
+    Finished,
+}
+
+/// The positions reached in each shard, persisted across restarts.
+pub(super) struct Checkpoints {
+    data_dir: PathBuf,
+    shards: Mutex<HashMap<String, ShardCheckpoint>>,
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    store: Option<Arc<dyn CheckpointStore>>,
+}
+
+impl Checkpoints {
+    pub(super) async fn load(data_dir: PathBuf) -> Result<Self, CheckpointsLoadError> {
+        let path = data_dir.join(CHECKPOINT_FILE_NAME);
+        let shards = match tokio::fs::read(&path).await {
+            Ok(data) => serde_json::from_slice(&data).context(ParseSnafu { path })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error).context(ReadSnafu { path }),
+        };
+
+        Ok(Self {
+            data_dir,
+            shards: Mutex::new(shards),
+            #[cfg(feature = "sources-utils-checkpoint-stores")]
+            store: None,
+        })
+    }
+
+    /// Persists the checkpoints to the given store as well as to the data directory, and loads
+    /// them from the store when there are none in the data directory.
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    pub(super) async fn with_store(
+        mut self,
+        store: Box<dyn CheckpointStore>,
+    ) -> Result<Self, CheckpointsLoadError> {
+        let store: Arc<dyn CheckpointStore> = Arc::from(store);
+        let shards = self.shards.get_mut();
+        if shards.is_empty() {
+            let data = run_blocking(&store, |store| store.load())
+                .await
+                .context(LoadStoredSnafu)?;
+            if let Some(data) = data {
+                *shards = serde_json::from_slice(&data).context(ParseStoredSnafu)?;
+            }
+        }
+        self.store = Some(store);
+        Ok(self)
+    }
+
+    pub(super) async fn get(&self, shard_id: &str) -> Option<ShardCheckpoint> {
+        self.shards.lock().await.get(shard_id).cloned()
+    }
+
+    pub(super) async fn is_finished(&self, shard_id: &str) -> bool {
+        self.get(shard_id).await == Some(ShardCheckpoint::Finished)
+    }
+
+    pub(super) async fn set(&self, shard_id: &str, checkpoint: ShardCheckpoint) {
+        let mut shards = self.shards.lock().await;
+        shards.insert(shard_id.to_owned(), checkpoint);
+        self.write(&shards).await;
+    }
+
+    /// Forgets the shards that no longer exist in the stream.
+    pub(super) async fn retain(&self, shard_ids: &HashSet<String>) {
+        let mut shards = self.shards.lock().await;
+        let count = shards.len();
+        shards.retain(|shard_id, _| shard_ids.contains(shard_id));
+        if shards.len() != count {
+            self.write(&shards).await;
+        }
+    }
+
+    async fn write(&self, shards: &HashMap<String, ShardCheckpoint>) {
+        // Replace the file atomically, so a crash can't leave it partially written.
+        let tmp_path = self.data_dir.join(TMP_CHECKPOINT_FILE_NAME);
+        let path = self.data_dir.join(CHECKPOINT_FILE_NAME);
+        let data = match serde_json::to_vec(shards) {
+            Ok(data) => data,
+            Err(error) => {
+                emit!(AwsKinesisStreamsCheckpointWriteError {
+                    error: error.into(),
+                    path: &path,
+                });
+                return;
+            }
+        };
+        let result = match tokio::fs::write(&tmp_path, &data).await {
+            Ok(()) => tokio::fs::rename(&tmp_path, &path).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            emit!(AwsKinesisStreamsCheckpointWriteError { error, path: &path });
+            return;
+        }
+
+        #[cfg(feature = "sources-utils-checkpoint-stores")]
+        if let Some(store) = &self.store {
+            if let Err(error) = run_blocking(store, move |store| store.store(&data)).await {
+                emit!(AwsKinesisStreamsCheckpointWriteError { error, path: &path });
+            }
+        }
+    }
+}
+
+/// Calls the checkpoint store from a blocking thread.
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+async fn run_blocking<T: Send + 'static>(
+    store: &Arc<dyn CheckpointStore>,
+    f: impl FnOnce(&dyn CheckpointStore) -> io::Result<T> + Send + 'static,
+) -> io::Result<T> {
+    let store = Arc::clone(store);
+    tokio::task::spawn_blocking(move || f(store.as_ref()))
+        .await
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn checkpoints_persist() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let checkpoints = Checkpoints::load(dir.path().to_owned()).await.unwrap();
+        assert_eq!(checkpoints.get("shardId-000000000000").await, None);
+        checkpoints
+            .set(
+                "shardId-000000000000",
+                ShardCheckpoint::Sequence(
+                    "49590338271490256608559692538361571095921575989136588898".into(),
+                ),
+            )
+            .await;
+        checkpoints
+            .set("shardId-000000000001", ShardCheckpoint::Finished)
+            .await;
+        checkpoints
+            .set("shardId-000000000002", ShardCheckpoint::Finished)
+            .await;
+        checkpoints
+            .retain(&HashSet::from([
+                "shardId-000000000000".to_owned(),
+                "shardId-000000000001".to_owned(),
+            ]))
+            .await;
+
+        let checkpoints = Checkpoints::load(dir.path().to_owned()).await.unwrap();
+        assert_eq!(
+            checkpoints.get("shardId-000000000000").await,
+            Some(ShardCheckpoint::Sequence(
+                "49590338271490256608559692538361571095921575989136588898".into()
+            ))
+        );
+        assert!(checkpoints.is_finished("shardId-000000000001").await);
+        assert_eq!(checkpoints.get("shardId-000000000002").await, None);
+    }
+
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[derive(Clone, Default)]
+    struct MemoryStore(Arc<std::sync::Mutex<Option<Vec<u8>>>>);
+
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    impl CheckpointStore for MemoryStore {
+        fn load(&self) -> io::Result<Option<Vec<u8>>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn store(&self, checkpoints: &[u8]) -> io::Result<()> {
+            *self.0.lock().unwrap() = Some(checkpoints.to_vec());
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[tokio::test]
+    async fn checkpoints_resume_from_store() {
+        let store = MemoryStore::default();
+
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoints = Checkpoints::load(dir.path().to_owned())
+            .await
+            .unwrap()
+            .with_store(Box::new(store.clone()))
+            .await
+            .unwrap();
+        checkpoints
+            .set("shardId-000000000000", ShardCheckpoint::Finished)
+            .await;
+
+        // A fresh data directory resumes from the store.
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoints = Checkpoints::load(dir.path().to_owned())
+            .await
+            .unwrap()
+            .with_store(Box::new(store))
+            .await
+            .unwrap();
+        assert!(checkpoints.is_finished("shardId-000000000000").await);
+    }
+}
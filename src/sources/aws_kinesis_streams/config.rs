@@ -0,0 +1,275 @@
+use std::{path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Utc};
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use lookup::owned_value_path;
+use serde_with::serde_as;
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::{LegacyKey, LogNamespace};
+
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+use crate::sources::util::checkpoint_store::CheckpointStoreConfig;
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    codecs::DecodingConfig,
+    common::kinesis::KinesisClientBuilder,
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sources::aws_kinesis_streams::{checkpoints::Checkpoints, source::KinesisStreamsSource},
+    tls::TlsConfig,
+};
+
+/// How records are read from the shards of the stream.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The kind of consumer."))]
+pub enum ConsumerMode {
+    /// Records are read by polling each shard with `GetRecords` requests.
+    ///
+    /// The read throughput of each shard is shared with the other consumers of the stream.
+    Polling {
+        /// How long to wait between requests to a shard that returned no records, in milliseconds.
+        #[serde(default = "default_poll_interval_ms")]
+        #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        poll_interval_ms: Duration,
+
+        /// The maximum number of records returned by each request.
+        #[serde(default = "default_max_records")]
+        #[configurable(metadata(docs::type_unit = "records"))]
+        max_records: u16,
+    },
+
+    /// Records are pushed to a registered consumer through [enhanced fan-out][efo].
+    ///
+    /// Each consumer gets the full read throughput of each shard. The consumer is registered
+    /// with the stream if it doesn't already exist.
+    ///
+    /// [efo]: https://docs.aws.amazon.com/streams/latest/dev/enhanced-consumers.html
+    EnhancedFanOut {
+        /// The name of the consumer to register with the stream.
+        #[configurable(metadata(docs::examples = "vector"))]
+        consumer_name: String,
+    },
+}
+
+impl Default for ConsumerMode {
+    fn default() -> Self {
+        Self::Polling {
+            poll_interval_ms: default_poll_interval_ms(),
+            max_records: default_max_records(),
+        }
+    }
+}
+
+const fn default_poll_interval_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+const fn default_max_records() -> u16 {
+    1000
+}
+
+/// Where to start reading shards without a checkpoint from.
+///
+/// Shards created by resharding after the source started are always read from their start.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The kind of start position."))]
+pub enum StartPosition {
+    /// Start from the oldest record available in each shard.
+    TrimHorizon,
+
+    /// Start after the most recent record in each shard, only reading new records.
+    #[default]
+    Latest,
+
+    /// Start from the first record written at or after the given time.
+    AtTimestamp {
+        /// The time to start from.
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// Configuration for the `aws_kinesis_streams` source.
+#[serde_as]
+#[configurable_component(source(
+    "aws_kinesis_streams",
+    "Collect logs from AWS Kinesis Data Streams."
+))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKinesisStreamsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The name of the stream to read records from.
+    #[configurable(metadata(docs::examples = "my-stream"))]
+    pub stream_name: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub consumer: ConsumerMode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub start_position: StartPosition,
+
+    /// How often to look for new shards, in seconds.
+    ///
+    /// Shards are created and closed when the stream is resharded. The child shards of a closed
+    /// shard are only read once it has been read completely, preserving the ordering of the
+    /// records sharing a partition key.
+    #[serde(default = "default_shard_discovery_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_shard_discovery_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub shard_discovery_interval_secs: Duration,
+
+    /// The directory used to persist the position reached in each shard.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub data_dir: Option<PathBuf>,
+
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[configurable(derived)]
+    pub checkpoint_store: Option<CheckpointStoreConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+const fn default_shard_discovery_interval_secs() -> Duration {
+    Duration::from_secs(30)
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_kinesis_streams")]
+impl SourceConfig for AwsKinesisStreamsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let client = create_client::<KinesisClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpoints = Checkpoints::load(data_dir).await?;
+        #[cfg(feature = "sources-utils-checkpoint-stores")]
+        let checkpoints = match &self.checkpoint_store {
+            Some(checkpoint_store) => {
+                checkpoints
+                    .with_store(checkpoint_store.build(&cx.proxy).await?)
+                    .await?
+            }
+            None => checkpoints,
+        };
+
+        Ok(Box::pin(
+            KinesisStreamsSource {
+                client,
+                stream_name: self.stream_name.clone(),
+                consumer: self.consumer.clone(),
+                start_position: self.start_position.clone(),
+                shard_discovery_interval: self.shard_discovery_interval_secs,
+                decoder,
+                checkpoints,
+                acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+                log_namespace,
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition = self
+            .decoding
+            .schema_definition(global_log_namespace.merge(self.log_namespace))
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("timestamp"))),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("stream_name"))),
+                &owned_value_path!("stream_name"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("shard_id"))),
+                &owned_value_path!("shard_id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("partition_key"))),
+                &owned_value_path!("partition_key"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("sequence_number"))),
+                &owned_value_path!("sequence_number"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl_generate_config_from_default!(AwsKinesisStreamsConfig);
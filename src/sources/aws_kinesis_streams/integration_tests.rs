@@ -0,0 +1,149 @@
+#![cfg(feature = "aws-kinesis-streams-integration-tests")]
+#![cfg(test)]
+
+use std::time::Duration;
+
+use aws_sdk_kinesis::{model::StreamStatus, types::Blob, Client as KinesisClient};
+use futures::StreamExt;
+use tokio::time::{sleep, timeout};
+
+use super::{
+    config::{ConsumerMode, StartPosition},
+    AwsKinesisStreamsConfig,
+};
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::kinesis::KinesisClientBuilder,
+    config::{log_schema, ProxyConfig, SourceConfig, SourceContext},
+    test_util::random_string,
+    SourceSender,
+};
+
+fn kinesis_address() -> String {
+    std::env::var("KINESIS_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
+}
+
+async fn client() -> KinesisClient {
+    let region = RegionOrEndpoint::with_both("us-east-1", kinesis_address());
+    create_client::<KinesisClientBuilder>(
+        &AwsAuthentication::test_auth(),
+        region.region(),
+        region.endpoint().unwrap(),
+        &ProxyConfig::default(),
+        &None,
+        false,
+    )
+    .await
+    .unwrap()
+}
+
+async fn wait_until_active(client: &KinesisClient, stream_name: &str) {
+    loop {
+        let status = client
+            .describe_stream_summary()
+            .stream_name(stream_name)
+            .send()
+            .await
+            .unwrap()
+            .stream_description_summary
+            .and_then(|summary| summary.stream_status);
+        if status == Some(StreamStatus::Active) {
+            return;
+        }
+        sleep(Duration::from_millis(200)).await;
+    }
+}
+
+async fn put_records(client: &KinesisClient, stream_name: &str, prefix: &str, count: usize) {
+    for i in 0..count {
+        client
+            .put_record()
+            .stream_name(stream_name)
+            .partition_key(format!("key-{}", i))
+            .data(Blob::new(format!("{}-{}", prefix, i)))
+            .send()
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn reads_child_shards_after_their_parent() {
+    let client = client().await;
+    let stream_name = format!("test-{}", random_string(10).to_lowercase());
+    client
+        .create_stream()
+        .stream_name(&stream_name)
+        .shard_count(1)
+        .send()
+        .await
+        .unwrap();
+    wait_until_active(&client, &stream_name).await;
+
+    put_records(&client, &stream_name, "parent", 10).await;
+
+    // Split the only shard in two, closing it.
+    let shard_id = client
+        .list_shards()
+        .stream_name(&stream_name)
+        .send()
+        .await
+        .unwrap()
+        .shards
+        .unwrap()
+        .remove(0)
+        .shard_id
+        .unwrap();
+    client
+        .split_shard()
+        .stream_name(&stream_name)
+        .shard_to_split(shard_id)
+        .new_starting_hash_key((1u128 << 127).to_string())
+        .send()
+        .await
+        .unwrap();
+    wait_until_active(&client, &stream_name).await;
+
+    put_records(&client, &stream_name, "child", 10).await;
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let config = AwsKinesisStreamsConfig {
+        region: RegionOrEndpoint::with_both("us-east-1", kinesis_address()),
+        auth: AwsAuthentication::test_auth(),
+        stream_name,
+        consumer: ConsumerMode::Polling {
+            poll_interval_ms: Duration::from_millis(100),
+            max_records: 1000,
+        },
+        start_position: StartPosition::TrimHorizon,
+        shard_discovery_interval_secs: Duration::from_secs(1),
+        data_dir: Some(data_dir.path().to_owned()),
+        ..Default::default()
+    };
+
+    let (tx, rx) = SourceSender::new_test();
+    tokio::spawn(async move {
+        config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+    });
+
+    let messages = timeout(Duration::from_secs(30), rx.take(20).collect::<Vec<_>>())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|event| {
+            event.as_log()[log_schema().message_key()]
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect::<Vec<_>>();
+
+    // The records of the parent shard are all read before those of its children.
+    let (parent, children) = messages.split_at(10);
+    assert!(parent.iter().all(|message| message.starts_with("parent-")));
+    assert!(children.iter().all(|message| message.starts_with("child-")));
+}
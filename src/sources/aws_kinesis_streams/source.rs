@@ -0,0 +1,687 @@
+use std::{
+    collections::{HashMap, HashSet},
+    panic,
+    sync::Arc,
+    time::Duration,
+};
+
+use aws_sdk_kinesis::{
+    model::{
+        ConsumerStatus, Record, Shard, ShardIteratorType, StartingPosition,
+        SubscribeToShardEventStream,
+    },
+    types::{DateTime as AwsDateTime, SdkError},
+    Client as KinesisClient,
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use lookup::path;
+use tokio::{pin, select, task::JoinHandle, time};
+use tokio_stream::wrappers::IntervalStream;
+use tracing_futures::Instrument;
+use vector_common::internal_event::{EventsReceived, Registered};
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use super::{
+    checkpoints::{Checkpoints, ShardCheckpoint},
+    config::{ConsumerMode, StartPosition},
+    AwsKinesisStreamsConfig,
+};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{AwsKinesisStreamsRequestError, EndpointBytesReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    sources::util,
+    SourceSender,
+};
+
+/// How long to wait before retrying a failed request.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How long to wait for a newly registered consumer to become active.
+const CONSUMER_ACTIVATION_DELAY: Duration = Duration::from_secs(5);
+
+pub(super) struct KinesisStreamsSource {
+    pub(super) client: KinesisClient,
+    pub(super) stream_name: String,
+    pub(super) consumer: ConsumerMode,
+    pub(super) start_position: StartPosition,
+    pub(super) shard_discovery_interval: Duration,
+    pub(super) decoder: Decoder,
+    pub(super) checkpoints: Checkpoints,
+    pub(super) acknowledgements: bool,
+    pub(super) log_namespace: LogNamespace,
+}
+
+impl KinesisStreamsSource {
+    pub(super) async fn run(self, out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let consumer_arn = match &self.consumer {
+            ConsumerMode::Polling { .. } => None,
+            ConsumerMode::EnhancedFanOut { consumer_name } => {
+                let consumer_name = consumer_name.clone();
+                let registration = self.register_consumer(&consumer_name);
+                pin!(registration);
+                select! {
+                    _ = shutdown.clone() => return Ok(()),
+                    consumer_arn = &mut registration => Some(consumer_arn),
+                }
+            }
+        };
+
+        let source = Arc::new(self);
+        let events_received = register!(EventsReceived);
+        let mut readers: HashMap<String, JoinHandle<()>> = HashMap::new();
+        let mut initial_discovery = true;
+        let mut interval = IntervalStream::new(time::interval(source.shard_discovery_interval))
+            .take_until(shutdown.clone());
+
+        while interval.next().await.is_some() {
+            let shards = match source.list_shards().await {
+                Ok(shards) => shards,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsRequestError {
+                        operation: "ListShards",
+                        shard_id: None,
+                        error: &error,
+                    });
+                    continue;
+                }
+            };
+
+            let shard_ids = shards
+                .iter()
+                .filter_map(|shard| shard.shard_id.clone())
+                .collect::<HashSet<_>>();
+            source.checkpoints.retain(&shard_ids).await;
+            readers.retain(|_, reader| !reader.is_finished());
+
+            for shard in shards {
+                let shard_id = match shard.shard_id.clone() {
+                    Some(shard_id) => shard_id,
+                    None => continue,
+                };
+                if readers.contains_key(&shard_id)
+                    || source.checkpoints.is_finished(&shard_id).await
+                    || !source.parents_finished(&shard, &shard_ids).await
+                {
+                    continue;
+                }
+
+                debug!(message = "Reading shard.", %shard_id);
+                let reader = ShardReader {
+                    source: Arc::clone(&source),
+                    shard_id: shard_id.clone(),
+                    // Shards appearing after startup were created by resharding, so none of their
+                    // records may be skipped.
+                    initial: initial_discovery,
+                    out: out.clone(),
+                    events_received: events_received.clone(),
+                };
+                let consumer_arn = consumer_arn.clone();
+                let shutdown = shutdown.clone();
+                let handle = tokio::spawn(
+                    async move {
+                        select! {
+                            _ = shutdown => {},
+                            _ = reader.run(consumer_arn) => {},
+                        }
+                    }
+                    .in_current_span(),
+                );
+                readers.insert(shard_id, handle);
+            }
+            initial_discovery = false;
+        }
+
+        // Wait for all of the readers to finish.  If any one of them panics, we resume
+        // that panic here to properly shutdown Vector.
+        for (_, reader) in readers.drain() {
+            if let Err(e) = reader.await {
+                if e.is_panic() {
+                    panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the parents of the shard, if any, were read completely.
+    ///
+    /// Parents that no longer exist in the stream have expired, so there is nothing left to read
+    /// from them.
+    async fn parents_finished(&self, shard: &Shard, shard_ids: &HashSet<String>) -> bool {
+        for parent in [&shard.parent_shard_id, &shard.adjacent_parent_shard_id]
+            .into_iter()
+            .flatten()
+        {
+            if shard_ids.contains(parent) && !self.checkpoints.is_finished(parent).await {
+                return false;
+            }
+        }
+        true
+    }
+
+    async fn list_shards(&self) -> Result<Vec<Shard>, crate::Error> {
+        let mut shards = Vec::new();
+        let mut next_token = None;
+        loop {
+            let request = self.client.list_shards();
+            // The stream name can't be set along with a pagination token.
+            let request = match next_token {
+                Some(token) => request.next_token(token),
+                None => request.stream_name(&self.stream_name),
+            };
+            let output = request.send().await?;
+
+            shards.extend(output.shards.unwrap_or_default());
+            match output.next_token {
+                Some(token) => next_token = Some(token),
+                None => return Ok(shards),
+            }
+        }
+    }
+
+    /// Registers the enhanced fan-out consumer, retrying until it is active.
+    async fn register_consumer(&self, consumer_name: &str) -> String {
+        loop {
+            match self.try_register_consumer(consumer_name).await {
+                Ok(Some(consumer_arn)) => return consumer_arn,
+                Ok(None) => time::sleep(CONSUMER_ACTIVATION_DELAY).await,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsRequestError {
+                        operation: "RegisterStreamConsumer",
+                        shard_id: None,
+                        error: &error,
+                    });
+                    time::sleep(RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// Returns the ARN of the consumer once it is active.
+    async fn try_register_consumer(
+        &self,
+        consumer_name: &str,
+    ) -> Result<Option<String>, crate::Error> {
+        let stream_arn = self
+            .client
+            .describe_stream_summary()
+            .stream_name(&self.stream_name)
+            .send()
+            .await?
+            .stream_description_summary
+            .and_then(|summary| summary.stream_arn)
+            .ok_or("stream summary is missing the stream ARN")?;
+
+        let consumer = match self
+            .client
+            .describe_stream_consumer()
+            .stream_arn(&stream_arn)
+            .consumer_name(consumer_name)
+            .send()
+            .await
+        {
+            Ok(output) => output
+                .consumer_description
+                .map(|consumer| (consumer.consumer_arn, consumer.consumer_status)),
+            Err(SdkError::ServiceError(error)) if error.err().is_resource_not_found_exception() => {
+                info!(message = "Registering stream consumer.", %consumer_name);
+                self.client
+                    .register_stream_consumer()
+                    .stream_arn(&stream_arn)
+                    .consumer_name(consumer_name)
+                    .send()
+                    .await?
+                    .consumer
+                    .map(|consumer| (consumer.consumer_arn, consumer.consumer_status))
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(match consumer {
+            Some((Some(consumer_arn), Some(ConsumerStatus::Active))) => Some(consumer_arn),
+            _ => None,
+        })
+    }
+
+    /// Returns where to resume reading the shard from.
+    async fn starting_position(&self, shard_id: &str, initial: bool) -> StartingPosition {
+        let builder = StartingPosition::builder();
+        match self.checkpoints.get(shard_id).await {
+            Some(ShardCheckpoint::Sequence(sequence_number)) => builder
+                .r#type(ShardIteratorType::AfterSequenceNumber)
+                .sequence_number(sequence_number),
+            _ if !initial => builder.r#type(ShardIteratorType::TrimHorizon),
+            _ => match &self.start_position {
+                StartPosition::TrimHorizon => builder.r#type(ShardIteratorType::TrimHorizon),
+                StartPosition::Latest => builder.r#type(ShardIteratorType::Latest),
+                StartPosition::AtTimestamp { timestamp } => builder
+                    .r#type(ShardIteratorType::AtTimestamp)
+                    .timestamp(AwsDateTime::from_millis(timestamp.timestamp_millis())),
+            },
+        }
+        .build()
+    }
+}
+
+struct ShardReader {
+    source: Arc<KinesisStreamsSource>,
+    shard_id: String,
+    initial: bool,
+    out: SourceSender,
+    events_received: Registered<EventsReceived>,
+}
+
+impl ShardReader {
+    async fn run(mut self, consumer_arn: Option<String>) {
+        let finished = match (&self.source.consumer, consumer_arn) {
+            (
+                ConsumerMode::Polling {
+                    poll_interval_ms,
+                    max_records,
+                },
+                _,
+            ) => self.poll(*poll_interval_ms, *max_records).await,
+            (ConsumerMode::EnhancedFanOut { .. }, Some(consumer_arn)) => {
+                self.subscribe(&consumer_arn).await
+            }
+            (ConsumerMode::EnhancedFanOut { .. }, None) => unreachable!("consumer is registered"),
+        };
+
+        if finished {
+            debug!(message = "Finished reading closed shard.", shard_id = %self.shard_id);
+            self.source
+                .checkpoints
+                .set(&self.shard_id, ShardCheckpoint::Finished)
+                .await;
+        }
+    }
+
+    /// Reads the shard with `GetRecords` requests, returning once the shard is closed and read
+    /// completely.
+    async fn poll(&mut self, poll_interval: Duration, max_records: u16) -> bool {
+        let mut shard_iterator = None;
+        loop {
+            let iterator = match shard_iterator.take() {
+                Some(iterator) => iterator,
+                None => match self.shard_iterator().await {
+                    Ok(Some(iterator)) => iterator,
+                    Ok(None) => return true,
+                    Err(error) => {
+                        self.emit_error("GetShardIterator", &error);
+                        time::sleep(RETRY_DELAY).await;
+                        continue;
+                    }
+                },
+            };
+
+            let output = match self
+                .source
+                .client
+                .get_records()
+                .shard_iterator(iterator)
+                .limit(i32::from(max_records))
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(error) => {
+                    // The iterator may have expired, so resume from the checkpoint.
+                    self.emit_error("GetRecords", &error);
+                    time::sleep(RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            let records = output.records.unwrap_or_default();
+            let caught_up = records.is_empty() || output.millis_behind_latest == Some(0);
+            if !records.is_empty() && !self.process_records(records).await {
+                // Resume from the checkpoint, so the records are read again.
+                time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+
+            match output.next_shard_iterator {
+                Some(next) => shard_iterator = Some(next),
+                None => return true,
+            }
+            if caught_up {
+                time::sleep(poll_interval).await;
+            }
+        }
+    }
+
+    async fn shard_iterator(&self) -> Result<Option<String>, crate::Error> {
+        let position = self
+            .source
+            .starting_position(&self.shard_id, self.initial)
+            .await;
+        let output = self
+            .source
+            .client
+            .get_shard_iterator()
+            .stream_name(&self.source.stream_name)
+            .shard_id(&self.shard_id)
+            .set_shard_iterator_type(position.r#type)
+            .set_starting_sequence_number(position.sequence_number)
+            .set_timestamp(position.timestamp)
+            .send()
+            .await?;
+        Ok(output.shard_iterator)
+    }
+
+    /// Reads the shard through enhanced fan-out subscriptions, returning once the shard is closed
+    /// and read completely.
+    async fn subscribe(&mut self, consumer_arn: &str) -> bool {
+        loop {
+            let position = self
+                .source
+                .starting_position(&self.shard_id, self.initial)
+                .await;
+            let mut events = match self
+                .source
+                .client
+                .subscribe_to_shard()
+                .consumer_arn(consumer_arn)
+                .shard_id(&self.shard_id)
+                .starting_position(position)
+                .send()
+                .await
+            {
+                Ok(output) => output.event_stream,
+                Err(error) => {
+                    self.emit_error("SubscribeToShard", &error);
+                    time::sleep(RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            // Subscriptions expire after five minutes, after which a new one is made.
+            loop {
+                match events.recv().await {
+                    Ok(Some(SubscribeToShardEventStream::SubscribeToShardEvent(event))) => {
+                        let records = event.records.unwrap_or_default();
+                        if !records.is_empty() && !self.process_records(records).await {
+                            time::sleep(RETRY_DELAY).await;
+                            break;
+                        }
+                        if event.continuation_sequence_number.is_none() {
+                            return true;
+                        }
+                    }
+                    Ok(Some(_)) => {}
+                    Ok(None) => break,
+                    Err(error) => {
+                        self.emit_error("SubscribeToShard", &error);
+                        time::sleep(RETRY_DELAY).await;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends the records downstream, checkpointing them once they are processed.
+    ///
+    /// Returns `false` if the records should be read again.
+    async fn process_records(&mut self, records: Vec<Record>) -> bool {
+        let last_sequence_number = match records.last().and_then(|r| r.sequence_number.clone()) {
+            Some(sequence_number) => sequence_number,
+            None => return true,
+        };
+        let log_namespace = self.source.log_namespace;
+
+        let byte_size = records
+            .iter()
+            .map(|record| record.data.as_ref().map_or(0, |data| data.as_ref().len()))
+            .sum();
+        emit!(EndpointBytesReceived {
+            byte_size,
+            protocol: "http",
+            endpoint: &self.source.stream_name,
+        });
+
+        let (batch, receiver) =
+            BatchNotifier::maybe_new_with_receiver(self.source.acknowledgements);
+        let mut events = Vec::with_capacity(records.len());
+        for record in records {
+            let data = match record.data {
+                Some(data) => data.into_inner(),
+                None => continue,
+            };
+            let timestamp = record
+                .approximate_arrival_timestamp
+                .and_then(|ts| Utc.timestamp_opt(ts.secs(), ts.subsec_nanos()).single());
+
+            // Error is logged by `crate::codecs::Decoder`, no further handling
+            // is needed here.
+            let decoded = util::decode_message(
+                self.source.decoder.clone(),
+                AwsKinesisStreamsConfig::NAME,
+                &data,
+                timestamp,
+                &batch,
+                log_namespace,
+                &self.events_received,
+            );
+            events.extend(decoded.map(|mut event| {
+                if let Event::Log(ref mut log) = event {
+                    let metadata = [
+                        ("stream_name", Some(&self.source.stream_name)),
+                        ("shard_id", Some(&self.shard_id)),
+                        ("partition_key", record.partition_key.as_ref()),
+                        ("sequence_number", record.sequence_number.as_ref()),
+                    ];
+                    for (key, value) in metadata {
+                        if let Some(value) = value {
+                            log_namespace.insert_source_metadata(
+                                AwsKinesisStreamsConfig::NAME,
+                                log,
+                                Some(LegacyKey::Overwrite(key)),
+                                path!(key),
+                                Bytes::from(value.clone()),
+                            );
+                        }
+                    }
+                }
+                event
+            }));
+        }
+        drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+        let count = events.len();
+        if let Err(error) = self.out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return false;
+        }
+
+        let processed = match receiver {
+            None => true,
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => true,
+                BatchStatus::Errored => false,
+                // Sinks are responsible for emitting ComponentEventsDropped.
+                // Failed events cannot be retried, so continue past them.
+                BatchStatus::Rejected => true,
+            },
+        };
+        if processed {
+            self.source
+                .checkpoints
+                .set(
+                    &self.shard_id,
+                    ShardCheckpoint::Sequence(last_sequence_number),
+                )
+                .await;
+        }
+        processed
+    }
+
+    fn emit_error<E: std::fmt::Display>(&self, operation: &'static str, error: &E) {
+        emit!(AwsKinesisStreamsRequestError {
+            operation,
+            shard_id: Some(&self.shard_id),
+            error,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aws_sdk_kinesis::types::Blob;
+    use futures::Stream;
+
+    use super::*;
+    use crate::{
+        aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+        common::kinesis::KinesisClientBuilder,
+        config::ProxyConfig,
+        event::EventStatus,
+    };
+
+    const SHARD_ID: &str = "shardId-000000000000";
+
+    async fn source(data_dir: &Path, acknowledgements: bool) -> Arc<KinesisStreamsSource> {
+        // The client is never called by these tests.
+        let region = RegionOrEndpoint::with_both("us-east-1", "http://localhost:1");
+        let client = create_client::<KinesisClientBuilder>(
+            &AwsAuthentication::test_auth(),
+            region.region(),
+            region.endpoint().unwrap(),
+            &ProxyConfig::default(),
+            &None,
+            false,
+        )
+        .await
+        .unwrap();
+
+        Arc::new(KinesisStreamsSource {
+            client,
+            stream_name: "my-stream".to_owned(),
+            consumer: ConsumerMode::default(),
+            start_position: StartPosition::default(),
+            shard_discovery_interval: Duration::from_secs(30),
+            decoder: Decoder::default(),
+            checkpoints: Checkpoints::load(data_dir.to_owned()).await.unwrap(),
+            acknowledgements,
+            log_namespace: LogNamespace::Legacy,
+        })
+    }
+
+    fn reader(source: &Arc<KinesisStreamsSource>, out: SourceSender) -> ShardReader {
+        ShardReader {
+            source: Arc::clone(source),
+            shard_id: SHARD_ID.to_owned(),
+            initial: true,
+            out,
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    fn record(data: &str, sequence_number: &str) -> Record {
+        Record::builder()
+            .data(Blob::new(data))
+            .partition_key("key")
+            .sequence_number(sequence_number)
+            .build()
+    }
+
+    /// Processes the records, finalizing the events they are decoded into as they are received.
+    async fn process(
+        reader: &mut ShardReader,
+        rx: impl Stream<Item = Event> + Unpin,
+        records: Vec<Record>,
+    ) -> (bool, Vec<Event>) {
+        let count = records.len();
+        tokio::join!(
+            reader.process_records(records),
+            rx.take(count).collect::<Vec<_>>()
+        )
+    }
+
+    #[tokio::test]
+    async fn reader_checkpoints_delivered_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = source(dir.path(), true).await;
+        let (out, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let mut reader = reader(&source, out);
+
+        let (processed, events) =
+            process(&mut reader, rx, vec![record("a", "1"), record("b", "2")]).await;
+
+        assert!(processed);
+        let log = events[1].as_log();
+        assert_eq!(log["message"], "b".into());
+        assert_eq!(log["stream_name"], "my-stream".into());
+        assert_eq!(log["shard_id"], SHARD_ID.into());
+        assert_eq!(log["partition_key"], "key".into());
+        assert_eq!(log["sequence_number"], "2".into());
+        assert_eq!(
+            source.checkpoints.get(SHARD_ID).await,
+            Some(ShardCheckpoint::Sequence("2".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn reader_rereads_errored_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = source(dir.path(), true).await;
+        let (out, rx) = SourceSender::new_test_finalize(EventStatus::Errored);
+        let mut reader = reader(&source, out);
+
+        let (processed, events) = process(&mut reader, rx, vec![record("a", "1")]).await;
+
+        assert!(!processed);
+        assert_eq!(events.len(), 1);
+        assert_eq!(source.checkpoints.get(SHARD_ID).await, None);
+    }
+
+    #[tokio::test]
+    async fn children_are_read_once_parents_are_finished() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = source(dir.path(), false).await;
+        let shard = |shard_id: &str, parent: Option<&str>, adjacent_parent: Option<&str>| {
+            Shard::builder()
+                .shard_id(shard_id)
+                .set_parent_shard_id(parent.map(Into::into))
+                .set_adjacent_parent_shard_id(adjacent_parent.map(Into::into))
+                .build()
+        };
+        let shard_ids = ["shardId-0", "shardId-1", "shardId-2"]
+            .into_iter()
+            .map(Into::into)
+            .collect::<HashSet<String>>();
+
+        // `shardId-2` is the result of merging `shardId-0` and `shardId-1`.
+        let merged = shard("shardId-2", Some("shardId-0"), Some("shardId-1"));
+        assert!(!source.parents_finished(&merged, &shard_ids).await);
+
+        source
+            .checkpoints
+            .set("shardId-0", ShardCheckpoint::Sequence("1".to_owned()))
+            .await;
+        assert!(!source.parents_finished(&merged, &shard_ids).await);
+
+        source
+            .checkpoints
+            .set("shardId-0", ShardCheckpoint::Finished)
+            .await;
+        assert!(!source.parents_finished(&merged, &shard_ids).await);
+
+        source
+            .checkpoints
+            .set("shardId-1", ShardCheckpoint::Finished)
+            .await;
+        assert!(source.parents_finished(&merged, &shard_ids).await);
+
+        // Parents which expired from the stream have nothing left to read.
+        let split = shard("shardId-3", Some("shardId-4"), None);
+        assert!(source.parents_finished(&split, &shard_ids).await);
+    }
+}
@@ -1,15 +1,20 @@
-use std::{convert::TryInto, io::ErrorKind};
+use std::{convert::TryInto, future::ready, io::ErrorKind, path::PathBuf};
 
 use async_compression::tokio::bufread;
-use aws_sdk_s3::types::ByteStream;
-use codecs::BytesDeserializerConfig;
-use futures::{stream, stream::StreamExt, TryStreamExt};
-use lookup::owned_value_path;
-use snafu::Snafu;
-use tokio_util::io::StreamReader;
+use aws_sdk_s3::{types::ByteStream, Client as S3Client};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use codecs::{BytesDeserializer, BytesDeserializerConfig, CharacterDelimitedDecoder};
+use futures::{stream, stream::StreamExt, Stream, TryStreamExt};
+use lookup::{metadata_path, owned_value_path, path, PathPrefix};
+use snafu::{ResultExt, Snafu};
+use tokio_util::{codec::FramedRead, io::StreamReader};
 use value::{kind::Collection, Kind};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+};
 use vector_config::configurable_component;
-use vector_core::config::{DataType, LegacyKey, LogNamespace};
+use vector_core::config::{log_schema, DataType, LegacyKey, LogNamespace};
 
 use super::util::MultilineConfig;
 use crate::{
@@ -18,11 +23,16 @@ use crate::{
     config::{
         ProxyConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput,
     },
-    line_agg,
+    event::{BatchNotifier, BatchStatus, EstimatedJsonEncodedSizeOf},
+    internal_events::{EventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
     serde::bool_or_struct,
     tls::TlsConfig,
+    SourceSender,
 };
+use sqs::{GetObjectSnafu, ProcessingError};
 
+pub mod poll;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -62,6 +72,12 @@ enum Strategy {
     /// [aws_sqs]: https://aws.amazon.com/sqs/
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes objects by periodically looking for new objects in a bucket.
+    ///
+    /// This is useful when bucket notifications cannot be set up. The keys of the processed
+    /// objects are persisted so they are not consumed again, including across restarts.
+    Poll,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -81,12 +97,14 @@ pub struct AwsS3Config {
     compression: Compression,
 
     /// The strategy to use to consume objects from S3.
-    #[configurable(metadata(docs::hidden))]
     strategy: Strategy,
 
     /// Configuration options for SQS.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for polling.
+    poll: Option<poll::Config>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -137,6 +155,19 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements, log_namespace),
             )),
+            Strategy::Poll => {
+                let data_dir = match self.poll {
+                    Some(ref poll) => cx
+                        .globals
+                        .resolve_and_make_data_subdir(poll.data_dir.as_ref(), cx.key.id())?,
+                    None => return Err(CreatePollIngestorError::ConfigMissing.into()),
+                };
+                Ok(Box::pin(
+                    self.create_poll_ingestor(multiline_config, &cx.proxy, data_dir)
+                        .await?
+                        .run(cx, self.acknowledgements, log_namespace),
+                ))
+            }
         }
     }
 
@@ -248,6 +279,67 @@ impl AwsS3Config {
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_poll_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+        proxy: &ProxyConfig,
+        data_dir: PathBuf,
+    ) -> crate::Result<poll::Ingestor> {
+        let region = self
+            .region
+            .region()
+            .ok_or(CreateSqsIngestorError::RegionMissing)?;
+
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|_| CreateSqsIngestorError::InvalidEndpoint)?;
+
+        let s3_client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            Some(region.clone()),
+            endpoint,
+            proxy,
+            &self.tls_options,
+            false,
+        )
+        .await?;
+
+        let poll = self
+            .poll
+            .as_ref()
+            .ok_or(CreatePollIngestorError::ConfigMissing)?;
+        let ingestor = poll::Ingestor::new(
+            region.as_ref().to_owned(),
+            s3_client,
+            poll.clone(),
+            self.compression,
+            multiline,
+            data_dir,
+        )
+        .await
+        .context(InitializePollSnafu)?;
+
+        #[cfg(feature = "sources-utils-checkpoint-stores")]
+        let ingestor = match &poll.checkpoint_store {
+            Some(checkpoint_store) => ingestor
+                .with_checkpoint_store(checkpoint_store.build(proxy).await?)
+                .await
+                .context(InitializePollSnafu)?,
+            None => ingestor,
+        };
+
+        Ok(ingestor)
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum CreatePollIngestorError {
+    #[snafu(display("Unable to initialize: {}", source))]
+    InitializePoll { source: poll::IngestorNewError },
+    #[snafu(display("Configuration for `poll` required when strategy=poll"))]
+    ConfigMissing,
 }
 
 #[derive(Debug, Snafu)]
@@ -266,6 +358,239 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+/// Reads objects from S3, sending their lines downstream as events.
+struct ObjectProcessor {
+    s3_client: S3Client,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+    out: SourceSender,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl ObjectProcessor {
+    fn new(
+        s3_client: S3Client,
+        compression: Compression,
+        multiline: Option<line_agg::Config>,
+        out: SourceSender,
+        acknowledgements: bool,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            s3_client,
+            compression,
+            multiline,
+            out,
+            acknowledgements,
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    /// Reads the object, resolving once its events are acknowledged if acknowledgements are
+    /// enabled.
+    async fn process(
+        &mut self,
+        bucket: &str,
+        key: &str,
+        region: &str,
+    ) -> Result<(), ProcessingError> {
+        let object_result = self
+            .s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .context(GetObjectSnafu { bucket, key });
+
+        let object = object_result?;
+
+        let metadata = object.metadata;
+
+        let timestamp = object.last_modified.map(|ts| {
+            Utc.timestamp_opt(ts.secs(), ts.subsec_nanos())
+                .single()
+                .expect("invalid timestamp")
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let object_reader = s3_object_decoder(
+            self.compression,
+            key,
+            object.content_encoding.as_deref(),
+            object.content_type.as_deref(),
+            object.body,
+        )
+        .await;
+
+        // Record the read error seen to propagate up later so we avoid ack'ing the SQS
+        // message or checkpointing the object
+        //
+        // String is used as we cannot clone std::io::Error to take ownership in closure
+        //
+        // FramedRead likely stops when it gets an i/o error but I found it more clear to
+        // show that we `take_while` there hasn't been an error
+        //
+        // This can result in objects being partially processed before an error, but we
+        // prefer duplicate lines over message loss. Future work could include recording
+        // the offset of the object that has been read, but this would only be relevant in
+        // the case that the same vector instance processes the same message.
+        let mut read_error = None;
+        let bytes_received = self.bytes_received.clone();
+        let events_received = self.events_received.clone();
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
+                .map(|res| {
+                    res.map(|bytes| {
+                        bytes_received.emit(ByteSize(bytes.len()));
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines = match &self.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context)| line),
+            ),
+            None => lines,
+        };
+
+        let log_namespace = self.log_namespace;
+        let mut stream = lines.map(|line| {
+            let deserializer = BytesDeserializer::new();
+            let mut log = deserializer
+                .parse_single(line, log_namespace)
+                .with_batch_notifier_option(&batch);
+
+            log_namespace.insert_source_metadata(
+                AwsS3Config::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("bucket"))),
+                path!("bucket"),
+                Bytes::from(bucket.as_bytes().to_vec()),
+            );
+            log_namespace.insert_source_metadata(
+                AwsS3Config::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("object"))),
+                path!("object"),
+                Bytes::from(key.as_bytes().to_vec()),
+            );
+            log_namespace.insert_source_metadata(
+                AwsS3Config::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("region"))),
+                path!("region"),
+                Bytes::from(region.as_bytes().to_vec()),
+            );
+
+            if let Some(metadata) = &metadata {
+                for (key, value) in metadata {
+                    log_namespace.insert_source_metadata(
+                        AwsS3Config::NAME,
+                        &mut log,
+                        Some(LegacyKey::Overwrite(key.as_str())),
+                        path!("metadata", key.as_str()),
+                        value.clone(),
+                    );
+                }
+            }
+
+            log_namespace.insert_vector_metadata(
+                &mut log,
+                Some(log_schema().source_type_key()),
+                path!("source_type"),
+                Bytes::from_static(AwsS3Config::NAME.as_bytes()),
+            );
+
+            // This handles the transition from the original timestamp logic. Originally the
+            // `timestamp_key` was populated by the `last_modified` time on the object, falling
+            // back to calling `now()`.
+            match log_namespace {
+                LogNamespace::Vector => {
+                    if let Some(timestamp) = timestamp {
+                        log.insert(metadata_path!(AwsS3Config::NAME, "timestamp"), timestamp);
+                    }
+
+                    log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+                }
+                LogNamespace::Legacy => {
+                    if let Some(timestamp_key) = log_schema().timestamp_key() {
+                        log.try_insert(
+                            (PathPrefix::Event, timestamp_key),
+                            timestamp.unwrap_or_else(Utc::now),
+                        );
+                    }
+                }
+            };
+
+            events_received.emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+
+            log
+        });
+
+        let send_error = match self.out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(error) => {
+                let (count, _) = stream.size_hint();
+                emit!(StreamClosedError { error, count });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        // The BatchNotifier is cloned for each LogEvent in the batch stream, but the last
+        // reference must be dropped before the status of the batch is sent to the channel.
+        drop(batch);
+
+        if let Some(error) = read_error {
+            Err(ProcessingError::ReadObject {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else if let Some(error) = send_error {
+            Err(ProcessingError::PipelineSend {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else {
+            match receiver {
+                None => Ok(()),
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => Ok(()),
+                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                    BatchStatus::Rejected => {
+                        // Sinks are responsible for emitting ComponentEventsDropped.
+                        // Failed events cannot be retried, so continue to delete the SQS source message
+                        // or checkpoint the object.
+                        Ok(())
+                    }
+                },
+            }
+        }
+    }
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
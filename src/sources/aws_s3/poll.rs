@@ -0,0 +1,738 @@
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+use std::sync::Arc;
+use std::{
+    collections::{BTreeSet, HashSet},
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aws_sdk_s3::{
+    error::{GetObjectError, ListObjectsV2Error},
+    Client as S3Client,
+};
+use aws_smithy_client::SdkError;
+use chrono::{DateTime, TimeZone, Utc};
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+use file_source::CheckpointStore;
+use futures::{FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use tokio::{io::AsyncReadExt, pin, select, time, time::Instant};
+use tokio_stream::wrappers::IntervalStream;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+use crate::sources::util::checkpoint_store::CheckpointStoreConfig;
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    internal_events::{
+        AwsS3CheckpointWriteError, AwsS3ObjectDiscoveryError, AwsS3ObjectProcessingError,
+    },
+    line_agg,
+    sources::aws_s3::{Compression, ObjectProcessor},
+};
+
+const CHECKPOINT_FILE_NAME: &str = "checkpoints.json";
+const TMP_CHECKPOINT_FILE_NAME: &str = "checkpoints.new.json";
+
+/// The checkpoints are written once this many objects were processed since they were last
+/// written, or once `CHECKPOINT_INTERVAL` elapsed, as well as at the end of each poll.
+const CHECKPOINT_BATCH_SIZE: usize = 100;
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Polling configuration options.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to consume objects from.
+    #[configurable(metadata(docs::examples = "my-bucket"))]
+    pub(super) bucket: String,
+
+    /// The prefix of the keys of the objects to consume.
+    ///
+    /// If not specified, all objects in the bucket are consumed.
+    #[configurable(metadata(docs::examples = "logs/"))]
+    pub(super) prefix: Option<String>,
+
+    /// How often to look for new objects, in seconds.
+    #[serde(default = "default_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_interval_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) interval_secs: Duration,
+
+    /// How long objects can take to appear in the bucket after their last modification time, in
+    /// seconds.
+    ///
+    /// Objects last modified before this delay, and before any object that couldn't be processed,
+    /// are skipped based on their last modification time, so that only the keys of the objects
+    /// modified since are persisted. Objects appearing later than this are not consumed, such as
+    /// multipart uploads taking longer to complete, whose last modification time is the time the
+    /// upload started.
+    #[serde(default = "default_watermark_delay_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_watermark_delay_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) watermark_delay_secs: Duration,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) discovery: Discovery,
+
+    /// The directory used to persist the keys of the objects that were processed.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    pub(super) data_dir: Option<PathBuf>,
+
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[configurable(derived)]
+    pub(super) checkpoint_store: Option<CheckpointStoreConfig>,
+}
+
+const fn default_interval_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+const fn default_watermark_delay_secs() -> Duration {
+    Duration::from_secs(3600)
+}
+
+/// The method used to discover new objects.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The kind of object discovery."))]
+pub(super) enum Discovery {
+    /// Objects are discovered by listing the keys under `prefix`.
+    #[default]
+    List,
+
+    /// Objects are discovered by reading the latest [S3 Inventory][inventory] report of the bucket.
+    ///
+    /// This avoids listing buckets containing many objects. Only reports in the CSV format are
+    /// supported.
+    ///
+    /// [inventory]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/storage-inventory.html
+    Inventory {
+        /// The name of the bucket the inventory reports are delivered to.
+        #[configurable(metadata(docs::examples = "my-inventory-bucket"))]
+        bucket: String,
+
+        /// The prefix under which the manifests of the inventory reports are delivered.
+        ///
+        /// This is usually `<destination prefix>/<source bucket>/<configuration ID>/`.
+        #[configurable(metadata(docs::examples = "inventory/my-bucket/daily/"))]
+        manifest_prefix: String,
+    },
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("Unable to read checkpoints from {:?}: {}", path, source))]
+    ReadCheckpoints { source: io::Error, path: PathBuf },
+    #[snafu(display("Unable to parse checkpoints from {:?}: {}", path, source))]
+    ParseCheckpoints {
+        source: serde_json::Error,
+        path: PathBuf,
+    },
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[snafu(display("Unable to load checkpoints from the checkpoint store: {}", source))]
+    LoadStoredCheckpoints { source: io::Error },
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[snafu(display("Unable to parse checkpoints from the checkpoint store: {}", source))]
+    ParseStoredCheckpoints { source: serde_json::Error },
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Snafu)]
+pub enum DiscoveryError {
+    #[snafu(display("Failed to list s3://{}/{}: {}", bucket, prefix, source))]
+    ListObjects {
+        source: SdkError<ListObjectsV2Error>,
+        bucket: String,
+        prefix: String,
+    },
+    #[snafu(display("Failed to fetch s3://{}/{}: {}", bucket, key, source))]
+    GetInventoryObject {
+        source: SdkError<GetObjectError>,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Failed to read s3://{}/{}: {}", bucket, key, source))]
+    ReadInventoryObject {
+        source: io::Error,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Invalid inventory manifest s3://{}/{}: {}", bucket, key, source))]
+    InvalidManifest {
+        source: serde_json::Error,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Unsupported inventory format: {}", format))]
+    UnsupportedInventoryFormat { format: String },
+    #[snafu(display("Inventory schema is missing the `Key` field"))]
+    MissingInventoryKey,
+    #[snafu(display("Invalid inventory file s3://{}/{}: {}", bucket, key, source))]
+    InvalidInventoryFile {
+        source: csv::Error,
+        bucket: String,
+        key: String,
+    },
+}
+
+/// The objects that were processed, persisted across restarts.
+///
+/// All the objects last modified before the watermark were processed, so only the keys of the
+/// objects modified since, or whose modification time is unknown, are kept.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Checkpoints {
+    #[serde(default)]
+    watermark: Option<DateTime<Utc>>,
+    objects: BTreeSet<String>,
+}
+
+impl Checkpoints {
+    fn is_before_watermark(&self, object: &Object) -> bool {
+        matches!(
+            (object.last_modified, self.watermark),
+            (Some(last_modified), Some(watermark)) if last_modified < watermark
+        )
+    }
+
+    fn is_processed(&self, object: &Object) -> bool {
+        self.is_before_watermark(object) || self.objects.contains(&object.key)
+    }
+
+    /// Advances the watermark up to `horizon`, but not past the objects still to be processed, and
+    /// forgets the keys of the objects before it, or which no longer exist.
+    fn advance(&mut self, objects: &[Object], horizon: DateTime<Utc>) {
+        let pending = objects
+            .iter()
+            .filter(|object| !self.is_processed(object))
+            .filter_map(|object| object.last_modified)
+            .min();
+        let watermark = pending.map_or(horizon, |pending| pending.min(horizon));
+        self.watermark = self.watermark.max(Some(watermark));
+
+        let retained = objects
+            .iter()
+            .filter(|object| !self.is_before_watermark(object))
+            .map(|object| object.key.as_str())
+            .collect::<HashSet<_>>();
+        self.objects.retain(|key| retained.contains(key.as_str()));
+    }
+}
+
+/// An object discovered in the bucket.
+#[derive(Clone, Debug)]
+struct Object {
+    key: String,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+/// The manifest of an S3 Inventory report.
+///
+/// https://docs.aws.amazon.com/AmazonS3/latest/userguide/storage-inventory-location.html
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InventoryManifest {
+    file_format: String,
+    file_schema: String,
+    files: Vec<InventoryFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InventoryFile {
+    key: String,
+}
+
+pub(super) struct Ingestor {
+    s3_client: S3Client,
+    region: String,
+    config: Config,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+    checkpoints_dir: PathBuf,
+    checkpoints: Checkpoints,
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+    /// The latest inventory manifest that was read, along with the objects it lists.
+    inventory: Option<(String, Vec<Object>)>,
+}
+
+impl Ingestor {
+    pub(super) async fn new(
+        region: String,
+        s3_client: S3Client,
+        config: Config,
+        compression: Compression,
+        multiline: Option<line_agg::Config>,
+        checkpoints_dir: PathBuf,
+    ) -> Result<Ingestor, IngestorNewError> {
+        let path = checkpoints_dir.join(CHECKPOINT_FILE_NAME);
+        let checkpoints = match tokio::fs::read(&path).await {
+            Ok(data) => serde_json::from_slice(&data).context(ParseCheckpointsSnafu { path })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Checkpoints::default(),
+            Err(error) => return Err(error).context(ReadCheckpointsSnafu { path }),
+        };
+
+        Ok(Ingestor {
+            s3_client,
+            region,
+            config,
+            compression,
+            multiline,
+            checkpoints_dir,
+            checkpoints,
+            #[cfg(feature = "sources-utils-checkpoint-stores")]
+            checkpoint_store: None,
+            inventory: None,
+        })
+    }
+
+    /// Persists the checkpoints to the given store as well as to the data directory, and loads
+    /// them from the store when there are none in the data directory.
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    pub(super) async fn with_checkpoint_store(
+        mut self,
+        store: Box<dyn CheckpointStore>,
+    ) -> Result<Ingestor, IngestorNewError> {
+        let store: Arc<dyn CheckpointStore> = Arc::from(store);
+        if self.checkpoints.objects.is_empty() {
+            let data = run_blocking(&store, |store| store.load())
+                .await
+                .context(LoadStoredCheckpointsSnafu)?;
+            if let Some(data) = data {
+                self.checkpoints =
+                    serde_json::from_slice(&data).context(ParseStoredCheckpointsSnafu)?;
+            }
+        }
+        self.checkpoint_store = Some(store);
+        Ok(self)
+    }
+
+    pub(super) async fn run(
+        mut self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        log_namespace: LogNamespace,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let mut objects = ObjectProcessor::new(
+            self.s3_client.clone(),
+            self.compression,
+            self.multiline.clone(),
+            cx.out,
+            acknowledgements,
+            log_namespace,
+        );
+        let shutdown = cx.shutdown;
+        let mut interval = IntervalStream::new(time::interval(self.config.interval_secs))
+            .take_until(shutdown.clone());
+        let shutdown = shutdown.fuse();
+        pin!(shutdown);
+
+        while interval.next().await.is_some() {
+            let discovered = match self.discover().await {
+                Ok(discovered) => discovered,
+                Err(error) => {
+                    emit!(AwsS3ObjectDiscoveryError { error: &error });
+                    continue;
+                }
+            };
+
+            let mut unwritten = 0;
+            let mut last_written = Instant::now();
+            for object in &discovered {
+                if self.checkpoints.is_processed(object) {
+                    continue;
+                }
+
+                select! {
+                    _ = &mut shutdown => {
+                        if unwritten > 0 {
+                            self.write_checkpoints().await;
+                        }
+                        return Ok(());
+                    },
+                    result = objects.process(&self.config.bucket, &object.key, &self.region) => match result {
+                        Ok(()) => {
+                            self.checkpoints.objects.insert(object.key.clone());
+                            unwritten += 1;
+                            if unwritten >= CHECKPOINT_BATCH_SIZE
+                                || last_written.elapsed() >= CHECKPOINT_INTERVAL
+                            {
+                                self.write_checkpoints().await;
+                                unwritten = 0;
+                                last_written = Instant::now();
+                            }
+                        }
+                        Err(error) => emit!(AwsS3ObjectProcessingError {
+                            bucket: &self.config.bucket,
+                            key: &object.key,
+                            error: &error,
+                        }),
+                    },
+                }
+            }
+
+            let horizon = chrono::Duration::from_std(self.config.watermark_delay_secs)
+                .ok()
+                .and_then(|delay| Utc::now().checked_sub_signed(delay))
+                .unwrap_or(DateTime::<Utc>::MIN_UTC);
+            self.checkpoints.advance(&discovered, horizon);
+            self.write_checkpoints().await;
+        }
+
+        Ok(())
+    }
+
+    /// Returns all of the objects currently in the bucket under the prefix.
+    async fn discover(&mut self) -> Result<Vec<Object>, DiscoveryError> {
+        let prefix = self.config.prefix.clone().unwrap_or_default();
+        let mut objects = match &self.config.discovery {
+            Discovery::List => self.list(&self.config.bucket, &prefix).await?,
+            Discovery::Inventory {
+                bucket,
+                manifest_prefix,
+            } => {
+                let bucket = bucket.clone();
+                let manifest = self
+                    .list(&bucket, manifest_prefix)
+                    .await?
+                    .into_iter()
+                    .map(|object| object.key)
+                    .filter(|key| key.ends_with("/manifest.json"))
+                    // Manifests are delivered under a directory named after their creation date.
+                    .max();
+
+                match (manifest, self.inventory.take()) {
+                    (None, _) => Vec::new(),
+                    (Some(manifest), Some((latest, objects))) if manifest == latest => {
+                        self.inventory = Some((latest, objects.clone()));
+                        objects
+                    }
+                    (Some(manifest), _) => {
+                        let objects = self.read_inventory(&bucket, &manifest).await?;
+                        self.inventory = Some((manifest, objects.clone()));
+                        objects
+                    }
+                }
+            }
+        };
+
+        objects.retain(|object| object.key.starts_with(&prefix) && !object.key.ends_with('/'));
+        Ok(objects)
+    }
+
+    async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<Object>, DiscoveryError> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let output = self
+                .s3_client
+                .list_objects_v2()
+                .bucket(bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .context(ListObjectsSnafu { bucket, prefix })?;
+
+            objects.extend(
+                output
+                    .contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|object| {
+                        Some(Object {
+                            key: object.key?,
+                            last_modified: object.last_modified.and_then(|timestamp| {
+                                Utc.timestamp_opt(timestamp.secs(), timestamp.subsec_nanos())
+                                    .single()
+                            }),
+                        })
+                    }),
+            );
+
+            match output.next_continuation_token {
+                Some(token) if output.is_truncated => continuation_token = Some(token),
+                _ => return Ok(objects),
+            }
+        }
+    }
+
+    async fn read_inventory(
+        &self,
+        bucket: &str,
+        manifest_key: &str,
+    ) -> Result<Vec<Object>, DiscoveryError> {
+        let manifest = self.read_object(bucket, manifest_key).await?;
+        let manifest: InventoryManifest =
+            serde_json::from_slice(&manifest).context(InvalidManifestSnafu {
+                bucket,
+                key: manifest_key,
+            })?;
+        if !manifest.file_format.eq_ignore_ascii_case("csv") {
+            return Err(DiscoveryError::UnsupportedInventoryFormat {
+                format: manifest.file_format,
+            });
+        }
+
+        let schema = manifest.file_schema.split(',').map(str::trim);
+        let mut key_index = None;
+        let mut is_latest_index = None;
+        let mut is_delete_marker_index = None;
+        let mut last_modified_index = None;
+        for (index, field) in schema.enumerate() {
+            match field {
+                "Key" => key_index = Some(index),
+                "IsLatest" => is_latest_index = Some(index),
+                "IsDeleteMarker" => is_delete_marker_index = Some(index),
+                "LastModifiedDate" => last_modified_index = Some(index),
+                _ => {}
+            }
+        }
+        let key_index = key_index.ok_or(DiscoveryError::MissingInventoryKey)?;
+
+        let mut objects = Vec::new();
+        for file in manifest.files {
+            let data = self.read_object(bucket, &file.key).await?;
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .from_reader(data.as_slice());
+            for record in reader.records() {
+                let record = record.context(InvalidInventoryFileSnafu {
+                    bucket,
+                    key: &file.key,
+                })?;
+                let is_flag_set = |index: Option<usize>| {
+                    index.and_then(|index| record.get(index)) == Some("true")
+                };
+                let is_outdated = is_latest_index
+                    .and_then(|index| record.get(index))
+                    .map_or(false, |value| value != "true");
+                if is_outdated || is_flag_set(is_delete_marker_index) {
+                    continue;
+                }
+                if let Some(key) = record.get(key_index) {
+                    objects.push(Object {
+                        key: decode_inventory_key(key),
+                        last_modified: last_modified_index
+                            .and_then(|index| record.get(index))
+                            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+                            .map(|date| date.with_timezone(&Utc)),
+                    });
+                }
+            }
+        }
+        Ok(objects)
+    }
+
+    /// Reads a whole object, decompressing it if needed.
+    async fn read_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>, DiscoveryError> {
+        let object = self
+            .s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .context(GetInventoryObjectSnafu { bucket, key })?;
+
+        let mut reader = super::s3_object_decoder(
+            Compression::Auto,
+            key,
+            object.content_encoding.as_deref(),
+            object.content_type.as_deref(),
+            object.body,
+        )
+        .await;
+        let mut data = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .await
+            .context(ReadInventoryObjectSnafu { bucket, key })?;
+        Ok(data)
+    }
+
+    async fn write_checkpoints(&self) {
+        let path = self.checkpoints_dir.join(CHECKPOINT_FILE_NAME);
+        if let Err(error) = write_checkpoints(&self.checkpoints_dir, &self.checkpoints).await {
+            emit!(AwsS3CheckpointWriteError { error, path: &path });
+            return;
+        }
+
+        #[cfg(feature = "sources-utils-checkpoint-stores")]
+        if let Some(store) = &self.checkpoint_store {
+            let result = match serde_json::to_vec(&self.checkpoints) {
+                Ok(data) => run_blocking(store, move |store| store.store(&data)).await,
+                Err(error) => Err(error.into()),
+            };
+            if let Err(error) = result {
+                emit!(AwsS3CheckpointWriteError { error, path: &path });
+            }
+        }
+    }
+}
+
+/// Calls the checkpoint store from a blocking thread.
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+async fn run_blocking<T: Send + 'static>(
+    store: &Arc<dyn CheckpointStore>,
+    f: impl FnOnce(&dyn CheckpointStore) -> io::Result<T> + Send + 'static,
+) -> io::Result<T> {
+    let store = Arc::clone(store);
+    tokio::task::spawn_blocking(move || f(store.as_ref()))
+        .await
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
+}
+
+/// Atomically replaces the checkpoints file.
+async fn write_checkpoints(dir: &Path, checkpoints: &Checkpoints) -> io::Result<()> {
+    let tmp_path = dir.join(TMP_CHECKPOINT_FILE_NAME);
+    tokio::fs::write(&tmp_path, serde_json::to_vec(checkpoints)?).await?;
+    tokio::fs::rename(&tmp_path, dir.join(CHECKPOINT_FILE_NAME)).await
+}
+
+/// Inventory reports URL encode object keys.
+fn decode_inventory_key(key: &str) -> String {
+    let key = key.replace('+', " ");
+    percent_encoding::percent_decode_str(&key)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_inventory_keys() {
+        assert_eq!(decode_inventory_key("logs/a+b%2Bc.log"), "logs/a b+c.log");
+        assert_eq!(decode_inventory_key("plain.log"), "plain.log");
+    }
+
+    #[tokio::test]
+    async fn checkpoints_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut checkpoints = Checkpoints::default();
+        checkpoints.objects.insert("logs/1.log".into());
+        checkpoints.objects.insert("logs/2.log".into());
+        checkpoints.watermark = Some(minute(1));
+        write_checkpoints(dir.path(), &checkpoints).await.unwrap();
+
+        let data = tokio::fs::read(dir.path().join(CHECKPOINT_FILE_NAME))
+            .await
+            .unwrap();
+        let read: Checkpoints = serde_json::from_slice(&data).unwrap();
+        assert_eq!(read.objects, checkpoints.objects);
+        assert_eq!(read.watermark, checkpoints.watermark);
+        assert!(!dir.path().join(TMP_CHECKPOINT_FILE_NAME).exists());
+    }
+
+    fn minute(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2023, 1, 1, 0, minute, 0).unwrap()
+    }
+
+    fn object(key: &str, minute_modified: Option<u32>) -> Object {
+        Object {
+            key: key.to_owned(),
+            last_modified: minute_modified.map(minute),
+        }
+    }
+
+    fn processed(checkpoints: &mut Checkpoints, objects: &[Object]) {
+        for object in objects {
+            checkpoints.objects.insert(object.key.clone());
+        }
+    }
+
+    #[test]
+    fn advances_watermark_to_pending_objects() {
+        let objects = [
+            object("a", Some(1)),
+            object("b", Some(2)),
+            object("c", Some(3)),
+            object("d", Some(5)),
+            object("e", None),
+        ];
+        let mut checkpoints = Checkpoints::default();
+        // `c` failed to be processed.
+        processed(&mut checkpoints, &objects[..2]);
+        processed(&mut checkpoints, &objects[3..]);
+
+        checkpoints.advance(&objects, minute(4));
+
+        assert_eq!(checkpoints.watermark, objects[2].last_modified);
+        assert_eq!(
+            checkpoints.objects.iter().collect::<Vec<_>>(),
+            vec!["d", "e"]
+        );
+        assert!(checkpoints.is_processed(&objects[0]));
+        assert!(!checkpoints.is_processed(&objects[2]));
+        assert!(checkpoints.is_processed(&objects[3]));
+    }
+
+    #[test]
+    fn advances_watermark_up_to_horizon() {
+        let objects = [object("a", Some(1)), object("b", Some(3))];
+        let mut checkpoints = Checkpoints::default();
+        processed(&mut checkpoints, &objects);
+
+        checkpoints.advance(&objects, minute(2));
+        assert_eq!(checkpoints.objects.iter().collect::<Vec<_>>(), vec!["b"]);
+
+        // Objects appearing with older modification times are skipped.
+        assert!(checkpoints.is_processed(&object("late", Some(1))));
+        assert!(!checkpoints.is_processed(&object("new", Some(2))));
+
+        // The watermark never moves back, and objects that no longer exist are forgotten.
+        checkpoints.advance(&[], minute(0));
+        assert_eq!(checkpoints.watermark, Some(minute(2)));
+        assert!(checkpoints.objects.is_empty());
+    }
+
+    #[test]
+    fn reads_checkpoints_without_watermark() {
+        let checkpoints: Checkpoints =
+            serde_json::from_str(r#"{"objects":["logs/1.log"]}"#).unwrap();
+        assert_eq!(checkpoints.watermark, None);
+        assert!(checkpoints.is_processed(&object("logs/1.log", Some(1))));
+    }
+
+    #[test]
+    fn parses_inventory_manifest() {
+        let manifest: InventoryManifest = serde_json::from_str(
+            r#"{
+            "sourceBucket": "example-source-bucket",
+            "destinationBucket": "arn:aws:s3:::example-inventory-destination-bucket",
+            "version": "2016-11-30",
+            "creationTimestamp" : "1514944800000",
+            "fileFormat": "CSV",
+            "fileSchema": "Bucket, Key, VersionId, IsLatest, IsDeleteMarker, Size",
+            "files": [
+                {
+                    "key": "inventory/example-source-bucket/data/939c6d46-85a9-4ba8-87bd-9db705a579ce.csv.gz",
+                    "size": 2147483647,
+                    "MD5checksum": "f11166069f1990abeb9c97ace9cdfabc"
+                }
+            ]
+        }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.file_format, "CSV");
+        assert_eq!(manifest.files.len(), 1);
+    }
+}
@@ -5,6 +5,7 @@ mod tests;
 
 pub mod logs;
 pub mod metrics;
+pub mod processes;
 pub mod traces;
 
 #[allow(warnings, clippy::pedantic, clippy::nursery)]
@@ -54,6 +55,7 @@ use crate::{
 
 pub const LOGS: &str = "logs";
 pub const METRICS: &str = "metrics";
+pub const PROCESSES: &str = "processes";
 pub const TRACES: &str = "traces";
 
 /// Configuration for the `datadog_agent` source.
@@ -91,12 +93,19 @@ pub struct DatadogAgentConfig {
     #[serde(default = "crate::serde::default_false")]
     disable_traces: bool,
 
-    /// If this is set to `true` logs, metrics, and traces are sent to different outputs.
+    /// If this is set to `true`, process and container payloads are not accepted by the component.
+    ///
+    /// These are sent by the process agent and the system probe, and are decoded into log events.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default = "crate::serde::default_false")]
+    disable_processes: bool,
+
+    /// If this is set to `true` logs, metrics, traces, and processes are sent to different outputs.
     ///
     ///
-    /// For a source component named `agent`, the received logs, metrics, and traces can then be
-    /// configured as input to other components by specifying `agent.logs`, `agent.metrics`, and
-    /// `agent.traces`, respectively.
+    /// For a source component named `agent`, the received logs, metrics, traces, and processes can
+    /// then be configured as input to other components by specifying `agent.logs`,
+    /// `agent.metrics`, `agent.traces`, and `agent.processes`, respectively.
     #[configurable(metadata(docs::advanced))]
     #[serde(default = "crate::serde::default_false")]
     multiple_outputs: bool,
@@ -134,6 +143,7 @@ impl GenerateConfig for DatadogAgentConfig {
             disable_logs: false,
             disable_metrics: false,
             disable_traces: false,
+            disable_processes: false,
             multiple_outputs: false,
             log_namespace: Some(false),
         })
@@ -250,6 +260,11 @@ impl SourceConfig for DatadogAgentConfig {
                 SourceOutput::new_logs(DataType::Log, definition).with_port(LOGS),
                 SourceOutput::new_metrics().with_port(METRICS),
                 SourceOutput::new_traces().with_port(TRACES),
+                SourceOutput::new_logs(
+                    DataType::Log,
+                    processes::schema_definition(global_log_namespace.merge(self.log_namespace)),
+                )
+                .with_port(PROCESSES),
             ]
         } else {
             vec![SourceOutput::new_logs(DataType::all(), definition)]
@@ -370,6 +385,18 @@ impl DatadogAgentSource {
                 .or(Some(trace_filter));
         }
 
+        if !config.disable_processes {
+            let processes_filter = processes::build_warp_filter(
+                acknowledgements,
+                config.multiple_outputs,
+                out.clone(),
+                self.clone(),
+            );
+            filters = filters
+                .map(|f| f.or(processes_filter.clone()).unify().boxed())
+                .or(Some(processes_filter));
+        }
+
         if !config.disable_metrics {
             let metrics_filter = metrics::build_warp_filter(
                 acknowledgements,
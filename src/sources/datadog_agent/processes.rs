@@ -0,0 +1,375 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    sync::Arc,
+};
+
+use bytes::{Buf, Bytes};
+use chrono::{TimeZone, Utc};
+use http::StatusCode;
+use ordered_float::NotNan;
+use value::{kind::Collection, Kind};
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+use warp::{filters::BoxedFilter, path, path::FullPath, reply::Response, Filter};
+
+use crate::{
+    event::{Event, LogEvent, Value},
+    schema,
+    sources::{
+        datadog_agent::{
+            handle_request, ApiKeyQueryParams, DatadogAgentConfig, DatadogAgentSource,
+        },
+        util::ErrorMessage,
+    },
+    SourceSender,
+};
+
+/// The intake endpoints the process agent and the system probe post their payloads to.
+const ENDPOINTS: [&str; 4] = ["collector", "container", "discovery", "connections"];
+
+// https://github.com/DataDog/agent-payload/blob/master/process/message.go
+const HEADER_LENGTH: usize = 16;
+const ENCODING_PROTOBUF: u8 = 0;
+const ENCODING_JSON: u8 = 1;
+const ENCODING_ZSTD_PROTOBUF: u8 = 2;
+const ENCODING_ZSTD_1X_PROTOBUF: u8 = 3;
+
+pub(crate) fn build_warp_filter(
+    acknowledgements: bool,
+    multiple_outputs: bool,
+    out: SourceSender,
+    source: DatadogAgentSource,
+) -> BoxedFilter<(Response,)> {
+    warp::post()
+        .and(path!("api" / "v1" / String))
+        .and_then(|endpoint: String| async move {
+            if ENDPOINTS.contains(&endpoint.as_str()) {
+                Ok(endpoint)
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::header::optional::<String>("dd-api-key"))
+        .and(warp::query::<ApiKeyQueryParams>())
+        .and(warp::body::bytes())
+        .and_then(
+            move |endpoint: String,
+                  path: FullPath,
+                  encoding_header: Option<String>,
+                  api_token: Option<String>,
+                  query_params: ApiKeyQueryParams,
+                  body: Bytes| {
+                let events = source
+                    .decode(&encoding_header, body, path.as_str())
+                    .and_then(|body| {
+                        decode_process_body(
+                            body,
+                            endpoint,
+                            source.api_key_extractor.extract(
+                                path.as_str(),
+                                api_token,
+                                query_params.dd_api_key,
+                            ),
+                            &source,
+                        )
+                    });
+
+                let output = multiple_outputs.then_some(super::PROCESSES);
+                handle_request(events, acknowledgements, out.clone(), output)
+            },
+        )
+        .boxed()
+}
+
+/// The definition of the events decoded from process payloads, in either namespace.
+pub(crate) fn schema_definition(log_namespace: LogNamespace) -> schema::Definition {
+    let event_kind = Kind::object(BTreeMap::from([
+        ("endpoint".into(), Kind::bytes()),
+        ("header".into(), Kind::object(Collection::any())),
+        ("payload".into(), Kind::any()),
+    ]));
+    schema::Definition::new_with_default_metadata(event_kind, [log_namespace])
+        .with_standard_vector_source_metadata()
+}
+
+/// The header prefixed to every payload sent by the process agent.
+#[derive(Debug, PartialEq)]
+struct MessageHeader {
+    version: u8,
+    encoding: u8,
+    message_type: u8,
+    subscription_id: u8,
+    org_id: i32,
+    timestamp: i64,
+}
+
+impl MessageHeader {
+    fn parse(body: &mut Bytes) -> Option<Self> {
+        (body.len() >= HEADER_LENGTH).then(|| Self {
+            version: body.get_u8(),
+            encoding: body.get_u8(),
+            message_type: body.get_u8(),
+            subscription_id: body.get_u8(),
+            org_id: body.get_i32(),
+            timestamp: body.get_i64(),
+        })
+    }
+
+    fn into_value(self) -> Value {
+        let mut header = BTreeMap::new();
+        header.insert("version".into(), Value::from(i64::from(self.version)));
+        header.insert("encoding".into(), Value::from(i64::from(self.encoding)));
+        header.insert("type".into(), Value::from(i64::from(self.message_type)));
+        header.insert(
+            "subscription_id".into(),
+            Value::from(i64::from(self.subscription_id)),
+        );
+        header.insert("org_id".into(), Value::from(i64::from(self.org_id)));
+        if let Some(timestamp) = Utc.timestamp_millis_opt(self.timestamp).single() {
+            header.insert("timestamp".into(), Value::from(timestamp));
+        }
+        Value::Object(header)
+    }
+}
+
+pub(crate) fn decode_process_body(
+    mut body: Bytes,
+    endpoint: String,
+    api_key: Option<Arc<str>>,
+    source: &DatadogAgentSource,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let header = MessageHeader::parse(&mut body).ok_or_else(|| {
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            "Process payload is shorter than its header.".to_string(),
+        )
+    })?;
+    let payload = decode_payload(header.encoding, body)?;
+
+    let mut object = BTreeMap::new();
+    object.insert("endpoint".into(), Value::from(endpoint));
+    object.insert("header".into(), header.into_value());
+    object.insert("payload".into(), payload);
+    let mut log = LogEvent::from(object);
+
+    source.log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        DatadogAgentConfig::NAME,
+        Utc::now(),
+    );
+    if let Some(api_key) = api_key {
+        log.metadata_mut().set_datadog_api_key(api_key);
+    }
+
+    source
+        .events_received
+        .emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+
+    Ok(vec![Event::from(log)])
+}
+
+fn decode_payload(encoding: u8, body: Bytes) -> Result<Value, ErrorMessage> {
+    let invalid = |format: &str| {
+        ErrorMessage::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Error decoding Datadog process payload as {}.", format),
+        )
+    };
+
+    match encoding {
+        ENCODING_PROTOBUF => decode_message(&body)
+            .map(Value::Object)
+            .ok_or_else(|| invalid("protobuf")),
+        ENCODING_JSON => serde_json::from_slice::<serde_json::Value>(&body)
+            .map(Value::from)
+            .map_err(|_| invalid("JSON")),
+        ENCODING_ZSTD_PROTOBUF | ENCODING_ZSTD_1X_PROTOBUF => {
+            zstd::stream::decode_all(body.reader())
+                .ok()
+                .and_then(|body| decode_message(&body))
+                .map(Value::Object)
+                .ok_or_else(|| invalid("zstd compressed protobuf"))
+        }
+        encoding => Err(ErrorMessage::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!("Unsupported process payload encoding {}", encoding),
+        )),
+    }
+}
+
+/// Decodes a protobuf message without its schema, keying the fields by their number.
+///
+/// Fields occurring more than once are collected into arrays. Length-delimited fields are decoded
+/// as strings when they are printable, then as nested messages, and are kept as raw bytes otherwise.
+fn decode_message(mut buf: &[u8]) -> Option<BTreeMap<String, Value>> {
+    let mut fields = BTreeMap::new();
+    while buf.has_remaining() {
+        let key = prost::encoding::decode_varint(&mut buf).ok()?;
+        let number = key >> 3;
+        if number == 0 {
+            return None;
+        }
+
+        let value = match key & 0x07 {
+            0 => Value::from(prost::encoding::decode_varint(&mut buf).ok()? as i64),
+            1 => {
+                if buf.remaining() < 8 {
+                    return None;
+                }
+                let bits = buf.get_u64_le();
+                NotNan::new(f64::from_bits(bits))
+                    .map(Value::Float)
+                    .unwrap_or_else(|_| Value::from(bits as i64))
+            }
+            2 => {
+                let length =
+                    usize::try_from(prost::encoding::decode_varint(&mut buf).ok()?).ok()?;
+                if buf.remaining() < length {
+                    return None;
+                }
+                let (bytes, rest) = buf.split_at(length);
+                buf = rest;
+                decode_length_delimited(bytes)
+            }
+            5 => {
+                if buf.remaining() < 4 {
+                    return None;
+                }
+                let bits = buf.get_u32_le();
+                NotNan::new(f64::from(f32::from_bits(bits)))
+                    .map(Value::Float)
+                    .unwrap_or_else(|_| Value::from(i64::from(bits)))
+            }
+            _ => return None,
+        };
+
+        match fields.entry(number.to_string()) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(mut entry) => match entry.get_mut() {
+                Value::Array(values) => values.push(value),
+                existing => {
+                    let first = std::mem::replace(existing, Value::Null);
+                    *existing = Value::Array(vec![first, value]);
+                }
+            },
+        }
+    }
+    Some(fields)
+}
+
+fn decode_length_delimited(bytes: &[u8]) -> Value {
+    match std::str::from_utf8(bytes) {
+        Ok(string)
+            if !string
+                .chars()
+                .any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r')) =>
+        {
+            Value::from(string)
+        }
+        _ => decode_message(bytes)
+            .map(Value::Object)
+            .unwrap_or_else(|| Value::from(Bytes::copy_from_slice(bytes))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn varint(value: u64, buf: &mut Vec<u8>) {
+        prost::encoding::encode_varint(value, buf);
+    }
+
+    fn field(number: u64, wire_type: u64, buf: &mut Vec<u8>) {
+        varint(number << 3 | wire_type, buf);
+    }
+
+    fn string(number: u64, value: &[u8], buf: &mut Vec<u8>) {
+        field(number, 2, buf);
+        varint(value.len() as u64, buf);
+        buf.extend_from_slice(value);
+    }
+
+    #[test]
+    fn parses_header() {
+        let mut body =
+            Bytes::from_static(&[3, 0, 12, 0, 0, 0, 0, 42, 0, 0, 1, 134, 160, 0, 0, 0, 0xff]);
+        let header = MessageHeader::parse(&mut body).unwrap();
+        assert_eq!(
+            header,
+            MessageHeader {
+                version: 3,
+                encoding: ENCODING_PROTOBUF,
+                message_type: 12,
+                subscription_id: 0,
+                org_id: 42,
+                timestamp: 0x0186_a000_0000,
+            }
+        );
+        assert_eq!(body.as_ref(), &[0xff]);
+
+        assert!(MessageHeader::parse(&mut Bytes::from_static(&[3, 0, 12])).is_none());
+    }
+
+    #[test]
+    fn decodes_protobuf_without_schema() {
+        let mut process = Vec::new();
+        field(1, 0, &mut process);
+        varint(1234, &mut process);
+        string(2, b"/usr/bin/agent", &mut process);
+        field(3, 1, &mut process);
+        process.extend_from_slice(&1.5f64.to_le_bytes());
+
+        let mut message = Vec::new();
+        string(1, b"host-1", &mut message);
+        string(2, &process, &mut message);
+        string(2, &process, &mut message);
+        field(4, 5, &mut message);
+        message.extend_from_slice(&0.25f32.to_le_bytes());
+
+        assert_eq!(
+            Value::Object(decode_message(&message).unwrap()),
+            vrl::value!({
+                "1": "host-1",
+                "2": [
+                    {"1": 1234, "2": "/usr/bin/agent", "3": 1.5},
+                    {"1": 1234, "2": "/usr/bin/agent", "3": 1.5}
+                ],
+                "4": 0.25
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_protobuf() {
+        let mut message = Vec::new();
+        string(1, b"host-1", &mut message);
+        message.truncate(message.len() - 1);
+        assert!(decode_message(&message).is_none());
+    }
+
+    #[test]
+    fn decodes_compressed_and_json_payloads() {
+        let mut message = Vec::new();
+        string(1, b"host-1", &mut message);
+        let compressed = zstd::stream::encode_all(message.as_slice(), 0).unwrap();
+
+        assert_eq!(
+            decode_payload(ENCODING_ZSTD_PROTOBUF, compressed.into()).unwrap(),
+            vrl::value!({"1": "host-1"})
+        );
+        assert_eq!(
+            decode_payload(
+                ENCODING_JSON,
+                Bytes::from_static(br#"{"hostName":"host-1"}"#)
+            )
+            .unwrap(),
+            vrl::value!({"hostName": "host-1"})
+        );
+        assert!(decode_payload(9, Bytes::new()).is_err());
+    }
+}
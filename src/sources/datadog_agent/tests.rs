@@ -9,7 +9,7 @@ use bytes::Bytes;
 use chrono::{TimeZone, Utc};
 use codecs::{
     decoding::{Deserializer, DeserializerConfig, Framer},
-    BytesDecoder, BytesDeserializer,
+    BytesDecoder, BytesDeserializer, JsonDeserializerConfig,
 };
 use futures::{Stream, StreamExt};
 use http::HeaderMap;
@@ -1595,7 +1595,7 @@ fn test_config_outputs() {
         (
             "json / single output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: JsonDeserializerConfig::new().into(),
                 multiple_outputs: false,
                 want: HashMap::from([(
                     None,
@@ -1620,7 +1620,7 @@ fn test_config_outputs() {
         (
             "json / multiple output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: JsonDeserializerConfig::new().into(),
                 multiple_outputs: true,
                 want: HashMap::from([
                     (
@@ -1825,6 +1825,7 @@ fn test_config_outputs() {
             disable_logs: false,
             disable_metrics: false,
             disable_traces: false,
+            disable_processes: false,
             log_namespace: Some(false),
         };
 
@@ -2257,3 +2258,68 @@ fn test_output_schema_definition_bytes_legacy_namespace() {
 fn assert_tags(metric: &Metric, tags: MetricTags) {
     assert_eq!(metric.tags().expect("Missing tags"), &tags);
 }
+
+#[tokio::test]
+async fn decode_process_payload() {
+    assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+        let (rx, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "dd-api-key",
+            "12345678abcdefgh12345678abcdefgh".parse().unwrap(),
+        );
+
+        // A V3 header announcing a JSON encoded container payload, sent at 1542182955000 ms.
+        let mut body = vec![3, 1, 39, 0, 0, 0, 0, 7];
+        body.extend_from_slice(&1542182955000_i64.to_be_bytes());
+        body.extend_from_slice(br#"{"hostName":"a_host","containers":[{"id":"abc"}]}"#);
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    200,
+                    send_with_path(
+                        addr,
+                        unsafe { str::from_utf8_unchecked(&body) },
+                        headers,
+                        "/api/v1/container"
+                    )
+                    .await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(log["endpoint"], "container".into());
+        assert_eq!(log["header.version"], 3.into());
+        assert_eq!(log["header.encoding"], 1.into());
+        assert_eq!(log["header.type"], 39.into());
+        assert_eq!(log["header.org_id"], 7.into());
+        assert_eq!(
+            log["header.timestamp"],
+            Utc.timestamp_millis_opt(1542182955000).unwrap().into()
+        );
+        assert_eq!(log["payload.hostName"], "a_host".into());
+        assert_eq!(log["payload.containers[0].id"], "abc".into());
+        assert_eq!(log[log_schema().source_type_key()], "datadog_agent".into());
+        assert_eq!(
+            &events[0].metadata().datadog_api_key().as_ref().unwrap()[..],
+            "12345678abcdefgh12345678abcdefgh"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn rejects_short_process_payload() {
+    let (_, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+    assert_eq!(
+        400,
+        send_with_path(addr, "short", HeaderMap::new(), "/api/v1/collector").await
+    );
+}
@@ -0,0 +1,127 @@
+use codecs::NativeDeserializerConfig;
+use futures::StreamExt;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use vector_common::internal_event::{
+    CountByteSize, EventsReceived, InternalEventHandle as _, Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::LogNamespace,
+    internal_event::{ComponentEventsDropped, UNINTENTIONAL},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    event_size::subscribe_dead_letters,
+    internal_events::StreamClosedError,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `dead_letter` source.
+#[configurable_component(source(
+    "dead_letter",
+    "Receive the events exceeding the maximum event size sent to the dead letter queue."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DeadLetterConfig {}
+
+impl_generate_config_from_default!(DeadLetterConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "dead_letter")]
+impl SourceConfig for DeadLetterConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        // Subscribe right away, to not miss the events sent while the topology starts.
+        let dead_letters = subscribe_dead_letters();
+        Ok(Box::pin(run(dead_letters, cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        // The events are sent as they were when they exceeded the maximum size.
+        let schema_definition = NativeDeserializerConfig.schema_definition(global_log_namespace);
+
+        vec![SourceOutput::new_logs(DataType::all(), schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    dead_letters: broadcast::Receiver<Event>,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let events_received: Registered<EventsReceived> = register!(EventsReceived);
+    let mut dead_letters = BroadcastStream::new(dead_letters).take_until(shutdown);
+
+    while let Some(received) = dead_letters.next().await {
+        let event = match received {
+            Ok(event) => event,
+            Err(BroadcastStreamRecvError::Lagged(count)) => {
+                emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                    count: count as usize,
+                    reason: "Dead letter queue is full.",
+                });
+                continue;
+            }
+        };
+        events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
+
+        if let Err(error) = out.send_event(event).await {
+            emit!(StreamClosedError { error, count: 1 });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{ComponentKey, Config},
+        event::{EventArray, LogEvent},
+        event_size::EventSizeGuard,
+        test_util::collect_ready,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DeadLetterConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_dead_letters() {
+        let (tx, rx) = SourceSender::new_test();
+        let source = DeadLetterConfig::default()
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let mut config = Config::default();
+        config.global.event_size.max_bytes = Some(16);
+        config.global.event_size.when_exceeded =
+            Some(vector_core::config::EventSizePolicy::DeadLetter);
+        let guard = EventSizeGuard::new(&config, &ComponentKey::from("in")).unwrap();
+        // Other tests may send dead letters too, which are told apart by their message.
+        let message = "receives_dead_letters ".repeat(4);
+        let mut array = EventArray::from(LogEvent::from(message.as_str()));
+        guard.enforce(&mut array);
+        assert!(array.is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let events = collect_ready(rx).await;
+        assert!(events
+            .iter()
+            .any(|event| event.as_log()["message"] == message.as_str().into()));
+    }
+}
@@ -6,8 +6,8 @@ use codecs::{BytesDeserializer, BytesDeserializerConfig};
 use file_source::{
     calculate_ignore_before,
     paths_provider::glob::{Glob, MatchOptions},
-    Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
-    ReadFromConfig,
+    Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line,
+    ReadBackend, ReadFrom, ReadFromConfig,
 };
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
 use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
@@ -21,6 +21,8 @@ use vector_common::finalizer::OrderedFinalizer;
 use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+use super::util::checkpoint_store::CheckpointStoreConfig;
 use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
     config::{
@@ -223,6 +225,15 @@ pub struct FileConfig {
     #[configurable(metadata(docs::examples = "\r\n"))]
     pub line_delimiter: String,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub read_backend: ReadBackend,
+
+    #[cfg(feature = "sources-utils-checkpoint-stores")]
+    #[configurable(derived)]
+    #[serde(default)]
+    pub checkpoint_store: Option<CheckpointStoreConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub encoding: Option<EncodingConfig>,
@@ -383,6 +394,9 @@ impl Default for FileConfig {
             oldest_first: false,
             remove_after_secs: None,
             line_delimiter: default_line_delimiter(),
+            read_backend: ReadBackend::default(),
+            #[cfg(feature = "sources-utils-checkpoint-stores")]
+            checkpoint_store: None,
             encoding: None,
             acknowledgements: Default::default(),
             log_namespace: None,
@@ -422,9 +436,19 @@ impl SourceConfig for FileConfig {
 
         let log_namespace = cx.log_namespace(self.log_namespace);
 
+        let checkpointer = Checkpointer::new(&data_dir);
+        #[cfg(feature = "sources-utils-checkpoint-stores")]
+        let checkpointer = match &self.checkpoint_store {
+            Some(checkpoint_store) => {
+                checkpointer.with_store(checkpoint_store.build(&cx.proxy).await?)
+            }
+            None => checkpointer,
+        };
+
         Ok(file_source(
             self,
             data_dir,
+            checkpointer,
             cx.shutdown,
             cx.out,
             acknowledgements,
@@ -478,6 +502,7 @@ impl SourceConfig for FileConfig {
 pub fn file_source(
     config: &FileConfig,
     data_dir: PathBuf,
+    checkpointer: Checkpointer,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
@@ -514,7 +539,6 @@ pub fn file_source(
         None => Bytes::from(config.line_delimiter.clone()),
     };
 
-    let checkpointer = Checkpointer::new(&data_dir);
     let file_server = FileServer {
         paths_provider,
         max_read_bytes: config.max_read_bytes,
@@ -523,6 +547,7 @@ pub fn file_source(
         ignore_before,
         max_line_bytes: config.max_line_bytes,
         line_delimiter: line_delimiter_as_bytes,
+        read_backend: config.read_backend,
         data_dir,
         glob_minimum_cooldown,
         fingerprinter: Fingerprinter {
@@ -2269,11 +2294,13 @@ mod tests {
 
             let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
             let data_dir = config.data_dir.clone().unwrap();
+            let checkpointer = Checkpointer::new(&data_dir);
             let acks = !matches!(acking_mode, NoAcks);
 
             tokio::spawn(file::file_source(
                 config,
                 data_dir,
+                checkpointer,
                 shutdown,
                 tx,
                 acks,
@@ -11,7 +11,7 @@ use vector_core::event::Value;
 /// The spec refers to 4 ways, but really CompressedPackedForward is encoded the
 /// same as PackedForward, it just has an additional decompression step.
 ///
-/// Not yet handled are the handshake messages.
+/// Of the handshake messages, only PING is sent by clients.
 ///
 /// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#event-modes>
 #[derive(Debug, Deserialize, Serialize)]
@@ -31,6 +31,7 @@ pub(super) enum FluentMessage {
     ForwardWithOptions(FluentTag, Vec<FluentEntry>, FluentMessageOptions),
     PackedForward(FluentTag, serde_bytes::ByteBuf),
     PackedForwardWithOptions(FluentTag, serde_bytes::ByteBuf, FluentMessageOptions),
+    Ping(FluentPing),
 
     // should be last as it'll match any other message
     Heartbeat(rmpv::Value), // should be Nil if heartbeat
@@ -47,6 +48,19 @@ pub(super) struct FluentMessageOptions {
     pub(super) compressed: Option<String>, // this one is required if present
 }
 
+/// Handshake message sent by clients in reply to the server's HELO.
+///
+/// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#ping>
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) struct FluentPing {
+    pub(super) kind: String, // always `PING`
+    pub(super) hostname: String,
+    pub(super) shared_key_salt: serde_bytes::ByteBuf,
+    pub(super) shared_key_hexdigest: String,
+    pub(super) username: String,
+    pub(super) password: String,
+}
+
 /// Fluent entry consisting of timestamp and record.
 ///
 /// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#forward-mode>
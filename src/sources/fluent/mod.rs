@@ -1,5 +1,6 @@
 use std::io::{self, Read};
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use base64::prelude::{Engine as _, BASE64_STANDARD};
@@ -9,12 +10,15 @@ use codecs::{BytesDeserializerConfig, StreamDecodingError};
 use flate2::read::MultiGzDecoder;
 use lookup::lookup_v2::parse_value_path;
 use lookup::{metadata_path, owned_value_path, path, OwnedValuePath, PathPrefix};
+use rand::Rng;
 use rmp_serde::{decode, Deserializer};
 use serde::Deserialize;
+use sha2::{Digest, Sha512};
 use smallvec::{smallvec, SmallVec};
 use tokio_util::codec::Decoder;
 use value::kind::Collection;
 use value::{Kind, Value};
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 use vector_core::schema::Definition;
@@ -33,7 +37,9 @@ use crate::{
 };
 
 mod message;
-use self::message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp};
+use self::message::{
+    FluentEntry, FluentMessage, FluentPing, FluentRecord, FluentTag, FluentTimestamp,
+};
 
 /// Configuration for the `fluent` source.
 #[configurable_component(source("fluent", "Collect logs from a Fluentd or Fluent Bit agent."))]
@@ -59,6 +65,9 @@ pub struct FluentConfig {
     #[configurable(derived)]
     tls: Option<TlsSourceConfig>,
 
+    #[configurable(derived)]
+    security: Option<FluentSecurityConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -69,6 +78,25 @@ pub struct FluentConfig {
     log_namespace: Option<bool>,
 }
 
+/// Forward protocol security configuration.
+///
+/// When set, clients must authenticate with a shared key through the forward protocol handshake
+/// before sending any events, as with the `<security>` section of Fluentd's `forward` input.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSecurityConfig {
+    /// The key shared with the clients.
+    #[configurable(metadata(docs::examples = "${FLUENT_SHARED_KEY}"))]
+    shared_key: SensitiveString,
+
+    /// The hostname the source identifies itself with during the handshake.
+    ///
+    /// By default, the hostname of the machine Vector is running on is used.
+    #[configurable(metadata(docs::examples = "vector-aggregator"))]
+    self_hostname: Option<String>,
+}
+
 impl GenerateConfig for FluentConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -76,6 +104,7 @@ impl GenerateConfig for FluentConfig {
             keepalive: None,
             tls: None,
             receive_buffer_bytes: None,
+            security: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
             log_namespace: None,
@@ -89,7 +118,17 @@ impl GenerateConfig for FluentConfig {
 impl SourceConfig for FluentConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let source = FluentSource::new(log_namespace);
+        let security = match &self.security {
+            Some(security) => Some(Arc::new(FluentSecurity {
+                shared_key: security.shared_key.clone(),
+                self_hostname: match &security.self_hostname {
+                    Some(hostname) => hostname.clone(),
+                    None => crate::get_hostname()?,
+                },
+            })),
+            None => None,
+        };
+        let source = FluentSource::new(log_namespace, security);
         let shutdown_secs = Duration::from_secs(30);
         let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
         let tls_client_metadata_key = self
@@ -202,17 +241,95 @@ impl FluentConfig {
 struct FluentSource {
     log_namespace: LogNamespace,
     legacy_host_key_path: Option<OwnedValuePath>,
+    security: Option<Arc<FluentSecurity>>,
 }
 
 impl FluentSource {
-    fn new(log_namespace: LogNamespace) -> Self {
+    fn new(log_namespace: LogNamespace, security: Option<Arc<FluentSecurity>>) -> Self {
         Self {
             log_namespace,
             legacy_host_key_path: parse_value_path(log_schema().host_key()).ok(),
+            security,
         }
     }
 }
 
+/// Resolved security configuration, shared by the connections.
+#[derive(Debug)]
+struct FluentSecurity {
+    shared_key: SensitiveString,
+    self_hostname: String,
+}
+
+/// State of the handshake of a connection.
+///
+/// <https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages>
+#[derive(Debug)]
+struct Handshake {
+    security: Arc<FluentSecurity>,
+    nonce: String,
+    authenticated: bool,
+}
+
+impl Handshake {
+    fn new(security: Arc<FluentSecurity>) -> Self {
+        Self {
+            security,
+            nonce: hex::encode(rand::thread_rng().gen::<[u8; 16]>()),
+            authenticated: false,
+        }
+    }
+
+    /// The HELO message, sent to the client as soon as it connects. User authentication isn't
+    /// supported, so `auth` is empty.
+    fn helo(&self) -> Bytes {
+        encode_msgpack(&rmpv::Value::Array(vec![
+            "HELO".into(),
+            rmpv::Value::Map(vec![
+                ("nonce".into(), self.nonce.as_str().into()),
+                ("auth".into(), "".into()),
+                ("keepalive".into(), true.into()),
+            ]),
+        ]))
+    }
+
+    /// Checks the shared key digest of the client's PING, and builds the PONG replying to it.
+    fn pong(&mut self, ping: &FluentPing) -> Bytes {
+        let (authenticated, reason) = if ping.kind != "PING" {
+            (false, "unexpected handshake message")
+        } else if ping.shared_key_hexdigest != self.digest(&ping.shared_key_salt, &ping.hostname) {
+            (false, "shared_key mismatch")
+        } else {
+            (true, "")
+        };
+        self.authenticated = authenticated;
+
+        encode_msgpack(&rmpv::Value::Array(vec![
+            "PONG".into(),
+            authenticated.into(),
+            reason.into(),
+            self.security.self_hostname.as_str().into(),
+            self.digest(&ping.shared_key_salt, &self.security.self_hostname)
+                .into(),
+        ]))
+    }
+
+    fn digest(&self, salt: &[u8], hostname: &str) -> String {
+        let mut digest = Sha512::new();
+        digest.update(salt);
+        digest.update(hostname.as_bytes());
+        digest.update(self.nonce.as_bytes());
+        digest.update(self.security.shared_key.inner().as_bytes());
+        hex::encode(digest.finalize())
+    }
+}
+
+fn encode_msgpack(value: &rmpv::Value) -> Bytes {
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, value).expect("writing to a Vec can't fail");
+    buf.into()
+}
+
 impl TcpSource for FluentSource {
     type Error = DecodeError;
     type Item = FluentFrame;
@@ -220,7 +337,14 @@ impl TcpSource for FluentSource {
     type Acker = FluentAcker;
 
     fn decoder(&self) -> Self::Decoder {
-        FluentDecoder::new(self.log_namespace)
+        FluentDecoder::new(
+            self.log_namespace,
+            self.security.clone().map(Handshake::new),
+        )
+    }
+
+    fn build_greeting(&self, decoder: &Self::Decoder) -> Option<Bytes> {
+        decoder.handshake.as_ref().map(Handshake::helo)
     }
 
     fn handle_events(&self, events: &mut [Event], host: SocketAddr) {
@@ -253,6 +377,8 @@ pub enum DecodeError {
     Decode(decode::Error),
     UnknownCompression(String),
     UnexpectedValue(rmpv::Value),
+    UnexpectedHandshake,
+    Unauthenticated,
 }
 
 impl std::fmt::Display for DecodeError {
@@ -266,6 +392,12 @@ impl std::fmt::Display for DecodeError {
             DecodeError::UnexpectedValue(value) => {
                 write!(f, "unexpected msgpack value, ignoring: {}", value)
             }
+            DecodeError::UnexpectedHandshake => {
+                write!(f, "unexpected handshake message, security is not enabled")
+            }
+            DecodeError::Unauthenticated => {
+                write!(f, "client sent a message before authenticating")
+            }
         }
     }
 }
@@ -277,6 +409,8 @@ impl StreamDecodingError for DecodeError {
             DecodeError::Decode(_) => true,
             DecodeError::UnknownCompression(_) => true,
             DecodeError::UnexpectedValue(_) => true,
+            DecodeError::UnexpectedHandshake => true,
+            DecodeError::Unauthenticated => false,
         }
     }
 }
@@ -296,11 +430,15 @@ impl From<decode::Error> for DecodeError {
 #[derive(Debug)]
 struct FluentDecoder {
     log_namespace: LogNamespace,
+    handshake: Option<Handshake>,
 }
 
 impl FluentDecoder {
-    const fn new(log_namespace: LogNamespace) -> Self {
-        Self { log_namespace }
+    const fn new(log_namespace: LogNamespace, handshake: Option<Handshake>) -> Self {
+        Self {
+            log_namespace,
+            handshake,
+        }
     }
 
     fn handle_message(
@@ -310,7 +448,23 @@ impl FluentDecoder {
     ) -> Result<Option<(FluentFrame, usize)>, DecodeError> {
         let log_namespace = &self.log_namespace;
 
-        match message? {
+        let message = match (&mut self.handshake, message?) {
+            (Some(handshake), FluentMessage::Ping(ping)) => {
+                let frame = FluentFrame {
+                    events: smallvec![],
+                    chunk: None,
+                    reply: Some(handshake.pong(&ping)),
+                };
+                return Ok(Some((frame, byte_size)));
+            }
+            (None, FluentMessage::Ping(_)) => return Err(DecodeError::UnexpectedHandshake),
+            (Some(handshake), _) if !handshake.authenticated => {
+                return Err(DecodeError::Unauthenticated)
+            }
+            (_, message) => message,
+        };
+
+        match message {
             FluentMessage::Message(tag, timestamp, record) => {
                 let event = Event::from(FluentEvent {
                     tag,
@@ -321,6 +475,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events: smallvec![event],
                     chunk: None,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -334,6 +489,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events: smallvec![event],
                     chunk: options.chunk,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -352,6 +508,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: None,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -370,6 +527,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: options.chunk,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -390,6 +548,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: None,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -422,11 +581,13 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: options.chunk,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
             FluentMessage::Heartbeat(rmpv::Value::Nil) => Ok(None),
             FluentMessage::Heartbeat(value) => Err(DecodeError::UnexpectedValue(value)),
+            FluentMessage::Ping(_) => unreachable!("handshake messages are handled above"),
         }
     }
 }
@@ -515,12 +676,14 @@ impl Decoder for FluentEntryStreamDecoder {
 }
 
 struct FluentAcker {
+    replies: Vec<Bytes>,
     chunks: Vec<String>,
 }
 
 impl FluentAcker {
     fn new(frames: &[FluentFrame]) -> Self {
         Self {
+            replies: frames.iter().filter_map(|f| f.reply.clone()).collect(),
             chunks: frames.iter().filter_map(|f| f.chunk.clone()).collect(),
         }
     }
@@ -528,19 +691,22 @@ impl FluentAcker {
 
 impl TcpSourceAcker for FluentAcker {
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
-        if self.chunks.is_empty() {
+        if self.replies.is_empty() && self.chunks.is_empty() {
             return None;
         }
 
-        let mut acks = String::new();
+        let mut acks = BytesMut::new();
+        for reply in self.replies {
+            acks.extend_from_slice(&reply);
+        }
         for chunk in self.chunks {
             let ack = match ack {
-                TcpSourceAck::Ack => format!(r#"{{"ack": "{}"}}"#, chunk),
-                _ => String::from("{}"),
+                TcpSourceAck::Ack => vec![("ack".into(), chunk.into())],
+                _ => Vec::new(),
             };
-            acks.push_str(&ack);
+            acks.extend_from_slice(&encode_msgpack(&rmpv::Value::Map(ack)));
         }
-        Some(acks.into())
+        Some(acks.freeze())
     }
 }
 
@@ -562,6 +728,7 @@ impl From<FluentEvent<'_>> for Event {
 struct FluentFrame {
     events: SmallVec<[Event; 1]>,
     chunk: Option<String>,
+    reply: Option<Bytes>,
 }
 
 impl From<FluentFrame> for SmallVec<[Event; 1]> {
@@ -844,7 +1011,7 @@ mod tests {
     fn decode_all(message: Vec<u8>) -> Result<(SmallVec<[Event; 1]>, usize), DecodeError> {
         let mut buf = BytesMut::from(&message[..]);
 
-        let mut decoder = FluentDecoder::new(LogNamespace::default());
+        let mut decoder = FluentDecoder::new(LogNamespace::default(), None);
 
         let (frame, byte_size) = decoder.decode(&mut buf)?.unwrap();
         Ok((frame.into(), byte_size))
@@ -861,7 +1028,8 @@ mod tests {
     async fn ack_delivered_with_chunk() {
         let (result, output) = check_acknowledgements(EventStatus::Delivered, true).await;
         assert_eq!(result.unwrap().unwrap(), output.len());
-        assert!(output.starts_with(b"{\"ack\":"));
+        let ack = rmpv::decode::read_value(&mut &output[..]).unwrap();
+        assert!(ack["ack"].is_str());
     }
 
     #[tokio::test]
@@ -875,7 +1043,10 @@ mod tests {
     async fn ack_failed_with_chunk() {
         let (result, output) = check_acknowledgements(EventStatus::Rejected, true).await;
         assert_eq!(result.unwrap().unwrap(), output.len());
-        assert_eq!(output, &b"{}"[..]);
+        assert_eq!(
+            rmpv::decode::read_value(&mut &output[..]).unwrap(),
+            rmpv::Value::Map(Vec::new())
+        );
     }
 
     async fn check_acknowledgements(
@@ -891,6 +1062,7 @@ mod tests {
             tls: None,
             keepalive: None,
             receive_buffer_bytes: None,
+            security: None,
             acknowledgements: true.into(),
             connection_limit: None,
             log_namespace: None,
@@ -928,6 +1100,139 @@ mod tests {
         (result, output.into())
     }
 
+    fn security(shared_key: &str) -> Arc<FluentSecurity> {
+        Arc::new(FluentSecurity {
+            shared_key: shared_key.to_owned().into(),
+            self_hostname: "server".to_owned(),
+        })
+    }
+
+    fn build_ping(nonce: &str, shared_key: &str) -> Vec<u8> {
+        let salt = "salt";
+        let digest = Sha512::new()
+            .chain_update(salt)
+            .chain_update("client")
+            .chain_update(nonce)
+            .chain_update(shared_key)
+            .finalize();
+        let ping = rmpv::Value::Array(vec![
+            "PING".into(),
+            "client".into(),
+            salt.into(),
+            hex::encode(digest).into(),
+            "".into(),
+            "".into(),
+        ]);
+        encode_msgpack(&ping).to_vec()
+    }
+
+    #[test]
+    fn handshake_authenticates_shared_key() {
+        let handshake = Handshake::new(security("secret"));
+        let helo = rmpv::decode::read_value(&mut &handshake.helo()[..]).unwrap();
+        assert_eq!(helo[0], "HELO".into());
+        assert_eq!(helo[1]["nonce"], handshake.nonce.as_str().into());
+        assert_eq!(helo[1]["auth"], "".into());
+
+        let mut decoder = FluentDecoder::new(LogNamespace::default(), Some(handshake));
+        let nonce = decoder.handshake.as_ref().unwrap().nonce.clone();
+
+        // Messages are refused until the client is authenticated.
+        let mut buf = BytesMut::from(&build_req("tag", &[("field", "value")], false)[..]);
+        assert!(matches!(
+            decoder.decode(&mut buf),
+            Err(DecodeError::Unauthenticated)
+        ));
+
+        let mut buf = BytesMut::from(&build_ping(&nonce, "secret")[..]);
+        let (frame, _) = decoder.decode(&mut buf).unwrap().unwrap();
+        assert!(frame.events.is_empty());
+        let pong = rmpv::decode::read_value(&mut &frame.reply.unwrap()[..]).unwrap();
+        assert_eq!(pong[0], "PONG".into());
+        assert_eq!(pong[1], true.into());
+        assert_eq!(pong[3], "server".into());
+        let digest = Sha512::new()
+            .chain_update("salt")
+            .chain_update("server")
+            .chain_update(&nonce)
+            .chain_update("secret")
+            .finalize();
+        assert_eq!(pong[4], hex::encode(digest).into());
+
+        let mut buf = BytesMut::from(&build_req("tag", &[("field", "value")], false)[..]);
+        let (frame, _) = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(frame.events.len(), 1);
+    }
+
+    #[test]
+    fn handshake_rejects_wrong_shared_key() {
+        let mut decoder = FluentDecoder::new(
+            LogNamespace::default(),
+            Some(Handshake::new(security("secret"))),
+        );
+        let nonce = decoder.handshake.as_ref().unwrap().nonce.clone();
+
+        let mut buf = BytesMut::from(&build_ping(&nonce, "guess")[..]);
+        let (frame, _) = decoder.decode(&mut buf).unwrap().unwrap();
+        let pong = rmpv::decode::read_value(&mut &frame.reply.unwrap()[..]).unwrap();
+        assert_eq!(pong[1], false.into());
+        assert_eq!(pong[2], "shared_key mismatch".into());
+
+        let mut buf = BytesMut::from(&build_req("tag", &[("field", "value")], false)[..]);
+        assert!(matches!(
+            decoder.decode(&mut buf),
+            Err(DecodeError::Unauthenticated)
+        ));
+    }
+
+    #[tokio::test]
+    async fn handshake_over_tcp() {
+        trace_init();
+
+        let (sender, recv) = SourceSender::new_test();
+        let address = next_addr();
+        let source = FluentConfig {
+            address: address.into(),
+            tls: None,
+            keepalive: None,
+            receive_buffer_bytes: None,
+            security: Some(FluentSecurityConfig {
+                shared_key: "secret".to_owned().into(),
+                self_hostname: Some("server".to_owned()),
+            }),
+            acknowledgements: false.into(),
+            connection_limit: None,
+            log_namespace: None,
+        }
+        .build(SourceContext::new_test(sender, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let mut socket = tokio::net::TcpStream::connect(address).await.unwrap();
+        let mut output = BytesMut::new();
+        socket.read_buf(&mut output).await.unwrap();
+        let helo = rmpv::decode::read_value(&mut &output[..]).unwrap();
+        let nonce = helo[1]["nonce"].as_str().unwrap().to_owned();
+
+        socket
+            .write_all(&build_ping(&nonce, "secret"))
+            .await
+            .unwrap();
+        output.clear();
+        socket.read_buf(&mut output).await.unwrap();
+        let pong = rmpv::decode::read_value(&mut &output[..]).unwrap();
+        assert_eq!(pong[1], true.into());
+
+        socket
+            .write_all(&build_req("tag", &[("field", "value")], false))
+            .await
+            .unwrap();
+        let events = test_util::collect_n(recv, 1).await;
+        assert_eq!(events[0].as_log()["field"], "value".into());
+    }
+
     fn build_req(tag: &str, fields: &[(&str, &str)], with_chunk: bool) -> Vec<u8> {
         let mut record = FluentRecord::default();
         for (tag, value) in fields {
@@ -955,6 +1260,7 @@ mod tests {
             tls: None,
             keepalive: None,
             receive_buffer_bytes: None,
+            security: None,
             acknowledgements: false.into(),
             connection_limit: None,
             log_namespace: Some(true),
@@ -1010,6 +1316,7 @@ mod tests {
             tls: None,
             keepalive: None,
             receive_buffer_bytes: None,
+            security: None,
             acknowledgements: false.into(),
             connection_limit: None,
             log_namespace: None,
@@ -1231,6 +1538,7 @@ mod integration_tests {
                 tls: None,
                 keepalive: None,
                 receive_buffer_bytes: None,
+                security: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
                 log_namespace: None,
@@ -171,7 +171,7 @@ impl SourceConfig for LogplexConfig {
         };
 
         source.run(
-            self.address,
+            self.address.into(),
             "events",
             HttpMethod::Post,
             true,
@@ -8,7 +8,11 @@ use tokio::{
 };
 use vector_core::metric_tags;
 
-use super::{filter_result_sync, CGroupsConfig, HostMetrics, MetricsBuffer};
+use super::{
+    filter_result_sync,
+    pressure::{read_pressure, RESOURCES},
+    CGroupsConfig, Collector, HostMetrics, MetricsBuffer,
+};
 use crate::event::MetricTags;
 
 const MICROSECONDS: f64 = 1.0 / 1_000_000.0;
@@ -63,6 +67,7 @@ struct CGroupRecurser<'a> {
     buffer: String,
     load_cpu: bool,
     load_memory: bool,
+    load_pressure: bool,
     config: CGroupsConfig,
 }
 
@@ -75,6 +80,7 @@ impl<'a> CGroupRecurser<'a> {
             buffer: String::new(),
             load_cpu: true,
             load_memory: true,
+            load_pressure: host.config.has_collector(Collector::Pressure),
             config: cgroups,
         }
     }
@@ -119,6 +125,9 @@ impl<'a> CGroupRecurser<'a> {
             if self.load_memory && !cgroup.is_root() {
                 self.load_memory(&cgroup, &tags).await;
             }
+            if self.load_pressure {
+                self.load_pressure(&cgroup, &tags).await;
+            }
 
             if level < self.config.levels {
                 let groups = self.config.groups.clone();
@@ -179,6 +188,22 @@ impl<'a> CGroupRecurser<'a> {
                 .gauge("cgroup_memory_file_bytes", stat.file as f64, tags.clone());
         }
     }
+
+    /// Try to load the pressure stall information files and emit metrics for those found. They
+    /// only exist in the v2 cgroups, when the kernel supports PSI.
+    async fn load_pressure(&mut self, cgroup: &CGroup, tags: &MetricTags) {
+        for resource in RESOURCES {
+            let filename = cgroup.make_path(format!("{}.pressure", resource));
+            if let Some(Some(stats)) = filter_result_sync(
+                read_pressure(filename).await,
+                "Failed to load cgroups pressure statistics.",
+            ) {
+                for stat in stats {
+                    stat.emit(self.output, "cgroup_", resource, tags.clone());
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -546,6 +571,46 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn parses_cgroups_pressure() {
+        // The pressure files exist in all the v2 groups but the root.
+        let mut base = Setup::new();
+        for subdir in SUBDIRS {
+            base.group(subdir, CPU_STAT | MEMORY_STAT, Some("cpu memory io\n"));
+            if subdir != "." {
+                for resource in ["cpu", "memory", "io"] {
+                    base.f(subdir, &format!("{resource}.pressure"), PRESSURE);
+                }
+            }
+        }
+
+        let path = base.0.path();
+        let config: HostMetricsConfig = toml::from_str(&format!(
+            r#"
+            collectors = ["cgroups", "pressure"]
+            cgroups.base_dir = {path:?}
+            "#
+        ))
+        .unwrap();
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(config).cgroups_metrics(&mut buffer).await;
+        let metrics = buffer.metrics;
+
+        // Both lines of the three files, with three windows each for the ratios.
+        assert_eq!(
+            count_name(&metrics, "cgroup_pressure_stalled_seconds_total"),
+            (SUBDIRS.len() - 1) * 6
+        );
+        assert_eq!(
+            count_name(&metrics, "cgroup_pressure_stalled_ratio"),
+            (SUBDIRS.len() - 1) * 18
+        );
+        assert_eq!(&all_tags(&metrics, "collector"), &["cgroups"]);
+    }
+
+    const PRESSURE: &str = "some avg10=0.50 avg60=0.20 avg300=0.10 total=123456\n\
+                            full avg10=0.10 avg60=0.00 avg300=0.00 total=2345\n";
+
     const SUBDIRS: [&str; 5] = [
         ".",
         "system.slice",
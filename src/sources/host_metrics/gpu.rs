@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+
+use tokio::process::Command;
+use vector_config::configurable_component;
+use vector_core::metric_tags;
+
+use crate::internal_events::HostMetricsScrapeDetailError;
+
+use super::{filter_result_sync, HostMetrics};
+
+/// The fields queried from `nvidia-smi`, in the order of its output columns.
+const GPU_QUERY: &str = "index,uuid,name,utilization.gpu,utilization.memory,memory.used,memory.total,temperature.gpu,power.draw";
+
+const MEBIBYTE: f64 = 1024.0 * 1024.0;
+
+/// Options for the GPU metrics collector.
+///
+/// The metrics of NVIDIA GPUs are read from [NVML][nvml] with `nvidia-smi`, which is installed
+/// along with the NVIDIA driver.
+///
+/// [nvml]: https://developer.nvidia.com/nvidia-management-library-nvml
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub struct GpuConfig {
+    /// The path of the `nvidia-smi` binary.
+    ///
+    /// By default, `nvidia-smi` is looked up in the `PATH`.
+    #[serde(default = "default_nvidia_smi_path")]
+    #[derivative(Default(value = "default_nvidia_smi_path()"))]
+    #[configurable(metadata(docs::examples = "/usr/bin/nvidia-smi"))]
+    nvidia_smi_path: PathBuf,
+}
+
+fn default_nvidia_smi_path() -> PathBuf {
+    PathBuf::from("nvidia-smi")
+}
+
+impl HostMetrics {
+    pub async fn gpu_metrics(&self, output: &mut super::MetricsBuffer) {
+        output.name = "gpu";
+        let result = Command::new(&self.config.gpu.nvidia_smi_path)
+            .arg(format!("--query-gpu={}", GPU_QUERY))
+            .arg("--format=csv,noheader,nounits")
+            .output()
+            .await;
+
+        let output_bytes = match filter_result_sync(result, "Failed to run nvidia-smi.") {
+            Some(result) if result.status.success() => result.stdout,
+            Some(result) => {
+                let error = String::from_utf8_lossy(&result.stderr);
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to read GPU data.",
+                    error: error.trim(),
+                });
+                return;
+            }
+            None => return,
+        };
+
+        for line in String::from_utf8_lossy(&output_bytes).lines() {
+            gpu_device_metrics(output, line);
+        }
+    }
+}
+
+/// Parses a value of `nvidia-smi`, which reports missing values with placeholders such as `[N/A]`.
+fn parse_value(value: Option<&str>) -> Option<f64> {
+    value.and_then(|value| value.parse().ok())
+}
+
+fn gpu_device_metrics(output: &mut super::MetricsBuffer, line: &str) {
+    let mut fields = line.split(',').map(str::trim);
+    let (index, uuid, name) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(index), Some(uuid), Some(name)) if !index.is_empty() => (index, uuid, name),
+        _ => return,
+    };
+    let tags = metric_tags! {
+        "device" => index,
+        "uuid" => uuid,
+        "model" => name,
+    };
+
+    if let Some(value) = parse_value(fields.next()) {
+        output.gauge("gpu_utilization_ratio", value / 100.0, tags.clone());
+    }
+    if let Some(value) = parse_value(fields.next()) {
+        output.gauge("gpu_memory_utilization_ratio", value / 100.0, tags.clone());
+    }
+    if let Some(value) = parse_value(fields.next()) {
+        output.gauge("gpu_memory_used_bytes", value * MEBIBYTE, tags.clone());
+    }
+    if let Some(value) = parse_value(fields.next()) {
+        output.gauge("gpu_memory_total_bytes", value * MEBIBYTE, tags.clone());
+    }
+    if let Some(value) = parse_value(fields.next()) {
+        output.gauge("gpu_temperature_celsius", value, tags.clone());
+    }
+    if let Some(value) = parse_value(fields.next()) {
+        output.gauge("gpu_power_draw_watts", value, tags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            tests::{all_gauges, count_name, count_tag},
+            MetricsBuffer,
+        },
+        gpu_device_metrics,
+    };
+
+    #[test]
+    fn generates_gpu_metrics() {
+        let mut buffer = MetricsBuffer::new(None);
+        buffer.name = "gpu";
+        gpu_device_metrics(
+            &mut buffer,
+            "0, GPU-5c1d2a1e-8f0b-4a51-93c5-3b3c5e0f1d2a, NVIDIA A100-SXM4-40GB, 35, 10, 1024, 40960, 45, 60.12",
+        );
+        gpu_device_metrics(
+            &mut buffer,
+            "1, GPU-7e2f3b2c-9a1c-4b62-a4d6-4c4d6f1a2e3b, NVIDIA T4, 0, 0, 0, 15360, 38, [N/A]",
+        );
+        let metrics = buffer.metrics;
+
+        assert_eq!(metrics.len(), 11);
+        assert!(all_gauges(&metrics));
+        assert_eq!(count_name(&metrics, "gpu_power_draw_watts"), 1);
+        assert_eq!(count_tag(&metrics, "device"), metrics.len());
+        assert_eq!(count_tag(&metrics, "uuid"), metrics.len());
+        assert_eq!(count_tag(&metrics, "model"), metrics.len());
+    }
+}
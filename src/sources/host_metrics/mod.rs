@@ -32,8 +32,12 @@ mod cgroups;
 mod cpu;
 mod disk;
 mod filesystem;
+mod gpu;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod pressure;
+mod smart;
 
 /// Collector types.
 #[serde_as]
@@ -66,6 +70,25 @@ pub enum Collector {
 
     /// Metrics related to network utilization.
     Network,
+
+    /// Metrics related to the health of disks, read from their SMART data.
+    ///
+    /// Not enabled by default.
+    Smart,
+
+    /// Metrics related to the utilization of NVIDIA GPUs.
+    ///
+    /// Not enabled by default.
+    Gpu,
+
+    /// Metrics related to the pressure stall information (PSI) of the CPU, memory, and I/O.
+    ///
+    /// The pressure is reported host-wide, and for each cgroup when the `cgroups` collector is
+    /// enabled too. These are better signals of the saturation of the resources than their
+    /// utilization.
+    ///
+    /// Only available on Linux, with cgroups v2 for the per-cgroup metrics. Not enabled by default.
+    Pressure,
 }
 
 /// Filtering configuration.
@@ -97,7 +120,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors, except `smart`, `gpu`, and `pressure`.
     #[configurable(metadata(docs::examples = "example_collectors()"))]
     #[derivative(Default(value = "default_collectors()"))]
     #[serde(default = "default_collectors")]
@@ -124,6 +147,14 @@ pub struct HostMetricsConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub smart: smart::SmartConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub gpu: gpu::GpuConfig,
 }
 
 /// Options for the cgroups (controller groups) metrics collector.
@@ -168,7 +199,7 @@ pub fn default_namespace() -> Option<String> {
     Some(String::from("host"))
 }
 
-const fn example_collectors() -> [&'static str; 8] {
+const fn example_collectors() -> [&'static str; 11] {
     [
         "cgroups",
         "cpu",
@@ -178,6 +209,9 @@ const fn example_collectors() -> [&'static str; 8] {
         "host",
         "memory",
         "network",
+        "smart",
+        "gpu",
+        "pressure",
     ]
 }
 
@@ -259,6 +293,9 @@ impl SourceConfig for HostMetricsConfig {
             if self.cgroups.is_some() || self.has_collector(Collector::CGroups) {
                 return Err("CGroups collector is only available on Linux systems".into());
             }
+            if self.has_collector(Collector::Pressure) {
+                return Err("Pressure collector is only available on Linux systems".into());
+            }
         }
 
         let mut config = self.clone();
@@ -371,6 +408,16 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        if self.config.has_collector(Collector::Smart) {
+            self.smart_metrics(&mut buffer).await;
+        }
+        if self.config.has_collector(Collector::Gpu) {
+            self.gpu_metrics(&mut buffer).await;
+        }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Pressure) {
+            self.pressure_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         self.events_received.emit(CountByteSize(
@@ -0,0 +1,228 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use snafu::Snafu;
+use tokio::fs;
+
+use super::{filter_result_sync, HostMetrics, MetricsBuffer};
+use crate::{event::MetricTags, internal_events::HostMetricsScrapeDetailError};
+
+const MICROSECONDS: f64 = 1.0 / 1_000_000.0;
+
+/// The resources whose pressure is reported, which are also the names of their PSI files.
+pub(super) const RESOURCES: [&str; 3] = ["cpu", "memory", "io"];
+
+#[derive(Debug, Snafu)]
+pub(super) enum PressureError {
+    #[snafu(display("Could not read pressure data file {:?}.", filename))]
+    Reading {
+        filename: PathBuf,
+        source: io::Error,
+    },
+    #[snafu(display(
+        "Could not parse line {:?} of pressure data file {:?}.",
+        line,
+        filename
+    ))]
+    Parsing { filename: PathBuf, line: String },
+}
+
+impl HostMetrics {
+    pub(super) async fn pressure_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "pressure";
+        let base = heim::os::linux::procfs_root().join("pressure");
+        host_pressure_metrics(output, &base).await;
+    }
+}
+
+async fn host_pressure_metrics(output: &mut MetricsBuffer, base: &Path) {
+    for resource in RESOURCES {
+        let filename = base.join(resource);
+        match filter_result_sync(
+            read_pressure(filename.clone()).await,
+            "Failed to load pressure statistics.",
+        ) {
+            Some(Some(stats)) => {
+                // The kernel reports a host-wide `full` line for the CPU only for compatibility,
+                // with all values set to zero.
+                let stats = stats
+                    .iter()
+                    .filter(|stat| !(resource == "cpu" && stat.stall == "full"));
+                for stat in stats {
+                    stat.emit(output, "", resource, MetricTags::default());
+                }
+            }
+            Some(None) => {
+                // Kernels built without PSI support have no `/proc/pressure` directory.
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Pressure stall information is not available.",
+                    error: filename.display(),
+                });
+                return;
+            }
+            None => {}
+        }
+    }
+}
+
+/// Reads a PSI file, as found in `/proc/pressure` and in cgroups v2 directories. Returns `Ok(None)`
+/// if the file doesn't exist.
+pub(super) async fn read_pressure(
+    filename: PathBuf,
+) -> Result<Option<Vec<PressureStat>>, PressureError> {
+    let text = match fs::read_to_string(&filename).await {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => return Err(PressureError::Reading { filename, source }),
+    };
+
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            PressureStat::parse(line).ok_or_else(|| PressureError::Parsing {
+                filename: filename.clone(),
+                line: line.into(),
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+/// One line of a PSI file, such as `some avg10=0.12 avg60=0.05 avg300=0.01 total=123456`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) struct PressureStat {
+    /// Either `some`, when at least one task was stalled, or `full`, when all of them were.
+    stall: &'static str,
+    /// The percentages of time stalled over the last 10, 60 and 300 seconds.
+    avg10: f64,
+    avg60: f64,
+    avg300: f64,
+    /// The total time stalled, in microseconds.
+    total: u64,
+}
+
+impl PressureStat {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let stall = match fields.next()? {
+            "some" => "some",
+            "full" => "full",
+            _ => return None,
+        };
+
+        let mut stat = Self {
+            stall,
+            ..Default::default()
+        };
+        for field in fields {
+            let (key, value) = field.split_once('=')?;
+            match key {
+                "avg10" => stat.avg10 = value.parse().ok()?,
+                "avg60" => stat.avg60 = value.parse().ok()?,
+                "avg300" => stat.avg300 = value.parse().ok()?,
+                "total" => stat.total = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        Some(stat)
+    }
+
+    /// Emits the metrics of this line, with their names starting with `prefix`.
+    pub(super) fn emit(
+        &self,
+        output: &mut MetricsBuffer,
+        prefix: &str,
+        resource: &str,
+        mut tags: MetricTags,
+    ) {
+        tags.replace("resource".into(), resource.to_string());
+        tags.replace("stall".into(), self.stall.to_string());
+
+        output.counter(
+            &format!("{}pressure_stalled_seconds_total", prefix),
+            self.total as f64 * MICROSECONDS,
+            tags.clone(),
+        );
+        for (window, value) in [
+            ("10s", self.avg10),
+            ("60s", self.avg60),
+            ("300s", self.avg300),
+        ] {
+            let mut tags = tags.clone();
+            tags.replace("window".into(), window.to_string());
+            output.gauge(
+                &format!("{}pressure_stalled_ratio", prefix),
+                value / 100.0,
+                tags,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::{
+        super::tests::{count_name, count_tag},
+        *,
+    };
+
+    #[test]
+    fn parses_pressure_lines() {
+        assert_eq!(
+            PressureStat::parse("some avg10=1.50 avg60=0.25 avg300=0.00 total=1234567"),
+            Some(PressureStat {
+                stall: "some",
+                avg10: 1.5,
+                avg60: 0.25,
+                avg300: 0.0,
+                total: 1234567,
+            })
+        );
+        assert_eq!(
+            PressureStat::parse("full avg10=0.00 avg60=0.00 avg300=0.00 total=0")
+                .map(|stat| stat.stall),
+            Some("full")
+        );
+        assert_eq!(PressureStat::parse("half avg10=0.00"), None);
+        assert_eq!(PressureStat::parse("some avg10=zero"), None);
+    }
+
+    #[tokio::test]
+    async fn generates_host_pressure_metrics() {
+        let base = tempfile::tempdir().unwrap();
+        let text = "some avg10=2.00 avg60=1.00 avg300=0.50 total=3000000\n\
+                    full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n";
+        for resource in RESOURCES {
+            std::fs::write(base.path().join(resource), text).unwrap();
+        }
+
+        let mut buffer = MetricsBuffer::new(None);
+        buffer.name = "pressure";
+        host_pressure_metrics(&mut buffer, base.path()).await;
+        let metrics = buffer.metrics;
+
+        // The host-wide `full` line of the CPU is skipped.
+        assert_eq!(count_name(&metrics, "pressure_stalled_seconds_total"), 5);
+        assert_eq!(count_name(&metrics, "pressure_stalled_ratio"), 15);
+        assert_eq!(count_tag(&metrics, "window"), 15);
+        assert_eq!(count_tag(&metrics, "resource"), metrics.len());
+
+        let cpu_some = metrics
+            .iter()
+            .find(|metric| {
+                let tags = metric.tags().unwrap();
+                metric.name() == "pressure_stalled_ratio"
+                    && tags.get("resource") == Some("cpu")
+                    && tags.get("window") == Some("10s")
+            })
+            .unwrap();
+        assert_eq!(
+            cpu_some.value(),
+            &crate::event::MetricValue::Gauge { value: 0.02 }
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tokio::process::Command;
+use vector_config::configurable_component;
+use vector_core::metric_tags;
+
+use crate::internal_events::HostMetricsScrapeDetailError;
+
+use super::{default_all_devices, example_devices, filter_result_sync, FilterList, HostMetrics};
+
+/// Options for the SMART metrics collector.
+///
+/// The metrics are read with [`smartctl`][smartctl], from smartmontools 7.0 or later, which
+/// usually needs to run as root.
+///
+/// [smartctl]: https://www.smartmontools.org/
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub struct SmartConfig {
+    /// The path of the `smartctl` binary.
+    ///
+    /// By default, `smartctl` is looked up in the `PATH`.
+    #[serde(default = "default_smartctl_path")]
+    #[derivative(Default(value = "default_smartctl_path()"))]
+    #[configurable(metadata(docs::examples = "/usr/sbin/smartctl"))]
+    smartctl_path: PathBuf,
+
+    /// Lists of device name patterns to include or exclude in gathering
+    /// SMART metrics.
+    #[configurable(metadata(docs::examples = "example_devices()"))]
+    #[serde(default = "default_all_devices")]
+    #[derivative(Default(value = "default_all_devices()"))]
+    devices: FilterList,
+}
+
+fn default_smartctl_path() -> PathBuf {
+    PathBuf::from("smartctl")
+}
+
+/// The devices found by `smartctl --scan-open`.
+#[derive(Debug, Deserialize)]
+struct SmartScan {
+    #[serde(default)]
+    devices: Vec<SmartScanDevice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartScanDevice {
+    name: String,
+    #[serde(rename = "type")]
+    device_type: String,
+}
+
+/// The subset of the report of `smartctl --all` turned into metrics.
+#[derive(Debug, Default, Deserialize)]
+struct SmartReport {
+    model_name: Option<String>,
+    smart_status: Option<SmartStatus>,
+    temperature: Option<SmartTemperature>,
+    power_on_time: Option<SmartPowerOnTime>,
+    power_cycle_count: Option<f64>,
+    ata_smart_attributes: Option<SmartAtaAttributes>,
+    nvme_smart_health_information_log: Option<SmartNvmeLog>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartStatus {
+    passed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartTemperature {
+    current: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartPowerOnTime {
+    hours: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartAtaAttributes {
+    #[serde(default)]
+    table: Vec<SmartAtaAttribute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartAtaAttribute {
+    id: u8,
+    name: String,
+    value: f64,
+    raw: SmartAtaRawValue,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartAtaRawValue {
+    value: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SmartNvmeLog {
+    percentage_used: Option<f64>,
+    available_spare: Option<f64>,
+    data_units_read: Option<f64>,
+    data_units_written: Option<f64>,
+    media_errors: Option<f64>,
+    unsafe_shutdowns: Option<f64>,
+}
+
+// NVMe data units are thousands of 512 byte blocks.
+const NVME_DATA_UNIT_BYTES: f64 = 512_000.0;
+
+impl HostMetrics {
+    pub async fn smart_metrics(&self, output: &mut super::MetricsBuffer) {
+        output.name = "smart";
+        let config = &self.config.smart;
+
+        let scan = smartctl(&config.smartctl_path, &["--scan-open"])
+            .await
+            .and_then(|stdout| {
+                filter_result_sync(
+                    serde_json::from_slice::<SmartScan>(&stdout),
+                    "Failed to parse the devices found by smartctl.",
+                )
+            });
+        let devices = match scan {
+            Some(scan) => scan.devices,
+            None => return,
+        };
+
+        for device in devices {
+            let name = device_name(&device.name);
+            if !config.devices.contains_str(Some(name)) {
+                continue;
+            }
+
+            let report = smartctl(
+                &config.smartctl_path,
+                &["--all", "--device", &device.device_type, &device.name],
+            )
+            .await
+            .and_then(|stdout| {
+                filter_result_sync(
+                    serde_json::from_slice::<SmartReport>(&stdout),
+                    "Failed to parse the SMART data of a device.",
+                )
+            });
+            if let Some(report) = report {
+                smart_device_metrics(output, name, &report);
+            }
+        }
+    }
+}
+
+/// Runs `smartctl` with JSON output, returning its output if it could open the device.
+async fn smartctl(path: &Path, args: &[&str]) -> Option<Vec<u8>> {
+    let output = filter_result_sync(
+        Command::new(path).arg("--json").args(args).output().await,
+        "Failed to run smartctl.",
+    )?;
+
+    // The low bits of the exit status report that the command line couldn't be parsed, or that the
+    // device couldn't be opened. The other bits report the health of the device.
+    match output.status.code() {
+        Some(code) if code & 0b11 == 0 => Some(output.stdout),
+        _ => {
+            let error = String::from_utf8_lossy(&output.stderr);
+            emit!(HostMetricsScrapeDetailError {
+                message: "Failed to read SMART data.",
+                error: error.trim(),
+            });
+            None
+        }
+    }
+}
+
+/// Strips the `/dev/` prefix of a device path.
+fn device_name(path: &str) -> &str {
+    path.strip_prefix("/dev/").unwrap_or(path)
+}
+
+fn smart_device_metrics(output: &mut super::MetricsBuffer, device: &str, report: &SmartReport) {
+    let mut tags = metric_tags! {
+        "device" => device,
+    };
+    if let Some(model_name) = &report.model_name {
+        tags.replace("model".into(), model_name.clone());
+    }
+
+    if let Some(status) = &report.smart_status {
+        output.gauge(
+            "smart_health_passed",
+            if status.passed { 1.0 } else { 0.0 },
+            tags.clone(),
+        );
+    }
+    if let Some(temperature) = report.temperature.as_ref().and_then(|t| t.current) {
+        output.gauge("smart_temperature_celsius", temperature, tags.clone());
+    }
+    if let Some(hours) = report.power_on_time.as_ref().and_then(|t| t.hours) {
+        output.counter("smart_power_on_hours_total", hours, tags.clone());
+    }
+    if let Some(count) = report.power_cycle_count {
+        output.counter("smart_power_cycles_total", count, tags.clone());
+    }
+
+    if let Some(attributes) = &report.ata_smart_attributes {
+        for attribute in &attributes.table {
+            let mut tags = tags.clone();
+            tags.replace("attribute_id".into(), attribute.id.to_string());
+            tags.replace("attribute".into(), attribute.name.clone());
+            output.gauge("smart_attribute_value", attribute.value, tags.clone());
+            output.gauge("smart_attribute_raw_value", attribute.raw.value, tags);
+        }
+    }
+
+    if let Some(log) = &report.nvme_smart_health_information_log {
+        if let Some(value) = log.percentage_used {
+            output.gauge("smart_nvme_percentage_used", value, tags.clone());
+        }
+        if let Some(value) = log.available_spare {
+            output.gauge("smart_nvme_available_spare", value, tags.clone());
+        }
+        if let Some(value) = log.data_units_read {
+            output.counter(
+                "smart_nvme_read_bytes_total",
+                value * NVME_DATA_UNIT_BYTES,
+                tags.clone(),
+            );
+        }
+        if let Some(value) = log.data_units_written {
+            output.counter(
+                "smart_nvme_written_bytes_total",
+                value * NVME_DATA_UNIT_BYTES,
+                tags.clone(),
+            );
+        }
+        if let Some(value) = log.media_errors {
+            output.counter("smart_nvme_media_errors_total", value, tags.clone());
+        }
+        if let Some(value) = log.unsafe_shutdowns {
+            output.counter("smart_nvme_unsafe_shutdowns_total", value, tags);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            tests::{count_name, count_tag},
+            MetricsBuffer,
+        },
+        smart_device_metrics, SmartReport,
+    };
+
+    #[test]
+    fn generates_nvme_metrics() {
+        let report: SmartReport = serde_json::from_str(
+            r#"{
+                "model_name": "Samsung SSD 980 PRO 1TB",
+                "smart_status": {"passed": true},
+                "temperature": {"current": 41},
+                "power_on_time": {"hours": 2045},
+                "power_cycle_count": 120,
+                "nvme_smart_health_information_log": {
+                    "percentage_used": 2,
+                    "available_spare": 100,
+                    "data_units_read": 1000,
+                    "data_units_written": 2000,
+                    "media_errors": 0,
+                    "unsafe_shutdowns": 7
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut buffer = MetricsBuffer::new(None);
+        buffer.name = "smart";
+        smart_device_metrics(&mut buffer, "nvme0", &report);
+        let metrics = buffer.metrics;
+
+        assert_eq!(metrics.len(), 10);
+        assert_eq!(count_tag(&metrics, "device"), metrics.len());
+        assert_eq!(count_tag(&metrics, "model"), metrics.len());
+        assert_eq!(count_name(&metrics, "smart_nvme_read_bytes_total"), 1);
+        assert_eq!(count_name(&metrics, "smart_attribute_raw_value"), 0);
+    }
+
+    #[test]
+    fn generates_ata_attribute_metrics() {
+        let report: SmartReport = serde_json::from_str(
+            r#"{
+                "smart_status": {"passed": false},
+                "ata_smart_attributes": {
+                    "table": [
+                        {"id": 5, "name": "Reallocated_Sector_Ct", "value": 100, "raw": {"value": 8}},
+                        {"id": 9, "name": "Power_On_Hours", "value": 95, "raw": {"value": 4321}}
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut buffer = MetricsBuffer::new(None);
+        buffer.name = "smart";
+        smart_device_metrics(&mut buffer, "sda", &report);
+        let metrics = buffer.metrics;
+
+        assert_eq!(metrics.len(), 5);
+        assert_eq!(count_name(&metrics, "smart_attribute_value"), 2);
+        assert_eq!(count_name(&metrics, "smart_attribute_raw_value"), 2);
+        assert_eq!(count_tag(&metrics, "attribute"), 4);
+        assert_eq!(count_tag(&metrics, "model"), 0);
+    }
+}
@@ -29,7 +29,7 @@ use crate::{
 use crate::{components::validation::*, sources::util::http_client};
 use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
-    StreamDecodingError,
+    JsonDeserializerConfig, StreamDecodingError,
 };
 use vector_config::configurable_component;
 use vector_core::{
@@ -234,7 +234,7 @@ impl ValidatableComponent for HttpClientConfig {
         let config = Self {
             endpoint: uri.to_string(),
             interval: Duration::from_secs(1),
-            decoding: DeserializerConfig::Json,
+            decoding: JsonDeserializerConfig::new().into(),
             ..Default::default()
         };
 
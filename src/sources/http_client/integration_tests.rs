@@ -15,7 +15,7 @@ use crate::{
     tls::TlsConfig,
     SourceSender,
 };
-use codecs::decoding::DeserializerConfig;
+use codecs::decoding::{DeserializerConfig, JsonDeserializerConfig};
 use vector_core::config::log_schema;
 
 use super::{
@@ -96,7 +96,7 @@ async fn collected_logs_json() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -173,7 +173,7 @@ async fn unauthorized_no_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -191,7 +191,7 @@ async fn unauthorized_wrong_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -212,7 +212,7 @@ async fn authorized() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -233,7 +233,7 @@ async fn tls_invalid_ca() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -254,7 +254,7 @@ async fn tls_valid() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -276,7 +276,7 @@ async fn shutdown() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -5,7 +5,7 @@ use warp::{http::HeaderMap, Filter};
 use crate::sources::util::http::HttpMethod;
 use crate::{serde::default_decoding, serde::default_framing_message_based};
 use codecs::decoding::{
-    CharacterDelimitedDecoderOptions, DeserializerConfig, FramingConfig,
+    CharacterDelimitedDecoderOptions, DeserializerConfig, FramingConfig, JsonDeserializerConfig,
     NewlineDelimitedDecoderOptions,
 };
 use vector_core::event::Event;
@@ -78,7 +78,7 @@ async fn json_decoding_newline_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: FramingConfig::NewlineDelimited {
             newline_delimited: NewlineDelimitedDecoderOptions::default(),
         },
@@ -108,7 +108,7 @@ async fn json_decoding_character_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         interval: INTERVAL,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: FramingConfig::CharacterDelimited {
             character_delimited: CharacterDelimitedDecoderOptions {
                 delimiter: b',',
@@ -146,7 +146,7 @@ async fn request_query_applied() {
                 vec!["val1".to_string(), "val2".to_string()],
             ),
         ]),
-        decoding: DeserializerConfig::Json,
+        decoding: JsonDeserializerConfig::new().into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
@@ -30,7 +30,7 @@ use crate::{
     register_validatable_component,
     serde::{bool_or_struct, default_decoding},
     sources::util::{
-        http::{add_query_parameters, HttpMethod},
+        http::{add_query_parameters, HttpListenAddr, HttpMethod},
         Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsEnableableConfig,
@@ -72,12 +72,8 @@ impl SourceConfig for HttpConfig {
 #[configurable_component(source("http_server", "Host an HTTP endpoint to receive logs."))]
 #[derive(Clone, Debug)]
 pub struct SimpleHttpConfig {
-    /// The socket address to listen for connections on.
-    ///
-    /// It _must_ include a port.
-    #[configurable(metadata(docs::examples = "0.0.0.0:80"))]
-    #[configurable(metadata(docs::examples = "localhost:80"))]
-    address: SocketAddr,
+    #[configurable(derived)]
+    address: HttpListenAddr,
 
     /// The expected encoding of received data.
     ///
@@ -233,7 +229,7 @@ impl SimpleHttpConfig {
 impl Default for SimpleHttpConfig {
     fn default() -> Self {
         Self {
-            address: "0.0.0.0:8080".parse().unwrap(),
+            address: SocketAddr::from(([0, 0, 0, 0], 8080)).into(),
             encoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
@@ -256,7 +252,7 @@ impl_generate_config_from_default!(SimpleHttpConfig);
 impl ValidatableComponent for SimpleHttpConfig {
     fn validation_configuration() -> ValidationConfiguration {
         let config = Self {
-            decoding: Some(DeserializerConfig::Json),
+            decoding: Some(JsonDeserializerConfig::new().into()),
             ..Default::default()
         };
 
@@ -325,7 +321,7 @@ impl SourceConfig for SimpleHttpConfig {
             log_namespace,
         };
         source.run(
-            self.address,
+            self.address.clone(),
             self.path.as_str(),
             self.method,
             self.strict_path,
@@ -353,7 +349,7 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+        self.address.as_tcp_resource().into_iter().collect()
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -483,13 +479,14 @@ mod tests {
     use similar_asserts::assert_eq;
 
     use super::{remove_duplicates, SimpleHttpConfig};
-    use crate::sources::http_server::HttpMethod;
+    use crate::sources::{http_server::HttpMethod, util::http::HttpListenAddr};
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
         event::{Event, EventStatus, Value},
         test_util::{
+            collect_n,
             components::{self, assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
-            next_addr, spawn_collect_n, wait_for_tcp,
+            next_addr, spawn_collect_n, temp_dir, wait_for, wait_for_tcp,
         },
         SourceSender,
     };
@@ -525,7 +522,7 @@ mod tests {
 
         tokio::spawn(async move {
             SimpleHttpConfig {
-                address,
+                address: address.into(),
                 headers,
                 encoding: None,
                 query_parameters,
@@ -714,6 +711,50 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn http_unix_socket() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::UnixStream,
+        };
+
+        let socket_path = temp_dir().join("http.sock");
+        let (sender, recv) = SourceSender::new_test();
+        let source = SimpleHttpConfig {
+            address: HttpListenAddr::Unix(socket_path.clone()),
+            ..Default::default()
+        }
+        .build(SourceContext::new_test(sender, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+
+        let path = socket_path.clone();
+        wait_for(move || {
+            let path = path.clone();
+            async move { UnixStream::connect(path).await.is_ok() }
+        })
+        .await;
+
+        let mut stream = UnixStream::connect(&socket_path).await.unwrap();
+        stream
+            .write_all(
+                b"POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 9\r\nConnection: close\r\n\r\ntest body",
+            )
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+        let events = collect_n(recv, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+    }
+
     #[tokio::test]
     async fn http_bytes_codec_preserves_newlines() {
         let body = "foo\nbar";
@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     io::Cursor,
     sync::Arc,
     time::Duration,
@@ -13,12 +13,12 @@ use codecs::{
     StreamDecodingError,
 };
 use futures::{Stream, StreamExt};
-use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath, PathPrefix};
 use once_cell::sync::OnceCell;
 use rdkafka::{
     consumer::{CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
-    message::{BorrowedMessage, Headers as _, Message},
-    ClientConfig, ClientContext, Statistics,
+    message::{BorrowedMessage, Headers, Message},
+    ClientConfig, ClientContext, Offset, Statistics, TopicPartitionList,
 };
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
@@ -40,8 +40,8 @@ use crate::{
     },
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
-        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaBytesReceived, KafkaEventsReceived, KafkaMessageFiltered, KafkaOffsetUpdateError,
+        KafkaReadError, StreamClosedError,
     },
     kafka,
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
@@ -55,6 +55,17 @@ enum BuildError {
     KafkaCreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     KafkaSubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not assign Kafka partitions: {}", source))]
+    KafkaAssignError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not look up Kafka offsets for the start timestamp: {}", source))]
+    KafkaOffsetsForTimesError { source: rdkafka::error::KafkaError },
+    #[snafu(display("`{}` requires `partitions` to be set", option))]
+    RequiresStaticAssignment { option: &'static str },
+    #[snafu(display(
+        "Topic {:?} is a regular expression, which is not supported with `partitions`",
+        topic
+    ))]
+    TopicPatternWithStaticAssignment { topic: String },
 }
 
 /// Metrics configuration.
@@ -65,6 +76,55 @@ struct Metrics {
     pub topic_lag_metric: bool,
 }
 
+/// The position to start consuming the statically assigned partitions from.
+///
+/// This overrides any offset committed for the consumer group. Requires `partitions` to be set.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The kind of start position."))]
+enum StartPosition {
+    /// Start from the earliest available offset.
+    Beginning,
+
+    /// Start from the latest offset, only consuming new messages.
+    End,
+
+    /// Start from an explicit offset.
+    Offset {
+        /// The offset to start from, in every assigned partition.
+        offset: i64,
+    },
+
+    /// Start from the earliest message whose timestamp is at or after the given time.
+    Timestamp {
+        /// The time to start from.
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// The position to stop consuming the statically assigned partitions at.
+///
+/// Messages at or past this position are not consumed. Once every assigned partition reaches it,
+/// the source shuts down, which allows bounded backfills. Requires `partitions` to be set.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The kind of stop position."))]
+enum StopPosition {
+    /// Stop at an explicit offset.
+    Offset {
+        /// The offset to stop at, in every assigned partition.
+        offset: i64,
+    },
+
+    /// Stop at the first message whose timestamp is at or after the given time.
+    Timestamp {
+        /// The time to stop at.
+        timestamp: DateTime<Utc>,
+    },
+}
+
 /// Configuration for the `kafka` source.
 #[serde_as]
 #[configurable_component(source("kafka", "Collect logs from Apache Kafka."))]
@@ -95,6 +155,20 @@ pub struct KafkaSourceConfig {
     #[configurable(metadata(docs::examples = "consumer-group-name"))]
     group_id: String,
 
+    /// The partitions to consume from, bypassing the consumer group assignment.
+    ///
+    /// When set, the listed partitions of every topic in `topics` are assigned to this consumer
+    /// directly, instead of being balanced across the members of the consumer group. Offsets are
+    /// still committed for `group_id`. Regular expression topics are not supported in this mode.
+    #[configurable(metadata(docs::examples = 0, docs::examples = 1))]
+    partitions: Option<Vec<i32>>,
+
+    #[configurable(derived)]
+    start_position: Option<StartPosition>,
+
+    #[configurable(derived)]
+    stop_position: Option<StopPosition>,
+
     /// If offsets for consumer group do not exist, set them using this strategy.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for the `auto.offset.reset` option for further clarification.
@@ -174,6 +248,26 @@ pub struct KafkaSourceConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     headers_key: OptionalValuePath,
 
+    /// Filters messages based on the values of their headers, before they are decoded.
+    ///
+    /// Each entry maps a header name to the list of its accepted values. A message is only decoded
+    /// when it carries every listed header with one of its accepted values. Other messages are
+    /// skipped, and their offsets are committed as if they had been processed.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "example_header_filters()"))]
+    #[configurable(metadata(docs::additional_props_description = "The accepted header values."))]
+    header_filters: HashMap<String, Vec<String>>,
+
+    /// Maps the values of message headers into event metadata.
+    ///
+    /// Each entry maps a header name to the name of the metadata field the header value is
+    /// inserted into. Metadata fields are accessible in VRL through the `%` prefix, for example
+    /// `%tenant`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "example_headers_to_metadata()"))]
+    #[configurable(metadata(docs::additional_props_description = "A metadata field name."))]
+    headers_to_metadata: HashMap<String, String>,
+
     /// Advanced options set directly on the underlying `librdkafka` client.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for details.
@@ -270,6 +364,17 @@ const fn example_auto_offset_reset_values() -> [&'static str; 7] {
     ]
 }
 
+fn example_header_filters() -> HashMap<String, Vec<String>> {
+    HashMap::<_, _>::from_iter([(
+        "tenant".to_string(),
+        vec!["tenant-a".to_string(), "tenant-b".to_string()],
+    )])
+}
+
+fn example_headers_to_metadata() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([("x-tenant".to_string(), "tenant".to_string())])
+}
+
 fn example_librdkafka_options() -> HashMap<String, String> {
     HashMap::<_, _>::from_iter(
         [
@@ -356,6 +461,17 @@ impl SourceConfig for KafkaSourceConfig {
                 None,
             );
 
+        let schema_definition =
+            self.headers_to_metadata
+                .values()
+                .fold(schema_definition, |definition, field| {
+                    definition.with_metadata_field(
+                        &owned_value_path!(field.as_str()),
+                        Kind::bytes().or_undefined(),
+                        None,
+                    )
+                });
+
         vec![SourceOutput::new_logs(
             self.decoding.output_type(),
             schema_definition,
@@ -388,6 +504,7 @@ async fn kafka_source(
             .expect("Finalizer is only set once");
     }
 
+    let mut stop = StopTracker::new(&config);
     let mut stream = consumer.stream();
 
     loop {
@@ -413,7 +530,25 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
-                    parse_message(msg, decoder.clone(), config.keys(), &finalizer, &mut out, &consumer, log_namespace).await;
+                    let stopped = match &mut stop {
+                        Some(stop) if stop.reached(msg.offset(), msg.timestamp().to_millis()) => {
+                            Some(stop.finish(&consumer, msg.topic(), msg.partition()))
+                        }
+                        _ => None,
+                    };
+                    if let Some(done) = stopped {
+                        if done {
+                            break;
+                        }
+                    } else if headers_match(msg.headers(), &config.header_filters) {
+                        parse_message(msg, decoder.clone(), config.keys(), &finalizer, &mut out, &consumer, log_namespace).await;
+                    } else {
+                        emit!(KafkaMessageFiltered {
+                            topic: msg.topic(),
+                            partition: msg.partition(),
+                        });
+                        skip_message(msg, &finalizer, &consumer);
+                    }
                 }
             },
         }
@@ -429,6 +564,98 @@ async fn kafka_source(
     Ok(())
 }
 
+/// Tracks which statically assigned partitions have reached the configured stop position.
+struct StopTracker {
+    position: StopPosition,
+    remaining: HashSet<(String, i32)>,
+}
+
+impl StopTracker {
+    fn new(config: &KafkaSourceConfig) -> Option<Self> {
+        let position = config.stop_position.clone()?;
+        let partitions = config.partitions.as_ref()?;
+        let remaining = config
+            .topics
+            .iter()
+            .flat_map(|topic| {
+                partitions
+                    .iter()
+                    .map(move |partition| (topic.clone(), *partition))
+            })
+            .collect();
+
+        Some(Self {
+            position,
+            remaining,
+        })
+    }
+
+    /// Checks whether a message is at or past the stop position.
+    fn reached(&self, offset: i64, timestamp_millis: Option<i64>) -> bool {
+        match &self.position {
+            StopPosition::Offset { offset: stop } => offset >= *stop,
+            StopPosition::Timestamp { timestamp } => {
+                timestamp_millis.map_or(false, |millis| millis >= timestamp.timestamp_millis())
+            }
+        }
+    }
+
+    /// Marks a partition as done and stops fetching from it.
+    ///
+    /// Returns `true` once every assigned partition is done.
+    fn finish(
+        &mut self,
+        consumer: &StreamConsumer<CustomContext>,
+        topic: &str,
+        partition: i32,
+    ) -> bool {
+        if self.remaining.remove(&(topic.to_string(), partition)) {
+            let mut paused = TopicPartitionList::new();
+            paused.add_partition(topic, partition);
+            // Pausing only saves fetching messages which are skipped anyway, so a failure here
+            // isn't worth surfacing.
+            let _ = consumer.pause(&paused);
+        }
+        self.remaining.is_empty()
+    }
+}
+
+/// Checks that the message carries every header of `filters` with one of its accepted values.
+fn headers_match<H: Headers>(headers: Option<&H>, filters: &HashMap<String, Vec<String>>) -> bool {
+    filters.iter().all(|(name, accepted)| {
+        headers.map_or(false, |headers| {
+            headers.iter().any(|header| {
+                header.key == name.as_str()
+                    && header.value.map_or(false, |value| {
+                        accepted.iter().any(|accepted| accepted.as_bytes() == value)
+                    })
+            })
+        })
+    })
+}
+
+/// Marks a message which won't be decoded as processed, so its offset is committed in order with
+/// the surrounding messages.
+fn skip_message(
+    msg: BorrowedMessage<'_>,
+    finalizer: &Option<Arc<OrderedFinalizer<FinalizerEntry>>>,
+    consumer: &Arc<StreamConsumer<CustomContext>>,
+) {
+    match finalizer {
+        Some(finalizer) => {
+            // The batch has no events, so the receiver resolves as delivered as soon as the
+            // notifier is dropped.
+            let (_, receiver) = BatchNotifier::new_with_receiver();
+            finalizer.add(msg.into(), receiver);
+        }
+        None => {
+            if let Err(error) = consumer.store_offset(msg.topic(), msg.partition(), msg.offset()) {
+                emit!(KafkaOffsetUpdateError { error });
+            }
+        }
+    }
+}
+
 async fn parse_message(
     msg: BorrowedMessage<'_>,
     decoder: Decoder,
@@ -523,6 +750,7 @@ struct Keys<'a> {
     partition: &'a Option<OwnedValuePath>,
     offset: &'a Option<OwnedValuePath>,
     headers: &'a Option<OwnedValuePath>,
+    headers_to_metadata: &'a HashMap<String, String>,
 }
 
 impl<'a> Keys<'a> {
@@ -534,6 +762,7 @@ impl<'a> Keys<'a> {
             partition: &config.partition_key.path,
             offset: &config.offset_key.path,
             headers: &config.headers_key.path,
+            headers_to_metadata: &config.headers_to_metadata,
         }
     }
 }
@@ -648,6 +877,12 @@ impl ReceivedMessage {
                 path!("headers"),
                 self.headers.clone(),
             );
+
+            for (header, field) in keys.headers_to_metadata {
+                if let Some(value) = self.headers.get(header) {
+                    log.insert((PathPrefix::Metadata, path!(field.as_str())), value.clone());
+                }
+            }
         }
     }
 }
@@ -710,8 +945,58 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<C
             config.metrics.topic_lag_metric,
         ))
         .context(KafkaCreateSnafu)?;
-    let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
-    consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+
+    match &config.partitions {
+        Some(partitions) => {
+            let offset = match &config.start_position {
+                None => Offset::Stored,
+                Some(StartPosition::Beginning) => Offset::Beginning,
+                Some(StartPosition::End) => Offset::End,
+                Some(StartPosition::Offset { offset }) => Offset::Offset(*offset),
+                // Resolved into actual offsets through `offsets_for_times` below.
+                Some(StartPosition::Timestamp { timestamp }) => {
+                    Offset::Offset(timestamp.timestamp_millis())
+                }
+            };
+
+            let mut assignment = TopicPartitionList::new();
+            for topic in &config.topics {
+                if topic.starts_with('^') {
+                    return Err(Box::new(BuildError::TopicPatternWithStaticAssignment {
+                        topic: topic.clone(),
+                    }));
+                }
+                for partition in partitions {
+                    assignment
+                        .add_partition_offset(topic, *partition, offset)
+                        .context(KafkaAssignSnafu)?;
+                }
+            }
+
+            if let Some(StartPosition::Timestamp { .. }) = &config.start_position {
+                assignment = consumer
+                    .offsets_for_times(assignment, config.socket_timeout_ms)
+                    .context(KafkaOffsetsForTimesSnafu)?;
+            }
+
+            consumer.assign(&assignment).context(KafkaAssignSnafu)?;
+        }
+        None => {
+            if config.start_position.is_some() {
+                return Err(Box::new(BuildError::RequiresStaticAssignment {
+                    option: "start_position",
+                }));
+            }
+            if config.stop_position.is_some() {
+                return Err(Box::new(BuildError::RequiresStaticAssignment {
+                    option: "stop_position",
+                }));
+            }
+
+            let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
+            consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+        }
+    }
 
     Ok(consumer)
 }
@@ -875,12 +1160,149 @@ mod test {
         )
     }
 
+    #[test]
+    fn headers_match_filters() {
+        use rdkafka::message::{Header, OwnedHeaders};
+
+        let headers = OwnedHeaders::new()
+            .insert(Header {
+                key: "tenant",
+                value: Some("a"),
+            })
+            .insert(Header {
+                key: "bare",
+                value: None::<&str>,
+            });
+        let filters = |filters: &[(&str, &[&str])]| -> HashMap<String, Vec<String>> {
+            filters
+                .iter()
+                .map(|(name, values)| {
+                    (
+                        name.to_string(),
+                        values.iter().map(|value| value.to_string()).collect(),
+                    )
+                })
+                .collect()
+        };
+
+        assert!(headers_match(Some(&headers), &filters(&[])));
+        assert!(headers_match(None::<&OwnedHeaders>, &filters(&[])));
+        assert!(headers_match(
+            Some(&headers),
+            &filters(&[("tenant", &["a", "b"])])
+        ));
+        assert!(!headers_match(
+            Some(&headers),
+            &filters(&[("tenant", &["b"])])
+        ));
+        assert!(!headers_match(Some(&headers), &filters(&[("bare", &[""])])));
+        assert!(!headers_match(
+            Some(&headers),
+            &filters(&[("tenant", &["a"]), ("missing", &["a"])])
+        ));
+        assert!(!headers_match(
+            None::<&OwnedHeaders>,
+            &filters(&[("tenant", &["a"])])
+        ));
+    }
+
+    #[test]
+    fn headers_to_metadata_schema_definition() {
+        let config = KafkaSourceConfig {
+            headers_to_metadata: HashMap::from([("x-tenant".to_string(), "tenant".to_string())]),
+            ..make_config("topic", "group", LogNamespace::Vector)
+        };
+        let definition = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true)
+            .unwrap();
+
+        assert_eq!(
+            definition
+                .metadata_kind()
+                .at_path(&owned_value_path!("tenant")),
+            Kind::bytes().or_undefined()
+        );
+    }
+
     #[tokio::test]
     async fn consumer_create_ok() {
         let config = make_config("topic", "group", LogNamespace::Legacy);
         assert!(create_consumer(&config).is_ok());
     }
 
+    #[tokio::test]
+    async fn consumer_create_static_assignment() {
+        let config = KafkaSourceConfig {
+            partitions: Some(vec![0, 1]),
+            start_position: Some(StartPosition::Offset { offset: 10 }),
+            stop_position: Some(StopPosition::Offset { offset: 20 }),
+            ..make_config("topic", "group", LogNamespace::Legacy)
+        };
+        let consumer = create_consumer(&config).unwrap();
+        let assignment = consumer.assignment().unwrap();
+        assert_eq!(assignment.count(), 2);
+        assert_eq!(
+            assignment.find_partition("topic", 1).unwrap().offset(),
+            Offset::Offset(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn consumer_create_seek_requires_static_assignment() {
+        let config = KafkaSourceConfig {
+            start_position: Some(StartPosition::Beginning),
+            ..make_config("topic", "group", LogNamespace::Legacy)
+        };
+        assert!(create_consumer(&config).is_err());
+
+        let config = KafkaSourceConfig {
+            stop_position: Some(StopPosition::Offset { offset: 1 }),
+            ..make_config("topic", "group", LogNamespace::Legacy)
+        };
+        assert!(create_consumer(&config).is_err());
+
+        let config = KafkaSourceConfig {
+            partitions: Some(vec![0]),
+            ..make_config("^topic-.*", "group", LogNamespace::Legacy)
+        };
+        assert!(create_consumer(&config).is_err());
+    }
+
+    #[test]
+    fn stop_tracker_offset() {
+        let config = KafkaSourceConfig {
+            partitions: Some(vec![0, 1]),
+            stop_position: Some(StopPosition::Offset { offset: 5 }),
+            ..make_config("topic", "group", LogNamespace::Legacy)
+        };
+        let tracker = StopTracker::new(&config).unwrap();
+        assert!(!tracker.reached(4, None));
+        assert!(tracker.reached(5, None));
+        assert_eq!(tracker.remaining.len(), 2);
+    }
+
+    #[test]
+    fn stop_tracker_timestamp() {
+        let timestamp = Utc.timestamp_millis_opt(1_000).unwrap();
+        let config = KafkaSourceConfig {
+            partitions: Some(vec![0]),
+            stop_position: Some(StopPosition::Timestamp { timestamp }),
+            ..make_config("topic", "group", LogNamespace::Legacy)
+        };
+        let tracker = StopTracker::new(&config).unwrap();
+        assert!(!tracker.reached(100, None));
+        assert!(!tracker.reached(100, Some(999)));
+        assert!(tracker.reached(0, Some(1_000)));
+
+        let config = KafkaSourceConfig {
+            partitions: None,
+            ..config
+        };
+        assert!(StopTracker::new(&config).is_none());
+    }
+
     #[tokio::test]
     async fn consumer_create_incorrect_auto_offset_reset() {
         let config = KafkaSourceConfig {
@@ -12,7 +12,7 @@ use chrono::Utc;
 use codecs::{BytesDeserializer, BytesDeserializerConfig};
 use file_source::{
     calculate_ignore_before, Checkpointer, FileServer, FileServerShutdown, FingerprintStrategy,
-    Fingerprinter, Line, ReadFrom, ReadFromConfig,
+    Fingerprinter, Line, ReadBackend, ReadFrom, ReadFromConfig,
 };
 use futures::{future::FutureExt, stream::StreamExt};
 use futures_util::Stream;
@@ -749,6 +749,8 @@ impl Source {
             max_line_bytes,
             // Delimiter bytes that is used to read the file line-by-line
             line_delimiter: Bytes::from("\n"),
+            // Read the files with the standard system calls.
+            read_backend: ReadBackend::Std,
             // The directory where to keep the checkpoints.
             data_dir,
             // This value specifies not exactly the globbing, but interval
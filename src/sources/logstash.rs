@@ -31,7 +31,7 @@ use crate::{
     event::{Event, LogEvent, Value},
     serde::bool_or_struct,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{CertificateMetadata, MaybeTlsSettings, TlsSourceConfig},
     types,
 };
 
@@ -106,12 +106,25 @@ impl LogstashConfig {
                 Self::NAME,
                 tls_client_metadata_path,
                 &owned_value_path!("tls_client_metadata"),
-                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                tls_client_metadata_kind().or_undefined(),
                 None,
             )
     }
 }
 
+/// The `tls_client_metadata` inserted for clients presenting a certificate, identifying the beat
+/// that sent the events.
+fn tls_client_metadata_kind() -> Kind {
+    Kind::object(BTreeMap::from([
+        ("subject".into(), Kind::bytes()),
+        ("common_name".into(), Kind::bytes().or_undefined()),
+        (
+            "subject_alt_names".into(),
+            Kind::array(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+        ),
+    ]))
+}
+
 impl Default for LogstashConfig {
     fn default() -> Self {
         Self {
@@ -202,6 +215,28 @@ impl TcpSource for LogstashSource {
         LogstashDecoder::new()
     }
 
+    fn build_tls_client_metadata(
+        &self,
+        certificate: &CertificateMetadata,
+    ) -> BTreeMap<String, Value> {
+        let mut metadata = BTreeMap::from([("subject".to_string(), certificate.subject().into())]);
+        if let Some(common_name) = &certificate.common_name {
+            metadata.insert("common_name".to_string(), common_name.as_str().into());
+        }
+        if !certificate.subject_alt_names.is_empty() {
+            metadata.insert(
+                "subject_alt_names".to_string(),
+                certificate
+                    .subject_alt_names
+                    .iter()
+                    .map(|name| Value::from(name.as_str()))
+                    .collect::<Vec<_>>()
+                    .into(),
+            );
+        }
+        metadata
+    }
+
     fn handle_events(&self, events: &mut [Event], host: SocketAddr) {
         let now = chrono::Utc::now();
         for event in events {
@@ -260,16 +295,19 @@ impl TcpSource for LogstashSource {
 
 struct LogstashAcker {
     sequence_number: u32,
+    window_size: Option<u32>,
     protocol_version: Option<LogstashProtocolVersion>,
 }
 
 impl LogstashAcker {
     fn new(frames: &[LogstashEventFrame]) -> Self {
         let mut sequence_number = 0;
+        let mut window_size = None;
         let mut protocol_version = None;
 
         for frame in frames {
             sequence_number = std::cmp::max(sequence_number, frame.sequence_number);
+            window_size = frame.window_size;
             // We assume that it's valid to ack via any of the protocol versions that we've seen in
             // a set of frames from a single stream, so here we just take the last. In reality, we
             // do not expect stream with multiple protocol versions to occur.
@@ -278,25 +316,47 @@ impl LogstashAcker {
 
         Self {
             sequence_number,
+            window_size,
             protocol_version,
         }
     }
+
+    /// Whether the frames complete the window announced by the writer, which waits for the ack of
+    /// its last frame before sending the next window. Acks being cumulative, the frames of a window
+    /// received in earlier batches are acked along with its last one.
+    fn completes_window(&self) -> bool {
+        self.window_size
+            .map_or(true, |window_size| self.sequence_number >= window_size)
+    }
+}
+
+fn encode_ack(protocol_version: LogstashProtocolVersion, sequence_number: u32) -> Bytes {
+    let mut bytes: Vec<u8> = Vec::with_capacity(6);
+    bytes.push(protocol_version.into());
+    bytes.push(LogstashFrameType::Ack.into());
+    bytes.extend(sequence_number.to_be_bytes().iter());
+    Bytes::from(bytes)
 }
 
 impl TcpSourceAcker for LogstashAcker {
     // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#ack-frame-type
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
         match (ack, self.protocol_version) {
-            (TcpSourceAck::Ack, Some(protocol_version)) => {
-                let mut bytes: Vec<u8> = Vec::with_capacity(6);
-                bytes.push(protocol_version.into());
-                bytes.push(LogstashFrameType::Ack.into());
-                bytes.extend(self.sequence_number.to_be_bytes().iter());
-                Some(Bytes::from(bytes))
+            (TcpSourceAck::Ack, Some(protocol_version)) if self.completes_window() => {
+                Some(encode_ack(protocol_version, self.sequence_number))
             }
             _ => None,
         }
     }
+
+    // Beats writers treat a version 2 ack of sequence number 0 as a keepalive, extending their
+    // timeout while the window is held back by backpressure, as done by Logstash.
+    fn build_keepalive(&self) -> Option<Bytes> {
+        match self.protocol_version {
+            Some(LogstashProtocolVersion::V2) => Some(encode_ack(LogstashProtocolVersion::V2, 0)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -310,12 +370,18 @@ enum LogstashDecoderReadState {
 #[derive(Debug)]
 struct LogstashDecoder {
     state: LogstashDecoderReadState,
+    window_size: Option<u32>,
 }
 
 impl LogstashDecoder {
     const fn new() -> Self {
+        Self::with_window_size(None)
+    }
+
+    const fn with_window_size(window_size: Option<u32>) -> Self {
         Self {
             state: LogstashDecoderReadState::ReadProtocol,
+            window_size,
         }
     }
 }
@@ -434,6 +500,7 @@ impl TryFrom<u8> for LogstashFrameType {
 struct LogstashEventFrame {
     protocol: LogstashProtocolVersion,
     sequence_number: u32,
+    window_size: Option<u32>,
     fields: BTreeMap<String, serde_json::Value>,
 }
 
@@ -487,8 +554,8 @@ impl Decoder for LogstashDecoder {
                     }
                 }
                 // The window size indicates how many events the writer will send before waiting
-                // for acks. As we forward events as we get them, and ack as they are received, we
-                // do not need to keep track of this.
+                // for acks. It is recorded on the frames that follow, so that the window is only
+                // acked once all of its events have been delivered.
                 //
                 // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#window-size-frame-type
                 LogstashDecoderReadState::ReadFrame(_protocol, LogstashFrameType::WindowSize) => {
@@ -496,7 +563,7 @@ impl Decoder for LogstashDecoder {
                         return Ok(None);
                     }
 
-                    let _window_size = src.get_u32();
+                    self.window_size = Some(src.get_u32());
 
                     LogstashDecoderReadState::ReadProtocol
                 }
@@ -560,6 +627,7 @@ impl Decoder for LogstashDecoder {
                         LogstashEventFrame {
                             protocol,
                             sequence_number,
+                            window_size: self.window_size,
                             fields,
                         },
                         byte_size,
@@ -599,6 +667,7 @@ impl Decoder for LogstashDecoder {
                                 LogstashEventFrame {
                                     protocol,
                                     sequence_number,
+                                    window_size: self.window_size,
                                     fields,
                                 },
                                 byte_size,
@@ -643,13 +712,14 @@ impl Decoder for LogstashDecoder {
                         res
                     }?;
 
-                    let mut decoder = LogstashDecoder::new();
+                    let mut decoder = LogstashDecoder::with_window_size(self.window_size);
 
                     let mut frames = VecDeque::new();
 
                     while let Some(s) = decoder.decode(&mut buf)? {
                         frames.push_back(s);
                     }
+                    self.window_size = decoder.window_size;
 
                     LogstashDecoderReadState::PendingFrames(frames)
                 }
@@ -784,6 +854,83 @@ mod test {
         assert_eq!(output.len(), 0);
     }
 
+    fn encode_window_size(window_size: u32) -> Bytes {
+        let mut req = BytesMut::new();
+        req.put_u8(b'2');
+        req.put_u8(b'W');
+        req.put_u32(window_size);
+        req.into()
+    }
+
+    fn decode_frames(req: &[u8]) -> Vec<LogstashEventFrame> {
+        let mut decoder = LogstashDecoder::new();
+        let mut buf = BytesMut::from(req);
+        let mut frames = Vec::new();
+        while let Some((frame, _byte_size)) = decoder.decode(&mut buf).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn acks_complete_windows() {
+        let mut req = BytesMut::new();
+        req.extend_from_slice(&encode_window_size(3));
+        for seq in 1..=3 {
+            req.extend_from_slice(&encode_req(seq, &[("message", "Hello, world!")]));
+        }
+        let frames = decode_frames(&req);
+        assert_eq!(frames.len(), 3);
+        assert!(frames.iter().all(|frame| frame.window_size == Some(3)));
+
+        // The first frames of the window are acked along with the last one.
+        let partial = LogstashAcker::new(&frames[..2]);
+        assert!(partial.build_keepalive().is_some());
+        assert_eq!(partial.build_ack(TcpSourceAck::Ack), None);
+
+        let complete = LogstashAcker::new(&frames[2..]);
+        assert_eq!(
+            complete.build_ack(TcpSourceAck::Ack),
+            Some(Bytes::from_static(&[b'2', b'A', 0, 0, 0, 3]))
+        );
+    }
+
+    #[test]
+    fn keepalive_is_an_ack_of_sequence_zero() {
+        let frames = decode_frames(&encode_req(1, &[("message", "Hello, world!")]));
+        assert_eq!(
+            LogstashAcker::new(&frames).build_keepalive(),
+            Some(Bytes::from_static(&[b'2', b'A', 0, 0, 0, 0]))
+        );
+    }
+
+    #[test]
+    fn tls_client_metadata_identifies_beat() {
+        let source = LogstashSource {
+            timestamp_converter: types::Conversion::Timestamp(vector_common::TimeZone::Local),
+            log_namespace: LogNamespace::Legacy,
+            legacy_host_key_path: None,
+        };
+        let certificate = CertificateMetadata {
+            country_name: Some("US".to_string()),
+            state_or_province_name: None,
+            locality_name: None,
+            organization_name: Some("Vector".to_string()),
+            organizational_unit_name: None,
+            common_name: Some("filebeat-1".to_string()),
+            subject_alt_names: vec!["filebeat-1.example.com".to_string(), "10.0.0.1".to_string()],
+        };
+
+        assert_eq!(
+            Value::from(source.build_tls_client_metadata(&certificate)),
+            vrl::value!({
+                "subject": "CN=filebeat-1,O=Vector,C=US",
+                "common_name": "filebeat-1",
+                "subject_alt_names": ["filebeat-1.example.com", "10.0.0.1"]
+            })
+        );
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = LogstashConfig {
@@ -821,7 +968,7 @@ mod test {
                 )
                 .with_metadata_field(
                     &owned_value_path!(LogstashConfig::NAME, "tls_client_metadata"),
-                    Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                    tls_client_metadata_kind().or_undefined(),
                     None,
                 );
 
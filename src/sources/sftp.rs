@@ -0,0 +1,795 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::Utc;
+use codecs::{
+    decoding::{DeserializerConfig, FramingConfig},
+    NewlineDelimitedDecoderConfig,
+};
+use futures::StreamExt;
+use glob::{MatchOptions, Pattern};
+use lookup::{owned_value_path, path};
+use serde_with::serde_as;
+use tokio::time::interval;
+use tokio_stream::wrappers::IntervalStream;
+use tokio_util::codec::FramedRead;
+use value::Kind;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    common::sftp::{
+        spawn_blocking, DirEntry, Filesystem, KnownHostsStrategy, SftpConnectionConfig,
+    },
+    config::{
+        GenerateConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        SftpAfterReadError, SftpCheckpointWriteError, SftpListError, SftpReadError,
+        StreamClosedError,
+    },
+    serde::{bool_or_struct, default_decoding},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const CHECKPOINTS_FILENAME: &str = "checkpoints.json";
+
+/// Configuration for the `sftp` source.
+#[serde_as]
+#[configurable_component(source("sftp", "Collect logs from the files of an SFTP server."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SftpSourceConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    connection: SftpConnectionConfig,
+
+    /// The directory the `include` and `exclude` patterns are relative to.
+    ///
+    /// Relative paths are resolved from the home directory of the user.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/upload"))]
+    root: String,
+
+    /// Glob patterns of the files to read.
+    ///
+    /// Patterns are matched against the paths of the files relative to `root`. `*` does not match
+    /// `/`, while `**` matches any number of directories.
+    #[configurable(metadata(docs::examples = "*.log"))]
+    #[configurable(metadata(docs::examples = "incoming/**/*.json"))]
+    include: Vec<String>,
+
+    /// Glob patterns of the files not to read.
+    ///
+    /// Takes precedence over `include`. Useful to skip the files being uploaded under a temporary
+    /// name.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "**/*.part"))]
+    exclude: Vec<String>,
+
+    /// The interval between polls of the server, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    poll_interval_secs: Duration,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    after_read: AfterReadAction,
+
+    /// The directory used to persist the checkpoints of the files read.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// What is done with the files once they are read.
+///
+/// When acknowledgements are enabled, files are only deleted or moved once their events are
+/// acknowledged.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The action taken on the files once read."))]
+pub enum AfterReadAction {
+    /// Leave the files in place.
+    ///
+    /// The data appended to the files is read on the next polls.
+    #[default]
+    Keep,
+
+    /// Delete the files.
+    Delete,
+
+    /// Move the files to another directory.
+    Move {
+        /// The directory the files are moved to, relative to `root`.
+        ///
+        /// The path of the files relative to `root` is kept within this directory. It must not be
+        /// matched by the `include` patterns.
+        #[configurable(metadata(docs::examples = "processed/"))]
+        directory: String,
+    },
+}
+
+impl AfterReadAction {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Delete => "delete",
+            Self::Move { .. } => "move",
+        }
+    }
+}
+
+const fn default_poll_interval_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_framing() -> FramingConfig {
+    NewlineDelimitedDecoderConfig::new().into()
+}
+
+impl GenerateConfig for SftpSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection: SftpConnectionConfig {
+                endpoint: "127.0.0.1:22".to_owned(),
+                user: "vector".to_owned(),
+                key: None,
+                known_hosts_strategy: KnownHostsStrategy::default(),
+            },
+            root: "/upload".to_owned(),
+            include: vec!["*.log".to_owned()],
+            exclude: Vec::new(),
+            poll_interval_secs: default_poll_interval_secs(),
+            after_read: AfterReadAction::default(),
+            data_dir: None,
+            framing: default_framing(),
+            decoding: default_decoding(),
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sftp")]
+impl SourceConfig for SftpSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpoints = Checkpoints::load(data_dir.join(CHECKPOINTS_FILENAME)).await?;
+        let matcher = FileMatcher::new(&self.include, &self.exclude)?;
+        let files = self.connection.build_filesystem(&self.root);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+
+        let source = SftpSource {
+            files,
+            matcher,
+            after_read: self.after_read.clone(),
+            checkpoints,
+            decoder,
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace,
+            out: cx.out,
+            bytes_received: register!(BytesReceived::from(Protocol("sftp".into()))),
+            events_received: register!(EventsReceived),
+        };
+        Ok(Box::pin(source.run(self.poll_interval_secs, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("path"))),
+                &owned_value_path!("path"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Matches the paths of the files, relative to the root, against the `include` and `exclude`
+/// patterns.
+struct FileMatcher {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl FileMatcher {
+    const OPTIONS: MatchOptions = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    fn new(include: &[String], exclude: &[String]) -> crate::Result<Self> {
+        if include.is_empty() {
+            return Err("At least one `include` pattern must be set.".into());
+        }
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| Pattern::new(pattern.trim_start_matches('/')))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        self.include
+            .iter()
+            .any(|pattern| pattern.matches_with(path, Self::OPTIONS))
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| pattern.matches_with(path, Self::OPTIONS))
+    }
+
+    /// The directories to list, with the depth up to which they are listed, so that the whole
+    /// tree is only walked for the patterns containing `**`.
+    fn directories(&self) -> BTreeMap<String, Option<usize>> {
+        let mut directories = BTreeMap::<String, Option<usize>>::new();
+        for pattern in &self.include {
+            let components = pattern.as_str().split('/').collect::<Vec<_>>();
+            let literal = components
+                .iter()
+                .take(components.len() - 1)
+                .take_while(|component| Pattern::escape(component) == **component)
+                .count();
+            let directory = components[..literal]
+                .iter()
+                .map(|component| format!("{}/", component))
+                .collect::<String>();
+            let depth = (!pattern.as_str().contains("**")).then(|| components.len() - literal);
+
+            directories
+                .entry(directory)
+                .and_modify(|max_depth| {
+                    *max_depth = max_depth
+                        .zip(depth)
+                        .map(|(max_depth, depth)| max_depth.max(depth))
+                })
+                .or_insert(depth);
+        }
+        directories
+    }
+}
+
+/// The offsets up to which the files were read, persisted in the data directory.
+struct Checkpoints {
+    path: PathBuf,
+    offsets: HashMap<String, u64>,
+}
+
+impl Checkpoints {
+    async fn load(path: PathBuf) -> crate::Result<Self> {
+        let offsets = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error.into()),
+        };
+        Ok(Self { path, offsets })
+    }
+
+    fn get(&self, path: &str) -> u64 {
+        self.offsets.get(path).copied().unwrap_or_default()
+    }
+
+    async fn set(&mut self, path: &str, offset: u64) {
+        self.offsets.insert(path.to_owned(), offset);
+        self.write().await;
+    }
+
+    async fn remove(&mut self, path: &str) {
+        if self.offsets.remove(path).is_some() {
+            self.write().await;
+        }
+    }
+
+    /// Forgets the files which are not found anymore.
+    async fn retain(&mut self, files: &BTreeMap<String, u64>) {
+        let count = self.offsets.len();
+        self.offsets.retain(|path, _| files.contains_key(path));
+        if self.offsets.len() != count {
+            self.write().await;
+        }
+    }
+
+    async fn write(&self) {
+        // The checkpoints are replaced atomically, so that they are not lost if Vector stops
+        // while writing them.
+        let temp_path = self.path.with_extension("json.tmp");
+        let contents = serde_json::to_vec(&self.offsets).expect("Serializing to JSON cannot fail.");
+        let result = match tokio::fs::write(&temp_path, contents).await {
+            Ok(()) => tokio::fs::rename(&temp_path, &self.path).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            emit!(SftpCheckpointWriteError {
+                error,
+                path: &self.path.to_string_lossy(),
+            });
+        }
+    }
+}
+
+struct SftpSource {
+    files: Arc<dyn Filesystem>,
+    matcher: FileMatcher,
+    after_read: AfterReadAction,
+    checkpoints: Checkpoints,
+    decoder: Decoder,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl SftpSource {
+    async fn run(mut self, poll_interval: Duration, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut ticks = IntervalStream::new(interval(poll_interval)).take_until(shutdown);
+        while ticks.next().await.is_some() {
+            self.poll().await?;
+        }
+        Ok(())
+    }
+
+    async fn poll(&mut self) -> Result<(), ()> {
+        let files = match self.list_files().await {
+            Some(files) => files,
+            // The checkpoints of files which could not be listed must not be forgotten.
+            None => return Ok(()),
+        };
+        self.checkpoints.retain(&files).await;
+
+        for (path, size) in files {
+            let mut offset = self.checkpoints.get(&path);
+            if offset > size {
+                debug!(message = "File was truncated, reading it from the start.", %path);
+                offset = 0;
+            }
+
+            if offset < size {
+                match self.read_file(&path, offset, size).await? {
+                    Some(BatchStatus::Delivered) => self.checkpoints.set(&path, size).await,
+                    // The file is read again on the next poll.
+                    Some(_) | None => continue,
+                }
+            }
+
+            self.handle_read_file(&path).await;
+        }
+        Ok(())
+    }
+
+    /// Lists the files matching the patterns, with their size.
+    async fn list_files(&self) -> Option<BTreeMap<String, u64>> {
+        let mut files = BTreeMap::new();
+        for (directory, max_depth) in self.matcher.directories() {
+            let mut directories = vec![(directory, 0)];
+            while let Some((directory, depth)) = directories.pop() {
+                let list_directory = directory.clone();
+                let entries =
+                    match spawn_blocking(&self.files, move |files| files.list(&list_directory))
+                        .await
+                    {
+                        Ok(entries) => entries,
+                        Err(error) => {
+                            emit!(SftpListError {
+                                error,
+                                directory: &directory,
+                            });
+                            return None;
+                        }
+                    };
+
+                for entry in entries {
+                    match entry {
+                        DirEntry::Directory(path) => {
+                            if max_depth.map_or(true, |max_depth| depth + 1 < max_depth) {
+                                directories.push((path, depth + 1));
+                            }
+                        }
+                        DirEntry::File(path, size) if self.matcher.matches(&path) => {
+                            files.insert(path, size);
+                        }
+                        DirEntry::File(..) => {}
+                    }
+                }
+            }
+        }
+        Some(files)
+    }
+
+    /// Reads the file from `offset` to `size`, and sends its events.
+    ///
+    /// Returns the status of the events once they are acknowledged, or `None` if the file could
+    /// not be read.
+    async fn read_file(
+        &mut self,
+        path: &str,
+        offset: u64,
+        size: u64,
+    ) -> Result<Option<BatchStatus>, ()> {
+        let read_path = path.to_owned();
+        let read = move |files: &dyn Filesystem| files.read_range(&read_path, offset..size);
+        let data = match spawn_blocking(&self.files, read).await {
+            Ok(data) => data,
+            Err(error) => {
+                emit!(SftpReadError { error, path });
+                return Ok(None);
+            }
+        };
+        self.bytes_received.emit(ByteSize(data.len()));
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let mut stream = FramedRead::new(data.as_slice(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((mut events, _byte_size)) => {
+                    let count = events.len();
+                    self.events_received.emit(CountByteSize(
+                        count,
+                        events.estimated_json_encoded_size_of(),
+                    ));
+
+                    let now = Utc::now();
+                    for event in &mut events {
+                        if let Event::Log(log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                SftpSourceConfig::NAME,
+                                now,
+                            );
+                            self.log_namespace.insert_source_metadata(
+                                SftpSourceConfig::NAME,
+                                log,
+                                Some(LegacyKey::InsertIfEmpty(path!("path"))),
+                                path!("path"),
+                                path,
+                            );
+                        }
+                    }
+                    let events = events
+                        .into_iter()
+                        .map(|event| event.with_batch_notifier_option(&batch));
+
+                    self.out.send_batch(events).await.map_err(|error| {
+                        emit!(StreamClosedError { error, count });
+                    })?;
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        drop(batch);
+
+        Ok(Some(match receiver {
+            Some(receiver) => receiver.await,
+            None => BatchStatus::Delivered,
+        }))
+    }
+
+    /// Deletes or moves the file, once it has been read up to its end.
+    async fn handle_read_file(&mut self, path: &str) {
+        let source = path.to_owned();
+        let result = match &self.after_read {
+            AfterReadAction::Keep => return,
+            AfterReadAction::Delete => {
+                spawn_blocking(&self.files, move |files| files.delete(&source)).await
+            }
+            AfterReadAction::Move { directory } => {
+                let destination = format!("{}/{}", directory.trim_end_matches('/'), path);
+                spawn_blocking(&self.files, move |files| {
+                    files.rename(&source, &destination)
+                })
+                .await
+            }
+        };
+
+        match result {
+            Ok(()) => self.checkpoints.remove(path).await,
+            Err(error) => emit!(SftpAfterReadError {
+                error,
+                path,
+                action: self.after_read.as_str(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, ops::Range, path::Path};
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{
+            components::{assert_source_compliance, SOURCE_TAGS},
+            temp_dir,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SftpSourceConfig>();
+    }
+
+    fn matcher(include: &[&str], exclude: &[&str]) -> FileMatcher {
+        let patterns = |patterns: &[&str]| {
+            patterns
+                .iter()
+                .map(|&pattern| pattern.to_owned())
+                .collect::<Vec<_>>()
+        };
+        FileMatcher::new(&patterns(include), &patterns(exclude)).unwrap()
+    }
+
+    #[test]
+    fn matches_files() {
+        let matcher = matcher(&["*.log", "incoming/**/*.json"], &["**/*.part.json"]);
+        assert!(matcher.matches("app.log"));
+        assert!(!matcher.matches("archive/app.log"));
+        assert!(matcher.matches("incoming/events.json"));
+        assert!(matcher.matches("incoming/2023/05/events.json"));
+        assert!(!matcher.matches("incoming/2023/05/events.part.json"));
+
+        assert_eq!(
+            matcher.directories(),
+            BTreeMap::from([("".to_owned(), Some(1)), ("incoming/".to_owned(), None)])
+        );
+        assert_eq!(
+            self::matcher(&["logs/*/app.log", "logs/app.log"], &[]).directories(),
+            BTreeMap::from([("logs/".to_owned(), Some(2))])
+        );
+    }
+
+    /// The files of a local directory, standing in for those of an SFTP server.
+    struct LocalFilesystem(PathBuf);
+
+    impl Filesystem for LocalFilesystem {
+        fn list(&self, directory: &str) -> io::Result<Vec<DirEntry>> {
+            fs::read_dir(self.0.join(directory))?
+                .map(|entry| {
+                    let entry = entry?;
+                    let path = format!("{}{}", directory, entry.file_name().to_string_lossy());
+                    let metadata = entry.metadata()?;
+                    Ok(if metadata.is_dir() {
+                        DirEntry::Directory(path + "/")
+                    } else {
+                        DirEntry::File(path, metadata.len())
+                    })
+                })
+                .collect()
+        }
+
+        fn read_range(&self, path: &str, range: Range<u64>) -> io::Result<Vec<u8>> {
+            let data = fs::read(self.0.join(path))?;
+            Ok(data[range.start as usize..range.end as usize].to_vec())
+        }
+
+        fn write(&self, path: &str, data: &[u8]) -> io::Result<()> {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, data)
+        }
+
+        fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+            let to = self.0.join(to);
+            fs::create_dir_all(to.parent().unwrap())?;
+            fs::rename(self.0.join(from), to)
+        }
+
+        fn delete(&self, path: &str) -> io::Result<()> {
+            fs::remove_file(self.0.join(path))
+        }
+
+        fn check(&self) -> io::Result<()> {
+            fs::metadata(&self.0).map(drop)
+        }
+    }
+
+    async fn run_poll(
+        root: &Path,
+        data_dir: &Path,
+        after_read: AfterReadAction,
+        status: EventStatus,
+    ) -> Vec<Event> {
+        let (tx, rx) = SourceSender::new_test_finalize(status);
+        let mut source = SftpSource {
+            files: Arc::new(LocalFilesystem(root.to_owned())),
+            matcher: matcher(&["*.log", "app/*.log"], &[]),
+            after_read,
+            checkpoints: Checkpoints::load(data_dir.join(CHECKPOINTS_FILENAME))
+                .await
+                .unwrap(),
+            decoder: DecodingConfig::new(
+                default_framing(),
+                default_decoding(),
+                LogNamespace::Legacy,
+            )
+            .build(),
+            acknowledgements: true,
+            log_namespace: LogNamespace::Legacy,
+            out: tx,
+            bytes_received: register!(BytesReceived::from(Protocol("sftp".into()))),
+            events_received: register!(EventsReceived),
+        };
+        source.poll().await.unwrap();
+        drop(source);
+
+        rx.collect().await
+    }
+
+    fn messages(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn reads_appended_data_after_checkpoint() {
+        let root = temp_dir();
+        let data_dir = temp_dir();
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::write(root.join("app/first.log"), "one\ntwo\n").unwrap();
+        fs::write(root.join("ignored.txt"), "ignored\n").unwrap();
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            run_poll(
+                &root,
+                &data_dir,
+                AfterReadAction::Keep,
+                EventStatus::Delivered,
+            )
+            .await
+        })
+        .await;
+        assert_eq!(messages(&events), vec!["one", "two"]);
+        assert_eq!(events[0].as_log()["path"], "app/first.log".into());
+        assert_eq!(events[0].as_log()["source_type"], "sftp".into());
+
+        fs::write(root.join("app/first.log"), "one\ntwo\nthree\n").unwrap();
+        let events = run_poll(
+            &root,
+            &data_dir,
+            AfterReadAction::Keep,
+            EventStatus::Delivered,
+        )
+        .await;
+        assert_eq!(messages(&events), vec!["three"]);
+
+        // Rejected events are read again.
+        fs::write(root.join("second.log"), "four\n").unwrap();
+        let events = run_poll(
+            &root,
+            &data_dir,
+            AfterReadAction::Keep,
+            EventStatus::Rejected,
+        )
+        .await;
+        assert_eq!(messages(&events), vec!["four"]);
+        let events = run_poll(
+            &root,
+            &data_dir,
+            AfterReadAction::Keep,
+            EventStatus::Delivered,
+        )
+        .await;
+        assert_eq!(messages(&events), vec!["four"]);
+        let events = run_poll(
+            &root,
+            &data_dir,
+            AfterReadAction::Keep,
+            EventStatus::Delivered,
+        )
+        .await;
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn deletes_and_moves_read_files() {
+        let root = temp_dir();
+        let data_dir = temp_dir();
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::write(root.join("app/first.log"), "one\n").unwrap();
+
+        let after_read = AfterReadAction::Move {
+            directory: "processed".to_owned(),
+        };
+        let events = run_poll(&root, &data_dir, after_read.clone(), EventStatus::Delivered).await;
+        assert_eq!(messages(&events), vec!["one"]);
+        assert!(!root.join("app/first.log").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("processed/app/first.log")).unwrap(),
+            "one\n"
+        );
+
+        fs::write(root.join("second.log"), "two\n").unwrap();
+        let events = run_poll(
+            &root,
+            &data_dir,
+            AfterReadAction::Delete,
+            EventStatus::Rejected,
+        )
+        .await;
+        assert_eq!(messages(&events), vec!["two"]);
+        assert!(root.join("second.log").exists());
+
+        let events = run_poll(
+            &root,
+            &data_dir,
+            AfterReadAction::Delete,
+            EventStatus::Delivered,
+        )
+        .await;
+        assert_eq!(messages(&events), vec!["two"]);
+        assert!(!root.join("second.log").exists());
+    }
+}
@@ -385,7 +385,9 @@ mod test {
 
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     use crate::{
-        config::{log_schema, ComponentKey, GlobalOptions, SourceConfig, SourceContext},
+        config::{
+            log_schema, ComponentKey, GlobalOptions, ProxyConfig, SourceConfig, SourceContext,
+        },
         event::{Event, LogEvent},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
@@ -784,7 +786,9 @@ mod test {
         let encoder = Serializer {
             bytes: message_bytes,
         };
-        let (sink, _healthcheck) = sink_config.build(Default::default(), encoder).unwrap();
+        let (sink, _healthcheck) = sink_config
+            .build(&ProxyConfig::default(), Default::default(), encoder)
+            .unwrap();
 
         tokio::spawn(async move {
             let input = stream::repeat_with(|| LogEvent::default().into()).boxed();
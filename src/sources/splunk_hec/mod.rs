@@ -11,7 +11,7 @@ use flate2::read::MultiGzDecoder;
 use futures::FutureExt;
 use http::StatusCode;
 use lookup::owned_value_path;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{de::Read as JsonRead, Deserializer, Value as JsonValue};
 use snafu::Snafu;
 use tracing::Span;
@@ -35,7 +35,10 @@ use self::{
 };
 use crate::{
     config::{log_schema, DataType, Resource, SourceConfig, SourceContext, SourceOutput},
-    event::{Event, LogEvent, Value},
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event, LogEvent, Value,
+    },
     internal_events::{
         EventsReceived, HttpBytesReceived, SplunkHecRequestBodyInvalidError, SplunkHecRequestError,
         SplunkHecRequestReceived,
@@ -55,7 +58,7 @@ pub const SOURCE: &str = "splunk_source";
 pub const SOURCETYPE: &str = "splunk_sourcetype";
 
 /// Configuration for the `splunk_hec` source.
-#[configurable_component(source("splunk_hec", "Receive logs from Splunk."))]
+#[configurable_component(source("splunk_hec", "Receive logs and metrics from Splunk."))]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields, default)]
 pub struct SplunkConfig {
@@ -237,7 +240,10 @@ impl SourceConfig for SplunkConfig {
             None,
         );
 
-        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+        vec![SourceOutput::new_logs(
+            DataType::Log | DataType::Metric,
+            schema_definition,
+        )]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -375,7 +381,7 @@ impl SplunkSource {
                         );
                         for result in iter {
                             match result {
-                                Ok(event) => events.push(event),
+                                Ok(batch) => events.extend(batch),
                                 Err(err) => {
                                     error = Some(err);
                                     break;
@@ -417,6 +423,7 @@ impl SplunkSource {
             .and(path!("raw" / "1.0").or(path!("raw")))
             .and(self.authorization())
             .and(SplunkSource::required_channel())
+            .and(warp::query::<RawQueryParams>())
             .and(warp::addr::remote())
             .and(warp::header::optional::<String>("X-Forwarded-For"))
             .and(self.gzip())
@@ -426,6 +433,7 @@ impl SplunkSource {
                 move |_,
                       token: Option<String>,
                       channel_id: String,
+                      params: RawQueryParams,
                       remote: Option<SocketAddr>,
                       xff: Option<String>,
                       gzip: bool,
@@ -455,6 +463,7 @@ impl SplunkSource {
                             body,
                             gzip,
                             channel_id,
+                            params,
                             remote,
                             xff,
                             batch,
@@ -592,7 +601,8 @@ impl SplunkSource {
             .boxed()
     }
 }
-/// Constructs one or more events from json-s coming from reader.
+/// Constructs one or more events from json-s coming from reader, a HEC metric event yielding
+/// one metric per measurement.
 /// If errors, it's done with input.
 struct EventIterator<'de, R: JsonRead<'de>> {
     /// Remaining request with JSON events
@@ -698,7 +708,36 @@ impl<'de, R: JsonRead<'de>> EventIterator<'de, R> {
             }
         }
 
-        // Process time field
+        let timestamp = self.extract_timestamp(&mut json)?;
+        self.log_namespace.insert_source_metadata(
+            SplunkConfig::NAME,
+            &mut log,
+            log_schema().timestamp_key().map(LegacyKey::Overwrite),
+            "timestamp",
+            timestamp,
+        );
+
+        // Extract default extracted fields
+        for de in self.extractors.iter_mut() {
+            de.extract(&mut log, &mut json);
+        }
+
+        // Add passthrough token if present
+        if let Some(token) = &self.token {
+            log.metadata_mut().set_splunk_hec_token(Arc::clone(token));
+        }
+
+        if let Some(batch) = self.batch.clone() {
+            log = log.with_batch_notifier(&batch);
+        }
+
+        self.events += 1;
+
+        Ok(log.into())
+    }
+
+    /// Updates the default time from the `time` field if present, returning the event's timestamp.
+    fn extract_timestamp(&mut self, json: &mut JsonValue) -> Result<DateTime<Utc>, Rejection> {
         let parsed_time = match json.get_mut("time").map(JsonValue::take) {
             Some(JsonValue::Number(time)) => Some(Some(time)),
             Some(JsonValue::String(time)) => Some(time.parse::<serde_json::Number>().ok()),
@@ -729,37 +768,99 @@ impl<'de, R: JsonRead<'de>> EventIterator<'de, R> {
             Some(None) => return Err(ApiError::InvalidDataFormat { event: self.events }.into()),
         }
 
-        // Add time field
-        let timestamp = match self.time.clone() {
+        Ok(match self.time.clone() {
             Time::Provided(time) => time,
             Time::Now(time) => time,
+        })
+    }
+
+    /// Build the metrics of an event in the HEC metric format, in which `event` is `"metric"` and
+    /// `fields` holds either a `metric_name` and its `_value`, or one `metric_name:<name>` field per
+    /// metric. The other fields are the dimensions of the metrics, and are mapped to tags.
+    ///
+    /// Returns `None` if the event isn't in the metric format.
+    fn build_metrics(&mut self, json: &mut JsonValue) -> Result<Option<Vec<Event>>, Rejection> {
+        if json.get("event").and_then(JsonValue::as_str) != Some("metric") {
+            return Ok(None);
+        }
+        let is_metric = |key: &str| key == "metric_name" || key.starts_with("metric_name:");
+        let fields = match json.get_mut("fields") {
+            Some(JsonValue::Object(fields)) if fields.keys().any(|key| is_metric(key)) => {
+                std::mem::take(fields)
+            }
+            _ => return Ok(None),
         };
 
-        self.log_namespace.insert_source_metadata(
-            SplunkConfig::NAME,
-            &mut log,
-            log_schema().timestamp_key().map(LegacyKey::Overwrite),
-            "timestamp",
-            timestamp,
-        );
+        let event = self.events;
+        let invalid = move || Rejection::from(ApiError::InvalidDataFormat { event });
+        let parse_value = |value: &JsonValue| match value {
+            JsonValue::Number(number) => number.as_f64(),
+            JsonValue::String(string) => string.parse().ok(),
+            _ => None,
+        };
 
-        // Extract default extracted fields
-        for de in self.extractors.iter_mut() {
-            de.extract(&mut log, &mut json);
+        let mut values = Vec::new();
+        let mut tags = MetricTags::default();
+        if let Some(name) = fields.get("metric_name") {
+            let name = name.as_str().ok_or_else(invalid)?;
+            let value = fields
+                .get("_value")
+                .and_then(parse_value)
+                .ok_or_else(invalid)?;
+            values.push((name.to_owned(), value));
         }
-
-        // Add passthrough token if present
-        if let Some(token) = &self.token {
-            log.metadata_mut().set_splunk_hec_token(Arc::clone(token));
+        for (key, value) in &fields {
+            if let Some(name) = key.strip_prefix("metric_name:") {
+                values.push((name.to_owned(), parse_value(value).ok_or_else(invalid)?));
+            } else if key != "metric_name" && key != "_value" {
+                match value {
+                    JsonValue::String(string) => tags.replace(key.clone(), string.clone()),
+                    JsonValue::Number(_) | JsonValue::Bool(_) => {
+                        tags.replace(key.clone(), value.to_string())
+                    }
+                    _ => None,
+                };
+            }
         }
 
-        if let Some(batch) = self.batch.clone() {
-            log = log.with_batch_notifier(&batch);
+        let timestamp = self.extract_timestamp(json)?;
+
+        let channel = match json.get_mut("channel").map(JsonValue::take) {
+            Some(JsonValue::String(guid)) => Some(guid),
+            _ => self
+                .channel
+                .as_ref()
+                .map(|guid| guid.to_string_lossy().into_owned()),
+        };
+        if let Some(channel) = channel {
+            tags.replace(CHANNEL.to_owned(), channel);
+        }
+        for de in self.extractors.iter_mut() {
+            if let Some(value) = de.update(json) {
+                tags.replace(de.to_field.to_owned(), value.to_string_lossy());
+            }
         }
 
         self.events += 1;
 
-        Ok(log.into())
+        Ok(Some(
+            values
+                .into_iter()
+                .map(|(name, value)| {
+                    let mut metric =
+                        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+                            .with_timestamp(Some(timestamp))
+                            .with_tags(Some(tags.clone()))
+                            .with_batch_notifier_option(&self.batch);
+                    if let Some(token) = &self.token {
+                        metric
+                            .metadata_mut()
+                            .set_splunk_hec_token(Arc::clone(token));
+                    }
+                    metric.into()
+                })
+                .collect(),
+        ))
     }
 
     /// Build the log event for the vector namespace.
@@ -830,11 +931,15 @@ impl<'de, R: JsonRead<'de>> EventIterator<'de, R> {
 }
 
 impl<'de, R: JsonRead<'de>> Iterator for EventIterator<'de, R> {
-    type Item = Result<Event, Rejection>;
+    type Item = Result<Vec<Event>, Rejection>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.deserializer.next() {
-            Some(Ok(json)) => Some(self.build_event(json)),
+            Some(Ok(mut json)) => Some(match self.build_metrics(&mut json) {
+                Ok(Some(metrics)) => Ok(metrics),
+                Ok(None) => self.build_event(json).map(|event| vec![event]),
+                Err(error) => Err(error),
+            }),
             None => {
                 if self.events == 0 {
                     Some(Err(ApiError::NoData.into()))
@@ -888,6 +993,21 @@ fn parse_timestamp(t: i64) -> Option<DateTime<Utc>> {
     Some(ts)
 }
 
+/// Parses the `time` query parameter of the raw endpoint, which may have a fractional part.
+fn parse_raw_time(time: &str) -> Option<DateTime<Utc>> {
+    match time.parse::<i64>() {
+        Ok(t) => parse_timestamp(t),
+        Err(_) => time
+            .parse::<f64>()
+            .ok()
+            .filter(|t| t.is_finite() && *t >= 0.0)
+            .and_then(|t| {
+                Utc.timestamp_opt(t.floor() as i64, (t.fract() * 1_000_000_000.0) as u32)
+                    .single()
+            }),
+    }
+}
+
 /// Maintains last known extracted value of field and uses it in the absence of field.
 struct DefaultExtractor {
     field: &'static str,
@@ -920,20 +1040,23 @@ impl DefaultExtractor {
         }
     }
 
-    fn extract(&mut self, log: &mut LogEvent, value: &mut JsonValue) {
-        // Process json_field
+    /// Updates the last known value from the json field if present, returning it.
+    fn update(&mut self, value: &mut JsonValue) -> Option<&Value> {
         if let Some(JsonValue::String(new_value)) = value.get_mut(self.field).map(JsonValue::take) {
             self.value = Some(new_value.into());
         }
+        self.value.as_ref()
+    }
 
+    fn extract(&mut self, log: &mut LogEvent, value: &mut JsonValue) {
         // Add data field
-        if let Some(index) = self.value.as_ref() {
+        if let Some(index) = self.update(value).cloned() {
             self.log_namespace.insert_source_metadata(
                 SplunkConfig::NAME,
                 log,
                 Some(LegacyKey::Overwrite(lookup::path!(self.to_field))),
                 lookup::path!(self.to_field),
-                index.clone(),
+                index,
             )
         }
     }
@@ -948,18 +1071,34 @@ enum Time {
     Provided(DateTime<Utc>),
 }
 
+/// Default fields of the events sent to the raw endpoint, given as query parameters.
+#[derive(Deserialize)]
+struct RawQueryParams {
+    host: Option<String>,
+    index: Option<String>,
+    source: Option<String>,
+    sourcetype: Option<String>,
+    time: Option<String>,
+}
+
 /// Creates event from raw request
 #[allow(clippy::too_many_arguments)]
 fn raw_event(
     bytes: Bytes,
     gzip: bool,
     channel: String,
+    params: RawQueryParams,
     remote: Option<SocketAddr>,
     xff: Option<String>,
     batch: Option<BatchNotifier>,
     log_namespace: LogNamespace,
     events_received: &Registered<EventsReceived>,
 ) -> Result<Event, Rejection> {
+    let time = match params.time {
+        Some(time) => Some(parse_raw_time(&time).ok_or(ApiError::InvalidDataFormat { event: 0 })?),
+        None => None,
+    };
+
     // Process gzip
     let message: Value = if gzip {
         let mut data = Vec::new();
@@ -997,13 +1136,13 @@ fn raw_event(
     );
 
     // host-field priority for raw endpoint:
-    // - x-forwarded-for is set to `host` field first, if present. If not present:
+    // - the `host` query parameter is set to `host` field first, if present. If not present:
+    // - x-forwarded-for is set to `host` field, if present. If not present:
     // - set remote addr to host field
-    let host = if let Some(remote_address) = xff {
-        Some(remote_address)
-    } else {
-        remote.map(|remote| remote.to_string())
-    };
+    let host = params
+        .host
+        .or(xff)
+        .or_else(|| remote.map(|remote| remote.to_string()));
 
     if let Some(host) = host {
         log_namespace.insert_source_metadata(
@@ -1015,8 +1154,34 @@ fn raw_event(
         );
     }
 
+    for (key, value) in [
+        (INDEX, params.index),
+        (SOURCE, params.source),
+        (SOURCETYPE, params.sourcetype),
+    ] {
+        if let Some(value) = value {
+            log_namespace.insert_source_metadata(
+                SplunkConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(key)),
+                key,
+                value,
+            );
+        }
+    }
+
     log_namespace.insert_standard_vector_source_metadata(&mut log, SplunkConfig::NAME, Utc::now());
 
+    if let Some(time) = time {
+        log_namespace.insert_source_metadata(
+            SplunkConfig::NAME,
+            &mut log,
+            log_schema().timestamp_key().map(LegacyKey::Overwrite),
+            "timestamp",
+            time,
+        );
+    }
+
     if let Some(batch) = batch {
         log = log.with_batch_notifier(&batch);
     }
@@ -1616,6 +1781,116 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn raw_query_params() {
+        let (source, address) = source(None).await;
+
+        let status = reqwest::Client::new()
+            .post(format!("http://{}/services/collector/raw", address))
+            .header("Authorization", format!("Splunk {}", TOKEN))
+            .query(&[
+                ("channel", "channel"),
+                ("host", "forwarder"),
+                ("index", "main"),
+                ("source", "/var/log/syslog"),
+                ("sourcetype", "syslog"),
+                ("time", "1638366107.5"),
+            ])
+            .body("raw")
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(200, status.as_u16());
+
+        let event = collect_n(source, 1).await.remove(0);
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "raw".into());
+        assert_eq!(log[log_schema().host_key()], "forwarder".into());
+        assert_eq!(log[&super::INDEX], "main".into());
+        assert_eq!(log[&super::SOURCE], "/var/log/syslog".into());
+        assert_eq!(log[&super::SOURCETYPE], "syslog".into());
+        assert_eq!(
+            log[log_schema().timestamp_key().unwrap()],
+            Utc.timestamp_opt(1638366107, 500_000_000).unwrap().into()
+        );
+
+        assert_eq!(
+            400,
+            post(address, "services/collector/raw?time=yesterday", "raw").await
+        );
+    }
+
+    #[tokio::test]
+    async fn metric_event() {
+        let message = r#"{"time":1638366107,"event":"metric","host":"host-1","source":"disk","fields":{"region":"us-west-1","cores":4,"metric_name":"cpu.usage","_value":"99.1"}}"#;
+        let (source, address) = source(None).await;
+
+        assert_eq!(
+            200,
+            post(address, "services/collector/event", message).await
+        );
+
+        let metric = collect_n(source, 1).await.remove(0).into_metric();
+        assert_eq!(metric.name(), "cpu.usage");
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        assert_eq!(*metric.value(), MetricValue::Gauge { value: 99.1 });
+        assert_eq!(
+            metric.timestamp(),
+            Some(Utc.timestamp_opt(1638366107, 0).unwrap())
+        );
+        let tags = metric.tags().unwrap();
+        assert_eq!(tags.get("region"), Some("us-west-1"));
+        assert_eq!(tags.get("cores"), Some("4"));
+        assert_eq!(tags.get(log_schema().host_key()), Some("host-1"));
+        assert_eq!(tags.get(super::SOURCE), Some("disk"));
+        assert_eq!(tags.get(super::CHANNEL), Some("channel"));
+    }
+
+    #[tokio::test]
+    async fn multiple_metric_event() {
+        let message = r#"{"event":"metric","fields":{"metric_name:cpu.usr":11.12,"metric_name:cpu.sys":12.23,"region":"us-west-1"}}{"event":"metric","fields":{"color":"blue"}}"#;
+        let (source, address) = source(None).await;
+
+        assert_eq!(
+            200,
+            post(address, "services/collector/event", message).await
+        );
+
+        let mut events = collect_n(source, 3).await;
+        let log = events.pop().unwrap().into_log();
+        assert_eq!(log[log_schema().message_key()], "metric".into());
+        assert_eq!(log["color"], "blue".into());
+
+        let mut metrics = events
+            .into_iter()
+            .map(|event| {
+                let metric = event.into_metric();
+                assert_eq!(metric.tags().unwrap().get("region"), Some("us-west-1"));
+                (metric.name().to_owned(), metric.value().clone())
+            })
+            .collect::<Vec<_>>();
+        metrics.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            metrics,
+            vec![
+                ("cpu.sys".to_owned(), MetricValue::Gauge { value: 12.23 }),
+                ("cpu.usr".to_owned(), MetricValue::Gauge { value: 11.12 }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_metric_value() {
+        let message = r#"{"event":"metric","fields":{"metric_name":"cpu.usage","_value":"high"}}"#;
+        let (_source, address) = source(None).await;
+
+        assert_eq!(
+            400,
+            post(address, "services/collector/event", message).await
+        );
+    }
+
     #[tokio::test]
     async fn root() {
         assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
@@ -0,0 +1,459 @@
+//! Server-Sent Events source.
+//! Consumes a `text/event-stream` endpoint, reconnecting and resuming from the last event received.
+
+mod parser;
+
+use std::{collections::HashMap, io, time::Duration};
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::{StreamExt, TryStreamExt};
+use http::{header, Request, StatusCode, Uri};
+use hyper::Body;
+use lookup::{owned_value_path, path};
+use serde_with::serde_as;
+use snafu::ResultExt;
+use tokio_util::{codec::FramedRead, io::StreamReader};
+use value::Kind;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use self::parser::{SseDecoder, SseEvent, SseFrame};
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    http::{Auth, HttpClient},
+    internal_events::{HttpClientHttpError, HttpClientHttpResponseError, StreamClosedError},
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sources,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+/// The longest delay before reconnecting after consecutive failures.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Configuration for the `sse` source.
+#[serde_as]
+#[configurable_component(source("sse", "Consume Server-Sent Events from an HTTP endpoint."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SseConfig {
+    /// The HTTP endpoint of the event stream.
+    #[configurable(metadata(docs::examples = "https://api.example.com/v1/audit/stream"))]
+    endpoint: String,
+
+    /// Headers to apply to the HTTP requests.
+    ///
+    /// One or more values for the same header can be provided.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP request header and its value(s)."
+    ))]
+    #[configurable(metadata(docs::examples = "headers_examples()"))]
+    headers: HashMap<String, Vec<String>>,
+
+    /// The delay before reconnecting once the stream ends.
+    ///
+    /// The server can change this delay by sending a `retry` field. Consecutive connection
+    /// failures double the delay, up to a minute.
+    #[serde(default = "default_reconnect_delay_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    reconnect_delay_ms: Duration,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+const fn default_reconnect_delay_ms() -> Duration {
+    Duration::from_secs(3)
+}
+
+fn headers_examples() -> HashMap<String, Vec<String>> {
+    HashMap::from([("X-Api-Version".to_owned(), vec!["2023-01-01".to_owned()])])
+}
+
+impl GenerateConfig for SseConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "https://api.example.com/v1/audit/stream"
+            decoding.codec = "json"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sse")]
+impl SourceConfig for SseConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let uri = self
+            .endpoint
+            .parse::<Uri>()
+            .context(sources::UriParseSnafu)?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+
+        let source = SseSource {
+            uri,
+            headers: self.headers.clone(),
+            auth: self.auth.clone(),
+            client,
+            decoder,
+            log_namespace,
+            out: cx.out,
+            reconnect_delay: self.reconnect_delay_ms,
+            last_event_id: String::new(),
+        };
+        Ok(Box::pin(source.run(cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(EVENT_ID_KEY))),
+                &owned_value_path!(EVENT_ID_KEY),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(EVENT_TYPE_KEY))),
+                &owned_value_path!(EVENT_TYPE_KEY),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+const EVENT_ID_KEY: &str = "event_id";
+const EVENT_TYPE_KEY: &str = "event_type";
+
+struct SseSource {
+    uri: Uri,
+    headers: HashMap<String, Vec<String>>,
+    auth: Option<Auth>,
+    client: HttpClient,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+    reconnect_delay: Duration,
+    /// The ID of the last event sent, to resume the stream from when reconnecting.
+    last_event_id: String,
+}
+
+impl SseSource {
+    async fn run(mut self, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let url = self.uri.to_string();
+        let mut failures = 0;
+
+        loop {
+            let response = tokio::select! {
+                response = self.client.send(self.build_request()) => response,
+                _ = shutdown.clone() => return Ok(()),
+            };
+
+            let delay = match response {
+                // The server asks not to reconnect.
+                Ok(response) if response.status() == StatusCode::NO_CONTENT => {
+                    warn!(message = "Event stream closed by the server; not reconnecting.", %url);
+                    shutdown.await;
+                    return Ok(());
+                }
+                Ok(response) if response.status().is_success() => {
+                    failures = 0;
+                    if self
+                        .read_stream(response.into_body(), &url, shutdown.clone())
+                        .await?
+                    {
+                        return Ok(());
+                    }
+                    self.reconnect_delay
+                }
+                Ok(response) => {
+                    emit!(HttpClientHttpResponseError {
+                        code: response.status(),
+                        url: url.clone(),
+                    });
+                    failures += 1;
+                    self.failure_delay(failures)
+                }
+                Err(error) => {
+                    emit!(HttpClientHttpError {
+                        error: error.into(),
+                        url: url.clone(),
+                    });
+                    failures += 1;
+                    self.failure_delay(failures)
+                }
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.clone() => return Ok(()),
+            }
+        }
+    }
+
+    fn build_request(&self) -> Request<Body> {
+        let mut builder = Request::get(&self.uri);
+        for (header, values) in &self.headers {
+            for value in values {
+                builder = builder.header(header, value);
+            }
+        }
+        builder = builder
+            .header(header::ACCEPT, "text/event-stream")
+            .header(header::CACHE_CONTROL, "no-cache");
+        if !self.last_event_id.is_empty() {
+            builder = builder.header("Last-Event-ID", &self.last_event_id);
+        }
+
+        // building an empty request should be infallible
+        let mut request = builder.body(Body::empty()).expect("error creating request");
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+        request
+    }
+
+    fn failure_delay(&self, failures: u32) -> Duration {
+        self.reconnect_delay
+            .saturating_mul(1 << failures.min(6))
+            .min(MAX_RECONNECT_DELAY)
+    }
+
+    /// Reads the events of the stream until it ends, returning whether the source is shutting
+    /// down.
+    async fn read_stream(
+        &mut self,
+        body: Body,
+        url: &str,
+        shutdown: ShutdownSignal,
+    ) -> Result<bool, ()> {
+        let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
+        let body = body
+            .inspect_ok(|chunk| bytes_received.emit(ByteSize(chunk.len())))
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error));
+        let mut frames = FramedRead::new(
+            StreamReader::new(body),
+            SseDecoder::new(self.last_event_id.clone()),
+        );
+
+        loop {
+            let frame = tokio::select! {
+                frame = frames.next() => frame,
+                _ = shutdown.clone() => return Ok(true),
+            };
+
+            match frame {
+                Some(Ok(SseFrame::Event(event))) => self.handle_event(event).await?,
+                Some(Ok(SseFrame::Retry(delay))) => self.reconnect_delay = delay,
+                Some(Err(error)) => {
+                    emit!(HttpClientHttpError {
+                        error: error.into(),
+                        url: url.to_owned(),
+                    });
+                    return Ok(false);
+                }
+                None => {
+                    debug!(message = "Event stream ended; reconnecting.", %url);
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    async fn handle_event(&mut self, sse_event: SseEvent) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        let mut stream = FramedRead::new(sse_event.data.as_bytes(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((mut events, _byte_size)) => {
+                    let count = events.len();
+                    let byte_size = events.estimated_json_encoded_size_of();
+                    events_received.emit(CountByteSize(count, byte_size));
+
+                    let now = Utc::now();
+                    for event in events.iter_mut() {
+                        if let Event::Log(log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                SseConfig::NAME,
+                                now,
+                            );
+                            if !sse_event.id.is_empty() {
+                                self.log_namespace.insert_source_metadata(
+                                    SseConfig::NAME,
+                                    log,
+                                    Some(LegacyKey::InsertIfEmpty(path!(EVENT_ID_KEY))),
+                                    path!(EVENT_ID_KEY),
+                                    sse_event.id.as_str(),
+                                );
+                            }
+                            self.log_namespace.insert_source_metadata(
+                                SseConfig::NAME,
+                                log,
+                                Some(LegacyKey::InsertIfEmpty(path!(EVENT_TYPE_KEY))),
+                                path!(EVENT_TYPE_KEY),
+                                sse_event.event_type.as_str(),
+                            );
+                        }
+                    }
+
+                    self.out.send_batch(events).await.map_err(|error| {
+                        emit!(StreamClosedError { error, count });
+                    })?;
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // The ID is only recorded once the events are sent, so that the stream is resumed from
+        // the last event that made it through.
+        self.last_event_id = sse_event.id;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Response, Server,
+    };
+
+    use super::*;
+    use crate::test_util::{collect_n, next_addr, trace_init, wait_for_tcp};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SseConfig>();
+    }
+
+    /// Serves one event per connection, recording the `Last-Event-ID` of each request.
+    fn serve(addr: SocketAddr, last_event_ids: Arc<Mutex<Vec<Option<String>>>>) {
+        let make_service = make_service_fn(move |_| {
+            let last_event_ids = Arc::clone(&last_event_ids);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let last_event_ids = Arc::clone(&last_event_ids);
+                    async move {
+                        let last_event_id = request
+                            .headers()
+                            .get("Last-Event-ID")
+                            .map(|value| value.to_str().unwrap().to_owned());
+                        let sequence = last_event_id
+                            .as_deref()
+                            .map_or(1, |id| id.parse::<u32>().unwrap() + 1);
+                        last_event_ids.lock().unwrap().push(last_event_id);
+
+                        let body = format!(
+                            "retry: 10\nid: {}\nevent: audit\ndata: {{\"sequence\": {}}}\n\n",
+                            sequence, sequence
+                        );
+                        Ok::<_, Infallible>(
+                            Response::builder()
+                                .header(header::CONTENT_TYPE, "text/event-stream")
+                                .body(Body::from(body))
+                                .unwrap(),
+                        )
+                    }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+    }
+
+    #[tokio::test]
+    async fn receives_events_and_resumes_after_reconnecting() {
+        trace_init();
+
+        let addr = next_addr();
+        let last_event_ids = Arc::new(Mutex::new(Vec::new()));
+        serve(addr, Arc::clone(&last_event_ids));
+        wait_for_tcp(addr).await;
+
+        let config = toml::from_str::<SseConfig>(&format!(
+            r#"
+            endpoint = "http://{}/stream"
+            decoding.codec = "json"
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 2).await;
+        assert_eq!(events[0].as_log()["sequence"], 1.into());
+        assert_eq!(events[0].as_log()["event_id"], "1".into());
+        assert_eq!(events[0].as_log()["event_type"], "audit".into());
+        assert_eq!(events[1].as_log()["sequence"], 2.into());
+        assert_eq!(
+            last_event_ids.lock().unwrap()[..2],
+            [None, Some("1".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Parser of the [`text/event-stream`][spec] format.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation
+
+use std::{io, time::Duration};
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::Decoder;
+
+/// An event dispatched by the server.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The last event ID set by the server, which may have been set by a previous event.
+    pub id: String,
+    /// The type of the event, `message` unless set by the server.
+    pub event_type: String,
+    pub data: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SseFrame {
+    Event(SseEvent),
+    /// The server set the delay before reconnecting.
+    Retry(Duration),
+}
+
+/// Decodes a `text/event-stream` body into the events dispatched by the server.
+pub struct SseDecoder {
+    last_event_id: String,
+    event_type: String,
+    data: String,
+    started: bool,
+}
+
+impl SseDecoder {
+    /// Creates a decoder, with the last event ID of a previous connection.
+    pub const fn new(last_event_id: String) -> Self {
+        Self {
+            last_event_id,
+            event_type: String::new(),
+            data: String::new(),
+            started: false,
+        }
+    }
+
+    /// Splits the next line, ended by CRLF, LF or CR.
+    fn next_line(src: &mut BytesMut) -> Option<BytesMut> {
+        let position = src.iter().position(|byte| matches!(byte, b'\r' | b'\n'))?;
+        let terminator_len = match (src[position], src.get(position + 1)) {
+            (b'\r', Some(b'\n')) => 2,
+            // The CR may be followed by a LF not received yet.
+            (b'\r', None) => return None,
+            _ => 1,
+        };
+
+        let line = src.split_to(position);
+        src.advance(terminator_len);
+        Some(line)
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<SseFrame> {
+        let mut line = String::from_utf8_lossy(line);
+        if !self.started {
+            self.started = true;
+            if let Some(stripped) = line.strip_prefix('\u{feff}') {
+                line = stripped.to_owned().into();
+            }
+        }
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            // Comments are typically sent to keep the connection alive.
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_ref(), ""),
+        };
+        match field {
+            "event" => self.event_type = value.to_owned(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = value.to_owned(),
+            "retry" if !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    return Some(SseFrame::Retry(Duration::from_millis(millis)));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseFrame> {
+        let event_type = std::mem::take(&mut self.event_type);
+        let mut data = std::mem::take(&mut self.data);
+        if data.is_empty() {
+            return None;
+        }
+        data.pop();
+
+        Some(SseFrame::Event(SseEvent {
+            id: self.last_event_id.clone(),
+            event_type: if event_type.is_empty() {
+                "message".to_owned()
+            } else {
+                event_type
+            },
+            data,
+        }))
+    }
+}
+
+impl Decoder for SseDecoder {
+    type Item = SseFrame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        while let Some(line) = Self::next_line(src) {
+            if let Some(frame) = self.process_line(&line) {
+                return Ok(Some(frame));
+            }
+        }
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // A trailing CR ends the last line.
+        if src.last() == Some(&b'\r') {
+            src.extend_from_slice(b"\n");
+        }
+        let frame = self.decode(src)?;
+        if frame.is_none() {
+            // An incomplete event at the end of the stream is discarded.
+            src.clear();
+        }
+        Ok(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(last_event_id: &str, chunks: &[&str]) -> Vec<SseFrame> {
+        let mut decoder = SseDecoder::new(last_event_id.to_owned());
+        let mut buffer = BytesMut::new();
+        let mut frames = Vec::new();
+        for chunk in chunks {
+            buffer.extend_from_slice(chunk.as_bytes());
+            while let Some(frame) = decoder.decode(&mut buffer).unwrap() {
+                frames.push(frame);
+            }
+        }
+        while let Some(frame) = decoder.decode_eof(&mut buffer).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    fn event(id: &str, event_type: &str, data: &str) -> SseFrame {
+        SseFrame::Event(SseEvent {
+            id: id.to_owned(),
+            event_type: event_type.to_owned(),
+            data: data.to_owned(),
+        })
+    }
+
+    #[test]
+    fn decodes_events() {
+        let frames = decode(
+            "",
+            &[
+                "\u{feff}: keepalive\n",
+                "id: 1\nevent: audit\ndata: {\"action\":\n",
+                "data:\"login\"}\n\n",
+                "data: second\n\n",
+                "id: 3\ndata\n\n",
+            ],
+        );
+        assert_eq!(
+            frames,
+            vec![
+                event("1", "audit", "{\"action\":\n\"login\"}"),
+                event("1", "message", "second"),
+                event("3", "message", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_line_endings_split_across_chunks() {
+        // The trailing CR ends the last line, but its event is not dispatched without an empty
+        // line.
+        let frames = decode("", &["data: first\r", "\ndata: line\r\r", "data: second\r"]);
+        assert_eq!(frames, vec![event("", "message", "first\nline")]);
+    }
+
+    #[test]
+    fn keeps_last_event_id_of_previous_connection() {
+        let frames = decode("41", &["data: resumed\n\n", "id\ndata: reset\n\n"]);
+        assert_eq!(
+            frames,
+            vec![
+                event("41", "message", "resumed"),
+                event("", "message", "reset")
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_retry_delays() {
+        let frames = decode("", &["retry: 5000\nretry: soon\n\n", "data: x\n\n"]);
+        assert_eq!(
+            frames,
+            vec![
+                SseFrame::Retry(Duration::from_millis(5000)),
+                event("", "message", "x")
+            ]
+        );
+    }
+
+    #[test]
+    fn discards_incomplete_events() {
+        let frames = decode("", &["data: complete\n\ndata: incomplete\n"]);
+        assert_eq!(frames, vec![event("", "message", "complete")]);
+    }
+}
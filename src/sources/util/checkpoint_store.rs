@@ -0,0 +1,363 @@
+//! Stores of source checkpoints which outlive the host Vector runs on.
+use std::{io, sync::Mutex};
+
+use aws_sdk_dynamodb::{model::AttributeValue, types::Blob, Client as DynamoDbClient};
+use aws_sdk_s3::{
+    types::{ByteStream, SdkError},
+    Client as S3Client,
+};
+use file_source::CheckpointStore;
+use redis::Commands;
+use tokio::runtime::Handle;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, RegionOrEndpoint},
+    common::{dynamodb::DynamoDbClientBuilder, s3::S3ClientBuilder},
+    config::ProxyConfig,
+    tls::TlsConfig,
+};
+
+/// Checkpoint store configuration.
+///
+/// Checkpoints are always persisted to the data directory. When a checkpoint store is configured,
+/// they are also persisted to it, and loaded from it when none are found in the data directory.
+/// This lets an instance of Vector running on a fresh host, such as a Fargate task or a spot
+/// instance, resume where the previous instance left off.
+///
+/// A checkpoint store must not be shared by instances of Vector running at the same time.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of checkpoint store."))]
+pub enum CheckpointStoreConfig {
+    /// Store the checkpoints in Redis.
+    Redis(RedisCheckpointStoreConfig),
+
+    /// Store the checkpoints in an AWS S3 object.
+    AwsS3(S3CheckpointStoreConfig),
+
+    /// Store the checkpoints in an item of an AWS DynamoDB table.
+    AwsDynamodb(DynamoDbCheckpointStoreConfig),
+}
+
+impl CheckpointStoreConfig {
+    /// Builds the checkpoint store.
+    pub async fn build(&self, proxy: &ProxyConfig) -> crate::Result<Box<dyn CheckpointStore>> {
+        match self {
+            Self::Redis(config) => config.build(),
+            Self::AwsS3(config) => config.build(proxy).await,
+            Self::AwsDynamodb(config) => config.build(proxy).await,
+        }
+    }
+}
+
+/// Redis checkpoint store configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisCheckpointStoreConfig {
+    /// The Redis URL to connect to.
+    ///
+    /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub endpoint: SensitiveString,
+
+    /// The Redis key under which the checkpoints are stored.
+    #[configurable(metadata(docs::examples = "vector:checkpoints:my-agent"))]
+    pub key: String,
+}
+
+impl RedisCheckpointStoreConfig {
+    fn build(&self) -> crate::Result<Box<dyn CheckpointStore>> {
+        let client = redis::Client::open(self.endpoint.inner())?;
+        Ok(Box::new(RedisCheckpointStore {
+            client,
+            key: self.key.clone(),
+            connection: Mutex::new(None),
+        }))
+    }
+}
+
+struct RedisCheckpointStore {
+    client: redis::Client,
+    key: String,
+    connection: Mutex<Option<redis::Connection>>,
+}
+
+impl RedisCheckpointStore {
+    fn with_connection<T>(
+        &self,
+        f: impl FnOnce(&mut redis::Connection) -> redis::RedisResult<T>,
+    ) -> io::Result<T> {
+        let mut connection = self.connection.lock().expect("Data poisoned.");
+        let result = match connection.as_mut() {
+            Some(connection) => f(connection),
+            None => self
+                .client
+                .get_connection()
+                .and_then(|new_connection| f(connection.insert(new_connection))),
+        };
+        // The connection is reopened by the next call, in case it was broken.
+        if result.is_err() {
+            *connection = None;
+        }
+        result.map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+    }
+}
+
+impl CheckpointStore for RedisCheckpointStore {
+    fn load(&self) -> io::Result<Option<Vec<u8>>> {
+        self.with_connection(|connection| connection.get(&self.key))
+    }
+
+    fn store(&self, checkpoints: &[u8]) -> io::Result<()> {
+        self.with_connection(|connection| connection.set(&self.key, checkpoints))
+    }
+}
+
+/// AWS S3 checkpoint store configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct S3CheckpointStoreConfig {
+    /// The S3 bucket name.
+    #[configurable(metadata(docs::examples = "my-bucket"))]
+    pub bucket: String,
+
+    /// The key of the S3 object the checkpoints are stored in.
+    #[configurable(metadata(docs::examples = "vector/checkpoints/my-agent.json"))]
+    pub key: String,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl S3CheckpointStoreConfig {
+    async fn build(&self, proxy: &ProxyConfig) -> crate::Result<Box<dyn CheckpointStore>> {
+        let client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        Ok(Box::new(S3CheckpointStore {
+            client,
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            handle: Handle::current(),
+        }))
+    }
+}
+
+struct S3CheckpointStore {
+    client: S3Client,
+    bucket: String,
+    key: String,
+    // Checkpoints are loaded and stored from blocking threads, which drive the requests on the
+    // runtime through this handle.
+    handle: Handle,
+}
+
+fn to_io_error(error: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
+impl CheckpointStore for S3CheckpointStore {
+    fn load(&self) -> io::Result<Option<Vec<u8>>> {
+        self.handle.block_on(async {
+            let object = self
+                .client
+                .get_object()
+                .bucket(&self.bucket)
+                .key(&self.key)
+                .send()
+                .await;
+            match object {
+                Ok(object) => {
+                    let data = object.body.collect().await.map_err(to_io_error)?;
+                    Ok(Some(data.into_bytes().to_vec()))
+                }
+                Err(SdkError::ServiceError { err, .. }) if err.is_no_such_key() => Ok(None),
+                Err(error) => Err(to_io_error(error)),
+            }
+        })
+    }
+
+    fn store(&self, checkpoints: &[u8]) -> io::Result<()> {
+        self.handle.block_on(async {
+            self.client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(&self.key)
+                .content_type("application/json")
+                .body(ByteStream::from(checkpoints.to_vec()))
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(to_io_error)
+        })
+    }
+}
+
+/// The attribute of the items of a DynamoDB checkpoint store holding the checkpoints.
+const DYNAMODB_CHECKPOINTS_ATTRIBUTE: &str = "checkpoints";
+
+/// AWS DynamoDB checkpoint store configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct DynamoDbCheckpointStoreConfig {
+    /// The name of the DynamoDB table.
+    ///
+    /// The table must have a string partition key named `key`, and no sort key. The checkpoints
+    /// are stored in the binary `checkpoints` attribute of the item.
+    #[configurable(metadata(docs::examples = "vector-checkpoints"))]
+    pub table: String,
+
+    /// The partition key of the item the checkpoints are stored in.
+    #[configurable(metadata(docs::examples = "my-agent"))]
+    pub key: String,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl DynamoDbCheckpointStoreConfig {
+    async fn build(&self, proxy: &ProxyConfig) -> crate::Result<Box<dyn CheckpointStore>> {
+        let client = create_client::<DynamoDbClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        Ok(Box::new(DynamoDbCheckpointStore {
+            client,
+            table: self.table.clone(),
+            key: self.key.clone(),
+            handle: Handle::current(),
+        }))
+    }
+}
+
+struct DynamoDbCheckpointStore {
+    client: DynamoDbClient,
+    table: String,
+    key: String,
+    // Checkpoints are loaded and stored from blocking threads, which drive the requests on the
+    // runtime through this handle.
+    handle: Handle,
+}
+
+impl CheckpointStore for DynamoDbCheckpointStore {
+    fn load(&self) -> io::Result<Option<Vec<u8>>> {
+        self.handle.block_on(async {
+            let item = self
+                .client
+                .get_item()
+                .table_name(&self.table)
+                .key("key", AttributeValue::S(self.key.clone()))
+                .consistent_read(true)
+                .send()
+                .await
+                .map_err(to_io_error)?
+                .item;
+            match item
+                .as_ref()
+                .and_then(|item| item.get(DYNAMODB_CHECKPOINTS_ATTRIBUTE))
+            {
+                None => Ok(None),
+                Some(AttributeValue::B(checkpoints)) => Ok(Some(checkpoints.as_ref().to_vec())),
+                Some(_) => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "The checkpoints attribute is not binary.",
+                )),
+            }
+        })
+    }
+
+    fn store(&self, checkpoints: &[u8]) -> io::Result<()> {
+        self.handle.block_on(async {
+            self.client
+                .put_item()
+                .table_name(&self.table)
+                .item("key", AttributeValue::S(self.key.clone()))
+                .item(
+                    DYNAMODB_CHECKPOINTS_ATTRIBUTE,
+                    AttributeValue::B(Blob::new(checkpoints)),
+                )
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(to_io_error)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_config() {
+        let config: CheckpointStoreConfig = toml::from_str(
+            r#"
+            type = "aws_s3"
+            bucket = "my-bucket"
+            key = "vector/checkpoints.json"
+            region = "us-east-1"
+            "#,
+        )
+        .unwrap();
+        assert!(
+            matches!(config, CheckpointStoreConfig::AwsS3(config) if config.bucket == "my-bucket")
+        );
+
+        let config: CheckpointStoreConfig = toml::from_str(
+            r#"
+            type = "redis"
+            endpoint = "redis://127.0.0.1:6379/0"
+            key = "vector:checkpoints"
+            "#,
+        )
+        .unwrap();
+        assert!(
+            matches!(config, CheckpointStoreConfig::Redis(config) if config.key == "vector:checkpoints")
+        );
+
+        let config: CheckpointStoreConfig = toml::from_str(
+            r#"
+            type = "aws_dynamodb"
+            table = "vector-checkpoints"
+            key = "my-agent"
+            region = "us-east-1"
+            "#,
+        )
+        .unwrap();
+        assert!(
+            matches!(config, CheckpointStoreConfig::AwsDynamodb(config) if config.table == "vector-checkpoints")
+        );
+    }
+}
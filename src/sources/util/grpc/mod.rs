@@ -12,16 +12,75 @@ use tonic::{
 };
 use tower::Service;
 use tracing::{Instrument, Span};
+use vector_config::configurable_component;
 
 mod decompression;
 pub use self::decompression::{DecompressionAndMetrics, DecompressionAndMetricsLayer};
 
+/// HTTP/2 settings of the gRPC server.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Http2Config {
+    /// The maximum number of concurrent streams on each connection.
+    ///
+    /// Clients multiplex their requests as streams of a single connection. If not set, the number
+    /// of streams is not limited.
+    #[configurable(metadata(docs::examples = 100))]
+    pub max_concurrent_streams: Option<u32>,
+
+    /// The initial flow control window size of each stream.
+    ///
+    /// If not set, the HTTP/2 default of 65535 bytes is used.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 1048576))]
+    pub initial_stream_window_size: Option<u32>,
+
+    /// The initial flow control window size of the connection.
+    ///
+    /// If not set, the HTTP/2 default of 65535 bytes is used.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 4194304))]
+    pub initial_connection_window_size: Option<u32>,
+
+    /// Whether to adapt the flow control windows to the measured bandwidth-delay product.
+    ///
+    /// When enabled, the window size settings are ignored.
+    #[serde(default)]
+    pub adaptive_window: bool,
+}
+
 pub async fn run_grpc_server<S>(
     address: SocketAddr,
     tls_settings: MaybeTlsSettings,
     service: S,
     shutdown: ShutdownSignal,
 ) -> crate::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + NamedService
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    run_grpc_server_with_http2(
+        address,
+        tls_settings,
+        service,
+        shutdown,
+        &Http2Config::default(),
+    )
+    .await
+}
+
+pub async fn run_grpc_server_with_http2<S>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    service: S,
+    shutdown: ShutdownSignal,
+    http2: &Http2Config,
+) -> crate::Result<()>
 where
     S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
         + NamedService
@@ -38,6 +97,10 @@ where
     info!(%address, "Building gRPC server.");
 
     Server::builder()
+        .max_concurrent_streams(http2.max_concurrent_streams)
+        .initial_stream_window_size(http2.initial_stream_window_size)
+        .initial_connection_window_size(http2.initial_connection_window_size)
+        .http2_adaptive_window(Some(http2.adaptive_window))
         .trace_fn(move |_| span.clone())
         // This layer explicitly decompresses payloads, if compressed, and reports the number of message bytes we've
         // received if the message is processed successfully, aka `BytesReceived`. We do this because otherwise the only
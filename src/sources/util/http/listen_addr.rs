@@ -0,0 +1,111 @@
+use std::{fmt, net::SocketAddr, path::PathBuf};
+
+use vector_config::configurable_component;
+
+use crate::{
+    config::Resource,
+    sources::util::net::{SocketListenAddr, SocketListenAddrParseError},
+};
+
+const UNIX_SCHEME: &str = "uds://";
+
+/// The address to listen for HTTP connections on.
+///
+/// This is either a socket address, which _must_ include a port, `systemd{#N}` to use the Nth
+/// socket passed by systemd socket activation, or `uds://` followed by the path of a Unix domain
+/// socket to create.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+#[serde(try_from = "String", into = "String")]
+#[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+#[configurable(metadata(docs::examples = "systemd"))]
+#[configurable(metadata(docs::examples = "uds:///var/run/vector/http.sock"))]
+pub enum HttpListenAddr {
+    /// A socket address or a socket passed by systemd.
+    Socket(SocketListenAddr),
+
+    /// The path of a Unix domain socket.
+    Unix(PathBuf),
+}
+
+impl HttpListenAddr {
+    /// Gets this listen address as a `Resource`, unless it is a Unix domain socket.
+    pub const fn as_tcp_resource(&self) -> Option<Resource> {
+        match self {
+            Self::Socket(addr) => Some(addr.as_tcp_resource()),
+            Self::Unix(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for HttpListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Socket(addr) => addr.fmt(f),
+            Self::Unix(path) => write!(f, "{}{}", UNIX_SCHEME, path.display()),
+        }
+    }
+}
+
+impl From<SocketListenAddr> for HttpListenAddr {
+    fn from(addr: SocketListenAddr) -> Self {
+        Self::Socket(addr)
+    }
+}
+
+impl From<SocketAddr> for HttpListenAddr {
+    fn from(addr: SocketAddr) -> Self {
+        Self::Socket(addr.into())
+    }
+}
+
+impl TryFrom<String> for HttpListenAddr {
+    type Error = SocketListenAddrParseError;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        match input.strip_prefix(UNIX_SCHEME) {
+            Some(path) if !path.is_empty() => Ok(Self::Unix(path.into())),
+            Some(_) => Err(SocketListenAddrParseError::UnableToParse),
+            None => SocketListenAddr::try_from(input).map(Into::into),
+        }
+    }
+}
+
+impl From<HttpListenAddr> for String {
+    fn from(addr: HttpListenAddr) -> String {
+        match addr {
+            HttpListenAddr::Socket(addr) => addr.into(),
+            HttpListenAddr::Unix(_) => addr.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        addr: HttpListenAddr,
+    }
+
+    #[test]
+    fn parse_http_listen_addr() {
+        let test: Config = toml::from_str(r#"addr="127.1.2.3:1234""#).unwrap();
+        assert_eq!(
+            test.addr,
+            HttpListenAddr::from("127.1.2.3:1234".parse::<SocketAddr>().unwrap())
+        );
+        let test: Config = toml::from_str(r#"addr="systemd#2""#).unwrap();
+        assert_eq!(test.addr, SocketListenAddr::SystemdFd(1).into());
+        let test: Config = toml::from_str(r#"addr="uds:///run/vector.sock""#).unwrap();
+        assert_eq!(test.addr, HttpListenAddr::Unix("/run/vector.sock".into()));
+        assert_eq!(String::from(test.addr), "uds:///run/vector.sock");
+
+        assert!(toml::from_str::<Config>(r#"addr="uds://""#).is_err());
+        assert!(toml::from_str::<Config>(r#"addr="127.1.2.3""#).is_err());
+    }
+}
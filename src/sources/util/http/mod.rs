@@ -4,6 +4,8 @@ mod auth;
 mod encoding;
 #[cfg(feature = "sources-utils-http-error")]
 mod error;
+#[cfg(feature = "sources-utils-http-prelude")]
+mod listen_addr;
 mod method;
 #[cfg(feature = "sources-utils-http-prelude")]
 mod prelude;
@@ -20,6 +22,8 @@ pub use auth::{HttpSourceAuth, HttpSourceAuthConfig};
 pub use encoding::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use error::ErrorMessage;
+#[cfg(feature = "sources-utils-http-prelude")]
+pub use listen_addr::HttpListenAddr;
 pub use method::HttpMethod;
 #[cfg(feature = "sources-utils-http-prelude")]
 pub use prelude::HttpSource;
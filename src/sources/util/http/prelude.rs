@@ -1,9 +1,10 @@
-use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
+use std::{collections::HashMap, convert::TryFrom, fmt};
 use vector_core::EstimatedJsonEncodedSizeOf;
 
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{FutureExt, TryFutureExt};
+use listenfd::ListenFd;
 use tracing::Span;
 use vector_core::{
     config::SourceAcknowledgementsConfig,
@@ -24,11 +25,17 @@ use crate::{
     internal_events::{
         HttpBadRequest, HttpBytesReceived, HttpEventsReceived, HttpInternalError, StreamClosedError,
     },
-    sources::util::http::HttpMethod,
+    sources::util::{
+        http::{HttpListenAddr, HttpMethod},
+        net::try_bind_tcp_listener,
+    },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
 
+#[cfg(unix)]
+use crate::internal_events::UnixSocketFileDeleteError;
+
 use super::{
     auth::{HttpSourceAuth, HttpSourceAuthConfig},
     encoding::decode,
@@ -60,7 +67,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
-        address: SocketAddr,
+        address: HttpListenAddr,
         path: &str,
         method: HttpMethod,
         strict_path: bool,
@@ -74,6 +81,14 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
         let path = path.to_owned();
         let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        if let HttpListenAddr::Unix(_) = &address {
+            if cfg!(not(unix)) {
+                return Err("Unix domain sockets are not supported on this platform.".into());
+            }
+            if tls.is_tls() {
+                return Err("TLS is not supported on Unix domain sockets.".into());
+            }
+        }
         Ok(Box::pin(async move {
             let span = Span::current();
             let mut filter: BoxedFilter<()> = match method {
@@ -173,19 +188,42 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
             info!(message = "Building HTTP server.", address = %address);
 
-            match tls.bind(&address).await {
-                Ok(listener) => {
+            match address {
+                HttpListenAddr::Socket(address) => {
+                    let listenfd = ListenFd::from_env();
+                    match try_bind_tcp_listener(address, listenfd, &tls).await {
+                        Ok(listener) => {
+                            warp::serve(routes)
+                                .serve_incoming_with_graceful_shutdown(
+                                    listener.accept_stream(),
+                                    cx.shutdown.map(|_| ()),
+                                )
+                                .await;
+                        }
+                        Err(error) => {
+                            error!("An error occurred: {:?}.", error);
+                            return Err(());
+                        }
+                    }
+                }
+                #[cfg(unix)]
+                HttpListenAddr::Unix(path) => {
+                    let listener = tokio::net::UnixListener::bind(&path).map_err(|error| {
+                        error!(message = "Failed to bind to Unix socket.", path = ?path, %error);
+                    })?;
                     warp::serve(routes)
                         .serve_incoming_with_graceful_shutdown(
-                            listener.accept_stream(),
+                            tokio_stream::wrappers::UnixListenerStream::new(listener),
                             cx.shutdown.map(|_| ()),
                         )
                         .await;
+
+                    if let Err(error) = std::fs::remove_file(&path) {
+                        emit!(UnixSocketFileDeleteError { path: &path, error });
+                    }
                 }
-                Err(error) => {
-                    error!("An error occurred: {:?}.", error);
-                    return Err(());
-                }
+                #[cfg(not(unix))]
+                HttpListenAddr::Unix(_) => unreachable!("Checked when building the source."),
             }
             Ok(())
         }))
@@ -1,6 +1,8 @@
 #![allow(missing_docs)]
 #[cfg(any(feature = "sources-http_server"))]
 mod body_decoding;
+#[cfg(feature = "sources-utils-checkpoint-stores")]
+pub mod checkpoint_store;
 mod encoding_config;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
@@ -1,6 +1,6 @@
 mod request_limiter;
 
-use std::{collections::BTreeMap, io, mem::drop, net::SocketAddr, time::Duration};
+use std::{collections::BTreeMap, future::Future, io, mem::drop, net::SocketAddr, time::Duration};
 
 use bytes::Bytes;
 use codecs::StreamDecodingError;
@@ -11,9 +11,9 @@ use lookup::{path, OwnedValuePath};
 use smallvec::SmallVec;
 use socket2::SockRef;
 use tokio::{
-    io::AsyncWriteExt,
+    io::{AsyncWrite, AsyncWriteExt},
     net::{TcpListener, TcpStream},
-    time::sleep,
+    time::{interval_at, sleep, Instant},
 };
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing::Instrument;
@@ -35,6 +35,7 @@ use crate::{
         TcpSocketTlsConnectionError,
     },
     shutdown::ShutdownSignal,
+    source_sender::ClosedError,
     sources::util::AfterReadExt,
     tcp::TcpKeepaliveConfig,
     tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
@@ -43,7 +44,10 @@ use crate::{
 
 const MAX_IN_FLIGHT_EVENTS_TARGET: usize = 100_000;
 
-async fn try_bind_tcp_listener(
+/// How often the keepalive of an acker is written to a client while its batch is being delivered.
+const ACK_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+pub async fn try_bind_tcp_listener(
     addr: SocketListenAddr,
     mut listenfd: ListenFd,
     tls: &MaybeTlsSettings,
@@ -70,6 +74,13 @@ pub enum TcpSourceAck {
 
 pub trait TcpSourceAcker {
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes>;
+
+    /// Builds the message written to the client periodically while its batch is still being
+    /// delivered, letting it know the connection is alive even when the source is applying
+    /// backpressure.
+    fn build_keepalive(&self) -> Option<Bytes> {
+        None
+    }
 }
 
 pub struct TcpNullAcker;
@@ -100,8 +111,23 @@ where
 
     fn decoder(&self) -> Self::Decoder;
 
+    /// Builds the greeting written to a client as soon as it connects, before anything is read
+    /// from it, given the decoder of its connection.
+    fn build_greeting(&self, _decoder: &Self::Decoder) -> Option<Bytes> {
+        None
+    }
+
     fn handle_events(&self, _events: &mut [Event], _host: std::net::SocketAddr) {}
 
+    /// Builds the `tls_client_metadata` inserted into the events received from a client which
+    /// presented a certificate.
+    fn build_tls_client_metadata(
+        &self,
+        certificate: &CertificateMetadata,
+    ) -> BTreeMap<String, value::Value> {
+        BTreeMap::from([("subject".to_string(), certificate.subject().into())])
+    }
+
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
     #[allow(clippy::too_many_arguments)]
@@ -273,6 +299,14 @@ async fn handle_stream<T>(
         }
     }
 
+    let decoder = source.decoder();
+    if let Some(greeting) = source.build_greeting(&decoder) {
+        if let Err(error) = socket.write_all(&greeting).await {
+            emit!(TcpSendAckError { error });
+            return;
+        }
+    }
+
     let socket = socket.after_read(move |byte_size| {
         emit!(TcpBytesReceived {
             byte_size,
@@ -280,13 +314,13 @@ async fn handle_stream<T>(
         });
     });
 
-    let certificate_metadata = socket
+    let tls_client_metadata = socket
         .get_ref()
         .ssl_stream()
         .and_then(|stream| stream.ssl().peer_certificate())
-        .map(CertificateMetadata::from);
+        .map(|certificate| source.build_tls_client_metadata(&certificate.into()));
 
-    let reader = FramedRead::new(socket, source.decoder());
+    let reader = FramedRead::new(socket, decoder);
     let mut reader = ReadyFrames::new(reader);
 
     let connection_close_timeout = OptionFuture::from(
@@ -362,9 +396,7 @@ async fn handle_stream<T>(
                         }
 
 
-                        if let Some(certificate_metadata) = &certificate_metadata {
-                            let mut metadata: BTreeMap<String, value::Value> = BTreeMap::new();
-                            metadata.insert("subject".to_string(), certificate_metadata.subject().into());
+                        if let Some(metadata) = &tls_client_metadata {
                             for event in &mut events {
                                 let log = event.as_mut_log();
 
@@ -379,20 +411,24 @@ async fn handle_stream<T>(
                         }
 
                         source.handle_events(&mut events, peer_addr);
-                        match out.send_batch(events).await {
-                            Ok(_) => {
-                                let ack = match receiver {
-                                    None => TcpSourceAck::Ack,
-                                    Some(receiver) =>
-                                        match receiver.await {
-                                            BatchStatus::Delivered => TcpSourceAck::Ack,
-                                            BatchStatus::Errored => {TcpSourceAck::Error},
-                                            BatchStatus::Rejected => {
-                                                // Sinks are responsible for emitting ComponentEventsDropped.
-                                                TcpSourceAck::Reject
-                                            }
+                        let delivery = async {
+                            out.send_batch(events).await?;
+                            Ok::<_, ClosedError>(match receiver {
+                                None => TcpSourceAck::Ack,
+                                Some(receiver) =>
+                                    match receiver.await {
+                                        BatchStatus::Delivered => TcpSourceAck::Ack,
+                                        BatchStatus::Errored => {TcpSourceAck::Error},
+                                        BatchStatus::Rejected => {
+                                            // Sinks are responsible for emitting ComponentEventsDropped.
+                                            TcpSourceAck::Reject
                                         }
-                                };
+                                    }
+                            })
+                        };
+                        let keepalive = acker.build_keepalive();
+                        match with_keepalive(delivery, keepalive, reader.get_mut().get_mut()).await {
+                            Ok(Ok(ack)) => {
                                 if let Some(ack_bytes) = acker.build_ack(ack){
                                     let stream = reader.get_mut().get_mut();
                                     if let Err(error) = stream.write_all(&ack_bytes).await {
@@ -404,10 +440,14 @@ async fn handle_stream<T>(
                                     break;
                                 }
                             }
-                            Err(error) => {
+                            Ok(Err(error)) => {
                                 emit!(StreamClosedError { error, count });
                                 break;
                             }
+                            Err(error) => {
+                                emit!(TcpSendAckError { error });
+                                break;
+                            }
                         }
                     }
                     Some(Err(error)) => {
@@ -429,6 +469,34 @@ async fn handle_stream<T>(
     }
 }
 
+/// Drives `future` to completion, writing `keepalive` to `stream` every `ACK_KEEPALIVE_INTERVAL`
+/// in the meantime.
+async fn with_keepalive<F, S>(
+    future: F,
+    keepalive: Option<Bytes>,
+    stream: &mut S,
+) -> io::Result<F::Output>
+where
+    F: Future,
+    S: AsyncWrite + Unpin,
+{
+    let Some(keepalive) = keepalive else {
+        return Ok(future.await);
+    };
+
+    tokio::pin!(future);
+    let mut interval = interval_at(
+        Instant::now() + ACK_KEEPALIVE_INTERVAL,
+        ACK_KEEPALIVE_INTERVAL,
+    );
+    loop {
+        tokio::select! {
+            output = &mut future => return Ok(output),
+            _ = interval.tick() => stream.write_all(&keepalive).await?,
+        }
+    }
+}
+
 fn close_socket(socket: &MaybeTlsIncomingStream<TcpStream>) -> bool {
     debug!("Start graceful shutdown.");
     // Close our write part of TCP socket to signal the other side
@@ -21,7 +21,10 @@ use crate::{
     internal_events::{EventsReceived, StreamClosedError},
     proto::vector as proto,
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{
+        util::grpc::{run_grpc_server_with_http2, Http2Config},
+        Source,
+    },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
@@ -134,6 +137,11 @@ pub struct VectorConfig {
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    http2: Http2Config,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -157,6 +165,7 @@ impl Default for VectorConfig {
             address: "0.0.0.0:6000".parse().unwrap(),
             tls: None,
             acknowledgements: Default::default(),
+            http2: Default::default(),
             log_namespace: None,
         }
     }
@@ -183,10 +192,16 @@ impl SourceConfig for VectorConfig {
         })
         .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
 
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
+        let source = run_grpc_server_with_http2(
+            self.address,
+            tls_settings,
+            service,
+            cx.shutdown,
+            &self.http2,
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
 
         Ok(Box::pin(source))
     }
@@ -344,4 +359,18 @@ mod tests {
         })
         .await;
     }
+    #[tokio::test]
+    async fn receive_zstd_compressed_message() {
+        let addr = test_util::next_addr();
+
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let config = format!(
+                r#"address = "{}"
+            compression = "zstd""#,
+                addr
+            );
+            run_test(&config, addr).await;
+        })
+        .await;
+    }
 }
@@ -0,0 +1,473 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures::StreamExt;
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
+use serde_with::serde_as;
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use value::{kind::Collection, Kind, Value};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{
+        metric::{Metric, MetricValue},
+        Event, LogEvent,
+    },
+    internal_events::{EventsReceived, StreamClosedError},
+    metrics::Controller,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// The fields of the events counting what happened during the interval.
+const COUNT_FIELDS: [&str; 6] = [
+    "received_events",
+    "received_event_bytes",
+    "sent_events",
+    "sent_event_bytes",
+    "errors",
+    "discarded_events",
+];
+
+/// Configuration for the `vector_stats` source.
+#[serde_as]
+#[configurable_component(source(
+    "vector_stats",
+    "Summarize the health of the components of the running Vector instance as log events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VectorStatsConfig {
+    /// The interval between summaries, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    #[serde(default = "default_interval")]
+    pub interval_secs: Duration,
+
+    /// Overrides the name of the log field used to add the current hostname to each event.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// Set to `""` to suppress this key.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    #[serde(default = "default_host_key")]
+    host_key: OptionalValuePath,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_host_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!(log_schema().host_key()))
+}
+
+impl Default for VectorStatsConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_interval(),
+            host_key: default_host_key(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(VectorStatsConfig);
+
+impl VectorStatsConfig {
+    /// Generates the `schema::Definition` for this component.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let host_key = self.host_key.clone().path.map(LegacyKey::Overwrite);
+
+        let mut definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            VectorStatsConfig::NAME,
+            host_key,
+            &owned_value_path!("host"),
+            Kind::bytes().or_undefined(),
+            Some("host"),
+        )
+        .with_event_field(&owned_value_path!("component_id"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("component_kind"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("component_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("interval_secs"), Kind::float(), None)
+        .with_event_field(
+            &owned_value_path!("received_events_per_second"),
+            Kind::float(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("sent_events_per_second"),
+            Kind::float(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("buffer_events"),
+            Kind::integer().or_undefined(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("buffer_byte_size"),
+            Kind::integer().or_undefined(),
+            None,
+        );
+        for field in COUNT_FIELDS {
+            definition =
+                definition.with_event_field(&owned_value_path!(field), Kind::integer(), None);
+        }
+        definition
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "vector_stats")]
+impl SourceConfig for VectorStatsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.interval_secs.is_zero() {
+            return Err("`interval_secs` must be greater than zero.".into());
+        }
+
+        let stats = VectorStats {
+            controller: Controller::get()?,
+            interval: self.interval_secs,
+            host_key: self.host_key.clone().path,
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+        Ok(Box::pin(stats.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// The counters of a component, as totals since it started.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct ComponentTotals {
+    received_events: f64,
+    received_event_bytes: f64,
+    sent_events: f64,
+    sent_event_bytes: f64,
+    errors: f64,
+    discarded_events: f64,
+}
+
+impl ComponentTotals {
+    const fn as_array(&self) -> [f64; 6] {
+        [
+            self.received_events,
+            self.received_event_bytes,
+            self.sent_events,
+            self.sent_event_bytes,
+            self.errors,
+            self.discarded_events,
+        ]
+    }
+}
+
+/// The telemetry of a component, gathered from the internal metrics.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ComponentStats {
+    kind: String,
+    component_type: String,
+    totals: ComponentTotals,
+    buffer_events: Option<f64>,
+    buffer_byte_size: Option<f64>,
+}
+
+impl ComponentStats {
+    fn add(&mut self, name: &str, value: &MetricValue) {
+        match (name, value) {
+            ("component_received_events_total", MetricValue::Counter { value }) => {
+                self.totals.received_events += value
+            }
+            ("component_received_event_bytes_total", MetricValue::Counter { value }) => {
+                self.totals.received_event_bytes += value
+            }
+            ("component_sent_events_total", MetricValue::Counter { value }) => {
+                self.totals.sent_events += value
+            }
+            ("component_sent_event_bytes_total", MetricValue::Counter { value }) => {
+                self.totals.sent_event_bytes += value
+            }
+            ("component_errors_total", MetricValue::Counter { value }) => {
+                self.totals.errors += value
+            }
+            ("component_discarded_events_total", MetricValue::Counter { value }) => {
+                self.totals.discarded_events += value
+            }
+            ("buffer_events", MetricValue::Gauge { value }) => {
+                *self.buffer_events.get_or_insert(0.0) += value
+            }
+            ("buffer_byte_size", MetricValue::Gauge { value }) => {
+                *self.buffer_byte_size.get_or_insert(0.0) += value
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Groups the internal metrics by the component they were emitted by.
+fn collect_stats(metrics: Vec<Metric>) -> BTreeMap<String, ComponentStats> {
+    let mut stats = BTreeMap::<String, ComponentStats>::new();
+    for metric in metrics {
+        let Some(tags) = metric.tags() else { continue };
+        let Some(id) = tags.get("component_id") else { continue };
+        let component = stats
+            .entry(id.to_owned())
+            .or_insert_with(|| ComponentStats {
+                kind: tags.get("component_kind").unwrap_or_default().to_owned(),
+                component_type: tags.get("component_type").unwrap_or_default().to_owned(),
+                ..Default::default()
+            });
+        component.add(metric.name(), metric.value());
+    }
+    stats
+}
+
+struct VectorStats {
+    controller: &'static Controller,
+    interval: Duration,
+    host_key: Option<OwnedValuePath>,
+    log_namespace: LogNamespace,
+}
+
+impl VectorStats {
+    async fn run(self, mut out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+
+        // The first summary covers the first interval, not the time since Vector started.
+        let mut previous = totals(&collect_stats(self.controller.capture_metrics()));
+        let mut previous_at = Instant::now();
+
+        let start = time::Instant::now() + self.interval;
+        let mut interval =
+            IntervalStream::new(time::interval_at(start, self.interval)).take_until(shutdown);
+        while interval.next().await.is_some() {
+            let stats = collect_stats(self.controller.capture_metrics());
+            let now = Instant::now();
+            let elapsed = now.duration_since(previous_at).as_secs_f64();
+
+            let events = stats
+                .iter()
+                .map(|(id, component)| {
+                    let previous = previous.get(id).copied().unwrap_or_default();
+                    Event::from(self.summarize(id, component, &previous, elapsed))
+                })
+                .collect::<Vec<_>>();
+            previous = totals(&stats);
+            previous_at = now;
+
+            let count = events.len();
+            bytes_received.emit(ByteSize(0));
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn summarize(
+        &self,
+        id: &str,
+        component: &ComponentStats,
+        previous: &ComponentTotals,
+        elapsed: f64,
+    ) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("component_id", id);
+        log.insert("component_kind", component.kind.as_str());
+        log.insert("component_type", component.component_type.as_str());
+        log.insert("interval_secs", Value::from(elapsed));
+
+        let (totals, previous) = (component.totals.as_array(), previous.as_array());
+        let counts: [f64; 6] = std::array::from_fn(|index| {
+            // The counters restart from zero when components are reloaded, or when their
+            // metrics expire.
+            if totals[index] >= previous[index] {
+                totals[index] - previous[index]
+            } else {
+                totals[index]
+            }
+        });
+        for (field, count) in COUNT_FIELDS.iter().zip(counts) {
+            log.insert(*field, count as i64);
+        }
+
+        // The counts are in the order of `COUNT_FIELDS`.
+        let (received_events, sent_events) = (counts[0], counts[2]);
+        let per_second = |count: f64| if elapsed > 0.0 { count / elapsed } else { 0.0 };
+        log.insert(
+            "received_events_per_second",
+            Value::from(per_second(received_events)),
+        );
+        log.insert(
+            "sent_events_per_second",
+            Value::from(per_second(sent_events)),
+        );
+
+        if let Some(buffer_events) = component.buffer_events {
+            log.insert("buffer_events", buffer_events as i64);
+        }
+        if let Some(buffer_byte_size) = component.buffer_byte_size {
+            log.insert("buffer_byte_size", buffer_byte_size as i64);
+        }
+
+        if let Ok(hostname) = crate::get_hostname() {
+            self.log_namespace.insert_source_metadata(
+                VectorStatsConfig::NAME,
+                &mut log,
+                self.host_key.as_ref().map(LegacyKey::Overwrite),
+                path!("host"),
+                hostname,
+            );
+        }
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            VectorStatsConfig::NAME,
+            Utc::now(),
+        );
+
+        log
+    }
+}
+
+fn totals(stats: &BTreeMap<String, ComponentStats>) -> HashMap<String, ComponentTotals> {
+    stats
+        .iter()
+        .map(|(id, component)| (id.clone(), component.totals))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::{event::MetricKind, metric_tags};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VectorStatsConfig>();
+    }
+
+    fn counter(name: &str, id: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value }).with_tags(Some(
+            metric_tags! {
+                "component_id" => id,
+                "component_kind" => "sink",
+                "component_type" => "http",
+            },
+        ))
+    }
+
+    #[test]
+    fn collects_stats_by_component() {
+        let buffer = Metric::new(
+            "buffer_events",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 7.0 },
+        )
+        .with_tags(Some(metric_tags! {
+            "component_id" => "out",
+            "stage" => "0",
+        }));
+        let stats = collect_stats(vec![
+            counter("component_received_events_total", "out", 10.0),
+            counter("component_errors_total", "out", 1.0),
+            counter("component_errors_total", "out", 2.0),
+            counter("component_sent_events_total", "other", 4.0),
+            buffer,
+            Metric::new(
+                "uptime_seconds",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 1.0 },
+            ),
+        ]);
+
+        assert_eq!(stats.len(), 2);
+        let out = &stats["out"];
+        assert_eq!(out.kind, "sink");
+        assert_eq!(out.component_type, "http");
+        assert_eq!(out.totals.received_events, 10.0);
+        assert_eq!(out.totals.errors, 3.0);
+        assert_eq!(out.buffer_events, Some(7.0));
+        assert_eq!(out.buffer_byte_size, None);
+        assert_eq!(stats["other"].totals.sent_events, 4.0);
+    }
+
+    #[test]
+    fn summarizes_the_interval() {
+        crate::metrics::init_test();
+        let stats = VectorStats {
+            controller: Controller::get().unwrap(),
+            interval: Duration::from_secs(10),
+            host_key: None,
+            log_namespace: LogNamespace::Legacy,
+        };
+        let component = ComponentStats {
+            kind: "sink".into(),
+            component_type: "http".into(),
+            totals: ComponentTotals {
+                received_events: 150.0,
+                sent_events: 120.0,
+                errors: 2.0,
+                ..Default::default()
+            },
+            buffer_events: Some(30.0),
+            buffer_byte_size: None,
+        };
+        let previous = ComponentTotals {
+            received_events: 50.0,
+            sent_events: 20.0,
+            errors: 5.0,
+            ..Default::default()
+        };
+
+        let log = stats.summarize("out", &component, &previous, 10.0);
+        assert_eq!(log["component_id"], "out".into());
+        assert_eq!(log["received_events"], 100.into());
+        assert_eq!(log["sent_events"], 100.into());
+        assert_eq!(log["received_events_per_second"], Value::from(10.0));
+        // The errors counter was reset since the previous summary.
+        assert_eq!(log["errors"], 2.into());
+        assert_eq!(log["buffer_events"], 30.into());
+        assert!(log.get("buffer_byte_size").is_none());
+        assert_eq!(log["source_type"], "vector_stats".into());
+    }
+}
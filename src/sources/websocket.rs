@@ -0,0 +1,535 @@
+use std::{
+    num::NonZeroU64,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::{SinkExt, StreamExt};
+use snafu::ResultExt;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tokio_util::codec::FramedRead;
+use value::Value;
+use vector_common::{
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+        Registered,
+    },
+    TimeZone,
+};
+use vector_config::configurable_component;
+use vector_core::{compile_vrl, config::LogNamespace, EstimatedJsonEncodedSizeOf};
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    common::websocket::{is_closed, ConnectSnafu, PingInterval, WebSocketConnector},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::{Event, TargetEvents, VrlTarget},
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, OpenGauge, StreamClosedError, WsConnectionError, WsConnectionShutdown,
+        WsReceiveError, WsResumeTokenError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+/// Configuration for the `websocket` source.
+#[configurable_component(source(
+    "websocket",
+    "Collect events from a WebSocket server, such as a streaming API."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketSourceConfig {
+    /// The WebSocket URI to connect to.
+    ///
+    /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    #[configurable(metadata(docs::examples = "wss://stream.example.com/v1/events"))]
+    uri: String,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    /// Text messages sent to the server each time the connection is established.
+    ///
+    /// This can be used to subscribe to the channels of a streaming API.
+    #[configurable(metadata(docs::examples = r#"{"type": "subscribe", "channels": ["trades"]}"#))]
+    #[serde(default)]
+    initial_messages: Vec<String>,
+
+    /// The interval, in seconds, between sending [Ping][ping]s to the remote peer.
+    ///
+    /// If this option is not configured, pings are not sent on an interval. Pings received from
+    /// the remote peer are always answered.
+    ///
+    /// [ping]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.2
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ping_interval: Option<NonZeroU64>,
+
+    /// The number of seconds to wait for a [Pong][pong] response from the remote peer.
+    ///
+    /// If a response is not received within this time, the connection is re-established.
+    ///
+    /// [pong]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.3
+    // NOTE: this option is not relevant if the `ping_interval` is not configured.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ping_timeout: Option<NonZeroU64>,
+
+    #[configurable(derived)]
+    resume: Option<ResumeConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// Resumption of the stream when reconnecting.
+///
+/// A resume token is read from each event, and passed to the server when reconnecting so that it
+/// resumes the stream after the last event received, rather than from its current position.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ResumeConfig {
+    /// The [VRL][vrl] expression reading the resume token from an event.
+    ///
+    /// The expression cannot modify the event. Events for which it returns `null` do not update
+    /// the resume token.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(docs::examples = ".sequence"))]
+    #[configurable(metadata(docs::examples = ".metadata.cursor"))]
+    token: String,
+
+    /// The query parameter of the URI the resume token is passed in when reconnecting.
+    #[serde(default = "default_query_parameter")]
+    #[configurable(metadata(docs::examples = "cursor"))]
+    query_parameter: String,
+}
+
+fn default_query_parameter() -> String {
+    "resume_token".to_owned()
+}
+
+impl GenerateConfig for WebSocketSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"uri = "wss://stream.example.com/v1/events"
+            decoding.codec = "json"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket")]
+impl SourceConfig for WebSocketSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(ConnectSnafu)?;
+        // Validates the URI before the source is started.
+        WebSocketConnector::new(self.uri.clone(), tls.clone(), self.auth.clone())?;
+
+        let resume = self
+            .resume
+            .as_ref()
+            .map(|resume| {
+                Ok::<_, crate::Error>(ResumeToken {
+                    program: compile_token(&resume.token)?,
+                    source: resume.token.clone(),
+                    query_parameter: resume.query_parameter.clone(),
+                    value: None,
+                })
+            })
+            .transpose()?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+
+        let source = WebSocketSource {
+            config: self.clone(),
+            tls,
+            resume,
+            decoder,
+            log_namespace,
+            out: cx.out,
+            bytes_received: register!(BytesReceived::from(Protocol("websocket".into()))),
+            events_received: register!(EventsReceived),
+        };
+        Ok(Box::pin(source.run(cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn compile_token(source: &str) -> crate::Result<Program> {
+    let mut functions = vrl_stdlib::all()
+        .into_iter()
+        .chain(vector_vrl_functions::all())
+        .collect::<Vec<_>>();
+    vector_core::fips::retain_vrl_functions(&mut functions);
+
+    let state = vrl::state::TypeState::default();
+
+    let mut config = CompileConfig::default();
+    config.set_read_only();
+
+    let CompilationResult {
+        program,
+        warnings,
+        config: _,
+    } = compile_vrl(source, &functions, &state, config)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+/// The resume token of the stream, read from the events received.
+struct ResumeToken {
+    program: Program,
+    source: String,
+    query_parameter: String,
+    value: Option<String>,
+}
+
+impl ResumeToken {
+    fn read(&self, event: Event) -> (Option<String>, Event) {
+        let mut target = VrlTarget::new(event, self.program.info(), false);
+        let result = Runtime::default().resolve(&mut target, &self.program, &TimeZone::default());
+        let event = match target.into_events() {
+            TargetEvents::One(event) => event,
+            _ => panic!(
+                "Event was modified by the resume token expression. This is an internal compiler error."
+            ),
+        };
+
+        let token = match result {
+            Ok(Value::Null) => None,
+            Ok(value) => Some(value.to_string_lossy().into_owned()),
+            Err(error) => {
+                emit!(WsResumeTokenError {
+                    error: &error.to_string(),
+                    source: &self.source,
+                });
+                None
+            }
+        };
+        (token, event)
+    }
+
+    /// Passes the token in the query of the URI, replacing the one of a previous connection.
+    fn apply(&self, uri: &str) -> String {
+        let Some(token) = &self.value else {
+            return uri.to_owned();
+        };
+        let Ok(mut url) = url::Url::parse(uri) else {
+            return uri.to_owned();
+        };
+
+        let pairs = url
+            .query_pairs()
+            .filter(|(name, _)| name != self.query_parameter.as_str())
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect::<Vec<_>>();
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(pairs)
+            .append_pair(&self.query_parameter, token);
+        url.to_string()
+    }
+}
+
+struct WebSocketSource {
+    config: WebSocketSourceConfig,
+    tls: MaybeTlsSettings,
+    resume: Option<ResumeToken>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl WebSocketSource {
+    async fn run(mut self, shutdown: ShutdownSignal) -> Result<(), ()> {
+        'connection: loop {
+            let uri = match &self.resume {
+                Some(resume) => resume.apply(&self.config.uri),
+                None => self.config.uri.clone(),
+            };
+            let connector =
+                WebSocketConnector::new(uri, self.tls.clone(), self.config.auth.clone())
+                    .map_err(|error| error!(message = "Invalid WebSocket URI.", %error))?;
+
+            let ws_stream = tokio::select! {
+                ws_stream = connector.connect_backoff() => ws_stream,
+                _ = shutdown.clone() => return Ok(()),
+            };
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+            let (mut ws_sink, mut ws_stream) = ws_stream.split();
+
+            for message in &self.config.initial_messages {
+                if let Err(error) = ws_sink.send(Message::text(message.clone())).await {
+                    emit!(WsConnectionError { error });
+                    continue 'connection;
+                }
+            }
+
+            // tokio::time::Interval panics if the period arg is zero. Since the struct members are
+            // using NonZeroU64 that is not something we need to account for.
+            let mut ping_interval = PingInterval::new(self.config.ping_interval.map(u64::from));
+            let mut last_pong = Instant::now();
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.clone() => {
+                        let _ = ws_sink.close().await;
+                        return Ok(());
+                    }
+
+                    _ = ping_interval.tick() => {
+                        if self.pong_timed_out(last_pong) {
+                            warn!(message = "Pong not received in time; reconnecting.");
+                            break;
+                        }
+                        if let Err(error) = ws_sink.send(Message::Ping(b"PING".to_vec())).await {
+                            emit!(WsConnectionError { error });
+                            break;
+                        }
+                    }
+
+                    // Pongs are sent automatically by tungstenite while reading from the stream.
+                    message = ws_stream.next() => match message {
+                        Some(Ok(Message::Text(text))) => self.handle_message(text.as_bytes()).await?,
+                        Some(Ok(Message::Binary(data))) => self.handle_message(&data).await?,
+                        Some(Ok(Message::Pong(_))) => last_pong = Instant::now(),
+                        Some(Ok(Message::Ping(_) | Message::Frame(_))) => {}
+                        Some(Ok(Message::Close(_))) | None => {
+                            emit!(WsConnectionShutdown);
+                            break;
+                        }
+                        Some(Err(error)) => {
+                            if is_closed(&error) {
+                                emit!(WsConnectionShutdown);
+                            } else {
+                                emit!(WsReceiveError { error });
+                            }
+                            break;
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    fn pong_timed_out(&self, last_pong: Instant) -> bool {
+        self.config.ping_timeout.map_or(false, |ping_timeout| {
+            last_pong.elapsed() > Duration::from_secs(ping_timeout.into())
+        })
+    }
+
+    async fn handle_message(&mut self, data: &[u8]) -> Result<(), ()> {
+        self.bytes_received.emit(ByteSize(data.len()));
+
+        let mut stream = FramedRead::new(data, self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    let count = events.len();
+                    let byte_size = events.estimated_json_encoded_size_of();
+                    self.events_received.emit(CountByteSize(count, byte_size));
+
+                    let now = Utc::now();
+                    let mut token = None;
+                    let events = events
+                        .into_iter()
+                        .map(|mut event| {
+                            if let Event::Log(ref mut log) = event {
+                                self.log_namespace.insert_standard_vector_source_metadata(
+                                    log,
+                                    WebSocketSourceConfig::NAME,
+                                    now,
+                                );
+                            }
+                            match &self.resume {
+                                Some(resume) => {
+                                    let (event_token, event) = resume.read(event);
+                                    token = event_token.or(token.take());
+                                    event
+                                }
+                                None => event,
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    self.out.send_batch(events).await.map_err(|error| {
+                        emit!(StreamClosedError { error, count });
+                    })?;
+
+                    // The token is only updated once its events are sent, so that the stream is
+                    // resumed from the last event that made it through.
+                    if let (Some(resume), Some(token)) = (&mut self.resume, token) {
+                        resume.value = Some(token);
+                    }
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{
+        accept_hdr_async,
+        tungstenite::handshake::server::{Request, Response},
+    };
+
+    use super::*;
+    use crate::test_util::{collect_n, next_addr, trace_init};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketSourceConfig>();
+    }
+
+    fn resume_token(value: Option<&str>) -> ResumeToken {
+        ResumeToken {
+            program: compile_token(".sequence").unwrap(),
+            source: ".sequence".to_owned(),
+            query_parameter: "cursor".to_owned(),
+            value: value.map(ToOwned::to_owned),
+        }
+    }
+
+    #[test]
+    fn passes_resume_token_in_query() {
+        let uri = "wss://stream.example.com/v1/events?channel=trades&cursor=1";
+        assert_eq!(resume_token(None).apply(uri), uri);
+        assert_eq!(
+            resume_token(Some("42")).apply(uri),
+            "wss://stream.example.com/v1/events?channel=trades&cursor=42"
+        );
+        assert_eq!(
+            resume_token(Some("a b")).apply("ws://localhost:8080"),
+            "ws://localhost:8080/?cursor=a+b"
+        );
+    }
+
+    #[test]
+    fn reads_resume_token_from_events() {
+        let resume = resume_token(None);
+        let mut log = crate::event::LogEvent::default();
+        log.insert("sequence", 7);
+        let (token, _) = resume.read(log.into());
+        assert_eq!(token.as_deref(), Some("7"));
+
+        let (token, _) = resume.read(crate::event::LogEvent::default().into());
+        assert_eq!(token, None);
+    }
+
+    #[tokio::test]
+    async fn receives_messages_and_resumes_after_reconnecting() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let server_requests = Arc::clone(&requests);
+        tokio::spawn(async move {
+            for sequence in 1..=2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let requests = Arc::clone(&server_requests);
+                let mut ws = accept_hdr_async(stream, |request: &Request, response: Response| {
+                    requests.lock().unwrap().push(request.uri().to_string());
+                    Ok(response)
+                })
+                .await
+                .unwrap();
+
+                let subscription = ws.next().await.unwrap().unwrap();
+                assert_eq!(subscription, Message::text("subscribe"));
+                ws.send(Message::text(format!(
+                    r#"{{"sequence": {}, "message": "event {}"}}"#,
+                    sequence, sequence
+                )))
+                .await
+                .unwrap();
+                ws.close(None).await.unwrap();
+            }
+        });
+
+        let config = toml::from_str::<WebSocketSourceConfig>(&format!(
+            r#"
+            uri = "ws://{}/events"
+            initial_messages = ["subscribe"]
+            decoding.codec = "json"
+            resume.token = ".sequence"
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 2).await;
+        assert_eq!(events[0].as_log()["message"], "event 1".into());
+        assert_eq!(events[1].as_log()["message"], "event 2".into());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["/events", "/events?resume_token=1"]
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use std::net::SocketAddr;
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tokio_util::codec::FramedRead;
+use tracing::Instrument;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    common::websocket::{accept, is_closed},
+    config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, OpenGauge, SocketBindError, SocketMode, SocketReceiveError,
+        StreamClosedError, TcpSocketConnectionEstablished, WsHandshakeError, WsReceiveError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+/// Configuration for the `websocket_server` source.
+#[configurable_component(source(
+    "websocket_server",
+    "Receive events from the clients connected to a WebSocket server."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketServerSourceConfig {
+    /// The socket address to listen for WebSocket connections on.
+    #[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+    address: SocketAddr,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for WebSocketServerSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "0.0.0.0:8080"
+            decoding.codec = "json"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket_server")]
+impl SourceConfig for WebSocketServerSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+
+        let source = WebSocketServerSource {
+            auth: self.auth.clone(),
+            decoder,
+            log_namespace,
+            out: cx.out,
+        };
+        Ok(Box::pin(source.run(self.address, tls, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone)]
+struct WebSocketServerSource {
+    auth: Option<Auth>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+}
+
+impl WebSocketServerSource {
+    async fn run(
+        self,
+        address: SocketAddr,
+        tls: MaybeTlsSettings,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let listener = tls.bind(&address).await.map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Tcp,
+                error,
+            })
+        })?;
+        info!(message = "Listening.", addr = %address);
+
+        let connection_gauge = OpenGauge::new();
+        let mut connections = listener.accept_stream().take_until(shutdown.clone());
+        while let Some(connection) = connections.next().await {
+            let stream = match connection {
+                Ok(stream) => stream,
+                Err(error) => {
+                    emit!(SocketReceiveError {
+                        mode: SocketMode::Tcp,
+                        error: &error
+                    });
+                    continue;
+                }
+            };
+
+            let peer_addr = stream.peer_addr();
+            let span = info_span!("connection", %peer_addr);
+            tokio::spawn(
+                self.clone()
+                    .serve_client(stream, connection_gauge.clone(), shutdown.clone())
+                    .instrument(span),
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn serve_client(
+        mut self,
+        stream: MaybeTlsIncomingStream<TcpStream>,
+        connection_gauge: OpenGauge,
+        shutdown: ShutdownSignal,
+    ) {
+        let peer_addr = stream.peer_addr();
+        let (ws_stream, _query) = match accept(stream, self.auth.as_ref()).await {
+            Ok(accepted) => accepted,
+            Err(error) => {
+                emit!(WsHandshakeError { error });
+                return;
+            }
+        };
+        emit!(TcpSocketConnectionEstablished {
+            peer_addr: Some(peer_addr)
+        });
+        let _open_token = connection_gauge.open(|count| emit!(ConnectionOpen { count }));
+
+        let (mut ws_sink, mut ws_stream) = ws_stream.split();
+        let bytes_received = register!(BytesReceived::from(Protocol("websocket".into())));
+        let events_received = register!(EventsReceived);
+
+        loop {
+            // Pongs are sent automatically by tungstenite while reading from the stream.
+            let result = tokio::select! {
+                _ = shutdown.clone() => {
+                    let _ = ws_sink.close().await;
+                    break;
+                }
+
+                message = ws_stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        self.handle_message(text.as_bytes(), &bytes_received, &events_received)
+                            .await
+                    }
+                    Some(Ok(Message::Binary(data))) => {
+                        self.handle_message(&data, &bytes_received, &events_received)
+                            .await
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => Ok(()),
+                    Some(Err(error)) => {
+                        if !is_closed(&error) {
+                            emit!(WsReceiveError { error });
+                        }
+                        break;
+                    }
+                },
+            };
+
+            if result.is_err() {
+                break;
+            }
+        }
+
+        debug!(message = "Client disconnected.");
+    }
+
+    async fn handle_message(
+        &mut self,
+        data: &[u8],
+        bytes_received: &Registered<BytesReceived>,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<(), ()> {
+        bytes_received.emit(ByteSize(data.len()));
+
+        let mut stream = FramedRead::new(data, self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((mut events, _byte_size)) => {
+                    let count = events.len();
+                    let byte_size = events.estimated_json_encoded_size_of();
+                    events_received.emit(CountByteSize(count, byte_size));
+
+                    let now = Utc::now();
+                    for event in &mut events {
+                        if let Event::Log(ref mut log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                WebSocketServerSourceConfig::NAME,
+                                now,
+                            );
+                        }
+                    }
+
+                    self.out.send_batch(events).await.map_err(|error| {
+                        emit!(StreamClosedError { error, count });
+                    })?;
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{client::IntoClientRequest, Error as WsError},
+    };
+
+    use super::*;
+    use crate::test_util::{collect_n, next_addr, trace_init, wait_for_tcp};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketServerSourceConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_messages_from_clients() {
+        trace_init();
+
+        let addr = next_addr();
+        let config = toml::from_str::<WebSocketServerSourceConfig>(&format!(
+            r#"
+            address = "{}"
+            decoding.codec = "json"
+            auth.strategy = "basic"
+            auth.user = "vector"
+            auth.password = "secret"
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(addr).await;
+
+        let unauthorized = connect_async(format!("ws://{}", addr)).await;
+        assert!(matches!(unauthorized, Err(WsError::Http(response)) if response.status() == 401));
+
+        let mut clients = Vec::new();
+        for client in 1..=2 {
+            let mut request = format!("ws://{}", addr).into_client_request().unwrap();
+            Auth::Basic {
+                user: "vector".to_owned(),
+                password: "secret".to_owned().into(),
+            }
+            .apply(&mut request);
+
+            let (mut ws, _) = connect_async(request).await.unwrap();
+            ws.send(Message::text(format!(r#"{{"client": {}}}"#, client)))
+                .await
+                .unwrap();
+            clients.push(ws);
+        }
+
+        let mut events = collect_n(rx, 2).await;
+        events.sort_by_key(|event| event.as_log()["client"].as_integer());
+        assert_eq!(events[0].as_log()["client"], 1.into());
+        assert_eq!(events[1].as_log()["client"], 2.into());
+        assert_eq!(events[0].as_log()["source_type"], "websocket_server".into());
+    }
+}
@@ -0,0 +1,104 @@
+//! Notifications to the systemd service manager.
+//!
+//! When Vector runs as a systemd service of `Type=notify`, it notifies systemd once the topology is
+//! running, and again when it starts shutting down. If the watchdog of the service is enabled with
+//! `WatchdogSec=`, Vector also keeps pinging it while running.
+//!
+//! Nothing is sent when Vector is not started by systemd.
+use std::{
+    env, io,
+    os::unix::{ffi::OsStrExt, io::AsRawFd, net::UnixDatagram},
+    time::Duration,
+};
+
+use nix::sys::socket::{sendto, MsgFlags, UnixAddr};
+
+use crate::internal_events::VectorSystemdNotifyError;
+
+/// Notifies systemd that Vector is running.
+pub fn notify_ready() {
+    notify("READY=1");
+}
+
+/// Notifies systemd that Vector is shutting down.
+pub fn notify_stopping() {
+    notify("STOPPING=1");
+}
+
+/// Pings the watchdog of the service, if enabled, for as long as the runtime is running.
+///
+/// The watchdog is pinged at half of its timeout, as recommended by systemd.
+pub async fn watchdog() {
+    if let Some(timeout) = watchdog_timeout() {
+        let mut interval = tokio::time::interval(timeout / 2);
+        loop {
+            interval.tick().await;
+            notify("WATCHDOG=1");
+        }
+    }
+}
+
+fn notify(state: &str) {
+    if let Err(error) = try_notify(state) {
+        emit!(VectorSystemdNotifyError { error, state });
+    }
+}
+
+fn try_notify(state: &str) -> io::Result<()> {
+    let path = match env::var_os("NOTIFY_SOCKET") {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+
+    let socket = UnixDatagram::unbound()?;
+    match path.as_bytes().strip_prefix(b"@") {
+        // Sockets in the abstract namespace can't be addressed by a path.
+        Some(name) => {
+            let address = UnixAddr::new_abstract(name)?;
+            sendto(
+                socket.as_raw_fd(),
+                state.as_bytes(),
+                &address,
+                MsgFlags::empty(),
+            )?;
+        }
+        None => {
+            socket.send_to(state.as_bytes(), path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn watchdog_timeout() -> Option<Duration> {
+    let usec = env::var("WATCHDOG_USEC").ok()?.parse::<u64>().ok()?;
+
+    // The watchdog may be enabled for another process of the service.
+    if let Ok(pid) = env::var("WATCHDOG_PID") {
+        if pid.parse::<u32>().ok()? != std::process::id() {
+            return None;
+        }
+    }
+
+    (usec > 0).then(|| Duration::from_micros(usec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifies_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let listener = UnixDatagram::bind(&path).unwrap();
+
+        env::set_var("NOTIFY_SOCKET", &path);
+        notify_ready();
+        env::remove_var("NOTIFY_SOCKET");
+
+        let mut buf = [0; 64];
+        let len = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"READY=1");
+    }
+}
@@ -12,6 +12,7 @@ use vector_api_client::{
     Client,
 };
 
+use super::output::Output;
 use crate::{
     config,
     signal::{SignalRx, SignalTo},
@@ -59,27 +60,54 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
             .collect()
     };
 
+    let mut output = match Output::new(opts) {
+        Ok(output) => output,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("[tap] Couldn't create output file: {}", error);
+            }
+            return exitcode::CANTCREAT;
+        }
+    };
+
+    // Colors are only meant for the screen.
+    if matches!(output, Output::File(_)) {
+        colored::control::set_override(false);
+    }
+
     let formatter = EventFormatter::new(opts.meta, opts.format);
 
-    loop {
+    let status = loop {
         tokio::select! {
             biased;
-            Ok(SignalTo::Shutdown | SignalTo::Quit) = signal_rx.recv() => break,
-            status = run(url.clone(), opts, outputs_patterns.clone(), formatter.clone()) => {
+            Ok(SignalTo::Shutdown | SignalTo::Quit) = signal_rx.recv() => break exitcode::OK,
+            status = run(url.clone(), opts, outputs_patterns.clone(), formatter.clone(), &mut output) => {
                 if status == exitcode::UNAVAILABLE || status == exitcode::TEMPFAIL && !opts.no_reconnect {
                     #[allow(clippy::print_stderr)]
                     {
                         eprintln!("[tap] Connection failed. Reconnecting in {:?} seconds.", RECONNECT_DELAY / 1000);
                     }
                     tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
+                } else if status == exitcode::IOERR {
+                    break status;
                 } else {
-                    break;
+                    break exitcode::OK;
                 }
             }
         }
+    };
+
+    // Compressed files are only readable once their trailer is written.
+    if let Err(error) = output.finish() {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("[tap] Couldn't write to output file: {}", error);
+        }
+        return exitcode::IOERR;
     }
 
-    exitcode::OK
+    status
 }
 
 async fn run(
@@ -87,6 +115,7 @@ async fn run(
     opts: &super::Opts,
     outputs_patterns: Vec<String>,
     formatter: EventFormatter,
+    output: &mut Output,
 ) -> exitcode::ExitCode {
     let subscription_client = match connect_subscription_client(url).await {
         Ok(c) => c,
@@ -110,30 +139,39 @@ async fn run(
     };
 
     // Loop over the returned results, printing out tap events.
-    #[allow(clippy::print_stdout)]
     #[allow(clippy::print_stderr)]
     loop {
         let message = stream.next().await;
         if let Some(Some(res)) = message {
             if let Some(d) = res.data {
                 for tap_event in d.output_events_by_component_id_patterns.iter() {
-                    match tap_event {
+                    let written = match tap_event {
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Log(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            output.write_line(&formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()))
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Metric(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            output.write_line(&formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()))
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Trace(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            output.write_line(&formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()))
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::EventNotification(ev) => {
                             if !opts.quiet {
                                 eprintln!("{}", ev.message);
                             }
+                            Ok(())
                         },
+                    };
+                    if let Err(error) = written {
+                        eprintln!("[tap] Couldn't write to output file: {}", error);
+                        return exitcode::IOERR;
                     }
                 }
+                // Events are sampled each interval, so make each sample available as it arrives.
+                if let Err(error) = output.flush() {
+                    eprintln!("[tap] Couldn't write to output file: {}", error);
+                    return exitcode::IOERR;
+                }
             }
         } else {
             return exitcode::TEMPFAIL;
@@ -1,5 +1,9 @@
 mod cmd;
+mod output;
 
+use std::path::PathBuf;
+
+use bytesize::ByteSize;
 use clap::Parser;
 pub(crate) use cmd::cmd;
 use url::Url;
@@ -47,4 +51,16 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    /// Write events to this file instead of the screen, one per line
+    #[arg(long)]
+    output_file: Option<PathBuf>,
+
+    /// Start a new output file, numbered after the previous one, once it holds this amount of data, such as `100MiB`
+    #[arg(long, requires = "output_file")]
+    rotate_size: Option<ByteSize>,
+
+    /// Compress output files with gzip, appending `.gz` to their name
+    #[arg(long, requires = "output_file")]
+    compress: bool,
 }
@@ -0,0 +1,207 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use bytesize::ByteSize;
+use flate2::{write::GzEncoder, Compression};
+
+/// Destination of the tapped events.
+pub(super) enum Output {
+    Stdout,
+    File(RotatingFile),
+}
+
+impl Output {
+    pub(super) fn new(opts: &super::Opts) -> io::Result<Self> {
+        match &opts.output_file {
+            Some(path) => {
+                RotatingFile::create(path, opts.rotate_size, opts.compress).map(Self::File)
+            }
+            None => Ok(Self::Stdout),
+        }
+    }
+
+    pub(super) fn write_line(&mut self, line: &str) -> io::Result<()> {
+        match self {
+            Self::Stdout => {
+                #[allow(clippy::print_stdout)]
+                {
+                    println!("{}", line);
+                }
+                Ok(())
+            }
+            Self::File(file) => file.write_line(line),
+        }
+    }
+
+    pub(super) fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout => Ok(()),
+            Self::File(file) => file.writer.flush(),
+        }
+    }
+
+    pub(super) fn finish(self) -> io::Result<()> {
+        match self {
+            Self::Stdout => Ok(()),
+            Self::File(file) => file.writer.finish(),
+        }
+    }
+}
+
+enum Writer {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl Writer {
+    fn open(path: &Path, compress: bool) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(if compress {
+            Self::Gzip(GzEncoder::new(file, Compression::default()))
+        } else {
+            Self::Plain(file)
+        })
+    }
+
+    /// Writes the trailer of compressed files and flushes the file.
+    fn finish(self) -> io::Result<()> {
+        let mut file = match self {
+            Self::Plain(file) => file,
+            Self::Gzip(encoder) => encoder.finish()?,
+        };
+        file.flush()
+    }
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(file) => file.write(buf),
+            Self::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(file) => file.flush(),
+            Self::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// A file of newline-delimited events, rotated once it holds a given amount of data.
+///
+/// Events are written to the given path, suffixed with `.gz` when compressed. Each rotation
+/// starts a new file with a sequence number inserted before that suffix, starting at 1, so that
+/// `tap.ndjson.gz` is followed by `tap.ndjson.1.gz`, `tap.ndjson.2.gz`, and so on.
+pub(super) struct RotatingFile {
+    path: PathBuf,
+    rotate_size: Option<ByteSize>,
+    compress: bool,
+    writer: Writer,
+    /// Bytes written to the current file, before compression.
+    written: u64,
+    rotations: usize,
+}
+
+impl RotatingFile {
+    fn create(path: &Path, rotate_size: Option<ByteSize>, compress: bool) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            rotate_size,
+            compress,
+            writer: Writer::open(&Self::file_path(path, 0, compress), compress)?,
+            written: 0,
+            rotations: 0,
+        })
+    }
+
+    fn file_path(path: &Path, rotation: usize, compress: bool) -> PathBuf {
+        let mut path = path.as_os_str().to_owned();
+        if rotation > 0 {
+            path.push(format!(".{}", rotation));
+        }
+        if compress {
+            path.push(".gz");
+        }
+        path.into()
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if self.rotate_size.map_or(false, |size| {
+            self.written > 0 && self.written >= size.as_u64()
+        }) {
+            self.rotate()?;
+        }
+
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.rotations += 1;
+        let path = Self::file_path(&self.path, self.rotations, self.compress);
+        let writer = Writer::open(&path, self.compress)?;
+        std::mem::replace(&mut self.writer, writer).finish()?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Read};
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn read_gzip(path: PathBuf) -> String {
+        let mut decoded = String::new();
+        GzDecoder::new(File::open(path).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        decoded
+    }
+
+    #[test]
+    fn rotates_compressed_files() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tap.ndjson");
+
+        let mut file = RotatingFile::create(&path, Some(ByteSize::b(10)), true).unwrap();
+        for line in ["{\"a\":1}", "{\"a\":2}", "{\"a\":3}"] {
+            file.write_line(line).unwrap();
+        }
+        Output::File(file).finish().unwrap();
+
+        assert_eq!(
+            read_gzip(dir.join("tap.ndjson.gz")),
+            "{\"a\":1}\n{\"a\":2}\n"
+        );
+        assert_eq!(read_gzip(dir.join("tap.ndjson.1.gz")), "{\"a\":3}\n");
+    }
+
+    #[test]
+    fn writes_plain_files_without_rotation() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tap.ndjson");
+
+        let mut file = RotatingFile::create(&path, None, false).unwrap();
+        for line in ["{\"a\":1}", "{\"a\":2}"] {
+            file.write_line(line).unwrap();
+        }
+        Output::File(file).finish().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\":1}\n{\"a\":2}\n");
+        assert!(!dir.join("tap.ndjson.1").exists());
+    }
+}
@@ -0,0 +1,731 @@
+//! Per-tenant quotas of the events received by sources.
+//!
+//! Sources configured with `tenancy` identify the tenant of each event they receive, and the
+//! topology enforces the quotas of each tenant before sending the events downstream.
+use std::{
+    collections::HashMap,
+    num::{NonZeroU32, NonZeroUsize},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use governor::{
+    clock::DefaultClock,
+    state::{InMemoryState, NotKeyed},
+    Quota, RateLimiter,
+};
+use lookup::{lookup_v2::ConfigTargetPath, owned_value_path, path, OwnedTargetPath, PathPrefix};
+use tokio::time::Instant;
+use value::Value;
+use vector_common::TimeZone;
+use vector_config::configurable_component;
+use vector_core::{compile_vrl, EstimatedJsonEncodedSizeOf};
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
+
+use crate::{
+    event::{Event, EventArray, LogEvent, TargetEvents, VrlTarget},
+    internal_events::{TenantEventsReceived, TenantIdVrlExecutionError, TenantQuotaExceeded},
+};
+
+type DirectRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+/// The tenant which the events of the tenants beyond `max_tenants` are accounted to.
+const OTHER_TENANT: &str = "other";
+
+/// How long the rate limiter of a tenant is kept once the tenant stops receiving events.
+///
+/// Quotas only allow bursts of one second, so the limiter of a tenant idle for longer is full, and
+/// replacing it with a new one doesn't change the quota of the tenant.
+const LIMITER_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Per-tenant quotas of the events received by a source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TenancyConfig {
+    #[configurable(derived)]
+    pub tenant_id: TenantIdConfig,
+
+    /// The tenant of the events whose tenant ID is missing.
+    #[serde(default = "default_tenant")]
+    #[configurable(metadata(docs::examples = "unknown"))]
+    pub default_tenant: String,
+
+    /// The maximum number of tenants whose quotas are enforced separately.
+    ///
+    /// The events of further tenants share a single default `quota`, and are reported as the
+    /// `other` tenant in the internal metrics. Tenants with a quota in `tenants` always have their
+    /// own, and tenants stop counting once they received no events for a minute.
+    #[serde(default = "default_max_tenants")]
+    pub max_tenants: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub quota: TenantQuota,
+
+    /// The quotas of specific tenants, overriding the default `quota`.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The quota of a tenant, keyed by its tenant ID."
+    ))]
+    pub tenants: HashMap<String, TenantQuota>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub over_quota_action: OverQuotaAction,
+
+    /// The field set to `true` on the events over quota, when `over_quota_action` is `tag`.
+    ///
+    /// Metrics are tagged with a tag of the same name instead.
+    #[serde(default = "default_over_quota_field")]
+    #[configurable(metadata(docs::examples = "tenant_over_quota"))]
+    pub over_quota_field: ConfigTargetPath,
+}
+
+fn default_tenant() -> String {
+    "default".to_owned()
+}
+
+fn default_max_tenants() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("static non-zero number")
+}
+
+fn default_over_quota_field() -> ConfigTargetPath {
+    ConfigTargetPath(OwnedTargetPath::event(owned_value_path!(
+        "tenant_over_quota"
+    )))
+}
+
+/// How the tenant ID of an event is identified.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The source of the tenant ID."))]
+pub enum TenantIdConfig {
+    /// The tenant ID is read from a field of the event.
+    ///
+    /// For metrics, the tenant ID is read from the tag of the same name.
+    Field {
+        /// The field holding the tenant ID.
+        #[configurable(metadata(docs::examples = "tenant", docs::examples = "%kafka.topic"))]
+        path: ConfigTargetPath,
+    },
+
+    /// The tenant ID is read from an HTTP header captured by the source.
+    ///
+    /// The header must be captured by the source, usually with its `headers` option.
+    Header {
+        /// The name of the header holding the tenant ID.
+        #[configurable(metadata(docs::examples = "X-Tenant-ID"))]
+        name: String,
+    },
+
+    /// The tenant ID is the result of a [Vector Remap Language][vrl] (VRL) expression.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    Vrl {
+        /// The VRL expression resolving to the tenant ID.
+        #[configurable(metadata(docs::examples = "downcase!(.org) + \"/\" + .team"))]
+        source: String,
+    },
+}
+
+/// The ingest quota of a tenant.
+///
+/// A tenant can go over its quota in bursts of up to one second of its rate.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TenantQuota {
+    /// The number of events per second allowed for the tenant.
+    ///
+    /// By default, the number of events is not limited.
+    #[configurable(metadata(docs::examples = 1000))]
+    pub events_per_second: Option<NonZeroU32>,
+
+    /// The number of bytes per second allowed for the tenant.
+    ///
+    /// Events are measured by their estimated JSON-encoded size. By default, the number of bytes is
+    /// not limited.
+    #[configurable(metadata(docs::examples = 1048576))]
+    pub bytes_per_second: Option<NonZeroU32>,
+}
+
+/// What to do with the events of a tenant over quota.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverQuotaAction {
+    /// Wait until the quota of the tenant allows the events.
+    ///
+    /// This applies backpressure to the source, slowing down all of its tenants.
+    #[default]
+    Throttle,
+
+    /// Drop the events.
+    Drop,
+
+    /// Send the events, setting `over_quota_field` on them.
+    Tag,
+}
+
+impl OverQuotaAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Throttle => "throttle",
+            Self::Drop => "drop",
+            Self::Tag => "tag",
+        }
+    }
+}
+
+enum TenantId {
+    Field { path: OwnedTargetPath, tag: String },
+    Header(String),
+    Vrl { program: Program, source: String },
+}
+
+/// The rate limiters of a tenant.
+struct TenantLimiter {
+    events: Option<DirectRateLimiter>,
+    bytes: Option<(DirectRateLimiter, NonZeroU32)>,
+}
+
+impl TenantLimiter {
+    fn new(quota: &TenantQuota) -> Self {
+        Self {
+            events: quota
+                .events_per_second
+                .map(|rate| RateLimiter::direct(Quota::per_second(rate))),
+            bytes: quota
+                .bytes_per_second
+                .map(|rate| (RateLimiter::direct(Quota::per_second(rate)), rate)),
+        }
+    }
+
+    /// Checks whether an event of the given size is within the quota.
+    ///
+    /// Events larger than the byte quota are always over quota.
+    fn check(&self, byte_size: NonZeroU32) -> bool {
+        self.events
+            .as_ref()
+            .map_or(true, |limiter| limiter.check().is_ok())
+            && self
+                .bytes
+                .as_ref()
+                .map_or(true, |(limiter, _)| limiter.check_n(byte_size).is_ok())
+    }
+
+    /// Waits until an event of the given size is within the quota.
+    ///
+    /// Events larger than the byte quota wait until the whole byte quota is available.
+    async fn until_ready(&self, byte_size: NonZeroU32) {
+        if let Some(limiter) = &self.events {
+            limiter.until_ready().await;
+        }
+        if let Some((limiter, rate)) = &self.bytes {
+            // This can't fail, as the quota allows bursts of its rate.
+            _ = limiter.until_n_ready(byte_size.min(*rate)).await;
+        }
+    }
+}
+
+/// The rate limiters of the tenants which recently received events.
+struct Limiters {
+    tenants: HashMap<String, (Arc<TenantLimiter>, Instant)>,
+    other: Arc<TenantLimiter>,
+    last_evicted: Instant,
+}
+
+impl Limiters {
+    fn new(quota: &TenantQuota) -> Self {
+        Self {
+            tenants: HashMap::new(),
+            other: Arc::new(TenantLimiter::new(quota)),
+            last_evicted: Instant::now(),
+        }
+    }
+
+    /// Removes the limiters of the tenants idle for longer than `LIMITER_IDLE_TIMEOUT`, at most
+    /// once per timeout.
+    fn evict_idle(&mut self, now: Instant) {
+        if now.duration_since(self.last_evicted) < LIMITER_IDLE_TIMEOUT {
+            return;
+        }
+        self.last_evicted = now;
+        self.tenants
+            .retain(|_, (_, last_used)| now.duration_since(*last_used) < LIMITER_IDLE_TIMEOUT);
+    }
+}
+
+/// Enforces the per-tenant quotas of the events received by a source.
+#[derive(Clone)]
+pub struct TenancyEnforcer {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    tenant_id: TenantId,
+    source_type: &'static str,
+    default_tenant: String,
+    max_tenants: NonZeroUsize,
+    quota: TenantQuota,
+    tenants: HashMap<String, TenantQuota>,
+    over_quota_action: OverQuotaAction,
+    over_quota_field: OwnedTargetPath,
+    over_quota_tag: String,
+    limiters: Mutex<Limiters>,
+}
+
+impl TenancyEnforcer {
+    pub fn new(
+        config: &TenancyConfig,
+        source_type: &'static str,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let tenant_id = match &config.tenant_id {
+            TenantIdConfig::Field { path } => TenantId::Field {
+                path: path.0.clone(),
+                tag: tag_name(path),
+            },
+            TenantIdConfig::Header { name } => TenantId::Header(name.clone()),
+            TenantIdConfig::Vrl { source } => TenantId::Vrl {
+                program: compile_tenant_id(source, enrichment_tables)?,
+                source: source.clone(),
+            },
+        };
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                tenant_id,
+                source_type,
+                default_tenant: config.default_tenant.clone(),
+                max_tenants: config.max_tenants,
+                quota: config.quota,
+                tenants: config.tenants.clone(),
+                over_quota_action: config.over_quota_action,
+                over_quota_field: config.over_quota_field.0.clone(),
+                over_quota_tag: tag_name(&config.over_quota_field),
+                limiters: Mutex::new(Limiters::new(&config.quota)),
+            }),
+        })
+    }
+
+    /// Enforces the quotas of the tenants of the events.
+    ///
+    /// Returns the events to send downstream, which may be empty when the events over quota are
+    /// dropped.
+    pub async fn enforce(&self, events: EventArray) -> EventArray {
+        match events {
+            EventArray::Logs(logs) => {
+                EventArray::Logs(self.enforce_events(logs, Event::Log, Event::into_log).await)
+            }
+            EventArray::Metrics(metrics) => EventArray::Metrics(
+                self.enforce_events(metrics, Event::Metric, Event::into_metric)
+                    .await,
+            ),
+            EventArray::Traces(traces) => EventArray::Traces(
+                self.enforce_events(traces, Event::Trace, Event::into_trace)
+                    .await,
+            ),
+        }
+    }
+
+    async fn enforce_events<T>(
+        &self,
+        events: Vec<T>,
+        into_event: fn(T) -> Event,
+        from_event: fn(Event) -> T,
+    ) -> Vec<T> {
+        let mut output = Vec::with_capacity(events.len());
+        for event in events {
+            if let Some(event) = self.enforce_event(into_event(event)).await {
+                output.push(from_event(event));
+            }
+        }
+        output
+    }
+
+    async fn enforce_event(&self, event: Event) -> Option<Event> {
+        let inner = &self.inner;
+        let (tenant, mut event) = self.tenant_id(event);
+        let tenant = tenant.unwrap_or_else(|| inner.default_tenant.clone());
+        let (tenant, limiter) = self.limiter(tenant);
+
+        let byte_size = event.estimated_json_encoded_size_of();
+        emit!(TenantEventsReceived {
+            tenant: &tenant,
+            count: 1,
+            byte_size,
+        });
+
+        let byte_size = u32::try_from(byte_size)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .unwrap_or(NonZeroU32::MAX);
+        if limiter.check(byte_size) {
+            return Some(event);
+        }
+
+        emit!(TenantQuotaExceeded {
+            tenant: &tenant,
+            action: inner.over_quota_action.as_str(),
+        });
+        match inner.over_quota_action {
+            OverQuotaAction::Throttle => {
+                limiter.until_ready(byte_size).await;
+                Some(event)
+            }
+            OverQuotaAction::Drop => None,
+            OverQuotaAction::Tag => {
+                match &mut event {
+                    Event::Log(log) => tag_log(log, &inner.over_quota_field),
+                    Event::Trace(trace) => tag_log(trace.as_mut(), &inner.over_quota_field),
+                    Event::Metric(metric) => {
+                        metric.replace_tag(inner.over_quota_tag.clone(), "true".into());
+                    }
+                }
+                Some(event)
+            }
+        }
+    }
+
+    /// Returns the tenant which the events of the tenant are accounted to, and its rate limiter.
+    ///
+    /// Once `max_tenants` tenants have a limiter, the other tenants without a quota of their own
+    /// share the limiter of the `other` tenant.
+    fn limiter(&self, tenant: String) -> (String, Arc<TenantLimiter>) {
+        let inner = &self.inner;
+        let now = Instant::now();
+        let mut limiters = inner.limiters.lock().expect("tenancy mutex poisoned");
+        limiters.evict_idle(now);
+
+        if let Some((limiter, last_used)) = limiters.tenants.get_mut(&tenant) {
+            *last_used = now;
+            return (tenant, Arc::clone(limiter));
+        }
+
+        let quota = match inner.tenants.get(&tenant) {
+            Some(quota) => quota,
+            None if limiters.tenants.len() >= inner.max_tenants.get() => {
+                return (OTHER_TENANT.to_owned(), Arc::clone(&limiters.other));
+            }
+            None => &inner.quota,
+        };
+        let limiter = Arc::new(TenantLimiter::new(quota));
+        limiters
+            .tenants
+            .insert(tenant.clone(), (Arc::clone(&limiter), now));
+        (tenant, limiter)
+    }
+
+    fn tenant_id(&self, event: Event) -> (Option<String>, Event) {
+        let tenant = match &self.inner.tenant_id {
+            TenantId::Vrl { program, source } => return run_tenant_id(program, source, event),
+            TenantId::Field { path, tag } => match &event {
+                Event::Log(log) => log.get(path).map(value_to_tenant),
+                Event::Trace(trace) => trace.get(path).map(value_to_tenant),
+                Event::Metric(metric) => metric.tag_value(tag),
+            },
+            TenantId::Header(name) => match &event {
+                Event::Log(log) => self.header(log, name),
+                Event::Trace(trace) => self.header(trace.as_ref(), name),
+                Event::Metric(_) => None,
+            },
+        };
+        (tenant, event)
+    }
+
+    /// Reads a header captured by the source, either in the source metadata of the event, or at
+    /// the root of the event with the legacy namespace.
+    fn header(&self, log: &LogEvent, name: &str) -> Option<String> {
+        log.get((
+            PathPrefix::Metadata,
+            path!(self.inner.source_type, "headers", name),
+        ))
+        .or_else(|| log.get((PathPrefix::Event, path!(name))))
+        .map(value_to_tenant)
+    }
+}
+
+/// The name of the metric tag matching a field.
+fn tag_name(path: &ConfigTargetPath) -> String {
+    let path = String::from(path.clone());
+    match path.strip_prefix('.') {
+        Some(name) => name.to_owned(),
+        None => path,
+    }
+}
+
+fn value_to_tenant(value: &Value) -> String {
+    value.to_string_lossy().into_owned()
+}
+
+fn tag_log(log: &mut LogEvent, field: &OwnedTargetPath) {
+    log.insert(field, true);
+}
+
+fn compile_tenant_id(
+    source: &str,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> crate::Result<Program> {
+    let mut functions = vrl_stdlib::all()
+        .into_iter()
+        .chain(enrichment::vrl_functions().into_iter())
+        .chain(vector_vrl_functions::all())
+        .collect::<Vec<_>>();
+    vector_core::fips::retain_vrl_functions(&mut functions);
+
+    let state = vrl::state::TypeState::default();
+
+    let mut config = CompileConfig::default();
+    config.set_custom(enrichment_tables.clone());
+    config.set_read_only();
+
+    let CompilationResult {
+        program,
+        warnings,
+        config: _,
+    } = compile_vrl(source, &functions, &state, config)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+fn run_tenant_id(program: &Program, source: &str, event: Event) -> (Option<String>, Event) {
+    let mut target = VrlTarget::new(event, program.info(), false);
+    let result = Runtime::default().resolve(&mut target, program, &TimeZone::default());
+    let event = match target.into_events() {
+        TargetEvents::One(event) => event,
+        _ => panic!(
+            "Event was modified by the tenant ID expression. This is an internal compiler error."
+        ),
+    };
+
+    let tenant = match result {
+        Ok(Value::Null) => None,
+        Ok(value) => Some(value_to_tenant(&value)),
+        Err(error) => {
+            emit!(TenantIdVrlExecutionError {
+                error: &error.to_string(),
+                source,
+            });
+            None
+        }
+    };
+    (tenant, event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{EventContainer, Metric, MetricKind, MetricValue},
+        log_event,
+    };
+
+    fn enforcer(config: &str) -> TenancyEnforcer {
+        let config: TenancyConfig = toml::from_str(config).unwrap();
+        TenancyEnforcer::new(
+            &config,
+            "http_server",
+            &enrichment::TableRegistry::default(),
+        )
+        .unwrap()
+    }
+
+    fn logs(events: Vec<Event>) -> EventArray {
+        EventArray::Logs(events.into_iter().map(Event::into_log).collect())
+    }
+
+    fn tenants(events: &EventArray) -> Vec<String> {
+        events
+            .iter_events()
+            .map(|event| event.as_log()["tenant"].to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn limited_tenants(enforcer: &TenancyEnforcer) -> Vec<String> {
+        let limiters = enforcer.inner.limiters.lock().unwrap();
+        let mut tenants = limiters.tenants.keys().cloned().collect::<Vec<_>>();
+        tenants.sort();
+        tenants
+    }
+
+    #[tokio::test]
+    async fn drops_events_over_quota() {
+        let enforcer = enforcer(
+            r#"
+            tenant_id.type = "field"
+            tenant_id.path = "tenant"
+            quota.events_per_second = 2
+            tenants.big.events_per_second = 10
+            over_quota_action = "drop"
+            "#,
+        );
+
+        let events = (0..5)
+            .map(|_| log_event!["tenant" => "small"])
+            .chain((0..5).map(|_| log_event!["tenant" => "big"]))
+            .collect();
+        let output = enforcer.enforce(logs(events)).await;
+
+        let count = |tenant: &str| {
+            output
+                .iter_events()
+                .filter(|event| event.as_log()["tenant"] == tenant.into())
+                .count()
+        };
+        assert_eq!(count("small"), 2);
+        assert_eq!(count("big"), 5);
+    }
+
+    #[tokio::test]
+    async fn tags_events_over_quota() {
+        let enforcer = enforcer(
+            r#"
+            tenant_id.type = "vrl"
+            tenant_id.source = "upcase!(.org)"
+            quota.events_per_second = 1
+            over_quota_action = "tag"
+            "#,
+        );
+
+        let events = vec![
+            log_event!["org" => "acme"],
+            log_event!["org" => "acme"],
+            log_event!["org" => "initech"],
+        ];
+        let output = enforcer.enforce(logs(events)).await;
+
+        let tagged = output
+            .iter_events()
+            .map(|event| event.as_log().get("tenant_over_quota").is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(tagged, vec![false, true, false]);
+    }
+
+    #[tokio::test]
+    async fn reads_tenant_from_header() {
+        let enforcer = enforcer(
+            r#"
+            tenant_id.type = "header"
+            tenant_id.name = "X-Tenant-ID"
+            quota.events_per_second = 1
+            over_quota_action = "drop"
+            "#,
+        );
+
+        // The header is either in the source metadata, or at the root of the event with the
+        // legacy namespace.
+        let mut metadata_header = LogEvent::default();
+        metadata_header.insert(
+            (
+                PathPrefix::Metadata,
+                path!("http_server", "headers", "X-Tenant-ID"),
+            ),
+            "acme",
+        );
+        let mut legacy_header = LogEvent::default();
+        legacy_header.insert((PathPrefix::Event, path!("X-Tenant-ID")), "acme");
+
+        let events = vec![
+            Event::from(metadata_header),
+            Event::from(legacy_header),
+            log_event!["message" => "no tenant"],
+        ];
+        let output = enforcer.enforce(logs(events)).await;
+
+        assert_eq!(output.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reads_tenant_from_metric_tag() {
+        let enforcer = enforcer(
+            r#"
+            tenant_id.type = "field"
+            tenant_id.path = "tenant"
+            quota.events_per_second = 1
+            over_quota_action = "tag"
+            "#,
+        );
+
+        let metric = |tenant: &str| {
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(vector_core::metric_tags!("tenant" => tenant)))
+        };
+        let output = enforcer
+            .enforce(EventArray::Metrics(vec![
+                metric("acme"),
+                metric("acme"),
+                metric("initech"),
+            ]))
+            .await;
+
+        let tagged = output
+            .iter_events()
+            .map(|event| event.as_metric().tag_value("tenant_over_quota"))
+            .collect::<Vec<_>>();
+        assert_eq!(tagged, vec![None, Some("true".to_owned()), None]);
+    }
+
+    #[tokio::test]
+    async fn accounts_tenants_beyond_max_tenants_to_other() {
+        let enforcer = enforcer(
+            r#"
+            tenant_id.type = "field"
+            tenant_id.path = "tenant"
+            max_tenants = 2
+            quota.events_per_second = 1
+            tenants.big.events_per_second = 10
+            over_quota_action = "drop"
+            "#,
+        );
+
+        let events = ["a", "b", "c", "d", "big", "a"]
+            .into_iter()
+            .map(|tenant| log_event!["tenant" => tenant])
+            .collect();
+        let output = enforcer.enforce(logs(events)).await;
+
+        // `c` and `d` share the quota of the `other` tenant, while `big` has a quota of its own.
+        assert_eq!(tenants(&output), vec!["a", "b", "c", "big"]);
+        assert_eq!(limited_tenants(&enforcer), vec!["a", "b", "big"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn evicts_idle_limiters() {
+        let enforcer = enforcer(
+            r#"
+            tenant_id.type = "field"
+            tenant_id.path = "tenant"
+            max_tenants = 1
+            quota.events_per_second = 1
+            over_quota_action = "drop"
+            "#,
+        );
+
+        let output = enforcer
+            .enforce(logs(vec![log_event!["tenant" => "a"]]))
+            .await;
+        assert_eq!(tenants(&output), vec!["a"]);
+        assert_eq!(limited_tenants(&enforcer), vec!["a"]);
+
+        tokio::time::advance(LIMITER_IDLE_TIMEOUT).await;
+
+        let output = enforcer
+            .enforce(logs(vec![log_event!["tenant" => "b"]]))
+            .await;
+        assert_eq!(tenants(&output), vec!["b"]);
+        assert_eq!(limited_tenants(&enforcer), vec!["b"]);
+    }
+}
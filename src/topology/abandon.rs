@@ -0,0 +1,118 @@
+use std::pin::Pin;
+
+use futures::{
+    task::{noop_waker_ref, Context, Poll},
+    Stream, StreamExt,
+};
+use tracing::Span;
+
+use crate::{
+    event::{EventArray, EventStatus, Finalizable},
+    internal_events::ComponentEventsAbandoned,
+};
+
+/// Input stream of a component which reports the events left in it as abandoned when it is dropped.
+///
+/// Components drain their input before finishing normally, so only the components torn down early,
+/// such as the ones aborted once their drain deadline has passed, abandon events. Events which are
+/// buffered but not yet ready to be read, as can be the case with disk buffers, aren't counted.
+///
+/// Abandoned events are marked as errored, so that sources waiting for their acknowledgement can
+/// tell their senders.
+pub(crate) struct AbandonOnDrop<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    inner: Option<S>,
+    // Components are aborted from outside of their span, so the span is captured to tag the
+    // abandoned events with the component.
+    span: Span,
+}
+
+impl<S> AbandonOnDrop<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    /// Wraps the input stream of the component of the current span.
+    pub(crate) fn new(inner: S) -> Self {
+        Self {
+            inner: Some(inner),
+            span: Span::current(),
+        }
+    }
+
+    /// Consumes this wrapper and returns the inner stream, without reporting the events left in it.
+    pub(crate) fn into_inner(mut self) -> S {
+        self.inner.take().expect("inner stream is only taken once")
+    }
+}
+
+impl<S> Stream for AbandonOnDrop<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.inner.as_mut() {
+            Some(inner) => inner.poll_next_unpin(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+impl<S> Drop for AbandonOnDrop<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    fn drop(&mut self) {
+        if let Some(mut inner) = self.inner.take() {
+            let mut cx = Context::from_waker(noop_waker_ref());
+            let mut count = 0;
+            while let Poll::Ready(Some(mut events)) = inner.poll_next_unpin(&mut cx) {
+                count += events.len();
+                events.take_finalizers().update_status(EventStatus::Errored);
+            }
+
+            if count > 0 {
+                let _enter = self.span.enter();
+                emit!(ComponentEventsAbandoned { count });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, LogEvent};
+
+    fn events(count: usize) -> (Vec<EventArray>, BatchStatusReceiver) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events = (0..count)
+            .map(|_| LogEvent::from("event").with_batch_notifier(&batch).into())
+            .collect();
+        (events, receiver)
+    }
+
+    #[tokio::test]
+    async fn drops_events_left_in_input() {
+        let (events, receiver) = events(3);
+        let mut input = AbandonOnDrop::new(stream::iter(events));
+        assert!(input.next().await.is_some());
+
+        drop(input);
+        assert_eq!(receiver.await, BatchStatus::Errored);
+    }
+
+    #[tokio::test]
+    async fn into_inner_keeps_events() {
+        let (events, _receiver) = events(3);
+        let input = AbandonOnDrop::new(stream::iter(events));
+
+        let inner = input.into_inner();
+        assert_eq!(inner.count().await, 3);
+    }
+}
@@ -6,14 +6,14 @@ use std::{
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
+use futures::{future::Either, stream::FuturesOrdered, FutureExt, StreamExt, TryStreamExt};
 use futures_util::stream::FuturesUnordered;
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
     select,
     sync::{mpsc::UnboundedSender, oneshot},
-    time::{timeout, Duration},
+    time::timeout,
 };
 use tracing::Instrument;
 use vector_common::internal_event::{
@@ -25,7 +25,7 @@ use vector_core::{
     buffers::{
         topology::{
             builder::TopologyBuilder,
-            channel::{BufferReceiver, BufferSender},
+            channel::{BufferReceiver, BufferReceiverStream, BufferSender},
         },
         BufferType, WhenFull,
     },
@@ -34,21 +34,41 @@ use vector_core::{
 };
 
 use super::{
+    abandon::AbandonOnDrop,
     fanout::{self, Fanout},
+    field_encryption::FieldEncryption,
+    memory_budget::{MemoryBudget, GLOBAL_MEMORY_USAGE},
+    ordered_merge::OrderedMerge,
+    quarantine::{HealthMonitor, Quarantine, QuarantineState, HEALTHCHECK_TIMEOUT},
     schema,
+    schema_validation::SchemaValidator,
+    shadow::Shadow,
+    stall::WatchedOutput,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
 };
+#[cfg(not(feature = "coordination"))]
+use crate::coordination::HaMode;
+#[cfg(feature = "coordination")]
+use crate::coordination::LeaderElector;
 use crate::{
     config::{
-        ComponentKey, DataType, EnrichmentTableConfig, Input, Inputs, OutputId, ProxyConfig,
-        SinkConfig, SinkContext, SourceContext, TransformContext, TransformOuter, TransformOutput,
+        ComponentKey, DataType, EnrichmentTableConfig, Input, Inputs, OrderedMergeConfig, OutputId,
+        ProxyConfig, SinkConfig, SinkContext, SourceContext, TransformContext, TransformOuter,
+        TransformOutput,
     },
     event::{EventArray, EventContainer},
+    event_labels::EventLabels,
+    event_size::EventSizeGuard,
     internal_events::EventsReceived,
+    latency::{SinkLatency, SourceLatency},
+    lineage::{SourceLineage, TransformLineage},
+    runtime::DedicatedRuntime,
     shutdown::SourceShutdownCoordinator,
+    source_log_schema::LogSchemaRemap,
     source_sender::CHUNK_SIZE,
     spawn_named,
+    tenancy::TenancyEnforcer,
     topology::task::TaskError,
     transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
     utilization::wrap,
@@ -86,11 +106,13 @@ struct Builder<'a> {
     shutdown_coordinator: SourceShutdownCoordinator,
     errors: Vec<String>,
     outputs: HashMap<OutputId, UnboundedSender<fanout::ControlMessage>>,
+    send_statuses: HashMap<OutputId, WatchedOutput>,
     tasks: HashMap<ComponentKey, Task>,
     buffers: HashMap<ComponentKey, BuiltBuffer>,
     inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
     healthchecks: HashMap<ComponentKey, Task>,
     detach_triggers: HashMap<ComponentKey, Trigger>,
+    runtimes: HashMap<ComponentKey, DedicatedRuntime>,
 }
 
 impl<'a> Builder<'a> {
@@ -106,10 +128,12 @@ impl<'a> Builder<'a> {
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             errors: vec![],
             outputs: HashMap::new(),
+            send_statuses: HashMap::new(),
             tasks: HashMap::new(),
             inputs: HashMap::new(),
             healthchecks: HashMap::new(),
             detach_triggers: HashMap::new(),
+            runtimes: HashMap::new(),
         }
     }
 
@@ -128,11 +152,13 @@ impl<'a> Builder<'a> {
             Ok(Pieces {
                 inputs: self.inputs,
                 outputs: Self::finalize_outputs(self.outputs),
+                send_statuses: self.send_statuses,
                 tasks: self.tasks,
                 source_tasks,
                 healthchecks: self.healthchecks,
                 shutdown_coordinator: self.shutdown_coordinator,
                 detach_triggers: self.detach_triggers,
+                runtimes: self.runtimes,
             })
         } else {
             Err(self.errors)
@@ -237,6 +263,27 @@ impl<'a> Builder<'a> {
                 key.id()
             );
 
+            let tenancy = match source
+                .tenancy
+                .as_ref()
+                .map(|tenancy| TenancyEnforcer::new(tenancy, typetag, &ENRICHMENT_TABLES))
+                .transpose()
+            {
+                Ok(tenancy) => tenancy,
+                Err(error) => {
+                    self.errors
+                        .push(format!("Source \"{}\": tenancy: {}", key, error));
+                    continue;
+                }
+            };
+
+            let log_schema_remap = source.log_schema.as_ref().and_then(LogSchemaRemap::new);
+            let lineage = SourceLineage::new(self.config, key);
+            let latency = SourceLatency::new(self.config, key);
+            let event_labels = EventLabels::new(self.config);
+            let size_guard = EventSizeGuard::new(self.config, key);
+            let priority = source.priority;
+
             let mut builder = SourceSender::builder().with_buffer(*SOURCE_SENDER_BUFFER_SIZE);
             let mut pumps = Vec::new();
             let mut controls = HashMap::new();
@@ -246,10 +293,48 @@ impl<'a> Builder<'a> {
                 let mut rx = builder.add_source_output(output.clone());
 
                 let (mut fanout, control) = Fanout::new();
+                let send_status = WatchedOutput::source(fanout.send_status());
+                let tenancy = tenancy.clone();
+                let log_schema_remap = log_schema_remap.clone();
+                let lineage = lineage.clone();
+                let latency = latency.clone();
+                let event_labels = event_labels.clone();
+                let size_guard = size_guard.clone();
                 let pump = async move {
                     debug!("Source pump starting.");
 
-                    while let Some(array) = rx.next().await {
+                    while let Some(mut array) = rx.next().await {
+                        if let Some(log_schema_remap) = &log_schema_remap {
+                            log_schema_remap.remap(&mut array);
+                        }
+                        if let Some(size_guard) = &size_guard {
+                            size_guard.enforce(&mut array);
+                            if array.is_empty() {
+                                continue;
+                            }
+                        }
+                        if let Some(tenancy) = &tenancy {
+                            array = tenancy.enforce(array).await;
+                            if array.is_empty() {
+                                continue;
+                            }
+                        }
+
+                        if let Some(lineage) = &lineage {
+                            lineage.stamp(&mut array);
+                        }
+                        if let Some(priority) = priority {
+                            for mut event in array.iter_events_mut() {
+                                event.metadata_mut().set_priority(priority);
+                            }
+                        }
+                        if let Some(latency) = &latency {
+                            latency.stamp(&mut array);
+                        }
+                        if let Some(event_labels) = &event_labels {
+                            event_labels.sent(array.iter_events());
+                        }
+
                         fanout.send(array).await.map_err(|e| {
                             debug!("Source pump finished with an error.");
                             TaskError::wrapped(e)
@@ -261,13 +346,12 @@ impl<'a> Builder<'a> {
                 };
 
                 pumps.push(pump.instrument(span.clone()));
-                controls.insert(
-                    OutputId {
-                        component: key.clone(),
-                        port: output.port.clone(),
-                    },
-                    control,
-                );
+                let output_id = OutputId {
+                    component: key.clone(),
+                    port: output.port.clone(),
+                };
+                self.send_statuses.insert(output_id.clone(), send_status);
+                controls.insert(output_id, control);
 
                 let port = output.port.clone();
                 if let Some(definition) = output.schema_definition(self.config.schema.enabled) {
@@ -313,6 +397,26 @@ impl<'a> Builder<'a> {
             let (shutdown_signal, force_shutdown_tripwire) =
                 self.shutdown_coordinator.register_source(key);
 
+            // Sources running in `active_passive` mode wait to acquire their lease before running.
+            #[cfg(feature = "coordination")]
+            let leader_elector =
+                match LeaderElector::build(source.ha_mode, source.coordination.as_ref()).await {
+                    Ok(elector) => elector.map(|elector| (elector, shutdown_signal.clone())),
+                    Err(error) => {
+                        self.errors
+                            .push(format!("Source \"{}\": coordination: {}", key, error));
+                        continue;
+                    }
+                };
+            #[cfg(not(feature = "coordination"))]
+            if source.ha_mode == HaMode::ActivePassive {
+                self.errors.push(format!(
+                    "Source \"{}\": `ha_mode` can only be `active_passive` when Vector is built with the `coordination` feature.",
+                    key
+                ));
+                continue;
+            }
+
             let context = SourceContext {
                 key: key.clone(),
                 globals: self.config.global.clone(),
@@ -331,6 +435,11 @@ impl<'a> Builder<'a> {
                 }
                 Ok(server) => server,
             };
+            #[cfg(feature = "coordination")]
+            let server = match leader_elector {
+                Some((elector, shutdown)) => elector.run(shutdown, server),
+                None => server,
+            };
 
             // Build a wrapper future that drives the actual source future, but returns early if we've
             // been signalled to forcefully shutdown, or if the source pump encounters an error.
@@ -443,8 +552,24 @@ impl<'a> Builder<'a> {
                 transform,
                 &input_definitions,
                 self.config.schema.log_namespace(),
+                TransformLineage::new(self.config, key),
+                EventLabels::new(self.config),
+                EventSizeGuard::new(self.config, key),
             );
 
+            if let Some(runtime) = &transform.runtime {
+                match DedicatedRuntime::new(key, runtime) {
+                    Err(error) => {
+                        self.errors
+                            .push(format!("Transform \"{}\": {}", key, error));
+                        continue;
+                    }
+                    Ok(runtime) => {
+                        self.runtimes.insert(key.clone(), runtime);
+                    }
+                }
+            }
+
             let transform = match transform
                 .inner
                 .build(&context)
@@ -465,12 +590,13 @@ impl<'a> Builder<'a> {
             self.inputs
                 .insert(key.clone(), (input_tx, node.inputs.clone()));
 
-            let (transform_task, transform_outputs) = {
+            let (transform_task, transform_outputs, send_statuses) = {
                 let _span = span.enter();
                 build_transform(transform, node, input_rx)
             };
 
             self.outputs.extend(transform_outputs);
+            self.send_statuses.extend(send_statuses);
             self.tasks.insert(key.clone(), transform_task);
         }
     }
@@ -486,9 +612,14 @@ impl<'a> Builder<'a> {
             let sink_inputs = &sink.inputs;
             let healthcheck = sink.healthcheck();
             let enable_healthcheck = healthcheck.enabled && self.config.healthchecks.enabled;
+            let healthcheck_interval = healthcheck.interval().filter(|_| enable_healthcheck);
+            let failure_threshold = healthcheck.failure_threshold;
+            let quarantine_policy = healthcheck.quarantine;
 
             let typetag = sink.inner.get_component_name();
             let input_type = sink.inner.input().data_type();
+            let memory_limit = sink.memory_limit_bytes;
+            let memory_budget = self.config.global.memory_budget.clone();
 
             // At this point, we've validated that all transforms are valid, including any
             // transform that mutates the schema provided by their sources. We can now validate the
@@ -529,13 +660,95 @@ impl<'a> Builder<'a> {
                 }
             };
 
+            if let Some(runtime) = &sink.runtime {
+                match DedicatedRuntime::new(key, runtime) {
+                    Err(error) => {
+                        self.errors.push(format!("Sink \"{}\": {}", key, error));
+                        continue;
+                    }
+                    Ok(runtime) => {
+                        self.runtimes.insert(key.clone(), runtime);
+                    }
+                }
+            }
+
+            let schema_validator = match sink.schema.validation.as_ref().map(SchemaValidator::new) {
+                Some(Err(error)) => {
+                    self.errors.push(format!("Sink \"{}\": {}", key, error));
+                    continue;
+                }
+                Some(Ok(validator)) => Some(validator),
+                None => None,
+            };
+
+            let proxy = ProxyConfig::merge_with_env(&self.config.global.proxy, sink.proxy());
+            let field_encryption = match &sink.encrypt_fields {
+                Some(config) => match FieldEncryption::new(config, &proxy).await {
+                    Err(error) => {
+                        self.errors.push(format!("Sink \"{}\": {}", key, error));
+                        continue;
+                    }
+                    Ok(encryption) => Some(Arc::new(encryption)),
+                },
+                None => None,
+            };
+
             let cx = SinkContext {
                 healthcheck,
                 globals: self.config.global.clone(),
-                proxy: ProxyConfig::merge_with_env(&self.config.global.proxy, sink.proxy()),
+                proxy,
                 schema: self.config.schema,
             };
 
+            let quarantine_state = QuarantineState::default();
+            let health_monitor = match healthcheck_interval {
+                Some(interval) => match HealthMonitor::build(
+                    &sink.inner,
+                    cx.clone(),
+                    interval,
+                    failure_threshold,
+                    quarantine_policy,
+                    quarantine_state.clone(),
+                )
+                .await
+                {
+                    Err(error) => {
+                        self.errors.push(format!("Sink \"{}\": {}", key, error));
+                        continue;
+                    }
+                    Ok(None) => {
+                        warn!(
+                            message = "Sink doesn't support periodic healthchecks; ignoring `healthcheck.interval_secs`.",
+                            component = %key,
+                        );
+                        None
+                    }
+                    Ok(health_monitor) => health_monitor,
+                },
+                None => None,
+            };
+
+            let shadow = match &sink.shadow {
+                Some(config) if !(0.0..=100.0).contains(&config.percentage) => {
+                    self.errors.push(format!(
+                        "Sink \"{}\": `shadow.percentage` must be between 0 and 100",
+                        key
+                    ));
+                    continue;
+                }
+                // The healthcheck of the secondary sink is ignored, as it must not hold the sink
+                // back.
+                Some(config) => match config.sink.build(cx.clone()).await {
+                    Err(error) => {
+                        self.errors
+                            .push(format!("Sink \"{}\": shadow sink: {}", key, error));
+                        continue;
+                    }
+                    Ok((shadow, _)) => Some(Shadow::new(key, config, shadow)),
+                },
+                None => None,
+            };
+
             let (sink, healthcheck) = match sink.inner.build(cx).await {
                 Err(error) => {
                     self.errors.push(format!("Sink \"{}\": {}", key, error));
@@ -545,6 +758,8 @@ impl<'a> Builder<'a> {
             };
 
             let (trigger, tripwire) = Tripwire::new();
+            let latency = SinkLatency::new(self.config);
+            let event_labels = EventLabels::new(self.config);
 
             let sink = async move {
                 debug!("Sink starting.");
@@ -561,29 +776,81 @@ impl<'a> Builder<'a> {
                     .take()
                     .expect("Task started but input has been taken.");
 
-                let mut rx = wrap(rx);
+                let mut rx = wrap(AbandonOnDrop::new(rx));
 
                 let events_received = register!(EventsReceived);
-                sink.run(
-                    rx.by_ref()
-                        .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                        .inspect(|events| {
-                            events_received.emit(CountByteSize(
-                                events.len(),
-                                events.estimated_json_encoded_size_of(),
-                            ))
+                let shadow = shadow.map(Shadow::start);
+                let input = rx
+                    .by_ref()
+                    .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
+                    .filter_map(move |events| {
+                        ready(match &schema_validator {
+                            Some(validator) => validator.validate(events),
+                            None => Some(events),
                         })
-                        .take_until_if(tripwire),
-                )
-                .await
-                .map(|_| {
-                    debug!("Sink finished normally.");
-                    TaskOutput::Sink(rx)
-                })
-                .map_err(|_| {
-                    debug!("Sink finished with an error.");
-                    TaskError::Opaque
-                })
+                    })
+                    .map(move |mut events| {
+                        if let Some(latency) = &latency {
+                            latency.track(&mut events);
+                        }
+                        events
+                    })
+                    .filter_map(move |events| {
+                        // Encrypt the fields last, for the sink to receive their ciphertext.
+                        match &field_encryption {
+                            Some(encryption) => {
+                                let encryption = Arc::clone(encryption);
+                                Either::Left(
+                                    async move { encryption.encrypt(events).await }.boxed(),
+                                )
+                            }
+                            None => Either::Right(ready(Some(events))),
+                        }
+                    })
+                    .inspect(move |events| {
+                        if let Some(shadow) = &shadow {
+                            shadow.copy(events);
+                        }
+                    });
+                let input = MemoryBudget::new(
+                    input,
+                    memory_limit,
+                    Arc::clone(&GLOBAL_MEMORY_USAGE),
+                    memory_budget.limit_bytes,
+                    memory_budget.when_exceeded.unwrap_or_default(),
+                );
+                let input = Quarantine::new(input, quarantine_state, quarantine_policy);
+
+                let health_monitor =
+                    health_monitor.map(|monitor| tokio::spawn(monitor.run().in_current_span()));
+                let result = sink
+                    .run(
+                        input
+                            .inspect(|events| {
+                                let byte_size = events.estimated_json_encoded_size_with(|size| {
+                                    events_received.record_event_size(size);
+                                });
+                                events_received.emit(CountByteSize(events.len(), byte_size));
+                                if let Some(event_labels) = &event_labels {
+                                    event_labels.received(events.iter_events());
+                                }
+                            })
+                            .take_until_if(tripwire),
+                    )
+                    .await;
+                if let Some(health_monitor) = health_monitor {
+                    health_monitor.abort();
+                }
+
+                result
+                    .map(|_| {
+                        debug!("Sink finished normally.");
+                        TaskOutput::Sink(rx.into_inner().into_inner())
+                    })
+                    .map_err(|_| {
+                        debug!("Sink finished with an error.");
+                        TaskError::Opaque
+                    })
             };
 
             let task = Task::new(key.clone(), typetag, sink);
@@ -591,8 +858,7 @@ impl<'a> Builder<'a> {
             let component_key = key.clone();
             let healthcheck_task = async move {
                 if enable_healthcheck {
-                    let duration = Duration::from_secs(10);
-                    timeout(duration, healthcheck)
+                    timeout(HEALTHCHECK_TIMEOUT, healthcheck)
                         .map(|result| match result {
                             Ok(Ok(_)) => {
                                 info!("Healthcheck passed.");
@@ -642,14 +908,16 @@ impl<'a> Builder<'a> {
 pub struct Pieces {
     pub(super) inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
     pub(crate) outputs: HashMap<ComponentKey, HashMap<Option<String>, fanout::ControlChannel>>,
+    pub(crate) send_statuses: HashMap<OutputId, WatchedOutput>,
     pub(super) tasks: HashMap<ComponentKey, Task>,
     pub(crate) source_tasks: HashMap<ComponentKey, Task>,
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
+    pub(super) runtimes: HashMap<ComponentKey, DedicatedRuntime>,
 }
 
-const fn filter_events_type(events: &EventArray, data_type: DataType) -> bool {
+pub(super) const fn filter_events_type(events: &EventArray, data_type: DataType) -> bool {
     match events {
         EventArray::Logs(_) => data_type.contains(DataType::Log),
         EventArray::Metrics(_) => data_type.contains(DataType::Metric),
@@ -665,6 +933,10 @@ struct TransformNode {
     input_details: Input,
     outputs: Vec<TransformOutput>,
     enable_concurrency: bool,
+    lineage: Option<TransformLineage>,
+    event_labels: Option<EventLabels>,
+    size_guard: Option<EventSizeGuard>,
+    ordered_merge: Option<OrderedMergeConfig>,
 }
 
 impl TransformNode {
@@ -673,6 +945,9 @@ impl TransformNode {
         transform: &TransformOuter<OutputId>,
         schema_definition: &[(OutputId, Definition)],
         global_log_namespace: LogNamespace,
+        lineage: Option<TransformLineage>,
+        event_labels: Option<EventLabels>,
+        size_guard: Option<EventSizeGuard>,
     ) -> Self {
         Self {
             key,
@@ -683,25 +958,51 @@ impl TransformNode {
                 .inner
                 .outputs(schema_definition, global_log_namespace),
             enable_concurrency: transform.inner.enable_concurrency(),
+            lineage,
+            event_labels,
+            size_guard,
+            ordered_merge: transform.ordered_merge.clone(),
         }
     }
 }
 
+/// The input stream of a transform, optionally merged by event timestamp.
+type TransformInput = Either<
+    AbandonOnDrop<BufferReceiverStream<EventArray>>,
+    OrderedMerge<AbandonOnDrop<BufferReceiverStream<EventArray>>>,
+>;
+
+/// A built transform, along with the control channels and the send statuses of its outputs.
+type BuiltTransform = (
+    Task,
+    HashMap<OutputId, fanout::ControlChannel>,
+    HashMap<OutputId, WatchedOutput>,
+);
+
 fn build_transform(
     transform: Transform,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
-) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+) -> BuiltTransform {
+    let input_rx = AbandonOnDrop::new(input_rx.into_stream());
+    let input = match &node.ordered_merge {
+        Some(config) => Either::Right(OrderedMerge::new(input_rx, config)),
+        None => Either::Left(input_rx),
+    };
+
     match transform {
         // TODO: avoid the double boxing for function transforms here
-        Transform::Function(t) => build_sync_transform(Box::new(t), node, input_rx),
-        Transform::Synchronous(t) => build_sync_transform(t, node, input_rx),
+        Transform::Function(t) => build_sync_transform(Box::new(t), node, input),
+        Transform::Synchronous(t) => build_sync_transform(t, node, input),
         Transform::Task(t) => build_task_transform(
             t,
-            input_rx,
+            input,
             node.input_details.data_type(),
             node.typetag,
             &node.key,
+            node.lineage,
+            node.event_labels,
+            node.size_guard,
         ),
     }
 }
@@ -709,11 +1010,20 @@ fn build_transform(
 fn build_sync_transform(
     t: Box<dyn SyncTransform>,
     node: TransformNode,
-    input_rx: BufferReceiver<EventArray>,
-) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+    input: TransformInput,
+) -> BuiltTransform {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
-
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
+    let send_statuses = outputs.send_statuses();
+
+    let runner = Runner::new(
+        t,
+        input,
+        node.input_details.data_type(),
+        outputs,
+        node.lineage.clone(),
+        node.event_labels.clone(),
+        node.size_guard.clone(),
+    );
     let transform = if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
@@ -735,35 +1045,46 @@ fn build_sync_transform(
         }
     };
 
+    let output_id = |name: Option<String>| {
+        name.map(|name| OutputId::from((&node.key, name)))
+            .unwrap_or_else(|| OutputId::from(&node.key))
+    };
     let mut output_controls = HashMap::new();
     for (name, control) in controls {
-        let id = name
-            .map(|name| OutputId::from((&node.key, name)))
-            .unwrap_or_else(|| OutputId::from(&node.key));
-        output_controls.insert(id, control);
+        output_controls.insert(output_id(name), control);
+    }
+    let mut output_statuses = HashMap::new();
+    for (name, status) in send_statuses {
+        output_statuses.insert(output_id(name), WatchedOutput::transform(status));
     }
 
     let task = Task::new(node.key.clone(), node.typetag, transform);
 
-    (task, output_controls)
+    (task, output_controls, output_statuses)
 }
 
 struct Runner {
     transform: Box<dyn SyncTransform>,
-    input_rx: Option<BufferReceiver<EventArray>>,
+    input_rx: Option<TransformInput>,
     input_type: DataType,
     outputs: TransformOutputs,
     timer: crate::utilization::Timer,
     last_report: Instant,
     events_received: Registered<EventsReceived>,
+    lineage: Option<TransformLineage>,
+    event_labels: Option<EventLabels>,
+    size_guard: Option<EventSizeGuard>,
 }
 
 impl Runner {
     fn new(
         transform: Box<dyn SyncTransform>,
-        input_rx: BufferReceiver<EventArray>,
+        input_rx: TransformInput,
         input_type: DataType,
         outputs: TransformOutputs,
+        lineage: Option<TransformLineage>,
+        event_labels: Option<EventLabels>,
+        size_guard: Option<EventSizeGuard>,
     ) -> Self {
         Self {
             transform,
@@ -773,23 +1094,40 @@ impl Runner {
             timer: crate::utilization::Timer::new(),
             last_report: Instant::now(),
             events_received: register!(EventsReceived),
+            lineage,
+            event_labels,
+            size_guard,
         }
     }
 
-    fn on_events_received(&mut self, events: &EventArray) {
+    fn on_events_received(&mut self, events: &mut EventArray) {
         let stopped = self.timer.stop_wait();
         if stopped.duration_since(self.last_report).as_secs() >= 5 {
             self.timer.report();
             self.last_report = stopped;
         }
 
-        self.events_received.emit(CountByteSize(
-            events.len(),
-            events.estimated_json_encoded_size_of(),
-        ));
+        let byte_size = events.estimated_json_encoded_size_with(|size| {
+            self.events_received.record_event_size(size);
+        });
+        self.events_received
+            .emit(CountByteSize(events.len(), byte_size));
+
+        if let Some(event_labels) = &self.event_labels {
+            event_labels.received(events.iter_events());
+        }
+        if let Some(lineage) = &self.lineage {
+            lineage.stamp(events);
+        }
     }
 
     async fn send_outputs(&mut self, outputs_buf: &mut TransformOutputsBuf) -> crate::Result<()> {
+        if let Some(size_guard) = &self.size_guard {
+            outputs_buf.update_arrays(|array| size_guard.enforce(array));
+        }
+        if let Some(event_labels) = &self.event_labels {
+            event_labels.sent(outputs_buf.iter_events());
+        }
         self.timer.start_wait();
         self.outputs.send(outputs_buf).await
     }
@@ -804,12 +1142,11 @@ impl Runner {
             .input_rx
             .take()
             .expect("can't run runner twice")
-            .into_stream()
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
         self.timer.start_wait();
-        while let Some(events) = input_rx.next().await {
-            self.on_events_received(&events);
+        while let Some(mut events) = input_rx.next().await {
+            self.on_events_received(&mut events);
             self.transform.transform_all(events, &mut outputs_buf);
             self.send_outputs(&mut outputs_buf)
                 .await
@@ -824,7 +1161,6 @@ impl Runner {
             .input_rx
             .take()
             .expect("can't run runner twice")
-            .into_stream()
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
         let mut input_rx =
@@ -851,9 +1187,9 @@ impl Runner {
 
                 input_arrays = input_rx.next(), if in_flight.len() < *TRANSFORM_CONCURRENCY_LIMIT && !shutting_down => {
                     match input_arrays {
-                        Some(input_arrays) => {
+                        Some(mut input_arrays) => {
                             let mut len = 0;
-                            for events in &input_arrays {
+                            for events in &mut input_arrays {
                                 self.on_events_received(events);
                                 len += events.len();
                             }
@@ -887,34 +1223,56 @@ impl Runner {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_task_transform(
     t: Box<dyn TaskTransform<EventArray>>,
-    input_rx: BufferReceiver<EventArray>,
+    input_rx: TransformInput,
     input_type: DataType,
     typetag: &str,
     key: &ComponentKey,
-) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+    lineage: Option<TransformLineage>,
+    event_labels: Option<EventLabels>,
+    size_guard: Option<EventSizeGuard>,
+) -> BuiltTransform {
     let (mut fanout, control) = Fanout::new();
+    let send_status = WatchedOutput::transform(fanout.send_status());
 
-    let input_rx = crate::utilization::wrap(input_rx.into_stream());
+    let input_rx = crate::utilization::wrap(input_rx);
 
     let events_received = register!(EventsReceived);
+    let received_labels = event_labels.clone();
     let filtered = input_rx
         .filter(move |events| ready(filter_events_type(events, input_type)))
         .inspect(move |events| {
-            events_received.emit(CountByteSize(
-                events.len(),
-                events.estimated_json_encoded_size_of(),
-            ))
+            let byte_size = events
+                .estimated_json_encoded_size_with(|size| events_received.record_event_size(size));
+            events_received.emit(CountByteSize(events.len(), byte_size));
+            if let Some(event_labels) = &received_labels {
+                event_labels.received(events.iter_events());
+            }
+        })
+        .map(move |mut events| {
+            if let Some(lineage) = &lineage {
+                lineage.stamp(&mut events);
+            }
+            events
         });
     let events_sent = register!(EventsSent::from(internal_event::Output(None)));
     let stream = t
         .transform(Box::pin(filtered))
+        .filter_map(move |mut events: EventArray| {
+            if let Some(size_guard) = &size_guard {
+                size_guard.enforce(&mut events);
+            }
+            ready((!events.is_empty()).then_some(events))
+        })
         .inspect(move |events: &EventArray| {
-            events_sent.emit(CountByteSize(
-                events.len(),
-                events.estimated_json_encoded_size_of(),
-            ));
+            let byte_size =
+                events.estimated_json_encoded_size_with(|size| events_sent.record_event_size(size));
+            events_sent.emit(CountByteSize(events.len(), byte_size));
+            if let Some(event_labels) = &event_labels {
+                event_labels.sent(events.iter_events());
+            }
         });
     let transform = async move {
         debug!("Task transform starting.");
@@ -934,8 +1292,10 @@ fn build_task_transform(
 
     let mut outputs = HashMap::new();
     outputs.insert(OutputId::from(key), control);
+    let mut send_statuses = HashMap::new();
+    send_statuses.insert(OutputId::from(key), send_status);
 
     let task = Task::new(key.clone(), typetag, transform);
 
-    (task, outputs)
+    (task, outputs, send_statuses)
 }
@@ -14,7 +14,7 @@ use crate::config::enterprise::{
 use crate::internal_events::{
     VectorConfigLoadError, VectorRecoveryError, VectorReloadError, VectorReloaded,
 };
-use crate::{config, topology::RunningTopology};
+use crate::{audit, config, topology::RunningTopology};
 
 #[derive(Clone, Debug)]
 pub struct SharedTopologyController(Arc<Mutex<TopologyController>>);
@@ -93,8 +93,18 @@ impl TopologyController {
             }
         }
 
+        let audit_diff = audit::AuditConfigDiff::from(&config::ConfigDiff::new(
+            self.topology.config(),
+            &new_config,
+        ));
+
         match self.topology.reload_config_and_respawn(new_config).await {
             Ok(true) => {
+                audit::record(audit::AuditAction::ConfigReloaded {
+                    config_paths: audit::config_paths(&self.config_paths),
+                    diff: audit_diff,
+                });
+
                 #[cfg(feature = "api")]
                 // Pass the new config to the API server.
                 if let Some(ref api_server) = self.api_server {
@@ -0,0 +1,460 @@
+//! Encryption of fields of the events sent to sinks.
+//!
+//! A data key is generated when the sink is built, and wrapped by the configured key encryption
+//! key. The fields are encrypted with AES-256-GCM using the data key, and each event gets an
+//! envelope in its metadata holding the wrapped data key, which is all that is needed to decrypt
+//! its fields along with the key encryption key. The data key is replaced by a new one once it
+//! encrypted a given number of fields, or reached a given age.
+use std::{
+    collections::BTreeMap,
+    fs,
+    time::{Duration, Instant},
+};
+
+use lookup::{lookup_v2::OwnedTargetPath, metadata_path};
+use openssl::{
+    base64,
+    rand::rand_bytes,
+    symm::{encrypt_aead, Cipher},
+};
+use tokio::sync::Mutex;
+use value::Value;
+use vector_core::event::{EventArray, EventContainer, EventStatus, Finalizable, LogEvent};
+
+use crate::{
+    config::{FieldEncryptionConfig, FileKeyConfig, KeyEncryptionKeyConfig, ProxyConfig},
+    internal_events::FieldEncryptionFailed,
+};
+
+const ALGORITHM: &str = "AES-256-GCM";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Encrypts fields of the events sent to a sink.
+pub(super) struct FieldEncryption {
+    key: KeyEncryptionKeyConfig,
+    proxy: ProxyConfig,
+    fields: Vec<OwnedTargetPath>,
+    max_seals: u64,
+    rotation_interval: Duration,
+    data_key: Mutex<DataKey>,
+}
+
+/// A data key, with the envelope of the events whose fields it encrypts.
+struct DataKey {
+    key: [u8; KEY_LEN],
+    /// The envelope of the events, without the paths of the encrypted fields.
+    envelope: BTreeMap<String, Value>,
+    /// The number of fields encrypted with the key.
+    seals: u64,
+    generated_at: Instant,
+}
+
+impl DataKey {
+    #[cfg_attr(not(feature = "field-encryption-aws_kms"), allow(unused_variables))]
+    async fn generate(key: &KeyEncryptionKeyConfig, proxy: &ProxyConfig) -> Result<Self, String> {
+        let (data_key, wrapped_key, key_id) = match key {
+            #[cfg(feature = "field-encryption-aws_kms")]
+            KeyEncryptionKeyConfig::AwsKms(key) => aws_kms::generate_data_key(key, proxy).await?,
+            KeyEncryptionKeyConfig::File(key) => generate_file_data_key(key)?,
+        };
+
+        let envelope = BTreeMap::from([
+            ("algorithm".to_owned(), Value::from(ALGORITHM)),
+            ("key_id".to_owned(), Value::from(key_id)),
+            (
+                "data_key".to_owned(),
+                Value::from(base64::encode_block(&wrapped_key)),
+            ),
+        ]);
+        Ok(Self {
+            key: data_key,
+            envelope,
+            seals: 0,
+            generated_at: Instant::now(),
+        })
+    }
+}
+
+impl FieldEncryption {
+    pub(super) async fn new(
+        config: &FieldEncryptionConfig,
+        proxy: &ProxyConfig,
+    ) -> Result<Self, String> {
+        if config.fields.is_empty() {
+            return Err("At least one field to encrypt must be set.".into());
+        }
+        if config.data_key_max_seals < config.fields.len() as u64 {
+            return Err(
+                "The `data_key_max_seals` must be at least the number of fields to encrypt.".into(),
+            );
+        }
+
+        let data_key = DataKey::generate(&config.key, proxy).await?;
+        Ok(Self {
+            key: config.key.clone(),
+            proxy: proxy.clone(),
+            fields: config.fields.iter().map(|field| field.0.clone()).collect(),
+            max_seals: config.data_key_max_seals,
+            rotation_interval: Duration::from_secs(config.data_key_rotation_secs),
+            data_key: Mutex::new(data_key),
+        })
+    }
+
+    /// Encrypts the fields of the log and trace events, removing the events which couldn't be
+    /// encrypted, and returning `None` if none are left.
+    pub(super) async fn encrypt(&self, mut events: EventArray) -> Option<EventArray> {
+        let failed = match &mut events {
+            EventArray::Logs(logs) => self.retain_encrypted(logs, |log| log).await,
+            EventArray::Traces(traces) => {
+                self.retain_encrypted(traces, |trace| trace.as_mut()).await
+            }
+            EventArray::Metrics(_) => None,
+        };
+        if let Some((count, error)) = failed {
+            emit!(FieldEncryptionFailed { count, error });
+        }
+
+        (!events.is_empty()).then_some(events)
+    }
+
+    /// Encrypts the fields of the events, rejecting the ones which couldn't be encrypted, and
+    /// returning their number and the first error.
+    async fn retain_encrypted<T: Finalizable>(
+        &self,
+        events: &mut Vec<T>,
+        as_log: impl Fn(&mut T) -> &mut LogEvent,
+    ) -> Option<(usize, String)> {
+        let mut data_key = self.data_key.lock().await;
+        let mut count = 0;
+        let mut first_error = None;
+        // A failed rotation is only retried with the next events sent to the sink.
+        let mut rotation_error = None;
+        let mut encrypted = Vec::with_capacity(events.len());
+        for mut event in events.drain(..) {
+            if rotation_error.is_none() && self.must_rotate(&data_key) {
+                match DataKey::generate(&self.key, &self.proxy).await {
+                    Ok(new_key) => *data_key = new_key,
+                    Err(error) => rotation_error = Some(error),
+                }
+            }
+            let result = match &rotation_error {
+                Some(error) => Err(format!("Could not rotate the data key: {}", error)),
+                None => self.encrypt_log(&mut data_key, as_log(&mut event)),
+            };
+            match result {
+                Ok(()) => encrypted.push(event),
+                Err(error) => {
+                    count += 1;
+                    first_error.get_or_insert(error);
+                    event.take_finalizers().update_status(EventStatus::Rejected);
+                }
+            }
+        }
+        *events = encrypted;
+        first_error.map(|error| (count, error))
+    }
+
+    /// Returns whether the data key must be replaced before encrypting the fields of an event.
+    fn must_rotate(&self, data_key: &DataKey) -> bool {
+        data_key.seals + self.fields.len() as u64 > self.max_seals
+            || data_key.generated_at.elapsed() >= self.rotation_interval
+    }
+
+    /// Encrypts the fields of the event and adds its envelope, leaving it untouched on failure or
+    /// when it has none of the fields.
+    fn encrypt_log(&self, data_key: &mut DataKey, log: &mut LogEvent) -> Result<(), String> {
+        let mut encrypted = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            let Some(value) = log.get(field) else {
+                continue;
+            };
+            let path = String::from(field.clone());
+            let plaintext = serde_json::to_vec(value).map_err(|error| error.to_string())?;
+            let ciphertext = seal(&data_key.key[..], path.as_bytes(), &plaintext)?;
+            data_key.seals += 1;
+            encrypted.push((field, path, ciphertext));
+        }
+        if encrypted.is_empty() {
+            return Ok(());
+        }
+
+        let mut envelope = data_key.envelope.clone();
+        let mut paths = Vec::with_capacity(encrypted.len());
+        for (field, path, ciphertext) in encrypted {
+            log.insert(field, base64::encode_block(&ciphertext));
+            paths.push(Value::from(path));
+        }
+        envelope.insert("fields".to_owned(), Value::Array(paths));
+        log.insert(metadata_path!("field_encryption"), Value::Object(envelope));
+        Ok(())
+    }
+}
+
+/// Encrypts the plaintext with AES-256-GCM, returning the nonce, the ciphertext and the tag.
+fn seal(key: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce = [0; NONCE_LEN];
+    let mut tag = [0; TAG_LEN];
+    rand_bytes(&mut nonce).map_err(|error| error.to_string())?;
+    let ciphertext = encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce),
+        aad,
+        plaintext,
+        &mut tag,
+    )
+    .map_err(|error| format!("Encryption failed: {}", error))?;
+
+    let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len() + TAG_LEN);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    sealed.extend_from_slice(&tag);
+    Ok(sealed)
+}
+
+/// Generates a data key wrapped by a local key, returning the data key, the wrapped data key and
+/// the ID of the local key.
+fn generate_file_data_key(
+    config: &FileKeyConfig,
+) -> Result<([u8; KEY_LEN], Vec<u8>, String), String> {
+    let content = fs::read(&config.path)
+        .map_err(|error| format!("Could not read key from {:?}: {}", config.path, error))?;
+    let key = if content.len() == KEY_LEN {
+        content
+    } else {
+        std::str::from_utf8(&content)
+            .ok()
+            .and_then(|encoded| base64::decode_block(encoded.trim()).ok())
+            .filter(|key| key.len() == KEY_LEN)
+            .ok_or_else(|| {
+                format!(
+                    "Key in {:?} must be {} bytes, or their base64 encoding.",
+                    config.path, KEY_LEN
+                )
+            })?
+    };
+
+    let mut data_key = [0; KEY_LEN];
+    rand_bytes(&mut data_key).map_err(|error| error.to_string())?;
+    let wrapped_key = seal(&key, &[], &data_key)?;
+    let key_id = config
+        .key_id
+        .clone()
+        .unwrap_or_else(|| config.path.display().to_string());
+    Ok((data_key, wrapped_key, key_id))
+}
+
+#[cfg(feature = "field-encryption-aws_kms")]
+mod aws_kms {
+    use aws_sdk_kms::model::DataKeySpec;
+
+    use super::KEY_LEN;
+    use crate::{
+        aws::create_client, common::kms::KmsClientBuilder, config::AwsKmsKeyConfig,
+        config::ProxyConfig,
+    };
+
+    /// Generates a data key wrapped by a KMS key, returning the data key, the wrapped data key and
+    /// the ARN of the KMS key.
+    pub(super) async fn generate_data_key(
+        config: &AwsKmsKeyConfig,
+        proxy: &ProxyConfig,
+    ) -> Result<([u8; KEY_LEN], Vec<u8>, String), String> {
+        let client = create_client::<KmsClientBuilder>(
+            &config.auth,
+            config.region.region(),
+            config
+                .region
+                .endpoint()
+                .map_err(|error| error.to_string())?,
+            proxy,
+            &config.tls,
+            true,
+        )
+        .await
+        .map_err(|error| format!("Could not create KMS client: {}", error))?;
+
+        let output = client
+            .generate_data_key()
+            .key_id(&config.key_id)
+            .key_spec(DataKeySpec::Aes256)
+            .send()
+            .await
+            .map_err(|error| format!("Could not generate data key with KMS: {}", error))?;
+
+        let data_key = output
+            .plaintext()
+            .and_then(|key| <[u8; KEY_LEN]>::try_from(key.as_ref()).ok())
+            .ok_or("KMS returned an invalid data key.")?;
+        let wrapped_key = output
+            .ciphertext_blob()
+            .map(|key| key.as_ref().to_vec())
+            .ok_or("KMS did not return the wrapped data key.")?;
+        let key_id = output.key_id().unwrap_or(&config.key_id).to_owned();
+        Ok((data_key, wrapped_key, key_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::symm::decrypt_aead;
+
+    use super::*;
+    use crate::{
+        event::{Event, Metric, MetricKind, MetricValue},
+        test_util::temp_file,
+    };
+
+    fn open(key: &[u8], aad: &[u8], sealed: &[u8]) -> Vec<u8> {
+        let (nonce, rest) = sealed.split_at(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+        decrypt_aead(
+            Cipher::aes_256_gcm(),
+            key,
+            Some(nonce),
+            aad,
+            ciphertext,
+            tag,
+        )
+        .unwrap()
+    }
+
+    fn config(key: &[u8]) -> FieldEncryptionConfig {
+        let path = temp_file();
+        fs::write(&path, base64::encode_block(key)).unwrap();
+        FieldEncryptionConfig {
+            fields: vec![
+                "user.email".to_owned().try_into().unwrap(),
+                "missing".to_owned().try_into().unwrap(),
+            ],
+            data_key_max_seals: 1 << 32,
+            data_key_rotation_secs: 86400,
+            key: KeyEncryptionKeyConfig::File(FileKeyConfig {
+                path,
+                key_id: Some("test".to_owned()),
+            }),
+        }
+    }
+
+    fn user_log(email: &str) -> LogEvent {
+        let mut log = LogEvent::from("message");
+        log.insert("user.email", email);
+        log
+    }
+
+    async fn encrypt_logs(config: &FieldEncryptionConfig, logs: Vec<LogEvent>) -> Vec<LogEvent> {
+        let encryption = FieldEncryption::new(config, &ProxyConfig::default())
+            .await
+            .unwrap();
+        let events = encryption.encrypt(EventArray::Logs(logs)).await.unwrap();
+        events.into_events().map(Event::into_log).collect()
+    }
+
+    fn envelope<'a>(log: &'a LogEvent, key: &str) -> &'a Value {
+        log.get(metadata_path!("field_encryption", key)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn encrypts_fields() {
+        let key = [7; KEY_LEN];
+        let logs = encrypt_logs(&config(&key), vec![user_log("jane@example.com")]).await;
+        let log = &logs[0];
+        assert_eq!(log["message"], "message".into());
+        assert_eq!(*envelope(log, "algorithm"), ALGORITHM.into());
+        assert_eq!(*envelope(log, "key_id"), "test".into());
+        assert_eq!(
+            *envelope(log, "fields"),
+            Value::Array(vec!["user.email".into()])
+        );
+        assert!(!log.contains("missing"));
+        // The envelope is only stored in the metadata.
+        assert!(!log.contains("encryption"));
+        assert!(!log.contains("field_encryption"));
+
+        // The fields can be decrypted with the data key unwrapped with the key.
+        let decode = |value: &Value| base64::decode_block(value.to_string_lossy().as_ref());
+        let data_key = open(&key, &[], &decode(envelope(log, "data_key")).unwrap());
+        let email = open(
+            &data_key,
+            b"user.email",
+            &decode(&log["user.email"]).unwrap(),
+        );
+        assert_eq!(email, br#""jane@example.com""#);
+    }
+
+    #[tokio::test]
+    async fn skips_events_without_fields() {
+        let log = LogEvent::from("message");
+        let logs = encrypt_logs(&config(&[7; KEY_LEN]), vec![log.clone()]).await;
+        assert_eq!(logs, [log]);
+        assert!(logs[0].get(metadata_path!("field_encryption")).is_none());
+    }
+
+    #[tokio::test]
+    async fn rotates_data_key_after_max_seals() {
+        let mut config = config(&[7; KEY_LEN]);
+        // Two fields are configured, so the third event could exceed the seals of the first key.
+        config.data_key_max_seals = 3;
+        let logs = (0..5).map(|i| user_log(&format!("user{}@example.com", i)));
+        let logs = encrypt_logs(&config, logs.collect()).await;
+
+        let data_keys = logs
+            .iter()
+            .map(|log| envelope(log, "data_key").clone())
+            .collect::<Vec<_>>();
+        assert_eq!(data_keys[0], data_keys[1]);
+        assert_ne!(data_keys[1], data_keys[2]);
+        assert_eq!(data_keys[2], data_keys[3]);
+        assert_ne!(data_keys[3], data_keys[4]);
+    }
+
+    #[tokio::test]
+    async fn rotates_data_key_after_interval() {
+        let mut config = config(&[7; KEY_LEN]);
+        config.data_key_rotation_secs = 0;
+        let logs = vec![user_log("jane@example.com"), user_log("john@example.com")];
+        let logs = encrypt_logs(&config, logs).await;
+
+        assert_ne!(
+            envelope(&logs[0], "data_key"),
+            envelope(&logs[1], "data_key")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_too_few_max_seals() {
+        let mut config = config(&[7; KEY_LEN]);
+        config.data_key_max_seals = 1;
+        assert!(FieldEncryption::new(&config, &ProxyConfig::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_keys() {
+        let path = temp_file();
+        fs::write(&path, "short").unwrap();
+        let mut config = config(&[7; KEY_LEN]);
+        config.key = KeyEncryptionKeyConfig::File(FileKeyConfig { path, key_id: None });
+        assert!(FieldEncryption::new(&config, &ProxyConfig::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn passes_metrics_through() {
+        let encryption = FieldEncryption::new(&config(&[7; KEY_LEN]), &ProxyConfig::default())
+            .await
+            .unwrap();
+        let metric = Metric::new(
+            "counter",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        let events = encryption.encrypt(metric.clone().into()).await.unwrap();
+        assert_eq!(events, metric.into());
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures::{
+    task::{Context, Poll},
+    FutureExt, Stream, StreamExt,
+};
+use once_cell::sync::Lazy;
+use tokio::time::{sleep, Sleep};
+use tracing::Instrument;
+use vector_core::{config::MemoryShedPolicy, ByteSizeOf};
+
+use crate::{
+    event::{BatchNotifier, EventArray, EventContainer, EventFinalizer},
+    internal_events::{ComponentMemoryUsed, MemoryLimitExceeded},
+};
+
+/// How long a blocked sink waits before checking its memory limits again.
+const BLOCKED_RECHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The approximate size of the events held in flight by all sinks.
+pub(super) static GLOBAL_MEMORY_USAGE: Lazy<Arc<MemoryUsage>> = Lazy::new(Default::default);
+
+/// The approximate size of the events held in flight by one or more sinks.
+#[derive(Debug, Default)]
+pub(super) struct MemoryUsage(AtomicUsize);
+
+impl MemoryUsage {
+    fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, bytes: usize) -> usize {
+        self.0.fetch_add(bytes, Ordering::Relaxed) + bytes
+    }
+
+    fn release(&self, bytes: usize) -> usize {
+        self.0.fetch_sub(bytes, Ordering::Relaxed) - bytes
+    }
+}
+
+/// Input stream of a sink which accounts for the size of the events it holds in flight, and
+/// applies the shedding policy while one of its memory limits is exceeded.
+///
+/// Events are accounted for from the time they are read until they are finalized, which is when
+/// they have been delivered or dropped by the sink. Without any limit, events are passed through
+/// as is.
+pub(super) struct MemoryBudget<S> {
+    inner: S,
+    usage: Arc<MemoryUsage>,
+    limit: Option<usize>,
+    global_usage: Arc<MemoryUsage>,
+    global_limit: Option<usize>,
+    policy: MemoryShedPolicy,
+    blocked: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S> MemoryBudget<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    /// Wraps the input stream of the sink of the current span.
+    pub(super) fn new(
+        inner: S,
+        limit: Option<usize>,
+        global_usage: Arc<MemoryUsage>,
+        global_limit: Option<usize>,
+        policy: MemoryShedPolicy,
+    ) -> Self {
+        Self {
+            inner,
+            usage: Arc::default(),
+            limit,
+            global_usage,
+            global_limit,
+            policy,
+            blocked: None,
+        }
+    }
+
+    /// Returns the limit currently exceeded, if any.
+    fn exceeded(&self) -> Option<&'static str> {
+        let exceeds = |usage: &MemoryUsage, limit: Option<usize>| {
+            limit.map_or(false, |limit| usage.get() >= limit)
+        };
+        if exceeds(&self.usage, self.limit) {
+            Some("component")
+        } else if exceeds(&self.global_usage, self.global_limit) {
+            Some("global")
+        } else {
+            None
+        }
+    }
+
+    /// Accounts for the events until they are finalized.
+    fn track(&self, events: &mut EventArray) {
+        let bytes = events.size_of();
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        for mut event in events.iter_events_mut() {
+            event
+                .metadata_mut()
+                .add_finalizer(EventFinalizer::new(batch.clone()));
+        }
+        drop(batch);
+
+        emit!(ComponentMemoryUsed {
+            bytes: self.usage.add(bytes)
+        });
+        self.global_usage.add(bytes);
+
+        let usage = Arc::clone(&self.usage);
+        let global_usage = Arc::clone(&self.global_usage);
+        tokio::spawn(
+            async move {
+                receiver.await;
+                emit!(ComponentMemoryUsed {
+                    bytes: usage.release(bytes)
+                });
+                global_usage.release(bytes);
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+impl<S> Stream for MemoryBudget<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.limit.is_none() && this.global_limit.is_none() {
+            return this.inner.poll_next_unpin(cx);
+        }
+
+        loop {
+            if let Some(blocked) = this.blocked.as_mut() {
+                if blocked.poll_unpin(cx).is_pending() {
+                    return Poll::Pending;
+                }
+            }
+
+            let exceeded = this.exceeded();
+            match (exceeded, this.policy) {
+                (Some(limit), MemoryShedPolicy::Block) => {
+                    if this.blocked.is_none() {
+                        emit!(MemoryLimitExceeded {
+                            limit,
+                            policy: "block",
+                            count: 0,
+                        });
+                    }
+                    this.blocked = Some(Box::pin(sleep(BLOCKED_RECHECK_INTERVAL)));
+                    continue;
+                }
+                (None, _) => this.blocked = None,
+                (Some(_), MemoryShedPolicy::DropNewest) => {}
+            }
+
+            let mut events = match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(events)) => events,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            match exceeded {
+                Some(limit) => emit!(MemoryLimitExceeded {
+                    limit,
+                    policy: "drop_newest",
+                    count: events.len(),
+                }),
+                None => {
+                    this.track(&mut events);
+                    return Poll::Ready(Some(events));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn events(count: usize) -> Vec<EventArray> {
+        (0..count).map(|_| LogEvent::from("event").into()).collect()
+    }
+
+    fn budget(
+        input: Vec<EventArray>,
+        limit: usize,
+        policy: MemoryShedPolicy,
+    ) -> MemoryBudget<stream::Iter<std::vec::IntoIter<EventArray>>> {
+        MemoryBudget::new(
+            stream::iter(input),
+            Some(limit),
+            Arc::default(),
+            None,
+            policy,
+        )
+    }
+
+    #[tokio::test]
+    async fn releases_finalized_events() {
+        let mut input = budget(events(2), usize::MAX, MemoryShedPolicy::Block);
+
+        let events = input.next().await.unwrap();
+        assert_eq!(input.usage.get(), events.size_of());
+        assert_eq!(input.global_usage.get(), events.size_of());
+
+        drop(events);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(input.usage.get(), 0);
+        assert_eq!(input.global_usage.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn drops_newest_events_over_limit() {
+        let mut input = budget(events(3), 1, MemoryShedPolicy::DropNewest);
+
+        let held = input.next().await.unwrap();
+        assert_eq!(held.len(), 1);
+
+        // The events read while the first ones are held are dropped.
+        assert!(input.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn blocks_over_limit() {
+        let mut input = budget(events(2), 1, MemoryShedPolicy::Block);
+
+        let held = input.next().await.unwrap();
+        assert!(input.next().now_or_never().is_none());
+
+        drop(held);
+        let next = input.next().await.unwrap();
+        assert_eq!(next.into_events().count(), 1);
+    }
+}
@@ -10,10 +10,18 @@
 pub(super) use vector_core::fanout;
 pub mod schema;
 
+mod abandon;
 pub mod builder;
 mod controller;
+mod field_encryption;
+mod memory_budget;
+mod ordered_merge;
+mod quarantine;
 mod ready_arrays;
 mod running;
+mod schema_validation;
+mod shadow;
+mod stall;
 mod task;
 
 #[cfg(test)]
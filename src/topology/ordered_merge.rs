@@ -0,0 +1,331 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::{
+    task::{Context, Poll},
+    FutureExt, Stream, StreamExt,
+};
+use tokio::time::{sleep, Sleep};
+use tracing::Span;
+
+use crate::{
+    config::OrderedMergeConfig,
+    event::{Event, EventArray, EventContainer, EventStatus, Finalizable},
+    internal_events::{ComponentEventsAbandoned, OrderedMergeLateEvents},
+};
+
+/// An event held for ordering.
+struct Held {
+    timestamp: DateTime<Utc>,
+    // Events with the same timestamp are released in the order they were received.
+    seq: u64,
+    event: Event,
+}
+
+impl PartialEq for Held {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Held {}
+
+impl PartialOrd for Held {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Held {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.timestamp, self.seq).cmp(&(other.timestamp, other.seq))
+    }
+}
+
+/// A stream combinator merging the input of a transform by event timestamp.
+///
+/// Events are held until an event with a timestamp later by the lateness window has been received,
+/// the lateness window has elapsed since the first of them was held, or the maximum number of
+/// held events has been reached. Events received after later events were released are passed on
+/// as soon as possible, and reported as late.
+pub(crate) struct OrderedMerge<S> {
+    inner: S,
+    inner_done: bool,
+    max_lateness: Duration,
+    max_events: usize,
+    held: BinaryHeap<Reverse<Held>>,
+    ready: VecDeque<Event>,
+    next_seq: u64,
+    latest: Option<DateTime<Utc>>,
+    last_released: Option<DateTime<Utc>>,
+    flush: Option<Pin<Box<Sleep>>>,
+    span: Span,
+}
+
+impl<S> OrderedMerge<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    /// Wraps the input stream of the transform of the current span.
+    pub(crate) fn new(inner: S, config: &OrderedMergeConfig) -> Self {
+        Self {
+            inner,
+            inner_done: false,
+            max_lateness: Duration::from_millis(config.max_lateness_ms),
+            max_events: config.max_events,
+            held: BinaryHeap::new(),
+            ready: VecDeque::new(),
+            next_seq: 0,
+            latest: None,
+            last_released: None,
+            flush: None,
+            span: Span::current(),
+        }
+    }
+
+    /// Holds an event for ordering, returning whether it was received late.
+    fn hold(&mut self, event: Event) -> bool {
+        let timestamp = match event_timestamp(&event) {
+            Some(timestamp) => timestamp,
+            None => {
+                self.ready.push_back(event);
+                return false;
+            }
+        };
+
+        self.latest = self.latest.max(Some(timestamp));
+        self.held.push(Reverse(Held {
+            timestamp,
+            seq: self.next_seq,
+            event,
+        }));
+        self.next_seq += 1;
+
+        self.last_released.map_or(false, |last| timestamp < last)
+    }
+
+    /// Releases the held events which can't be preceded by an event still to be received, or all
+    /// of them.
+    fn release(&mut self, all: bool) {
+        let watermark = self.latest.and_then(|latest| {
+            chrono::Duration::from_std(self.max_lateness)
+                .ok()
+                .and_then(|max_lateness| latest.checked_sub_signed(max_lateness))
+        });
+
+        while let Some(Reverse(earliest)) = self.held.peek() {
+            let release = all
+                || self.held.len() > self.max_events
+                || watermark.map_or(false, |watermark| earliest.timestamp <= watermark);
+            if !release {
+                break;
+            }
+
+            let Reverse(earliest) = self.held.pop().expect("held event was peeked");
+            self.last_released = self.last_released.max(Some(earliest.timestamp));
+            self.ready.push_back(earliest.event);
+        }
+
+        if self.held.is_empty() {
+            self.flush = None;
+        }
+    }
+}
+
+impl<S> Stream for OrderedMerge<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(array) = next_array(&mut this.ready) {
+                return Poll::Ready(Some(array));
+            }
+
+            if this.inner_done {
+                if this.held.is_empty() {
+                    return Poll::Ready(None);
+                }
+                this.release(true);
+                continue;
+            }
+
+            // Take in the input ready to be read, without holding more events than allowed.
+            let mut late = 0;
+            while this.held.len() <= this.max_events {
+                match this.inner.poll_next_unpin(cx) {
+                    Poll::Ready(Some(events)) => {
+                        for event in events.into_events() {
+                            late += usize::from(this.hold(event));
+                        }
+                    }
+                    Poll::Ready(None) => {
+                        this.inner_done = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+            if late > 0 {
+                emit!(OrderedMergeLateEvents { count: late });
+            }
+            if this.inner_done {
+                continue;
+            }
+
+            this.release(false);
+            if !this.ready.is_empty() {
+                continue;
+            }
+            if this.held.is_empty() {
+                return Poll::Pending;
+            }
+
+            let max_lateness = this.max_lateness;
+            let flush = this
+                .flush
+                .get_or_insert_with(|| Box::pin(sleep(max_lateness)));
+            match flush.poll_unpin(cx) {
+                Poll::Ready(()) => this.release(true),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S> Drop for OrderedMerge<S> {
+    fn drop(&mut self) {
+        let mut count = 0;
+        let held = self.held.drain().map(|Reverse(held)| held.event);
+        for mut event in held.chain(self.ready.drain(..)) {
+            count += 1;
+            event.take_finalizers().update_status(EventStatus::Errored);
+        }
+
+        if count > 0 {
+            let _enter = self.span.enter();
+            emit!(ComponentEventsAbandoned { count });
+        }
+    }
+}
+
+fn event_timestamp(event: &Event) -> Option<DateTime<Utc>> {
+    match event {
+        Event::Log(log) => log.get_timestamp().and_then(|v| v.as_timestamp()).copied(),
+        Event::Metric(metric) => metric.timestamp(),
+        Event::Trace(_) => None,
+    }
+}
+
+/// Takes the longest run of ready events of the same type.
+fn next_array(ready: &mut VecDeque<Event>) -> Option<EventArray> {
+    let mut array = EventArray::from(ready.pop_front()?);
+    while let Some(event) = ready.pop_front() {
+        match (&mut array, event) {
+            (EventArray::Logs(logs), Event::Log(log)) => logs.push(log),
+            (EventArray::Metrics(metrics), Event::Metric(metric)) => metrics.push(metric),
+            (EventArray::Traces(traces), Event::Trace(trace)) => traces.push(trace),
+            (_, event) => {
+                ready.push_front(event);
+                break;
+            }
+        }
+    }
+    Some(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use futures::{stream, SinkExt};
+    use lookup::PathPrefix;
+    use vector_core::config::log_schema;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn log(message: &str, secs: i64) -> EventArray {
+        let mut log = LogEvent::from(message);
+        log.insert(
+            (PathPrefix::Event, log_schema().timestamp_key().unwrap()),
+            Utc.timestamp_opt(secs, 0).unwrap(),
+        );
+        log.into()
+    }
+
+    fn messages(arrays: Vec<EventArray>) -> Vec<String> {
+        arrays
+            .into_iter()
+            .flat_map(EventContainer::into_events)
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn config(max_lateness_ms: u64) -> OrderedMergeConfig {
+        OrderedMergeConfig {
+            max_lateness_ms,
+            max_events: 10_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn merges_by_timestamp() {
+        let input = stream::iter(vec![
+            log("b", 2),
+            log("a", 1),
+            log("d", 4),
+            log("c", 3),
+            log("e", 5),
+        ]);
+        let output = OrderedMerge::new(input, &config(60_000)).collect().await;
+
+        assert_eq!(messages(output), vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[tokio::test]
+    async fn releases_events_past_the_lateness_window() {
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut output = OrderedMerge::new(rx, &config(2_000));
+
+        tx.send(log("b", 20)).await.unwrap();
+        tx.send(log("a", 10)).await.unwrap();
+        tx.send(log("c", 30)).await.unwrap();
+
+        // Events preceding the latest event by more than the lateness window are released.
+        let released = output.next().await.unwrap();
+        assert_eq!(messages(vec![released]), vec!["a", "b"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn flushes_held_events_after_the_lateness_window() {
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let mut output = OrderedMerge::new(rx, &config(5_000));
+
+        tx.send(log("b", 2)).await.unwrap();
+        tx.send(log("a", 1)).await.unwrap();
+
+        // Time is advanced by the paused runtime until the held events are flushed.
+        let released = output.next().await.unwrap();
+        assert_eq!(messages(vec![released]), vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn caps_held_events() {
+        let input = stream::iter(vec![log("c", 3), log("b", 2), log("a", 1)]);
+        let config = OrderedMergeConfig {
+            max_lateness_ms: 60_000,
+            max_events: 1,
+        };
+        let output = OrderedMerge::new(input, &config).collect().await;
+
+        assert_eq!(messages(output), vec!["b", "a", "c"]);
+    }
+}
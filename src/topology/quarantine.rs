@@ -0,0 +1,302 @@
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures::{
+    task::{Context, Poll},
+    FutureExt, Stream, StreamExt,
+};
+use tokio::time::{sleep, timeout, Instant, MissedTickBehavior, Sleep};
+
+use crate::{
+    config::{QuarantinePolicy, SinkConfig, SinkContext},
+    event::EventArray,
+    internal_events::{
+        SinkHealthcheckFailed, SinkQuarantineEventsDropped, SinkQuarantineReleased, SinkQuarantined,
+    },
+    sinks::HealthcheckFactory,
+};
+
+/// How long a healthcheck may take before it is considered as failed.
+pub(super) const HEALTHCHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a quarantined sink buffering its events waits before checking its state again.
+const QUARANTINED_RECHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Whether a sink is quarantined, shared between its healthchecks and its input stream.
+#[derive(Clone, Debug, Default)]
+pub(super) struct QuarantineState(Arc<AtomicBool>);
+
+impl QuarantineState {
+    fn is_quarantined(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Quarantines the sink, returning whether it wasn't already.
+    fn quarantine(&self) -> bool {
+        !self.0.swap(true, Ordering::Relaxed)
+    }
+
+    /// Releases the sink from quarantine, returning whether it was quarantined.
+    fn release(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Periodic healthchecks of a running sink.
+///
+/// The sink is quarantined once `failure_threshold` consecutive healthchecks have failed, and
+/// released from quarantine as soon as one passes.
+pub(super) struct HealthMonitor {
+    healthcheck: HealthcheckFactory,
+    interval: Duration,
+    failure_threshold: u32,
+    policy: QuarantinePolicy,
+    state: QuarantineState,
+}
+
+impl HealthMonitor {
+    /// Builds the periodic healthchecks of the sink, returning `None` if it doesn't support them.
+    ///
+    /// The healthchecks are built once, and the sink itself is never built again.
+    pub(super) async fn build(
+        sink: &dyn SinkConfig,
+        cx: SinkContext,
+        interval: Duration,
+        failure_threshold: u32,
+        policy: QuarantinePolicy,
+        state: QuarantineState,
+    ) -> crate::Result<Option<Self>> {
+        let healthcheck = sink.build_healthcheck_factory(cx).await?;
+        Ok(healthcheck.map(|healthcheck| Self {
+            healthcheck,
+            interval,
+            failure_threshold,
+            policy,
+            state,
+        }))
+    }
+
+    /// Checks the health of the sink of the current span until dropped.
+    pub(super) async fn run(self) {
+        let mut interval = tokio::time::interval_at(Instant::now() + self.interval, self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut failures = 0;
+        loop {
+            interval.tick().await;
+            match self.check().await {
+                Ok(()) => {
+                    failures = 0;
+                    if self.state.release() {
+                        emit!(SinkQuarantineReleased);
+                    }
+                }
+                Err(error) => {
+                    failures += 1;
+                    emit!(SinkHealthcheckFailed {
+                        error: &error,
+                        consecutive_failures: failures,
+                    });
+                    if failures >= self.failure_threshold && self.state.quarantine() {
+                        emit!(SinkQuarantined {
+                            policy: self.policy,
+                            consecutive_failures: failures,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    async fn check(&self) -> crate::Result<()> {
+        timeout(HEALTHCHECK_TIMEOUT, (self.healthcheck)()).await?
+    }
+}
+
+/// Input stream of a sink which applies the quarantine policy while the sink is quarantined.
+pub(super) struct Quarantine<S> {
+    inner: S,
+    state: QuarantineState,
+    policy: QuarantinePolicy,
+    blocked: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S> Quarantine<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    pub(super) const fn new(inner: S, state: QuarantineState, policy: QuarantinePolicy) -> Self {
+        Self {
+            inner,
+            state,
+            policy,
+            blocked: None,
+        }
+    }
+}
+
+impl<S> Stream for Quarantine<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(blocked) = this.blocked.as_mut() {
+                if blocked.poll_unpin(cx).is_pending() {
+                    return Poll::Pending;
+                }
+            }
+
+            let quarantined = this.state.is_quarantined();
+            if quarantined && this.policy == QuarantinePolicy::Buffer {
+                this.blocked = Some(Box::pin(sleep(QUARANTINED_RECHECK_INTERVAL)));
+                continue;
+            }
+            this.blocked = None;
+
+            let events = match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(events)) => events,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if quarantined {
+                emit!(SinkQuarantineEventsDropped {
+                    count: events.len()
+                });
+            } else {
+                return Poll::Ready(Some(events));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use async_trait::async_trait;
+    use futures::stream;
+    use vector_config::NamedComponent;
+    use vector_core::config::{AcknowledgementsConfig, Input};
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        sinks::{Healthcheck, VectorSink},
+    };
+
+    /// Sink counting how many times it is built and probed, whose healthchecks always fail.
+    #[derive(Debug, Default)]
+    struct ProbedSinkConfig {
+        builds: Arc<AtomicUsize>,
+        probes: Arc<AtomicUsize>,
+    }
+
+    impl NamedComponent for ProbedSinkConfig {
+        fn get_component_name(&self) -> &'static str {
+            "test_probed"
+        }
+    }
+
+    #[async_trait]
+    impl SinkConfig for ProbedSinkConfig {
+        async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+            self.builds.fetch_add(1, Ordering::Relaxed);
+            Err("the sink can't be built".into())
+        }
+
+        async fn build_healthcheck_factory(
+            &self,
+            _cx: SinkContext,
+        ) -> crate::Result<Option<HealthcheckFactory>> {
+            let probes = Arc::clone(&self.probes);
+            Ok(Some(Arc::new(move || {
+                probes.fetch_add(1, Ordering::Relaxed);
+                futures::future::err(crate::Error::from("unhealthy")).boxed()
+            })))
+        }
+
+        fn input(&self) -> Input {
+            Input::log()
+        }
+
+        fn acknowledgements(&self) -> &AcknowledgementsConfig {
+            &AcknowledgementsConfig::DEFAULT
+        }
+    }
+
+    fn quarantine(
+        count: usize,
+        policy: QuarantinePolicy,
+    ) -> (
+        Quarantine<stream::Iter<std::vec::IntoIter<EventArray>>>,
+        QuarantineState,
+    ) {
+        let input = (0..count)
+            .map(|_| LogEvent::from("event").into())
+            .collect::<Vec<EventArray>>();
+        let state = QuarantineState::default();
+        (
+            Quarantine::new(stream::iter(input), state.clone(), policy),
+            state,
+        )
+    }
+
+    #[tokio::test]
+    async fn sheds_events_while_quarantined() {
+        let (mut input, state) = quarantine(3, QuarantinePolicy::Shed);
+
+        assert!(input.next().await.is_some());
+        assert!(state.quarantine());
+        assert!(input.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn buffers_events_while_quarantined() {
+        let (mut input, state) = quarantine(2, QuarantinePolicy::Buffer);
+
+        assert!(state.quarantine());
+        assert!(!state.quarantine());
+        assert!(input.next().now_or_never().is_none());
+
+        assert!(state.release());
+        assert!(input.next().await.is_some());
+        assert!(input.next().await.is_some());
+        assert!(input.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn probes_quarantined_sinks_without_building_them() {
+        let sink = ProbedSinkConfig::default();
+        let state = QuarantineState::default();
+        let monitor = HealthMonitor::build(
+            &sink,
+            SinkContext::new_test(),
+            Duration::from_secs(1),
+            2,
+            QuarantinePolicy::Buffer,
+            state.clone(),
+        )
+        .await
+        .unwrap()
+        .expect("the sink supports periodic healthchecks");
+        let monitor = tokio::spawn(monitor.run());
+
+        sleep(Duration::from_millis(4500)).await;
+        monitor.abort();
+
+        assert!(state.is_quarantined());
+        assert_eq!(sink.probes.load(Ordering::Relaxed), 4);
+        assert_eq!(sink.builds.load(Ordering::Relaxed), 0);
+    }
+}
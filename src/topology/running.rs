@@ -9,7 +9,7 @@ use std::{
 use futures::{future, Future, FutureExt};
 use tokio::{
     sync::{mpsc, watch},
-    time::{interval, sleep_until, Duration, Instant},
+    time::{interval, sleep_until, timeout, Duration, Instant},
 };
 use tracing::Instrument;
 use vector_buffers::topology::channel::BufferSender;
@@ -19,14 +19,17 @@ use super::{TapOutput, TapResource};
 use crate::{
     config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, Inputs, OutputId, Resource},
     event::EventArray,
+    internal_events::VectorReloadDrainTimeout,
     shutdown::SourceShutdownCoordinator,
     spawn_named,
     topology::{
         build_or_log_errors, builder,
         builder::Pieces,
         fanout::{ControlChannel, ControlMessage},
-        handle_errors, retain, take_healthchecks,
-        task::TaskOutput,
+        handle_errors, retain,
+        stall::StallMonitor,
+        take_healthchecks,
+        task::{TaskError, TaskOutput, TaskResult},
         BuiltBuffer, TaskHandle, WatchRx, WatchTx,
     },
 };
@@ -39,8 +42,10 @@ pub struct RunningTopology {
     outputs_tap_metadata: HashMap<ComponentKey, (&'static str, String)>,
     source_tasks: HashMap<ComponentKey, TaskHandle>,
     tasks: HashMap<ComponentKey, TaskHandle>,
+    draining_tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<ComponentKey, DisabledTrigger>,
+    stall_monitor: StallMonitor,
     pub(crate) config: Config,
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
@@ -49,6 +54,11 @@ pub struct RunningTopology {
 
 impl RunningTopology {
     pub fn new(config: Config, abort_tx: mpsc::UnboundedSender<()>) -> Self {
+        let stall_monitor = StallMonitor::default();
+        if let Some(threshold) = config.global.stall_detection.threshold() {
+            stall_monitor.spawn(threshold);
+        }
+
         Self {
             inputs: HashMap::new(),
             inputs_tap_metadata: HashMap::new(),
@@ -57,8 +67,10 @@ impl RunningTopology {
             config,
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
+            stall_monitor,
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
+            draining_tasks: HashMap::new(),
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
@@ -112,8 +124,14 @@ impl RunningTopology {
         let mut check_handles = HashMap::<ComponentKey, Vec<_>>::new();
 
         // We need to give some time to the sources to gracefully shutdown, so
-        // we will merge them with other tasks.
-        for (key, task) in self.tasks.into_iter().chain(self.source_tasks.into_iter()) {
+        // we will merge them with other tasks. Components removed by a reload
+        // which are still draining are waited for as well.
+        for (key, task) in self
+            .tasks
+            .into_iter()
+            .chain(self.source_tasks.into_iter())
+            .chain(self.draining_tasks.into_iter())
+        {
             let task = task.map(|_result| ()).shared();
 
             wait_handles.push(task.clone());
@@ -368,14 +386,18 @@ impl RunningTopology {
         // components, the shutdown of sources they depend on, or the shutdown of transforms they
         // depend on, and thus the closing of their buffer, will naturally cause them to shutdown,
         // which is why we don't do any manual triggering of shutdown here.
+        //
+        // Removed transforms are left running until they have drained their input into their
+        // downstream components, or until their drain deadline has passed.
         for key in &diff.transforms.to_remove {
             debug!(component = %key, "Removing transform.");
 
-            let previous = self.tasks.remove(key).unwrap();
-            drop(previous); // detach and forget
-
             self.remove_inputs(key, diff, new_config).await;
             self.remove_outputs(key);
+
+            let previous = self.tasks.remove(key).unwrap();
+            let drain_timeout = self.config.transform(key).unwrap().drain_timeout();
+            self.spawn_drain(key, previous, drain_timeout);
         }
 
         for key in &diff.transforms.to_change {
@@ -475,14 +497,16 @@ impl RunningTopology {
         // marked for reuse.
         //
         // If a sink we're removing isn't tying up any resource that a changed/added sink depends
-        // on, we don't bother waiting for it to shutdown.
+        // on, we don't bother waiting for it to shutdown, and leave it draining its buffer in the
+        // background instead. Either way, it is aborted once its drain deadline has passed.
         for key in &diff.sinks.to_remove {
             let previous = self.tasks.remove(key).unwrap();
+            let drain_timeout = self.config.sink(key).unwrap().drain_timeout();
             if wait_for_sinks.contains(key) {
                 debug!(message = "Waiting for sink to shutdown.", %key);
-                previous.await.unwrap().unwrap();
+                let _ = drain(key.clone(), previous, drain_timeout).await;
             } else {
-                drop(previous); // detach and forget
+                self.spawn_drain(key, previous, drain_timeout);
             }
         }
 
@@ -503,7 +527,7 @@ impl RunningTopology {
                     // buffers reused and treat them differently at other stages.
                     let tx = buffer_tx.remove(key).unwrap();
                     let rx = match buffer {
-                        TaskOutput::Sink(rx) => rx.into_inner(),
+                        TaskOutput::Sink(rx) => rx,
                         _ => unreachable!(),
                     };
 
@@ -653,6 +677,9 @@ impl RunningTopology {
                 port,
             };
 
+            if let Some(status) = new_pieces.send_statuses.remove(&id) {
+                self.stall_monitor.watch(id.clone(), status);
+            }
             self.outputs.insert(id, output);
         }
     }
@@ -704,8 +731,22 @@ impl RunningTopology {
             .map(|trigger| self.detach_triggers.insert(key.clone(), trigger.into()));
     }
 
+    /// Leaves a removed component draining its input in the background, until its drain deadline.
+    fn spawn_drain(&mut self, key: &ComponentKey, task: TaskHandle, drain_timeout: Duration) {
+        // Components which finished draining after previous reloads are forgotten.
+        self.draining_tasks
+            .retain(|_key, draining| !draining.is_finished());
+
+        let task_name = format!("drain ({})", key);
+        let drain = spawn_named(drain(key.clone(), task, drain_timeout), task_name.as_ref());
+        if let Some(previous) = self.draining_tasks.insert(key.clone(), drain) {
+            drop(previous); // detach and forget
+        }
+    }
+
     fn remove_outputs(&mut self, key: &ComponentKey) {
         self.outputs.retain(|id, _output| &id.component != key);
+        self.stall_monitor.unwatch(key);
     }
 
     async fn remove_inputs(&mut self, key: &ComponentKey, diff: &ConfigDiff, new_config: &Config) {
@@ -846,7 +887,10 @@ impl RunningTopology {
 
         let task_name = format!(">> {} ({})", task.typetag(), task.id());
         let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
-        let spawned = spawn_named(task, task_name.as_ref());
+        let spawned = match new_pieces.runtimes.remove(key) {
+            Some(runtime) => runtime.spawn(task),
+            None => spawn_named(task, task_name.as_ref()),
+        };
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
         }
@@ -883,7 +927,10 @@ impl RunningTopology {
 
         let task_name = format!(">> {} ({}) >>", task.typetag(), task.id());
         let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
-        let spawned = spawn_named(task, task_name.as_ref());
+        let spawned = match new_pieces.runtimes.remove(key) {
+            Some(runtime) => runtime.spawn(task),
+            None => spawn_named(task, task_name.as_ref()),
+        };
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
         }
@@ -937,6 +984,26 @@ impl RunningTopology {
     }
 }
 
+/// Waits for a component removed by a reload to drain its input into its downstream components.
+///
+/// Once its drain deadline has passed, the component is aborted, abandoning the events left in its
+/// input.
+async fn drain(key: ComponentKey, mut task: TaskHandle, drain_timeout: Duration) -> TaskResult {
+    match timeout(drain_timeout, &mut task).await {
+        Ok(result) => result.unwrap_or_else(|error| Err(error.into())),
+        Err(_) => {
+            emit!(VectorReloadDrainTimeout {
+                component: &key,
+                timeout: drain_timeout,
+            });
+            task.abort();
+            // Wait for the component to be dropped, which releases its resources.
+            let _ = task.await;
+            Err(TaskError::Cancelled)
+        }
+    }
+}
+
 fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<OutputId> {
     let mut changed_outputs = Vec::new();
 
@@ -0,0 +1,186 @@
+use std::{fs, sync::Arc};
+
+use jsonschema::JSONSchema;
+use serde::Serialize;
+use vector_core::event::{EventContainer, EventStatus, Finalizable};
+
+use crate::{
+    config::{SchemaValidationConfig, SchemaValidationFailure},
+    event::EventArray,
+    internal_events::SchemaValidationFailed,
+};
+
+/// Removes the events which don't conform to the JSON Schema of a sink from its input.
+#[derive(Clone)]
+pub(super) struct SchemaValidator {
+    schema: Arc<JSONSchema>,
+    on_failure: SchemaValidationFailure,
+}
+
+impl SchemaValidator {
+    pub(super) fn new(config: &SchemaValidationConfig) -> Result<Self, String> {
+        let schema = match (&config.json_schema, &config.json_schema_path) {
+            (Some(schema), None) => serde_json::to_value(schema)
+                .map_err(|error| format!("Invalid JSON Schema: {}", error))?,
+            (None, Some(path)) => fs::read_to_string(path)
+                .map_err(|error| error.to_string())
+                .and_then(|schema| serde_json::from_str(&schema).map_err(|error| error.to_string()))
+                .map_err(|error| {
+                    format!("Could not load JSON Schema from {:?}: {}", path, error)
+                })?,
+            _ => {
+                return Err(
+                    "Exactly one of `json_schema` and `json_schema_path` must be set.".into(),
+                )
+            }
+        };
+        let schema = JSONSchema::compile(&schema)
+            .map_err(|error| format!("Invalid JSON Schema: {}", error))?;
+
+        Ok(Self {
+            schema: Arc::new(schema),
+            on_failure: config.on_failure,
+        })
+    }
+
+    /// Removes the events which don't conform to the schema, returning `None` if none are left.
+    pub(super) fn validate(&self, mut events: EventArray) -> Option<EventArray> {
+        let failed = match &mut events {
+            EventArray::Logs(logs) => self.retain_valid(logs),
+            EventArray::Metrics(metrics) => self.retain_valid(metrics),
+            EventArray::Traces(traces) => self.retain_valid(traces),
+        };
+        if let Some((count, error)) = failed {
+            emit!(SchemaValidationFailed {
+                count,
+                error,
+                policy: match self.on_failure {
+                    SchemaValidationFailure::Drop => "drop",
+                    SchemaValidationFailure::Reject => "reject",
+                },
+            });
+        }
+
+        (!events.is_empty()).then_some(events)
+    }
+
+    /// Retains the valid events, returning the number of invalid ones and the first error.
+    fn retain_valid<T>(&self, events: &mut Vec<T>) -> Option<(usize, String)>
+    where
+        T: Finalizable + Serialize,
+    {
+        let mut count = 0;
+        let mut first_error = None;
+        events.retain_mut(|event| match self.error(event) {
+            None => true,
+            Some(error) => {
+                count += 1;
+                first_error.get_or_insert(error);
+                if self.on_failure == SchemaValidationFailure::Reject {
+                    event.take_finalizers().update_status(EventStatus::Rejected);
+                }
+                false
+            }
+        });
+        first_error.map(|error| (count, error))
+    }
+
+    fn error(&self, event: &impl Serialize) -> Option<String> {
+        let event = match serde_json::to_value(event) {
+            Ok(event) => event,
+            Err(error) => return Some(error.to_string()),
+        };
+        let error = self.schema.validate(&event).err()?.next()?;
+        Some(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        event::{LogEvent, Value},
+        test_util::temp_file,
+    };
+
+    fn config(on_failure: SchemaValidationFailure) -> SchemaValidationConfig {
+        SchemaValidationConfig {
+            json_schema: Some(
+                toml::from_str(
+                    r#"
+                    type = "object"
+                    required = ["message"]
+                    properties.status = { type = "integer" }
+                    "#,
+                )
+                .unwrap(),
+            ),
+            json_schema_path: None,
+            on_failure,
+        }
+    }
+
+    fn log(status: impl Into<Value>) -> LogEvent {
+        let mut log = LogEvent::from("message");
+        log.insert("status", status);
+        log
+    }
+
+    #[test]
+    fn drops_invalid_events() {
+        let validator = SchemaValidator::new(&config(SchemaValidationFailure::Drop)).unwrap();
+        let mut no_message = LogEvent::default();
+        no_message.insert("status", 200);
+
+        let events = validator
+            .validate(vec![log(200), log("ok"), no_message, log(404)].into())
+            .unwrap();
+
+        let statuses: Vec<_> = events
+            .iter_events()
+            .map(|event| event.into_log()["status"].clone())
+            .collect();
+        assert_eq!(statuses, vec![200.into(), 404.into()]);
+        assert!(validator.validate(log("ok").into()).is_none());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_events() {
+        let validator = SchemaValidator::new(&config(SchemaValidationFailure::Reject)).unwrap();
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = log("ok").with_batch_notifier(&batch);
+        drop(batch);
+
+        assert!(validator.validate(event.into()).is_none());
+        assert_eq!(receiver.await, BatchStatus::Rejected);
+    }
+
+    #[test]
+    fn loads_schema_files() {
+        let path = temp_file();
+        fs::write(&path, r#"{"type": "object", "required": ["message"]}"#).unwrap();
+        let config = SchemaValidationConfig {
+            json_schema: None,
+            json_schema_path: Some(path),
+            on_failure: SchemaValidationFailure::Drop,
+        };
+
+        let validator = SchemaValidator::new(&config).unwrap();
+        assert!(validator.validate(LogEvent::default().into()).is_none());
+        assert!(validator
+            .validate(LogEvent::from("message").into())
+            .is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        let mut config = config(SchemaValidationFailure::Drop);
+        config.json_schema = Some(toml::from_str(r#"type = "nothing""#).unwrap());
+        assert!(SchemaValidator::new(&config).is_err());
+
+        config.json_schema = None;
+        assert!(SchemaValidator::new(&config).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use futures::FutureExt;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{Instrument, Span};
+use vector_core::{config::DataType, event::Finalizable, sink::VectorSink};
+
+use super::builder::filter_events_type;
+use crate::{
+    config::{ComponentKey, ShadowConfig, SinkConfig},
+    event::{EventArray, EventContainer},
+    internal_events::SinkShadowEventsDropped,
+};
+
+/// How many batches of copied events may wait for the secondary sink before copies are dropped.
+const CHANNEL_CAPACITY: usize = 100;
+
+/// The built secondary sink of a sink, mirroring a sample of its events.
+pub(super) struct Shadow {
+    sink: VectorSink,
+    span: Span,
+    input_type: DataType,
+    ratio: f64,
+}
+
+impl Shadow {
+    pub(super) fn new(key: &ComponentKey, config: &ShadowConfig, sink: VectorSink) -> Self {
+        let id = format!("{}.shadow", key.id());
+        let span = error_span!(
+            "sink",
+            component_kind = "sink",
+            component_id = %id,
+            component_type = config.sink.get_component_name(),
+            component_name = %id,
+        );
+        Self {
+            sink,
+            span,
+            input_type: config.sink.input().data_type(),
+            ratio: config.percentage / 100.0,
+        }
+    }
+
+    /// Starts the secondary sink, returning the tee sending it copies of the events.
+    ///
+    /// The secondary sink stops once its input is exhausted, which is when the tee is dropped.
+    pub(super) fn start(self) -> ShadowTee {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let sink = self.sink.run(ReceiverStream::new(rx)).map(|result| {
+            if result.is_err() {
+                debug!("Shadow sink finished with an error.");
+            }
+        });
+        tokio::spawn(sink.instrument(self.span));
+
+        ShadowTee {
+            tx,
+            input_type: self.input_type,
+            ratio: self.ratio,
+        }
+    }
+}
+
+/// Sends copies of a sample of the events of a sink to its secondary sink.
+pub(super) struct ShadowTee {
+    tx: mpsc::Sender<EventArray>,
+    input_type: DataType,
+    ratio: f64,
+}
+
+impl ShadowTee {
+    /// Copies the events to the secondary sink if they are sampled.
+    ///
+    /// The copy is detached from the acknowledgement of the events, and dropped if the secondary
+    /// sink isn't keeping up, so that it never holds the sink back.
+    pub(super) fn copy(&self, events: &EventArray) {
+        if !filter_events_type(events, self.input_type) || rand::random::<f64>() >= self.ratio {
+            return;
+        }
+
+        let mut copy = events.clone();
+        // The finalizers are shared with the original events, which still hold them.
+        drop(copy.take_finalizers());
+        let count = copy.len();
+        if self.tx.try_send(copy).is_err() {
+            emit!(SinkShadowEventsDropped { count });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    fn tee(capacity: usize, ratio: f64) -> (ShadowTee, mpsc::Receiver<EventArray>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        let tee = ShadowTee {
+            tx,
+            input_type: DataType::all(),
+            ratio,
+        };
+        (tee, rx)
+    }
+
+    #[tokio::test]
+    async fn copies_are_detached_from_acknowledgements() {
+        let (tee, mut rx) = tee(1, 1.0);
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events = EventArray::from(LogEvent::from("event").with_batch_notifier(&batch));
+        drop(batch);
+
+        tee.copy(&events);
+        drop(events);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+
+        let copy = rx.try_recv().expect("events are copied");
+        assert_eq!(copy.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn drops_copies_when_full() {
+        let (tee, mut rx) = tee(1, 1.0);
+        tee.copy(&LogEvent::from("first").into());
+        tee.copy(&LogEvent::from("second").into());
+
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn samples_nothing_at_zero_percent() {
+        let (tee, mut rx) = tee(1, 0.0);
+        tee.copy(&LogEvent::from("event").into());
+
+        assert!(rx.try_recv().is_err());
+    }
+}
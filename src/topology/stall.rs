@@ -0,0 +1,273 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use tokio::time::interval;
+
+use super::fanout::{BlockedSend, SendStatus};
+use crate::{
+    config::{ComponentKey, OutputId},
+    internal_events::SourceStalled,
+    spawn_named,
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The send status of an output of a source or a transform.
+#[derive(Clone, Debug)]
+pub(crate) struct WatchedOutput {
+    status: SendStatus,
+    source: bool,
+}
+
+impl WatchedOutput {
+    pub(crate) const fn source(status: SendStatus) -> Self {
+        Self {
+            status,
+            source: true,
+        }
+    }
+
+    pub(crate) const fn transform(status: SendStatus) -> Self {
+        Self {
+            status,
+            source: false,
+        }
+    }
+}
+
+struct Watched {
+    output: WatchedOutput,
+    // The start of the last blocked send reported, so that each stall is only reported once.
+    reported: Option<Instant>,
+}
+
+/// A source blocked on send for longer than the threshold.
+#[derive(Debug, PartialEq, Eq)]
+struct Stall {
+    output: OutputId,
+    blocked_for: Duration,
+    blame_chain: Vec<ComponentKey>,
+}
+
+/// Monitors the outputs of the topology to detect the sources stalled by backpressure.
+///
+/// A source is stalled when a send of one of its outputs has been blocked for longer than the
+/// threshold. The stall is attributed by following, from the source, the downstream components
+/// the send is waiting on which are themselves blocked on send, until reaching a component which
+/// isn't: that component is the one not keeping up with its input.
+#[derive(Clone, Default)]
+pub(super) struct StallMonitor {
+    outputs: Arc<Mutex<HashMap<OutputId, Watched>>>,
+}
+
+impl StallMonitor {
+    pub(super) fn watch(&self, id: OutputId, output: WatchedOutput) {
+        let watched = Watched {
+            output,
+            reported: None,
+        };
+        self.outputs.lock().unwrap().insert(id, watched);
+    }
+
+    pub(super) fn unwatch(&self, key: &ComponentKey) {
+        self.outputs
+            .lock()
+            .unwrap()
+            .retain(|id, _| &id.component != key);
+    }
+
+    /// Spawns the task checking the outputs periodically, which finishes once the monitor is
+    /// dropped.
+    pub(super) fn spawn(&self, threshold: Duration) {
+        let outputs = Arc::downgrade(&self.outputs);
+        spawn_named(run(outputs, threshold), "stall_monitor");
+    }
+}
+
+async fn run(outputs: Weak<Mutex<HashMap<OutputId, Watched>>>, threshold: Duration) {
+    let mut interval = interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let shared = match outputs.upgrade() {
+            Some(shared) => shared,
+            None => break,
+        };
+        let mut outputs = shared.lock().unwrap();
+        let blocked = blocked_sends(&outputs);
+        let stalls = check(&mut outputs, &blocked, threshold, Instant::now());
+        drop(outputs);
+
+        for stall in stalls {
+            emit!(SourceStalled {
+                output: &stall.output,
+                blocked_for: stall.blocked_for,
+                blame_chain: &stall.blame_chain,
+            });
+        }
+    }
+}
+
+fn blocked_sends(outputs: &HashMap<OutputId, Watched>) -> HashMap<OutputId, BlockedSend> {
+    outputs
+        .iter()
+        .filter_map(|(id, watched)| Some((id.clone(), watched.output.status.blocked()?)))
+        .collect()
+}
+
+/// Returns the sources newly stalled, given the sends currently blocked.
+fn check(
+    outputs: &mut HashMap<OutputId, Watched>,
+    blocked: &HashMap<OutputId, BlockedSend>,
+    threshold: Duration,
+    now: Instant,
+) -> Vec<Stall> {
+    let mut stalls = Vec::new();
+    for (id, watched) in outputs.iter_mut() {
+        let send = match blocked.get(id) {
+            Some(send) if watched.output.source => send,
+            _ => continue,
+        };
+
+        let blocked_for = now.saturating_duration_since(send.since);
+        if blocked_for < threshold || watched.reported == Some(send.since) {
+            continue;
+        }
+        watched.reported = Some(send.since);
+
+        stalls.push(Stall {
+            output: id.clone(),
+            blocked_for,
+            blame_chain: blame_chain(&id.component, send, blocked),
+        });
+    }
+    stalls
+}
+
+/// Follows the blocked sends from the given source to the component holding them up.
+fn blame_chain(
+    source: &ComponentKey,
+    send: &BlockedSend,
+    blocked: &HashMap<OutputId, BlockedSend>,
+) -> Vec<ComponentKey> {
+    let mut chain = vec![source.clone()];
+    let mut waiting_on = &send.waiting_on;
+    loop {
+        // Among the downstream components blocked on send themselves, the one blocked the longest
+        // is followed, as it's the one which held up the others first.
+        let next = waiting_on
+            .iter()
+            .filter(|key| !chain.contains(key))
+            .filter_map(|key| Some((key, blocked_send(key, blocked)?)))
+            .min_by_key(|(_, send)| send.since);
+
+        match next {
+            Some((key, send)) => {
+                chain.push(key.clone());
+                waiting_on = &send.waiting_on;
+            }
+            None => {
+                if let Some(key) = waiting_on.iter().find(|key| !chain.contains(key)) {
+                    chain.push(key.clone());
+                }
+                return chain;
+            }
+        }
+    }
+}
+
+/// Returns the longest blocked send of the outputs of the given component.
+fn blocked_send<'a>(
+    key: &ComponentKey,
+    blocked: &'a HashMap<OutputId, BlockedSend>,
+) -> Option<&'a BlockedSend> {
+    blocked
+        .iter()
+        .filter(|(id, _)| &id.component == key)
+        .map(|(_, send)| send)
+        .min_by_key(|send| send.since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(since: Instant, waiting_on: &[&str]) -> BlockedSend {
+        BlockedSend {
+            since,
+            waiting_on: waiting_on.iter().map(|&key| key.into()).collect(),
+        }
+    }
+
+    fn keys(keys: &[&str]) -> Vec<ComponentKey> {
+        keys.iter().map(|&key| key.into()).collect()
+    }
+
+    fn watched(output: WatchedOutput) -> Watched {
+        Watched {
+            output,
+            reported: None,
+        }
+    }
+
+    #[test]
+    fn blames_first_component_not_blocked() {
+        let now = Instant::now();
+        let blocked = HashMap::from([
+            (OutputId::from("parse"), send(now, &["filter", "slow"])),
+            (
+                OutputId::from("filter"),
+                send(now + Duration::from_secs(5), &["fast"]),
+            ),
+            (OutputId::from("slow"), send(now, &["sink"])),
+        ]);
+
+        let chain = blame_chain(&"in".into(), &send(now, &["parse"]), &blocked);
+        assert_eq!(chain, keys(&["in", "parse", "slow", "sink"]));
+    }
+
+    #[test]
+    fn blames_direct_downstream_component() {
+        let now = Instant::now();
+        let chain = blame_chain(&"in".into(), &send(now, &["sink"]), &HashMap::new());
+        assert_eq!(chain, keys(&["in", "sink"]));
+    }
+
+    #[test]
+    fn reports_each_stall_once() {
+        let now = Instant::now();
+        let threshold = Duration::from_secs(10);
+        let mut outputs = HashMap::from([
+            (
+                OutputId::from("in"),
+                watched(WatchedOutput::source(SendStatus::default())),
+            ),
+            (
+                OutputId::from("parse"),
+                watched(WatchedOutput::transform(SendStatus::default())),
+            ),
+        ]);
+        let blocked = HashMap::from([
+            (OutputId::from("in"), send(now, &["parse"])),
+            (OutputId::from("parse"), send(now, &["sink"])),
+        ]);
+
+        // Blocked transforms aren't reported, nor are sources blocked for less than the threshold.
+        let soon = now + Duration::from_secs(5);
+        assert!(check(&mut outputs, &blocked, threshold, soon).is_empty());
+
+        let later = now + Duration::from_secs(15);
+        assert_eq!(
+            check(&mut outputs, &blocked, threshold, later),
+            vec![Stall {
+                output: OutputId::from("in"),
+                blocked_for: Duration::from_secs(15),
+                blame_chain: keys(&["in", "parse", "sink"]),
+            }]
+        );
+        assert!(check(&mut outputs, &blocked, threshold, later).is_empty());
+    }
+}
@@ -12,14 +12,14 @@ use tokio::task::JoinError;
 use vector_buffers::topology::channel::BufferReceiverStream;
 use vector_core::event::EventArray;
 
-use crate::{config::ComponentKey, utilization::Utilization};
+use crate::config::ComponentKey;
 
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum TaskOutput {
     Source,
     Transform,
     /// Buffer of sink
-    Sink(Utilization<BufferReceiverStream<EventArray>>),
+    Sink(BufferReceiverStream<EventArray>),
     Healthcheck,
 }
 
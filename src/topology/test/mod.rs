@@ -9,11 +9,14 @@ use std::{
 
 use crate::{
     config::{Config, ConfigDiff, SinkOuter},
-    event::{into_event_stream, Event, EventArray, EventContainer, LogEvent},
+    event::{
+        into_event_stream, BatchNotifier, BatchStatus, Event, EventArray, EventContainer, LogEvent,
+    },
     test_util::{
         mock::{
             basic_sink, basic_sink_failing_healthcheck, basic_sink_with_data, basic_source,
             basic_source_with_data, basic_source_with_event_counter, basic_transform,
+            sinks::BasicSinkConfig,
         },
         start_topology, trace_init,
     },
@@ -24,7 +27,7 @@ use tokio::{
     task::yield_now,
     time::{sleep, Duration},
 };
-use vector_buffers::{BufferConfig, BufferType, WhenFull};
+use vector_buffers::{BufferConfig, BufferType, FlushMode, WhenFull};
 
 mod backpressure;
 mod compliance;
@@ -324,6 +327,53 @@ async fn topology_remove_one_sink() {
     assert_eq!(Vec::<Event>::new(), res2);
 }
 
+#[tokio::test]
+async fn topology_remove_stuck_sink_abandons_events() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    // The default mock sink never reads its input, so it can't drain it when removed.
+    let mut stuck = SinkOuter::new(vec!["in1".to_owned()], BasicSinkConfig::default());
+    stuck.drain_timeout_secs = Some(1);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+    config.add_sink_outer("stuck", stuck);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let event = LogEvent::from("this").with_batch_notifier(&batch);
+    drop(batch);
+    in1.send_event(event).await.unwrap();
+    sleep(Duration::from_millis(100)).await;
+
+    let mut config = Config::builder();
+    config.add_source("in1", basic_source().1);
+    config.add_sink("out1", &["in1"], basic_sink(10).1);
+
+    assert!(topology
+        .reload_config_and_respawn(config.build().unwrap())
+        .await
+        .unwrap());
+
+    // The removed sink is aborted once its drain deadline has passed, abandoning the event left in
+    // its buffer.
+    let status = tokio::time::timeout(Duration::from_secs(5), receiver)
+        .await
+        .expect("removed sink was not aborted");
+    assert_eq!(status, BatchStatus::Errored);
+
+    drop(in1);
+    topology.stop().await;
+
+    let res1 = out1.flat_map(into_message_stream).collect::<Vec<_>>().await;
+    assert_eq!(vec!["this"], res1);
+}
+
 #[tokio::test]
 async fn topology_remove_one_transform() {
     trace_init();
@@ -786,6 +836,7 @@ async fn topology_disk_buffer_flushes_on_idle() {
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: std::num::NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::DropNewest,
+        flush_mode: FlushMode::default(),
     });
     config.add_sink_outer("out1", sink1_outer);
 
@@ -0,0 +1,589 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+};
+
+use chrono::Utc;
+use lookup::{owned_value_path, PathPrefix};
+use ordered_float::NotNan;
+use snafu::Snafu;
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::config::{log_schema, LogNamespace};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{
+        metric::{Metric, MetricKind, MetricSeries, MetricValue},
+        Event, LogEvent, Value,
+    },
+    internal_events::AnomalyDetected,
+    schema,
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
+};
+
+const ANOMALIES: &str = "anomalies";
+const ANOMALY_TAG: &str = "anomaly";
+
+/// Configuration for the `anomaly_detection` transform.
+#[configurable_component(transform(
+    "anomaly_detection",
+    "Detect anomalous values in metric series by comparing them to a learned baseline."
+))]
+#[derive(Clone, Debug)]
+pub struct AnomalyDetectionConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub model: Model,
+
+    /// The width of the band of expected values around the baseline, in standard deviations.
+    ///
+    /// Values outside of the band are anomalous.
+    #[serde(default = "default_band")]
+    pub band: f64,
+
+    /// The number of values of a series learned before detecting anomalies in it.
+    ///
+    /// With the `holt_winters` model, at least two full seasons are always learned.
+    #[serde(default = "default_warmup")]
+    pub warmup: usize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub annotation: Annotation,
+}
+
+/// The model of the baseline learned for each series.
+///
+/// Counters and gauges are supported, the other metrics being forwarded as they are. Absolute
+/// counters are compared through their increments.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "model", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(
+    docs::enum_tag_description = "The model of the baseline learned for each series."
+))]
+pub enum Model {
+    /// An exponentially weighted moving average.
+    ///
+    /// Suited to series fluctuating around a level which changes slowly.
+    Ewma {
+        /// The smoothing factor of the average, greater than 0 and at most 1.
+        ///
+        /// Higher values give more weight to recent values, so that the baseline adapts faster.
+        #[serde(default = "default_alpha")]
+        alpha: f64,
+    },
+
+    /// Holt-Winters triple exponential smoothing, with an additive trend and seasonality.
+    ///
+    /// Suited to series following a trend and a regular cycle, such as daily traffic.
+    HoltWinters {
+        /// The smoothing factor of the level, greater than 0 and at most 1.
+        #[serde(default = "default_alpha")]
+        alpha: f64,
+
+        /// The smoothing factor of the trend, greater than 0 and at most 1.
+        #[serde(default = "default_beta")]
+        beta: f64,
+
+        /// The smoothing factor of the seasonality, greater than 0 and at most 1.
+        #[serde(default = "default_gamma")]
+        gamma: f64,
+
+        /// The number of values in a season.
+        ///
+        /// For example, a series reported every minute with a daily cycle has seasons of 1440
+        /// values.
+        #[configurable(metadata(docs::examples = 1440))]
+        season_length: NonZeroUsize,
+    },
+}
+
+/// How anomalies are reported.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Annotation {
+    /// Tag the anomalous metrics with `anomaly`, set to `above` or `below` the band of expected
+    /// values.
+    #[default]
+    Tag,
+
+    /// Send a log event describing each anomaly to the `anomalies` output.
+    ///
+    /// The metrics are forwarded unchanged.
+    Event,
+}
+
+const fn default_band() -> f64 {
+    3.0
+}
+
+const fn default_warmup() -> usize {
+    10
+}
+
+const fn default_alpha() -> f64 {
+    0.3
+}
+
+const fn default_beta() -> f64 {
+    0.1
+}
+
+const fn default_gamma() -> f64 {
+    0.1
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`{}` must be greater than 0 and at most 1, got {}.", name, value))]
+    InvalidSmoothingFactor { name: &'static str, value: f64 },
+
+    #[snafu(display("`band` must be greater than 0, got {}.", band))]
+    InvalidBand { band: f64 },
+}
+
+impl Model {
+    fn validate(&self) -> Result<(), BuildError> {
+        let factors = match *self {
+            Self::Ewma { alpha } => vec![("alpha", alpha)],
+            Self::HoltWinters {
+                alpha, beta, gamma, ..
+            } => vec![("alpha", alpha), ("beta", beta), ("gamma", gamma)],
+        };
+        match factors
+            .into_iter()
+            .find(|(_, value)| !(0.0..=1.0).contains(value) || *value == 0.0)
+        {
+            Some((name, value)) => Err(BuildError::InvalidSmoothingFactor { name, value }),
+            None => Ok(()),
+        }
+    }
+
+    /// The smoothing factor of the variance of the prediction errors.
+    const fn alpha(&self) -> f64 {
+        match self {
+            Self::Ewma { alpha } | Self::HoltWinters { alpha, .. } => *alpha,
+        }
+    }
+}
+
+impl GenerateConfig for AnomalyDetectionConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"model = "ewma"
+            alpha = 0.3
+            band = 3.0"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "anomaly_detection")]
+impl TransformConfig for AnomalyDetectionConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        AnomalyDetection::new(self).map(Transform::synchronous)
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(
+        &self,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let default_output = TransformOutput::new(DataType::Metric, HashMap::new());
+
+        match self.annotation {
+            Annotation::Tag => vec![default_output],
+            Annotation::Event => {
+                let definition = anomaly_definition();
+                let definitions = input_definitions
+                    .iter()
+                    .map(|(output, _)| (output.clone(), definition.clone()))
+                    .collect();
+                vec![
+                    default_output,
+                    TransformOutput::new(DataType::Log, definitions).with_port(ANOMALIES),
+                ]
+            }
+        }
+    }
+}
+
+/// The definition of the log events describing anomalies.
+fn anomaly_definition() -> schema::Definition {
+    let mut definition = schema::Definition::empty_legacy_namespace()
+        .with_event_field(
+            &owned_value_path!(log_schema().message_key()),
+            Kind::bytes(),
+            None,
+        )
+        .with_event_field(&owned_value_path!("name"), Kind::bytes(), None)
+        .with_event_field(
+            &owned_value_path!("namespace"),
+            Kind::bytes().or_undefined(),
+            None,
+        )
+        .with_event_field(
+            &owned_value_path!("tags"),
+            Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+            None,
+        )
+        .with_event_field(&owned_value_path!("direction"), Kind::bytes(), None);
+    for field in ["value", "expected", "lower", "upper"] {
+        definition = definition.with_event_field(&owned_value_path!(field), Kind::float(), None);
+    }
+    if let Some(timestamp_key) = log_schema().timestamp_key() {
+        definition = definition.with_event_field(timestamp_key, Kind::timestamp(), None);
+    }
+    definition
+}
+
+/// The baseline learned from the values of a series.
+#[derive(Clone, Debug, Default)]
+struct Baseline {
+    /// The number of values learned.
+    count: usize,
+    level: f64,
+    trend: f64,
+    /// The seasonal components, with the `holt_winters` model.
+    seasonal: Vec<f64>,
+    /// The exponentially weighted variance of the prediction errors.
+    variance: f64,
+    /// The last value of an absolute counter, whose increments are learned.
+    last_total: Option<f64>,
+}
+
+impl Baseline {
+    /// The prediction of the next value of the series.
+    fn predict(&self) -> f64 {
+        let seasonal = match self.seasonal.len() {
+            0 => 0.0,
+            len => self.seasonal[self.count % len],
+        };
+        self.level + self.trend + seasonal
+    }
+
+    fn learn(&mut self, model: &Model, value: f64) {
+        if self.count == 0 {
+            self.level = value;
+            if let Model::HoltWinters { season_length, .. } = model {
+                self.seasonal = vec![0.0; season_length.get()];
+            }
+            self.count = 1;
+            return;
+        }
+
+        let error = value - self.predict();
+        match *model {
+            Model::Ewma { alpha } => self.level += alpha * error,
+            Model::HoltWinters {
+                alpha, beta, gamma, ..
+            } => {
+                let season = self.count % self.seasonal.len();
+                let level = alpha * (value - self.seasonal[season])
+                    + (1.0 - alpha) * (self.level + self.trend);
+                self.trend = beta * (level - self.level) + (1.0 - beta) * self.trend;
+                self.seasonal[season] =
+                    gamma * (value - level) + (1.0 - gamma) * self.seasonal[season];
+                self.level = level;
+            }
+        }
+        let alpha = model.alpha();
+        self.variance = (1.0 - alpha) * (self.variance + alpha * error * error);
+        self.count += 1;
+    }
+}
+
+/// A value outside of the band of expected values of its series.
+#[derive(Debug)]
+struct Anomaly {
+    value: f64,
+    expected: f64,
+    lower: f64,
+    upper: f64,
+}
+
+impl Anomaly {
+    fn direction(&self) -> &'static str {
+        if self.value > self.upper {
+            "above"
+        } else {
+            "below"
+        }
+    }
+
+    fn to_event(&self, metric: &Metric) -> Event {
+        let mut log = LogEvent::from(format!("Anomalous value of metric {:?}.", metric.name()));
+        if let Some(timestamp_key) = log_schema().timestamp_key() {
+            log.insert(
+                (PathPrefix::Event, timestamp_key),
+                metric.timestamp().unwrap_or_else(Utc::now),
+            );
+        }
+        log.insert("name", metric.name());
+        if let Some(namespace) = metric.namespace() {
+            log.insert("namespace", namespace);
+        }
+        if let Some(tags) = metric.tags() {
+            let tags: BTreeMap<String, Value> = tags
+                .iter_single()
+                .map(|(key, value)| (key.to_owned(), value.into()))
+                .collect();
+            log.insert("tags", tags);
+        }
+        log.insert("value", float(self.value));
+        log.insert("expected", float(self.expected));
+        log.insert("lower", float(self.lower));
+        log.insert("upper", float(self.upper));
+        log.insert("direction", self.direction());
+        log.into()
+    }
+}
+
+fn float(value: f64) -> Value {
+    NotNan::new(value).map_or(Value::Null, Value::Float)
+}
+
+#[derive(Clone, Debug)]
+pub struct AnomalyDetection {
+    model: Model,
+    band: f64,
+    warmup: usize,
+    annotation: Annotation,
+    series: HashMap<MetricSeries, Baseline>,
+}
+
+impl AnomalyDetection {
+    pub fn new(config: &AnomalyDetectionConfig) -> crate::Result<Self> {
+        config.model.validate()?;
+        if config.band.is_nan() || config.band <= 0.0 {
+            return Err(BuildError::InvalidBand { band: config.band }.into());
+        }
+
+        let warmup = match &config.model {
+            Model::Ewma { .. } => config.warmup,
+            Model::HoltWinters { season_length, .. } => config.warmup.max(2 * season_length.get()),
+        };
+        Ok(Self {
+            model: config.model.clone(),
+            band: config.band,
+            warmup: warmup.max(1),
+            annotation: config.annotation,
+            series: HashMap::new(),
+        })
+    }
+
+    /// Learns the value of a metric, returning it if it's anomalous.
+    fn observe(&mut self, metric: &Metric) -> Option<Anomaly> {
+        if !matches!(
+            metric.value(),
+            MetricValue::Counter { .. } | MetricValue::Gauge { .. }
+        ) {
+            return None;
+        }
+
+        if !self.series.contains_key(metric.series()) {
+            self.series
+                .insert(metric.series().clone(), Baseline::default());
+        }
+        let baseline = self
+            .series
+            .get_mut(metric.series())
+            .expect("baseline was inserted");
+        let value = match (metric.kind(), metric.value()) {
+            (MetricKind::Absolute, MetricValue::Counter { value }) => {
+                let last = baseline.last_total.replace(*value)?;
+                // Counters restart from zero when their emitter restarts.
+                if *value >= last {
+                    value - last
+                } else {
+                    *value
+                }
+            }
+            (_, MetricValue::Counter { value } | MetricValue::Gauge { value }) => *value,
+            _ => return None,
+        };
+        if !value.is_finite() {
+            return None;
+        }
+
+        let learned = baseline.count >= self.warmup;
+        let expected = baseline.predict();
+        let deviation = self.band * baseline.variance.sqrt();
+        baseline.learn(&self.model, value);
+
+        (learned && (value - expected).abs() > deviation).then_some(Anomaly {
+            value,
+            expected,
+            lower: expected - deviation,
+            upper: expected + deviation,
+        })
+    }
+}
+
+impl SyncTransform for AnomalyDetection {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut metric = event.into_metric();
+        if let Some(anomaly) = self.observe(&metric) {
+            emit!(AnomalyDetected {
+                name: metric.name(),
+                value: anomaly.value,
+                expected: anomaly.expected,
+                direction: anomaly.direction(),
+            });
+            match self.annotation {
+                Annotation::Tag => {
+                    metric.replace_tag(ANOMALY_TAG.to_owned(), anomaly.direction().to_owned());
+                }
+                Annotation::Event => output.push_named(ANOMALIES, anomaly.to_event(&metric)),
+            }
+        }
+        output.push(metric.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AnomalyDetectionConfig>();
+    }
+
+    fn config(model: Model, annotation: Annotation) -> AnomalyDetectionConfig {
+        AnomalyDetectionConfig {
+            model,
+            band: default_band(),
+            warmup: default_warmup(),
+            annotation,
+        }
+    }
+
+    fn gauge(value: f64) -> Event {
+        Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value },
+        )
+        .into()
+    }
+
+    /// Runs the transform over the events, returning the `anomaly` tags of the metrics.
+    fn anomaly_tags(config: AnomalyDetectionConfig, events: Vec<Event>) -> Vec<Option<String>> {
+        let outputs = config.outputs(&[], LogNamespace::Legacy);
+        let mut transform = AnomalyDetection::new(&config).unwrap();
+        let mut buf = TransformOutputsBuf::new_with_capacity(outputs, events.len());
+        for event in events {
+            transform.transform(event, &mut buf);
+        }
+        buf.drain()
+            .map(|event| event.as_metric().tag_value(ANOMALY_TAG))
+            .collect()
+    }
+
+    #[test]
+    fn tags_values_outside_of_an_ewma_band() {
+        let values = [
+            10.0, 11.0, 10.0, 9.0, 10.0, 11.0, 10.0, 9.0, 10.0, 11.0, 50.0, 10.0,
+        ];
+        let tags = anomaly_tags(
+            config(
+                Model::Ewma {
+                    alpha: default_alpha(),
+                },
+                Annotation::Tag,
+            ),
+            values.into_iter().map(gauge).collect(),
+        );
+
+        let mut expected = vec![None; values.len()];
+        expected[10] = Some("above".to_owned());
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn learns_seasons_with_holt_winters() {
+        let mut values = [0.0, 10.0, 0.0, 10.0].repeat(10);
+        values.extend([10.0, 10.0, 0.0, 10.0]);
+        let tags = anomaly_tags(
+            config(
+                Model::HoltWinters {
+                    alpha: default_alpha(),
+                    beta: default_beta(),
+                    gamma: default_gamma(),
+                    season_length: NonZeroUsize::new(4).unwrap(),
+                },
+                Annotation::Tag,
+            ),
+            values.into_iter().map(gauge).collect(),
+        );
+
+        let anomalies: Vec<_> = tags
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tag)| tag.as_ref().map(|tag| (index, tag.as_str())))
+            .collect();
+        assert_eq!(anomalies, vec![(40, "above")]);
+    }
+
+    #[test]
+    fn sends_events_for_absolute_counter_increments() {
+        let config = config(
+            Model::Ewma {
+                alpha: default_alpha(),
+            },
+            Annotation::Event,
+        );
+        let outputs = config.outputs(&[], LogNamespace::Legacy);
+        assert_eq!(outputs[1].port.as_deref(), Some(ANOMALIES));
+
+        let mut transform = AnomalyDetection::new(&config).unwrap();
+        let mut buf = TransformOutputsBuf::new_with_capacity(outputs, 16);
+        let totals = (0..13).map(|i| 100.0 + 10.0 * f64::from(i)).chain([320.0]);
+        for total in totals {
+            let counter = Metric::new(
+                "requests_total",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: total },
+            )
+            .with_tags(Some(vector_core::metric_tags!("host" => "a")));
+            transform.transform(counter.into(), &mut buf);
+        }
+
+        let metrics: Vec<_> = buf.drain().collect();
+        assert_eq!(metrics.len(), 14);
+        assert!(metrics
+            .iter()
+            .all(|event| event.as_metric().tag_value(ANOMALY_TAG).is_none()));
+
+        let anomalies: Vec<_> = buf.drain_named(ANOMALIES).collect();
+        assert_eq!(anomalies.len(), 1);
+        let log = anomalies[0].as_log();
+        assert_eq!(log["name"], "requests_total".into());
+        assert_eq!(log["tags.host"], "a".into());
+        assert_eq!(log["value"], float(100.0));
+        assert_eq!(log["expected"], float(10.0));
+        assert_eq!(log["direction"], "above".into());
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        let mut invalid = config(Model::Ewma { alpha: 1.5 }, Annotation::Tag);
+        assert!(AnomalyDetection::new(&invalid).is_err());
+
+        invalid.model = Model::Ewma { alpha: 0.5 };
+        invalid.band = 0.0;
+        assert!(AnomalyDetection::new(&invalid).is_err());
+    }
+}
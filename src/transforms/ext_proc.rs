@@ -0,0 +1,416 @@
+use std::{num::NonZeroUsize, pin::Pin, time::Duration};
+
+use futures::{stream, Stream, StreamExt};
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use vector_common::internal_event::error_type;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{proto::EventWrapper, Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::ExtProcRequestError,
+    proto::ext_proc::{Client, ProcessRequest},
+    schema::{self, Definition},
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `ext_proc` transform.
+#[configurable_component(transform(
+    "ext_proc",
+    "Process events with an external service over gRPC."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExtProcConfig {
+    /// The URI of the external processor.
+    ///
+    /// The external processor must implement the `ExternalProcessor` service of
+    /// [`ext_proc.proto`][ext_proc_proto], which receives batches of events and returns the events
+    /// replacing them. Events are dropped by leaving them out of the response, and modified or
+    /// added by including them in it.
+    ///
+    /// Connections to `https` URIs are secured with TLS, using the root certificates of the system.
+    ///
+    /// [ext_proc_proto]: https://github.com/vectordotdev/vector/blob/master/proto/ext_proc.proto
+    #[configurable(metadata(docs::examples = "http://127.0.0.1:50051"))]
+    #[configurable(validation(format = "uri"))]
+    pub endpoint: String,
+
+    /// The maximum number of events sent to the external processor in a request.
+    #[serde(default = "default_max_events")]
+    pub max_events: NonZeroUsize,
+
+    /// The maximum number of requests in flight.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: NonZeroUsize,
+
+    /// How long to wait for the response of the external processor, in milliseconds.
+    ///
+    /// The `fallback` is applied to the events of the requests which time out.
+    #[serde(default = "default_timeout_ms")]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub timeout_ms: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub fallback: Fallback,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub ordering: Ordering,
+}
+
+/// What is done with the events of the requests which fail or time out.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Fallback {
+    /// The events are forwarded unmodified.
+    #[default]
+    PassThrough,
+
+    /// The events are dropped.
+    ///
+    /// If end-to-end acknowledgements are enabled, the events are reported as failed to their
+    /// source.
+    Drop,
+}
+
+/// The order in which the processed events are forwarded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Ordering {
+    /// The events are forwarded in the order they were received.
+    ///
+    /// A slow request holds back the events of the requests sent after it.
+    #[default]
+    Ordered,
+
+    /// The events of each request are forwarded as soon as its response is received.
+    Unordered,
+}
+
+fn default_max_events() -> NonZeroUsize {
+    NonZeroUsize::new(100).expect("static non-zero number")
+}
+
+fn default_concurrency() -> NonZeroUsize {
+    NonZeroUsize::new(8).expect("static non-zero number")
+}
+
+const fn default_timeout_ms() -> u64 {
+    1000
+}
+
+impl GenerateConfig for ExtProcConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "http://127.0.0.1:50051".to_owned(),
+            max_events: default_max_events(),
+            concurrency: default_concurrency(),
+            timeout_ms: default_timeout_ms(),
+            fallback: Fallback::default(),
+            ordering: Ordering::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "ext_proc")]
+impl TransformConfig for ExtProcConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        let mut endpoint = Endpoint::from_shared(self.endpoint.clone())?;
+        if endpoint.uri().scheme_str() == Some("https") {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+        }
+
+        Ok(Transform::event_task(ExtProc {
+            client: Client::new(endpoint.connect_lazy()),
+            max_events: self.max_events.get(),
+            concurrency: self.concurrency.get(),
+            timeout: Duration::from_millis(self.timeout_ms),
+            fallback: self.fallback,
+            ordering: self.ordering,
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(
+        &self,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The external processor can modify the events in any way.
+        let namespaces = input_definitions
+            .iter()
+            .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+            .collect();
+
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, _definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(&namespaces),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::all(), definitions)]
+    }
+}
+
+#[derive(Clone)]
+struct ExtProc {
+    client: Client<Channel>,
+    max_events: usize,
+    concurrency: usize,
+    timeout: Duration,
+    fallback: Fallback,
+    ordering: Ordering,
+}
+
+impl ExtProc {
+    /// Processes a batch of events with the external processor.
+    async fn process(mut self, mut events: Vec<Event>) -> Vec<Event> {
+        let count = events.len();
+        let finalizers = events
+            .iter_mut()
+            .map(Finalizable::take_finalizers)
+            .collect::<Vec<_>>();
+        let originals = (self.fallback == Fallback::PassThrough).then(|| events.clone());
+
+        let request = ProcessRequest {
+            events: events.into_iter().map(EventWrapper::from).collect(),
+        };
+        let (error, error_type) =
+            match tokio::time::timeout(self.timeout, self.client.process(request)).await {
+                Ok(Ok(response)) => {
+                    // The events replacing the batch are finalized together, once all of them are.
+                    let finalizers = finalizers.into_iter().fold(
+                        EventFinalizers::default(),
+                        |mut merged, finalizers| {
+                            merged.merge(finalizers);
+                            merged
+                        },
+                    );
+                    return response
+                        .into_inner()
+                        .events
+                        .into_iter()
+                        .map(|event| {
+                            let mut event = Event::from(event);
+                            event.metadata_mut().merge_finalizers(finalizers.clone());
+                            event
+                        })
+                        .collect();
+                }
+                Ok(Err(status)) => (status.to_string(), error_type::REQUEST_FAILED),
+                Err(_) => ("Request timed out.".to_owned(), error_type::TIMED_OUT),
+            };
+
+        emit!(ExtProcRequestError {
+            error: &error,
+            error_type,
+            count,
+            dropped: originals.is_none(),
+        });
+        match originals {
+            Some(originals) => originals
+                .into_iter()
+                .zip(finalizers)
+                .map(|(mut event, finalizers)| {
+                    event.metadata_mut().merge_finalizers(finalizers);
+                    event
+                })
+                .collect(),
+            None => {
+                for finalizers in finalizers {
+                    finalizers.update_status(EventStatus::Errored);
+                }
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for ExtProc {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>> {
+        let this = *self;
+        let concurrency = this.concurrency;
+        let ordering = this.ordering;
+        let responses = task
+            .ready_chunks(this.max_events)
+            .map(move |events| this.clone().process(events));
+
+        match ordering {
+            Ordering::Ordered => responses
+                .buffered(concurrency)
+                .flat_map(stream::iter)
+                .boxed(),
+            Ordering::Unordered => responses
+                .buffer_unordered(concurrency)
+                .flat_map(stream::iter)
+                .boxed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+    use tonic::{Request, Response, Status};
+
+    use super::*;
+    use crate::{
+        event::{BatchNotifier, BatchStatus, LogEvent},
+        proto::ext_proc::{ProcessResponse, Server, Service},
+        test_util::{components::assert_transform_compliance, next_addr},
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExtProcConfig>();
+    }
+
+    /// Drops the events with a `drop` field, and sets the `processed` field of the others.
+    struct TestProcessor;
+
+    #[tonic::async_trait]
+    impl Service for TestProcessor {
+        async fn process(
+            &self,
+            request: Request<ProcessRequest>,
+        ) -> Result<Response<ProcessResponse>, Status> {
+            let events = request
+                .into_inner()
+                .events
+                .into_iter()
+                .map(Event::from)
+                .filter(|event| !event.as_log().contains("drop"))
+                .map(|mut event| {
+                    event.as_mut_log().insert("processed", true);
+                    EventWrapper::from(event)
+                })
+                .collect();
+            Ok(Response::new(ProcessResponse { events }))
+        }
+    }
+
+    async fn start_processor() -> SocketAddr {
+        let addr = next_addr();
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(Server::new(TestProcessor))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        addr
+    }
+
+    fn ext_proc_config(addr: SocketAddr, fallback: Fallback) -> ExtProcConfig {
+        ExtProcConfig {
+            endpoint: format!("http://{}", addr),
+            max_events: default_max_events(),
+            concurrency: default_concurrency(),
+            timeout_ms: default_timeout_ms(),
+            fallback,
+            ordering: Ordering::Ordered,
+        }
+    }
+
+    async fn transform_events(config: ExtProcConfig, events: Vec<Event>) -> Vec<Event> {
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            let mut output = Vec::new();
+            for event in events {
+                tx.send(event).await.unwrap();
+                while let Ok(Some(event)) =
+                    tokio::time::timeout(Duration::from_millis(100), out.recv()).await
+                {
+                    output.push(event);
+                }
+            }
+
+            drop(tx);
+            topology.stop().await;
+            while let Some(event) = out.recv().await {
+                output.push(event);
+            }
+            output
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn processes_events() {
+        let addr = start_processor().await;
+        let mut kept = LogEvent::from("kept");
+        kept.insert("id", 1);
+        let mut dropped = LogEvent::from("dropped");
+        dropped.insert("drop", true);
+
+        let output = transform_events(
+            ext_proc_config(addr, Fallback::PassThrough),
+            vec![kept.into(), dropped.into()],
+        )
+        .await;
+
+        assert_eq!(output.len(), 1);
+        let log = output[0].as_log();
+        assert_eq!(log["id"], 1.into());
+        assert_eq!(log["processed"], true.into());
+    }
+
+    #[tokio::test]
+    async fn passes_through_events_when_unavailable() {
+        let output = transform_events(
+            ext_proc_config(next_addr(), Fallback::PassThrough),
+            vec![LogEvent::from("unprocessed").into()],
+        )
+        .await;
+
+        assert_eq!(output.len(), 1);
+        assert!(!output[0].as_log().contains("processed"));
+    }
+
+    #[tokio::test]
+    async fn drops_events_when_unavailable() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = LogEvent::from("unprocessed").with_batch_notifier(&batch);
+        drop(batch);
+
+        let transform = ExtProcConfig::build(
+            &ext_proc_config(next_addr(), Fallback::Drop),
+            &TransformContext::default(),
+        )
+        .await
+        .unwrap()
+        .into_task();
+        let output = transform
+            .transform_events(stream::iter(vec![event.into()]).boxed())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert!(output.is_empty());
+        assert_eq!(receiver.await, BatchStatus::Errored);
+    }
+}
@@ -1,3 +1,10 @@
+use std::collections::{BTreeMap, HashMap};
+
+use lookup::{
+    lookup_v2::{parse_value_path, ValuePath},
+    metadata_path, owned_value_path, path, PathPrefix,
+};
+use value::Kind;
 use vector_common::internal_event::{Count, InternalEventHandle as _, Registered};
 use vector_config::configurable_component;
 use vector_core::config::{clone_input_definitions, LogNamespace};
@@ -5,15 +12,17 @@ use vector_core::config::{clone_input_definitions, LogNamespace};
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{
-        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
-        TransformOutput,
+        log_schema, ComponentKey, DataType, GenerateConfig, Input, OutputId, TransformConfig,
+        TransformContext, TransformOutput,
     },
     event::Event,
     internal_events::FilterEventsDropped,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{FunctionTransform, OutputBuffer, SyncTransform, Transform, TransformOutputsBuf},
 };
 
+const DROPPED: &str = "dropped";
+
 /// Configuration for the `filter` transform.
 #[configurable_component(transform("filter", "Filter events based on a set of conditions."))]
 #[derive(Clone, Debug)]
@@ -24,11 +33,25 @@ pub struct FilterConfig {
     ///
     /// If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
     condition: AnyCondition,
+
+    /// Reroutes the events which don't match the condition to a named output instead of dropping
+    /// them.
+    ///
+    /// When set, the events which don't match the condition are forwarded to a specially-named
+    /// output, `dropped`. They are annotated with fields describing the condition they didn't
+    /// match, so that they can be archived without repeating the condition in a `route`
+    /// transform.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    reroute_dropped: bool,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition,
+            reroute_dropped: false,
+        }
     }
 }
 
@@ -42,9 +65,16 @@ impl GenerateConfig for FilterConfig {
 #[typetag::serde(name = "filter")]
 impl TransformConfig for FilterConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Filter::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
+        let condition = self.condition.build(&context.enrichment_tables)?;
+        Ok(if self.reroute_dropped {
+            Transform::synchronous(ReroutingFilter::new(
+                condition,
+                &self.condition,
+                context.key.as_ref(),
+            ))
+        } else {
+            Transform::function(Filter::new(condition))
+        })
     }
 
     fn input(&self) -> Input {
@@ -56,10 +86,18 @@ impl TransformConfig for FilterConfig {
         input_definitions: &[(OutputId, schema::Definition)],
         _: LogNamespace,
     ) -> Vec<TransformOutput> {
-        vec![TransformOutput::new(
-            DataType::all(),
-            clone_input_definitions(input_definitions),
-        )]
+        let default_output =
+            TransformOutput::new(DataType::all(), clone_input_definitions(input_definitions));
+
+        if self.reroute_dropped {
+            vec![
+                default_output,
+                TransformOutput::new(DataType::all(), dropped_definitions(input_definitions))
+                    .with_port(DROPPED),
+            ]
+        } else {
+            vec![default_output]
+        }
     }
 
     fn enable_concurrency(&self) -> bool {
@@ -67,6 +105,58 @@ impl TransformConfig for FilterConfig {
     }
 }
 
+/// The definitions of the events forwarded to the `dropped` output, which are annotated with the
+/// condition they didn't match.
+fn dropped_definitions(
+    input_definitions: &[(OutputId, schema::Definition)],
+) -> HashMap<OutputId, schema::Definition> {
+    let dropped_kind = Kind::object(BTreeMap::from([
+        ("reason".into(), Kind::bytes()),
+        ("condition".into(), Kind::bytes()),
+        ("component_id".into(), Kind::bytes()),
+        ("component_type".into(), Kind::bytes()),
+        ("component_kind".into(), Kind::bytes()),
+    ]));
+
+    input_definitions
+        .iter()
+        .map(|(output_id, input_definition)| {
+            let mut definition = schema::Definition::new_with_default_metadata(
+                Kind::never(),
+                input_definition.log_namespaces().clone(),
+            );
+
+            if input_definition
+                .log_namespaces()
+                .contains(&LogNamespace::Legacy)
+            {
+                definition = definition.merge(
+                    input_definition.clone().with_event_field(
+                        &parse_value_path(log_schema().metadata_key())
+                            .expect("valid metadata key")
+                            .with_field_appended("dropped"),
+                        dropped_kind.clone(),
+                        None,
+                    ),
+                );
+            }
+
+            if input_definition
+                .log_namespaces()
+                .contains(&LogNamespace::Vector)
+            {
+                definition = definition.merge(input_definition.clone().with_metadata_field(
+                    &owned_value_path!("vector", "dropped"),
+                    dropped_kind.clone(),
+                    None,
+                ));
+            }
+
+            (output_id.clone(), definition)
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct Filter {
     condition: Condition,
@@ -93,10 +183,104 @@ impl FunctionTransform for Filter {
     }
 }
 
+/// Filter forwarding the events which don't match its condition to the `dropped` output.
+#[derive(Clone)]
+struct ReroutingFilter {
+    condition: Condition,
+    /// The condition as configured, which the rerouted events are annotated with.
+    condition_source: String,
+    component_id: String,
+}
+
+impl ReroutingFilter {
+    fn new(
+        condition: Condition,
+        config: &AnyCondition,
+        component_key: Option<&ComponentKey>,
+    ) -> Self {
+        let condition_source = match config {
+            AnyCondition::String(source) => source.clone(),
+            AnyCondition::Map(config) => {
+                serde_json::to_string(config).expect("conditions are serializable")
+            }
+        };
+        Self {
+            condition,
+            condition_source,
+            component_id: component_key.map(ToString::to_string).unwrap_or_default(),
+        }
+    }
+
+    fn dropped_data(&self) -> serde_json::Value {
+        serde_json::json!({
+            "reason": "condition_not_matched",
+            "condition": self.condition_source,
+            "component_id": self.component_id,
+            "component_type": "filter",
+            "component_kind": "transform",
+        })
+    }
+
+    fn annotate_dropped(&self, event: &mut Event) {
+        match event {
+            Event::Log(log) => match log.namespace() {
+                LogNamespace::Legacy => {
+                    log.insert(
+                        (
+                            PathPrefix::Event,
+                            log_schema().metadata_key().concat(path!("dropped")),
+                        ),
+                        self.dropped_data(),
+                    );
+                }
+                LogNamespace::Vector => {
+                    log.insert(metadata_path!("vector", "dropped"), self.dropped_data());
+                }
+            },
+            Event::Metric(metric) => {
+                let m = log_schema().metadata_key();
+                metric.replace_tag(
+                    format!("{}.dropped.reason", m),
+                    "condition_not_matched".into(),
+                );
+                metric.replace_tag(
+                    format!("{}.dropped.condition", m),
+                    self.condition_source.clone(),
+                );
+                metric.replace_tag(
+                    format!("{}.dropped.component_id", m),
+                    self.component_id.clone(),
+                );
+                metric.replace_tag(format!("{}.dropped.component_type", m), "filter".into());
+                metric.replace_tag(format!("{}.dropped.component_kind", m), "transform".into());
+            }
+            Event::Trace(trace) => {
+                trace.insert(
+                    format!("{}.dropped", log_schema().metadata_key()),
+                    self.dropped_data(),
+                );
+            }
+        }
+    }
+}
+
+impl SyncTransform for ReroutingFilter {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let (result, mut event) = self.condition.check(event);
+        if result {
+            output.push(event);
+        } else {
+            self.annotate_dropped(&mut event);
+            output.push_named(DROPPED, event);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
+    use value::Value;
     use vector_core::event::{Metric, MetricKind, MetricValue};
 
     use super::*;
@@ -139,4 +323,42 @@ mod test {
         })
         .await;
     }
+    #[test]
+    fn reroutes_dropped_events() {
+        let condition = AnyCondition::String(r#".level == "info""#.to_owned());
+        let config = FilterConfig {
+            condition: condition.clone(),
+            reroute_dropped: true,
+        };
+        let outputs = config.outputs(&[], LogNamespace::Legacy);
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[1].port.as_deref(), Some(DROPPED));
+
+        let mut filter = ReroutingFilter::new(
+            condition.build(&Default::default()).unwrap(),
+            &condition,
+            Some(&ComponentKey::from("my_filter")),
+        );
+        let mut buf = TransformOutputsBuf::new_with_capacity(outputs, 2);
+        let mut info = LogEvent::from("message");
+        info.insert("level", "info");
+        let mut debug = LogEvent::from("message");
+        debug.insert("level", "debug");
+        filter.transform(info.clone().into(), &mut buf);
+        filter.transform(debug.into(), &mut buf);
+
+        assert_eq!(buf.drain().collect::<Vec<_>>(), vec![Event::from(info)]);
+        let dropped = buf.drain_named(DROPPED).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert_eq!(log["level"], Value::from("debug"));
+        assert_eq!(
+            log["metadata.dropped.condition"],
+            Value::from(r#".level == "info""#)
+        );
+        assert_eq!(
+            log["metadata.dropped.component_id"],
+            Value::from("my_filter")
+        );
+    }
 }
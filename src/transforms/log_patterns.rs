@@ -0,0 +1,682 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    future,
+    num::{NonZeroU64, NonZeroUsize},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{stream, Stream, StreamExt};
+use lookup::{lookup_v2::ConfigTargetPath, owned_value_path, OwnedTargetPath};
+use snafu::Snafu;
+use tokio::time::{interval_at, Instant, Interval};
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::{log_schema, LogNamespace};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        Event, Value,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The token replacing the variable parts of the messages in templates.
+const WILDCARD: &str = "<*>";
+
+/// Configuration for the `log_patterns` transform.
+#[configurable_component(transform(
+    "log_patterns",
+    "Cluster log messages into patterns using the Drain algorithm."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LogPatternsConfig {
+    /// The field holding the message to cluster.
+    ///
+    /// Defaults to the message field of the global log schema. Events without this field are
+    /// forwarded unchanged.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub message_field: Option<ConfigTargetPath>,
+
+    /// The field the pattern of an event is written to.
+    ///
+    /// The pattern is an object holding its `id`, its `template`, where the variable tokens of the
+    /// messages are replaced with `<*>`, and the `variables` of the event, which are the tokens of
+    /// its message replaced in the template.
+    #[serde(default = "default_target_field")]
+    #[configurable(metadata(docs::examples = "pattern"))]
+    pub target_field: ConfigTargetPath,
+
+    /// The depth of the parse tree, including its root and leaves.
+    ///
+    /// Messages are routed through the tree by their number of tokens and then by their first
+    /// `depth - 3` tokens, before being compared to the patterns of the leaf they reach.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// The minimum ratio of the tokens of a message matching a pattern for the message to be
+    /// clustered with it.
+    ///
+    /// Higher values create more, stricter patterns.
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+
+    /// The maximum number of children of the nodes of the parse tree.
+    ///
+    /// Tokens in excess are routed through a wildcard node.
+    #[serde(default = "default_max_children")]
+    pub max_children: usize,
+
+    /// The maximum number of patterns kept.
+    ///
+    /// When it's reached, the least recently matched pattern is forgotten.
+    #[serde(default = "default_max_patterns")]
+    pub max_patterns: NonZeroUsize,
+
+    /// The interval between the metrics about the patterns, in seconds.
+    ///
+    /// The `log_pattern_events` counter holds the number of events of each pattern, tagged with the
+    /// `pattern_id` and `pattern` template, the `log_patterns` gauge the number of patterns, and the
+    /// `log_patterns_created` counter the number of patterns created since the previous metrics.
+    /// They're sent alongside the events.
+    ///
+    /// If unset, no metric is sent.
+    pub metrics_interval_secs: Option<NonZeroU64>,
+}
+
+fn default_target_field() -> ConfigTargetPath {
+    ConfigTargetPath(OwnedTargetPath::event(owned_value_path!("pattern")))
+}
+
+const fn default_depth() -> usize {
+    4
+}
+
+const fn default_similarity_threshold() -> f64 {
+    0.4
+}
+
+const fn default_max_children() -> usize {
+    100
+}
+
+fn default_max_patterns() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("not zero")
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`depth` must be at least 3, got {}.", depth))]
+    InvalidDepth { depth: usize },
+
+    #[snafu(display(
+        "`similarity_threshold` must be between 0 and 1, got {}.",
+        similarity_threshold
+    ))]
+    InvalidSimilarityThreshold { similarity_threshold: f64 },
+
+    #[snafu(display("`max_children` must be at least 2, got {}.", max_children))]
+    InvalidMaxChildren { max_children: usize },
+}
+
+impl GenerateConfig for LogPatternsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"target_field = "pattern"
+            similarity_threshold = 0.4
+            metrics_interval_secs = 60"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "log_patterns")]
+impl TransformConfig for LogPatternsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        LogPatterns::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let pattern_kind = Kind::object(BTreeMap::from([
+            ("id".into(), Kind::bytes()),
+            ("template".into(), Kind::bytes()),
+            ("variables".into(), Kind::array(Kind::bytes())),
+        ]));
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                (
+                    output.clone(),
+                    definition.clone().with_field(
+                        &self.target_field.0,
+                        pattern_kind.clone().or_undefined(),
+                        None,
+                    ),
+                )
+            })
+            .collect();
+
+        let data_type = if self.metrics_interval_secs.is_some() {
+            DataType::Log | DataType::Metric
+        } else {
+            DataType::Log
+        };
+        vec![TransformOutput::new(data_type, definitions)]
+    }
+}
+
+/// A pattern of messages.
+#[derive(Debug)]
+struct Cluster {
+    id: String,
+    template: Vec<String>,
+    /// The value of the clock of the parser when a message last matched the pattern.
+    last_matched: u64,
+    /// The number of events matching the pattern since the last metrics.
+    events: u64,
+}
+
+impl Cluster {
+    /// The ratio of the tokens matching the template, and the number of wildcards it holds.
+    fn similarity(&self, tokens: &[&str]) -> (f64, usize) {
+        if tokens.is_empty() {
+            return (1.0, 0);
+        }
+
+        let mut matching = 0;
+        let mut wildcards = 0;
+        for (template, token) in self.template.iter().zip(tokens) {
+            if template == WILDCARD {
+                wildcards += 1;
+            } else if template == token {
+                matching += 1;
+            }
+        }
+        (matching as f64 / tokens.len() as f64, wildcards)
+    }
+
+    /// Replaces the tokens of the template which don't match the message with wildcards.
+    fn merge(&mut self, tokens: &[&str]) {
+        for (template, token) in self.template.iter_mut().zip(tokens) {
+            if template != token {
+                *template = WILDCARD.to_owned();
+            }
+        }
+    }
+}
+
+/// A node of the parse tree, which routes messages to the patterns they can match.
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    /// The keys of the patterns of a leaf, which may have been forgotten since.
+    clusters: Vec<u64>,
+}
+
+fn has_digits(token: &str) -> bool {
+    token.bytes().any(|byte| byte.is_ascii_digit())
+}
+
+pub struct LogPatterns {
+    message_field: ConfigTargetPath,
+    target_field: ConfigTargetPath,
+    /// The number of layers of the parse tree routing messages by their first tokens.
+    prefix_depth: usize,
+    similarity_threshold: f64,
+    max_children: usize,
+    max_patterns: usize,
+    metrics_interval: Option<Duration>,
+    /// The roots of the parse tree, by number of tokens.
+    roots: HashMap<usize, Node>,
+    clusters: HashMap<u64, Cluster>,
+    next_cluster: u64,
+    clock: u64,
+    created: u64,
+}
+
+impl LogPatterns {
+    pub fn new(config: &LogPatternsConfig) -> crate::Result<Self> {
+        if config.depth < 3 {
+            return Err(BuildError::InvalidDepth {
+                depth: config.depth,
+            }
+            .into());
+        }
+        if !(0.0..=1.0).contains(&config.similarity_threshold) {
+            return Err(BuildError::InvalidSimilarityThreshold {
+                similarity_threshold: config.similarity_threshold,
+            }
+            .into());
+        }
+        if config.max_children < 2 {
+            return Err(BuildError::InvalidMaxChildren {
+                max_children: config.max_children,
+            }
+            .into());
+        }
+
+        let message_field = config.message_field.clone().unwrap_or_else(|| {
+            ConfigTargetPath(OwnedTargetPath::event(owned_value_path!(
+                log_schema().message_key()
+            )))
+        });
+        Ok(Self {
+            message_field,
+            target_field: config.target_field.clone(),
+            prefix_depth: config.depth - 3,
+            similarity_threshold: config.similarity_threshold,
+            max_children: config.max_children,
+            max_patterns: config.max_patterns.get(),
+            metrics_interval: config
+                .metrics_interval_secs
+                .map(|secs| Duration::from_secs(secs.get())),
+            roots: HashMap::new(),
+            clusters: HashMap::new(),
+            next_cluster: 0,
+            clock: 0,
+            created: 0,
+        })
+    }
+
+    /// Writes the pattern of the message of an event to it.
+    fn annotate(&mut self, mut event: Event) -> Event {
+        let log = event.as_mut_log();
+        let message = match log.get(&self.message_field) {
+            Some(message) => message.to_string_lossy().into_owned(),
+            None => return event,
+        };
+        let tokens: Vec<&str> = message.split_whitespace().collect();
+
+        let cluster = self.cluster(&tokens);
+        let variables = cluster
+            .template
+            .iter()
+            .zip(&tokens)
+            .filter(|(template, _)| *template == WILDCARD)
+            .map(|(_, token)| Value::from(*token))
+            .collect::<Vec<_>>();
+        let pattern = BTreeMap::from([
+            ("id".to_owned(), Value::from(cluster.id.as_str())),
+            ("template".to_owned(), cluster.template.join(" ").into()),
+            ("variables".to_owned(), Value::Array(variables)),
+        ]);
+        log.insert(&self.target_field, pattern);
+        event
+    }
+
+    /// Returns the pattern matching the tokens of a message, creating it if there's none.
+    fn cluster(&mut self, tokens: &[&str]) -> &Cluster {
+        self.clock += 1;
+        let key = match self.find(tokens) {
+            Some(key) => {
+                let cluster = self.clusters.get_mut(&key).expect("found cluster");
+                cluster.merge(tokens);
+                key
+            }
+            None => self.create(tokens),
+        };
+
+        let cluster = self.clusters.get_mut(&key).expect("known cluster");
+        cluster.last_matched = self.clock;
+        cluster.events += 1;
+        cluster
+    }
+
+    /// Finds the most similar pattern in the leaf of the parse tree the message is routed to.
+    fn find(&self, tokens: &[&str]) -> Option<u64> {
+        let mut node = self.roots.get(&tokens.len())?;
+        for token in tokens.iter().take(self.prefix_depth) {
+            node = node
+                .children
+                .get(*token)
+                .or_else(|| node.children.get(WILDCARD))?;
+        }
+
+        let mut best: Option<(u64, f64, usize)> = None;
+        for key in &node.clusters {
+            let cluster = match self.clusters.get(key) {
+                Some(cluster) => cluster,
+                None => continue,
+            };
+            let (similarity, wildcards) = cluster.similarity(tokens);
+            let better = best.map_or(true, |(_, best_similarity, best_wildcards)| {
+                similarity > best_similarity
+                    || (similarity == best_similarity && wildcards > best_wildcards)
+            });
+            if better {
+                best = Some((*key, similarity, wildcards));
+            }
+        }
+        best.filter(|(_, similarity, _)| *similarity >= self.similarity_threshold)
+            .map(|(key, _, _)| key)
+    }
+
+    /// Creates a pattern from a message, adding it to the parse tree.
+    fn create(&mut self, tokens: &[&str]) -> u64 {
+        if self.clusters.len() >= self.max_patterns {
+            let forgotten = self
+                .clusters
+                .iter()
+                .min_by_key(|(_, cluster)| cluster.last_matched)
+                .map(|(key, _)| *key);
+            if let Some(forgotten) = forgotten {
+                self.clusters.remove(&forgotten);
+            }
+        }
+
+        let key = self.next_cluster;
+        self.next_cluster += 1;
+        self.created += 1;
+        let template = tokens.join(" ");
+        self.clusters.insert(
+            key,
+            Cluster {
+                // The ID is derived from the first message of the pattern, so that it's the same
+                // across restarts.
+                id: format!("{:016x}", seahash::hash(template.as_bytes())),
+                template: tokens.iter().map(|token| (*token).to_owned()).collect(),
+                last_matched: self.clock,
+                events: 0,
+            },
+        );
+
+        let mut node = self.roots.entry(tokens.len()).or_default();
+        for token in tokens.iter().take(self.prefix_depth) {
+            let child = if node.children.contains_key(*token) {
+                *token
+            } else if has_digits(token) || node.children.len() + 1 >= self.max_children {
+                WILDCARD
+            } else {
+                *token
+            };
+            node = node.children.entry(child.to_owned()).or_default();
+        }
+        let clusters = &self.clusters;
+        node.clusters.retain(|key| clusters.contains_key(key));
+        node.clusters.push(key);
+        key
+    }
+
+    /// Appends the metrics about the patterns, resetting their counts.
+    fn metrics(&mut self, output: &mut Vec<Event>) {
+        let timestamp = Some(Utc::now());
+        for cluster in self
+            .clusters
+            .values_mut()
+            .filter(|cluster| cluster.events > 0)
+        {
+            output.push(
+                Metric::new(
+                    "log_pattern_events",
+                    MetricKind::Incremental,
+                    MetricValue::Counter {
+                        value: cluster.events as f64,
+                    },
+                )
+                .with_tags(Some(vector_core::metric_tags!(
+                    "pattern_id" => cluster.id.clone(),
+                    "pattern" => cluster.template.join(" "),
+                )))
+                .with_timestamp(timestamp)
+                .into(),
+            );
+            cluster.events = 0;
+        }
+        output.push(
+            Metric::new(
+                "log_patterns",
+                MetricKind::Absolute,
+                MetricValue::Gauge {
+                    value: self.clusters.len() as f64,
+                },
+            )
+            .with_timestamp(timestamp)
+            .into(),
+        );
+        output.push(
+            Metric::new(
+                "log_patterns_created",
+                MetricKind::Incremental,
+                MetricValue::Counter {
+                    value: self.created as f64,
+                },
+            )
+            .with_timestamp(timestamp)
+            .into(),
+        );
+        self.created = 0;
+    }
+}
+
+/// Waits for the next tick of the interval, if there's one.
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+impl TaskTransform<Event> for LogPatterns {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+        let mut interval = me
+            .metrics_interval
+            .map(|period| interval_at(Instant::now() + period, period));
+
+        Box::pin(
+            stream! {
+                loop {
+                    let mut output = Vec::new();
+                    let done = tokio::select! {
+                        _ = tick(&mut interval) => {
+                            me.metrics(&mut output);
+                            false
+                        }
+                        maybe_event = input_rx.next() => {
+                            match maybe_event {
+                                None => {
+                                    if interval.is_some() {
+                                        me.metrics(&mut output);
+                                    }
+                                    true
+                                }
+                                Some(event) => {
+                                    output.push(me.annotate(event));
+                                    false
+                                }
+                            }
+                        }
+                    };
+                    yield stream::iter(output.into_iter());
+                    if done { break }
+                }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::LogEvent, test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogPatternsConfig>();
+    }
+
+    fn config() -> LogPatternsConfig {
+        toml::from_str("").unwrap()
+    }
+
+    fn pattern(patterns: &mut LogPatterns, message: &str) -> (String, String, Vec<String>) {
+        let event = patterns.annotate(LogEvent::from(message).into());
+        let pattern = event.as_log()["pattern"].clone();
+        let variables = match &pattern.as_object().unwrap()["variables"] {
+            Value::Array(variables) => variables
+                .iter()
+                .map(|variable| variable.to_string_lossy().into_owned())
+                .collect(),
+            _ => panic!("variables must be an array"),
+        };
+        (
+            pattern.get("id").unwrap().to_string_lossy().into_owned(),
+            pattern
+                .get("template")
+                .unwrap()
+                .to_string_lossy()
+                .into_owned(),
+            variables,
+        )
+    }
+
+    #[test]
+    fn clusters_similar_messages() {
+        let mut patterns = LogPatterns::new(&config()).unwrap();
+
+        let (first_id, template, _) = pattern(&mut patterns, "user alice logged in from web");
+        assert_eq!(template, "user alice logged in from web");
+
+        let (id, template, variables) = pattern(&mut patterns, "user bob logged in from mobile");
+        assert_eq!(id, first_id);
+        assert_eq!(template, "user <*> logged in from <*>");
+        assert_eq!(variables, vec!["bob", "mobile"]);
+
+        let (other_id, template, _) = pattern(&mut patterns, "connection closed by peer now");
+        assert_ne!(other_id, first_id);
+        assert_eq!(template, "connection closed by peer now");
+
+        let (id, _, variables) = pattern(&mut patterns, "user carol logged in from web");
+        assert_eq!(id, first_id);
+        assert_eq!(variables, vec!["carol", "web"]);
+    }
+
+    #[test]
+    fn routes_tokens_with_digits_through_wildcards() {
+        let mut patterns = LogPatterns::new(&config()).unwrap();
+
+        let (first_id, _, _) = pattern(&mut patterns, "42 requests served");
+        let (id, template, variables) = pattern(&mut patterns, "17 requests served");
+        assert_eq!(id, first_id);
+        assert_eq!(template, "<*> requests served");
+        assert_eq!(variables, vec!["17"]);
+
+        let (id, _, _) = pattern(&mut patterns, "requests served fast");
+        assert_ne!(id, first_id);
+    }
+
+    #[test]
+    fn forgets_least_recently_matched_patterns() {
+        let mut config = config();
+        config.max_patterns = NonZeroUsize::new(2).unwrap();
+        let mut patterns = LogPatterns::new(&config).unwrap();
+
+        pattern(&mut patterns, "a b c");
+        pattern(&mut patterns, "d e f g");
+        pattern(&mut patterns, "a b c");
+        pattern(&mut patterns, "h i");
+
+        let templates: Vec<_> = patterns
+            .clusters
+            .values()
+            .map(|cluster| cluster.template.join(" "))
+            .collect();
+        assert_eq!(templates.len(), 2);
+        assert!(templates.contains(&"a b c".to_owned()));
+        assert!(templates.contains(&"h i".to_owned()));
+    }
+
+    #[test]
+    fn counts_events_by_pattern() {
+        let mut patterns = LogPatterns::new(&config()).unwrap();
+        pattern(&mut patterns, "disk sda full");
+        pattern(&mut patterns, "disk sdb full");
+        pattern(&mut patterns, "service started");
+
+        let mut output = Vec::new();
+        patterns.metrics(&mut output);
+        let mut counts: Vec<_> = output
+            .iter()
+            .map(|event| event.as_metric())
+            .filter(|metric| metric.name() == "log_pattern_events")
+            .map(|metric| (metric.tag_value("pattern").unwrap(), metric.value().clone()))
+            .collect();
+        counts.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            counts,
+            vec![
+                (
+                    "disk <*> full".to_owned(),
+                    MetricValue::Counter { value: 2.0 }
+                ),
+                (
+                    "service started".to_owned(),
+                    MetricValue::Counter { value: 1.0 }
+                ),
+            ]
+        );
+        let created = output
+            .iter()
+            .map(|event| event.as_metric())
+            .find(|metric| metric.name() == "log_patterns_created")
+            .unwrap();
+        assert_eq!(created.value(), &MetricValue::Counter { value: 2.0 });
+
+        let mut output = Vec::new();
+        patterns.metrics(&mut output);
+        assert_eq!(output.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn annotates_events_in_topology() {
+        assert_transform_compliance(async {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config()).await;
+
+            tx.send(LogEvent::from("job 1 done").into()).await.unwrap();
+            let event = out.recv().await.unwrap();
+            assert_eq!(
+                event.as_log()["pattern.template"],
+                Value::from("job 1 done")
+            );
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
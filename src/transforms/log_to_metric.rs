@@ -2,20 +2,22 @@ use std::{collections::HashMap, num::ParseFloatError};
 
 use chrono::Utc;
 use indexmap::IndexMap;
+use lookup::{event_path, path, PathPrefix};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
 use crate::{
     config::{
-        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        log_schema, DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
         TransformOutput,
     },
     event::{
         metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind, TagValue},
-        Event, Value,
+        Event, LogEvent, Value,
     },
     internal_events::{
-        LogToMetricFieldNullError, LogToMetricParseFloatError, ParserMissingFieldError, DROP_EVENT,
+        LogToMetricDeserializeError, LogToMetricFieldNullError, LogToMetricParseFloatError,
+        ParserMissingFieldError, DROP_EVENT,
     },
     schema,
     template::{Template, TemplateRenderingError},
@@ -28,7 +30,21 @@ use crate::{
 #[serde(deny_unknown_fields)]
 pub struct LogToMetricConfig {
     /// A list of metrics to generate.
+    #[serde(default)]
     pub metrics: Vec<MetricConfig>,
+
+    /// Converts log events which describe a whole metric back into that metric.
+    ///
+    /// When enabled, `metrics` is ignored, and every incoming log event is expected to have the shape
+    /// produced by the `metric_to_log` transform: the `name`, `namespace`, `tags`, `kind`,
+    /// `timestamp`, and `interval_ms` fields, along with one field describing the metric value, such
+    /// as `counter`, `gauge`, or `aggregated_histogram`. Combined with the `metric_to_log`
+    /// transform using `metric_tag_values = "full"`, this allows metrics to be stored as logs and
+    /// later converted back without losing their kind, value layout, tags, or timestamp.
+    ///
+    /// Log events which can't be converted are dropped.
+    #[serde(default)]
+    pub all_metrics: bool,
 }
 
 /// Specification of a counter derived from a log event.
@@ -141,6 +157,7 @@ impl GenerateConfig for LogToMetricConfig {
                     kind: MetricKind::Incremental,
                 }),
             }],
+            all_metrics: false,
         })
         .unwrap()
     }
@@ -189,6 +206,9 @@ enum TransformError {
         field: String,
         error: ParseFloatError,
     },
+    MetricDeserializeError {
+        error: serde_json::Error,
+    },
 }
 
 fn render_template(template: &Template, event: &Event) -> Result<String, TransformError> {
@@ -353,8 +373,74 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
         .with_timestamp(timestamp))
 }
 
+/// Converts a log event with the shape produced by the `metric_to_log` transform back into the
+/// metric it was created from.
+fn to_whole_metric(mut log: LogEvent) -> Result<Metric, TransformError> {
+    match log.namespace() {
+        LogNamespace::Legacy => {
+            // Undo the relocation of the timestamp and host done by `metric_to_log`.
+            if let Some(timestamp) = log.remove_timestamp() {
+                log.insert(event_path!("timestamp"), timestamp);
+            }
+            let host_key = log_schema().host_key();
+            if let Some(host) = log.remove(host_key) {
+                log.insert(format!("tags.{}", host_key).as_str(), host);
+            }
+        }
+        LogNamespace::Vector => {
+            // Drop the namespace marker added by `metric_to_log`.
+            log.remove((PathPrefix::Metadata, path!("vector")));
+        }
+    }
+
+    let (value, metadata) = log.into_parts();
+    let metric = serde_json::to_value(value)
+        .and_then(serde_json::from_value::<Metric>)
+        .map_err(|error| TransformError::MetricDeserializeError { error })?;
+    let (series, data, _) = metric.into_parts();
+
+    Ok(Metric::from_parts(series, data, metadata))
+}
+
+fn emit_transform_error(error: TransformError) {
+    match error {
+        TransformError::FieldNull { field } => emit!(LogToMetricFieldNullError {
+            field: field.as_ref()
+        }),
+        TransformError::FieldNotFound { field } => {
+            emit!(ParserMissingFieldError::<DROP_EVENT> {
+                field: field.as_ref()
+            })
+        }
+        TransformError::ParseFloatError { field, error } => {
+            emit!(LogToMetricParseFloatError {
+                field: field.as_ref(),
+                error
+            })
+        }
+        TransformError::TemplateRenderingError(error) => {
+            emit!(crate::internal_events::TemplateRenderingError {
+                error,
+                drop_event: true,
+                field: None,
+            })
+        }
+        TransformError::MetricDeserializeError { error } => {
+            emit!(LogToMetricDeserializeError { error })
+        }
+    };
+}
+
 impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        if self.config.all_metrics {
+            match to_whole_metric(event.into_log()) {
+                Ok(metric) => output.push(Event::Metric(metric)),
+                Err(error) => emit_transform_error(error),
+            }
+            return;
+        }
+
         // Metrics are "all or none" for a specific log. If a single fails, none are produced.
         let mut buffer = Vec::with_capacity(self.config.metrics.len());
 
@@ -363,30 +449,8 @@ impl FunctionTransform for LogToMetric {
                 Ok(metric) => {
                     buffer.push(Event::Metric(metric));
                 }
-                Err(err) => {
-                    match err {
-                        TransformError::FieldNull { field } => emit!(LogToMetricFieldNullError {
-                            field: field.as_ref()
-                        }),
-                        TransformError::FieldNotFound { field } => {
-                            emit!(ParserMissingFieldError::<DROP_EVENT> {
-                                field: field.as_ref()
-                            })
-                        }
-                        TransformError::ParseFloatError { field, error } => {
-                            emit!(LogToMetricParseFloatError {
-                                field: field.as_ref(),
-                                error
-                            })
-                        }
-                        TransformError::TemplateRenderingError(error) => {
-                            emit!(crate::internal_events::TemplateRenderingError {
-                                error,
-                                drop_event: true,
-                                field: None,
-                            })
-                        }
-                    };
+                Err(error) => {
+                    emit_transform_error(error);
                     // early return to prevent the partial buffer from being sent
                     return;
                 }
@@ -1,5 +1,6 @@
 pub mod v1;
 pub mod v2;
+pub mod v3;
 
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -61,6 +62,28 @@ pub struct LuaConfigV2 {
     config: v2::LuaConfig,
 }
 
+/// Marker type for the version three of the configuration for the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+enum V3 {
+    /// Lua transform API version 3.
+    #[serde(rename = "3")]
+    V3,
+}
+
+/// Configuration for the version three of the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct LuaConfigV3 {
+    /// Transform API version.
+    ///
+    /// Specifying this version ensures that backward compatibility is not broken.
+    version: V3,
+
+    #[serde(flatten)]
+    config: v3::LuaConfig,
+}
+
 /// Configuration for the `lua` transform.
 #[configurable_component(transform(
     "lua",
@@ -74,12 +97,15 @@ pub enum LuaConfig {
 
     /// Configuration for version two.
     V2(LuaConfigV2),
+
+    /// Configuration for version three.
+    V3(LuaConfigV3),
 }
 
 impl GenerateConfig for LuaConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
-            r#"version = "2"
+            r#"version = "3"
             hooks.process = """#,
         )
         .unwrap()
@@ -93,6 +119,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.build(),
             LuaConfig::V2(v2) => v2.config.build(),
+            LuaConfig::V3(v3) => v3.config.build(),
         }
     }
 
@@ -100,6 +127,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.input(),
             LuaConfig::V2(v2) => v2.config.input(),
+            LuaConfig::V3(v3) => v3.config.input(),
         }
     }
 
@@ -111,6 +139,7 @@ impl TransformConfig for LuaConfig {
         match self {
             LuaConfig::V1(v1) => v1.config.outputs(input_definitions),
             LuaConfig::V2(v2) => v2.config.outputs(input_definitions),
+            LuaConfig::V3(v3) => v3.config.outputs(input_definitions),
         }
     }
 }
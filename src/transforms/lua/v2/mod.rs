@@ -87,7 +87,7 @@ pub struct LuaConfig {
     metric_tag_values: MetricTagValues,
 }
 
-fn default_config_paths() -> Vec<PathBuf> {
+pub(super) fn default_config_paths() -> Vec<PathBuf> {
     match CONFIG_PATHS.lock().ok() {
         Some(config_paths) => config_paths
             .clone()
@@ -211,7 +211,7 @@ impl LuaConfig {
 //
 // This constant is larger than 1 because calling GC is an expensive operation, so doing it
 // after each transform would have significant footprint on the performance.
-const GC_INTERVAL: usize = 16;
+pub(super) const GC_INTERVAL: usize = 16;
 
 pub struct Lua {
     lua: mlua::Lua,
@@ -0,0 +1,187 @@
+use lookup::{lookup_v2::parse_target_path, path, OwnedTargetPath};
+use mlua::prelude::*;
+use vrl::{ProgramInfo, Target};
+
+use crate::event::{
+    lua::metric::LuaMetric, Event, LogEvent, Metric, TargetEvents, Value, VrlTarget,
+};
+
+/// The keys of the table representation of a metric which hold its value.
+const METRIC_VALUE_KEYS: [&str; 7] = [
+    "counter",
+    "gauge",
+    "set",
+    "distribution",
+    "aggregated_histogram",
+    "aggregated_summary",
+    "sketch",
+];
+
+/// An event exposed to Lua as userdata, whose fields are accessed through VRL paths.
+///
+/// Paths behave as they do in VRL: `.` refers to the event and `%` to its metadata, and metrics
+/// only expose their `name`, `namespace`, `timestamp`, `kind`, `tags` and `type` fields.
+pub struct LuaEvent {
+    target: VrlTarget,
+}
+
+impl LuaEvent {
+    pub fn new(event: Event, multi_value_tags: bool) -> Self {
+        // Querying the root of the event makes the target expose every field of metrics.
+        let info = ProgramInfo {
+            fallible: false,
+            abortable: false,
+            target_queries: vec![OwnedTargetPath::event_root()],
+            target_assignments: vec![],
+        };
+        Self {
+            target: VrlTarget::new(event, &info, multi_value_tags),
+        }
+    }
+
+    /// Emits the events held by this one, which are several if its root was set to an array.
+    pub fn emit_into<F: FnMut(Event)>(self, mut emit_fn: F) {
+        match self.target.into_events() {
+            TargetEvents::One(event) => emit_fn(event),
+            TargetEvents::Logs(events) => events.for_each(emit_fn),
+            TargetEvents::Traces(events) => events.for_each(emit_fn),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self.target {
+            VrlTarget::LogEvent(..) => "log",
+            VrlTarget::Metric { .. } => "metric",
+            VrlTarget::Trace(..) => "trace",
+        }
+    }
+
+    fn metric(&self) -> LuaResult<&Metric> {
+        match &self.target {
+            VrlTarget::Metric { metric, .. } => Ok(metric),
+            _ => Err(not_a_metric(self.kind())),
+        }
+    }
+
+    /// Returns the value of the metric as a table holding a single key named after its type, in
+    /// the same format as the version two of the transform.
+    fn metric_value<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaTable<'lua>> {
+        let metric = self.metric()?;
+        let table = LuaMetric {
+            metric: Metric::new("", metric.kind(), metric.value().clone()),
+            multi_value_tags: false,
+        }
+        .to_lua(lua)?;
+        let LuaValue::Table(table) = table else {
+            unreachable!("metrics are converted to tables");
+        };
+
+        let value = lua.create_table()?;
+        for key in METRIC_VALUE_KEYS {
+            let field: LuaValue<'_> = table.raw_get(key)?;
+            if !field.is_nil() {
+                value.raw_set(key, field)?;
+            }
+        }
+        Ok(value)
+    }
+
+    fn set_metric_value<'lua>(&mut self, lua: &'lua Lua, value: LuaTable<'lua>) -> LuaResult<()> {
+        let kind = self.kind();
+        let VrlTarget::Metric { metric, value: fields, .. } = &mut self.target else {
+            return Err(not_a_metric(kind));
+        };
+
+        let table = lua.create_table()?;
+        table.raw_set("name", "")?;
+        for key in METRIC_VALUE_KEYS {
+            table.raw_set(key, value.raw_get::<_, LuaValue<'_>>(key)?)?;
+        }
+        let parsed = Metric::from_lua(LuaValue::Table(table), lua)?;
+
+        *metric.value_mut() = parsed.value().clone();
+        fields.insert(path!("type"), metric.value().clone().into());
+        Ok(())
+    }
+}
+
+fn parse_path(path: &str) -> LuaResult<OwnedTargetPath> {
+    parse_target_path(path)
+        .map_err(|error| LuaError::RuntimeError(format!("Invalid path \"{path}\": {error}")))
+}
+
+fn not_a_metric(kind: &str) -> LuaError {
+    LuaError::RuntimeError(format!("Expected a metric event, found a {kind} event"))
+}
+
+impl LuaUserData for LuaEvent {
+    fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("kind", |_, this, ()| Ok(this.kind()));
+
+        methods.add_method("get", |_, this, path: String| {
+            this.target
+                .target_get(&parse_path(&path)?)
+                .map(Option::<&Value>::cloned)
+                .map_err(LuaError::RuntimeError)
+        });
+
+        methods.add_method_mut("set", |_, this, (path, value): (String, Value)| {
+            this.target
+                .target_insert(&parse_path(&path)?, value)
+                .map_err(LuaError::RuntimeError)
+        });
+
+        methods.add_method_mut("remove", |_, this, path: String| {
+            this.target
+                .target_remove(&parse_path(&path)?, false)
+                .map_err(LuaError::RuntimeError)
+        });
+
+        methods.add_method("value", |lua, this, ()| this.metric_value(lua));
+
+        methods.add_method_mut("set_value", |lua, this, value: LuaTable<'_>| {
+            this.set_metric_value(lua, value)
+        });
+
+        methods.add_method("copy", |_, this, ()| {
+            Ok(Self {
+                target: this.target.clone(),
+            })
+        });
+    }
+}
+
+/// Registers the `vector` table, which holds the functions creating new events.
+pub fn register_constructors(lua: &Lua, multi_value_tags: bool) -> LuaResult<()> {
+    let vector = lua.create_table()?;
+
+    vector.raw_set(
+        "log",
+        lua.create_function(move |_, fields: Value| {
+            let fields = match fields {
+                Value::Object(fields) => fields,
+                // Empty tables and `nil` are converted to these.
+                Value::Null => Default::default(),
+                Value::Array(array) if array.is_empty() => Default::default(),
+                _ => {
+                    return Err(LuaError::RuntimeError(
+                        "The fields of a log event should be a table".to_string(),
+                    ))
+                }
+            };
+            Ok(LuaEvent::new(
+                Event::Log(LogEvent::from(fields)),
+                multi_value_tags,
+            ))
+        })?,
+    )?;
+
+    vector.raw_set(
+        "metric",
+        lua.create_function(move |_, metric: Metric| {
+            Ok(LuaEvent::new(Event::Metric(metric), multi_value_tags))
+        })?,
+    )?;
+
+    lua.globals().raw_set("vector", vector)
+}
@@ -0,0 +1,631 @@
+mod event;
+
+use std::{path::PathBuf, time::Duration};
+
+use codecs::MetricTagValues;
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+use vector_core::transform::runtime_transform::{RuntimeTransform, Timer};
+
+use self::event::{register_constructors, LuaEvent};
+use super::v2::{default_config_paths, GC_INTERVAL};
+use crate::config::OutputId;
+use crate::schema::Definition;
+use crate::{
+    config::{DataType, Input, TransformOutput},
+    event::Event,
+    internal_events::{LuaBuildError, LuaGcTriggered},
+    schema,
+    transforms::Transform,
+};
+
+#[derive(Debug, Snafu)]
+pub enum BuildError {
+    #[snafu(display("Invalid \"search_dirs\": {}", source))]
+    InvalidSearchDirs { source: mlua::Error },
+    #[snafu(display("Cannot register the \"vector\" table: {}", source))]
+    InvalidConstructors { source: mlua::Error },
+    #[snafu(display("Cannot evaluate Lua code in \"source\": {}", source))]
+    InvalidSource { source: mlua::Error },
+
+    #[snafu(display("Cannot evaluate Lua code defining \"hooks.init\": {}", source))]
+    InvalidHooksInit { source: mlua::Error },
+    #[snafu(display("Cannot evaluate Lua code defining \"hooks.process\": {}", source))]
+    InvalidHooksProcess { source: mlua::Error },
+    #[snafu(display("Cannot evaluate Lua code defining \"hooks.shutdown\": {}", source))]
+    InvalidHooksShutdown { source: mlua::Error },
+    #[snafu(display("Cannot evaluate Lua code defining timer handler: {}", source))]
+    InvalidTimerHandler { source: mlua::Error },
+
+    #[snafu(display("Runtime error in \"hooks.init\" function: {}", source))]
+    RuntimeErrorHooksInit { source: mlua::Error },
+    #[snafu(display("Runtime error in \"hooks.process\" function: {}", source))]
+    RuntimeErrorHooksProcess { source: mlua::Error },
+    #[snafu(display("Runtime error in \"hooks.shutdown\" function: {}", source))]
+    RuntimeErrorHooksShutdown { source: mlua::Error },
+    #[snafu(display("Runtime error in timer handler: {}", source))]
+    RuntimeErrorTimerHandler { source: mlua::Error },
+
+    #[snafu(display("Cannot call GC in Lua runtime: {}", source))]
+    RuntimeErrorGc { source: mlua::Error },
+}
+
+/// Configuration for the version three of the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LuaConfig {
+    /// The Lua program to initialize the transform with.
+    ///
+    /// The program can be used to to import external dependencies, as well as define the functions
+    /// used for the various lifecycle hooks. However, it's not strictly required, as the lifecycle
+    /// hooks can be configured directly with inline Lua source for each respective hook.
+    #[configurable(metadata(
+        docs::examples = "function init()\n\tcount = 0\nend\n\nfunction process(event, emit)\n\tcount = count + 1\nend\n\nfunction timer_handler(emit)\n\twhile true do\n\t\temit(vector.metric({\n\t\t\tname = \"event_counter\",\n\t\t\tkind = \"incremental\",\n\t\t\tcounter = { value = count },\n\t\t}))\n\t\tcount = 0\n\t\temit = coroutine.yield()\n\tend\nend",
+        docs::examples = "-- external file with hooks and timers defined\nrequire('custom_module')",
+    ))]
+    source: Option<String>,
+
+    /// A list of directories to search when loading a Lua file via the `require` function.
+    ///
+    /// If not specified, the modules are looked up in the configuration directories.
+    #[serde(default = "default_config_paths")]
+    #[configurable(metadata(docs::examples = "/etc/vector/lua"))]
+    search_dirs: Vec<PathBuf>,
+
+    #[configurable(derived)]
+    hooks: HooksConfig,
+
+    /// A list of timers which should be configured and executed periodically.
+    #[serde(default)]
+    timers: Vec<TimerConfig>,
+
+    /// When set to `single`, metric tag values are exposed as single strings, the
+    /// same as they were before this config option. Tags with multiple values show the last assigned value, and null values
+    /// are ignored.
+    ///
+    /// When set to `full`, all metric tags are exposed as arrays of either string or null
+    /// values.
+    #[serde(default)]
+    metric_tag_values: MetricTagValues,
+}
+
+/// Lifecycle hooks.
+///
+/// These hooks can be set to perform additional processing during the lifecycle of the transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct HooksConfig {
+    /// The function called when the first event comes in, before `hooks.process` is called.
+    ///
+    /// It can produce new events using the `emit` function.
+    ///
+    /// This can either be inline Lua that defines a closure to use, or the name of the Lua function to call. In both
+    /// cases, the closure/function takes a single parameter, `emit`, which is a reference to a function for emitting events.
+    #[configurable(metadata(
+        docs::examples = "function (emit)\n\t-- Custom Lua code here\nend",
+        docs::examples = "init",
+    ))]
+    init: Option<String>,
+
+    /// The function called for each incoming event.
+    ///
+    /// It can produce new events using the `emit` function.
+    ///
+    /// This can either be inline Lua that defines a closure to use, or the name of the Lua function to call. In both
+    /// cases, the closure/function takes two parameters. The first parameter, `event`, is the event being processed,
+    /// while the second parameter, `emit`, is a reference to a function for emitting events.
+    ///
+    /// The fields of the event are accessed with the `get`, `set`, and `remove` methods, which take
+    /// a VRL path. An event can't be used anymore once emitted, unless it's emitted as a copy made
+    /// with its `copy` method.
+    #[configurable(metadata(
+        docs::examples = "function (event, emit)\n\tevent:set(\".field\", \"value\") -- set value of a field\n\tevent:remove(\".another_field\") -- remove field\n\tevent:set(\".second\", event:remove(\".first\")) -- rename field\n\t-- Very important! Emit the processed event.\n\temit(event)\nend",
+        docs::examples = "process",
+    ))]
+    process: String,
+
+    /// The function called when the transform is stopped.
+    ///
+    /// It can produce new events using the `emit` function.
+    ///
+    /// This can either be inline Lua that defines a closure to use, or the name of the Lua function to call. In both
+    /// cases, the closure/function takes a single parameter, `emit`, which is a reference to a function for emitting events.
+    #[configurable(metadata(
+        docs::examples = "function (emit)\n\t-- Custom Lua code here\nend",
+        docs::examples = "shutdown",
+    ))]
+    shutdown: Option<String>,
+}
+
+/// A Lua timer.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+struct TimerConfig {
+    /// The interval to execute the handler, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    interval_seconds: Duration,
+
+    /// The handler function which is called when the timer ticks.
+    ///
+    /// It can produce new events using the `emit` function.
+    ///
+    /// This can either be inline Lua that defines a closure to use, or the name of the Lua function
+    /// to call. In both cases, the closure/function takes a single parameter, `emit`, which is a
+    /// reference to a function for emitting events.
+    ///
+    /// The handler runs as a coroutine: when it yields, the next tick of the timer resumes it
+    /// instead of calling it again, with the `emit` function of that tick returned by
+    /// `coroutine.yield`. This allows keeping state in local variables between ticks.
+    #[configurable(metadata(docs::examples = "timer_handler"))]
+    handler: String,
+}
+
+impl LuaConfig {
+    pub fn build(&self) -> crate::Result<Transform> {
+        Lua::new(self).map(Transform::event_task)
+    }
+
+    pub fn input(&self) -> Input {
+        Input::new(DataType::Metric | DataType::Log)
+    }
+
+    pub fn outputs(
+        &self,
+        input_definitions: &[(OutputId, schema::Definition)],
+    ) -> Vec<TransformOutput> {
+        // Lua causes the type definition to be reset
+        let namespaces = input_definitions
+            .iter()
+            .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+            .collect();
+
+        let definition = input_definitions
+            .iter()
+            .map(|(output, _definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(&namespaces),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(
+            DataType::Metric | DataType::Log,
+            definition,
+        )]
+    }
+}
+
+/// The handler of a timer, along with the coroutine running it if it yielded on a previous tick.
+struct TimerHandler {
+    function: mlua::RegistryKey,
+    coroutine: Option<mlua::RegistryKey>,
+}
+
+pub struct Lua {
+    lua: mlua::Lua,
+    invocations_after_gc: usize,
+    hook_init: Option<mlua::RegistryKey>,
+    hook_process: mlua::RegistryKey,
+    hook_shutdown: Option<mlua::RegistryKey>,
+    timers: Vec<(Timer, TimerHandler)>,
+    multi_value_tags: bool,
+}
+
+// Helper to create `RegistryKey` from Lua function code
+fn make_registry_value(lua: &mlua::Lua, source: &str) -> mlua::Result<mlua::RegistryKey> {
+    lua.load(source)
+        .eval::<mlua::Function>()
+        .and_then(|f| lua.create_registry_value(f))
+}
+
+impl Lua {
+    pub fn new(config: &LuaConfig) -> crate::Result<Self> {
+        // In order to support loading C modules in Lua, we need to create unsafe instance
+        // without debug library.
+        let lua = unsafe {
+            mlua::Lua::unsafe_new_with(mlua::StdLib::ALL_SAFE, mlua::LuaOptions::default())
+        };
+
+        let additional_paths = config
+            .search_dirs
+            .iter()
+            .map(|d| format!("{}/?.lua", d.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        if !additional_paths.is_empty() {
+            let package = lua
+                .globals()
+                .get::<_, mlua::Table<'_>>("package")
+                .context(InvalidSearchDirsSnafu)?;
+            let current_paths = package
+                .get::<_, String>("path")
+                .unwrap_or_else(|_| ";".to_string());
+            let paths = format!("{};{}", additional_paths, current_paths);
+            package.set("path", paths).context(InvalidSearchDirsSnafu)?;
+        }
+
+        let multi_value_tags = config.metric_tag_values == MetricTagValues::Full;
+        register_constructors(&lua, multi_value_tags).context(InvalidConstructorsSnafu)?;
+
+        if let Some(source) = &config.source {
+            lua.load(source).eval().context(InvalidSourceSnafu)?;
+        }
+
+        let hook_init = config
+            .hooks
+            .init
+            .as_ref()
+            .map(|code| make_registry_value(&lua, code))
+            .transpose()
+            .context(InvalidHooksInitSnafu)?;
+
+        let hook_process =
+            make_registry_value(&lua, &config.hooks.process).context(InvalidHooksProcessSnafu)?;
+
+        let hook_shutdown = config
+            .hooks
+            .shutdown
+            .as_ref()
+            .map(|code| make_registry_value(&lua, code))
+            .transpose()
+            .context(InvalidHooksShutdownSnafu)?;
+
+        let mut timers = Vec::new();
+        for (id, timer) in config.timers.iter().enumerate() {
+            let function =
+                make_registry_value(&lua, &timer.handler).context(InvalidTimerHandlerSnafu)?;
+
+            let timer = Timer {
+                id: id as u32,
+                interval: timer.interval_seconds,
+            };
+            let handler = TimerHandler {
+                function,
+                coroutine: None,
+            };
+            timers.push((timer, handler));
+        }
+
+        Ok(Self {
+            lua,
+            invocations_after_gc: 0,
+            hook_init,
+            hook_process,
+            hook_shutdown,
+            timers,
+            multi_value_tags,
+        })
+    }
+
+    fn attempt_gc(&mut self) {
+        self.invocations_after_gc += 1;
+        if self.invocations_after_gc % GC_INTERVAL == 0 {
+            emit!(LuaGcTriggered {
+                used_memory: self.lua.used_memory()
+            });
+            let _ = self
+                .lua
+                .gc_collect()
+                .context(RuntimeErrorGcSnafu)
+                .map_err(|error| error!(%error, rate_limit = 30));
+            self.invocations_after_gc = 0;
+        }
+    }
+}
+
+// A helper that reduces code duplication.
+fn wrap_emit_fn<'lua, 'scope, F: 'scope>(
+    scope: &mlua::Scope<'lua, 'scope>,
+    mut emit_fn: F,
+) -> mlua::Result<mlua::Function<'lua>>
+where
+    F: FnMut(Event),
+{
+    scope.create_function_mut(move |_, event: mlua::AnyUserData<'_>| -> mlua::Result<()> {
+        // Taking the event out of Lua avoids copying it, which is why it can't be used once
+        // emitted.
+        event.take::<LuaEvent>()?.emit_into(&mut emit_fn);
+        Ok(())
+    })
+}
+
+impl RuntimeTransform for Lua {
+    fn hook_process<F>(&mut self, event: Event, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        let lua = &self.lua;
+        let _ = lua
+            .scope(|scope| -> mlua::Result<()> {
+                lua.registry_value::<mlua::Function>(&self.hook_process)?
+                    .call((
+                        LuaEvent::new(event, self.multi_value_tags),
+                        wrap_emit_fn(scope, emit_fn)?,
+                    ))
+            })
+            .context(RuntimeErrorHooksProcessSnafu)
+            .map_err(|e| emit!(LuaBuildError { error: e }));
+
+        self.attempt_gc();
+    }
+
+    fn hook_init<F>(&mut self, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        let lua = &self.lua;
+        let _ = lua
+            .scope(|scope| -> mlua::Result<()> {
+                match &self.hook_init {
+                    Some(key) => lua
+                        .registry_value::<mlua::Function>(key)?
+                        .call(wrap_emit_fn(scope, emit_fn)?),
+                    None => Ok(()),
+                }
+            })
+            .context(RuntimeErrorHooksInitSnafu)
+            .map_err(|error| error!(%error, rate_limit = 30));
+
+        self.attempt_gc();
+    }
+
+    fn hook_shutdown<F>(&mut self, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        let lua = &self.lua;
+        let _ = lua
+            .scope(|scope| -> mlua::Result<()> {
+                match &self.hook_shutdown {
+                    Some(key) => lua
+                        .registry_value::<mlua::Function>(key)?
+                        .call(wrap_emit_fn(scope, emit_fn)?),
+                    None => Ok(()),
+                }
+            })
+            .context(RuntimeErrorHooksShutdownSnafu)
+            .map_err(|error| error!(%error, rate_limit = 30));
+
+        self.attempt_gc();
+    }
+
+    fn timer_handler<F>(&mut self, timer: Timer, emit_fn: F)
+    where
+        F: FnMut(Event),
+    {
+        let lua = &self.lua;
+        let handler = &mut self.timers[timer.id as usize].1;
+        let _ = lua
+            .scope(|scope| -> mlua::Result<()> {
+                // Resume the coroutine of the previous tick if it yielded, otherwise start a new
+                // one. A coroutine which failed is dropped, so that the next tick starts anew.
+                let coroutine = match handler.coroutine.take() {
+                    Some(key) => {
+                        let coroutine = lua.registry_value::<mlua::Thread>(&key)?;
+                        lua.remove_registry_value(key)?;
+                        coroutine
+                    }
+                    None => lua.create_thread(lua.registry_value(&handler.function)?)?,
+                };
+
+                coroutine.resume::<_, ()>(wrap_emit_fn(scope, emit_fn)?)?;
+
+                if coroutine.status() == mlua::ThreadStatus::Resumable {
+                    handler.coroutine = Some(lua.create_registry_value(coroutine)?);
+                }
+                Ok(())
+            })
+            .context(RuntimeErrorTimerHandlerSnafu)
+            .map_err(|error| error!(%error, rate_limit = 30));
+
+        self.attempt_gc();
+    }
+
+    fn timers(&self) -> Vec<Timer> {
+        self.timers.iter().map(|(timer, _)| *timer).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::metric_tags;
+
+    use super::*;
+    use crate::event::{
+        metric::{Metric, MetricKind, MetricValue},
+        LogEvent, Value,
+    };
+
+    fn from_config(config: &str) -> Lua {
+        Lua::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn process(lua: &mut Lua, event: impl Into<Event>) -> Vec<Event> {
+        let mut output = Vec::new();
+        lua.hook_process(event.into(), |event| output.push(event));
+        output
+    }
+
+    fn tick(lua: &mut Lua, id: u32) -> Vec<Event> {
+        let mut output = Vec::new();
+        let timer = lua.timers()[id as usize];
+        lua.timer_handler(timer, |event| output.push(event));
+        output
+    }
+
+    #[test]
+    fn get_set_and_remove_paths() {
+        let mut lua = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                event:set(".user.name", event:get(".name"))
+                event:remove(".name")
+                event:set(".tags[1]", "second")
+                event:set("%source", "lua")
+                event:set(".kind", event:kind())
+                emit(event)
+            end
+            """
+            "#,
+        );
+
+        let mut log = LogEvent::default();
+        log.insert("name", "Bob");
+        let output = process(&mut lua, log);
+
+        assert_eq!(output.len(), 1);
+        let log = output[0].as_log();
+        assert_eq!(log["user.name"], "Bob".into());
+        assert_eq!(log.get("name"), None);
+        assert_eq!(
+            log["tags"],
+            Value::Array(vec![Value::Null, "second".into()])
+        );
+        assert_eq!(log["kind"], "log".into());
+        assert_eq!(
+            log.metadata().value().get("source"),
+            Some(&Value::from("lua"))
+        );
+    }
+
+    #[test]
+    fn setting_root_to_an_array_emits_several_events() {
+        let mut lua = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                event:set(".", { { message = "first" }, { message = "second" } })
+                emit(event)
+            end
+            """
+            "#,
+        );
+
+        let output = process(&mut lua, LogEvent::from("original"));
+
+        let messages = output
+            .iter()
+            .map(|event| event.as_log()["message"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first".into(), "second".into()]);
+    }
+
+    #[test]
+    fn copy_emits_the_same_event_twice() {
+        let mut lua = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                emit(event:copy())
+                emit(event)
+            end
+            """
+            "#,
+        );
+
+        let output = process(&mut lua, LogEvent::from("twice"));
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[0], output[1]);
+    }
+
+    #[test]
+    fn modify_metric() {
+        let mut lua = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                local value = event:value()
+                value.counter.value = value.counter.value + 1
+                event:set_value(value)
+                event:set(".tags.host", "localhost")
+                event:set(".name", event:get(".namespace") .. "_" .. event:get(".name"))
+                emit(event)
+            end
+            """
+            "#,
+        );
+
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_namespace(Some("app"));
+        let expected = Metric::new(
+            "app_requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 2.0 },
+        )
+        .with_namespace(Some("app"))
+        .with_tags(Some(metric_tags!("host" => "localhost")));
+
+        let output = process(&mut lua, metric);
+
+        assert_eq!(output, vec![Event::Metric(expected)]);
+    }
+
+    #[test]
+    fn invalid_path_is_an_error() {
+        let mut lua = from_config(
+            r#"
+            hooks.process = """function (event, emit)
+                event:set(".value", 1)
+                emit(event)
+            end
+            """
+            "#,
+        );
+
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        // Metrics don't have a `.value` field, so the event is dropped.
+        assert!(process(&mut lua, metric).is_empty());
+    }
+
+    #[test]
+    fn timer_coroutine_keeps_state_between_ticks() {
+        let mut lua = from_config(
+            r#"
+            hooks.process = "function (event, emit) end"
+
+            [[timers]]
+            interval_seconds = 10
+            handler = """function (emit)
+                local ticks = 0
+                while ticks < 2 do
+                    ticks = ticks + 1
+                    emit(vector.log({ ticks = ticks }))
+                    emit = coroutine.yield()
+                end
+            end
+            """
+
+            [[timers]]
+            interval_seconds = 60
+            handler = """function (emit)
+                emit(vector.metric({ name = "ticks", gauge = { value = 1 } }))
+            end
+            """
+            "#,
+        );
+
+        let ticks = (0..3)
+            .flat_map(|_| tick(&mut lua, 0))
+            .map(|event| event.as_log()["ticks"].clone())
+            .collect::<Vec<_>>();
+        // The coroutine finished on the third tick, so the next one starts it again.
+        assert_eq!(ticks, vec![Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(tick(&mut lua, 0)[0].as_log()["ticks"], Value::Integer(1));
+
+        let output = tick(&mut lua, 1);
+        assert_eq!(output[0].as_metric().name(), "ticks");
+    }
+}
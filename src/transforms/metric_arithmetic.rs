@@ -0,0 +1,548 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use snafu::Snafu;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event,
+    },
+    internal_events::{
+        MetricArithmeticDivisionByZero, MetricArithmeticUnsupportedValue, TemplateRenderingError,
+    },
+    schema,
+    template::Template,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `metric_arithmetic` transform.
+#[configurable_component(transform(
+    "metric_arithmetic",
+    "Compute derived metrics from multiple incoming metric series."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MetricArithmeticConfig {
+    /// The interval between evaluations, in milliseconds.
+    ///
+    /// Operand values are collected during this time frame, and derived metrics are computed from
+    /// the collected values at the end of it.
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+
+    /// Whether or not to forward the incoming metrics alongside the derived ones.
+    #[serde(default = "crate::serde::default_true")]
+    pub passthrough: bool,
+
+    /// The derived metrics to compute.
+    pub metrics: Vec<DerivedMetricConfig>,
+}
+
+/// Configuration of a single derived metric.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DerivedMetricConfig {
+    /// The name of the derived metric.
+    #[configurable(metadata(docs::examples = "http_error_ratio"))]
+    pub name: String,
+
+    /// The namespace of the derived metric.
+    pub namespace: Option<String>,
+
+    /// The operation used to combine the operands.
+    pub operation: Operation,
+
+    /// The names of the metrics used as operands.
+    ///
+    /// At least two operands are required. The operation is applied from left to right, so
+    /// `["a", "b", "c"]` with the `subtract` operation computes `a - b - c`.
+    #[configurable(metadata(docs::examples = "http_errors_total"))]
+    #[configurable(metadata(docs::examples = "http_requests_total"))]
+    pub operands: Vec<String>,
+
+    /// A template used to match series of the different operands together.
+    ///
+    /// Operand series that render to the same value are combined into one derived series. Only
+    /// the tags that are identical across all the matched operand series are kept on the derived
+    /// metric.
+    ///
+    /// If unset, operand series are only matched when their tags are identical.
+    #[configurable(metadata(docs::examples = "{{ tags.host }}"))]
+    #[configurable(metadata(docs::examples = "{{ tags.service }}-{{ tags.region }}"))]
+    pub group_by: Option<Template>,
+}
+
+/// An arithmetic operation applied to operands.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// Sum the operands.
+    Add,
+
+    /// Subtract the subsequent operands from the first one.
+    Subtract,
+
+    /// Multiply the operands.
+    Multiply,
+
+    /// Divide the first operand by the subsequent ones.
+    ///
+    /// Series where a divisor is zero are skipped.
+    Divide,
+}
+
+impl Operation {
+    fn apply(self, left: f64, right: f64) -> Option<f64> {
+        match self {
+            Self::Add => Some(left + right),
+            Self::Subtract => Some(left - right),
+            Self::Multiply => Some(left * right),
+            Self::Divide => (right != 0.0).then_some(left / right),
+        }
+    }
+}
+
+const fn default_interval_ms() -> u64 {
+    10 * 1000
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "Derived metric {:?} must have at least two operands, got {}.",
+        name,
+        count
+    ))]
+    NotEnoughOperands { name: String, count: usize },
+}
+
+impl_generate_config_from_default!(MetricArithmeticConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "metric_arithmetic")]
+impl TransformConfig for MetricArithmeticConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        MetricArithmetic::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(
+        &self,
+        _: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(DataType::Metric, HashMap::new())]
+    }
+}
+
+/// The operand values collected for one derived series during the current window.
+#[derive(Debug)]
+struct Group {
+    values: Vec<Option<f64>>,
+    tags: Option<MetricTags>,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl Group {
+    fn new(operands: usize, tags: Option<MetricTags>) -> Self {
+        Self {
+            values: vec![None; operands],
+            tags,
+            timestamp: None,
+        }
+    }
+
+    fn record(&mut self, position: usize, metric: &Metric, value: f64) {
+        let slot = &mut self.values[position];
+        *slot = match (metric.kind(), *slot) {
+            (MetricKind::Incremental, Some(existing)) => Some(existing + value),
+            _ => Some(value),
+        };
+
+        // Only keep the tags which have the same value across every matched series.
+        match (&mut self.tags, metric.tags()) {
+            (Some(tags), Some(other)) => {
+                tags.retain(|key, value| other.get(key) == value.as_single());
+            }
+            (tags, None) => *tags = None,
+            (None, Some(_)) => {}
+        }
+
+        self.timestamp = self.timestamp.max(metric.timestamp());
+    }
+}
+
+type GroupKey = (Option<String>, Option<MetricTags>);
+
+#[derive(Debug)]
+struct DerivedMetric {
+    name: String,
+    namespace: Option<String>,
+    operation: Operation,
+    operands: usize,
+    group_by: Option<Template>,
+    groups: HashMap<GroupKey, Group>,
+}
+
+impl DerivedMetric {
+    fn group_key(&self, event: &Event) -> Option<GroupKey> {
+        match &self.group_by {
+            Some(template) => template
+                .render_string(event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("group_by"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+                .map(|key| (Some(key), None)),
+            None => Some((None, event.as_metric().tags().cloned())),
+        }
+    }
+
+    fn evaluate(&mut self, output: &mut Vec<Event>) {
+        for (_, group) in self.groups.drain() {
+            // Skip series for which at least one operand wasn't seen during this window.
+            let values = match group.values.into_iter().collect::<Option<Vec<f64>>>() {
+                Some(values) => values,
+                None => continue,
+            };
+
+            let operation = self.operation;
+            match values[1..]
+                .iter()
+                .try_fold(values[0], |acc, value| operation.apply(acc, *value))
+            {
+                Some(value) => {
+                    let metric = Metric::new(
+                        self.name.clone(),
+                        MetricKind::Absolute,
+                        MetricValue::Gauge { value },
+                    )
+                    .with_namespace(self.namespace.clone())
+                    .with_tags(group.tags)
+                    .with_timestamp(group.timestamp);
+                    output.push(Event::Metric(metric));
+                }
+                None => emit!(MetricArithmeticDivisionByZero { name: &self.name }),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MetricArithmetic {
+    interval: Duration,
+    passthrough: bool,
+    derived: Vec<DerivedMetric>,
+    /// Maps an operand metric name to the derived metrics and positions it is used at.
+    operands: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl MetricArithmetic {
+    pub fn new(config: &MetricArithmeticConfig) -> crate::Result<Self> {
+        let mut derived = Vec::with_capacity(config.metrics.len());
+        let mut operands: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+
+        for (index, metric) in config.metrics.iter().enumerate() {
+            if metric.operands.len() < 2 {
+                return Err(Box::new(BuildError::NotEnoughOperands {
+                    name: metric.name.clone(),
+                    count: metric.operands.len(),
+                }));
+            }
+
+            for (position, operand) in metric.operands.iter().enumerate() {
+                operands
+                    .entry(operand.clone())
+                    .or_default()
+                    .push((index, position));
+            }
+
+            derived.push(DerivedMetric {
+                name: metric.name.clone(),
+                namespace: metric.namespace.clone(),
+                operation: metric.operation,
+                operands: metric.operands.len(),
+                group_by: metric.group_by.clone(),
+                groups: HashMap::new(),
+            });
+        }
+
+        Ok(Self {
+            interval: Duration::from_millis(config.interval_ms),
+            passthrough: config.passthrough,
+            derived,
+            operands,
+        })
+    }
+
+    fn record(&mut self, event: &Event) {
+        let metric = event.as_metric();
+        let positions = match self.operands.get(metric.name()) {
+            Some(positions) => positions,
+            None => return,
+        };
+
+        let value = match metric.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+            _ => {
+                emit!(MetricArithmeticUnsupportedValue {
+                    name: metric.name()
+                });
+                return;
+            }
+        };
+
+        for &(index, position) in positions {
+            let derived = &mut self.derived[index];
+            let key = match derived.group_key(event) {
+                Some(key) => key,
+                None => continue,
+            };
+            let operands = derived.operands;
+            let group = match derived.groups.entry(key) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(Group::new(operands, metric.tags().cloned())),
+            };
+            group.record(position, metric, value);
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        for derived in &mut self.derived {
+            derived.evaluate(output);
+        }
+    }
+}
+
+impl TaskTransform<Event> for MetricArithmetic {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.interval);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => {
+                                self.record(&event);
+                                if self.passthrough {
+                                    output.push(event);
+                                }
+                            }
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MetricArithmeticConfig>();
+    }
+
+    fn derived(
+        operation: Operation,
+        operands: &[&str],
+        group_by: Option<&str>,
+    ) -> DerivedMetricConfig {
+        DerivedMetricConfig {
+            name: "derived".into(),
+            namespace: None,
+            operation,
+            operands: operands.iter().map(|s| s.to_string()).collect(),
+            group_by: group_by.map(|template| Template::try_from(template).unwrap()),
+        }
+    }
+
+    fn config(metrics: Vec<DerivedMetricConfig>) -> MetricArithmeticConfig {
+        MetricArithmeticConfig {
+            interval_ms: 1000,
+            passthrough: false,
+            metrics,
+        }
+    }
+
+    fn tags(pairs: &[(&str, &str)]) -> Option<MetricTags> {
+        Some(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    fn counter(name: &str, value: f64, tags: Option<MetricTags>) -> Event {
+        Event::Metric(
+            Metric::new(
+                name,
+                MetricKind::Incremental,
+                MetricValue::Counter { value },
+            )
+            .with_tags(tags),
+        )
+    }
+
+    fn evaluate(transform: &mut MetricArithmetic, events: Vec<Event>) -> Vec<Metric> {
+        for event in &events {
+            transform.record(event);
+        }
+        let mut output = Vec::new();
+        transform.flush_into(&mut output);
+        let mut metrics: Vec<_> = output.into_iter().map(Event::into_metric).collect();
+        metrics.sort_by(|a, b| a.series().cmp(b.series()));
+        metrics
+    }
+
+    #[test]
+    fn requires_two_operands() {
+        let config = config(vec![derived(Operation::Add, &["a"], None)]);
+        assert!(MetricArithmetic::new(&config).is_err());
+    }
+
+    #[test]
+    fn ratio_of_counters() {
+        let mut transform = MetricArithmetic::new(&config(vec![derived(
+            Operation::Divide,
+            &["errors", "requests"],
+            None,
+        )]))
+        .unwrap();
+
+        let output = evaluate(
+            &mut transform,
+            vec![
+                counter("errors", 1.0, tags(&[("host", "a")])),
+                counter("errors", 2.0, tags(&[("host", "a")])),
+                counter("requests", 12.0, tags(&[("host", "a")])),
+                counter("errors", 1.0, tags(&[("host", "b")])),
+            ],
+        );
+
+        // `host=b` has no `requests` operand, so no value can be derived for it.
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].name(), "derived");
+        assert_eq!(output[0].kind(), MetricKind::Absolute);
+        assert_eq!(output[0].value(), &MetricValue::Gauge { value: 0.25 });
+        assert_eq!(output[0].tags(), tags(&[("host", "a")]).as_ref());
+
+        // State is reset after each evaluation.
+        assert!(evaluate(&mut transform, vec![]).is_empty());
+    }
+
+    #[test]
+    fn group_by_template() {
+        let mut transform = MetricArithmetic::new(&config(vec![derived(
+            Operation::Subtract,
+            &["sent", "received"],
+            Some("{{ tags.host }}"),
+        )]))
+        .unwrap();
+
+        let output = evaluate(
+            &mut transform,
+            vec![
+                counter("sent", 10.0, tags(&[("host", "a"), ("dir", "out")])),
+                counter("received", 4.0, tags(&[("host", "a"), ("dir", "in")])),
+                counter("sent", 3.0, tags(&[("host", "b"), ("dir", "out")])),
+                counter("received", 3.0, tags(&[("host", "b"), ("dir", "in")])),
+            ],
+        );
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[0].value(), &MetricValue::Gauge { value: 6.0 });
+        assert_eq!(output[0].tags(), tags(&[("host", "a")]).as_ref());
+        assert_eq!(output[1].value(), &MetricValue::Gauge { value: 0.0 });
+        assert_eq!(output[1].tags(), tags(&[("host", "b")]).as_ref());
+    }
+
+    #[test]
+    fn division_by_zero_is_skipped() {
+        let mut transform = MetricArithmetic::new(&config(vec![derived(
+            Operation::Divide,
+            &["errors", "requests"],
+            None,
+        )]))
+        .unwrap();
+
+        let output = evaluate(
+            &mut transform,
+            vec![counter("errors", 1.0, None), counter("requests", 0.0, None)],
+        );
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn emits_derived_metrics() {
+        let mut transform_config = config(vec![derived(Operation::Add, &["a", "b"], None)]);
+        transform_config.passthrough = true;
+
+        assert_transform_compliance(async move {
+            let inputs = vec![counter("a", 1.0, None), counter("b", 2.0, None)];
+            let (topology, mut out) = create_topology(stream::iter(inputs), transform_config).await;
+
+            let mut names = Vec::new();
+            for _ in 0..3 {
+                let event = out.recv().await.unwrap();
+                let metric = event.as_metric();
+                if metric.name() == "derived" {
+                    assert_eq!(metric.value(), &MetricValue::Gauge { value: 3.0 });
+                }
+                names.push(metric.name().to_string());
+            }
+            names.sort();
+            assert_eq!(names, vec!["a", "b", "derived"]);
+
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
@@ -340,13 +340,18 @@ mod tests {
     use tokio_stream::wrappers::ReceiverStream;
     use vector_core::metric_tags;
 
+    use std::num::NonZeroU32;
+
     use super::*;
     use crate::event::{
         metric::{MetricKind, MetricTags, MetricValue, StatisticKind, TagValue, TagValueSet},
         Metric, Value,
     };
     use crate::test_util::{components::assert_transform_compliance, random_string};
-    use crate::transforms::test::create_topology;
+    use crate::transforms::{
+        log_to_metric::{LogToMetric, LogToMetricConfig},
+        test::{create_topology, transform_one},
+    };
 
     #[test]
     fn generate_config() {
@@ -612,6 +617,79 @@ mod tests {
         assert_eq!(log.metadata(), &metadata);
     }
 
+    #[test]
+    fn round_trip_through_log_to_metric() {
+        let metrics = vec![
+            Metric::new(
+                "counter",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_namespace(Some("app"))
+            .with_tags(Some(metric_tags! {
+                "host" => "localhost",
+                "multi" => "a",
+                "multi" => "b",
+                "bare" => TagValue::Bare,
+            }))
+            .with_interval_ms(NonZeroU32::new(10_000)),
+            Metric::new(
+                "histo",
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vector_core::buckets![1.0 => 10, 2.0 => 20],
+                    count: 30,
+                    sum: 50.5,
+                },
+            ),
+            Metric::new(
+                "summary",
+                MetricKind::Absolute,
+                MetricValue::AggregatedSummary {
+                    quantiles: vector_core::quantiles![0.5 => 10.0, 0.9 => 20.0],
+                    count: 30,
+                    sum: 50.5,
+                },
+            ),
+            Metric::new(
+                "distribution",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![1.5 => 10, 2.5 => 20],
+                    statistic: StatisticKind::Summary,
+                },
+            ),
+            Metric::new(
+                "set",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["one".into(), "two".into()].into_iter().collect(),
+                },
+            ),
+        ];
+
+        let mut log_to_metric = LogToMetric::new(LogToMetricConfig {
+            metrics: vec![],
+            all_metrics: true,
+        });
+
+        for log_namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let metric_to_log = MetricToLog::new(
+                Some("host"),
+                Default::default(),
+                log_namespace,
+                MetricTagValues::Full,
+            );
+
+            for metric in &metrics {
+                let metric = metric.clone().with_timestamp(Some(ts()));
+                let log = metric_to_log.transform_one(metric.clone()).unwrap();
+                let output = transform_one(&mut log_to_metric, log.into()).unwrap();
+                assert_eq!(output.into_metric(), metric);
+            }
+        }
+    }
+
     // Test the encoding of tag values with the `metric_tag_values` flag.
     proptest! {
         #[test]
@@ -0,0 +1,507 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use ordered_float::NotNan;
+use pyo3::{
+    types::{PyBool, PyBytes, PyDict, PyFloat, PyList, PyLong, PyString, PyTuple},
+    PyAny, PyErr, PyObject, PyResult, Python as Interpreter, ToPyObject,
+};
+use snafu::Snafu;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::PythonScriptError,
+    schema::{self, Definition},
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The builtins available to the program, which exclude the ones giving access to the file system,
+/// to the interpreter, or to arbitrary code evaluation.
+const ALLOWED_BUILTINS: &[&str] = &[
+    "abs",
+    "all",
+    "any",
+    "bool",
+    "bytes",
+    "dict",
+    "divmod",
+    "enumerate",
+    "filter",
+    "float",
+    "format",
+    "frozenset",
+    "int",
+    "isinstance",
+    "len",
+    "list",
+    "map",
+    "max",
+    "min",
+    "next",
+    "range",
+    "repr",
+    "reversed",
+    "round",
+    "set",
+    "slice",
+    "sorted",
+    "str",
+    "sum",
+    "tuple",
+    "zip",
+    "ArithmeticError",
+    "AttributeError",
+    "Exception",
+    "ImportError",
+    "IndexError",
+    "KeyError",
+    "LookupError",
+    "StopIteration",
+    "TypeError",
+    "ValueError",
+    "ZeroDivisionError",
+];
+
+/// Builds an `__import__` function only importing the allowed modules.
+const RESTRICTED_IMPORT: &str = r#"
+def restricted_import(allowed, real_import):
+    def __import__(name, globals=None, locals=None, fromlist=(), level=0):
+        if level != 0 or name.split(".")[0] not in allowed:
+            raise ImportError(f"import of module '{name}' is not allowed")
+        return real_import(name, globals, locals, fromlist, level)
+    return __import__
+"#;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Cannot set up the restricted environment: {}", message))]
+    Environment { message: String },
+    #[snafu(display("Cannot evaluate Python code in \"source\": {}", message))]
+    InvalidSource { message: String },
+    #[snafu(display("The function {:?} isn't defined by \"source\"", function))]
+    MissingFunction { function: String },
+}
+
+/// Configuration for the `python` transform.
+#[configurable_component(transform(
+    "python",
+    "Modify event data using the Python programming language."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PythonConfig {
+    /// The Python program defining the function called for each event.
+    ///
+    /// The program runs in a restricted environment: only a subset of the builtins is available,
+    /// and only the modules listed in `allowed_modules` can be imported. This restriction guards
+    /// against mistakes, and isn't a security boundary for untrusted code.
+    #[configurable(metadata(
+        docs::examples = "def process(event):\n\tevent[\"processed\"] = True\n\treturn event"
+    ))]
+    source: String,
+
+    /// The name of the function called for each event.
+    ///
+    /// The function takes the fields of a log event as a dictionary. It returns the fields of the
+    /// event to emit as a dictionary, a list of dictionaries to emit several events, or `None` to
+    /// drop the event.
+    #[serde(default = "default_function")]
+    function: String,
+
+    /// The modules the program is allowed to import.
+    #[serde(default = "default_allowed_modules")]
+    #[configurable(metadata(docs::examples = "re", docs::examples = "urllib.parse"))]
+    allowed_modules: Vec<String>,
+}
+
+fn default_function() -> String {
+    "process".to_string()
+}
+
+fn default_allowed_modules() -> Vec<String> {
+    ["base64", "datetime", "json", "math", "re"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+impl GenerateConfig for PythonConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"source = """
+def process(event):
+    return event
+""""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "python")]
+impl TransformConfig for PythonConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Python::new(self)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // Python causes the type definition to be reset
+        let namespaces = input_definitions
+            .iter()
+            .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+            .collect();
+
+        let definition = input_definitions
+            .iter()
+            .map(|(output, _definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(&namespaces),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::Log, definition)]
+    }
+}
+
+#[derive(Clone)]
+pub struct Python {
+    function: PyObject,
+    datetime: PyObject,
+    utc: PyObject,
+}
+
+impl Python {
+    fn new(config: &PythonConfig) -> crate::Result<Self> {
+        Interpreter::with_gil(|py| -> Result<Self, BuildError> {
+            let environment = |error: PyErr| BuildError::Environment {
+                message: error.to_string(),
+            };
+
+            let globals = restricted_globals(py, &config.allowed_modules).map_err(environment)?;
+            py.run(&config.source, Some(globals), None)
+                .map_err(|error| BuildError::InvalidSource {
+                    message: format_error(py, &error),
+                })?;
+            let function = globals
+                .get_item(&config.function)
+                .filter(|function| function.is_callable())
+                .ok_or_else(|| BuildError::MissingFunction {
+                    function: config.function.clone(),
+                })?;
+
+            let datetime = py.import("datetime").map_err(environment)?;
+            Ok(Self {
+                function: function.into(),
+                datetime: datetime.getattr("datetime").map_err(environment)?.into(),
+                utc: datetime
+                    .getattr("timezone")
+                    .and_then(|timezone| timezone.getattr("utc"))
+                    .map_err(environment)?
+                    .into(),
+            })
+        })
+        .map_err(Into::into)
+    }
+
+    fn process(&self, py: Interpreter<'_>, log: LogEvent) -> PyResult<Vec<LogEvent>> {
+        let (value, metadata) = log.into_parts();
+        let argument = self.value_to_python(py, &value)?;
+        let result = self.function.as_ref(py).call1((argument,))?;
+
+        let values = if result.is_none() {
+            Vec::new()
+        } else if let Ok(list) = result.downcast::<PyList>() {
+            list.iter()
+                .map(|item| self.python_to_value(item))
+                .collect::<PyResult<_>>()?
+        } else {
+            vec![self.python_to_value(result)?]
+        };
+
+        values
+            .into_iter()
+            .map(|value| match value {
+                value @ Value::Object(_) => Ok(LogEvent::from_parts(value, metadata.clone())),
+                _ => Err(type_error(
+                    "the function should return dictionaries".to_string(),
+                )),
+            })
+            .collect()
+    }
+
+    fn value_to_python(&self, py: Interpreter<'_>, value: &Value) -> PyResult<PyObject> {
+        Ok(match value {
+            Value::Bytes(bytes) => String::from_utf8_lossy(bytes).to_object(py),
+            Value::Regex(regex) => regex.as_str().to_object(py),
+            Value::Integer(integer) => integer.to_object(py),
+            Value::Float(float) => float.into_inner().to_object(py),
+            Value::Boolean(boolean) => boolean.to_object(py),
+            Value::Timestamp(timestamp) => {
+                // `fromisoformat` only parses up to microseconds.
+                let timestamp = timestamp.to_rfc3339_opts(SecondsFormat::Micros, false);
+                self.datetime
+                    .call_method1(py, "fromisoformat", (timestamp,))?
+            }
+            Value::Object(object) => {
+                let dict = PyDict::new(py);
+                for (key, value) in object {
+                    dict.set_item(key, self.value_to_python(py, value)?)?;
+                }
+                dict.to_object(py)
+            }
+            Value::Array(array) => {
+                let items = array
+                    .iter()
+                    .map(|value| self.value_to_python(py, value))
+                    .collect::<PyResult<Vec<_>>>()?;
+                PyList::new(py, items).to_object(py)
+            }
+            Value::Null => py.None(),
+        })
+    }
+
+    fn python_to_value(&self, object: &PyAny) -> PyResult<Value> {
+        let py = object.py();
+        if object.is_none() {
+            Ok(Value::Null)
+        } else if let Ok(boolean) = object.downcast::<PyBool>() {
+            // Booleans are integers in Python, so they're checked first.
+            Ok(Value::Boolean(boolean.is_true()))
+        } else if object.downcast::<PyLong>().is_ok() {
+            Ok(Value::Integer(object.extract()?))
+        } else if object.downcast::<PyFloat>().is_ok() {
+            NotNan::new(object.extract::<f64>()?)
+                .map(Value::Float)
+                .map_err(|_| type_error("NaN can't be stored in an event".to_string()))
+        } else if let Ok(string) = object.downcast::<PyString>() {
+            Ok(Value::from(string.to_str()?))
+        } else if let Ok(bytes) = object.downcast::<PyBytes>() {
+            Ok(Value::Bytes(bytes.as_bytes().to_vec().into()))
+        } else if let Ok(dict) = object.downcast::<PyDict>() {
+            dict.iter()
+                .map(|(key, value)| {
+                    let key = key
+                        .downcast::<PyString>()
+                        .map_err(|_| type_error("dictionary keys should be strings".to_string()))?;
+                    Ok((key.to_str()?.to_owned(), self.python_to_value(value)?))
+                })
+                .collect::<PyResult<BTreeMap<_, _>>>()
+                .map(Value::Object)
+        } else if object.downcast::<PyList>().is_ok() || object.downcast::<PyTuple>().is_ok() {
+            object
+                .iter()?
+                .map(|item| self.python_to_value(item?))
+                .collect::<PyResult<Vec<_>>>()
+                .map(Value::Array)
+        } else if object.is_instance(self.datetime.as_ref(py))? {
+            // Naive datetimes are assumed to be in UTC.
+            let object = if object.getattr("tzinfo")?.is_none() {
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("tzinfo", &self.utc)?;
+                object.call_method("replace", (), Some(kwargs))?
+            } else {
+                object
+            };
+            let timestamp: String = object.call_method0("isoformat")?.extract()?;
+            DateTime::parse_from_rfc3339(&timestamp)
+                .map(|timestamp| Value::Timestamp(timestamp.with_timezone(&Utc)))
+                .map_err(|error| type_error(format!("invalid datetime {timestamp:?}: {error}")))
+        } else {
+            Err(type_error(format!(
+                "values of type {} can't be stored in an event",
+                object.get_type().name()?
+            )))
+        }
+    }
+}
+
+impl FunctionTransform for Python {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        Interpreter::with_gil(|py| match self.process(py, event.into_log()) {
+            Ok(logs) => logs.into_iter().for_each(|log| output.push(log.into())),
+            Err(error) => emit!(PythonScriptError {
+                error: format_error(py, &error),
+            }),
+        });
+    }
+}
+
+/// Creates the globals of the program, whose builtins are restricted.
+fn restricted_globals<'py>(
+    py: Interpreter<'py>,
+    allowed_modules: &[String],
+) -> PyResult<&'py PyDict> {
+    let builtins = py.import("builtins")?;
+    let restricted = PyDict::new(py);
+    for name in ALLOWED_BUILTINS {
+        restricted.set_item(name, builtins.getattr(name)?)?;
+    }
+
+    let helpers = PyDict::new(py);
+    py.run(RESTRICTED_IMPORT, Some(helpers), None)?;
+    let import = helpers
+        .get_item("restricted_import")
+        .expect("helper is defined")
+        .call1((
+            allowed_modules.to_object(py),
+            builtins.getattr("__import__")?,
+        ))?;
+    restricted.set_item("__import__", import)?;
+
+    let globals = PyDict::new(py);
+    globals.set_item("__builtins__", restricted)?;
+    Ok(globals)
+}
+
+fn type_error(message: String) -> PyErr {
+    pyo3::exceptions::PyTypeError::new_err(message)
+}
+
+/// Formats the error along with its traceback, if any.
+fn format_error(py: Interpreter<'_>, error: &PyErr) -> String {
+    match error
+        .traceback(py)
+        .and_then(|traceback| traceback.format().ok())
+    {
+        Some(traceback) => format!("{error}\n{traceback}"),
+        None => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    fn config(source: &str) -> PythonConfig {
+        toml::from_str(&format!("source = '''{source}'''")).unwrap()
+    }
+
+    fn transform(source: &str) -> Python {
+        Python::new(&config(source)).unwrap()
+    }
+
+    fn process(python: &Python, log: LogEvent) -> Vec<LogEvent> {
+        Interpreter::with_gil(|py| python.process(py, log)).unwrap()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PythonConfig>();
+    }
+
+    #[test]
+    fn modifies_fields() {
+        let mut python = transform(
+            r#"
+import re
+
+def process(event):
+    name = re.search(r"my name is (\w+)", event.pop("message")).group(1)
+    event["user"] = {"name": name, "tags": ["a", 1, 2.5, True, None]}
+    return event
+"#,
+        );
+
+        let log =
+            transform_one(&mut python, LogEvent::from("Hello, my name is Bob.").into()).unwrap();
+        let log = log.as_log();
+
+        assert_eq!(log.get("message"), None);
+        assert_eq!(log["user.name"], "Bob".into());
+        assert_eq!(
+            log["user.tags"],
+            Value::Array(vec![
+                "a".into(),
+                Value::Integer(1),
+                Value::Float(NotNan::new(2.5).unwrap()),
+                Value::Boolean(true),
+                Value::Null
+            ])
+        );
+    }
+
+    #[test]
+    fn drops_and_splits_events() {
+        let python = transform(
+            r#"
+def process(event):
+    if event["message"] == "drop":
+        return None
+    return [{"message": part} for part in event["message"].split(",")]
+"#,
+        );
+
+        assert!(process(&python, LogEvent::from("drop")).is_empty());
+
+        let messages = process(&python, LogEvent::from("a,b"))
+            .into_iter()
+            .map(|log| log["message"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["a".into(), "b".into()]);
+    }
+
+    #[test]
+    fn converts_timestamps() {
+        let python = transform(
+            r#"
+import datetime
+
+def process(event):
+    event["next_day"] = event["timestamp"] + datetime.timedelta(days=1)
+    return event
+"#,
+        );
+
+        let timestamp = Utc::now();
+        let mut log = LogEvent::default();
+        log.insert("timestamp", timestamp);
+
+        let logs = process(&python, log);
+
+        let expected = DateTime::parse_from_rfc3339(
+            &(timestamp + chrono::Duration::days(1)).to_rfc3339_opts(SecondsFormat::Micros, true),
+        )
+        .unwrap()
+        .with_timezone(&Utc);
+        assert_eq!(logs[0]["next_day"], Value::Timestamp(expected));
+    }
+
+    #[test]
+    fn restricts_environment() {
+        let config = config("import os\ndef process(event): return event");
+        assert!(Python::new(&config)
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("import of module 'os' is not allowed"));
+
+        let python = transform("def process(event): return open('/etc/passwd')");
+        assert!(Interpreter::with_gil(|py| python.process(py, LogEvent::default())).is_err());
+    }
+
+    #[test]
+    fn missing_function() {
+        let config = config("def other(event): return event");
+        assert_eq!(
+            Python::new(&config).err().unwrap().to_string(),
+            r#"The function "process" isn't defined by "source""#
+        );
+    }
+}
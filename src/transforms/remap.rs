@@ -165,6 +165,7 @@ impl RemapConfig {
         let mut functions = vrl_stdlib::all();
         functions.append(&mut enrichment::vrl_functions());
         functions.append(&mut vector_vrl_functions::all());
+        vector_core::fips::retain_vrl_functions(&mut functions);
 
         let state = TypeState {
             local: Default::default(),
@@ -0,0 +1,568 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    num::NonZeroU64,
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream, StreamExt};
+use tokio::time::{interval_at, Instant};
+use value::{kind::Collection, Kind, Value};
+use vector_common::TimeZone;
+use vector_config::configurable_component;
+use vector_core::{compile_vrl, config::LogNamespace};
+use vrl::{
+    diagnostic::Formatter,
+    state::{ExternalEnv, TypeState},
+    CompileConfig, Program, Runtime,
+};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, EventMetadata, LogEvent, TargetEvents, VrlTarget},
+    internal_events::{TemplateRenderingError, WindowProgramError},
+    schema,
+    template::Template,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `window` transform.
+#[configurable_component(transform(
+    "window",
+    "Buffer events into tumbling or sliding windows, and aggregate them with a VRL program."
+))]
+#[derive(Clone, Debug)]
+pub struct WindowConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub mode: WindowMode,
+
+    /// The key of the windows of an event.
+    ///
+    /// Events with different keys are buffered into separate windows. When not set, all events
+    /// are buffered into the same windows.
+    #[configurable(metadata(docs::examples = "{{ user_id }}"))]
+    pub group_by: Option<Template>,
+
+    /// The [Vector Remap Language][vrl] (VRL) program to run over the events of each window, when
+    /// it closes.
+    ///
+    /// The program runs on an event holding the events of the window in its `events` field, in the
+    /// order they were received, the key of the window in its `key` field, and the times the
+    /// window started and ended at in its `start` and `end` fields. The resulting event is emitted,
+    /// or each of the resulting events if the program results in an array, which can be empty.
+    ///
+    /// Windows without any event are skipped.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = ". = { \"user_id\": .key, \"events\": length(.events) }",
+        docs::syntax_override = "remap_program"
+    ))]
+    pub source: String,
+}
+
+/// The windows events are buffered into.
+///
+/// Windows are aligned on the time the transform started at, and events are assigned to them by
+/// the time they are received at.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The windows events are buffered into."))]
+pub enum WindowMode {
+    /// Consecutive windows, which don't overlap.
+    ///
+    /// Each event is part of a single window.
+    Tumbling {
+        /// The duration of the windows, in milliseconds.
+        duration_ms: NonZeroU64,
+    },
+
+    /// Windows starting at a regular interval.
+    ///
+    /// When the interval is shorter than the duration of the windows, they overlap and each event
+    /// is part of several windows. When it's longer, the events received between two windows
+    /// aren't part of any.
+    Sliding {
+        /// The duration of the windows, in milliseconds.
+        duration_ms: NonZeroU64,
+
+        /// The interval between the start of consecutive windows, in milliseconds.
+        step_ms: NonZeroU64,
+    },
+}
+
+impl WindowMode {
+    fn duration(&self) -> Duration {
+        match self {
+            Self::Tumbling { duration_ms } | Self::Sliding { duration_ms, .. } => {
+                Duration::from_millis(duration_ms.get())
+            }
+        }
+    }
+
+    fn step(&self) -> Duration {
+        match self {
+            Self::Tumbling { duration_ms } => Duration::from_millis(duration_ms.get()),
+            Self::Sliding { step_ms, .. } => Duration::from_millis(step_ms.get()),
+        }
+    }
+}
+
+impl GenerateConfig for WindowConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"mode = "tumbling"
+            duration_ms = 60000
+            group_by = "{{ user_id }}"
+            source = '. = { "user_id": .key, "events": length(.events) }'"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "window")]
+impl TransformConfig for WindowConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Window::new(self, &context.enrichment_tables, context.globals.timezone())
+            .map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, input)| {
+                (
+                    output.clone(),
+                    schema::Definition::new_with_default_metadata(
+                        Kind::any_object(),
+                        input.log_namespaces().clone(),
+                    ),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::Log, definitions)]
+    }
+}
+
+/// The events of the open windows of a key.
+#[derive(Debug, Default)]
+struct Group {
+    /// The events with the time they were received at, relative to the start of the transform.
+    events: VecDeque<(Duration, Value)>,
+    /// The metadata of the events received since the last window of the key closed.
+    metadata: EventMetadata,
+}
+
+pub struct Window {
+    program: Program,
+    timezone: TimeZone,
+    group_by: Option<Template>,
+    duration: Duration,
+    step: Duration,
+    groups: HashMap<Option<String>, Group>,
+    /// The index of the next window to close, the first window starting with the transform.
+    next_window: u32,
+    started: Instant,
+    started_at: DateTime<Utc>,
+}
+
+impl Window {
+    pub fn new(
+        config: &WindowConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+        timezone: TimeZone,
+    ) -> crate::Result<Self> {
+        let mut functions = vrl_stdlib::all();
+        functions.append(&mut enrichment::vrl_functions());
+        functions.append(&mut vector_vrl_functions::all());
+        vector_core::fips::retain_vrl_functions(&mut functions);
+
+        let state = TypeState {
+            local: Default::default(),
+            external: ExternalEnv::new_with_kind(
+                Kind::object(BTreeMap::from([
+                    ("events".into(), Kind::array(Collection::any())),
+                    ("key".into(), Kind::bytes().or_null()),
+                    ("start".into(), Kind::timestamp()),
+                    ("end".into(), Kind::timestamp()),
+                ])),
+                Kind::object(Collection::any()),
+            ),
+        };
+        let mut compile_config = CompileConfig::default();
+        compile_config.set_custom(enrichment_tables.clone());
+
+        let program = compile_vrl(&config.source, &functions, &state, compile_config)
+            .map_err(|diagnostics| {
+                Formatter::new(&config.source, diagnostics)
+                    .colored()
+                    .to_string()
+            })?
+            .program;
+
+        Ok(Self {
+            program,
+            timezone,
+            group_by: config.group_by.clone(),
+            duration: config.mode.duration(),
+            step: config.mode.step(),
+            groups: HashMap::new(),
+            next_window: 0,
+            started: Instant::now(),
+            started_at: Utc::now(),
+        })
+    }
+
+    /// Buffers an event received at the given time.
+    fn add(&mut self, event: Event, received: Instant) {
+        let log = event.into_log();
+        let key = match &self.group_by {
+            None => None,
+            Some(template) => match template.render_string(&log) {
+                Ok(key) => Some(key),
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("group_by"),
+                        drop_event: true,
+                    });
+                    return;
+                }
+            },
+        };
+
+        let (value, metadata) = log.into_parts();
+        let group = self.groups.entry(key).or_default();
+        group
+            .events
+            .push_back((received.saturating_duration_since(self.started), value));
+        group.metadata.merge(metadata);
+    }
+
+    /// Closes the next window of every key.
+    fn close_next(&mut self, output: &mut Vec<Event>) {
+        let start = self.step * self.next_window;
+        let end = start + self.duration;
+        self.next_window += 1;
+        let next_start = self.step * self.next_window;
+
+        let window = WindowBounds {
+            start: self.started_at + chrono::Duration::from_std(start).unwrap_or_default(),
+            end: self.started_at + chrono::Duration::from_std(end).unwrap_or_default(),
+        };
+        let program = &self.program;
+        let timezone = &self.timezone;
+        self.groups.retain(|key, group| {
+            let events: Vec<Value> = group
+                .events
+                .iter()
+                .filter(|(received, _)| (start..end).contains(received))
+                .map(|(_, event)| event.clone())
+                .collect();
+
+            // The events received before the next window starts aren't part of any other window.
+            while group
+                .events
+                .front()
+                .map_or(false, |(received, _)| *received < next_start)
+            {
+                group.events.pop_front();
+            }
+
+            if !events.is_empty() {
+                let metadata = std::mem::take(&mut group.metadata);
+                run(program, timezone, key, events, &window, metadata, output);
+            }
+            !group.events.is_empty()
+        });
+    }
+
+    /// Closes the windows holding the events left.
+    fn close_all(&mut self, output: &mut Vec<Event>) {
+        while !self.groups.is_empty() {
+            self.close_next(output);
+        }
+    }
+}
+
+struct WindowBounds {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+/// Runs the program over the events of a window.
+fn run(
+    program: &Program,
+    timezone: &TimeZone,
+    key: &Option<String>,
+    events: Vec<Value>,
+    window: &WindowBounds,
+    metadata: EventMetadata,
+    output: &mut Vec<Event>,
+) {
+    let count = events.len();
+    let log = LogEvent::from_map(
+        BTreeMap::from([
+            ("events".to_owned(), Value::Array(events)),
+            (
+                "key".to_owned(),
+                key.as_ref().map_or(Value::Null, |key| key.as_str().into()),
+            ),
+            ("start".to_owned(), Value::Timestamp(window.start)),
+            ("end".to_owned(), Value::Timestamp(window.end)),
+        ]),
+        metadata,
+    );
+
+    let mut target = VrlTarget::new(Event::Log(log), program.info(), false);
+    match Runtime::default().resolve(&mut target, program, timezone) {
+        Ok(_) => match target.into_events() {
+            TargetEvents::One(event) => output.push(event),
+            TargetEvents::Logs(events) => output.extend(events),
+            TargetEvents::Traces(events) => output.extend(events),
+        },
+        Err(error) => emit!(WindowProgramError {
+            error: error.to_string(),
+            count,
+        }),
+    }
+}
+
+impl TaskTransform<Event> for Window {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+        me.started = Instant::now();
+        me.started_at = Utc::now();
+
+        // The windows close one after the other, at the interval they start at.
+        let mut closes = interval_at(me.started + me.duration, me.step);
+
+        Box::pin(
+            stream! {
+                loop {
+                    let mut output = Vec::new();
+                    let done = tokio::select! {
+                        _ = closes.tick() => {
+                            me.close_next(&mut output);
+                            false
+                        }
+                        maybe_event = input_rx.next() => {
+                            match maybe_event {
+                                None => {
+                                    me.close_all(&mut output);
+                                    true
+                                }
+                                Some(event) => {
+                                    me.add(event, Instant::now());
+                                    false
+                                }
+                            }
+                        }
+                    };
+                    yield stream::iter(output.into_iter());
+                    if done { break }
+                }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    const COUNT_EVENTS: &str = r#". = { "user": .key, "count": length(.events) }"#;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowConfig>();
+    }
+
+    fn window(mode: WindowMode, source: &str) -> Window {
+        let config = WindowConfig {
+            mode,
+            group_by: Some(Template::try_from("{{ user }}").unwrap()),
+            source: source.to_owned(),
+        };
+        Window::new(&config, &Default::default(), TimeZone::default()).unwrap()
+    }
+
+    fn millis(millis: u64) -> NonZeroU64 {
+        NonZeroU64::new(millis).unwrap()
+    }
+
+    fn add(window: &mut Window, user: &str, received_ms: u64) {
+        let mut log = LogEvent::from("message");
+        log.insert("user", user);
+        let received = window.started + Duration::from_millis(received_ms);
+        window.add(log.into(), received);
+    }
+
+    fn close_next(window: &mut Window) -> Vec<(Value, Value)> {
+        let mut output = Vec::new();
+        window.close_next(&mut output);
+        let mut counts: Vec<_> = output
+            .into_iter()
+            .map(|event| {
+                let log = event.into_log();
+                (log["user"].clone(), log["count"].clone())
+            })
+            .collect();
+        counts.sort_by_key(|(user, _)| user.to_string_lossy().into_owned());
+        counts
+    }
+
+    #[test]
+    fn tumbling_windows() {
+        let mut window = window(
+            WindowMode::Tumbling {
+                duration_ms: millis(1000),
+            },
+            COUNT_EVENTS,
+        );
+        add(&mut window, "a", 0);
+        add(&mut window, "a", 500);
+        add(&mut window, "b", 700);
+        add(&mut window, "a", 1200);
+
+        assert_eq!(
+            close_next(&mut window),
+            vec![("a".into(), 2.into()), ("b".into(), 1.into())]
+        );
+        assert_eq!(close_next(&mut window), vec![("a".into(), 1.into())]);
+        assert!(window.groups.is_empty());
+    }
+
+    #[test]
+    fn sliding_windows() {
+        let mut window = window(
+            WindowMode::Sliding {
+                duration_ms: millis(1000),
+                step_ms: millis(500),
+            },
+            COUNT_EVENTS,
+        );
+        add(&mut window, "a", 100);
+        add(&mut window, "a", 600);
+        add(&mut window, "a", 1100);
+
+        assert_eq!(close_next(&mut window), vec![("a".into(), 2.into())]);
+        assert_eq!(close_next(&mut window), vec![("a".into(), 2.into())]);
+        assert_eq!(close_next(&mut window), vec![("a".into(), 1.into())]);
+        assert!(window.groups.is_empty());
+    }
+
+    #[test]
+    fn emits_arrays_of_events() {
+        let mut window = window(
+            WindowMode::Tumbling {
+                duration_ms: millis(1000),
+            },
+            r#". = map_values(.events) -> |event| { event.window_end = .end; event }"#,
+        );
+        add(&mut window, "a", 0);
+        add(&mut window, "a", 10);
+
+        let mut output = Vec::new();
+        window.close_all(&mut output);
+        assert_eq!(output.len(), 2);
+        assert!(output[0].as_log()["window_end"].is_timestamp());
+    }
+
+    #[test]
+    fn drops_windows_on_errors() {
+        let mut window = window(
+            WindowMode::Tumbling {
+                duration_ms: millis(1000),
+            },
+            r#". = { "count": to_int!(.key) }"#,
+        );
+        add(&mut window, "a", 0);
+
+        let mut output = Vec::new();
+        window.close_all(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn closes_windows_on_shutdown() {
+        assert_transform_compliance(async {
+            let config = WindowConfig {
+                mode: WindowMode::Tumbling {
+                    duration_ms: millis(60_000),
+                },
+                group_by: None,
+                source: COUNT_EVENTS.to_owned(),
+            };
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            for _ in 0..3 {
+                tx.send(LogEvent::from("message").into()).await.unwrap();
+            }
+            drop(tx);
+            topology.stop().await;
+
+            let event = out.recv().await.unwrap();
+            assert_eq!(event.as_log()["count"], 3.into());
+            assert_eq!(event.as_log()["user"], Value::Null);
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[test]
+    fn finalizes_events_with_windows() {
+        let mut window = window(
+            WindowMode::Tumbling {
+                duration_ms: millis(1000),
+            },
+            COUNT_EVENTS,
+        );
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut log = LogEvent::from("message").with_batch_notifier(&batch);
+        drop(batch);
+        log.insert("user", "a");
+        let started = window.started;
+        window.add(log.into(), started);
+
+        let mut output = Vec::new();
+        window.close_all(&mut output);
+        drop(output);
+        assert_eq!(
+            futures::executor::block_on(receiver),
+            BatchStatus::Delivered
+        );
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(missing_docs)]
-use std::{ffi::OsString, time::Duration};
+use std::{ffi::OsString, io, time::Duration};
 
 use windows_service::{
     define_windows_service,
@@ -11,7 +11,7 @@ use windows_service::{
     service_dispatcher, Result,
 };
 
-use crate::{app::Application, signal::SignalTo};
+use crate::{app::Application, cli::Opts, signal::SignalTo};
 
 const SERVICE_NAME: &str = "vector";
 const SERVICE_TYPE: ServiceType = ServiceType::OWN_PROCESS;
@@ -24,7 +24,8 @@ pub mod service_control {
     use snafu::ResultExt;
     use windows_service::{
         service::{
-            ServiceAccess, ServiceErrorControl, ServiceExitCode, ServiceInfo, ServiceStartType,
+            ServiceAccess, ServiceAction, ServiceActionType, ServiceErrorControl, ServiceExitCode,
+            ServiceFailureActions, ServiceFailureResetPeriod, ServiceInfo, ServiceStartType,
             ServiceState, ServiceStatus,
         },
         service_manager::{ServiceManager, ServiceManagerAccess},
@@ -98,6 +99,20 @@ pub mod service_control {
         }
     }
 
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum StartType {
+        Manual,
+        Automatic,
+        DelayedAutomatic,
+        Disabled,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum RecoveryAction {
+        None,
+        Restart,
+    }
+
     pub struct ServiceDefinition {
         pub name: OsString,
         pub display_name: OsString,
@@ -105,6 +120,12 @@ pub mod service_control {
 
         pub executable_path: std::path::PathBuf,
         pub launch_arguments: Vec<OsString>,
+
+        pub start_type: StartType,
+        /// The actions taken on the first, second, and subsequent failures of the service.
+        pub recovery_actions: Vec<RecoveryAction>,
+        pub recovery_delay: Duration,
+        pub recovery_reset_period: Duration,
     }
 
     impl std::str::FromStr for ControlAction {
@@ -212,11 +233,18 @@ pub mod service_control {
         let service_manager =
             ServiceManager::local_computer(None::<&str>, manager_access).context(ServiceSnafu)?;
 
+        let (start_type, delayed_auto_start) = match service_def.start_type {
+            StartType::Manual => (ServiceStartType::OnDemand, false),
+            StartType::Automatic => (ServiceStartType::AutoStart, false),
+            StartType::DelayedAutomatic => (ServiceStartType::AutoStart, true),
+            StartType::Disabled => (ServiceStartType::Disabled, false),
+        };
+
         let service_info = ServiceInfo {
             name: service_def.name.clone(),
             display_name: service_def.display_name.clone(),
             service_type: SERVICE_TYPE,
-            start_type: ServiceStartType::OnDemand,
+            start_type,
             error_control: ServiceErrorControl::Normal,
             executable_path: service_def.executable_path.clone(),
             launch_arguments: service_def.launch_arguments.clone(),
@@ -225,19 +253,64 @@ pub mod service_control {
             account_password: None,
         };
 
-        service_manager
-            .create_service(&service_info, ServiceAccess::empty())
+        // Configuring restarts on failure requires the access to start the service.
+        let service = service_manager
+            .create_service(
+                &service_info,
+                ServiceAccess::CHANGE_CONFIG | ServiceAccess::START,
+            )
             .context(ServiceSnafu)?;
 
+        service
+            .set_description(&service_def.description)
+            .context(ServiceSnafu)?;
+
+        if delayed_auto_start {
+            service.set_delayed_auto_start(true).context(ServiceSnafu)?;
+        }
+
+        if !service_def.recovery_actions.is_empty() {
+            set_recovery_actions(&service, service_def)?;
+        }
+
         emit!(WindowsServiceInstall {
             name: &*service_def.name.to_string_lossy(),
         });
 
-        // TODO: It is currently not possible to change the description of the service.
-        // Waiting for the following PR to get merged in
-        // https://github.com/mullvad/windows-service-rs/pull/32
-        //
-        // service.set_description(&self.description);
+        Ok(())
+    }
+
+    fn set_recovery_actions(
+        service: &windows_service::service::Service,
+        service_def: &ServiceDefinition,
+    ) -> crate::Result<()> {
+        let actions = service_def
+            .recovery_actions
+            .iter()
+            .map(|action| ServiceAction {
+                action_type: match action {
+                    RecoveryAction::None => ServiceActionType::None,
+                    RecoveryAction::Restart => ServiceActionType::Restart,
+                },
+                delay: service_def.recovery_delay,
+            })
+            .collect();
+
+        service
+            .update_failure_actions(ServiceFailureActions {
+                reset_period: ServiceFailureResetPeriod::After(service_def.recovery_reset_period),
+                reboot_msg: None,
+                command: None,
+                actions: Some(actions),
+            })
+            .context(ServiceSnafu)?;
+
+        // Vector reports failures to start with an exit code rather than by crashing, so the
+        // actions must also apply when the service stops with an error.
+        service
+            .set_failure_actions_on_non_crash_failures(true)
+            .context(ServiceSnafu)?;
+
         Ok(())
     }
 
@@ -358,15 +431,39 @@ pub mod service_control {
 define_windows_service!(ffi_service_main, win_main);
 
 fn win_main(arguments: Vec<OsString>) {
-    if let Err(_e) = run_service(arguments) {}
+    if let Err(error) = run_service(arguments) {
+        error!(message = "Vector service failed.", %error);
+    }
 }
 
 pub fn run() -> Result<()> {
     service_dispatcher::start(SERVICE_NAME, ffi_service_main)
 }
 
-fn run_service(_arguments: Vec<OsString>) -> Result<()> {
-    match Application::prepare_start() {
+fn run_service(arguments: Vec<OsString>) -> Result<()> {
+    let started_as = started_service_name(&arguments);
+    let opts = match Opts::get_matches() {
+        Ok(opts) => opts,
+        Err(error) => {
+            // The failure is reported to the system, which records its exit code in the event log
+            // and takes the recovery actions of the service.
+            error!(message = "Invalid command line arguments.", %error);
+            report_failure(
+                started_as.as_deref().unwrap_or(SERVICE_NAME),
+                exitcode::USAGE,
+            )?;
+            return Err(usage_error(&error));
+        }
+    };
+    let service_name = started_as
+        .or_else(|| opts.root.service_name.clone())
+        .unwrap_or_else(|| SERVICE_NAME.to_owned());
+    let stop_timeout = Duration::from_secs(opts.root.service_stop_timeout);
+
+    let prepared = Application::prepare_from_opts(opts)
+        .and_then(|(runtime, app)| app.start(&runtime).map(|app| (runtime, app)));
+
+    match prepared {
         Ok((runtime, app)) => {
             let signal_tx = app.signals.handler.clone_tx();
             let event_handler = move |control_event| -> ServiceControlHandlerResult {
@@ -375,8 +472,8 @@ fn run_service(_arguments: Vec<OsString>) -> Result<()> {
                     // control manager. Always return NoError even if not implemented.
                     ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
 
-                    // Handle stop
-                    ServiceControl::Stop => {
+                    // Handle stop, and the shutdown of the system
+                    ServiceControl::Stop | ServiceControl::Shutdown => {
                         while signal_tx.send(SignalTo::Shutdown).is_err() {}
                         ServiceControlHandlerResult::NoError
                     }
@@ -386,33 +483,127 @@ fn run_service(_arguments: Vec<OsString>) -> Result<()> {
             };
 
             let status_handle =
-                windows_service::service_control_handler::register(SERVICE_NAME, event_handler)?;
+                windows_service::service_control_handler::register(&service_name, event_handler)?;
 
             status_handle.set_service_status(ServiceStatus {
                 service_type: SERVICE_TYPE,
                 current_state: ServiceState::Running,
-                controls_accepted: ServiceControlAccept::STOP,
+                controls_accepted: ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
                 exit_code: ServiceExitCode::Win32(NO_ERROR),
                 checkpoint: 0,
                 wait_hint: Duration::default(),
                 process_id: None,
             })?;
 
-            runtime.block_on(app.run());
+            runtime.block_on(async {
+                let app = app.main().await;
+
+                // Tell the system how long the graceful shutdown may take.
+                status_handle.set_service_status(ServiceStatus {
+                    service_type: SERVICE_TYPE,
+                    current_state: ServiceState::StopPending,
+                    controls_accepted: ServiceControlAccept::empty(),
+                    exit_code: ServiceExitCode::Win32(NO_ERROR),
+                    checkpoint: 1,
+                    wait_hint: stop_timeout,
+                    process_id: None,
+                })?;
+
+                if tokio::time::timeout(stop_timeout, app.shutdown())
+                    .await
+                    .is_err()
+                {
+                    warn!(
+                        message = "Graceful shutdown timed out, stopping the service.",
+                        timeout_secs = stop_timeout.as_secs(),
+                    );
+                }
 
-            // Tell the system that service has stopped.
-            status_handle.set_service_status(ServiceStatus {
-                service_type: SERVICE_TYPE,
-                current_state: ServiceState::Stopped,
-                controls_accepted: ServiceControlAccept::empty(),
-                exit_code: ServiceExitCode::Win32(NO_ERROR),
-                checkpoint: 0,
-                wait_hint: Duration::default(),
-                process_id: None,
+                Result::Ok(())
             })?;
 
+            // Tell the system that service has stopped.
+            status_handle.set_service_status(stopped_status(ServiceExitCode::Win32(NO_ERROR)))?;
+
             Ok(())
         }
-        _ => Ok(()),
+        // Report the failure, so that the recovery actions of the service are taken.
+        Err(exit_code) => report_failure(&service_name, exit_code),
+    }
+}
+
+/// The name of the service started, which the system passes as the first argument of the service.
+fn started_service_name(arguments: &[OsString]) -> Option<String> {
+    arguments
+        .first()
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| !name.is_empty())
+}
+
+/// Reports to the system that the service stopped with the given exit code.
+fn report_failure(service_name: &str, exit_code: exitcode::ExitCode) -> Result<()> {
+    let status_handle = windows_service::service_control_handler::register(service_name, |_| {
+        ServiceControlHandlerResult::NotImplemented
+    })?;
+    status_handle.set_service_status(stopped_status(ServiceExitCode::ServiceSpecific(
+        exit_code as u32,
+    )))
+}
+
+fn stopped_status(exit_code: ServiceExitCode) -> ServiceStatus {
+    ServiceStatus {
+        service_type: SERVICE_TYPE,
+        current_state: ServiceState::Stopped,
+        controls_accepted: ServiceControlAccept::empty(),
+        exit_code,
+        checkpoint: 0,
+        wait_hint: Duration::default(),
+        process_id: None,
+    }
+}
+
+/// Converts the error parsing the command line arguments into the error the service fails with.
+fn usage_error(error: &clap::Error) -> windows_service::Error {
+    windows_service::Error::Winapi(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        error.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn reports_failures_as_stopped() {
+        let status = stopped_status(ServiceExitCode::ServiceSpecific(exitcode::CONFIG as u32));
+        assert_eq!(status.current_state, ServiceState::Stopped);
+        assert_eq!(status.controls_accepted, ServiceControlAccept::empty());
+        assert_eq!(
+            status.exit_code,
+            ServiceExitCode::ServiceSpecific(exitcode::CONFIG as u32)
+        );
+    }
+
+    #[test]
+    fn takes_service_name_from_arguments() {
+        let arguments = vec![OsString::from("vector-edge"), OsString::from("--verbose")];
+        assert_eq!(
+            started_service_name(&arguments).as_deref(),
+            Some("vector-edge")
+        );
+        assert_eq!(started_service_name(&[]), None);
+    }
+
+    #[test]
+    fn keeps_invalid_arguments_errors() {
+        let error = Opts::try_parse_from(["vector", "--no-such-option"]).unwrap_err();
+        let windows_service::Error::Winapi(io_error) = usage_error(&error) else {
+            panic!("Unexpected error kind");
+        };
+        assert_eq!(io_error.kind(), io::ErrorKind::InvalidInput);
+        assert!(io_error.to_string().contains("--no-such-option"));
     }
 }
@@ -85,6 +85,10 @@ cli: {
 	name: "vector"
 
 	flags: _default_flags & {
+		"fips": {
+			description: env_vars.VECTOR_FIPS.description
+			env_var:     "VECTOR_FIPS"
+		}
 		"quiet": {
 			_short: "q"
 			description: """
@@ -162,6 +166,16 @@ cli: {
 			type:        "integer"
 			env_var:     "VECTOR_THREADS"
 		}
+		"max-blocking-threads": {
+			description: env_vars.VECTOR_MAX_BLOCKING_THREADS.description
+			type:        "integer"
+			env_var:     "VECTOR_MAX_BLOCKING_THREADS"
+		}
+		"cpu-affinity": {
+			description: env_vars.VECTOR_CPU_AFFINITY.description
+			type:        "list"
+			env_var:     "VECTOR_CPU_AFFINITY"
+		}
 		"internal-log-rate-limit": {
 			_short:      "i"
 			description: env_vars.VECTOR_INTERNAL_LOG_RATE_LIMIT.description
@@ -171,20 +185,51 @@ cli: {
 		}
 	}
 
-	options: _core_options
+	options: _core_options & {
+		"audit-log-path": {
+			description: env_vars.VECTOR_AUDIT_LOG_PATH.description
+			type:        "string"
+			env_var:     "VECTOR_AUDIT_LOG_PATH"
+		}
+	}
 
 	commands: {
 		"graph": {
 			description: """
-				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
-				which can be rendered using [GraphViz](\(urls.graphviz)).
+				Generate a visual representation of topologies. By default, the output is in the
+				[DOT format](\(urls.dot_format)), which can be rendered using [GraphViz](\(urls.graphviz)).
+				[Mermaid](\(urls.mermaid)) and [D2](\(urls.d2)) diagrams can be output instead.
 
 				You can also visualize the output online at [webgraphviz.com](http://www.webgraphviz.com/).
+
+				When given the URL of the API of a running instance, the graph is annotated with live
+				metrics: edges are labeled with the rate of events sent through them, and erroring
+				components with their rate of errors.
 				"""
 
 			example: "vector graph --config /etc/vector/vector.toml | dot -Tsvg > graph.svg"
 
-			options: _core_options
+			options: _core_options & {
+				"format": {
+					description: "Format of the generated graph"
+					type:        "enum"
+					default:     "dot"
+					enum: {
+						dot:     "The DOT language, which can be rendered by GraphViz"
+						mermaid: "A Mermaid flowchart"
+						d2:      "The D2 language"
+					}
+				}
+				"metrics-interval": {
+					description: "Interval over which the live metrics are measured, in milliseconds"
+					type:        "integer"
+					default:     1000
+				}
+				"metrics-url": {
+					description: "The URL for the GraphQL endpoint of the running Vector instance to annotate the graph with live metrics from"
+					type:        "string"
+				}
+			}
 		}
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
@@ -289,6 +334,9 @@ cli: {
 					_short:      "n"
 					description: "Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops."
 				}
+				"compress": {
+					description: "Compress output files with gzip, appending `.gz` to their name. Requires `--output-file`."
+				}
 			}
 
 			options: {
@@ -328,6 +376,18 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"output-file": {
+					description: "Write events to this file instead of the screen, one per line. With the `json` format, this produces newline-delimited JSON."
+					type:        "string"
+				}
+				"rotate-size": {
+					description: """
+						Start a new output file once the current one holds this amount of data, such as `100MiB`,
+						before compression. Each new file is named after the output file with a sequence number
+						appended, such as `tap.ndjson.1`, `tap.ndjson.2`, and so on. Requires `--output-file`.
+						"""
+					type: "string"
+				}
 			}
 
 			args: {
@@ -503,6 +563,17 @@ cli: {
 				examples: ["/mnt/host/sys"]
 			}
 		}
+		VECTOR_AUDIT_LOG_PATH: {
+			description: """
+				Append an audit log of configuration and control operations to this file. Each line of
+				the file is a JSON record of a configuration load or reload, an API tap session, or a
+				retrieval of secrets.
+				"""
+			type: string: {
+				default: null
+				examples: ["/var/log/vector/audit.log"]
+			}
+		}
 		VECTOR_COLOR: {
 			description: "Control when ANSI terminal formatting is used."
 			type: string: {
@@ -555,6 +626,15 @@ cli: {
 				"""
 			type: string: default: null
 		}
+		VECTOR_FIPS: {
+			description: """
+				Run in FIPS mode. OpenSSL is switched to its FIPS mode, which requires Vector to be built
+				with the `fips` feature against BoringSSL built in FIPS mode, and components requesting
+				cryptographic algorithms which aren't FIPS-approved fail to build: TLS versions before 1.2,
+				cipher suites which aren't FIPS-approved, HTTP/3, and the VRL cryptographic functions.
+				"""
+			type: bool: default: false
+		}
 		VECTOR_LOG: {
 			description: "Vector's log level. Each log level includes messages from higher priority levels."
 			type: string: {
@@ -592,6 +672,27 @@ cli: {
 				unit:    null
 			}
 		}
+		VECTOR_MAX_BLOCKING_THREADS: {
+			description: """
+				The maximum number of threads to use for blocking operations, such as file system
+				operations. The default is 512.
+				"""
+			type: uint: {
+				default: null
+				unit:    null
+			}
+		}
+		VECTOR_CPU_AFFINITY: {
+			description: """
+				The IDs of the CPUs to pin the processing threads to, separated by commas. By default,
+				the processing threads can run on any CPU. Only supported on Linux. Components can be
+				given their own processing threads with their `runtime` option.
+				"""
+			type: string: {
+				default: null
+				examples: ["0,1,2,3"]
+			}
+		}
 		VECTOR_WATCH_CONFIG: {
 			description: "Watch for changes in the configuration file and reload accordingly"
 			type: bool: default: false
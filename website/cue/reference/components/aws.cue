@@ -223,7 +223,25 @@ components: _aws: {
 					body: """
 						Vector can assume an AWS IAM role via the [`auth.assume_role`](#auth.assume_role) option. This is an
 						optional setting that is helpful for a variety of use cases, such as cross
-						account access.
+						account access. Further roles can be assumed in sequence, each with the credentials
+						of the previous one, via the [`auth.chained_roles`](#auth.chained_roles) option.
+						"""
+				},
+				{
+					title: "SSO and external processes"
+					body: """
+						Vector can use an AWS IAM Identity Center (SSO) profile of the AWS config file via the
+						[`auth.sso_profile`](#auth.sso_profile) option, once its token has been cached by running
+						`aws sso login`. Credentials can also be fetched by an external command via the
+						[`auth.credential_process`](#auth.credential_process) option.
+						"""
+				},
+				{
+					title: "Refreshing credentials"
+					body: """
+						Temporary credentials are cached and refreshed shortly before they expire. How long before
+						can be set via the [`auth.refresh_buffer_secs`](#auth.refresh_buffer_secs) option, which
+						defaults to 10 seconds.
 						"""
 				},
 			]
@@ -1,60 +1,401 @@
 package metadata
 
-base: components: sources: configuration: proxy: {
-	description: """
-		Proxy configuration.
-
-		Configure to proxy traffic through an HTTP(S) proxy when making external requests.
-
-		Similar to common proxy configuration convention, users can set different proxies
-		to use based on the type of traffic being proxied, as well as set specific hosts that
-		should not be proxied.
-		"""
-	required: false
-	type: object: options: {
-		enabled: {
-			description: "Enables proxying support."
-			required:    false
-			type: bool: default: true
+base: components: sources: configuration: {
+	coordination: {
+		description: "Configuration of the lease held by the instance of Vector running a source."
+		required:    false
+		type: object: options: {
+			backend: {
+				description: "The service the lease is stored in."
+				required:    true
+				type: object: options: {
+					endpoint: {
+						description: """
+							The Redis URL to connect to.
+
+							The URL must take the form of `protocol://server:port/db` where the `protocol` can either be
+							`redis` or `rediss` for connections secured via TLS.
+							"""
+						relevant_when: "type = \"redis\""
+						required:      true
+						type: string: examples: ["redis://127.0.0.1:6379/0"]
+					}
+					kube_config_file: {
+						description: """
+							Optional path to a readable [kubeconfig][kubeconfig] file.
+
+							If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+							[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+							"""
+						relevant_when: "type = \"kubernetes_lease\""
+						required:      false
+						type: string: examples: ["/path/to/.kube/config"]
+					}
+					namespace: {
+						description: """
+							The namespace of the Lease object.
+
+							By default, the namespace of the kubeconfig, or the namespace Vector runs in, is used.
+							"""
+						relevant_when: "type = \"kubernetes_lease\""
+						required:      false
+						type: string: examples: ["vector"]
+					}
+					type: {
+						description: "The type of coordination backend."
+						required:    true
+						type: string: enum: {
+							kubernetes_lease: """
+								Store the lease in a Kubernetes [Lease][lease] object.
+
+								[lease]: https://kubernetes.io/docs/concepts/architecture/leases/
+								"""
+							redis: "Store the lease in a Redis key which expires when the lease isn't renewed."
+						}
+					}
+				}
+			}
+			identity: {
+				description: """
+					The identity of this instance in the group.
+
+					By default, the hostname followed by a random suffix is used.
+					"""
+				required: false
+				type: string: examples: ["vector-0"]
+			}
+			lease_duration_secs: {
+				description: """
+					How long the lease is held for without being renewed, in seconds.
+
+					When the instance holding the lease stops, another instance takes over after this delay.
+					"""
+				required: false
+				type: uint: {
+					default: 15
+					unit:    "seconds"
+				}
+			}
+			lease_name: {
+				description: """
+					The name of the lease.
+
+					The instances sharing a lease of the same name form a group, in which the source only
+					runs on one instance at a time.
+					"""
+				required: true
+				type: string: examples: ["vector-http-client"]
+			}
+			renew_interval_secs: {
+				description: """
+					How often the lease is renewed, or its acquisition retried, in seconds.
+
+					This must be less than `lease_duration_secs`.
+					"""
+				required: false
+				type: uint: {
+					default: 5
+					unit:    "seconds"
+				}
+			}
 		}
-		http: {
-			description: """
-				Proxy endpoint to use when proxying HTTP traffic.
-
-				Must be a valid URI string.
-				"""
-			required: false
-			type: string: examples: ["http://foo.bar:3128"]
+	}
+	enabled: {
+		description: """
+			Whether the component is enabled.
+
+			Disabled components are removed from the configuration when it is loaded, along with the
+			references to their outputs in the `inputs` of other components. This allows a single
+			configuration to be shared between environments which only differ by some of their components.
+			"""
+		required: false
+		type: {
+			bool: default: true
+			string: examples: ["'${ENV}' == 'prod'", "env('REGION') != 'eu' && hostname() != 'canary'"]
 		}
-		https: {
-			description: """
-				Proxy endpoint to use when proxying HTTPS traffic.
-
-				Must be a valid URI string.
-				"""
-			required: false
-			type: string: examples: ["http://foo.bar:3128"]
+	}
+	ha_mode: {
+		description: "How a source runs when several instances of Vector share its configuration."
+		required:    false
+		type: string: {
+			default: "active_active"
+			enum: {
+				active_active: "The source runs on every instance."
+				active_passive: """
+					The source only runs on the instance holding the lease configured in `coordination`.
+
+					If the lease is lost, the source is stopped with an error, which stops Vector.
+					"""
+			}
 		}
-		no_proxy: {
-			description: """
-				A list of hosts to avoid proxying.
-
-				Multiple patterns are allowed:
-
-				| Pattern             | Example match                                                               |
-				| ------------------- | --------------------------------------------------------------------------- |
-				| Domain names        | `example.com` matches requests to `example.com`                     |
-				| Wildcard domains    | `.example.com` matches requests to `example.com` and its subdomains |
-				| IP addresses        | `127.0.0.1` matches requests to `127.0.0.1`                         |
-				| [CIDR][cidr] blocks | `192.168.0.0/16` matches requests to any IP addresses in this range     |
-				| Splat               | `*` matches all hosts                                                   |
-
-				[cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
-				"""
-			required: false
-			type: array: {
-				default: []
-				items: type: string: examples: ["localhost", ".foo.bar", "*"]
+	}
+	log_schema: {
+		description: """
+			The fields of the global log schema held by other fields in the events received by the source.
+
+			These fields are moved to the fields of the global log schema, replacing them if they exist,
+			before the events are sent downstream. Only the events using the `legacy` log namespace are
+			remapped.
+			"""
+		required: false
+		type: object: options: {
+			host_key: {
+				description: "The field holding the host which sent the events."
+				required:    false
+				type: string: examples: ["hostname", "agent.hostname"]
+			}
+			message_key: {
+				description: "The field holding the message of the events."
+				required:    false
+				type: string: examples: ["msg"]
+			}
+			timestamp_key: {
+				description: "The field holding the timestamp of the events."
+				required:    false
+				type: string: examples: ["@timestamp"]
+			}
+		}
+	}
+	priority: {
+		description: """
+			The priority of the events produced by the source.
+
+			Buffers which drop events when full shed the events of lower priorities first. The
+			priority is held under `vector.priority` in the metadata of the events, and can be changed
+			in VRL, for example with `%vector.priority = "low"`. By default, events are of the `normal`
+			priority.
+			"""
+		required: false
+		type: string: enum: {
+			background: "Shed once the buffer is half full."
+			low:        "Shed once the buffer is three quarters full."
+			normal:     "Shed only once the buffer is full."
+		}
+	}
+	proxy: {
+		description: """
+			Proxy configuration.
+
+			Configure to proxy traffic through an HTTP(S) proxy when making external requests.
+
+			Similar to common proxy configuration convention, users can set different proxies
+			to use based on the type of traffic being proxied, as well as set specific hosts that
+			should not be proxied.
+			"""
+		required: false
+		type: object: options: {
+			enabled: {
+				description: "Enables proxying support."
+				required:    false
+				type: bool: default: true
+			}
+			http: {
+				description: """
+					Proxy endpoint to use when proxying HTTP traffic.
+
+					Must be a valid URI string.
+					"""
+				required: false
+				type: string: examples: ["http://foo.bar:3128"]
+			}
+			https: {
+				description: """
+					Proxy endpoint to use when proxying HTTPS traffic.
+
+					Must be a valid URI string.
+					"""
+				required: false
+				type: string: examples: ["http://foo.bar:3128"]
+			}
+			no_proxy: {
+				description: """
+					A list of hosts to avoid proxying.
+
+					Multiple patterns are allowed:
+
+					| Pattern             | Example match                                                               |
+					| ------------------- | --------------------------------------------------------------------------- |
+					| Domain names        | `example.com` matches requests to `example.com`                     |
+					| Wildcard domains    | `.example.com` matches requests to `example.com` and its subdomains |
+					| IP addresses        | `127.0.0.1` matches requests to `127.0.0.1`                         |
+					| [CIDR][cidr] blocks | `192.168.0.0/16` matches requests to any IP addresses in this range     |
+					| Splat               | `*` matches all hosts                                                   |
+
+					[cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["localhost", ".foo.bar", "*"]
+				}
+			}
+			tcp: {
+				description: """
+					Proxy endpoint to use when proxying raw TCP traffic, such as the one of socket sinks.
+
+					Must be a valid URI string, with either the `socks5` scheme for a SOCKS5 proxy, or the
+					`http` scheme for an HTTP proxy tunneling the connections with `CONNECT` requests.
+					Credentials can be set in the URI.
+					"""
+				required: false
+				type: string: examples: ["socks5://foo.bar:1080", "http://foo.bar:3128"]
+			}
+		}
+	}
+	tenancy: {
+		description: "Per-tenant quotas of the events received by a source."
+		required:    false
+		type: object: options: {
+			default_tenant: {
+				description: "The tenant of the events whose tenant ID is missing."
+				required:    false
+				type: string: {
+					default: "default"
+					examples: ["unknown"]
+				}
+			}
+			max_tenants: {
+				description: """
+					The maximum number of tenants whose quotas are enforced separately.
+
+					The events of further tenants share a single default `quota`, and are reported as the
+					`other` tenant in the internal metrics. Tenants with a quota in `tenants` always have their
+					own, and tenants stop counting once they received no events for a minute.
+					"""
+				required: false
+				type: uint: default: 1000
+			}
+			over_quota_action: {
+				description: "What to do with the events of a tenant over quota."
+				required:    false
+				type: string: {
+					default: "throttle"
+					enum: {
+						drop: "Drop the events."
+						tag:  "Send the events, setting `over_quota_field` on them."
+						throttle: """
+							Wait until the quota of the tenant allows the events.
+
+							This applies backpressure to the source, slowing down all of its tenants.
+							"""
+					}
+				}
+			}
+			over_quota_field: {
+				description: """
+					The field set to `true` on the events over quota, when `over_quota_action` is `tag`.
+
+					Metrics are tagged with a tag of the same name instead.
+					"""
+				required: false
+				type: string: {
+					default: "tenant_over_quota"
+					examples: ["tenant_over_quota"]
+				}
+			}
+			quota: {
+				description: """
+					The ingest quota of a tenant.
+
+					A tenant can go over its quota in bursts of up to one second of its rate.
+					"""
+				required: false
+				type: object: options: {
+					bytes_per_second: {
+						description: """
+							The number of bytes per second allowed for the tenant.
+
+							Events are measured by their estimated JSON-encoded size. By default, the number of bytes is
+							not limited.
+							"""
+						required: false
+						type: uint: examples: [1048576]
+					}
+					events_per_second: {
+						description: """
+							The number of events per second allowed for the tenant.
+
+							By default, the number of events is not limited.
+							"""
+						required: false
+						type: uint: examples: [1000]
+					}
+				}
+			}
+			tenant_id: {
+				description: "How the tenant ID of an event is identified."
+				required:    true
+				type: object: options: {
+					name: {
+						description:   "The name of the header holding the tenant ID."
+						relevant_when: "type = \"header\""
+						required:      true
+						type: string: examples: ["X-Tenant-ID"]
+					}
+					path: {
+						description:   "The field holding the tenant ID."
+						relevant_when: "type = \"field\""
+						required:      true
+						type: string: examples: ["tenant", "%kafka.topic"]
+					}
+					source: {
+						description:   "The VRL expression resolving to the tenant ID."
+						relevant_when: "type = \"vrl\""
+						required:      true
+						type: string: examples: ["downcase!(.org) + \"/\" + .team"]
+					}
+					type: {
+						description: "The source of the tenant ID."
+						required:    true
+						type: string: enum: {
+							field: """
+								The tenant ID is read from a field of the event.
+
+								For metrics, the tenant ID is read from the tag of the same name.
+								"""
+							header: """
+								The tenant ID is read from an HTTP header captured by the source.
+
+								The header must be captured by the source, usually with its `headers` option.
+								"""
+							vrl: """
+								The tenant ID is the result of a [Vector Remap Language][vrl] (VRL) expression.
+
+								[vrl]: https://vector.dev/docs/reference/vrl
+								"""
+						}
+					}
+				}
+			}
+			tenants: {
+				description: "The quotas of specific tenants, overriding the default `quota`."
+				required:    false
+				type: object: options: "*": {
+					description: "The quota of a tenant, keyed by its tenant ID."
+					required:    true
+					type: object: options: {
+						bytes_per_second: {
+							description: """
+								The number of bytes per second allowed for the tenant.
+
+								Events are measured by their estimated JSON-encoded size. By default, the number of bytes is
+								not limited.
+								"""
+							required: false
+							type: uint: examples: [1048576]
+						}
+						events_per_second: {
+							description: """
+								The number of events per second allowed for the tenant.
+
+								By default, the number of events is not limited.
+								"""
+							required: false
+							type: uint: examples: [1000]
+						}
+					}
+				}
 			}
 		}
 	}
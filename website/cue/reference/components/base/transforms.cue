@@ -1,17 +1,113 @@
 package metadata
 
-base: components: transforms: configuration: inputs: {
-	description: """
-		A list of upstream [source][sources] or [transform][transforms] IDs.
+base: components: transforms: configuration: {
+	drain_timeout_secs: {
+		description: """
+			How long to wait, in seconds, for the transform to drain its input into its downstream
+			components when it is removed by a configuration reload.
 
-		Wildcards (`*`) are supported.
+			The events left in its input once this deadline has passed are abandoned. Defaults to 30
+			seconds.
+			"""
+		required: false
+		type: uint: {}
+	}
+	enabled: {
+		description: """
+			Whether the component is enabled.
 
-		See [configuration][configuration] for more info.
+			Disabled components are removed from the configuration when it is loaded, along with the
+			references to their outputs in the `inputs` of other components. This allows a single
+			configuration to be shared between environments which only differ by some of their components.
+			"""
+		required: false
+		type: {
+			bool: default: true
+			string: examples: ["'${ENV}' == 'prod'", "env('REGION') != 'eu' && hostname() != 'canary'"]
+		}
+	}
+	inputs: {
+		description: """
+			A list of upstream [source][sources] or [transform][transforms] IDs.
 
-		[sources]: https://vector.dev/docs/reference/configuration/sources/
-		[transforms]: https://vector.dev/docs/reference/configuration/transforms/
-		[configuration]: https://vector.dev/docs/reference/configuration/
-		"""
-	required: true
-	type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
+			Wildcards (`*`) are supported.
+
+			See [configuration][configuration] for more info.
+
+			[sources]: https://vector.dev/docs/reference/configuration/sources/
+			[transforms]: https://vector.dev/docs/reference/configuration/transforms/
+			[configuration]: https://vector.dev/docs/reference/configuration/
+			"""
+		required: true
+		type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
+	}
+	ordered_merge: {
+		description: """
+			Ordered merge of the inputs of a transform.
+
+			When set, the events received by the transform are merged by timestamp, so that the events of
+			all of its inputs are processed in chronological order. Events are held for at most the
+			lateness window, and events without a timestamp are passed through as they are received.
+			"""
+		required: false
+		type: object: options: {
+			max_events: {
+				description: """
+					The maximum number of events held for ordering.
+
+					Once reached, the earliest events are released without waiting for the lateness window.
+					"""
+				required: false
+				type: uint: default: 10000
+			}
+			max_lateness_ms: {
+				description: """
+					How late an event can be received, in milliseconds, and still be merged in order.
+
+					Events are held until an event with a timestamp later by this much has been received, and
+					at most for this long.
+					"""
+				required: false
+				type: uint: {
+					default: 1000
+					unit:    "milliseconds"
+				}
+			}
+		}
+	}
+	runtime: {
+		description: """
+			Dedicated runtime of a component.
+
+			When set, the component runs on its own pool of worker threads instead of the worker threads
+			shared by all components, so that a component using a lot of CPU can't starve the others, and
+			the other way around.
+			"""
+		required: false
+		type: object: options: {
+			cpu_affinity: {
+				description: """
+					The IDs of the CPUs to pin the threads of the component to.
+
+					By default, the threads can run on any CPU. Only supported on Linux.
+					"""
+				required: false
+				type: array: items: type: uint: {}
+			}
+			max_blocking_threads: {
+				description: """
+					The maximum number of threads the component can use for blocking operations.
+
+					Defaults to 512.
+					"""
+				required: false
+				type: uint: {}
+			}
+			threads: {
+				description: "The number of worker threads of the component."
+				required:    true
+				type: uint: {}
+			}
+		}
+	}
 }
@@ -6,13 +6,18 @@ components: sinks: [Name=string]: {
 	features: _
 
 	configuration: {
-		inputs: base.components.sinks.configuration.inputs
-		buffer: base.components.sinks.configuration.buffer
+		inputs:             base.components.sinks.configuration.inputs
+		buffer:             base.components.sinks.configuration.buffer
+		drain_timeout_secs: base.components.sinks.configuration.drain_timeout_secs
+		enabled:            base.components.sinks.configuration.enabled
 		healthcheck: {
 			description: base.components.sinks.configuration.healthcheck.description
 			required:    base.components.sinks.configuration.healthcheck.required
 			type: object: options: {
-				enabled: base.components.sinks.configuration.healthcheck.type.object.options.enabled
+				enabled:           base.components.sinks.configuration.healthcheck.type.object.options.enabled
+				failure_threshold: base.components.sinks.configuration.healthcheck.type.object.options.failure_threshold
+				interval_secs:     base.components.sinks.configuration.healthcheck.type.object.options.interval_secs
+				quarantine:        base.components.sinks.configuration.healthcheck.type.object.options.quarantine
 
 				if features.healthcheck != _|_ {
 					if features.healthcheck.uses_uri != _|_ {
@@ -392,6 +397,12 @@ components: sinks: [Name=string]: {
 										unit:    null
 									}
 								}
+								retry_budget_ratio: {
+									common:      false
+									description: "The maximum ratio of retries to requests. Requests aren't retried once the budget is exhausted. By default, retries are only limited by `retry_attempts`."
+									required:    false
+									type: float: {}
+								}
 								retry_initial_backoff_secs: {
 									common:      false
 									description: "The amount of time to wait before attempting the first retry for a failed request. Once, the first retry has failed the fibonacci sequence will be used to select future backoffs."
@@ -401,6 +412,18 @@ components: sinks: [Name=string]: {
 										unit:    "seconds"
 									}
 								}
+								retry_jitter_mode: {
+									common:      false
+									description: "The jitter mode to use for retry backoff."
+									required:    false
+									type: string: {
+										default: "none"
+										enum: {
+											full: "Retries wait for a random delay between zero and the backoff."
+											none: "Retries wait for the full backoff."
+										}
+									}
+								}
 								retry_max_duration_secs: {
 									common:      false
 									description: "The maximum amount of time, in seconds, to wait between retries."
@@ -467,6 +490,15 @@ components: sinks: [Name=string]: {
 						type: object: {
 							examples: []
 							options: {
+								interval_secs: {
+									common:      false
+									description: "The time between TCP keepalive probes, once they started to be sent. Not supported on all platforms, on which the default of the operating system is used."
+									required:    false
+									type: uint: {
+										default: null
+										unit:    "seconds"
+									}
+								}
 								time_secs: {
 									common:      false
 									description: "The time a connection needs to be idle before sending TCP keepalive probes."
@@ -563,6 +595,21 @@ components: sinks: [Name=string]: {
 							```
 							"""
 					},
+					{
+						title: "Periodic health checks"
+						body: """
+							If you'd like to detect the degradation of the downstream service while Vector is running,
+							you can set the `healthcheck.interval_secs` option to check the health of this sink
+							periodically. After `healthcheck.failure_threshold` consecutive failures, the sink is
+							quarantined: depending on the `healthcheck.quarantine` option, it either stops reading
+							events, leaving them in its buffer, or drops them. The sink is released from quarantine
+							by the next health check which passes. Both state changes are logged, and reported by the
+							`quarantined` internal metric.
+
+							Periodic health checks reuse the client of the sink, and are only supported by the `http`,
+							Datadog, and Splunk HEC sinks. Other sinks log a warning and ignore the option.
+							"""
+					},
 					{
 						title: "Disable health checks"
 						body: """
@@ -633,6 +680,18 @@ components: sinks: [Name=string]: {
 						Other responses will not be retried. You can control the number of
 						retry attempts and backoff rate with the `request.retry_attempts` and
 						`request.retry_backoff_secs` options.
+
+						The backoff can be jittered with `request.retry_jitter_mode`, and the share
+						of retries among the requests can be capped with
+						`request.retry_budget_ratio`.
+
+						Most HTTP-based sinks, such as `http`, `elasticsearch`, `splunk_hec`,
+						`loki`, `datadog_metrics`, and `datadog_traces`, also honor the
+						`Retry-After` and `x-ratelimit-reset` headers of a retried response: the
+						retry waits for the delay the header asks for, up to
+						`request.retry_max_duration_secs`. Sinks that don't keep the headers of a
+						failed response, such as `datadog_logs` and `new_relic`, always use the
+						backoff.
 						"""
 				}
 			}
@@ -645,6 +704,10 @@ components: sinks: [Name=string]: {
 					body:  """
 						Vector uses [OpenSSL](\(urls.openssl)) for TLS protocols due to OpenSSL's maturity. You can
 						enable and adjust TLS behavior using the [`tls.*`](#tls) options.
+
+						The `ca_file`, `crt_file` and `key_file` files are reloaded when they change, without
+						restarting Vector: new connections use the new certificates, while established
+						connections are kept.
 						"""
 				}
 			}
@@ -652,17 +715,19 @@ components: sinks: [Name=string]: {
 	}
 
 	telemetry: metrics: {
-		component_received_events_count:      components.sources.internal_metrics.output.metrics.component_received_events_count
-		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
-		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
-		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
-		utilization:                          components.sources.internal_metrics.output.metrics.utilization
-		buffer_byte_size:                     components.sources.internal_metrics.output.metrics.buffer_byte_size
-		buffer_events:                        components.sources.internal_metrics.output.metrics.buffer_events
-		buffer_received_events_total:         components.sources.internal_metrics.output.metrics.buffer_received_events_total
-		buffer_received_event_bytes_total:    components.sources.internal_metrics.output.metrics.buffer_received_event_bytes_total
-		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
-		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
-		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		component_received_events_count:           components.sources.internal_metrics.output.metrics.component_received_events_count
+		component_received_events_total:           components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total:      components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_event_size_bytes:       components.sources.internal_metrics.output.metrics.component_received_event_size_bytes
+		events_in_total:                           components.sources.internal_metrics.output.metrics.events_in_total
+		utilization:                               components.sources.internal_metrics.output.metrics.utilization
+		buffer_byte_size:                          components.sources.internal_metrics.output.metrics.buffer_byte_size
+		buffer_events:                             components.sources.internal_metrics.output.metrics.buffer_events
+		buffer_received_events_total:              components.sources.internal_metrics.output.metrics.buffer_received_events_total
+		buffer_received_event_bytes_total:         components.sources.internal_metrics.output.metrics.buffer_received_event_bytes_total
+		buffer_sent_events_total:                  components.sources.internal_metrics.output.metrics.buffer_sent_events_total
+		buffer_sent_event_bytes_total:             components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
+		buffer_discarded_events_total:             components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		buffer_discarded_events_by_priority_total: components.sources.internal_metrics.output.metrics.buffer_discarded_events_by_priority_total
 	}
 }
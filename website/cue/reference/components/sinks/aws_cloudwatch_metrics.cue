@@ -113,6 +113,33 @@ components: sinks: aws_cloudwatch_metrics: components._aws & {
 				},
 			]
 		},
+		{
+			platform: "aws"
+			_service: "AmazonCloudWatchLogs"
+
+			policies: [
+				{
+					_action:       "CreateLogGroup"
+					required_when: "[`emf.create_missing_group`](#emf.create_missing_group) is set to `true`"
+				},
+				{
+					_action:       "CreateLogStream"
+					required_when: "[`emf.create_missing_stream`](#emf.create_missing_stream) is set to `true`"
+				},
+				{
+					_action:       "DescribeLogGroups"
+					required_when: "[`emf`](#emf) is set"
+				},
+				{
+					_action:       "DescribeLogStreams"
+					required_when: "[`emf`](#emf) is set"
+				},
+				{
+					_action:       "PutLogEvents"
+					required_when: "[`emf`](#emf) is set"
+				},
+			]
+		},
 	]
 
 	telemetry: metrics: {
@@ -222,6 +222,9 @@ components: sinks: aws_s3: components._aws & {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		open_partitions:                  components.sources.internal_metrics.output.metrics.open_partitions
+		partitions_evicted_total:         components.sources.internal_metrics.output.metrics.partitions_evicted_total
+		partitions_opened_total:          components.sources.internal_metrics.output.metrics.partitions_opened_total
 		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
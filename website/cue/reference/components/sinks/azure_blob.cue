@@ -122,6 +122,23 @@ components: sinks: azure_blob: {
 				```
 				"""
 		}
+		append_blobs: {
+			title: "Append blobs"
+			body: """
+				When [`blob_type`](#blob_type) is set to `append`, batches are appended to
+				[append blobs](https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs#about-append-blobs)
+				instead of each being written to a new block blob. Batches whose blob keys are the same
+				are appended to the same blob, which is created when the first batch is written to it.
+
+				No UUID is appended to the blob keys, and the timestamp defaults to the `%Y-%m-%d-%H`
+				format, so a new blob is started every hour. Requests are sent one at a time by default,
+				so that batches are appended in order.
+
+				Blobs can be read while they are being appended to. When compression is enabled, each
+				batch is appended as a separate gzip member, so setting `compression` to `none` is
+				recommended for services that tail the blobs.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
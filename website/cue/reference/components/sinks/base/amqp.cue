@@ -215,6 +215,33 @@ base: components: sinks: amqp: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			cipher_suites: {
+				description: """
+					Sets the list of allowed cipher suites, as OpenSSL names.
+
+					The TLS 1.3 cipher suites, whose names start with `TLS_`, and the cipher suites of the previous versions are set
+					separately: when none of either kind is listed, the default ones are kept for the corresponding versions. In FIPS
+					mode, only the FIPS-approved cipher suites can be listed, and they are the default ones. FIPS builds of Vector don't
+					support listing TLS 1.3 cipher suites.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crl_file: {
+				description: """
+					Absolute path to a certificate revocation list (CRL) file.
+
+					The revocation of all the certificates of the chain presented by the peer is checked against the lists of the
+					file, which must be in the DER or PEM format. Connections with peers presenting a revoked certificate, or a
+					certificate issued by a CA without revocation list, are refused. This is not supported by FIPS builds of Vector.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: examples: ["/path/to/revocation_list.crl"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -245,6 +272,72 @@ base: components: sinks: amqp: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			min_tls_version: {
+				description: """
+					The minimum version of the TLS protocol.
+
+					Defaults to the minimum version supported by OpenSSL, or to `TLSv1.2` in FIPS mode.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: enum: {
+					"TLSv1.0": "TLS 1.0."
+					"TLSv1.1": "TLS 1.1."
+					"TLSv1.2": "TLS 1.2."
+					"TLSv1.3": "TLS 1.3."
+				}
+			}
+			pinned_public_keys: {
+				description: """
+					Pins the public keys of the certificates presented by the remote host.
+
+					When set, the certificate chain presented by the remote host must hold a certificate, either the leaf
+					certificate or a CA certificate, whose public key is pinned. The public keys are pinned by the base64 encoded
+					SHA-256 hash of their DER encoded SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+
+					This has no effect unless `verify_certificate` is enabled.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["sha256//YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg="]
+			}
+			spiffe: {
+				description: """
+					Uses the SPIFFE workload identity fetched from the SPIFFE Workload API.
+
+					The identity certificate and the trust bundle are rotated without restarting Vector, as the
+					SPIFFE agent issues new SVIDs. This can't be used with `crt_file` and `key_file`.
+
+					As the peers are identified by their SPIFFE ID rather than by their hostname,
+					`verify_hostname` defaults to `false` when this is set.
+					"""
+				required: false
+				type: object: options: {
+					allowed_ids: {
+						description: """
+							The SPIFFE IDs allowed to connect to, or be connected from.
+
+							If empty, all the peers presenting an SVID issued by the trust bundle of the workload are
+							allowed.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["spiffe://example.org/vector/aggregator"]
+						}
+					}
+					endpoint_socket: {
+						description: """
+							The address of the SPIFFE Workload API.
+
+							Must be a Unix socket address, in the form `unix:///path/to/agent.sock`. If not set, the
+							address is read from the `SPIFFE_ENDPOINT_SOCKET` environment variable.
+							"""
+						required: false
+						type: string: examples: ["unix:///run/spire/sockets/agent.sock"]
+					}
+				}
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -275,6 +368,18 @@ base: components: sinks: amqp: configuration: {
 				required: false
 				type: bool: {}
 			}
+			verify_ocsp_stapling: {
+				description: """
+					Requires the remote host to staple a valid OCSP response to its certificate.
+
+					The stapled OCSP response must report the certificate presented by the remote host as good, which prevents
+					connecting to hosts whose certificate has been revoked. This is not supported by FIPS builds of Vector.
+
+					Only relevant for outgoing connections.
+					"""
+				required: false
+				type: bool: {}
+			}
 		}
 	}
 }
@@ -45,6 +45,40 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required: true
 				type: string: examples: ["arn:aws:iam::123456789098:role/my_role"]
 			}
+			chained_roles: {
+				description: """
+					The ARNs of further IAM roles to assume, in order, after `assume_role`.
+
+					Each role is assumed with the credentials of the previous one, which allows reaching
+					roles only trusted by another role.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["arn:aws:iam::123456789098:role/other_role"]
+				}
+			}
+			config_file: {
+				description: """
+					Path to the AWS config file.
+
+					If not set, the default config file is used.
+					"""
+				required: false
+				type: string: examples: ["/my/aws/config"]
+			}
+			credential_process: {
+				description: """
+					The command to run to fetch credentials.
+
+					The command must print the credentials in the [`credential_process`][credential_process]
+					format, and is run again when they are about to expire.
+
+					[credential_process]: https://docs.aws.amazon.com/cli/latest/userguide/cli-configure-sourcing-external.html
+					"""
+				required: true
+				type: string: examples: ["/usr/local/bin/fetch-aws-credentials --role vector"]
+			}
 			credentials_file: {
 				description: "Path to the credentials file."
 				required:    true
@@ -75,6 +109,19 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 							unit:    "seconds"
 						}
 					}
+					token_ttl_seconds: {
+						description: """
+							Lifetime of the IMDSv2 session tokens.
+
+							The IMDSv2 hop limit is set on the instance itself, through its `HttpPutResponseHopLimit`
+							metadata option, and must be at least 2 when Vector runs in a container.
+							"""
+						required: false
+						type: uint: {
+							default: 21600
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 			load_timeout_secs: {
@@ -101,6 +148,14 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 					examples: ["develop"]
 				}
 			}
+			refresh_buffer_secs: {
+				description: "How long before they expire the credentials are refreshed, in seconds."
+				required:    false
+				type: uint: {
+					examples: [300]
+					unit: "seconds"
+				}
+			}
 			region: {
 				description: """
 					The [AWS region][aws_region] to send STS requests to.
@@ -118,6 +173,11 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required:    true
 				type: string: examples: ["wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY"]
 			}
+			sso_profile: {
+				description: "The name of the SSO profile to use."
+				required:    true
+				type: string: examples: ["develop"]
+			}
 		}
 	}
 	batch: {
@@ -356,6 +416,15 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 			syntax: "template"
 		}
 	}
+	kms_key: {
+		description: """
+			The ARN of the [KMS key][kms_key] encrypting the events of the log groups created.
+
+			[kms_key]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/encrypt-log-data-kms.html
+			"""
+		required: false
+		type: string: examples: ["arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab"]
+	}
 	region: {
 		description: """
 			The [AWS region][aws_region] of the target service.
@@ -484,6 +553,19 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 					unit:    "retries"
 				}
 			}
+			retry_budget_ratio: {
+				description: """
+					The maximum ratio of retries to requests.
+
+					For example, `0.1` allows one retry for every ten requests sent over the last ten seconds.
+					At least one retry per second is always allowed. Requests aren't retried once the budget is
+					exhausted.
+
+					By default, retries are only limited by `retry_attempts`.
+					"""
+				required: false
+				type: float: {}
+			}
 			retry_initial_backoff_secs: {
 				description: """
 					The amount of time to wait before attempting the first retry for a failed request.
@@ -496,6 +578,26 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 					unit:    "seconds"
 				}
 			}
+			retry_jitter_mode: {
+				description: "The jitter mode to use for retry backoff."
+				required:    false
+				type: string: {
+					default: "none"
+					enum: {
+						full: """
+							Full jitter.
+
+							Retries wait for a random delay between zero and the backoff, which spreads the retries of
+							many clients recovering from the same failure instead of having them retry in lockstep.
+							"""
+						none: """
+							No jitter.
+
+							Retries wait for the full backoff.
+							"""
+					}
+				}
+			}
 			retry_max_duration_secs: {
 				description: "The maximum amount of time to wait between retries."
 				required:    false
@@ -519,6 +621,21 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 			}
 		}
 	}
+	retention_days: {
+		description: """
+			The number of days to retain the events of the log groups created by the sink.
+
+			Must be one of the [values supported by CloudWatch Logs][retention]. By default, the events
+			are retained indefinitely.
+
+			[retention]: https://docs.aws.amazon.com/AmazonCloudWatchLogs/latest/APIReference/API_PutRetentionPolicy.html
+			"""
+		required: false
+		type: uint: {
+			examples: [30]
+			unit: "days"
+		}
+	}
 	stream_name: {
 		description: """
 			The [stream name][stream_name] of the target CloudWatch Logs stream.
@@ -535,6 +652,36 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 			syntax: "template"
 		}
 	}
+	stream_shards: {
+		description: """
+			The number of log streams the events of each stream are spread over.
+
+			Events can only be written to a log stream by one request at a time, so spreading them over
+			several streams allows sending more of them concurrently. When set, the batches are sent in
+			turn to streams named after `stream_name`, suffixed with `-` and the index of the stream,
+			starting from zero.
+			"""
+		required: false
+		type: uint: examples: [4]
+	}
+	tags: {
+		description: """
+			The [tags][tags] applied to the log groups created by the sink.
+
+			[tags]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html#log-group-tagging
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				team: "observability"
+			}]
+			options: "*": {
+				description: "A tag value."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
 	tls: {
 		description: "TLS configuration."
 		required:    false
@@ -558,6 +705,33 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			cipher_suites: {
+				description: """
+					Sets the list of allowed cipher suites, as OpenSSL names.
+
+					The TLS 1.3 cipher suites, whose names start with `TLS_`, and the cipher suites of the previous versions are set
+					separately: when none of either kind is listed, the default ones are kept for the corresponding versions. In FIPS
+					mode, only the FIPS-approved cipher suites can be listed, and they are the default ones. FIPS builds of Vector don't
+					support listing TLS 1.3 cipher suites.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crl_file: {
+				description: """
+					Absolute path to a certificate revocation list (CRL) file.
+
+					The revocation of all the certificates of the chain presented by the peer is checked against the lists of the
+					file, which must be in the DER or PEM format. Connections with peers presenting a revoked certificate, or a
+					certificate issued by a CA without revocation list, are refused. This is not supported by FIPS builds of Vector.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: examples: ["/path/to/revocation_list.crl"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -588,6 +762,72 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			min_tls_version: {
+				description: """
+					The minimum version of the TLS protocol.
+
+					Defaults to the minimum version supported by OpenSSL, or to `TLSv1.2` in FIPS mode.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: enum: {
+					"TLSv1.0": "TLS 1.0."
+					"TLSv1.1": "TLS 1.1."
+					"TLSv1.2": "TLS 1.2."
+					"TLSv1.3": "TLS 1.3."
+				}
+			}
+			pinned_public_keys: {
+				description: """
+					Pins the public keys of the certificates presented by the remote host.
+
+					When set, the certificate chain presented by the remote host must hold a certificate, either the leaf
+					certificate or a CA certificate, whose public key is pinned. The public keys are pinned by the base64 encoded
+					SHA-256 hash of their DER encoded SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+
+					This has no effect unless `verify_certificate` is enabled.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["sha256//YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg="]
+			}
+			spiffe: {
+				description: """
+					Uses the SPIFFE workload identity fetched from the SPIFFE Workload API.
+
+					The identity certificate and the trust bundle are rotated without restarting Vector, as the
+					SPIFFE agent issues new SVIDs. This can't be used with `crt_file` and `key_file`.
+
+					As the peers are identified by their SPIFFE ID rather than by their hostname,
+					`verify_hostname` defaults to `false` when this is set.
+					"""
+				required: false
+				type: object: options: {
+					allowed_ids: {
+						description: """
+							The SPIFFE IDs allowed to connect to, or be connected from.
+
+							If empty, all the peers presenting an SVID issued by the trust bundle of the workload are
+							allowed.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["spiffe://example.org/vector/aggregator"]
+						}
+					}
+					endpoint_socket: {
+						description: """
+							The address of the SPIFFE Workload API.
+
+							Must be a Unix socket address, in the form `unix:///path/to/agent.sock`. If not set, the
+							address is read from the `SPIFFE_ENDPOINT_SOCKET` environment variable.
+							"""
+						required: false
+						type: string: examples: ["unix:///run/spire/sockets/agent.sock"]
+					}
+				}
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -618,6 +858,18 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				required: false
 				type: bool: {}
 			}
+			verify_ocsp_stapling: {
+				description: """
+					Requires the remote host to staple a valid OCSP response to its certificate.
+
+					The stapled OCSP response must report the certificate presented by the remote host as good, which prevents
+					connecting to hosts whose certificate has been revoked. This is not supported by FIPS builds of Vector.
+
+					Only relevant for outgoing connections.
+					"""
+				required: false
+				type: bool: {}
+			}
 		}
 	}
 }
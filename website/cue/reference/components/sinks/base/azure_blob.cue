@@ -27,6 +27,63 @@ base: components: sinks: azure_blob: configuration: {
 			type: bool: {}
 		}
 	}
+	auth: {
+		description: "The credentials used to authenticate with a storage account."
+		required:    false
+		type: object: options: {
+			client_id: {
+				description: """
+					The client ID of a user-assigned managed identity.
+
+					If not set, the system-assigned managed identity is used.
+					"""
+				relevant_when: "type = \"managed_identity\""
+				required:      false
+				type: string: examples: ["00000000-0000-0000-0000-000000000000"]
+			}
+			path: {
+				description:   "The path of the file holding the SAS token."
+				relevant_when: "type = \"sas_token_file\""
+				required:      true
+				type: string: examples: ["/var/run/secrets/azure/sas-token"]
+			}
+			type: {
+				description: "The kind of credentials."
+				required:    false
+				type: string: {
+					default: "default"
+					enum: {
+						default: """
+							Credentials are loaded in the following ways, in order:
+
+							- read from environment variables ([more information][env_cred_docs])
+							- looks for a [Managed Identity][managed_ident_docs]
+							- uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+
+							[env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+							[managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+							[az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+							"""
+						managed_identity: """
+							Authenticate with an Azure AD [managed identity][managed_ident_docs].
+
+							Access tokens are requested again before they expire.
+
+							[managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+							"""
+						sas_token_file: """
+							Authenticate with a [SAS token][sas_docs] read from a file.
+
+							The file is read again whenever it changes, so that the token can be rotated without
+							restarting Vector, such as when it is mounted from a Kubernetes secret.
+
+							[sas_docs]: https://learn.microsoft.com/en-us/azure/storage/common/storage-sas-overview
+							"""
+					}
+				}
+			}
+		}
+	}
 	batch: {
 		description: "Event batching behavior."
 		required:    false
@@ -70,6 +127,8 @@ base: components: sinks: azure_blob: configuration: {
 
 			This ensures there are no name collisions, and can be useful in high-volume workloads where
 			blob keys must be unique.
+
+			This can't be enabled when `blob_type` is `append`.
 			"""
 		required: false
 		type: bool: {}
@@ -106,11 +165,37 @@ base: components: sinks: azure_blob: configuration: {
 
 			When set to an empty string, no timestamp is appended to the blob prefix.
 
+			When `blob_type` is `append`, this defaults to `%Y-%m-%d-%H`, so that batches are appended to
+			a new blob every hour.
+
 			[chrono_strftime_specifiers]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
 			"""
 		required: false
 		type: string: syntax: "strftime"
 	}
+	blob_type: {
+		description: "The type of blob to write batches to."
+		required:    false
+		type: string: {
+			default: "block"
+			enum: {
+				append: """
+					Batches are appended to an [append blob][append_blobs], which is created if it doesn't
+					exist yet.
+
+					Batches whose blob keys are the same are written to the same blob, so fewer and larger blobs
+					are created, and they can be read while they are being written to.
+
+					[append_blobs]: https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs#about-append-blobs
+					"""
+				block: """
+					Each batch is written to a new [block blob][block_blobs].
+
+					[block_blobs]: https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs#about-block-blobs
+					"""
+			}
+		}
+	}
 	compression: {
 		description: """
 			Compression configuration.
@@ -443,6 +528,19 @@ base: components: sinks: azure_blob: configuration: {
 					unit:    "retries"
 				}
 			}
+			retry_budget_ratio: {
+				description: """
+					The maximum ratio of retries to requests.
+
+					For example, `0.1` allows one retry for every ten requests sent over the last ten seconds.
+					At least one retry per second is always allowed. Requests aren't retried once the budget is
+					exhausted.
+
+					By default, retries are only limited by `retry_attempts`.
+					"""
+				required: false
+				type: float: {}
+			}
 			retry_initial_backoff_secs: {
 				description: """
 					The amount of time to wait before attempting the first retry for a failed request.
@@ -455,6 +553,26 @@ base: components: sinks: azure_blob: configuration: {
 					unit:    "seconds"
 				}
 			}
+			retry_jitter_mode: {
+				description: "The jitter mode to use for retry backoff."
+				required:    false
+				type: string: {
+					default: "none"
+					enum: {
+						full: """
+							Full jitter.
+
+							Retries wait for a random delay between zero and the backoff, which spreads the retries of
+							many clients recovering from the same failure instead of having them retry in lockstep.
+							"""
+						none: """
+							No jitter.
+
+							Retries wait for the full backoff.
+							"""
+					}
+				}
+			}
 			retry_max_duration_secs: {
 				description: "The maximum amount of time to wait between retries."
 				required:    false
@@ -482,7 +600,8 @@ base: components: sinks: azure_blob: configuration: {
 		description: """
 			The Azure Blob Storage Account name.
 
-			Attempts to load credentials for the account in the following ways, in order:
+			The credentials for the account are chosen with `auth`. By default, they are loaded in the
+			following ways, in order:
 
 			- read from environment variables ([more information][env_cred_docs])
 			- looks for a [Managed Identity][managed_ident_docs]
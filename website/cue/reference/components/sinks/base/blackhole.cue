@@ -27,6 +27,93 @@ base: components: sinks: blackhole: configuration: {
 			type: bool: {}
 		}
 	}
+	error_rate: {
+		description: """
+			The fraction of events, between `0` and `1`, that the sink fails to deliver.
+
+			Failed events are reported as errored to the sources that wait for acknowledgements, which
+			can be used to test how the pipeline handles delivery failures.
+
+			By default, all events are delivered.
+			"""
+		required: false
+		type: float: examples: [0.01]
+	}
+	latency: {
+		description: """
+			Artificial latency added before consuming each batch of events.
+
+			This slows down the sink without limiting its throughput to a fixed rate, which can be used to
+			simulate a slow endpoint.
+			"""
+		required: false
+		type: object: options: {
+			distribution: {
+				description: "The distribution of the latency."
+				required:    true
+				type: string: enum: {
+					constant: "The same latency for every batch."
+					exponential: """
+						A latency following an exponential distribution.
+
+						Most batches are delayed briefly, while a few are delayed much longer, like the tail
+						latency of a real service.
+						"""
+					normal: """
+						A latency following a normal distribution.
+
+						Negative samples are treated as no latency.
+						"""
+					uniform: "A latency picked uniformly between two bounds."
+				}
+			}
+			max_ms: {
+				description:   "The highest latency, in milliseconds."
+				relevant_when: "distribution = \"uniform\""
+				required:      true
+				type: uint: {
+					examples: [500]
+					unit: "milliseconds"
+				}
+			}
+			mean_ms: {
+				description:   "The mean latency, in milliseconds."
+				relevant_when: "distribution = \"normal\" or distribution = \"exponential\""
+				required:      true
+				type: uint: {
+					examples: [100]
+					unit: "milliseconds"
+				}
+			}
+			min_ms: {
+				description:   "The lowest latency, in milliseconds."
+				relevant_when: "distribution = \"uniform\""
+				required:      true
+				type: uint: {
+					examples: [10]
+					unit: "milliseconds"
+				}
+			}
+			ms: {
+				description:   "The latency, in milliseconds."
+				relevant_when: "distribution = \"constant\""
+				required:      true
+				type: uint: {
+					examples: [100]
+					unit: "milliseconds"
+				}
+			}
+			stddev_ms: {
+				description:   "The standard deviation of the latency, in milliseconds."
+				relevant_when: "distribution = \"normal\""
+				required:      true
+				type: uint: {
+					examples: [20]
+					unit: "milliseconds"
+				}
+			}
+		}
+	}
 	print_interval_secs: {
 		description: """
 			The interval between reporting a summary of activity.
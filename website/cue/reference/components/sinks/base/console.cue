@@ -196,6 +196,68 @@ base: components: sinks: console: configuration: {
 			}
 		}
 	}
+	pretty: {
+		description: """
+			Configuration for writing events in a human-readable layout.
+
+			When set, events are written with this layout instead of being serialized with
+			`encoding.codec`. The `encoding` options that select fields still apply.
+			"""
+		required: false
+		type: object: options: {
+			colors: {
+				description: "When to color the output with ANSI escape codes."
+				required:    false
+				type: string: {
+					default: "auto"
+					enum: {
+						always: "Always color the output."
+						auto:   "Color the output when writing to a terminal."
+						never:  "Never color the output."
+					}
+				}
+			}
+			fields: {
+				description: """
+					The fields to write, in order.
+
+					By default, all fields are written, starting with the timestamp, the severity and the
+					message.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["timestamp", "level", "message"]
+			}
+			layout: {
+				description: "The layout of the written events."
+				required:    false
+				type: string: {
+					default: "key_value"
+					enum: {
+						key_value: "Each event is written on a line of `key=value` pairs."
+						table: """
+							Events are written as the rows of a table, with a header naming the fields.
+
+							The columns are sized after the first event. Unless `fields` is set, the columns are the
+							fields of the first event.
+							"""
+					}
+				}
+			}
+			severity_field: {
+				description: """
+					The field holding the severity of events.
+
+					The severity is colored according to its value: red for errors, yellow for warnings, green
+					for informational messages, and blue for debug messages.
+					"""
+				required: false
+				type: string: {
+					default: "level"
+					examples: ["severity"]
+				}
+			}
+		}
+	}
 	target: {
 		description: """
 			The [standard stream][standard_streams] to write to.
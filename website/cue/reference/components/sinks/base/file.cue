@@ -243,4 +243,103 @@ base: components: sinks: file: configuration: {
 			syntax: "template"
 		}
 	}
+	rotation: {
+		description: """
+			Configuration for rotating the files written by the `file` sink.
+
+			When a file is rotated, it is renamed by appending `suffix` to its name, and events are written
+			to a new file at the original path. Files in the same directory whose name starts with the name
+			of the original file are considered to be its rotated files when applying the retention policy.
+			"""
+		required: false
+		type: object: options: {
+			compression: {
+				description: """
+					The compression applied to rotated files.
+
+					The extension of the compression format is appended to the name of compressed files. This
+					can not be combined with the `compression` option of the sink.
+					"""
+				required: false
+				type: string: {
+					default: "none"
+					enum: {
+						gzip: """
+							[Gzip][gzip] compression.
+
+							[gzip]: https://www.gzip.org/
+							"""
+						none: "No compression."
+						zstd: """
+							[Zstandard][zstd] compression.
+
+							[zstd]: https://facebook.github.io/zstd/
+							"""
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The age a file can reach before it is rotated, in seconds.
+
+					Files are rotated before writing the next event, so idle files are not rotated until they
+					are written to again.
+					"""
+				required: false
+				type: uint: {
+					examples: [86400]
+					unit: "seconds"
+				}
+			}
+			max_bytes: {
+				description: """
+					The size a file can reach before it is rotated, in bytes.
+
+					Files are rotated before writing the next event, so they can exceed this size by at most
+					one event.
+					"""
+				required: false
+				type: uint: {
+					examples: [104857600]
+					unit: "bytes"
+				}
+			}
+			max_files: {
+				description: """
+					The maximum number of rotated files to keep for each file.
+
+					When exceeded, the oldest rotated files are deleted.
+					"""
+				required: false
+				type: uint: examples: [10]
+			}
+			retention_secs: {
+				description: """
+					The maximum age of rotated files, in seconds.
+
+					Rotated files that were last modified longer ago are deleted.
+					"""
+				required: false
+				type: uint: {
+					examples: [604800]
+					unit: "seconds"
+				}
+			}
+			suffix: {
+				description: """
+					The suffix appended to the name of rotated files.
+
+					[`strftime` specifiers][strftime] are replaced with the time of rotation, in UTC. If a
+					rotated file with the same name already exists, a counter is appended.
+
+					[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+					"""
+				required: false
+				type: string: {
+					default: ".%Y%m%dT%H%M%S"
+					examples: [".%Y-%m-%d"]
+				}
+			}
+		}
+	}
 }
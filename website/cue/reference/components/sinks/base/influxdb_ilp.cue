@@ -0,0 +1,495 @@
+package metadata
+
+base: components: sinks: influxdb_ilp: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, waits for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: """
+			The address to connect to.
+
+			Both IP address and hostname are accepted formats.
+
+			The address _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["92.12.333.224:5000", "https://somehost:5000"]
+	}
+	bind_address: {
+		description: """
+			The local IP address to send from.
+
+			On hosts with several addresses, this controls the source address of the connections. Only
+			addresses of the same family are connected to.
+			"""
+		required: false
+		type: string: examples: ["192.168.0.10"]
+	}
+	bind_interface: {
+		description: """
+			The network interface to send through.
+
+			If set, the `SO_BINDTODEVICE` option is set on the socket. Only supported on Linux.
+			"""
+		required: false
+		type: string: examples: ["eth1"]
+	}
+	connections: {
+		description: """
+			The number of connections to send events over concurrently.
+
+			Each event is sent over the connection with the fewest events waiting to be sent, so that a
+			slow connection does not hold back the others. The order of events is only preserved with a
+			single connection. This does not apply when application-level acknowledgements are enabled.
+
+			Defaults to a single connection.
+			"""
+		required: false
+		type: uint: examples: [4]
+	}
+	dns_refresh_secs: {
+		description: """
+			How often to resolve the address again while connected, in seconds.
+
+			When the resolved IP addresses change, for example after a DNS-based failover, the
+			connections are closed and established again to the new addresses. This does not apply when
+			connecting through a proxy.
+
+			Regardless of this setting, new connections are made to each of the resolved addresses in
+			turn.
+			"""
+		required: false
+		type: uint: {
+			examples: [60]
+			unit: "seconds"
+		}
+	}
+	encoding: {
+		description: "Transformations to prepare an event for serialization."
+		required:    false
+		type: object: options: {
+			except_fields: {
+				description: "List of fields that are excluded from the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			only_fields: {
+				description: "List of fields that are included in the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			timestamp_format: {
+				description: "Format used for timestamp fields."
+				required:    false
+				type: string: enum: {
+					rfc3339: "Represent the timestamp as a RFC 3339 timestamp."
+					unix:    "Represent the timestamp as a Unix timestamp."
+				}
+			}
+		}
+	}
+	fields: {
+		description: """
+			The fields written with each line, and the fields of the events they are taken from.
+
+			If not set, all the fields of the events are written, except for the fields of the tags
+			and the timestamp. Nested fields are written with their full path as the name, such as
+			`parent.child`.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				message: "message"
+				status:  "http.status"
+			}]
+			options: "*": {
+				description: "The path of the field holding the value of the line protocol field."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	idle_timeout_secs: {
+		description: """
+			The amount of time a connection can stay idle before it is closed, in seconds.
+
+			Firewalls and load balancers may silently drop connections that are idle for a while, which
+			causes the first events sent afterwards to be lost. When set, idle connections are closed,
+			and a new connection is established once there are events to send again.
+			"""
+		required: false
+		type: uint: {
+			examples: [300]
+			unit: "seconds"
+		}
+	}
+	keepalive: {
+		description: "TCP keepalive settings for socket-based components."
+		required:    false
+		type: object: options: {
+			interval_secs: {
+				description: """
+					The time to wait between TCP keepalive probes, once they started to be sent.
+
+					Not supported on all platforms, on which the default of the operating system is used.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			time_secs: {
+				description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
+				required:    false
+				type: uint: unit: "seconds"
+			}
+		}
+	}
+	measurement: {
+		description: """
+			The name of the measurement that is written to.
+
+			With QuestDB, the measurement is the name of the table.
+			"""
+		required: true
+		type: string: {
+			examples: ["logs", "{{ service }}"]
+			syntax: "template"
+		}
+	}
+	nodelay: {
+		description: """
+			Whether or not to disable Nagle's algorithm on the connection.
+
+			If set, the `TCP_NODELAY` option is set on the socket, so that small writes are sent
+			right away instead of being buffered until more data is written.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	reconnect: {
+		description: """
+			Reconnection settings.
+
+			When the connection can't be established, attempts are retried after an exponentially
+			increasing delay.
+			"""
+		required: false
+		type: object: options: {
+			backoff_factor: {
+				description: "The multiplier applied to the delay after each failed reconnection attempt."
+				required:    false
+				type: uint: default: 2
+			}
+			initial_backoff_ms: {
+				description: "The delay before the first reconnection attempt, in milliseconds."
+				required:    false
+				type: uint: {
+					default: 500
+					unit:    "milliseconds"
+				}
+			}
+			jitter_mode: {
+				description: """
+					The jitter applied to the delay between reconnection attempts.
+
+					Setting it spreads the reconnections of many sinks recovering from the same failure.
+					"""
+				required: false
+				type: string: {
+					default: "none"
+					enum: {
+						full: """
+							Full jitter.
+
+							Retries wait for a random delay between zero and the backoff, which spreads the retries of
+							many clients recovering from the same failure instead of having them retry in lockstep.
+							"""
+						none: """
+							No jitter.
+
+							Retries wait for the full backoff.
+							"""
+					}
+				}
+			}
+			max_backoff_secs: {
+				description: "The maximum delay between reconnection attempts, in seconds."
+				required:    false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	send_buffer_bytes: {
+		description: """
+			The size of the socket's send buffer.
+
+			If set, the value of the setting is passed via the `SO_SNDBUF` option.
+			"""
+		required: false
+		type: uint: {
+			examples: [
+				65536,
+			]
+			unit: "bytes"
+		}
+	}
+	send_timeout_secs: {
+		description: """
+			How long a write can be stalled before the connection is closed, in seconds.
+
+			A peer which stops reading without closing the connection would otherwise block the sink
+			indefinitely. When set, the events being written are considered as failed, and a new
+			connection is established to send the next events.
+			"""
+		required: false
+		type: uint: {
+			examples: [30]
+			unit: "seconds"
+		}
+	}
+	tags: {
+		description: """
+			The tags written with each line, and the fields of the events they are taken from.
+
+			Tags whose field is missing from an event are not written. With QuestDB, tags are written
+			to `SYMBOL` columns.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				host:    "host"
+				service: "kubernetes.container_name"
+			}]
+			options: "*": {
+				description: "The path of the field holding the value of the tag."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	tls: {
+		description: "Configures the TLS options for incoming/outgoing connections."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			cipher_suites: {
+				description: """
+					Sets the list of allowed cipher suites, as OpenSSL names.
+
+					The TLS 1.3 cipher suites, whose names start with `TLS_`, and the cipher suites of the previous versions are set
+					separately: when none of either kind is listed, the default ones are kept for the corresponding versions. In FIPS
+					mode, only the FIPS-approved cipher suites can be listed, and they are the default ones. FIPS builds of Vector don't
+					support listing TLS 1.3 cipher suites.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crl_file: {
+				description: """
+					Absolute path to a certificate revocation list (CRL) file.
+
+					The revocation of all the certificates of the chain presented by the peer is checked against the lists of the
+					file, which must be in the DER or PEM format. Connections with peers presenting a revoked certificate, or a
+					certificate issued by a CA without revocation list, are refused. This is not supported by FIPS builds of Vector.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: examples: ["/path/to/revocation_list.crl"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			min_tls_version: {
+				description: """
+					The minimum version of the TLS protocol.
+
+					Defaults to the minimum version supported by OpenSSL, or to `TLSv1.2` in FIPS mode.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: enum: {
+					"TLSv1.0": "TLS 1.0."
+					"TLSv1.1": "TLS 1.1."
+					"TLSv1.2": "TLS 1.2."
+					"TLSv1.3": "TLS 1.3."
+				}
+			}
+			pinned_public_keys: {
+				description: """
+					Pins the public keys of the certificates presented by the remote host.
+
+					When set, the certificate chain presented by the remote host must hold a certificate, either the leaf
+					certificate or a CA certificate, whose public key is pinned. The public keys are pinned by the base64 encoded
+					SHA-256 hash of their DER encoded SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+
+					This has no effect unless `verify_certificate` is enabled.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["sha256//YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg="]
+			}
+			spiffe: {
+				description: """
+					Uses the SPIFFE workload identity fetched from the SPIFFE Workload API.
+
+					The identity certificate and the trust bundle are rotated without restarting Vector, as the
+					SPIFFE agent issues new SVIDs. This can't be used with `crt_file` and `key_file`.
+
+					As the peers are identified by their SPIFFE ID rather than by their hostname,
+					`verify_hostname` defaults to `false` when this is set.
+					"""
+				required: false
+				type: object: options: {
+					allowed_ids: {
+						description: """
+							The SPIFFE IDs allowed to connect to, or be connected from.
+
+							If empty, all the peers presenting an SVID issued by the trust bundle of the workload are
+							allowed.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["spiffe://example.org/vector/aggregator"]
+						}
+					}
+					endpoint_socket: {
+						description: """
+							The address of the SPIFFE Workload API.
+
+							Must be a Unix socket address, in the form `unix:///path/to/agent.sock`. If not set, the
+							address is read from the `SPIFFE_ENDPOINT_SOCKET` environment variable.
+							"""
+						required: false
+						type: string: examples: ["unix:///run/spire/sockets/agent.sock"]
+					}
+				}
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_ocsp_stapling: {
+				description: """
+					Requires the remote host to staple a valid OCSP response to its certificate.
+
+					The stapled OCSP response must report the certificate presented by the remote host as good, which prevents
+					connecting to hosts whose certificate has been revoked. This is not supported by FIPS builds of Vector.
+
+					Only relevant for outgoing connections.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
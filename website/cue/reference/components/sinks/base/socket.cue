@@ -27,6 +27,49 @@ base: components: sinks: socket: configuration: {
 			type: bool: {}
 		}
 	}
+	ack_protocol: {
+		description: """
+			Application-level acknowledgement settings.
+
+			When set, events are sent in batches, each prefixed with its length as a big-endian unsigned
+			32-bit integer. The peer must respond to each batch with an acknowledgement token, prefixed with
+			its length in the same way, before the next batch is sent. Events are only finalized once their
+			batch is acknowledged, and batches that aren't acknowledged in time are sent again over a new
+			connection.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: {
+			max_events: {
+				description: "The maximum number of events sent in each batch."
+				required:    false
+				type: uint: {
+					default: 1000
+					unit:    "events"
+				}
+			}
+			timeout_secs: {
+				description: "How long to wait for the acknowledgement of a batch before sending it again, in seconds."
+				required:    false
+				type: uint: {
+					default: 30
+					unit:    "seconds"
+				}
+			}
+			token: {
+				description: """
+					The token the peer responds with to acknowledge a batch.
+
+					Any other response rejects the events of the batch.
+					"""
+				required: false
+				type: string: {
+					default: "ack"
+					examples: ["ok"]
+				}
+			}
+		}
+	}
 	address: {
 		description: """
 			The address to connect to.
@@ -39,6 +82,59 @@ base: components: sinks: socket: configuration: {
 		required:      true
 		type: string: examples: ["92.12.333.224:5000", "https://somehost:5000"]
 	}
+	bind_address: {
+		description: """
+			The local IP address to send from.
+
+			On hosts with several addresses, this controls the source address of the connections. Only
+			addresses of the same family are connected to.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: string: examples: ["192.168.0.10"]
+	}
+	bind_interface: {
+		description: """
+			The network interface to send through.
+
+			If set, the `SO_BINDTODEVICE` option is set on the socket. Only supported on Linux.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: string: examples: ["eth1"]
+	}
+	connections: {
+		description: """
+			The number of connections to send events over concurrently.
+
+			Each event is sent over the connection with the fewest events waiting to be sent, so that a
+			slow connection does not hold back the others. The order of events is only preserved with a
+			single connection. This does not apply when application-level acknowledgements are enabled.
+
+			Defaults to a single connection.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: examples: [4]
+	}
+	dns_refresh_secs: {
+		description: """
+			How often to resolve the address again while connected, in seconds.
+
+			When the resolved IP addresses change, for example after a DNS-based failover, the
+			connections are closed and established again to the new addresses. This does not apply when
+			connecting through a proxy.
+
+			Regardless of this setting, new connections are made to each of the resolved addresses in
+			turn.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: {
+			examples: [60]
+			unit: "seconds"
+		}
+	}
 	encoding: {
 		description: "Configures how events are encoded into raw bytes."
 		required:    true
@@ -209,14 +305,40 @@ base: components: sinks: socket: configuration: {
 			}
 		}
 	}
+	idle_timeout_secs: {
+		description: """
+			The amount of time a connection can stay idle before it is closed, in seconds.
+
+			Firewalls and load balancers may silently drop connections that are idle for a while, which
+			causes the first events sent afterwards to be lost. When set, idle connections are closed,
+			and a new connection is established once there are events to send again.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: {
+			examples: [300]
+			unit: "seconds"
+		}
+	}
 	keepalive: {
 		description:   "TCP keepalive settings for socket-based components."
 		relevant_when: "mode = \"tcp\""
 		required:      false
-		type: object: options: time_secs: {
-			description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
-			required:    false
-			type: uint: unit: "seconds"
+		type: object: options: {
+			interval_secs: {
+				description: """
+					The time to wait between TCP keepalive probes, once they started to be sent.
+
+					Not supported on all platforms, on which the default of the operating system is used.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			time_secs: {
+				description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
+				required:    false
+				type: uint: unit: "seconds"
+			}
 		}
 	}
 	mode: {
@@ -228,6 +350,17 @@ base: components: sinks: socket: configuration: {
 			unix: "Send over a Unix domain socket (UDS)."
 		}
 	}
+	nodelay: {
+		description: """
+			Whether or not to disable Nagle's algorithm on the connection.
+
+			If set, the `TCP_NODELAY` option is set on the socket, so that small writes are sent
+			right away instead of being buffered until more data is written.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: bool: default: false
+	}
 	path: {
 		description: """
 			The Unix socket path.
@@ -238,6 +371,63 @@ base: components: sinks: socket: configuration: {
 		required:      true
 		type: string: examples: ["/path/to/socket"]
 	}
+	reconnect: {
+		description: """
+			Reconnection settings.
+
+			When the connection can't be established, attempts are retried after an exponentially
+			increasing delay.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: {
+			backoff_factor: {
+				description: "The multiplier applied to the delay after each failed reconnection attempt."
+				required:    false
+				type: uint: default: 2
+			}
+			initial_backoff_ms: {
+				description: "The delay before the first reconnection attempt, in milliseconds."
+				required:    false
+				type: uint: {
+					default: 500
+					unit:    "milliseconds"
+				}
+			}
+			jitter_mode: {
+				description: """
+					The jitter applied to the delay between reconnection attempts.
+
+					Setting it spreads the reconnections of many sinks recovering from the same failure.
+					"""
+				required: false
+				type: string: {
+					default: "none"
+					enum: {
+						full: """
+							Full jitter.
+
+							Retries wait for a random delay between zero and the backoff, which spreads the retries of
+							many clients recovering from the same failure instead of having them retry in lockstep.
+							"""
+						none: """
+							No jitter.
+
+							Retries wait for the full backoff.
+							"""
+					}
+				}
+			}
+			max_backoff_secs: {
+				description: "The maximum delay between reconnection attempts, in seconds."
+				required:    false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+		}
+	}
 	send_buffer_bytes: {
 		description: """
 			The size of the socket's send buffer.
@@ -253,6 +443,21 @@ base: components: sinks: socket: configuration: {
 			unit: "bytes"
 		}
 	}
+	send_timeout_secs: {
+		description: """
+			How long a write can be stalled before the connection is closed, in seconds.
+
+			A peer which stops reading without closing the connection would otherwise block the sink
+			indefinitely. When set, the events being written are considered as failed, and a new
+			connection is established to send the next events.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: {
+			examples: [30]
+			unit: "seconds"
+		}
+	}
 	tls: {
 		description:   "Configures the TLS options for incoming/outgoing connections."
 		relevant_when: "mode = \"tcp\""
@@ -277,6 +482,33 @@ base: components: sinks: socket: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			cipher_suites: {
+				description: """
+					Sets the list of allowed cipher suites, as OpenSSL names.
+
+					The TLS 1.3 cipher suites, whose names start with `TLS_`, and the cipher suites of the previous versions are set
+					separately: when none of either kind is listed, the default ones are kept for the corresponding versions. In FIPS
+					mode, only the FIPS-approved cipher suites can be listed, and they are the default ones. FIPS builds of Vector don't
+					support listing TLS 1.3 cipher suites.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crl_file: {
+				description: """
+					Absolute path to a certificate revocation list (CRL) file.
+
+					The revocation of all the certificates of the chain presented by the peer is checked against the lists of the
+					file, which must be in the DER or PEM format. Connections with peers presenting a revoked certificate, or a
+					certificate issued by a CA without revocation list, are refused. This is not supported by FIPS builds of Vector.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: examples: ["/path/to/revocation_list.crl"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -317,6 +549,72 @@ base: components: sinks: socket: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			min_tls_version: {
+				description: """
+					The minimum version of the TLS protocol.
+
+					Defaults to the minimum version supported by OpenSSL, or to `TLSv1.2` in FIPS mode.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: enum: {
+					"TLSv1.0": "TLS 1.0."
+					"TLSv1.1": "TLS 1.1."
+					"TLSv1.2": "TLS 1.2."
+					"TLSv1.3": "TLS 1.3."
+				}
+			}
+			pinned_public_keys: {
+				description: """
+					Pins the public keys of the certificates presented by the remote host.
+
+					When set, the certificate chain presented by the remote host must hold a certificate, either the leaf
+					certificate or a CA certificate, whose public key is pinned. The public keys are pinned by the base64 encoded
+					SHA-256 hash of their DER encoded SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+
+					This has no effect unless `verify_certificate` is enabled.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["sha256//YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg="]
+			}
+			spiffe: {
+				description: """
+					Uses the SPIFFE workload identity fetched from the SPIFFE Workload API.
+
+					The identity certificate and the trust bundle are rotated without restarting Vector, as the
+					SPIFFE agent issues new SVIDs. This can't be used with `crt_file` and `key_file`.
+
+					As the peers are identified by their SPIFFE ID rather than by their hostname,
+					`verify_hostname` defaults to `false` when this is set.
+					"""
+				required: false
+				type: object: options: {
+					allowed_ids: {
+						description: """
+							The SPIFFE IDs allowed to connect to, or be connected from.
+
+							If empty, all the peers presenting an SVID issued by the trust bundle of the workload are
+							allowed.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["spiffe://example.org/vector/aggregator"]
+						}
+					}
+					endpoint_socket: {
+						description: """
+							The address of the SPIFFE Workload API.
+
+							Must be a Unix socket address, in the form `unix:///path/to/agent.sock`. If not set, the
+							address is read from the `SPIFFE_ENDPOINT_SOCKET` environment variable.
+							"""
+						required: false
+						type: string: examples: ["unix:///run/spire/sockets/agent.sock"]
+					}
+				}
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -347,6 +645,30 @@ base: components: sinks: socket: configuration: {
 				required: false
 				type: bool: {}
 			}
+			verify_ocsp_stapling: {
+				description: """
+					Requires the remote host to staple a valid OCSP response to its certificate.
+
+					The stapled OCSP response must report the certificate presented by the remote host as good, which prevents
+					connecting to hosts whose certificate has been revoked. This is not supported by FIPS builds of Vector.
+
+					Only relevant for outgoing connections.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+	unix_mode: {
+		description:   "The type of Unix socket."
+		relevant_when: "mode = \"unix\""
+		required:      false
+		type: string: {
+			default: "stream"
+			enum: {
+				datagram: "A datagram socket (`SOCK_DGRAM`), to which each frame is sent as a datagram."
+				stream:   "A stream socket (`SOCK_STREAM`), to which a connection is established."
+			}
 		}
 	}
 }
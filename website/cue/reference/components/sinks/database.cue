@@ -0,0 +1,156 @@
+package metadata
+
+components: sinks: database: {
+	title: "Database"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				[PostgreSQL](\(urls.postgresql)) version `>= 9.5` is required for the `upsert_keys` option.
+				""",
+			"""
+				[MySQL](\(urls.mysql)) version `>= 5.7` is required, for its `JSON` columns.
+				""",
+		]
+		warnings: []
+		notices: [
+			"""
+				Only PostgreSQL and MySQL, and servers compatible with their wire protocols, are supported.
+				The server is chosen by the scheme of the `endpoint`.
+				""",
+			"""
+				Client certificates are not supported with MySQL.
+				""",
+		]
+	}
+
+	configuration: base.components.sinks.database.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		column_mapping: {
+			title: "Column mapping"
+			body: """
+				Each event is inserted as a row, filling the `columns` with the values of the mapped fields,
+				or `NULL` when an event does not have the field. Values are converted to the types of the
+				columns by the server, so that, for example, a timestamp is inserted into a `TIMESTAMPTZ`
+				column and an object into a `JSONB` column.
+
+				When no `columns` are mapped, the columns of the table are queried on the first insert, and
+				filled with the top-level fields of the same name. Columns with a default value, such as
+				identity columns, are left to their default.
+				"""
+		}
+		auto_create: {
+			title: "Creating the table"
+			body: """
+				With `auto_create` enabled, the table is created if it does not exist before the first
+				insert. The type of each column is inferred from the first value of the mapped field in
+				the first batch of events:
+
+				| Value     | PostgreSQL column type | MySQL column type |
+				|:----------|:-----------------------|:------------------|
+				| Integer   | `BIGINT`               | `BIGINT`          |
+				| Float     | `DOUBLE PRECISION`     | `DOUBLE`          |
+				| Boolean   | `BOOLEAN`              | `BOOLEAN`         |
+				| Timestamp | `TIMESTAMPTZ`          | `DATETIME(6)`     |
+				| Object    | `JSONB`                | `JSON`            |
+				| Array     | `JSONB`                | `JSON`            |
+				| Other     | `TEXT`                 | `TEXT`            |
+
+				The `upsert_keys`, if any, are used as the primary key of the table. As MySQL cannot index
+				whole text or JSON columns, its key columns of those types are created as `VARCHAR(255)`.
+				Timestamps are inserted into MySQL in UTC.
+				"""
+		}
+		upserts: {
+			title: "Upserts"
+			body: """
+				With `upsert_keys` set, rows conflicting with an existing row on those columns update the
+				other columns of the existing row rather than failing the insert. The columns must have a
+				unique constraint, such as a primary key. With MySQL, any unique constraint of the table
+				is used, whatever the `upsert_keys`. As a single statement cannot update the same row
+				twice, only the last event of each key is kept within a batch.
+				"""
+		}
+		batching: {
+			title: "Batched inserts"
+			body: """
+				Each batch of events is inserted with a single statement, in a transaction of its own, over
+				a pool of up to `pool_size` connections. Connections are opened when needed, and replaced
+				when they are closed by the server. With MySQL, batches with more values than a statement
+				can hold are split between several statements of the same transaction.
+				"""
+		}
+		retries: {
+			title: "Retries"
+			body: """
+				Inserts failing because of the connection, a serialization failure or deadlock, or a
+				server running out of resources are retried. Inserts rejected because of the rows
+				themselves, such as a constraint violation or a value of the wrong type, are not, and
+				their events are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -73,6 +73,19 @@ components: sinks: file: {
 				disk before acknowledging the events.
 				"""
 		}
+
+		rotation: {
+			title: "File Rotation"
+			body: """
+				With the `rotation` option, Vector rotates files by
+				itself once they reach `max_bytes` or `max_age_secs`,
+				so no coordination with external tools such as
+				`logrotate` is required. Rotated files are renamed in
+				place, can be compressed in the background, and are
+				deleted once they exceed `max_files` or
+				`retention_secs`.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
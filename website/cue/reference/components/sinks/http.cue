@@ -92,6 +92,30 @@ components: sinks: http: {
 		traces: true
 	}
 
+	how_it_works: {
+		http3: {
+			title: "HTTP/3"
+			body: """
+				When `http3.enabled` is set, requests are sent over HTTP/3, which runs over QUIC rather
+				than TCP. QUIC avoids head-of-line blocking and recovers faster from packet loss, which
+				substantially improves delivery throughput on lossy or high-latency links, such as
+				edge deployments.
+
+				One QUIC connection is kept open per server. When reconnecting to a server it already
+				connected to, Vector sends the first requests as 0-RTT early data, saving a round trip.
+				Early data can be replayed, so servers that can't tolerate duplicate requests should
+				reject it, or `http3.zero_rtt` should be disabled.
+
+				TLS is always used with HTTP/3. The `tls` options are supported, except disabling
+				certificate or hostname verification, `alpn_protocols` and `key_pass`.
+
+				HTTP/3 is only available in builds of Vector with the `http3` feature, which is not
+				enabled by default. It can't be used in FIPS mode, as QUIC is provided by rustls, whose
+				cryptography doesn't come from the validated module.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
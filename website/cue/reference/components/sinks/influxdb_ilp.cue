@@ -0,0 +1,110 @@
+package metadata
+
+components: sinks: influxdb_ilp: {
+	title: "InfluxDB Line Protocol"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["InfluxData", "QuestDB"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			send_buffer_bytes: enabled: true
+			keepalive: enabled:         true
+			request: enabled:           false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.questdb
+
+				interface: {
+					socket: {
+						api: {
+							title: "InfluxDB line protocol"
+							url:   urls.questdb_ilp
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.influxdb_ilp.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		mapping: {
+			title: "Mapping log fields"
+			body: """
+				Each event is written as a line of the [InfluxDB line protocol](\(urls.influxdb_line_protocol)),
+				in the `measurement` rendered from the event:
+
+				| Field                       | Line protocol |
+				|:----------------------------|:--------------|
+				| The fields mapped in `tags` | tag           |
+				| The timestamp               | timestamp     |
+				| The other fields            | field         |
+
+				When `fields` is set, only the fields mapped there are written. Integers are written with
+				the `i` suffix, floats and booleans as is, and all other values as strings. Events
+				without any field to write are dropped.
+				"""
+		}
+		timestamps: {
+			title: "Timestamps"
+			body: """
+				Timestamps are written in nanoseconds, the default precision of the line protocol over
+				TCP. Events without a timestamp are written with the time they are encoded at.
+				"""
+		}
+		questdb: {
+			title: "QuestDB"
+			body: """
+				[QuestDB](\(urls.questdb)) accepts the line protocol over TCP on port `9009` by default.
+				The measurement is the name of the table, created on the first write if it does not
+				exist, and tags are written to `SYMBOL` columns.
+
+				As the line protocol over TCP has no responses, lines rejected by the server are not
+				reported, and events written while the connection is lost may be dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:  components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total: components.sources.internal_metrics.output.metrics.component_sent_events_total
+		connection_errors_total:     components.sources.internal_metrics.output.metrics.connection_errors_total
+	}
+}
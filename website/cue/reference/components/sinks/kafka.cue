@@ -69,20 +69,38 @@ components: sinks: kafka: {
 	how_it_works: components._kafka.how_it_works
 
 	telemetry: metrics: {
-		component_sent_events_total:         components.sources.internal_metrics.output.metrics.component_sent_events_total
-		component_sent_event_bytes_total:    components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
-		component_sent_bytes_total:          components.sources.internal_metrics.output.metrics.component_sent_bytes_total
-		events_discarded_total:              components.sources.internal_metrics.output.metrics.events_discarded_total
-		processing_errors_total:             components.sources.internal_metrics.output.metrics.processing_errors_total
-		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
-		kafka_queue_messages_bytes:          components.sources.internal_metrics.output.metrics.kafka_queue_messages_bytes
-		kafka_requests_total:                components.sources.internal_metrics.output.metrics.kafka_requests_total
-		kafka_requests_bytes_total:          components.sources.internal_metrics.output.metrics.kafka_requests_bytes_total
-		kafka_responses_total:               components.sources.internal_metrics.output.metrics.kafka_responses_total
-		kafka_responses_bytes_total:         components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
-		kafka_produced_messages_total:       components.sources.internal_metrics.output.metrics.kafka_produced_messages_total
-		kafka_produced_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
-		kafka_consumed_messages_total:       components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
-		kafka_consumed_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		component_sent_events_total:                components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total:           components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_sent_bytes_total:                 components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		events_discarded_total:                     components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total:                    components.sources.internal_metrics.output.metrics.processing_errors_total
+		kafka_queue_messages:                       components.sources.internal_metrics.output.metrics.kafka_queue_messages
+		kafka_queue_messages_bytes:                 components.sources.internal_metrics.output.metrics.kafka_queue_messages_bytes
+		kafka_requests_total:                       components.sources.internal_metrics.output.metrics.kafka_requests_total
+		kafka_requests_bytes_total:                 components.sources.internal_metrics.output.metrics.kafka_requests_bytes_total
+		kafka_responses_total:                      components.sources.internal_metrics.output.metrics.kafka_responses_total
+		kafka_responses_bytes_total:                components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
+		kafka_produced_messages_total:              components.sources.internal_metrics.output.metrics.kafka_produced_messages_total
+		kafka_produced_messages_bytes_total:        components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
+		kafka_consumed_messages_total:              components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
+		kafka_consumed_messages_bytes_total:        components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		kafka_broker_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_broker_queue_messages
+		kafka_broker_inflight_requests:             components.sources.internal_metrics.output.metrics.kafka_broker_inflight_requests
+		kafka_broker_queue_latency_seconds:         components.sources.internal_metrics.output.metrics.kafka_broker_queue_latency_seconds
+		kafka_broker_request_queue_latency_seconds: components.sources.internal_metrics.output.metrics.kafka_broker_request_queue_latency_seconds
+		kafka_broker_rtt_seconds:                   components.sources.internal_metrics.output.metrics.kafka_broker_rtt_seconds
+		kafka_broker_requests_total:                components.sources.internal_metrics.output.metrics.kafka_broker_requests_total
+		kafka_broker_request_errors_total:          components.sources.internal_metrics.output.metrics.kafka_broker_request_errors_total
+		kafka_broker_request_retries_total:         components.sources.internal_metrics.output.metrics.kafka_broker_request_retries_total
+		kafka_broker_request_timeouts_total:        components.sources.internal_metrics.output.metrics.kafka_broker_request_timeouts_total
+		kafka_broker_responses_total:               components.sources.internal_metrics.output.metrics.kafka_broker_responses_total
+		kafka_broker_response_errors_total:         components.sources.internal_metrics.output.metrics.kafka_broker_response_errors_total
+		kafka_broker_connects_total:                components.sources.internal_metrics.output.metrics.kafka_broker_connects_total
+		kafka_broker_disconnects_total:             components.sources.internal_metrics.output.metrics.kafka_broker_disconnects_total
+		kafka_partition_queue_messages:             components.sources.internal_metrics.output.metrics.kafka_partition_queue_messages
+		kafka_partition_transmit_queue_messages:    components.sources.internal_metrics.output.metrics.kafka_partition_transmit_queue_messages
+		kafka_partition_inflight_messages:          components.sources.internal_metrics.output.metrics.kafka_partition_inflight_messages
+		kafka_partition_produced_messages_total:    components.sources.internal_metrics.output.metrics.kafka_partition_produced_messages_total
+		kafka_unknown_topic_events_total:           components.sources.internal_metrics.output.metrics.kafka_unknown_topic_events_total
 	}
 }
@@ -145,6 +145,20 @@ components: sinks: loki: {
 				"""
 		}
 
+		rate_limiting: {
+			title: "Rate Limiting and Rejected Entries"
+			body: """
+				When Loki responds with `429 Too Many Requests`, further requests
+				for the same tenant are delayed with an exponential backoff that
+				honors the `Retry-After` header, while requests for other tenants
+				are sent as usual.
+
+				When Loki rejects some entries of a request for being out of
+				order, it still stores the others. Such requests are not retried;
+				the rejected entries are counted as dropped instead.
+				"""
+		}
+
 		request_encoding: {
 			title: "Request Encoding"
 			body: """
@@ -163,6 +177,9 @@ components: sinks: loki: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		open_partitions:                  components.sources.internal_metrics.output.metrics.open_partitions
+		partitions_evicted_total:         components.sources.internal_metrics.output.metrics.partitions_evicted_total
+		partitions_opened_total:          components.sources.internal_metrics.output.metrics.partitions_opened_total
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
 		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
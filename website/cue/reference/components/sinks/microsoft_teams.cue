@@ -0,0 +1,103 @@
+package metadata
+
+components: sinks: microsoft_teams: {
+	title: "Microsoft Teams"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["Microsoft Teams"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.microsoft_teams
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.microsoft_teams.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		cards: {
+			title: "Adaptive cards"
+			body: """
+				Events are posted to a Microsoft Teams [incoming webhook](\(urls.microsoft_teams_incoming_webhook))
+				as [adaptive cards](\(urls.adaptive_cards)): a title rendered from `title`, if set, a text rendered
+				from `text`, and a list of facts holding the severity of the event followed by the ones rendered
+				from `facts`.
+
+				Events whose templates cannot be rendered are dropped.
+				"""
+		}
+
+		severity: {
+			title: "Severity"
+			body: """
+				The severity of each notification is read from the `severity.field` field of its event. Its value
+				is looked up in `severity.mapping`, so that the levels of an application can be mapped to
+				`critical`, `error`, `warning` or `info`, and otherwise used as is if it already is one of them.
+				Events without a severity that can be mapped get the `severity.default` one.
+
+				The title of the cards is colored after their severity.
+				"""
+		}
+
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				Each event is sent as its own notification. To stay within the limits of the service, requests are
+				rate limited to four cards per second by default. Set `request.rate_limit_num` and
+				`request.rate_limit_duration_secs` to use another limit. Requests failing with a `429` or a `5xx`
+				status are retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -0,0 +1,105 @@
+package metadata
+
+components: sinks: pagerduty: {
+	title: "PagerDuty"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["PagerDuty"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.pagerduty
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.pagerduty.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		alerts: {
+			title: "Alerts"
+			body: """
+				Events are sent as alerts to the PagerDuty [Events API v2](\(urls.pagerduty_events_api_v2)), for
+				the service of the `routing_key`. Triggered alerts carry a summary, a source and, if set, a
+				component, group and class, all rendered from templates, along with the timestamp of the event
+				and its fields as custom details. Alerts with the same `dedup_key` are grouped into the same
+				incident.
+
+				Set `event_action` to `acknowledge` or `resolve` to acknowledge or resolve the incident of the
+				`dedup_key` of each event instead, which then requires a `dedup_key`.
+
+				Events whose templates cannot be rendered are dropped.
+				"""
+		}
+
+		severity: {
+			title: "Severity"
+			body: """
+				The severity of each notification is read from the `severity.field` field of its event. Its value
+				is looked up in `severity.mapping`, so that the levels of an application can be mapped to
+				`critical`, `error`, `warning` or `info`, and otherwise used as is if it already is one of them.
+				Events without a severity that can be mapped get the `severity.default` one.
+				"""
+		}
+
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				Each event is sent as its own notification. To stay within the limits of the service, requests are
+				rate limited to 120 alerts per minute by default. Set `request.rate_limit_num` and
+				`request.rate_limit_duration_secs` to use another limit. Requests failing with a `429` or a `5xx`
+				status are retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -0,0 +1,102 @@
+package metadata
+
+components: sinks: smtp: {
+	title: "SMTP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   100
+				timeout_secs: 60.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.smtp
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.smtp.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		digests: {
+			title: "Digests"
+			body: """
+				The events of a batch are sent as a single plain text email, a digest, so that a burst of alerts
+				does not flood the recipients. Its subject is rendered from the first event of the batch, followed
+				by the number of other events it holds, and its body holds the body rendered for each event.
+				Tune `batch.max_events` and `batch.timeout_secs` to control how many events are grouped, setting
+				`batch.max_events` to `1` sends an email per event.
+
+				Events whose subject or body templates cannot be rendered are dropped.
+				"""
+		}
+
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				The number of emails sent can be limited with the `request.rate_limit_num` and
+				`request.rate_limit_duration_secs` options, holding back the following digests until the window
+				allows them, for example to stay within the sending limits of a mail provider.
+				"""
+		}
+
+		transport_security: {
+			title: "Transport security"
+			body: """
+				By default, the connection is upgraded to TLS with the `STARTTLS` command, on port 587. Set `tls`
+				to `tls` to connect with TLS from the start, on port 465, or to `none` to send emails in clear
+				text, on port 25. Server certificates are verified against the system's trusted roots.
+
+				Emails rejected by the server with a permanent (5xx) reply are not retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -67,6 +67,26 @@ components: sinks: socket: {
 		traces:  false
 	}
 
+	how_it_works: {
+		ack_protocol: {
+			title: "Application-level acknowledgements"
+			body: """
+				Plain TCP gives no guarantee that the events written to a connection were received
+				by the peer, so the sink delivers them on a best-effort basis by default. When
+				`ack_protocol` is set, the sink sends events in batches of up to `max_events` events,
+				each prefixed with its length in bytes as a big-endian unsigned 32-bit integer. The peer
+				must respond to each batch with the configured `token`, prefixed with its length in
+				the same way.
+
+				Events are only acknowledged once their batch is, so end-to-end acknowledgements
+				can be used with custom receivers. A batch that isn't acknowledged within
+				`timeout_secs` seconds, or whose connection fails, is sent again over a new
+				connection, so the peer may receive duplicates. Any response other than the token
+				rejects the events of the batch.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
 		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
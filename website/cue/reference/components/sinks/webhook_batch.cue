@@ -0,0 +1,138 @@
+package metadata
+
+components: sinks: webhook_batch: {
+	title: "Webhook Batch"
+
+	classes: {
+		commonly_used: false
+		service_providers: []
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zlib"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: {
+					name:     "HTTP"
+					thing:    "an \(name) API"
+					url:      urls.http_server
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.webhook_batch.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		partitioning: {
+			title: "Partitioning"
+			body: """
+				The `uri`, the values of the `headers` and the `envelope.template` are all templates. Events are
+				batched by the values rendered from them, so that every request is sent to the URI, with the
+				headers and in the envelope rendered for all of its events. Events whose templates cannot be
+				rendered are dropped.
+
+				As there is no single endpoint to check, the sink has no healthcheck.
+				"""
+		}
+
+		body_modes: {
+			title: "Body modes"
+			body: """
+				With the default `json_array` body mode, the events of a batch are sent as a JSON array, with an
+				`application/json` content type. With the `ndjson` body mode, they are sent as newline-delimited
+				JSON objects, with an `application/x-ndjson` content type.
+				"""
+		}
+
+		envelope: {
+			title: "Envelope"
+			body: """
+				Some APIs expect the events to be wrapped in an object holding request-level fields. The
+				`envelope.template` is rendered to that JSON object, and the array of events is inserted in it
+				under the `envelope.events_field` field. The envelope is only supported with the `json_array`
+				body mode.
+
+				The rendered values are not escaped, so fields holding quotes or backslashes produce an invalid
+				envelope, and the requests of such batches fail to be built.
+				"""
+		}
+
+		retries: {
+			title: "Retries"
+			body: """
+				Requests failing with a `429` or a `5xx` status, except `501`, or because of a network error are
+				retried. Requests failing with other statuses are not.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -0,0 +1,102 @@
+package metadata
+
+components: sinks: websocket_server: {
+	_port: 8080
+
+	title: "WebSocket Server"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "expose"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		exposes: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+
+			for: {
+				service: services.websocket
+
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      _port
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.websocket_server.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		broadcasting: {
+			title: "Broadcasting"
+			body: """
+				Each event is encoded once, and sent as a message to all the clients connected at the time.
+				Events are not kept for the clients connecting later on, and events received while no
+				client is connected are dropped, so that this sink suits live-tail UIs rather than
+				delivery. Messages are sent as text if the encoded event is valid UTF-8, and as binary
+				otherwise.
+
+				Each client is buffered up to `client_buffer_size` messages. A client falling further
+				behind skips the oldest messages, without slowing down the sink or the other clients.
+				"""
+		}
+		filtering: {
+			title: "Filtering"
+			body: """
+				Clients can subscribe to a subset of the events with the query parameters of the URI they
+				connect to. Each query parameter names the path of a field, and an event is sent to the
+				client if, for each of the fields, its value is one of the values given for that field.
+
+				For example, a client connecting to `ws://localhost:8080/?service=api&service=web&level=error`
+				receives the events whose `service` field is either `api` or `web`, and whose `level` field
+				is `error`. Clients without query parameters receive all the events.
+				"""
+		}
+		authentication: {
+			title: "Authentication"
+			body: """
+				With `auth` set, clients must send the matching `Authorization` header, and connections
+				without it are rejected with a `401 Unauthorized` response. Browsers do not allow setting
+				headers on WebSocket connections, so UIs served to browsers need a proxy adding the header
+				when authentication is enabled.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		open_connections:                 components.sources.internal_metrics.output.metrics.open_connections
+		connection_established_total:     components.sources.internal_metrics.output.metrics.connection_established_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -6,6 +6,12 @@ components: sources: [Name=string]: {
 	features: _
 
 	configuration: {
+		coordination: base.components.sources.configuration.coordination
+		enabled:      base.components.sources.configuration.enabled
+		ha_mode:      base.components.sources.configuration.ha_mode
+		log_schema:   base.components.sources.configuration.log_schema
+		tenancy:      base.components.sources.configuration.tenancy
+
 		if features.collect != _|_ {
 			if features.collect.proxy != _|_ {
 				if features.collect.proxy.enabled {
@@ -215,6 +221,31 @@ components: sources: [Name=string]: {
 									}
 								}
 							}
+							engine: {
+								description:   "The engine used to parse JSON."
+								relevant_when: "codec = \"json\""
+								required:      false
+								common:        false
+								type: string: {
+									default: "serde"
+									enum: {
+										serde: """
+											Parses JSON with [`serde_json`][serde_json].
+
+											[serde_json]: https://docs.rs/serde_json
+											"""
+										simd: """
+											Parses JSON with [`simd-json`][simd_json], which is faster on large volumes of JSON by
+											making use of the SIMD instructions of the CPU.
+
+											This engine is only available when Vector is built with the `codecs-simd-json` feature.
+											Otherwise, the `serde` engine is used instead.
+
+											[simd_json]: https://docs.rs/simd-json
+											"""
+									}
+								}
+							}
 						}
 					}
 				}
@@ -279,6 +310,15 @@ components: sources: [Name=string]: {
 						type: object: {
 							examples: []
 							options: {
+								interval_secs: {
+									common:      false
+									description: "The time between TCP keepalive probes, once they started to be sent. Not supported on all platforms, on which the default of the operating system is used."
+									required:    false
+									type: uint: {
+										default: null
+										unit:    "seconds"
+									}
+								}
 								time_secs: {
 									common:      false
 									description: "The time a connection needs to be idle before sending TCP keepalive probes."
@@ -358,6 +398,10 @@ components: sources: [Name=string]: {
 			body:  """
 				  Vector uses [OpenSSL](\(urls.openssl)) for TLS protocols. You can
 				  adjust TLS behavior via the `tls.*` options.
+
+				  The `ca_file`, `crt_file` and `key_file` files are reloaded when they
+				  change, without restarting Vector: new connections use the new
+				  certificates, while established connections are kept.
 				  """
 		}
 
@@ -404,6 +448,7 @@ components: sources: [Name=string]: {
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_sent_event_size_bytes:  components.sources.internal_metrics.output.metrics.component_sent_event_size_bytes
 		source_lag_time_seconds:          components.sources.internal_metrics.output.metrics.source_lag_time_seconds
 	}
 }
@@ -0,0 +1,138 @@
+package metadata
+
+components: sources: audit_logs: {
+	title:       "Audit Logs"
+	description: "The audit logs source exposes the audit log of configuration and control operations of the running Vector instance."
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.audit_logs.configuration
+
+	output: logs: record: {
+		description: "A record of a configuration or control operation."
+		fields: {
+			action: {
+				description: "The audited operation."
+				required:    true
+				type: string: {
+					enum: {
+						config_loaded:     "The configuration was loaded when starting."
+						config_reloaded:   "The configuration was reloaded, changing the running topology."
+						tap_started:       "A client of the API started tapping into events."
+						tap_stopped:       "A client of the API stopped tapping into events."
+						secrets_retrieved: "Secrets were retrieved from a secrets backend."
+					}
+				}
+			}
+			config_paths: {
+				description: "The configuration paths, for the `config_loaded` and `config_reloaded` actions."
+				required:    false
+				type: array: items: type: string: examples: ["/etc/vector/vector.toml"]
+			}
+			diff: {
+				description: "The `added`, `changed`, and `removed` `sources`, `transforms`, `sinks`, and `enrichment_tables`, for the `config_loaded` and `config_reloaded` actions."
+				required:    false
+				type: object: {}
+			}
+			session_id: {
+				description: "The identifier of the tap session, for the `tap_started` and `tap_stopped` actions."
+				required:    false
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			outputs_patterns: {
+				description: "The patterns of the tapped component outputs, for the `tap_started` action."
+				required:    false
+				type: array: items: type: string: examples: ["my_source"]
+			}
+			inputs_patterns: {
+				description: "The patterns of the tapped component inputs, for the `tap_started` action."
+				required:    false
+				type: array: items: type: string: examples: ["my_sink"]
+			}
+			backend: {
+				description: "The secrets backend, for the `secrets_retrieved` action."
+				required:    false
+				type: string: examples: ["vault"]
+			}
+			secret_keys: {
+				description: "The names of the retrieved secrets, for the `secrets_retrieved` action. The values of the secrets are never recorded."
+				required:    false
+				type: array: items: type: string: examples: ["api_key"]
+			}
+			success: {
+				description: "Whether the secrets were retrieved, for the `secrets_retrieved` action."
+				required:    false
+				type: bool: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["audit_logs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The exact time the operation was recorded."
+			}
+		}
+	}
+
+	how_it_works: {
+		audit_log_file: {
+			title: "Audit log file"
+			body: """
+				The audit log is also appended, as JSON lines, to the file given with the
+				`--audit-log-path` command-line option or the `VECTOR_AUDIT_LOG_PATH` environment
+				variable. Only the operations recorded while the `audit_logs` source is running are
+				received by it.
+				"""
+		}
+		api_operations: {
+			title: "API operations"
+			body: """
+				The API doesn't expose any mutations, so the tap sessions are its only audited
+				operations.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
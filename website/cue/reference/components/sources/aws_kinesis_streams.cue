@@ -0,0 +1,190 @@
+package metadata
+
+components: sources: aws_kinesis_streams: components._aws & {
+	title: "AWS Kinesis Data Streams"
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_kinesis_data_streams
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.aws_kinesis_streams.configuration & {
+		_aws_include: false
+	}
+
+	output: logs: record: {
+		description: "An individual Kinesis record"
+		fields: {
+			message: {
+				description: "The data of the Kinesis record."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			partition_key: {
+				description: "The partition key of the record."
+				required:    true
+				type: string: {
+					examples: ["7e6a1ecd"]
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the record within its shard."
+				required:    true
+				type: string: {
+					examples: ["49590338271490256608559692538361571095921575989136588898"]
+				}
+			}
+			shard_id: {
+				description: "The ID of the shard the record was read from."
+				required:    true
+				type: string: {
+					examples: ["shardId-000000000000"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_kinesis_streams"]
+				}
+			}
+			stream_name: {
+				description: "The name of the stream the record was read from."
+				required:    true
+				type: string: {
+					examples: ["my-stream"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The approximate time the record was written to the stream."
+			}
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service: "kinesis"
+
+			policies: [
+				{
+					_action: "ListShards"
+				},
+				{
+					_action: "GetShardIterator"
+				},
+				{
+					_action: "GetRecords"
+				},
+				{
+					_action: "DescribeStreamSummary"
+					required_for: ["enhanced fan-out"]
+				},
+				{
+					_action: "DescribeStreamConsumer"
+					required_for: ["enhanced fan-out"]
+				},
+				{
+					_action: "RegisterStreamConsumer"
+					required_for: ["enhanced fan-out"]
+				},
+				{
+					_action: "SubscribeToShard"
+					required_for: ["enhanced fan-out"]
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	how_it_works: {
+		aws_kinesis_streams: {
+			title: "AWS Kinesis Data Streams"
+			body: """
+				The `aws_kinesis_streams` source reads records from every shard of an
+				[AWS Kinesis Data Stream](https://aws.amazon.com/kinesis/data-streams/). Shards are
+				either polled with `GetRecords` requests, or records are pushed to a consumer
+				registered for [enhanced fan-out](https://docs.aws.amazon.com/streams/latest/dev/enhanced-consumers.html),
+				depending on the `consumer` setting.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The sequence number of the last record processed in each shard is persisted in the
+				`data_dir` directory, and reading resumes after it when Vector restarts. When
+				`acknowledgements` are enabled, a record is only checkpointed once it has been
+				delivered by the sinks.
+
+				When `checkpoint_store` is set, the checkpoints are also persisted to Redis, an AWS S3
+				object or an AWS DynamoDB item, and loaded from it when the `data_dir` has none, so
+				that an instance of Vector running on a fresh host resumes where the previous one left
+				off. The shards are not leased between instances though, so a stream should only be
+				read by one instance of the source at a time.
+				"""
+		}
+		resharding: {
+			title: "Resharding"
+			body: """
+				The shards of the stream are listed every `shard_discovery_interval_secs` seconds,
+				picking up the shards created by resharding. To preserve the ordering of the records
+				sharing a partition key, the child shards of a closed shard are only read once all of
+				the records of their parents were processed.
+				"""
+		}
+	}
+}
@@ -129,6 +129,26 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+
+		polling: {
+			title: "Consuming objects without SQS"
+			body:  """
+				When bucket notifications can't be sent to an SQS queue, setting `strategy` to `poll`
+				makes the source look for new objects every `poll.interval_secs` instead, either by
+				listing the keys under `poll.prefix`, or by reading the latest
+				[S3 Inventory](https://docs.aws.amazon.com/AmazonS3/latest/userguide/storage-inventory.html)
+				report of the bucket, which is cheaper for buckets containing many objects.
+
+				The processed objects are persisted in the `data_dir`, so objects are only consumed
+				once, including across restarts. Objects that are modified after being processed
+				aren't consumed again. Only the keys of the objects modified in the last
+				`poll.watermark_delay_secs` are kept, older objects being skipped based on their last
+				modification time.
+
+				The checkpoints are written every 100 objects or 10 seconds, and at the end of each poll,
+				so the objects processed just before Vector crashes may be consumed again.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -142,6 +162,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `poll`"
+				},
 			]
 		},
 		{
@@ -40,6 +40,40 @@ base: components: sources: aws_s3: configuration: {
 				required: true
 				type: string: examples: ["arn:aws:iam::123456789098:role/my_role"]
 			}
+			chained_roles: {
+				description: """
+					The ARNs of further IAM roles to assume, in order, after `assume_role`.
+
+					Each role is assumed with the credentials of the previous one, which allows reaching
+					roles only trusted by another role.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["arn:aws:iam::123456789098:role/other_role"]
+				}
+			}
+			config_file: {
+				description: """
+					Path to the AWS config file.
+
+					If not set, the default config file is used.
+					"""
+				required: false
+				type: string: examples: ["/my/aws/config"]
+			}
+			credential_process: {
+				description: """
+					The command to run to fetch credentials.
+
+					The command must print the credentials in the [`credential_process`][credential_process]
+					format, and is run again when they are about to expire.
+
+					[credential_process]: https://docs.aws.amazon.com/cli/latest/userguide/cli-configure-sourcing-external.html
+					"""
+				required: true
+				type: string: examples: ["/usr/local/bin/fetch-aws-credentials --role vector"]
+			}
 			credentials_file: {
 				description: "Path to the credentials file."
 				required:    true
@@ -70,6 +104,19 @@ base: components: sources: aws_s3: configuration: {
 							unit:    "seconds"
 						}
 					}
+					token_ttl_seconds: {
+						description: """
+							Lifetime of the IMDSv2 session tokens.
+
+							The IMDSv2 hop limit is set on the instance itself, through its `HttpPutResponseHopLimit`
+							metadata option, and must be at least 2 when Vector runs in a container.
+							"""
+						required: false
+						type: uint: {
+							default: 21600
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 			load_timeout_secs: {
@@ -96,6 +143,14 @@ base: components: sources: aws_s3: configuration: {
 					examples: ["develop"]
 				}
 			}
+			refresh_buffer_secs: {
+				description: "How long before they expire the credentials are refreshed, in seconds."
+				required:    false
+				type: uint: {
+					examples: [300]
+					unit: "seconds"
+				}
+			}
 			region: {
 				description: """
 					The [AWS region][aws_region] to send STS requests to.
@@ -113,6 +168,11 @@ base: components: sources: aws_s3: configuration: {
 				required:    true
 				type: string: examples: ["wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY"]
 			}
+			sso_profile: {
+				description: "The name of the SSO profile to use."
+				required:    true
+				type: string: examples: ["develop"]
+			}
 		}
 	}
 	compression: {
@@ -210,6 +270,164 @@ base: components: sources: aws_s3: configuration: {
 			}
 		}
 	}
+	poll: {
+		description: "Configuration options for polling."
+		required:    false
+		type: object: options: {
+			bucket: {
+				description: "The name of the bucket to consume objects from."
+				required:    true
+				type: string: examples: ["my-bucket"]
+			}
+			checkpoint_store: {
+				description: """
+					Checkpoint store configuration.
+
+					Checkpoints are always persisted to the data directory. When a checkpoint store is configured,
+					they are also persisted to it, and loaded from it when none are found in the data directory.
+					This lets an instance of Vector running on a fresh host, such as a Fargate task or a spot
+					instance, resume where the previous instance left off.
+
+					A checkpoint store must not be shared by instances of Vector running at the same time.
+					"""
+				required: false
+				type: object: options: {
+					bucket: {
+						description:   "The S3 bucket name."
+						relevant_when: "type = \"aws_s3\""
+						required:      true
+						type: string: examples: ["my-bucket"]
+					}
+					endpoint: {
+						description: """
+							The Redis URL to connect to.
+
+							The URL must take the form of `protocol://server:port/db` where the `protocol` can either be
+							`redis` or `rediss` for connections secured via TLS.
+							"""
+						relevant_when: "type = \"redis\""
+						required:      true
+						type: string: examples: ["redis://127.0.0.1:6379/0"]
+					}
+					key: {
+						description: "The key under which the checkpoints are stored."
+						required:    true
+						type: string: examples: ["vector:checkpoints:my-agent", "vector/checkpoints/my-agent.json", "my-agent"]
+					}
+					region: {
+						description:   "The AWS region of the target service."
+						relevant_when: "type = \"aws_s3\" or type = \"aws_dynamodb\""
+						required:      false
+						type: string: examples: ["us-east-1"]
+					}
+					table: {
+						description: """
+							The name of the DynamoDB table.
+
+							The table must have a string partition key named `key`, and no sort key. The checkpoints
+							are stored in the binary `checkpoints` attribute of the item.
+							"""
+						relevant_when: "type = \"aws_dynamodb\""
+						required:      true
+						type: string: examples: ["vector-checkpoints"]
+					}
+					type: {
+						description: "The type of checkpoint store."
+						required:    true
+						type: string: enum: {
+							aws_dynamodb: "Store the checkpoints in an item of an AWS DynamoDB table."
+							aws_s3:       "Store the checkpoints in an AWS S3 object."
+							redis:        "Store the checkpoints in Redis."
+						}
+					}
+				}
+			}
+			data_dir: {
+				description: """
+					The directory used to persist the keys of the objects that were processed.
+
+					By default, the global `data_dir` option is used. Make sure the running user has write
+					permissions to this directory.
+					"""
+				required: false
+				type: string: examples: ["/var/lib/vector"]
+			}
+			discovery: {
+				description: "The method used to discover new objects."
+				required:    false
+				type: object: options: {
+					bucket: {
+						description:   "The name of the bucket the inventory reports are delivered to."
+						relevant_when: "type = \"inventory\""
+						required:      true
+						type: string: examples: ["my-inventory-bucket"]
+					}
+					manifest_prefix: {
+						description: """
+							The prefix under which the manifests of the inventory reports are delivered.
+
+							This is usually `<destination prefix>/<source bucket>/<configuration ID>/`.
+							"""
+						relevant_when: "type = \"inventory\""
+						required:      true
+						type: string: examples: ["inventory/my-bucket/daily/"]
+					}
+					type: {
+						description: "The kind of object discovery."
+						required:    false
+						type: string: {
+							default: "list"
+							enum: {
+								inventory: """
+									Objects are discovered by reading the latest [S3 Inventory][inventory] report of the bucket.
+
+									This avoids listing buckets containing many objects. Only reports in the CSV format are
+									supported.
+
+									[inventory]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/storage-inventory.html
+									"""
+								list: "Objects are discovered by listing the keys under `prefix`."
+							}
+						}
+					}
+				}
+			}
+			interval_secs: {
+				description: "How often to look for new objects, in seconds."
+				required:    false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+			prefix: {
+				description: """
+					The prefix of the keys of the objects to consume.
+
+					If not specified, all objects in the bucket are consumed.
+					"""
+				required: false
+				type: string: examples: ["logs/"]
+			}
+			watermark_delay_secs: {
+				description: """
+					How long objects can take to appear in the bucket after their last modification time, in
+					seconds.
+
+					Objects last modified before this delay, and before any object that couldn't be processed,
+					are skipped based on their last modification time, so that only the keys of the objects
+					modified since are persisted. Objects appearing later than this are not consumed, such as
+					multipart uploads taking longer to complete, whose last modification time is the time the
+					upload started.
+					"""
+				required: false
+				type: uint: {
+					default: 3600
+					unit:    "seconds"
+				}
+			}
+		}
+	}
 	region: {
 		description: """
 			The [AWS region][aws_region] of the target service.
@@ -291,6 +509,33 @@ base: components: sources: aws_s3: configuration: {
 						required: false
 						type: string: examples: ["/path/to/certificate_authority.crt"]
 					}
+					cipher_suites: {
+						description: """
+							Sets the list of allowed cipher suites, as OpenSSL names.
+
+							The TLS 1.3 cipher suites, whose names start with `TLS_`, and the cipher suites of the previous versions are set
+							separately: when none of either kind is listed, the default ones are kept for the corresponding versions. In FIPS
+							mode, only the FIPS-approved cipher suites can be listed, and they are the default ones. FIPS builds of Vector don't
+							support listing TLS 1.3 cipher suites.
+
+							Relevant for both incoming and outgoing connections.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+					}
+					crl_file: {
+						description: """
+							Absolute path to a certificate revocation list (CRL) file.
+
+							The revocation of all the certificates of the chain presented by the peer is checked against the lists of the
+							file, which must be in the DER or PEM format. Connections with peers presenting a revoked certificate, or a
+							certificate issued by a CA without revocation list, are refused. This is not supported by FIPS builds of Vector.
+
+							Relevant for both incoming and outgoing connections.
+							"""
+						required: false
+						type: string: examples: ["/path/to/revocation_list.crl"]
+					}
 					crt_file: {
 						description: """
 																Absolute path to a certificate file used to identify this server.
@@ -321,6 +566,72 @@ base: components: sources: aws_s3: configuration: {
 						required: false
 						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 					}
+					min_tls_version: {
+						description: """
+							The minimum version of the TLS protocol.
+
+							Defaults to the minimum version supported by OpenSSL, or to `TLSv1.2` in FIPS mode.
+
+							Relevant for both incoming and outgoing connections.
+							"""
+						required: false
+						type: string: enum: {
+							"TLSv1.0": "TLS 1.0."
+							"TLSv1.1": "TLS 1.1."
+							"TLSv1.2": "TLS 1.2."
+							"TLSv1.3": "TLS 1.3."
+						}
+					}
+					pinned_public_keys: {
+						description: """
+							Pins the public keys of the certificates presented by the remote host.
+
+							When set, the certificate chain presented by the remote host must hold a certificate, either the leaf
+							certificate or a CA certificate, whose public key is pinned. The public keys are pinned by the base64 encoded
+							SHA-256 hash of their DER encoded SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+
+							This has no effect unless `verify_certificate` is enabled.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["sha256//YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg="]
+					}
+					spiffe: {
+						description: """
+							Uses the SPIFFE workload identity fetched from the SPIFFE Workload API.
+
+							The identity certificate and the trust bundle are rotated without restarting Vector, as the
+							SPIFFE agent issues new SVIDs. This can't be used with `crt_file` and `key_file`.
+
+							As the peers are identified by their SPIFFE ID rather than by their hostname,
+							`verify_hostname` defaults to `false` when this is set.
+							"""
+						required: false
+						type: object: options: {
+							allowed_ids: {
+								description: """
+									The SPIFFE IDs allowed to connect to, or be connected from.
+
+									If empty, all the peers presenting an SVID issued by the trust bundle of the workload are
+									allowed.
+									"""
+								required: false
+								type: array: {
+									default: []
+									items: type: string: examples: ["spiffe://example.org/vector/aggregator"]
+								}
+							}
+							endpoint_socket: {
+								description: """
+									The address of the SPIFFE Workload API.
+
+									Must be a Unix socket address, in the form `unix:///path/to/agent.sock`. If not set, the
+									address is read from the `SPIFFE_ENDPOINT_SOCKET` environment variable.
+									"""
+								required: false
+								type: string: examples: ["unix:///run/spire/sockets/agent.sock"]
+							}
+						}
+					}
 					verify_certificate: {
 						description: """
 																Enables certificate verification.
@@ -351,6 +662,18 @@ base: components: sources: aws_s3: configuration: {
 						required: false
 						type: bool: {}
 					}
+					verify_ocsp_stapling: {
+						description: """
+							Requires the remote host to staple a valid OCSP response to its certificate.
+
+							The stapled OCSP response must report the certificate presented by the remote host as good, which prevents
+							connecting to hosts whose certificate has been revoked. This is not supported by FIPS builds of Vector.
+
+							Only relevant for outgoing connections.
+							"""
+						required: false
+						type: bool: {}
+					}
 				}
 			}
 			visibility_timeout_secs: {
@@ -370,6 +693,26 @@ base: components: sources: aws_s3: configuration: {
 			}
 		}
 	}
+	strategy: {
+		description: "The strategy to use to consume objects from S3."
+		required:    false
+		type: string: {
+			default: "sqs"
+			enum: {
+				poll: """
+					Consumes objects by periodically looking for new objects in a bucket.
+
+					This is useful when bucket notifications cannot be set up. The keys of the processed
+					objects are persisted so they are not consumed again, including across restarts.
+					"""
+				sqs: """
+					Consumes objects by processing bucket notification events sent to an [AWS SQS queue][aws_sqs].
+
+					[aws_sqs]: https://aws.amazon.com/sqs/
+					"""
+			}
+		}
+	}
 	tls_options: {
 		description: "TLS configuration."
 		required:    false
@@ -393,6 +736,33 @@ base: components: sources: aws_s3: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			cipher_suites: {
+				description: """
+					Sets the list of allowed cipher suites, as OpenSSL names.
+
+					The TLS 1.3 cipher suites, whose names start with `TLS_`, and the cipher suites of the previous versions are set
+					separately: when none of either kind is listed, the default ones are kept for the corresponding versions. In FIPS
+					mode, only the FIPS-approved cipher suites can be listed, and they are the default ones. FIPS builds of Vector don't
+					support listing TLS 1.3 cipher suites.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crl_file: {
+				description: """
+					Absolute path to a certificate revocation list (CRL) file.
+
+					The revocation of all the certificates of the chain presented by the peer is checked against the lists of the
+					file, which must be in the DER or PEM format. Connections with peers presenting a revoked certificate, or a
+					certificate issued by a CA without revocation list, are refused. This is not supported by FIPS builds of Vector.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: examples: ["/path/to/revocation_list.crl"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -423,6 +793,72 @@ base: components: sources: aws_s3: configuration: {
 				required: false
 				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 			}
+			min_tls_version: {
+				description: """
+					The minimum version of the TLS protocol.
+
+					Defaults to the minimum version supported by OpenSSL, or to `TLSv1.2` in FIPS mode.
+
+					Relevant for both incoming and outgoing connections.
+					"""
+				required: false
+				type: string: enum: {
+					"TLSv1.0": "TLS 1.0."
+					"TLSv1.1": "TLS 1.1."
+					"TLSv1.2": "TLS 1.2."
+					"TLSv1.3": "TLS 1.3."
+				}
+			}
+			pinned_public_keys: {
+				description: """
+					Pins the public keys of the certificates presented by the remote host.
+
+					When set, the certificate chain presented by the remote host must hold a certificate, either the leaf
+					certificate or a CA certificate, whose public key is pinned. The public keys are pinned by the base64 encoded
+					SHA-256 hash of their DER encoded SubjectPublicKeyInfo, optionally prefixed by `sha256//`.
+
+					This has no effect unless `verify_certificate` is enabled.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["sha256//YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg="]
+			}
+			spiffe: {
+				description: """
+					Uses the SPIFFE workload identity fetched from the SPIFFE Workload API.
+
+					The identity certificate and the trust bundle are rotated without restarting Vector, as the
+					SPIFFE agent issues new SVIDs. This can't be used with `crt_file` and `key_file`.
+
+					As the peers are identified by their SPIFFE ID rather than by their hostname,
+					`verify_hostname` defaults to `false` when this is set.
+					"""
+				required: false
+				type: object: options: {
+					allowed_ids: {
+						description: """
+							The SPIFFE IDs allowed to connect to, or be connected from.
+
+							If empty, all the peers presenting an SVID issued by the trust bundle of the workload are
+							allowed.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["spiffe://example.org/vector/aggregator"]
+						}
+					}
+					endpoint_socket: {
+						description: """
+							The address of the SPIFFE Workload API.
+
+							Must be a Unix socket address, in the form `unix:///path/to/agent.sock`. If not set, the
+							address is read from the `SPIFFE_ENDPOINT_SOCKET` environment variable.
+							"""
+						required: false
+						type: string: examples: ["unix:///run/spire/sockets/agent.sock"]
+					}
+				}
+			}
 			verify_certificate: {
 				description: """
 					Enables certificate verification.
@@ -453,6 +889,18 @@ base: components: sources: aws_s3: configuration: {
 				required: false
 				type: bool: {}
 			}
+			verify_ocsp_stapling: {
+				description: """
+					Requires the remote host to staple a valid OCSP response to its certificate.
+
+					The stapled OCSP response must report the certificate presented by the remote host as good, which prevents
+					connecting to hosts whose certificate has been revoked. This is not supported by FIPS builds of Vector.
+
+					Only relevant for outgoing connections.
+					"""
+				required: false
+				type: bool: {}
+			}
 		}
 	}
 }
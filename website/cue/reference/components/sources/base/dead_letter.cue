@@ -0,0 +1,3 @@
+package metadata
+
+base: components: sources: dead_letter: configuration: {}
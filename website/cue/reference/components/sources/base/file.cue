@@ -22,6 +22,69 @@ base: components: sources: file: configuration: {
 			type: bool: {}
 		}
 	}
+	checkpoint_store: {
+		description: """
+			Checkpoint store configuration.
+
+			Checkpoints are always persisted to the data directory. When a checkpoint store is configured,
+			they are also persisted to it, and loaded from it when none are found in the data directory.
+			This lets an instance of Vector running on a fresh host, such as a Fargate task or a spot
+			instance, resume where the previous instance left off.
+
+			A checkpoint store must not be shared by instances of Vector running at the same time.
+			"""
+		required: false
+		type: object: options: {
+			bucket: {
+				description:   "The S3 bucket name."
+				relevant_when: "type = \"aws_s3\""
+				required:      true
+				type: string: examples: ["my-bucket"]
+			}
+			endpoint: {
+				description: """
+					The Redis URL to connect to.
+
+					The URL must take the form of `protocol://server:port/db` where the `protocol` can either be
+					`redis` or `rediss` for connections secured via TLS.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+			key: {
+				description: "The key under which the checkpoints are stored."
+				required:    true
+				type: string: examples: ["vector:checkpoints:my-agent", "vector/checkpoints/my-agent.json", "my-agent"]
+			}
+			region: {
+				description:   "The AWS region of the target service."
+				relevant_when: "type = \"aws_s3\" or type = \"aws_dynamodb\""
+				required:      false
+				type: string: examples: ["us-east-1"]
+			}
+			table: {
+				description: """
+					The name of the DynamoDB table.
+
+					The table must have a string partition key named `key`, and no sort key. The checkpoints
+					are stored in the binary `checkpoints` attribute of the item.
+					"""
+				relevant_when: "type = \"aws_dynamodb\""
+				required:      true
+				type: string: examples: ["vector-checkpoints"]
+			}
+			type: {
+				description: "The type of checkpoint store."
+				required:    true
+				type: string: enum: {
+					aws_dynamodb: "Store the checkpoints in an item of an AWS DynamoDB table."
+					aws_s3:       "Store the checkpoints in an AWS S3 object."
+					redis:        "Store the checkpoints in Redis."
+				}
+			}
+		}
+	}
 	data_dir: {
 		description: """
 			The directory used to persist file checkpoint positions.
@@ -323,6 +386,24 @@ base: components: sources: file: configuration: {
 		required:    false
 		type: bool: default: false
 	}
+	read_backend: {
+		description: "Interface used to read the files."
+		required:    false
+		type: string: {
+			default: "std"
+			enum: {
+				io_uring: """
+					Read the files through `io_uring`.
+
+					The files are read ahead in a single batch every time they are polled, which reduces the
+					overhead of the system calls when tailing a large number of files. Only available on Linux,
+					when Vector is built with the `sources-file-io-uring` feature. Otherwise, or when the kernel
+					doesn't support `io_uring`, the files are read with the standard system calls.
+					"""
+				std: "Read the files with the standard system calls."
+			}
+		}
+	}
 	read_from: {
 		description: "File position to use when reading a new file."
 		required:    false
@@ -68,7 +68,7 @@ base: components: sources: host_metrics: configuration: {
 		description: """
 			The list of host metric collector services to use.
 
-			Defaults to all collectors.
+			Defaults to all collectors, except `smart`, `gpu`, and `pressure`.
 			"""
 		required: false
 		type: array: {
@@ -83,12 +83,31 @@ base: components: sources: host_metrics: configuration: {
 					cpu:        "Metrics related to CPU utilization."
 					disk:       "Metrics related to disk I/O utilization."
 					filesystem: "Metrics related to filesystem space utilization."
-					host:       "Metrics related to the host."
-					load:       "Metrics related to the system load average."
-					memory:     "Metrics related to memory utilization."
-					network:    "Metrics related to network utilization."
+					gpu: """
+						Metrics related to the utilization of NVIDIA GPUs.
+
+						Not enabled by default.
+						"""
+					host:    "Metrics related to the host."
+					load:    "Metrics related to the system load average."
+					memory:  "Metrics related to memory utilization."
+					network: "Metrics related to network utilization."
+					pressure: """
+						Metrics related to the pressure stall information (PSI) of the CPU, memory, and I/O.
+
+						The pressure is reported host-wide, and for each cgroup when the `cgroups` collector is
+						enabled too. These are better signals of the saturation of the resources than their
+						utilization.
+
+						Only available on Linux, with cgroups v2 for the per-cgroup metrics. Not enabled by default.
+						"""
+					smart: """
+						Metrics related to the health of disks, read from their SMART data.
+
+						Not enabled by default.
+						"""
 				}
-				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network", "smart", "gpu", "pressure"]
 			}
 		}
 	}
@@ -246,6 +265,29 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	gpu: {
+		description: """
+			Options for the GPU metrics collector.
+
+			The metrics of NVIDIA GPUs are read from [NVML][nvml] with `nvidia-smi`, which is installed
+			along with the NVIDIA driver.
+
+			[nvml]: https://developer.nvidia.com/nvidia-management-library-nvml
+			"""
+		required: false
+		type: object: options: nvidia_smi_path: {
+			description: """
+				The path of the `nvidia-smi` binary.
+
+				By default, `nvidia-smi` is looked up in the `PATH`.
+				"""
+			required: false
+			type: string: {
+				default: "nvidia-smi"
+				examples: ["/usr/bin/nvidia-smi"]
+			}
+		}
+	}
 	namespace: {
 		description: "Overrides the default namespace for the metrics emitted by the source."
 		required:    false
@@ -299,4 +341,65 @@ base: components: sources: host_metrics: configuration: {
 			unit:    "seconds"
 		}
 	}
+	smart: {
+		description: """
+			Options for the SMART metrics collector.
+
+			The metrics are read with [`smartctl`][smartctl], from smartmontools 7.0 or later, which
+			usually needs to run as root.
+
+			[smartctl]: https://www.smartmontools.org/
+			"""
+		required: false
+		type: object: options: {
+			devices: {
+				description: """
+					Lists of device name patterns to include or exclude in gathering
+					SMART metrics.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						excludes: ["dm-*"]
+						includes: ["sda"]
+					}]
+					options: {
+						excludes: {
+							description: """
+																	Any patterns which should be excluded.
+
+																	The patterns are matched using globbing.
+																	"""
+							required: false
+							type: array: items: type: string: {}
+						}
+						includes: {
+							description: """
+																	Any patterns which should be included.
+
+																	The patterns are matched using globbing.
+																	"""
+							required: false
+							type: array: {
+								default: ["*"]
+								items: type: string: {}
+							}
+						}
+					}
+				}
+			}
+			smartctl_path: {
+				description: """
+					The path of the `smartctl` binary.
+
+					By default, `smartctl` is looked up in the `PATH`.
+					"""
+				required: false
+				type: string: {
+					default: "smartctl"
+					examples: ["/usr/sbin/smartctl"]
+				}
+			}
+		}
+	}
 }
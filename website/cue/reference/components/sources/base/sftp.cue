@@ -0,0 +1,314 @@
+package metadata
+
+base: components: sources: sftp: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	after_read: {
+		description: """
+			What is done with the files once they are read.
+
+			When acknowledgements are enabled, files are only deleted or moved once their events are
+			acknowledged.
+			"""
+		required: false
+		type: object: options: {
+			action: {
+				description: "The action taken on the files once read."
+				required:    false
+				type: string: {
+					default: "keep"
+					enum: {
+						delete: "Delete the files."
+						keep: """
+							Leave the files in place.
+
+							The data appended to the files is read on the next polls.
+							"""
+						move: "Move the files to another directory."
+					}
+				}
+			}
+			directory: {
+				description: """
+					The directory the files are moved to, relative to `root`.
+
+					The path of the files relative to `root` is kept within this directory. It must not be
+					matched by the `include` patterns.
+					"""
+				relevant_when: "action = \"move\""
+				required:      true
+				type: string: examples: ["processed/"]
+			}
+		}
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the checkpoints of the files read.
+
+			By default, the global `data_dir` option is used. Make sure the running user has write
+			permissions to this directory.
+			"""
+		required: false
+		type: string: examples: ["/var/lib/vector"]
+	}
+	decoding: {
+		description: "Decoder to use on the HTTP responses."
+		required:    false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes: "Uses the raw bytes as-is."
+						gelf: """
+							Decodes the raw bytes as a [GELF][gelf] message.
+
+							[gelf]: https://docs.graylog.org/docs/gelf
+							"""
+						json: """
+							Decodes the raw bytes as [JSON][json].
+
+							[json]: https://www.json.org/
+							"""
+						native: """
+							Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						native_json: """
+							Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+							This codec is **[experimental][experimental]**.
+
+							[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+							[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+							"""
+						syslog: """
+							Decodes the raw bytes as a Syslog message.
+
+							Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+							[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+							[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+							[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+							"""
+					}
+				}
+			}
+			engine: {
+				description:   "The engine used to parse JSON."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: string: {
+					default: "serde"
+					enum: {
+						serde: """
+							Parses JSON with [`serde_json`][serde_json].
+
+							[serde_json]: https://docs.rs/serde_json
+							"""
+						simd: """
+							Parses JSON with [`simd-json`][simd_json], which is faster on large volumes of JSON by
+							making use of the SIMD instructions of the CPU.
+
+							This engine is only available when Vector is built with the `codecs-simd-json` feature.
+							Otherwise, the `serde` engine is used instead.
+
+							[simd_json]: https://docs.rs/simd-json
+							"""
+					}
+				}
+			}
+		}
+	}
+	endpoint: {
+		description: """
+			The SFTP server to connect to.
+
+			The port defaults to 22.
+			"""
+		required: true
+		type: string: examples: ["sftp.example.com", "10.0.0.5:2222"]
+	}
+	exclude: {
+		description: """
+			Glob patterns of the files not to read.
+
+			Takes precedence over `include`. Useful to skip the files being uploaded under a temporary
+			name.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["**/*.part"]
+		}
+	}
+	framing: {
+		description: "Framing to use in the decoding."
+		required:    false
+		type: object: options: {
+			character_delimited: {
+				description:   "Options for the character delimited decoder."
+				relevant_when: "method = \"character_delimited\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The character that delimits byte sequences."
+						required:    true
+						type: uint: {}
+					}
+					max_length: {
+						description: """
+																The maximum length of the byte buffer.
+
+																This length does *not* include the trailing delimiter.
+
+																By default, there is no maximum length enforced. If events are malformed, this can lead to
+																additional resource usage as events continue to be buffered in memory, and can potentially
+																lead to memory exhaustion in extreme cases.
+
+																If there is a risk of processing malformed data, such as logs with user-controlled input,
+																consider setting the maximum length to a reasonably large value as a safety net. This
+																ensures that processing is not actually unbounded.
+																"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			method: {
+				description: "The framing method."
+				required:    false
+				type: string: {
+					default: "newline_delimited"
+					enum: {
+						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+						character_delimited: "Byte frames which are delimited by a chosen character."
+						length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						newline_delimited:   "Byte frames which are delimited by a newline character."
+						octet_counting: """
+															Byte frames according to the [octet counting][octet_counting] format.
+
+															[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+															"""
+					}
+				}
+			}
+			newline_delimited: {
+				description:   "Options for the newline delimited decoder."
+				relevant_when: "method = \"newline_delimited\""
+				required:      false
+				type: object: options: max_length: {
+					description: """
+						The maximum length of the byte buffer.
+
+						This length does *not* include the trailing delimiter.
+
+						By default, there is no maximum length enforced. If events are malformed, this can lead to
+						additional resource usage as events continue to be buffered in memory, and can potentially
+						lead to memory exhaustion in extreme cases.
+
+						If there is a risk of processing malformed data, such as logs with user-controlled input,
+						consider setting the maximum length to a reasonably large value as a safety net. This
+						ensures that processing is not actually unbounded.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+			octet_counting: {
+				description:   "Options for the octet counting decoder."
+				relevant_when: "method = \"octet_counting\""
+				required:      false
+				type: object: options: max_length: {
+					description: "The maximum length of the byte buffer."
+					required:    false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	include: {
+		description: """
+			Glob patterns of the files to read.
+
+			Patterns are matched against the paths of the files relative to `root`. `*` does not match
+			`/`, while `**` matches any number of directories.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["*.log", "incoming/**/*.json"]
+	}
+	key: {
+		description: "The path of the private key to authenticate with."
+		required:    false
+		type: string: examples: ["/home/vector/.ssh/id_ed25519"]
+	}
+	known_hosts_strategy: {
+		description: "How the host key of the SFTP server is checked."
+		required:    false
+		type: string: {
+			default: "strict"
+			enum: {
+				accept: """
+					Connect to any server, without checking its host key.
+
+					This is vulnerable to man-in-the-middle attacks, and should only be used for testing.
+					"""
+				add: """
+					Add the host keys of unknown servers to the `known_hosts` file, but refuse to connect to
+					servers whose host key changed.
+					"""
+				strict: "Only connect to servers whose host key is in the `known_hosts` file."
+			}
+		}
+	}
+	poll_interval_secs: {
+		description: "The interval between polls of the server, in seconds."
+		required:    false
+		type: uint: {
+			default: 60
+			unit:    "seconds"
+		}
+	}
+	root: {
+		description: """
+			The directory the `include` and `exclude` patterns are relative to.
+
+			Relative paths are resolved from the home directory of the user.
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["/upload"]
+		}
+	}
+	user: {
+		description: "The user to log in as."
+		required:    true
+		type: string: examples: ["vector"]
+	}
+}
@@ -0,0 +1,22 @@
+package metadata
+
+base: components: sources: vector_stats: configuration: {
+	host_key: {
+		description: """
+			Overrides the name of the log field used to add the current hostname to each event.
+
+			By default, the [global `log_schema.host_key` option][global_host_key] is used.
+
+			Set to `""` to suppress this key.
+
+			[global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+			"""
+		required: false
+		type: string: default: "host"
+	}
+	interval_secs: {
+		description: "The interval between summaries, in seconds."
+		required:    false
+		type: float: default: 60.0
+	}
+}
@@ -83,6 +83,12 @@ components: sources: datadog_agent: {
 				If [multiple_outputs](#multiple_outputs) is enabled, received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "processes"
+			description: """
+				If [multiple_outputs](#multiple_outputs) is enabled, received process and container payloads will go to this output stream. Use `<component_id>.processes` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -135,6 +141,43 @@ components: sources: datadog_agent: {
 				}
 			}
 		}
+		logs: process: {
+			description: "A process, container, or network connections payload received through an HTTP POST request sent by a Datadog Process Agent or System Probe."
+			fields: {
+				endpoint: {
+					description: "The intake endpoint the payload was sent to."
+					required:    true
+					type: string: {
+						enum: {
+							collector:   "Processes and real-time process updates."
+							container:   "Containers and real-time container updates."
+							discovery:   "Discovered processes."
+							connections: "Network connections collected by the System Probe."
+						}
+					}
+				}
+				header: {
+					description: "The header of the payload, with its `version`, `encoding`, message `type`, `subscription_id`, `org_id`, and `timestamp`."
+					required:    true
+					type: object: {}
+				}
+				payload: {
+					description: """
+						The decoded payload. JSON payloads are kept as they are, while protobuf payloads are
+						decoded without their schema, so their fields are keyed by field number.
+						"""
+					required: true
+					type: object: {}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["datadog_agent"]
+					}
+				}
+			}
+		}
 		metrics: {
 			_extra_tags: {
 				"source_type": {
@@ -204,6 +247,11 @@ components: sources: datadog_agent: {
 					traces.enabled: true
 					traces.url: http://"<VECTOR_HOST>:<SOURCE_PORT>" # Use https if SSL is enabled in Vector source configuration
 				```
+
+				Process and container payloads are accepted on the `/api/v1/collector`, `/api/v1/container`,
+				`/api/v1/discovery`, and `/api/v1/connections` endpoints. To send them, point the
+				`process_config.process_dd_url` option of the [Datadog Agent](\(urls.datadog_agent_doc))
+				configuration at this source.
 				"""
 		}
 		trace_support: {
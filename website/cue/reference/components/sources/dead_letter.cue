@@ -0,0 +1,90 @@
+package metadata
+
+components: sources: dead_letter: {
+	title:       "Dead Letter"
+	description: "The dead letter source receives the events exceeding the maximum event size, when the `event_size.when_exceeded` global option is set to `dead_letter`."
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.dead_letter.configuration
+
+	output: {
+		logs: event: {
+			description: "A log event exceeding the maximum event size."
+			fields: {
+				"*": {
+					description: "The events are forwarded as they were when they exceeded the maximum event size. The `dead_letter` source doesn't modify or add fields."
+					required:    true
+					type: "*": {}
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			set:          output._passthrough_set
+		}
+	}
+
+	how_it_works: {
+		dead_letter_metadata: {
+			title: "Dead letter metadata"
+			body: """
+				The events sent to the dead letter queue hold the ID of the component which sent them, and
+				their size in bytes, in their metadata, under `vector.dead_letter.component_id` and
+				`vector.dead_letter.size_bytes`. They can be read in VRL with `%vector.dead_letter`.
+
+				The events exceeding the maximum event size again after going through the dead letter queue
+				are dropped, to not loop through it forever.
+				"""
+		}
+		best_effort: {
+			title: "Best effort delivery"
+			body: """
+				The dead letter queue holds up to 1024 events for each `dead_letter` source. The events sent
+				while it is full are dropped, and counted in the `component_discarded_events_total` counter
+				of the source. The events sent to the dead letter queue while there is no `dead_letter`
+				source are dropped too.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -163,15 +163,13 @@ components: sources: fluent: {
 		secure_mode: {
 			title: "Secure forward mode support"
 			body:  """
-				The `fluent` source currently supports using TLS, but does not support the authentication part of the
-				Fluent protocol including:
+				The `fluent` source supports using TLS and the shared key authentication part of the Fluent
+				protocol. When `security.shared_key` is set, clients must complete the `HELO`/`PING`/`PONG`
+				handshake with a matching key before any events are accepted, as with the `<security>` section of
+				Fluentd's `forward` input.
 
-				- Shared key
-				- Username and password
-
-				And so these options of the secure forward output plugins for Fluent and Fluent Bit cannot be used.
-
-				If you would find this useful, [please let us know](\(urls.vector_repo)/issues/7532).
+				Username and password authentication is not supported, and so those options of the secure forward
+				output plugins for Fluentd and Fluent Bit cannot be used.
 				"""
 		}
 	}
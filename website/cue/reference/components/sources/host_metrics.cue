@@ -109,12 +109,14 @@ components: sources: host_metrics: {
 		}
 
 		// Host cgroups
-		cgroup_cpu_usage_seconds_total:  _host & _cgroup_cpu & {description:    "The total amount CPU time used by this cgroup and its descendants, in seconds."}
-		cgroup_cpu_user_seconds_total:   _host & _cgroup_cpu & {description:    "The total amount of CPU time spent by this cgroup in user space, in seconds."}
-		cgroup_cpu_system_seconds_total: _host & _cgroup_cpu & {description:    "The total amount of CPU time spent by this cgroup in system tasks, in seconds."}
-		cgroup_memory_current_bytes:     _host & _cgroup_memory & {description: "The total amount of memory currently being used by this cgroup and its descendants, in bytes."}
-		cgroup_memory_anon_bytes:        _host & _cgroup_memory & {description: "The total amount of memory used by this cgroup in anonymous mappings (normal program allocation), in bytes."}
-		cgroup_memory_file_bytes:        _host & _cgroup_memory & {description: "The total amount of memory used by this cgroup to cache filesystem data, including tmpfs and shared memory, in bytes."}
+		cgroup_cpu_usage_seconds_total:        _host & _cgroup_cpu & {description:    "The total amount CPU time used by this cgroup and its descendants, in seconds."}
+		cgroup_cpu_user_seconds_total:         _host & _cgroup_cpu & {description:    "The total amount of CPU time spent by this cgroup in user space, in seconds."}
+		cgroup_cpu_system_seconds_total:       _host & _cgroup_cpu & {description:    "The total amount of CPU time spent by this cgroup in system tasks, in seconds."}
+		cgroup_memory_current_bytes:           _host & _cgroup_memory & {description: "The total amount of memory currently being used by this cgroup and its descendants, in bytes."}
+		cgroup_memory_anon_bytes:              _host & _cgroup_memory & {description: "The total amount of memory used by this cgroup in anonymous mappings (normal program allocation), in bytes."}
+		cgroup_memory_file_bytes:              _host & _cgroup_memory & {description: "The total amount of memory used by this cgroup to cache filesystem data, including tmpfs and shared memory, in bytes."}
+		cgroup_pressure_stalled_seconds_total: _host & _cgroup_pressure & {type: "counter", description: "The total time during which tasks of this cgroup were stalled waiting for the resource, in seconds."}
+		cgroup_pressure_stalled_ratio:         _host & _cgroup_pressure & _pressure_window & {type: "gauge", description: "The ratio of time during which tasks of this cgroup were stalled waiting for the resource, averaged over the window."}
 
 		// Host disk
 		disk_read_bytes_total:       _host & _disk_counter & {description: "The accumulated number of bytes read in."}
@@ -167,6 +169,32 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host SMART
+		smart_health_passed:               _host & _smart_gauge & {description: "Whether the device passed its SMART overall health self-assessment, as `1` or `0`."}
+		smart_temperature_celsius:         _host & _smart_gauge & {description: "The current temperature of the device, in degrees Celsius."}
+		smart_power_on_hours_total:        _host & _smart_counter & {description: "The number of hours the device has been powered on."}
+		smart_power_cycles_total:          _host & _smart_counter & {description: "The number of times the device has been powered on."}
+		smart_attribute_value:             _host & _smart_attribute & {description: "The normalized value of a SMART attribute of an ATA device."}
+		smart_attribute_raw_value:         _host & _smart_attribute & {description: "The raw value of a SMART attribute of an ATA device."}
+		smart_nvme_percentage_used:        _host & _smart_gauge & {description: "The estimated percentage of the endurance of an NVMe device that was used."}
+		smart_nvme_available_spare:        _host & _smart_gauge & {description: "The percentage of the spare capacity of an NVMe device that is available."}
+		smart_nvme_read_bytes_total:       _host & _smart_counter & {description: "The number of bytes read by the host from an NVMe device."}
+		smart_nvme_written_bytes_total:    _host & _smart_counter & {description: "The number of bytes written by the host to an NVMe device."}
+		smart_nvme_media_errors_total:     _host & _smart_counter & {description: "The number of unrecovered data integrity errors of an NVMe device."}
+		smart_nvme_unsafe_shutdowns_total: _host & _smart_counter & {description: "The number of unsafe shutdowns of an NVMe device."}
+
+		// Host GPU
+		gpu_utilization_ratio:        _host & _gpu_gauge & {description: "The ratio of time during which the GPU was running kernels."}
+		gpu_memory_utilization_ratio: _host & _gpu_gauge & {description: "The ratio of time during which the GPU memory was read or written."}
+		gpu_memory_used_bytes:        _host & _gpu_gauge & {description: "The number of bytes of GPU memory used."}
+		gpu_memory_total_bytes:       _host & _gpu_gauge & {description: "The total number of bytes of GPU memory."}
+		gpu_temperature_celsius:      _host & _gpu_gauge & {description: "The temperature of the GPU, in degrees Celsius."}
+		gpu_power_draw_watts:         _host & _gpu_gauge & {description: "The power drawn by the GPU, in watts."}
+
+		// Host pressure
+		pressure_stalled_seconds_total: _host & _pressure & {type: "counter", description: "The total time during which tasks were stalled waiting for the resource, in seconds."}
+		pressure_stalled_ratio:         _host & _pressure & _pressure_window & {type: "gauge", description: "The ratio of time during which tasks were stalled waiting for the resource, averaged over the window."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -186,6 +214,13 @@ components: sources: host_metrics: {
 				cgroup: _cgroup_name
 			}
 		}
+		_cgroup_pressure: {
+			tags: _host_metrics_tags & _pressure_tags & {
+				collector: examples: ["cgroups"]
+				cgroup: _cgroup_name
+			}
+			relevant_when: "The `pressure` collector is enabled"
+		}
 		_cgroup_name: {
 			description: "The control group name."
 			required:    true
@@ -256,6 +291,94 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_smart_tags: _host_metrics_tags & {
+			collector: examples: ["smart"]
+			device: _disk_device
+			model: {
+				description: "The model of the device."
+				required:    false
+				examples: ["Samsung SSD 980 PRO 1TB"]
+			}
+		}
+		_smart_counter: {
+			type: "counter"
+			tags: _smart_tags
+		}
+		_smart_gauge: {
+			type: "gauge"
+			tags: _smart_tags
+		}
+		_smart_attribute: {
+			type: "gauge"
+			tags: _smart_tags & {
+				attribute: {
+					description: "The name of the SMART attribute."
+					required:    true
+					examples: ["Reallocated_Sector_Ct"]
+				}
+				attribute_id: {
+					description: "The identifier of the SMART attribute."
+					required:    true
+					examples: ["5"]
+				}
+			}
+		}
+		_pressure: {
+			tags: _host_metrics_tags & _pressure_tags & {
+				collector: examples: ["pressure"]
+			}
+		}
+		_pressure_tags: {
+			resource: {
+				description: "The stalled resource."
+				required:    true
+				enum: {
+					cpu:    "The CPU."
+					io:     "The I/O devices."
+					memory: "The memory."
+				}
+			}
+			stall: {
+				description: "Whether some tasks were stalled, or all of them at once."
+				required:    true
+				enum: {
+					full: "All the non-idle tasks were stalled at once. Not reported host-wide for the CPU."
+					some: "At least one task was stalled."
+				}
+			}
+		}
+		_pressure_window: {
+			tags: window: {
+				description: "The window over which the ratio is averaged."
+				required:    true
+				enum: {
+					"10s":  "The last 10 seconds."
+					"60s":  "The last 60 seconds."
+					"300s": "The last 300 seconds."
+				}
+			}
+		}
+		_gpu_gauge: {
+			type: "gauge"
+			tags: _host_metrics_tags & {
+				collector: examples: ["gpu"]
+				device: {
+					description: "The index of the GPU."
+					required:    true
+					examples: ["0"]
+				}
+				model: {
+					description: "The model of the GPU."
+					required:    true
+					examples: ["NVIDIA A100-SXM4-40GB"]
+				}
+				uuid: {
+					description: "The UUID of the GPU."
+					required:    true
+					examples: ["GPU-5c1d2a1e-8f0b-4a51-93c5-3b3c5e0f1d2a"]
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
@@ -196,5 +196,16 @@ components: sources: http_server: {
 				Supported algorithms are `gzip`, `deflate`, `snappy`, and `zstd`.
 				"""
 		}
+		unix_domain_sockets: {
+			title: "Unix domain sockets"
+			body: """
+				With an `address` of the form `uds:///path/to/http.sock`, the source listens on a Unix
+				domain socket instead of a TCP port, which lets sidecars running on the same host send
+				events without exposing a port. The socket file is created when the source starts, and
+				removed when it stops. TLS is not supported on Unix domain sockets.
+
+				The `http` sink can send events to such a socket with its `unix_socket` option.
+				"""
+		}
 	}
 }
@@ -138,12 +138,42 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		reload_drain_timeouts_total: {
+			description:       "The total number of components removed by a reload which were aborted because they didn't drain their input before their drain deadline."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_id: _component_id
+			}
+		}
 		reloaded_total: {
 			description:       "The total number of times the Vector instance has been reloaded."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		saturation_ratio: {
+			description:       "The saturation of the Vector instance for a resource, from 0 to 1, sampled every 5 seconds."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				resource: {
+					description: "The saturated resource."
+					required:    true
+					enum: {
+						buffer:      "The ratio of the size of the fullest buffer to its maximum size."
+						concurrency: "The highest ratio of the requests in flight of a sink to its adaptive concurrency limit."
+						cpu:         "The ratio of the CPU time used by the process to the CPU time available to it."
+					}
+				}
+			}
+		}
+		saturation_score: {
+			description:       "The saturation score of the Vector instance, the highest of its `saturation_ratio`s. This is meant to drive the autoscaling of aggregators."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
 		started_total: {
 			description:       "The total number of times the Vector instance has been started."
 			type:              "counter"
@@ -183,6 +213,21 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		anomalies_detected_total: {
+			description:       "The number of anomalous metric values detected by the anomaly_detection transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				direction: {
+					description: "Whether the value was above or below the band of expected values."
+					required:    true
+					enum: {
+						above: "The value was above the band."
+						below: "The value was below the band."
+					}
+				}
+			}
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -366,6 +411,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		end_to_end_latency_seconds: {
+			description:       "The time elapsed, in seconds, between the receipt of events by a source and their delivery by this sink. Only reported when `latency.enabled` is set."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				source_id: {
+					description: "The ID of the source which received the events."
+					required:    true
+				}
+			}
+		}
 		events_discarded_total: {
 			description:       "The total number of events discarded by this component."
 			type:              "counter"
@@ -430,6 +486,22 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_discarded_events_by_priority_total: {
+			description:       "The number of events dropped by this non-blocking buffer, by priority."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				priority: {
+					description: "The priority of the dropped events."
+					required:    true
+					enum: {
+						background: "Events shed once the buffer is half full."
+						low:        "Events shed once the buffer is three quarters full."
+						normal:     "Events dropped once the buffer is full."
+					}
+				}
+			}
+		}
 		buffer_received_event_bytes_total: {
 			description:       "The number of bytes received by this buffer."
 			type:              "counter"
@@ -454,6 +526,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_abandoned_events_total: {
+			description:       "The number of events left in the input of this component when it was torn down before draining it, such as when it is removed by a reload and its drain deadline has passed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_discarded_events_total: {
 			description:       "The number of events dropped by this component."
 			type:              "counter"
@@ -469,6 +547,12 @@ components: sources: internal_metrics: {
 				stage:      _stage
 			}
 		}
+		component_memory_used_bytes: {
+			description:       "The approximate size, in bytes, of the events held in flight by the sink. Only reported when a memory limit is set."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_received_bytes_total: {
 			description:       string | *"The number of raw bytes accepted by this component from source origins."
 			type:              "counter"
@@ -556,6 +640,38 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              component_received_events_total.tags
 		}
+		component_received_event_size_bytes: {
+			description: """
+				A histogram of the size of the events accepted by this component, in bytes.
+
+				One sample is recorded per event, with its estimated JSON encoded size.
+				"""
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              component_received_events_total.tags
+		}
+		component_received_labeled_event_bytes_total: {
+			description: """
+				The number of event bytes accepted by this component, with a tag for each of the labels configured in
+				`telemetry.event_labels`, holding the value of its field in the events. The value is `none` for
+				the events missing the field, and `other` once the label has `telemetry.max_label_values`
+				distinct values.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		component_received_labeled_events_total: {
+			description: """
+				The number of events accepted by this component, with a tag for each of the labels configured in
+				`telemetry.event_labels`, holding the value of its field in the events. The value is `none` for
+				the events missing the field, and `other` once the label has `telemetry.max_label_values`
+				distinct values.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_sent_bytes_total: {
 			description:       "The number of raw bytes sent by this component to destination sinks."
 			type:              "counter"
@@ -591,6 +707,38 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags & {output: _output}
 		}
+		component_sent_event_size_bytes: {
+			description: """
+				A histogram of the size of the events emitted by this component, in bytes.
+
+				One sample is recorded per event, with its estimated JSON encoded size.
+				"""
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & {output: _output}
+		}
+		component_sent_labeled_event_bytes_total: {
+			description: """
+				The number of event bytes emitted by this component, with a tag for each of the labels configured in
+				`telemetry.event_labels`, holding the value of its field in the events. The value is `none` for
+				the events missing the field, and `other` once the label has `telemetry.max_label_values`
+				distinct values.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		component_sent_labeled_events_total: {
+			description: """
+				The number of events emitted by this component, with a tag for each of the labels configured in
+				`telemetry.event_labels`, holding the value of its field in the events. The value is `none` for
+				the events missing the field, and `other` once the label has `telemetry.max_label_values`
+				distinct values.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		datadog_logs_received_in_total: {
 			description:       "Number of Datadog logs received."
 			type:              "counter"
@@ -603,6 +751,22 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		event_size_exceeded_total: {
+			description:       "The total number of events which exceeded the maximum event size set by the `event_size.max_bytes` option."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				outcome: {
+					description: "What was done with the events."
+					required:    true
+					enum: {
+						dead_lettered: "The events were sent to the dead letter queue."
+						dropped:       "The events were dropped."
+						truncated:     "The events were truncated to fit."
+					}
+				}
+			}
+		}
 		internal_metrics_cardinality: {
 			description:       "The total number of metrics emitted from the internal metrics registry."
 			type:              "gauge"
@@ -675,6 +839,142 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		kafka_filtered_messages_total: {
+			description:       "The number of Kafka messages skipped by the header filters."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				topic: {
+					description: "The Kafka topic."
+					required:    true
+				}
+				partition: {
+					description: "The Kafka partition."
+					required:    true
+				}
+			}
+		}
+		kafka_broker_queue_messages: {
+			description:       "Current number of messages waiting to be sent to the Kafka broker."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_inflight_requests: {
+			description:       "Current number of requests sent to the Kafka broker that are awaiting a response."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_queue_latency_seconds: {
+			description:       "Average time messages spent in the producer queue before being sent to the Kafka broker, over the last statistics interval."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_request_queue_latency_seconds: {
+			description:       "Average time requests spent in the queue before being sent to the Kafka broker, over the last statistics interval."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_rtt_seconds: {
+			description:       "Average round-trip time of requests to the Kafka broker, over the last statistics interval."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_requests_total: {
+			description:       "Total number of requests sent to the Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_request_errors_total: {
+			description:       "Total number of errors when sending requests to the Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_request_retries_total: {
+			description:       "Total number of requests retried to the Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_request_timeouts_total: {
+			description:       "Total number of requests to the Kafka broker that timed out."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_responses_total: {
+			description:       "Total number of responses received from the Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_response_errors_total: {
+			description:       "Total number of errors when receiving responses from the Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_connects_total: {
+			description:       "Total number of connections established to the Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_broker_disconnects_total: {
+			description:       "Total number of disconnections from the Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {broker: _kafka_broker}
+		}
+		kafka_consumer_group_rebalances_total: {
+			description:       "Total number of rebalances of the Kafka consumer group."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_consumer_group_assigned_partitions: {
+			description:       "Current number of partitions assigned to the consumer by the Kafka consumer group."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_partition_queue_messages: {
+			description:       "Current number of messages in the producer queue of the Kafka partition."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {topic_id: _kafka_topic_id, partition_id: _kafka_partition_id}
+		}
+		kafka_partition_transmit_queue_messages: {
+			description:       "Current number of messages ready to be sent to the Kafka partition."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {topic_id: _kafka_topic_id, partition_id: _kafka_partition_id}
+		}
+		kafka_partition_inflight_messages: {
+			description:       "Current number of messages sent to the Kafka partition that are awaiting acknowledgement."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {topic_id: _kafka_topic_id, partition_id: _kafka_partition_id}
+		}
+		kafka_partition_produced_messages_total: {
+			description:       "Total number of messages produced to the Kafka partition."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {topic_id: _kafka_topic_id, partition_id: _kafka_partition_id}
+		}
+		kafka_unknown_topic_events_total: {
+			description:       "The number of events dropped because their Kafka topic does not exist."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+
 		kafka_consumer_lag: {
 			description:       "The Kafka consumer lag."
 			type:              "gauge"
@@ -690,6 +990,18 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		leader: {
+			description:       "Whether this instance holds the lease of a source with `ha_mode` set to `active_passive`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		leadership_acquisitions_total: {
+			description:       "The number of times this instance acquired the lease of a source with `ha_mode` set to `active_passive`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		file_delete_errors_total: {
 			description:       "The total number of failures to delete a file. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -827,6 +1139,56 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		memory_limit_exceeded_total: {
+			description:       "The total number of times a sink read events while a memory limit was exceeded, or started blocking because of it."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				limit: {
+					description: "The memory limit exceeded."
+					required:    true
+					enum: {
+						component: "The limit of the sink, set by its `memory_limit_bytes` option."
+						global:    "The limit of all sinks, set by the `memory_budget.limit_bytes` option."
+					}
+				}
+				policy: {
+					description: "The policy applied while the limit is exceeded."
+					required:    true
+					enum: {
+						block:       "The sink stopped reading events."
+						drop_newest: "The events read were dropped."
+					}
+				}
+			}
+		}
+		healthcheck_failures_total: {
+			description:       "The total number of periodic healthchecks of the sink which failed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		quarantined: {
+			description:       "Whether the sink is quarantined (`1`) or not (`0`). Only reported when periodic healthchecks are enabled."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		quarantines_total: {
+			description:       "The total number of times the sink was quarantined after consecutive failed healthchecks."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				policy: {
+					description: "The policy applied while the sink is quarantined."
+					required:    true
+					enum: {
+						buffer: "The sink stopped reading events."
+						shed:   "The events read were dropped."
+					}
+				}
+			}
+		}
 		metadata_refresh_failed_total: {
 			description:       "The total number of failed efforts to refresh AWS EC2 metadata."
 			type:              "counter"
@@ -839,18 +1201,57 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		metric_arithmetic_division_by_zero_total: {
+			description:       "The number of derived metrics skipped by the metric_arithmetic transform because of a division by zero."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		open_connections: {
 			description:       "The number of current open connections to Vector."
 			type:              "gauge"
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		open_partitions: {
+			description:       "The number of partitions this sink currently holds a batch for."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		ordered_merge_late_events_total: {
+			description:       "The total number of events received by this transform after later events were already released by the ordered merge of its inputs."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		parse_errors_total: {
 			description:       "The total number of errors parsing metrics for this component."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		partitions_evicted_total: {
+			description:       "The total number of partitions whose batch was flushed early to respect the limits on the partitions batched at once."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "The limit which was reached."
+					required:    true
+					enum: {
+						max_open_bytes:      "The maximum size of the batches of all partitions was exceeded."
+						max_open_partitions: "The maximum number of partitions batched at once was reached."
+					}
+				}
+			}
+		}
+		partitions_opened_total: {
+			description:       "The total number of partitions this sink opened a batch for."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		processed_bytes_total: {
 			description:       "The number of bytes processed by the component."
 			type:              "counter"
@@ -933,6 +1334,21 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		schema_validation_failed_events_total: {
+			description:       "The total number of events a sink dropped because they didn't conform to its `schema.validation.json_schema`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				policy: {
+					description: "The `schema.validation.on_failure` policy of the sink."
+					required:    true
+					enum: {
+						drop:   "The events were dropped."
+						reject: "The events were dropped and rejected."
+					}
+				}
+			}
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
@@ -945,6 +1361,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		source_stalls_total: {
+			description:       "The total number of times a source was blocked sending events downstream for longer than the stall detection threshold. Only reported when `stall_detection.enabled` is set."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_id: _component_id
+				blamed_component_id: {
+					description: "The ID of the downstream component the stall is attributed to."
+					required:    true
+				}
+			}
+		}
 		splunk_pending_acks: {
 			description:       "The number of outstanding Splunk HEC indexer acknowledgement acks."
 			type:              "gauge"
@@ -1035,6 +1463,39 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tenant_over_quota_events_total: {
+			description:       "The number of events of a tenant over the quota of a source with `tenancy`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				tenant: _tenant
+				action: {
+					description: "The action taken on the events over quota."
+					required:    true
+					enum: {
+						drop:     "The events were dropped."
+						tag:      "The events were tagged."
+						throttle: "The events were throttled."
+					}
+				}
+			}
+		}
+		tenant_received_event_bytes_total: {
+			description:       "The number of event bytes of a tenant received by a source with `tenancy`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				tenant: _tenant
+			}
+		}
+		tenant_received_events_total: {
+			description:       "The number of events of a tenant received by a source with `tenancy`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				tenant: _tenant
+			}
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
@@ -1177,6 +1638,18 @@ components: sources: internal_metrics: {
 				"transform": "Vector transform components"
 			}
 		}
+		_kafka_broker: {
+			description: "The name of the Kafka broker."
+			required:    true
+		}
+		_kafka_partition_id: {
+			description: "The Kafka partition id."
+			required:    true
+		}
+		_kafka_topic_id: {
+			description: "The Kafka topic id."
+			required:    true
+		}
 		_component_id: {
 			description: "The Vector component ID."
 			required:    true
@@ -1265,6 +1738,10 @@ components: sources: internal_metrics: {
 			description: "The path that produced the error."
 			required:    true
 		}
+		_tenant: {
+			description: "The tenant ID of the events, or `other` for the tenants beyond the `max_tenants` of the source."
+			required:    true
+		}
 		_reason: {
 			description: "The type of the error"
 			required:    true
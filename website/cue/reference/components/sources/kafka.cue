@@ -87,27 +87,43 @@ components: sources: kafka: {
 	}
 
 	telemetry: metrics: {
-		events_failed_total:                  components.sources.internal_metrics.output.metrics.events_failed_total
-		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
-		consumer_offset_updates_failed_total: components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
-		kafka_queue_messages:                 components.sources.internal_metrics.output.metrics.kafka_queue_messages
-		kafka_queue_messages_bytes:           components.sources.internal_metrics.output.metrics.kafka_queue_messages_bytes
-		kafka_requests_total:                 components.sources.internal_metrics.output.metrics.kafka_requests_total
-		kafka_requests_bytes_total:           components.sources.internal_metrics.output.metrics.kafka_requests_bytes_total
-		kafka_responses_total:                components.sources.internal_metrics.output.metrics.kafka_responses_total
-		kafka_responses_bytes_total:          components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
-		kafka_produced_messages_total:        components.sources.internal_metrics.output.metrics.kafka_produced_messages_total
-		kafka_produced_messages_bytes_total:  components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
-		kafka_consumed_messages_total:        components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
-		kafka_consumed_messages_bytes_total:  components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
-		kafka_consumer_lag:                   components.sources.internal_metrics.output.metrics.kafka_consumer_lag
-		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
-		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
-		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
-		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
-		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
-		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		events_failed_total:                        components.sources.internal_metrics.output.metrics.events_failed_total
+		events_in_total:                            components.sources.internal_metrics.output.metrics.events_in_total
+		consumer_offset_updates_failed_total:       components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
+		kafka_queue_messages:                       components.sources.internal_metrics.output.metrics.kafka_queue_messages
+		kafka_queue_messages_bytes:                 components.sources.internal_metrics.output.metrics.kafka_queue_messages_bytes
+		kafka_requests_total:                       components.sources.internal_metrics.output.metrics.kafka_requests_total
+		kafka_requests_bytes_total:                 components.sources.internal_metrics.output.metrics.kafka_requests_bytes_total
+		kafka_responses_total:                      components.sources.internal_metrics.output.metrics.kafka_responses_total
+		kafka_responses_bytes_total:                components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
+		kafka_produced_messages_total:              components.sources.internal_metrics.output.metrics.kafka_produced_messages_total
+		kafka_produced_messages_bytes_total:        components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
+		kafka_consumed_messages_total:              components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
+		kafka_consumed_messages_bytes_total:        components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		kafka_consumer_lag:                         components.sources.internal_metrics.output.metrics.kafka_consumer_lag
+		kafka_broker_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_broker_queue_messages
+		kafka_broker_inflight_requests:             components.sources.internal_metrics.output.metrics.kafka_broker_inflight_requests
+		kafka_broker_queue_latency_seconds:         components.sources.internal_metrics.output.metrics.kafka_broker_queue_latency_seconds
+		kafka_broker_request_queue_latency_seconds: components.sources.internal_metrics.output.metrics.kafka_broker_request_queue_latency_seconds
+		kafka_broker_rtt_seconds:                   components.sources.internal_metrics.output.metrics.kafka_broker_rtt_seconds
+		kafka_broker_requests_total:                components.sources.internal_metrics.output.metrics.kafka_broker_requests_total
+		kafka_broker_request_errors_total:          components.sources.internal_metrics.output.metrics.kafka_broker_request_errors_total
+		kafka_broker_request_retries_total:         components.sources.internal_metrics.output.metrics.kafka_broker_request_retries_total
+		kafka_broker_request_timeouts_total:        components.sources.internal_metrics.output.metrics.kafka_broker_request_timeouts_total
+		kafka_broker_responses_total:               components.sources.internal_metrics.output.metrics.kafka_broker_responses_total
+		kafka_broker_response_errors_total:         components.sources.internal_metrics.output.metrics.kafka_broker_response_errors_total
+		kafka_broker_connects_total:                components.sources.internal_metrics.output.metrics.kafka_broker_connects_total
+		kafka_broker_disconnects_total:             components.sources.internal_metrics.output.metrics.kafka_broker_disconnects_total
+		kafka_consumer_group_rebalances_total:      components.sources.internal_metrics.output.metrics.kafka_consumer_group_rebalances_total
+		kafka_consumer_group_assigned_partitions:   components.sources.internal_metrics.output.metrics.kafka_consumer_group_assigned_partitions
+		kafka_filtered_messages_total:              components.sources.internal_metrics.output.metrics.kafka_filtered_messages_total
+		processed_bytes_total:                      components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                     components.sources.internal_metrics.output.metrics.processed_events_total
+		component_discarded_events_total:           components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:                     components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:             components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:            components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 	}
 
 	how_it_works: components._kafka.how_it_works
@@ -302,6 +302,26 @@ components: sources: logstash: {
 				acknowledgements.
 				"""
 		}
+
+		windowing: {
+			title: "Window size and backpressure"
+			body: """
+				Beats announce how many events they send before waiting for an acknowledgement with a window size
+				frame. This source acknowledges a window once all of its events have been sent on, or delivered when
+				end-to-end acknowledgements are enabled, so a slow downstream holds back the next window. While a
+				window is held back, version 2 clients periodically receive a keepalive acknowledgement so that they
+				do not time out.
+				"""
+		}
+
+		tls_client_metadata: {
+			title: "Client certificate metadata"
+			body: """
+				When `tls.client_metadata_key` is set and a client presents a certificate, its `subject`, its
+				`common_name` and its `subject_alt_names` are inserted into the events it sends, attributing them to
+				the beat that sent them.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
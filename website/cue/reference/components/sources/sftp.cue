@@ -0,0 +1,128 @@
+package metadata
+
+components: sources: sftp: {
+	title: "SFTP"
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		codecs: {
+			enabled:         true
+			default_framing: "`newline_delimited`"
+		}
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.sftp
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "disabled"
+				}
+			}
+			proxy: enabled: false
+			tls: enabled:   false
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.sftp.configuration
+
+	output: logs: line: {
+		description: "An event read from a file."
+		fields: {
+			message: {
+				description: "The raw line from the file, if decoded with the `bytes` codec."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			path: {
+				description: "The path of the file, relative to `root`."
+				required:    true
+				type: string: {
+					examples: ["incoming/2023/05/app.log"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["sftp"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Every `poll_interval_secs`, the source lists the directories of the `include` patterns,
+				and reads the files matching them which are not matched by the `exclude` patterns. Only
+				the patterns containing `**` cause the directories to be listed recursively.
+
+				Files are read as a whole, and their content is decoded with the configured `framing`
+				and `decoding`. Files should be uploaded atomically, for instance by uploading them under
+				a name excluded by the patterns and renaming them once complete, so that they are not
+				read while being written.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The offset up to which each file was read is persisted in the `data_dir`, once its
+				events are sent downstream, or acknowledged when acknowledgements are enabled. Files are
+				read again from their offset when they grow, and from the start when they shrink. Files
+				whose events are rejected are read again on the next poll.
+				"""
+		}
+		after_read: {
+			title: "Deleting or moving files after reading"
+			body: """
+				With the `delete` or `move` `after_read.action`, files are deleted or moved to the
+				`after_read.directory` once read to their end. Failures to do so are retried on the next
+				poll, without reading the files again.
+				"""
+		}
+		authentication: {
+			title: "Authentication"
+			body: """
+				Only key-based authentication is supported. The private key is read from `key`, or else
+				the keys of the SSH agent of the user running Vector are tried. The host key of the
+				server is checked against the `~/.ssh/known_hosts` file of that user according to the
+				`known_hosts_strategy`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
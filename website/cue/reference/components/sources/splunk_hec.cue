@@ -6,7 +6,7 @@ components: sources: splunk_hec: {
 	title: "Splunk HTTP Event Collector (HEC)"
 
 	description: """
-		This source exposes three HTTP endpoints at a configurable address that jointly implement the [Splunk HEC API](https://docs.splunk.com/Documentation/Splunk/9.0.3/Data/UsetheHTTPEventCollector): `/services/collector/event`, `/services/collector/raw`, and `/services/collector/health`.
+		This source exposes four HTTP endpoints at a configurable address that jointly implement the [Splunk HEC API](https://docs.splunk.com/Documentation/Splunk/9.0.3/Data/UsetheHTTPEventCollector): `/services/collector/event`, `/services/collector/raw`, `/services/collector/health`, and `/services/collector/ack`.
 		"""
 
 	classes: {
@@ -78,6 +78,33 @@ components: sources: splunk_hec: {
 		}
 	}
 
+	output: metrics: gauge: output._passthrough_gauge & {
+		description: "A measurement of an event in the HEC metric format, the dimensions of which are mapped to tags."
+		tags: {
+			"*": {
+				description: "The dimensions of the measurement."
+				examples: ["us-west-1"]
+				required: false
+			}
+			splunk_channel: {
+				description: "The Splunk channel of the event."
+				required:    false
+			}
+			splunk_index: {
+				description: "The `index` of the event."
+				required:    false
+			}
+			splunk_source: {
+				description: "The `source` of the event."
+				required:    false
+			}
+			splunk_sourcetype: {
+				description: "The `sourcetype` of the event."
+				required:    false
+			}
+		}
+	}
+
 	telemetry: metrics: {
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
@@ -94,6 +121,25 @@ components: sources: splunk_hec: {
 			body: """
 				With acknowledgements enabled, the source uses the [Splunk HEC indexer acknowledgements protocol](https://docs.splunk.com/Documentation/Splunk/8.2.3/Data/AboutHECIDXAck) to allow clients to verify data has been delivered to destination sinks.
 				To summarize the protocol, each request to the source is associated with an integer identifier (an ack id) that the client is given and can use to query for the status of the request.
+				Requests to both the `/services/collector/event` and `/services/collector/raw` endpoints are acknowledged, and their statuses are queried through `/services/collector/ack` on the same channel.
+				"""
+		}
+		raw_endpoint: {
+			title: "Raw endpoint"
+			body: """
+				Each request to the `/services/collector/raw` endpoint is received as a single event, and must specify its channel.
+				As with Splunk, the `host`, `index`, `source`, `sourcetype`, and `time` query parameters set the
+				corresponding fields of the event, `time` being in seconds since the Unix epoch.
+				"""
+		}
+		metric_events: {
+			title: "Metric events"
+			body: """
+				Events sent to the `/services/collector/event` endpoint in the [HEC metric format](\(urls.splunk_hec_metric_format)),
+				in which `event` is `"metric"` and `fields` holds either a `metric_name` and its `_value`, or one
+				`metric_name:<name>` field per measurement, are received as gauge metrics. The other string and
+				number fields are mapped to tags, along with the `host`, `index`, `source`, and `sourcetype` of the
+				event.
 				"""
 		}
 	}
@@ -0,0 +1,129 @@
+package metadata
+
+components: sources: sse: {
+	title: "Server-Sent Events"
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		codecs: {
+			enabled:         true
+			default_framing: "`bytes`"
+		}
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.server_sent_events
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator", "sidecar"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.sse.configuration
+
+	output: logs: record: {
+		description: "An event dispatched by the server."
+		fields: {
+			message: {
+				description: "The data of the event, if decoded with the `bytes` codec."
+				required:    true
+				type: string: {
+					examples: ["{\"action\": \"login\", \"user\": \"alice\"}"]
+				}
+			}
+			event_id: {
+				description: "The last event ID set by the server, which may have been set by a previous event."
+				required:    false
+				type: string: {
+					examples: ["7f3c2a"]
+				}
+			}
+			event_type: {
+				description: "The type of the event, `message` unless set by the server."
+				required:    true
+				type: string: {
+					examples: ["message", "audit"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["sse"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		events: {
+			title: "Events"
+			body: """
+				The source sends a `GET` request to the `endpoint`, and reads the [`text/event-stream`][sse]
+				response as it is streamed. The `data` of each event dispatched by the server is decoded
+				with the configured `framing` and `decoding`, and the ID and type of the event are added
+				to the decoded events. Comments, typically sent by servers to keep the connection alive,
+				are ignored.
+
+				[sse]: \(urls.server_sent_events)
+				"""
+		}
+		reconnection: {
+			title: "Reconnection and resumption"
+			body: """
+				Once the stream ends, the source reconnects after the `reconnect_delay_ms`, or the delay
+				set by the server with the `retry` field. Failed requests and error responses are retried
+				with a delay doubling on each consecutive failure, up to a minute. A `204 No Content`
+				response asks the source to stop reconnecting.
+
+				When reconnecting, the ID of the last event sent downstream is passed in the
+				`Last-Event-ID` header, so that the server resumes the stream after it. The ID is kept in
+				memory only, so that the stream is not resumed across restarts.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		http_error_response_total:            components.sources.internal_metrics.output.metrics.http_error_response_total
+		http_request_errors_total:            components.sources.internal_metrics.output.metrics.http_request_errors_total
+	}
+}
@@ -0,0 +1,179 @@
+package metadata
+
+components: sources: vector_stats: {
+	title:       "Vector Stats"
+	description: """
+		The Vector stats source periodically summarizes the health of each component of the running
+		Vector instance, such as its throughput, errors, and buffer depth, as log events.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.vector_stats.configuration
+
+	output: logs: summary: {
+		description: "The summary of a component over the last interval."
+		fields: {
+			component_id: {
+				description: "The ID of the component."
+				required:    true
+				type: string: examples: ["my_http_sink"]
+			}
+			component_kind: {
+				description: "The kind of the component."
+				required:    true
+				type: string: enum: {
+					source:    "A source."
+					transform: "A transform."
+					sink:      "A sink."
+				}
+			}
+			component_type: {
+				description: "The type of the component."
+				required:    true
+				type: string: examples: ["http", "remap"]
+			}
+			interval_secs: {
+				description: "The duration of the interval that the summary covers, in seconds."
+				required:    true
+				type: float: examples: [60.0]
+			}
+			received_events: {
+				description: "The number of events received by the component during the interval."
+				required:    true
+				type: uint: {
+					examples: [1200]
+					unit: null
+				}
+			}
+			received_event_bytes: {
+				description: "The estimated size of the events received by the component during the interval, in bytes, as encoded in JSON."
+				required:    true
+				type: uint: {
+					examples: [524288]
+					unit: "bytes"
+				}
+			}
+			received_events_per_second: {
+				description: "The number of events received per second during the interval."
+				required:    true
+				type: float: examples: [20.0]
+			}
+			sent_events: {
+				description: "The number of events sent by the component during the interval."
+				required:    true
+				type: uint: {
+					examples: [1180]
+					unit: null
+				}
+			}
+			sent_event_bytes: {
+				description: "The estimated size of the events sent by the component during the interval, in bytes, as encoded in JSON."
+				required:    true
+				type: uint: {
+					examples: [515000]
+					unit: "bytes"
+				}
+			}
+			sent_events_per_second: {
+				description: "The number of events sent per second during the interval."
+				required:    true
+				type: float: examples: [19.7]
+			}
+			errors: {
+				description: "The number of errors encountered by the component during the interval."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			discarded_events: {
+				description: "The number of events discarded by the component during the interval."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			buffer_events: {
+				description: "The number of events in the buffer of the component. Only set for components with a buffer."
+				required:    false
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			buffer_byte_size: {
+				description: "The size of the events in the buffer of the component, in bytes. Only set for components with a buffer."
+				required:    false
+				type: uint: {
+					examples: [21504]
+					unit: "bytes"
+				}
+			}
+			host: fields._local_host
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["vector_stats"]
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the summary was generated."
+			}
+		}
+	}
+
+	how_it_works: {
+		derived_from_internal_metrics: {
+			title: "Derived from internal metrics"
+			body: """
+				The summaries are derived from the same telemetry as the `internal_metrics`
+				source. Each event
+				summarizes one component, with the counts of what happened since the previous
+				summary, so that the health of Vector can be shipped to the same log backend as
+				everything else, without a metrics pipeline.
+
+				The first summaries are emitted one interval after Vector starts. Components whose
+				counters were reset, because they were reloaded or their metrics expired, report
+				their counts since the reset.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,117 @@
+package metadata
+
+components: sources: websocket: {
+	title: "WebSocket"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.websocket
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator", "sidecar"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.websocket.configuration
+
+	output: logs: record: {
+		description: "An individual message received from the WebSocket server."
+		fields: {
+			message: {
+				description: "The message received from the server, if decoded with the `bytes` codec."
+				required:    true
+				type: string: {
+					examples: ["{\"type\": \"trade\", \"price\": 42.1}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		messages: {
+			title: "Messages"
+			body: """
+				Each text or binary message received from the server is decoded with the configured
+				`framing` and `decoding`, and every message is framed on its own. The `initial_messages`
+				are sent to the server each time the connection is established, which can be used to
+				subscribe to the channels of a streaming API.
+				"""
+		}
+		reconnection: {
+			title: "Reconnection"
+			body: """
+				The source reconnects with an exponential backoff whenever the connection is closed or
+				fails. Pings received from the server are answered automatically. With `ping_interval`
+				set, the source also sends pings of its own, and reconnects when no pong is received
+				within the `ping_timeout`.
+				"""
+		}
+		resume_tokens: {
+			title: "Resume tokens"
+			body: """
+				Streaming APIs often allow resuming a stream from a cursor or sequence number rather than
+				from their current position, so that no event is lost while reconnecting. With `resume`
+				configured, the `resume.token` VRL expression is evaluated against each event received,
+				and its last non-null result is passed in the `resume.query_parameter` of the URI when
+				reconnecting. The token is only updated once the events are sent downstream, and is kept
+				in memory only, so that the stream is not resumed across restarts.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		connection_established_total:         components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_shutdown_total:            components.sources.internal_metrics.output.metrics.connection_shutdown_total
+	}
+}
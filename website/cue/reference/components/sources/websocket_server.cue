@@ -0,0 +1,103 @@
+package metadata
+
+components: sources: websocket_server: {
+	_port: 8080
+
+	title: "WebSocket Server"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		receive: {
+			from: {
+				service: services.websocket
+
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      _port
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.websocket_server.configuration
+
+	output: logs: record: {
+		description: "An individual message received from a WebSocket client."
+		fields: {
+			message: {
+				description: "The message received from the client, if decoded with the `bytes` codec."
+				required:    true
+				type: string: {
+					examples: ["{\"action\": \"click\", \"target\": \"checkout\"}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket_server"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		messages: {
+			title: "Messages"
+			body: """
+				Each text or binary message received from a client is decoded with the configured
+				`framing` and `decoding`, and every message is framed on its own. Pings received from the
+				clients are answered automatically.
+
+				With `auth` set, clients must send the matching `Authorization` header, and connections
+				without it are rejected with a `401 Unauthorized` response.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		connection_established_total:         components.sources.internal_metrics.output.metrics.connection_established_total
+		open_connections:                     components.sources.internal_metrics.output.metrics.open_connections
+	}
+}
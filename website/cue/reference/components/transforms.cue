@@ -18,8 +18,10 @@ components: transforms: [Name=string]: {
 		component_received_events_count:      components.sources.internal_metrics.output.metrics.component_received_events_count
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_event_size_bytes:  components.sources.internal_metrics.output.metrics.component_received_event_size_bytes
 		utilization:                          components.sources.internal_metrics.output.metrics.utilization
 		component_sent_events_total:          components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total:     components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_sent_event_size_bytes:      components.sources.internal_metrics.output.metrics.component_sent_event_size_bytes
 	}
 }
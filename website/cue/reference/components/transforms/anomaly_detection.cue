@@ -0,0 +1,78 @@
+package metadata
+
+components: transforms: anomaly_detection: {
+	title: "Anomaly Detection"
+
+	description: """
+		Learns a baseline for each metric series, and flags the values which deviate from it by more
+		than a configurable band, to pre-filter anomalies at the edge before they reach alerting
+		systems.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.anomaly_detection.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "anomalies"
+			description: """
+				When `annotation` is set to `event`, a log event describing each anomaly is sent to the
+				`anomalies` output. For a transform component named `foo`, this output can be accessed
+				by specifying `foo.anomalies` as the input to another component. The events hold the
+				`name`, `namespace`, and `tags` of the metric, its `value`, the `expected` value, the
+				`lower` and `upper` bounds of the band, and the `direction` of the deviation.
+				"""
+		},
+	]
+
+	telemetry: metrics: {
+		anomalies_detected_total: components.sources.internal_metrics.output.metrics.anomalies_detected_total
+	}
+
+	how_it_works: {
+		baselines: {
+			title: "Baselines"
+			body: """
+				A baseline is learned for each series, identified by the name, namespace, and tags of its
+				metrics. Before each value is learned, it's compared to the value predicted by the baseline:
+				it's anomalous when it's further from the prediction than `band` times the standard deviation
+				of the past prediction errors. Anomalous values are learned too, so that the baseline adapts
+				to lasting changes.
+
+				Counters and gauges are supported, the other metrics being forwarded as they are. Absolute
+				counters are compared through their increments. The baselines are kept in memory, for as long
+				as Vector runs.
+				"""
+		}
+	}
+}
@@ -0,0 +1,84 @@
+package metadata
+
+base: components: transforms: anomaly_detection: configuration: {
+	alpha: {
+		description: "The smoothing factor of the level, greater than 0 and at most 1."
+		required:    false
+		type: float: default: 0.3
+	}
+	annotation: {
+		description: "How anomalies are reported."
+		required:    false
+		type: string: {
+			default: "tag"
+			enum: {
+				event: """
+					Send a log event describing each anomaly to the `anomalies` output.
+
+					The metrics are forwarded unchanged.
+					"""
+				tag: """
+					Tag the anomalous metrics with `anomaly`, set to `above` or `below` the band of expected
+					values.
+					"""
+			}
+		}
+	}
+	band: {
+		description: """
+			The width of the band of expected values around the baseline, in standard deviations.
+
+			Values outside of the band are anomalous.
+			"""
+		required: false
+		type: float: default: 3.0
+	}
+	beta: {
+		description:   "The smoothing factor of the trend, greater than 0 and at most 1."
+		relevant_when: "model = \"holt_winters\""
+		required:      false
+		type: float: default: 0.1
+	}
+	gamma: {
+		description:   "The smoothing factor of the seasonality, greater than 0 and at most 1."
+		relevant_when: "model = \"holt_winters\""
+		required:      false
+		type: float: default: 0.1
+	}
+	model: {
+		description: "The model of the baseline learned for each series."
+		required:    true
+		type: string: enum: {
+			ewma: """
+				An exponentially weighted moving average.
+
+				Suited to series fluctuating around a level which changes slowly.
+				"""
+			holt_winters: """
+				Holt-Winters triple exponential smoothing, with an additive trend and seasonality.
+
+				Suited to series following a trend and a regular cycle, such as daily traffic.
+				"""
+		}
+	}
+	season_length: {
+		description: """
+			The number of values in a season.
+
+			For example, a series reported every minute with a daily cycle has seasons of 1440
+			values.
+			"""
+		relevant_when: "model = \"holt_winters\""
+		required:      true
+		type: uint: examples: [1440]
+	}
+	warmup: {
+		description: """
+			The number of values of a series learned before detecting anomalies in it.
+
+			With the `holt_winters` model, at least two full seasons are always learned.
+			"""
+		required: false
+		type: uint: default: 10
+	}
+}
@@ -76,6 +76,17 @@ base: components: transforms: aws_ec2_metadata: configuration: {
 					items: type: string: examples: ["localhost", ".foo.bar", "*"]
 				}
 			}
+			tcp: {
+				description: """
+					Proxy endpoint to use when proxying raw TCP traffic, such as the one of socket sinks.
+
+					Must be a valid URI string, with either the `socks5` scheme for a SOCKS5 proxy, or the
+					`http` scheme for an HTTP proxy tunneling the connections with `CONNECT` requests.
+					Credentials can be set in the URI.
+					"""
+				required: false
+				type: string: examples: ["socks5://foo.bar:1080", "http://foo.bar:3128"]
+			}
 		}
 	}
 	refresh_interval_secs: {
@@ -0,0 +1,73 @@
+package metadata
+
+base: components: transforms: ext_proc: configuration: {
+	concurrency: {
+		description: "The maximum number of requests in flight."
+		required:    false
+		type: uint: default: 8
+	}
+	endpoint: {
+		description: """
+			The URI of the external processor.
+
+			The external processor must implement the `ExternalProcessor` service of
+			[`ext_proc.proto`][ext_proc_proto], which receives batches of events and returns the events
+			replacing them. Events are dropped by leaving them out of the response, and modified or
+			added by including them in it.
+
+			Connections to `https` URIs are secured with TLS, using the root certificates of the system.
+
+			[ext_proc_proto]: https://github.com/vectordotdev/vector/blob/master/proto/ext_proc.proto
+			"""
+		required: true
+		type: string: examples: ["http://127.0.0.1:50051"]
+	}
+	fallback: {
+		description: "What is done with the events of the requests which fail or time out."
+		required:    false
+		type: string: {
+			default: "pass_through"
+			enum: {
+				drop: """
+					The events are dropped.
+
+					If end-to-end acknowledgements are enabled, the events are reported as failed to their
+					source.
+					"""
+				pass_through: "The events are forwarded unmodified."
+			}
+		}
+	}
+	max_events: {
+		description: "The maximum number of events sent to the external processor in a request."
+		required:    false
+		type: uint: default: 100
+	}
+	ordering: {
+		description: "The order in which the processed events are forwarded."
+		required:    false
+		type: string: {
+			default: "ordered"
+			enum: {
+				ordered: """
+					The events are forwarded in the order they were received.
+
+					A slow request holds back the events of the requests sent after it.
+					"""
+				unordered: "The events of each request are forwarded as soon as its response is received."
+			}
+		}
+	}
+	timeout_ms: {
+		description: """
+			How long to wait for the response of the external processor, in milliseconds.
+
+			The `fallback` is applied to the events of the requests which time out.
+			"""
+		required: false
+		type: uint: {
+			default: 1000
+			unit:    "milliseconds"
+		}
+	}
+}
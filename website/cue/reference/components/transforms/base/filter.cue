@@ -1,11 +1,26 @@
 package metadata
 
-base: components: transforms: filter: configuration: condition: {
-	description: """
-		The condition that every input event is matched against.
+base: components: transforms: filter: configuration: {
+	condition: {
+		description: """
+			The condition that every input event is matched against.
 
-		If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
-		"""
-	required: true
-	type: condition: {}
+			If an event is matched by the condition, it is forwarded. Otherwise, the event is dropped.
+			"""
+		required: true
+		type: condition: {}
+	}
+	reroute_dropped: {
+		description: """
+			Reroutes the events which don't match the condition to a named output instead of dropping
+			them.
+
+			When set, the events which don't match the condition are forwarded to a specially-named
+			output, `dropped`. They are annotated with fields describing the condition they didn't
+			match, so that they can be archived without repeating the condition in a `route`
+			transform.
+			"""
+		required: false
+		type: bool: default: false
+	}
 }
@@ -0,0 +1,80 @@
+package metadata
+
+base: components: transforms: log_patterns: configuration: {
+	depth: {
+		description: """
+			The depth of the parse tree, including its root and leaves.
+
+			Messages are routed through the tree by their number of tokens and then by their first
+			`depth - 3` tokens, before being compared to the patterns of the leaf they reach.
+			"""
+		required: false
+		type: uint: default: 4
+	}
+	max_children: {
+		description: """
+			The maximum number of children of the nodes of the parse tree.
+
+			Tokens in excess are routed through a wildcard node.
+			"""
+		required: false
+		type: uint: default: 100
+	}
+	max_patterns: {
+		description: """
+			The maximum number of patterns kept.
+
+			When it's reached, the least recently matched pattern is forgotten.
+			"""
+		required: false
+		type: uint: default: 1000
+	}
+	message_field: {
+		description: """
+			The field holding the message to cluster.
+
+			Defaults to the message field of the global log schema. Events without this field are
+			forwarded unchanged.
+			"""
+		required: false
+		type: string: examples: ["message"]
+	}
+	metrics_interval_secs: {
+		description: """
+			The interval between the metrics about the patterns, in seconds.
+
+			The `log_pattern_events` counter holds the number of events of each pattern, tagged with the
+			`pattern_id` and `pattern` template, the `log_patterns` gauge the number of patterns, and the
+			`log_patterns_created` counter the number of patterns created since the previous metrics.
+			They're sent alongside the events.
+
+			If unset, no metric is sent.
+			"""
+		required: false
+		type: uint: {}
+	}
+	similarity_threshold: {
+		description: """
+			The minimum ratio of the tokens of a message matching a pattern for the message to be
+			clustered with it.
+
+			Higher values create more, stricter patterns.
+			"""
+		required: false
+		type: float: default: 0.4
+	}
+	target_field: {
+		description: """
+			The field the pattern of an event is written to.
+
+			The pattern is an object holding its `id`, its `template`, where the variable tokens of the
+			messages are replaced with `<*>`, and the `variables` of the event, which are the tokens of
+			its message replaced in the template.
+			"""
+		required: false
+		type: string: {
+			default: "pattern"
+			examples: ["pattern"]
+		}
+	}
+}
@@ -181,6 +181,7 @@ base: components: transforms: lua: configuration: {
 				This version is deprecated and will be removed in a future version.
 				"""
 			"2": "Lua transform API version 2."
+			"3": "Lua transform API version 3."
 		}
 	}
 }
@@ -0,0 +1,75 @@
+package metadata
+
+base: components: transforms: metric_arithmetic: configuration: {
+	interval_ms: {
+		description: """
+			The interval between evaluations, in milliseconds.
+
+			Operand values are collected during this time frame, and derived metrics are computed from
+			the collected values at the end of it.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	metrics: {
+		description: "The derived metrics to compute."
+		required:    true
+		type: array: items: type: object: options: {
+			group_by: {
+				description: """
+					A template used to match series of the different operands together.
+
+					Operand series that render to the same value are combined into one derived series. Only
+					the tags that are identical across all the matched operand series are kept on the derived
+					metric.
+
+					If unset, operand series are only matched when their tags are identical.
+					"""
+				required: false
+				type: string: {
+					examples: ["{{ tags.host }}", "{{ tags.service }}-{{ tags.region }}"]
+					syntax: "template"
+				}
+			}
+			name: {
+				description: "The name of the derived metric."
+				required:    true
+				type: string: examples: ["http_error_ratio"]
+			}
+			namespace: {
+				description: "The namespace of the derived metric."
+				required:    false
+				type: string: {}
+			}
+			operands: {
+				description: """
+					The names of the metrics used as operands.
+
+					At least two operands are required. The operation is applied from left to right, so
+					`["a", "b", "c"]` with the `subtract` operation computes `a - b - c`.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["http_errors_total", "http_requests_total"]
+			}
+			operation: {
+				description: "The operation used to combine the operands."
+				required:    true
+				type: string: enum: {
+					add: "Sum the operands."
+					divide: """
+						Divide the first operand by the subsequent ones.
+
+						Series where a divisor is zero are skipped.
+						"""
+					multiply: "Multiply the operands."
+					subtract: "Subtract the subsequent operands from the first one."
+				}
+			}
+		}
+	}
+	passthrough: {
+		description: "Whether or not to forward the incoming metrics alongside the derived ones."
+		required:    false
+		type: bool: default: true
+	}
+}
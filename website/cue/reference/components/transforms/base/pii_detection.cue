@@ -0,0 +1,74 @@
+package metadata
+
+base: components: transforms: pii_detection: configuration: {
+	custom_detectors: {
+		description: "Additional detectors, matching regular expressions."
+		required:    false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				name: {
+					description: "The name of the detector, under which its matches are counted."
+					required:    true
+					type: string: examples: ["employee_id"]
+				}
+				pattern: {
+					description: "The regular expression matching the PII."
+					required:    true
+					type: string: examples: ["EMP-[0-9]{6}"]
+				}
+			}
+		}
+	}
+	detectors: {
+		description: "The built-in detectors to run."
+		required:    false
+		type: array: {
+			default: ["email", "us_ssn", "credit_card", "api_key"]
+			items: type: string: enum: {
+				api_key: """
+					API keys with a recognizable format, such as AWS access key IDs, and GitHub, Slack, Stripe,
+					and Google API tokens.
+					"""
+				credit_card: """
+					Payment card numbers of 13 to 19 digits, optionally grouped with spaces or dashes.
+
+					Numbers failing the Luhn checksum are ignored.
+					"""
+				email: "Email addresses."
+				us_ssn: """
+					US Social Security numbers, in the `AAA-GG-SSSS` format.
+
+					Numbers which can't be assigned, such as those with an area of `000`, `666`, or `9xx`, are
+					ignored.
+					"""
+			}
+		}
+	}
+	fields: {
+		description: """
+			The fields scanned for PII.
+
+			Objects and arrays are scanned recursively. If empty, the whole event is scanned.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["message", "user.email"]
+		}
+	}
+	target_field: {
+		description: """
+			The field the PII detected in an event is described in.
+
+			It's only set on the events where PII is detected, to an object holding the total `count`
+			of matches and the number of matches of each detector in `detectors`. It's a metadata field
+			by default, so that it can be used to route events without being sent to sinks.
+			"""
+		required: false
+		type: string: {
+			default: "%pii"
+			examples: ["%pii"]
+		}
+	}
+}
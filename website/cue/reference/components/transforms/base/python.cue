@@ -0,0 +1,38 @@
+package metadata
+
+base: components: transforms: python: configuration: {
+	allowed_modules: {
+		description: "The modules the program is allowed to import."
+		required:    false
+		type: array: {
+			default: ["base64", "datetime", "json", "math", "re"]
+			items: type: string: examples: ["re", "urllib.parse"]
+		}
+	}
+	function: {
+		description: """
+			The name of the function called for each event.
+
+			The function takes the fields of a log event as a dictionary. It returns the fields of the
+			event to emit as a dictionary, a list of dictionaries to emit several events, or `None` to
+			drop the event.
+			"""
+		required: false
+		type: string: default: "process"
+	}
+	source: {
+		description: """
+			The Python program defining the function called for each event.
+
+			The program runs in a restricted environment: only a subset of the builtins is available,
+			and only the modules listed in `allowed_modules` can be imported. This restriction guards
+			against mistakes, and isn't a security boundary for untrusted code.
+			"""
+		required: true
+		type: string: examples: ["""
+			def process(event):
+				event["processed"] = True
+				return event
+			"""]
+	}
+}
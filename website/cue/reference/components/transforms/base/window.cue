@@ -0,0 +1,66 @@
+package metadata
+
+base: components: transforms: window: configuration: {
+	duration_ms: {
+		description: "The duration of the windows, in milliseconds."
+		required:    true
+		type: uint: {}
+	}
+	group_by: {
+		description: """
+			The key of the windows of an event.
+
+			Events with different keys are buffered into separate windows. When not set, all events
+			are buffered into the same windows.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ user_id }}"]
+			syntax: "template"
+		}
+	}
+	mode: {
+		description: "The windows events are buffered into."
+		required:    true
+		type: string: enum: {
+			sliding: """
+				Windows starting at a regular interval.
+
+				When the interval is shorter than the duration of the windows, they overlap and each event
+				is part of several windows. When it's longer, the events received between two windows
+				aren't part of any.
+				"""
+			tumbling: """
+				Consecutive windows, which don't overlap.
+
+				Each event is part of a single window.
+				"""
+		}
+	}
+	source: {
+		description: """
+			The [Vector Remap Language][vrl] (VRL) program to run over the events of each window, when
+			it closes.
+
+			The program runs on an event holding the events of the window in its `events` field, in the
+			order they were received, the key of the window in its `key` field, and the times the
+			window started and ended at in its `start` and `end` fields. The resulting event is emitted,
+			or each of the resulting events if the program results in an array, which can be empty.
+
+			Windows without any event are skipped.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: true
+		type: string: {
+			examples: [". = { \"user_id\": .key, \"events\": length(.events) }"]
+			syntax: "remap_program"
+		}
+	}
+	step_ms: {
+		description:   "The interval between the start of consecutive windows, in milliseconds."
+		relevant_when: "mode = \"sliding\""
+		required:      true
+		type: uint: {}
+	}
+}
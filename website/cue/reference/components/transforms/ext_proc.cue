@@ -0,0 +1,85 @@
+package metadata
+
+components: transforms: ext_proc: {
+	title: "External Processor"
+
+	description: """
+		Processes events with an external service over gRPC, for logic that must stay in an
+		existing service, such as scoring events with a machine learning model or parsing them with a
+		proprietary parser.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				The events returned by the external processor are acknowledged together, once all of them
+				are delivered. Event metadata, such as secrets, isn't sent to the external processor and is
+				lost.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.transforms.ext_proc.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		protocol: {
+			title: "Protocol"
+			body: """
+				Events are sent to the external processor in batches of up to `max_events` events, through
+				the `Process` method of the `ExternalProcessor` service of
+				[`ext_proc.proto`](\(urls.vector_repo)/blob/master/proto/ext_proc.proto). Events are
+				encoded as in the `vector` source and sink. The response contains the events replacing
+				those of the request: events are dropped by leaving them out of the response, and
+				modified or added by including them in it.
+				"""
+		}
+
+		fallback: {
+			title: "Fallback"
+			body: """
+				When a request fails, or doesn't receive a response within `timeout_ms`, its events are
+				either forwarded unmodified or dropped, depending on `fallback`.
+				"""
+		}
+
+		ordering: {
+			title: "Ordering"
+			body: """
+				Up to `concurrency` requests are sent at the same time. With `ordering` set to `ordered`,
+				the processed events are forwarded in the order they were received, so a slow request
+				holds back the events of the requests sent after it. With `unordered`, the events of each
+				request are forwarded as soon as its response is received.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
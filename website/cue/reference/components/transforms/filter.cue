@@ -70,6 +70,23 @@ components: transforms: filter: {
 		},
 	]
 
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When
+				`reroute_dropped` is set to `true`, the events which don't match the condition are
+				sent to the `dropped` output instead of being dropped. For a transform component
+				named `foo`, this output can be accessed by specifying `foo.dropped` as the input to
+				another component. Log events sent to this output are annotated with the
+				`metadata.dropped` field (`%vector.dropped` with the Vector log namespace) holding the
+				`condition` they didn't match and the `component_id` of the transform, and metric
+				events with the equivalent `metadata.dropped.*` tags.
+				"""
+		},
+	]
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
@@ -0,0 +1,53 @@
+package metadata
+
+components: transforms: log_patterns: {
+	title: "Log Patterns"
+
+	description: """
+		Clusters log messages into patterns using the [Drain](\(urls.drain_paper)) algorithm,
+		attaching the pattern of each event and the variables extracted from its message to it, and
+		optionally sending metrics about the volume of each pattern.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.log_patterns.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		drain: {
+			title: "Drain"
+			body: """
+				Messages are split into tokens on whitespace, and routed through a parse tree by their
+				number of tokens and their first tokens, tokens holding digits being routed through a
+				wildcard node. The message is then compared to the patterns of the leaf it reaches: when the
+				ratio of its tokens matching the most similar pattern is at least `similarity_threshold`, the
+				tokens of the pattern which don't match are replaced with `<*>`. Otherwise, the message
+				starts a new pattern.
+
+				The ID of a pattern is derived from its first message, and doesn't change as its template
+				gets more general. Patterns are kept in memory, up to `max_patterns`.
+				"""
+		}
+	}
+}
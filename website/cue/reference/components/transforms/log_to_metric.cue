@@ -325,6 +325,15 @@ components: transforms: log_to_metric: {
 				will not be emitted.
 				"""
 		}
+		round_trip: {
+			title: "Converting Logs Back Into Metrics"
+			body: """
+				Metrics can be archived as logs using the [`metric_to_log` transform](\(urls.vector_transforms)/metric_to_log)
+				and later re-hydrated by enabling `all_metrics`. To preserve multi-valued and bare tags,
+				configure `metric_to_log` with `metric_tag_values = "full"`. Metrics without a timestamp
+				are given the current time by `metric_to_log` when using the legacy log namespace.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
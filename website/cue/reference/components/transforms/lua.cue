@@ -253,9 +253,84 @@ components: transforms: lua: {
 				}
 			}
 		},
+		{
+			title: "Count the number of logs with a coroutine timer (version 3)"
+			configuration: {
+				version: "3"
+				hooks: process: """
+					function (event, emit)
+						count = (count or 0) + 1
+					end
+					"""
+				timers: [
+					{interval_seconds: 5, handler: "timer_handler"},
+				]
+				source: """
+					function timer_handler(emit)
+						local total = 0
+						while true do
+							total = total + (count or 0)
+							emit(vector.metric({
+								name = "event_counter",
+								kind = "absolute",
+								counter = { value = total },
+							}))
+							count = 0
+							emit = coroutine.yield()
+						end
+					end
+					"""
+			}
+			input: log: {}
+			output: metric: {
+				kind: "absolute"
+				name: "event_counter"
+				counter: {
+					value: 1.0
+				}
+			}
+		},
 	]
 
 	how_it_works: {
+		version_3_event_api: {
+			title: "Event API (version 3)"
+			body:  """
+				In version 3 of the transform, events are opaque objects whose fields are accessed
+				with [VRL paths](\(urls.vrl_path_expressions)), in the same way as in the `remap`
+				transform: `.` refers to the event and `%` to its metadata. Events have the following
+				methods:
+
+				| Method                  | Description                                                                                              |
+				|:------------------------|:---------------------------------------------------------------------------------------------------------|
+				| `event:get(path)`       | Returns the value at the path, or `nil` if there is none.                                               |
+				| `event:set(path, value)`| Sets the value at the path.                                                                              |
+				| `event:remove(path)`    | Removes the value at the path, and returns it.                                                           |
+				| `event:kind()`          | Returns the kind of the event: `log` or `metric`.                                                        |
+				| `event:value()`         | Returns the value of a metric as a table, such as `{ counter = { value = 1 } }`.                         |
+				| `event:set_value(value)`| Sets the value of a metric from such a table, which can change its type.                                 |
+				| `event:copy()`          | Returns a copy of the event.                                                                             |
+
+				Metrics expose the same fields as in VRL: `.name`, `.namespace`, `.timestamp`,
+				`.kind`, `.tags`, and the read-only `.type`.
+
+				Emitting an event hands it over to Vector, so it can't be used by the script afterwards.
+				Emit a copy of the event to emit it more than once. New events are created with the
+				`vector.log(fields)` and `vector.metric(metric)` functions, the latter taking a table in
+				the format used by version 2 of the transform.
+				"""
+		}
+		version_3_timers: {
+			title: "Coroutine Timers (version 3)"
+			body:  """
+				In version 3 of the transform, timer handlers run as
+				[coroutines](\(urls.lua_manual)#2.6). When a handler yields with `coroutine.yield`, the
+				next tick of its timer resumes it instead of calling it again, and `coroutine.yield`
+				returns the `emit` function of that tick. This allows keeping the state of a timer in
+				local variables. A handler which returns or fails is started again on the next tick.
+				"""
+		}
+
 		event_data_model: {
 			title: "Event Data Model"
 			body:  """
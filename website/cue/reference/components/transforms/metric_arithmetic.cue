@@ -0,0 +1,125 @@
+package metadata
+
+components: transforms: metric_arithmetic: {
+	title: "Metric Arithmetic"
+
+	description: """
+		Computes derived metrics from multiple incoming metric series, such as
+		the ratio of errors to total requests or the delta between two
+		counters, over a defined interval window.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.transforms.metric_arithmetic.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    false
+			set:          false
+			summary:      false
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Error ratio per host"
+			input: [
+				{
+					metric: {
+						kind: "incremental"
+						name: "http_errors_total"
+						tags: {
+							host: "my.host.com"
+						}
+						counter: {
+							value: 5.0
+						}
+					}
+				},
+				{
+					metric: {
+						kind: "incremental"
+						name: "http_requests_total"
+						tags: {
+							host: "my.host.com"
+						}
+						counter: {
+							value: 100.0
+						}
+					}
+				},
+			]
+			configuration: {
+				interval_ms: 10000
+				passthrough: false
+				metrics: [{
+					name:      "http_error_ratio"
+					operation: "divide"
+					operands: ["http_errors_total", "http_requests_total"]
+					group_by: "{{ tags.host }}"
+				}]
+			}
+			output: [
+				{
+					metric: {
+						kind: "absolute"
+						name: "http_error_ratio"
+						tags: {
+							host: "my.host.com"
+						}
+						gauge: {
+							value: 0.05
+						}
+					}
+				},
+			]
+		},
+	]
+
+	how_it_works: {
+		evaluation: {
+			title: "Evaluation"
+			body: """
+				During an interval, the values of the operand series are collected: `incremental`
+				values are summed and `absolute` values replace older ones. At the end of the interval,
+				each derived metric is computed from the collected values by applying the operation from
+				left to right, and emitted as an `absolute` `gauge`. Series for which any operand wasn't
+				seen during the interval are skipped, as are divisions by zero.
+				"""
+		}
+
+		series_matching: {
+			title: "Series Matching"
+			body: """
+				By default, operand series are only combined when their tags are identical. The
+				`group_by` template allows matching series with differing tags, in which case only the
+				tags whose values are identical across all matched series are kept.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		metric_arithmetic_division_by_zero_total: components.sources.internal_metrics.output.metrics.metric_arithmetic_division_by_zero_total
+	}
+}
@@ -0,0 +1,52 @@
+package metadata
+
+components: transforms: pii_detection: {
+	title: "PII Detection"
+
+	description: """
+		Scans events for personally identifiable information (PII), such as email addresses, card
+		numbers, or API keys, and describes the PII detected in a field of the events, so that
+		sensitive events can be routed to restricted sinks.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.pii_detection.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		routing: {
+			title: "Routing sensitive events"
+			body: """
+				The events holding PII are annotated with an object such as
+				`{"count": 3, "detectors": {"email": 2, "credit_card": 1}}`, written to the `%pii` metadata
+				field by default. Events without PII aren't annotated, so that they can be told apart with
+				a `route` transform and a condition such as `exists(%pii)`.
+
+				Detection relies on regular expressions, checked with validators such as the Luhn checksum
+				of card numbers where possible. It reduces the risk of sending PII to the wrong sink, but
+				doesn't guarantee that every PII is detected.
+				"""
+		}
+	}
+}
@@ -0,0 +1,99 @@
+package metadata
+
+components: transforms: python: {
+	title: "Python"
+
+	description: """
+		Modifies log events with a Python function, to prototype parsing logic in Python before
+		porting it to VRL.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "Python"
+				url:     urls.python
+				version: "3"
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The `python` transform is only available in builds of Vector with the `transforms-python`
+				feature, which embeds CPython and requires a Python 3 installation to build and run.
+				""",
+		]
+		warnings: [
+			"""
+				Calling the Python function holds the Python global interpreter lock, so events are
+				processed one at a time. Use the `remap` transform once the logic is settled.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.transforms.python.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Parse a message"
+			configuration: {
+				source: """
+					import re
+
+					def process(event):
+						match = re.match(r"(?P<level>\\w+): (?P<text>.*)", event["message"])
+						if match:
+							event.update(match.groupdict())
+						return event
+					"""
+			}
+			input: log: message: "ERROR: disk full"
+			output: log: {
+				message: "ERROR: disk full"
+				level:   "ERROR"
+				text:    "disk full"
+			}
+		},
+	]
+
+	how_it_works: {
+		type_mappings: {
+			title: "Type Mappings"
+			body: """
+				Strings, integers, floats, booleans, null values, objects, and arrays are converted to
+				their Python counterparts: `str`, `int`, `float`, `bool`, `None`, `dict`, and `list`.
+				Timestamps are converted to timezone-aware `datetime.datetime` objects, and naive
+				datetimes returned by the function are assumed to be in UTC. Tuples and `bytes` are
+				accepted in returned events, and are converted to arrays and strings.
+				"""
+		}
+
+		errors: {
+			title: "Errors"
+			body: """
+				Events for which the function raises an exception, or returns values that can't be
+				stored in an event, are dropped and the error is logged with its traceback.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,64 @@
+package metadata
+
+components: transforms: window: {
+	title: "Window"
+
+	description: """
+		Buffers events into tumbling or sliding windows of time, and aggregates the events of each
+		window with a [Vector Remap Language](\(urls.vrl_reference)) (VRL) program when it closes.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.window.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+
+	how_it_works: {
+		windows: {
+			title: "Windows"
+			body: """
+				Windows are aligned on the time the transform started at, and events are assigned to them by
+				the time they are received at. Tumbling windows follow each other, so that each event is part
+				of a single window. Sliding windows start every `step_ms`, so that each event is part of every
+				window open when it's received.
+
+				The events are buffered in memory until the last window they're part of closes. When Vector
+				stops, the windows holding events are closed early.
+				"""
+		}
+		aggregation: {
+			title: "Aggregation"
+			body: """
+				When a window closes, the `source` program runs once for each key with events in the window,
+				on an event holding these events in its `events` field, the key in its `key` field, and the
+				bounds of the window in its `start` and `end` fields. The program can emit a single event, or
+				several events by assigning an array to `.`. If the program fails, the events of the window
+				are dropped.
+				"""
+		}
+	}
+}
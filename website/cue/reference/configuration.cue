@@ -252,12 +252,68 @@ configuration: {
 			}
 		}
 
+		latency: {
+			common:      false
+			description: """
+				Tracks the end-to-end latency of events, from their receipt by a source to their delivery by a
+				sink. Sources record the time at which they received each event under `vector.ingest` in the
+				event metadata, and sinks report the time elapsed since then once the event has been delivered,
+				in the `end_to_end_latency_seconds` histogram tagged with the IDs of the source and the sink.
+
+				Events which couldn't be delivered, and events created by transforms, such as the ones created by
+				`log_to_metric`, aren't reported. For sinks that don't support end-to-end acknowledgements, events
+				are reported once they have been processed by the sink.
+				"""
+			required:    false
+			type: object: options: {
+				enabled: {
+					common:      true
+					description: "Whether or not to track the end-to-end latency of events."
+					required:    false
+					type: bool: default: false
+				}
+			}
+		}
+
+		lineage: {
+			common:      false
+			description: """
+				Stamps events with a lineage record in their metadata, so downstream systems can audit how the
+				data was produced. The record is stored under `vector.lineage` in the event metadata, and can
+				be read in VRL with the [`get_lineage`](\(urls.vrl_functions)/#get_lineage) function.
+
+				The record holds the ID of the source that produced the event, the IDs of the transforms the
+				event went through, in order, the ID of the Vector instance, and the hash of the running
+				configuration. Events created by transforms, such as the ones created by `log_to_metric`, have
+				no lineage record.
+				"""
+			required:    false
+			type: object: options: {
+				enabled: {
+					common:      true
+					description: "Whether or not to stamp events with a lineage record."
+					required:    false
+					type: bool: default: false
+				}
+				instance_id: {
+					common:      false
+					description: "The ID of this Vector instance in lineage records. Defaults to the hostname."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector-aggregator-0"]
+					}
+				}
+			}
+		}
+
 		log_schema: {
 			common: false
 			description: """
 				Configures default log schema for all events. This is used by
 				Vector source components to assign the fields on incoming
-				events.
+				events. Sources receiving events which use other fields can
+				remap them into this schema with their `log_schema` option.
 				"""
 			required: false
 			type: object: {
@@ -365,6 +421,92 @@ configuration: {
 			}
 		}
 
+		memory_budget: {
+			common:      false
+			description: """
+				Limits the approximate size of the events held in flight by all sinks, to prevent Vector from
+				running out of memory on constrained hosts. Events are held in flight by a sink from the time
+				they are read from its buffer until they are delivered or dropped. Each sink can also be
+				given its own limit with its `memory_limit_bytes` option.
+
+				Limits are soft: the size of events is approximate, and a sink stops reading events only once
+				a limit has been reached. Events held in buffers and by transforms aren't accounted for. The
+				size of the events held in flight by each sink is reported in the `component_memory_used_bytes`
+				gauge.
+				"""
+			required:    false
+			type: object: options: {
+				limit_bytes: {
+					common:      true
+					description: "The soft limit, in bytes, on the approximate size of the events held in flight by all sinks."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [268435456]
+						unit: "bytes"
+					}
+				}
+				when_exceeded: {
+					common:      false
+					description: "What a sink does with its input while a memory limit is exceeded."
+					required:    false
+					type: string: {
+						default: "block"
+						enum: {
+							block:       "Stop reading events until enough of the events held in flight are delivered or dropped. Backpressure is applied upstream."
+							drop_newest: "Drop the events read while the limit is exceeded."
+						}
+					}
+				}
+			}
+		}
+
+		event_size: {
+			common:      false
+			description: """
+				Limits the size of the events sent by sources and transforms, to prevent a single oversized
+				event from destabilizing buffers and sinks. The size of an event is the estimated size of its
+				JSON encoding. Events are checked after being decoded by sources, and again after each
+				transform. The events exceeding the limit are counted in the `event_size_exceeded_total`
+				counter.
+				"""
+			required:    false
+			type: object: options: {
+				max_bytes: {
+					common:      true
+					description: "The maximum size, in bytes, of the events sent by sources and transforms."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [1048576]
+						unit: "bytes"
+					}
+				}
+				when_exceeded: {
+					common:      false
+					description: "What to do with the events exceeding the maximum size."
+					required:    false
+					type: string: {
+						default: "truncate"
+						enum: {
+							truncate:    "Truncate the string values of the `truncate_fields` of log and trace events until the events fit. Metric events, and the events still exceeding the maximum size, are dropped."
+							drop:        "Drop the events."
+							dead_letter: "Send the events to the `dead_letter` sources, with the ID of the component which sent them in their metadata, under `vector.dead_letter`. The events are dropped when there is no `dead_letter` source, and when they exceed the maximum size after going through the dead letter queue once already."
+						}
+					}
+				}
+				truncate_fields: {
+					common:      false
+					description: "The fields truncated, in order, by the `truncate` policy. Defaults to the message field of the global log schema."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: examples: ["message", "payload.body"]
+					}
+				}
+			}
+		}
+
 		secret: {
 			common: false
 			description: """
@@ -427,6 +569,74 @@ configuration: {
 			}
 		}
 
+		stall_detection: {
+			common:      false
+			description: """
+				Detects sources stalled by backpressure. A source is stalled when it has been blocked sending
+				events downstream for longer than the threshold. Each stall is reported once, with a warning
+				holding the chain of components it is attributed to, and the `source_stalls_total` metric.
+
+				The stall is attributed by following, from the source, the downstream components which are
+				themselves blocked sending events, until reaching the component which isn't: that component
+				is the one not keeping up with its input.
+				"""
+			required:    false
+			type: object: options: {
+				enabled: {
+					common:      true
+					description: "Whether or not to detect sources stalled by backpressure."
+					required:    false
+					type: bool: default: false
+				}
+				threshold_secs: {
+					common:      false
+					description: "How long, in seconds, a source must be blocked sending events downstream to be reported."
+					required:    false
+					type: uint: {
+						default: 30
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+
+		telemetry: {
+			common:      false
+			description: """
+				Labels the internal telemetry of components with values taken from the events, to break
+				down their throughput by fields such as the Kubernetes namespace of the events. When
+				`event_labels` is set, components count the events they receive and send in the
+				`component_received_labeled_events_total` and `component_sent_labeled_events_total` metrics,
+				along with their size in the `component_received_labeled_event_bytes_total` and
+				`component_sent_labeled_event_bytes_total` metrics.
+
+				The number of distinct values of each label is bounded per component, to bound the
+				cardinality of the metrics: once reached, the events holding other values are counted with
+				the value `other`.
+				"""
+			required:    false
+			type: object: options: {
+				event_labels: {
+					common:      true
+					description: "The labels to add to the metrics, keyed by label name, with the field of the events to take their value from. Metric events are labeled with the value of the tag of the same name as the field."
+					required:    false
+					type: object: {
+						examples: [{namespace: ".kubernetes.pod_namespace"}]
+						options: {}
+					}
+				}
+				max_label_values: {
+					common:      false
+					description: "The maximum number of distinct values of each label, per component."
+					required:    false
+					type: uint: {
+						default: 100
+						unit:    null
+					}
+				}
+			}
+		}
+
 		timezone: {
 			common:      false
 			description: """
@@ -492,6 +702,15 @@ configuration: {
 						}
 					}
 				}
+				tcp: {
+					common:      false
+					description: "The URL to proxy raw TCP traffic, such as the one of socket sinks, through. Either a `socks5` URL for a SOCKS5 proxy, or an `http` URL for an HTTP proxy tunneling the connections with `CONNECT` requests."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["socks5://foo.bar:1080", "http://foo.bar:3128"]
+					}
+				}
 			}
 		}
 	}
@@ -618,6 +837,43 @@ configuration: {
 				},
 			]
 		}
+		pipeline_templates: {
+			title: "Pipeline templates"
+			body: """
+				Groups of components repeated with small variations can be defined once as a pipeline
+				template, in the `pipelines` section, and instantiated as many times as needed. The components
+				of each instance are named `<instance>.<component>`, and the instance itself can be used as an
+				input of other components, standing for the `output` component of the template:
+
+				```toml title="vector.toml"
+				[pipelines.parse_nginx]
+				  output = "parse"
+				  params.field = { default = "message" }
+
+				[pipelines.parse_nginx.transforms.parse]
+				  type = "remap"
+				  inputs = ["%{inputs}"]
+				  source = '. |= parse_nginx_log!(.%{field}, "combined")'
+
+				[pipelines.parse_nginx.instances.frontend]
+				  inputs = ["frontend_logs"]
+
+				[pipelines.parse_nginx.instances.backend]
+				  inputs = ["backend_logs"]
+				  params.field = "log"
+
+				[sinks.out]
+				  type = "console"
+				  inputs = ["frontend", "backend"]
+				  encoding.codec = "json"
+				```
+
+				Within the components of a template, `%{name}` is replaced by the value of the parameter `name`
+				of the instance, or by its `default` value. In the `inputs` of a component, `%{inputs}` is
+				replaced by the inputs of the instance, and the names of the other components of the template
+				refer to the components of the same instance.
+				"""
+		}
 		formats: {
 			title: "Formats"
 			body:  """
@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: get_lineage: {
+	category: "Event"
+	description: """
+		Returns the lineage record of an event, or `null` if the event has no lineage record.
+
+		The lineage record is stamped on events when the global `lineage.enabled` option is set. It holds the
+		ID of the source that produced the event, the IDs of the transforms the event went through, in order,
+		the ID of the Vector instance, and the hash of the running configuration.
+		"""
+
+	arguments: []
+	internal_failure_reasons: []
+	return: types: ["object", "null"]
+
+	examples: [
+		{
+			title: "Get the lineage record of the event."
+			source: #"""
+				get_lineage()
+				"""#
+			return: {
+				source_id: "app_logs"
+				transforms: ["parse", "enrich"]
+				instance_id: "vector-aggregator-0"
+				config_hash: "6c98bea9d9e2f3133e2d39ba04592d17f96340a9bc4c8d697b09f5af388a76bd"
+			}
+		},
+	]
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: microsoft_teams: {
+	name:     "Microsoft Teams"
+	thing:    "a \(name) channel"
+	url:      urls.microsoft_teams
+	versions: null
+
+	description: "[Microsoft Teams](\(urls.microsoft_teams)) is Microsoft's chat and collaboration platform, organized in teams and channels."
+}
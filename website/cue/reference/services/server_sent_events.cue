@@ -0,0 +1,8 @@
+package metadata
+
+services: server_sent_events: {
+	name:     "Server-Sent Events"
+	thing:    "a \(name) endpoint"
+	url:      urls.server_sent_events
+	versions: null
+}
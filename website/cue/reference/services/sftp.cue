@@ -0,0 +1,8 @@
+package metadata
+
+services: sftp: {
+	name:     "SFTP"
+	thing:    "an \(name) server"
+	url:      urls.sftp
+	versions: null
+}
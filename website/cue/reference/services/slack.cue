@@ -0,0 +1,10 @@
+package metadata
+
+services: slack: {
+	name:     "Slack"
+	thing:    "a \(name) channel"
+	url:      urls.slack
+	versions: null
+
+	description: "[Slack](\(urls.slack)) is a messaging platform for teams, organized in channels."
+}
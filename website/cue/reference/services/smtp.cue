@@ -0,0 +1,10 @@
+package metadata
+
+services: smtp: {
+	name:     "SMTP"
+	thing:    "an \(name) server"
+	url:      urls.rfc_5321
+	versions: null
+
+	description: "The [Simple Mail Transfer Protocol (SMTP)](\(urls.rfc_5321)) is the standard protocol used to send emails through mail servers."
+}
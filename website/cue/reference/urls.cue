@@ -8,6 +8,7 @@ urls: {
 	azure_event_hubs_connection_string:         "https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-get-connection-string"
 	azure_event_hubs_tiers:                     "https://learn.microsoft.com/en-us/azure/event-hubs/compare-tiers"
 	affine_type_system:                         "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
+	adaptive_cards:                             "https://adaptivecards.io"
 	adaptive_request_concurrency_post:          "/blog/adaptive-request-concurrency/"
 	amazon_linux:                               "https://aws.amazon.com/amazon-linux-ami/"
 	amqp_protocol:                              "https://www.amqp.org/about/what"
@@ -119,6 +120,7 @@ urls: {
 	ctime:                                      "https://www.cplusplus.com/reference/ctime"
 	cue:                                        "https://cuelang.org/"
 	csv:                                        "\(wikipedia)/wiki/Comma-separated_values"
+	d2:                                         "https://d2lang.com"
 	dag:                                        "\(wikipedia)/wiki/Directed_acyclic_graph"
 	databend:                                   "https://databend.rs"
 	databend_rest:                              "https://databend.rs/doc/integrations/api/rest"
@@ -172,6 +174,7 @@ urls: {
 	dogstatsd:                                  "\(datadog_docs)/developers/dogstatsd/?tab=hostagent"
 	dot_format:                                 "https://graphviz.org/doc/info/lang.html"
 	dpkg:                                       "https://wiki.debian.org/dpkg"
+	drain_paper:                                "https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
 	elastic_beats:                              "https://www.elastic.co/beats/"
@@ -350,12 +353,16 @@ urls: {
 	maxmind_geolite2_city:                      "https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access"
 	memory_safety:                              "\(wikipedia)/wiki/Memory_safety"
 	memory_safety_bugs:                         "https://thenewstack.io/microsoft-rust-is-the-industrys-best-chance-at-safe-systems-programming/"
+	mermaid:                                    "https://mermaid.js.org"
 	metric_event_source:                        "\(vector_repo)/blob/master/src/event/metric.rs"
+	microsoft_teams:                            "https://www.microsoft.com/microsoft-teams"
+	microsoft_teams_incoming_webhook:           "https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/add-incoming-webhook"
 	mlua:                                       "\(github)/khvzak/mlua"
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
+	mysql:                                      "https://www.mysql.com/"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
@@ -381,6 +388,8 @@ urls: {
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
+	pagerduty:                                  "https://www.pagerduty.com"
+	pagerduty_events_api_v2:                    "https://developer.pagerduty.com/docs/events-api-v2/overview/"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
@@ -415,6 +424,9 @@ urls: {
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	python:                                     "https://www.python.org"
+	questdb:                                    "https://questdb.io/"
+	questdb_ilp:                                "https://questdb.io/docs/reference/api/ilp/overview/"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -431,6 +443,7 @@ urls: {
 	rfc_2822:                                   "https://tools.ietf.org/html/rfc2822#section-3.3"
 	rfc_3339:                                   "https://tools.ietf.org/html/rfc3339"
 	rfc_4180:                                   "https://tools.ietf.org/html/rfc4180"
+	rfc_5321:                                   "https://tools.ietf.org/html/rfc5321"
 	rfc_6587_3_4_1:                             "https://tools.ietf.org/html/rfc6587#section-3.4.1"
 	rfc_6891:                                   "https://tools.ietf.org/html/rfc6891"
 	rhel:                                       "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
@@ -457,10 +470,15 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	server_sent_events:                         "https://html.spec.whatwg.org/multipage/server-sent-events.html"
+	sftp:                                       "\(wikipedia)/wiki/SSH_File_Transfer_Protocol"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
+	slack:                                      "https://slack.com"
+	slack_block_kit:                            "https://api.slack.com/block-kit"
+	slack_incoming_webhooks:                    "https://api.slack.com/messaging/webhooks"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
@@ -474,6 +492,7 @@ urls: {
 	splunk_hec_raw_endpoint:                    "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fraw"
 	splunk_hec_setup:                           "https://docs.splunk.com/Documentation/Splunk/latest/Data/UsetheHTTPEventCollector"
 	splunk_hec_metadata:                        "https://docs.splunk.com/Documentation/Splunk/latest/Data/FormateventsforHTTPEventCollector#Event_metadata"
+	splunk_hec_metric_format:                   "https://docs.splunk.com/Documentation/Splunk/latest/Metrics/GetMetricsInOther#Example_of_sending_metrics_using_HEC"
 	specs_instrumentation:                      "\(vector_repo)/blob/master/docs/specs/instrumentation.md)"
 	standard_streams:                           "\(wikipedia)/wiki/Standard_streams"
 	statsd:                                     "\(github)/statsd/statsd"